@@ -16,11 +16,70 @@ License: Proprietary (200-key limited release)
 """
 
 import json
+import logging
 import requests
 from requests.exceptions import RequestException, Timeout
-from typing import Dict, Any, Optional
+from typing import Dict, Any, List, Optional
 from pathlib import Path
 
+try:
+    from ..utils.structured_logging import span
+except ImportError:
+    from utils.structured_logging import span
+
+logger = logging.getLogger(__name__)
+
+
+def classification_prompt(filename: str, extension: str, text_snippet: Optional[str] = None,
+                          file_size: Optional[int] = None) -> str:
+    """The file classification prompt, shared by every AI provider (see ai.providers)."""
+    size_info = f"\nSize: {file_size} bytes" if file_size else ""
+    snippet_info = f"\nContent preview:\n{text_snippet[:500]}" if text_snippet else ""
+
+    prompt = f"""You are a file classification AI assistant. Your task is to analyze file information and suggest an organized storage location.
+
+File Information:
+- Filename: {filename}
+- Type: {extension}{size_info}{snippet_info}
+
+Based on this information, provide a classification suggestion in the following JSON format:
+{{
+  "category": "The main category (e.g., Documents, Finance, Projects, Media)",
+  "suggested_path": "Relative path for organization (e.g., Documents/Invoices/2025/)",
+  "rename": "Suggested filename if renaming would improve clarity (or null if current name is good)",
+  "reason": "Brief explanation (1-2 sentences) for your suggestion",
+  "tags": ["2-5 short lowercase keywords describing the file, e.g. invoice, tax, 2024"]
+}}
+
+Important guidelines:
+1. Choose clear, intuitive categories
+2. Use date-based subfolders (YYYY/MM) when appropriate for time-sensitive documents
+3. Only suggest renaming if the current filename is unclear or could be improved
+4. Keep paths concise but descriptive
+5. Return ONLY the JSON object, no additional text
+
+Provide your classification:"""
+
+    return prompt
+
+
+def parse_json_reply(response_text: str) -> Dict[str, Any]:
+    """
+    The JSON object in a model's reply, which may be wrapped in a markdown code block.
+
+    Raises:
+        json.JSONDecodeError: If the reply holds no valid JSON
+    """
+    if "```json" in response_text:
+        json_start = response_text.find("```json") + 7
+        json_end = response_text.find("```", json_start)
+        response_text = response_text[json_start:json_end].strip()
+    elif "```" in response_text:
+        json_start = response_text.find("```") + 3
+        json_end = response_text.find("```", json_start)
+        response_text = response_text[json_start:json_end].strip()
+    return json.loads(response_text)
+
 
 class OllamaClient:
     """
@@ -78,6 +137,63 @@ class OllamaClient:
 
         return []
 
+    def list_local_models(self) -> List[Dict[str, Any]]:
+        """
+        List the models installed on the Ollama server, with their details.
+
+        Returns:
+            List[Dict]: One dict per model (name, size, modified_at, family,
+                        parameter_size, quantization), empty if the server does not answer
+        """
+        try:
+            response = requests.get(f"{self.base_url}/api/tags", timeout=self.timeout)
+            if response.status_code != 200:
+                return []
+            models = response.json().get('models', [])
+        except Exception as e:
+            logger.warning(f"Could not list local models at {self.base_url}: {e}")
+            return []
+
+        local_models = []
+        for model in models:
+            details = model.get('details') or {}
+            local_models.append({
+                'name': model.get('name', ''),
+                'size': model.get('size'),
+                'modified_at': model.get('modified_at'),
+                'family': details.get('family'),
+                'parameter_size': details.get('parameter_size'),
+                'quantization': details.get('quantization_level'),
+            })
+        return sorted(local_models, key=lambda model: model['name'])
+
+    def detect(self) -> Dict[str, Any]:
+        """
+        Look for a running Ollama server and whether it has the configured model.
+
+        Returns:
+            Dict: 'running', 'base_url', 'version', 'model', 'model_installed'
+                  and 'models' (names of the installed models)
+        """
+        status = {'running': False, 'base_url': self.base_url, 'version': None, 'model': self.model,
+                  'model_installed': False, 'models': []}
+        try:
+            response = requests.get(f"{self.base_url}/api/version", timeout=5)
+        except Exception:
+            return status
+        if response.status_code != 200:
+            return status
+        try:
+            status['version'] = response.json().get('version')
+        except ValueError:
+            pass
+        status['running'] = True
+        status['models'] = [model['name'] for model in self.list_local_models()]
+        # "llama3" is installed as "llama3:latest"
+        status['model_installed'] = any(name == self.model or name == f"{self.model}:latest"
+                                        for name in status['models'])
+        return status
+
     def _construct_classification_prompt(self, filename: str, extension: str,
                                         text_snippet: Optional[str] = None,
                                         file_size: Optional[int] = None) -> str:
@@ -93,33 +209,7 @@ class OllamaClient:
         Returns:
             str: Formatted prompt for Ollama
         """
-        size_info = f"\nSize: {file_size} bytes" if file_size else ""
-        snippet_info = f"\nContent preview:\n{text_snippet[:500]}" if text_snippet else ""
-
-        prompt = f"""You are a file classification AI assistant. Your task is to analyze file information and suggest an organized storage location.
-
-File Information:
-- Filename: {filename}
-- Type: {extension}{size_info}{snippet_info}
-
-Based on this information, provide a classification suggestion in the following JSON format:
-{{
-  "category": "The main category (e.g., Documents, Finance, Projects, Media)",
-  "suggested_path": "Relative path for organization (e.g., Documents/Invoices/2025/)",
-  "rename": "Suggested filename if renaming would improve clarity (or null if current name is good)",
-  "reason": "Brief explanation (1-2 sentences) for your suggestion"
-}}
-
-Important guidelines:
-1. Choose clear, intuitive categories
-2. Use date-based subfolders (YYYY/MM) when appropriate for time-sensitive documents
-3. Only suggest renaming if the current filename is unclear or could be improved
-4. Keep paths concise but descriptive
-5. Return ONLY the JSON object, no additional text
-
-Provide your classification:"""
-
-        return prompt
+        return classification_prompt(filename, extension, text_snippet, file_size)
 
     def classify_file(self, filename: str, extension: str,
                      text_snippet: Optional[str] = None,
@@ -140,8 +230,19 @@ Provide your classification:"""
                 - rename (str or None): Suggested new filename
                 - reason (str): Explanation for classification
                 - success (bool): Whether classification succeeded
+                - model (str): Model that answered (on success)
+                - tokens_used (int): Prompt and completion tokens (on success)
                 - error (str, optional): Error message if failed
         """
+        with span('ollama.classify', logger, model=self.model, extension=extension) as trace:
+            result = self._classify_file(filename, extension, text_snippet, file_size)
+            if not result.get('success'):
+                trace.fail(result.get('error', 'Classification failed'))
+            return result
+
+    def _classify_file(self, filename: str, extension: str,
+                       text_snippet: Optional[str], file_size: Optional[int]) -> Dict[str, Any]:
+        """Call the API for classify_file(); failures come back as the fallback result."""
         # Default fallback response
         fallback = {
             "category": "Unsorted",
@@ -183,17 +284,13 @@ Provide your classification:"""
             # Try to parse JSON from response
             try:
                 # Sometimes the model returns JSON wrapped in markdown code blocks
-                if "```json" in response_text:
-                    json_start = response_text.find("```json") + 7
-                    json_end = response_text.find("```", json_start)
-                    response_text = response_text[json_start:json_end].strip()
-                elif "```" in response_text:
-                    json_start = response_text.find("```") + 3
-                    json_end = response_text.find("```", json_start)
-                    response_text = response_text[json_start:json_end].strip()
-
-                classification = json.loads(response_text)
+                classification = parse_json_reply(response_text)
                 classification["success"] = True
+                # Local models cost nothing; the usage ledger prices them if configured
+                classification["provider"] = "ollama"
+                classification["model"] = result.get("model", self.model)
+                classification["tokens_used"] = int(result.get("prompt_eval_count") or 0) + \
+                    int(result.get("eval_count") or 0)
                 return classification
 
             except json.JSONDecodeError as e:
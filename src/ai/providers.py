@@ -0,0 +1,246 @@
+"""
+AI Providers Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module lets classification use more than the local Ollama server.
+Every provider implements Provider - is_available(), list_models() and
+classify_file() with OllamaClient's result shape - so the classifier takes
+any of them as its AI client:
+
+- "ollama": a local (or LAN) Ollama server, through OllamaClient
+- "openai": any OpenAI-compatible chat completions API over HTTPS - OpenAI
+  itself, or LM Studio, vLLM, OpenRouter and similar at another base_url
+
+The provider is chosen by the "ai_provider" section of config.json; when it
+names none, the user settings' default_tier decides ("cloud": openai,
+otherwise ollama). API keys come from the OS keychain or user settings
+(see core.settings), else the OPENAI_API_KEY environment variable.
+
+    "ai_provider": {"provider": "openai", "base_url": "https://api.openai.com/v1",
+                    "model": "gpt-4o-mini", "timeout": 30}
+
+Successful results carry "provider", "model" and "tokens_used" for the
+usage ledger (see core.usage).
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+from abc import ABC, abstractmethod
+from typing import Dict, Any, List, Optional
+
+import requests
+from requests.exceptions import Timeout
+
+from .ollama_client import OllamaClient, classification_prompt, parse_json_reply
+from src.core.keychain import get_secret
+from src.core.settings import SettingsStore, api_key_secret
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_OPENAI_URL = 'https://api.openai.com/v1'
+DEFAULT_OPENAI_MODEL = 'gpt-4o-mini'
+SYSTEM_PROMPT = 'You classify files for a file organizer and answer with one JSON object only.'
+
+
+class Provider(ABC):
+    """An AI service that can classify files."""
+
+    name = ''
+
+    def __init__(self, model: str, timeout: int = 30):
+        self.model = model
+        self.timeout = timeout
+
+    @abstractmethod
+    def is_available(self) -> bool:
+        """Whether the service answers (and accepts our credentials)."""
+
+    @abstractmethod
+    def list_models(self) -> List[str]:
+        """Models the service offers."""
+
+    @abstractmethod
+    def classify_file(self, filename: str, extension: str, text_snippet: Optional[str] = None,
+                      file_size: Optional[int] = None) -> Dict[str, Any]:
+        """
+        Classify a file (see OllamaClient.classify_file for the result).
+
+        Failures come back as a result with 'success': False and an 'error',
+        never as an exception.
+        """
+
+    def _fallback(self, error: str) -> Dict[str, Any]:
+        return {'category': 'Unsorted', 'suggested_path': None, 'rename': None,
+                'reason': 'AI classification unavailable', 'success': False, 'error': error,
+                'provider': self.name}
+
+
+class OllamaProvider(Provider):
+    """A local Ollama server."""
+
+    name = 'ollama'
+
+    def __init__(self, base_url: str = 'http://localhost:11434', model: str = 'qwen2.5:7b-instruct',
+                 timeout: int = 30):
+        super().__init__(model, timeout)
+        self.client = OllamaClient(base_url=base_url, model=model, timeout=timeout)
+        self.base_url = self.client.base_url
+
+    def is_available(self) -> bool:
+        return self.client.is_available()
+
+    def list_models(self) -> List[str]:
+        return self.client.list_models()
+
+    def list_local_models(self) -> List[Dict[str, Any]]:
+        return self.client.list_local_models()
+
+    def detect(self) -> Dict[str, Any]:
+        return self.client.detect()
+
+    def classify_file(self, filename: str, extension: str, text_snippet: Optional[str] = None,
+                      file_size: Optional[int] = None) -> Dict[str, Any]:
+        return self.client.classify_file(filename, extension, text_snippet, file_size)
+
+
+class OpenAICompatibleProvider(Provider):
+    """An OpenAI-compatible chat completions API."""
+
+    name = 'openai'
+
+    def __init__(self, base_url: str = DEFAULT_OPENAI_URL, model: str = DEFAULT_OPENAI_MODEL,
+                 api_key: Optional[str] = None, timeout: int = 30):
+        super().__init__(model, timeout)
+        self.base_url = (base_url or DEFAULT_OPENAI_URL).rstrip('/')
+        self.api_key = api_key
+
+    def _headers(self) -> Dict[str, str]:
+        headers = {'Content-Type': 'application/json'}
+        if self.api_key:
+            headers['Authorization'] = f"Bearer {self.api_key}"
+        return headers
+
+    def is_available(self) -> bool:
+        # The hosted API needs a key; a local server (LM Studio, vLLM) may not
+        if not self.api_key and self.base_url == DEFAULT_OPENAI_URL:
+            return False
+        try:
+            response = requests.get(f"{self.base_url}/models", headers=self._headers(), timeout=5)
+            return response.status_code == 200
+        except Exception:
+            return False
+
+    def list_models(self) -> List[str]:
+        try:
+            response = requests.get(f"{self.base_url}/models", headers=self._headers(), timeout=self.timeout)
+            if response.status_code == 200:
+                return sorted(model['id'] for model in response.json().get('data', []))
+        except Exception as e:
+            logger.warning(f"Could not list models at {self.base_url}: {e}")
+        return []
+
+    def classify_file(self, filename: str, extension: str, text_snippet: Optional[str] = None,
+                      file_size: Optional[int] = None) -> Dict[str, Any]:
+        payload = {
+            'model': self.model,
+            'messages': [{'role': 'system', 'content': SYSTEM_PROMPT},
+                         {'role': 'user', 'content': classification_prompt(filename, extension, text_snippet,
+                                                                           file_size)}],
+            'temperature': 0,
+            'response_format': {'type': 'json_object'},
+        }
+        try:
+            response = requests.post(f"{self.base_url}/chat/completions", headers=self._headers(),
+                                     json=payload, timeout=self.timeout)
+        except Timeout:
+            return self._fallback('Request timed out')
+        except Exception as e:
+            return self._fallback(f"Request failed: {e}")
+        if response.status_code != 200:
+            return self._fallback(f"API returned status {response.status_code}: {_error_message(response)}")
+
+        try:
+            body = response.json()
+            content = body['choices'][0]['message']['content'] or ''
+        except (ValueError, KeyError, IndexError, TypeError) as e:
+            return self._fallback(f"Unexpected API response: {e}")
+        try:
+            classification = parse_json_reply(content)
+        except json.JSONDecodeError as e:
+            result = self._fallback(f"Failed to parse JSON response: {e}")
+            result['raw_response'] = content[:200]
+            return result
+        if not isinstance(classification, dict):
+            return self._fallback('The model did not answer with a JSON object')
+        usage = body.get('usage') or {}
+        classification.update(success=True, provider=self.name, model=body.get('model', self.model),
+                              tokens_used=int(usage.get('total_tokens') or 0))
+        return classification
+
+
+PROVIDERS = {'ollama': OllamaProvider, 'openai': OpenAICompatibleProvider}
+
+
+def _error_message(response) -> str:
+    try:
+        return str(response.json().get('error', {}).get('message', ''))[:200]
+    except (ValueError, AttributeError):
+        return response.text[:200]
+
+
+def api_key(provider: str) -> Optional[str]:
+    """A provider's API key: keychain, then user settings, then {PROVIDER}_API_KEY."""
+    try:
+        key = get_secret(api_key_secret(provider))
+        if not key:
+            key = SettingsStore().get_settings(reveal_secrets=True)['api_keys'].get(provider)
+    except Exception as e:
+        logger.warning(f"Could not read the {provider} API key: {e}")
+        key = None
+    return key or os.environ.get(f"{provider.upper()}_API_KEY") or None
+
+
+def _default_provider() -> str:
+    try:
+        tier = SettingsStore().load()['default_tier']
+    except ConfigurationError:
+        tier = 'local'
+    return 'openai' if tier == 'cloud' else 'ollama'
+
+
+def create_provider(config) -> Provider:
+    """
+    The AI provider the settings select.
+
+    Args:
+        config: Configuration object (ai_provider_settings, ollama_base_url, ollama_model)
+
+    Returns:
+        Provider: The provider (check is_available() before use)
+
+    Raises:
+        ConfigurationError: If the settings name an unknown provider
+    """
+    settings = getattr(config, 'ai_provider_settings', None)
+    settings = settings if isinstance(settings, dict) else {}
+    name = settings.get('provider') or _default_provider()
+    timeout = int(settings.get('timeout') or config.get('ollama_timeout', 30))
+    if name == 'ollama':
+        return OllamaProvider(settings.get('base_url') or config.ollama_base_url,
+                              settings.get('model') or config.ollama_model, timeout)
+    if name == 'openai':
+        return OpenAICompatibleProvider(settings.get('base_url') or DEFAULT_OPENAI_URL,
+                                        settings.get('model') or DEFAULT_OPENAI_MODEL,
+                                        api_key('openai'), timeout)
+    raise ConfigurationError(f"Unknown AI provider {name!r} (use {', '.join(PROVIDERS)})", 'ai_provider.provider')
@@ -14,23 +14,91 @@ from pathlib import Path
 sys.path.insert(0, str(Path(__file__).resolve().parent.parent.parent))
 
 from src.cli.helpers import (
-    print_header, print_success, print_error, print_warning, print_info
+    print_header, print_success, print_error, print_warning, print_info, confirm_action, format_size
 )
 from src.cli.intent_detector import IntentDetector
+from src.cli.json_output import emit, enable_output, exit_code, json_mode
+from src.utils.structured_logging import configure_logging, current_span, span
+from src.utils.output_schemas import (
+    outcome_exit_code, EXIT_OK, EXIT_ERROR, EXIT_CONFLICTS, EXIT_BACKEND_UNAVAILABLE, EXIT_USAGE
+)
+
+
+class AifoGroup(click.Group):
+    """Command group whose usage errors exit with EXIT_USAGE (2 means "plan has conflicts")."""
+
+    def parse_args(self, ctx, args):
+        try:
+            return super().parse_args(ctx, args)
+        except click.UsageError as e:
+            e.exit_code = EXIT_USAGE
+            raise
+
+    def invoke(self, ctx):
+        try:
+            return super().invoke(ctx)
+        except click.UsageError as e:
+            e.exit_code = EXIT_USAGE
+            raise
 
 
-@click.group()
+@click.group(cls=AifoGroup)
 @click.version_option(version='1.0.0')
-def cli():
+@click.option('--json', 'as_json', is_flag=True, help='Print one versioned JSON document on stdout')
+@click.option('--yes', '-y', 'assume_yes', is_flag=True, help='Answer yes to confirmations (needed to act with --json)')
+@click.pass_context
+def cli(ctx, as_json, assume_yes):
     """
     AI File Organiser - Intelligent file organization powered by local AI
 
     Common Commands:
       aifo space      - Free up disk space (duplicates, large old files)
       aifo organize   - Organize files intelligently
+      aifo watch      - Watch a folder and organize new files as they land
       aifo find       - Find duplicate files
+      aifo dedupe     - Find duplicates fast; trash, move or hard-link the extra copies
+      aifo move       - Move files safely, never overwriting (undo with aifo undo)
+      aifo trash      - Move files to the system trash, list or restore them
+      aifo metadata   - Show date taken, GPS, audio tags or PDF details of files
+      aifo preview    - Show the start of a file, or its PDF / Office structure
+      aifo clean      - Review and trash installer leftovers, temp files and empty folders
+      aifo corrections - See (or teach) where you corrected files to go
+      aifo cache      - See (or clear) the cached AI classifications
       aifo scan       - Quick folder inventory
       aifo stats      - Show organization statistics
+      aifo snapshots  - List or restore pre-organize snapshots
+      aifo undo       - Step back through (or list) past operations
+      aifo redo       - Step forward again after undoing
+      aifo rename     - Rename a file, checking the name works on every platform
+      aifo versions   - List or restore kept versions of replaced files
+      aifo history    - Show a folder as it was at a past point in time
+      aifo workspace  - Scan, dedupe or organize several roots as one
+      aifo consolidate - Gather scattered files into one folder tree
+      aifo compare    - Compare two folders (e.g. backups) and merge them
+      aifo pin        - Pin favorite folders and see their quick stats
+      aifo collection - Group files into virtual collections without moving them
+      aifo people     - Group photos by the people in them (offline face clustering)
+      aifo bursts     - Find photo bursts and near-identical shots, keep the best
+      aifo music      - Preview tag fixes before filing songs as Artist/Album
+      aifo ebooks     - Find books present in several copies, formats or editions
+      aifo leave-alone - Show or change the folders organizing never touches
+      aifo invoices   - Read vendor, date and total of invoices (CSV export)
+      aifo fonts      - Find duplicate font versions and install fonts
+      aifo classify   - Classify files (local rules only when the engine is down)
+      aifo daemon     - Drive the running engine (python -m src.main daemon)
+      aifo jobs       - List the engine's organize jobs, or cancel one
+      aifo validate-rules - Lint a YAML rules file (e.g. a shared rule pack)
+      aifo log-level  - Show or change the running app's log levels
+      aifo logs       - Show (or follow) the running app's live log
+      aifo crash-reports - Review and send saved crash reports (opt-in)
+      aifo diagnostics - Save logs and environment info for a bug report
+      aifo self-test  - Check scan, classify, plan, apply and undo end-to-end
+      aifo simulate   - Try organizing a sandboxed copy of a sample of a folder
+      aifo doctor     - Check Python, packages and the AI backend, and how to fix them
+      aifo update     - Check for and install updates, or roll back the last one
+      aifo telemetry  - See (and control) the usage statistics kept and sent
+      aifo plugins    - List plugin actions and grant them what they ask for
+      aifo rest-api   - Turn on the engine's REST API and show its token
       aifo ask        - Ask what you want in natural language
 
     Examples:
@@ -38,11 +106,35 @@ def cli():
       aifo organize              # Organize Downloads folder
       aifo find --delete         # Find and delete duplicates
       aifo ask "free up space"   # Natural language query
+      aifo --json scan ~/Downloads       # Machine-readable output
+      aifo --json --yes organize         # Act without prompting
+
+    Exit codes:
+      0 ok, 1 failed, 2 plan has conflicts, 3 partial failure,
+      4 AI backend unavailable, 64 invalid arguments
 
     For help on specific command:
       aifo COMMAND --help
     """
-    pass
+    enable_output(ctx, as_json, assume_yes)
+    ctx.with_resource(span(f"aifo.{ctx.invoked_subcommand or 'cli'}"))
+
+
+@cli.result_callback()
+@click.pass_context
+def finish(ctx, result, **_options):
+    """Exit with the command's deterministic exit code."""
+    code = exit_code(result)
+    if ctx.invoked_subcommand:
+        from src.core.telemetry import get_telemetry
+        get_telemetry().record(f"cli.{ctx.invoked_subcommand}", f"exit-{code}" if code else None)
+    trace = current_span()
+    if trace is not None:
+        trace.record(exit_code=code)
+        if code:
+            trace.fail(f"exit code {code}")
+    if code:
+        ctx.exit(code)
 
 
 @cli.command()
@@ -68,6 +160,9 @@ def space(analyze, duplicates, large_old, migrate, auto):
 
     # If no options specified, show interactive menu
     if not any([analyze, duplicates, large_old, migrate]):
+        if json_mode():
+            print_error("The interactive menu needs a terminal; use --analyze, --duplicates, --large-old or --migrate")
+            return
         manager.interactive_menu(auto)
         return
 
@@ -84,13 +179,23 @@ def space(analyze, duplicates, large_old, migrate, auto):
     if migrate:
         manager.migrate_to_drive(migrate, auto)
 
+    emit('space', {'actions': [name for name, wanted in (('analyze', analyze), ('duplicates', duplicates),
+                                                         ('large_old', large_old), ('migrate', migrate))
+                               if wanted]})
+
 
 @cli.command()
 @click.argument('folder', type=click.Path(exists=True), required=False)
 @click.option('--preview', '-p', is_flag=True, help='Preview changes (dry run)')
 @click.option('--auto', '-a', is_flag=True, help='Auto-approve (skip confirmation)')
 @click.option('--deep', '-d', is_flag=True, help='Deep AI analysis (slower, better)')
-def organize(folder, preview, auto, deep):
+@click.option('--save-plan', type=click.Path(dir_okay=False), help='Write a plan file to review instead of moving files')
+@click.option('--apply-plan', type=click.Path(exists=True, dir_okay=False), help='Apply a plan file written by --save-plan')
+@click.option('--strategy', '-s', type=click.Choice(['classify', 'photos', 'music', 'videos', 'ebooks', 'fonts']),
+              help='How to place files (default: per folder policy, else classify)')
+@click.option('--profile', help='Organization profile from your settings (e.g. photos, work)')
+@click.option('--events', is_flag=True, help='Stream progress as JSON lines on stdout (for front ends)')
+def organize(folder, preview, auto, deep, save_plan, apply_plan, strategy, profile, events):
     """
     Organize files intelligently
 
@@ -100,11 +205,170 @@ def organize(folder, preview, auto, deep):
       aifo organize -p             # Preview first
       aifo organize -a             # Auto-approve
       aifo organize -d ~/Pictures  # Deep AI for photos
+      aifo organize -s photos ~/Pictures/Import  # Photos into Pictures/YYYY/MM by capture date
+      aifo organize --profile work ~/Desktop     # Root, folders, rules and AI tier of a saved profile
+                                   # (photo_layout "location" in a folder policy: YYYY/Country/City)
+      aifo organize ~/Downloads --save-plan plan.json   # Plan now (e.g. in CI)...
+      aifo --yes organize --apply-plan plan.json        # ...apply after review
+      aifo organize ~/Downloads --events -a             # Live progress for a front end
+    """
+    from src.cli.organizer import Organizer
+
+    if save_plan and apply_plan:
+        raise click.UsageError("Use either --save-plan or --apply-plan, not both")
+    if events and (save_plan or apply_plan or json_mode()):
+        raise click.UsageError("--events cannot be combined with --json, --save-plan or --apply-plan")
+
+    org = Organizer()
+    if save_plan or apply_plan:
+        return _organize_with_plan(org, folder, deep, save_plan, apply_plan, strategy, profile)
+
+    if events:
+        import signal
+        import threading
+        from contextlib import redirect_stdout
+        from src.core.progress_events import EventWriter
+
+        # A job being cancelled gets SIGTERM: finish the current file, then stop
+        stop = threading.Event()
+        try:
+            signal.signal(signal.SIGTERM, lambda *_: stop.set())
+        except ValueError:  # not the main thread
+            pass
+
+        # Events own stdout; headers and progress bars go to stderr
+        writer = EventWriter(sys.stdout)
+        with redirect_stdout(sys.stderr):
+            summary = org.organize_folder(folder, preview, auto, deep, strategy, profile, on_event=writer,
+                                          should_stop=stop.is_set)
+        if summary is None:
+            return EXIT_ERROR
+        return outcome_exit_code(summary['organized'], summary['errors'])
+
+    summary = org.organize_folder(folder, preview, auto, deep, strategy, profile)
+    emit('organize', summary, ok=summary is not None and not summary['errors'])
+    if summary is None:
+        return EXIT_ERROR
+    return outcome_exit_code(summary['organized'], summary['errors'])
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--auto/--review', 'auto_organize', default=None,
+              help='Move new files right away, or only show where they would go (default: auto_mode)')
+@click.option('--ext', 'extensions', multiple=True, help='Only files with this extension (repeatable)')
+@click.option('--no-recursive', is_flag=True, help='Ignore files landing in subfolders')
+@click.option('--deep', '-d', is_flag=True, help='Deep AI analysis (slower, better)')
+def watch(folder, auto_organize, extensions, no_recursive, deep):
+    """
+    Watch a folder and classify (or organize) new files as they land
+
+    Examples:
+      aifo watch ~/Downloads --auto           # File downloads as they finish
+      aifo watch ~/Desktop --review --ext png # Only show where screenshots would go
     """
+    import time
     from src.cli.organizer import Organizer
+    from src.core.watcher import FolderWatchManager, EVENT_FILE_DETECTED, EVENT_FILE_ORGANIZED
+
+    def show(event, payload):
+        name = Path(payload['file']).name
+        if event == EVENT_FILE_DETECTED:
+            classification = payload['classification']
+            click.echo(f"📁 {name}: {classification.get('category')} -> {classification.get('suggested_path') or '-'}")
+        elif event == EVENT_FILE_ORGANIZED:
+            print_success(f"   {payload['message']}")
+        else:
+            print_warning(f"   {name}: {payload['message']}")
 
     org = Organizer()
-    org.organize_folder(folder, preview, auto, deep)
+    manager = FolderWatchManager(org.config, org.classifier, org.action_manager, listener=show)
+    rules = {'extensions': list(extensions), 'recursive': not no_recursive, 'deep': deep}
+    if auto_organize is not None:
+        rules['auto_organize'] = auto_organize
+    result = manager.start_watching(folder, rules)
+    if not result['success']:
+        print_error(result['message'])
+        return EXIT_ERROR
+
+    mode = 'organizing' if result['watch']['rules']['auto_organize'] else 'reporting'
+    print_success(f"{result['message']} ({mode} new files)")
+    print_info("Press Ctrl+C to stop")
+    try:
+        while True:
+            time.sleep(1)
+    except KeyboardInterrupt:
+        pass
+    watched = manager.watching()[0]
+    manager.stop_all()
+    click.echo(f"\n{watched['detected']} detected, {watched['organized']} organized, {watched['errors']} error(s)")
+    emit('watch', watched)
+
+
+def _organize_with_plan(org, folder, deep, save_plan, apply_plan, strategy=None, profile=None):
+    """Write an organize plan file for review, or apply a reviewed one."""
+    from src.core.plans import OrganizePlanner
+
+    planner = OrganizePlanner(org.config, org.classifier, org.action_manager)
+
+    if apply_plan:
+        try:
+            plan = planner.load_plan(apply_plan)
+        except ValueError as e:
+            print_error(str(e))
+            return EXIT_ERROR
+        print_header(f"🗂️  Applying plan for {plan['root']}")
+        click.echo(f"{len(plan['items'])} planned action(s), made {plan['created_at']}")
+        if not plan['items'] or not confirm_action(f"\nApply {len(plan['items'])} action(s)?", default=True):
+            print_info("Nothing applied.")
+            emit('organize', {'plan': plan, 'result': None})
+            return None
+        result = planner.apply_plan(plan)
+        for entry in result['failed'] + result['skipped']:
+            print_warning(f"{entry['file']}: {entry['message']}")
+        for conflict in result['conflicts']:
+            print_warning(f"{conflict['source']}: {conflict['destination']} exists (conflict {conflict['id']})")
+        (print_success if result['success'] else print_warning)(result['message'])
+        if result['snapshot_id']:
+            print_info(f"Snapshot {result['snapshot_id']} saved (restore with: aifo snapshots --restore "
+                       f"{result['snapshot_id']})")
+        if result['applied']:
+            print_info("Undo this run with: aifo undo")
+        emit('organize', {'plan': plan, 'result': result}, ok=result['success'])
+        return outcome_exit_code(len(result['applied']),
+                                 len(result['failed']) + len(result['skipped']) + len(result['conflicts']))
+
+    if folder is None:
+        if not org.config.watched_folders:
+            print_error("No watched folders configured in config.json")
+            return EXIT_ERROR
+        folder = org.config.watched_folders[0]
+    if deep and not org.ollama:
+        print_error("Deep AI analysis requested but Ollama is not available")
+        return EXIT_BACKEND_UNAVAILABLE
+    try:
+        plan = planner.build_plan(folder, deep=deep, strategy=strategy, profile=profile)
+    except (NotADirectoryError, ValueError) as e:
+        print_error(str(e))
+        return EXIT_ERROR
+    path = planner.save_plan(plan, save_plan)
+
+    print_header(f"🗂️  Plan for {plan['root']}")
+    for item in plan['items']:
+        flag = f"  [{item['conflict'].replace('_', ' ')}]" if item['conflict'] else ''
+        if item.get('access'):
+            flag += f"  [cannot move: {item['access']['message']}]"
+        click.echo(f"  {item['file']} -> {item['destination']}{flag}")
+    if plan['conflicts']:
+        print_warning(f"{plan['conflicts']} destination conflict(s) to review before applying")
+    if plan.get('access_problems'):
+        print_warning(f"{plan['access_problems']} file(s) cannot be moved as things stand (see: cannot move)")
+    print_info(f"{len(plan['items'])} action(s), {plan['unchanged']} file(s) unchanged")
+    if plan['left_alone']:
+        print_info(f"{len(plan['left_alone'])} folder(s) left alone (see: aifo leave-alone {plan['root']})")
+    print_success(f"Plan written to {path}; apply it with: aifo organize --apply-plan {path}")
+    emit('organize', {'plan_file': path, 'plan': plan})
+    return EXIT_CONFLICTS if plan['conflicts'] else None
 
 
 @cli.command()
@@ -124,7 +388,380 @@ def find(folder, delete, min_size):
     from src.cli.duplicate_finder import DuplicateFinder
 
     finder = DuplicateFinder()
-    finder.find_duplicates(folder, delete, min_size)
+    report = finder.find_duplicates(folder, delete, min_size)
+    emit('find', report)
+    deleted = report['deleted']
+    if deleted and deleted['errors']:
+        return outcome_exit_code(deleted['count'], len(deleted['errors']))
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--algorithm', type=click.Choice(['blake2b', 'xxhash', 'sha256', 'sha1', 'md5']),
+              default='blake2b', show_default=True, help='Content hash')
+@click.option('--resolve', 'mode', type=click.Choice(['trash', 'move', 'hardlink']),
+              help='What to do with the copies not kept')
+@click.option('--to', 'destination', type=click.Path(file_okay=False), help='Folder for --resolve move')
+@click.option('--min-size', 'min_size', default=1, show_default=True, help='Ignore smaller files (bytes)')
+@click.option('--preview', is_flag=True, help='Show what --resolve would do')
+@click.option('--no-recursive', is_flag=True, help='Only the folder itself, not subfolders')
+def dedupe(folder, algorithm, mode, destination, min_size, preview, no_recursive):
+    """
+    Find duplicate files in a folder and resolve them in one undoable step
+
+    Files are compared by size first and only same-size files are hashed.
+    The copy in the most permanent, shortest path is kept.
+
+    Examples:
+      aifo dedupe ~/Pictures
+      aifo dedupe ~/Pictures --resolve hardlink --preview
+      aifo dedupe ~/Downloads --resolve move --to ~/Duplicates
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.dedupe import Deduplicator
+
+    if mode == 'move' and not destination:
+        raise click.UsageError("--resolve move needs --to FOLDER")
+    deduplicator = Deduplicator(get_config(), DatabaseManager())
+    try:
+        found = deduplicator.find_duplicates(folder, algorithm, recursive=not no_recursive, min_size=min_size)
+    except ValueError as e:
+        print_error(str(e))
+        return EXIT_USAGE
+
+    print_header(f"👯 Duplicates in {folder}")
+    for group in found['groups']:
+        click.echo(f"  {format_size(group['size'])} x{group['count']}  keep {group['keep']}")
+        for path in group['delete']:
+            click.echo(f"      {path}")
+    if found['errors']:
+        print_warning(f"{len(found['errors'])} file(s) could not be read")
+    print_info(f"{found['message']} ({found['scanned']} files, {found['hashed']} hashed)")
+    if not mode or not found['groups']:
+        emit('dedupe', {'found': found, 'result': None})
+        return None
+
+    copies = found['summary']['total_duplicate_files']
+    if not preview and not confirm_action(f"\n{mode.capitalize()} {copies} extra copies?"):
+        emit('dedupe', {'found': found, 'result': None})
+        return None
+    result = deduplicator.resolve_duplicates(found['groups'], mode, destination, dry_run=preview)
+    for entry in result['skipped']:
+        print_warning(f"{entry['path']}: {entry['reason']}")
+    (print_success if result['success'] else print_warning)(result['message'])
+    if result['operation_id']:
+        print_info(f"Freed {format_size(result['space_freed'])}; undo with: aifo undo")
+    emit('dedupe', {'found': found, 'result': result}, ok=result['success'])
+    return outcome_exit_code(len(result['resolved']), len(result['skipped']))
+
+
+@cli.command()
+@click.argument('sources', nargs=-1, required=True, type=click.Path(exists=True, dir_okay=False))
+@click.argument('destination', type=click.Path())
+@click.option('--on-conflict', type=click.Choice(['keep_both', 'replace', 'skip']), default='keep_both',
+              show_default=True, help='When a file of that name is already there')
+@click.option('--preview', is_flag=True, help='Show where files would go')
+def move(sources, destination, on_conflict, preview):
+    """
+    Move or rename files safely, as one undoable operation
+
+    Several files go into DESTINATION as a folder. An existing file is
+    never overwritten unless --on-conflict replace: the moved file gets a
+    free name such as "report (2).pdf". Moves to another drive are copied,
+    checked and only then removed from where they were.
+
+    Examples:
+      aifo move report.pdf ~/Documents/
+      aifo move notes.txt notes-2025.txt
+      aifo move *.jpg ~/Pictures/Trip --preview
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.fsops import FileMover
+
+    target = Path(destination).expanduser()
+    if len(sources) > 1 or destination.endswith(('/', '\\')):
+        if target.exists() and not target.is_dir():
+            raise click.UsageError(f"{destination} is not a folder")
+        target.mkdir(parents=True, exist_ok=True)
+    moves = [{'source': source, 'destination': str(target)} for source in sources]
+    result = FileMover(get_config(), DatabaseManager()).apply_plan(moves, on_conflict, dry_run=preview)
+
+    for entry in result['results']:
+        if entry['status'] == 'moved':
+            click.echo(f"  {entry['source']} -> {entry['destination']}")
+        else:
+            print_warning(f"{entry['source']}: {entry['message']}")
+    (print_success if result['success'] else print_warning)(result['message'])
+    if result['operation_id']:
+        print_info("Undo with: aifo undo")
+    emit('move', result, ok=result['success'])
+    return outcome_exit_code(result['moved'], result['failed'])
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False), required=False)
+@click.option('--save-plan', type=click.Path(dir_okay=False), help='Write the cleanup plan to review instead')
+@click.option('--no-recursive', is_flag=True, help='Only check the folder itself')
+def clean(folder, save_plan, no_recursive):
+    """
+    Move junk to the trash: installers of installed apps, old partial
+    downloads, Office temp files, empty files and empty folders
+
+    Everything goes to the trash as one operation, so "aifo undo" brings
+    it all back.
+
+    Examples:
+      aifo clean                        # Clean the first watched folder
+      aifo clean ~/Desktop --save-plan cleanup.json
+      aifo organize --apply-plan cleanup.json   # Apply it after review
+    """
+    from src.cli.organizer import Organizer
+    from src.core.plans import OrganizePlanner
+
+    org = Organizer()
+    if folder is None:
+        if not org.config.watched_folders:
+            print_error("No watched folders configured in config.json")
+            return EXIT_ERROR
+        folder = org.config.watched_folders[0]
+    planner = OrganizePlanner(org.config, org.classifier, org.action_manager)
+    try:
+        plan = planner.build_cleanup_plan(folder, recursive=not no_recursive)
+    except NotADirectoryError as e:
+        print_error(str(e))
+        return EXIT_ERROR
+
+    print_header(f"🧹 Junk in {plan['root']}")
+    for item in plan['items']:
+        click.echo(f"  {item['reason'].replace('_', ' '):<17} {item['file']}")
+    if not plan['items']:
+        print_info("Nothing to clean")
+        emit('clean', {'plan': plan, 'result': None})
+        return None
+    print_info(f"{len(plan['items'])} item(s), {format_size(plan['total_size'])}")
+    if save_plan:
+        path = planner.save_plan(plan, save_plan)
+        print_success(f"Plan written to {path}; apply it with: aifo organize --apply-plan {path}")
+        emit('clean', {'plan_file': path, 'plan': plan})
+        return None
+    if not confirm_action(f"\nMove {len(plan['items'])} item(s) to the trash?"):
+        emit('clean', {'plan': plan, 'result': None})
+        return None
+    result = planner.apply_plan(plan)
+    for entry in result['failed'] + result['skipped']:
+        print_warning(f"{entry['file']}: {entry['message']}")
+    (print_success if result['success'] else print_warning)(result['message'])
+    if result['applied']:
+        print_info("Undo this cleanup with: aifo undo")
+    emit('clean', {'plan': plan, 'result': result}, ok=result['success'])
+    return outcome_exit_code(len(result['applied']), len(result['failed']) + len(result['skipped']))
+
+
+@cli.command()
+@click.option('--learn', nargs=2, metavar='FILE FOLDER', help='Teach where files like FILE belong')
+@click.option('--category', help='Category for --learn')
+@click.option('--forget', 'forget_id', type=int, metavar='ID', help='Forget one correction')
+@click.option('--clear', is_flag=True, help='Forget all corrections')
+@click.option('--limit', default=30, show_default=True, help='Corrections to list')
+def corrections(learn, category, forget_id, clear, limit):
+    """
+    List the destination corrections organizing has learned
+
+    Whenever you send a file somewhere other than suggested (a custom
+    path in the dashboard, or an "override" in a plan file), similar files
+    are suggested there next time - after two agreeing corrections, or at
+    once for the same file content.
+
+    Examples:
+      aifo corrections
+      aifo corrections --learn ~/Downloads/invoice-acme.pdf Finance/Acme
+      aifo corrections --forget 12
+    """
+    import os
+    from src.config import get_config
+    from src.core.corrections import CorrectionLearner
+    from src.core.db_manager import DatabaseManager
+
+    learner = CorrectionLearner(get_config(), DatabaseManager())
+    actions = []
+    if learn:
+        actions.append(learner.record(learn[0], learn[1], category))
+    if forget_id is not None or clear:
+        actions.append(learner.forget(None if clear else forget_id))
+    for result in actions:
+        if not result['success']:
+            emit('corrections', result, ok=False)
+            print_error(result['message'])
+            return EXIT_ERROR
+        print_success(result['message'])
+
+    rows = learner.list(limit)
+    emit('corrections', {'enabled': learner.enabled, 'min_repeats': learner.min_repeats, 'corrections': rows})
+    print_header("🎓 Learned corrections")
+    if not learner.enabled:
+        print_warning("Learning from corrections is turned off (corrections.enabled)")
+    for row in rows:
+        chosen = row['destination']
+        where = os.path.join(chosen['destination_root'] or '', chosen['suggested_path'])
+        words = ' '.join(row['name_tokens'][:6])
+        click.echo(f"  #{row['id']:<5} {row['created_at']}  .{row['extension'] or '(none)':<6} {words:<30} -> {where}")
+    if not rows:
+        print_info("Nothing learned yet")
+
+
+@cli.command()
+@click.option('--clear', is_flag=True, help='Forget every cached classification')
+def cache(clear):
+    """
+    Show how many AI classifications are cached, or clear them
+
+    Unchanged files (same path, size and time, or same content) reuse
+    their earlier AI classification instead of asking the model again.
+
+    Examples:
+      aifo cache
+      aifo cache --clear
+    """
+    from src.config import get_config
+    from src.core.classification_cache import ClassificationCache
+    from src.core.db_manager import DatabaseManager
+
+    classification_cache = ClassificationCache(get_config(), DatabaseManager())
+    if clear:
+        result = classification_cache.clear()
+        emit('cache', result)
+        print_success(result['message'])
+        return None
+
+    stats = classification_cache.stats()
+    emit('cache', stats)
+    print_header("🗃️  Classification cache")
+    if not stats['enabled']:
+        print_warning("Caching is turned off (classification_cache.enabled)")
+    click.echo(f"  Cached files: {stats['entries']}")
+    click.echo(f"  Reused:       {stats['total_hits']} time(s)")
+    if stats['oldest']:
+        click.echo(f"  Oldest entry: {stats['oldest']}")
+
+
+@cli.command()
+@click.argument('paths', nargs=-1, type=click.Path(exists=True))
+@click.option('--list', 'list_items', is_flag=True, help='Show recently removed files')
+@click.option('--restore', 'restore_ids', multiple=True, type=int, help='Put an item back (ID from --list)')
+def trash(paths, list_items, restore_ids):
+    """
+    Move files to the system trash, or list and restore what was removed
+
+    Files go to the platform trash (Recycle Bin, macOS Trash, freedesktop
+    Trash) and are never deleted outright.
+
+    Examples:
+      aifo trash old-notes.txt ~/Downloads/setup.exe
+      aifo trash --list
+      aifo trash --restore 12
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.trash import SystemTrash
+
+    if bool(paths) + bool(list_items) + bool(restore_ids) != 1:
+        raise click.UsageError("Give files to trash, --list or --restore ID")
+    system_trash = SystemTrash(get_config(), DatabaseManager())
+
+    if list_items:
+        items = system_trash.list_trash_items()
+        emit('trash', {'items': items})
+        print_header("🗑️  Recently removed")
+        for item in items:
+            where = '' if item['in_trash'] is not False else '  (no longer in the trash)'
+            click.echo(f"  #{item['id']:<5} {item['trashed_at']}  {format_size(item['size']):>9}  "
+                       f"{item['original_path']}{where}")
+        if not items:
+            print_info("Nothing removed yet")
+        return None
+
+    if restore_ids:
+        result = system_trash.restore_from_trash(list(restore_ids))
+        for entry in result['restored']:
+            click.echo(f"  #{entry['id']} -> {entry['path']}")
+        problems = result['failed']
+        done = len(result['restored'])
+    else:
+        if not confirm_action(f"Move {len(paths)} item(s) to the trash?", default=True):
+            return None
+        result = system_trash.move_to_trash(list(paths))
+        problems = [{'id': entry['path'], 'reason': entry['reason']} for entry in result['failed']]
+        done = len(result['trashed'])
+    for entry in problems:
+        print_warning(f"{entry['id']}: {entry['reason']}")
+    (print_success if result['success'] else print_warning)(result['message'])
+    emit('trash', result, ok=result['success'])
+    return outcome_exit_code(done, len(problems))
+
+
+@cli.command()
+@click.argument('paths', nargs=-1, required=True, type=click.Path(exists=True, dir_okay=False))
+def metadata(paths):
+    """
+    Show what files say about themselves: photo date taken, GPS and
+    camera, song tags, PDF title, author and page count
+
+    Examples:
+      aifo metadata IMG_0042.jpg
+      aifo metadata ~/Music/*.mp3
+    """
+    from src.core.extract import extract_many
+
+    results = extract_many(list(paths))
+    emit('metadata', {'results': results})
+    labels = (('date_taken', 'Taken'), ('camera', 'Camera'), ('title', 'Title'), ('artist', 'Artist'),
+              ('album', 'Album'), ('track', 'Track'), ('year', 'Year'), ('author', 'Author'),
+              ('page_count', 'Pages'))
+    for meta in results:
+        print_header(f"{Path(meta['path']).name} ({meta['kind']})")
+        if meta['error']:
+            print_warning(meta['error'])
+        for field_name, label in labels:
+            if meta[field_name] is not None and not (field_name == 'author' and meta['kind'] == 'audio'):
+                click.echo(f"  {label + ':':<8} {meta[field_name]}")
+        if meta['latitude'] is not None:
+            click.echo(f"  {'GPS:':<8} {meta['latitude']}, {meta['longitude']}")
+    if any(meta['error'] for meta in results):
+        return outcome_exit_code(sum(1 for meta in results if not meta['error']),
+                                 sum(1 for meta in results if meta['error']))
+
+
+@cli.command()
+@click.argument('path', type=click.Path(exists=True, dir_okay=False))
+@click.option('--bytes', 'max_bytes', default=4096, show_default=True, help='Most bytes of text to show')
+def preview(path, max_bytes):
+    """
+    Show what a file is before organizing it: the start of a text file,
+    or the structure of a PDF or Office document
+
+    Examples:
+      aifo preview notes.txt
+      aifo preview ~/Downloads/contract.docx
+    """
+    from src.core.preview import get_file_preview
+
+    result = get_file_preview(path, max_bytes)
+    emit('preview', result)
+    print_header(f"{result['name']} ({result['kind']}, {format_size(result['size'])})")
+    if result['message']:
+        print_warning(result['message'])
+    for name, value in (result['structure'] or {}).items():
+        if name != 'text' and value not in (None, [], ''):
+            click.echo(f"  {name}: {', '.join(value) if isinstance(value, list) else value}")
+    text = result['text'] if result['text'] is not None else (result['structure'] or {}).get('text')
+    if text:
+        click.echo(f"\n{text}")
+        if result['truncated']:
+            print_info(f"... first {max_bytes} bytes shown ({result['encoding']})")
+    elif result['thumbnail']:
+        print_info(f"Thumbnail available ({result['thumbnail']['source']})")
 
 
 @cli.command()
@@ -142,45 +779,1784 @@ def scan(folder, detailed):
     from src.cli.scanner import Scanner
 
     scanner = Scanner()
-    scanner.scan_folder(folder, detailed)
+    result = scanner.scan_folder(folder, detailed)
+    emit('scan', result, ok=result is not None)
 
 
 @cli.command()
-def stats():
+@click.option('--timings', '-t', is_flag=True, help='Show where recent runs spent their time')
+@click.option('--runs', '-n', default=10, show_default=True, help='With --timings, number of recent runs')
+def stats(timings, runs):
     """
     Show organization statistics
 
-    Example:
-      aifo stats    # Show all-time stats
+    Examples:
+      aifo stats              # Show all-time stats
+      aifo stats --timings    # Time per stage (scan, classify, ai, move...) of recent runs
     """
+    if timings:
+        _show_timings(runs)
+        return
+
     from src.cli.stats_viewer import StatsViewer
 
     viewer = StatsViewer()
-    viewer.show_stats()
+    emit('stats', viewer.show_stats())
+
+
+def _show_timings(runs):
+    """Print the per-stage timings of recent runs."""
+    report = _operation_journal().timing_report(runs)
+    emit('stats', {'timings': report})
+    if not report['runs']:
+        print_info("No timed runs yet.")
+        return
+    print_header("⏱️  Where the time went")
+    for name, entry in report['stages'].items():
+        click.echo(f"  {name:<10} {entry['seconds']:>9.2f}s  {entry['share'] * 100:>5.1f}%  ({entry['count']}x)")
+    click.echo("\nRecent runs:")
+    for run in report['runs']:
+        stages = ', '.join(f"{name} {entry['seconds']:.2f}s" for name, entry in run['timings']['stages'].items())
+        click.echo(f"  #{run['id']:<5} {run['created_at']}  {run['timings']['elapsed']:>7.2f}s  {stages}")
 
 
 @cli.command()
-@click.argument('query', nargs=-1)
-def ask(query):
+@click.option('--restore', '-r', type=int, help='Restore the snapshot with this ID')
+@click.option('--preview', '-p', is_flag=True, help='Show what a restore would move')
+def snapshots(restore, preview):
     """
-    Ask what you want in natural language
+    List or restore folder snapshots taken before organizing
 
     Examples:
-      aifo ask "I need to free up space on C drive"
-      aifo ask "organize my photos"
-      aifo ask "find duplicate files"
+      aifo snapshots              # List recent snapshots
+      aifo snapshots -r 3 -p      # Preview restoring snapshot 3
+      aifo snapshots -r 3         # Restore snapshot 3
     """
-    detector = IntentDetector()
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.snapshots import SnapshotManager
 
-    # Join query arguments
-    query_str = ' '.join(query)
+    manager = SnapshotManager(get_config(), DatabaseManager())
 
-    # If no query provided, prompt user
-    if not query_str:
-        query_str = click.prompt("What would you like to do?")
+    if restore is None:
+        items = manager.list_snapshots()
+        emit('snapshots', {'snapshots': items})
+        if not items:
+            print_info("No snapshots yet.")
+            return
+        print_header("📸 Snapshots")
+        for item in items:
+            click.echo(f"  #{item['id']:<4} {item['created_at']}  {item['file_count']:>6} files  "
+                       f"[{item['status']}]  {item['root_path']}")
+            if item.get('reason'):
+                click.echo(f"         {item['reason']}")
+        return
 
-    # Detect intent
-    result = detector.detect_and_suggest(query_str)
+    result = manager.restore_snapshot(restore, dry_run=True)
+    if not result['success']:
+        print_error(result['message'])
+        return
+
+    click.echo(result['message'])
+    for move in result['restored'][:20]:
+        click.echo(f"  {move['from']} -> {move['to']}")
+    if len(result['restored']) > 20:
+        click.echo(f"  ... and {len(result['restored']) - 20} more")
+    if result['missing']:
+        print_warning(f"{len(result['missing'])} file(s) could not be found")
+    if result['conflicts']:
+        print_warning(f"{len(result['conflicts'])} path(s) are occupied by other files and will be skipped")
+
+    if preview or not result['restored']:
+        emit('snapshots', {'restore': result, 'applied': False})
+        return
+    if not confirm_action(f"\nRestore {len(result['restored'])} file(s)?", default=True):
+        print_info("Cancelled.")
+        emit('snapshots', {'restore': result, 'applied': False})
+        return
+
+    result = manager.restore_snapshot(restore)
+    if result['success']:
+        print_success(result['message'])
+    else:
+        print_error(result['message'])
+    emit('snapshots', {'restore': result, 'applied': True}, ok=result['success'])
+
+
+def _operation_journal():
+    """Operation journal on the default database."""
+    from src.core.db_manager import DatabaseManager
+    from src.core.journal import OperationJournal
+
+    return OperationJournal(DatabaseManager())
+
+
+def _step_stack(journal, direction, steps, preview):
+    """Undo or redo up to `steps` operations from the top of the stack."""
+    step = journal.undo_last if direction == 'undo' else journal.redo_last
+    results = []
+    for _ in range(steps):
+        result = step(dry_run=preview)
+        results.append(result)
+        if not result['success']:
+            if 'operation_id' in result:
+                print_error(result['message'])
+            else:
+                print_info(result['message'])
+            break
+        print_success(result['message'])
+        if preview:
+            break  # The stack does not move in a preview
+    emit(direction, {'preview': preview, 'results': results})
+
+
+def _undo_operation(journal, operation_id, item_id, show_list, preview, keep_both):
+    """Show, undo, or undo a single item of one journal operation."""
+    if show_list:
+        operation = journal.get_operation(operation_id)
+        if not operation:
+            print_error(f"Operation {operation_id} not found")
+            return
+        emit('undo', {'operation': operation})
+        print_header(f"Operation #{operation_id}: {operation.get('description') or operation['kind']}")
+        click.echo(f"Status: {operation['status']}")
+        for step in operation['steps']:
+            click.echo(f"  item {step['id']:<6} [{step['status']:<7}] {step['action']:<6} "
+                       f"{step.get('source_path') or ''} -> {step.get('dest_path') or ''}")
+        return
+
+    if item_id is None:
+        result = journal.undo(operation_id, dry_run=preview)
+    else:
+        result = journal.undo_item(operation_id, item_id, dry_run=preview,
+                                   on_conflict='keep_both' if keep_both else 'fail')
+    emit('undo', {'preview': preview, 'results': [result]}, ok=result['success'])
+
+    if result['success']:
+        print_success(result['message'])
+        if result.get('restored_to'):
+            click.echo(f"  Restored to {result['restored_to']}")
+    else:
+        print_error(result['message'])
+        if result.get('conflict'):
+            print_info("Use --keep-both to restore it next to the existing file")
+
+
+@cli.command()
+@click.option('--list', '-l', 'show_list', is_flag=True, help='Show the undo and redo stacks')
+@click.option('--steps', '-n', default=1, show_default=True, help='Number of operations to undo')
+@click.option('--preview', '-p', is_flag=True, help='Show what would be undone')
+@click.option('--operation', '-o', 'operation_id', type=int, help='Undo (or with --list, show) this operation')
+@click.option('--item', '-i', 'item_id', type=int, help='Undo only this item of --operation')
+@click.option('--keep-both', is_flag=True, help='Restore next to a file now occupying the original path')
+def undo(show_list, steps, preview, operation_id, item_id, keep_both):
+    """
+    Undo past operations (moves, renames, deletes, organize runs)
+
+    Examples:
+      aifo undo                   # Undo the last operation
+      aifo undo -n 3              # Undo the last three operations
+      aifo undo --list            # Show what can be undone and redone
+      aifo undo -o 12 --list      # Show the items of operation 12
+      aifo undo -o 12 -i 40       # Put back a single file from operation 12
+    """
+    journal = _operation_journal()
+
+    if operation_id is not None:
+        _undo_operation(journal, operation_id, item_id, show_list, preview, keep_both)
+        return
+    if item_id is not None:
+        print_error("--item needs --operation")
+        return
+
+    if not show_list:
+        _step_stack(journal, 'undo', steps, preview)
+        return
+
+    undo_items = journal.undo_stack(20)
+    redo_items = journal.redo_stack(20)
+    emit('undo', {'undo_stack': undo_items, 'redo_stack': redo_items})
+    if not undo_items and not redo_items:
+        print_info("Nothing to undo or redo.")
+        return
+    print_header("↩️  Undo / Redo")
+    for title, items in (("Redo (newest undone first)", redo_items), ("Undo (newest first)", undo_items)):
+        if not items:
+            continue
+        click.echo(f"\n{title}:")
+        for item in items:
+            when = item.get('undone_at') if title.startswith("Redo") else item.get('completed_at')
+            click.echo(f"  #{item['id']:<5} {when or item['created_at']}  {item['kind']:<16} "
+                       f"{item['step_count']:>4} step(s)  {item.get('description') or ''}")
+
+
+@cli.command()
+@click.option('--steps', '-n', default=1, show_default=True, help='Number of operations to redo')
+@click.option('--preview', '-p', is_flag=True, help='Show what would be redone')
+def redo(steps, preview):
+    """
+    Redo operations that were undone
+
+    Examples:
+      aifo redo                   # Redo the last undone operation
+      aifo redo -n 2              # Redo two operations
+    """
+    _step_stack(_operation_journal(), 'redo', steps, preview)
+
+
+@cli.command()
+@click.argument('path', type=click.Path(exists=True))
+@click.argument('new_name')
+@click.option('--rules', type=click.Choice(['portable', 'windows', 'posix', 'macos']), default='portable',
+              show_default=True, help='File systems the name must work on')
+@click.option('--preview', '-p', is_flag=True, help='Only check the name')
+def rename(path, new_name, rules, preview):
+    """
+    Rename a file or folder (undo with aifo undo)
+
+    The new name is checked first: characters Windows rejects, reserved
+    names such as CON or NUL, trailing dots or spaces and over-long names
+    are refused, with a suggested fix.
+
+    Examples:
+      aifo rename ~/Downloads/scan001.pdf "Lease 2024.pdf"
+      aifo rename ./notes.txt "a:b.txt" --rules posix
+    """
+    from src.config import get_config
+    from src.core.actions import ActionManager
+    from src.core.db_manager import DatabaseManager
+
+    result = ActionManager(get_config(), DatabaseManager(), dry_run=preview).rename_file(path, new_name, rules)
+    emit('rename', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        if result.get('suggestion'):
+            print_info(f"Try: {result['suggestion']}")
+        return EXIT_ERROR
+    print_success(result['message'])
+
+
+@cli.command()
+@click.argument('path', type=click.Path(), required=False)
+@click.option('--restore', '-r', type=int, help='Restore the version with this ID')
+@click.option('--to', 'target', type=click.Path(), help='Restore to this path instead of the original')
+def versions(path, restore, target):
+    """
+    List or restore versions kept when files were replaced
+
+    Examples:
+      aifo versions                         # Recently kept versions
+      aifo versions ~/Documents/report.pdf  # Versions of one file
+      aifo versions -r 12                   # Restore version 12 to its path
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.versions import VersionStore
+
+    store = VersionStore(get_config(), DatabaseManager())
+
+    if restore is not None:
+        result = store.restore_version(restore, target)
+        emit('versions', {'restore': result}, ok=result['success'])
+        if result['success']:
+            print_success(result['message'])
+        else:
+            print_error(result['message'])
+        return
+
+    items = store.list_versions(path, limit=50)
+    emit('versions', {'versions': items})
+    if not items:
+        print_info("No versions kept" + (f" for {path}." if path else " yet."))
+        return
+    print_header("🗂️  Versions")
+    for item in items:
+        size_kb = (item.get('file_size') or 0) / 1024
+        click.echo(f"  #{item['id']:<5} {item['created_at']}  {size_kb:>9.1f} KB  {item['file_path']}")
+        if item.get('reason'):
+            click.echo(f"          {item['reason']}")
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--at', 'at', required=True, help='Point in time, e.g. "last monday", "3 days ago", 2025-01-31')
+@click.option('--files', '-f', is_flag=True, help='List every file the folder contained')
+def history(folder, at, files):
+    """
+    Show what a folder looked like at a past point in time
+
+    Examples:
+      aifo history ~/Desktop --at "last monday"
+      aifo history ~/Downloads --at "2 days ago" --files
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.history import FolderHistory, parse_point_in_time
+
+    try:
+        when = parse_point_in_time(at)
+    except ValueError as e:
+        print_error(str(e))
+        return
+
+    result = FolderHistory(get_config(), DatabaseManager()).folder_as_of(folder, when, include_files=files)
+    emit('history', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        return
+
+    print_header("🕰️  Folder History")
+    click.echo(result['message'])
+    click.echo(f"Reconstructed from: {result['source']}")
+
+    diff = result['diff']
+    for title, items in (("Added since", diff['added']), ("Removed since", diff['removed'])):
+        if items:
+            click.echo(f"\n{title} ({len(items)}):")
+            for item in items[:30]:
+                click.echo(f"  {item}")
+            if len(items) > 30:
+                click.echo(f"  ... and {len(items) - 30} more")
+    if diff['moved']:
+        click.echo(f"\nMoved away since ({len(diff['moved'])}):")
+        for item in diff['moved'][:30]:
+            click.echo(f"  {item['path']} -> {item['now_at']}")
+
+    if files:
+        click.echo(f"\nFiles as of {at}:")
+        for item in result['files']:
+            click.echo(f"  {item}")
+
+
+@cli.command()
+@click.argument('name', required=False)
+@click.option('--root', '-r', 'roots', multiple=True, type=click.Path(), help='Set the roots (repeatable)')
+@click.option('--remove', is_flag=True, help='Delete the workspace definition')
+@click.option('--map', 'mappings', multiple=True, metavar='CATEGORY=FOLDER',
+              help='Where a category lands ("*" = everything else, FOLDER "@root" = in place, empty = unmap)')
+@click.option('--scan', 'do_scan', is_flag=True, help='Inventory every root')
+@click.option('--duplicates', '-d', is_flag=True, help='Find duplicates across all roots')
+@click.option('--organize', '-o', 'do_organize', is_flag=True, help='Organize every root as one run')
+@click.option('--preview', '-p', is_flag=True, help='With --organize, only show the plan')
+def workspace(name, roots, remove, mappings, do_scan, duplicates, do_organize, preview):
+    """
+    Work on several folders (and drives) as one workspace
+
+    Examples:
+      aifo workspace                                    # List workspaces
+      aifo workspace home -r ~/Desktop -r ~/Downloads -r E:/  # Define one
+      aifo workspace home --map Documents=D:/Docs --map Videos=//nas/Video
+      aifo workspace home --scan
+      aifo workspace home --duplicates
+      aifo workspace home --organize --preview
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.workspaces import WorkspaceManager
+
+    config = get_config()
+    db = DatabaseManager()
+    manager = WorkspaceManager(config, db)
+
+    if not name:
+        items = manager.list_workspaces()
+        emit('workspace', {'workspaces': items})
+        if not items:
+            print_info("No workspaces yet. Create one with: aifo workspace NAME -r FOLDER -r FOLDER")
+            return
+        print_header("🗃️  Workspaces")
+        for item in items:
+            click.echo(f"  {item['name']}" + (f" - {item['description']}" if item['description'] else ''))
+            for root in item['roots']:
+                click.echo(f"      {'✓' if root['available'] else '✗'} {root['path']}")
+            for category, target in sorted(item['destinations'].items()):
+                click.echo(f"      {category} → {target}")
+        return
+
+    destinations = {}
+    for mapping in mappings:
+        category, sep, target = mapping.partition('=')
+        if not sep or not category.strip():
+            print_error(f"Invalid mapping (expected CATEGORY=FOLDER): {mapping}")
+            return
+        destinations[category.strip()] = target.strip() or None
+
+    if roots:
+        result = manager.save_workspace(name, list(roots))
+        (print_success if result['success'] else print_error)(result['message'])
+        if not result['success']:
+            return
+    elif remove:
+        if manager.delete_workspace(name):
+            print_success(f"Workspace '{name}' removed")
+        else:
+            print_error(f"Workspace '{name}' not found")
+        return
+
+    if destinations:
+        result = manager.set_destinations(name, destinations)
+        (print_success if result['success'] else print_error)(result['message'])
+        if not result['success']:
+            return
+
+    if manager.get_workspace(name) is None:
+        print_error(f"Workspace '{name}' not found")
+        return
+    report = {'workspace': manager.get_workspace(name)}
+
+    def show_progress(event):
+        if event.get('done') or event.get('processed') == event.get('total'):
+            state = 'unavailable' if event.get('available') is False else f"{event['processed']} file(s)"
+            click.echo(f"  [{event['root_index'] + 1}/{event['root_count']}] {event['root']}: {state}")
+
+    if do_scan:
+        print_header(f"🔍 Scanning workspace '{name}'")
+        result = manager.scan(name, show_progress)
+        report['scan'] = result
+        click.echo(f"\nTotal: {result['total_files']} files, {result['total_size'] / (1024 ** 3):.2f} GB")
+
+    if duplicates:
+        from src.core.duplicates import DuplicateFinder
+
+        print_header(f"🔁 Duplicates across '{name}'")
+        manager.duplicate_finder = DuplicateFinder(config, db)
+        groups = manager.find_duplicates(name, show_progress)
+        cross = [g for g in groups if g['cross_root']]
+        summary = manager.duplicate_finder.get_duplicate_summary(groups)
+        report['duplicates'] = {'groups': groups, 'summary': summary}
+        click.echo(f"\n{len(groups)} duplicate group(s), {len(cross)} spanning several roots")
+        click.echo(f"Wasted space: {summary.get('total_wasted_space_mb', 0):.1f} MB")
+
+    if do_organize:
+        from src.cli.organizer import Organizer
+
+        org = Organizer()
+        manager.classifier, manager.action_manager = org.classifier, org.action_manager
+        print_header(f"🗂️  Planning workspace '{name}'")
+        plan = manager.plan_organize(name, show_progress)
+        report['organize'] = {'plan': plan, 'result': None}
+        for root, counts in plan['per_root'].items():
+            click.echo(f"  {root}: {counts['planned']} to organize, {counts['unplanned']} left alone")
+        for destination, count in sorted(plan['per_destination'].items()):
+            click.echo(f"  → {destination}: {count} file(s)")
+        if plan['held']:
+            offline = sorted({h['destination_root'] for h in plan['held']})
+            print_warning(f"{len(plan['held'])} file(s) held back, destination offline: {', '.join(offline)}")
+        if not preview and plan['items']:
+            if confirm_action(f"\nOrganize {len(plan['items'])} files across all roots?", default=True):
+                result = manager.apply_plan(plan)
+                report['organize']['result'] = result
+                (print_success if result['success'] else print_warning)(result['message'])
+                print_info("Undo this run with: aifo undo")
+            else:
+                print_info("Cancelled.")
+
+    result = (report.get('organize') or {}).get('result')
+    emit('workspace', report, ok=result is None or result['success'])
+    if result:
+        counts = result['per_root'].values()
+        return outcome_exit_code(sum(c['moved'] for c in counts), sum(c['failed'] for c in counts))
+
+
+@cli.command()
+@click.argument('rule_name', required=False)
+@click.option('--from', '-f', 'sources', multiple=True, type=click.Path(exists=True), help='Folder to gather from (repeatable)')
+@click.option('--workspace', '-w', help='Gather from every root of a workspace')
+@click.option('--keyword', '-k', 'keywords', multiple=True, help='Match names/paths containing this (repeatable)')
+@click.option('--ext', '-e', 'extensions', multiple=True, help='Match this extension (repeatable)')
+@click.option('--category', '-c', 'categories', multiple=True, help='Match this classifier category (repeatable)')
+@click.option('--to', 'destination', help='Destination template, e.g. "Finance/Taxes/{year}"')
+@click.option('--preview', '-p', is_flag=True, help='Only show the plan')
+def consolidate(rule_name, sources, workspace, keywords, extensions, categories, destination, preview):
+    """
+    Gather matching files from many folders into one destination tree
+
+    Examples:
+      aifo consolidate                                   # List saved rules
+      aifo consolidate taxes                             # Run a saved rule
+      aifo consolidate -f ~/Desktop -f ~/Downloads -k tax -e pdf --to "Finance/Taxes/{year}"
+      aifo consolidate -w home -k invoice --to "Finance/Invoices/{year}" --preview
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.consolidation import ConsolidationPlanner, ConsolidationRule
+
+    config = get_config()
+    planner = ConsolidationPlanner(config, DatabaseManager())
+
+    if rule_name:
+        rule = planner.get_rule(rule_name)
+        if rule is None:
+            print_error(f"Consolidation rule '{rule_name}' not found")
+            return
+    elif destination:
+        rule = ConsolidationRule.from_dict({
+            'name': 'ad hoc', 'sources': list(sources), 'workspace': workspace,
+            'keywords': list(keywords), 'extensions': list(extensions),
+            'categories': list(categories), 'destination': destination
+        })
+    else:
+        rules = planner.saved_rules()
+        emit('consolidate', {'rules': [vars(rule) for rule in rules]})
+        if not rules:
+            print_info('No saved rules. Run one directly with: aifo consolidate -f FOLDER -k WORD --to "Dest/{year}"')
+            return
+        print_header("🧲 Consolidation rules")
+        for rule in rules:
+            click.echo(f"  {rule.name}: -> {rule.destination}")
+        return
+
+    if rule.categories or not preview:
+        from src.cli.organizer import Organizer
+
+        org = Organizer()
+        planner.classifier, planner.action_manager = org.classifier, org.action_manager
+
+    try:
+        plan = planner.plan(rule)
+    except KeyError as e:
+        print_error(str(e))
+        return
+    if not plan['success']:
+        print_error(plan['message'])
+        return
+
+    print_header(f"🧲 Consolidating '{rule.name}'")
+    for folder, count in sorted(plan['per_destination'].items()):
+        click.echo(f"  {folder}: {count} file(s)")
+    occupied = sum(1 for item in plan['items'] if item['occupied'])
+    if occupied:
+        print_warning(f"{occupied} file(s) already have a namesake at the destination")
+    print_info(plan['message'])
+    if preview or not plan['items']:
+        emit('consolidate', {'rule': vars(rule), 'plan': plan, 'result': None})
+        return
+    if not confirm_action(f"\nMove {len(plan['items'])} files?", default=True):
+        print_info("Cancelled.")
+        emit('consolidate', {'rule': vars(rule), 'plan': plan, 'result': None})
+        return
+
+    result = planner.apply(plan)
+    emit('consolidate', {'rule': vars(rule), 'plan': plan, 'result': result}, ok=result['success'])
+    (print_success if result['success'] else print_warning)(result['message'])
+    print_info("Undo this run with: aifo undo")
+    return outcome_exit_code(len(result.get('moved', [])), len(result.get('failed', [])))
+
+
+@cli.command()
+@click.argument('folder_a', type=click.Path(exists=True, file_okay=False))
+@click.argument('folder_b', type=click.Path(exists=True, file_okay=False))
+@click.option('--copy-missing', '-m', is_flag=True, help='Copy files missing from the other side')
+@click.option('--keep-newer', '-n', is_flag=True, help='Overwrite older conflicting copies with newer ones')
+@click.option('--both', 'both_ways', is_flag=True, help='Merge in both directions (default: A into B)')
+@click.option('--preview', '-p', is_flag=True, help='Only show what would be copied')
+@click.option('--list', '-l', 'show_files', is_flag=True, help='List the files in each group')
+def compare(folder_a, folder_b, copy_missing, keep_newer, both_ways, preview, show_files):
+    """
+    Compare two folders by content and optionally merge them
+
+    Examples:
+      aifo compare E:/Backup2019 E:/Backup2021            # Show differences
+      aifo compare OLD NEW --copy-missing --preview
+      aifo compare OLD NEW --copy-missing --keep-newer --both
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.folder_compare import FolderComparer
+
+    comparer = FolderComparer(get_config(), DatabaseManager())
+
+    print_header(f"🔀 Comparing {folder_a} and {folder_b}")
+    result = comparer.compare_folders(folder_a, folder_b)
+    if not result['success']:
+        print_error(result['message'])
+        return
+
+    summary = result['summary']
+    click.echo(f"  Identical:    {summary['identical']}")
+    click.echo(f"  Only in A:    {summary['only_in_a']}")
+    click.echo(f"  Only in B:    {summary['only_in_b']}")
+    click.echo(f"  Conflicting:  {summary['conflicting']}")
+    if show_files:
+        for label, paths in (('Only in A', result['only_in_a']), ('Only in B', result['only_in_b'])):
+            if paths:
+                click.echo(f"\n{label}:")
+                for path in paths:
+                    click.echo(f"  {path}")
+        if result['conflicting']:
+            click.echo("\nConflicting (newer side):")
+            for conflict in result['conflicting']:
+                click.echo(f"  {conflict['path']} ({conflict['newer']})")
+
+    actions = [name for name, wanted in (('copy_missing', copy_missing), ('keep_newer', keep_newer)) if wanted]
+    if not actions:
+        emit('compare', {'comparison': result, 'merge_plan': None, 'merge': None})
+        return
+
+    direction = 'both' if both_ways else 'a_to_b'
+    plan = comparer.merge(folder_a, folder_b, actions, direction, dry_run=True)
+    print_info(plan['message'])
+    if preview or not plan['copies']:
+        emit('compare', {'comparison': result, 'merge_plan': plan, 'merge': None})
+        return
+    if not confirm_action(f"\nCopy {len(plan['copies'])} files?", default=True):
+        print_info("Cancelled.")
+        emit('compare', {'comparison': result, 'merge_plan': plan, 'merge': None})
+        return
+
+    merged = comparer.merge(folder_a, folder_b, actions, direction)
+    emit('compare', {'comparison': result, 'merge_plan': plan, 'merge': merged}, ok=merged['success'])
+    (print_success if merged['success'] else print_warning)(merged['message'])
+    print_info("Undo this merge with: aifo undo")
+    return outcome_exit_code(len(merged.get('copies', [])), len(merged.get('failed', [])))
+
+
+@cli.command()
+@click.argument('path', required=False, type=click.Path())
+@click.option('--label', '-l', help='Display label for the pin')
+@click.option('--remove', is_flag=True, help='Unpin the folder')
+@click.option('--refresh', '-r', is_flag=True, help='Recompute quick stats now')
+def pin(path, label, remove, refresh):
+    """
+    Pin favorite folders as one-click targets
+
+    Examples:
+      aifo pin                         # List pins with quick stats
+      aifo pin ~/Downloads             # Pin a folder
+      aifo pin ~/Desktop -l "Desk"     # Pin with a label
+      aifo pin ~/Desktop --remove
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.pins import PinnedFolders
+
+    pins = PinnedFolders(get_config(), DatabaseManager())
+
+    if path and remove:
+        if pins.unpin(path):
+            print_success(f"Unpinned {path}")
+        else:
+            print_error(f"Not pinned: {path}")
+        return
+    if path:
+        result = pins.pin(path, label)
+        (print_success if result['success'] else print_error)(result['message'])
+        if not result['success']:
+            return
+    elif refresh:
+        pins.refresh()
+
+    items = pins.list()
+    emit('pin', {'pins': items})
+    if not items:
+        print_info("No pinned folders yet. Pin one with: aifo pin FOLDER")
+        return
+    print_header("📌 Pinned folders")
+    for item in items:
+        if not item['available']:
+            click.echo(f"  ✗ {item['label']:<20} {item['path']} (not available)")
+            continue
+        last_run = item['last_run'] or 'never'
+        click.echo(f"  {item['label']:<20} {item['item_count']} items, "
+                   f"{item['unorganized_count']} unorganized, last run: {last_run}")
+
+
+@cli.command()
+@click.argument('name', required=False)
+@click.option('--add', '-a', 'add_paths', multiple=True, type=click.Path(exists=True), help='Add a file or folder (repeatable)')
+@click.option('--remove', '-r', 'remove_path', help='Remove a file (path or content hash)')
+@click.option('--rule-folder', multiple=True, type=click.Path(exists=True, file_okay=False),
+              help='Create with a rule over this folder (repeatable)')
+@click.option('--ext', '-e', 'extensions', multiple=True, help='Rule: match this extension (repeatable)')
+@click.option('--keyword', '-k', 'keywords', multiple=True, help='Rule: match names containing this (repeatable)')
+@click.option('--populate', is_flag=True, help="Re-run the collection's rule")
+@click.option('--export', 'export_to', type=click.Path(), help='Export to this file or folder')
+@click.option('--format', 'export_format', type=click.Choice(['json', 'csv', 'm3u', 'folder']), default='json',
+              help='Export format')
+@click.option('--delete', is_flag=True, help='Delete the collection (files are kept)')
+def collection(name, add_paths, remove_path, rule_folder, extensions, keywords, populate,
+               export_to, export_format, delete):
+    """
+    Group files into virtual collections without moving them
+
+    Examples:
+      aifo collection                                    # List collections
+      aifo collection "Tax 2024" -a ~/Desktop/return.pdf -a ~/Mail/receipts
+      aifo collection Music --rule-folder ~/Downloads -e mp3 -e flac
+      aifo collection Music --export music.m3u --format m3u
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.file_collections import CollectionManager
+
+    manager = CollectionManager(get_config(), DatabaseManager())
+
+    if not name:
+        items = manager.list()
+        emit('collection', {'collections': items})
+        if not items:
+            print_info("No collections yet. Create one with: aifo collection NAME --add FILE")
+            return
+        print_header("🗂️  Collections")
+        for item in items:
+            rule = ' (rule)' if item['rule'] else ''
+            click.echo(f"  {item['name']}: {item['item_count']} file(s){rule}")
+        return
+
+    if delete:
+        if manager.delete(name):
+            print_success(f"Collection '{name}' deleted; files were not touched")
+        else:
+            print_error(f"Collection '{name}' not found")
+        return
+
+    if manager.get(name) is None:
+        rule = None
+        if rule_folder:
+            rule = {'folders': list(rule_folder), 'extensions': list(extensions), 'keywords': list(keywords)}
+        result = manager.create(name, rule=rule)
+        (print_success if result['success'] else print_error)(result['message'])
+        if not result['success']:
+            return
+    elif populate:
+        print_success(f"Added {manager.populate(name)} new file(s)")
+
+    if add_paths:
+        print_success(manager.add(name, list(add_paths))['message'])
+    if remove_path:
+        if manager.remove(name, remove_path):
+            print_success(f"Removed {remove_path}")
+        else:
+            print_error(f"Not in '{name}': {remove_path}")
+
+    if export_to:
+        result = manager.export(name, export_to, export_format)
+        (print_success if result['success'] else print_error)(result['message'])
+        emit('collection', {'collection': manager.get(name), 'export': result}, ok=result['success'])
+        return
+
+    items = manager.items(name)
+    emit('collection', {'collection': manager.get(name), 'items': items})
+    print_header(f"🗂️  {name} ({len(items)} files)")
+    for item in items:
+        click.echo(f"  {'✓' if item['available'] else '✗'} {item['path']}")
+
+
+@cli.command()
+@click.option('--scan', '-s', 'scan_folder', type=click.Path(exists=True, file_okay=False),
+              help='Find faces in the photos below this folder')
+@click.option('--rescan', is_flag=True, help='With --scan: process already scanned photos again')
+@click.option('--rename', nargs=2, type=(int, str), metavar='ID NAME', help='Name a person')
+@click.option('--merge', nargs=2, type=(int, int), metavar='KEEP OTHER',
+              help='Merge OTHER into KEEP (the same person found twice)')
+@click.option('--sync', 'sync', is_flag=True, help='Create or refresh a "People: NAME" collection per person')
+def people(scan_folder, rescan, rename, merge, sync):
+    """
+    Group photos by the people in them, entirely offline
+
+    Needs local face models (faces.detector_model / faces.embedding_model
+    in config.json) and the optional onnxruntime, numpy and pillow packages.
+
+    Examples:
+      aifo people --scan ~/Pictures --sync    # Find faces, then make collections
+      aifo people                             # List people found
+      aifo people --rename 3 "Grandma"
+      aifo people --merge 3 7                 # Person 7 is also Grandma
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.faces import FaceClusterer
+
+    clusterer = FaceClusterer(get_config(), DatabaseManager())
+    results = []
+
+    if scan_folder:
+        print_info(f"Looking for faces in {scan_folder}...")
+        result = clusterer.scan(scan_folder, rescan=rescan)
+        if not result['success']:
+            emit('people', {'people': [], 'results': [result]}, ok=False)
+            print_error(result['message'])
+            return EXIT_ERROR
+        print_success(result['message'])
+        for failure in result['failed']:
+            print_warning(f"{failure['path']}: {failure['message']}")
+        results.append(result)
+
+    actions = []
+    if merge:
+        actions.append(clusterer.merge_people(*merge))
+    if rename:
+        actions.append(clusterer.rename_person(*rename))
+    if sync:
+        actions.append(clusterer.sync_collections())
+    for result in actions:
+        (print_success if result['success'] else print_error)(result['message'])
+    results.extend(actions)
+
+    found = clusterer.people()
+    ok = all(result['success'] for result in results)
+    emit('people', {'people': found, 'results': results}, ok=ok)
+    if not found:
+        print_info("No people yet. Find faces with: aifo people --scan FOLDER")
+    else:
+        print_header(f"👪 People ({len(found)})")
+        for person in found:
+            click.echo(f"  #{person['id']:<4} {person['display_name']}: "
+                       f"{person['photo_count']} photo(s), {person['face_count']} face(s)")
+    if not ok:
+        return EXIT_ERROR
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--archive', 'action', flag_value='archive', help='Keep the best shot of each group, archive the rest')
+@click.option('--trash', 'action', flag_value='trash', help='Keep the best shot of each group, trash the rest')
+@click.option('--archive-to', type=click.Path(file_okay=False), help='With --archive: where the extra shots go')
+@click.option('--preview', '-p', is_flag=True, help='Only show what would be archived or trashed')
+def bursts(folder, action, archive_to, preview):
+    """
+    Find photo bursts and near-identical shots
+
+    Shows each group with the suggested best shot (sharpest, or largest
+    file without Pillow) and can archive or trash the others in one go.
+
+    Examples:
+      aifo bursts ~/Pictures/2024              # Show groups
+      aifo bursts ~/Pictures/2024 --archive    # Keep the best, archive the rest
+      aifo bursts ~/Pictures/2024 --trash --preview
+    """
+    from src.config import get_config
+    from src.core.bursts import BurstDetector
+
+    detector = BurstDetector(get_config())
+    found = detector.find_groups(folder)
+    if not found['success']:
+        emit('bursts', {'groups': [], 'resolution': None}, ok=False)
+        print_error(found['message'])
+        return EXIT_ERROR
+
+    print_header(f"📸 Bursts in {folder}")
+    for group in found['groups']:
+        click.echo(f"\n  {group['kind'].capitalize()} #{group['id']}, {group['start'][:19]} ({len(group['photos'])} shots)")
+        for photo in group['photos']:
+            mark = '★' if photo['path'] == group['best'] else ' '
+            click.echo(f"   {mark} {photo['path']}")
+    print_info(found['message'])
+
+    if not action or not found['groups']:
+        emit('bursts', {'groups': found['groups'], 'resolution': None})
+        return
+
+    plan = detector.resolve(found['groups'], action, archive_to, dry_run=True)
+    print_info(plan['message'])
+    if preview or not confirm_action(f"\n{action.capitalize()} {len(plan['removed'])} photos?", default=True):
+        if not preview:
+            print_info("Cancelled.")
+        emit('bursts', {'groups': found['groups'], 'resolution': plan})
+        return
+
+    from src.cli.organizer import Organizer
+
+    detector.action_manager = Organizer().action_manager
+    result = detector.resolve(found['groups'], action, archive_to)
+    emit('bursts', {'groups': found['groups'], 'resolution': result}, ok=result['success'])
+    (print_success if result['success'] else print_warning)(result['message'])
+    for failure in result['failed']:
+        print_warning(f"{failure['path']}: {failure['message']}")
+    print_info("Undo this with: aifo undo")
+    return outcome_exit_code(len(result['removed']), len(result['failed']))
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--no-recursive', is_flag=True, help='Only check the folder itself')
+def music(folder, no_recursive):
+    """
+    Preview tag fixes for a music folder
+
+    Lists missing or inconsistent tags (titles, track numbers, artists,
+    albums, album artists, compilation flags) with the value the music
+    strategy would use. Nothing is written to the files.
+
+    Examples:
+      aifo music ~/Music/Incoming                    # Preview tag fixes
+      aifo organize ~/Music/Incoming -s music -p     # Preview Artist/Album layout
+    """
+    from src.config import get_config
+    from src.core.music import MusicLibraryStrategy
+
+    result = MusicLibraryStrategy(get_config()).tag_fixups(folder, recursive=not no_recursive)
+    emit('music', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        return EXIT_ERROR
+
+    print_header(f"🎵 Tags in {folder}")
+    current = None
+    for fixup in result['fixups']:
+        if fixup['path'] != current:
+            current = fixup['path']
+            click.echo(f"\n  {current}")
+        click.echo(f"    {fixup['field']}: {fixup['current']!r} → {fixup['suggested']!r}  ({fixup['reason']})")
+    print_info(result['message'])
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--no-recursive', is_flag=True, help='Only check the folder itself')
+def ebooks(folder, no_recursive):
+    """
+    Find ebooks present more than once
+
+    Matches books by ISBN, or by author and title, and tells identical
+    copies apart from the same book in other formats or editions.
+
+    Examples:
+      aifo ebooks ~/Books                          # Report duplicate editions
+      aifo organize ~/Downloads -s ebooks -p       # Preview Author/Title layout
+    """
+    from src.config import get_config
+    from src.core.ebooks import EbookLibraryStrategy
+
+    result = EbookLibraryStrategy(get_config()).find_editions(folder, recursive=not no_recursive)
+    emit('ebooks', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        return EXIT_ERROR
+
+    print_header(f"📚 Ebooks in {folder}")
+    for group in result['groups']:
+        click.echo(f"\n  {group['title']} by {group['author'] or 'unknown author'} ({group['kind']})")
+        for book in group['files']:
+            isbn = f"  ISBN {book['isbn']}" if book['isbn'] else ''
+            click.echo(f"    {book['path']}  [{book['format']}, {format_size(book['size'])}]{isbn}")
+    print_info(result['message'])
+
+
+@cli.command('leave-alone')
+@click.argument('folder', required=False, type=click.Path(exists=True, file_okay=False))
+@click.option('--add', 'add_path', type=click.Path(file_okay=False), help='Always leave this folder alone')
+@click.option('--allow', 'allow_path', type=click.Path(file_okay=False),
+              help='Organize this folder even if it looks like it should be left alone')
+@click.option('--remove', 'remove_path', type=click.Path(file_okay=False), help='Take a folder off both lists')
+def leave_alone(folder, add_path, allow_path, remove_path):
+    """
+    Show or change the folders organizing never touches
+
+    Code repositories, app bundles, game installs and sync roots (Dropbox,
+    OneDrive, ...) are detected automatically; the lists override that.
+
+    Examples:
+      aifo leave-alone ~/Documents             # What is left alone there
+      aifo leave-alone --add ~/Work/Scans      # Never organize a folder
+      aifo leave-alone --allow ~/Dropbox/Inbox # Organize a detected folder
+    """
+    from src.config import get_config
+    from src.core.leave_alone import LeaveAloneDetector
+
+    detector = LeaveAloneDetector(get_config())
+    for path, mode in ((add_path, 'always'), (allow_path, 'allow'), (remove_path, None)):
+        if path:
+            result = detector.set_override(path, mode)
+            if not result['success']:
+                emit('leave-alone', result, ok=False)
+                print_error(result['message'])
+                return EXIT_ERROR
+            print_success(result['message'])
+
+    result = detector.find(folder) if folder else {
+        'success': True, 'message': '', 'folders': [],
+        'always': [str(p) for p in detector.always], 'allow': [str(p) for p in detector.allow]}
+    emit('leave-alone', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        return EXIT_ERROR
+    if folder:
+        print_header(f"🚧 Left alone in {folder}")
+        for entry in result['folders']:
+            click.echo(f"  {entry['path']}  ({entry['reason']})")
+        print_info(result['message'])
+    for title, paths in (("Always left alone", result['always']), ("Organized anyway", result['allow'])):
+        if paths:
+            click.echo(f"\n  {title}:")
+            for path in paths:
+                click.echo(f"    {path}")
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--csv', 'csv_path', type=click.Path(dir_okay=False), help='Write the fields to a CSV file')
+@click.option('--no-recursive', is_flag=True, help='Only check the folder itself')
+def invoices(folder, csv_path, no_recursive):
+    """
+    Read vendor, date and total of the invoices and receipts in a folder
+
+    The same fields name invoices when organizing, following the
+    invoices.template setting (Finance/{year}/{year}-{month} {vendor} {amount}).
+
+    Examples:
+      aifo invoices ~/Documents/Receipts                      # Show the fields
+      aifo invoices ~/Documents --csv ~/expenses-2024.csv     # Export them
+    """
+    from src.config import get_config
+    from src.core.invoices import InvoiceExtractor
+
+    extractor = InvoiceExtractor(get_config())
+    result = extractor.scan(folder, recursive=not no_recursive)
+    if result['success'] and csv_path:
+        try:
+            result['csv'] = extractor.export_csv(result['rows'], csv_path)
+        except OSError as e:
+            result.update(success=False, message=f"Cannot write {csv_path}: {e}")
+    emit('invoices', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        return EXIT_ERROR
+
+    print_header(f"🧾 Invoices in {folder}")
+    for row in result['rows']:
+        amount = f"{row['amount']:.2f} {row['currency'] or ''}".strip() if row['amount'] is not None else '?'
+        click.echo(f"  {row['date'] or '????-??-??'}  {row['vendor'] or 'unknown vendor':<24} {amount:>14}  "
+                   f"{Path(row['path']).name}")
+    print_info(result['message'])
+    if result.get('csv'):
+        print_success(f"Fields written to {result['csv']}")
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--install', is_flag=True, help='Install the fonts for the current user (newest version of each)')
+@click.option('--no-recursive', is_flag=True, help='Only check the folder itself')
+def fonts(folder, install, no_recursive):
+    """
+    Find fonts present in several copies or versions
+
+    Fonts are matched by the family and style in their name table.
+
+    Examples:
+      aifo fonts ~/Downloads/Fonts                 # Report duplicates
+      aifo fonts ~/Downloads/Fonts --install       # Install them for you
+      aifo organize ~/Downloads -s fonts -p        # Preview Fonts/<Family> layout
+    """
+    from src.config import get_config
+    from src.core.fonts import FontLibraryStrategy, FONT_EXTENSIONS
+
+    strategy = FontLibraryStrategy(get_config())
+    result = strategy.find_duplicates(folder, recursive=not no_recursive)
+    if not result['success']:
+        emit('fonts', result, ok=False)
+        print_error(result['message'])
+        return EXIT_ERROR
+    if install:
+        older = {f['path'] for g in result['groups'] for f in g['files'] if f['path'] != g['newest']}
+        pattern = '*' if no_recursive else '**/*'
+        result['installed'] = [strategy.install(str(p)) for p in sorted(Path(folder).glob(pattern))
+                               if p.suffix.lower() in FONT_EXTENSIONS and p.is_file() and str(p) not in older]
+        result['success'] = all(r['success'] for r in result['installed'])
+    emit('fonts', result, ok=result['success'])
+
+    print_header(f"🔤 Fonts in {folder}")
+    for group in result['groups']:
+        click.echo(f"\n  {group['family']} {group['style']} ({group['kind']})")
+        for font in group['files']:
+            marker = '  ← newest' if group['kind'] == 'versions' and font['path'] == group['newest'] else ''
+            click.echo(f"    {font['path']}  [{font['version'] or 'no version'}, {format_size(font['size'])}]{marker}")
+    print_info(result['message'])
+    for installed in result.get('installed', []):
+        (print_success if installed['success'] else print_error)(installed['message'])
+    if not result['success']:
+        return EXIT_ERROR
+
+
+@cli.command()
+@click.argument('paths', nargs=-1, required=True, type=click.Path())
+@click.option('--offline', is_flag=True, help='Only use local rules (extension, MIME type, file name)')
+@click.option('--deep', is_flag=True, help='Ask the engine for deep AI analysis')
+@click.option('--jobs', 'max_concurrent', type=click.IntRange(1, 16), default=4, show_default=True,
+              help='Files the engine classifies at once')
+def classify(paths, offline, deep, max_concurrent):
+    """
+    Classify files, falling back to local rules when the engine is not running
+
+    Examples:
+      aifo classify ~/Downloads/report.pdf
+      aifo classify --offline *.png     # Never contact the engine
+    """
+    from src.config import get_config
+    from src.core.control import ControlClient, ControlError, UNAVAILABLE, default_address
+    from src.core.local_rules import classify_file_offline
+
+    config = get_config()
+    paths = [str(Path(path).expanduser().resolve()) for path in paths]
+    results = {}
+    if not offline:
+        try:
+            # AI classification can take seconds per file
+            with ControlClient(default_address(config), timeout=max(30.0, 5.0 * len(paths))) as client:
+                outcomes = client.call('classify_files', paths=paths, max_concurrent=max_concurrent, deep=deep)
+            for outcome in outcomes:
+                results[outcome['path']] = outcome['result'] if outcome['ok'] else {
+                    'suggested_path': None, 'confidence': None, 'method': 'failed', 'reason': outcome['error']}
+        except ControlError as e:
+            if e.code != UNAVAILABLE:
+                print_error(str(e))
+                return EXIT_ERROR
+            print_warning("Engine not running; classifying with local rules only")
+            results = {}
+    for path in paths:
+        if path not in results:
+            results[path] = classify_file_offline(path, config)
+
+    emit('classify', {'results': [{'path': path, **result} for path, result in results.items()]})
+    for path, result in results.items():
+        click.echo(f"  {Path(path).name} -> {result.get('suggested_path')} "
+                   f"({result.get('confidence')}, {result.get('model_used') or result.get('method')})")
+        click.echo(f"      {result.get('reason')}")
+
+
+@cli.command()
+@click.argument('method', default='status')
+@click.option('--param', '-p', 'params', multiple=True, metavar='KEY=VALUE',
+              help='Method parameter (repeatable; VALUE is parsed as JSON when possible)')
+def daemon(method, params):
+    """
+    Call a method on the running engine over its control socket
+
+    Examples:
+      aifo daemon                         # Engine status
+      aifo daemon methods                 # What the engine can do
+      aifo daemon watcher.start
+      aifo daemon plan -p folder=~/Downloads
+      aifo daemon shutdown
+    """
+    import json
+    from src.config import get_config
+    from src.core.control import ControlClient, ControlError, UNAVAILABLE, default_address
+
+    arguments = {}
+    for param in params:
+        key, sep, value = param.partition('=')
+        if not sep or not key.strip():
+            raise click.UsageError(f"Invalid parameter (expected KEY=VALUE): {param}")
+        try:
+            arguments[key.strip()] = json.loads(value)
+        except ValueError:
+            arguments[key.strip()] = value
+    if isinstance(arguments.get('folder'), str):
+        arguments['folder'] = str(Path(arguments['folder']).expanduser())
+
+    address = default_address(get_config())
+    try:
+        with ControlClient(address) as client:
+            result = client.call(method, **arguments)
+    except ControlError as e:
+        print_error(str(e))
+        if e.code != UNAVAILABLE:
+            return EXIT_ERROR
+        print_info("Start one with: python -m src.main daemon")
+        return EXIT_BACKEND_UNAVAILABLE
+
+    emit('daemon', result)
+    click.echo(json.dumps(result, indent=2, default=str))
+
+
+@cli.command()
+@click.option('--cancel', 'cancel_id', metavar='JOB_ID', help='Stop this job after the file it is moving')
+@click.option('--running', is_flag=True, help='Only jobs still running')
+def jobs(cancel_id, running):
+    """
+    List the running engine's organize jobs, or cancel one
+
+    Examples:
+      aifo daemon jobs.start -p folder=~/Downloads   # Start a job
+      aifo jobs                                      # What is running
+      aifo jobs --cancel 3f2a...                     # Stop it
+    """
+    from src.config import get_config
+    from src.core.control import ControlClient, ControlError, UNAVAILABLE, default_address
+
+    try:
+        with ControlClient(default_address(get_config())) as client:
+            if cancel_id:
+                result = client.call('jobs.cancel', job_id=cancel_id)
+                emit('jobs', result, ok=result['success'])
+                if not result['success']:
+                    print_error(result['message'])
+                    return EXIT_ERROR
+                print_success(result['message'])
+                return None
+            listed = client.call('jobs.list', include_finished=not running)
+    except ControlError as e:
+        print_error(str(e))
+        return EXIT_BACKEND_UNAVAILABLE if e.code == UNAVAILABLE else EXIT_ERROR
+
+    emit('jobs', {'jobs': listed})
+    if not listed:
+        print_info("No jobs")
+    for job in listed:
+        progress = job['progress']
+        where = f"{progress['phase']} {progress['done']}/{progress['total']}" if job['state'] == 'running' else ''
+        click.echo(f"  {job['id']}  {job['state']:<10} {where:<20} {job['folder']}")
+        if job['message'] and job['state'] != 'running':
+            click.echo(f"      {job['message']}")
+
+
+@cli.command('log-level')
+@click.argument('level', required=False,
+                type=click.Choice(['DEBUG', 'INFO', 'WARNING', 'ERROR', 'CRITICAL'], case_sensitive=False))
+@click.option('--logger', '-l', 'logger_name', help='Only this logger (e.g. src.core.plans)')
+def log_level(level, logger_name):
+    """
+    Show or change log levels of the running app, without restarting it
+
+    Examples:
+      aifo log-level                        # Current levels
+      aifo log-level DEBUG                  # Everything, for debugging
+      aifo log-level DEBUG -l src.core.control
+      aifo log-level INFO                   # Back to normal
+    """
+    from src.config import get_config
+    from src.core.control import ControlClient, ControlError, UNAVAILABLE, default_address
+
+    try:
+        with ControlClient(default_address(get_config())) as client:
+            result = client.call('log_level', level=level, logger=logger_name)
+    except ControlError as e:
+        print_error(str(e))
+        return EXIT_BACKEND_UNAVAILABLE if e.code == UNAVAILABLE else EXIT_ERROR
+
+    emit('log-level', result)
+    if result['changed']:
+        changed = result['changed']
+        print_success(f"{changed['logger']}: {changed['previous']} -> {changed['level']}")
+    for name, value in result['levels'].items():
+        click.echo(f"  {name:<30} {value}")
+
+
+@cli.command()
+@click.option('--follow', '-f', is_flag=True, help='Keep printing new records until Ctrl+C')
+@click.option('--level', type=click.Choice(['DEBUG', 'INFO', 'WARNING', 'ERROR'], case_sensitive=False),
+              default='INFO', show_default=True, help='Minimum level')
+@click.option('--module', '-m', help='Only this module and below (e.g. src.core)')
+@click.option('--lines', '-n', default=50, show_default=True, help='Recent records to show first')
+def logs(follow, level, module, lines):
+    """
+    Show the running app's live log, e.g. to see why a job is stuck
+
+    Examples:
+      aifo logs                          # Recent records
+      aifo logs -f --level DEBUG -m src.core.plans
+    """
+    from src.config import get_config
+    from src.core.control import ControlClient, ControlError, UNAVAILABLE, MAX_TAIL_WAIT, default_address
+
+    shown = []
+    try:
+        with ControlClient(default_address(get_config()), timeout=MAX_TAIL_WAIT + 10) as client:
+            page = client.call('logs.tail', level=level, module=module, limit=lines)
+            while True:
+                for record in page['records']:
+                    shown.append(record)
+                    where = f" [{record['span']}]" if record.get('span') else ''
+                    click.echo(f"{record['timestamp'][11:19]} {record['level']:<8} {record['logger']}{where}: "
+                               f"{record['message']}")
+                if not follow:
+                    break
+                page = client.call('logs.tail', after=page['cursor'], level=level, module=module,
+                                   wait=MAX_TAIL_WAIT)
+    except KeyboardInterrupt:
+        pass
+    except ControlError as e:
+        print_error(str(e))
+        return EXIT_BACKEND_UNAVAILABLE if e.code == UNAVAILABLE else EXIT_ERROR
+    emit('logs', {'records': shown})
+
+
+@cli.command('crash-reports')
+@click.option('--show', 'show_id', metavar='ID', help='Print a report exactly as it would be sent')
+@click.option('--send', 'send_id', metavar='ID', help='Send a report to the developers')
+@click.option('--delete', 'delete_id', metavar='ID', help='Delete a report')
+def crash_reports(show_id, send_id, delete_id):
+    """
+    Review and send crash reports saved on this machine
+
+    Reports are only recorded when crash_reports.enabled is set, and file
+    paths are removed unless crash_reports.include_paths is set.
+
+    Examples:
+      aifo crash-reports                  # List saved reports
+      aifo crash-reports --show ID
+      aifo crash-reports --send ID
+    """
+    import json
+    from src.config import get_config
+    from src.core.crash_reports import CrashReporter
+
+    reporter = CrashReporter(get_config())
+    if show_id:
+        report = reporter.get_report(show_id)
+        if report is None:
+            print_error(f"Crash report not found: {show_id}")
+            return
+        emit('crash-reports', {'report': report})
+        click.echo(json.dumps(report, indent=2))
+        return
+    if send_id or delete_id:
+        if send_id:
+            result = reporter.send_report(send_id)
+        else:
+            deleted = reporter.delete_report(delete_id)
+            result = {'success': deleted,
+                      'message': f"Deleted crash report {delete_id}" if deleted else f"Crash report not found: {delete_id}"}
+        (print_success if result['success'] else print_error)(result['message'])
+        emit('crash-reports', result, ok=result['success'])
+        return
+
+    items = reporter.list_reports()
+    emit('crash-reports', {'enabled': reporter.enabled, 'reports': items})
+    if not reporter.enabled:
+        print_info("Crash reporting is off; turn it on with crash_reports.enabled in config.json")
+    if not items:
+        print_info("No crash reports saved")
+        return
+    print_header("💥 Crash reports")
+    for item in items:
+        sent = f"sent {item['sent_at']}" if item['sent_at'] else 'not sent'
+        click.echo(f"  {item['id']}  {item['created_at']}  ({sent})")
+        click.echo(f"      {item['summary']}")
+
+
+@cli.command()
+@click.option('--output', '-o', type=click.Path(dir_okay=False), help='Zip to write (default: ~/Downloads)')
+def diagnostics(output):
+    """
+    Save a diagnostics zip to attach to a bug report
+
+    Holds the logs, environment and dependency versions, settings with
+    secrets removed, recent jobs and crash report summaries.
+
+    Examples:
+      aifo diagnostics
+      aifo diagnostics -o ~/Desktop/aifo-diagnostics.zip
+    """
+    from src.config import get_config
+    from src.core.db_manager import DatabaseManager
+    from src.core.diagnostics import DiagnosticsExporter
+
+    try:
+        db = DatabaseManager()
+    except Exception as e:
+        print_warning(f"Database unavailable, recent jobs not included: {e}")
+        db = None
+    result = DiagnosticsExporter(get_config(), db).export(output)
+    emit('diagnostics', result, ok=result['success'])
+    if not result['success']:
+        print_error(result['message'])
+        return
+    print_success(result['message'])
+    for name in result['files']:
+        click.echo(f"  {name}")
+
+
+@cli.command('self-test')
+@click.option('--keep', is_flag=True, help='Keep the temporary folder for inspection')
+def self_test(keep):
+    """
+    Check the whole stack end-to-end in a temporary folder
+
+    Creates sample files, then scans, classifies (rules only, no AI),
+    plans, applies and undoes, and reports each subsystem.
+
+    Examples:
+      aifo self-test
+      aifo --json self-test       # Paste the output into a support request
+    """
+    from src.core.self_test import run_self_test
+
+    result = run_self_test(keep=keep)
+    emit('self-test', result, ok=result['success'])
+    print_header("🩺 Self test")
+    marks = {'passed': '✅', 'failed': '❌', 'skipped': '⏭️ '}
+    for check in result['subsystems']:
+        click.echo(f"  {marks[check['status']]} {check['name']:<11} {check['seconds']:>7.2f}s  {check['message']}")
+    if result['sandbox']:
+        print_info(f"Sandbox kept at {result['sandbox']}")
+    if result['success']:
+        print_success(result['message'])
+        return
+    print_error(result['message'])
+    return EXIT_ERROR
+
+
+@cli.command()
+@click.argument('folder', type=click.Path(exists=True, file_okay=False))
+@click.option('--sample', '-n', 'sample_size', type=click.IntRange(min=1), default=25, show_default=True,
+              help='Files to copy into the sandbox')
+@click.option('--deep', '-d', is_flag=True, help='Deep AI analysis (slower, better)')
+@click.option('--seed', type=int, help='Pick the same sample again')
+@click.option('--keep', is_flag=True, help='Keep the sandbox for inspection')
+def simulate(folder, sample_size, deep, seed, keep):
+    """
+    Try organizing a sandboxed copy of a sample of a folder
+
+    Copies a random sample of the folder's files to a temporary folder,
+    organizes them there with your rules, taxonomy and AI, and shows
+    where each would go. Nothing in the folder itself changes.
+
+    Examples:
+      aifo simulate ~/Downloads
+      aifo simulate ~/Documents -n 50 --keep   # Look at the organized copy afterwards
+    """
+    from src.cli.organizer import Organizer
+    from src.core.simulation import simulate_on_copy
+
+    org = Organizer()
+    result = simulate_on_copy(folder, sample_size, org.config, org.classifier, deep, seed, keep)
+    emit('simulate', result, ok=result['success'])
+    print_header("🧪 Simulation")
+    for move in result['moves']:
+        click.echo(f"  {Path(move['file']).name} → {move['destination']}  ({move['category'] or 'Unknown'})")
+    for item in result['outside_sandbox']:
+        print_warning(f"  {Path(item['file']).name} → {item['destination']} (outside the sandbox, not tried)")
+    for item in result['failed']:
+        print_error(f"  {Path(item['file']).name}: {item['message']}")
+    if result['sandbox']:
+        print_info(f"Sandbox kept at {result['sandbox']}")
+    if result['success']:
+        print_success(result['message'])
+        return
+    print_error(result['message'])
+    return EXIT_ERROR
+
+
+@cli.command()
+@click.option('--expect-version', help='App version the scripts should be')
+def doctor(expect_version):
+    """
+    Check that the backend can run, and say how to fix what is missing
+
+    Looks at the Python interpreter, the app's scripts, required and
+    optional packages, and the AI backend the settings select.
+
+    Examples:
+      aifo doctor
+      aifo --json doctor          # For the setup wizard
+    """
+    from src.core.backend_check import check_backend
+
+    result = check_backend(expected_version=expect_version)
+    emit('doctor', result, ok=result['success'])
+    print_header("🩺 Backend check")
+    marks = {'ok': '✅', 'warning': '⚠️ ', 'error': '❌'}
+    for check in result['checks']:
+        click.echo(f"  {marks[check['status']]} {check['name']:<17} {check['message']}")
+        if check['fix'] and check['status'] != 'ok':
+            click.echo(f"     → {check['fix']}")
+    if result['success']:
+        print_success(result['message'])
+        return
+    print_error(result['message'])
+    return EXIT_ERROR
+
+
+@cli.command()
+@click.option('--check', 'check_only', is_flag=True, help='Only check whether an update is available')
+@click.option('--channel', type=click.Choice(['stable', 'beta']), help='Switch release channel first')
+@click.option('--rollback', is_flag=True, help='Go back to the version the last update replaced')
+def update(check_only, channel, rollback):
+    """
+    Check for and install updates
+
+    Updates are signed and verified before installing; the next start
+    runs the self test and restores the previous version if it fails.
+
+    Examples:
+      aifo update                   # Check, then install after asking
+      aifo update --check
+      aifo update --channel beta    # Follow beta releases
+      aifo update --rollback
+    """
+    from src.config import get_config
+    from src.core.updater import UpdateManager
+
+    manager = UpdateManager(get_config())
+    if channel:
+        switched = manager.set_channel(channel)
+        if not switched['success']:
+            emit('update', switched, ok=False)
+            print_error(switched['message'])
+            return EXIT_ERROR
+        print_success(switched['message'])
+
+    if rollback:
+        result = manager.rollback()
+        emit('update', result, ok=result['success'])
+        if not result['success']:
+            print_error(result['message'])
+            return EXIT_ERROR
+        print_success(result['message'])
+        return
+
+    result = manager.check()
+    manifest = result.pop('manifest', None)
+    print_header(f"⬆️  Updates ({manager.channel})")
+    if not result['success']:
+        emit('update', result, ok=False)
+        print_error(result['message'])
+        return EXIT_ERROR
+    print_info(result['message'])
+    if result.get('notes') and result['available']:
+        click.echo(f"\n{result['notes']}\n")
+    if check_only or not result['available']:
+        emit('update', result)
+        return
+    size = f" ({format_size(result['size'])}{', delta' if result['delta'] else ''})" if result.get('size') else ''
+    if not confirm_action(f"Install {result['version']}{size}?"):
+        emit('update', result)
+        return
+
+    downloaded = manager.download(manifest)
+    installed = manager.apply() if downloaded['success'] else downloaded
+    result.update(installed=installed['success'], message=installed['message'])
+    emit('update', result, ok=installed['success'])
+    if not installed['success']:
+        print_error(installed['message'])
+        return EXIT_ERROR
+    print_success(installed['message'])
+
+
+@cli.command()
+@click.option('--enable/--disable', 'enable', default=None, help='Collect usage statistics locally')
+@click.option('--upload/--no-upload', 'upload', default=None, help='Send the anonymized totals')
+@click.option('--preview', is_flag=True, help='Show exactly what would be sent')
+@click.option('--send', is_flag=True, help='Send the totals now (when uploading is on)')
+@click.option('--clear', is_flag=True, help='Delete the locally collected statistics')
+@click.option('--days', default=30, show_default=True, help='Days to summarize')
+def telemetry(enable, upload, preview, send, clear, days):
+    """
+    Show the usage statistics collected on this computer
+
+    Collecting is off until you enable it; counts stay on this computer
+    unless you also turn on uploading, which sends only the totals shown
+    by --preview (feature names and counts, no file names or paths).
+
+    Examples:
+      aifo telemetry                    # What has been counted
+      aifo telemetry --enable           # Count locally
+      aifo telemetry --preview          # What an upload would contain
+      aifo telemetry --upload --send
+    """
+    import json
+    from src.core.telemetry import get_telemetry
+
+    recorder = get_telemetry()
+    for wanted, action in ((enable is not None or upload is not None,
+                            lambda: recorder.set_options(enable, upload)),
+                           (clear, recorder.clear), (send, recorder.upload)):
+        if not wanted:
+            continue
+        outcome = action()
+        if not outcome['success']:
+            emit('telemetry', outcome, ok=False)
+            print_error(outcome['message'])
+            return EXIT_ERROR
+        print_success(outcome['message'])
+
+    if preview:
+        payload = recorder.preview()
+        emit('telemetry', payload)
+        print_header("📤 What would be sent")
+        click.echo(json.dumps(payload, indent=2, sort_keys=True))
+        return
+
+    summary = recorder.summary(days)
+    emit('telemetry', summary)
+    print_header("📊 Usage statistics")
+    state = ('collected locally and sent as totals' if summary['upload']
+             else 'collected locally only' if summary['enabled'] else 'not collected (aifo telemetry --enable)')
+    print_info(f"Usage statistics are {state}")
+    for feature in summary['features'][:25]:
+        errors = f"  {feature['errors']} failed ({feature['error_rate']:.0%})" if feature['errors'] else ''
+        click.echo(f"  {feature['name']:<40} {feature['uses']:>6}{errors}")
+    if summary['last_upload']:
+        print_info(f"Last sent: totals up to {summary['last_upload']}")
+
+
+@cli.command()
+@click.option('--grant', 'grant_name', metavar='PLUGIN', help='Let a plugin run, with the capabilities it asks for')
+@click.option('--revoke', 'revoke_name', metavar='PLUGIN', help='Stop a plugin from running')
+@click.option('--run', 'run_action', nargs=2, metavar='PLUGIN:ACTION FILE', help='Run one action on a file now')
+def plugins(grant_name, revoke_name, run_action):
+    """
+    List plugin actions, and grant or revoke their capabilities
+
+    Plugin actions run on files after organizing moved them, in a sandbox
+    that can only read the file, write new files next to it or reach the
+    hosts it was granted.
+
+    Examples:
+      aifo plugins
+      aifo plugins --grant bookkeeping
+      aifo plugins --run heic-convert:convert ~/Pictures/IMG_0001.heic
+    """
+    from src.config import get_config
+    from src.core.plugins import PluginManager, HAS_WASMTIME
+
+    manager = PluginManager(get_config())
+    for name, grant in ((grant_name, True), (revoke_name, False)):
+        if name:
+            result = manager.set_grant(name, grant)
+            if not result['success']:
+                emit('plugins', result, ok=False)
+                print_error(result['message'])
+                return EXIT_ERROR
+            print_success(result['message'])
+
+    if run_action:
+        label, file_path = run_action
+        name, _, action = label.partition(':')
+        result = manager.run_action(name, action, file_path)
+        emit('plugins', result, ok=result['success'])
+        if not result['success']:
+            print_error(result['message'])
+            return EXIT_ERROR
+        print_success(result['message'])
+        for output in result['outputs']:
+            print_info(f"Wrote {output}")
+        return
+
+    found = manager.discover()
+    emit('plugins', {'plugin_dir': str(manager.plugin_dir), 'runtime': HAS_WASMTIME, 'plugins': found})
+    print_header(f"🧩 Plugins in {manager.plugin_dir}")
+    if not HAS_WASMTIME:
+        print_warning("Plugins need the wasmtime package to run (pip install wasmtime)")
+    for plugin in found:
+        status = 'broken' if plugin['error'] else 'granted' if plugin['granted'] else 'not granted'
+        click.echo(f"\n  {plugin['name']} {plugin.get('version', '')}  [{status}]")
+        if plugin['error']:
+            click.echo(f"    {plugin['error']}")
+        click.echo(f"    capabilities: {', '.join(plugin['capabilities']) or 'none'}")
+        for action in plugin['actions']:
+            click.echo(f"    {plugin['name']}:{action['name']}  {action.get('description', '')}")
+    if not found:
+        print_info("No plugins installed")
+
+
+@cli.command('rest-api')
+@click.option('--enable/--disable', 'enable', default=None, help='Serve the REST API from the engine (daemon)')
+@click.option('--token', 'show_token', is_flag=True, help='Print the API token')
+@click.option('--rotate', is_flag=True, help='Replace the API token')
+def rest_api(enable, show_token, rotate):
+    """
+    Show or change the engine's REST API for scripts and home automation
+
+    The running engine (python -m src.main daemon) serves scan, plan,
+    apply and status endpoints on this computer; clients send the token
+    as "Authorization: Bearer <token>".
+
+    Examples:
+      aifo rest-api --enable
+      aifo rest-api --token
+      aifo rest-api --rotate     # Lock out clients using the old token
+    """
+    from src.config import get_config
+    from src.core.rest_api import api_token, rotate_api_token, API_PREFIX, DEFAULT_PORT
+
+    config = get_config()
+    settings = dict(config.rest_api_settings or {})
+    if enable is not None:
+        settings['enabled'] = enable
+        try:
+            config.update('rest_api', settings)
+            config.save()
+        except OSError as e:
+            emit('rest-api', {'success': False, 'message': f"Cannot save config: {e}"}, ok=False)
+            print_error(f"Cannot save config: {e}")
+            return EXIT_ERROR
+        print_success(f"REST API {'enabled' if enable else 'disabled'} (restart the engine to apply)")
+    if rotate and settings.get('token'):
+        emit('rest-api', {'success': False, 'message': 'The token is set in config.json (rest_api.token)'}, ok=False)
+        print_error("The token is set in config.json (rest_api.token); change it there")
+        return EXIT_ERROR
+
+    try:
+        token = rotate_api_token(settings) if rotate else api_token(settings)
+    except OSError as e:
+        emit('rest-api', {'success': False, 'message': f"Cannot save the API token: {e}"}, ok=False)
+        print_error(f"Cannot save the API token: {e}")
+        return EXIT_ERROR
+    url = f"http://{settings.get('host') or '127.0.0.1'}:{settings.get('port') or DEFAULT_PORT}{API_PREFIX}"
+    emit('rest-api', {'enabled': bool(settings.get('enabled')), 'url': url,
+                      'token': token if show_token or rotate else None})
+    print_header("🌐 REST API")
+    print_info(f"{'Enabled' if settings.get('enabled') else 'Disabled (aifo rest-api --enable)'}: {url}")
+    if rotate:
+        print_success("New token created; clients using the old one are locked out")
+    if show_token or rotate:
+        click.echo(f"  Token: {token}")
+
+
+@cli.command('validate-rules')
+@click.argument('rules_file', type=click.Path(exists=True, dir_okay=False))
+@click.option('--strict', is_flag=True, help='Fail on warnings too')
+def validate_rules(rules_file, strict):
+    """
+    Lint a YAML rules file before using it
+
+    Checks syntax, regexes, dates and destination templates, and finds
+    rules that can never act or that fight over the same files.
+
+    Examples:
+      aifo validate-rules config.yaml
+      aifo --json validate-rules shared-rules.yaml --strict
+    """
+    from src.core.rule_validation import validate_rules as lint
+
+    report = lint(rules_file)
+    failed = not report['valid'] or (strict and report['warnings'])
+    emit('validate-rules', report, ok=not failed)
+
+    print_header(f"📏 {report['file']}: {report['rules']} rule(s)")
+    for diagnostic in report['diagnostics']:
+        icon = '❌' if diagnostic['severity'] == 'error' else '⚠️ '
+        location = f"{diagnostic['location']}: " if diagnostic['location'] else ''
+        click.echo(f"  {icon} {location}{diagnostic['message']} [{diagnostic['code']}]")
+    if report['valid'] and not report['warnings']:
+        print_success("No problems found")
+    else:
+        print_info(f"{report['errors']} error(s), {report['warnings']} warning(s)")
+    return EXIT_ERROR if failed else EXIT_OK
+
+
+@cli.command()
+@click.argument('query', nargs=-1)
+def ask(query):
+    """
+    Ask what you want in natural language
+
+    Examples:
+      aifo ask "I need to free up space on C drive"
+      aifo ask "organize my photos"
+      aifo ask "find duplicate files"
+    """
+    detector = IntentDetector()
+
+    # Join query arguments
+    query_str = ' '.join(query)
+
+    # If no query provided, prompt user
+    if not query_str:
+        if json_mode():
+            print_error("A query is required with --json")
+            return
+        query_str = click.prompt("What would you like to do?")
+
+    # Detect intent
+    result = detector.detect_and_suggest(query_str)
+    if json_mode():
+        # Suggestions are reported, not run
+        emit('ask', result)
+        return
 
     print_header(f"🤔 {detector.format_intent_name(result['intent'])}")
 
@@ -233,4 +2609,8 @@ def ask(query):
 
 
 if __name__ == '__main__':
+    from src.config import get_config
+    from src.core.crash_reports import CrashReporter
+    configure_logging(get_config().logging_settings)
+    CrashReporter(get_config()).install()
     cli()
@@ -12,8 +12,9 @@ import click
 # Add src to path
 sys.path.insert(0, str(Path(__file__).parent.parent.parent))
 
-from src.cli.helpers import print_header, print_success, print_error, print_warning, print_info
+from src.cli.helpers import print_header, print_success, print_error, print_warning, print_info, confirm_action
 from src.core.duplicates import DuplicateFinder as CoreDuplicateFinder
+from src.core.trash import SystemTrash
 from src.core.db_manager import DatabaseManager
 from src.config import get_config
 
@@ -35,6 +36,10 @@ class DuplicateFinder:
             folder: Folder to scan (default: watched folders)
             delete: Delete duplicates (keeps newest)
             min_size: Minimum file size (e.g., '1MB', '10MB')
+
+        Returns:
+            Dict: 'groups', 'summary', 'protected_groups' and, after --delete,
+                  'deleted' ({'count', 'space_freed', 'errors'})
         """
         # Parse min_size
         min_bytes = self._parse_size(min_size)
@@ -73,7 +78,8 @@ class DuplicateFinder:
 
         if not all_duplicates:
             print_success("\n✅ No duplicates found!")
-            return
+            return {'groups': [], 'summary': self.finder.get_duplicate_summary([]),
+                    'protected_groups': 0, 'deleted': None}
 
         # Filter for safety
         safe_duplicates, protected_groups_count, _protected_files_count = self.finder.filter_protected_duplicates(all_duplicates)
@@ -83,6 +89,8 @@ class DuplicateFinder:
 
         # Show summary
         summary = self.finder.get_duplicate_summary(safe_duplicates)
+        report = {'groups': safe_duplicates, 'summary': summary,
+                  'protected_groups': protected_groups_count, 'deleted': None}
 
         click.echo(f"\n{'='*70}")
         click.echo("DUPLICATE FILES SUMMARY")
@@ -119,15 +127,16 @@ class DuplicateFinder:
         # Delete if requested
         if delete:
             click.echo()
-            if not click.confirm("Delete duplicate files? (keeps newest copy)", default=False):
+            if not confirm_action("Move duplicate files to the trash? (keeps newest copy)", default=False):
                 print_info("Cancelled.")
-                return
+                return report
 
-            print_info("\nDeleting duplicates...")
+            print_info("\nMoving duplicates to the trash...")
 
             deleted_count = 0
             space_freed = 0
             errors = []
+            trash = SystemTrash(self.config, self.db)
 
             for group in safe_duplicates:
                 # Get file paths with modification times
@@ -154,12 +163,14 @@ class DuplicateFinder:
                     try:
                         file_obj = Path(file_path)
                         file_size = file_obj.stat().st_size
-                        file_obj.unlink()
+                        result = trash.move_to_trash([file_path])
+                        if result['failed']:
+                            raise OSError(result['failed'][0]['reason'])
 
                         deleted_count += 1
                         space_freed += file_size
 
-                        click.echo(f"✓ Deleted: {file_path}")
+                        click.echo(f"✓ Trashed: {file_path}")
 
                     except Exception as e:
                         errors.append(f"{file_path}: {str(e)}")
@@ -169,6 +180,7 @@ class DuplicateFinder:
             click.echo("DELETION COMPLETE")
             click.echo(f"{'='*70}")
 
+            report['deleted'] = {'count': deleted_count, 'space_freed': space_freed, 'errors': errors}
             print_success(f"✅ Deleted: {deleted_count} files")
             print_success(f"✅ Freed: {space_freed / (1024*1024):.2f} MB ({space_freed / (1024*1024*1024):.2f} GB)")
 
@@ -183,6 +195,8 @@ class DuplicateFinder:
             click.echo()
             print_info("💡 Tip: Use --delete flag to remove duplicates")
 
+        return report
+
     def _parse_size(self, size_str: str) -> int:
         """
         Parse size string to bytes.
@@ -11,6 +11,8 @@ from pathlib import Path
 from typing import Tuple, Dict, List, Union
 import click
 
+from src.cli import json_output
+
 
 def parse_size(size_str: str) -> int:
     """
@@ -173,21 +175,25 @@ def print_header(text: str):
 
 def print_success(text: str):
     """Print success message"""
+    json_output.record('success', text)
     click.echo(f"✅ {text}")
 
 
 def print_warning(text: str):
     """Print warning message"""
+    json_output.record('warning', text)
     click.echo(f"⚠️  {text}")
 
 
 def print_error(text: str):
     """Print error message"""
+    json_output.record('error', text)
     click.echo(f"❌ {text}")
 
 
 def print_info(text: str):
     """Print info message"""
+    json_output.record('info', text)
     click.echo(f"ℹ️  {text}")
 
 
@@ -195,6 +201,9 @@ def confirm_action(message: str, default: bool = True) -> bool:
     """
     Ask for user confirmation
 
+    With --json there is nobody to ask: the answer is yes with --yes and
+    no otherwise, so the command reports its plan without acting.
+
     Args:
         message: Confirmation message
         default: Default response
@@ -202,6 +211,9 @@ def confirm_action(message: str, default: bool = True) -> bool:
     Returns:
         True if user confirms, False otherwise
     """
+    answer = json_output.confirm(message, default)
+    if answer is not None:
+        return answer
     return click.confirm(message, default=default)
 
 
@@ -0,0 +1,120 @@
+"""
+AI File Organiser - Machine-Readable Output
+
+`aifo --json <command>` prints exactly one JSON document on stdout, in
+the format described in src/utils/output_schemas.py.
+
+Headers, progress bars and other human output go to stderr in this mode,
+so stdout stays parseable; the messages are also collected into the
+document. Confirmations are declined unless --yes is given, so a JSON run
+reports what it would do and never waits for input.
+
+Every command exits with the codes in output_schemas (EXIT_*): a command
+may return one explicitly; otherwise it exits 1 if it printed an error.
+
+Copyright © 2025 Alexandru Emanuel Vasile. All Rights Reserved.
+"""
+
+import sys
+from contextlib import redirect_stdout
+from typing import Any, Dict, Optional
+
+import click
+
+from src.utils.output_schemas import json_document, EXIT_OK, EXIT_ERROR
+
+
+def _options() -> Optional[Dict[str, Any]]:
+    ctx = click.get_current_context(silent=True)
+    if ctx is None:
+        return None
+    root = ctx.find_root()
+    return root.obj if isinstance(root.obj, dict) else None
+
+
+def _state() -> Optional[Dict[str, Any]]:
+    options = _options()
+    return options if options and options.get('json') else None
+
+
+def enable_output(ctx: click.Context, as_json: bool, assume_yes: bool = False) -> None:
+    """
+    Set up the output mode for the current invocation.
+
+    Args:
+        ctx: Root click context
+        as_json: Print a JSON document instead of human output
+        assume_yes: Answer confirmations with yes
+    """
+    ctx.obj = {'json': as_json, 'assume_yes': assume_yes, 'messages': [], 'errors': []}
+    if not as_json:
+        return
+    ctx.obj.update(stdout=sys.stdout, emitted=False)
+    ctx.with_resource(redirect_stdout(sys.stderr))
+    ctx.call_on_close(lambda: _emit_fallback(ctx))
+
+
+def json_mode() -> bool:
+    """Whether the running command should emit JSON."""
+    return _state() is not None
+
+
+def record(level: str, text: str) -> None:
+    """Keep a human message for the JSON document and the exit code."""
+    options = _options()
+    if options is not None and 'errors' in options:
+        (options['errors'] if level == 'error' else options['messages']).append(text)
+
+
+def exit_code(result: Any) -> int:
+    """
+    Exit code for a finished command.
+
+    Args:
+        result: The command's return value (an EXIT_* code, or None)
+
+    Returns:
+        int: The returned code, else EXIT_ERROR if an error was printed, else EXIT_OK
+    """
+    if isinstance(result, int) and not isinstance(result, bool):
+        return result
+    options = _options()
+    return EXIT_ERROR if options and options.get('errors') else EXIT_OK
+
+
+def confirm(message: str, default: bool = True) -> Optional[bool]:
+    """
+    Answer a confirmation without asking, when the options allow it.
+
+    Returns:
+        bool or None: True with --yes, False with --json alone, None to ask the user
+    """
+    options = _options()
+    if options and options.get('assume_yes'):
+        return True
+    return False if _state() is not None else None
+
+
+def emit(schema: str, data: Any, ok: bool = True) -> None:
+    """
+    Print the command's JSON document (no-op outside JSON mode).
+
+    Args:
+        schema (str): Schema name (see output_schemas.SCHEMA_VERSIONS)
+        data: JSON-serializable payload
+        ok (bool): Whether the command succeeded
+    """
+    state = _state()
+    if state is None or state['emitted']:
+        return
+    state['emitted'] = True
+    state['stdout'].write(json_document(schema, data, ok, state['messages'], state['errors']) + '\n')
+    state['stdout'].flush()
+
+
+def _emit_fallback(ctx: click.Context) -> None:
+    """Make sure every run prints a document, even for commands without data."""
+    if ctx.obj.get('emitted'):
+        return
+    with ctx.scope(cleanup=False):
+        emit(ctx.invoked_subcommand or 'aifo', None)
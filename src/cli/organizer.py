@@ -6,17 +6,27 @@ Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
 
 import sys
 from pathlib import Path
-from typing import List, Dict, Any, Optional
+from typing import Callable, List, Dict, Any, Optional
 import click
 
 # Add src to path
 sys.path.insert(0, str(Path(__file__).parent.parent.parent))
 
-from src.cli.helpers import print_header, print_success, print_error, print_warning, print_info
+from src.cli.helpers import print_header, print_success, print_error, print_warning, print_info, confirm_action
 from src.core.classifier import FileClassifier
+from src.core.corrections import CorrectionLearner
+from src.core.rules import UserRules
+from src.core.settings import SettingsStore
+from src.core.usage import UsageLedger
+from src.core.classification_cache import ClassificationCache
+from src.core.tags import TagStore
 from src.core.actions import ActionManager
 from src.core.db_manager import DatabaseManager
-from src.ai.ollama_client import OllamaClient
+from src.core.snapshots import SnapshotManager
+from src.core.plans import OrganizePlanner
+from src.core.ignore_rules import IgnoreRules
+from src.core.progress_events import EVENT_PROGRESS, EVENT_FILE_DONE, EVENT_ERROR
+from src.ai.providers import create_provider
 from src.config import get_config
 
 
@@ -28,14 +38,10 @@ class Organizer:
         self.config = get_config()
         self.db = DatabaseManager()
 
-        # Initialize Ollama client
+        # Initialize the AI client (Ollama, or the provider the settings select)
         self.ollama = None
         try:
-            self.ollama = OllamaClient(
-                base_url=self.config.ollama_base_url,
-                model=self.config.ollama_model,
-                timeout=self.config.get('ollama_timeout', 30)
-            )
+            self.ollama = create_provider(self.config)
             if not self.ollama.is_available():
                 self.ollama = None
         except Exception:
@@ -43,12 +49,27 @@ class Organizer:
 
         # Initialize classifier
         self.classifier = FileClassifier(self.config, self.ollama)
+        self.classifier.user_rules = UserRules(self.config)
+        self.classifier.settings = SettingsStore()
+        self.classifier.corrections = CorrectionLearner(self.config, self.db)
+        self.classifier.cache = ClassificationCache(self.config, self.db)
+        self.classifier.usage = UsageLedger(self.config, self.db)
+        self.classifier.tags = TagStore(self.config, self.db)
 
         # Initialize action manager
         self.action_manager = ActionManager(self.config, self.db)
 
+        # Picks the planner strategy (e.g. photos by date) per folder
+        self.planner = OrganizePlanner(self.config, self.classifier, self.action_manager)
+
+        # Snapshots taken before large runs
+        self.snapshots = SnapshotManager(self.config, self.db)
+
     def organize_folder(self, folder: Optional[str] = None, preview: bool = False,
-                       auto: bool = False, deep: bool = False):
+                       auto: bool = False, deep: bool = False, strategy: Optional[str] = None,
+                       profile: Optional[str] = None,
+                       on_event: Optional[Callable[[str, Dict[str, Any]], None]] = None,
+                       should_stop: Optional[Callable[[], bool]] = None):
         """
         Organize files in a folder.
 
@@ -57,11 +78,30 @@ class Organizer:
             preview: Preview changes without applying (dry run)
             auto: Auto-approve all actions
             deep: Use deep AI analysis
+            strategy: Planner strategy for every file (default: per folder policy)
+            profile: Organization profile for this run (see core.profiles)
+            on_event: Called as (event, payload) with the progress events of
+                      core.progress_events, as the run goes
+            should_stop: Checked before each file; once it returns True the run
+                         stops there (files already moved stay moved, in one
+                         undoable operation)
+
+        Returns:
+            Dict: Run summary ('folder', 'preview', 'categories', 'organized',
+                  'skipped', 'errors', 'cancelled'), or None if nothing ran
         """
+        def emit(event: str, **payload) -> None:
+            if on_event is not None:
+                on_event(event, payload)
+
+        def stopped() -> bool:
+            return should_stop is not None and should_stop()
+
         # Determine folder to organize
         if folder is None:
             if not self.config.watched_folders:
                 print_error("No watched folders configured in config.json")
+                emit(EVENT_ERROR, file=None, message="No watched folders configured")
                 return
             folder = self.config.watched_folders[0]
 
@@ -69,13 +109,24 @@ class Organizer:
 
         if not folder_path.exists():
             print_error(f"Folder not found: {folder}")
+            emit(EVENT_ERROR, file=str(folder_path), message="Folder not found")
             return
 
         if not folder_path.is_dir():
             print_error(f"Not a directory: {folder}")
+            emit(EVENT_ERROR, file=str(folder_path), message="Not a directory")
             return
 
-        print_header(f"🗂️  Organizing: {folder_path}")
+        run = None
+        if profile:
+            try:
+                run = self.planner.profiles.run(profile)
+            except ValueError as e:
+                print_error(str(e))
+                emit(EVENT_ERROR, file=None, message=str(e))
+                return
+
+        print_header(f"🗂️  Organizing: {folder_path}" + (f" (profile {run.profile.name})" if run else ''))
 
         if preview:
             print_info("PREVIEW MODE - No files will be moved\n")
@@ -88,12 +139,19 @@ class Organizer:
 
         # Find all files
         print_info("Scanning for files...")
+        emit(EVENT_PROGRESS, phase='scanning', done=0, total=0, file=None)
         files = list(folder_path.rglob('*'))
         files = [f for f in files if f.is_file()]
+        if run is not None and run.profile.exclusions:
+            excluded = IgnoreRules(str(folder_path), run.profile.exclusion_rules())
+            files = [f for f in files if not excluded.ignores(str(f))]
 
         if not files:
             print_success("No files found to organize!")
-            return
+            summary = {'folder': str(folder_path), 'preview': preview, 'files': 0, 'categories': {},
+                       'organized': 0, 'skipped': 0, 'errors': 0, 'cancelled': False}
+            emit(EVENT_PROGRESS, phase='done', done=0, total=0, file=None, summary=summary)
+            return summary
 
         print_info(f"Found {len(files)} files\n")
 
@@ -102,18 +160,19 @@ class Organizer:
 
         click.echo("Classifying files...")
         with click.progressbar(files, label='Analyzing') as bar:
-            for file_path in bar:
+            for index, file_path in enumerate(bar):
+                if stopped():
+                    break
                 try:
-                    classification = self.classifier.classify(
-                        str(file_path),
-                        deep_analysis=deep
-                    )
+                    classification = self.planner.classify(str(file_path), folder_path, deep, strategy, run)
                     classifications.append({
                         'file': file_path,
                         'classification': classification
                     })
                 except Exception as e:
                     print_error(f"\nError classifying {file_path.name}: {e}")
+                    emit(EVENT_ERROR, file=str(file_path), message=f"Could not classify: {e}")
+                emit(EVENT_PROGRESS, phase='classifying', done=index + 1, total=len(files), file=str(file_path))
 
         # Show summary
         categories = {}
@@ -132,11 +191,30 @@ class Organizer:
 
         click.echo(f"\n{'-'*60}")
 
+        summary = {
+            'folder': str(folder_path),
+            'preview': preview,
+            'files': len(files),
+            'categories': {category: len(items) for category, items in sorted(categories.items())},
+            'organized': 0, 'skipped': 0, 'errors': 0, 'cancelled': False
+        }
+
         # Ask for confirmation if not auto
-        if not auto and not preview:
-            if not click.confirm(f"\nOrganize {len(files)} files?", default=True):
-                print_info("Cancelled.")
-                return
+        if stopped() or (not auto and not preview
+                         and not confirm_action(f"\nOrganize {len(files)} files?", default=True)):
+            print_info("Cancelled.")
+            summary['cancelled'] = True
+            emit(EVENT_PROGRESS, phase='done', done=0, total=len(classifications), file=None, summary=summary)
+            return summary
+
+        # Snapshot the folder first so the run can be restored in one step
+        if not preview:
+            snapshot_id = self.snapshots.maybe_snapshot(
+                [str(item['file']) for item in classifications if item['classification'].get('suggested_path')],
+                reason=f"Before organizing {folder_path}"
+            )
+            if snapshot_id:
+                print_info(f"Snapshot {snapshot_id} saved (restore with: aifo snapshots --restore {snapshot_id})")
 
         # Execute organization
         click.echo("\nOrganizing files...")
@@ -145,14 +223,24 @@ class Organizer:
         error_count = 0
         skipped_count = 0
 
-        with click.progressbar(classifications, label='Organizing') as bar:
-            for item in bar:
+        # The whole run is one entry on the undo stack
+        run_description = f"Organize {folder_path}"
+        with self.action_manager.journal_scope(kind='apply', description=run_description), \
+                click.progressbar(classifications, label='Organizing') as bar:
+            for index, item in enumerate(bar):
+                if stopped():
+                    print_warning("\nStopped: the remaining files were left in place")
+                    summary['cancelled'] = True
+                    break
                 file_path = item['file']
                 classification = item['classification']
+                emit(EVENT_PROGRESS, phase='organizing', done=index, total=len(classifications), file=str(file_path))
 
                 # Skip if no suggested path
                 if not classification.get('suggested_path'):
                     skipped_count += 1
+                    emit(EVENT_FILE_DONE, file=str(file_path), status='skipped', destination=None,
+                         message='No destination')
                     continue
 
                 try:
@@ -164,12 +252,19 @@ class Organizer:
 
                     if result['success']:
                         success_count += 1
+                        emit(EVENT_FILE_DONE, file=str(file_path), status='preview' if preview else 'organized',
+                             destination=result.get('new_path'), message=result.get('message'))
                     else:
                         error_count += 1
+                        emit(EVENT_FILE_DONE, file=str(file_path), status='failed', destination=None,
+                             message=result.get('message'))
+                        emit(EVENT_ERROR, file=str(file_path), message=result.get('message'))
 
                 except Exception as e:
                     error_count += 1
                     click.echo(f"\nError organizing {file_path.name}: {e}")
+                    emit(EVENT_FILE_DONE, file=str(file_path), status='failed', destination=None, message=str(e))
+                    emit(EVENT_ERROR, file=str(file_path), message=str(e))
 
         # Final summary
         click.echo(f"\n{'='*60}")
@@ -180,6 +275,8 @@ class Organizer:
             print_info(f"Would organize: {success_count} files")
         else:
             print_success(f"✅ Organized: {success_count} files")
+            if success_count:
+                print_info("Undo this run with: aifo undo")
 
         if skipped_count > 0:
             print_warning(f"⏭️  Skipped: {skipped_count} files (no destination)")
@@ -192,3 +289,8 @@ class Organizer:
         time_saved = stats.get('time_saved_hours', 0)
         if time_saved > 0:
             print_success(f"\n⏱️  Total time saved: {time_saved:.2f} hours")
+
+        summary.update(organized=success_count, skipped=skipped_count, errors=error_count)
+        emit(EVENT_PROGRESS, phase='done', done=len(classifications), total=len(classifications), file=None,
+             summary=summary)
+        return summary
@@ -31,6 +31,9 @@ class Scanner:
         Args:
             folder: Folder to scan (default: first watched folder)
             detailed: Show detailed breakdown
+
+        Returns:
+            Dict: Scan statistics, or None if the folder could not be scanned
         """
         # Determine folder
         if folder is None:
@@ -125,6 +128,7 @@ class Scanner:
 
         # Display results
         self._display_stats(stats, detailed)
+        return {'folder': str(folder_path), **stats}
 
     def _display_stats(self, stats: Dict[str, Any], detailed: bool):
         """Display scan statistics."""
@@ -12,8 +12,9 @@ import click
 # Add src to path
 sys.path.insert(0, str(Path(__file__).parent.parent.parent))
 
-from src.cli.helpers import print_header, print_success, print_error, print_warning, print_info
+from src.cli.helpers import print_header, print_success, print_error, print_warning, print_info, confirm_action
 from src.core.duplicates import DuplicateFinder
+from src.core.trash import SystemTrash
 from src.core.db_manager import DatabaseManager
 from src.config import get_config
 
@@ -88,9 +89,10 @@ class SpaceManager:
                 click.echo(f"   ... and {len(group['paths']) - 3} more")
 
         # Ask to delete
-        if auto or click.confirm(f"\n\nDelete duplicates? (keeps newest copy)", default=False):
+        if auto or confirm_action(f"\n\nMove duplicates to the trash? (keeps newest copy)", default=False):
             deleted_count = 0
             space_freed = 0
+            trash = SystemTrash(self.config, self.db)
 
             for group in all_duplicates:
                 # Keep the newest file, delete others
@@ -102,14 +104,16 @@ class SpaceManager:
                 paths_with_times.sort(key=lambda x: x[1], reverse=True)
                 keep_file = paths_with_times[0][0]
 
-                # Delete older copies
+                # Move older copies to the trash
                 for file_path, _ in paths_with_times[1:]:
                     try:
                         file_size = Path(file_path).stat().st_size
-                        Path(file_path).unlink()
+                        result = trash.move_to_trash([file_path])
+                        if result['failed']:
+                            raise OSError(result['failed'][0]['reason'])
                         deleted_count += 1
                         space_freed += file_size
-                        click.echo(f"   Deleted: {file_path}")
+                        click.echo(f"   Trashed: {file_path}")
                     except Exception as e:
                         print_error(f"   Failed to delete {file_path}: {e}")
 
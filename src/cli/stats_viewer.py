@@ -25,11 +25,19 @@ class StatsViewer:
         self.db = DatabaseManager()
 
     def show_stats(self):
-        """Show organization statistics."""
+        """
+        Show organization statistics.
+
+        Returns:
+            Dict: 'all_time', 'week' and 'today' stats, 'recent' operations,
+                  'categories' and 'operations' breakdowns
+        """
         print_header("📊 AI File Organiser Statistics")
 
         # Get all-time stats
         stats = self.db.get_stats('all')
+        report = {'all_time': stats, 'week': None, 'today': None,
+                  'recent': [], 'categories': [], 'operations': []}
 
         # Overview
         click.echo(f"\n{'='*70}")
@@ -54,6 +62,7 @@ class StatsViewer:
         # Get recent activity
         try:
             recent_logs = self.db.get_recent_logs(10)
+            report['recent'] = recent_logs
 
             if recent_logs:
                 click.echo(f"\n{'-'*70}")
@@ -97,6 +106,10 @@ class StatsViewer:
                 """)
 
                 category_stats = cursor.fetchall()
+                report['categories'] = [
+                    {'category': category, 'count': count, 'time_saved_hours': total_time or 0}
+                    for category, count, total_time in category_stats
+                ]
 
                 if category_stats:
                     click.echo(f"\n{'-'*70}")
@@ -122,6 +135,7 @@ class StatsViewer:
             """)
 
             operation_stats = cursor.fetchall()
+            report['operations'] = [{'operation': operation, 'count': count} for operation, count in operation_stats]
 
             if operation_stats:
                 click.echo(f"\n{'-'*70}")
@@ -139,6 +153,7 @@ class StatsViewer:
         # Weekly stats
         try:
             week_stats = self.db.get_stats('week')
+            report['week'] = week_stats
 
             click.echo(f"\n{'-'*70}")
             click.echo("THIS WEEK")
@@ -159,6 +174,7 @@ class StatsViewer:
         # Daily stats
         try:
             day_stats = self.db.get_stats('day')
+            report['today'] = day_stats
 
             click.echo(f"\n{'-'*70}")
             click.echo("TODAY")
@@ -210,3 +226,5 @@ class StatsViewer:
             click.echo("  Keep organizing to unlock achievements!")
 
         click.echo()
+        report['achievements'] = achievements
+        return report
@@ -17,6 +17,7 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
+import copy
 import json
 import os
 from pathlib import Path
@@ -150,6 +151,11 @@ class Config:
         """Check if dry run mode is enabled (no actual file operations)."""
         return self.get("dry_run", True)
 
+    @property
+    def locale(self) -> str:
+        """Get UI/folder locale (e.g. 'de' for "Rechnungen" instead of "Invoices")."""
+        return self.get("locale", "en")
+
     @property
     def time_estimates(self) -> Dict[str, float]:
         """Get time estimates (in minutes) for different operations."""
@@ -201,6 +207,227 @@ class Config:
         """Check if offline license validation is enabled."""
         return self.get("license.offline_mode", True)
 
+    @property
+    def archive_staging_dir(self) -> str:
+        """Directory where archives are extracted before their contents are organized.
+
+        Empty (default) means `data/staging` in the project root.
+        """
+        return self.get("archives.staging_dir", "")
+
+    @property
+    def archive_original_action(self) -> str:
+        """What to do with an archive after extract-and-organize: 'keep' or 'trash'."""
+        return self.get("archives.original_after_extract", "keep")
+
+    @property
+    def archive_inspection(self) -> Dict[str, Any]:
+        """Zip-bomb limits for archive inspection (max_depth, max_entries,
+        max_total_size_mb, max_ratio, max_nested_size_mb)."""
+        return self.get("archives.inspection", {})
+
+    @property
+    def archive_tool_paths(self) -> Dict[str, str]:
+        """Explicit paths to external archive tools, e.g. {"7z": "C:/Tools/7z.exe", "unrar": ""}.
+
+        Empty values mean auto-detection (bundled tools/ directory, PATH, default install folders).
+        """
+        return self.get("archives.tools", {})
+
+    @property
+    def archive_password_settings(self) -> Dict[str, Any]:
+        """Password handling for encrypted archives (use_keyring, prompt_timeout_seconds)."""
+        return self.get("archives.passwords", {})
+
+    @property
+    def trash_dir(self) -> str:
+        """Holding directory for trashed files. Empty (default) means `data/trash`."""
+        return self.get("archives.trash_dir", "")
+
+    @property
+    def snapshot_settings(self) -> Dict[str, Any]:
+        """Pre-run folder snapshots (enabled, min_files, keep, max_files, hash_max_size_mb)."""
+        return self.get("snapshots", {})
+
+    @property
+    def version_settings(self) -> Dict[str, Any]:
+        """Versions kept when a file is replaced (enabled, keep_versions, max_age_days, store_dir)."""
+        return self.get("versions", {})
+
+    @property
+    def conflict_resolution(self) -> str:
+        """What to do when a destination already exists: 'keep_both' (default), 'replace' or 'ask'.
+
+        'ask' makes applying a plan set such files aside as conflicts (see core.conflicts);
+        elsewhere it works like 'keep_both'.
+        """
+        return self.get("conflict_resolution", "keep_both")
+
+    @property
+    def workspaces(self) -> Dict[str, Dict[str, Any]]:
+        """Named multi-root workspaces: {"name": {"roots": [...], "description": ""}}."""
+        return self.get("workspaces", {})
+
+    @property
+    def consolidation_rules(self) -> List[Dict[str, Any]]:
+        """Saved consolidation rules: [{"name", "sources"/"workspace", "keywords", ..., "destination"}]."""
+        return self.get("consolidation_rules", [])
+
+    @property
+    def pinned_folder_settings(self) -> Dict[str, Any]:
+        """Pinned folder quick stats (stats_max_age in seconds)."""
+        return self.get("pinned_folders", {})
+
+    @property
+    def logging_settings(self) -> Dict[str, Any]:
+        """Log file settings (level, file, json, max_mb, backups); the level can also change at runtime."""
+        return self.get("logging", {})
+
+    @property
+    def crash_report_settings(self) -> Dict[str, Any]:
+        """Opt-in crash reports (enabled, include_paths consent, endpoint, keep)."""
+        return self.get("crash_reports", {})
+
+    @property
+    def geocoding_settings(self) -> Dict[str, Any]:
+        """Reverse geocoding for photo folders (online toggle, endpoint, dataset, max_distance_km)."""
+        return self.get("geocoding", {})
+
+    @property
+    def faces_settings(self) -> Dict[str, Any]:
+        """Offline face clustering (enabled, detector_model, embedding_model, similarity, min_faces)."""
+        return self.get("faces", {})
+
+    @property
+    def burst_settings(self) -> Dict[str, Any]:
+        """Burst and look-alike photo grouping (burst_seconds, similar_seconds, max_distance, archive_dir)."""
+        return self.get("bursts", {})
+
+    @property
+    def raw_pair_settings(self) -> Dict[str, Any]:
+        """RAW+JPEG pairs (separate_raw: move RAW files into their own raw_tree)."""
+        return self.get("raw_pairs", {})
+
+    @property
+    def screenshot_settings(self) -> Dict[str, Any]:
+        """Screenshot OCR naming (ocr_naming, tesseract_path, languages, max_words)."""
+        return self.get("screenshots", {})
+
+    @property
+    def video_settings(self) -> Dict[str, Any]:
+        """Movie/TV title lookup (online toggle, api_key, endpoint, language)."""
+        return self.get("videos", {})
+
+    @property
+    def ebook_settings(self) -> Dict[str, Any]:
+        """Ebook ISBN lookup (online toggle, endpoint)."""
+        return self.get("ebooks", {})
+
+    @property
+    def leave_alone_settings(self) -> Dict[str, Any]:
+        """Folders the planner must not look inside (enabled, always and allow lists)."""
+        return self.get("leave_alone", {})
+
+    @property
+    def invoice_settings(self) -> Dict[str, Any]:
+        """Invoice fields (enabled, destination template, ocr, llm fallback, day_first)."""
+        return self.get("invoices", {})
+
+    @property
+    def font_settings(self) -> Dict[str, Any]:
+        """Font library (install_dir: where fonts are installed; empty = the user's font folder)."""
+        return self.get("fonts", {})
+
+    @property
+    def update_settings(self) -> Dict[str, Any]:
+        """Updates (enabled, channel: stable/beta, manifest_url with {channel}, public_key: Ed25519 hex)."""
+        return self.get("updates", {})
+
+    @property
+    def telemetry_settings(self) -> Dict[str, Any]:
+        """Usage statistics (enabled: count locally; upload: send anonymized totals; endpoint)."""
+        return self.get("telemetry", {})
+
+    @property
+    def plugin_settings(self) -> Dict[str, Any]:
+        """WASM plugin actions (enabled, dir, granted capabilities per plugin, fuel, memory_mb)."""
+        return self.get("plugins", {})
+
+    @property
+    def rest_api_settings(self) -> Dict[str, Any]:
+        """Daemon REST API (enabled, host, port, token; allow_remote to listen beyond localhost)."""
+        return self.get("rest_api", {})
+
+    @property
+    def cleanup_settings(self) -> Dict[str, Any]:
+        """Junk cleaner (kinds, partial_age_days, installer_max_age_days)."""
+        return self.get("cleanup", {})
+
+    @property
+    def corrections_settings(self) -> Dict[str, Any]:
+        """Learning from corrections (enabled, min_repeats, hash_max_mb)."""
+        return self.get("corrections", {})
+
+    @property
+    def classification_cache_settings(self) -> Dict[str, Any]:
+        """Reusing AI classifications of unchanged files (enabled, hash_max_mb, max_entries)."""
+        return self.get("classification_cache", {})
+
+    @property
+    def trash_settings(self) -> Dict[str, Any]:
+        """Where removed files go (backend: system = platform trash, app = trash_dir)."""
+        return self.get("trash", {})
+
+    @property
+    def thumbnail_settings(self) -> Dict[str, Any]:
+        """Where rendered thumbnails are cached and how large the cache may grow."""
+        return self.get("thumbnails", {})
+
+    @property
+    def ai_provider_settings(self) -> Dict[str, Any]:
+        """AI provider for classification (provider: ollama/openai, base_url, model, timeout; empty = by tier)."""
+        return self.get("ai_provider", {})
+
+    @property
+    def usage_settings(self) -> Dict[str, Any]:
+        """AI spend tracking (monthly_budget_usd, on_budget_exceeded: warn/block, prices per 1k tokens)."""
+        return self.get("usage", {})
+
+    @property
+    def runtime_settings(self) -> Dict[str, Any]:
+        """Interpreter and app folder for child processes (python, app_root; empty = detect)."""
+        return self.get("runtime", {})
+
+    @property
+    def search_settings(self) -> Dict[str, Any]:
+        """Search index (text_limit: characters indexed per file, max_results)."""
+        return self.get("search", {})
+
+    @property
+    def tag_settings(self) -> Dict[str, Any]:
+        """File tags (write_to_file: also into extended attributes / NTFS streams)."""
+        return self.get("tags", {})
+
+    @property
+    def rename_settings(self) -> Dict[str, Any]:
+        """Bulk renames (template, e.g. "{date}-{category}-{original}"; date_format)."""
+        return self.get("rename", {})
+
+    @property
+    def ocr_settings(self) -> Dict[str, Any]:
+        """OCR of screenshots and scans (enabled, languages, tesseract_path, max_pages, timeout, skip_camera_photos)."""
+        return self.get("ocr", {})
+
+    @property
+    def tray_settings(self) -> Dict[str, Any]:
+        """Tray icon (enabled, downloads_folder for "Organize Downloads now", dashboard_url)."""
+        return self.get("tray", {})
+
+    @property
+    def control_settings(self) -> Dict[str, Any]:
+        """Daemon control socket (address: Unix socket path or named pipe; empty = per-user default)."""
+        return self.get("control", {})
+
     @property
     def base_destination(self) -> str:
         """Get base destination directory for suggested paths.
@@ -256,6 +483,34 @@ class Config:
 
         target[keys[-1]] = value
 
+    def as_dict(self) -> Dict[str, Any]:
+        """
+        Get a deep copy of the full configuration dictionary.
+
+        Returns:
+            Dict[str, Any]: Copy of all settings (safe to modify)
+        """
+        return copy.deepcopy(self._config)
+
+    def merge(self, values: Dict[str, Any]) -> None:
+        """
+        Deep-merge a dictionary of settings into the current configuration.
+
+        Nested dictionaries are merged key by key; all other values (including
+        lists) replace the existing value. Keys not present in `values` are kept.
+
+        Args:
+            values (Dict[str, Any]): Settings to merge
+        """
+        def _merge(target: Dict[str, Any], source: Dict[str, Any]) -> None:
+            for key, value in source.items():
+                if isinstance(value, dict) and isinstance(target.get(key), dict):
+                    _merge(target[key], value)
+                else:
+                    target[key] = copy.deepcopy(value)
+
+        _merge(self._config, values)
+
     def _is_path_blacklisted(self, path: Path, blacklist: List[str]) -> bool:
         """
         Check if path (or symlink target) is blacklisted (HIGH #7 FIX).
@@ -320,6 +575,7 @@ class Config:
                 - auto_mode (bool): Override auto_mode for this folder
                 - allow_move (bool): Whether moves are allowed
                 - use_ai (bool): Whether to use AI for this folder
+                - strategy (str): Planner strategy, e.g. 'photos' (see core.plans)
                 Returns None if no policy matches.
 
         Example:
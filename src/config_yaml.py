@@ -115,12 +115,13 @@ class FilterRule:
 class ActionRule:
     """Action rule with templating and conflict resolution."""
     name: str
-    action: str  # 'move', 'copy', 'delete', 'rename', 'script'
+    action: str  # 'move', 'copy', 'delete', 'rename', 'script', 'compress'
     destination: Optional[str] = None
     template: Optional[str] = None
     script: Optional[str] = None
     conflict_resolution: str = 'rename'  # 'rename', 'overwrite', 'skip'
     tags: Optional[List[str]] = None
+    remove_source: bool = False  # 'compress': remove file once the archive is verified
 
     def execute(self, file_path: Path, context: Dict[str, Any]) -> Dict[str, Any]:
         """Execute the action with given context."""
@@ -134,6 +135,8 @@ class ActionRule:
             return self._execute_delete(file_path, context)
         elif self.action == 'rename':
             return self._execute_rename(file_path, context)
+        elif self.action == 'compress':
+            return self._execute_compress(file_path, context)
 
         return {'success': False, 'error': f'Unknown action: {self.action}'}
 
@@ -218,6 +221,33 @@ class ActionRule:
         except Exception as e:
             return {'success': False, 'error': str(e)}
 
+    def _execute_compress(self, file_path: Path, context: Dict[str, Any]) -> Dict[str, Any]:
+        """Add file to an archive (created if missing), verify it, optionally remove the source."""
+        from src.core.archives import write_archive, verify_archive
+
+        archive_path = self._resolve_template(self.destination or self.template, context)
+        if not archive_path:
+            return {'success': False, 'error': 'No archive destination specified'}
+
+        try:
+            manifest = write_archive([str(file_path)], archive_path, base_dir=str(file_path.parent), append=True)
+            verification = verify_archive(archive_path, manifest)
+            if not verification['success']:
+                return {'success': False, 'error': f"Archive verification failed: {verification['message']}"}
+
+            if self.remove_source:
+                file_path.unlink()
+
+            return {
+                'success': True,
+                'action': 'compress',
+                'from': str(file_path),
+                'to': archive_path,
+                'source_removed': self.remove_source
+            }
+        except Exception as e:
+            return {'success': False, 'error': str(e)}
+
     def _execute_script(self, file_path: Path, context: Dict[str, Any]) -> Dict[str, Any]:
         """Execute Python script action."""
         if not self.script:
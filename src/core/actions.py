@@ -8,6 +8,10 @@ This module handles file operations (move, rename, delete, archive) based on
 classification results. It supports dry-run mode, undo functionality, and
 comprehensive logging of all actions.
 
+Every applied action is recorded in the operation journal, so moves,
+renames, deletes (which go to the trash holding directory) and batch
+applies can be stepped back and forth on the undo/redo stack.
+
 NOTICE: This software is proprietary and confidential. Unauthorized copying,
 modification, distribution, or use is strictly prohibited.
 See LICENSE.txt for full terms and conditions.
@@ -21,10 +25,12 @@ import os
 import shutil
 import logging
 import asyncio
+import threading
+from contextlib import contextmanager
 from concurrent.futures import ThreadPoolExecutor
 from pathlib import Path
 from datetime import datetime
-from typing import Dict, Any, Optional, List
+from typing import Dict, Any, Optional, List, Iterator
 import json
 
 # Import new libraries
@@ -38,7 +44,14 @@ except (ImportError, SyntaxError):
 
 # Import Safety Guardian for final safety checks
 from .safety_guardian import SafetyGuardian
+from .split_archives import split_part_info, split_archive_parts, unit_base_for, renamed_part
+from .raw_pairs import raw_partner, raw_destination, is_raw, DEFAULT_RAW_TREE
+from .journal import OperationJournal, trash_root
+from .versions import VersionStore
+from .file_names import validate_file_name
+from .fsops import move_file
 from src.utils.logger import get_logger
+from src.utils.structured_logging import collect_timings, merge_timings, timed
 from src.utils.error_handler import (
     FileOperationError, ClassificationError, DatabaseError,
     SafetyViolationError, ConfigurationError, WatcherError
@@ -57,9 +70,11 @@ class ActionManager:
         db_manager: Database manager for logging
         dry_run (bool): If True, simulate actions without actually performing them
         undo_history (List): Stack of recent actions for undo functionality
+        journal (OperationJournal): Journal backing the persistent undo/redo stack
     """
 
-    def __init__(self, config, db_manager, dry_run: Optional[bool] = None, ollama_client=None):
+    def __init__(self, config, db_manager, dry_run: Optional[bool] = None, ollama_client=None,
+                 journal: Optional[OperationJournal] = None):
         """
         Initialize action manager.
 
@@ -68,12 +83,16 @@ class ActionManager:
             db_manager: Database manager instance
             dry_run (bool, optional): Override config dry_run setting
             ollama_client: Optional Ollama client for AI safety checks
+            journal (OperationJournal, optional): Journal; created from db_manager if omitted
         """
         self.config = config
         self.db_manager = db_manager
         self.dry_run = dry_run if dry_run is not None else config.dry_run
         self.undo_history: List[Dict[str, Any]] = []
         self.max_undo_history = 50  # Keep last 50 actions
+        self.journal = journal or OperationJournal(db_manager)
+        self._journal_local = threading.local()
+        self.versions = VersionStore(config, db_manager)
 
         # Initialize Safety Guardian for final evaluation
         self.safety_guardian = SafetyGuardian(config, ollama_client)
@@ -174,13 +193,29 @@ class ActionManager:
             action_type = action_result['action_type']
             new_path = action_result['new_path']
 
+            # Split archives (.zip.001, .part1.rar, .z01) move as one unit
+            parts = split_archive_parts(path)
+            if len(parts) > 1:
+                with timed('move'):
+                    return self._execute_split_archive(path, parts, new_path.parent, action_type,
+                                                       classification, user_approved)
+
+            # RAW+JPEG pairs move together and keep sharing a name
+            partner = raw_partner(path)
+            if partner is not None:
+                with timed('move'):
+                    return self._execute_raw_pair(path, partner, new_path, action_type,
+                                                  classification, user_approved)
+
             # Step 4: Safety Guardian check
             safety_result = self._perform_safety_check(path, new_path, action_type, classification, user_approved)
             if not safety_result['approved']:
                 return safety_result['result']
 
             # Step 5: Execute the action
-            execution_result = self._execute_determined_action(path, new_path, action_type, classification, user_approved)
+            with timed('move'):
+                execution_result = self._execute_determined_action(path, new_path, action_type,
+                                                                   classification, user_approved)
             return execution_result
 
         except (FileOperationError, SafetyViolationError, ConfigurationError) as e:
@@ -334,11 +369,13 @@ class ActionManager:
         """Determine action type and build destination path."""
         suggested_path = classification.get('suggested_path')
         suggested_rename = classification.get('rename')
+        replace = self._conflict_strategy(classification) == 'replace'
 
         # Build new path with path traversal validation
         if suggested_path:
             try:
-                new_path = self._build_destination_path(path, suggested_path, suggested_rename)
+                base_dir = self._destination_root(classification)
+                new_path = self._build_destination_path(path, suggested_path, suggested_rename, replace, base_dir)
                 action_type = 'move'
             except ValueError as e:
                 return {
@@ -374,6 +411,32 @@ class ActionManager:
             'new_path': new_path
         }
 
+    def preview_destination(self, file_path: str, classification: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Work out what execute() would do with a file, without touching it.
+
+        Args:
+            file_path (str): Current file path
+            classification (Dict): Classification result
+
+        Returns:
+            Dict: 'action' ('move', 'rename', 'none' or 'blocked'), 'new_path' and 'message'
+        """
+        determined = self._determine_action(Path(file_path), classification)
+        if not determined['determined']:
+            result = determined['result']
+            return {'action': result['action'], 'new_path': None, 'message': result['message']}
+        return {'action': determined['action_type'], 'new_path': str(determined['new_path']), 'message': ''}
+
+    def _conflict_strategy(self, classification: Dict[str, Any]) -> str:
+        """
+        How to resolve an existing destination: 'keep_both' or 'replace'.
+
+        A classification's 'on_conflict' overrides the configured default.
+        """
+        strategy = classification.get('on_conflict') or getattr(self.config, 'conflict_resolution', None)
+        return strategy if strategy in ('keep_both', 'replace') else 'keep_both'
+
     def _perform_safety_check(self, path: Path, new_path: Path, action_type: str,
                              classification: Dict[str, Any], user_approved: bool) -> Dict[str, Any]:
         """Perform Safety Guardian evaluation."""
@@ -421,13 +484,32 @@ class ActionManager:
         return {'approved': True}
 
     def _execute_determined_action(self, path: Path, new_path: Path, action_type: str,
-                                  classification: Dict[str, Any], user_approved: bool) -> Dict[str, Any]:
-        """Execute the determined action and handle logging."""
+                                  classification: Dict[str, Any], user_approved: bool,
+                                  count: bool = True) -> Dict[str, Any]:
+        """Execute the determined action and handle logging (count=False keeps it out of the stats)."""
         # Perform the action
+        replaced: Optional[Dict[str, Any]] = None
         if self.dry_run:
             result = self._dry_run_action(path, new_path, action_type)
         else:
+            try:
+                replaced = self._keep_replaced_version(path, new_path)
+            except OSError as e:
+                return {
+                    'success': False,
+                    'action': 'error',
+                    'old_path': str(path),
+                    'new_path': str(new_path),
+                    'time_saved': 0.0,
+                    'message': f'Could not keep a version of {new_path} before replacing it: {e}'
+                }
             result = self._perform_action(path, new_path, action_type)
+            if replaced and not result['success']:
+                self.versions.restore_version(replaced['id'], str(new_path))
+            if result['success']:
+                new_path = Path(result['new_path'])
+        if replaced:
+            result['replaced_version'] = replaced['id']
 
         # Log action to database and file system
         if result['success']:
@@ -442,7 +524,9 @@ class ActionManager:
                 time_saved=time_saved,
                 category=classification.get('category'),
                 ai_suggested=classification.get('method') == 'ai',
-                user_approved=user_approved
+                user_approved=user_approved,
+                model_name=classification.get('model'),
+                count_stats=count
             )
 
             try:
@@ -458,6 +542,12 @@ class ActionManager:
 
             result['time_saved'] = time_saved
 
+            if not self.dry_run:
+                details = None
+                if replaced:
+                    details = {'replaced_version': replaced['id'], 'version_object': replaced['object_path']}
+                self._journal_move(action_type, path, new_path, details)
+
             # Add to undo history
             self._add_to_undo_history({
                 'action': action_type,
@@ -470,6 +560,261 @@ class ActionManager:
 
         return result
 
+    def _keep_replaced_version(self, path: Path, new_path: Path) -> Optional[Dict[str, Any]]:
+        """
+        Keep an existing destination as a version and clear it for the move.
+
+        Args:
+            path (Path): File being moved
+            new_path (Path): Destination that may already exist
+
+        Returns:
+            Dict or None: Version record if a file is being replaced
+        """
+        if not new_path.is_file() or new_path.resolve() == path.resolve():
+            return None
+        version = self.versions.save_version(str(new_path), reason=f'replaced by {path.name}')
+        new_path.unlink()
+        return version
+
+    @contextmanager
+    def journal_scope(self, operation_id: Optional[int] = None, kind: str = 'apply',
+                      description: str = '') -> Iterator[Dict[str, Any]]:
+        """
+        Record every action performed inside the block as one journal operation.
+
+        Without an operation_id the operation is created on the first applied
+        action (dry runs create nothing) and completed when the block exits;
+        it is marked failed if the block raises. With an operation_id, steps
+        are added to that existing operation, which the caller completes.
+        Nested scopes join the outermost one. The stage timings of the block
+        (plus any in scope['timings'], such as a plan's) are stored with an
+        operation the scope created.
+
+        Args:
+            operation_id (int, optional): Existing operation to record into
+            kind (str): Operation type for a new operation (e.g. 'apply')
+            description (str): Summary for a new operation
+
+        Yields:
+            Dict: Scope state; 'operation_id' is set once a step is recorded
+        """
+        current = getattr(self._journal_local, 'scope', None)
+        if current is not None:
+            yield current
+            return
+
+        scope = {'operation_id': operation_id, 'kind': kind, 'description': description,
+                 'owned': operation_id is None, 'timings': None}
+        self._journal_local.scope = scope
+        failed = False
+        with collect_timings() as timings:
+            try:
+                yield scope
+            except BaseException:
+                failed = True
+                raise
+            finally:
+                self._journal_local.scope = None
+                if scope['owned'] and scope['operation_id'] is not None:
+                    try:
+                        self.journal.record_timings(scope['operation_id'],
+                                                    merge_timings(scope['timings'], timings.as_dict()))
+                    except Exception as e:
+                        logger.warning(f"Could not store timings of operation {scope['operation_id']}: {e}")
+                    self.journal.complete(scope['operation_id'], success=not failed)
+
+    def _journal_move(self, kind: str, source: Path, dest: Path,
+                      details: Optional[Dict[str, Any]] = None) -> None:
+        """
+        Journal an applied move so it appears on the undo stack.
+
+        Journal failures are logged but never fail the file operation itself.
+
+        Args:
+            kind (str): Operation type when the move is its own operation
+            source (Path): Original location
+            dest (Path): New location
+            details (Dict, optional): Extra step details
+        """
+        try:
+            scope = getattr(self._journal_local, 'scope', None)
+            if scope is None:
+                operation_id = self.journal.begin(kind, f"{kind.capitalize()} {source.name}")
+                self.journal.record_step(operation_id, 'move', str(source), str(dest), details)
+                self.journal.complete(operation_id)
+                return
+
+            if scope['operation_id'] is None:
+                scope['operation_id'] = self.journal.begin(
+                    scope['kind'], scope['description'] or f"{scope['kind'].capitalize()} files")
+            self.journal.record_step(scope['operation_id'], 'move', str(source), str(dest), details)
+        except Exception as e:
+            logger.warning(f"Could not journal {kind} of {source}: {e}")
+
+    def _execute_split_archive(self, path: Path, parts: List[Path], dest_dir: Path, action_type: str,
+                               classification: Dict[str, Any], user_approved: bool) -> Dict[str, Any]:
+        """
+        Move or rename every part of a split archive together.
+
+        A suggested rename is applied to the shared base name so volume
+        names keep matching each other. If any part cannot be moved, the
+        parts already moved are put back.
+
+        Args:
+            path (Path): Part the action was requested for
+            parts (List[Path]): All parts of the unit, primary first
+            dest_dir (Path): Destination directory
+            action_type (str): 'move' or 'rename'
+            classification (Dict): Classification result
+            user_approved (bool): Whether user explicitly approved this action
+
+        Returns:
+            Dict: Action result with a 'parts' list of old/new paths
+        """
+        info = split_part_info(path)
+        old_base = info.base
+        new_base = unit_base_for(classification.get('rename') or path.name, info.scheme) or old_base
+
+        # Resolve conflicts for the unit as a whole
+        base = new_base
+        counter = 1
+        while True:
+            targets = [dest_dir / renamed_part(p.name, old_base, base) for p in parts]
+            if not any(t.exists() and t not in parts for t in targets):
+                break
+            base = f"{new_base}_{counter}"
+            counter += 1
+
+        for part, target in zip(parts, targets):
+            safety_result = self._perform_safety_check(part, target, action_type, classification, user_approved)
+            if not safety_result['approved']:
+                result = safety_result['result']
+                result['message'] = f"Split archive part {part.name}: {result['message']}"
+                return result
+
+        moved: List[Dict[str, Any]] = []
+        try:
+            with self.journal_scope(kind=action_type, description=f"{action_type.capitalize()} split archive {path.name}"):
+                for part, target in zip(parts, targets):
+                    result = self._execute_determined_action(part, target, action_type, classification, user_approved)
+                    if not result['success']:
+                        raise FileOperationError(result.get('message', 'Action failed'),
+                                                 file_path=str(part), operation=action_type)
+                    moved.append(result)
+        except FileOperationError as e:
+            if not self.dry_run:
+                for result in reversed(moved):
+                    try:
+                        shutil.move(result['new_path'], result['old_path'])
+                    except (OSError, shutil.Error) as rollback_error:
+                        logger.error(f"Could not restore split archive part {result['new_path']}: {rollback_error}")
+            return {
+                'success': False,
+                'action': 'error',
+                'old_path': str(path),
+                'new_path': None,
+                'time_saved': 0.0,
+                'message': f'Split archive not moved: {e}'
+            }
+
+        primary = moved[0]
+        return {
+            'success': True,
+            'action': primary['action'],
+            'old_path': str(path),
+            'new_path': next(r['new_path'] for r in moved if r['old_path'] == str(path)),
+            'time_saved': sum(r.get('time_saved', 0.0) for r in moved),
+            'message': f"{primary['message']} ({len(moved)} split archive parts)",
+            'parts': [{'old_path': r['old_path'], 'new_path': r['new_path']} for r in moved]
+        }
+
+    def _execute_raw_pair(self, path: Path, partner: Path, new_path: Path, action_type: str,
+                          classification: Dict[str, Any], user_approved: bool) -> Dict[str, Any]:
+        """
+        Move or rename both files of a RAW+JPEG pair together.
+
+        The RAW takes the JPEG's new name with its own extension and goes
+        next to it, or into the raw_pairs.raw_tree tree when
+        raw_pairs.separate_raw is set. The pair counts as one file in the
+        statistics. If the second file cannot be moved, the first is put back.
+
+        Args:
+            path (Path): File the action was requested for
+            partner (Path): The other file of the pair
+            new_path (Path): Destination determined for path
+            action_type (str): 'move' or 'rename'
+            classification (Dict): Classification result
+            user_approved (bool): Whether user explicitly approved this action
+
+        Returns:
+            Dict: Action result with a 'pair' list of old/new paths
+        """
+        settings = getattr(self.config, 'raw_pair_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        jpeg, raw = (partner, path) if is_raw(path) else (path, partner)
+        try:
+            root = self._destination_root(classification) or Path(self.config.base_destination).expanduser().resolve()
+        except (AttributeError, OSError, TypeError):
+            root = None
+
+        # Resolve conflicts for the pair as a whole
+        stem, counter = new_path.stem, 1
+        while True:
+            jpeg_target = new_path.with_name(stem + jpeg.suffix)
+            raw_target = raw_destination(jpeg_target, raw, root, bool(settings.get('separate_raw')),
+                                         settings.get('raw_tree') or DEFAULT_RAW_TREE)
+            if not any(t.exists() and t not in (jpeg, raw) for t in (jpeg_target, raw_target)):
+                break
+            stem = f"{new_path.stem}_{counter}"
+            counter += 1
+
+        moves = [(path, jpeg_target if path == jpeg else raw_target),
+                 (partner, jpeg_target if partner == jpeg else raw_target)]
+        for source, target in moves:
+            safety_result = self._perform_safety_check(source, target, action_type, classification, user_approved)
+            if not safety_result['approved']:
+                result = safety_result['result']
+                result['message'] = f"RAW+JPEG pair file {source.name}: {result['message']}"
+                return result
+
+        moved: List[Dict[str, Any]] = []
+        try:
+            with self.journal_scope(kind=action_type, description=f"{action_type.capitalize()} {jpeg.name} (RAW+JPEG)"):
+                for source, target in moves:
+                    result = self._execute_determined_action(source, target, action_type, classification,
+                                                             user_approved, count=not moved)
+                    if not result['success']:
+                        raise FileOperationError(result.get('message', 'Action failed'),
+                                                 file_path=str(source), operation=action_type)
+                    moved.append(result)
+        except FileOperationError as e:
+            if not self.dry_run:
+                for result in reversed(moved):
+                    try:
+                        shutil.move(result['new_path'], result['old_path'])
+                    except (OSError, shutil.Error) as rollback_error:
+                        logger.error(f"Could not restore {result['new_path']}: {rollback_error}")
+            return {
+                'success': False,
+                'action': 'error',
+                'old_path': str(path),
+                'new_path': None,
+                'time_saved': 0.0,
+                'message': f'RAW+JPEG pair not moved: {e}'
+            }
+
+        primary = moved[0]
+        return {
+            'success': True,
+            'action': primary['action'],
+            'old_path': str(path),
+            'new_path': primary['new_path'],
+            'time_saved': primary.get('time_saved', 0.0),
+            'message': f"{primary['message']} (with {partner.name})",
+            'pair': [{'old_path': r['old_path'], 'new_path': r['new_path']} for r in moved]
+        }
+
     async def async_execute_determined_action(self, path: Path, new_path: Path, action_type: str,
                                        classification: Dict[str, Any], user_approved: bool) -> Dict[str, Any]:
         """
@@ -563,8 +908,39 @@ class ActionManager:
         except (ValueError, OSError) as e:
             return False, f"Path escapes base directory: {str(e)}"
 
+    def _destination_root(self, classification: Dict[str, Any]) -> Optional[Path]:
+        """
+        Resolve the 'destination_root' a planner may set instead of base_destination.
+
+        Args:
+            classification (Dict): Classification result
+
+        Returns:
+            Path or None: Absolute destination root, or None for base_destination
+
+        Raises:
+            ValueError: If the root is relative or blacklisted
+        """
+        root = classification.get('destination_root')
+        if not root:
+            return None
+        root_path = Path(root).expanduser()
+        if not root_path.is_absolute():
+            raise ValueError("Destination root must be an absolute path")
+        resolved = root_path.resolve()
+        for blocked in getattr(self.config, 'path_blacklist', []) or []:
+            try:
+                blocked_path = str(Path(blocked).expanduser().resolve())
+                inside = os.path.commonpath([str(resolved), blocked_path]) == blocked_path
+            except (OSError, TypeError, ValueError):
+                continue  # e.g. different drives
+            if inside:
+                raise ValueError(f"Destination root is blacklisted ({blocked_path})")
+        return resolved
+
     def _build_destination_path(self, source_path: Path, suggested_path: str,
-                                suggested_rename: Optional[str] = None) -> Path:
+                                suggested_rename: Optional[str] = None, replace: bool = False,
+                                base_dir: Optional[Path] = None) -> Path:
         """
         Build complete destination path for file with path traversal protection.
 
@@ -572,6 +948,9 @@ class ActionManager:
             source_path (Path): Current file path
             suggested_path (str): Suggested destination directory
             suggested_rename (str, optional): Suggested new filename
+            replace (bool): Keep an existing destination path (it will be
+                            replaced) instead of adding a counter
+            base_dir (Path, optional): Root to build under instead of base_destination
 
         Returns:
             Path: Complete destination path
@@ -580,10 +959,11 @@ class ActionManager:
             ValueError: If path validation fails (path traversal attempt)
         """
         # Use configured base destination (CRITICAL FIX #2)
-        try:
-            base_dir = Path(self.config.base_destination).expanduser().resolve()
-        except (AttributeError, OSError):
-            base_dir = Path.home()  # Fallback only on error
+        if base_dir is None:
+            try:
+                base_dir = Path(self.config.base_destination).expanduser().resolve()
+            except (AttributeError, OSError):
+                base_dir = Path.home()  # Fallback only on error
 
         # Validate path safety (MEDIUM #3 FIX - Security)
         is_safe, error_msg = self._validate_path_safety(suggested_path, base_dir)
@@ -605,7 +985,7 @@ class ActionManager:
         # Handle filename conflicts
         dest_path = dest_dir / filename
 
-        if dest_path.exists() and dest_path != source_path:
+        if dest_path.exists() and dest_path != source_path and not (replace and dest_path.is_file()):
             # Add counter to filename
             stem = dest_path.stem
             suffix = dest_path.suffix
@@ -652,11 +1032,16 @@ class ActionManager:
                         operation=action
                     ) from e
 
-                # Ensure destination directory exists
-                destination.parent.mkdir(parents=True, exist_ok=True)
-
-                # Perform move/rename
-                shutil.move(str(source), str(destination))
+                # Never overwrites a file that appeared meanwhile; verified across drives
+                moved = move_file(str(source), str(destination), on_conflict='keep_both')
+                if moved['status'] != 'moved':
+                    raise FileOperationError(
+                        moved['message'],
+                        file_path=str(source),
+                        destination=str(destination),
+                        operation=action
+                    )
+                destination = Path(moved['destination'])
 
             return {
                 'success': True,
@@ -704,13 +1089,18 @@ class ActionManager:
             'message': f'[DRY RUN] Would {action} file to {destination}'
         }
 
-    def delete_file(self, file_path: str, reason: str = "User requested") -> Dict[str, Any]:
+    def delete_file(self, file_path: str, reason: str = "User requested",
+                    permanent: bool = False) -> Dict[str, Any]:
         """
         Delete a file.
 
+        By default the file is moved to the trash holding directory so the
+        delete can be undone; permanent deletes cannot.
+
         Args:
             file_path (str): Path to file to delete
-            _reason (str): Reason for deletion (currently unused, reserved for future logging)
+            reason (str): Reason for deletion, stored with the journal entry
+            permanent (bool): Remove the file instead of trashing it
 
         Returns:
             Dict: Result information
@@ -731,10 +1121,17 @@ class ActionManager:
                     self._logger.log_operation('DELETE', str(path), str(path), 'DELETED', 'DRY_RUN')
                 except Exception:
                     pass
-            else:
+            elif permanent:
                 path.unlink()
                 message = f'Deleted {path}'
+            else:
+                trash_dest = trash_root(self.config) / datetime.now().strftime('%Y%m%d_%H%M%S_%f') / path.name
+                trash_dest.parent.mkdir(parents=True, exist_ok=True)
+                shutil.move(str(path), str(trash_dest))
+                self._journal_move('delete', path, trash_dest, {'role': 'trash', 'reason': reason})
+                message = f'Deleted {path} (moved to trash)'
 
+            if not self.dry_run:
                 # Log deletion
                 time_saved = self.config.time_estimates.get('delete', 0.2)
                 self.db_manager.log_action(
@@ -809,6 +1206,7 @@ class ActionManager:
 
                 # Move to archive
                 shutil.move(str(path), str(dest_path))
+                self._journal_move('archive', path, dest_path)
                 message = f'Archived to {dest_path}'
 
                 # Log action
@@ -837,60 +1235,116 @@ class ActionManager:
                 'message': f'Error archiving file: {str(e)}'
             }
 
-    def undo_last_action(self) -> Dict[str, Any]:
+    def rename_file(self, file_path: str, new_name: str, rules: str = 'portable') -> Dict[str, Any]:
         """
-        Undo the last file operation.
+        Rename a file or folder in place, after checking the new name.
 
-        Returns:
-            Dict: Result of undo operation
-        """
-        if not self.undo_history:
-            return {
-                'success': False,
-                'message': 'No actions to undo'
-            }
+        The rename is journaled, so it can be undone like any other action.
 
-        # Get last action from database
-        last_action = self.db_manager.undo_last_action()
+        Args:
+            file_path (str): File or folder to rename
+            new_name (str): New name (no folder part)
+            rules (str): Naming rules to check against (see validate_file_name)
 
-        if not last_action:
+        Returns:
+            Dict: Result information; a rejected name also has 'problems' and
+                  'suggestion'
+        """
+        path = Path(file_path)
+        try:
+            check = validate_file_name(new_name, rules, parent=str(path.parent))
+        except ValueError as e:
+            return {'success': False, 'action': 'rename', 'message': str(e)}
+        if not check['valid']:
             return {
                 'success': False,
-                'message': 'No undoable actions in database'
+                'action': 'rename',
+                'old_path': str(path),
+                'message': f"Invalid name: {'; '.join(check['problems'])}",
+                'problems': check['problems'],
+                'suggestion': check['suggestion']
             }
+        if not path.exists() and not path.is_symlink():
+            return {'success': False, 'action': 'rename', 'message': 'File not found'}
+
+        dest_path = path.with_name(new_name)
+        if dest_path.name == path.name:
+            return {'success': True, 'action': 'rename', 'old_path': str(path), 'new_path': str(path),
+                    'message': 'Name unchanged'}
+        # A case-only rename on a case-insensitive file system finds "itself" at the destination
+        same_file = dest_path.exists() and dest_path.samefile(path)
+        if dest_path.exists() and not same_file:
+            return {'success': False, 'action': 'rename', 'old_path': str(path),
+                    'message': f'A file named {new_name} already exists'}
+
+        checked = self._check_policies_and_security(path, str(path), None)
+        if not checked['allowed']:
+            return checked['result']
 
         try:
-            old_path = Path(last_action['old_path'])
-            new_path = Path(last_action['new_path']) if last_action['new_path'] else None
-
-            if not new_path or not new_path.exists():
-                return {
-                    'success': False,
-                    'message': 'Cannot undo: destination file not found'
-                }
-
             if self.dry_run:
-                message = f'[DRY RUN] Would undo: move {new_path} back to {old_path}'
+                message = f'[DRY RUN] Would rename to {new_name}'
             else:
-                # Ensure original directory exists
-                old_path.parent.mkdir(parents=True, exist_ok=True)
-
-                # Move back
-                shutil.move(str(new_path), str(old_path))
-                message = f'Undone: restored {old_path}'
+                if same_file:
+                    step = path.with_name(f".{path.name}.renaming")
+                    path.rename(step)
+                    step.rename(dest_path)
+                else:
+                    path.rename(dest_path)
+                self._journal_move('rename', path, dest_path)
+                message = f'Renamed to {new_name}'
+
+                time_saved = self.config.time_estimates.get('rename', 0.3)
+                self.db_manager.log_action(
+                    filename=new_name,
+                    old_path=str(path),
+                    new_path=str(dest_path),
+                    operation='rename',
+                    time_saved=time_saved,
+                    user_approved=True
+                )
 
             return {
                 'success': True,
-                'action': 'undo',
+                'action': 'rename',
+                'old_path': str(path),
+                'new_path': str(dest_path),
                 'message': message
             }
 
-        except Exception as e:
+        except OSError as e:
             return {
                 'success': False,
-                'message': f'Error undoing action: {str(e)}'
+                'action': 'rename',
+                'old_path': str(path),
+                'message': f'Error renaming file: {str(e)}'
             }
 
+    def undo_last_action(self) -> Dict[str, Any]:
+        """
+        Undo the operation on top of the undo stack.
+
+        The stack is persistent and spans moves, renames, deletes, archives
+        and grouped applies, so repeated calls keep stepping back.
+
+        Returns:
+            Dict: Result of undo operation
+        """
+        result = self.journal.undo_last(dry_run=self.dry_run)
+        result['action'] = 'undo'
+        return result
+
+    def redo_last_action(self) -> Dict[str, Any]:
+        """
+        Redo the most recently undone operation.
+
+        Returns:
+            Dict: Result of redo operation
+        """
+        result = self.journal.redo_last(dry_run=self.dry_run)
+        result['action'] = 'redo'
+        return result
+
     def _add_to_undo_history(self, action: Dict[str, Any]):
         """
         Add action to undo history.
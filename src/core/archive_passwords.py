@@ -0,0 +1,320 @@
+"""
+Archive Password Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module brokers passwords for encrypted archives. When an archive
+workflow meets an encrypted archive it asks the broker for a password; the
+broker first tries a password remembered in the system keyring, otherwise
+it emits an 'archive_password_required' event and waits for the UI (or CLI)
+to answer the request. A password is only remembered after it actually
+unlocked the archive, and only if the user asked for it.
+
+Keyring storage uses the optional `keyring` package; without it passwords
+are never persisted.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import hashlib
+import logging
+import threading
+import uuid
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Callable
+
+try:
+    import keyring  # type: ignore
+    KEYRING_SUPPORT = True
+except ImportError:
+    keyring = None  # type: ignore
+    KEYRING_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+KEYRING_SERVICE = 'ai-file-organiser-archives'
+DEFAULT_PROMPT_TIMEOUT = 300.0
+
+
+def archive_identity(archive_path: str) -> str:
+    """
+    Key under which an archive's password is remembered.
+
+    Uses the file name and size so the password survives the archive being
+    moved, without storing the full path in the keyring.
+
+    Args:
+        archive_path (str): Archive path
+
+    Returns:
+        str: Stable identifier
+    """
+    path = Path(archive_path)
+    try:
+        size = path.stat().st_size
+    except OSError:
+        size = 0
+    return hashlib.sha256(f"{path.name.lower()}:{size}".encode('utf-8')).hexdigest()[:32]
+
+
+class _PasswordRequest:
+    """A pending password request waiting for an answer."""
+
+    def __init__(self, archive_path: str, attempt: int, error: Optional[str]):
+        self.id = uuid.uuid4().hex
+        self.archive_path = archive_path
+        self.attempt = attempt
+        self.error = error
+        self.created_at = datetime.now().isoformat()
+        self.answered = threading.Event()
+        self.password: Optional[str] = None
+        self.remember = False
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            'request_id': self.id,
+            'archive': self.archive_path,
+            'name': Path(self.archive_path).name,
+            'attempt': self.attempt,
+            'error': self.error,
+            'created_at': self.created_at
+        }
+
+
+class ArchivePasswordBroker:
+    """
+    Round-trips password prompts between archive workflows and the UI.
+
+    Attributes:
+        config: Configuration object
+        timeout (float): Seconds to wait for an answer before giving up
+    """
+
+    def __init__(self, config=None, timeout: Optional[float] = None):
+        """
+        Initialize broker.
+
+        Args:
+            config: Configuration object (reads `archives.passwords`)
+            timeout (float, optional): Prompt timeout override in seconds
+        """
+        settings = getattr(config, 'archive_password_settings', None)
+        if not isinstance(settings, dict):
+            settings = {}
+        self.config = config
+        self.timeout = timeout if timeout is not None else float(
+            settings.get('prompt_timeout_seconds', DEFAULT_PROMPT_TIMEOUT))
+        self.use_keyring = bool(settings.get('use_keyring', True)) and KEYRING_SUPPORT
+        self._listeners: List[Callable[[Dict[str, Any]], None]] = []
+        self._pending: Dict[str, _PasswordRequest] = {}
+        self._remember_on_success: Dict[str, str] = {}
+        self._lock = threading.Lock()
+
+    # ==================== Events ====================
+
+    def add_listener(self, callback: Callable[[Dict[str, Any]], None]) -> None:
+        """
+        Register a callback for password events.
+
+        Callbacks receive dicts with 'type' ('archive_password_required' or
+        'archive_password_resolved') plus the request fields.
+
+        Args:
+            callback (Callable): Event handler
+        """
+        self._listeners.append(callback)
+
+    def _emit(self, event: Dict[str, Any]) -> None:
+        for callback in list(self._listeners):
+            try:
+                callback(event)
+            except Exception as e:
+                logger.warning(f"Password event listener failed: {e}")
+
+    def pending(self) -> List[Dict[str, Any]]:
+        """
+        List password requests waiting for an answer.
+
+        Returns:
+            List[Dict]: Pending requests, oldest first
+        """
+        with self._lock:
+            return [r.to_dict() for r in self._pending.values()]
+
+    # ==================== Prompting ====================
+
+    def request_password(self, archive_path: str, attempt: int = 1,
+                         error: Optional[str] = None) -> Optional[str]:
+        """
+        Get a password for an encrypted archive.
+
+        On the first attempt a remembered password is returned if there is
+        one; otherwise an event is emitted and the call blocks until the
+        request is answered, cancelled or times out.
+
+        Args:
+            archive_path (str): Encrypted archive
+            attempt (int): 1 for the first try, higher after a wrong password
+            error (str, optional): Why the previous attempt failed
+
+        Returns:
+            str or None: Password, or None if none was given
+        """
+        if attempt == 1:
+            stored = self.stored_password(archive_path)
+            if stored:
+                logger.info(f"Using remembered password for {Path(archive_path).name}")
+                return stored
+
+        request = _PasswordRequest(archive_path, attempt, error)
+        with self._lock:
+            self._pending[request.id] = request
+
+        self._emit({'type': 'archive_password_required', **request.to_dict()})
+        if not self._listeners:
+            logger.info(f"Password required for {archive_path} (request {request.id})")
+
+        answered = request.answered.wait(self.timeout)
+
+        with self._lock:
+            self._pending.pop(request.id, None)
+
+        if not answered:
+            logger.info(f"Password request for {archive_path} timed out")
+        self._emit({'type': 'archive_password_resolved', **request.to_dict(),
+                    'provided': answered and request.password is not None})
+
+        if not answered or request.password is None:
+            return None
+        if request.remember:
+            self._remember_on_success[archive_path] = request.password
+        return request.password
+
+    def answer(self, request_id: str, password: Optional[str], remember: bool = False) -> bool:
+        """
+        Answer a pending request.
+
+        Args:
+            request_id (str): Request ID from the event
+            password (str or None): Password, or None to cancel
+            remember (bool): Store the password in the keyring once it works
+
+        Returns:
+            bool: False if the request is unknown or already resolved
+        """
+        with self._lock:
+            request = self._pending.get(request_id)
+        if request is None:
+            return False
+        request.password = password
+        request.remember = remember and password is not None
+        request.answered.set()
+        return True
+
+    def cancel_all(self) -> int:
+        """
+        Cancel every pending request (e.g. on shutdown).
+
+        Returns:
+            int: Number of requests cancelled
+        """
+        with self._lock:
+            requests = list(self._pending.values())
+        for request in requests:
+            request.answered.set()
+        return len(requests)
+
+    # ==================== Keyring ====================
+
+    def password_accepted(self, archive_path: str, password: str) -> bool:
+        """
+        Report that a password unlocked an archive.
+
+        Stores it in the keyring if the user asked to remember it.
+
+        Args:
+            archive_path (str): Archive path
+            password (str): Password that worked
+
+        Returns:
+            bool: True if the password was stored
+        """
+        if self._remember_on_success.pop(archive_path, None) != password:
+            return False
+        return self.remember(archive_path, password)
+
+    def password_rejected(self, archive_path: str) -> None:
+        """
+        Report that a password did not unlock an archive.
+
+        A remembered password that no longer works is forgotten.
+
+        Args:
+            archive_path (str): Archive path
+        """
+        self._remember_on_success.pop(archive_path, None)
+        if self.stored_password(archive_path):
+            self.forget(archive_path)
+
+    def stored_password(self, archive_path: str) -> Optional[str]:
+        """
+        Look up a remembered password.
+
+        Args:
+            archive_path (str): Archive path
+
+        Returns:
+            str or None: Password, or None if none is stored
+        """
+        if not self.use_keyring:
+            return None
+        try:
+            return keyring.get_password(KEYRING_SERVICE, archive_identity(archive_path))
+        except Exception as e:
+            logger.warning(f"Keyring lookup failed: {e}")
+            return None
+
+    def remember(self, archive_path: str, password: str) -> bool:
+        """
+        Store a password in the keyring.
+
+        Args:
+            archive_path (str): Archive path
+            password (str): Password
+
+        Returns:
+            bool: True if stored
+        """
+        if not self.use_keyring:
+            return False
+        try:
+            keyring.set_password(KEYRING_SERVICE, archive_identity(archive_path), password)
+            return True
+        except Exception as e:
+            logger.warning(f"Could not store archive password in keyring: {e}")
+            return False
+
+    def forget(self, archive_path: str) -> bool:
+        """
+        Remove a remembered password.
+
+        Args:
+            archive_path (str): Archive path
+
+        Returns:
+            bool: True if a password was removed
+        """
+        if not self.use_keyring:
+            return False
+        try:
+            keyring.delete_password(KEYRING_SERVICE, archive_identity(archive_path))
+            return True
+        except Exception:
+            return False
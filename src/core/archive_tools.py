@@ -0,0 +1,394 @@
+"""
+External Archive Tools Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module detects and drives external archive tools so archive workflows
+can handle the formats Python cannot read natively: 7z (via `7z`, `7za` or
+`7zz`) and RAR (via `unrar`, or 7-Zip as a fallback). Tools are looked up
+in the configured paths, a bundled `tools/` directory, PATH and the usual
+Windows install locations.
+
+A capability report describes which formats can be listed, extracted and
+created with the tools found on this machine, for display in the UI.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import shutil
+import subprocess
+from pathlib import Path
+from typing import Dict, Any, Collection, List, Optional
+
+from src.utils.error_handler import FileOperationError, ArchivePasswordError
+
+logger = logging.getLogger(__name__)
+
+
+PROJECT_ROOT = Path(__file__).parent.parent.parent
+BUNDLED_TOOLS_DIR = PROJECT_ROOT / "tools"
+
+TOOL_CANDIDATES = {
+    '7z': ['7z', '7zz', '7za'],
+    'unrar': ['unrar', 'UnRAR'],
+}
+
+WINDOWS_LOCATIONS = {
+    '7z': [r'C:\Program Files\7-Zip\7z.exe', r'C:\Program Files (x86)\7-Zip\7z.exe'],
+    'unrar': [r'C:\Program Files\WinRAR\UnRAR.exe', r'C:\Program Files (x86)\WinRAR\UnRAR.exe'],
+}
+
+LIST_TIMEOUT_SECONDS = 60
+EXTRACT_TIMEOUT_SECONDS = 600
+
+# Tool output that means the archive needs a (different) password
+PASSWORD_ERROR_MARKERS = (
+    'wrong password', 'incorrect password', 'password is incorrect',
+    'can not open encrypted archive', 'enter password', 'encrypted file',
+)
+
+
+class ArchiveToolkit:
+    """
+    Locates 7-Zip / UnRAR and runs them for listing and extraction.
+
+    Attributes:
+        config: Configuration object
+    """
+
+    def __init__(self, config=None):
+        """
+        Initialize toolkit.
+
+        Args:
+            config: Configuration object (reads `archives.tools`)
+        """
+        self.config = config
+        self._resolved: Dict[str, Optional[str]] = {}
+
+    # ==================== Tool Discovery ====================
+
+    def find_tool(self, tool: str) -> Optional[str]:
+        """
+        Find an executable for a tool ('7z' or 'unrar').
+
+        Args:
+            tool (str): Tool key
+
+        Returns:
+            str or None: Executable path
+        """
+        if tool in self._resolved:
+            return self._resolved[tool]
+
+        configured = (getattr(self.config, 'archive_tool_paths', None) or {}).get(tool)
+        candidates: List[str] = []
+        if configured:
+            candidates.append(os.path.expanduser(configured))
+
+        for name in TOOL_CANDIDATES.get(tool, []):
+            for suffix in ('', '.exe'):
+                candidates.append(str(BUNDLED_TOOLS_DIR / f"{name}{suffix}"))
+
+        found = None
+        for candidate in candidates:
+            if Path(candidate).is_file() and os.access(candidate, os.X_OK):
+                found = candidate
+                break
+
+        if found is None:
+            for name in TOOL_CANDIDATES.get(tool, []):
+                found = shutil.which(name)
+                if found:
+                    break
+
+        if found is None and os.name == 'nt':
+            found = next((p for p in WINDOWS_LOCATIONS.get(tool, []) if Path(p).is_file()), None)
+
+        self._resolved[tool] = found
+        return found
+
+    def tool_version(self, tool: str) -> Optional[str]:
+        """
+        Get the banner/version line of a tool.
+
+        Args:
+            tool (str): Tool key
+
+        Returns:
+            str or None: Version line, or None if the tool is missing
+        """
+        executable = self.find_tool(tool)
+        if not executable:
+            return None
+        try:
+            proc = subprocess.run([executable], capture_output=True, text=True, timeout=5)
+        except (OSError, subprocess.TimeoutExpired):
+            return None
+        for line in (proc.stdout + proc.stderr).splitlines():
+            if '7-Zip' in line or 'RAR' in line.upper():
+                return line.strip()
+        return None
+
+    def backend_for(self, fmt: str) -> Optional[str]:
+        """
+        Choose the tool used for a format.
+
+        Args:
+            fmt (str): '7z' or 'rar'
+
+        Returns:
+            str or None: Tool key, or None if no tool can handle the format
+        """
+        if fmt == 'rar' and self.find_tool('unrar'):
+            return 'unrar'
+        if fmt in ('7z', 'rar') and self.find_tool('7z'):
+            return '7z'
+        return None
+
+    def capabilities(self) -> Dict[str, Any]:
+        """
+        Report available tools and what each archive format supports.
+
+        Returns:
+            Dict: {'tools': {tool: {available, path, version}},
+                   'formats': {fmt: {list, extract, create, backend}}}
+        """
+        tools = {}
+        for tool in TOOL_CANDIDATES:
+            path = self.find_tool(tool)
+            tools[tool] = {
+                'available': path is not None,
+                'path': path,
+                'version': self.tool_version(tool) if path else None
+            }
+
+        builtin = {'list': True, 'extract': True, 'create': True, 'backend': 'builtin'}
+        formats: Dict[str, Any] = {'zip': dict(builtin), 'tar': dict(builtin)}
+        for fmt in ('7z', 'rar'):
+            backend = self.backend_for(fmt)
+            formats[fmt] = {
+                'list': backend is not None,
+                'extract': backend is not None,
+                'create': False,
+                'backend': backend
+            }
+
+        missing = [fmt for fmt in ('7z', 'rar') if not formats[fmt]['extract']]
+        hint = None
+        if missing:
+            hint = (f"Install 7-Zip (https://www.7-zip.org) or place 7z/unrar in {BUNDLED_TOOLS_DIR} "
+                    f"to enable: {', '.join(missing)}")
+
+        return {'tools': tools, 'formats': formats, 'hint': hint}
+
+    # ==================== Listing / Extraction ====================
+
+    def _require_backend(self, archive_path: Path, fmt: str) -> str:
+        backend = self.backend_for(fmt)
+        if backend is None:
+            raise FileOperationError(
+                f'No tool available for .{fmt} archives - install 7-Zip or UnRAR',
+                file_path=str(archive_path),
+                operation='extract'
+            )
+        return backend
+
+    def _run(self, args: List[str], timeout: int, archive_path: Path, operation: str,
+             password_given: bool = False) -> str:
+        """Run a tool and return stdout, raising FileOperationError on failure."""
+        try:
+            proc = subprocess.run(args, capture_output=True, text=True, timeout=timeout,
+                                  stdin=subprocess.DEVNULL)
+        except subprocess.TimeoutExpired as e:
+            raise FileOperationError(f'{Path(args[0]).name} timed out', file_path=str(archive_path),
+                                     operation=operation) from e
+        except OSError as e:
+            raise FileOperationError(f'Cannot run {args[0]}: {e}', file_path=str(archive_path),
+                                     operation=operation) from e
+
+        if proc.returncode != 0:
+            detail = (proc.stderr or proc.stdout).strip().splitlines()
+            output = (proc.stderr + proc.stdout).lower()
+            if any(marker in output for marker in PASSWORD_ERROR_MARKERS):
+                raise ArchivePasswordError(
+                    'Archive is password protected' if not password_given else 'Wrong archive password',
+                    file_path=str(archive_path),
+                    wrong_password=password_given
+                )
+            raise FileOperationError(
+                f"{Path(args[0]).name} failed (exit {proc.returncode}): {detail[-1] if detail else 'no output'}",
+                file_path=str(archive_path),
+                operation=operation
+            )
+        return proc.stdout
+
+    @staticmethod
+    def _password_args(backend: str, password: Optional[str]) -> List[str]:
+        """Password switches; unrar is told never to prompt when there is none."""
+        if password is not None:
+            return [f'-p{password}']
+        return ['-p-'] if backend == 'unrar' else []
+
+    def list_entries(self, archive_path: str, fmt: str, password: Optional[str] = None) -> List[Dict[str, Any]]:
+        """
+        List regular-file entries of a 7z/rar archive.
+
+        Args:
+            archive_path (str): Archive path
+            fmt (str): '7z' or 'rar'
+            password (str, optional): Password for archives with encrypted headers
+
+        Returns:
+            List[Dict]: Entries with 'name', 'size', 'compressed_size', 'encrypted'
+
+        Raises:
+            ArchivePasswordError: If the file list itself is encrypted and no
+                (or a wrong) password was given
+            FileOperationError: If no tool is available or listing fails
+        """
+        path = Path(archive_path)
+        backend = self._require_backend(path, fmt)
+        executable = self.find_tool(backend)
+        secret = self._password_args(backend, password)
+
+        if backend == '7z':
+            output = self._run([executable, 'l', '-slt', *secret, '--', str(path)], LIST_TIMEOUT_SECONDS, path,
+                               'list', password_given=password is not None)
+            return _parse_7z_listing(output)
+
+        output = self._run([executable, 'lt', '-c-', *secret, '--', str(path)], LIST_TIMEOUT_SECONDS, path,
+                           'list', password_given=password is not None)
+        return _parse_unrar_listing(output)
+
+    def is_encrypted(self, archive_path: str, fmt: str) -> bool:
+        """
+        Check whether a 7z/rar archive needs a password.
+
+        Args:
+            archive_path (str): Archive path
+            fmt (str): '7z' or 'rar'
+
+        Returns:
+            bool: True if any entry (or the file list) is encrypted
+        """
+        try:
+            return any(entry.get('encrypted') for entry in self.list_entries(archive_path, fmt))
+        except ArchivePasswordError:
+            return True
+
+    def extract(self, archive_path: str, destination: Path, password: Optional[str] = None,
+                members: Optional[Collection[str]] = None) -> List[Path]:
+        """
+        Extract an archive into destination (7z/rar, or zip needing 7-Zip).
+
+        Entry names are checked for traversal before extraction; symlinks
+        created by the tool are removed afterwards.
+
+        Args:
+            archive_path (str): Archive path
+            destination (Path): Resolved directory to extract into
+            password (str, optional): Archive password
+            members (Collection[str], optional): Only extract these entries (default: all)
+
+        Returns:
+            List[Path]: Extracted regular files
+
+        Raises:
+            ArchivePasswordError: If a password is needed or the given one is wrong
+            FileOperationError: If no tool is available, an entry is unsafe or extraction fails
+        """
+        from .archives import archive_format, _safe_member_path
+
+        path = Path(archive_path)
+        fmt = archive_format(path)
+        list_fmt = '7z' if fmt == 'zip' else fmt
+        backend = self._require_backend(path, list_fmt)
+        executable = self.find_tool(backend)
+        secret = self._password_args(backend, password)
+
+        selected: List[str] = []
+        for entry in self.list_entries(str(path), list_fmt, password):
+            if members is not None and entry['name'] not in members:
+                continue
+            if _safe_member_path(destination, entry['name']) is None:
+                raise FileOperationError(f"Unsafe archive entry: {entry['name']}",
+                                         file_path=str(path), operation='extract')
+            selected.append(entry['name'])
+        if members is not None and not selected:
+            return []
+        names = selected if members is not None else []
+
+        if backend == '7z':
+            args = [executable, 'x', '-y', *secret, f'-o{destination}', '--', str(path), *names]
+        else:
+            args = [executable, 'x', '-y', '-o+', '-c-', *secret, '--', str(path), *names, f'{destination}{os.sep}']
+        self._run(args, EXTRACT_TIMEOUT_SECONDS, path, 'extract', password_given=password is not None)
+
+        extracted: List[Path] = []
+        for item in destination.rglob('*'):
+            if item.is_symlink():
+                logger.warning(f"Removing symlink extracted from archive: {item}")
+                item.unlink()
+            elif item.is_file():
+                extracted.append(item)
+        return extracted
+
+
+def _parse_7z_listing(output: str) -> List[Dict[str, Any]]:
+    """Parse `7z l -slt` output into entries (skipping the archive header block)."""
+    entries: List[Dict[str, Any]] = []
+    _, separator, body = output.partition('\n----------')
+    if not separator:
+        return entries
+
+    for block in body.split('\n\n'):
+        fields = {}
+        for line in block.strip().splitlines():
+            key, sep, value = line.partition(' = ')
+            if sep:
+                fields[key.strip()] = value.strip()
+        if 'Path' not in fields:
+            continue
+        if fields.get('Folder') == '+' or fields.get('Attributes', '').startswith('D'):
+            continue
+        entries.append({
+            'name': fields['Path'].replace('\\', '/'),
+            'size': int(fields.get('Size') or 0),
+            'compressed_size': int(fields['Packed Size']) if fields.get('Packed Size') else None,
+            'encrypted': fields.get('Encrypted') == '+'
+        })
+    return entries
+
+
+def _parse_unrar_listing(output: str) -> List[Dict[str, Any]]:
+    """Parse `unrar lt` output into entries."""
+    entries: List[Dict[str, Any]] = []
+    current: Dict[str, str] = {}
+
+    def flush():
+        if current.get('Name') and current.get('Type', 'File') == 'File':
+            entries.append({
+                'name': current['Name'].replace('\\', '/'),
+                'size': int(current.get('Size') or 0),
+                'compressed_size': int(current['Packed size']) if current.get('Packed size') else None,
+                'encrypted': current.get('Flags', '').lower().find('encrypted') >= 0
+            })
+
+    for line in output.splitlines():
+        key, sep, value = line.strip().partition(': ')
+        if not sep:
+            continue
+        if key == 'Name':
+            flush()
+            current = {}
+        current[key] = value.strip()
+    flush()
+    return entries
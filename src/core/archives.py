@@ -0,0 +1,1234 @@
+"""
+Archive Workflow Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module implements archive workflows:
+
+- Extract-then-organize unpacks a zip/tar archive into a staging area,
+  classifies and organizes each extracted file through the normal
+  ActionManager pipeline (so all safety checks apply), and finally keeps or
+  trashes the original archive.
+- Archive creation bundles a set of files (a finished project, a year's
+  receipts) into a compressed archive with an embedded manifest of sizes and
+  SHA-256 hashes. The archive is verified against the manifest before any
+  source file is removed.
+
+Both workflows are recorded as one operation in the OperationJournal and can
+be undone at once.
+
+Archives can also be inspected without extracting them, recursing into
+archives-within-archives up to a limited depth. Listing the contents
+adds the category each file would be filed under, so an archive can be
+classified by what is inside; chosen entries (or all) can then be
+extracted into a folder, as stored or sorted into category folders. Inspection enforces zip-bomb
+limits (entry count, total expanded size, per-entry expansion ratio) and
+stops as soon as one is exceeded.
+
+Extraction is hardened against path traversal ("../" entries, absolute
+paths) and never materializes symlinks or device files.
+
+zip and tar are handled natively; 7z and rar are listed and extracted with
+external tools (see archive_tools.ArchiveToolkit) and cannot be created.
+
+Encrypted archives are detected up front; passwords are obtained through
+an ArchivePasswordBroker (remembered keyring entry or a UI prompt) and the
+contents are classified once the archive is unlocked.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import hashlib
+import io
+import json
+import logging
+import os
+import shutil
+import tarfile
+import tempfile
+import zipfile
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, Collection, List, Optional, Union, BinaryIO
+
+from .journal import OperationJournal, trash_root
+from .archive_tools import ArchiveToolkit
+from .disk_usage import categories_by_extension
+from .fsops import move_file
+from .archive_passwords import ArchivePasswordBroker
+from src.utils.error_handler import FileOperationError, ArchivePasswordError
+
+logger = logging.getLogger(__name__)
+
+
+PROJECT_ROOT = Path(__file__).parent.parent.parent
+
+# Multi-part suffixes must be checked before single suffixes
+TAR_SUFFIXES = ('.tar.gz', '.tar.bz2', '.tar.xz', '.tgz', '.tbz2', '.txz', '.tar')
+ZIP_SUFFIXES = ('.zip',)
+TOOL_SUFFIXES = {'.7z': '7z', '.rar': 'rar'}  # handled by external tools
+
+# tarfile write modes by suffix
+TAR_WRITE_MODES = {
+    '.tar.gz': 'w:gz', '.tgz': 'w:gz',
+    '.tar.bz2': 'w:bz2', '.tbz2': 'w:bz2',
+    '.tar.xz': 'w:xz', '.txz': 'w:xz',
+    '.tar': 'w',
+}
+
+MAX_PASSWORD_ATTEMPTS = 3
+ZIP_ENCRYPTED_FLAG = 0x1
+ZIP_AES_METHOD = 99  # WinZip AES, not supported by zipfile
+
+MANIFEST_NAME = 'MANIFEST.aifo.json'
+MANIFEST_FORMAT = 'ai-file-organiser-manifest'
+
+
+def archive_format(path: Path) -> Optional[str]:
+    """
+    Detect the archive format from a file name.
+
+    Args:
+        path (Path): Archive path
+
+    Returns:
+        str or None: 'zip', 'tar', '7z', 'rar', or None if not a supported archive
+    """
+    name = path.name.lower()
+    if name.endswith(ZIP_SUFFIXES):
+        return 'zip'
+    if name.endswith(TAR_SUFFIXES):
+        return 'tar'
+    for suffix, fmt in TOOL_SUFFIXES.items():
+        if name.endswith(suffix):
+            return fmt
+    return None
+
+
+def archive_stem(path: Path) -> str:
+    """Archive name without its (possibly multi-part) archive suffix."""
+    name = path.name
+    for suffix in ZIP_SUFFIXES + TAR_SUFFIXES + tuple(TOOL_SUFFIXES):
+        if name.lower().endswith(suffix):
+            return name[:-len(suffix)] or name
+    return path.stem
+
+
+def is_encrypted_archive(archive_path: str, toolkit: Optional[ArchiveToolkit] = None) -> bool:
+    """
+    Check whether an archive needs a password.
+
+    Args:
+        archive_path (str): Archive path
+        toolkit (ArchiveToolkit, optional): External tools for 7z/rar archives
+
+    Returns:
+        bool: True if any entry (or a 7z/rar file list) is encrypted
+    """
+    path = Path(archive_path)
+    fmt = archive_format(path)
+    try:
+        if fmt == 'zip':
+            with zipfile.ZipFile(path) as zf:
+                return any(info.flag_bits & ZIP_ENCRYPTED_FLAG for info in zf.infolist())
+        if fmt in TOOL_SUFFIXES.values():
+            return (toolkit or ArchiveToolkit()).is_encrypted(str(path), fmt)
+    except (zipfile.BadZipFile, OSError, FileOperationError):
+        return False
+    return False
+
+
+def _sha256_stream(stream) -> str:
+    """SHA-256 of a binary stream, read in chunks."""
+    digest = hashlib.sha256()
+    for chunk in iter(lambda: stream.read(1024 * 1024), b''):
+        digest.update(chunk)
+    return digest.hexdigest()
+
+
+def _read_members(archive_path: Path):
+    """
+    Iterate over regular-file members of an archive.
+
+    Yields:
+        Tuple[str, BinaryIO]: Member name and an open binary stream
+    """
+    if archive_format(archive_path) == 'zip':
+        with zipfile.ZipFile(archive_path) as zf:
+            for info in zf.infolist():
+                if not info.is_dir():
+                    with zf.open(info) as stream:
+                        yield info.filename, stream
+    else:
+        with tarfile.open(archive_path) as tf:
+            for member in tf:
+                if member.isfile():
+                    stream = tf.extractfile(member)
+                    if stream is not None:
+                        with stream:
+                            yield member.name, stream
+
+
+def read_manifest(archive_path: str) -> Optional[Dict[str, Any]]:
+    """
+    Read the embedded manifest of an archive created by this module.
+
+    Args:
+        archive_path (str): Archive path
+
+    Returns:
+        Dict or None: Manifest, or None if the archive has none
+    """
+    for name, stream in _read_members(Path(archive_path)):
+        if name == MANIFEST_NAME:
+            return json.loads(stream.read().decode('utf-8'))
+    return None
+
+
+def write_archive(file_paths: List[str], archive_path: str, base_dir: Optional[str] = None,
+                  append: bool = False) -> Dict[str, Any]:
+    """
+    Write files into a zip or tar archive together with a manifest.
+
+    The archive is written to a temporary file next to the target and moved
+    into place only when complete. With `append`, members of an existing
+    archive are carried over and the manifest is extended.
+
+    Args:
+        file_paths (List[str]): Files to add
+        archive_path (str): Target archive (.zip, .tar, .tar.gz, .tar.bz2, .tar.xz)
+        base_dir (str, optional): Member names are relative to this directory.
+            Defaults to the common parent directory of the files
+        append (bool): Keep the members of an existing archive
+
+    Returns:
+        Dict: The manifest written into the archive
+
+    Raises:
+        FileOperationError: If the format is unsupported or writing fails
+    """
+    target = Path(archive_path)
+    fmt = archive_format(target)
+    if fmt is None:
+        raise FileOperationError('Unsupported archive format', destination=str(target), operation='compress')
+    if fmt in TOOL_SUFFIXES.values():
+        raise FileOperationError(f'Creating .{fmt} archives is not supported - use .zip or .tar.gz',
+                                 destination=str(target), operation='compress')
+
+    sources = [Path(p).resolve() for p in file_paths]
+    if base_dir:
+        base = Path(base_dir).resolve()
+    elif sources:
+        base = Path(os.path.commonpath([str(p.parent) for p in sources]))
+    else:
+        base = target.parent.resolve()
+
+    existing: List[Dict[str, Any]] = []
+    if append and target.exists():
+        existing = (read_manifest(str(target)) or {}).get('files', [])
+
+    used_names = {entry['name'] for entry in existing}
+    entries: List[Dict[str, Any]] = []
+    for source in sources:
+        try:
+            name = source.relative_to(base).as_posix()
+        except ValueError:
+            name = source.name
+        # Keep member names unique, using the same _N suffix as file moves
+        stem, suffix, counter = Path(name).stem, Path(name).suffix, 1
+        parent = Path(name).parent.as_posix()
+        while name in used_names or name == MANIFEST_NAME:
+            candidate = f"{stem}_{counter}{suffix}"
+            name = candidate if parent == '.' else f"{parent}/{candidate}"
+            counter += 1
+        used_names.add(name)
+
+        with open(source, 'rb') as f:
+            sha256 = _sha256_stream(f)
+        stat = source.stat()
+        entries.append({
+            'name': name,
+            'source': str(source),
+            'size': stat.st_size,
+            'sha256': sha256,
+            'modified': datetime.fromtimestamp(stat.st_mtime).isoformat()
+        })
+
+    manifest = {
+        'format': MANIFEST_FORMAT,
+        'version': 1,
+        'created_at': datetime.now().isoformat(),
+        'files': existing + entries
+    }
+    manifest_bytes = json.dumps(manifest, indent=2, ensure_ascii=False).encode('utf-8')
+
+    target.parent.mkdir(parents=True, exist_ok=True)
+    partial = target.with_name(target.name + '.partial')
+
+    try:
+        if fmt == 'zip':
+            with zipfile.ZipFile(partial, 'w', compression=zipfile.ZIP_DEFLATED) as zf:
+                if existing:
+                    for name, stream in _read_members(target):
+                        if name != MANIFEST_NAME:
+                            with zf.open(name, 'w') as dst:
+                                shutil.copyfileobj(stream, dst)
+                for entry in entries:
+                    zf.write(entry['source'], entry['name'])
+                zf.writestr(MANIFEST_NAME, manifest_bytes)
+        else:
+            mode = next(m for suffix, m in TAR_WRITE_MODES.items() if target.name.lower().endswith(suffix))
+            with tarfile.open(partial, mode) as tf:
+                if existing:
+                    with tarfile.open(target) as old:
+                        for member in old:
+                            if member.isfile() and member.name != MANIFEST_NAME:
+                                tf.addfile(member, old.extractfile(member))
+                for entry in entries:
+                    tf.add(entry['source'], arcname=entry['name'], recursive=False)
+                info = tarfile.TarInfo(MANIFEST_NAME)
+                info.size = len(manifest_bytes)
+                info.mtime = int(datetime.now().timestamp())
+                tf.addfile(info, io.BytesIO(manifest_bytes))
+        os.replace(partial, target)
+    except (OSError, zipfile.BadZipFile, tarfile.TarError) as e:
+        partial.unlink(missing_ok=True)
+        raise FileOperationError(
+            f'Failed to write archive: {str(e)}',
+            destination=str(target),
+            operation='compress'
+        ) from e
+
+    return manifest
+
+
+def verify_archive(archive_path: str, manifest: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """
+    Verify that every file listed in the manifest is in the archive intact.
+
+    Args:
+        archive_path (str): Archive path
+        manifest (Dict, optional): Expected manifest. Defaults to the embedded one
+
+    Returns:
+        Dict: Result with 'success', 'message', 'verified' (count) and 'problems'
+    """
+    try:
+        manifest = manifest or read_manifest(archive_path)
+        if not manifest:
+            return {'success': False, 'message': 'Archive has no manifest', 'verified': 0, 'problems': []}
+
+        actual = {}
+        for name, stream in _read_members(Path(archive_path)):
+            if name != MANIFEST_NAME:
+                actual[name] = _sha256_stream(stream)
+    except (OSError, zipfile.BadZipFile, tarfile.TarError, json.JSONDecodeError) as e:
+        return {'success': False, 'message': f'Cannot read archive: {str(e)}', 'verified': 0, 'problems': []}
+
+    problems = []
+    for entry in manifest['files']:
+        if entry['name'] not in actual:
+            problems.append({'name': entry['name'], 'problem': 'missing'})
+        elif actual[entry['name']] != entry['sha256']:
+            problems.append({'name': entry['name'], 'problem': 'checksum mismatch'})
+
+    verified = len(manifest['files']) - len(problems)
+    if problems:
+        return {
+            'success': False,
+            'message': f'{len(problems)} of {len(manifest["files"])} file(s) failed verification',
+            'verified': verified,
+            'problems': problems
+        }
+    return {'success': True, 'message': f'Verified {verified} file(s)', 'verified': verified, 'problems': []}
+
+
+def _missing_folders(root: Path, folder: Path) -> List[Path]:
+    """Folders between root and folder that do not exist yet, outermost first."""
+    missing: List[Path] = []
+    while folder != root and root in folder.parents and not folder.exists():
+        missing.append(folder)
+        folder = folder.parent
+    return missing[::-1]
+
+
+def _safe_member_path(root: Path, member_name: str) -> Optional[Path]:
+    """
+    Resolve an archive member name inside root, rejecting traversal.
+
+    Args:
+        root (Path): Resolved extraction root
+        member_name (str): Name stored in the archive
+
+    Returns:
+        Path or None: Target path, or None if the entry would escape root
+    """
+    name = member_name.replace('\\', '/').lstrip('/')
+    if not name or any(part == '..' for part in name.split('/')):
+        return None
+    target = (root / name).resolve()
+    try:
+        target.relative_to(root)
+    except ValueError:
+        return None
+    return target
+
+
+# ==================== Inspection ====================
+
+@dataclass
+class InspectionLimits:
+    """Zip-bomb protection limits for archive inspection."""
+    max_depth: int = 2                            # nesting levels below the outer archive
+    max_entries: int = 10000                      # total entries across all levels
+    max_total_size: int = 1024 * 1024 * 1024      # total expanded bytes across all levels
+    max_ratio: float = 100.0                      # expanded/compressed size per entry
+    ratio_min_size: int = 1024 * 1024             # ratio only checked for entries at least this big
+    max_nested_size: int = 64 * 1024 * 1024       # largest nested archive loaded into memory
+
+    @classmethod
+    def from_config(cls, config) -> 'InspectionLimits':
+        """Build limits from the `archives.inspection` config section."""
+        values = getattr(config, 'archive_inspection', None) or {}
+        mb = 1024 * 1024
+        defaults = cls()
+        return cls(
+            max_depth=int(values.get('max_depth', defaults.max_depth)),
+            max_entries=int(values.get('max_entries', defaults.max_entries)),
+            max_total_size=int(values.get('max_total_size_mb', defaults.max_total_size // mb)) * mb,
+            max_ratio=float(values.get('max_ratio', defaults.max_ratio)),
+            max_nested_size=int(values.get('max_nested_size_mb', defaults.max_nested_size // mb)) * mb,
+        )
+
+
+class _InspectionBudget:
+    """Running totals shared by all nesting levels of one inspection."""
+
+    def __init__(self, limits: InspectionLimits):
+        self.limits = limits
+        self.entries = 0
+        self.total_size = 0
+        self.limit_hit: Optional[str] = None
+
+    def admit(self, size: int, compressed_size: Optional[int]) -> bool:
+        """Account for one entry; returns False (and records why) when a limit is exceeded."""
+        self.entries += 1
+        self.total_size += size
+        if self.entries > self.limits.max_entries:
+            self.limit_hit = f'entry count exceeds {self.limits.max_entries}'
+        elif self.total_size > self.limits.max_total_size:
+            self.limit_hit = f'expanded size exceeds {self.limits.max_total_size} bytes'
+        elif (compressed_size and size >= self.limits.ratio_min_size
+              and size / compressed_size > self.limits.max_ratio):
+            self.limit_hit = f'expansion ratio exceeds {self.limits.max_ratio:g}:1'
+        return self.limit_hit is None
+
+
+def _iter_entries(source: Union[Path, BinaryIO], fmt: str):
+    """
+    Iterate over regular-file entries of an archive given as a path or file object.
+
+    Yields:
+        Tuple[str, int, Optional[int], bool, Callable]: name, size, compressed
+            size (zip only), encrypted flag and a function returning an open
+            binary stream
+    """
+    if fmt == 'zip':
+        with zipfile.ZipFile(source) as zf:
+            for info in zf.infolist():
+                if not info.is_dir():
+                    yield (info.filename, info.file_size, info.compress_size,
+                           bool(info.flag_bits & ZIP_ENCRYPTED_FLAG), (lambda i=info: zf.open(i)))
+    else:
+        kwargs = {'name': str(source)} if isinstance(source, Path) else {'fileobj': source}
+        with tarfile.open(**kwargs) as tf:
+            for member in tf:
+                if member.isfile():
+                    yield member.name, member.size, None, False, (lambda m=member: tf.extractfile(m))
+
+
+def _hash_stream(stream, algorithm: str, budget: _InspectionBudget, declared_size: int) -> Optional[str]:
+    """Hash a member stream, refusing to read more than its declared size."""
+    hasher = hashlib.new(algorithm)
+    read = 0
+    for chunk in iter(lambda: stream.read(1024 * 1024), b''):
+        read += len(chunk)
+        if read > declared_size:
+            budget.limit_hit = 'entry larger than its declared size'
+            return None
+        hasher.update(chunk)
+    return hasher.hexdigest()
+
+
+def _inspect_tool_archive(path: Path, fmt: str, toolkit: ArchiveToolkit,
+                          budget: _InspectionBudget, entries: List[Dict[str, Any]]) -> None:
+    """List a 7z/rar archive through an external tool (no recursion or hashing)."""
+    for item in toolkit.list_entries(str(path), fmt):
+        if not budget.admit(item['size'], item['compressed_size']):
+            return
+        entries.append({
+            'path': f"{path.name}!/{item['name']}",
+            'name': item['name'],
+            'size': item['size'],
+            'compressed_size': item['compressed_size'],
+            'depth': 0,
+            'encrypted': item.get('encrypted', False),
+        })
+
+
+def _inspect_level(source: Union[Path, BinaryIO], fmt: str, prefix: str, depth: int,
+                   budget: _InspectionBudget, hash_algorithm: Optional[str],
+                   entries: List[Dict[str, Any]]) -> None:
+    """Inspect one archive level, recursing into nested archives."""
+    # Tar entries carry no per-entry compressed size; check the whole level's ratio instead
+    if isinstance(source, Path):
+        archive_size = source.stat().st_size
+    else:
+        archive_size = len(source.getbuffer())
+    level_total = 0
+
+    for name, size, compressed_size, encrypted, opener in _iter_entries(source, fmt):
+        if not budget.admit(size, compressed_size):
+            return
+
+        level_total += size
+        if (fmt == 'tar' and archive_size and level_total >= budget.limits.ratio_min_size
+                and level_total / archive_size > budget.limits.max_ratio):
+            budget.limit_hit = f'expansion ratio exceeds {budget.limits.max_ratio:g}:1'
+            return
+
+        virtual_path = f"{prefix}!/{name}"
+        entry: Dict[str, Any] = {
+            'path': virtual_path,
+            'name': name,
+            'size': size,
+            'compressed_size': compressed_size,
+            'depth': depth,
+        }
+        entries.append(entry)
+
+        if encrypted:
+            # Listed but not read: the content needs the archive password
+            entry['encrypted'] = True
+            continue
+
+        nested_fmt = archive_format(Path(name))
+        descend = nested_fmt is not None and depth < budget.limits.max_depth
+
+        if descend and nested_fmt in TOOL_SUFFIXES.values():
+            entry['skipped'] = f'nested .{nested_fmt} archives are not inspected'
+            descend = False
+
+        if descend and size > budget.limits.max_nested_size:
+            entry['skipped'] = 'nested archive too large to inspect'
+            descend = False
+
+        if not descend and not hash_algorithm:
+            continue
+
+        with opener() as stream:
+            if descend:
+                data = stream.read(budget.limits.max_nested_size + 1)
+                if len(data) > size:
+                    budget.limit_hit = 'entry larger than its declared size'
+                    return
+                entry['nested'] = True
+                if hash_algorithm:
+                    entry['hash'] = hashlib.new(hash_algorithm, data).hexdigest()
+                try:
+                    _inspect_level(io.BytesIO(data), nested_fmt, virtual_path, depth + 1,
+                                   budget, hash_algorithm, entries)
+                except (zipfile.BadZipFile, tarfile.TarError) as e:
+                    entry['error'] = f'unreadable nested archive: {e}'
+            else:
+                entry['hash'] = _hash_stream(stream, hash_algorithm, budget, size)
+
+        if budget.limit_hit:
+            return
+
+
+def inspect_archive(archive_path: str, limits: Optional[InspectionLimits] = None,
+                    hash_algorithm: Optional[str] = None,
+                    toolkit: Optional[ArchiveToolkit] = None) -> Dict[str, Any]:
+    """
+    List archive contents, recursing into nested archives within limits.
+
+    Entry paths use '!/' to separate nesting levels, e.g.
+    'backup.zip!/photos.tar.gz!/img001.jpg'.
+
+    Args:
+        archive_path (str): Archive to inspect
+        limits (InspectionLimits, optional): Zip-bomb limits (defaults apply if omitted)
+        hash_algorithm (str, optional): If set (e.g. 'sha1'), hash every entry
+            (zip/tar only)
+        toolkit (ArchiveToolkit, optional): External tools for 7z/rar archives
+
+    Returns:
+        Dict: Result with 'success', 'message', 'entries', 'entry_count',
+              'total_size', 'max_depth_reached', 'limit_hit' (None when complete)
+              and 'encrypted' (entries that need a password are listed but not hashed).
+              A hit limit means the archive is suspicious and the listing is partial.
+    """
+    path = Path(archive_path)
+    fmt = archive_format(path)
+    if fmt is None:
+        return {'success': False, 'message': f'Unsupported archive format: {path.name}', 'entries': []}
+    if not path.exists():
+        return {'success': False, 'message': 'Archive not found', 'entries': []}
+
+    limits = limits or InspectionLimits()
+    budget = _InspectionBudget(limits)
+    entries: List[Dict[str, Any]] = []
+
+    try:
+        if fmt in TOOL_SUFFIXES.values():
+            _inspect_tool_archive(path, fmt, toolkit or ArchiveToolkit(), budget, entries)
+        else:
+            _inspect_level(path, fmt, path.name, 0, budget, hash_algorithm, entries)
+    except ArchivePasswordError as e:
+        return {'success': False, 'message': e.message, 'entries': entries, 'encrypted': True}
+    except FileOperationError as e:
+        return {'success': False, 'message': e.message, 'entries': entries}
+    except (zipfile.BadZipFile, tarfile.TarError, OSError) as e:
+        return {'success': False, 'message': f'Cannot read archive: {str(e)}', 'entries': entries}
+
+    if budget.limit_hit:
+        logger.warning(f"Archive inspection stopped for {path}: {budget.limit_hit}")
+        message = f'Inspection stopped: {budget.limit_hit} (possible zip bomb)'
+    else:
+        message = f'{len(entries)} entr{"y" if len(entries) == 1 else "ies"} inspected'
+
+    return {
+        'success': True,
+        'message': message,
+        'entries': entries,
+        'entry_count': len(entries),
+        'total_size': budget.total_size,
+        'max_depth_reached': max((e['depth'] for e in entries), default=0),
+        'limit_hit': budget.limit_hit,
+        'encrypted': any(e.get('encrypted') for e in entries)
+    }
+
+
+class ArchiveManager:
+    """
+    Runs archive workflows on top of the classifier and action manager.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager
+        classifier: FileClassifier used for extracted files
+        action_manager: ActionManager used to move extracted files
+        journal (OperationJournal): Journal recording undoable operations
+        passwords (ArchivePasswordBroker): Password prompts for encrypted archives
+    """
+
+    def __init__(self, config, db_manager, classifier=None, action_manager=None,
+                 journal: Optional[OperationJournal] = None,
+                 password_broker: Optional[ArchivePasswordBroker] = None):
+        """
+        Initialize archive manager.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+            classifier: FileClassifier instance (required for extract-and-organize)
+            action_manager: ActionManager instance (required for extract-and-organize)
+            journal (OperationJournal, optional): Journal; created from db_manager if omitted
+            password_broker (ArchivePasswordBroker, optional): Password broker; created if omitted
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.classifier = classifier
+        self.action_manager = action_manager
+        self.journal = journal or OperationJournal(db_manager)
+        self.toolkit = ArchiveToolkit(config)
+        self.passwords = password_broker or ArchivePasswordBroker(config)
+
+    def is_archive(self, file_path: str) -> bool:
+        """
+        Check whether a file is a supported archive.
+
+        Args:
+            file_path (str): Path to check
+
+        Returns:
+            bool: True for zip and tar (optionally compressed) archives
+        """
+        return archive_format(Path(file_path)) is not None
+
+    def inspect(self, archive_path: str, hash_algorithm: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Inspect an archive using the configured zip-bomb limits.
+
+        Args:
+            archive_path (str): Archive to inspect
+            hash_algorithm (str, optional): Hash entries with this algorithm
+
+        Returns:
+            Dict: See inspect_archive()
+        """
+        return inspect_archive(archive_path, InspectionLimits.from_config(self.config), hash_algorithm,
+                               self.toolkit)
+
+    def list_contents(self, archive_path: str) -> Dict[str, Any]:
+        """
+        List an archive's files with the category each would be filed under.
+
+        Nothing is extracted: categories come from the entries' extensions.
+
+        Args:
+            archive_path (str): Archive to list
+
+        Returns:
+            Dict: See inspect_archive(); every entry also has 'category', and
+                  the result 'categories' (category -> files) and 'category'
+                  (the one most of the content belongs to, by size)
+        """
+        result = self.inspect(archive_path)
+        by_extension = categories_by_extension(self.config)
+        sizes: Dict[str, int] = {}
+        counts: Dict[str, int] = {}
+        for entry in result.get('entries', []):
+            extension = Path(entry['name']).suffix.lower().lstrip('.')
+            category = 'Archives' if entry.get('nested') else by_extension.get(extension, 'Other')
+            entry['category'] = category
+            if entry.get('nested'):
+                continue  # Its files are counted instead
+            counts[category] = counts.get(category, 0) + 1
+            sizes[category] = sizes.get(category, 0) + entry['size']
+        result['categories'] = dict(sorted(counts.items(), key=lambda item: (-item[1], item[0])))
+        result['category'] = max(sizes, key=lambda c: (sizes[c], counts[c])) if sizes else None
+        return result
+
+    def capabilities(self) -> Dict[str, Any]:
+        """
+        Report which archive formats can be listed, extracted and created.
+
+        Returns:
+            Dict: See ArchiveToolkit.capabilities()
+        """
+        return self.toolkit.capabilities()
+
+    def _staging_root(self) -> Path:
+        """Root directory for extraction staging areas."""
+        configured = getattr(self.config, 'archive_staging_dir', '') or ''
+        return Path(configured).expanduser() if configured else PROJECT_ROOT / "data" / "staging"
+
+    def _trash_root(self) -> Path:
+        """Holding directory for trashed archives."""
+        return trash_root(self.config)
+
+    # ==================== Extraction ====================
+
+    def extract(self, archive_path: str, destination: str, password: Optional[str] = None,
+                members: Optional[Collection[str]] = None) -> List[Path]:
+        """
+        Safely extract regular files from an archive.
+
+        Entries that would escape the destination, symlinks, hard links and
+        special files are skipped.
+
+        Args:
+            archive_path (str): Archive to extract
+            destination (str): Directory to extract into (created if missing)
+            password (str, optional): Password for encrypted archives
+            members (Collection[str], optional): Only extract these entry names (default: all)
+
+        Returns:
+            List[Path]: Extracted file paths
+
+        Raises:
+            ArchivePasswordError: If the archive is encrypted and the password is missing or wrong
+            FileOperationError: If the archive is unsupported or unreadable
+        """
+        path = Path(archive_path)
+        fmt = archive_format(path)
+        if fmt is None:
+            raise FileOperationError('Unsupported archive format', file_path=str(path), operation='extract')
+
+        root = Path(destination)
+        root.mkdir(parents=True, exist_ok=True)
+        root = root.resolve()
+        extracted: List[Path] = []
+
+        if fmt in TOOL_SUFFIXES.values():
+            return self.toolkit.extract(str(path), root, password, members)
+
+        try:
+            if fmt == 'zip':
+                with zipfile.ZipFile(path) as zf:
+                    wanted = [i for i in zf.infolist() if members is None or i.filename in members]
+                    encrypted = [i for i in wanted if i.flag_bits & ZIP_ENCRYPTED_FLAG]
+                    if encrypted and password is None:
+                        raise ArchivePasswordError('Archive is password protected', file_path=str(path))
+                    if any(i.compress_type == ZIP_AES_METHOD for i in encrypted):
+                        # AES-encrypted zips need 7-Zip
+                        return self.toolkit.extract(str(path), root, password, members)
+                    pwd = password.encode('utf-8') if password is not None else None
+
+                    for info in wanted:
+                        if info.is_dir():
+                            continue
+                        # Symlinks are stored with S_IFLNK in the high bits of external_attr
+                        if (info.external_attr >> 16) & 0o170000 == 0o120000:
+                            logger.warning(f"Skipping symlink in archive: {info.filename}")
+                            continue
+                        target = _safe_member_path(root, info.filename)
+                        if target is None:
+                            logger.warning(f"Skipping unsafe archive entry: {info.filename}")
+                            continue
+                        target.parent.mkdir(parents=True, exist_ok=True)
+                        try:
+                            with zf.open(info, pwd=pwd) as src, open(target, 'wb') as dst:
+                                shutil.copyfileobj(src, dst)
+                        except (RuntimeError, zipfile.BadZipFile) as e:
+                            if not info.flag_bits & ZIP_ENCRYPTED_FLAG:
+                                raise
+                            # Bad password is detected on open, or as a CRC error for 1 in 256 passwords
+                            target.unlink(missing_ok=True)
+                            raise ArchivePasswordError('Wrong archive password', file_path=str(path),
+                                                       wrong_password=True) from e
+                        extracted.append(target)
+            else:
+                with tarfile.open(path) as tf:
+                    for member in tf:
+                        if not member.isfile() or (members is not None and member.name not in members):
+                            continue
+                        target = _safe_member_path(root, member.name)
+                        if target is None:
+                            logger.warning(f"Skipping unsafe archive entry: {member.name}")
+                            continue
+                        src = tf.extractfile(member)
+                        if src is None:
+                            continue
+                        target.parent.mkdir(parents=True, exist_ok=True)
+                        with src, open(target, 'wb') as dst:
+                            shutil.copyfileobj(src, dst)
+                        extracted.append(target)
+
+        except (zipfile.BadZipFile, tarfile.TarError, OSError) as e:
+            raise FileOperationError(
+                f'Failed to extract archive: {str(e)}',
+                file_path=str(path),
+                destination=str(root),
+                operation='extract'
+            ) from e
+
+        return extracted
+
+    def extract_unlocked(self, archive_path: str, destination: str, password: Optional[str] = None,
+                         members: Optional[Collection[str]] = None) -> List[Path]:
+        """
+        Extract an archive, asking for a password if it is encrypted.
+
+        The password passed in (if any) is tried first, then the broker is
+        asked - up to MAX_PASSWORD_ATTEMPTS times after wrong passwords. A
+        password that works is remembered if the user asked for it.
+
+        Args:
+            archive_path (str): Archive to extract
+            destination (str): Directory to extract into
+            password (str, optional): Password to try first
+            members (Collection[str], optional): Only extract these entry names (default: all)
+
+        Returns:
+            List[Path]: Extracted file paths
+
+        Raises:
+            ArchivePasswordError: If no working password was provided
+            FileOperationError: If the archive is unsupported or unreadable
+        """
+        if password is None and is_encrypted_archive(archive_path, self.toolkit):
+            password = self.passwords.request_password(archive_path)
+            if password is None:
+                raise ArchivePasswordError('Archive is password protected; no password provided',
+                                           file_path=archive_path)
+
+        attempt = 1
+        while True:
+            try:
+                extracted = self.extract(archive_path, destination, password, members)
+            except ArchivePasswordError as e:
+                if password is not None:
+                    self.passwords.password_rejected(archive_path)
+                attempt += 1
+                if attempt > MAX_PASSWORD_ATTEMPTS:
+                    raise
+                password = self.passwords.request_password(archive_path, attempt, e.message)
+                if password is None:
+                    raise ArchivePasswordError('Archive is password protected; no password provided',
+                                               file_path=archive_path,
+                                               wrong_password=e.wrong_password) from e
+                continue
+
+            if password is not None:
+                self.passwords.password_accepted(archive_path, password)
+            return extracted
+
+    def extract_archive(self, archive_path: str, destination: str, selective: Optional[List[str]] = None,
+                        organize: bool = False, password: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Extract an archive, or chosen entries of it, into a folder as one undoable operation.
+
+        Existing files are never overwritten: an extracted file whose name
+        is taken is kept as "name (2).ext".
+
+        Args:
+            archive_path (str): Archive to extract
+            destination (str): Folder to extract into (created if missing)
+            selective (List[str], optional): Entry names to extract, as listed
+                by list_contents() (default: all)
+            organize (bool): Sort files into category folders (as classified)
+                instead of keeping the archive's folders
+            password (str, optional): Password for an encrypted archive
+
+        Returns:
+            Dict: 'success', 'message', 'operation_id', 'files' (one {'name',
+                  'path', 'category', 'status', 'message'} per extracted file),
+                  'extracted' and 'missing' (selected names not in the archive).
+                  Failed results for encrypted archives carry 'needs_password': True
+        """
+        path = Path(archive_path)
+        if not path.exists():
+            return {'success': False, 'message': 'Archive not found', 'files': []}
+        if not self.is_archive(str(path)):
+            return {'success': False, 'message': f'Unsupported archive format: {path.name}', 'files': []}
+
+        root = Path(destination).expanduser()
+        root.mkdir(parents=True, exist_ok=True)
+        root = root.resolve()
+        members = set(selective) if selective else None
+        # Staged next to the destination, so files are put in place by a rename
+        staging = Path(tempfile.mkdtemp(prefix='.aifo_extract_', dir=root))
+        operation_id: Optional[int] = None
+        files: List[Dict[str, Any]] = []
+
+        try:
+            extracted = self.extract_unlocked(str(path), str(staging), password, members)
+            operation_id = self.journal.begin('extract', f'Extract {path.name}')
+            for file_path in extracted:
+                name = file_path.relative_to(staging.resolve()).as_posix()
+                category = None
+                if organize:
+                    category, folder = self._organized_folder(file_path, root)
+                    target = folder / file_path.name
+                else:
+                    target = root / name
+                for created in _missing_folders(root, target.parent):
+                    created.mkdir()
+                    self.journal.record_step(operation_id, 'mkdir', None, str(created), {'role': 'extract'})
+                moved = move_file(str(file_path), str(target), on_conflict='keep_both')
+                if moved['status'] == 'moved':
+                    self.journal.record_step(operation_id, 'create', str(path), moved['destination'],
+                                             {'entry': name, 'role': 'extract'})
+                files.append({'name': name, 'path': moved['destination'], 'category': category,
+                              'status': 'extracted' if moved['status'] == 'moved' else moved['status'],
+                              'message': moved['message']})
+            self.journal.complete(operation_id)
+
+        except ArchivePasswordError as e:
+            logger.info(f"Encrypted archive not extracted: {path} ({e.message})")
+            return {'success': False, 'message': e.message, 'operation_id': None, 'files': [],
+                    'needs_password': True, 'wrong_password': e.wrong_password}
+        except FileOperationError as e:
+            if operation_id is not None:
+                self.journal.complete(operation_id, success=False)
+            return {'success': False, 'message': e.message, 'operation_id': operation_id, 'files': files}
+        finally:
+            shutil.rmtree(staging, ignore_errors=True)
+
+        done = sum(1 for f in files if f['status'] == 'extracted')
+        missing = sorted(members - {f['name'] for f in files}) if members else []
+        message = f"Extracted {done} file(s) from {path.name}" + (' into category folders' if organize else '')
+        if missing:
+            message += f"; {len(missing)} not in the archive"
+        return {'success': done == len(files), 'message': message, 'operation_id': operation_id,
+                'files': files, 'extracted': done, 'missing': missing}
+
+    def _organized_folder(self, file_path: Path, root: Path):
+        """The category of an extracted file and the folder under root it is sorted into."""
+        category, suggested = None, None
+        if self.classifier is not None:
+            classification = self.classifier.classify(str(file_path))
+            category = classification.get('category')
+            suggested = classification.get('suggested_path')
+        if not category:
+            category = categories_by_extension(self.config).get(file_path.suffix.lower().lstrip('.'), 'Other')
+        folder = _safe_member_path(root, suggested or category) or _safe_member_path(root, 'Other')
+        return category, folder
+
+    # ==================== Extract-then-Organize ====================
+
+    def extract_and_organize(self, archive_path: str, original_action: Optional[str] = None,
+                             user_approved: bool = True, password: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Extract an archive to staging, organize its contents, then keep or trash it.
+
+        In dry-run mode the archive is extracted to a temporary directory that
+        is removed afterwards, and the result describes what would happen.
+        Encrypted archives are unlocked through the password broker; without
+        a password the archive is left untouched.
+
+        Args:
+            archive_path (str): Archive to process
+            original_action (str, optional): 'keep' or 'trash'. Defaults to config setting
+            user_approved (bool): Passed to ActionManager for each extracted file
+            password (str, optional): Password for an encrypted archive
+
+        Returns:
+            Dict: Result with 'success', 'message', 'operation_id', 'files'
+                  (per-file results), 'organized', 'remaining', 'staging_dir'
+                  and 'archive_new_path' (when trashed). Failed results for
+                  encrypted archives carry 'needs_password': True
+        """
+        if self.classifier is None or self.action_manager is None:
+            return {'success': False, 'message': 'Classifier and action manager are required'}
+
+        path = Path(archive_path)
+        if not path.exists():
+            return {'success': False, 'message': 'Archive not found'}
+        if not self.is_archive(str(path)):
+            return {'success': False, 'message': f'Unsupported archive format: {path.name}'}
+
+        original_action = original_action or getattr(self.config, 'archive_original_action', 'keep')
+        if original_action not in ('keep', 'trash'):
+            return {'success': False, 'message': f"Invalid original action: {original_action} (use 'keep' or 'trash')"}
+
+        dry_run = self.action_manager.dry_run
+        stamp = datetime.now().strftime('%Y%m%d_%H%M%S')
+
+        if dry_run:
+            staging = Path(tempfile.mkdtemp(prefix='aifo_extract_'))
+            operation_id = None
+        else:
+            staging = self._staging_root() / f"{archive_stem(path)}_{stamp}"
+            operation_id = self.journal.begin('extract_organize', f'Extract and organize {path.name}')
+
+        files: List[Dict[str, Any]] = []
+        archive_new_path: Optional[str] = None
+
+        try:
+            extracted = self.extract_unlocked(str(path), str(staging), password)
+            if operation_id is not None:
+                self.journal.record_step(
+                    operation_id, 'create', str(path), str(staging),
+                    {'entries': len(extracted), 'role': 'staging'}
+                )
+
+            # Moves made by the action manager are recorded into this operation
+            with self.action_manager.journal_scope(operation_id):
+                for file_path in extracted:
+                    classification = self.classifier.classify(str(file_path))
+                    result = self.action_manager.execute(str(file_path), classification, user_approved=user_approved)
+
+                    files.append({
+                        'name': str(file_path.relative_to(staging.resolve())),
+                        'category': classification.get('category'),
+                        'success': result.get('success', False),
+                        'new_path': result.get('new_path'),
+                        'message': result.get('message', '')
+                    })
+
+            if original_action == 'trash':
+                trash_dest = self._trash_root() / (str(operation_id) if operation_id is not None else stamp) / path.name
+                archive_new_path = str(trash_dest)
+                if not dry_run:
+                    trash_dest.parent.mkdir(parents=True, exist_ok=True)
+                    shutil.move(str(path), str(trash_dest))
+                    self.journal.record_step(operation_id, 'move', str(path), str(trash_dest), {'role': 'trash'})
+
+            if operation_id is not None:
+                self.journal.complete(operation_id)
+                self._remove_empty_dirs(staging)
+
+        except ArchivePasswordError as e:
+            logger.info(f"Encrypted archive left untouched: {path} ({e.message})")
+            if operation_id is not None:
+                self.journal.complete(operation_id, success=False)
+                shutil.rmtree(staging, ignore_errors=True)
+            return {
+                'success': False,
+                'message': e.message,
+                'operation_id': operation_id,
+                'files': [],
+                'needs_password': True,
+                'wrong_password': e.wrong_password
+            }
+        except Exception as e:
+            logger.error(f"Extract-and-organize failed for {path}: {e}", exc_info=True)
+            if operation_id is not None:
+                self.journal.complete(operation_id, success=False)
+                rollback = self.journal.undo(operation_id)
+                logger.info(f"Rolled back partial operation {operation_id}: {rollback['message']}")
+            return {
+                'success': False,
+                'message': f'Extract-and-organize failed: {str(e)}',
+                'operation_id': operation_id,
+                'files': files
+            }
+        finally:
+            if dry_run:
+                shutil.rmtree(staging, ignore_errors=True)
+
+        organized = sum(1 for f in files if f['success'])
+        remaining = len(files) - organized
+        prefix = '[DRY RUN] Would organize' if dry_run else 'Organized'
+        message = f"{prefix} {organized} of {len(files)} file(s) from {path.name}"
+        if remaining and not dry_run:
+            message += f"; {remaining} left in {staging}"
+        if original_action == 'trash':
+            message += '; archive trashed' if not dry_run else '; archive would be trashed'
+
+        return {
+            'success': True,
+            'message': message,
+            'operation_id': operation_id,
+            'files': files,
+            'organized': organized,
+            'remaining': remaining,
+            'staging_dir': None if dry_run else str(staging),
+            'archive_new_path': archive_new_path,
+            'dry_run': dry_run
+        }
+
+    # ==================== Archive Creation ====================
+
+    def create_archive(self, file_paths: List[str], archive_path: str,
+                       remove_sources: bool = False, base_dir: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Bundle files into a compressed archive with a manifest.
+
+        The archive is verified against the manifest before sources are
+        removed; removed sources go to the trash holding directory so the
+        operation can be undone.
+
+        Args:
+            file_paths (List[str]): Files to bundle
+            archive_path (str): Target archive path; an existing file gets a _N suffix
+            remove_sources (bool): Trash the source files after successful verification
+            base_dir (str, optional): Directory member names are relative to
+
+        Returns:
+            Dict: Result with 'success', 'message', 'archive_path', 'operation_id',
+                  'files' (count), 'verification' and 'removed_sources'
+        """
+        if not file_paths:
+            return {'success': False, 'message': 'No files to archive'}
+
+        target = Path(archive_path).expanduser()
+        if archive_format(target) is None:
+            return {'success': False, 'message': f'Unsupported archive format: {target.name}'}
+
+        sources = [Path(p) for p in file_paths]
+        missing = [str(p) for p in sources if not p.is_file()]
+        if missing:
+            return {'success': False, 'message': f'File(s) not found: {", ".join(missing)}'}
+
+        guardian = getattr(self.action_manager, 'safety_guardian', None)
+        if remove_sources and guardian is not None:
+            for source in sources:
+                safe, reason = guardian.is_file_safe_to_modify(source)
+                if not safe:
+                    return {'success': False, 'message': f'Cannot remove {source}: {reason}'}
+
+        if target.exists():
+            stem = archive_stem(target)
+            suffix = target.name[len(stem):]
+            counter = 1
+            while target.exists():
+                target = target.with_name(f"{stem}_{counter}{suffix}")
+                counter += 1
+
+        dry_run = getattr(self.action_manager, 'dry_run', getattr(self.config, 'dry_run', False))
+        if dry_run:
+            action = 'and trash the sources' if remove_sources else ''
+            return {
+                'success': True,
+                'message': f'[DRY RUN] Would archive {len(sources)} file(s) to {target} {action}'.strip(),
+                'archive_path': str(target),
+                'operation_id': None,
+                'files': len(sources),
+                'dry_run': True
+            }
+
+        operation_id = self.journal.begin('create_archive', f'Archive {len(sources)} file(s) to {target.name}')
+        try:
+            manifest = write_archive([str(p) for p in sources], str(target), base_dir=base_dir)
+            self.journal.record_step(operation_id, 'create', None, str(target), {'files': len(sources)})
+
+            verification = verify_archive(str(target), manifest)
+            if not verification['success']:
+                self.journal.complete(operation_id, success=False)
+                self.journal.undo(operation_id)
+                return {
+                    'success': False,
+                    'message': f"Archive verification failed: {verification['message']}",
+                    'operation_id': operation_id,
+                    'verification': verification
+                }
+
+            removed: List[str] = []
+            if remove_sources:
+                trash_root = self._trash_root() / str(operation_id)
+                for entry in manifest['files']:
+                    trash_dest = trash_root / entry['name']
+                    trash_dest.parent.mkdir(parents=True, exist_ok=True)
+                    shutil.move(entry['source'], str(trash_dest))
+                    self.journal.record_step(operation_id, 'move', entry['source'], str(trash_dest), {'role': 'trash'})
+                    removed.append(entry['source'])
+
+            self.journal.complete(operation_id)
+
+        except Exception as e:
+            logger.error(f"Archive creation failed for {target}: {e}", exc_info=True)
+            self.journal.complete(operation_id, success=False)
+            self.journal.undo(operation_id)
+            return {'success': False, 'message': f'Archive creation failed: {str(e)}', 'operation_id': operation_id}
+
+        try:
+            self.db_manager.log_action(
+                filename=target.name,
+                old_path=str(Path(manifest['files'][0]['source']).parent),
+                new_path=str(target),
+                operation='compress',
+                time_saved=self.config.time_estimates.get('archive', 0.4),
+                user_approved=True
+            )
+        except Exception as e:
+            logger.warning(f"Could not log archive creation: {e}")
+
+        message = f'Archived {len(sources)} file(s) to {target}'
+        if removed:
+            message += f' and trashed {len(removed)} source(s)'
+
+        return {
+            'success': True,
+            'message': message,
+            'archive_path': str(target),
+            'operation_id': operation_id,
+            'files': len(sources),
+            'verification': verification,
+            'removed_sources': removed
+        }
+
+    @staticmethod
+    def _remove_empty_dirs(root: Path) -> None:
+        """Remove empty directories below (and including) root."""
+        if not root.exists():
+            return
+        for directory in sorted((p for p in root.rglob('*') if p.is_dir()), key=lambda p: len(p.parts), reverse=True):
+            try:
+                directory.rmdir()
+            except OSError:
+                pass
+        try:
+            root.rmdir()
+        except OSError:
+            pass
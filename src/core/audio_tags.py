@@ -0,0 +1,350 @@
+"""
+Audio Tags Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module reads the tags the music library needs (title, artist, album,
+album artist, track and disc numbers, year, genre, compilation flag) from
+audio files. Mutagen is used when installed; otherwise the common formats
+are read with the standard library:
+
+- MP3 and other ID3 files: ID3v2.2/2.3/2.4, falling back to ID3v1
+- FLAC, Ogg Vorbis and Opus: Vorbis comments
+- M4A/MP4/ALAC: iTunes-style metadata atoms
+
+Every reader returns the same normalized dict; fields that are missing
+are simply absent.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import re
+import struct
+from pathlib import Path
+from typing import Dict, Any, BinaryIO, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+try:
+    import mutagen  # type: ignore
+    HAS_MUTAGEN = True
+except ImportError:
+    mutagen = None  # type: ignore
+    HAS_MUTAGEN = False
+
+
+AUDIO_EXTENSIONS = {'.mp3', '.flac', '.ogg', '.oga', '.opus', '.m4a', '.m4b', '.mp4', '.aac',
+                    '.alac', '.wav', '.aiff', '.aif', '.wma', '.ape', '.wv'}
+FIELDS = ('title', 'artist', 'album', 'albumartist', 'track', 'tracktotal',
+          'disc', 'disctotal', 'year', 'genre', 'compilation')
+MAX_HEADER_BYTES = 256 * 1024
+
+# Frame/key names per format -> normalized field
+ID3_FRAMES = {
+    'TIT2': 'title', 'TPE1': 'artist', 'TALB': 'album', 'TPE2': 'albumartist', 'TRCK': 'track',
+    'TPOS': 'disc', 'TYER': 'year', 'TDRC': 'year', 'TCON': 'genre', 'TCMP': 'compilation',
+    'TT2': 'title', 'TP1': 'artist', 'TAL': 'album', 'TP2': 'albumartist', 'TRK': 'track',
+    'TPA': 'disc', 'TYE': 'year', 'TCO': 'genre', 'TCP': 'compilation',
+}
+VORBIS_KEYS = {
+    'TITLE': 'title', 'ARTIST': 'artist', 'ALBUM': 'album', 'ALBUMARTIST': 'albumartist',
+    'ALBUM ARTIST': 'albumartist', 'TRACKNUMBER': 'track', 'TRACKTOTAL': 'tracktotal',
+    'TOTALTRACKS': 'tracktotal', 'DISCNUMBER': 'disc', 'DISCTOTAL': 'disctotal',
+    'TOTALDISCS': 'disctotal', 'DATE': 'year', 'YEAR': 'year', 'GENRE': 'genre',
+    'COMPILATION': 'compilation',
+}
+MP4_ATOMS = {
+    b'\xa9nam': 'title', b'\xa9ART': 'artist', b'\xa9alb': 'album', b'aART': 'albumartist',
+    b'\xa9day': 'year', b'\xa9gen': 'genre', b'trkn': 'track', b'disk': 'disc', b'cpil': 'compilation',
+}
+_NUMBER_PAIR = re.compile(r'^\s*(\d+)\s*(?:/\s*(\d+))?')
+_YEAR = re.compile(r'(\d{4})')
+
+
+def read_tags(path: str) -> Dict[str, Any]:
+    """
+    Read the tags of an audio file.
+
+    Args:
+        path (str): Audio file
+
+    Returns:
+        Dict: Normalized tags: strings for text fields, ints for track,
+              tracktotal, disc, disctotal and year, a bool for compilation
+    """
+    file_path = Path(path)
+    raw: Dict[str, Any] = {}
+    try:
+        if HAS_MUTAGEN:
+            raw = _read_with_mutagen(file_path)
+        if not raw:
+            raw = _read_builtin(file_path)
+    except (OSError, struct.error, ValueError, IndexError) as e:
+        logger.debug(f"Unreadable tags in {path}: {e}")
+        return {}
+    except Exception as e:  # Mutagen raises its own error types
+        logger.debug(f"Tag reader failed for {path}: {e}")
+        return {}
+    return normalize(raw)
+
+
+def normalize(raw: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Normalize raw tag values (e.g. '3/12' track numbers, '2019-04-01' dates).
+
+    Args:
+        raw (Dict): Field -> raw value
+
+    Returns:
+        Dict: Field -> normalized value, without empty fields
+    """
+    tags: Dict[str, Any] = {}
+    for field in ('title', 'artist', 'album', 'albumartist', 'genre'):
+        value = raw.get(field)
+        if isinstance(value, (list, tuple)):
+            value = value[0] if value else None
+        if value is not None and str(value).strip(' \x00'):
+            tags[field] = str(value).strip(' \x00')
+    genre = tags.get('genre', '')
+    if re.fullmatch(r'\(?\d+\)?', genre):
+        del tags['genre']  # ID3v1 numeric genre reference, not worth a lookup table
+    for field, total in (('track', 'tracktotal'), ('disc', 'disctotal')):
+        value = raw.get(field)
+        if isinstance(value, tuple):
+            number, count = value
+        else:
+            match = _NUMBER_PAIR.match(str(value or ''))
+            number, count = (int(match.group(1)), int(match.group(2) or 0)) if match else (0, 0)
+        if number:
+            tags[field] = int(number)
+        raw_total = raw.get(total)
+        count = count or (int(_NUMBER_PAIR.match(str(raw_total)).group(1))
+                          if raw_total and _NUMBER_PAIR.match(str(raw_total)) else 0)
+        if count:
+            tags[total] = int(count)
+    year = _YEAR.search(str(raw.get('year') or ''))
+    if year and year.group(1) != '0000':
+        tags['year'] = int(year.group(1))
+    compilation = raw.get('compilation')
+    if compilation not in (None, ''):
+        tags['compilation'] = str(compilation).strip().lower() in ('1', 'true', 'yes')
+    return tags
+
+
+def _read_with_mutagen(path: Path) -> Dict[str, Any]:
+    audio = mutagen.File(str(path), easy=True)
+    if audio is None or not audio.tags:
+        return {}
+    keys = {'title': 'title', 'artist': 'artist', 'album': 'album', 'albumartist': 'albumartist',
+            'tracknumber': 'track', 'discnumber': 'disc', 'date': 'year', 'genre': 'genre',
+            'compilation': 'compilation'}
+    raw = {}
+    for key, field in keys.items():
+        try:
+            value = audio.tags.get(key)
+        except (KeyError, ValueError):
+            continue
+        if value:
+            raw[field] = value[0] if isinstance(value, list) else value
+    return raw
+
+
+def _read_builtin(path: Path) -> Dict[str, Any]:
+    with open(path, 'rb') as f:
+        head = f.read(12)
+        f.seek(0)
+        if head[:3] == b'ID3':
+            return _read_id3v2(f) or _read_id3v1(f)
+        if head[:4] == b'fLaC':
+            return _read_flac(f)
+        if head[:4] == b'OggS':
+            return _read_ogg(f)
+        if head[4:8] == b'ftyp':
+            return _read_mp4(f)
+        return _read_id3v1(f)
+
+
+# ==================== ID3 ====================
+
+def _id3_text(data: bytes) -> str:
+    """Decode an ID3 text frame (first value only)."""
+    if not data:
+        return ''
+    encoding, body = data[0], data[1:]
+    if encoding == 0:
+        text = body.decode('latin-1')
+    elif encoding == 1:
+        text = body.decode('utf-16', errors='replace')
+    elif encoding == 2:
+        text = body.decode('utf-16-be', errors='replace')
+    else:
+        text = body.decode('utf-8', errors='replace')
+    return text.split('\x00')[0]
+
+
+def _syncsafe(data: bytes) -> int:
+    return (data[0] << 21) | (data[1] << 14) | (data[2] << 7) | data[3]
+
+
+def _read_id3v2(f: BinaryIO) -> Dict[str, Any]:
+    header = f.read(10)
+    major, flags = header[3], header[5]
+    size = _syncsafe(header[6:10])
+    data = f.read(min(size, MAX_HEADER_BYTES * 8))
+    pos = 0
+    if flags & 0x40 and major >= 3:  # Extended header
+        ext_size = _syncsafe(data[:4]) if major == 4 else struct.unpack('>L', data[:4])[0] + 4
+        pos = ext_size
+    id_len, header_len = (3, 6) if major == 2 else (4, 10)
+
+    raw: Dict[str, Any] = {}
+    while pos + header_len <= len(data):
+        frame_id = data[pos:pos + id_len].decode('latin-1', errors='replace')
+        if not frame_id.strip('\x00'):
+            break  # Padding
+        if major == 2:
+            frame_size = int.from_bytes(data[pos + 3:pos + 6], 'big')
+        elif major == 4:
+            frame_size = _syncsafe(data[pos + 4:pos + 8])
+        else:
+            frame_size = struct.unpack('>L', data[pos + 4:pos + 8])[0]
+        body = data[pos + header_len:pos + header_len + frame_size]
+        field = ID3_FRAMES.get(frame_id)
+        if field and field not in raw:
+            raw[field] = _id3_text(body)
+        pos += header_len + frame_size
+    return raw
+
+
+def _read_id3v1(f: BinaryIO) -> Dict[str, Any]:
+    f.seek(0, 2)
+    if f.tell() < 128:
+        return {}
+    f.seek(-128, 2)
+    block = f.read(128)
+    if block[:3] != b'TAG':
+        return {}
+
+    def text(start, end):
+        return block[start:end].split(b'\x00')[0].decode('latin-1').strip()
+
+    raw = {'title': text(3, 33), 'artist': text(33, 63), 'album': text(63, 93), 'year': text(93, 97)}
+    if block[125] == 0 and block[126]:
+        raw['track'] = str(block[126])
+    return raw
+
+
+# ==================== Vorbis comments ====================
+
+def _vorbis_comments(data: bytes) -> Dict[str, Any]:
+    """Parse a Vorbis comment block (vendor string, then KEY=value entries)."""
+    vendor_len = struct.unpack('<L', data[:4])[0]
+    pos = 4 + vendor_len
+    count = struct.unpack('<L', data[pos:pos + 4])[0]
+    pos += 4
+    raw: Dict[str, Any] = {}
+    for _ in range(count):
+        length = struct.unpack('<L', data[pos:pos + 4])[0]
+        entry = data[pos + 4:pos + 4 + length].decode('utf-8', errors='replace')
+        pos += 4 + length
+        if len(entry) < length // 4:
+            break  # Truncated
+        key, _, value = entry.partition('=')
+        field = VORBIS_KEYS.get(key.upper())
+        if field and field not in raw:
+            raw[field] = value
+    return raw
+
+
+def _read_flac(f: BinaryIO) -> Dict[str, Any]:
+    f.seek(4)
+    while True:
+        header = f.read(4)
+        if len(header) < 4:
+            return {}
+        last, block_type = header[0] & 0x80, header[0] & 0x7F
+        length = int.from_bytes(header[1:4], 'big')
+        if block_type == 4:
+            return _vorbis_comments(f.read(length))
+        if last:
+            return {}
+        f.seek(length, 1)
+
+
+def _read_ogg(f: BinaryIO) -> Dict[str, Any]:
+    # Join the packet data of the first pages, dropping the page headers
+    packets = b''
+    for _ in range(8):
+        header = f.read(27)
+        if len(header) < 27 or header[:4] != b'OggS':
+            break
+        segments = f.read(header[26])
+        packets += f.read(sum(segments))
+    for marker in (b'\x03vorbis', b'OpusTags'):
+        start = packets.find(marker)
+        if start >= 0:
+            return _vorbis_comments(packets[start + len(marker):])
+    return {}
+
+
+# ==================== MP4 ====================
+
+def _atoms(f: BinaryIO, start: int, end: int):
+    """Yield (type, payload start, payload end) for the atoms in a byte range."""
+    pos = start
+    while pos + 8 <= end:
+        f.seek(pos)
+        size, kind = struct.unpack('>L4s', f.read(8))
+        header = 8
+        if size == 1:
+            size = struct.unpack('>Q', f.read(8))[0]
+            header = 16
+        elif size == 0:
+            size = end - pos
+        if size < header:
+            return
+        yield kind, pos + header, min(pos + size, end)
+        pos += size
+
+
+def _find_atom(f: BinaryIO, start: int, end: int, path: Tuple[bytes, ...]) -> Optional[Tuple[int, int]]:
+    for kind, payload, atom_end in _atoms(f, start, end):
+        if kind != path[0]:
+            continue
+        if kind == b'meta':
+            payload += 4  # Version and flags
+        if len(path) == 1:
+            return payload, atom_end
+        return _find_atom(f, payload, atom_end, path[1:])
+    return None
+
+
+def _read_mp4(f: BinaryIO) -> Dict[str, Any]:
+    f.seek(0, 2)
+    ilst = _find_atom(f, 0, f.tell(), (b'moov', b'udta', b'meta', b'ilst'))
+    if ilst is None:
+        return {}
+    raw: Dict[str, Any] = {}
+    for kind, payload, end in _atoms(f, *ilst):
+        field = MP4_ATOMS.get(kind)
+        if field is None:
+            continue
+        data = _find_atom(f, payload, end, (b'data',))
+        if data is None:
+            continue
+        f.seek(data[0] + 8)  # Type and locale
+        value = f.read(min(data[1] - data[0] - 8, 4096))
+        if kind in (b'trkn', b'disk'):
+            if len(value) >= 6:
+                raw[field] = struct.unpack('>HH', value[2:6])
+        elif kind == b'cpil':
+            raw[field] = '1' if value[:1] == b'\x01' else '0'
+        else:
+            raw[field] = value.decode('utf-8', errors='replace')
+    return raw
@@ -0,0 +1,196 @@
+"""
+Autostart Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module starts the organiser when the user logs in, in background
+mode (`python -m src.main background`): the dashboard server, folder
+watcher and scheduled organize runs keep going with only the tray icon
+showing, so files are organized without the window open. The tray's
+"Open dashboard" brings the window back; closing it changes nothing.
+
+Start-on-login is registered the way each system expects, for the
+current user only:
+
+- Windows: a value in HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Run
+- macOS: a launch agent in ~/Library/LaunchAgents
+- Linux: a desktop entry in ~/.config/autostart (XDG autostart)
+
+Packaged builds register their executable; a source checkout registers
+the interpreter resolve_python() picks, started in the app folder.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import plistlib
+import subprocess
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .interpreter import resolve_app_root, resolve_python
+
+try:
+    import winreg  # type: ignore
+    HAS_WINREG = True
+except ImportError:
+    winreg = None  # type: ignore
+    HAS_WINREG = False
+
+logger = logging.getLogger(__name__)
+
+
+APP_NAME = 'AI File Organiser'
+AGENT_LABEL = 'com.aifileorganiser.background'
+DESKTOP_FILE = 'ai-file-organiser.desktop'
+RUN_KEY = r'Software\Microsoft\Windows\CurrentVersion\Run'
+BACKGROUND_COMMAND = 'background'
+# Windows' Run key has no working folder, so a source checkout changes into the app folder itself
+_WINDOWS_BOOTSTRAP = ("import os, runpy, sys; os.chdir({root!r}); sys.path.insert(0, {root!r}); "
+                      "sys.argv = ['aifo', {command!r}]; runpy.run_module('src.main', run_name='__main__')")
+
+
+def _system() -> str:
+    if sys.platform.startswith('win'):
+        return 'windows'
+    return 'macos' if sys.platform == 'darwin' else 'linux'
+
+
+def launch_command(config=None) -> Tuple[List[str], Optional[str]]:
+    """
+    The command line that starts background mode, and the folder to start it in.
+
+    Args:
+        config: Configuration object (reads `runtime_settings`; default: the active configuration)
+
+    Returns:
+        Tuple[List[str], Optional[str]]: (arguments, working folder or None)
+    """
+    if getattr(sys, 'frozen', False):
+        return [sys.executable, BACKGROUND_COMMAND], None
+    root = str(resolve_app_root(config))
+    python = resolve_python(config)
+    if _system() != 'windows':
+        return [python, '-m', 'src.main', BACKGROUND_COMMAND], root
+    # pythonw runs without a console window
+    windowless = Path(python).with_name('pythonw.exe')
+    python = str(windowless) if windowless.is_file() else python
+    return [python, '-c', _WINDOWS_BOOTSTRAP.format(root=root, command=BACKGROUND_COMMAND)], None
+
+
+def _entry_path() -> Path:
+    if _system() == 'macos':
+        return Path.home() / 'Library' / 'LaunchAgents' / f"{AGENT_LABEL}.plist"
+    base = os.environ.get('XDG_CONFIG_HOME') or str(Path.home() / '.config')
+    return Path(base) / 'autostart' / DESKTOP_FILE
+
+
+def _desktop_quote(argument: str) -> str:
+    """Quote an argument for a desktop entry's Exec key."""
+    if argument and not any(c in argument for c in ' \t\n"\'\\><~|&;$*?#()`'):
+        return argument
+    escaped = ''.join('\\' + c if c in '"`$\\' else c for c in argument)
+    return f'"{escaped}"'
+
+
+def _desktop_entry(command: List[str], cwd: Optional[str]) -> str:
+    lines = ['[Desktop Entry]', 'Type=Application', f"Name={APP_NAME}",
+             'Comment=Keeps organizing files in the background',
+             'Exec=' + ' '.join(_desktop_quote(argument) for argument in command)]
+    if cwd:
+        lines.append(f"Path={cwd}")
+    lines += ['Terminal=false', 'X-GNOME-Autostart-enabled=true']
+    return '\n'.join(lines) + '\n'
+
+
+def _launch_agent(command: List[str], cwd: Optional[str]) -> bytes:
+    agent: Dict[str, Any] = {'Label': AGENT_LABEL, 'ProgramArguments': command, 'RunAtLoad': True,
+                             'ProcessType': 'Interactive'}
+    if cwd:
+        agent['WorkingDirectory'] = cwd
+    return plistlib.dumps(agent)
+
+
+def autostart_status() -> Dict[str, Any]:
+    """
+    Whether the organiser starts on login.
+
+    Returns:
+        Dict: 'enabled', 'system', 'location' (registry value or file) and
+              'command' (the registered command line, or None)
+    """
+    system = _system()
+    if system == 'windows':
+        location = f"HKCU\\{RUN_KEY}\\{APP_NAME}"
+        command = None
+        if HAS_WINREG:
+            try:
+                with winreg.OpenKey(winreg.HKEY_CURRENT_USER, RUN_KEY) as key:
+                    command = winreg.QueryValueEx(key, APP_NAME)[0]
+            except OSError:
+                command = None
+        return {'enabled': command is not None, 'system': system, 'location': location, 'command': command}
+
+    path = _entry_path()
+    command = None
+    try:
+        if system == 'macos':
+            command = subprocess.list2cmdline(plistlib.loads(path.read_bytes()).get('ProgramArguments', []))
+        else:
+            command = next((line[5:] for line in path.read_text(encoding='utf-8').splitlines()
+                            if line.startswith('Exec=')), None)
+    except (OSError, ValueError):  # Missing, or not a plist
+        command = None
+    return {'enabled': command is not None, 'system': system, 'location': str(path), 'command': command}
+
+
+def set_autostart(enabled: bool, config=None) -> Dict[str, Any]:
+    """
+    Start the organiser in background mode on login, or stop doing so.
+
+    Args:
+        enabled (bool): Register (True) or remove (False) the login item
+        config: Configuration object (reads `runtime_settings`; default: the active configuration)
+
+    Returns:
+        Dict: 'success', 'message' and the autostart_status() fields
+    """
+    system = _system()
+    try:
+        if enabled:
+            command, cwd = launch_command(config)
+        if system == 'windows':
+            if not HAS_WINREG:
+                return {'success': False, 'message': 'The Windows registry is not available', **autostart_status()}
+            with winreg.CreateKey(winreg.HKEY_CURRENT_USER, RUN_KEY) as key:
+                if enabled:
+                    winreg.SetValueEx(key, APP_NAME, 0, winreg.REG_SZ, subprocess.list2cmdline(command))
+                else:
+                    try:
+                        winreg.DeleteValue(key, APP_NAME)
+                    except FileNotFoundError:
+                        pass
+        else:
+            path = _entry_path()
+            if enabled:
+                path.parent.mkdir(parents=True, exist_ok=True)
+                if system == 'macos':
+                    path.write_bytes(_launch_agent(command, cwd))
+                else:
+                    path.write_text(_desktop_entry(command, cwd), encoding='utf-8')
+            else:
+                path.unlink(missing_ok=True)
+    except OSError as e:  # Also FileNotFoundError: no interpreter to register
+        logger.error(f"Could not {'enable' if enabled else 'disable'} start on login: {e}")
+        return {'success': False, 'message': f"Could not change start on login: {e}", **autostart_status()}
+
+    logger.info(f"Start on login {'enabled' if enabled else 'disabled'}")
+    message = 'Starts in the background on login' if enabled else 'No longer starts on login'
+    return {'success': True, 'message': message, **autostart_status()}
@@ -0,0 +1,217 @@
+"""
+Backend Check Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module checks that the Python side of the app can run and reports
+what to fix when it cannot, so the UI can walk the user through setup
+instead of showing "Python script failed". check_backend() looks at:
+
+- python: a supported interpreter, the one child processes run (see
+  core.interpreter), and python3 on PATH for the shell extensions
+- scripts: the app's entry scripts are installed, at the version the
+  caller expects
+- packages: required packages import; optional ones are reported with
+  the feature that is off without them
+- ai: the AI backend the settings select - a running Ollama server with
+  its model pulled, or an API key for a cloud provider
+
+Every check is {"name", "status": "ok" | "warning" | "error", "message",
+"fix"}; errors stop the backend from working, warnings only disable a
+feature.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import importlib.util
+import logging
+import os
+import sys
+from importlib import metadata
+from typing import Dict, Any, List, Optional
+
+from .interpreter import python_on_path, resolve_python
+from .journal import PROJECT_ROOT
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+MIN_PYTHON = (3, 8)
+
+# Entry scripts the app starts, relative to the project root
+EXPECTED_SCRIPTS = ('launcher.py', 'src/main.py', 'src/cli/commands.py', 'src/ui/dashboard.py')
+
+# Import name -> distribution to install
+REQUIRED_PACKAGES = {
+    'requests': 'requests', 'yaml': 'PyYAML', 'pydantic': 'pydantic', 'click': 'click',
+    'fastapi': 'fastapi', 'uvicorn': 'uvicorn',
+}
+
+# Import name -> (distribution, what is off without it)
+OPTIONAL_PACKAGES = {
+    'watchdog': ('watchdog', 'watching folders'),
+    'PyPDF2': ('PyPDF2', 'reading text from PDFs'),
+    'docx': ('python-docx', 'reading text from Word documents'),
+    'PIL': ('Pillow', 'photo metadata and thumbnails'),
+    'mutagen': ('mutagen', 'music tags'),
+    'keyring': ('keyring', 'keeping API keys in the OS keychain'),
+    'diskcache': ('diskcache', 'the classification disk cache'),
+}
+
+OLLAMA_DOWNLOAD = 'https://ollama.com/download'
+
+
+def _check(name: str, status: str, message: str, fix: Optional[str] = None) -> Dict[str, Any]:
+    return {'name': name, 'status': status, 'message': message, 'fix': fix}
+
+
+def check_backend(config=None, expected_version: Optional[str] = None, probe_ai: bool = True) -> Dict[str, Any]:
+    """
+    Check the interpreter, scripts, packages and AI backend.
+
+    Args:
+        config: Configuration object (default: the active configuration)
+        expected_version (str, optional): App version the caller was built for
+        probe_ai (bool): Contact the AI backend (False: only check its settings)
+
+    Returns:
+        Dict: 'success' (no errors), 'ai_ready', 'message', 'python' (the
+              interpreter) and 'checks' (see the module docstring)
+    """
+    if config is None:
+        from src.config import get_config
+        config = get_config()
+
+    checks = [*_check_python(), _check_scripts(expected_version), *_check_packages()]
+    ai = _check_ai(config, probe_ai)
+    checks.append(ai)
+
+    errors = [check for check in checks if check['status'] == 'error']
+    warnings = [check for check in checks if check['status'] == 'warning']
+    if errors:
+        message = f"{len(errors)} problem{'s' if len(errors) != 1 else ''} to fix before the backend can run"
+    elif warnings:
+        message = f"Backend ready; {len(warnings)} warning{'s' if len(warnings) != 1 else ''}"
+    else:
+        message = "Backend ready"
+    return {
+        'success': not errors,
+        'ai_ready': ai['status'] == 'ok',
+        'message': message,
+        'python': {'executable': sys.executable, 'version': '.'.join(str(part) for part in sys.version_info[:3])},
+        'checks': checks,
+    }
+
+
+def _check_python() -> List[Dict[str, Any]]:
+    checks = []
+    version = '.'.join(str(part) for part in sys.version_info[:3])
+    needed = '.'.join(str(part) for part in MIN_PYTHON)
+    if sys.version_info[:2] < MIN_PYTHON:
+        checks.append(_check('python', 'error', f"Python {version} is too old (needs {needed} or later)",
+                             f"Install Python {needed} or later from https://www.python.org/downloads/"))
+    else:
+        checks.append(_check('python', 'ok', f"Python {version} at {sys.executable}"))
+
+    try:
+        engine = resolve_python()
+        checks.append(_check('engine_python', 'ok', f"Child processes run {engine}"))
+    except FileNotFoundError as e:
+        checks.append(_check('engine_python', 'error', str(e),
+                             'Set runtime.python in config.json to a Python 3 interpreter'))
+
+    on_path = python_on_path()
+    if on_path:
+        checks.append(_check('python_on_path', 'ok', f"{on_path} is on PATH"))
+    else:
+        checks.append(_check('python_on_path', 'warning',
+                             "No python3 on PATH; shell extensions and scripts cannot start the engine",
+                             f"Add {os.path.dirname(sys.executable)} to PATH"))
+    return checks
+
+
+def _check_scripts(expected_version: Optional[str]) -> Dict[str, Any]:
+    missing = [script for script in EXPECTED_SCRIPTS if not (PROJECT_ROOT / script).is_file()]
+    if missing:
+        return _check('scripts', 'error', f"Missing from {PROJECT_ROOT}: {', '.join(missing)}",
+                      "Reinstall the app")
+    from src import __version__
+    if expected_version and expected_version != __version__:
+        return _check('scripts', 'error', f"Scripts are version {__version__}, expected {expected_version}",
+                      f"Reinstall the app, or update it to {expected_version}")
+    return _check('scripts', 'ok', f"Scripts {__version__} in {PROJECT_ROOT}")
+
+
+def _version(distribution: str) -> str:
+    try:
+        return metadata.version(distribution)
+    except metadata.PackageNotFoundError:
+        return 'unknown version'
+
+
+def _check_packages() -> List[Dict[str, Any]]:
+    missing = [(module, distribution) for module, distribution in REQUIRED_PACKAGES.items()
+               if importlib.util.find_spec(module) is None]
+    if missing:
+        distributions = ' '.join(distribution for _, distribution in missing)
+        checks = [_check('packages', 'error', f"Required packages missing: {distributions}",
+                         f'"{sys.executable}" -m pip install {distributions}')]
+    else:
+        checks = [_check('packages', 'ok', ', '.join(f"{distribution} {_version(distribution)}"
+                                                     for distribution in REQUIRED_PACKAGES.values()))]
+
+    off = [(distribution, feature) for module, (distribution, feature) in OPTIONAL_PACKAGES.items()
+           if importlib.util.find_spec(module) is None]
+    if off:
+        checks.append(_check('optional_packages', 'warning',
+                             'Not installed: ' + '; '.join(f"{distribution} ({feature})" for distribution, feature in off),
+                             f'"{sys.executable}" -m pip install {" ".join(distribution for distribution, _ in off)}'))
+    else:
+        checks.append(_check('optional_packages', 'ok', 'All optional packages installed'))
+    return checks
+
+
+def _check_ai(config, probe: bool) -> Dict[str, Any]:
+    from src.ai.providers import DEFAULT_OPENAI_URL, OllamaProvider, api_key, create_provider
+    from src.core.settings import SettingsStore
+
+    try:
+        tier = SettingsStore().load()['default_tier']
+    except ConfigurationError as e:
+        return _check('ai', 'error', f"Settings unreadable: {e}", "Fix or delete the settings file")
+    if tier == 'rules' or not config.enable_ai:
+        return _check('ai', 'ok', 'AI classification is off; files are sorted by rules')
+    try:
+        provider = create_provider(config)
+    except ConfigurationError as e:
+        return _check('ai', 'error', str(e), 'Set ai_provider.provider in config.json to ollama or openai')
+
+    if isinstance(provider, OllamaProvider) or tier == 'local':
+        ollama = provider if isinstance(provider, OllamaProvider) else OllamaProvider(
+            config.ollama_base_url, config.ollama_model)
+        if not probe:
+            return _check('ai', 'ok', f"Ollama at {ollama.base_url} (not contacted)")
+        status = ollama.detect()
+        if not status['running']:
+            return _check('ai', 'warning', f"No Ollama server answers at {ollama.base_url}; files are sorted by rules",
+                          f"Install Ollama from {OLLAMA_DOWNLOAD}, then run: ollama serve")
+        server = f"Ollama {status['version']}" if status['version'] else 'Ollama'
+        if not status['model_installed']:
+            return _check('ai', 'warning', f"{server} is running but has no {ollama.model}",
+                          f"ollama pull {ollama.model}")
+        return _check('ai', 'ok', f"{server} at {ollama.base_url} with {ollama.model}")
+
+    # A local OpenAI-compatible server (LM Studio, vLLM) may not need a key
+    if not api_key(provider.name) and provider.base_url == DEFAULT_OPENAI_URL:
+        return _check('ai', 'error', f"No API key for {provider.name}",
+                      f"Add your {provider.name} API key in Settings, or set {provider.name.upper()}_API_KEY")
+    if probe and not provider.is_available():
+        return _check('ai', 'warning', f"{provider.name} at {provider.base_url} did not accept the request",
+                      "Check the API key and your internet connection")
+    return _check('ai', 'ok', f"{provider.name} at {provider.base_url} with {provider.model}")
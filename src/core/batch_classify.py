@@ -0,0 +1,84 @@
+"""
+Batch Classification Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module classifies many files in one request, a few at a time, so a
+front end showing hundreds of files does not make hundreds of round trips.
+At most max_concurrent classifications run at once (AI calls are the slow
+part, and the backend serves only so many); results come back in the
+order the paths were given, one per path:
+
+    {"path", "ok": true, "result": {...}, "error": null}
+    {"path", "ok": false, "result": null, "error": "Permission denied"}
+
+Each finished file is reported as a 'classify://progress' event with
+'done', 'total', 'file' and 'ok'; streams end with a 'classify://done'
+event carrying the outcomes.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+from concurrent.futures import ThreadPoolExecutor, as_completed
+from typing import Callable, Dict, Any, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+EVENT_CLASSIFY_PROGRESS = 'classify://progress'
+EVENT_CLASSIFY_DONE = 'classify://done'
+DEFAULT_CONCURRENCY = 4
+MAX_CONCURRENCY = 16
+MAX_BATCH = 5000
+
+
+def classify_files(classify: Callable[[str], Dict[str, Any]], paths: List[str],
+                   max_concurrent: int = DEFAULT_CONCURRENCY,
+                   on_event: Optional[Callable[[str, Dict[str, Any]], None]] = None) -> List[Dict[str, Any]]:
+    """
+    Classify files concurrently.
+
+    Args:
+        classify (Callable): Classifies one path (e.g. FileClassifier.classify)
+        paths (List[str]): Files to classify (at most MAX_BATCH)
+        max_concurrent (int): Classifications running at once (1 to MAX_CONCURRENCY)
+        on_event (Callable, optional): Called as (event, payload) after each file
+
+    Returns:
+        List[Dict]: One outcome per path, in the order given
+
+    Raises:
+        ValueError: If there are more than MAX_BATCH paths
+    """
+    paths = [str(path) for path in paths]
+    if len(paths) > MAX_BATCH:
+        raise ValueError(f"At most {MAX_BATCH} files per batch (got {len(paths)})")
+    workers = max(1, min(int(max_concurrent or 1), MAX_CONCURRENCY))
+    outcomes: List[Optional[Dict[str, Any]]] = [None] * len(paths)
+    done = 0
+
+    def run(path: str) -> Dict[str, Any]:
+        try:
+            return {'path': path, 'ok': True, 'result': classify(path), 'error': None}
+        except Exception as e:
+            logger.warning(f"Could not classify {path}: {e}")
+            return {'path': path, 'ok': False, 'result': None, 'error': str(e) or type(e).__name__}
+
+    with ThreadPoolExecutor(max_workers=workers, thread_name_prefix='classify') as pool:
+        futures = {pool.submit(run, path): index for index, path in enumerate(paths)}
+        for future in as_completed(futures):
+            outcome = outcomes[futures[future]] = future.result()
+            done += 1
+            payload = {'done': done, 'total': len(paths), 'file': outcome['path'], 'ok': outcome['ok']}
+            if on_event is not None:
+                try:
+                    on_event(EVENT_CLASSIFY_PROGRESS, payload)
+                except Exception as e:
+                    logger.debug(f"Classify listener failed: {e}")
+    return outcomes
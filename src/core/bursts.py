@@ -0,0 +1,335 @@
+"""
+Burst Detection Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module finds photo bursts and near-identical shots so a user can keep
+the best frame of each and archive or trash the rest in one action.
+
+Two photos belong to the same group when either
+
+- they are a burst: taken by the same camera at most
+  `bursts.burst_seconds` apart (chains of such shots form one burst), or
+- they look alike: taken at most `bursts.similar_seconds` apart and their
+  perceptual hashes (64-bit difference hash) differ in at most
+  `bursts.max_distance` bits.
+
+Capture times come from EXIF, falling back to the file date. Perceptual
+hashes and the sharpness used to pick the best frame need Pillow; without
+it only bursts are found and the largest file is suggested as the best.
+
+Resolving groups archives (or trashes) every photo but the one kept, as a
+single operation on the undo stack.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .exif import PHOTO_EXTENSIONS, RAW_EXTENSIONS, read_exif, capture_time
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_BURST_SECONDS = 2.0
+DEFAULT_SIMILAR_SECONDS = 60.0
+DEFAULT_MAX_DISTANCE = 6       # Of 64 hash bits
+ACTIONS = ('archive', 'trash')
+# RAW files cannot be decoded for hashing, and are paired with their JPEGs separately
+BURST_EXTENSIONS = PHOTO_EXTENSIONS - RAW_EXTENSIONS
+
+
+def _pillow():
+    try:
+        from PIL import Image, ImageFilter, ImageOps, ImageStat
+    except ImportError:
+        return None
+    return Image, ImageFilter, ImageOps, ImageStat
+
+
+def perceptual_hash(path: str) -> Optional[int]:
+    """
+    64-bit difference hash: brightness gradients of a 9x8 grayscale thumbnail.
+
+    Args:
+        path (str): Photo file
+
+    Returns:
+        int or None: Hash, or None without Pillow or for unreadable images
+    """
+    pillow = _pillow()
+    if pillow is None:
+        return None
+    Image, _, ImageOps, _ = pillow
+    try:
+        with Image.open(path) as opened:
+            image = ImageOps.exif_transpose(opened).convert('L').resize((9, 8))
+    except Exception as e:
+        logger.debug(f"Cannot hash {path}: {e}")
+        return None
+    pixels = list(image.getdata())
+    value = 0
+    for row in range(8):
+        for col in range(8):
+            value = (value << 1) | (pixels[row * 9 + col] > pixels[row * 9 + col + 1])
+    return value
+
+
+def sharpness(path: str) -> Optional[float]:
+    """
+    Edge variance of a downscaled grayscale copy; blurry frames score lower.
+
+    Args:
+        path (str): Photo file
+
+    Returns:
+        float or None: Score, or None without Pillow or for unreadable images
+    """
+    pillow = _pillow()
+    if pillow is None:
+        return None
+    Image, ImageFilter, ImageOps, ImageStat = pillow
+    try:
+        with Image.open(path) as opened:
+            image = ImageOps.exif_transpose(opened).convert('L')
+            image.thumbnail((512, 512))
+            return float(ImageStat.Stat(image.filter(ImageFilter.FIND_EDGES)).var[0])
+    except Exception as e:
+        logger.debug(f"Cannot measure sharpness of {path}: {e}")
+        return None
+
+
+def hamming(a: int, b: int) -> int:
+    """Number of differing bits between two hashes."""
+    return bin(a ^ b).count('1')
+
+
+class BurstDetector:
+    """
+    Groups bursts and near-identical photos and resolves them.
+
+    Attributes:
+        config: Configuration object
+        action_manager: Action manager used to archive or trash photos
+        burst_seconds (float): Largest gap between shots of one burst
+        similar_seconds (float): Largest gap between near-identical shots
+        max_distance (int): Largest hash distance for near-identical shots
+    """
+
+    def __init__(self, config, action_manager=None):
+        """
+        Initialize burst detector.
+
+        Args:
+            config: Configuration object
+            action_manager: Action manager (needed to resolve groups)
+        """
+        self.config = config
+        self.action_manager = action_manager
+        settings = getattr(config, 'burst_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.burst_seconds = float(settings.get('burst_seconds', DEFAULT_BURST_SECONDS))
+        self.similar_seconds = float(settings.get('similar_seconds', DEFAULT_SIMILAR_SECONDS))
+        self.max_distance = int(settings.get('max_distance', DEFAULT_MAX_DISTANCE))
+        self.archive_dir = settings.get('archive_dir') or None
+
+    # ==================== Detection ====================
+
+    def find_groups(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Find bursts and near-identical shots in a folder.
+
+        Args:
+            folder (str): Folder to look in
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: Result with 'success', 'message', 'groups', 'photos' (scanned)
+                  and 'perceptual' (whether look-alike detection was available).
+                  Each group has 'id', 'kind' ('burst' or 'similar'), 'start',
+                  'end', 'best' and 'photos' ({'path', 'size', 'taken',
+                  'sharpness'}, in shooting order).
+        """
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {folder}", 'groups': []}
+
+        photos = [self._describe(path) for path in self._photos(root, recursive)]
+        photos = [p for p in photos if p is not None]
+        photos.sort(key=lambda p: (p['_time'], p['path']))
+        perceptual = _pillow() is not None
+
+        parent = list(range(len(photos)))
+
+        def find(i):
+            while parent[i] != i:
+                parent[i] = parent[parent[i]]
+                i = parent[i]
+            return i
+
+        def union(i, j):
+            parent[find(j)] = find(i)
+
+        similar_links = set()
+        for i, photo in enumerate(photos):
+            for j in range(i + 1, len(photos)):
+                other = photos[j]
+                gap = other['_time'] - photo['_time']
+                if gap > max(self.burst_seconds, self.similar_seconds):
+                    break
+                if gap <= self.burst_seconds and photo['_camera'] == other['_camera']:
+                    union(i, j)
+                elif (gap <= self.similar_seconds and perceptual
+                      and self._hash(photo) is not None and self._hash(other) is not None
+                      and hamming(self._hash(photo), self._hash(other)) <= self.max_distance):
+                    union(i, j)
+                    similar_links.add(i)
+
+        members: Dict[int, List[int]] = {}
+        for i in range(len(photos)):
+            members.setdefault(find(i), []).append(i)
+
+        groups = []
+        for indices in members.values():
+            if len(indices) < 2:
+                continue
+            group_photos = [photos[i] for i in indices]
+            kind = 'similar' if any(i in similar_links for i in indices) else 'burst'
+            for photo in group_photos:
+                if photo['sharpness'] is None and perceptual:
+                    photo['sharpness'] = sharpness(photo['path'])
+            groups.append({
+                'id': len(groups) + 1,
+                'kind': kind,
+                'start': group_photos[0]['taken'],
+                'end': group_photos[-1]['taken'],
+                'best': self.best(group_photos),
+                'photos': [{k: v for k, v in p.items() if not k.startswith('_')} for p in group_photos]
+            })
+
+        extras = sum(len(g['photos']) - 1 for g in groups)
+        message = f"{len(groups)} group(s) in {len(photos)} photo(s); {extras} extra shot(s) could go"
+        if not perceptual:
+            message += " (install Pillow to also find look-alike shots)"
+        return {'success': True, 'message': message, 'groups': groups,
+                'photos': len(photos), 'perceptual': perceptual}
+
+    @staticmethod
+    def _photos(root: Path, recursive: bool) -> List[Path]:
+        found = []
+        for dirpath, dirnames, filenames in os.walk(root):
+            dirnames[:] = [d for d in dirnames if not d.startswith('.')] if recursive else []
+            for name in filenames:
+                path = Path(dirpath) / name
+                if not name.startswith('.') and path.suffix.lower() in BURST_EXTENSIONS and not path.is_symlink():
+                    found.append(path)
+        return found
+
+    @staticmethod
+    def _describe(path: Path) -> Optional[Dict[str, Any]]:
+        try:
+            stat = path.stat()
+        except OSError:
+            return None
+        tags = read_exif(str(path))
+        taken, _ = capture_time(tags)
+        if taken is None:
+            taken = datetime.fromtimestamp(stat.st_mtime)
+        # The camera's wall-clock time; shots of one burst share its offset
+        taken = taken.replace(tzinfo=None)
+        camera = ' '.join(str(tags.get(k, '')) for k in ('Make', 'Model', 'BodySerialNumber')).strip()
+        return {
+            'path': str(path),
+            'size': stat.st_size,
+            'taken': taken.isoformat(),
+            'sharpness': None,
+            '_time': taken.timestamp(),
+            '_camera': camera,
+            '_hash': False  # Not computed yet
+        }
+
+    @staticmethod
+    def _hash(photo: Dict[str, Any]) -> Optional[int]:
+        if photo['_hash'] is False:
+            photo['_hash'] = perceptual_hash(photo['path'])
+        return photo['_hash']
+
+    @staticmethod
+    def best(photos: List[Dict[str, Any]]) -> str:
+        """
+        The photo to keep: the sharpest, or the largest file when sharpness is unknown.
+
+        Args:
+            photos (List[Dict]): Group members with 'path', 'size' and 'sharpness'
+
+        Returns:
+            str: Path of the photo to keep
+        """
+        if all(p.get('sharpness') is not None for p in photos):
+            return max(photos, key=lambda p: (p['sharpness'], p['size']))['path']
+        return max(photos, key=lambda p: p['size'])['path']
+
+    # ==================== Resolving ====================
+
+    def resolve(self, groups: List[Dict[str, Any]], action: str = 'archive',
+                archive_dir: Optional[str] = None, dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Keep one photo per group and archive or trash the others, as one undoable operation.
+
+        Args:
+            groups (List[Dict]): Groups from find_groups(); a group's 'keep'
+                                 (a path) overrides its suggested 'best'
+            action (str): 'archive' or 'trash'
+            archive_dir (str, optional): Where archived shots go (default:
+                                         bursts.archive_dir, else the app's archive)
+            dry_run (bool): Only report what would happen
+
+        Returns:
+            Dict: Result with 'success', 'message', 'kept', 'removed' and 'failed'
+        """
+        if action not in ACTIONS:
+            return {'success': False, 'message': f"Unknown action {action!r} (use archive or trash)"}
+        if self.action_manager is None and not dry_run:
+            return {'success': False, 'message': 'No action manager to move photos with'}
+
+        targets, kept = [], []
+        for group in groups:
+            paths = [p['path'] if isinstance(p, dict) else str(p) for p in group.get('photos', [])]
+            keep = group.get('keep') or group.get('best')
+            if keep not in paths:
+                return {'success': False, 'message': f"Group {group.get('id', '?')}: the photo to keep is not in the group"}
+            kept.append(keep)
+            targets.extend(p for p in paths if p != keep)
+
+        if dry_run:
+            return {'success': True, 'kept': kept, 'removed': targets, 'failed': [],
+                    'message': f"Would {action} {len(targets)} photo(s) and keep {len(kept)}"}
+
+        removed, failed = [], []
+        destination = archive_dir or self.archive_dir
+        with self.action_manager.journal_scope(kind=action, description=f"{action.capitalize()} burst extras"):
+            for path in targets:
+                if action == 'trash':
+                    result = self.action_manager.delete_file(path, reason='burst extra')
+                else:
+                    result = self.action_manager.archive_file(path, destination)
+                (removed if result['success'] else failed).append(
+                    path if result['success'] else {'path': path, 'message': result['message']})
+
+        verb = 'Trashed' if action == 'trash' else 'Archived'
+        return {
+            'success': not failed,
+            'message': f"{verb} {len(removed)} photo(s), kept {len(kept)}" + (f"; {len(failed)} failed" if failed else ''),
+            'kept': kept,
+            'removed': removed,
+            'failed': failed
+        }
@@ -0,0 +1,184 @@
+"""
+Classification Cache Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module remembers AI classifications in the database so an unchanged
+file is never sent to the model twice, across restarts.
+
+An entry is keyed by the file's path, size, modification time and a
+BLAKE2b hash of its content. A file whose path, size and time match is a
+hit without reading it; otherwise the content is hashed, and a file that
+was only moved, copied or touched is still a hit (the entry then follows
+it). Files over `classification_cache.hash_max_mb` are matched by path,
+size and time only.
+
+Only AI and agent results are stored: rule results cost nothing and
+should follow rule changes. Entries are the canonical (English) results,
+so changing the locale keeps them valid.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+import threading
+from typing import Dict, Any, Optional
+
+from .hashing import hash_file
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_HASH_MAX_MB = 64
+DEFAULT_MAX_ENTRIES = 50000
+CACHED_METHODS = ('ai', 'agent')
+
+
+class ClassificationCache:
+    """
+    Stores and looks up classifications of unchanged files.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        enabled (bool): Whether results are stored and reused
+        max_entries (int): Oldest entries beyond this are dropped
+    """
+
+    def __init__(self, config, db_manager):
+        """
+        Initialize cache.
+
+        Args:
+            config: Configuration object (reads `classification_cache_settings`)
+            db_manager: Database manager instance
+        """
+        self.config = config
+        self.db_manager = db_manager
+        settings = getattr(config, 'classification_cache_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', True))
+        self.hash_max_bytes = int(settings.get('hash_max_mb', DEFAULT_HASH_MAX_MB)) * 1024 * 1024
+        self.max_entries = max(1, int(settings.get('max_entries', DEFAULT_MAX_ENTRIES)))
+        self._session = {'hits': 0, 'misses': 0, 'stored': 0}
+        self._lock = threading.Lock()
+
+    def _key(self, file_path: str) -> Optional[Dict[str, Any]]:
+        try:
+            stat = os.stat(file_path)
+        except OSError:
+            return None
+        return {'path': os.path.normcase(os.path.abspath(file_path)), 'size': stat.st_size,
+                'mtime': stat.st_mtime}
+
+    def _hash(self, file_path: str, size: int) -> Optional[str]:
+        if size > self.hash_max_bytes:
+            return None
+        try:
+            return hash_file(file_path)
+        except OSError:
+            return None
+
+    def _count(self, name: str) -> None:
+        with self._lock:
+            self._session[name] += 1
+
+    def get(self, file_path: str) -> Optional[Dict[str, Any]]:
+        """
+        The stored classification of a file, if it has not changed since.
+
+        Args:
+            file_path (str): File
+
+        Returns:
+            Dict or None: Classification with 'cached': True
+        """
+        if not self.enabled:
+            return None
+        key = self._key(file_path)
+        if key is None:
+            return None
+        try:
+            row = self.db_manager.get_cached_classification(key['path'])
+            if row is not None and row['size'] == key['size'] and row['mtime'] == key['mtime']:
+                found = row
+            else:
+                digest = self._hash(file_path, key['size'])
+                found = (self.db_manager.find_cached_classification(digest, key['size'])
+                         if digest else None)
+                if found is not None:
+                    # Same content under a new path or time: the entry follows the file
+                    self.db_manager.put_cached_classification(key['path'], key['size'], key['mtime'],
+                                                              digest, found['result'])
+            if found is None:
+                self._count('misses')
+                return None
+            self.db_manager.record_classification_cache_hit(key['path'])
+            result = json.loads(found['result'])
+        except Exception as e:
+            logger.debug(f"Classification cache lookup failed for {file_path}: {e}")
+            return None
+        self._count('hits')
+        result['cached'] = True
+        return result
+
+    def put(self, file_path: str, result: Dict[str, Any]) -> bool:
+        """
+        Store a classification (only AI and agent results are kept).
+
+        Args:
+            file_path (str): File that was classified
+            result (Dict): Canonical classification result
+
+        Returns:
+            bool: Whether it was stored
+        """
+        if not self.enabled or result.get('method') not in CACHED_METHODS:
+            return False
+        key = self._key(file_path)
+        if key is None:
+            return False
+        stored = {name: value for name, value in result.items() if name != 'cached'}
+        try:
+            self.db_manager.put_cached_classification(key['path'], key['size'], key['mtime'],
+                                                      self._hash(file_path, key['size']),
+                                                      json.dumps(stored, default=str))
+            self.db_manager.prune_classification_cache(self.max_entries)
+        except Exception as e:
+            logger.debug(f"Could not cache the classification of {file_path}: {e}")
+            return False
+        self._count('stored')
+        return True
+
+    def clear(self) -> Dict[str, Any]:
+        """
+        Forget every stored classification.
+
+        Returns:
+            Dict: 'success', 'message' and 'removed'
+        """
+        removed = self.db_manager.clear_classification_cache()
+        logger.info(f"Classification cache cleared ({removed} entries)")
+        return {'success': True, 'message': f"Removed {removed} cached classification(s)", 'removed': removed}
+
+    def stats(self) -> Dict[str, Any]:
+        """
+        How much the cache holds and how often it helped.
+
+        Returns:
+            Dict: 'enabled', 'entries', 'total_hits', 'oldest', 'newest',
+                  and 'session' hits/misses/stored since this process started
+        """
+        stats = self.db_manager.classification_cache_stats()
+        with self._lock:
+            session = dict(self._session)
+        looked_up = session['hits'] + session['misses']
+        session['hit_rate'] = round(session['hits'] / looked_up, 3) if looked_up else None
+        return {'enabled': self.enabled, **stats, 'session': session}
@@ -23,7 +23,10 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
+import logging
 import re
+import threading
+from contextlib import contextmanager
 from pathlib import Path
 from typing import Dict, Any, Optional
 import mimetypes
@@ -66,6 +69,17 @@ except ImportError:
     Document = None  # type: ignore
     DOCX_SUPPORT = False
 
+from .i18n import get_translator
+from .screenshots import ScreenshotNamer, is_screenshot
+from .invoices import InvoiceExtractor, INVOICE_NAME, INVOICE_WORDS
+from .ocr import OcrEngine
+try:
+    from ..utils.structured_logging import timed
+except ImportError:
+    from utils.structured_logging import timed
+
+logger = logging.getLogger(__name__)
+
 
 class FileClassifier:
     """
@@ -91,6 +105,25 @@ class FileClassifier:
         self.destination_rules = config.destination_rules
         self.enable_ai = config.enable_ai and ollama_client is not None
         self.text_extract_limit = config.text_extract_limit
+        self.screenshot_namer = ScreenshotNamer(config)
+        self.invoice_extractor = InvoiceExtractor(config, ollama_client)
+        self.ocr = OcrEngine(config)
+        # AI token and cost ledger with the monthly budget (UsageLedger), set where a database is available
+        self.usage = None
+        # The user's own rules (UserRules), set where user settings apply
+        self.user_rules = None
+        # The user's settings (SettingsStore) for the AI tier, set where user settings apply
+        self.settings = None
+        # The user's category tree (TaxonomyManager) results are mapped onto, set where user settings apply
+        self.taxonomy = None
+        self._local_client = None
+        self._tier = threading.local()  # Tier of a run in this thread (using_tier)
+        # Learned corrections (CorrectionLearner), set where a database is available
+        self.corrections = None
+        # AI results of unchanged files (ClassificationCache), set where a database is available
+        self.cache = None
+        # Keeps the tags AI results suggest (TagStore), set where a database is available
+        self.tags = None
 
         # Initialize caching
         self._init_caching()
@@ -116,7 +149,61 @@ class FileClassifier:
                 - evidence (list, optional): Evidence strings (if agent used)
                 - action (str, optional): Suggested action (if agent used)
                 - block_reason (str, optional): Reason for blocking (if agent used)
+                - category_label (str): Category name in the configured locale
+                - locale (str): Locale used for category_label and suggested_path
+                - original_category (str, optional): The category before it was
+                  mapped onto the user's taxonomy (see core.taxonomy)
+        """
+        with timed('classify'):
+            result = self._classify(file_path, deep_analysis)
+        if self.taxonomy is not None:
+            result = self.taxonomy.map_classification(result, file_path)
+        if self.tags is not None and result.get('tags'):
+            try:
+                self.tags.record_classification(file_path, result)
+            except Exception as e:
+                logger.warning(f"Tags of {file_path} not kept: {e}")
+        return get_translator(getattr(self.config, 'locale', None)).localize_classification(result)
+
+    def _classify(self, file_path: str, deep_analysis: bool = False) -> Dict[str, Any]:
         """
+        Classify a file using canonical (English) category and folder names.
+
+        Results are cached in this form so that changing the locale never
+        invalidates or pollutes the classification cache.
+
+        Args:
+            file_path (str): Path to the file to classify
+            deep_analysis (bool): If True, use agent analyzer for deep multi-step analysis
+
+        Returns:
+            Dict: Classification result (see classify)
+        """
+        # Rules the user wrote come before everything else and are never cached
+        if self.user_rules is not None:
+            ruled = self.user_rules.classify(file_path)
+            if ruled:
+                return ruled
+
+        # Then destinations the user corrected similar files to
+        if self.corrections is not None and not deep_analysis:
+            learned = self.corrections.suggest(file_path)
+            if learned:
+                return learned
+
+        # An unchanged file is not sent to the model again
+        if self.cache is not None and not deep_analysis:
+            cached = self.cache.get(file_path)
+            if cached:
+                return cached
+
+        result = self._classify_uncached(file_path, deep_analysis)
+        if self.cache is not None:
+            self.cache.put(file_path, result)
+        return result
+
+    def _classify_uncached(self, file_path: str, deep_analysis: bool = False) -> Dict[str, Any]:
+        """Classify a file by rules, AI or agent (see _classify)."""
         path = Path(file_path)
 
         # Check cache first for quick results
@@ -128,7 +215,8 @@ class FileClassifier:
 
         try:
             # Basic file information
-            file_info = self._extract_file_info(path)
+            with timed('extract'):
+                file_info = self._extract_file_info(path)
         except FileNotFoundError:
             # Handle non-existent files gracefully
             return {
@@ -156,7 +244,8 @@ class FileClassifier:
         # Stage 2: Check if we should use agent deep analysis
         if deep_analysis or rule_result['confidence'] == 'low':
             # Try agent analysis if available
-            agent_result = self._classify_by_agent(file_path)
+            with timed('ai'):
+                agent_result = self._classify_by_agent(file_path)
             if agent_result and agent_result.get('success') and agent_result.get('confidence') in ['high', 'medium']:
                 return agent_result
 
@@ -166,7 +255,8 @@ class FileClassifier:
 
         # Stage 3: Try standard AI classification if enabled
         if self.enable_ai and self.ollama_client:
-            ai_result = self._classify_by_ai(file_info)
+            with timed('ai'):
+                ai_result = self._classify_by_ai(file_info)
             if ai_result.get('success'):
                 return {
                     'category': ai_result.get('category', 'Unsorted'),
@@ -174,7 +264,11 @@ class FileClassifier:
                     'rename': ai_result.get('rename'),
                     'reason': ai_result.get('reason', 'AI classification'),
                     'confidence': 'high',
-                    'method': 'ai'
+                    'method': 'ai',
+                    'model': ai_result.get('model'),
+                    'tags': ai_result.get('tags') if isinstance(ai_result.get('tags'), list) else [],
+                    'tokens_used': ai_result.get('tokens_used', 0),
+                    'cost_usd': ai_result.get('cost_usd', 0.0)
                 }
 
         # Fallback to rule-based result
@@ -210,6 +304,9 @@ class FileClassifier:
 
         # Extract text snippet if possible
         text_snippet = self._extract_text(path, extension)
+        ocr_text = self._read_by_ocr(path) if not (text_snippet and text_snippet.strip()) else None
+        if ocr_text:
+            text_snippet = ocr_text[:self.text_extract_limit]
 
         file_info = {
             'path': file_path_str,
@@ -219,6 +316,7 @@ class FileClassifier:
             'size': stat.st_size,
             'mime_type': mime_type,
             'text_snippet': text_snippet,
+            'ocr_text': ocr_text,
             'modified_time': stat.st_mtime
         }
 
@@ -290,6 +388,15 @@ class FileClassifier:
 
         return None
 
+    def _read_by_ocr(self, path: Path) -> Optional[str]:
+        """The text of a screenshot, photographed receipt or scan without a text layer, if OCR can read it."""
+        if not self.ocr.wants(str(path)):
+            return None
+        try:
+            return self.ocr.ocr_file(str(path)) or None
+        except OSError:
+            return None
+
     def _extract_pdf_text(self, path: Path) -> Optional[str]:
         """Extract text from PDF file (first page only)."""
         try:
@@ -332,6 +439,16 @@ class FileClassifier:
         filename = file_info['filename'].lower()
         stem = file_info['stem'].lower()
 
+        # Screenshots get their own folder and a name from the text they show
+        screenshot_result = self._classify_screenshot(file_info)
+        if screenshot_result:
+            return screenshot_result
+
+        # Invoices and receipts are filed by vendor, date and amount
+        invoice_result = self._classify_invoice(file_info)
+        if invoice_result:
+            return invoice_result
+
         # Check destination rules
         if extension in self.destination_rules:
             suggested_path = self.destination_rules[extension]
@@ -421,6 +538,45 @@ class FileClassifier:
 
         return None
 
+    def _classify_screenshot(self, file_info: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """
+        Classify a screenshot, suggesting a name read from it by OCR.
+
+        Args:
+            file_info (Dict): File information
+
+        Returns:
+            Dict or None: Classification result if the file is a screenshot
+        """
+        path = Path(file_info['path'])
+        if not is_screenshot(path):
+            return None
+        rename = self.screenshot_namer.suggest_name(path)
+        return {
+            'category': 'Pictures',
+            'suggested_path': 'Pictures/Screenshots/',
+            'rename': rename,
+            'reason': 'Detected screenshot' + ('; named after its text' if rename else ''),
+            'confidence': 'high',
+            'method': 'rule-based'
+        }
+
+    def _classify_invoice(self, file_info: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """
+        Classify an invoice or receipt by the fields read from it.
+
+        Args:
+            file_info (Dict): File information
+
+        Returns:
+            Dict or None: Classification result if the file is an invoice
+        """
+        snippet = file_info.get('text_snippet') or ''
+        if not (INVOICE_NAME.search(file_info['stem']) or INVOICE_WORDS.search(snippet)):
+            return None
+        # The snippet is cut short; totals are usually at the end (OCR text is kept whole, not read twice)
+        return self.invoice_extractor.classify(file_info['path'], file_info.get('ocr_text'))
+
     def _refine_path_by_patterns(self, filename: str, stem: str, base_path: str) -> str:
         """
         Refine destination path based on filename patterns.
@@ -478,15 +634,63 @@ class FileClassifier:
         Returns:
             Dict: AI classification result
         """
-        if not self.ollama_client:
+        client = self._ai_client()
+        if not client:
             return {'success': False, 'error': 'No AI client available'}
+        if self.usage is not None and not self.usage.allows_ai():
+            return {'success': False, 'error': 'Monthly AI budget reached'}
 
-        return self.ollama_client.classify_file(
+        result = client.classify_file(
             filename=file_info['filename'],
             extension=file_info['extension'],
             text_snippet=file_info.get('text_snippet'),
             file_size=file_info['size']
         )
+        if isinstance(result, dict) and not result.get('model') and isinstance(getattr(client, 'model', None), str):
+            result['model'] = client.model
+        if self.usage is not None and result.get('tokens_used'):
+            result['cost_usd'] = self.usage.record(result.get('model') or getattr(client, 'model', ''),
+                                                   result['tokens_used'], result.get('cost_usd'),
+                                                   result.get('provider', 'ollama'))
+        return result
+
+    @contextmanager
+    def using_tier(self, tier: Optional[str]):
+        """Classify with this AI tier instead of the settings' while the block runs, in this thread."""
+        previous = getattr(self._tier, 'name', None)
+        self._tier.name = tier
+        try:
+            yield self
+        finally:
+            self._tier.name = previous
+
+    def _ai_client(self):
+        """
+        The AI client for the user's tier.
+
+        "rules" uses no AI at all, and "local" keeps files on this machine:
+        when the client is a cloud provider, a local Ollama server answers
+        instead (the fallback result if none is running).
+        """
+        tier = getattr(self._tier, 'name', None)
+        if tier is None and self.settings is not None:
+            try:
+                tier = self.settings.load()['default_tier']
+            except Exception as e:
+                logger.warning(f"Settings unreadable, keeping AI local: {e}")
+                tier = 'local'
+        if tier == 'rules' or not self.ollama_client:
+            return None
+        if tier == 'local' and getattr(self.ollama_client, 'name', 'ollama') != 'ollama':
+            if self._local_client is None:
+                try:
+                    from ..ai.providers import OllamaProvider
+                except ImportError:
+                    from ai.providers import OllamaProvider
+                self._local_client = OllamaProvider(self.config.ollama_base_url, self.config.ollama_model,
+                                                    self.config.get('ollama_timeout', 30))
+            return self._local_client
+        return self.ollama_client
 
     def _classify_by_agent(self, file_path: str) -> Optional[Dict[str, Any]]:
         """
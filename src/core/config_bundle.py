@@ -0,0 +1,523 @@
+"""
+Configuration Bundle Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module exports the complete user configuration (settings from
+config.json, organisation rules from config.yaml and any other registered
+sections such as taxonomy or profiles) as one portable JSON bundle, and
+imports such a bundle back with a preview of every change before it is
+applied.
+
+Given the user's settings (see core.settings), a bundle also carries them:
+"user_settings" (default tier, folder templates, exclusions and
+notifications), "user_rules" (see core.rules), "profiles" (see
+core.profiles) and "taxonomy" (see core.taxonomy). Rules, profiles and
+categories are keyed by name; an imported one replaces the local one of
+that name and the others are added after the local ones.
+
+export_configuration(path) and import_configuration(path) do it all in one
+call. A path ending in .zip holds the bundle as bundle.json in a zip file,
+for sharing by mail or chat; any other path is the JSON itself.
+
+Secrets (API keys, tokens, passwords, license keys) are never written to a
+bundle, and importing a bundle never removes settings that exist locally.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import copy
+import json
+import logging
+import re
+import shutil
+import zipfile
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Callable, Tuple
+
+import yaml
+
+from .profiles import Profile
+from .rules import validate_rule
+from .settings import SettingsStore
+from .taxonomy import Category, Taxonomy, parents_first
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+BUNDLE_FORMAT = 'ai-file-organiser-config'
+BUNDLE_VERSION = 1
+BUNDLE_MEMBER = 'bundle.json'  # The bundle inside a .zip bundle
+USER_SETTING_KEYS = ('default_tier', 'folder_templates', 'exclusions', 'notifications')
+
+# Setting names that must never leave the machine
+SECRET_KEY_PATTERN = re.compile(
+    r'(^|_)(api_key|key|secret|token|password|passphrase|credentials?|license_key)$',
+    re.IGNORECASE
+)
+
+Exporter = Callable[[], Any]
+Importer = Callable[[Any], None]
+
+
+def redact_secrets(values: Any) -> Any:
+    """
+    Return a copy of a settings structure with secret entries removed.
+
+    Args:
+        values (Any): Settings value (dicts and lists are walked recursively)
+
+    Returns:
+        Any: Copy without secret keys
+    """
+    if isinstance(values, dict):
+        return {
+            key: redact_secrets(value)
+            for key, value in values.items()
+            if not SECRET_KEY_PATTERN.search(str(key))
+        }
+    if isinstance(values, list):
+        return [redact_secrets(item) for item in values]
+    return copy.deepcopy(values)
+
+
+def diff_values(old: Any, new: Any, prefix: str = '') -> List[Dict[str, Any]]:
+    """
+    Compute additions and changes needed to turn `old` into `new`.
+
+    Only dictionaries are descended into; lists and scalars are compared as
+    whole values. Keys missing from `new` are not reported because imports
+    never delete local settings.
+
+    Args:
+        old (Any): Current value
+        new (Any): Incoming value
+        prefix (str): Dotted key prefix for reporting
+
+    Returns:
+        List[Dict]: Changes with keys 'key', 'action' ('added'/'changed'), 'old', 'new'
+    """
+    changes: List[Dict[str, Any]] = []
+
+    if isinstance(old, dict) and isinstance(new, dict):
+        for key, value in new.items():
+            dotted = f"{prefix}.{key}" if prefix else str(key)
+            if key not in old:
+                changes.append({'key': dotted, 'action': 'added', 'old': None, 'new': value})
+            else:
+                changes.extend(diff_values(old[key], value, dotted))
+        return changes
+
+    if old != new:
+        changes.append({
+            'key': prefix,
+            'action': 'added' if old is None else 'changed',
+            'old': old,
+            'new': new
+        })
+    return changes
+
+
+def by_name(entries: List[Dict[str, Any]]) -> Dict[str, Dict[str, Any]]:
+    """Named entries (rules, profiles, categories) keyed by their name, in order."""
+    return {entry['name']: entry for entry in entries if isinstance(entry, dict) and entry.get('name')}
+
+
+def merge_named(local: List[Dict[str, Any]], incoming: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """Entries of `incoming` replace local ones of the same name (ignoring case); the others are added after."""
+    merged = copy.deepcopy(local)
+    index = {str(entry.get('name')).lower(): i for i, entry in enumerate(merged)}
+    for entry in incoming:
+        key = str(entry['name']).lower()
+        if key in index:
+            merged[index[key]] = entry
+        else:
+            index[key] = len(merged)
+            merged.append(entry)
+    return merged
+
+
+class ConfigBundleManager:
+    """
+    Exports and imports configuration bundles.
+
+    Sections are pluggable: each has an exporter returning a JSON-serialisable
+    value and an importer applying such a value. 'settings' and 'rules' are
+    always available, the user settings' sections when a settings store is
+    given; other subsystems add theirs with register_section().
+
+    Attributes:
+        config: Configuration object (config.json)
+        rules_path (Path): Path to the YAML rules file
+        settings (SettingsStore, optional): The user's settings
+    """
+
+    def __init__(self, config, rules_path: Optional[str] = None, settings: Optional[SettingsStore] = None):
+        """
+        Initialize bundle manager.
+
+        Args:
+            config: Configuration object
+            rules_path (str, optional): YAML rules file. Defaults to config.yaml next to config.json
+            settings (SettingsStore, optional): User settings to export and import along
+        """
+        self.config = config
+        if rules_path is not None:
+            self.rules_path = Path(rules_path)
+        else:
+            self.rules_path = Path(config.config_path).parent / "config.yaml"
+        self.settings = settings
+
+        self._sections: Dict[str, Tuple[Exporter, Importer]] = {}
+        self.register_section('settings', self._export_settings, self._import_settings)
+        self.register_section('rules', self._export_rules, self._import_rules)
+        if settings is not None:
+            self.register_section('user_settings', self._export_user_settings, self._import_user_settings)
+            self.register_section('user_rules', lambda: by_name(settings.get('rules') or []),
+                                  self._import_user_rules)
+            self.register_section('profiles', lambda: by_name(settings.get('profiles') or []),
+                                  self._import_profiles)
+            self.register_section('taxonomy', lambda: by_name(settings.get('taxonomy') or []),
+                                  self._import_taxonomy)
+
+    def register_section(self, name: str, exporter: Exporter, importer: Importer) -> None:
+        """
+        Register an additional bundle section.
+
+        Args:
+            name (str): Section name in the bundle
+            exporter (Callable): Returns the section value for export
+            importer (Callable): Applies an imported section value
+        """
+        self._sections[name] = (exporter, importer)
+
+    @property
+    def sections(self) -> List[str]:
+        """Names of all registered sections."""
+        return list(self._sections.keys())
+
+    # ==================== Export ====================
+
+    def build_bundle(self) -> Dict[str, Any]:
+        """
+        Build a bundle containing every registered section.
+
+        Returns:
+            Dict: Bundle with 'format', 'version', 'created_at' and 'sections'
+        """
+        sections: Dict[str, Any] = {}
+        for name, (exporter, _) in self._sections.items():
+            try:
+                value = exporter()
+            except Exception as e:
+                logger.warning(f"Skipping section '{name}' in config export: {e}")
+                continue
+            if value is not None:
+                sections[name] = redact_secrets(value)
+
+        return {
+            'format': BUNDLE_FORMAT,
+            'version': BUNDLE_VERSION,
+            'created_at': datetime.now().isoformat(),
+            'sections': sections
+        }
+
+    def export_bundle(self, output_path: str) -> Dict[str, Any]:
+        """
+        Write a configuration bundle to a file.
+
+        Args:
+            output_path (str): Destination file path
+
+        Returns:
+            Dict: Result with 'success', 'message', 'path' and 'sections'
+        """
+        try:
+            bundle = self.build_bundle()
+            path = Path(output_path).expanduser()
+            path.parent.mkdir(parents=True, exist_ok=True)
+            if path.suffix.lower() == '.zip':
+                with zipfile.ZipFile(path, 'w', zipfile.ZIP_DEFLATED) as archive:
+                    archive.writestr(BUNDLE_MEMBER, json.dumps(bundle, indent=2, ensure_ascii=False))
+            else:
+                with open(path, 'w', encoding='utf-8') as f:
+                    json.dump(bundle, f, indent=2, ensure_ascii=False)
+
+            return {
+                'success': True,
+                'message': f"Exported {len(bundle['sections'])} section(s) to {path}",
+                'path': str(path),
+                'sections': list(bundle['sections'].keys())
+            }
+        except Exception as e:
+            return {'success': False, 'message': f"Export failed: {str(e)}"}
+
+    def export_configuration(self, path: str) -> Dict[str, Any]:
+        """
+        Export everything to a bundle file (.zip for a zipped bundle).
+
+        Args:
+            path (str): Bundle file to write
+
+        Returns:
+            Dict: Result with 'success', 'message', 'path' and 'sections'
+        """
+        return self.export_bundle(path)
+
+    # ==================== Import ====================
+
+    def load_bundle(self, bundle_path: str) -> Dict[str, Any]:
+        """
+        Read and validate a bundle file.
+
+        Args:
+            bundle_path (str): Path to the bundle file
+
+        Returns:
+            Dict: Validated bundle
+
+        Raises:
+            ConfigurationError: If the file is missing, malformed or not a bundle
+        """
+        path = Path(bundle_path).expanduser()
+        if not path.exists():
+            raise ConfigurationError(f"Bundle file not found: {path}")
+
+        try:
+            if zipfile.is_zipfile(path):
+                with zipfile.ZipFile(path) as archive:
+                    bundle = json.loads(archive.read(BUNDLE_MEMBER).decode('utf-8'))
+            else:
+                with open(path, 'r', encoding='utf-8') as f:
+                    bundle = json.load(f)
+        except KeyError:
+            raise ConfigurationError(f"Zip file has no {BUNDLE_MEMBER}: {path}")
+        except (json.JSONDecodeError, UnicodeDecodeError) as e:
+            raise ConfigurationError(f"Bundle file is not valid JSON: {e}")
+
+        self.validate_bundle(bundle)
+        return bundle
+
+    def validate_bundle(self, bundle: Any) -> None:
+        """
+        Check that a value is a bundle this version can apply.
+
+        Args:
+            bundle (Any): Parsed bundle
+
+        Raises:
+            ConfigurationError: If the bundle is invalid or from a newer version
+        """
+        if not isinstance(bundle, dict) or bundle.get('format') != BUNDLE_FORMAT:
+            raise ConfigurationError("Not an AI File Organiser configuration bundle")
+
+        version = bundle.get('version')
+        if not isinstance(version, int) or version > BUNDLE_VERSION:
+            raise ConfigurationError(
+                f"Unsupported bundle version: {version} (supported: {BUNDLE_VERSION})"
+            )
+
+        if not isinstance(bundle.get('sections'), dict):
+            raise ConfigurationError("Bundle has no 'sections' object")
+
+    def preview_import(self, bundle: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Describe what importing a bundle would change, without applying it.
+
+        Args:
+            bundle (Dict): Validated bundle
+
+        Returns:
+            Dict: Result with 'success', 'changes' (list of section/key/action/old/new),
+                  'sections', 'unknown_sections' and 'message'
+        """
+        self.validate_bundle(bundle)
+
+        changes: List[Dict[str, Any]] = []
+        unknown: List[str] = []
+
+        for name, incoming in bundle['sections'].items():
+            if name not in self._sections:
+                unknown.append(name)
+                continue
+            exporter, _ = self._sections[name]
+            try:
+                current = redact_secrets(exporter())
+            except Exception:
+                current = None
+            for change in diff_values(current, incoming):
+                change['section'] = name
+                changes.append(change)
+
+        message = f"{len(changes)} change(s) in {len(bundle['sections']) - len(unknown)} section(s)"
+        if unknown:
+            message += f"; ignoring unknown section(s): {', '.join(unknown)}"
+
+        return {
+            'success': True,
+            'message': message,
+            'changes': changes,
+            'sections': [name for name in bundle['sections'] if name not in unknown],
+            'unknown_sections': unknown
+        }
+
+    def apply_import(self, bundle: Dict[str, Any],
+                     sections: Optional[List[str]] = None) -> Dict[str, Any]:
+        """
+        Apply a bundle. config.json and config.yaml are backed up first.
+
+        Args:
+            bundle (Dict): Validated bundle
+            sections (List[str], optional): Only apply these sections
+
+        Returns:
+            Dict: Result with 'success', 'message', 'applied' and 'backups'
+        """
+        preview = self.preview_import(bundle)
+        selected = [
+            name for name in preview['sections']
+            if sections is None or name in sections
+        ]
+
+        backups = self._backup_files()
+        applied: List[str] = []
+
+        for name in selected:
+            _, importer = self._sections[name]
+            try:
+                importer(bundle['sections'][name])
+                applied.append(name)
+            except Exception as e:
+                logger.error(f"Failed to import section '{name}': {e}")
+                return {
+                    'success': False,
+                    'message': f"Import failed in section '{name}': {str(e)}",
+                    'applied': applied,
+                    'backups': backups
+                }
+
+        return {
+            'success': True,
+            'message': f"Imported {len(applied)} section(s)",
+            'applied': applied,
+            'backups': backups,
+            'changes': [c for c in preview['changes'] if c['section'] in applied]
+        }
+
+    def import_configuration(self, path: str, sections: Optional[List[str]] = None) -> Dict[str, Any]:
+        """
+        Import a bundle file (JSON or .zip) without a preview step.
+
+        Args:
+            path (str): Bundle file
+            sections (List[str], optional): Only apply these sections
+
+        Returns:
+            Dict: Result with 'success', 'message', 'applied', 'backups' and 'changes'
+        """
+        try:
+            bundle = self.load_bundle(path)
+        except ConfigurationError as e:
+            return {'success': False, 'message': str(e), 'applied': [], 'backups': []}
+        return self.apply_import(bundle, sections)
+
+    def _backup_files(self) -> List[str]:
+        """Copy config files aside before an import overwrites them."""
+        stamp = datetime.now().strftime('%Y%m%d_%H%M%S')
+        backups = []
+        paths = [Path(self.config.config_path), self.rules_path]
+        if self.settings is not None:
+            paths.append(self.settings.path)
+        for path in paths:
+            if path.exists():
+                backup = path.with_name(f"{path.name}.{stamp}.bak")
+                shutil.copy2(path, backup)
+                backups.append(str(backup))
+        return backups
+
+    # ==================== Built-in Sections ====================
+
+    def _export_settings(self) -> Dict[str, Any]:
+        """Export config.json settings."""
+        return self.config.as_dict()
+
+    def _import_settings(self, values: Dict[str, Any]) -> None:
+        """Merge settings into config.json, keeping local secrets."""
+        if not isinstance(values, dict):
+            raise ConfigurationError("'settings' section must be an object", config_key='settings')
+        self.config.merge(redact_secrets(values))
+        self.config.save()
+
+    def _export_rules(self) -> Optional[Dict[str, Any]]:
+        """Export config.yaml rules, filters and actions."""
+        if not self.rules_path.exists():
+            return None
+        with open(self.rules_path, 'r', encoding='utf-8') as f:
+            return yaml.safe_load(f) or {}
+
+    def _import_rules(self, values: Dict[str, Any]) -> None:
+        """Merge imported filters, actions and rules into config.yaml."""
+        if not isinstance(values, dict):
+            raise ConfigurationError("'rules' section must be an object", config_key='rules')
+        merged = self._export_rules() or {}
+        _merge_dicts(merged, values)
+        with open(self.rules_path, 'w', encoding='utf-8') as f:
+            yaml.dump(merged, f, default_flow_style=False, sort_keys=False, allow_unicode=True)
+
+
+    def _export_user_settings(self) -> Dict[str, Any]:
+        """Export the user settings that are not keyed lists (API keys stay behind)."""
+        return {key: self.settings.get(key) for key in USER_SETTING_KEYS}
+
+    def _import_user_settings(self, values: Dict[str, Any]) -> None:
+        """Merge user settings; exclusions are added to the local ones."""
+        if not isinstance(values, dict):
+            raise ConfigurationError("'user_settings' section must be an object", config_key='user_settings')
+        changes = {key: values[key] for key in USER_SETTING_KEYS if key in values}
+        if isinstance(changes.get('exclusions'), list):
+            local = self.settings.get('exclusions') or []
+            changes['exclusions'] = local + [pattern for pattern in changes['exclusions'] if pattern not in local]
+        self.settings.update_settings(changes)
+
+    def _incoming(self, section: str, values: Any, check: Callable[[Any], Dict[str, Any]]) -> List[Dict[str, Any]]:
+        """A named section's entries, checked (the key is the name of an entry without one)."""
+        if not isinstance(values, dict):
+            raise ConfigurationError(f"'{section}' section must map names to entries", config_key=section)
+        try:
+            return [check({'name': name, **entry} if isinstance(entry, dict) else entry)
+                    for name, entry in values.items()]
+        except ValueError as e:
+            raise ConfigurationError(f"'{section}' section: {e}", config_key=section)
+
+    def _import_user_rules(self, values: Dict[str, Any]) -> None:
+        incoming = self._incoming('user_rules', values, validate_rule)
+        self.settings.update_settings({'rules': merge_named(self.settings.get('rules') or [], incoming)})
+
+    def _import_profiles(self, values: Dict[str, Any]) -> None:
+        incoming = self._incoming('profiles', values, lambda entry: Profile.from_dict(entry).to_dict())
+        self.settings.update_settings({'profiles': merge_named(self.settings.get('profiles') or [], incoming)})
+
+    def _import_taxonomy(self, values: Dict[str, Any]) -> None:
+        incoming = self._incoming('taxonomy', values, lambda entry: Category.from_dict(entry).to_dict())
+        merged = merge_named(self.settings.get('taxonomy') or [], incoming)
+        try:
+            taxonomy = Taxonomy(parents_first([Category.from_dict(entry) for entry in merged]))
+        except ValueError as e:
+            raise ConfigurationError(f"'taxonomy' section: {e}", config_key='taxonomy')
+        self.settings.update_settings({'taxonomy': [category.to_dict() for category in taxonomy.categories]})
+
+
+def _merge_dicts(target: Dict[str, Any], source: Dict[str, Any]) -> None:
+    """Deep-merge `source` into `target`; non-dict values replace."""
+    for key, value in source.items():
+        if isinstance(value, dict) and isinstance(target.get(key), dict):
+            _merge_dicts(target[key], value)
+        else:
+            target[key] = copy.deepcopy(value)
@@ -0,0 +1,280 @@
+"""
+Conflicts Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps the moves that could not be made because their
+destination was taken, until the user decides what happens to them.
+Nothing is overwritten and nothing fails quietly: applying a plan with
+"conflict_resolution": "ask" (see core.plans) leaves such files where
+they are and returns them as Conflicts, which stay listed (in the
+database) across restarts.
+
+resolve_conflict(id, strategy) settles one:
+
+- keep_both: move it anyway, under the next free name ("report (2).pdf")
+- overwrite_if_newer: replace the destination if the source was modified
+  later, else leave the source where it is; the replaced file can be
+  brought back with undo
+- skip: leave the source where it is
+- merge_folders: move a folder's contents into the existing folder; files
+  that exist on both sides become new conflicts
+
+Moves made by a resolution are journaled, so undo reverts them. A
+resolution whose destination was freed meanwhile just moves the source.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import shutil
+from dataclasses import asdict, dataclass
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .fsops import FileMover, free_name
+from .journal import OperationJournal
+
+logger = logging.getLogger(__name__)
+
+
+STRATEGIES = ('keep_both', 'overwrite_if_newer', 'skip', 'merge_folders')
+
+
+@dataclass
+class Conflict:
+    """A move set aside because 'destination' is taken; sizes and times are read from disk when listed."""
+    id: Optional[int]
+    source: str
+    destination: str
+    reason: str
+    is_dir: bool
+    root: Optional[str] = None
+    created_at: Optional[str] = None
+    source_size: Optional[int] = None
+    source_modified: Optional[float] = None
+    destination_size: Optional[int] = None
+    destination_modified: Optional[float] = None
+
+    @classmethod
+    def from_paths(cls, conflict_id: Optional[int], source: str, destination: str, reason: str,
+                   root: Optional[str] = None, created_at: Optional[str] = None) -> 'Conflict':
+        conflict = cls(id=conflict_id, source=source, destination=destination, reason=reason,
+                       is_dir=os.path.isdir(source), root=root, created_at=created_at)
+        for side in ('source', 'destination'):
+            try:
+                stat = os.stat(getattr(conflict, side))
+            except OSError:
+                continue
+            setattr(conflict, f"{side}_size", None if conflict.is_dir else stat.st_size)
+            setattr(conflict, f"{side}_modified", stat.st_mtime)
+        return conflict
+
+    @property
+    def source_is_newer(self) -> bool:
+        return (self.source_modified or 0) > (self.destination_modified or 0)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {**asdict(self), 'source_is_newer': self.source_is_newer}
+
+
+def destination_taken(source: str, destination: Optional[str]) -> bool:
+    """Whether moving source to destination would meet something else already there."""
+    if not destination or not os.path.lexists(destination):
+        return False
+    try:
+        return not os.path.samefile(source, destination)
+    except OSError:
+        return True
+
+
+class ConflictManager:
+    """
+    Records conflicts and applies the user's resolutions.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager (conflicts and the operation journal)
+        journal (OperationJournal): Records resolution moves for undo
+        notifier (Notifier, optional): Told when new conflicts need the user
+    """
+
+    def __init__(self, config, db_manager, journal: Optional[OperationJournal] = None, notifier=None):
+        """
+        Initialize conflict manager.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+            journal (OperationJournal, optional): Journal to record moves in
+            notifier (Notifier, optional): Notifies about new conflicts
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.journal = journal or OperationJournal(db_manager)
+        self.mover = FileMover(config, db_manager, self.journal)
+        self.notifier = notifier
+
+    def record(self, source: str, destination: str, reason: str = 'destination_exists',
+               root: Optional[str] = None) -> Conflict:
+        """
+        Set a move aside as a conflict.
+
+        Args:
+            source (str): Path that was to move
+            destination (str): Its taken destination
+            reason (str): 'destination_exists' or 'shared_destination'
+            root (str, optional): Folder the plan was for
+
+        Returns:
+            Conflict: The stored conflict
+        """
+        conflict_id = self.db_manager.add_conflict(source, destination, reason, os.path.isdir(source), root)
+        logger.info(f"Conflict {conflict_id}: {source} -> {destination} is taken")
+        return Conflict.from_paths(conflict_id, source, destination, reason, root)
+
+    def announce(self, conflicts: List[Conflict], folder: Optional[str] = None) -> None:
+        """Let the user know that conflicts wait for them."""
+        if conflicts and self.notifier is not None:
+            self.notifier.conflict(len(conflicts), folder)
+
+    def get(self, conflict_id: int) -> Optional[Conflict]:
+        """A conflict by ID (resolved or not), or None."""
+        row = self.db_manager.get_conflict(int(conflict_id))
+        return self._from_row(row) if row else None
+
+    def list_conflicts(self, limit: int = 100) -> List[Conflict]:
+        """Conflicts still waiting for a resolution, oldest first."""
+        return [self._from_row(row) for row in self.db_manager.list_conflicts(limit)]
+
+    @staticmethod
+    def _from_row(row: Dict[str, Any]) -> Conflict:
+        return Conflict.from_paths(row['id'], row['source'], row['destination'], row['reason'],
+                                   row['root'], row['created_at'])
+
+    @staticmethod
+    def check_strategy(strategy: str) -> str:
+        """
+        The strategy's canonical name ('keep-both' is accepted for 'keep_both').
+
+        Raises:
+            ValueError: If the strategy is not known
+        """
+        name = str(strategy or '').strip().lower().replace('-', '_')
+        if name not in STRATEGIES:
+            raise ValueError(f"Unknown conflict strategy {strategy!r} (use {', '.join(STRATEGIES)})")
+        return name
+
+    def resolve_conflict(self, conflict_id: int, strategy: str) -> Dict[str, Any]:
+        """
+        Settle a conflict.
+
+        Args:
+            conflict_id (int): Conflict ID
+            strategy (str): One of STRATEGIES
+
+        Returns:
+            Dict: 'success', 'message', 'conflict', 'strategy', 'new_path'
+                  (where the source is now, if it moved), 'operation_id' and
+                  'conflicts' (new ones, from merging folders)
+
+        Raises:
+            ValueError: If the strategy is not known or does not fit the
+                        conflict, or the conflict is unknown or already resolved
+        """
+        strategy = self.check_strategy(strategy)
+        row = self.db_manager.get_conflict(int(conflict_id))
+        if row is None:
+            raise ValueError(f"No such conflict: {conflict_id}")
+        if row['resolved_at']:
+            raise ValueError(f"Conflict {conflict_id} was already resolved ({row['resolution']})")
+        conflict = self._from_row(row)
+        source, destination = Path(conflict.source), Path(conflict.destination)
+        result: Dict[str, Any] = {'success': True, 'conflict': conflict.to_dict(), 'strategy': strategy,
+                                  'new_path': None, 'operation_id': None, 'conflicts': []}
+
+        if not os.path.lexists(source):
+            self.db_manager.mark_conflict_resolved(conflict.id, 'gone')
+            return {**result, 'success': False, 'message': f"{source.name} no longer exists"}
+        if strategy == 'merge_folders' and not (source.is_dir() and destination.is_dir()):
+            raise ValueError("Only two folders can be merged")
+        if strategy == 'overwrite_if_newer' and conflict.is_dir:
+            raise ValueError("A folder cannot overwrite another; merge them instead")
+
+        if strategy == 'skip':
+            self.db_manager.log_action(source.name, str(source), str(destination), 'skipped',
+                                       user_approved=True, count_stats=False)
+            result['message'] = f"Left {source.name} where it is"
+        elif not destination_taken(str(source), str(destination)):
+            result.update(self._move(source, destination, 'skip'))  # Freed meanwhile
+        elif strategy == 'merge_folders':
+            result.update(self._merge(source, destination, conflict.root))
+        elif strategy == 'overwrite_if_newer':
+            if conflict.source_is_newer:
+                result.update(self._move(source, destination, 'replace'))
+            else:
+                result['message'] = f"Kept {destination.name}: it is not older than {source.name}"
+        elif conflict.is_dir:
+            result.update(self._move_folder(source, free_name(destination)))
+        else:
+            result.update(self._move(source, destination, 'keep_both'))
+
+        if result['success']:
+            self.db_manager.mark_conflict_resolved(conflict.id, strategy, result['new_path'])
+            logger.info(f"Conflict {conflict.id} resolved ({strategy}): {result['message']}")
+        return result
+
+    def _move(self, source: Path, destination: Path, on_conflict: str) -> Dict[str, Any]:
+        if source.is_dir():
+            return self._move_folder(source, destination)
+        applied = self.mover.apply_plan([{'source': str(source), 'destination': str(destination)}], on_conflict)
+        moved = applied['results'][0] if applied['results'] else {}
+        return {'success': moved.get('status') == 'moved', 'message': moved.get('message') or applied['message'],
+                'new_path': moved.get('destination') if moved.get('status') == 'moved' else None,
+                'operation_id': applied['operation_id']}
+
+    def _move_folder(self, source: Path, destination: Path) -> Dict[str, Any]:
+        try:
+            destination.parent.mkdir(parents=True, exist_ok=True)
+            shutil.move(str(source), str(destination))
+        except OSError as e:
+            return {'success': False, 'message': f"Could not move {source.name}: {e}"}
+        operation_id = self.journal.begin('move', f"Move {source.name}")
+        self.journal.record_step(operation_id, 'move', str(source), str(destination), {'method': 'folder'})
+        self.journal.complete(operation_id)
+        return {'message': f"Moved to {destination}", 'new_path': str(destination), 'operation_id': operation_id}
+
+    def _merge(self, source: Path, destination: Path, root: Optional[str]) -> Dict[str, Any]:
+        """Move a folder's files into the matching places of another; clashes become new conflicts."""
+        moves, clashes = [], []
+        for folder, _, names in os.walk(source):
+            for name in names:
+                path = Path(folder) / name
+                target = destination / path.relative_to(source)
+                if destination_taken(str(path), str(target)):
+                    clashes.append((path, target))
+                else:
+                    moves.append({'source': str(path), 'destination': str(target)})
+        applied = self.mover.apply_plan(moves, 'skip')
+        for folder, _, _ in sorted(os.walk(source), key=lambda entry: -len(entry[0])):
+            try:
+                os.rmdir(folder)  # Only the ones left empty
+            except OSError:
+                pass
+        conflicts = [self.record(str(path), str(target), 'destination_exists', root) for path, target in clashes]
+        self.announce(conflicts, root)
+
+        message = f"Merged {applied['moved']} file(s) into {destination}"
+        if applied['failed']:
+            message += f", {applied['failed']} failed"
+        if conflicts:
+            message += f"; {len(conflicts)} file(s) exist in both and wait for a resolution"
+        return {'success': applied['failed'] == 0, 'message': message,
+                'new_path': str(destination), 'operation_id': applied['operation_id'],
+                'conflicts': [conflict.to_dict() for conflict in conflicts]}
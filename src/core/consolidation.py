@@ -0,0 +1,296 @@
+"""
+Consolidation Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module plans and applies consolidation runs: gathering every file
+that matches a topic rule from many scattered locations into one canonical
+destination tree, e.g. every tax document from five folders into
+`Finance/Taxes/<year>`.
+
+A rule names its source folders (or a workspace), what to match (keywords
+in the name or path, extensions, glob patterns and/or classifier
+categories) and a destination template relative to base_destination with
+{year}, {month}, {category}, {ext} and {source} placeholders. The year
+comes from a year in the file name when there is one, otherwise from the
+modification time. A consolidation run is journaled as one operation, so
+it can be undone in full.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import fnmatch
+import logging
+import os
+import re
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .split_archives import collapse_split_archives
+
+logger = logging.getLogger(__name__)
+
+
+YEAR_IN_NAME = re.compile(r'(?<!\d)((?:19|20)\d{2})(?!\d)')
+TEMPLATE_FIELDS = ('year', 'month', 'category', 'ext', 'source')
+
+
+@dataclass
+class ConsolidationRule:
+    """What to gather, from where, and into which tree."""
+    name: str
+    destination: str
+    sources: List[str] = field(default_factory=list)
+    workspace: Optional[str] = None
+    keywords: List[str] = field(default_factory=list)
+    extensions: List[str] = field(default_factory=list)
+    patterns: List[str] = field(default_factory=list)
+    categories: List[str] = field(default_factory=list)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> 'ConsolidationRule':
+        """Build a rule from a config or API dictionary."""
+        return cls(
+            name=data.get('name', 'consolidation'),
+            destination=data.get('destination', ''),
+            sources=list(data.get('sources') or []),
+            workspace=data.get('workspace'),
+            keywords=[k.lower() for k in data.get('keywords') or []],
+            extensions=[e.lower() if e.startswith('.') else f'.{e.lower()}' for e in data.get('extensions') or []],
+            patterns=list(data.get('patterns') or []),
+            categories=list(data.get('categories') or [])
+        )
+
+    def validate(self) -> Optional[str]:
+        """Return an error message, or None if the rule is usable."""
+        if not self.destination:
+            return 'A destination template is required'
+        if Path(self.destination).is_absolute() or '..' in Path(self.destination).parts:
+            return 'Destination must be relative to base_destination'
+        try:
+            self.destination.format(**{name: 'x' for name in TEMPLATE_FIELDS})
+        except (KeyError, IndexError, ValueError) as e:
+            return f'Invalid destination template: {e}'
+        if not (self.keywords or self.extensions or self.patterns or self.categories):
+            return 'A rule needs at least one of keywords, extensions, patterns or categories'
+        if not (self.sources or self.workspace):
+            return 'A rule needs sources or a workspace'
+        return None
+
+
+class ConsolidationPlanner:
+    """
+    Plans and applies consolidation runs.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        classifier: Optional classifier (required for category rules)
+        action_manager: Action manager used to apply plans
+    """
+
+    def __init__(self, config, db_manager, classifier=None, action_manager=None):
+        """
+        Initialize planner.
+
+        Args:
+            config: Configuration object (reads `base_destination`, `workspaces`, `consolidation_rules`)
+            db_manager: Database manager instance
+            classifier: Optional FileClassifier for category matching
+            action_manager: Optional ActionManager for applying plans
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.classifier = classifier
+        self.action_manager = action_manager
+
+    def saved_rules(self) -> List[ConsolidationRule]:
+        """
+        Rules stored in the `consolidation_rules` config list.
+
+        Returns:
+            List[ConsolidationRule]: Saved rules
+        """
+        rules = getattr(self.config, 'consolidation_rules', None)
+        if not isinstance(rules, list):
+            return []
+        return [ConsolidationRule.from_dict(r) for r in rules if isinstance(r, dict)]
+
+    def get_rule(self, name: str) -> Optional[ConsolidationRule]:
+        """Find a saved rule by name."""
+        return next((r for r in self.saved_rules() if r.name == name), None)
+
+    # ==================== Planning ====================
+
+    def _source_dirs(self, rule: ConsolidationRule) -> List[Path]:
+        sources = [Path(s).expanduser() for s in rule.sources]
+        if rule.workspace:
+            workspaces = getattr(self.config, 'workspaces', None)
+            definition = workspaces.get(rule.workspace) if isinstance(workspaces, dict) else None
+            if definition is None:
+                raise KeyError(f"Unknown workspace: {rule.workspace}")
+            sources.extend(Path(r).expanduser() for r in definition.get('roots', []))
+        return [s.resolve() for s in sources if s.is_dir()]
+
+    def _base_dir(self) -> Path:
+        try:
+            return Path(self.config.base_destination).expanduser().resolve()
+        except (AttributeError, TypeError, OSError):
+            return Path.home()
+
+    def _matches(self, path: Path, source: Path, rule: ConsolidationRule) -> Optional[Dict[str, Any]]:
+        """Return the classification-derived fields if the file matches, else None."""
+        name = path.name.lower()
+        if rule.extensions and path.suffix.lower() not in rule.extensions:
+            return None
+        if rule.patterns and not any(fnmatch.fnmatch(name, p.lower()) for p in rule.patterns):
+            return None
+        if rule.keywords:
+            haystack = path.relative_to(source).as_posix().lower()
+            if not any(k in haystack for k in rule.keywords):
+                return None
+
+        category = None
+        if rule.categories:
+            if self.classifier is None:
+                raise RuntimeError("Category rules need a classifier")
+            category = self.classifier.classify(str(path)).get('category')
+            if category not in rule.categories:
+                return None
+        return {'category': category}
+
+    @staticmethod
+    def _file_date(path: Path) -> datetime:
+        try:
+            return datetime.fromtimestamp(path.stat().st_mtime)
+        except OSError:
+            return datetime.now()
+
+    def _destination_for(self, path: Path, source: Path, rule: ConsolidationRule,
+                         category: Optional[str]) -> str:
+        modified = self._file_date(path)
+        year_match = YEAR_IN_NAME.search(path.stem)
+        values = {
+            'year': year_match.group(1) if year_match else str(modified.year),
+            'month': f"{modified.month:02d}",
+            'category': category or 'Unsorted',
+            'ext': path.suffix.lower().lstrip('.') or 'none',
+            'source': source.name or 'root'
+        }
+        return rule.destination.format(**values).strip('/') + '/'
+
+    def plan(self, rule: ConsolidationRule) -> Dict[str, Any]:
+        """
+        Find every matching file and where it would go.
+
+        Files already inside the rule's destination tree are left out.
+
+        Args:
+            rule (ConsolidationRule): Rule to plan
+
+        Returns:
+            Dict: Plan with 'success', 'message', 'rule', 'items'
+                  ({'file', 'source', 'suggested_path', 'destination', 'occupied'})
+                  and 'per_destination' counts
+        """
+        error = rule.validate()
+        if error:
+            return {'success': False, 'message': error, 'items': []}
+
+        base_dir = self._base_dir()
+        fixed_prefix = rule.destination.split('{', 1)[0].rsplit('/', 1)[0]
+        tree_root = (base_dir / fixed_prefix).resolve() if fixed_prefix else None
+
+        items: List[Dict[str, Any]] = []
+        seen = set()
+        for source in self._source_dirs(rule):
+            paths = []
+            for dirpath, dirnames, filenames in os.walk(source):
+                dirnames[:] = [d for d in dirnames if not d.startswith('.')]
+                paths.extend(os.path.join(dirpath, f) for f in filenames)
+
+            for file_path in collapse_split_archives(paths):
+                path = Path(file_path)
+                if path.is_symlink() or str(path) in seen:
+                    continue
+                if tree_root and (path == tree_root or tree_root in path.parents):
+                    continue
+                match = self._matches(path, source, rule)
+                if match is None:
+                    continue
+                seen.add(str(path))
+                suggested = self._destination_for(path, source, rule, match['category'])
+                destination = base_dir / suggested / path.name
+                items.append({
+                    'file': str(path),
+                    'source': str(source),
+                    'category': match['category'],
+                    'suggested_path': suggested,
+                    'destination': str(destination),
+                    'occupied': destination.exists()
+                })
+
+        per_destination: Dict[str, int] = {}
+        for item in items:
+            per_destination[item['suggested_path']] = per_destination.get(item['suggested_path'], 0) + 1
+
+        return {
+            'success': True,
+            'message': f"Rule '{rule.name}': {len(items)} file(s) to gather into {len(per_destination)} folder(s)",
+            'rule': rule.name,
+            'items': items,
+            'per_destination': per_destination
+        }
+
+    # ==================== Applying ====================
+
+    def apply(self, plan: Dict[str, Any], user_approved: bool = True) -> Dict[str, Any]:
+        """
+        Move every planned file as one journaled (and undoable) operation.
+
+        Args:
+            plan (Dict): Plan from plan()
+            user_approved (bool): Whether the user approved the plan
+
+        Returns:
+            Dict: Result with 'success', 'message', 'operation_id', 'moved' and 'failed'
+        """
+        if self.action_manager is None:
+            raise RuntimeError("Applying a consolidation needs an ActionManager")
+        if not plan.get('success'):
+            return {'success': False, 'message': plan.get('message', 'Invalid plan')}
+
+        moved: List[Dict[str, Any]] = []
+        failed: List[Dict[str, Any]] = []
+        with self.action_manager.journal_scope(kind='consolidate',
+                                               description=f"Consolidate '{plan['rule']}'") as scope:
+            for item in plan['items']:
+                result = self.action_manager.execute(item['file'], {
+                    'category': item.get('category') or 'Consolidated',
+                    'suggested_path': item['suggested_path'],
+                    'confidence': 'high',
+                    'method': 'consolidation',
+                    'reason': f"Consolidation rule '{plan['rule']}'"
+                }, user_approved=user_approved)
+                if result.get('success'):
+                    moved.append({'file': item['file'], 'new_path': result.get('new_path')})
+                else:
+                    failed.append({'file': item['file'], 'message': result.get('message', '')})
+
+        message = f"Consolidated {len(moved)} file(s) for rule '{plan['rule']}'"
+        if failed:
+            message += f"; {len(failed)} failed"
+        return {
+            'success': not failed,
+            'message': message,
+            'operation_id': scope.get('operation_id'),
+            'moved': moved,
+            'failed': failed
+        }
@@ -0,0 +1,424 @@
+"""
+Control Socket Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module lets one long-running engine (the daemon) be driven by the
+GUI, the CLI and shell extensions over a local control socket: a Unix
+domain socket on macOS/Linux, a named pipe on Windows. Only the current
+user can connect (the socket file is created with 0600 permissions).
+
+Protocol: each message is a 4-byte big-endian length followed by a UTF-8
+JSON object. A request is {"id": 1, "method": "status", "params": {}};
+the reply carries the same id and either "result" or
+"error": {"code": ..., "message": ...}. Codes follow JSON-RPC (-32700
+malformed request, -32601 unknown method, -32602 bad params, -32000 the
+method failed; clients use -32001 when no engine answers). A failed
+method's error also has "data": the AppError (see utils.error_handler),
+e.g. {"code": "not_found", "message": ..., "details": {"path": ...}}. Built-in
+methods: ping, methods, shutdown, log_level (read or change log levels
+while the engine runs) and logs.tail (read the live log stream).
+
+The socket also enforces a single instance: a second launch (e.g. from
+the Explorer/Finder context menu with file arguments) hands its paths to
+the running instance's "open" method and exits, instead of starting a
+second engine that would fight over the watchers and the database.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import getpass
+import inspect
+import json
+import logging
+import os
+import stat
+import sys
+import tempfile
+import threading
+from multiprocessing.connection import Client, Listener
+from pathlib import Path
+from typing import Dict, Any, Callable, List, Optional
+
+from src.utils.error_handler import AppError, ErrorCode
+from src.utils.structured_logging import span, set_log_level, log_levels, log_stream
+
+try:
+    import fcntl
+except ImportError:  # Windows: named pipes are exclusive by themselves
+    fcntl = None
+
+logger = logging.getLogger(__name__)
+
+
+PROTOCOL_VERSION = 1
+
+PARSE_ERROR = -32700
+METHOD_NOT_FOUND = -32601
+INVALID_PARAMS = -32602
+METHOD_FAILED = -32000
+UNAVAILABLE = -32001  # Client side: no engine answered
+
+MAX_TAIL_WAIT = 20.0  # Seconds logs.tail may hold a request open
+
+
+class ControlError(Exception):
+    """Error reply from the daemon, or no daemon to talk to."""
+
+    def __init__(self, message: str, code: int = METHOD_FAILED, error: Optional[AppError] = None):
+        super().__init__(message)
+        self.code = code
+        # What the method failed with, when it failed on the engine
+        self.error = error or AppError(ErrorCode.BACKEND_UNAVAILABLE if code == UNAVAILABLE else ErrorCode.INTERNAL,
+                                       message)
+
+
+def default_address(config=None) -> str:
+    """
+    Control socket address for the current user.
+
+    Args:
+        config: Configuration object (control.address overrides the default)
+
+    Returns:
+        str: Named pipe on Windows, Unix socket path elsewhere
+    """
+    settings = getattr(config, 'control_settings', None)
+    if isinstance(settings, dict) and settings.get('address'):
+        return os.path.expanduser(str(settings['address']))
+    user = getpass.getuser()
+    if sys.platform == 'win32':
+        return rf'\\.\pipe\aifo-control-{user}'
+    runtime_dir = os.environ.get('XDG_RUNTIME_DIR') or tempfile.gettempdir()
+    return os.path.join(runtime_dir, f'aifo-control-{user}.sock')
+
+
+def _family(address: str) -> str:
+    return 'AF_PIPE' if address.startswith('\\\\.\\pipe\\') else 'AF_UNIX'
+
+
+class ControlServer:
+    """
+    Serves registered methods on the control socket, one thread per client.
+
+    Attributes:
+        address (str): Socket path or pipe name
+        methods (Dict[str, Callable]): Method name -> handler(**params)
+    """
+
+    def __init__(self, address: str, methods: Optional[Dict[str, Callable[..., Any]]] = None):
+        """
+        Initialize control server.
+
+        Args:
+            address (str): Socket path or pipe name (see default_address())
+            methods (Dict, optional): Handlers to register
+        """
+        self.address = address
+        self.methods: Dict[str, Callable[..., Any]] = {}
+        self._listener: Optional[Listener] = None
+        self._lock_file = None
+        self._thread: Optional[threading.Thread] = None
+        self._stopped = threading.Event()
+
+        self.register('ping', lambda: {'pong': True, 'protocol': PROTOCOL_VERSION, 'pid': os.getpid()})
+        self.register('methods', lambda: sorted(self.methods))
+        self.register('shutdown', self._request_shutdown)
+        self.register('log_level', self._log_level)
+        self.register('logs.tail', self._tail_logs)
+        for name, handler in (methods or {}).items():
+            self.register(name, handler)
+
+    def register(self, name: str, handler: Callable[..., Any]) -> None:
+        """Register a method; its keyword arguments are the request params."""
+        self.methods[name] = handler
+
+    # ==================== Lifecycle ====================
+
+    def start(self, background: bool = True) -> None:
+        """
+        Open the socket and serve requests.
+
+        Args:
+            background (bool): Serve on a thread (True) or block until stopped
+
+        Raises:
+            RuntimeError: If another engine already answers on the address
+        """
+        family = _family(self.address)
+        if family == 'AF_UNIX':
+            self._acquire_lock()
+        if is_running(self.address):
+            self._release_lock()
+            raise RuntimeError(f"An engine is already running on {self.address}")
+
+        if family == 'AF_UNIX' and os.path.exists(self.address):
+            if not stat.S_ISSOCK(os.stat(self.address).st_mode):
+                raise RuntimeError(f"{self.address} exists and is not a socket")
+            os.unlink(self.address)  # Stale socket left by an engine that crashed
+        self._listener = Listener(self.address, family=family)
+        if family == 'AF_UNIX':
+            os.chmod(self.address, 0o600)
+        self._stopped.clear()
+        logger.info(f"Control socket listening on {self.address}")
+
+        if background:
+            self._thread = threading.Thread(target=self._serve, name='aifo-control', daemon=True)
+            self._thread.start()
+        else:
+            self._serve()
+
+    def stop(self) -> None:
+        """Stop accepting clients and remove the socket."""
+        if self._stopped.is_set():
+            return
+        self._stopped.set()
+        listener, self._listener = self._listener, None
+        if listener is not None:
+            # Wake the blocking accept() so the serving thread can exit
+            try:
+                Client(self.address, family=_family(self.address)).close()
+            except OSError:
+                pass
+            listener.close()
+        self._release_lock()
+        if self._thread is not None and self._thread is not threading.current_thread():
+            self._thread.join(timeout=5)
+
+    def _acquire_lock(self) -> None:
+        """Hold <socket>.lock so two engines starting at once cannot both bind."""
+        if fcntl is None:
+            return
+        self._lock_file = open(f"{self.address}.lock", 'a')
+        try:
+            fcntl.flock(self._lock_file, fcntl.LOCK_EX | fcntl.LOCK_NB)
+        except OSError:
+            self._lock_file.close()
+            self._lock_file = None
+            raise RuntimeError(f"An engine is already starting on {self.address}")
+
+    def _release_lock(self) -> None:
+        if self._lock_file is not None:
+            self._lock_file.close()  # Closing releases the lock
+            self._lock_file = None
+
+    def wait(self) -> None:
+        """Block until the server is stopped (e.g. by the shutdown method)."""
+        self._stopped.wait()
+
+    @staticmethod
+    def _log_level(level: str = None, logger: str = None) -> Dict[str, Any]:
+        # Change a level for debugging without restarting the engine
+        changed = set_log_level(level, logger) if level else None
+        return {'changed': changed, 'levels': log_levels()}
+
+    @staticmethod
+    def _tail_logs(after: int = 0, level: str = 'INFO', module: str = None,
+                   limit: int = 200, wait: float = 0) -> Dict[str, Any]:
+        # Long poll: the client's reply timeout must stay above the wait
+        return log_stream().read(int(after), level, module, int(limit), min(float(wait), MAX_TAIL_WAIT))
+
+    def _request_shutdown(self) -> Dict[str, Any]:
+        # Reply first; stop once the response has been sent
+        threading.Timer(0.1, self.stop).start()
+        return {'stopping': True}
+
+    # ==================== Serving ====================
+
+    def _serve(self) -> None:
+        while not self._stopped.is_set():
+            try:
+                conn = self._listener.accept()
+            except (OSError, AttributeError):
+                break
+            if self._stopped.is_set():
+                conn.close()
+                break
+            threading.Thread(target=self._handle, args=(conn,), daemon=True).start()
+
+    def _handle(self, conn) -> None:
+        with conn:
+            while True:
+                try:
+                    raw = conn.recv_bytes()
+                except (EOFError, OSError):
+                    return
+                response = self.dispatch(raw)
+                try:
+                    conn.send_bytes(json.dumps(response, default=str).encode('utf-8'))
+                except OSError:
+                    return
+
+    def dispatch(self, raw: bytes) -> Dict[str, Any]:
+        """
+        Answer one request.
+
+        Args:
+            raw (bytes): JSON request
+
+        Returns:
+            Dict: Response with 'id' and 'result' or 'error'
+        """
+        try:
+            request = json.loads(raw.decode('utf-8'))
+            if not isinstance(request, dict) or not isinstance(request.get('method'), str):
+                raise ValueError('a request is an object with a "method"')
+        except (ValueError, UnicodeDecodeError) as e:
+            return {'id': None, 'error': {'code': PARSE_ERROR, 'message': f'Malformed request: {e}'}}
+
+        request_id = request.get('id')
+        params = request.get('params') or {}
+        handler = self.methods.get(request['method'])
+        if handler is None:
+            return {'id': request_id, 'error': {'code': METHOD_NOT_FOUND,
+                                                'message': f"Unknown method: {request['method']}"}}
+        try:
+            if not isinstance(params, dict):
+                raise TypeError('params must be an object')
+            inspect.signature(handler).bind(**params)
+        except TypeError as e:
+            return {'id': request_id, 'error': {'code': INVALID_PARAMS, 'message': str(e)}}
+
+        try:
+            with span(f"control.{request['method']}", logger, request_id=request_id):
+                result = handler(**params)
+            return {'id': request_id, 'result': result}
+        except Exception as e:
+            error = AppError.from_exception(e)
+            return {'id': request_id, 'error': {'code': METHOD_FAILED, 'message': error.message,
+                                                'data': error.to_dict()}}
+
+
+class ControlClient:
+    """
+    Talks to a running engine over its control socket.
+
+    Attributes:
+        address (str): Socket path or pipe name
+    """
+
+    def __init__(self, address: str, timeout: float = 30.0):
+        """
+        Initialize control client.
+
+        Args:
+            address (str): Socket path or pipe name
+            timeout (float): Seconds to wait for each reply
+        """
+        self.address = address
+        self.timeout = timeout
+        self._conn = None
+        self._next_id = 0
+
+    def connect(self) -> 'ControlClient':
+        """
+        Open the connection.
+
+        Raises:
+            ControlError: If no engine is running on the address
+        """
+        if self._conn is None:
+            try:
+                self._conn = Client(self.address, family=_family(self.address))
+            except (OSError, EOFError) as e:
+                raise ControlError(f"No engine running on {self.address} ({e})", UNAVAILABLE)
+        return self
+
+    def close(self) -> None:
+        """Close the connection."""
+        if self._conn is not None:
+            self._conn.close()
+            self._conn = None
+
+    def __enter__(self):
+        return self.connect()
+
+    def __exit__(self, *exc):
+        self.close()
+
+    def call(self, method: str, **params) -> Any:
+        """
+        Call a method on the engine.
+
+        Args:
+            method (str): Method name
+            **params: Method parameters
+
+        Returns:
+            The method's result
+
+        Raises:
+            ControlError: If the engine is unreachable or the method failed
+        """
+        self.connect()
+        self._next_id += 1
+        try:
+            self._conn.send_bytes(json.dumps({'id': self._next_id, 'method': method, 'params': params}).encode('utf-8'))
+            if not self._conn.poll(self.timeout):
+                raise ControlError(f"No reply to {method} within {self.timeout:g}s")
+            response = json.loads(self._conn.recv_bytes().decode('utf-8'))
+        except (OSError, EOFError) as e:
+            self.close()
+            raise ControlError(f"Lost connection to the engine: {e}", UNAVAILABLE)
+        if 'error' in response:
+            error = response['error']
+            data = error.get('data') if isinstance(error.get('data'), dict) else None
+            app_error = None
+            if data and data.get('code') in ErrorCode._value2member_map_:
+                app_error = AppError(ErrorCode(data['code']), data.get('message', ''), data.get('details') or {})
+            raise ControlError(error.get('message', 'Unknown error'), error.get('code', METHOD_FAILED), app_error)
+        return response.get('result')
+
+
+def is_running(address: str) -> bool:
+    """Whether an engine answers on the address."""
+    try:
+        with ControlClient(address, timeout=2.0) as client:
+            return bool(client.call('ping').get('pong'))
+    except ControlError:
+        return False
+
+
+def files_to_open(paths: List[str]) -> List[str]:
+    """
+    Files named by an "open" request: files as given, folders by their files.
+
+    Args:
+        paths (List[str]): Files or folders
+
+    Returns:
+        List[str]: Absolute file paths (hidden files skipped)
+    """
+    files = []
+    for raw in paths:
+        path = Path(raw).expanduser().resolve()
+        if path.is_file():
+            files.append(str(path))
+        elif path.is_dir():
+            files.extend(str(p) for p in sorted(path.iterdir()) if p.is_file() and not p.name.startswith('.'))
+    return files
+
+
+def forward_to_running(address: str, paths: List[str]) -> Optional[Dict[str, Any]]:
+    """
+    Hand paths to the instance already running, which also raises its window.
+
+    Args:
+        address (str): Control socket address
+        paths (List[str]): Files or folders to open (may be empty)
+
+    Returns:
+        Dict or None: The running instance's reply, or None if none is running
+    """
+    try:
+        with ControlClient(address, timeout=10.0) as client:
+            return client.call('open', paths=[str(Path(p).expanduser().resolve()) for p in paths])
+    except ControlError as e:
+        if e.code == UNAVAILABLE:
+            return None
+        raise
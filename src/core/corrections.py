@@ -0,0 +1,257 @@
+"""
+Learned Corrections Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module remembers when the user sends a file somewhere other than
+where it was suggested, and suggests the user's choice next time - before
+the rules or the AI are asked - so a correction made twice sticks.
+
+Each correction is stored with the file's features: extension, source
+folder, name words and content hash. A later file gets a learned
+destination when:
+
+- the same content was corrected before (the newest choice wins), or
+- at least `corrections.min_repeats` (2) corrections of files with the
+  same extension that also share its source folder or most of its name
+  words agree on a destination, and more of them do than on any other
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+import re
+from collections import Counter
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Set
+
+from .hashing import hash_file
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_MIN_REPEATS = 2
+DEFAULT_HASH_MAX_MB = 64
+TOKEN_OVERLAP = 0.5
+_WORD = re.compile(r'[a-z]+|\d+')
+
+
+def name_tokens(name: str) -> List[str]:
+    """
+    The words of a file name, lowercase, without numbers or the extension.
+
+    Args:
+        name (str): File name
+
+    Returns:
+        List[str]: Sorted unique words of two letters or more
+    """
+    stem = Path(name).stem.lower()
+    return sorted({word for word in _WORD.findall(stem) if not word.isdigit() and len(word) > 1})
+
+
+def _overlap(a: Set[str], b: Set[str]) -> float:
+    return len(a & b) / len(a | b) if a and b else 0.0
+
+
+class CorrectionLearner:
+    """
+    Records destination corrections and suggests them for similar files.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        enabled (bool): Whether corrections are recorded and applied
+        min_repeats (int): Agreeing similar corrections needed to suggest one
+    """
+
+    def __init__(self, config, db_manager):
+        """
+        Initialize learner.
+
+        Args:
+            config: Configuration object (reads `corrections_settings`)
+            db_manager: Database manager instance
+        """
+        self.config = config
+        self.db_manager = db_manager
+        settings = getattr(config, 'corrections_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', True))
+        self.min_repeats = max(1, int(settings.get('min_repeats', DEFAULT_MIN_REPEATS)))
+        self.hash_max_bytes = int(settings.get('hash_max_mb', DEFAULT_HASH_MAX_MB)) * 1024 * 1024
+
+    def features(self, file_path: str) -> Dict[str, Any]:
+        """
+        The features a correction is keyed by.
+
+        Args:
+            file_path (str): File
+
+        Returns:
+            Dict: 'extension', 'source_folder', 'tokens' and 'content_hash'
+                  (None for files over corrections.hash_max_mb or unreadable)
+        """
+        path = Path(file_path)
+        content_hash = None
+        try:
+            if path.stat().st_size <= self.hash_max_bytes:
+                content_hash = hash_file(path)
+        except OSError:
+            pass
+        return {
+            'extension': path.suffix.lower().lstrip('.'),
+            'source_folder': os.path.normcase(os.path.abspath(path.parent)),
+            'tokens': name_tokens(path.name),
+            'content_hash': content_hash
+        }
+
+    def destination_for(self, destination: str) -> Dict[str, Optional[str]]:
+        """Split a chosen folder into the suggested_path/destination_root pair classifications use."""
+        folder = Path(destination).expanduser()
+        if not folder.is_absolute():
+            return {'suggested_path': destination.strip('/\\').replace('\\', '/') + '/', 'destination_root': None}
+        try:
+            base = Path(self.config.base_destination).expanduser().resolve()
+            relative = folder.resolve().relative_to(base)
+            if str(relative) != '.':
+                return {'suggested_path': relative.as_posix() + '/', 'destination_root': None}
+        except (AttributeError, OSError, TypeError, ValueError):
+            pass
+        return {'suggested_path': folder.name + '/', 'destination_root': str(folder.parent)}
+
+    # ==================== Recording ====================
+
+    def record(self, file_path: str, destination: str, category: Optional[str] = None,
+               suggested: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Remember that the user wanted a file somewhere else.
+
+        Args:
+            file_path (str): The file, at its original location
+            destination (str): Folder the user chose (relative to the base
+                               destination, or absolute)
+            category (str, optional): Category the user chose
+            suggested (Dict, optional): The classification that was overridden
+
+        Returns:
+            Dict: 'success', 'message' and the correction 'id'
+        """
+        if not self.enabled:
+            return {'success': False, 'message': 'Learning from corrections is turned off (corrections.enabled)'}
+        if not destination or not str(destination).strip('/\\'):
+            return {'success': False, 'message': 'No destination given'}
+        suggested = suggested or {}
+        features = self.features(file_path)
+        chosen = self.destination_for(str(destination))
+        try:
+            correction_id = self.db_manager.add_correction(
+                features['extension'], features['source_folder'], json.dumps(features['tokens']),
+                features['content_hash'], suggested.get('category'), suggested.get('suggested_path'),
+                category, json.dumps(chosen)
+            )
+        except Exception as e:  # a failed write never blocks the move itself
+            logger.warning(f"Could not record correction for {file_path}: {e}")
+            return {'success': False, 'message': f"Could not record the correction: {e}"}
+        logger.info(f"Learned: .{features['extension'] or '(none)'} files like {Path(file_path).name} "
+                    f"go to {chosen['suggested_path']}")
+        where = os.path.join(chosen['destination_root'] or '', chosen['suggested_path'])
+        return {'success': True, 'message': f"Learned: files like {Path(file_path).name} go to {where}",
+                'id': correction_id}
+
+    # ==================== Suggesting ====================
+
+    def suggest(self, file_path: str) -> Optional[Dict[str, Any]]:
+        """
+        A learned classification for a file, if past corrections agree on one.
+
+        Args:
+            file_path (str): File to classify
+
+        Returns:
+            Dict or None: Classification with method 'learned', or None
+        """
+        if not self.enabled:
+            return None
+        features = self.features(file_path)
+        try:
+            rows = list(self.db_manager.find_corrections(features['extension'], features['content_hash']))
+        except Exception as e:
+            logger.debug(f"Cannot read corrections: {e}")
+            return None
+        if not rows:
+            return None
+
+        for row in rows:
+            if features['content_hash'] and row['content_hash'] == features['content_hash']:
+                return self._classification(row, 1, 'You moved this same file there before')
+
+        tokens = set(features['tokens'])
+        votes: Counter = Counter()
+        newest: Dict[str, Dict[str, Any]] = {}
+        for row in rows:
+            if row['extension'] != features['extension']:
+                continue
+            similar = row['source_folder'] == features['source_folder'] or \
+                _overlap(tokens, set(json.loads(row['name_tokens'] or '[]'))) >= TOKEN_OVERLAP
+            if similar:
+                votes[row['destination']] += 1
+                newest.setdefault(row['destination'], dict(row))
+                if not newest[row['destination']]['category']:
+                    newest[row['destination']]['category'] = row['category']
+        if not votes:
+            return None
+        ranked = votes.most_common(2)
+        destination, count = ranked[0]
+        if count < self.min_repeats or (len(ranked) > 1 and ranked[1][1] == count):
+            return None
+        return self._classification(newest[destination], count,
+                                    f"Learned from {count} earlier correction(s) of similar files")
+
+    @staticmethod
+    def _classification(row: Dict[str, Any], count: int, reason: str) -> Dict[str, Any]:
+        chosen = json.loads(row['destination'])
+        result = {
+            'category': row['category'] or row['suggested_category'] or 'Unsorted',
+            'suggested_path': chosen['suggested_path'],
+            'rename': None,
+            'reason': reason,
+            'confidence': 'high',
+            'method': 'learned',
+            'corrections': count
+        }
+        if chosen.get('destination_root'):
+            result['destination_root'] = chosen['destination_root']
+        return result
+
+    # ==================== Managing ====================
+
+    def list(self, limit: int = 100) -> List[Dict[str, Any]]:
+        """Recent corrections, with their destinations decoded."""
+        rows = self.db_manager.list_corrections(limit)
+        for row in rows:
+            row['destination'] = json.loads(row['destination'])
+            row['name_tokens'] = json.loads(row['name_tokens'] or '[]')
+        return rows
+
+    def forget(self, correction_id: Optional[int] = None) -> Dict[str, Any]:
+        """
+        Forget one correction, or all of them.
+
+        Args:
+            correction_id (int, optional): Correction to forget (default: all)
+
+        Returns:
+            Dict: 'success', 'message' and 'deleted' count
+        """
+        deleted = self.db_manager.delete_corrections(correction_id)
+        if correction_id is not None and not deleted:
+            return {'success': False, 'message': f"No correction {correction_id}", 'deleted': 0}
+        return {'success': True, 'message': f"Forgot {deleted} correction(s)", 'deleted': deleted}
@@ -0,0 +1,318 @@
+"""
+Crash Reports Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module provides opt-in crash reporting. When `crash_reports.enabled`
+is set, uncaught exceptions (in any thread) are written to a local report,
+and faulthandler keeps a dump file open so a native crash (segfault,
+abort) still leaves the stacks of all threads behind; the next start turns
+that dump into a report.
+
+Reports are sanitized before they are stored: file paths are replaced by
+placeholders unless the user consented with `crash_reports.include_paths`,
+and no file contents are ever captured. Nothing leaves the machine until
+the user sends a report (send_report), which posts it to
+`crash_reports.endpoint`.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import atexit
+import faulthandler
+import json
+import logging
+import os
+import platform
+import re
+import sys
+import threading
+import traceback
+import urllib.request
+import uuid
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+REPORT_VERSION = 1
+DEFAULT_KEEP = 20
+
+_APP_ROOT = str(Path(__file__).resolve().parent.parent.parent)
+# Absolute paths: /unix/style or C:\windows\style, up to whitespace or a quote
+_ABSOLUTE_PATH = re.compile(r'''(?:[A-Za-z]:[\\/]|(?<![\w.>])/)[^\s'"<>|:*?]+''')
+
+
+def _pid_alive(pid: str) -> bool:
+    """Whether a process is still running (assumed so where it cannot be checked)."""
+    if sys.platform == 'win32' or not pid.isdigit():
+        return True
+    try:
+        os.kill(int(pid), 0)
+    except ProcessLookupError:
+        return False
+    except OSError:
+        pass
+    return True
+
+
+class CrashReporter:
+    """
+    Captures, stores and sends crash reports.
+
+    Attributes:
+        config: Configuration object
+        enabled (bool): Whether crashes are captured at all
+        include_paths (bool): Whether the user consented to keeping file paths
+        endpoint (str): URL reports are posted to when sent
+        store_dir (Path): Folder holding the reports
+    """
+
+    def __init__(self, config, store_dir: Optional[str] = None):
+        """
+        Initialize crash reporter.
+
+        Args:
+            config: Configuration object (reads `crash_report_settings`)
+            store_dir (str, optional): Report folder (default: data/crashes)
+        """
+        self.config = config
+        settings = getattr(config, 'crash_report_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', False))
+        self.include_paths = bool(settings.get('include_paths', False))
+        self.endpoint = settings.get('endpoint') or ''
+        self.keep = int(settings.get('keep', DEFAULT_KEEP))
+        self.store_dir = Path(store_dir or settings.get('store_dir') or Path(_APP_ROOT) / 'data' / 'crashes').expanduser()
+        self._dump_file = None
+
+    # ==================== Capturing ====================
+
+    def install(self) -> bool:
+        """
+        Install the crash handlers (only if crash reporting is enabled).
+
+        Returns:
+            bool: Whether the handlers were installed
+        """
+        if not self.enabled:
+            return False
+        self.store_dir.mkdir(parents=True, exist_ok=True)
+        self.collect_native_dumps()
+
+        previous_hook = sys.excepthook
+        previous_thread_hook = threading.excepthook
+
+        def excepthook(exc_type, exc, tb):
+            if not issubclass(exc_type, KeyboardInterrupt):
+                self.capture(exc_type, exc, tb)
+            previous_hook(exc_type, exc, tb)
+
+        def thread_excepthook(args):
+            if args.exc_type is not SystemExit:
+                self.capture(args.exc_type, args.exc_value, args.exc_traceback,
+                             thread=args.thread.name if args.thread else None)
+            previous_thread_hook(args)
+
+        sys.excepthook = excepthook
+        threading.excepthook = thread_excepthook
+
+        # Native crashes: faulthandler writes all thread stacks to this file
+        dump_path = self.store_dir / f"native-{os.getpid()}.dump"
+        self._dump_file = open(dump_path, 'w', encoding='utf-8')
+        faulthandler.enable(file=self._dump_file, all_threads=True)
+        atexit.register(self._remove_dump_file, dump_path)
+        return True
+
+    def _remove_dump_file(self, dump_path: Path) -> None:
+        """A clean exit leaves no native dump behind."""
+        faulthandler.disable()
+        if self._dump_file is not None:
+            self._dump_file.close()
+            self._dump_file = None
+        try:
+            if dump_path.stat().st_size == 0:
+                dump_path.unlink()
+        except OSError:
+            pass
+
+    def capture(self, exc_type, exc, tb, thread: Optional[str] = None) -> Optional[str]:
+        """
+        Store a sanitized report for an exception.
+
+        Args:
+            exc_type: Exception class
+            exc: Exception instance
+            tb: Traceback
+            thread (str, optional): Name of the thread it was raised in
+
+        Returns:
+            str or None: Report ID, or None if the report could not be written
+        """
+        frames = [{
+            'file': self._sanitize(frame.filename),
+            'line': frame.lineno,
+            'function': frame.name
+        } for frame in traceback.extract_tb(tb)]
+        report = self._new_report('exception')
+        report.update({
+            'thread': thread or threading.current_thread().name,
+            'exception': {
+                'type': f"{exc_type.__module__}.{exc_type.__qualname__}",
+                'message': self._sanitize(str(exc))
+            },
+            'frames': frames
+        })
+        return self._store(report)
+
+    def collect_native_dumps(self) -> List[str]:
+        """
+        Turn dumps left by native crashes of earlier runs into reports.
+
+        Returns:
+            List[str]: IDs of the new reports
+        """
+        report_ids = []
+        for dump in sorted(self.store_dir.glob('native-*.dump')):
+            if dump.name == f"native-{os.getpid()}.dump":
+                continue
+            try:
+                text = dump.read_text(encoding='utf-8', errors='replace')
+                if text.strip():
+                    # faulthandler only writes when the process dies
+                    report = self._new_report('native_crash')
+                    report['stacks'] = self._sanitize(text)
+                    report_id = self._store(report)
+                    if report_id:
+                        report_ids.append(report_id)
+                    dump.unlink()
+                elif not _pid_alive(dump.stem.split('-', 1)[1]):
+                    dump.unlink()  # Left by a process that was killed
+            except OSError as e:
+                logger.warning(f"Could not read native crash dump {dump.name}: {e}")
+        return report_ids
+
+    def _new_report(self, kind: str) -> Dict[str, Any]:
+        from src import __version__
+
+        return {
+            'version': REPORT_VERSION,
+            'id': f"{datetime.now():%Y%m%d-%H%M%S}-{uuid.uuid4().hex[:6]}",
+            'kind': kind,
+            'created_at': datetime.now().isoformat(timespec='seconds'),
+            'app_version': __version__,
+            'python': platform.python_version(),
+            'platform': f"{platform.system()} {platform.release()} ({platform.machine()})",
+            'paths_included': self.include_paths,
+            'sent_at': None
+        }
+
+    def _sanitize(self, text: str) -> str:
+        """Replace file paths by placeholders unless the user consented to keep them."""
+        if self.include_paths:
+            return text
+        # App and Python sources are not user data: keep them, relative
+        text = text.replace(_APP_ROOT + os.sep, '')
+        for prefix in {sys.base_prefix, sys.prefix}:
+            text = text.replace(prefix + os.sep, '<python>/')
+        return _ABSOLUTE_PATH.sub('<path>', text)
+
+    def _store(self, report: Dict[str, Any]) -> Optional[str]:
+        try:
+            self.store_dir.mkdir(parents=True, exist_ok=True)
+            path = self.store_dir / f"{report['id']}.json"
+            path.write_text(json.dumps(report, indent=2), encoding='utf-8')
+            self._prune()
+        except OSError as e:
+            logger.error(f"Could not write crash report: {e}")
+            return None
+        logger.error(f"Crash report {report['id']} saved; send it with: aifo crash-reports --send {report['id']}")
+        return report['id']
+
+    def _prune(self) -> None:
+        reports = sorted(self.store_dir.glob('*.json'))
+        for old in reports[:max(0, len(reports) - self.keep)]:
+            old.unlink()
+
+    # ==================== Reviewing and sending ====================
+
+    def list_reports(self) -> List[Dict[str, Any]]:
+        """
+        List stored reports, newest first.
+
+        Returns:
+            List[Dict]: 'id', 'kind', 'created_at', 'summary' and 'sent_at'
+        """
+        items = []
+        for path in sorted(self.store_dir.glob('*.json'), reverse=True):
+            report = self._read(path)
+            if report is None:
+                continue
+            if report.get('exception'):
+                summary = f"{report['exception']['type']}: {report['exception']['message']}"
+            else:
+                summary = 'Native crash'
+            items.append({'id': report['id'], 'kind': report['kind'], 'created_at': report['created_at'],
+                          'summary': summary[:200], 'sent_at': report.get('sent_at')})
+        return items
+
+    def get_report(self, report_id: str) -> Optional[Dict[str, Any]]:
+        """Full report, exactly as it would be sent."""
+        return self._read(self._path(report_id))
+
+    def delete_report(self, report_id: str) -> bool:
+        """Delete a stored report."""
+        path = self._path(report_id)
+        if not path.exists():
+            return False
+        path.unlink()
+        return True
+
+    def send_report(self, report_id: str) -> Dict[str, Any]:
+        """
+        Post a report to the configured endpoint.
+
+        Args:
+            report_id (str): Report to send
+
+        Returns:
+            Dict: Result with 'success' and 'message'
+        """
+        report = self.get_report(report_id)
+        if report is None:
+            return {'success': False, 'message': f"Crash report not found: {report_id}"}
+        if not self.endpoint:
+            return {'success': False, 'message': "No crash report endpoint configured (crash_reports.endpoint)"}
+
+        request = urllib.request.Request(
+            self.endpoint, data=json.dumps(report).encode('utf-8'),
+            headers={'Content-Type': 'application/json'}, method='POST'
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=15) as response:
+                if response.status >= 300:
+                    return {'success': False, 'message': f"Server answered {response.status}"}
+        except OSError as e:
+            return {'success': False, 'message': f"Could not send crash report: {e}"}
+
+        report['sent_at'] = datetime.now().isoformat(timespec='seconds')
+        self._path(report_id).write_text(json.dumps(report, indent=2), encoding='utf-8')
+        return {'success': True, 'message': f"Crash report {report_id} sent. Thank you!"}
+
+    def _path(self, report_id: str) -> Path:
+        # IDs come from users and URLs: never let one point outside the folder
+        return self.store_dir / f"{Path(str(report_id)).name}.json"
+
+    @staticmethod
+    def _read(path: Path) -> Optional[Dict[str, Any]]:
+        try:
+            return json.loads(path.read_text(encoding='utf-8'))
+        except (OSError, ValueError):
+            return None
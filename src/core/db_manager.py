@@ -12,6 +12,8 @@ Tables:
     - duplicates: Tracks duplicate file hashes
     - license: Stores license validation status
     - stats: Aggregated statistics (daily, weekly, monthly)
+    - operations / operation_steps: Journal of multi-step, undoable operations
+    - file_versions: Replaced files kept as restorable versions
 
 NOTICE: This software is proprietary and confidential.
 See LICENSE.txt for full terms and conditions.
@@ -20,6 +22,9 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
+import os
+import json
+import logging
 import sqlite3
 import hashlib
 from pathlib import Path
@@ -30,6 +35,16 @@ import threading
 import queue
 import time
 
+logger = logging.getLogger(__name__)
+
+
+SEARCH_UNAVAILABLE = "Full-text search is unavailable: this SQLite build lacks FTS5 (SQLite 3.27 or later is needed)"
+
+
+def _escape_like(value: str) -> str:
+    """Escape LIKE wildcards (with ESCAPE '\\') so a path matches literally."""
+    return value.replace('\\', '\\\\').replace('%', '\\%').replace('_', '\\_')
+
 
 class ConnectionPool:
     """
@@ -56,11 +71,13 @@ class ConnectionPool:
 
         # Pre-populate the pool with initial connections
         for _ in range(min(3, max_connections)):  # Start with 3 connections
-            self._create_connection()
+            self._pool.put_nowait(self._create_connection())
+            self._active_connections += 1
 
     def _create_connection(self) -> sqlite3.Connection:
         """Create a new database connection with optimized settings."""
-        conn = sqlite3.connect(self.db_path, timeout=30.0, isolation_level=None)
+        # Pooled connections serve whichever thread takes them (one at a time)
+        conn = sqlite3.connect(self.db_path, timeout=30.0, isolation_level=None, check_same_thread=False)
         conn.row_factory = sqlite3.Row
         conn.execute("PRAGMA journal_mode=WAL")  # Write-Ahead Logging for better concurrency
         conn.execute("PRAGMA synchronous=NORMAL")  # Balance between performance and safety
@@ -154,6 +171,9 @@ class DatabaseManager:
         # Cache for prepared statements
         self._prepared_statements = {}
 
+        # False when SQLite cannot create the full-text index (see SEARCH_UNAVAILABLE)
+        self.search_available = True
+
         self._initialize_database()
 
     @contextmanager
@@ -370,6 +390,319 @@ class DatabaseManager:
                 """
             )
 
+            # Operation journal: one row per user-level operation (e.g. extract-and-organize)
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS operations (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    kind TEXT NOT NULL,
+                    description TEXT,
+                    status TEXT DEFAULT 'pending', -- pending | applied | failed | undone | partially_undone
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    completed_at DATETIME,
+                    undone_at DATETIME,
+                    stack_seq INTEGER, -- position on the undo stack (set when applied or redone)
+                    redo_seq INTEGER,  -- position on the redo stack (NULL = not redoable)
+                    timings TEXT       -- JSON: time spent per stage of the run
+                )
+                """
+            )
+
+            # Undo/redo stack positions for journals created before they existed
+            for column in ('stack_seq', 'redo_seq'):
+                try:
+                    cursor.execute(f"ALTER TABLE operations ADD COLUMN {column} INTEGER")
+                except sqlite3.OperationalError:
+                    pass  # Column already exists
+            try:
+                cursor.execute("ALTER TABLE operations ADD COLUMN timings TEXT")
+            except sqlite3.OperationalError:
+                pass  # Column already exists
+
+            # Individual reversible steps of an operation, replayed backwards on undo
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS operation_steps (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    operation_id INTEGER NOT NULL REFERENCES operations(id) ON DELETE CASCADE,
+                    seq INTEGER NOT NULL,
+                    action TEXT NOT NULL, -- move | create | mkdir | copy
+                    source_path TEXT,
+                    dest_path TEXT,
+                    details TEXT,
+                    status TEXT DEFAULT 'applied', -- applied | undone | failed
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            # Folder snapshots taken before large runs (paths + hashes of a subtree)
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS snapshots (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    root_path TEXT NOT NULL,
+                    reason TEXT,
+                    file_count INTEGER DEFAULT 0,
+                    total_size INTEGER DEFAULT 0,
+                    hash_algorithm TEXT,
+                    status TEXT DEFAULT 'active', -- active | restored
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    restored_at DATETIME
+                )
+                """
+            )
+
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS snapshot_entries (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id) ON DELETE CASCADE,
+                    rel_path TEXT NOT NULL,
+                    file_size INTEGER,
+                    modified_time REAL,
+                    file_hash TEXT
+                )
+                """
+            )
+
+            # Replaced files kept as content-addressed versions of their path
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS file_versions (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    file_path TEXT NOT NULL,
+                    content_hash TEXT NOT NULL,
+                    file_size INTEGER,
+                    modified_time REAL,
+                    reason TEXT,
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            # Pinned folders shown as one-click targets, with cached quick stats
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS pinned_folders (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    folder_path TEXT NOT NULL UNIQUE,
+                    label TEXT,
+                    position INTEGER DEFAULT 0,
+                    item_count INTEGER,
+                    unorganized_count INTEGER,
+                    last_run DATETIME,
+                    stats_updated_at DATETIME,
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            # Virtual collections: named sets of files referenced by content hash
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS collections (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL UNIQUE,
+                    description TEXT,
+                    rule TEXT, -- JSON rule that populates the collection, if any
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS collection_items (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+                    content_hash TEXT NOT NULL,
+                    file_path TEXT, -- last known location
+                    file_size INTEGER,
+                    added_by TEXT DEFAULT 'user', -- user | rule | faces
+                    added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE(collection_id, content_hash)
+                )
+                """
+            )
+
+            # Face clustering: photos already scanned, the faces found and the people they belong to
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS face_scans (
+                    content_hash TEXT PRIMARY KEY,
+                    file_path TEXT NOT NULL, -- last known location
+                    face_count INTEGER NOT NULL DEFAULT 0,
+                    scanned_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS people (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT, -- NULL until the user names the person
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS face_embeddings (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    content_hash TEXT NOT NULL REFERENCES face_scans(content_hash) ON DELETE CASCADE,
+                    face_index INTEGER NOT NULL,
+                    box TEXT, -- JSON [left, top, right, bottom] in pixels
+                    embedding TEXT NOT NULL, -- JSON list of floats (L2-normalized)
+                    person_id INTEGER REFERENCES people(id) ON DELETE SET NULL,
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    UNIQUE(content_hash, face_index)
+                )
+                """
+            )
+
+            # Destinations the user chose instead of the suggested ones, keyed by file features
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS corrections (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    extension TEXT NOT NULL, -- lowercase, without the dot ('' if none)
+                    source_folder TEXT,
+                    name_tokens TEXT, -- JSON list of lowercase name words
+                    content_hash TEXT,
+                    suggested_category TEXT,
+                    suggested_path TEXT,
+                    category TEXT,
+                    destination TEXT NOT NULL, -- JSON {suggested_path, destination_root}
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+
+            # AI classifications of files, reused while the file is unchanged
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS classification_cache (
+                    path TEXT PRIMARY KEY, -- normalized absolute path
+                    size INTEGER NOT NULL,
+                    mtime REAL NOT NULL,
+                    content_hash TEXT, -- BLAKE2b-256, NULL for files over hash_max_mb
+                    result TEXT NOT NULL, -- JSON canonical classification
+                    hits INTEGER DEFAULT 0,
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    last_hit_at DATETIME
+                )
+                """
+            )
+
+            # Files this application put in the platform trash, for the "recently removed" view
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS trash_items (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    original_path TEXT NOT NULL,
+                    trashed_path TEXT, -- NULL where the platform does not say (Windows Recycle Bin)
+                    backend TEXT NOT NULL, -- freedesktop, macos, windows or app
+                    size INTEGER DEFAULT 0,
+                    is_dir INTEGER DEFAULT 0,
+                    trashed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    restored_at DATETIME
+                )
+                """
+            )
+
+            # Organize plans shown for review, so the approved part can be applied later by ID
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS saved_plans (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    root TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    plan TEXT NOT NULL, -- JSON plan file
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    applied_at DATETIME,
+                    operation_id INTEGER
+                )
+                """
+            )
+
+            # Moves set aside because their destination was taken, until the user picks a resolution
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS conflicts (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    source TEXT NOT NULL,
+                    destination TEXT NOT NULL, -- the taken path
+                    reason TEXT NOT NULL, -- destination_exists or shared_destination
+                    is_dir INTEGER DEFAULT 0,
+                    root TEXT, -- folder the plan was for
+                    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    resolved_at DATETIME,
+                    resolution TEXT, -- strategy used, or 'gone' when the source disappeared
+                    result_path TEXT -- where the source ended up, if it moved
+                )
+                """
+            )
+
+            # Tokens and cost of each AI call, for usage summaries and the monthly budget
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS ai_usage (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    recorded_at DATETIME NOT NULL, -- local time
+                    run_id TEXT NOT NULL,
+                    run_label TEXT,
+                    provider TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    tokens INTEGER DEFAULT 0,
+                    cost_usd REAL DEFAULT 0
+                )
+                """
+            )
+
+            # Files of the search index; their words are in search_fts under the same rowid
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS search_documents (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    path TEXT NOT NULL UNIQUE, -- normalized absolute path
+                    name TEXT NOT NULL,
+                    folder TEXT NOT NULL,
+                    extension TEXT,
+                    size INTEGER,
+                    mtime REAL,
+                    year INTEGER, -- of mtime, local time
+                    category TEXT,
+                    tags TEXT, -- JSON list
+                    summary TEXT,
+                    indexed_at DATETIME DEFAULT CURRENT_TIMESTAMP
+                )
+                """
+            )
+            try:
+                cursor.execute(
+                    "CREATE VIRTUAL TABLE IF NOT EXISTS search_fts USING fts5("
+                    "name, folders, text, tags, summary, tokenize = 'unicode61 remove_diacritics 2')"
+                )
+            except sqlite3.OperationalError as e:
+                self.search_available = False
+                logger.warning(f"{SEARCH_UNAVAILABLE} ({e})")
+
+            # Tags of files, set by the user or kept from AI classifications
+            cursor.execute(
+                """
+                CREATE TABLE IF NOT EXISTS file_tags (
+                    path TEXT NOT NULL, -- normalized absolute path
+                    tag TEXT NOT NULL COLLATE NOCASE,
+                    source TEXT NOT NULL DEFAULT 'user', -- user, ai or file (read from the file's attributes)
+                    added_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                    PRIMARY KEY (path, tag)
+                )
+                """
+            )
+
             # Create comprehensive indexes for performance
             cursor.execute("CREATE INDEX IF NOT EXISTS idx_files_timestamp ON files_log(timestamp)")
             cursor.execute("CREATE INDEX IF NOT EXISTS idx_files_category ON files_log(category)")
@@ -380,6 +713,20 @@ class DatabaseManager:
             cursor.execute("CREATE INDEX IF NOT EXISTS idx_stats_date ON stats(stat_date)")
             cursor.execute("CREATE INDEX IF NOT EXISTS idx_deferred_status_eligible ON deferred_queue(status, eligible_at)")
             cursor.execute("CREATE INDEX IF NOT EXISTS idx_deferred_eligible ON deferred_queue(eligible_at)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_operations_created ON operations(created_at)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_operation_steps_op ON operation_steps(operation_id, seq)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_snapshot_entries_snapshot ON snapshot_entries(snapshot_id)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_file_versions_path ON file_versions(file_path, id)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_file_versions_hash ON file_versions(content_hash)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_collection_items_hash ON collection_items(content_hash)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_face_embeddings_person ON face_embeddings(person_id)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_corrections_extension ON corrections(extension, id)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_corrections_hash ON corrections(content_hash)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_classification_cache_hash ON classification_cache(content_hash, size)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_ai_usage_recorded ON ai_usage(recorded_at)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_search_documents_folder ON search_documents(folder)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_file_tags_tag ON file_tags(tag)")
+            cursor.execute("CREATE INDEX IF NOT EXISTS idx_conflicts_open ON conflicts(resolved_at, id)")
 
             # Composite indexes for common queries
             cursor.execute("CREATE INDEX IF NOT EXISTS idx_files_timestamp_category ON files_log(timestamp, category)")
@@ -393,7 +740,7 @@ class DatabaseManager:
                    operation: str, time_saved: float = 0.0, category: Optional[str] = None,
                    ai_suggested: bool = False, user_approved: bool = False,
                    raw_response: Optional[str] = None, model_name: Optional[str] = None,
-                   prompt_hash: Optional[str] = None) -> int:
+                   prompt_hash: Optional[str] = None, count_stats: bool = True) -> int:
         """
         Log a file operation to the database with atomic transaction support.
 
@@ -409,6 +756,8 @@ class DatabaseManager:
             raw_response (str, optional): Raw LLM response for debugging
             model_name (str, optional): Name of the model used
             prompt_hash (str, optional): Hash of the prompt for traceability
+            count_stats (bool): Count it in the daily stats (False for a file
+                                that moved along with another, e.g. a RAW with its JPEG)
 
         Returns:
             int: ID of the inserted log entry
@@ -434,15 +783,16 @@ class DatabaseManager:
                     raise RuntimeError("Failed to get log ID after insert")
 
                 # Update daily stats
-                today = datetime.now().date()
-                cursor.execute("""
-                    INSERT INTO stats (stat_date, files_organised, time_saved_minutes, ai_classifications)
-                    VALUES (?, 1, ?, ?)
-                    ON CONFLICT(stat_date) DO UPDATE SET
-                        files_organised = files_organised + 1,
-                        time_saved_minutes = time_saved_minutes + ?,
-                        ai_classifications = ai_classifications + ?
-                """, (today, time_saved, 1 if ai_suggested else 0, time_saved, 1 if ai_suggested else 0))
+                if count_stats:
+                    today = datetime.now().date()
+                    cursor.execute("""
+                        INSERT INTO stats (stat_date, files_organised, time_saved_minutes, ai_classifications)
+                        VALUES (?, 1, ?, ?)
+                        ON CONFLICT(stat_date) DO UPDATE SET
+                            files_organised = files_organised + 1,
+                            time_saved_minutes = time_saved_minutes + ?,
+                            ai_classifications = ai_classifications + ?
+                    """, (today, time_saved, 1 if ai_suggested else 0, time_saved, 1 if ai_suggested else 0))
 
                 # Commit transaction (HIGH #5 FIX)
                 conn.commit()
@@ -524,6 +874,52 @@ class DatabaseManager:
             cursor.execute(prepared_sql, tuple(params))
             return [dict(row) for row in cursor.fetchall()]
 
+    def get_moves_since(self, since: str) -> List[Dict[str, Any]]:
+        """
+        Get logged moves and renames since a point in time, oldest first.
+
+        Args:
+            since (str): SQLite timestamp ('YYYY-MM-DD HH:MM:SS', UTC)
+
+        Returns:
+            List[Dict]: Entries with 'old_path' and 'new_path'
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT id, old_path, new_path, operation, timestamp
+                FROM files_log
+                WHERE timestamp >= ? AND new_path IS NOT NULL AND operation IN ('move', 'rename')
+                ORDER BY id ASC
+                """,
+                (since,)
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    def get_file_changes_since(self, since: str) -> List[Dict[str, Any]]:
+        """
+        Get logged moves, renames, archives and deletes since a point in time, oldest first.
+
+        Args:
+            since (str): SQLite timestamp ('YYYY-MM-DD HH:MM:SS', UTC)
+
+        Returns:
+            List[Dict]: Entries with 'old_path', 'new_path' (None for deletes) and 'timestamp'
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT id, old_path, new_path, operation, timestamp
+                FROM files_log
+                WHERE timestamp >= ? AND operation IN ('move', 'rename', 'archive', 'delete')
+                ORDER BY id ASC
+                """,
+                (since,)
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
     def undo_last_action(self) -> Optional[Dict[str, Any]]:
         """
         Get information about the last action for undo functionality.
@@ -814,6 +1210,1422 @@ class DatabaseManager:
                 (status, error, item_id)
             )
 
+    # ==================== Operation Journal Operations ====================
+
+    def create_operation(self, kind: str, description: str = '') -> int:
+        """
+        Create a new journal operation in 'pending' state.
+
+        Args:
+            kind (str): Operation type (e.g. 'extract_organize')
+            description (str): Human-readable summary
+
+        Returns:
+            int: Operation ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "INSERT INTO operations (kind, description, status) VALUES (?, ?, 'pending')",
+                (kind, description)
+            )
+            operation_id = cursor.lastrowid
+            if operation_id is None:
+                raise RuntimeError("Failed to get operation ID after insert")
+            return operation_id
+
+    def add_operation_step(self, operation_id: int, action: str, source_path: Optional[str],
+                           dest_path: Optional[str], details: Optional[str] = None) -> int:
+        """
+        Append a step to a journal operation.
+
+        Args:
+            operation_id (int): Parent operation ID
+            action (str): Step action (move, create, mkdir, copy)
+            source_path (str, optional): Source path
+            dest_path (str, optional): Destination path
+            details (str, optional): JSON-encoded extra details
+
+        Returns:
+            int: Step ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "SELECT COALESCE(MAX(seq), 0) + 1 FROM operation_steps WHERE operation_id = ?",
+                (operation_id,)
+            )
+            seq = cursor.fetchone()[0]
+            cursor.execute(
+                """
+                INSERT INTO operation_steps (operation_id, seq, action, source_path, dest_path, details)
+                VALUES (?, ?, ?, ?, ?, ?)
+                """,
+                (operation_id, seq, action, source_path, dest_path, details)
+            )
+            step_id = cursor.lastrowid
+            if step_id is None:
+                raise RuntimeError("Failed to get step ID after insert")
+            return step_id
+
+    def update_operation_status(self, operation_id: int, status: str) -> None:
+        """
+        Update a journal operation's status and matching timestamp.
+
+        Args:
+            operation_id (int): Operation ID
+            status (str): New status
+        """
+        column = 'undone_at' if status in ('undone', 'partially_undone') else 'completed_at'
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                f"UPDATE operations SET status = ?, {column} = CURRENT_TIMESTAMP WHERE id = ?",
+                (status, operation_id)
+            )
+
+    def set_operation_timings(self, operation_id: int, timings: str) -> None:
+        """
+        Store the stage timings of a journal operation.
+
+        Args:
+            operation_id (int): Operation ID
+            timings (str): JSON-encoded timings
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE operations SET timings = ? WHERE id = ?", (timings, operation_id))
+
+    def update_operation_step_status(self, step_id: int, status: str) -> None:
+        """Update the status of a single journal step."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE operation_steps SET status = ? WHERE id = ?", (status, step_id))
+
+    def get_operation(self, operation_id: int) -> Optional[Dict[str, Any]]:
+        """
+        Get a journal operation with its steps.
+
+        Args:
+            operation_id (int): Operation ID
+
+        Returns:
+            Dict or None: Operation row with a 'steps' list ordered by seq
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM operations WHERE id = ?", (operation_id,))
+            row = cursor.fetchone()
+            if not row:
+                return None
+            operation = dict(row)
+            cursor.execute(
+                "SELECT * FROM operation_steps WHERE operation_id = ? ORDER BY seq ASC",
+                (operation_id,)
+            )
+            operation['steps'] = [dict(step) for step in cursor.fetchall()]
+            return operation
+
+    def list_operations(self, limit: int = 50, status: Optional[str] = None) -> List[Dict[str, Any]]:
+        """
+        List journal operations, newest first.
+
+        Args:
+            limit (int): Maximum number of operations
+            status (str, optional): Only return operations with this status
+
+        Returns:
+            List[Dict]: Operations with a 'step_count' field
+        """
+        sql = """
+            SELECT o.*, COUNT(s.id) AS step_count
+            FROM operations o
+            LEFT JOIN operation_steps s ON s.operation_id = o.id
+        """
+        params: List[Any] = []
+        if status:
+            sql += " WHERE o.status = ?"
+            params.append(status)
+        sql += " GROUP BY o.id ORDER BY o.id DESC LIMIT ?"
+        params.append(limit)
+
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(sql, tuple(params))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def push_undo_stack(self, operation_id: int) -> None:
+        """
+        Put an operation on top of the undo stack (and take it off the redo stack).
+
+        Args:
+            operation_id (int): Operation ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                UPDATE operations
+                SET stack_seq = (SELECT COALESCE(MAX(stack_seq), 0) + 1 FROM operations), redo_seq = NULL
+                WHERE id = ?
+                """,
+                (operation_id,)
+            )
+
+    def push_redo_stack(self, operation_id: int) -> None:
+        """
+        Put an undone operation on top of the redo stack.
+
+        Args:
+            operation_id (int): Operation ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "UPDATE operations SET redo_seq = (SELECT COALESCE(MAX(redo_seq), 0) + 1 FROM operations) WHERE id = ?",
+                (operation_id,)
+            )
+
+    def clear_redo_stack(self) -> int:
+        """
+        Drop every operation from the redo stack.
+
+        Returns:
+            int: Number of operations that were redoable
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE operations SET redo_seq = NULL WHERE redo_seq IS NOT NULL")
+            return cursor.rowcount
+
+    def list_operation_stack(self, stack: str, limit: int = 50) -> List[Dict[str, Any]]:
+        """
+        List the undo or redo stack, top first.
+
+        Args:
+            stack (str): 'undo' (applied operations) or 'redo' (undone, redoable operations)
+            limit (int): Maximum number of operations
+
+        Returns:
+            List[Dict]: Operations with a 'step_count' field
+        """
+        if stack == 'undo':
+            where, order = "o.status = 'applied' AND o.stack_seq IS NOT NULL", "o.stack_seq"
+        elif stack == 'redo':
+            where, order = "o.status = 'undone' AND o.redo_seq IS NOT NULL", "o.redo_seq"
+        else:
+            raise ValueError(f"Unknown operation stack: {stack}")
+
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                f"""
+                SELECT o.*, COUNT(s.id) AS step_count
+                FROM operations o
+                LEFT JOIN operation_steps s ON s.operation_id = o.id
+                WHERE {where}
+                GROUP BY o.id ORDER BY {order} DESC LIMIT ?
+                """,
+                (limit,)
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    def get_operation_steps_since(self, since: str) -> List[Dict[str, Any]]:
+        """
+        Get journal steps applied or undone since a point in time.
+
+        Args:
+            since (str): SQLite timestamp ('YYYY-MM-DD HH:MM:SS', UTC)
+
+        Returns:
+            List[Dict]: Steps with their operation's 'operation_status' and 'undone_at'
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT s.*, o.status AS operation_status, o.undone_at
+                FROM operation_steps s
+                JOIN operations o ON o.id = s.operation_id
+                WHERE s.created_at >= ? OR o.undone_at >= ?
+                ORDER BY s.id ASC
+                """,
+                (since, since)
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    # ==================== Snapshot Operations ====================
+
+    def create_snapshot(self, root_path: str, reason: str, hash_algorithm: str,
+                        entries: List[Tuple[str, int, float, Optional[str]]]) -> int:
+        """
+        Store a folder snapshot and its entries.
+
+        Args:
+            root_path (str): Snapshot root directory
+            reason (str): Why the snapshot was taken
+            hash_algorithm (str): Algorithm used for entry hashes
+            entries (List[Tuple]): (rel_path, size, mtime, hash) per file
+
+        Returns:
+            int: Snapshot ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                INSERT INTO snapshots (root_path, reason, file_count, total_size, hash_algorithm)
+                VALUES (?, ?, ?, ?, ?)
+                """,
+                (root_path, reason, len(entries), sum(e[1] for e in entries), hash_algorithm)
+            )
+            snapshot_id = cursor.lastrowid
+            if snapshot_id is None:
+                raise RuntimeError("Failed to get snapshot ID after insert")
+            cursor.executemany(
+                """
+                INSERT INTO snapshot_entries (snapshot_id, rel_path, file_size, modified_time, file_hash)
+                VALUES (?, ?, ?, ?, ?)
+                """,
+                [(snapshot_id, *entry) for entry in entries]
+            )
+            return snapshot_id
+
+    def get_snapshot(self, snapshot_id: int, include_entries: bool = True) -> Optional[Dict[str, Any]]:
+        """
+        Get a snapshot, optionally with its entries.
+
+        Args:
+            snapshot_id (int): Snapshot ID
+            include_entries (bool): Also load the 'entries' list
+
+        Returns:
+            Dict or None: Snapshot row
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM snapshots WHERE id = ?", (snapshot_id,))
+            row = cursor.fetchone()
+            if not row:
+                return None
+            snapshot = dict(row)
+            if include_entries:
+                cursor.execute(
+                    "SELECT rel_path, file_size, modified_time, file_hash FROM snapshot_entries "
+                    "WHERE snapshot_id = ? ORDER BY rel_path",
+                    (snapshot_id,)
+                )
+                snapshot['entries'] = [dict(entry) for entry in cursor.fetchall()]
+            return snapshot
+
+    def list_snapshots(self, limit: int = 50) -> List[Dict[str, Any]]:
+        """
+        List snapshots, newest first.
+
+        Args:
+            limit (int): Maximum number of snapshots
+
+        Returns:
+            List[Dict]: Snapshot rows (without entries)
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM snapshots ORDER BY id DESC LIMIT ?", (limit,))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def mark_snapshot_restored(self, snapshot_id: int) -> None:
+        """Mark a snapshot as restored."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "UPDATE snapshots SET status = 'restored', restored_at = CURRENT_TIMESTAMP WHERE id = ?",
+                (snapshot_id,)
+            )
+
+    def prune_snapshots(self, keep: int) -> int:
+        """
+        Delete all but the newest snapshots.
+
+        Args:
+            keep (int): Number of snapshots to keep
+
+        Returns:
+            int: Number of snapshots deleted
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT id FROM snapshots ORDER BY id DESC LIMIT -1 OFFSET ?", (keep,))
+            old_ids = [row[0] for row in cursor.fetchall()]
+            for snapshot_id in old_ids:
+                cursor.execute("DELETE FROM snapshot_entries WHERE snapshot_id = ?", (snapshot_id,))
+                cursor.execute("DELETE FROM snapshots WHERE id = ?", (snapshot_id,))
+            return len(old_ids)
+
+    # ==================== File Version Operations ====================
+
+    def add_file_version(self, file_path: str, content_hash: str, file_size: int,
+                         modified_time: float, reason: str = '') -> int:
+        """
+        Record a stored version of a file.
+
+        Args:
+            file_path (str): Path the version belonged to
+            content_hash (str): Hash naming the stored content
+            file_size (int): Size in bytes
+            modified_time (float): Modification time of the replaced file
+            reason (str): Why the version was kept (e.g. 'replaced by move')
+
+        Returns:
+            int: Version ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                INSERT INTO file_versions (file_path, content_hash, file_size, modified_time, reason)
+                VALUES (?, ?, ?, ?, ?)
+                """,
+                (file_path, content_hash, file_size, modified_time, reason)
+            )
+            version_id = cursor.lastrowid
+            if version_id is None:
+                raise RuntimeError("Failed to get version ID after insert")
+            return version_id
+
+    def get_file_version(self, version_id: int) -> Optional[Dict[str, Any]]:
+        """Get a single file version by ID."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM file_versions WHERE id = ?", (version_id,))
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def list_file_versions(self, file_path: Optional[str] = None, limit: int = 100) -> List[Dict[str, Any]]:
+        """
+        List stored versions, newest first.
+
+        Args:
+            file_path (str, optional): Only versions of this path
+            limit (int): Maximum number of versions
+
+        Returns:
+            List[Dict]: Version rows
+        """
+        sql = "SELECT * FROM file_versions"
+        params: List[Any] = []
+        if file_path:
+            sql += " WHERE file_path = ?"
+            params.append(file_path)
+        sql += " ORDER BY id DESC LIMIT ?"
+        params.append(limit)
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(sql, tuple(params))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def prune_file_versions(self, keep: int, max_age_days: Optional[int] = None,
+                            file_path: Optional[str] = None) -> List[str]:
+        """
+        Delete versions beyond the newest `keep` per path, or older than max_age_days.
+
+        Args:
+            keep (int): Versions to keep per path
+            max_age_days (int, optional): Delete versions older than this
+            file_path (str, optional): Only prune versions of this path
+
+        Returns:
+            List[str]: Content hashes no longer referenced by any version
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            where, params = "", []
+            if file_path:
+                where, params = "WHERE file_path = ?", [file_path]
+            cursor.execute(
+                f"""
+                SELECT id, content_hash FROM (
+                    SELECT id, content_hash, created_at,
+                           ROW_NUMBER() OVER (PARTITION BY file_path ORDER BY id DESC) AS rank
+                    FROM file_versions {where}
+                )
+                WHERE rank > ? OR (? IS NOT NULL AND created_at < datetime('now', ?))
+                """,
+                (*params, keep, max_age_days, f'-{max_age_days or 0} days')
+            )
+            doomed = cursor.fetchall()
+            hashes = {row[1] for row in doomed}
+            cursor.executemany("DELETE FROM file_versions WHERE id = ?", [(row[0],) for row in doomed])
+
+            orphaned = []
+            for content_hash in hashes:
+                cursor.execute("SELECT 1 FROM file_versions WHERE content_hash = ? LIMIT 1", (content_hash,))
+                if cursor.fetchone() is None:
+                    orphaned.append(content_hash)
+            return orphaned
+
+    # ==================== Pinned Folder Operations ====================
+
+    def pin_folder(self, folder_path: str, label: Optional[str] = None) -> int:
+        """
+        Pin a folder (or relabel an already pinned one).
+
+        Args:
+            folder_path (str): Folder path
+            label (str, optional): Display label
+
+        Returns:
+            int: Pin ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                INSERT INTO pinned_folders (folder_path, label, position)
+                VALUES (?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM pinned_folders))
+                ON CONFLICT(folder_path) DO UPDATE SET label = COALESCE(excluded.label, label)
+                """,
+                (folder_path, label)
+            )
+            cursor.execute("SELECT id FROM pinned_folders WHERE folder_path = ?", (folder_path,))
+            return cursor.fetchone()['id']
+
+    def unpin_folder(self, folder_path: str) -> bool:
+        """Remove a pin. Returns True if the folder was pinned."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("DELETE FROM pinned_folders WHERE folder_path = ?", (folder_path,))
+            return cursor.rowcount > 0
+
+    def list_pinned_folders(self) -> List[Dict[str, Any]]:
+        """List pinned folders in sidebar order."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM pinned_folders ORDER BY position ASC, id ASC")
+            return [dict(row) for row in cursor.fetchall()]
+
+    def reorder_pinned_folders(self, folder_paths: List[str]) -> None:
+        """Set the sidebar order; pins not listed keep their relative order after these."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE pinned_folders SET position = position + ?", (len(folder_paths),))
+            for position, folder_path in enumerate(folder_paths):
+                cursor.execute("UPDATE pinned_folders SET position = ? WHERE folder_path = ?",
+                               (position, folder_path))
+
+    def update_pinned_folder_stats(self, folder_path: str, item_count: Optional[int],
+                                   unorganized_count: Optional[int], last_run: Optional[str]) -> None:
+        """Store freshly computed quick stats for a pinned folder."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                UPDATE pinned_folders
+                SET item_count = ?, unorganized_count = ?, last_run = ?, stats_updated_at = CURRENT_TIMESTAMP
+                WHERE folder_path = ?
+                """,
+                (item_count, unorganized_count, last_run, folder_path)
+            )
+
+    def get_last_operation_time(self, folder_path: str) -> Optional[str]:
+        """
+        When an applied journal operation last moved files out of or into a folder.
+
+        Args:
+            folder_path (str): Folder path
+
+        Returns:
+            str or None: SQLite timestamp (UTC) of the latest such operation
+        """
+        prefix = folder_path.rstrip('/\\') + os.sep
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT MAX(COALESCE(o.completed_at, o.created_at)) AS last_run
+                FROM operations o
+                JOIN operation_steps s ON s.operation_id = o.id
+                WHERE o.status IN ('applied', 'partially_undone')
+                  AND (substr(s.source_path, 1, ?) = ? OR substr(s.dest_path, 1, ?) = ?)
+                """,
+                (len(prefix), prefix, len(prefix), prefix)
+            )
+            row = cursor.fetchone()
+            return row['last_run'] if row else None
+
+    # ==================== Collection Operations ====================
+
+    def create_collection(self, name: str, description: str = '', rule: Optional[str] = None) -> int:
+        """
+        Create a collection.
+
+        Args:
+            name (str): Unique collection name
+            description (str): Optional description
+            rule (str, optional): JSON rule that populates the collection
+
+        Returns:
+            int: Collection ID
+
+        Raises:
+            sqlite3.IntegrityError: If the name is taken
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "INSERT INTO collections (name, description, rule) VALUES (?, ?, ?)",
+                (name, description, rule)
+            )
+            collection_id = cursor.lastrowid
+            if collection_id is None:
+                raise RuntimeError("Failed to get collection ID after insert")
+            return collection_id
+
+    def get_collection(self, name: str) -> Optional[Dict[str, Any]]:
+        """Get a collection by name, with its item count."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT c.*, (SELECT COUNT(*) FROM collection_items i WHERE i.collection_id = c.id) AS item_count
+                FROM collections c WHERE c.name = ?
+                """,
+                (name,)
+            )
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def list_collections(self) -> List[Dict[str, Any]]:
+        """List collections by name, with item counts."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT c.*, (SELECT COUNT(*) FROM collection_items i WHERE i.collection_id = c.id) AS item_count
+                FROM collections c ORDER BY c.name ASC
+                """
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    def delete_collection(self, collection_id: int) -> None:
+        """Delete a collection and its references (files are not touched)."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("DELETE FROM collection_items WHERE collection_id = ?", (collection_id,))
+            cursor.execute("DELETE FROM collections WHERE id = ?", (collection_id,))
+
+    def add_collection_item(self, collection_id: int, content_hash: str, file_path: str,
+                            file_size: int, added_by: str = 'user') -> bool:
+        """
+        Add a file reference; refreshes the known location if the content is already in.
+
+        Returns:
+            bool: True if the content was new to the collection
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                INSERT OR IGNORE INTO collection_items (collection_id, content_hash, file_path, file_size, added_by)
+                VALUES (?, ?, ?, ?, ?)
+                """,
+                (collection_id, content_hash, file_path, file_size, added_by)
+            )
+            if cursor.rowcount:
+                return True
+            cursor.execute(
+                "UPDATE collection_items SET file_path = ? WHERE collection_id = ? AND content_hash = ?",
+                (file_path, collection_id, content_hash)
+            )
+            return False
+
+    def remove_collection_item(self, collection_id: int, content_hash: str) -> bool:
+        """Remove a file reference. Returns True if it was in the collection."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "DELETE FROM collection_items WHERE collection_id = ? AND content_hash = ?",
+                (collection_id, content_hash)
+            )
+            return cursor.rowcount > 0
+
+    def list_collection_items(self, collection_id: int) -> List[Dict[str, Any]]:
+        """List a collection's file references in the order they were added."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "SELECT * FROM collection_items WHERE collection_id = ? ORDER BY id ASC",
+                (collection_id,)
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    def update_collection_item_path(self, item_id: int, file_path: str) -> None:
+        """Record where a referenced file lives now."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE collection_items SET file_path = ? WHERE id = ?", (file_path, item_id))
+
+    def find_moved_path(self, file_path: str) -> Optional[str]:
+        """
+        Follow applied journal moves of a path to where the file went.
+
+        Args:
+            file_path (str): Former location
+
+        Returns:
+            str or None: Latest known destination, or None if it never moved
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            current, seen = file_path, set()
+            while current not in seen:
+                seen.add(current)
+                cursor.execute(
+                    """
+                    SELECT dest_path FROM operation_steps
+                    WHERE action = 'move' AND status = 'applied' AND source_path = ?
+                    ORDER BY id DESC LIMIT 1
+                    """,
+                    (current,)
+                )
+                row = cursor.fetchone()
+                if not row or not row['dest_path']:
+                    break
+                current = row['dest_path']
+            return current if current != file_path else None
+
+    # ==================== Face Operations ====================
+
+    def is_face_scanned(self, content_hash: str) -> bool:
+        """Whether a photo with this content was already scanned for faces."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT 1 FROM face_scans WHERE content_hash = ?", (content_hash,))
+            return cursor.fetchone() is not None
+
+    def record_face_scan(self, content_hash: str, file_path: str, faces: List[Dict[str, Any]]) -> None:
+        """
+        Store the faces found in a photo (replacing an earlier scan of the same content).
+
+        Args:
+            content_hash (str): Content hash of the photo (see core.hashing)
+            file_path (str): Where the photo is
+            faces (List[Dict]): {'box', 'embedding'} per face, both JSON-encoded
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("DELETE FROM face_embeddings WHERE content_hash = ?", (content_hash,))
+            cursor.execute(
+                "INSERT OR REPLACE INTO face_scans (content_hash, file_path, face_count) VALUES (?, ?, ?)",
+                (content_hash, file_path, len(faces))
+            )
+            cursor.executemany(
+                "INSERT INTO face_embeddings (content_hash, face_index, box, embedding) VALUES (?, ?, ?, ?)",
+                [(content_hash, i, face.get('box'), face['embedding']) for i, face in enumerate(faces)]
+            )
+
+    def update_face_scan_path(self, content_hash: str, file_path: str) -> None:
+        """Record where a scanned photo lives now."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE face_scans SET file_path = ? WHERE content_hash = ?", (file_path, content_hash))
+
+    def list_faces(self, person_id: Optional[int] = None, unassigned: bool = False) -> List[Dict[str, Any]]:
+        """
+        List detected faces with the photo they are in.
+
+        Args:
+            person_id (int, optional): Only this person's faces
+            unassigned (bool): Only faces not yet assigned to a person
+
+        Returns:
+            List[Dict]: Rows with 'id', 'content_hash', 'file_path', 'box', 'embedding' (JSON) and 'person_id'
+        """
+        query = """
+            SELECT f.*, s.file_path FROM face_embeddings f
+            JOIN face_scans s ON s.content_hash = f.content_hash
+        """
+        params: tuple = ()
+        if person_id is not None:
+            query += " WHERE f.person_id = ?"
+            params = (person_id,)
+        elif unassigned:
+            query += " WHERE f.person_id IS NULL"
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(query + " ORDER BY f.id ASC", params)
+            return [dict(row) for row in cursor.fetchall()]
+
+    def assign_face(self, face_id: int, person_id: Optional[int]) -> None:
+        """Assign a face to a person (None to unassign)."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE face_embeddings SET person_id = ? WHERE id = ?", (person_id, face_id))
+
+    def create_person(self, name: Optional[str] = None) -> int:
+        """Create a person (unnamed by default) and return its ID."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("INSERT INTO people (name) VALUES (?)", (name,))
+            person_id = cursor.lastrowid
+            if person_id is None:
+                raise RuntimeError("Failed to get person ID after insert")
+            return person_id
+
+    def get_person(self, person_id: int) -> Optional[Dict[str, Any]]:
+        """Get a person with their face and photo counts."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT p.*, COUNT(f.id) AS face_count, COUNT(DISTINCT f.content_hash) AS photo_count
+                FROM people p LEFT JOIN face_embeddings f ON f.person_id = p.id
+                WHERE p.id = ? GROUP BY p.id
+                """,
+                (person_id,)
+            )
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def list_people(self) -> List[Dict[str, Any]]:
+        """List people, most photographed first, with face and photo counts."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                SELECT p.*, COUNT(f.id) AS face_count, COUNT(DISTINCT f.content_hash) AS photo_count
+                FROM people p LEFT JOIN face_embeddings f ON f.person_id = p.id
+                GROUP BY p.id ORDER BY face_count DESC, p.id ASC
+                """
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    def rename_person(self, person_id: int, name: Optional[str]) -> bool:
+        """Name (or unname) a person. Returns True if the person exists."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE people SET name = ? WHERE id = ?", (name, person_id))
+            return cursor.rowcount > 0
+
+    def merge_people(self, keep_id: int, other_id: int) -> int:
+        """
+        Move every face of one person to another and delete the emptied person.
+
+        Returns:
+            int: Number of faces moved
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE face_embeddings SET person_id = ? WHERE person_id = ?", (keep_id, other_id))
+            moved = cursor.rowcount
+            cursor.execute("DELETE FROM people WHERE id = ?", (other_id,))
+            return moved
+
+    def delete_empty_people(self) -> int:
+        """Delete people left without faces. Returns how many were deleted."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "DELETE FROM people WHERE id NOT IN (SELECT DISTINCT person_id FROM face_embeddings "
+                "WHERE person_id IS NOT NULL)"
+            )
+            return cursor.rowcount
+
+    # ==================== Corrections ====================
+
+    def add_correction(self, extension: str, source_folder: Optional[str], name_tokens: str,
+                       content_hash: Optional[str], suggested_category: Optional[str],
+                       suggested_path: Optional[str], category: Optional[str], destination: str) -> int:
+        """
+        Record a user's correction of a suggested destination.
+
+        Args:
+            extension (str): File extension (lowercase, no dot)
+            source_folder (str, optional): Folder the file was in
+            name_tokens (str): JSON list of name words
+            content_hash (str, optional): Content hash (see core.hashing)
+            suggested_category (str, optional): Category that was suggested
+            suggested_path (str, optional): Destination that was suggested
+            category (str, optional): Category the user chose
+            destination (str): JSON destination the user chose
+
+        Returns:
+            int: Correction ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                INSERT INTO corrections (extension, source_folder, name_tokens, content_hash,
+                                         suggested_category, suggested_path, category, destination)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                """,
+                (extension, source_folder, name_tokens, content_hash, suggested_category,
+                 suggested_path, category, destination)
+            )
+            correction_id = cursor.lastrowid
+            if correction_id is None:
+                raise RuntimeError("Failed to get correction ID after insert")
+            return correction_id
+
+    def find_corrections(self, extension: str, content_hash: Optional[str] = None,
+                         limit: int = 200) -> List[Dict[str, Any]]:
+        """Recent corrections for an extension, plus any for the same content, newest first."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "SELECT * FROM corrections WHERE extension = ? OR (content_hash IS NOT NULL AND content_hash = ?) "
+                "ORDER BY id DESC LIMIT ?",
+                (extension, content_hash, limit)
+            )
+            return [dict(row) for row in cursor.fetchall()]
+
+    def list_corrections(self, limit: int = 100) -> List[Dict[str, Any]]:
+        """Recent corrections, newest first."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM corrections ORDER BY id DESC LIMIT ?", (limit,))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def delete_corrections(self, correction_id: Optional[int] = None) -> int:
+        """Forget one correction, or all of them. Returns how many were deleted."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            if correction_id is None:
+                cursor.execute("DELETE FROM corrections")
+            else:
+                cursor.execute("DELETE FROM corrections WHERE id = ?", (correction_id,))
+            return cursor.rowcount
+
+    # ==================== Classification Cache ====================
+
+    def get_cached_classification(self, path: str) -> Optional[Dict[str, Any]]:
+        """The cache entry for a path, or None."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM classification_cache WHERE path = ?", (path,))
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def find_cached_classification(self, content_hash: str, size: int) -> Optional[Dict[str, Any]]:
+        """The newest cache entry for the same content, or None."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "SELECT * FROM classification_cache WHERE content_hash = ? AND size = ? "
+                "ORDER BY created_at DESC LIMIT 1",
+                (content_hash, size)
+            )
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def put_cached_classification(self, path: str, size: int, mtime: float,
+                                  content_hash: Optional[str], result: str) -> None:
+        """
+        Store (or replace) the cache entry for a path.
+
+        Args:
+            path (str): Normalized absolute path
+            size (int): File size in bytes
+            mtime (float): Modification time
+            content_hash (str, optional): Content hash (see core.hashing)
+            result (str): JSON classification
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                INSERT OR REPLACE INTO classification_cache (path, size, mtime, content_hash, result)
+                VALUES (?, ?, ?, ?, ?)
+                """,
+                (path, size, mtime, content_hash, result)
+            )
+
+    def record_classification_cache_hit(self, path: str) -> None:
+        """Count a reuse of a path's cache entry."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "UPDATE classification_cache SET hits = hits + 1, last_hit_at = CURRENT_TIMESTAMP WHERE path = ?",
+                (path,)
+            )
+
+    def prune_classification_cache(self, keep: int) -> int:
+        """Drop the least recently used entries beyond keep. Returns how many were dropped."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                """
+                DELETE FROM classification_cache WHERE path IN (
+                    SELECT path FROM classification_cache
+                    ORDER BY COALESCE(last_hit_at, created_at) DESC LIMIT -1 OFFSET ?
+                )
+                """,
+                (keep,)
+            )
+            return cursor.rowcount
+
+    def clear_classification_cache(self) -> int:
+        """Forget every cached classification. Returns how many were deleted."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("DELETE FROM classification_cache")
+            return cursor.rowcount
+
+    def classification_cache_stats(self) -> Dict[str, Any]:
+        """Entry count, total hits and the oldest and newest entry times."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "SELECT COUNT(*) AS entries, COALESCE(SUM(hits), 0) AS total_hits, "
+                "MIN(created_at) AS oldest, MAX(created_at) AS newest FROM classification_cache"
+            )
+            return dict(cursor.fetchone())
+
+    # ==================== Trash ====================
+
+    def add_trash_item(self, original_path: str, trashed_path: Optional[str], backend: str,
+                       size: int = 0, is_dir: bool = False) -> int:
+        """
+        Remember a file put in the trash.
+
+        Args:
+            original_path (str): Where it was
+            trashed_path (str, optional): Where it is in the trash, if known
+            backend (str): Trash it went to
+            size (int): Size in bytes
+            is_dir (bool): Whether it is a folder
+
+        Returns:
+            int: Trash item ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "INSERT INTO trash_items (original_path, trashed_path, backend, size, is_dir) VALUES (?, ?, ?, ?, ?)",
+                (original_path, trashed_path, backend, size, int(is_dir))
+            )
+            return cursor.lastrowid
+
+    def get_trash_item(self, item_id: int) -> Optional[Dict[str, Any]]:
+        """A trash item by ID, or None."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM trash_items WHERE id = ?", (item_id,))
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def list_trash_items(self, limit: int = 100, include_restored: bool = False) -> List[Dict[str, Any]]:
+        """Trash items, newest first."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            where = '' if include_restored else 'WHERE restored_at IS NULL '
+            cursor.execute(f"SELECT * FROM trash_items {where}ORDER BY id DESC LIMIT ?", (limit,))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def mark_trash_item_restored(self, item_id: int) -> None:
+        """Record that a trash item was put back."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE trash_items SET restored_at = CURRENT_TIMESTAMP WHERE id = ?", (item_id,))
+
+    # ==================== Saved Plans ====================
+
+    def save_plan(self, root: str, kind: str, plan: str, keep: int = 50) -> int:
+        """
+        Store a plan for later review, forgetting all but the newest keep.
+
+        Args:
+            root (str): Folder the plan is for
+            kind (str): Plan kind (organize, cleanup)
+            plan (str): Plan as JSON
+            keep (int): Number of plans to keep
+
+        Returns:
+            int: Plan ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("INSERT INTO saved_plans (root, kind, plan) VALUES (?, ?, ?)", (root, kind, plan))
+            plan_id = cursor.lastrowid
+            cursor.execute("DELETE FROM saved_plans WHERE id <= ?", (plan_id - keep,))
+            return plan_id
+
+    def get_saved_plan(self, plan_id: int) -> Optional[Dict[str, Any]]:
+        """A stored plan by ID (its 'plan' still JSON), or None."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM saved_plans WHERE id = ?", (plan_id,))
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def mark_plan_applied(self, plan_id: int, operation_id: Optional[int]) -> None:
+        """Record that a stored plan was applied (and by which operation)."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE saved_plans SET applied_at = CURRENT_TIMESTAMP, operation_id = ? WHERE id = ?",
+                           (operation_id, plan_id))
+
+    # ==================== Conflicts ====================
+
+    def add_conflict(self, source: str, destination: str, reason: str, is_dir: bool = False,
+                     root: Optional[str] = None) -> int:
+        """
+        Remember a move whose destination is taken; an open conflict for the same paths is reused.
+
+        Args:
+            source (str): Path that was to move
+            destination (str): Taken destination
+            reason (str): Why it conflicts
+            is_dir (bool): Whether the source is a folder
+            root (str, optional): Folder the plan was for
+
+        Returns:
+            int: Conflict ID
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT id FROM conflicts WHERE source = ? AND destination = ? AND resolved_at IS NULL",
+                           (source, destination))
+            row = cursor.fetchone()
+            if row:
+                return row['id']
+            cursor.execute(
+                "INSERT INTO conflicts (source, destination, reason, is_dir, root) VALUES (?, ?, ?, ?, ?)",
+                (source, destination, reason, int(is_dir), root)
+            )
+            return cursor.lastrowid
+
+    def get_conflict(self, conflict_id: int) -> Optional[Dict[str, Any]]:
+        """A conflict by ID, or None."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT * FROM conflicts WHERE id = ?", (conflict_id,))
+            row = cursor.fetchone()
+            return dict(row) if row else None
+
+    def list_conflicts(self, limit: int = 100, include_resolved: bool = False) -> List[Dict[str, Any]]:
+        """Conflicts, oldest first (open ones only unless include_resolved)."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            where = '' if include_resolved else 'WHERE resolved_at IS NULL '
+            cursor.execute(f"SELECT * FROM conflicts {where}ORDER BY id LIMIT ?", (limit,))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def mark_conflict_resolved(self, conflict_id: int, resolution: str, result_path: Optional[str] = None) -> None:
+        """Record how a conflict was resolved (and where its source ended up)."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("UPDATE conflicts SET resolved_at = CURRENT_TIMESTAMP, resolution = ?, result_path = ? "
+                           "WHERE id = ?", (resolution, result_path, conflict_id))
+
+    # ==================== AI Usage ====================
+
+    def add_usage(self, run_id: str, run_label: Optional[str], provider: str, model: str,
+                  tokens: int, cost_usd: float, recorded_at: str) -> int:
+        """Record one AI call's tokens and cost."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "INSERT INTO ai_usage (recorded_at, run_id, run_label, provider, model, tokens, cost_usd) "
+                "VALUES (?, ?, ?, ?, ?, ?, ?)",
+                (recorded_at, run_id, run_label, provider, model, tokens, cost_usd)
+            )
+            return cursor.lastrowid
+
+    def usage_totals(self, since: Optional[str] = None, runs: int = 20) -> Dict[str, Any]:
+        """
+        AI usage since a time, in total, per model and per run.
+
+        Args:
+            since (str, optional): Local time 'YYYY-MM-DD HH:MM:SS'; default: all usage
+            runs (int): Newest runs to list
+
+        Returns:
+            Dict: 'calls', 'tokens', 'cost_usd', 'by_model' and 'by_run'
+        """
+        where, params = ("WHERE recorded_at >= ?", (since,)) if since else ("", ())
+        sums = "COUNT(*) AS calls, COALESCE(SUM(tokens), 0) AS tokens, COALESCE(SUM(cost_usd), 0) AS cost_usd"
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(f"SELECT {sums} FROM ai_usage {where}", params)
+            totals = dict(cursor.fetchone())
+            cursor.execute(f"SELECT provider, model, {sums} FROM ai_usage {where} "
+                           f"GROUP BY provider, model ORDER BY cost_usd DESC, tokens DESC", params)
+            totals['by_model'] = [dict(row) for row in cursor.fetchall()]
+            totals['by_run'] = []
+            if runs:
+                cursor.execute(f"SELECT run_id, run_label, MIN(recorded_at) AS started_at, {sums} FROM ai_usage "
+                               f"{where} GROUP BY run_id ORDER BY MAX(id) DESC LIMIT ?", params + (runs,))
+                totals['by_run'] = [dict(row) for row in cursor.fetchall()]
+        totals['cost_usd'] = round(totals['cost_usd'], 6)
+        return totals
+
+    def history_stats(self, filters: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Logged file operations and AI usage, aggregated for the history view.
+
+        Args:
+            filters (Dict): Any of since / until (UTC 'YYYY-MM-DD HH:MM:SS'),
+                            usage_since / usage_until (the same in local time,
+                            for AI usage), category, model, operations (list),
+                            exclude_operations (list) and folder (prefix of the
+                            old or new path)
+
+        Returns:
+            Dict: 'files', 'time_saved_minutes', 'by_day' (local days, oldest
+                  first), 'by_category', 'by_model', 'by_operation', and
+                  'usage' ('calls', 'tokens', 'cost_usd' and 'by_model')
+        """
+        model = "COALESCE(model_name, CASE WHEN ai_suggested THEN 'ai' ELSE 'rules' END)"
+        where, params = [], []
+        for key, condition in (('since', "timestamp >= ?"), ('until', "timestamp <= ?"),
+                               ('category', "category = ? COLLATE NOCASE"), ('model', f"{model} = ?")):
+            if filters.get(key):
+                where.append(condition)
+                params.append(filters[key])
+        for key, operator in (('operations', 'IN'), ('exclude_operations', 'NOT IN')):
+            if filters.get(key):
+                where.append(f"operation {operator} ({', '.join('?' for _ in filters[key])})")
+                params += list(filters[key])
+        if filters.get('folder'):
+            folder = filters['folder'].rstrip('/\\')
+            below = _escape_like(folder + os.sep) + '%'
+            where.append("(old_path = ? OR old_path LIKE ? ESCAPE '\\' "
+                         "OR new_path = ? OR new_path LIKE ? ESCAPE '\\')")
+            params += [folder, below, folder, below]
+        clause = f"WHERE {' AND '.join(where)}" if where else ""
+        usage_where, usage_params = [], []
+        for key, condition in (('usage_since', "recorded_at >= ?"), ('usage_until', "recorded_at <= ?"),
+                               ('model', "model = ?")):
+            if filters.get(key):
+                usage_where.append(condition)
+                usage_params.append(filters[key])
+        usage_clause = f"WHERE {' AND '.join(usage_where)}" if usage_where else ""
+        sums = "COUNT(*) AS calls, COALESCE(SUM(tokens), 0) AS tokens, COALESCE(SUM(cost_usd), 0) AS cost_usd"
+
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(f"SELECT COUNT(*) AS files, COALESCE(SUM(time_saved), 0) AS time_saved_minutes "
+                           f"FROM files_log {clause}", params)
+            stats = dict(cursor.fetchone())
+            for name, column in (('by_day', "date(timestamp, 'localtime')"), ('by_category', "category"),
+                                 ('by_model', model), ('by_operation', "operation")):
+                label = name[3:]
+                order = f"{label} ASC" if name == 'by_day' else f"files DESC, {label} ASC"
+                cursor.execute(f"SELECT {column} AS {label}, COUNT(*) AS files FROM files_log {clause} "
+                               f"GROUP BY {label} ORDER BY {order}", params)
+                stats[name] = [dict(row) for row in cursor.fetchall()]
+            cursor.execute(f"SELECT {sums} FROM ai_usage {usage_clause}", usage_params)
+            stats['usage'] = dict(cursor.fetchone())
+            cursor.execute(f"SELECT provider, model, {sums} FROM ai_usage {usage_clause} "
+                           f"GROUP BY provider, model ORDER BY cost_usd DESC, tokens DESC", usage_params)
+            stats['usage']['by_model'] = [dict(row) for row in cursor.fetchall()]
+        stats['usage']['cost_usd'] = round(stats['usage']['cost_usd'], 6)
+        return stats
+
+    # ==================== Search Index ====================
+
+    def _require_search(self) -> None:
+        """Raise RuntimeError(SEARCH_UNAVAILABLE) if there is no full-text index."""
+        if not self.search_available:
+            raise RuntimeError(SEARCH_UNAVAILABLE)
+
+    def upsert_search_document(self, document: Dict[str, Any], text: str, folders: str) -> int:
+        """
+        Add a file to the search index, or replace its entry.
+
+        Args:
+            document (Dict): path, name, folder, extension, size, mtime, year,
+                             category, tags (list) and summary
+            text (str): Extracted text
+            folders (str): Names of the folders above it, for matching
+
+        Returns:
+            int: The document's ID
+
+        Raises:
+            RuntimeError: If full-text search is unavailable
+        """
+        self._require_search()
+        tags = document.get('tags') or []
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT id FROM search_documents WHERE path = ?", (document['path'],))
+            row = cursor.fetchone()
+            if row is not None:
+                cursor.execute("DELETE FROM search_fts WHERE rowid = ?", (row['id'],))
+                cursor.execute("DELETE FROM search_documents WHERE id = ?", (row['id'],))
+            cursor.execute(
+                "INSERT INTO search_documents (path, name, folder, extension, size, mtime, year, category, tags, summary) "
+                "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                (document['path'], document['name'], document['folder'], document.get('extension'),
+                 document.get('size'), document.get('mtime'), document.get('year'), document.get('category'),
+                 json.dumps(tags), document.get('summary'))
+            )
+            document_id = cursor.lastrowid
+            cursor.execute("INSERT INTO search_fts (rowid, name, folders, text, tags, summary) VALUES (?, ?, ?, ?, ?, ?)",
+                           (document_id, document['name'], folders, text or '', ' '.join(tags),
+                            document.get('summary') or ''))
+            conn.commit()
+            return document_id
+
+    def delete_search_documents(self, path: str, below: bool = False) -> int:
+        """Remove a file (or, with below, everything under a folder) from the search index."""
+        self._require_search()
+        where, params = ("path = ? OR path LIKE ? ESCAPE '\\'",
+                         (path, _escape_like(path.rstrip('/\\') + os.sep) + '%')) if below else ("path = ?", (path,))
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(f"SELECT id FROM search_documents WHERE {where}", params)
+            ids = [row['id'] for row in cursor.fetchall()]
+            cursor.executemany("DELETE FROM search_fts WHERE rowid = ?", [(i,) for i in ids])
+            cursor.executemany("DELETE FROM search_documents WHERE id = ?", [(i,) for i in ids])
+            conn.commit()
+            return len(ids)
+
+    def search_document_stamps(self, folder: str) -> Dict[str, Tuple[int, float]]:
+        """Path -> (size, mtime) of the indexed files under a folder."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT path, size, mtime FROM search_documents WHERE path LIKE ? ESCAPE '\\'",
+                           (_escape_like(folder.rstrip('/\\') + os.sep) + '%',))
+            return {row['path']: (row['size'], row['mtime']) for row in cursor.fetchall()}
+
+    def search_documents(self, match: Optional[str], filters: Dict[str, Any], limit: int = 50,
+                         year_match: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Indexed files matching a full-text query and filters, best first.
+
+        Args:
+            match (str, optional): FTS5 query; None matches every file
+            filters (Dict): Any of extensions (list), category, folder (prefix), year,
+                            modified_after / modified_before (timestamps),
+                            min_size, max_size and tag
+            limit (int): Most results to return
+            year_match (str, optional): FTS5 query a file may match instead of the year filter
+
+        Returns:
+            Dict: 'total' and 'results' (rows with 'snippet' and 'score')
+
+        Raises:
+            RuntimeError: If full-text search is unavailable
+        """
+        self._require_search()
+        where, params = [], []
+        if match:
+            where.append("search_fts MATCH ?")
+            params.append(match)
+        if filters.get('extensions'):
+            where.append(f"d.extension IN ({', '.join('?' for _ in filters['extensions'])})")
+            params += list(filters['extensions'])
+        if filters.get('category'):
+            where.append("d.category = ? COLLATE NOCASE")
+            params.append(filters['category'])
+        if filters.get('folder'):
+            where.append("(d.folder = ? OR d.folder LIKE ? ESCAPE '\\')")
+            params += [filters['folder'], _escape_like(filters['folder'].rstrip('/\\') + os.sep) + '%']
+        if filters.get('year'):
+            if year_match:
+                where.append("(d.year = ? OR d.id IN (SELECT rowid FROM search_fts WHERE search_fts MATCH ?))")
+                params += [int(filters['year']), year_match]
+            else:
+                where.append("d.year = ?")
+                params.append(int(filters['year']))
+        for key, condition in (('modified_after', "d.mtime >= ?"), ('modified_before', "d.mtime < ?"),
+                               ('min_size', "d.size >= ?"), ('max_size', "d.size <= ?")):
+            if filters.get(key) is not None:
+                where.append(condition)
+                params.append(filters[key])
+        if filters.get('tag'):
+            where.append("EXISTS (SELECT 1 FROM json_each(d.tags) WHERE json_each.value = ? COLLATE NOCASE)")
+            params.append(filters['tag'])
+
+        clause = f"WHERE {' AND '.join(where)}" if where else ""
+        # Name matches count most, then tags, summary, folder names and text
+        score = "bm25(search_fts, 10.0, 2.0, 1.0, 5.0, 3.0)" if match else "0.0"
+        snippet = "snippet(search_fts, 2, '[', ']', '…', 12)" if match else "NULL"
+        order = "score, d.mtime DESC" if match else "d.mtime DESC"
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            source = "search_documents d JOIN search_fts ON search_fts.rowid = d.id"
+            cursor.execute(f"SELECT COUNT(*) AS total FROM {source} {clause}", params)
+            total = cursor.fetchone()['total']
+            cursor.execute(f"SELECT d.*, {snippet} AS snippet, {score} AS score FROM {source} {clause} "
+                           f"ORDER BY {order} LIMIT ?", params + [limit])
+            rows = [dict(row) for row in cursor.fetchall()]
+        for row in rows:
+            row['tags'] = json.loads(row['tags'] or '[]')
+        return {'total': total, 'results': rows}
+
+    def search_index_counts(self) -> Dict[str, Any]:
+        """Files in the search index, in total and per top category."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT COUNT(*) AS files, COALESCE(SUM(size), 0) AS size, MAX(indexed_at) AS updated_at "
+                           "FROM search_documents")
+            counts = dict(cursor.fetchone())
+            cursor.execute("SELECT category, COUNT(*) AS files FROM search_documents "
+                           "GROUP BY category ORDER BY files DESC LIMIT 20")
+            counts['categories'] = {row['category'] or 'Unknown': row['files'] for row in cursor.fetchall()}
+            return counts
+
+    # ==================== File Tags ====================
+
+    def set_file_tags(self, path: str, tags: List[str], source: str = 'user', replace: bool = True) -> None:
+        """
+        Store the tags of a file.
+
+        Args:
+            path (str): Normalized file path
+            tags (List[str]): Tags (compared without case)
+            source (str): 'user', 'ai' or 'file'
+            replace (bool): Drop the file's other tags (False: add to them, keeping their source)
+        """
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            if replace:
+                cursor.execute("DELETE FROM file_tags WHERE path = ?", (path,))
+            cursor.executemany("INSERT OR IGNORE INTO file_tags (path, tag, source) VALUES (?, ?, ?)",
+                               [(path, tag, source) for tag in tags])
+            conn.commit()
+
+    def get_file_tags(self, path: str) -> List[Dict[str, Any]]:
+        """A file's tags with their source, in the order they were added."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT tag, source, added_at FROM file_tags WHERE path = ? ORDER BY rowid", (path,))
+            return [dict(row) for row in cursor.fetchall()]
+
+    def find_files_by_tag(self, tag: str, limit: int = 1000) -> List[Dict[str, Any]]:
+        """Files with a tag (any case), newest first, each with all its tags."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute(
+                "SELECT t.path, t.added_at, (SELECT GROUP_CONCAT(tag, char(31)) FROM "
+                "(SELECT tag FROM file_tags a WHERE a.path = t.path ORDER BY a.rowid)) AS tags "
+                "FROM file_tags t WHERE t.tag = ? ORDER BY t.added_at DESC, t.path LIMIT ?",
+                (tag, limit)
+            )
+            return [{'path': row['path'], 'tags': row['tags'].split('\x1f'), 'added_at': row['added_at']}
+                    for row in cursor.fetchall()]
+
+    def file_tag_counts(self) -> List[Dict[str, Any]]:
+        """Every tag in use with how many files have it, most used first."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("SELECT tag, COUNT(*) AS files FROM file_tags GROUP BY tag ORDER BY files DESC, tag")
+            return [dict(row) for row in cursor.fetchall()]
+
+    def move_file_tags(self, old_path: str, new_path: str) -> int:
+        """Carry a file's tags to its new path; returns the tags moved."""
+        with self.get_connection() as conn:
+            cursor = conn.cursor()
+            cursor.execute("DELETE FROM file_tags WHERE path = ? AND tag IN (SELECT tag FROM file_tags WHERE path = ?)",
+                           (new_path, old_path))
+            cursor.execute("UPDATE file_tags SET path = ? WHERE path = ?", (new_path, old_path))
+            conn.commit()
+            return cursor.rowcount
+
     def cleanup(self) -> None:
         """
         Clean up resources and close connection pool.
@@ -0,0 +1,328 @@
+"""
+Dedupe Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module finds duplicate files quickly and resolves them in one
+undoable operation.
+
+Finding only reads what it must: files are grouped by size first (a file
+with a unique size has no duplicate), then by a hash of their first
+HEAD_BYTES, and only files still alike are hashed in full, several at a
+time. Each group suggests which copy to keep (see
+DuplicateFinder.suggest_duplicates_to_keep):
+
+    {"hash", "algorithm", "size", "count", "paths", "total_wasted_space",
+     "keep", "delete", "reason"}
+
+Resolving a group (keep and delete may be changed first) hashes both files
+again, so a file changed since the scan is left alone, then for each
+copy to delete:
+    - trash:    moves it to the trash holding directory
+    - move:     moves it into a chosen folder
+    - hardlink: moves it to the trash and links its path to the kept copy,
+                so every path still works but the data is stored once
+Protected (application) files are skipped. `aifo undo` puts it all back.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import shutil
+from collections import defaultdict
+from concurrent.futures import ThreadPoolExecutor
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, Dict, Any, List, Optional, Tuple
+
+from .duplicates import DuplicateFinder
+from .fsops import free_name
+from .hashing import DEFAULT_ALGORITHM, hash_file, new_hasher
+from .journal import OperationJournal, trash_root
+from .safety_guardian import SafetyGuardian
+
+logger = logging.getLogger(__name__)
+
+
+RESOLVE_MODES = ('trash', 'move', 'hardlink')
+HEAD_BYTES = 64 * 1024
+DEFAULT_WORKERS = 4
+
+
+class Deduplicator:
+    """
+    Finds duplicate files and resolves them.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager (for the operation journal)
+        journal (OperationJournal): Records resolutions for undo
+    """
+
+    def __init__(self, config, db_manager, journal: Optional[OperationJournal] = None):
+        """
+        Initialize deduplicator.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+            journal (OperationJournal, optional): Journal to record resolutions in
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.journal = journal or OperationJournal(db_manager)
+        self._finder = DuplicateFinder(config, db_manager)
+        self._guardian = SafetyGuardian(config)
+
+    # ==================== Finding ====================
+
+    def find_duplicates(self, folder: str, algorithm: str = DEFAULT_ALGORITHM, recursive: bool = True,
+                        min_size: int = 1, include_hidden: bool = False, max_workers: int = DEFAULT_WORKERS,
+                        on_progress: Optional[Callable[[str, int, int], None]] = None) -> Dict[str, Any]:
+        """
+        Find groups of identical files in a folder.
+
+        Args:
+            folder (str): Folder to scan
+            algorithm (str): Hash algorithm (one of hashing.ALGORITHMS)
+            recursive (bool): Include subfolders
+            min_size (int): Ignore files smaller than this (bytes; empty files are always ignored)
+            include_hidden (bool): Include dot files and folders
+            max_workers (int): Files hashed at once
+            on_progress (Callable, optional): Called as (phase, done, total) while hashing
+
+        Returns:
+            Dict: 'success', 'message', 'groups' (largest waste first), 'scanned',
+                  'hashed' (files read in full), 'errors' and 'summary'
+
+        Raises:
+            ValueError: If the algorithm is unknown or not installed
+        """
+        new_hasher(algorithm)
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {folder}", 'groups': [],
+                    'scanned': 0, 'hashed': 0, 'errors': [], 'summary': None}
+
+        by_size: Dict[int, List[str]] = defaultdict(list)
+        errors: List[Dict[str, str]] = []
+        scanned = 0
+        for path, size in self._walk(root, recursive, include_hidden, errors):
+            scanned += 1
+            if size >= max(1, min_size):
+                by_size[size].append(path)
+        candidates = [(path, size) for size, paths in by_size.items() if len(paths) > 1 for path in paths]
+
+        # Files larger than the head are told apart by their head before reading them whole
+        heads = self._hash_all([path for path, size in candidates if size > HEAD_BYTES], algorithm,
+                               HEAD_BYTES, max_workers, errors, 'head', on_progress)
+        alike: Dict[Tuple, List[Tuple[str, int]]] = defaultdict(list)
+        for path, size in candidates:
+            if size <= HEAD_BYTES or path in heads:
+                alike[(size, heads.get(path))].append((path, size))
+        to_hash = [(path, size) for files in alike.values() if len(files) > 1 for path, size in files]
+
+        digests = self._hash_all([path for path, _ in to_hash], algorithm, None, max_workers, errors,
+                                 'full', on_progress)
+        by_hash: Dict[Tuple[int, str], List[str]] = defaultdict(list)
+        for path, size in to_hash:
+            if path in digests:
+                by_hash[(size, digests[path])].append(path)
+
+        groups = []
+        for (size, digest), paths in by_hash.items():
+            if len(paths) < 2:
+                continue
+            paths.sort()
+            group = {
+                'hash': digest,
+                'algorithm': algorithm,
+                'size': size,
+                'count': len(paths),
+                'paths': paths,
+                'total_wasted_space': size * (len(paths) - 1)
+            }
+            group.update(self._finder.suggest_duplicates_to_keep(group))
+            groups.append(group)
+        groups.sort(key=lambda group: group['total_wasted_space'], reverse=True)
+
+        summary = self._finder.get_duplicate_summary(groups)
+        logger.info(f"Dedupe {root}: {scanned} files, {len(to_hash)} hashed, {len(groups)} duplicate groups")
+        return {
+            'success': True,
+            'message': f"Found {len(groups)} group(s) of duplicates "
+                       f"({summary['total_wasted_space_mb']} MB could be freed)",
+            'groups': groups,
+            'scanned': scanned,
+            'hashed': len(to_hash),
+            'errors': errors,
+            'summary': summary
+        }
+
+    def _walk(self, root: Path, recursive: bool, include_hidden: bool, errors: List[Dict[str, str]]):
+        """Regular files (not symlinks) under root, as (path, size)."""
+        stack = [root]
+        while stack:
+            folder = stack.pop()
+            try:
+                entries = list(os.scandir(folder))
+            except OSError as e:
+                errors.append({'path': str(folder), 'error': str(e)})
+                continue
+            for entry in entries:
+                if not include_hidden and entry.name.startswith('.'):
+                    continue
+                try:
+                    if entry.is_symlink():
+                        continue
+                    if entry.is_dir():
+                        if recursive:
+                            stack.append(Path(entry.path))
+                    elif entry.is_file():
+                        yield entry.path, entry.stat().st_size
+                except OSError as e:
+                    errors.append({'path': entry.path, 'error': str(e)})
+
+    def _hash_all(self, paths: List[str], algorithm: str, limit: Optional[int], max_workers: int,
+                  errors: List[Dict[str, str]], phase: str,
+                  on_progress: Optional[Callable[[str, int, int], None]]) -> Dict[str, str]:
+        """Hash files in parallel; unreadable files are recorded in errors and left out."""
+        digests: Dict[str, str] = {}
+        if not paths:
+            return digests
+
+        def run(path: str):
+            try:
+                return path, hash_file(path, algorithm, limit), None
+            except OSError as e:
+                return path, None, str(e)
+
+        with ThreadPoolExecutor(max_workers=max(1, int(max_workers)), thread_name_prefix='dedupe') as pool:
+            for done, (path, digest, error) in enumerate(pool.map(run, paths), 1):
+                if digest is None:
+                    errors.append({'path': path, 'error': error})
+                else:
+                    digests[path] = digest
+                if on_progress is not None:
+                    on_progress(phase, done, len(paths))
+        return digests
+
+    # ==================== Resolving ====================
+
+    def resolve_duplicates(self, groups: List[Dict[str, Any]], mode: str = 'trash',
+                           destination: Optional[str] = None, dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Remove the extra copies of duplicate groups, as one undoable operation.
+
+        Args:
+            groups (List[Dict]): Groups with 'keep' and 'delete' (and 'algorithm')
+            mode (str): 'trash', 'move' (into destination) or 'hardlink'
+            destination (str, optional): Folder for mode 'move'
+            dry_run (bool): Only check and report what would be done
+
+        Returns:
+            Dict: 'success', 'message', 'operation_id', 'resolved' (list of
+                  {path, kept, to}), 'skipped' (list of {path, reason}),
+                  'space_freed' and 'dry_run'
+        """
+        if mode not in RESOLVE_MODES:
+            return self._resolved(False, f"Unknown mode: {mode} (use {', '.join(RESOLVE_MODES)})", dry_run)
+        if mode == 'move':
+            if not destination:
+                return self._resolved(False, "Mode 'move' needs a destination folder", dry_run)
+            destination = str(Path(destination).expanduser())
+        holding = trash_root(self.config) / f"duplicates_{datetime.now().strftime('%Y%m%d_%H%M%S_%f')}"
+
+        resolved: List[Dict[str, Any]] = []
+        skipped: List[Dict[str, str]] = []
+        kept_hashes: Dict[str, str] = {}
+        operation_id = None
+        space_freed = 0
+        for group in groups:
+            keep = group.get('keep')
+            algorithm = group.get('algorithm') or DEFAULT_ALGORITHM
+            for path in group.get('delete') or []:
+                reason = self._check(keep, path, algorithm, mode, kept_hashes)
+                if reason:
+                    skipped.append({'path': path, 'reason': reason})
+                    continue
+                target = (Path(destination) / Path(path).name if mode == 'move'
+                          else holding / self._relative_name(path))
+                if dry_run:
+                    resolved.append({'path': path, 'kept': keep, 'to': str(target)})
+                    space_freed += group.get('size', 0)
+                    continue
+                try:
+                    if operation_id is None:
+                        operation_id = self.journal.begin('dedupe', f"Resolve duplicates ({mode})")
+                    self._apply(operation_id, keep, path, target, mode)
+                except (OSError, shutil.Error) as e:
+                    skipped.append({'path': path, 'reason': str(e)})
+                    continue
+                resolved.append({'path': path, 'kept': keep, 'to': str(target) if mode != 'hardlink' else keep})
+                space_freed += group.get('size', 0)
+
+        if operation_id is not None:
+            self.journal.complete(operation_id)
+        verb = ({'trash': 'Would trash', 'move': 'Would move', 'hardlink': 'Would link'} if dry_run
+                else {'trash': 'Trashed', 'move': 'Moved', 'hardlink': 'Linked'})[mode]
+        message = f"{verb} {len(resolved)} duplicate(s)"
+        if skipped:
+            message += f", skipped {len(skipped)}"
+        result = self._resolved(bool(resolved) or not skipped, message, dry_run)
+        result.update({'operation_id': operation_id, 'resolved': resolved, 'skipped': skipped,
+                       'space_freed': space_freed, 'mode': mode})
+        return result
+
+    def _check(self, keep: Optional[str], path: str, algorithm: str, mode: str,
+               kept_hashes: Dict[str, str]) -> Optional[str]:
+        """Why a copy must not be resolved, or None."""
+        if not keep or os.path.abspath(keep) == os.path.abspath(path):
+            return 'no other copy is kept'
+        try:
+            keep_stat, path_stat = os.stat(keep), os.lstat(path)
+        except OSError as e:
+            return f"gone since the scan ({e.strerror or e})"
+        if os.path.samestat(keep_stat, path_stat):
+            return 'already the same file (hard link)'
+        if mode == 'hardlink' and keep_stat.st_dev != path_stat.st_dev:
+            return 'on another drive than the kept copy (hard links stay on one drive)'
+        is_safe, reason = self._guardian.is_file_safe_to_modify(Path(path))
+        if not is_safe:
+            return reason
+        try:
+            if keep_stat.st_size != path_stat.st_size:
+                return 'changed since the scan'
+            if keep not in kept_hashes:
+                kept_hashes[keep] = hash_file(keep, algorithm)
+            if kept_hashes[keep] != hash_file(path, algorithm):
+                return 'changed since the scan'
+        except OSError as e:
+            return str(e)
+        return None
+
+    def _apply(self, operation_id: int, keep: str, path: str, target: Path, mode: str) -> None:
+        target = free_name(target)
+        target.parent.mkdir(parents=True, exist_ok=True)
+        shutil.move(path, str(target))
+        self.journal.record_step(operation_id, 'move', path, str(target), {'role': 'duplicate', 'kept': keep})
+        if mode == 'hardlink':
+            os.link(keep, path)
+            self.journal.record_step(operation_id, 'create', None, path, {'hardlink_to': keep})
+
+    @staticmethod
+    def _relative_name(path: str) -> str:
+        """Where a copy goes inside the holding folder: its path without the drive or root."""
+        drive, rest = os.path.splitdrive(os.path.abspath(path))
+        return os.path.join(drive.strip(':\\/') or '', rest.lstrip('\\/'))
+
+    @staticmethod
+    def _resolved(success: bool, message: str, dry_run: bool) -> Dict[str, Any]:
+        return {'success': success, 'message': message, 'operation_id': None, 'resolved': [],
+                'skipped': [], 'space_freed': 0, 'dry_run': dry_run}
@@ -0,0 +1,210 @@
+"""
+Diagnostics Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module builds a diagnostics bundle: one zip the user can attach to a
+bug report, holding
+
+    manifest.json      what is in the bundle and when it was made
+    environment.json   app, Python, OS and dependency versions
+    settings.json      config.json with secrets removed
+    jobs.json          recent operations from the journal
+    crash_reports.json summaries of saved crash reports
+    logs/              the app's log files (large ones trimmed to their end)
+
+Nothing is sent anywhere; the user decides what to do with the file.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+import platform
+import sys
+import zipfile
+from datetime import datetime
+from importlib import metadata
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .config_bundle import redact_secrets
+from .crash_reports import CrashReporter
+
+logger = logging.getLogger(__name__)
+
+
+BUNDLE_VERSION = 1
+MAX_LOG_BYTES = 5 * 1024 * 1024  # Per log file; the end is kept
+RECENT_JOBS = 100
+
+# Distributions whose versions help reproduce a problem
+DEPENDENCIES = ('fastapi', 'uvicorn', 'pydantic', 'click', 'typer', 'requests', 'PyYAML',
+                'watchfiles', 'watchdog', 'filelock', 'Pillow', 'PyPDF2', 'python-docx')
+
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+
+
+class DiagnosticsExporter:
+    """
+    Collects diagnostics into a zip bundle.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager (for recent jobs), may be None
+        log_dirs (List[Path]): Folders whose *.log files are included
+    """
+
+    def __init__(self, config, db_manager=None, log_dirs: Optional[List[str]] = None):
+        """
+        Initialize diagnostics exporter.
+
+        Args:
+            config: Configuration object
+            db_manager: Optional DatabaseManager; without one no jobs are included
+            log_dirs (List[str], optional): Log folders (default: logs/ and the
+                                            folder of logging.file)
+        """
+        self.config = config
+        self.db_manager = db_manager
+        if log_dirs is None:
+            dirs = [_APP_ROOT / 'logs']
+            settings = getattr(config, 'logging_settings', None)
+            if isinstance(settings, dict) and settings.get('file'):
+                log_file = Path(settings['file']).expanduser()
+                dirs.append((log_file if log_file.is_absolute() else _APP_ROOT / log_file).parent)
+            self.log_dirs = list(dict.fromkeys(dirs))
+        else:
+            self.log_dirs = [Path(d).expanduser() for d in log_dirs]
+
+    def export(self, output_path: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Write the diagnostics bundle.
+
+        Args:
+            output_path (str, optional): Zip to write (default:
+                                         ~/Downloads/aifo-diagnostics-<time>.zip)
+
+        Returns:
+            Dict: Result with 'success', 'message', 'path' and 'files'
+        """
+        path = Path(output_path).expanduser() if output_path else self._default_path()
+        files: List[str] = []
+        try:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            with zipfile.ZipFile(path, 'w', compression=zipfile.ZIP_DEFLATED) as bundle:
+                for name, builder in (('environment.json', self.environment),
+                                      ('settings.json', self.settings),
+                                      ('jobs.json', self.recent_jobs),
+                                      ('crash_reports.json', self.crash_reports)):
+                    try:
+                        content = builder()
+                    except Exception as e:
+                        # A broken part should not cost the user the whole bundle
+                        logger.warning(f"Diagnostics: skipping {name}: {e}")
+                        content = {'error': str(e)}
+                    bundle.writestr(name, json.dumps(content, indent=2, default=str, ensure_ascii=False))
+                    files.append(name)
+
+                for log_file in self._log_files():
+                    arcname = f"logs/{log_file.name}"
+                    if arcname in files:
+                        continue
+                    bundle.writestr(arcname, self._read_log(log_file))
+                    files.append(arcname)
+
+                manifest = {
+                    'version': BUNDLE_VERSION,
+                    'created_at': datetime.now().isoformat(timespec='seconds'),
+                    'files': files
+                }
+                bundle.writestr('manifest.json', json.dumps(manifest, indent=2))
+        except OSError as e:
+            return {'success': False, 'message': f"Could not write diagnostics: {e}", 'path': str(path), 'files': []}
+
+        return {
+            'success': True,
+            'message': f"Diagnostics saved to {path}; attach it to your bug report",
+            'path': str(path),
+            'files': ['manifest.json'] + files
+        }
+
+    # ==================== Sections ====================
+
+    def environment(self) -> Dict[str, Any]:
+        """App, Python, OS and dependency versions."""
+        from src import __version__
+
+        versions = {}
+        for name in DEPENDENCIES:
+            try:
+                versions[name] = metadata.version(name)
+            except metadata.PackageNotFoundError:
+                versions[name] = None
+        return {
+            'app_version': __version__,
+            'python': {
+                'version': platform.python_version(),
+                'implementation': platform.python_implementation(),
+                'executable': sys.executable
+            },
+            'platform': {
+                'system': platform.system(),
+                'release': platform.release(),
+                'version': platform.version(),
+                'machine': platform.machine()
+            },
+            'cpu_count': os.cpu_count(),
+            'frozen': bool(getattr(sys, 'frozen', False)),
+            'dependencies': versions,
+            'ai_backend': {
+                'base_url': getattr(self.config, 'ollama_base_url', None),
+                'model': getattr(self.config, 'ollama_model', None)
+            }
+        }
+
+    def settings(self) -> Dict[str, Any]:
+        """The configuration with API keys, tokens, passwords and license keys removed."""
+        return redact_secrets(self.config.as_dict())
+
+    def recent_jobs(self) -> List[Dict[str, Any]]:
+        """Recent journal operations, newest first."""
+        if self.db_manager is None:
+            return []
+        return self.db_manager.list_operations(RECENT_JOBS)
+
+    def crash_reports(self) -> List[Dict[str, Any]]:
+        """Summaries of saved (already sanitized) crash reports."""
+        return CrashReporter(self.config).list_reports()
+
+    # ==================== Logs ====================
+
+    def _log_files(self) -> List[Path]:
+        found = []
+        for log_dir in self.log_dirs:
+            if log_dir.is_dir():
+                # *.log plus rotated copies (app.log.1, ...)
+                found.extend(sorted(p for p in log_dir.iterdir() if p.is_file() and '.log' in p.name))
+        return found
+
+    @staticmethod
+    def _read_log(path: Path) -> bytes:
+        with open(path, 'rb') as f:
+            size = f.seek(0, os.SEEK_END)
+            if size <= MAX_LOG_BYTES:
+                f.seek(0)
+                return f.read()
+            f.seek(size - MAX_LOG_BYTES)
+            return f"[trimmed: first {size - MAX_LOG_BYTES} bytes omitted]\n".encode('utf-8') + f.read()
+
+    @staticmethod
+    def _default_path() -> Path:
+        downloads = Path.home() / 'Downloads'
+        folder = downloads if downloads.is_dir() else Path.home()
+        return folder / f"aifo-diagnostics-{datetime.now():%Y%m%d-%H%M%S}.zip"
@@ -0,0 +1,229 @@
+"""
+Disk Usage Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module measures what takes up space in a folder before it is
+organized, as a tree a treemap view can draw directly:
+
+    {"name": "Downloads", "path": ..., "size": 52428800, "files": 120, "dirs": 4,
+     "children": [{"name": "Installers", ...}, {"name": "movie.mkv", "type": "file", ...},
+                  {"name": "(37 smaller files)", "type": "files", ...}]}
+
+Folders below `depth` levels are summed but not broken down; each
+folder lists its largest files (MAX_FILES_PER_FOLDER) and one entry for
+the rest. Totals per category (from the destination rules, as the
+classifier files them) and per extension come with the tree.
+
+The subfolders of the top folder are walked in parallel; hard links are
+counted once, symlinks are not followed, and nothing is skipped for the
+ignore rules - node_modules is exactly what this should find.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import threading
+import time
+from collections import defaultdict
+from concurrent.futures import ThreadPoolExecutor
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Set, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_DEPTH = 3
+MAX_DEPTH = 10
+DEFAULT_WORKERS = 8
+MAX_FILES_PER_FOLDER = 20   # Files shown per folder; the rest are one entry
+TOP_EXTENSIONS = 30
+LARGEST_FILES = 20
+
+
+class _Walk:
+    """State shared by the walkers of one analysis."""
+
+    def __init__(self, categories: Dict[str, str]):
+        self.categories = categories
+        self.lock = threading.Lock()
+        self.seen: Set[Tuple[int, int]] = set()
+        self.by_category: Dict[str, List[int]] = defaultdict(lambda: [0, 0])   # category -> [size, files]
+        self.by_extension: Dict[str, List[int]] = defaultdict(lambda: [0, 0])
+        self.largest: List[Tuple[int, str]] = []
+        self.skipped: List[Dict[str, str]] = []
+
+    def count(self, path: str, name: str, stat: os.stat_result) -> bool:
+        """Record a file in the totals; False for another link to a file already counted."""
+        extension = os.path.splitext(name)[1].lower().lstrip('.')
+        category = self.categories.get(extension, 'Other')
+        with self.lock:
+            if stat.st_nlink > 1:
+                key = (stat.st_dev, stat.st_ino)
+                if key in self.seen:
+                    return False
+                self.seen.add(key)
+            for totals, key in ((self.by_category, category), (self.by_extension, extension or '(none)')):
+                totals[key][0] += stat.st_size
+                totals[key][1] += 1
+            self.largest.append((stat.st_size, path))
+            if len(self.largest) > LARGEST_FILES * 4:
+                self.largest = sorted(self.largest, reverse=True)[:LARGEST_FILES]
+        return True
+
+
+def categories_by_extension(config) -> Dict[str, str]:
+    """Extension -> category, the first folder of its destination rule."""
+    rules = getattr(config, 'destination_rules', None) if config is not None else None
+    if not isinstance(rules, dict):
+        return {}
+    return {str(extension).lower().lstrip('.'): str(path).strip('/').split('/')[0] or 'Other'
+            for extension, path in rules.items()}
+
+
+def _node(path: Path, kind: str = 'folder') -> Dict[str, Any]:
+    return {'name': path.name or str(path), 'path': str(path), 'type': kind, 'size': 0, 'files': 0, 'dirs': 0}
+
+
+def _walk_folder(folder: Path, walk: _Walk, levels: int) -> Dict[str, Any]:
+    """
+    Measure a folder; `levels` more levels of subfolders get children.
+
+    Folders are entered one after another here; parallelism is per
+    top-level subfolder (see analyze_disk_usage).
+    """
+    node = _node(folder)
+    files: List[Tuple[int, str]] = []
+    subfolders: List[Path] = []
+    try:
+        with os.scandir(folder) as scan:
+            entries = list(scan)
+    except OSError as e:
+        with walk.lock:
+            walk.skipped.append({'path': str(folder), 'reason': e.strerror or str(e)})
+        return node
+
+    for entry in entries:
+        try:
+            if entry.is_dir(follow_symlinks=False):
+                subfolders.append(Path(entry.path))
+                continue
+            if not entry.is_file(follow_symlinks=False):
+                continue  # Symlinks, sockets, devices
+            stat = entry.stat(follow_symlinks=False)
+        except OSError:
+            continue
+        if walk.count(entry.path, entry.name, stat):
+            node['size'] += stat.st_size
+            node['files'] += 1
+            files.append((stat.st_size, entry.name))
+
+    children = []
+    for subfolder in subfolders:
+        child = _walk_folder(subfolder, walk, levels - 1)
+        _add(node, child)
+        children.append(child)
+    if levels > 0:
+        node['children'] = _children(folder, children, files)
+    return node
+
+
+def _add(node: Dict[str, Any], child: Dict[str, Any]) -> None:
+    node['size'] += child['size']
+    node['files'] += child['files']
+    node['dirs'] += child['dirs'] + 1
+
+
+def _children(folder: Path, folders: List[Dict[str, Any]], files: List[Tuple[int, str]]) -> List[Dict[str, Any]]:
+    """A folder's subfolders, its largest files and one entry for its other files, largest first."""
+    children = list(folders)
+    files.sort(reverse=True)
+    for size, name in files[:MAX_FILES_PER_FOLDER]:
+        entry = _node(folder / name, 'file')
+        entry.update(size=size, files=1)
+        children.append(entry)
+    rest = files[MAX_FILES_PER_FOLDER:]
+    if rest:
+        children.append({'name': f"({len(rest)} smaller files)", 'path': str(folder), 'type': 'files',
+                         'size': sum(size for size, _ in rest), 'files': len(rest), 'dirs': 0})
+    return sorted(children, key=lambda child: child['size'], reverse=True)
+
+
+def _totals(totals: Dict[str, List[int]], key: str, limit: Optional[int] = None) -> List[Dict[str, Any]]:
+    ranked = sorted(totals.items(), key=lambda item: item[1][0], reverse=True)
+    return [{key: name, 'size': size, 'files': files} for name, (size, files) in ranked[:limit]]
+
+
+def analyze_disk_usage(folder: str, depth: int = DEFAULT_DEPTH, config=None,
+                       workers: int = DEFAULT_WORKERS) -> Dict[str, Any]:
+    """
+    Measure a folder's disk usage as a tree, with totals per category and extension.
+
+    Args:
+        folder (str): Folder to analyze
+        depth (int): Levels of subfolders broken down in the tree (1 to MAX_DEPTH);
+                     deeper folders are still counted in their parents
+        config: Configuration object (destination_rules give the categories)
+        workers (int): Top-level subfolders walked at once
+
+    Returns:
+        Dict: 'root', 'size', 'files', 'dirs', 'tree' (see the module docstring),
+              'by_category', 'by_extension' (top TOP_EXTENSIONS), 'largest_files',
+              'skipped' (unreadable folders) and 'seconds'
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+    """
+    root = Path(folder).expanduser().resolve()
+    if not root.is_dir():
+        raise NotADirectoryError(f"Not a folder: {root}")
+    depth = max(1, min(int(depth), MAX_DEPTH))
+    started = time.perf_counter()
+    walk = _Walk(categories_by_extension(config))
+
+    # The top folder's own files, then its subfolders in parallel
+    tree = _node(root)
+    files: List[Tuple[int, str]] = []
+    subfolders: List[Path] = []
+    with os.scandir(root) as scan:
+        entries = list(scan)
+    for entry in entries:
+        try:
+            if entry.is_dir(follow_symlinks=False):
+                subfolders.append(Path(entry.path))
+            elif entry.is_file(follow_symlinks=False):
+                stat = entry.stat(follow_symlinks=False)
+                if walk.count(entry.path, entry.name, stat):
+                    tree['size'] += stat.st_size
+                    tree['files'] += 1
+                    files.append((stat.st_size, entry.name))
+        except OSError:
+            continue
+
+    with ThreadPoolExecutor(max_workers=max(1, min(int(workers), 32)), thread_name_prefix='disk-usage') as pool:
+        children = list(pool.map(lambda subfolder: _walk_folder(subfolder, walk, depth - 1), subfolders))
+    for child in children:
+        _add(tree, child)
+    tree['children'] = _children(root, children, files)
+
+    largest = sorted(walk.largest, reverse=True)[:LARGEST_FILES]
+    seconds = round(time.perf_counter() - started, 3)
+    logger.info(f"Disk usage of {root}: {tree['size']} bytes in {tree['files']} files ({seconds}s)")
+    return {
+        'root': str(root),
+        'size': tree['size'],
+        'files': tree['files'],
+        'dirs': tree['dirs'],
+        'tree': tree,
+        'by_category': _totals(walk.by_category, 'category'),
+        'by_extension': _totals(walk.by_extension, 'extension', TOP_EXTENSIONS),
+        'largest_files': [{'path': path, 'size': size} for size, path in largest],
+        'skipped': walk.skipped,
+        'seconds': seconds,
+    }
@@ -16,12 +16,16 @@ License: Proprietary (200-key limited release)
 
 import hashlib
 from pathlib import Path
-from typing import Dict, List, Set, Optional, Tuple, Any
+from typing import Dict, List, Set, Optional, Tuple, Any, Callable
 from collections import defaultdict
 import os
 from datetime import datetime, timezone
 from .safety_guardian import SafetyGuardian
+from .trash import SystemTrash
+from .split_archives import group_split_archives, volume_order, is_split_part
+from .raw_pairs import fold_raw_duplicates
 from src.utils.logger import get_logger
+from src.utils.structured_logging import timed
 import json
 import string
 import fnmatch
@@ -55,7 +59,9 @@ class DuplicateFinder:
         self.hash_algorithm = hash_algorithm or config.hash_algorithm or 'sha1'
         self.min_file_size = min_file_size
         self.file_hashes: Dict[str, str] = {}  # path -> hash cache
+        self.split_parts: Dict[str, List[str]] = {}  # primary part -> all parts of a split archive
         self._guardian = SafetyGuardian(config)
+        self._trash = SystemTrash(config, db_manager, self._guardian)
         self._logger = get_logger()
         self._progress = get_progress_reporter()
         self._parallel = get_parallel_processor()
@@ -84,15 +90,10 @@ class DuplicateFinder:
                 return None
 
             # Choose hash algorithm
-            if self.hash_algorithm == 'md5':
-                hasher = hashlib.md5()
-            elif self.hash_algorithm == 'sha256':
-                hasher = hashlib.sha256()
-            else:  # default to sha1
-                hasher = hashlib.sha1()
+            hasher = self._new_hasher()
 
             # Read file in chunks and update hash
-            with open(path, 'rb') as f:
+            with timed('hash'), open(path, 'rb') as f:
                 while chunk := f.read(chunk_size):
                     hasher.update(chunk)
 
@@ -107,6 +108,71 @@ class DuplicateFinder:
             print(f"Error hashing {file_path}: {e}")
             return None
 
+    def _new_hasher(self):
+        """Create a hasher for the configured algorithm."""
+        if self.hash_algorithm == 'md5':
+            return hashlib.md5()
+        if self.hash_algorithm == 'sha256':
+            return hashlib.sha256()
+        return hashlib.sha1()
+
+    def calculate_unit_hash(self, parts: List[Path], chunk_size: int = 8192) -> Optional[str]:
+        """
+        Calculate the hash of a split archive as one unit.
+
+        Parts are hashed in volume order as if they were a single file, so
+        two copies of the same split archive match even if they were split
+        under different names.
+
+        Args:
+            parts (List[Path]): Parts of the split archive
+            chunk_size (int): Size of chunks to read (bytes)
+
+        Returns:
+            str or None: Hex digest, or None if error or below min_file_size
+        """
+        try:
+            if sum(p.stat().st_size for p in parts) < self.min_file_size:
+                return None
+            hasher = self._new_hasher()
+            for part in volume_order(parts):
+                with timed('hash'), open(part, 'rb') as f:
+                    while chunk := f.read(chunk_size):
+                        hasher.update(chunk)
+            return hasher.hexdigest()
+        except OSError as e:
+            print(f"Error hashing split archive {parts[0]}: {e}")
+            return None
+
+    def _hash_unit(self, unit: List[Path]) -> Tuple[str, Optional[str], int]:
+        """
+        Hash a logical unit (a single file or all parts of a split archive).
+
+        Args:
+            unit (List[Path]): Paths of the unit, primary first
+
+        Returns:
+            Tuple: (primary path, hash or None, total size)
+        """
+        primary = str(unit[0])
+        try:
+            size = sum(p.stat().st_size for p in unit)
+        except OSError:
+            return primary, None, 0
+
+        if len(unit) == 1:
+            return primary, self.calculate_hash(primary), size
+
+        self.split_parts[primary] = [str(p) for p in unit]
+        return primary, self.calculate_unit_hash(unit), size
+
+    def _attach_split_parts(self, group: Dict[str, Any]) -> Dict[str, Any]:
+        """Add a 'parts' mapping to a duplicate group that contains split archives."""
+        parts = {path: self.split_parts[path] for path in group['paths'] if path in self.split_parts}
+        if parts:
+            group['parts'] = parts
+        return group
+
     def find_duplicates_in_directory(self, directory: str, recursive: bool = True) -> List[Dict[str, Any]]:
         """
         Find all duplicate files in a directory.
@@ -115,12 +181,18 @@ class DuplicateFinder:
             directory (str): Directory path to scan
             recursive (bool): If True, scan subdirectories
 
+        Split archives are compared as a whole: each group lists the primary
+        part, and 'parts' maps it to every part of the archive. A RAW+JPEG
+        pair duplicated as a whole is one group of JPEGs whose 'parts'
+        include their RAW files ('raw_pair' is set).
+
         Returns:
             List[Dict]: List of duplicate groups, each containing:
                 - hash (str): File content hash
                 - paths (List[str]): List of duplicate file paths
                 - size (int): File size in bytes
                 - total_wasted_space (int): Space that could be freed
+                - parts (Dict[str, List[str]], optional): Split archive parts
         """
         dir_path = Path(directory)
 
@@ -140,16 +212,12 @@ class DuplicateFinder:
         print(f"Scanning for duplicates in: {directory}")
         scanned_count = 0
 
-        for file_path in files:
-            if not file_path.is_file():
-                continue
-
-            # Calculate hash
-            file_hash = self.calculate_hash(str(file_path))
+        for unit in group_split_archives(f for f in files if f.is_file()):
+            # Calculate hash (split archive parts are hashed together)
+            file_path, file_hash, file_size = self._hash_unit(unit)
 
             if file_hash:
-                file_size = file_path.stat().st_size
-                hash_map[file_hash].append((str(file_path), file_size))
+                hash_map[file_hash].append((file_path, file_size))
                 scanned_count += 1
 
                 if scanned_count % 100 == 0:
@@ -166,13 +234,13 @@ class DuplicateFinder:
                 size = file_list[0][1]  # All duplicates have same size
                 wasted_space = size * (len(file_list) - 1)  # Space occupied by duplicates
 
-                duplicate_group = {
+                duplicate_group = self._attach_split_parts({
                     'hash': file_hash,
                     'paths': paths,
                     'size': size,
                     'total_wasted_space': wasted_space,
                     'count': len(paths)
-                }
+                })
 
                 duplicates.append(duplicate_group)
 
@@ -180,6 +248,9 @@ class DuplicateFinder:
                 for path in paths:
                     self.db_manager.add_duplicate(file_hash, path, size)
 
+        # A RAW+JPEG pair copied as a whole counts once
+        duplicates = fold_raw_duplicates(duplicates)
+
         # Sort by wasted space (descending)
         duplicates.sort(key=lambda x: x['total_wasted_space'], reverse=True)
 
@@ -215,11 +286,11 @@ class DuplicateFinder:
         else:
             all_files = list(dir_path.glob('*'))
 
-        # Filter to files only
-        file_paths = [f for f in all_files if f.is_file()]
-        task.total = len(file_paths)
+        # Filter to files only; split archive parts form one unit
+        units = group_split_archives(f for f in all_files if f.is_file())
+        task.total = len(units)
 
-        print(f"Found {len(file_paths)} files, calculating hashes...")
+        print(f"Found {len(units)} files, calculating hashes...")
 
         # Calculate hashes in parallel
         def calculate_hash_with_progress(file_path):
@@ -229,21 +300,17 @@ class DuplicateFinder:
 
         # Process files in parallel
         hash_results = self._parallel.map_with_progress(
-            lambda fp: (fp, self.calculate_hash(fp)),
-            [str(fp) for fp in file_paths],
+            self._hash_unit,
+            units,
             "Hashing files"
         )
 
         # Build hash map
         hash_map: Dict[str, List[Tuple[str, int]]] = defaultdict(list)
 
-        for file_path_str, file_hash in hash_results:
+        for file_path_str, file_hash, file_size in hash_results:
             if file_hash:
-                try:
-                    file_size = Path(file_path_str).stat().st_size
-                    hash_map[file_hash].append((file_path_str, file_size))
-                except OSError:
-                    continue
+                hash_map[file_hash].append((file_path_str, file_size))
 
         self._progress.complete_task("duplicate_scan")
 
@@ -255,18 +322,21 @@ class DuplicateFinder:
                 size = file_list[0][1]
                 wasted_space = size * (len(file_list) - 1)
 
-                duplicate_group = {
+                duplicate_group = self._attach_split_parts({
                     'hash': file_hash,
                     'paths': paths,
                     'size': size,
                     'total_wasted_space': wasted_space,
                     'count': len(paths)
-                }
+                })
 
                 duplicates.append(duplicate_group)
                 for path in paths:
                     self.db_manager.add_duplicate(file_hash, path, size)
 
+        # A RAW+JPEG pair copied as a whole counts once
+        duplicates = fold_raw_duplicates(duplicates)
+
         # Sort by wasted space
         duplicates.sort(key=lambda x: x['total_wasted_space'], reverse=True)
 
@@ -290,6 +360,126 @@ class DuplicateFinder:
 
         return all_duplicates
 
+    def find_duplicates_across(self, directories: List[str],
+                               on_progress: Optional[Callable[[str, int, int], None]] = None) -> List[Dict[str, Any]]:
+        """
+        Find duplicates across several directories treated as one pool.
+
+        Unlike find_duplicates_in_multiple_directories, a file in one
+        directory and its copy in another form a single group.
+
+        Args:
+            directories (List[str]): Directory paths
+            on_progress (Callable, optional): Called as (directory, index, files_hashed)
+                                              while each directory is processed
+
+        Returns:
+            List[Dict]: Duplicate groups (same format as find_duplicates_in_directory)
+        """
+        hash_map: Dict[str, List[Tuple[str, int]]] = defaultdict(list)
+
+        for index, directory in enumerate(directories):
+            dir_path = Path(directory)
+            if not dir_path.is_dir():
+                continue
+            hashed = 0
+            for unit in group_split_archives(f for f in dir_path.rglob('*') if f.is_file()):
+                file_path, file_hash, file_size = self._hash_unit(unit)
+                if file_hash:
+                    hash_map[file_hash].append((file_path, file_size))
+                    hashed += 1
+                    if on_progress and hashed % 100 == 0:
+                        on_progress(directory, index, hashed)
+            if on_progress:
+                on_progress(directory, index, hashed)
+
+        duplicates = []
+        for file_hash, file_list in hash_map.items():
+            if len(file_list) > 1:
+                paths = [path for path, size in file_list]
+                size = file_list[0][1]
+                duplicates.append(self._attach_split_parts({
+                    'hash': file_hash,
+                    'paths': paths,
+                    'size': size,
+                    'total_wasted_space': size * (len(file_list) - 1),
+                    'count': len(paths)
+                }))
+                for path in paths:
+                    self.db_manager.add_duplicate(file_hash, path, size)
+
+        duplicates.sort(key=lambda x: x['total_wasted_space'], reverse=True)
+        return duplicates
+
+    def find_archived_copies(self, directory: str, recursive: bool = True) -> List[Dict[str, Any]]:
+        """
+        Find loose files whose content is already stored inside an archive.
+
+        Archives in the directory are inspected (including nested archives,
+        within the configured zip-bomb limits) and their entries are hashed
+        with the same algorithm as regular files. Archives that hit a limit
+        are skipped and reported.
+
+        Args:
+            directory (str): Directory path to scan
+            recursive (bool): If True, scan subdirectories
+
+        Returns:
+            List[Dict]: One item per loose file with a copy inside an archive:
+                - path (str): Loose file path
+                - hash (str): File content hash
+                - size (int): File size in bytes
+                - archived_in (List[str]): Archive entry paths ('a.zip!/b.txt')
+        """
+        from .archives import archive_format, inspect_archive, InspectionLimits
+
+        dir_path = Path(directory)
+        if not dir_path.exists() or not dir_path.is_dir():
+            print(f"Directory not found: {directory}")
+            return []
+
+        files = dir_path.rglob('*') if recursive else dir_path.glob('*')
+        limits = InspectionLimits.from_config(self.config)
+
+        loose: Dict[str, List[Tuple[str, int]]] = defaultdict(list)
+        archived: Dict[str, List[str]] = defaultdict(list)
+
+        for file_path in files:
+            if not file_path.is_file():
+                continue
+
+            fmt = archive_format(file_path)
+            if is_split_part(file_path):
+                continue  # Volumes cannot be inspected on their own
+            if fmt in ('7z', 'rar'):
+                continue  # Listed by external tools without content hashes
+            if fmt:
+                result = inspect_archive(str(file_path), limits, self.hash_algorithm)
+                if result.get('limit_hit'):
+                    print(f"Skipping suspicious archive {file_path}: {result['limit_hit']}")
+                    continue
+                for entry in result.get('entries', []):
+                    if entry.get('hash') and entry['size'] >= self.min_file_size:
+                        archived[entry['hash']].append(f"{file_path}{entry['path'][len(file_path.name):]}")
+                continue
+
+            file_hash = self.calculate_hash(str(file_path))
+            if file_hash:
+                loose[file_hash].append((str(file_path), file_path.stat().st_size))
+
+        copies = []
+        for file_hash, entries in archived.items():
+            for path, size in loose.get(file_hash, []):
+                copies.append({
+                    'path': path,
+                    'hash': file_hash,
+                    'size': size,
+                    'archived_in': entries
+                })
+
+        copies.sort(key=lambda x: x['size'], reverse=True)
+        return copies
+
     def get_duplicate_summary(self, duplicates: List[Dict[str, Any]]) -> Dict[str, Any]:
         """
         Generate summary statistics for duplicates.
@@ -402,6 +592,9 @@ class DuplicateFinder:
         """
         Clean up duplicate files (delete all but one).
 
+        Split archives listed in the group's 'parts' are deleted with all
+        their parts, never one part on its own.
+
         Args:
             duplicate_group (Dict): Duplicate group to clean
             dry_run (bool): If True, don't actually delete files
@@ -429,8 +622,7 @@ class DuplicateFinder:
                     except Exception:
                         pass
                 else:
-                    # Perform deletion to Recycle Bin in future (send2trash); unlink for now
-                    Path(file_path).unlink()
+                    self._move_to_trash(duplicate_group.get('parts', {}).get(file_path, [file_path]))
                     self.db_manager.remove_duplicate_entry(file_path)
                     try:
                         self._logger.log_operation('DELETE', file_path, file_path, 'DELETED', 'SUCCESS')
@@ -450,6 +642,13 @@ class DuplicateFinder:
             'space_freed': duplicate_group['size'] * deleted_count
         }
 
+    def _move_to_trash(self, paths: List[str]) -> None:
+        """Trash the copies of a duplicate (raises OSError if one could not be trashed)."""
+        result = self._trash.move_to_trash(paths)
+        if result['failed']:
+            failed = result['failed'][0]
+            raise OSError(f"{failed['path']}: {failed['reason']}")
+
     def cleanup_duplicates_parallel(self, duplicates: List[Dict[str, Any]],
                                   dry_run: bool = True, strategy: str = "newest") -> Dict[str, Any]:
         """
@@ -507,7 +706,7 @@ class DuplicateFinder:
                     if dry_run:
                         self._logger.log_operation('DELETE', file_path, file_path, 'DELETED', 'DRY_RUN')
                     else:
-                        Path(file_path).unlink()
+                        self._move_to_trash([file_path])
                         self.db_manager.remove_duplicate_entry(file_path)
                         self._logger.log_operation('DELETE', file_path, file_path, 'DELETED', 'SUCCESS')
 
@@ -0,0 +1,510 @@
+"""
+Ebook Library Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module is the "ebooks" planner strategy: books are filed by their
+embedded metadata as `Books/<Author>/<Title>/<Title>.<ext>`, so the EPUB,
+MOBI and PDF of one book end up side by side.
+
+Title, authors and ISBN are read offline:
+
+- EPUB: the OPF package document (dc:title, dc:creator, dc:identifier)
+- MOBI/AZW/AZW3: the MOBI header's full name and its EXTH records
+- PDF: the document info (with PyPDF2 when installed, otherwise from an
+  uncompressed info dictionary), and an "ISBN" on the first pages
+
+With `ebooks.online` on, books that have an ISBN are enriched from Open
+Library (`ebooks.endpoint`); only the ISBN is sent, and answers are cached
+in data/ebook_cache.json.
+
+`find_editions()` reports books present more than once: identical copies
+(same ISBN and format), the same book in several formats, and different
+editions (different ISBNs of the same author and title).
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import re
+import struct
+import threading
+import urllib.parse
+import urllib.request
+import xml.etree.ElementTree as ET
+import zipfile
+from collections import defaultdict
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .extract import decode_pdf_string
+from .file_names import safe_name, place_in_destination
+
+logger = logging.getLogger(__name__)
+
+try:
+    import PyPDF2  # type: ignore
+    HAS_PYPDF2 = True
+except ImportError:
+    PyPDF2 = None  # type: ignore
+    HAS_PYPDF2 = False
+
+
+EBOOK_EXTENSIONS = {'.epub', '.mobi', '.azw', '.azw3', '.pdf'}
+DEFAULT_DESTINATION = 'Books'
+DEFAULT_ENDPOINT = 'https://openlibrary.org/api/books'
+UNKNOWN_AUTHOR = 'Unknown Author'
+PDF_SCAN_BYTES = 256 * 1024
+
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+_ISBN_TEXT = re.compile(r'(?i)ISBN(?:-1[03])?:?\s*((?:97[89][-\s]?)?(?:\d[-\s]?){9}[\dX])')
+_DC = '{http://purl.org/dc/elements/1.1/}'
+_OPF = '{http://www.idpf.org/2007/opf}'
+_THE = re.compile(r'^(?:the|a|an|der|die|das|le|la|les|el|il)\s+')
+
+# EXTH record types
+EXTH_AUTHOR, EXTH_PUBLISHER, EXTH_ISBN, EXTH_DATE, EXTH_TITLE, EXTH_LANGUAGE = 100, 101, 104, 106, 503, 524
+
+
+# ==================== ISBN ====================
+
+def normalize_isbn(text: Any) -> Optional[str]:
+    """
+    Validate an ISBN and return it as ISBN-13.
+
+    Args:
+        text: ISBN-10 or ISBN-13, with or without hyphens
+
+    Returns:
+        str or None: 13 digits, or None if the checksum does not match
+    """
+    digits = re.sub(r'[^0-9Xx]', '', str(text or '')).upper()
+    if len(digits) == 10 and digits[:9].isdigit():
+        total = sum((10 - i) * int(d) for i, d in enumerate(digits[:9]))
+        check = 10 if digits[9] == 'X' else int(digits[9]) if digits[9].isdigit() else -1
+        if (total + check) % 11 != 0:
+            return None
+        digits = '978' + digits[:9]
+        return digits + str((10 - sum((3 if i % 2 else 1) * int(d) for i, d in enumerate(digits)) % 10) % 10)
+    if len(digits) == 13 and digits.isdigit() and digits[:3] in ('978', '979'):
+        if sum((3 if i % 2 else 1) * int(d) for i, d in enumerate(digits)) % 10 == 0:
+            return digits
+    return None
+
+
+def find_isbn(text: str) -> Optional[str]:
+    """First valid ISBN mentioned as 'ISBN ...' in a text."""
+    for match in _ISBN_TEXT.finditer(text or ''):
+        isbn = normalize_isbn(match.group(1))
+        if isbn:
+            return isbn
+    return None
+
+
+# ==================== Metadata ====================
+
+def read_ebook_metadata(path: str) -> Dict[str, Any]:
+    """
+    Read an ebook's metadata.
+
+    Args:
+        path (str): EPUB, MOBI/AZW or PDF file
+
+    Returns:
+        Dict: Some of 'title', 'authors' (list), 'isbn' (ISBN-13),
+              'publisher', 'year', 'language'; plus 'format'
+    """
+    file_path = Path(path)
+    suffix = file_path.suffix.lower()
+    try:
+        if suffix == '.epub':
+            meta = _read_epub(file_path)
+        elif suffix in ('.mobi', '.azw', '.azw3'):
+            meta = _read_mobi(file_path)
+        elif suffix == '.pdf':
+            meta = _read_pdf(file_path)
+        else:
+            return {}
+    except (OSError, zipfile.BadZipFile, ET.ParseError, struct.error, ValueError, KeyError, IndexError) as e:
+        logger.debug(f"Unreadable ebook metadata in {path}: {e}")
+        meta = {}
+    except Exception as e:  # PyPDF2 raises its own error types
+        logger.debug(f"Ebook reader failed for {path}: {e}")
+        meta = {}
+    meta = {k: v for k, v in meta.items() if v}
+    meta['format'] = suffix.lstrip('.')
+    return meta
+
+
+def _year(text: Any) -> Optional[int]:
+    match = re.search(r'\b(1[5-9]\d{2}|20\d{2})\b', str(text or ''))
+    return int(match.group(1)) if match else None
+
+
+def _read_epub(path: Path) -> Dict[str, Any]:
+    with zipfile.ZipFile(path) as epub:
+        container = ET.fromstring(epub.read('META-INF/container.xml'))
+        rootfile = next(el for el in container.iter() if el.tag.endswith('rootfile'))
+        package = ET.fromstring(epub.read(rootfile.attrib['full-path']))
+
+    meta: Dict[str, Any] = {'authors': []}
+    for el in package.iter():
+        text = (el.text or '').strip()
+        if not text:
+            continue
+        if el.tag == f'{_DC}title' and 'title' not in meta:
+            meta['title'] = text
+        elif el.tag == f'{_DC}creator':
+            role = el.attrib.get(f'{_OPF}role') or el.attrib.get('role') or 'aut'
+            if role == 'aut':
+                meta['authors'].append(text)
+        elif el.tag == f'{_DC}identifier' and 'isbn' not in meta:
+            scheme = (el.attrib.get(f'{_OPF}scheme') or el.attrib.get('scheme') or '').lower()
+            if scheme == 'isbn' or 'isbn' in text.lower() or text.replace('-', '').isdigit():
+                meta['isbn'] = normalize_isbn(text.lower().replace('urn:isbn:', '').replace('isbn', ''))
+        elif el.tag == f'{_DC}publisher':
+            meta.setdefault('publisher', text)
+        elif el.tag == f'{_DC}date':
+            meta.setdefault('year', _year(text))
+        elif el.tag == f'{_DC}language':
+            meta.setdefault('language', text)
+    return meta
+
+
+def _read_mobi(path: Path) -> Dict[str, Any]:
+    with open(path, 'rb') as f:
+        header = f.read(78 + 8)
+        if header[60:68] not in (b'BOOKMOBI', b'TEXtREAd'):
+            return {}
+        record0 = struct.unpack('>L', header[78:82])[0]
+        f.seek(record0)
+        data = f.read(64 * 1024)
+
+    meta: Dict[str, Any] = {'title': header[:32].split(b'\x00')[0].decode('latin-1').replace('_', ' ')}
+    if data[16:20] != b'MOBI':
+        return meta
+    mobi_length, encoding = struct.unpack('>LL', data[20:28])
+    codec = 'utf-8' if encoding == 65001 else 'cp1252'
+    name_offset, name_length = struct.unpack('>LL', data[84:92])
+    if name_length:
+        meta['title'] = data[name_offset:name_offset + name_length].decode(codec, errors='replace')
+
+    exth_flags = struct.unpack('>L', data[128:132])[0]
+    exth = 16 + mobi_length
+    if exth_flags & 0x40 and data[exth:exth + 4] == b'EXTH':
+        count = struct.unpack('>L', data[exth + 8:exth + 12])[0]
+        pos = exth + 12
+        authors = []
+        for _ in range(count):
+            kind, length = struct.unpack('>LL', data[pos:pos + 8])
+            value = data[pos + 8:pos + length].decode(codec, errors='replace').strip()
+            pos += length
+            if length < 8:
+                break
+            if kind == EXTH_AUTHOR:
+                authors.append(value)
+            elif kind == EXTH_TITLE:
+                meta['title'] = value
+            elif kind == EXTH_ISBN:
+                meta['isbn'] = normalize_isbn(value)
+            elif kind == EXTH_PUBLISHER:
+                meta['publisher'] = value
+            elif kind == EXTH_DATE:
+                meta['year'] = _year(value)
+            elif kind == EXTH_LANGUAGE:
+                meta['language'] = value
+        meta['authors'] = authors
+    return meta
+
+
+def _read_pdf(path: Path) -> Dict[str, Any]:
+    meta: Dict[str, Any] = {}
+    if HAS_PYPDF2:
+        with open(path, 'rb') as f:
+            pdf = PyPDF2.PdfReader(f)
+            info = pdf.metadata or {}
+            meta['title'] = str(info.get('/Title') or '').strip()
+            author = str(info.get('/Author') or '').strip()
+            meta['authors'] = _split_authors(author)
+            meta['year'] = _year(info.get('/CreationDate'))
+            text = ''
+            for page in pdf.pages[:5]:
+                text += page.extract_text() or ''
+            meta['isbn'] = find_isbn(text)
+        return meta
+
+    with open(path, 'rb') as f:
+        head = f.read(PDF_SCAN_BYTES)
+        f.seek(0, 2)
+        f.seek(max(0, f.tell() - PDF_SCAN_BYTES))
+        tail = f.read()
+    for chunk in (tail, head):
+        text = chunk.decode('latin-1')
+        title = re.search(r'/Title\s*\(((?:\\.|[^\\)])*)\)', text)
+        author = re.search(r'/Author\s*\(((?:\\.|[^\\)])*)\)', text)
+        if title and 'title' not in meta:
+            meta['title'] = decode_pdf_string(title.group(1))
+        if author and 'authors' not in meta:
+            meta['authors'] = _split_authors(decode_pdf_string(author.group(1)))
+    meta['isbn'] = find_isbn(head.decode('latin-1'))
+    return meta
+
+
+def _split_authors(text: str) -> List[str]:
+    parts = re.split(r'\s*(?:;|&|\band\b)\s*', text or '')
+    return [p for p in (part.strip() for part in parts) if p]
+
+
+def display_author(name: str) -> str:
+    """'Tolkien, J. R. R.' -> 'J. R. R. Tolkien'."""
+    if name.count(',') == 1:
+        last, first = (part.strip() for part in name.split(','))
+        if first and last:
+            return f"{first} {last}"
+    return name.strip()
+
+
+def work_key(meta: Dict[str, Any]) -> Optional[str]:
+    """Author and title reduced to compare editions ('tolkien|hobbit')."""
+    title = (meta.get('title') or '').lower()
+    authors = meta.get('authors') or []
+    if not title or not authors:
+        return None
+    title = re.split(r'\s*[:(\[]', title)[0]
+    title = _THE.sub('', re.sub(r'[^\w\s]', '', title)).strip()
+    title = re.sub(r'\s+', ' ', title)
+    surname = display_author(authors[0]).lower().split()[-1] if display_author(authors[0]) else ''
+    return f"{surname}|{title}" if title and surname else None
+
+
+# ==================== Online ====================
+
+class EbookMetadataLookup:
+    """
+    Optional Open Library lookup by ISBN.
+
+    Attributes:
+        online (bool): Whether lookups may be made (privacy toggle)
+        endpoint (str): Open Library books API URL
+    """
+
+    def __init__(self, config, cache_path: Optional[str] = None):
+        """
+        Initialize lookup.
+
+        Args:
+            config: Configuration object (reads `ebook_settings`)
+            cache_path (str, optional): Answer cache (default: data/ebook_cache.json)
+        """
+        settings = getattr(config, 'ebook_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.online = bool(settings.get('online', False))
+        self.endpoint = settings.get('endpoint') or DEFAULT_ENDPOINT
+        self.cache_path = Path(cache_path or settings.get('cache_file')
+                               or _APP_ROOT / 'data' / 'ebook_cache.json').expanduser()
+        self._cache: Optional[Dict[str, Any]] = None
+        self._lock = threading.Lock()
+
+    def by_isbn(self, isbn: str) -> Optional[Dict[str, Any]]:
+        """
+        Title, authors and year of a book.
+
+        Returns:
+            Dict or None: 'title', 'authors' and 'year', or None if off, unknown or unreachable
+        """
+        if not self.online or not isbn:
+            return None
+        with self._lock:
+            cache = self._load_cache()
+            if isbn in cache:
+                return cache[isbn]
+        query = urllib.parse.urlencode({'bibkeys': f'ISBN:{isbn}', 'format': 'json', 'jscmd': 'data'})
+        request = urllib.request.Request(f"{self.endpoint}?{query}",
+                                         headers={'User-Agent': 'AI-File-Organiser (ebook library)'})
+        try:
+            with urllib.request.urlopen(request, timeout=10) as response:
+                answer = json.loads(response.read().decode('utf-8'))
+        except (OSError, ValueError) as e:
+            logger.warning(f"Ebook metadata lookup failed: {e}")
+            return None  # Not cached: try again next time
+
+        book = answer.get(f'ISBN:{isbn}') if isinstance(answer, dict) else None
+        found = None
+        if isinstance(book, dict) and book.get('title'):
+            found = {'title': book['title'],
+                     'authors': [a['name'] for a in book.get('authors', []) if a.get('name')],
+                     'year': _year(book.get('publish_date'))}
+        with self._lock:
+            cache[isbn] = found
+            self._save_cache()
+        return found
+
+    def _load_cache(self) -> Dict[str, Any]:
+        if self._cache is None:
+            try:
+                self._cache = json.loads(self.cache_path.read_text(encoding='utf-8'))
+            except (OSError, ValueError):
+                self._cache = {}
+        return self._cache
+
+    def _save_cache(self) -> None:
+        try:
+            self.cache_path.parent.mkdir(parents=True, exist_ok=True)
+            self.cache_path.write_text(json.dumps(self._cache, indent=2, ensure_ascii=False), encoding='utf-8')
+        except OSError as e:
+            logger.warning(f"Cannot save ebook metadata cache: {e}")
+
+
+# ==================== Strategy ====================
+
+class EbookLibraryStrategy:
+    """
+    Plans ebook destinations from their metadata.
+
+    Attributes:
+        config: Configuration object
+    """
+
+    name = 'ebooks'
+
+    def __init__(self, config):
+        """
+        Initialize ebook strategy.
+
+        Args:
+            config: Configuration object
+        """
+        self.config = config
+        self._lookup: Optional[EbookMetadataLookup] = None
+
+    @property
+    def lookup(self) -> EbookMetadataLookup:
+        if self._lookup is None:
+            self._lookup = EbookMetadataLookup(self.config)
+        return self._lookup
+
+    def metadata(self, path: Path) -> Dict[str, Any]:
+        """The book's embedded metadata, enriched online when enabled."""
+        meta = read_ebook_metadata(str(path))
+        found = self.lookup.by_isbn(meta.get('isbn'))
+        if found:
+            meta.update({k: v for k, v in found.items() if v})
+            meta['source'] = 'online'
+        return meta
+
+    def classify(self, file_path: str, root: Optional[Path] = None,
+                 policy: Optional[Dict[str, Any]] = None) -> Optional[Dict[str, Any]]:
+        """
+        Work out where an ebook belongs.
+
+        Args:
+            file_path (str): Ebook to place
+            root (Path, optional): Folder being organized (unused)
+            policy (Dict, optional): Folder policy with `ebook_destination`
+
+        Returns:
+            Dict or None: Classification, or None if the file is not an
+                          ebook or has no title (the classifier decides then)
+        """
+        path = Path(file_path)
+        if path.suffix.lower() not in EBOOK_EXTENSIONS:
+            return None
+        meta = self.metadata(path)
+        title = safe_name(meta.get('title'))
+        if not title:
+            return None
+        policy = policy or {}
+        authors = [display_author(a) for a in meta.get('authors') or []]
+        author = safe_name(authors[0]) if authors else UNKNOWN_AUTHOR
+        folder = f"{author}/{title}"
+        rename = f"{title}{path.suffix.lower()}"
+
+        reason = f"{title} by {', '.join(authors) or 'unknown author'}"
+        if meta.get('isbn'):
+            reason += f" (ISBN {meta['isbn']})"
+        classification = {
+            'category': 'Documents',
+            'suggested_path': f"{folder}/",
+            'rename': rename if rename != path.name else None,
+            'reason': reason,
+            'confidence': 'high' if authors else 'medium',
+            'method': 'ebooks',
+            'isbn': meta.get('isbn')
+        }
+        destination = str(policy.get('ebook_destination') or DEFAULT_DESTINATION)
+        return place_in_destination(classification, destination, folder)
+
+    # ==================== Editions ====================
+
+    def find_editions(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Find books that are in a folder more than once.
+
+        Books match by ISBN, or else by author and title (ignoring
+        subtitles, punctuation and leading articles). Each group's 'kind'
+        is 'copies' if some files share ISBN (or, without ISBNs, title) and
+        format, 'editions' if they carry different ISBNs, else 'formats'.
+
+        Args:
+            folder (str): Folder to check
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: 'success', 'message', 'books' count and 'groups', each
+                  {title, author, kind, files: [{path, format, isbn, size}]}
+        """
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {root}", 'books': 0, 'groups': []}
+        pattern = '**/*' if recursive else '*'
+        books = sorted(p for p in root.glob(pattern) if p.suffix.lower() in EBOOK_EXTENSIONS and p.is_file())
+
+        by_key: Dict[str, List[Dict[str, Any]]] = defaultdict(list)
+        isbn_keys: Dict[str, str] = {}
+        for book in books:
+            meta = read_ebook_metadata(str(book))
+            key = work_key(meta)
+            isbn = meta.get('isbn')
+            if isbn:
+                # An ISBN seen before joins that book even if titles differ slightly
+                key = isbn_keys.setdefault(isbn, key or f"isbn|{isbn}")
+            if key is None:
+                continue
+            try:
+                size = book.stat().st_size
+            except OSError:
+                size = 0
+            by_key[key].append({'path': str(book), 'format': meta['format'], 'isbn': isbn, 'size': size,
+                                'title': meta.get('title'), 'authors': meta.get('authors') or []})
+
+        groups = []
+        for files in by_key.values():
+            if len(files) < 2:
+                continue
+            isbns = {f['isbn'] for f in files if f['isbn']}
+            copies = {(f['isbn'], f['format']) for f in files}
+            if len(copies) < len(files):
+                kind = 'copies'
+            elif len(isbns) > 1:
+                kind = 'editions'
+            else:
+                kind = 'formats'
+            first = files[0]
+            groups.append({
+                'title': first['title'],
+                'author': display_author(first['authors'][0]) if first['authors'] else None,
+                'kind': kind,
+                'files': [{k: f[k] for k in ('path', 'format', 'isbn', 'size')} for f in files]
+            })
+        return {
+            'success': True,
+            'message': f"{len(groups)} book(s) present more than once among {len(books)} ebook(s)",
+            'books': len(books),
+            'groups': groups
+        }
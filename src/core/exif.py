@@ -0,0 +1,273 @@
+"""
+EXIF Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module reads the handful of EXIF tags the photo features need
+(capture time and its timezone offset, camera, orientation, GPS) straight
+from the file, without Pillow:
+
+- JPEG: the TIFF structure inside the APP1 "Exif" segment
+- TIFF and TIFF-based RAW formats (DNG, CR2, NEF, ARW, ORF, PEF, ...)
+
+Only the start of the file is read. Formats it cannot parse (HEIC, PNG
+without eXIf, corrupt files) simply return no tags, and callers fall back
+to file dates.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import re
+import struct
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+from typing import Dict, Any, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+MAX_HEADER_BYTES = 1024 * 1024  # EXIF lives near the start of the file
+
+JPEG_EXTENSIONS = {'.jpg', '.jpeg', '.jpe', '.jfif'}
+TIFF_EXTENSIONS = {'.tif', '.tiff', '.dng', '.cr2', '.nef', '.nrw', '.arw', '.srf', '.sr2',
+                   '.orf', '.pef', '.raw', '.rwl', '.srw', '.3fr', '.erf', '.kdc', '.mef', '.iiq'}
+RAW_EXTENSIONS = TIFF_EXTENSIONS - {'.tif', '.tiff'} | {'.cr3', '.raf', '.rw2', '.x3f'}
+PHOTO_EXTENSIONS = JPEG_EXTENSIONS | TIFF_EXTENSIONS | RAW_EXTENSIONS | {'.heic', '.heif', '.png', '.webp'}
+
+# Tag IDs -> names, for the tags that are read
+IFD0_TAGS = {0x010F: 'Make', 0x0110: 'Model', 0x0112: 'Orientation', 0x0132: 'DateTime'}
+EXIF_TAGS = {0x9003: 'DateTimeOriginal', 0x9004: 'DateTimeDigitized', 0x9010: 'OffsetTime',
+             0x9011: 'OffsetTimeOriginal', 0x9012: 'OffsetTimeDigitized',
+             0x9290: 'SubSecTime', 0x9291: 'SubSecTimeOriginal', 0xA431: 'BodySerialNumber'}
+GPS_TAGS = {0x01: 'GPSLatitudeRef', 0x02: 'GPSLatitude', 0x03: 'GPSLongitudeRef',
+            0x04: 'GPSLongitude', 0x05: 'GPSAltitudeRef', 0x06: 'GPSAltitude',
+            0x07: 'GPSTimeStamp', 0x1D: 'GPSDateStamp'}
+EXIF_IFD_POINTER = 0x8769
+GPS_IFD_POINTER = 0x8825
+THUMBNAIL_OFFSET_TAG = 0x0201  # JPEGInterchangeFormat, in IFD1
+THUMBNAIL_LENGTH_TAG = 0x0202
+
+# Type id -> (struct code, size in bytes)
+_TYPES = {1: ('B', 1), 2: ('s', 1), 3: ('H', 2), 4: ('L', 4), 5: ('LL', 8),
+          7: ('B', 1), 9: ('l', 4), 10: ('ll', 8)}
+
+_OFFSET = re.compile(r'^([+-])(\d{2}):?(\d{2})$')
+
+
+def read_exif(path: str) -> Dict[str, Any]:
+    """
+    Read EXIF tags from a photo.
+
+    Args:
+        path (str): Photo file
+
+    Returns:
+        Dict: Tag name -> value (strings, ints, or float tuples for GPS);
+              empty when the file has no readable EXIF
+    """
+    file_path = Path(path)
+    try:
+        with open(file_path, 'rb') as f:
+            data = f.read(MAX_HEADER_BYTES)
+    except OSError as e:
+        logger.debug(f"Cannot read {path}: {e}")
+        return {}
+
+    tiff = _tiff_block(data)
+    if tiff is None:
+        return {}
+    try:
+        return _parse_tiff(tiff)
+    except (struct.error, IndexError, ValueError) as e:
+        logger.debug(f"Unreadable EXIF in {path}: {e}")
+        return {}
+
+
+def embedded_thumbnail(path: str) -> Optional[bytes]:
+    """
+    The small JPEG preview cameras store in IFD1, or None.
+
+    Args:
+        path (str): Photo file
+
+    Returns:
+        bytes or None: JPEG data of the thumbnail
+    """
+    try:
+        with open(path, 'rb') as f:
+            data = f.read(MAX_HEADER_BYTES)
+        tiff = _tiff_block(data)
+        if tiff is None:
+            return None
+        order = '<' if tiff[:2] == b'II' else '>'
+        ifd0 = struct.unpack(order + 'L', tiff[4:8])[0]
+        count = struct.unpack(order + 'H', tiff[ifd0:ifd0 + 2])[0]
+        ifd1 = struct.unpack(order + 'L', tiff[ifd0 + 2 + 12 * count:ifd0 + 6 + 12 * count])[0]
+        if not ifd1:
+            return None
+        entries = {}
+        for i in range(struct.unpack(order + 'H', tiff[ifd1:ifd1 + 2])[0]):
+            entry = ifd1 + 2 + 12 * i
+            tag, type_id, _ = struct.unpack(order + 'HHL', tiff[entry:entry + 8])
+            code, size = ('H', 2) if type_id == 3 else ('L', 4)
+            entries[tag] = struct.unpack(order + code, tiff[entry + 8:entry + 8 + size])[0]
+    except (OSError, struct.error, IndexError) as e:
+        logger.debug(f"No embedded thumbnail in {path}: {e}")
+        return None
+    start, length = entries.get(THUMBNAIL_OFFSET_TAG), entries.get(THUMBNAIL_LENGTH_TAG)
+    if not start or not length:
+        return None
+    thumbnail = tiff[start:start + length]
+    return thumbnail if thumbnail[:2] == b'\xff\xd8' and len(thumbnail) == length else None
+
+
+def _tiff_block(data: bytes) -> Optional[bytes]:
+    """The TIFF structure holding the EXIF tags, from a JPEG or a TIFF-based file."""
+    if data[:4] in (b'II*\x00', b'MM\x00*'):
+        return data
+    if data[:2] != b'\xff\xd8':
+        return None
+    pos = 2
+    while pos + 4 <= len(data):
+        if data[pos] != 0xFF:
+            return None
+        marker = data[pos + 1]
+        if marker in (0xD9, 0xDA):  # End of image / start of scan: no EXIF before the pixels
+            return None
+        length = struct.unpack('>H', data[pos + 2:pos + 4])[0]
+        segment = data[pos + 4:pos + 2 + length]
+        if marker == 0xE1 and segment[:6] == b'Exif\x00\x00':
+            return segment[6:]
+        pos += 2 + length
+    return None
+
+
+def _parse_tiff(tiff: bytes) -> Dict[str, Any]:
+    order = '<' if tiff[:2] == b'II' else '>'
+    ifd0 = struct.unpack(order + 'L', tiff[4:8])[0]
+    tags: Dict[str, Any] = {}
+    pointers = _read_ifd(tiff, order, ifd0, IFD0_TAGS, tags)
+    if EXIF_IFD_POINTER in pointers:
+        _read_ifd(tiff, order, pointers[EXIF_IFD_POINTER], EXIF_TAGS, tags)
+    if GPS_IFD_POINTER in pointers:
+        _read_ifd(tiff, order, pointers[GPS_IFD_POINTER], GPS_TAGS, tags)
+    return tags
+
+
+def _read_ifd(tiff: bytes, order: str, offset: int, names: Dict[int, str],
+              tags: Dict[str, Any]) -> Dict[int, int]:
+    """Read the wanted tags of one IFD into `tags`; returns the sub-IFD pointers found."""
+    pointers: Dict[int, int] = {}
+    count = struct.unpack(order + 'H', tiff[offset:offset + 2])[0]
+    for i in range(count):
+        entry = offset + 2 + i * 12
+        tag, type_id, n = struct.unpack(order + 'HHL', tiff[entry:entry + 8])
+        if tag in (EXIF_IFD_POINTER, GPS_IFD_POINTER):
+            pointers[tag] = struct.unpack(order + 'L', tiff[entry + 8:entry + 12])[0]
+            continue
+        if tag not in names or type_id not in _TYPES:
+            continue
+        code, size = _TYPES[type_id]
+        length = size * n
+        if length <= 4:
+            raw = tiff[entry + 8:entry + 8 + length]
+        else:
+            start = struct.unpack(order + 'L', tiff[entry + 8:entry + 12])[0]
+            raw = tiff[start:start + length]
+            if len(raw) < length:
+                continue  # Value lies beyond the bytes read
+        tags[names[tag]] = _decode(raw, order, type_id, code, size, n)
+    return pointers
+
+
+def _decode(raw: bytes, order: str, type_id: int, code: str, size: int, n: int) -> Any:
+    if type_id == 2:
+        return raw.split(b'\x00', 1)[0].decode('ascii', errors='replace').strip()
+    if type_id in (5, 10):
+        values = []
+        for i in range(n):
+            num, den = struct.unpack(order + code, raw[i * size:(i + 1) * size])
+            values.append(num / den if den else 0.0)
+        return tuple(values) if n > 1 else values[0]
+    if type_id == 7:
+        return raw
+    values = struct.unpack(order + code * n, raw)
+    return values if n > 1 else values[0]
+
+
+# ==================== Interpreted values ====================
+
+def parse_offset(value: Optional[str]) -> Optional[timezone]:
+    """'+01:00' / '-0530' -> timezone, or None if missing or malformed."""
+    match = _OFFSET.match((value or '').strip())
+    if not match:
+        return None
+    sign, hours, minutes = match.groups()
+    delta = timedelta(hours=int(hours), minutes=int(minutes))
+    return timezone(-delta if sign == '-' else delta)
+
+
+def parse_exif_datetime(value: Optional[str]) -> Optional[datetime]:
+    """'2024:05:17 14:03:22' -> naive datetime (None for blank values like '0000:00:00 00:00:00')."""
+    if not value:
+        return None
+    try:
+        return datetime.strptime(value.strip()[:19], '%Y:%m:%d %H:%M:%S')
+    except ValueError:
+        return None
+
+
+def capture_time(tags: Dict[str, Any]) -> Tuple[Optional[datetime], Optional[str]]:
+    """
+    When a photo was taken, according to its EXIF tags.
+
+    The time is the camera's wall-clock time; when the camera recorded its
+    timezone offset the result is timezone-aware, otherwise it is naive.
+
+    Args:
+        tags (Dict): Result of read_exif()
+
+    Returns:
+        Tuple: (datetime or None, name of the tag it came from)
+    """
+    for name, offset_name in (('DateTimeOriginal', 'OffsetTimeOriginal'),
+                              ('DateTimeDigitized', 'OffsetTimeDigitized'),
+                              ('DateTime', 'OffsetTime')):
+        taken = parse_exif_datetime(tags.get(name))
+        if taken is None:
+            continue
+        subsec = tags.get('SubSecTimeOriginal') if name == 'DateTimeOriginal' else None
+        if isinstance(subsec, str) and subsec.strip().isdigit():
+            taken = taken.replace(microsecond=int(subsec.strip()[:6].ljust(6, '0')))
+        tz = parse_offset(tags.get(offset_name)) or parse_offset(tags.get('OffsetTime'))
+        return (taken.replace(tzinfo=tz) if tz else taken), name
+    return None, None
+
+
+def gps_coordinates(tags: Dict[str, Any]) -> Optional[Tuple[float, float]]:
+    """
+    Decimal (latitude, longitude) from the GPS tags, or None.
+
+    Args:
+        tags (Dict): Result of read_exif()
+    """
+    lat, lon = tags.get('GPSLatitude'), tags.get('GPSLongitude')
+    if not isinstance(lat, tuple) or not isinstance(lon, tuple) or len(lat) != 3 or len(lon) != 3:
+        return None
+    latitude = lat[0] + lat[1] / 60 + lat[2] / 3600
+    longitude = lon[0] + lon[1] / 60 + lon[2] / 3600
+    if str(tags.get('GPSLatitudeRef', 'N')).upper().startswith('S'):
+        latitude = -latitude
+    if str(tags.get('GPSLongitudeRef', 'E')).upper().startswith('W'):
+        longitude = -longitude
+    if latitude == 0 and longitude == 0:
+        return None  # "No fix" written as zeros
+    if not (-90 <= latitude <= 90 and -180 <= longitude <= 180):
+        return None
+    return round(latitude, 6), round(longitude, 6)
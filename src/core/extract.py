@@ -0,0 +1,236 @@
+"""
+Metadata Extraction Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module reads the metadata organizing decisions need, in one call and
+with no optional dependency:
+
+- Images: when the photo was taken (EXIF, with its timezone offset when
+  the camera recorded one), GPS position, camera and orientation
+- Audio: title, artist, album, album artist, track, year and genre
+  (ID3, FLAC/Ogg Vorbis comments, MP4 atoms)
+- PDF: title, author and page count
+
+extract_metadata() always returns a FileMetadata; fields the file does not
+have are None, and a file that cannot be read has 'error' set instead of
+raising. It builds on the EXIF and audio tag readers the photo and music
+strategies use, so all of them agree on what a file says.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import re
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .audio_tags import AUDIO_EXTENSIONS, read_tags
+from .exif import PHOTO_EXTENSIONS, capture_time, gps_coordinates, read_exif
+
+logger = logging.getLogger(__name__)
+
+
+PDF_CHUNK_BYTES = 1024 * 1024
+PDF_MAX_SCAN_BYTES = 256 * 1024 * 1024
+PDF_OVERLAP_BYTES = 4096
+
+_PAGE_OBJECT = re.compile(rb'/Type\s*/Page(?![A-Za-z])')
+_PAGE_COUNT = re.compile(rb'/Type\s*/Pages\b[^>]*?/Count\s+(\d+)|/Count\s+(\d+)[^>]*?/Type\s*/Pages\b', re.S)
+_PDF_TEXT = {name: re.compile(rb'/' + name.encode() + rb'\s*\(((?:\\.|[^\\)])*)\)', re.S)
+             for name in ('Title', 'Author')}
+_PDF_HEX = {name: re.compile(rb'/' + name.encode() + rb'\s*<([0-9A-Fa-f\s]*)>')
+            for name in ('Title', 'Author')}
+
+
+@dataclass
+class FileMetadata:
+    """What a file says about itself."""
+    path: str
+    kind: str = 'other'
+    size: int = 0
+    modified: Optional[str] = None
+    title: Optional[str] = None
+    author: Optional[str] = None
+    # Images
+    date_taken: Optional[str] = None
+    date_source: Optional[str] = None
+    latitude: Optional[float] = None
+    longitude: Optional[float] = None
+    camera: Optional[str] = None
+    orientation: Optional[int] = None
+    # Audio
+    artist: Optional[str] = None
+    album: Optional[str] = None
+    album_artist: Optional[str] = None
+    track: Optional[int] = None
+    year: Optional[int] = None
+    genre: Optional[str] = None
+    # PDF
+    page_count: Optional[int] = None
+    error: Optional[str] = None
+    tags: Dict[str, Any] = field(default_factory=dict)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """JSON-ready dictionary (raw tags included)."""
+        data = asdict(self)
+        data['tags'] = {name: _jsonable(value) for name, value in self.tags.items()}
+        return data
+
+
+def extract_metadata(path: str) -> FileMetadata:
+    """
+    Read a file's metadata.
+
+    Args:
+        path (str): File to read
+
+    Returns:
+        FileMetadata: Everything found (None for what the file does not have)
+    """
+    file_path = Path(path).expanduser()
+    meta = FileMetadata(path=str(file_path), kind=file_kind(file_path))
+    try:
+        stat = file_path.stat()
+    except OSError as e:
+        meta.error = e.strerror or str(e)
+        return meta
+    meta.size = stat.st_size
+    meta.modified = datetime.fromtimestamp(stat.st_mtime).astimezone().isoformat()
+    try:
+        if meta.kind == 'image':
+            _image(file_path, meta)
+        elif meta.kind == 'audio':
+            _audio(file_path, meta)
+        elif meta.kind == 'pdf':
+            _pdf(file_path, meta)
+    except OSError as e:
+        meta.error = e.strerror or str(e)
+    except Exception as e:
+        logger.debug(f"Metadata extraction failed for {path}: {e}")
+        meta.error = str(e) or type(e).__name__
+    return meta
+
+
+def extract_many(paths: List[str]) -> List[Dict[str, Any]]:
+    """extract_metadata() for several files, as dictionaries in the order given."""
+    return [extract_metadata(path).to_dict() for path in paths]
+
+
+def file_kind(path: Path) -> str:
+    """'image', 'audio', 'pdf' or 'other', by extension."""
+    extension = path.suffix.lower()
+    if extension in PHOTO_EXTENSIONS:
+        return 'image'
+    if extension in AUDIO_EXTENSIONS:
+        return 'audio'
+    if extension == '.pdf':
+        return 'pdf'
+    return 'other'
+
+
+def _image(path: Path, meta: FileMetadata) -> None:
+    tags = read_exif(str(path))
+    meta.tags = tags
+    taken, source = capture_time(tags)
+    if taken is not None:
+        meta.date_taken, meta.date_source = taken.isoformat(), source
+    position = gps_coordinates(tags)
+    if position:
+        meta.latitude, meta.longitude = position
+    make, model = (str(tags.get(name) or '').strip() for name in ('Make', 'Model'))
+    if make or model:
+        # Models usually repeat the make ("Canon" / "Canon EOS R5")
+        meta.camera = model if make and model.lower().startswith(make.lower()) else f"{make} {model}".strip()
+    if isinstance(tags.get('Orientation'), int):
+        meta.orientation = tags['Orientation']
+
+
+def _audio(path: Path, meta: FileMetadata) -> None:
+    tags = read_tags(str(path))
+    meta.tags = tags
+    meta.title = tags.get('title')
+    meta.artist = tags.get('artist')
+    meta.author = tags.get('artist')
+    meta.album = tags.get('album')
+    meta.album_artist = tags.get('albumartist')
+    meta.track = tags.get('track')
+    meta.year = tags.get('year')
+    meta.genre = tags.get('genre')
+
+
+def _pdf(path: Path, meta: FileMetadata) -> None:
+    pages = 0
+    declared = 0
+    info: Dict[str, str] = {}
+    tail = b''
+    scanned = 0
+    with open(path, 'rb') as f:
+        if not f.read(5).startswith(b'%PDF'):
+            meta.error = 'Not a PDF file'
+            return
+        f.seek(0)
+        while scanned < PDF_MAX_SCAN_BYTES:
+            chunk = f.read(PDF_CHUNK_BYTES)
+            if not chunk:
+                break
+            scanned += len(chunk)
+            # The end of each chunk is looked at again with the next, so nothing split between them is missed
+            data = tail + chunk
+            last = len(chunk) < PDF_CHUNK_BYTES
+            cut = len(data) if last else max(0, len(data) - PDF_OVERLAP_BYTES)
+            pages += sum(1 for match in _PAGE_OBJECT.finditer(data) if match.start() < cut)
+            for match in _PAGE_COUNT.finditer(data):
+                declared = max(declared, int(match.group(1) or match.group(2)))
+            for name in ('Title', 'Author'):
+                if name not in info:
+                    found = _pdf_info(data, name)
+                    if found:
+                        info[name] = found
+            tail = data[cut:]
+    pages += len(_PAGE_OBJECT.findall(tail))
+    meta.title = info.get('Title')
+    meta.author = info.get('Author')
+    # The page tree's count is exact; counting page objects is the fallback for damaged files
+    meta.page_count = declared or pages or None
+    meta.tags = {name.lower(): value for name, value in info.items()}
+
+
+def decode_pdf_string(raw: str) -> str:
+    """Decode a PDF literal string (escapes, UTF-16 with a BOM)."""
+    text = re.sub(r'\\([nrtbf()\\])', lambda m: {'n': '\n', 'r': '\r', 't': '\t', 'b': '\b',
+                                                 'f': '\f'}.get(m.group(1), m.group(1)), raw)
+    text = re.sub(r'\\([0-7]{1,3})', lambda m: chr(int(m.group(1), 8)), text)
+    data = text.encode('latin-1', errors='replace')
+    if data[:2] == b'\xfe\xff':
+        return data[2:].decode('utf-16-be', errors='replace').strip()
+    return text.strip()
+
+
+def _pdf_info(data: bytes, name: str) -> Optional[str]:
+    match = _PDF_TEXT[name].search(data)
+    if match:
+        return decode_pdf_string(match.group(1).decode('latin-1')) or None
+    match = _PDF_HEX[name].search(data)
+    if match:
+        raw = bytes.fromhex(re.sub(r'\s', '', match.group(1).decode('ascii')))
+        text = raw[2:].decode('utf-16-be', errors='replace') if raw[:2] == b'\xfe\xff' else raw.decode('latin-1')
+        return text.strip() or None
+    return None
+
+
+def _jsonable(value: Any) -> Any:
+    if isinstance(value, bytes):
+        return value.hex()
+    if isinstance(value, tuple):
+        return [_jsonable(item) for item in value]
+    if isinstance(value, (str, int, float, bool)) or value is None:
+        return value
+    return str(value)
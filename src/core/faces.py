@@ -0,0 +1,486 @@
+"""
+Face Clustering Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module groups photos by the people in them, entirely offline. Faces
+are found and turned into embeddings by local ONNX models, similar faces
+are clustered into people, and each person can be named, merged with
+another and exposed as a "People: <name>" virtual collection. No image or
+embedding ever leaves the machine.
+
+The models are not bundled; point config.json at them:
+
+    "faces": {
+        "enabled": true,
+        "detector_model": "~/models/version-RFB-320.onnx",
+        "embedding_model": "~/models/arcface-r100.onnx"
+    }
+
+The detector is an UltraFace-style model (outputs: scores, boxes in
+relative coordinates) and the embedder an ArcFace-style model taking a
+112x112 face crop. Both need onnxruntime, numpy and Pillow, which are
+optional dependencies: without them the feature reports itself as
+unavailable and the rest of the app is unaffected.
+
+Photos are remembered by content hash, so a rescan only looks at new
+photos, and clustering is incremental: new faces join the closest existing
+person or start a new one. Names and merges made by the user are kept.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import math
+import os
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Protocol
+
+from .exif import JPEG_EXTENSIONS
+from .file_collections import CollectionManager
+from .hashing import hash_file
+
+logger = logging.getLogger(__name__)
+
+
+# Formats Pillow decodes without plugins
+SCANNABLE_EXTENSIONS = JPEG_EXTENSIONS | {'.png', '.webp', '.tif', '.tiff', '.bmp'}
+
+DEFAULT_SIMILARITY = 0.5      # Cosine similarity for two faces to count as the same person
+DEFAULT_MIN_FACES = 2         # Faces a person needs before getting a collection
+DEFAULT_MIN_SCORE = 0.7       # Detector confidence for a face
+DEFAULT_MIN_FACE_PIXELS = 40  # Smaller faces give unreliable embeddings
+DEFAULT_PREFIX = 'People'
+EMBEDDING_SIZE = 112
+NMS_OVERLAP = 0.3
+
+
+class FaceEmbedder(Protocol):
+    """Finds faces in a photo and embeds each one."""
+
+    def faces(self, path: str) -> List[Dict[str, Any]]:
+        """Return {'box': [left, top, right, bottom], 'embedding': [floats]} per face."""
+        ...
+
+
+class OnnxFaceEmbedder:
+    """
+    Face detection and embedding with local ONNX models.
+
+    Attributes:
+        detector_model (str): UltraFace-style detector (.onnx)
+        embedding_model (str): ArcFace-style embedder (.onnx)
+        min_score (float): Detector confidence threshold
+        min_face_pixels (int): Smallest face side kept, in pixels
+    """
+
+    def __init__(self, detector_model: str, embedding_model: str,
+                 min_score: float = DEFAULT_MIN_SCORE, min_face_pixels: int = DEFAULT_MIN_FACE_PIXELS):
+        """
+        Initialize the embedder (models are loaded on first use).
+
+        Args:
+            detector_model (str): Path to the detector model
+            embedding_model (str): Path to the embedding model
+            min_score (float): Detector confidence threshold
+            min_face_pixels (int): Smallest face side kept, in pixels
+        """
+        self.detector_model = str(Path(detector_model).expanduser()) if detector_model else ''
+        self.embedding_model = str(Path(embedding_model).expanduser()) if embedding_model else ''
+        self.min_score = min_score
+        self.min_face_pixels = min_face_pixels
+        self._detector = None
+        self._embedder = None
+
+    def unavailable_reason(self) -> Optional[str]:
+        """Why faces cannot be processed, or None if everything is in place."""
+        missing = [name for name in ('onnxruntime', 'numpy', 'PIL') if not _importable(name)]
+        if missing:
+            return f"Missing optional dependencies: {', '.join(missing)} (pip install onnxruntime numpy pillow)"
+        for label, model in (('detector_model', self.detector_model), ('embedding_model', self.embedding_model)):
+            if not model:
+                return f"faces.{label} is not set"
+            if not Path(model).is_file():
+                return f"faces.{label} not found: {model}"
+        return None
+
+    def _sessions(self):
+        if self._detector is None:
+            import onnxruntime
+            options = {'providers': ['CPUExecutionProvider']}
+            self._detector = onnxruntime.InferenceSession(self.detector_model, **options)
+            self._embedder = onnxruntime.InferenceSession(self.embedding_model, **options)
+        return self._detector, self._embedder
+
+    def faces(self, path: str) -> List[Dict[str, Any]]:
+        """
+        Detect and embed the faces in a photo.
+
+        Args:
+            path (str): Photo file
+
+        Returns:
+            List[Dict]: {'box': [left, top, right, bottom], 'embedding': [floats]} per face
+        """
+        import numpy as np
+        from PIL import Image, ImageOps
+
+        detector, embedder = self._sessions()
+        with Image.open(path) as opened:
+            image = ImageOps.exif_transpose(opened).convert('RGB')
+        width, height = image.size
+
+        # Detector input: NCHW, size taken from the model, (x - 127) / 128
+        _, _, in_h, in_w = detector.get_inputs()[0].shape
+        resized = np.asarray(image.resize((int(in_w), int(in_h))), dtype=np.float32)
+        blob = ((resized - 127.0) / 128.0).transpose(2, 0, 1)[np.newaxis]
+        scores, boxes = detector.run(None, {detector.get_inputs()[0].name: blob})[:2]
+
+        candidates = []
+        for score, box in zip(scores[0][:, 1], boxes[0]):
+            if score < self.min_score:
+                continue
+            left, top = max(0, int(box[0] * width)), max(0, int(box[1] * height))
+            right, bottom = min(width, int(box[2] * width)), min(height, int(box[3] * height))
+            if min(right - left, bottom - top) >= self.min_face_pixels:
+                candidates.append((float(score), [left, top, right, bottom]))
+
+        results = []
+        embed_input = embedder.get_inputs()[0]
+        channels_last = embed_input.shape[-1] == 3
+        for _, box in _suppress_overlaps(candidates):
+            crop = image.crop(_square(box, width, height)).resize((EMBEDDING_SIZE, EMBEDDING_SIZE))
+            face = (np.asarray(crop, dtype=np.float32) - 127.5) / 128.0
+            face = face[np.newaxis] if channels_last else face.transpose(2, 0, 1)[np.newaxis]
+            vector = embedder.run(None, {embed_input.name: face})[0][0]
+            results.append({'box': box, 'embedding': normalize([float(v) for v in vector])})
+        return results
+
+
+def _importable(module: str) -> bool:
+    import importlib.util
+    return importlib.util.find_spec(module) is not None
+
+
+def _square(box: List[int], width: int, height: int) -> tuple:
+    """Grow a face box into a square with some margin, as the embedder expects."""
+    left, top, right, bottom = box
+    side = max(right - left, bottom - top) * 1.2
+    cx, cy = (left + right) / 2, (top + bottom) / 2
+    return (max(0, int(cx - side / 2)), max(0, int(cy - side / 2)),
+            min(width, int(cx + side / 2)), min(height, int(cy + side / 2)))
+
+
+def _suppress_overlaps(candidates: List[tuple]) -> List[tuple]:
+    """Non-maximum suppression: keep the best box of each overlapping group."""
+    kept: List[tuple] = []
+    for score, box in sorted(candidates, key=lambda c: c[0], reverse=True):
+        if all(_overlap(box, other) < NMS_OVERLAP for _, other in kept):
+            kept.append((score, box))
+    return kept
+
+
+def _overlap(a: List[int], b: List[int]) -> float:
+    """Intersection over union of two boxes."""
+    width = min(a[2], b[2]) - max(a[0], b[0])
+    height = min(a[3], b[3]) - max(a[1], b[1])
+    if width <= 0 or height <= 0:
+        return 0.0
+    inter = width * height
+    union = (a[2] - a[0]) * (a[3] - a[1]) + (b[2] - b[0]) * (b[3] - b[1]) - inter
+    return inter / union if union else 0.0
+
+
+def normalize(vector: List[float]) -> List[float]:
+    """Scale a vector to unit length."""
+    length = math.sqrt(sum(v * v for v in vector))
+    return [v / length for v in vector] if length else list(vector)
+
+
+def cosine(a: List[float], b: List[float]) -> float:
+    """Cosine similarity of two vectors."""
+    dot = sum(x * y for x, y in zip(a, b))
+    length = math.sqrt(sum(x * x for x in a)) * math.sqrt(sum(y * y for y in b))
+    return dot / length if length else 0.0
+
+
+class FaceClusterer:
+    """
+    Scans photos for faces and clusters them into people.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        settings (Dict): The faces settings
+        embedder: Face embedder (ONNX by default)
+    """
+
+    def __init__(self, config, db_manager, embedder: Optional[FaceEmbedder] = None):
+        """
+        Initialize face clusterer.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+            embedder (optional): Face embedder to use instead of the ONNX models
+        """
+        self.config = config
+        self.db_manager = db_manager
+        settings = getattr(config, 'faces_settings', None)
+        self.settings: Dict[str, Any] = settings if isinstance(settings, dict) else {}
+        self._injected = embedder is not None
+        self.embedder = embedder or OnnxFaceEmbedder(
+            self.settings.get('detector_model', ''),
+            self.settings.get('embedding_model', ''),
+            min_score=float(self.settings.get('min_score', DEFAULT_MIN_SCORE)),
+            min_face_pixels=int(self.settings.get('min_face_pixels', DEFAULT_MIN_FACE_PIXELS))
+        )
+        self.similarity = float(self.settings.get('similarity', DEFAULT_SIMILARITY))
+        self.min_faces = int(self.settings.get('min_faces', DEFAULT_MIN_FACES))
+        self.prefix = str(self.settings.get('collection_prefix') or DEFAULT_PREFIX)
+
+    def unavailable_reason(self) -> Optional[str]:
+        """Why face clustering cannot run, or None if it can."""
+        if self._injected:
+            return None
+        if not self.settings.get('enabled'):
+            return "Face clustering is off (set faces.enabled and the model paths in config.json)"
+        return self.embedder.unavailable_reason()
+
+    # ==================== Scanning ====================
+
+    def scan(self, folder: str, rescan: bool = False) -> Dict[str, Any]:
+        """
+        Find faces in the photos below a folder and cluster them.
+
+        Args:
+            folder (str): Folder to scan (recursively)
+            rescan (bool): Process photos again even if already scanned
+
+        Returns:
+            Dict: Result with 'success', 'message', 'scanned', 'faces',
+                  'skipped' (already scanned), 'failed' and 'clustering'
+        """
+        reason = self.unavailable_reason()
+        if reason:
+            return {'success': False, 'message': reason}
+        root = Path(folder).expanduser().resolve()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {folder}"}
+
+        scanned, found, skipped, failed = 0, 0, 0, []
+        for path in self._photos(root):
+            try:
+                content_hash = hash_file(path)
+            except OSError as e:
+                failed.append({'path': str(path), 'message': str(e)})
+                continue
+            if not rescan and self.db_manager.is_face_scanned(content_hash):
+                self.db_manager.update_face_scan_path(content_hash, str(path))
+                skipped += 1
+                continue
+            try:
+                faces = self.embedder.faces(str(path))
+            except Exception as e:
+                logger.warning(f"Face detection failed for {path}: {e}")
+                failed.append({'path': str(path), 'message': str(e)})
+                continue
+            self.db_manager.record_face_scan(content_hash, str(path), [
+                {'box': json.dumps(face.get('box')),
+                 'embedding': json.dumps([round(v, 6) for v in normalize(face['embedding'])])}
+                for face in faces
+            ])
+            scanned += 1
+            found += len(faces)
+
+        clustering = self.cluster()
+        return {
+            'success': True,
+            'message': (f"Scanned {scanned} photo(s), found {found} face(s); "
+                        f"{clustering['people']} people"),
+            'scanned': scanned,
+            'faces': found,
+            'skipped': skipped,
+            'failed': failed,
+            'clustering': clustering
+        }
+
+    @staticmethod
+    def _photos(root: Path) -> List[Path]:
+        photos = []
+        for dirpath, dirnames, filenames in os.walk(root):
+            dirnames[:] = [d for d in dirnames if not d.startswith('.')]
+            for name in filenames:
+                path = Path(dirpath) / name
+                if not name.startswith('.') and path.suffix.lower() in SCANNABLE_EXTENSIONS and not path.is_symlink():
+                    photos.append(path)
+        return sorted(photos)
+
+    # ==================== Clustering ====================
+
+    def cluster(self) -> Dict[str, Any]:
+        """
+        Assign unassigned faces to people.
+
+        Each face joins the person whose mean embedding is most similar,
+        if at least `faces.similarity`; otherwise it starts a new person.
+        Existing assignments (including the user's merges) are kept.
+
+        Returns:
+            Dict: {'assigned': faces joining existing people, 'new_people', 'people': total}
+        """
+        centroids: Dict[int, List[float]] = {}
+        counts: Dict[int, int] = {}
+        for face in self.db_manager.list_faces():
+            person_id = face['person_id']
+            if person_id is None:
+                continue
+            vector = json.loads(face['embedding'])
+            total = centroids.setdefault(person_id, [0.0] * len(vector))
+            for i, v in enumerate(vector):
+                total[i] += v
+            counts[person_id] = counts.get(person_id, 0) + 1
+
+        assigned, new_people = 0, 0
+        for face in self.db_manager.list_faces(unassigned=True):
+            vector = json.loads(face['embedding'])
+            best_id, best = None, self.similarity
+            for person_id, total in centroids.items():
+                score = cosine(vector, total)
+                if score >= best:
+                    best_id, best = person_id, score
+            if best_id is None:
+                best_id = self.db_manager.create_person()
+                centroids[best_id] = [0.0] * len(vector)
+                counts[best_id] = 0
+                new_people += 1
+            else:
+                assigned += 1
+            self.db_manager.assign_face(face['id'], best_id)
+            # The summed vector points the same way as the mean, which is all cosine needs
+            centroids[best_id] = [t + v for t, v in zip(centroids[best_id], vector)]
+            counts[best_id] += 1
+
+        return {'assigned': assigned, 'new_people': new_people, 'people': len(self.db_manager.list_people())}
+
+    # ==================== People ====================
+
+    def display_name(self, person: Dict[str, Any]) -> str:
+        """A person's name, or 'Person <id>' until they are named."""
+        return person.get('name') or f"Person {person['id']}"
+
+    def people(self) -> List[Dict[str, Any]]:
+        """
+        List people found so far.
+
+        Returns:
+            List[Dict]: {'id', 'name', 'display_name', 'face_count', 'photo_count',
+                         'sample'} per person, most photographed first
+        """
+        result = []
+        for row in self.db_manager.list_people():
+            faces = self.db_manager.list_faces(person_id=row['id'])
+            result.append({
+                'id': row['id'],
+                'name': row['name'],
+                'display_name': self.display_name(row),
+                'face_count': row['face_count'],
+                'photo_count': row['photo_count'],
+                'sample': faces[0]['file_path'] if faces else None
+            })
+        return result
+
+    def photos_of(self, person_id: int) -> List[str]:
+        """Photos a person appears in, where they were last seen."""
+        return list(dict.fromkeys(face['file_path'] for face in self.db_manager.list_faces(person_id=person_id)))
+
+    def people_in(self, file_path: str) -> List[str]:
+        """Names of the people recognized in one photo (for tagging)."""
+        try:
+            content_hash = hash_file(file_path)
+        except OSError:
+            return []
+        ids = {face['person_id'] for face in self.db_manager.list_faces()
+               if face['content_hash'] == content_hash and face['person_id'] is not None}
+        return [self.display_name(self.db_manager.get_person(i)) for i in sorted(ids)]
+
+    def rename_person(self, person_id: int, name: str) -> Dict[str, Any]:
+        """
+        Name a person; their collection follows the new name on the next sync.
+
+        Args:
+            person_id (int): Person ID
+            name (str): New name (empty to unname)
+
+        Returns:
+            Dict: Result with 'success' and 'message'
+        """
+        person = self.db_manager.get_person(person_id)
+        if person is None:
+            return {'success': False, 'message': f"Person {person_id} not found"}
+        old_collection = self._collection_name(person)
+        self.db_manager.rename_person(person_id, name.strip() or None)
+        self._collections().delete(old_collection)
+        return {'success': True, 'message': f"Person {person_id} is now {name.strip() or 'unnamed'}"}
+
+    def merge_people(self, keep_id: int, other_id: int) -> Dict[str, Any]:
+        """
+        Merge two people found separately who are the same person.
+
+        Args:
+            keep_id (int): Person to keep
+            other_id (int): Person whose faces move to keep_id
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'moved'
+        """
+        if keep_id == other_id:
+            return {'success': False, 'message': 'Cannot merge a person with themselves'}
+        keep, other = self.db_manager.get_person(keep_id), self.db_manager.get_person(other_id)
+        if keep is None or other is None:
+            missing = keep_id if keep is None else other_id
+            return {'success': False, 'message': f"Person {missing} not found"}
+        moved = self.db_manager.merge_people(keep_id, other_id)
+        self._collections().delete(self._collection_name(other))
+        return {'success': True, 'moved': moved,
+                'message': f"Merged {self.display_name(other)} into {self.display_name(keep)} ({moved} face(s))"}
+
+    # ==================== Collections ====================
+
+    def _collections(self) -> CollectionManager:
+        return CollectionManager(self.config, self.db_manager)
+
+    def _collection_name(self, person: Dict[str, Any]) -> str:
+        return f"{self.prefix}: {self.display_name(person)}"
+
+    def sync_collections(self) -> Dict[str, Any]:
+        """
+        Create or refresh a virtual collection per person with enough faces.
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'collections' (names)
+        """
+        collections = self._collections()
+        names, added = [], 0
+        for person in self.db_manager.list_people():
+            if person['face_count'] < self.min_faces:
+                continue
+            photos = [p for p in self.photos_of(person['id']) if Path(p).exists()]
+            if not photos:
+                continue
+            name = self._collection_name(person)
+            if collections.get(name) is None:
+                created = collections.create(name, f"Photos of {self.display_name(person)}")
+                if not created['success']:
+                    logger.warning(f"Cannot create collection {name}: {created['message']}")
+                    continue
+            added += collections.add(name, photos, added_by='faces')['added']
+            names.append(name)
+        return {'success': True, 'collections': names,
+                'message': f"{len(names)} people collection(s), {added} photo(s) added"}
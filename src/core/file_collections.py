@@ -0,0 +1,319 @@
+"""
+Collections Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module provides virtual collections: named sets of file references
+that are decoupled from the disk layout, for cases where physically
+reorganizing files isn't wanted ("Tax 2024", "Wedding photos").
+
+Items are referenced by content hash, with their last known location. When
+a file has moved, its location is recovered by following the operation
+journal, so collections keep working after the app organizes files.
+Collections can be filled by hand or by a rule (folders plus extensions,
+keywords or glob patterns), browsed, and exported as a JSON, CSV or M3U
+list or as a folder of copies - nothing is moved on disk.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import csv
+import fnmatch
+import json
+import logging
+import os
+import shutil
+import sqlite3
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .hashing import hash_file
+
+logger = logging.getLogger(__name__)
+
+
+EXPORT_FORMATS = ('json', 'csv', 'm3u', 'folder')
+
+
+class CollectionManager:
+    """
+    Creates, fills, browses and exports virtual collections.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+    """
+
+    def __init__(self, config, db_manager):
+        """
+        Initialize collection manager.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+        """
+        self.config = config
+        self.db_manager = db_manager
+
+    @staticmethod
+    def _describe(row: Dict[str, Any]) -> Dict[str, Any]:
+        return {
+            'id': row['id'],
+            'name': row['name'],
+            'description': row['description'] or '',
+            'rule': json.loads(row['rule']) if row['rule'] else None,
+            'item_count': row['item_count'],
+            'created_at': row['created_at']
+        }
+
+    def _require(self, name: str) -> Dict[str, Any]:
+        row = self.db_manager.get_collection(name)
+        if row is None:
+            raise KeyError(f"Collection '{name}' not found")
+        return row
+
+    # ==================== Collections ====================
+
+    def create(self, name: str, description: str = '', rule: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Create a collection, optionally with a populating rule.
+
+        Args:
+            name (str): Collection name
+            description (str): Optional description
+            rule (Dict, optional): {'folders': [...], 'extensions', 'keywords', 'patterns'}
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'collection'
+        """
+        name = name.strip()
+        if not name:
+            return {'success': False, 'message': 'A collection needs a name'}
+        if rule is not None and not rule.get('folders'):
+            return {'success': False, 'message': 'A collection rule needs at least one folder'}
+        try:
+            self.db_manager.create_collection(name, description, json.dumps(rule) if rule else None)
+        except sqlite3.IntegrityError:
+            return {'success': False, 'message': f"Collection '{name}' already exists"}
+
+        result = {'success': True, 'message': f"Collection '{name}' created"}
+        if rule:
+            added = self.populate(name)
+            result['message'] += f" with {added} file(s)"
+        result['collection'] = self.get(name)
+        return result
+
+    def get(self, name: str) -> Optional[Dict[str, Any]]:
+        """Get a collection by name."""
+        row = self.db_manager.get_collection(name)
+        return self._describe(row) if row else None
+
+    def list(self) -> List[Dict[str, Any]]:
+        """
+        List collections.
+
+        Returns:
+            List[Dict]: Collections with 'name', 'description', 'rule' and 'item_count'
+        """
+        return [self._describe(row) for row in self.db_manager.list_collections()]
+
+    def delete(self, name: str) -> bool:
+        """
+        Delete a collection (the referenced files are not touched).
+
+        Args:
+            name (str): Collection name
+
+        Returns:
+            bool: True if it existed
+        """
+        row = self.db_manager.get_collection(name)
+        if row is None:
+            return False
+        self.db_manager.delete_collection(row['id'])
+        return True
+
+    # ==================== Items ====================
+
+    def add(self, name: str, paths: List[str], added_by: str = 'user') -> Dict[str, Any]:
+        """
+        Add files (or every file below given folders) by reference.
+
+        Args:
+            name (str): Collection name
+            paths (List[str]): Files or folders
+            added_by (str): 'user', 'rule' or 'faces'
+
+        Returns:
+            Dict: Result with 'success', 'message', 'added' and 'skipped'
+        """
+        collection = self._require(name)
+        added, skipped = 0, []
+        for file_path in self._expand(paths):
+            try:
+                content_hash = hash_file(file_path)
+                size = file_path.stat().st_size
+            except OSError as e:
+                skipped.append({'path': str(file_path), 'message': str(e)})
+                continue
+            if self.db_manager.add_collection_item(collection['id'], content_hash, str(file_path), size, added_by):
+                added += 1
+        return {
+            'success': True,
+            'message': f"Added {added} file(s) to '{name}'",
+            'added': added,
+            'skipped': skipped
+        }
+
+    @staticmethod
+    def _expand(paths: List[str]) -> List[Path]:
+        files = []
+        for raw in paths:
+            path = Path(raw).expanduser().resolve()
+            if path.is_file():
+                files.append(path)
+            elif path.is_dir():
+                for dirpath, dirnames, filenames in os.walk(path):
+                    dirnames[:] = [d for d in dirnames if not d.startswith('.')]
+                    files.extend(Path(dirpath) / f for f in filenames if not f.startswith('.'))
+        return [f for f in files if not f.is_symlink()]
+
+    def remove(self, name: str, path_or_hash: str) -> bool:
+        """
+        Remove one reference, given by its file path or content hash.
+
+        Args:
+            name (str): Collection name
+            path_or_hash (str): File path (current or last known) or content hash
+
+        Returns:
+            bool: True if a reference was removed
+        """
+        collection = self._require(name)
+        target = str(Path(path_or_hash).expanduser().resolve())
+        for item in self.db_manager.list_collection_items(collection['id']):
+            if path_or_hash in (item['content_hash'], item['file_path']) or target == item['file_path']:
+                return self.db_manager.remove_collection_item(collection['id'], item['content_hash'])
+        return False
+
+    def items(self, name: str) -> List[Dict[str, Any]]:
+        """
+        Browse a collection, locating each file where it lives now.
+
+        Files moved by the app are followed through the journal and their
+        known location is updated.
+
+        Args:
+            name (str): Collection name
+
+        Returns:
+            List[Dict]: Items with 'content_hash', 'path', 'size', 'added_by',
+                        'added_at' and 'available'
+        """
+        collection = self._require(name)
+        results = []
+        for item in self.db_manager.list_collection_items(collection['id']):
+            path = item['file_path']
+            if not path or not os.path.isfile(path):
+                moved = self.db_manager.find_moved_path(path) if path else None
+                if moved and os.path.isfile(moved):
+                    self.db_manager.update_collection_item_path(item['id'], moved)
+                    path = moved
+            results.append({
+                'content_hash': item['content_hash'],
+                'path': path,
+                'name': Path(path).name if path else None,
+                'size': item['file_size'],
+                'added_by': item['added_by'],
+                'added_at': item['added_at'],
+                'available': bool(path) and os.path.isfile(path)
+            })
+        return results
+
+    def populate(self, name: str) -> int:
+        """
+        Add every file matching the collection's rule.
+
+        Args:
+            name (str): Collection name
+
+        Returns:
+            int: Number of files newly added
+        """
+        collection = self._require(name)
+        rule = json.loads(collection['rule']) if collection['rule'] else None
+        if not rule:
+            return 0
+
+        extensions = {e.lower() if e.startswith('.') else f'.{e.lower()}' for e in rule.get('extensions') or []}
+        keywords = [k.lower() for k in rule.get('keywords') or []]
+        patterns = [p.lower() for p in rule.get('patterns') or []]
+
+        matches = []
+        for file_path in self._expand(rule.get('folders') or []):
+            name_lower = file_path.name.lower()
+            if extensions and file_path.suffix.lower() not in extensions:
+                continue
+            if keywords and not any(k in name_lower for k in keywords):
+                continue
+            if patterns and not any(fnmatch.fnmatch(name_lower, p) for p in patterns):
+                continue
+            matches.append(str(file_path))
+        return self.add(name, matches, added_by='rule')['added'] if matches else 0
+
+    # ==================== Export ====================
+
+    def export(self, name: str, target: str, fmt: str = 'json') -> Dict[str, Any]:
+        """
+        Export a collection without touching the referenced files.
+
+        Args:
+            name (str): Collection name
+            target (str): Output file (json, csv, m3u) or folder (folder: copies)
+            fmt (str): One of EXPORT_FORMATS
+
+        Returns:
+            Dict: Result with 'success', 'message', 'path' and 'exported'
+        """
+        if fmt not in EXPORT_FORMATS:
+            return {'success': False, 'message': f'Unknown export format: {fmt}'}
+        items = [i for i in self.items(name) if i['available']]
+        out = Path(target).expanduser()
+
+        try:
+            if fmt == 'folder':
+                out.mkdir(parents=True, exist_ok=True)
+                for item in items:
+                    dest = out / item['name']
+                    counter = 1
+                    while dest.exists():
+                        dest = out / f"{Path(item['name']).stem}_{counter}{Path(item['name']).suffix}"
+                        counter += 1
+                    shutil.copy2(item['path'], dest)
+            else:
+                out.parent.mkdir(parents=True, exist_ok=True)
+                with open(out, 'w', encoding='utf-8', newline='') as f:
+                    if fmt == 'json':
+                        json.dump({'collection': name, 'items': items}, f, indent=2)
+                    elif fmt == 'csv':
+                        writer = csv.DictWriter(f, fieldnames=['name', 'path', 'size', 'content_hash', 'added_at'],
+                                                extrasaction='ignore')
+                        writer.writeheader()
+                        writer.writerows(items)
+                    else:
+                        f.write('#EXTM3U\n')
+                        f.writelines(f"{item['path']}\n" for item in items)
+        except OSError as e:
+            return {'success': False, 'message': f'Export failed: {e}'}
+
+        return {
+            'success': True,
+            'message': f"Exported {len(items)} file(s) from '{name}' to {out}",
+            'path': str(out),
+            'exported': len(items)
+        }
@@ -0,0 +1,591 @@
+"""
+File Listing Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module lists folders for the file browser of the GUI and dashboard:
+one level (list_files) or a whole tree in one call (list_files_recursive),
+so showing all of Downloads does not take a request per subfolder.
+
+Each entry is a dict:
+
+    {"name", "path" (relative to the listed folder, "/"-separated),
+     "is_dir", "is_symlink", "size" (None for folders),
+     "created", "modified", "accessed" (RFC 3339 local times such as
+         "2024-03-01T09:30:00.000+01:00"; None when the system does not
+         record it - Linux keeps no creation time),
+     "is_hidden" (a dot name, or the Windows / macOS hidden flag),
+     "is_readonly", "extension" (lowercase, without the dot; None for folders),
+     "depth" (0 for the listed folder's own entries),
+     "mime_type" (from the file's content, see core.filetype; None for folders)}
+
+A file browser showing a big folder in a virtual list asks for one page
+at a time (list_files_paged): the folder is sorted here - by name, size,
+date or category, folders first - and only the page's files are sniffed
+for their type.
+
+Listings say which shape they use in 'version' (LISTING_VERSION, now 2).
+Version 1 - "modified" as seconds since the epoch and none of the fields
+added since - is still given to callers that ask for version=1.
+
+The recursive listing never enters a folder twice, so symlink loops (and
+bind mounts showing a parent again) end instead of recursing forever.
+
+Folders with hundreds of thousands of entries are better streamed
+(stream_files): several folders are read at once and entries are handed
+over in batches as they are found, in no particular order between
+folders, with an estimate of the total for a progress bar:
+
+    {"event": "files://batch", "payload": {"version", "root", "items", "done", "estimate"}}
+    {"event": "files://done", "payload": {"version", "root", "total", "truncated", "skipped", "ignored",
+                                          "cancelled"}}
+
+The estimate is the entries found so far plus, for every folder still to
+be read, the average entries per folder read so far; it settles as the
+walk goes on and equals 'total' at the end.
+Files and folders the ignore rules exclude (see core.ignore_rules: .git,
+node_modules, temp files, a folder's .organizerignore) are left out and
+counted in 'ignored'.
+
+Files dragged onto the window are taken in by ingest_dropped_paths():
+dropped folders are expanded to their files, paths that are missing,
+unreadable or blacklisted are turned away with a reason, and the rest
+come back as entries (with absolute paths) ready for classification.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import stat as stat_module
+import threading
+import time
+from concurrent.futures import FIRST_COMPLETED, ThreadPoolExecutor, wait
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, Dict, Any, List, Optional, Set, Tuple
+
+from .disk_usage import categories_by_extension
+from .filetype import sniff_mime_type
+from .ignore_rules import IgnoreRules
+
+logger = logging.getLogger(__name__)
+
+
+MAX_ENTRIES = 50000
+MAX_PAGE = 1000
+SORT_KEYS = ('name', 'size', 'date', 'category')
+LISTING_VERSION = 2
+LISTING_VERSIONS = (1, 2)
+WINDOWS_HIDDEN = 0x2      # FILE_ATTRIBUTE_HIDDEN
+WINDOWS_READONLY = 0x1    # FILE_ATTRIBUTE_READONLY
+MACOS_HIDDEN = 0x8000     # UF_HIDDEN
+STREAM_BATCH = 500
+FLUSH_INTERVAL = 0.25   # Seconds a partial batch may wait, so slow walks still show progress
+WALK_WORKERS = min(8, (os.cpu_count() or 2) * 2)
+
+EVENT_FILES_BATCH = 'files://batch'
+EVENT_FILES_DONE = 'files://done'
+
+
+def _resolve_folder(directory: str) -> Path:
+    path = Path(directory).expanduser().resolve()
+    if not path.is_dir():
+        raise NotADirectoryError(f"Not a folder: {path}")
+    return path
+
+
+def _rfc3339(timestamp: Optional[float]) -> Optional[str]:
+    if timestamp is None:
+        return None
+    try:
+        return datetime.fromtimestamp(timestamp).astimezone().isoformat(timespec='milliseconds')
+    except (OverflowError, OSError, ValueError):
+        return None
+
+
+def _check_version(version: int) -> None:
+    if version not in LISTING_VERSIONS:
+        raise ValueError(f"Unknown listing version {version} (use {', '.join(map(str, LISTING_VERSIONS))})")
+
+
+def _entry(entry: os.DirEntry, relative: str, depth: int, follow_symlinks: bool,
+           version: int = LISTING_VERSION) -> Dict[str, Any]:
+    try:
+        is_dir = entry.is_dir(follow_symlinks=follow_symlinks)
+        stat = entry.stat(follow_symlinks=follow_symlinks)
+        size, modified = (None if is_dir else stat.st_size), stat.st_mtime
+    except OSError:
+        is_dir, size, modified, stat = False, None, None, None  # e.g. a dangling symlink
+    if version == 1:
+        return {'name': entry.name, 'path': relative, 'is_dir': is_dir, 'is_symlink': entry.is_symlink(),
+                'size': size, 'modified': modified, 'depth': depth, 'mime_type': None}
+
+    attributes = getattr(stat, 'st_file_attributes', 0) if stat is not None else 0
+    flags = getattr(stat, 'st_flags', 0) if stat is not None else 0
+    if stat is None:
+        created = accessed = None
+    else:
+        # Windows reports creation as st_ctime; elsewhere st_ctime is the last metadata change
+        created = getattr(stat, 'st_birthtime', stat.st_ctime if os.name == 'nt' else None)
+        accessed = stat.st_atime
+    suffix = Path(entry.name).suffix
+    return {
+        'name': entry.name,
+        'path': relative,
+        'is_dir': is_dir,
+        'is_symlink': entry.is_symlink(),
+        'size': size,
+        'created': _rfc3339(created),
+        'modified': _rfc3339(modified),
+        'accessed': _rfc3339(accessed),
+        'is_hidden': entry.name.startswith('.') or bool(attributes & WINDOWS_HIDDEN) or bool(flags & MACOS_HIDDEN),
+        'is_readonly': stat is not None and (bool(attributes & WINDOWS_READONLY)
+                                             or not stat.st_mode & stat_module.S_IWUSR),
+        'extension': suffix[1:].lower() if suffix and not is_dir else None,
+        'depth': depth,
+        'mime_type': None
+    }
+
+
+def list_files(directory: str, include_hidden: bool = False, respect_ignore: bool = True,
+               detect_types: bool = True, version: int = LISTING_VERSION) -> Dict[str, Any]:
+    """
+    List the entries of one folder.
+
+    Args:
+        directory (str): Folder to list
+        include_hidden (bool): Include names starting with '.'
+        respect_ignore (bool): Leave out what the ignore rules exclude
+        detect_types (bool): Read each file's first bytes for its 'mime_type'
+        version (int): Entry shape (see the module docstring)
+
+    Returns:
+        Dict: As list_files_recursive(); 'items' folders first, then by name
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+        PermissionError: If the folder cannot be read
+        ValueError: If the version is unknown
+    """
+    return list_files_recursive(directory, max_depth=0, include_hidden=include_hidden, respect_ignore=respect_ignore,
+                                detect_types=detect_types, version=version)
+
+
+def list_files_paged(directory: str, offset: int = 0, limit: int = 100, sort_by: str = 'name', order: str = 'asc',
+                     include_hidden: bool = False, respect_ignore: bool = True, detect_types: bool = True,
+                     config=None, version: int = LISTING_VERSION) -> Dict[str, Any]:
+    """
+    One page of a folder's entries, sorted (folders first).
+
+    Args:
+        directory (str): Folder to list
+        offset (int): Entries to skip
+        limit (int): Entries in the page (at most MAX_PAGE)
+        sort_by (str): 'name', 'size', 'date' (modified) or 'category'
+        order (str): 'asc' or 'desc'
+        include_hidden (bool): Include names starting with '.'
+        respect_ignore (bool): Leave out what the ignore rules exclude
+        detect_types (bool): Read the page's files' first bytes for their 'mime_type'
+        config: Configuration object whose destination rules give the categories
+        version (int): Entry shape (see the module docstring)
+
+    Returns:
+        Dict: 'version', 'root', 'items' (each also with 'category': None
+              for folders, 'Other' for unknown files), 'offset', 'limit',
+              'total' (entries in the folder), 'sort_by', 'order' and 'ignored'
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+        PermissionError: If the folder cannot be read
+        ValueError: If the sort key, order, page or version is invalid
+    """
+    _check_version(version)
+    if sort_by not in SORT_KEYS:
+        raise ValueError(f"Cannot sort by {sort_by!r} (use {', '.join(SORT_KEYS)})")
+    if order not in ('asc', 'desc'):
+        raise ValueError(f"Unknown order {order!r} (use asc or desc)")
+    if offset < 0 or not 0 < limit <= MAX_PAGE:
+        raise ValueError(f"Pages start at offset 0 or later and hold 1 to {MAX_PAGE} entries")
+    root = _resolve_folder(directory)
+    rules = IgnoreRules.for_folder(str(root)) if respect_ignore else None
+    categories = categories_by_extension(config)
+
+    rows: List[Tuple[Any, os.DirEntry, Dict[str, Any]]] = []
+    ignored = 0
+    with os.scandir(root) as scan:
+        for entry in scan:
+            if not include_hidden and entry.name.startswith('.'):
+                continue
+            item = _entry(entry, entry.name, 0, False, version)
+            if rules is not None and rules.match(entry.name, item['is_dir']):
+                ignored += 1
+                continue
+            extension = Path(entry.name).suffix.lower().lstrip('.')
+            item['category'] = None if item['is_dir'] else categories.get(extension, 'Other')
+            rows.append((_sort_value(entry, item, sort_by), entry, item))
+
+    # Folders stay ahead of files whichever way the page is ordered
+    rows.sort(key=lambda row: row[0], reverse=order == 'desc')
+    rows.sort(key=lambda row: not row[2]['is_dir'])
+    page = [item for _, _, item in rows[offset:offset + limit]]
+    if detect_types:
+        for item in page:
+            if item['size'] is not None:
+                item['mime_type'] = sniff_mime_type(str(root / item['path']))
+    return {'version': version, 'root': str(root), 'items': page, 'offset': offset, 'limit': limit,
+            'total': len(rows), 'sort_by': sort_by, 'order': order, 'ignored': ignored}
+
+
+def _sort_value(entry: os.DirEntry, item: Dict[str, Any], sort_by: str) -> Tuple:
+    name = (entry.name.lower(), entry.name)
+    if sort_by == 'size':
+        return (item['size'] or 0,) + name
+    if sort_by == 'date':
+        try:
+            modified = entry.stat(follow_symlinks=False).st_mtime  # Cached by the entry
+        except OSError:
+            modified = 0.0
+        return (modified,) + name
+    if sort_by == 'category':
+        return (item['category'] or '',) + name
+    return name
+
+
+def list_files_recursive(directory: str, max_depth: Optional[int] = None, follow_symlinks: bool = False,
+                         include_hidden: bool = False, limit: int = MAX_ENTRIES,
+                         respect_ignore: bool = True, detect_types: bool = True,
+                         version: int = LISTING_VERSION) -> Dict[str, Any]:
+    """
+    List a folder and its subfolders.
+
+    Args:
+        directory (str): Folder to list
+        max_depth (int, optional): Subfolder levels to enter (0: only the
+                                   folder itself; default: all)
+        follow_symlinks (bool): Enter symlinked folders (loops are skipped)
+        include_hidden (bool): Include names starting with '.'
+        limit (int): Stop after this many entries
+        respect_ignore (bool): Leave out (and do not enter) what the ignore rules exclude
+        detect_types (bool): Read each file's first bytes for its 'mime_type'
+        version (int): Entry shape (see the module docstring)
+
+    Returns:
+        Dict: 'version', 'root', 'items' (each folder's entries follow it,
+              folders first, then by name), 'truncated' (the limit was
+              reached), 'skipped' ({path, reason} for loops and unreadable
+              folders) and 'ignored' (entries the ignore rules left out)
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+        PermissionError: If the folder itself cannot be read
+        ValueError: If the version is unknown
+    """
+    _check_version(version)
+    root = _resolve_folder(directory)
+    items: List[Dict[str, Any]] = []
+    skipped: List[Dict[str, str]] = []
+    root_stat = root.stat()
+    visited: Set[Tuple[int, int]] = {(root_stat.st_dev, root_stat.st_ino)}
+    rules = IgnoreRules.for_folder(str(root)) if respect_ignore else None
+    truncated = False
+    ignored = 0
+
+    def walk(folder: Path, prefix: str, depth: int) -> None:
+        nonlocal truncated, ignored
+        try:
+            with os.scandir(folder) as scan:
+                entries = [e for e in scan if include_hidden or not e.name.startswith('.')]
+        except OSError as e:
+            if depth == 0:
+                raise
+            skipped.append({'path': prefix.rstrip('/'), 'reason': e.strerror or str(e)})
+            return
+        entries.sort(key=lambda e: (not _is_dir(e, follow_symlinks), e.name.lower(), e.name))
+        for entry in entries:
+            if len(items) >= limit:
+                truncated = True
+                return
+            relative = prefix + entry.name
+            item = _entry(entry, relative, depth, follow_symlinks, version)
+            if rules is not None and rules.match(relative, item['is_dir']):
+                ignored += 1
+                continue
+            if detect_types and item['size'] is not None:
+                item['mime_type'] = sniff_mime_type(entry.path)
+            items.append(item)
+            if not item['is_dir'] or (max_depth is not None and depth >= max_depth):
+                continue
+            if item['is_symlink'] and not follow_symlinks:
+                continue
+            try:
+                stat = entry.stat(follow_symlinks=True)
+            except OSError as e:
+                skipped.append({'path': relative, 'reason': e.strerror or str(e)})
+                continue
+            key = (stat.st_dev, stat.st_ino)
+            if key in visited:
+                skipped.append({'path': relative, 'reason': 'already listed (folder loop)'})
+                continue
+            visited.add(key)
+            walk(Path(entry.path), relative + '/', depth + 1)
+            if truncated:
+                return
+
+    walk(root, '', 0)
+    if skipped:
+        logger.debug(f"Listing {root}: skipped {len(skipped)} folder(s)")
+    return {'version': version, 'root': str(root), 'items': items, 'truncated': truncated, 'skipped': skipped,
+            'ignored': ignored}
+
+
+def _is_dir(entry: os.DirEntry, follow_symlinks: bool) -> bool:
+    try:
+        return entry.is_dir(follow_symlinks=follow_symlinks)
+    except OSError:
+        return False
+
+
+def stream_files(directory: str, on_event: Callable[[str, Dict[str, Any]], None],
+                 max_depth: Optional[int] = None, follow_symlinks: bool = False, include_hidden: bool = False,
+                 respect_ignore: bool = True, detect_types: bool = False, batch_size: int = STREAM_BATCH,
+                 limit: Optional[int] = None, workers: int = WALK_WORKERS,
+                 cancel: Optional[threading.Event] = None, version: int = LISTING_VERSION) -> Dict[str, Any]:
+    """
+    Walk a folder tree with several threads, handing entries over in batches as they are found.
+
+    Args:
+        directory (str): Folder to list
+        on_event (Callable): Called as (event, payload) for every batch and at the end
+        max_depth (int, optional): Subfolder levels to enter (default: all)
+        follow_symlinks (bool): Enter symlinked folders (loops are skipped)
+        include_hidden (bool): Include names starting with '.'
+        respect_ignore (bool): Leave out (and do not enter) what the ignore rules exclude
+        detect_types (bool): Read each file's first bytes for its 'mime_type' (slower)
+        batch_size (int): Entries per 'files://batch' event (fewer when the walk is slow)
+        limit (int, optional): Stop after this many entries (default: no limit)
+        workers (int): Folders read at once
+        cancel (threading.Event, optional): Set to stop the walk early
+        version (int): Entry shape (see the module docstring)
+
+    Returns:
+        Dict: The 'files://done' payload: 'version', 'root', 'total',
+              'truncated', 'skipped', 'ignored' and 'cancelled'
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+        PermissionError: If the folder itself cannot be read
+        ValueError: If the version is unknown
+    """
+    _check_version(version)
+    root = _resolve_folder(directory)
+    rules = IgnoreRules.for_folder(str(root)) if respect_ignore else None
+    root_stat = root.stat()
+    visited: Set[Tuple[int, int]] = {(root_stat.st_dev, root_stat.st_ino)}
+    skipped: List[Dict[str, str]] = []
+    batch: List[Dict[str, Any]] = []
+    done = ignored = folders_read = 0
+    truncated = False
+    last_flush = time.monotonic()
+
+    def read(folder: str, prefix: str, depth: int):
+        """One folder's kept entries and the subfolders to enter (runs in a worker)."""
+        with os.scandir(folder) as scan:
+            entries = [e for e in scan if include_hidden or not e.name.startswith('.')]
+        entries.sort(key=lambda e: (not _is_dir(e, follow_symlinks), e.name.lower(), e.name))
+        kept, subfolders, left_out = [], [], 0
+        for entry in entries:
+            relative = prefix + entry.name
+            item = _entry(entry, relative, depth, follow_symlinks, version)
+            if rules is not None and rules.match(relative, item['is_dir']):
+                left_out += 1
+                continue
+            if detect_types and item['size'] is not None:
+                item['mime_type'] = sniff_mime_type(entry.path)
+            kept.append(item)
+            if item['is_dir'] and (max_depth is None or depth < max_depth) \
+                    and (follow_symlinks or not item['is_symlink']):
+                subfolders.append(entry)
+        return kept, subfolders, left_out
+
+    def estimate(pending: int) -> int:
+        return done + len(batch) + round(pending * (done + len(batch)) / max(folders_read, 1))
+
+    def flush(pending: int) -> None:
+        nonlocal done, batch, last_flush
+        last_flush = time.monotonic()
+        if batch:
+            payload = {'version': version, 'root': str(root), 'items': batch, 'done': done + len(batch),
+                       'estimate': estimate(pending)}
+            done += len(batch)
+            batch = []
+            on_event(EVENT_FILES_BATCH, payload)
+
+    with ThreadPoolExecutor(max_workers=max(1, workers), thread_name_prefix='walk') as pool:
+        running = {pool.submit(read, str(root), '', 0): ('', 0)}
+        while running:
+            finished, _ = wait(running, return_when=FIRST_COMPLETED)
+            for future in finished:
+                prefix, depth = running.pop(future)
+                try:
+                    kept, subfolders, left_out = future.result()
+                except OSError as e:
+                    if depth == 0:
+                        raise
+                    skipped.append({'path': prefix.rstrip('/'), 'reason': e.strerror or str(e)})
+                    continue
+                folders_read += 1
+                ignored += left_out
+                if truncated or (cancel is not None and cancel.is_set()):
+                    continue
+                for item in kept:
+                    if limit is not None and done + len(batch) >= limit:
+                        truncated = True
+                        break
+                    batch.append(item)
+                    if len(batch) >= batch_size:
+                        flush(len(running))
+                if truncated:
+                    continue
+                for entry in subfolders:
+                    relative = prefix + entry.name
+                    try:
+                        stat = entry.stat(follow_symlinks=True)
+                    except OSError as e:
+                        skipped.append({'path': relative, 'reason': e.strerror or str(e)})
+                        continue
+                    key = (stat.st_dev, stat.st_ino)
+                    if key in visited:
+                        skipped.append({'path': relative, 'reason': 'already listed (folder loop)'})
+                        continue
+                    visited.add(key)
+                    running[pool.submit(read, entry.path, relative + '/', depth + 1)] = (relative + '/', depth + 1)
+            if batch and time.monotonic() - last_flush >= FLUSH_INTERVAL:
+                flush(len(running))
+
+    flush(0)
+    summary = {'version': version, 'root': str(root), 'total': done, 'truncated': truncated, 'skipped': skipped,
+               'ignored': ignored, 'cancelled': bool(cancel is not None and cancel.is_set())}
+    on_event(EVENT_FILES_DONE, summary)
+    return summary
+
+
+class _DroppedEntry:
+    """A dropped file, standing in for the os.DirEntry listing entries are made from."""
+
+    def __init__(self, path: Path):
+        self.name = path.name
+        self.path = str(path)
+
+    def is_dir(self, follow_symlinks: bool = True) -> bool:
+        return stat_module.S_ISDIR(self.stat(follow_symlinks).st_mode)
+
+    def stat(self, follow_symlinks: bool = True) -> os.stat_result:
+        return os.stat(self.path, follow_symlinks=follow_symlinks)
+
+    def is_symlink(self) -> bool:
+        return os.path.islink(self.path)
+
+
+def ingest_dropped_paths(paths: List[str], include_hidden: bool = False, respect_ignore: bool = True,
+                         detect_types: bool = True, limit: int = MAX_ENTRIES, config=None,
+                         version: int = LISTING_VERSION) -> Dict[str, Any]:
+    """
+    The files dropped on the window, ready to be classified.
+
+    Dropped folders are expanded to the files below them. Paths that are
+    relative, missing, unreadable or blacklisted are turned away, and what
+    the ignore rules exclude is left out, as in a listing.
+
+    Args:
+        paths (List[str]): Dropped files and folders
+        include_hidden (bool): Include names starting with '.' found in dropped folders
+        respect_ignore (bool): Leave out what the ignore rules exclude
+        detect_types (bool): Read each file's first bytes for its 'mime_type'
+        limit (int): Stop after this many files
+        config: Configuration object whose `path_blacklist` is turned away
+        version (int): Entry shape (see the module docstring)
+
+    Returns:
+        Dict: 'success', 'message', 'version', 'items' (entries with the
+              absolute 'path' and the dropped 'source' it came from, in drop
+              order, each file once), 'rejected' ({path, reason}), 'ignored'
+              and 'truncated'
+
+    Raises:
+        ValueError: If the version is unknown
+    """
+    _check_version(version)
+    blacklist = [Path(os.path.abspath(Path(p).expanduser())) for p in (getattr(config, 'path_blacklist', None) or [])]
+    items: List[Dict[str, Any]] = []
+    rejected: List[Dict[str, str]] = []
+    seen: Set[str] = set()
+    rules_by_folder: Dict[Path, IgnoreRules] = {}
+    ignored = 0
+    truncated = False
+
+    def add(item: Dict[str, Any], full: str, source: Path) -> None:
+        if full not in seen:
+            seen.add(full)
+            items.append({**item, 'path': full, 'source': str(source)})
+
+    for raw in paths:
+        if len(items) >= limit:
+            truncated = True
+            break
+        if not isinstance(raw, str) or not raw.strip():
+            rejected.append({'path': str(raw), 'reason': 'empty path'})
+            continue
+        path = Path(raw).expanduser()
+        if not path.is_absolute():
+            rejected.append({'path': raw, 'reason': 'not an absolute path'})
+            continue
+        path = Path(os.path.abspath(path))
+        if not path.exists():
+            rejected.append({'path': raw, 'reason': 'does not exist'})
+            continue
+        blocked = next((b for b in blacklist if path == b or b in path.parents), None)
+        if blocked is not None:
+            rejected.append({'path': raw, 'reason': f"blacklisted ({blocked})"})
+            continue
+        if respect_ignore:
+            rules = rules_by_folder.get(path.parent)
+            if rules is None:
+                rules = rules_by_folder[path.parent] = IgnoreRules.for_folder(str(path.parent))
+            if rules.match(str(path)):
+                ignored += 1
+                continue
+
+        if path.is_dir():
+            try:
+                listing = list_files_recursive(str(path), include_hidden=include_hidden, limit=limit - len(items),
+                                               respect_ignore=respect_ignore, detect_types=detect_types,
+                                               version=version)
+            except OSError as e:
+                rejected.append({'path': raw, 'reason': e.strerror or str(e)})
+                continue
+            ignored += listing['ignored']
+            truncated = truncated or listing['truncated']
+            for item in listing['items']:
+                if not item['is_dir']:
+                    add(item, str(path / item['path']), path)
+        elif not path.is_file():
+            rejected.append({'path': raw, 'reason': 'not a file or folder'})
+        elif not os.access(path, os.R_OK):
+            rejected.append({'path': raw, 'reason': 'cannot be read'})
+        else:
+            item = _entry(_DroppedEntry(path), path.name, 0, True, version)
+            if detect_types:
+                item['mime_type'] = sniff_mime_type(str(path))
+            add(item, str(path), path)
+
+    message = f"{len(items)} file(s) ready to classify"
+    if rejected:
+        message += f", {len(rejected)} path(s) turned away"
+    return {'success': True, 'message': message, 'version': version, 'items': items, 'rejected': rejected,
+            'ignored': ignored, 'truncated': truncated}
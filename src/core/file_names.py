@@ -0,0 +1,180 @@
+"""
+File Names Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module checks file names against the rules of the file systems they
+may end up on, so a rename typed in the GUI fails with a clear reason
+instead of an OS error - or worse, a name that works here and breaks once
+the folder is synced to another computer.
+
+Rule sets:
+
+- windows: no < > : " / \\ | ? * or control characters, no reserved device
+  names (CON, PRN, AUX, NUL, COM1-9, LPT1-9, also with an extension), no
+  trailing dot or space, at most 255 UTF-16 units, full path under 260
+- posix: no / or NUL, at most 255 bytes of UTF-8
+- macos: posix, plus no : (Finder shows it as /)
+- portable (default): all of the above, so the name works everywhere
+
+It also holds what the library strategies (photos, music, videos, ebooks,
+fonts, invoices) share when they build folders from what a file says:
+safe_name() turns a tag or title into a usable folder or file name, and
+place_in_destination() puts a classification under the strategy's
+destination folder, relative to the organize root or absolute.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import os
+import re
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+RULE_SETS = ('portable', 'windows', 'posix', 'macos')
+WINDOWS_RESERVED = ({'CON', 'PRN', 'AUX', 'NUL'} | {f"COM{i}" for i in range(1, 10)}
+                    | {f"LPT{i}" for i in range(1, 10)})
+WINDOWS_ILLEGAL = '<>:"/\\|?*'
+MAX_NAME_UNITS = 255
+WINDOWS_MAX_PATH = 260
+
+_CONTROL = re.compile(r'[\x00-\x1f]')
+_UNSAFE_NAME = re.compile(r'[\\/:*?"<>|\x00-\x1f]+')
+
+
+def platform_rules() -> str:
+    """The rule set of the file system this computer uses."""
+    if sys.platform.startswith('win'):
+        return 'windows'
+    return 'macos' if sys.platform == 'darwin' else 'posix'
+
+
+def validate_file_name(name: str, rules: str = 'portable', parent: Optional[str] = None) -> Dict[str, Any]:
+    """
+    Check a file name against a platform's naming rules.
+
+    Args:
+        name (str): The name (no folder part)
+        rules (str): 'portable', 'windows', 'posix' or 'macos'
+        parent (str, optional): Folder the file is in, to check the full path length
+
+    Returns:
+        Dict: 'valid', 'problems' (one message each) and 'suggestion', a
+              close name that passes (None if the name is fine or unusable)
+
+    Raises:
+        ValueError: If the rule set is unknown
+    """
+    if rules not in RULE_SETS:
+        raise ValueError(f"Unknown naming rules '{rules}' (use one of: {', '.join(RULE_SETS)})")
+    windows = rules in ('portable', 'windows')
+    problems: List[str] = []
+
+    if not name or not name.strip():
+        return {'valid': False, 'problems': ['The name is empty'], 'suggestion': None}
+    if name in ('.', '..'):
+        return {'valid': False, 'problems': [f"'{name}' is not a file name"], 'suggestion': None}
+
+    illegal = set('/\x00')
+    if windows:
+        illegal |= set(WINDOWS_ILLEGAL)
+    elif rules == 'macos':
+        illegal.add(':')
+    found = sorted(ch for ch in set(name) if ch in illegal)
+    if found:
+        problems.append(f"Contains characters that are not allowed: {' '.join(found)}")
+    if windows and _CONTROL.search(name.replace('\x00', '')):
+        problems.append("Contains control characters")
+
+    if windows:
+        stem = name.split('.', 1)[0].rstrip(' ').upper()
+        if stem in WINDOWS_RESERVED:
+            problems.append(f"'{stem}' is a reserved device name on Windows")
+        if name[-1] in '. ':
+            problems.append("Ends with a dot or a space, which Windows removes")
+        if len(name.encode('utf-16-le')) // 2 > MAX_NAME_UNITS:
+            problems.append(f"Longer than {MAX_NAME_UNITS} characters")
+        if parent is not None and len(str(parent).rstrip('/\\')) + 1 + len(name) >= WINDOWS_MAX_PATH:
+            problems.append(f"The full path would be {WINDOWS_MAX_PATH} characters or longer")
+    if rules != 'windows' and len(name.encode('utf-8')) > MAX_NAME_UNITS:
+        problems.append(f"Longer than {MAX_NAME_UNITS} bytes")
+
+    suggestion = None
+    if problems:
+        suggestion = suggest_file_name(name, rules)
+        if suggestion == name or not suggestion:
+            suggestion = None
+    return {'valid': not problems, 'problems': problems, 'suggestion': suggestion}
+
+
+def suggest_file_name(name: str, rules: str = 'portable') -> str:
+    """
+    A close name that passes the rules: bad characters become '_',
+    reserved names get a '_' suffix, and long names are shortened before
+    the extension.
+
+    Args:
+        name (str): Name to fix
+        rules (str): Rule set (see validate_file_name)
+
+    Returns:
+        str: Fixed name ('' if nothing usable is left)
+    """
+    windows = rules in ('portable', 'windows')
+    illegal = set('/\x00') | (set(WINDOWS_ILLEGAL) if windows else {':'} if rules == 'macos' else set())
+    fixed = ''.join('_' if ch in illegal or (windows and _CONTROL.match(ch)) else ch for ch in name)
+    if windows:
+        fixed = fixed.rstrip('. ')
+        stem, dot, ext = fixed.partition('.')
+        if stem.rstrip(' ').upper() in WINDOWS_RESERVED:
+            fixed = f"{stem}_{dot}{ext}"
+
+    stem, ext = (fixed.rsplit('.', 1)[0], '.' + fixed.rsplit('.', 1)[1]) if '.' in fixed[1:] else (fixed, '')
+    while stem and (len(f"{stem}{ext}".encode('utf-8')) > MAX_NAME_UNITS
+                    or len(f"{stem}{ext}".encode('utf-16-le')) // 2 > MAX_NAME_UNITS):
+        stem = stem[:-1]
+    fixed = f"{stem.rstrip('. ') if windows else stem}{ext}"
+    return '' if fixed in ('.', '..') or not fixed.strip('. _') else fixed
+
+
+def safe_name(name: Any, replacement: str = ' ') -> str:
+    """
+    A file or folder name made from any text (a tag, a title): path
+    separators, characters Windows rejects and control characters become
+    the replacement, and leading or trailing dots and spaces go.
+
+    Args:
+        name: Text to make a name of (None gives '')
+        replacement (str): What each run of unusable characters becomes
+
+    Returns:
+        str: The name ('' if nothing usable is left)
+    """
+    return _UNSAFE_NAME.sub(replacement, str(name or '')).strip(' .' + os.sep)
+
+
+def place_in_destination(classification: Dict[str, Any], destination: str, folder: str) -> Dict[str, Any]:
+    """
+    Put a strategy's classification under its destination folder.
+
+    Args:
+        classification (Dict): Classification whose 'suggested_path' is the folder
+        destination (str): The strategy's destination: absolute (becomes the
+            'destination_root') or relative to the organize root
+        folder (str): Folder below the destination, e.g. '2024/06'
+
+    Returns:
+        Dict: The classification
+    """
+    target = Path(destination).expanduser()
+    if target.is_absolute():
+        classification['destination_root'] = str(target)
+    else:
+        classification['suggested_path'] = f"{destination.strip('/')}/{folder}/"
+    return classification
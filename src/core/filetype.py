@@ -0,0 +1,345 @@
+"""
+File Type Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module tells what a file is from its first bytes (magic numbers),
+not from its extension, so a download named "invoice.pdf" that is really
+an HTML error page or a program is sorted - and flagged - for what it is.
+
+detect_file_type() reads at most HEADER_BYTES and knows the common
+documents, images, audio, video, archives, fonts and executables. ZIP
+containers are looked into (a .docx, .xlsx, .epub and .jar are all ZIP
+files); files with no signature that decode as text are text, typed by
+their extension when it is a text format. Anything else falls back to
+the extension.
+
+detect_mismatched_extensions() lists the files of a folder whose
+extension does not fit their content, with the extension they should
+have; an executable behind a document or media extension is a "danger".
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import mimetypes
+import os
+import zipfile
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .ignore_rules import IgnoreRules
+
+logger = logging.getLogger(__name__)
+
+
+HEADER_BYTES = 4096
+FALLBACK_MIME = 'application/octet-stream'
+
+# (offset, signature, mime type, extensions it may have - the first is the usual one)
+SIGNATURES: List[Tuple[int, bytes, str, Tuple[str, ...]]] = [
+    (0, b'%PDF-', 'application/pdf', ('pdf', 'ai')),
+    (0, b'\x89PNG\r\n\x1a\n', 'image/png', ('png',)),
+    (0, b'\xff\xd8\xff', 'image/jpeg', ('jpg', 'jpeg', 'jpe', 'jfif')),
+    (0, b'GIF87a', 'image/gif', ('gif',)),
+    (0, b'GIF89a', 'image/gif', ('gif',)),
+    (0, b'BM', 'image/bmp', ('bmp', 'dib')),
+    (0, b'II*\x00', 'image/tiff', ('tif', 'tiff', 'dng', 'cr2', 'nef', 'arw')),
+    (0, b'MM\x00*', 'image/tiff', ('tif', 'tiff', 'dng', 'nef', 'pef')),
+    (0, b'\x00\x00\x01\x00', 'image/x-icon', ('ico',)),
+    (0, b'8BPS', 'image/vnd.adobe.photoshop', ('psd',)),
+    (0, b'ID3', 'audio/mpeg', ('mp3',)),
+    (0, b'fLaC', 'audio/flac', ('flac',)),
+    (0, b'OggS', 'audio/ogg', ('ogg', 'oga', 'opus', 'ogv')),
+    (0, b'MThd', 'audio/midi', ('mid', 'midi')),
+    (0, b'\x1aE\xdf\xa3', 'video/x-matroska', ('mkv', 'webm', 'mka')),
+    (0, b'FLV', 'video/x-flv', ('flv',)),
+    (0, b'7z\xbc\xaf\x27\x1c', 'application/x-7z-compressed', ('7z',)),
+    (0, b'Rar!\x1a\x07', 'application/vnd.rar', ('rar',)),
+    (0, b'\x1f\x8b', 'application/gzip', ('gz', 'tgz')),
+    (0, b'BZh', 'application/x-bzip2', ('bz2', 'tbz2')),
+    (0, b'\xfd7zXZ\x00', 'application/x-xz', ('xz', 'txz')),
+    (0, b'\x28\xb5\x2f\xfd', 'application/zstd', ('zst',)),
+    (257, b'ustar', 'application/x-tar', ('tar',)),
+    (0, b'\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1', 'application/x-ole-storage', ('doc', 'xls', 'ppt', 'msg', 'msi', 'vsd')),
+    (0, b'SQLite format 3\x00', 'application/vnd.sqlite3', ('sqlite', 'sqlite3', 'db')),
+    (0, b'{\\rtf', 'application/rtf', ('rtf',)),
+    (0, b'%!PS', 'application/postscript', ('ps', 'eps')),
+    (0, b'wOFF', 'font/woff', ('woff',)),
+    (0, b'wOF2', 'font/woff2', ('woff2',)),
+    (0, b'OTTO', 'font/otf', ('otf',)),
+    (0, b'\x00\x01\x00\x00\x00', 'font/ttf', ('ttf',)),
+    (0, b'MZ', 'application/x-msdownload', ('exe', 'dll', 'sys', 'scr', 'com')),
+    (0, b'\x7fELF', 'application/x-executable', ('', 'so', 'bin', 'elf')),
+    (0, b'\xcf\xfa\xed\xfe', 'application/x-mach-binary', ('', 'dylib', 'bundle')),
+    (0, b'\xca\xfe\xba\xbe', 'application/x-mach-binary', ('', 'dylib', 'class')),
+    (0, b'#!', 'text/x-script', ('sh', 'bash', 'zsh', 'py', 'pl', 'rb', 'command', '')),
+]
+
+# RIFF and ISO base media (ftyp) files name their format a few bytes in
+RIFF_TYPES = {b'WEBP': ('image/webp', ('webp',)), b'WAVE': ('audio/wav', ('wav',)),
+              b'AVI ': ('video/x-msvideo', ('avi',))}
+FTYP_BRANDS = {
+    b'heic': ('image/heic', ('heic', 'heif')), b'heix': ('image/heic', ('heic', 'heif')),
+    b'mif1': ('image/heif', ('heif', 'heic')), b'msf1': ('image/heif', ('heif', 'heic')),
+    b'avif': ('image/avif', ('avif',)),
+    b'qt  ': ('video/quicktime', ('mov', 'qt')),
+    b'M4A ': ('audio/mp4', ('m4a', 'm4b', 'mp4')), b'M4B ': ('audio/mp4', ('m4b', 'm4a')),
+    b'crx ': ('image/x-canon-cr3', ('cr3',)),
+    b'3gp4': ('video/3gpp', ('3gp', 'mp4')), b'3gp5': ('video/3gpp', ('3gp', 'mp4')),
+}
+MP4_TYPE = ('video/mp4', ('mp4', 'm4v', 'mov', 'm4a'))
+
+# ZIP containers, by a file they hold (checked in order)
+ZIP_TYPES = [
+    ('word/', 'application/vnd.openxmlformats-officedocument.wordprocessingml.document', ('docx', 'docm', 'dotx')),
+    ('xl/', 'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet', ('xlsx', 'xlsm', 'xltx')),
+    ('ppt/', 'application/vnd.openxmlformats-officedocument.presentationml.presentation', ('pptx', 'pptm', 'ppsx')),
+    ('META-INF/MANIFEST.MF', 'application/java-archive', ('jar', 'war', 'ear', 'zip')),
+    ('AndroidManifest.xml', 'application/vnd.android.package-archive', ('apk', 'aab')),
+]
+# ...or by the "mimetype" entry OpenDocument and EPUB files start with
+ZIP_MIMETYPES = {
+    'application/epub+zip': ('epub',),
+    'application/vnd.oasis.opendocument.text': ('odt',),
+    'application/vnd.oasis.opendocument.spreadsheet': ('ods',),
+    'application/vnd.oasis.opendocument.presentation': ('odp',),
+    'application/vnd.oasis.opendocument.graphics': ('odg',),
+}
+ZIP_GENERIC = ('application/zip', ('zip', 'cbz', 'xpi', 'whl', 'nupkg', 'kmz', 'ipa', 'sketch', 'pages',
+                                   'numbers', 'key', 'xd'))
+
+# Text that starts like markup: (prefix, mime type, extensions; empty when any text extension fits)
+TEXT_MARKUP = [
+    (b'<svg', 'image/svg+xml', ('svg',)),
+    (b'<!doctype html', 'text/html', ('html', 'htm', 'xhtml')),
+    (b'<html', 'text/html', ('html', 'htm', 'xhtml')),
+    (b'<?xml', 'application/xml', ()),
+]
+# Text content fits any of these extensions
+TEXT_EXTENSIONS = frozenset(
+    'txt text md markdown rst log csv tsv json jsonl ndjson xml html htm xhtml svg css js mjs ts tsx jsx py '
+    'rb pl php java c h cpp hpp cc cs go rs swift kt sh bash zsh ps1 bat cmd ini cfg conf toml yaml yml env '
+    'sql tex bib srt vtt ass ics vcf eml mbox url webloc desktop plist reg properties gitignore lock nfo '
+    'm3u m3u8 pls cue gpx kml geojson ipynb r m scss less vue'.split()
+)
+# Programs; one behind another extension is a danger
+EXECUTABLE_MIMES = ('application/x-msdownload', 'application/x-executable', 'application/x-mach-binary')
+
+
+def _read_header(path: Path) -> bytes:
+    with open(path, 'rb') as f:
+        return f.read(HEADER_BYTES)
+
+
+def _zip_type(path: Path) -> Tuple[str, Tuple[str, ...]]:
+    try:
+        with zipfile.ZipFile(path) as archive:
+            names = archive.namelist()
+            if 'mimetype' in names:
+                declared = archive.read('mimetype')[:100].decode('ascii', errors='ignore').strip()
+                if declared in ZIP_MIMETYPES:
+                    return declared, ZIP_MIMETYPES[declared]
+    except (zipfile.BadZipFile, OSError, RuntimeError, ValueError):
+        return ZIP_GENERIC
+    for marker, mime, extensions in ZIP_TYPES:
+        if any(name == marker or name.startswith(marker) for name in names):
+            return mime, extensions
+    return ZIP_GENERIC
+
+
+def _looks_like_text(header: bytes) -> bool:
+    if not header:
+        return False
+    if header.startswith((b'\xff\xfe', b'\xfe\xff')):
+        return True  # UTF-16 with a byte order mark
+    if b'\x00' in header:
+        return False
+    try:
+        header.decode('utf-8')
+        return True
+    except UnicodeDecodeError as e:
+        # A multi-byte character cut off by the header limit is still text
+        if e.start >= len(header) - 3:
+            return True
+    try:
+        text = header.decode('cp1252')
+    except UnicodeDecodeError:
+        return False
+    printable = sum(ch.isprintable() or ch in '\r\n\t' for ch in text)
+    return printable / len(text) > 0.95
+
+
+def _by_signature(path: Path, header: bytes) -> Optional[Tuple[str, Tuple[str, ...]]]:
+    if header[:4] == b'PK\x03\x04' or header[:4] == b'PK\x05\x06':
+        return _zip_type(path)
+    if header[:4] == b'RIFF' and header[8:12] in RIFF_TYPES:
+        return RIFF_TYPES[header[8:12]]
+    if header[4:8] == b'ftyp':
+        return FTYP_BRANDS.get(header[8:12], MP4_TYPE)
+    if header[:2] in (b'\xff\xfb', b'\xff\xf3', b'\xff\xf2'):
+        return 'audio/mpeg', ('mp3',)
+    if header[:2] == b'\xff\xf1' or header[:2] == b'\xff\xf9':
+        return 'audio/aac', ('aac',)
+    for offset, signature, mime, extensions in SIGNATURES:
+        if header[offset:offset + len(signature)] == signature:
+            return mime, extensions
+    return None
+
+
+def detect_file_type(path: str) -> Dict[str, Any]:
+    """
+    What a file is, by its content.
+
+    Args:
+        path (str): File
+
+    Returns:
+        Dict: 'path', 'mime_type', 'extensions' (extensions that fit it, the
+              usual one first; empty when any text extension fits),
+              'method' ('content', 'text' or 'extension') and 'text'
+
+    Raises:
+        FileNotFoundError: If the file does not exist
+        IsADirectoryError: If the path is a folder
+    """
+    file = Path(path)
+    if file.is_dir():
+        raise IsADirectoryError(f"Not a file: {file}")
+    header = _read_header(file)
+    extension = file.suffix.lower().lstrip('.')
+    found = _by_signature(file, header)
+    if found:
+        mime, extensions = found
+        return {'path': str(file), 'mime_type': mime, 'extensions': list(extensions), 'method': 'content',
+                'text': mime.startswith('text/')}
+
+    if _looks_like_text(header):
+        lowered = header[:200].lstrip(b'\xef\xbb\xbf \t\r\n').lower()
+        for prefix, mime, extensions in TEXT_MARKUP:
+            if lowered.startswith(prefix):
+                return {'path': str(file), 'mime_type': mime, 'extensions': list(extensions), 'method': 'text',
+                        'text': True}
+        guessed = mimetypes.guess_type(file.name)[0] if extension in TEXT_EXTENSIONS else None
+        return {'path': str(file), 'mime_type': guessed or 'text/plain', 'extensions': [], 'method': 'text',
+                'text': True}
+
+    guessed = mimetypes.guess_type(file.name)[0]
+    return {'path': str(file), 'mime_type': guessed or FALLBACK_MIME, 'extensions': [extension] if guessed else [],
+            'method': 'extension', 'text': False}
+
+
+def sniff_mime_type(path: str) -> Optional[str]:
+    """A file's MIME type by content, or None if it cannot be read."""
+    try:
+        return detect_file_type(path)['mime_type']
+    except OSError:
+        return None
+
+
+def check_extension(path: str) -> Optional[Dict[str, Any]]:
+    """
+    Whether a file's extension lies about its content.
+
+    Returns:
+        Dict or None: None when it fits; else 'path', 'extension',
+                      'detected_mime', 'suggested_extension', 'severity'
+                      ('warning' or 'danger') and 'message'
+    """
+    detected = detect_file_type(path)
+    file = Path(path)
+    extension = file.suffix.lower().lstrip('.')
+    if detected['method'] == 'extension' or file.stat().st_size == 0:
+        return None
+    if detected['text']:
+        # Text fits any text extension, and extensions no signature claims
+        if (extension in TEXT_EXTENSIONS or extension in detected['extensions']
+                or extension not in _binary_extensions()):
+            return None
+        suggested = detected['extensions'][0] or None if detected['extensions'] else 'txt'
+    else:
+        if extension in detected['extensions']:
+            return None
+        suggested = detected['extensions'][0] or None
+    danger = detected['mime_type'] in EXECUTABLE_MIMES + ('text/x-script',)
+    shown = f".{extension}" if extension else 'no extension'
+    return {
+        'path': str(file),
+        'extension': extension,
+        'detected_mime': detected['mime_type'],
+        'suggested_extension': suggested,
+        'severity': 'danger' if danger else 'warning',
+        'message': f"{file.name} has {shown} but is {detected['mime_type']}"
+                   + (' - a program disguised as another file' if danger else ''),
+    }
+
+
+_BINARY_EXTENSIONS: Optional[frozenset] = None
+
+
+def _binary_extensions() -> frozenset:
+    """Every extension a signature above identifies."""
+    global _BINARY_EXTENSIONS
+    if _BINARY_EXTENSIONS is None:
+        extensions = {ext for _, _, mime, exts in SIGNATURES if not mime.startswith('text/') for ext in exts}
+        for _, exts in list(RIFF_TYPES.values()) + list(FTYP_BRANDS.values()) + [MP4_TYPE, ZIP_GENERIC]:
+            extensions.update(exts)
+        for _, _, exts in ZIP_TYPES:
+            extensions.update(exts)
+        for exts in ZIP_MIMETYPES.values():
+            extensions.update(exts)
+        extensions.update(('mp3', 'aac'))
+        _BINARY_EXTENSIONS = frozenset(extensions - {''})
+    return _BINARY_EXTENSIONS
+
+
+def detect_mismatched_extensions(folder: str, recursive: bool = True) -> Dict[str, Any]:
+    """
+    Find the files in a folder whose extension does not fit their content.
+
+    Files the ignore rules exclude are not checked.
+
+    Args:
+        folder (str): Folder to check
+        recursive (bool): Include subfolders
+
+    Returns:
+        Dict: 'success', 'folder', 'checked', 'mismatches' (see check_extension;
+              dangers first), 'unreadable' and 'message'
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+    """
+    root = Path(folder).expanduser().resolve()
+    if not root.is_dir():
+        raise NotADirectoryError(f"Not a folder: {root}")
+    rules = IgnoreRules.for_folder(str(root))
+    mismatches, checked, unreadable = [], 0, 0
+    for current, dirs, names in os.walk(root):
+        dirs[:] = [name for name in dirs
+                   if recursive and not rules.match(os.path.join(current, name), is_dir=True)]
+        for name in sorted(names):
+            path = os.path.join(current, name)
+            if os.path.islink(path) or rules.match(path, is_dir=False):
+                continue
+            try:
+                mismatch = check_extension(path)
+            except OSError as e:
+                logger.debug(f"Could not check {path}: {e}")
+                unreadable += 1
+                continue
+            checked += 1
+            if mismatch:
+                mismatches.append(mismatch)
+    mismatches.sort(key=lambda item: (item['severity'] != 'danger', item['path']))
+    dangers = sum(1 for item in mismatches if item['severity'] == 'danger')
+    message = f"{len(mismatches)} of {checked} file{'s' if checked != 1 else ''} have the wrong extension"
+    if dangers:
+        message += f"; {dangers} {'is a program' if dangers == 1 else 'are programs'} in disguise"
+    return {'success': True, 'folder': str(root), 'checked': checked, 'mismatches': mismatches,
+            'unreadable': unreadable, 'message': message}
@@ -0,0 +1,269 @@
+"""
+Folder Compare Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module compares two folder trees by content, for users consolidating
+old backup drives: which files are identical (wherever they sit), which
+exist only on one side, and which share a path but differ. Merge actions
+then copy missing files across and/or let the newer copy of a conflicting
+file win. Merges are journaled as one operation, and files overwritten by
+"keep newer" are kept as versions first, so a merge can be undone.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import shutil
+from collections import defaultdict
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .hashing import hash_file
+from .journal import OperationJournal
+from .versions import VersionStore
+
+logger = logging.getLogger(__name__)
+
+
+MERGE_ACTIONS = ('copy_missing', 'keep_newer')
+MERGE_DIRECTIONS = ('a_to_b', 'b_to_a', 'both')
+
+
+class FolderComparer:
+    """
+    Compares two folder trees and merges them.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        journal: Operation journal used to make merges undoable
+        versions: Version store keeping files replaced by newer copies
+    """
+
+    def __init__(self, config, db_manager, journal: Optional[OperationJournal] = None,
+                 versions: Optional[VersionStore] = None):
+        """
+        Initialize comparer.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+            journal (OperationJournal, optional): Journal for merges (created if omitted)
+            versions (VersionStore, optional): Version store (created if omitted)
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.journal = journal or OperationJournal(db_manager)
+        self.versions = versions or VersionStore(config, db_manager)
+
+    # ==================== Comparison ====================
+
+    @staticmethod
+    def _inventory(root: Path) -> Dict[str, os.stat_result]:
+        files = {}
+        for dirpath, dirnames, filenames in os.walk(root):
+            dirnames[:] = [d for d in dirnames if not d.startswith('.')]
+            for filename in filenames:
+                path = Path(dirpath) / filename
+                if path.is_symlink():
+                    continue
+                try:
+                    files[path.relative_to(root).as_posix()] = path.stat()
+                except OSError:
+                    continue
+        return files
+
+    @staticmethod
+    def _hash(path: Path) -> Optional[str]:
+        try:
+            return hash_file(path)
+        except OSError as e:
+            logger.warning(f"Could not hash {path}: {e}")
+            return None
+
+    @staticmethod
+    def _describe(stat: os.stat_result) -> Dict[str, Any]:
+        return {'size': stat.st_size, 'modified': stat.st_mtime}
+
+    def compare_folders(self, a: str, b: str) -> Dict[str, Any]:
+        """
+        Compare two folder trees by content hash.
+
+        Only files whose size also occurs on the other side are hashed.
+
+        Args:
+            a (str): First folder
+            b (str): Second folder
+
+        Returns:
+            Dict: 'success', 'a', 'b' and the groups
+                  'identical' ({'hash', 'size', 'a': [...], 'b': [...]}),
+                  'only_in_a' / 'only_in_b' (relative paths),
+                  'conflicting' ({'path', 'a', 'b', 'newer'}) and 'summary' counts
+        """
+        root_a, root_b = Path(a).expanduser().resolve(), Path(b).expanduser().resolve()
+        for root in (root_a, root_b):
+            if not root.is_dir():
+                return {'success': False, 'message': f'Not a folder: {root}'}
+        if root_a == root_b or root_a in root_b.parents or root_b in root_a.parents:
+            return {'success': False, 'message': 'Folders must not contain each other'}
+
+        files_a, files_b = self._inventory(root_a), self._inventory(root_b)
+        sizes_b = {s.st_size for s in files_b.values()}
+        sizes_a = {s.st_size for s in files_a.values()}
+
+        hashes: Dict[Tuple[str, str], Optional[str]] = {}
+        for side, root, files, other_sizes in (('a', root_a, files_a, sizes_b), ('b', root_b, files_b, sizes_a)):
+            for rel, stat in files.items():
+                if stat.st_size in other_sizes:
+                    hashes[(side, rel)] = self._hash(root / rel)
+
+        by_hash: Dict[str, Dict[str, List[str]]] = defaultdict(lambda: {'a': [], 'b': []})
+        for (side, rel), digest in hashes.items():
+            if digest:
+                by_hash[digest][side].append(rel)
+
+        identical = []
+        matched = set()
+        for digest, sides in by_hash.items():
+            if sides['a'] and sides['b']:
+                first = sides['a'][0]
+                identical.append({'hash': digest, 'size': files_a[first].st_size,
+                                  'a': sorted(sides['a']), 'b': sorted(sides['b'])})
+                matched.update(('a', rel) for rel in sides['a'])
+                matched.update(('b', rel) for rel in sides['b'])
+
+        conflicting = []
+        for rel in sorted(files_a.keys() & files_b.keys()):
+            digest = hashes.get(('a', rel))
+            if digest and digest == hashes.get(('b', rel)):
+                continue
+            stat_a, stat_b = files_a[rel], files_b[rel]
+            if stat_a.st_mtime == stat_b.st_mtime:
+                newer = 'same'
+            else:
+                newer = 'a' if stat_a.st_mtime > stat_b.st_mtime else 'b'
+            conflicting.append({'path': rel, 'a': self._describe(stat_a), 'b': self._describe(stat_b), 'newer': newer})
+        conflict_paths = {c['path'] for c in conflicting}
+
+        only_in_a = sorted(rel for rel in files_a if ('a', rel) not in matched and rel not in conflict_paths)
+        only_in_b = sorted(rel for rel in files_b if ('b', rel) not in matched and rel not in conflict_paths)
+        identical.sort(key=lambda g: g['a'][0])
+
+        return {
+            'success': True,
+            'a': str(root_a),
+            'b': str(root_b),
+            'identical': identical,
+            'only_in_a': only_in_a,
+            'only_in_b': only_in_b,
+            'conflicting': conflicting,
+            'summary': {
+                'identical': len(identical),
+                'only_in_a': len(only_in_a),
+                'only_in_b': len(only_in_b),
+                'conflicting': len(conflicting)
+            }
+        }
+
+    # ==================== Merging ====================
+
+    def _planned_copies(self, comparison: Dict[str, Any], actions: List[str],
+                        direction: str) -> List[Dict[str, Any]]:
+        roots = {'a': Path(comparison['a']), 'b': Path(comparison['b'])}
+        copies = []
+        if 'copy_missing' in actions:
+            if direction in ('a_to_b', 'both'):
+                copies += [{'rel': rel, 'from': 'a', 'reason': 'missing'} for rel in comparison['only_in_a']]
+            if direction in ('b_to_a', 'both'):
+                copies += [{'rel': rel, 'from': 'b', 'reason': 'missing'} for rel in comparison['only_in_b']]
+        if 'keep_newer' in actions:
+            allowed = {'a_to_b': ('a',), 'b_to_a': ('b',), 'both': ('a', 'b')}[direction]
+            copies += [{'rel': c['path'], 'from': c['newer'], 'reason': 'newer'}
+                       for c in comparison['conflicting'] if c['newer'] in allowed]
+
+        for copy in copies:
+            target = 'b' if copy['from'] == 'a' else 'a'
+            copy['source'] = str(roots[copy['from']] / copy['rel'])
+            copy['dest'] = str(roots[target] / copy['rel'])
+        return copies
+
+    def merge(self, a: str, b: str, actions: Optional[List[str]] = None,
+              direction: str = 'a_to_b', dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Merge two folders as one undoable operation.
+
+        'copy_missing' copies files that only exist on the source side;
+        'keep_newer' overwrites the older copy of each conflicting file with
+        the newer one, keeping the overwritten file as a version.
+
+        Args:
+            a (str): First folder
+            b (str): Second folder
+            actions (List[str], optional): Merge actions (default: copy_missing)
+            direction (str): 'a_to_b', 'b_to_a' or 'both'
+            dry_run (bool): Only report what would be copied
+
+        Returns:
+            Dict: Result with 'success', 'message', 'copies', 'failed' and 'operation_id'
+        """
+        actions = list(actions or ['copy_missing'])
+        unknown = [act for act in actions if act not in MERGE_ACTIONS]
+        if unknown:
+            return {'success': False, 'message': f"Unknown merge action(s): {', '.join(unknown)}"}
+        if direction not in MERGE_DIRECTIONS:
+            return {'success': False, 'message': f'Unknown merge direction: {direction}'}
+
+        comparison = self.compare_folders(a, b)
+        if not comparison['success']:
+            return comparison
+
+        copies = self._planned_copies(comparison, actions, direction)
+        if dry_run or not copies:
+            return {
+                'success': True,
+                'message': f"{len(copies)} file(s) would be copied" if dry_run else 'Folders are already merged',
+                'dry_run': dry_run,
+                'copies': copies,
+                'failed': [],
+                'operation_id': None
+            }
+
+        operation_id = self.journal.begin('merge', f"Merge {comparison['a']} and {comparison['b']}")
+        done, failed = [], []
+        for copy in copies:
+            source, dest = Path(copy['source']), Path(copy['dest'])
+            details = None
+            try:
+                if copy['reason'] == 'newer' and dest.is_file():
+                    version = self.versions.save_version(str(dest), reason='merge')
+                    if version is None:
+                        raise OSError(f'versions are disabled, refusing to overwrite {dest}')
+                    details = {'replaced_version': version['id'], 'version_object': version['object_path']}
+                dest.parent.mkdir(parents=True, exist_ok=True)
+                shutil.copy2(source, dest)
+            except (OSError, shutil.Error) as e:
+                failed.append({**copy, 'message': str(e)})
+                continue
+            self.journal.record_step(operation_id, 'copy', str(source), str(dest), details)
+            done.append(copy)
+        self.journal.complete(operation_id, success=bool(done) or not failed)
+
+        message = f"Copied {len(done)} file(s)"
+        if failed:
+            message += f"; {len(failed)} failed"
+        return {
+            'success': not failed,
+            'message': message,
+            'dry_run': False,
+            'copies': done,
+            'failed': failed,
+            'operation_id': operation_id
+        }
@@ -0,0 +1,361 @@
+"""
+Font Library Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module files font files into one folder per family:
+
+    Fonts/Inter/Inter-Regular.otf
+    Fonts/Inter/Inter-BoldItalic.otf
+    Fonts/Source Serif 4/SourceSerif4-Variable.ttf
+
+Family and style come from the font's own name table (TrueType, OpenType,
+collections and WOFF), not from the file name, so "inter_b.ttf" and
+"Inter-Bold.otf" end up together. The typographic family (name ID 16) is
+preferred over the legacy one, which splits weights into families of
+their own ("Inter Semi Bold").
+
+find_duplicates() reports fonts present more than once, either as copies
+of one version or as different versions (name ID 5) with the newest
+marked; install() copies a font into the user's font folder.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import re
+import shutil
+import struct
+import subprocess
+import sys
+import zlib
+from collections import defaultdict
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .file_names import safe_name, place_in_destination
+
+logger = logging.getLogger(__name__)
+
+try:
+    import winreg  # type: ignore
+    HAS_WINREG = True
+except ImportError:
+    winreg = None  # type: ignore
+    HAS_WINREG = False
+
+
+FONT_EXTENSIONS = {'.ttf', '.otf', '.ttc', '.otc', '.woff', '.woff2'}
+DEFAULT_DESTINATION = 'Fonts'
+MAX_FONT_BYTES = 64 * 1024 * 1024
+NAME_IDS = {1: 'family', 2: 'style', 4: 'full_name', 5: 'version', 6: 'postscript_name',
+            16: 'typographic_family', 17: 'typographic_style'}
+_VERSION = re.compile(r'(\d+(?:\.\d+)*)')
+
+
+def _name_table(data: bytes, offset: int, length: int) -> Dict[str, str]:
+    """Decode the name table, preferring US English Windows names."""
+    if length < 6 or offset + length > len(data):
+        return {}
+    table = data[offset:offset + length]
+    _, count, string_offset = struct.unpack_from('>HHH', table, 0)
+    ranked: Dict[int, Tuple[int, str]] = {}
+    for index in range(count):
+        position = 6 + index * 12
+        if position + 12 > len(table):
+            break
+        platform, encoding, language, name_id, size, start = struct.unpack_from('>HHHHHH', table, position)
+        if name_id not in NAME_IDS:
+            continue
+        raw = table[string_offset + start:string_offset + start + size]
+        if platform == 3 or platform == 0:
+            text = raw.decode('utf-16-be', errors='ignore')
+            rank = 0 if (platform, language) == (3, 0x409) else 1 if platform == 3 else 2
+        elif platform == 1 and encoding == 0:
+            text = raw.decode('mac_roman', errors='ignore')
+            rank = 3 if language == 0 else 4
+        else:
+            continue
+        text = text.replace('\x00', '').strip()
+        if text and (name_id not in ranked or rank < ranked[name_id][0]):
+            ranked[name_id] = (rank, text)
+    return {NAME_IDS[name_id]: text for name_id, (_, text) in ranked.items()}
+
+
+def _sfnt_names(data: bytes, start: int = 0) -> Dict[str, str]:
+    """Names of the font whose table directory starts at `start`."""
+    if start + 12 > len(data):
+        return {}
+    count = struct.unpack_from('>H', data, start + 4)[0]
+    for index in range(count):
+        position = start + 12 + index * 16
+        if position + 16 > len(data):
+            break
+        tag, _, offset, length = struct.unpack_from('>4sLLL', data, position)
+        if tag == b'name':
+            return _name_table(data, offset, length)
+    return {}
+
+
+def _woff_names(data: bytes) -> Dict[str, str]:
+    """Names from a WOFF 1 font, inflating its name table if compressed."""
+    if len(data) < 44:
+        return {}
+    count = struct.unpack_from('>H', data, 12)[0]
+    for index in range(count):
+        position = 44 + index * 20
+        if position + 20 > len(data):
+            break
+        tag, offset, compressed, original, _ = struct.unpack_from('>4sLLLL', data, position)
+        if tag != b'name':
+            continue
+        table = data[offset:offset + compressed]
+        if compressed < original:
+            try:
+                table = zlib.decompress(table)
+            except zlib.error:
+                return {}
+        return _name_table(table, 0, len(table))
+    return {}
+
+
+def read_font_names(file_path: str) -> Dict[str, Any]:
+    """
+    Read a font's family, style and version.
+
+    Args:
+        file_path (str): Font file
+
+    Returns:
+        Dict: 'family', 'style', 'version' (string as written), 'full_name',
+              'postscript_name' where present, plus 'format' and, for
+              collections, 'fonts' (number of faces). WOFF2's compressed
+              tables are not read; such fonts return the format only.
+    """
+    path = Path(file_path)
+    try:
+        with open(path, 'rb') as f:
+            data = f.read(MAX_FONT_BYTES)
+    except OSError as e:
+        logger.debug(f"Cannot read font {path}: {e}")
+        return {}
+    magic = data[:4]
+    info: Dict[str, Any] = {}
+    if magic in (b'\x00\x01\x00\x00', b'OTTO', b'true', b'typ1'):
+        names = _sfnt_names(data)
+        info['format'] = 'opentype' if magic == b'OTTO' else 'truetype'
+    elif magic == b'ttcf' and len(data) >= 16:
+        faces = struct.unpack_from('>L', data, 8)[0]
+        first = struct.unpack_from('>L', data, 12)[0]
+        names = _sfnt_names(data, first)
+        info.update(format='collection', fonts=faces)
+    elif magic == b'wOFF':
+        names = _woff_names(data)
+        info['format'] = 'woff'
+    elif magic == b'wOF2':
+        return {'format': 'woff2'}
+    else:
+        return {}
+    family = names.get('typographic_family') or names.get('family')
+    style = names.get('typographic_style') or names.get('style')
+    if family:
+        info['family'] = family
+    if style:
+        info['style'] = style
+    for key in ('version', 'full_name', 'postscript_name'):
+        if names.get(key):
+            info[key] = names[key]
+    return info
+
+
+def version_key(version: Optional[str]) -> Tuple[int, ...]:
+    """'Version 2.001;hotconv' -> (2, 1) for comparing versions."""
+    match = _VERSION.search(version or '')
+    return tuple(int(part) for part in match.group(1).split('.')) if match else ()
+
+
+def user_font_dir() -> Path:
+    """Where fonts are installed for the current user on this platform."""
+    if sys.platform == 'win32':
+        base = os.environ.get('LOCALAPPDATA') or str(Path.home() / 'AppData' / 'Local')
+        return Path(base) / 'Microsoft' / 'Windows' / 'Fonts'
+    if sys.platform == 'darwin':
+        return Path.home() / 'Library' / 'Fonts'
+    base = os.environ.get('XDG_DATA_HOME') or str(Path.home() / '.local' / 'share')
+    return Path(base) / 'fonts'
+
+
+class FontLibraryStrategy:
+    """
+    Plans font destinations by family, finds duplicates and installs fonts.
+
+    Attributes:
+        config: Configuration object
+        install_dir (Path): Folder fonts are installed into
+    """
+
+    name = 'fonts'
+
+    def __init__(self, config):
+        """
+        Initialize font strategy.
+
+        Args:
+            config: Configuration object (reads `font_settings`)
+        """
+        self.config = config
+        settings = getattr(config, 'font_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        install_dir = settings.get('install_dir')
+        self.install_dir = Path(install_dir).expanduser() if install_dir else user_font_dir()
+
+    def classify(self, file_path: str, root: Optional[Path] = None,
+                 policy: Optional[Dict[str, Any]] = None) -> Optional[Dict[str, Any]]:
+        """
+        Work out where a font belongs.
+
+        Args:
+            file_path (str): Font to place
+            root (Path, optional): Folder being organized (unused)
+            policy (Dict, optional): Folder policy with `font_destination`
+
+        Returns:
+            Dict or None: Classification, or None if the file is not a font
+        """
+        path = Path(file_path)
+        if path.suffix.lower() not in FONT_EXTENSIONS:
+            return None
+        info = read_font_names(str(path))
+        family = safe_name(info.get('family'))
+        if family:
+            reason = f"{family} {info.get('style') or ''}".strip()
+            if info.get('version'):
+                reason += f" ({info['version']})"
+            confidence = 'high'
+        else:
+            # No readable name table: the file name up to the style suffix
+            family = safe_name(re.split(r'[-_ ](?:regular|bold|italic|light|medium|black|thin|semibold|'
+                                         r'extrabold|variable|vf)\b', path.stem, flags=re.I)[0]) or 'Unknown'
+            reason = f"{family} (from the file name)"
+            confidence = 'medium'
+        classification = {
+            'category': 'Fonts',
+            'suggested_path': f"{family}/",
+            'rename': None,
+            'reason': reason,
+            'confidence': confidence,
+            'method': 'fonts'
+        }
+        destination = str((policy or {}).get('font_destination') or DEFAULT_DESTINATION)
+        return place_in_destination(classification, destination, family)
+
+    # ==================== Duplicates ====================
+
+    def find_duplicates(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Find fonts that are in a folder more than once.
+
+        Fonts match by family and style. A group's 'kind' is 'copies' if
+        all files carry the same version, else 'versions', with the path of
+        the newest in 'newest'.
+
+        Args:
+            folder (str): Folder to check
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: 'success', 'message', 'fonts' count and 'groups', each
+                  {family, style, kind, newest, files: [{path, format, version, size}]}
+        """
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {root}", 'fonts': 0, 'groups': []}
+        pattern = '**/*' if recursive else '*'
+        fonts = sorted(p for p in root.glob(pattern) if p.suffix.lower() in FONT_EXTENSIONS and p.is_file())
+
+        by_face: Dict[Tuple[str, str], List[Dict[str, Any]]] = defaultdict(list)
+        for font in fonts:
+            info = read_font_names(str(font))
+            if not info.get('family'):
+                continue
+            try:
+                size = font.stat().st_size
+            except OSError:
+                size = 0
+            key = (info['family'].casefold(), (info.get('style') or 'Regular').casefold())
+            by_face[key].append({'path': str(font), 'format': info['format'], 'version': info.get('version'),
+                                 'size': size, 'family': info['family'], 'style': info.get('style') or 'Regular'})
+
+        groups = []
+        for files in by_face.values():
+            if len(files) < 2:
+                continue
+            versions = {version_key(f['version']) for f in files}
+            newest = max(files, key=lambda f: (version_key(f['version']), f['size']))
+            groups.append({
+                'family': files[0]['family'],
+                'style': files[0]['style'],
+                'kind': 'copies' if len(versions) == 1 else 'versions',
+                'newest': newest['path'],
+                'files': [{k: f[k] for k in ('path', 'format', 'version', 'size')} for f in files]
+            })
+        groups.sort(key=lambda g: (g['family'].casefold(), g['style'].casefold()))
+        return {
+            'success': True,
+            'message': f"{len(groups)} font(s) present more than once among {len(fonts)} font file(s)",
+            'fonts': len(fonts),
+            'groups': groups
+        }
+
+    # ==================== Install ====================
+
+    def install(self, file_path: str) -> Dict[str, Any]:
+        """
+        Install a font for the current user, by copying it into their font folder.
+
+        On Windows the font is also registered under HKEY_CURRENT_USER; on
+        Linux the fontconfig cache is refreshed when fc-cache is available.
+
+        Args:
+            file_path (str): Font file
+
+        Returns:
+            Dict: 'success', 'message' and the installed 'path'
+        """
+        source = Path(file_path).expanduser()
+        if not source.is_file() or source.suffix.lower() not in FONT_EXTENSIONS:
+            return {'success': False, 'message': f"Not a font file: {source}", 'path': None}
+        info = read_font_names(str(source))
+        target = self.install_dir / source.name
+        if target.exists():
+            installed = read_font_names(str(target))
+            if version_key(installed.get('version')) >= version_key(info.get('version')):
+                return {'success': True, 'message': f"{source.name} is already installed", 'path': str(target)}
+        try:
+            self.install_dir.mkdir(parents=True, exist_ok=True)
+            shutil.copy2(source, target)
+        except OSError as e:
+            return {'success': False, 'message': f"Cannot install {source.name}: {e}", 'path': None}
+
+        if sys.platform == 'win32' and HAS_WINREG:
+            label = f"{info.get('full_name') or source.stem} ({'OpenType' if info.get('format') == 'opentype' else 'TrueType'})"
+            try:
+                with winreg.CreateKey(winreg.HKEY_CURRENT_USER,
+                                      r'Software\Microsoft\Windows NT\CurrentVersion\Fonts') as key:
+                    winreg.SetValueEx(key, label, 0, winreg.REG_SZ, str(target))
+            except OSError as e:
+                logger.warning(f"Installed {target} but could not register it: {e}")
+        elif sys.platform.startswith('linux') and shutil.which('fc-cache'):
+            try:
+                subprocess.run(['fc-cache', '-f', str(self.install_dir)], capture_output=True, timeout=60)
+            except (OSError, subprocess.TimeoutExpired) as e:
+                logger.debug(f"fc-cache failed: {e}")
+        name = f"{info.get('family') or source.stem} {info.get('style') or ''}".strip()
+        logger.info(f"Installed font {target}")
+        return {'success': True, 'message': f"Installed {name}", 'path': str(target)}
@@ -0,0 +1,331 @@
+"""
+File Operations Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module moves and renames files without ever losing or overwriting
+one by accident.
+
+On the same drive a move is a single rename: the file is at its old path
+or its new one, never half-way. A move to another drive is a copy into a
+hidden partial file next to the destination, which is flushed, checked
+(size and BLAKE2b hash against the source) and only then renamed into
+place and the source deleted; if anything fails the partial file is
+removed and the source is left as it was.
+
+A destination that already exists is resolved by on_conflict:
+    - keep_both: the file gets the next free name, e.g. "report (2).pdf"
+    - replace:   the existing file is replaced
+    - skip:      nothing is moved
+Even with keep_both, a destination that appears while the file is being
+moved is never overwritten: the final rename refuses to clobber it.
+
+A plan is a list of moves, each {"source", "destination"}; a destination
+that is an existing folder means "into that folder". apply_plan() reports
+one result per move, in order:
+
+    {"source", "destination", "status": "moved" | "skipped" | "failed",
+     "method": "rename" | "copy" | null, "message"}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import errno
+import logging
+import os
+import shutil
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .hashing import hash_file
+from .journal import OperationJournal, trash_root
+from .safety_guardian import SafetyGuardian
+
+logger = logging.getLogger(__name__)
+
+
+CONFLICT_MODES = ('keep_both', 'replace', 'skip')
+PARTIAL_SUFFIX = '.aifo-partial'
+CHUNK_SIZE = 1024 * 1024
+MAX_PLAN = 10000
+
+
+def free_name(destination: Path) -> Path:
+    """
+    The destination itself if it is free, else the first free "name (N).ext".
+
+    Args:
+        destination (Path): Wanted path
+
+    Returns:
+        Path: A path that does not exist yet
+    """
+    destination = Path(destination)
+    counter = 2
+    candidate = destination
+    while os.path.lexists(candidate):
+        candidate = destination.with_name(f"{destination.stem} ({counter}){destination.suffix}")
+        counter += 1
+    return candidate
+
+
+def move_file(source: str, destination: str, on_conflict: str = 'keep_both',
+              verify: bool = True) -> Dict[str, Any]:
+    """
+    Move or rename one file safely.
+
+    Args:
+        source (str): File to move
+        destination (str): New path of the file
+        on_conflict (str): 'keep_both', 'replace' or 'skip' (see module docs)
+        verify (bool): Hash-check copies made across drives
+
+    Returns:
+        Dict: 'source', 'destination' (where the file is now), 'status',
+              'method' and 'message'
+    """
+    if on_conflict not in CONFLICT_MODES:
+        raise ValueError(f"Unknown conflict mode: {on_conflict} (use {', '.join(CONFLICT_MODES)})")
+    source_path, wanted = Path(source), Path(destination)
+    report = {'source': str(source_path), 'destination': str(wanted), 'status': 'failed',
+              'method': None, 'message': ''}
+
+    if not source_path.is_file():
+        report['message'] = f"Not a file: {source_path}"
+        return report
+    if os.path.lexists(wanted):
+        if _same_file(source_path, wanted):
+            report.update(status='skipped', message='Already at the destination')
+            return report
+        if _case_rename(source_path, wanted):
+            os.rename(source_path, wanted)
+            report.update(status='moved', method='rename', message=f"Renamed to {wanted.name}")
+            return report
+        if on_conflict == 'skip':
+            report.update(status='skipped', message=f"{wanted.name} already exists")
+            return report
+        if on_conflict == 'keep_both':
+            wanted = free_name(wanted)
+    replace = on_conflict == 'replace'
+
+    try:
+        wanted.parent.mkdir(parents=True, exist_ok=True)
+        try:
+            final = _rename(source_path, wanted, replace)
+            method = 'rename'
+        except OSError as e:
+            if e.errno != errno.EXDEV:
+                raise
+            final = _copy_across(source_path, wanted, replace, verify)
+            method = 'copy'
+    except OSError as e:
+        report['message'] = f"Could not move {source_path.name}: {e.strerror or e}"
+        logger.warning(f"Move failed: {source_path} -> {wanted}: {e}")
+        return report
+
+    report.update(destination=str(final), status='moved', method=method,
+                  message=f"Moved to {final}" + (' (copied across drives)' if method == 'copy' else ''))
+    return report
+
+
+def _same_file(a: Path, b: Path) -> bool:
+    try:
+        return os.path.samefile(a, b) and os.path.abspath(a) == os.path.abspath(b)
+    except OSError:
+        return False
+
+
+def _case_rename(source: Path, destination: Path) -> bool:
+    """Whether destination is the source itself, spelled differently (case-insensitive drives)."""
+    try:
+        return (os.path.samefile(source, destination)
+                and str(source.resolve()).lower() == str(destination.resolve()).lower())
+    except OSError:
+        return False
+
+
+def _rename(source: Path, destination: Path, replace: bool) -> Path:
+    """Rename in one step; without replace an existing destination gets the next free name."""
+    if replace:
+        os.replace(source, destination)
+        return destination
+    while True:
+        try:
+            # A hard link fails instead of overwriting, which os.rename does not on POSIX
+            os.link(source, destination)
+        except FileExistsError:
+            destination = free_name(destination)
+            continue
+        except OSError as e:
+            if e.errno == errno.EXDEV:
+                raise
+            # No hard links on this file system (FAT, some network shares)
+            if os.path.lexists(destination):
+                destination = free_name(destination)
+            os.rename(source, destination)
+            return destination
+        os.unlink(source)
+        return destination
+
+
+def _copy_across(source: Path, destination: Path, replace: bool, verify: bool) -> Path:
+    """Copy to a partial file, check it, put it in place, then delete the source."""
+    partial = destination.with_name(f".{destination.name}{PARTIAL_SUFFIX}")
+    try:
+        with open(source, 'rb') as src, open(partial, 'wb') as dst:
+            shutil.copyfileobj(src, dst, CHUNK_SIZE)
+            dst.flush()
+            os.fsync(dst.fileno())
+        shutil.copystat(source, partial)
+        if os.path.getsize(partial) != os.path.getsize(source):
+            raise OSError(errno.EIO, 'copy is incomplete')
+        if verify and hash_file(partial) != hash_file(source):
+            raise OSError(errno.EIO, 'copy does not match the original')
+        final = _rename(partial, destination, replace)
+    except BaseException:
+        try:
+            os.unlink(partial)
+        except OSError:
+            pass
+        raise
+    os.unlink(source)
+    return final
+
+
+class FileMover:
+    """
+    Applies a list of moves as one undoable operation.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager (for the operation journal)
+        journal (OperationJournal): Records moves for undo
+    """
+
+    def __init__(self, config, db_manager, journal: Optional[OperationJournal] = None):
+        """
+        Initialize file mover.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+            journal (OperationJournal, optional): Journal to record moves in
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.journal = journal or OperationJournal(db_manager)
+        self._guardian = SafetyGuardian(config)
+
+    def apply_plan(self, moves: List[Dict[str, Any]], on_conflict: str = 'keep_both',
+                   dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Move every file of a plan, reporting each one.
+
+        Args:
+            moves (List[Dict]): Moves with 'source' and 'destination'
+            on_conflict (str): 'keep_both', 'replace' or 'skip'
+            dry_run (bool): Only check and report where files would go
+
+        Returns:
+            Dict: 'success', 'message', 'operation_id', 'results' (one per
+                  move), 'moved', 'skipped', 'failed' and 'dry_run'
+        """
+        if on_conflict not in CONFLICT_MODES:
+            return self._applied(False, f"Unknown conflict mode: {on_conflict} "
+                                        f"(use {', '.join(CONFLICT_MODES)})", dry_run)
+        if len(moves) > MAX_PLAN:
+            return self._applied(False, f"At most {MAX_PLAN} moves per plan (got {len(moves)})", dry_run)
+
+        results: List[Dict[str, Any]] = []
+        operation_id = None
+        for move in moves:
+            source, destination = move.get('source'), move.get('destination')
+            if not source or not destination:
+                results.append({'source': source, 'destination': destination, 'status': 'failed',
+                                'method': None, 'message': 'A move needs a source and a destination'})
+                continue
+            source = str(Path(source).expanduser())
+            destination = Path(destination).expanduser()
+            if destination.is_dir():
+                destination = destination / Path(source).name
+            reason = self._check(source, destination)
+            if reason:
+                results.append({'source': source, 'destination': str(destination), 'status': 'skipped',
+                                'method': None, 'message': reason})
+                continue
+            if dry_run:
+                results.append(self._preview(source, destination, on_conflict))
+                continue
+            if operation_id is None:
+                operation_id = self.journal.begin('move', f"Move {len(moves)} file(s)")
+            result = self._apply(operation_id, source, destination, on_conflict)
+            results.append(result)
+
+        if operation_id is not None:
+            self.journal.complete(operation_id)
+        counts = {status: sum(1 for r in results if r['status'] == status)
+                  for status in ('moved', 'skipped', 'failed')}
+        message = f"{'Would move' if dry_run else 'Moved'} {counts['moved']} file(s)"
+        if counts['skipped']:
+            message += f", skipped {counts['skipped']}"
+        if counts['failed']:
+            message += f", {counts['failed']} failed"
+        result = self._applied(counts['failed'] == 0, message, dry_run)
+        result.update({'operation_id': operation_id, 'results': results, **counts})
+        return result
+
+    def _check(self, source: str, destination: Path) -> Optional[str]:
+        """Why a move must not be made, or None."""
+        is_safe, reason = self._guardian.is_file_safe_to_modify(Path(source))
+        if not is_safe:
+            return reason
+        is_safe, reason = self._guardian.is_file_safe_to_modify(destination)
+        if not is_safe:
+            return reason
+        return None
+
+    def _apply(self, operation_id: int, source: str, destination: Path, on_conflict: str) -> Dict[str, Any]:
+        replaced = None
+        if on_conflict == 'replace' and destination.is_file() and not _same_file(Path(source), destination):
+            # Keep what is replaced in the trash so undo can bring it back
+            replaced = self._set_aside(operation_id, destination)
+        result = move_file(source, str(destination), on_conflict)
+        if result['status'] == 'moved':
+            self.journal.record_step(operation_id, 'move', source, result['destination'],
+                                     {'method': result['method']})
+        elif replaced:
+            shutil.move(replaced, str(destination))
+        return result
+
+    def _set_aside(self, operation_id: int, path: Path) -> str:
+        holding = free_name(trash_root(self.config) / f"replaced_{operation_id}" / path.name)
+        holding.parent.mkdir(parents=True, exist_ok=True)
+        shutil.move(str(path), str(holding))
+        self.journal.record_step(operation_id, 'move', str(path), str(holding), {'role': 'replaced'})
+        return str(holding)
+
+    @staticmethod
+    def _preview(source: str, destination: Path, on_conflict: str) -> Dict[str, Any]:
+        preview = {'source': source, 'destination': str(destination), 'status': 'moved',
+                   'method': None, 'message': f"Would move to {destination}"}
+        if not Path(source).is_file():
+            preview.update(status='failed', message=f"Not a file: {source}")
+        elif os.path.lexists(destination) and not _same_file(Path(source), destination):
+            if on_conflict == 'skip':
+                preview.update(status='skipped', message=f"{destination.name} already exists")
+            elif on_conflict == 'keep_both':
+                target = free_name(destination)
+                preview.update(destination=str(target), message=f"Would move to {target}")
+            else:
+                preview['message'] = f"Would replace {destination}"
+        return preview
+
+    @staticmethod
+    def _applied(success: bool, message: str, dry_run: bool) -> Dict[str, Any]:
+        return {'success': success, 'message': message, 'operation_id': None, 'results': [],
+                'moved': 0, 'skipped': 0, 'failed': 0, 'dry_run': dry_run}
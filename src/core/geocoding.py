@@ -0,0 +1,336 @@
+"""
+Reverse Geocoding Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module turns photo GPS coordinates into coarse place names (country
+and city) for location-based photo folders such as `2024/Portugal/Lisbon`.
+
+Lookups are offline by default: the nearest place within
+`geocoding.max_distance_km` is taken from a built-in list of major cities,
+or from a larger dataset the user points `geocoding.dataset` at, either a
+GeoNames cities file (cities15000.txt, tab-separated) or a CSV with
+`city,country,latitude,longitude` columns.
+
+An online lookup (OpenStreetMap Nominatim by default) is only used when
+the user turns on `geocoding.online`. Coordinates are rounded to about a
+kilometre before they leave the machine, requests are spaced at least a
+second apart, and answers are cached in data/geocode_cache.json so each
+place is asked for once.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import csv
+import json
+import logging
+import math
+import threading
+import time
+import urllib.parse
+import urllib.request
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_ENDPOINT = 'https://nominatim.openstreetmap.org/reverse'
+DEFAULT_MAX_DISTANCE_KM = 75.0
+ONLINE_PRECISION = 2          # Decimal places kept when asking online (~1 km)
+MIN_REQUEST_INTERVAL = 1.0    # Nominatim usage policy: at most one request per second
+EARTH_RADIUS_KM = 6371.0
+
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+_request_lock = threading.Lock()
+_last_request = 0.0
+
+# Built-in coarse places: (city, country, latitude, longitude)
+BUILTIN_PLACES: Tuple[Tuple[str, str, float, float], ...] = (
+    # Europe
+    ('Lisbon', 'Portugal', 38.72, -9.14), ('Porto', 'Portugal', 41.15, -8.61),
+    ('Faro', 'Portugal', 37.02, -7.93), ('Coimbra', 'Portugal', 40.21, -8.43),
+    ('Funchal', 'Portugal', 32.65, -16.91), ('Madrid', 'Spain', 40.42, -3.70),
+    ('Barcelona', 'Spain', 41.39, 2.17), ('Valencia', 'Spain', 39.47, -0.38),
+    ('Seville', 'Spain', 37.39, -5.98), ('Malaga', 'Spain', 36.72, -4.42),
+    ('Bilbao', 'Spain', 43.26, -2.93), ('Palma', 'Spain', 39.57, 2.65),
+    ('Las Palmas', 'Spain', 28.12, -15.43), ('Paris', 'France', 48.86, 2.35),
+    ('Lyon', 'France', 45.76, 4.84), ('Marseille', 'France', 43.30, 5.37),
+    ('Nice', 'France', 43.70, 7.27), ('Bordeaux', 'France', 44.84, -0.58),
+    ('Toulouse', 'France', 43.60, 1.44), ('Strasbourg', 'France', 48.57, 7.75),
+    ('London', 'United Kingdom', 51.51, -0.13), ('Manchester', 'United Kingdom', 53.48, -2.24),
+    ('Edinburgh', 'United Kingdom', 55.95, -3.19), ('Glasgow', 'United Kingdom', 55.86, -4.25),
+    ('Birmingham', 'United Kingdom', 52.49, -1.89), ('Belfast', 'United Kingdom', 54.60, -5.93),
+    ('Dublin', 'Ireland', 53.35, -6.26), ('Cork', 'Ireland', 51.90, -8.47),
+    ('Amsterdam', 'Netherlands', 52.37, 4.90), ('Rotterdam', 'Netherlands', 51.92, 4.48),
+    ('Brussels', 'Belgium', 50.85, 4.35), ('Antwerp', 'Belgium', 51.22, 4.40),
+    ('Luxembourg', 'Luxembourg', 49.61, 6.13), ('Berlin', 'Germany', 52.52, 13.40),
+    ('Hamburg', 'Germany', 53.55, 9.99), ('Munich', 'Germany', 48.14, 11.58),
+    ('Cologne', 'Germany', 50.94, 6.96), ('Frankfurt', 'Germany', 50.11, 8.68),
+    ('Stuttgart', 'Germany', 48.78, 9.18), ('Dresden', 'Germany', 51.05, 13.74),
+    ('Zurich', 'Switzerland', 47.38, 8.54), ('Geneva', 'Switzerland', 46.20, 6.14),
+    ('Vienna', 'Austria', 48.21, 16.37), ('Salzburg', 'Austria', 47.81, 13.04),
+    ('Innsbruck', 'Austria', 47.27, 11.39), ('Rome', 'Italy', 41.90, 12.50),
+    ('Milan', 'Italy', 45.46, 9.19), ('Naples', 'Italy', 40.85, 14.27),
+    ('Florence', 'Italy', 43.77, 11.26), ('Venice', 'Italy', 45.44, 12.32),
+    ('Turin', 'Italy', 45.07, 7.69), ('Palermo', 'Italy', 38.12, 13.36),
+    ('Bologna', 'Italy', 44.49, 11.34), ('Copenhagen', 'Denmark', 55.68, 12.57),
+    ('Stockholm', 'Sweden', 59.33, 18.07), ('Gothenburg', 'Sweden', 57.71, 11.97),
+    ('Oslo', 'Norway', 59.91, 10.75), ('Bergen', 'Norway', 60.39, 5.32),
+    ('Helsinki', 'Finland', 60.17, 24.94), ('Reykjavik', 'Iceland', 64.15, -21.94),
+    ('Warsaw', 'Poland', 52.23, 21.01), ('Krakow', 'Poland', 50.06, 19.94),
+    ('Gdansk', 'Poland', 54.35, 18.65), ('Prague', 'Czechia', 50.08, 14.44),
+    ('Brno', 'Czechia', 49.20, 16.61), ('Bratislava', 'Slovakia', 48.15, 17.11),
+    ('Budapest', 'Hungary', 47.50, 19.04), ('Bucharest', 'Romania', 44.43, 26.10),
+    ('Cluj-Napoca', 'Romania', 46.77, 23.59), ('Timisoara', 'Romania', 45.75, 21.23),
+    ('Iasi', 'Romania', 47.16, 27.59), ('Constanta', 'Romania', 44.18, 28.63),
+    ('Brasov', 'Romania', 45.66, 25.61), ('Chisinau', 'Moldova', 47.01, 28.86),
+    ('Sofia', 'Bulgaria', 42.70, 23.32), ('Varna', 'Bulgaria', 43.21, 27.91),
+    ('Belgrade', 'Serbia', 44.79, 20.45), ('Zagreb', 'Croatia', 45.81, 15.98),
+    ('Split', 'Croatia', 43.51, 16.44), ('Dubrovnik', 'Croatia', 42.65, 18.09),
+    ('Ljubljana', 'Slovenia', 46.06, 14.51), ('Sarajevo', 'Bosnia and Herzegovina', 43.86, 18.41),
+    ('Athens', 'Greece', 37.98, 23.73), ('Thessaloniki', 'Greece', 40.64, 22.94),
+    ('Heraklion', 'Greece', 35.34, 25.14), ('Valletta', 'Malta', 35.90, 14.51),
+    ('Nicosia', 'Cyprus', 35.17, 33.36), ('Kyiv', 'Ukraine', 50.45, 30.52),
+    ('Lviv', 'Ukraine', 49.84, 24.03), ('Odesa', 'Ukraine', 46.48, 30.73),
+    ('Vilnius', 'Lithuania', 54.69, 25.28), ('Riga', 'Latvia', 56.95, 24.11),
+    ('Tallinn', 'Estonia', 59.44, 24.75), ('Istanbul', 'Turkey', 41.01, 28.98),
+    ('Ankara', 'Turkey', 39.93, 32.86), ('Antalya', 'Turkey', 36.90, 30.70),
+    ('Moscow', 'Russia', 55.76, 37.62), ('Saint Petersburg', 'Russia', 59.94, 30.31),
+    # Americas
+    ('New York', 'United States', 40.71, -74.01), ('Los Angeles', 'United States', 34.05, -118.24),
+    ('Chicago', 'United States', 41.88, -87.63), ('San Francisco', 'United States', 37.77, -122.42),
+    ('Seattle', 'United States', 47.61, -122.33), ('Boston', 'United States', 42.36, -71.06),
+    ('Washington', 'United States', 38.91, -77.04), ('Miami', 'United States', 25.76, -80.19),
+    ('Orlando', 'United States', 28.54, -81.38), ('Las Vegas', 'United States', 36.17, -115.14),
+    ('Denver', 'United States', 39.74, -104.99), ('Houston', 'United States', 29.76, -95.37),
+    ('Dallas', 'United States', 32.78, -96.80), ('Atlanta', 'United States', 33.75, -84.39),
+    ('New Orleans', 'United States', 29.95, -90.07), ('Honolulu', 'United States', 21.31, -157.86),
+    ('Toronto', 'Canada', 43.65, -79.38), ('Montreal', 'Canada', 45.50, -73.57),
+    ('Vancouver', 'Canada', 49.28, -123.12), ('Calgary', 'Canada', 51.05, -114.07),
+    ('Mexico City', 'Mexico', 19.43, -99.13), ('Cancun', 'Mexico', 21.16, -86.85),
+    ('Havana', 'Cuba', 23.11, -82.37), ('Bogota', 'Colombia', 4.71, -74.07),
+    ('Lima', 'Peru', -12.05, -77.04), ('Cusco', 'Peru', -13.53, -71.97),
+    ('Santiago', 'Chile', -33.45, -70.67), ('Buenos Aires', 'Argentina', -34.60, -58.38),
+    ('Rio de Janeiro', 'Brazil', -22.91, -43.17), ('Sao Paulo', 'Brazil', -23.55, -46.63),
+    # Africa and the Middle East
+    ('Cairo', 'Egypt', 30.04, 31.24), ('Marrakesh', 'Morocco', 31.63, -7.99),
+    ('Casablanca', 'Morocco', 33.57, -7.59), ('Tunis', 'Tunisia', 36.81, 10.18),
+    ('Cape Town', 'South Africa', -33.92, 18.42), ('Johannesburg', 'South Africa', -26.20, 28.05),
+    ('Nairobi', 'Kenya', -1.29, 36.82), ('Lagos', 'Nigeria', 6.52, 3.38),
+    ('Dubai', 'United Arab Emirates', 25.20, 55.27), ('Abu Dhabi', 'United Arab Emirates', 24.45, 54.38),
+    ('Doha', 'Qatar', 25.29, 51.53), ('Tel Aviv', 'Israel', 32.09, 34.78),
+    ('Jerusalem', 'Israel', 31.77, 35.21), ('Amman', 'Jordan', 31.95, 35.93),
+    # Asia and Oceania
+    ('Tokyo', 'Japan', 35.68, 139.69), ('Osaka', 'Japan', 34.69, 135.50),
+    ('Kyoto', 'Japan', 35.01, 135.77), ('Sapporo', 'Japan', 43.06, 141.35),
+    ('Seoul', 'South Korea', 37.57, 126.98), ('Busan', 'South Korea', 35.18, 129.08),
+    ('Beijing', 'China', 39.90, 116.41), ('Shanghai', 'China', 31.23, 121.47),
+    ('Hong Kong', 'China', 22.32, 114.17), ('Taipei', 'Taiwan', 25.03, 121.57),
+    ('Bangkok', 'Thailand', 13.76, 100.50), ('Phuket', 'Thailand', 7.88, 98.39),
+    ('Chiang Mai', 'Thailand', 18.79, 98.98), ('Hanoi', 'Vietnam', 21.03, 105.85),
+    ('Ho Chi Minh City', 'Vietnam', 10.82, 106.63), ('Singapore', 'Singapore', 1.35, 103.82),
+    ('Kuala Lumpur', 'Malaysia', 3.14, 101.69), ('Jakarta', 'Indonesia', -6.21, 106.85),
+    ('Denpasar', 'Indonesia', -8.65, 115.22), ('Manila', 'Philippines', 14.60, 120.98),
+    ('Delhi', 'India', 28.61, 77.21), ('Mumbai', 'India', 19.08, 72.88),
+    ('Bengaluru', 'India', 12.97, 77.59), ('Goa', 'India', 15.50, 73.83),
+    ('Kathmandu', 'Nepal', 27.72, 85.32), ('Colombo', 'Sri Lanka', 6.93, 79.86),
+    ('Male', 'Maldives', 4.18, 73.51), ('Sydney', 'Australia', -33.87, 151.21),
+    ('Melbourne', 'Australia', -37.81, 144.96), ('Brisbane', 'Australia', -27.47, 153.03),
+    ('Perth', 'Australia', -31.95, 115.86), ('Auckland', 'New Zealand', -36.85, 174.76),
+    ('Queenstown', 'New Zealand', -45.03, 168.66),
+)
+
+# Country names for the ISO codes found in GeoNames files (others keep their code)
+COUNTRY_NAMES = {
+    'PT': 'Portugal', 'ES': 'Spain', 'FR': 'France', 'GB': 'United Kingdom', 'IE': 'Ireland',
+    'NL': 'Netherlands', 'BE': 'Belgium', 'LU': 'Luxembourg', 'DE': 'Germany', 'CH': 'Switzerland',
+    'AT': 'Austria', 'IT': 'Italy', 'DK': 'Denmark', 'SE': 'Sweden', 'NO': 'Norway', 'FI': 'Finland',
+    'IS': 'Iceland', 'PL': 'Poland', 'CZ': 'Czechia', 'SK': 'Slovakia', 'HU': 'Hungary',
+    'RO': 'Romania', 'MD': 'Moldova', 'BG': 'Bulgaria', 'RS': 'Serbia', 'HR': 'Croatia',
+    'SI': 'Slovenia', 'BA': 'Bosnia and Herzegovina', 'GR': 'Greece', 'MT': 'Malta', 'CY': 'Cyprus',
+    'UA': 'Ukraine', 'LT': 'Lithuania', 'LV': 'Latvia', 'EE': 'Estonia', 'TR': 'Turkey',
+    'RU': 'Russia', 'US': 'United States', 'CA': 'Canada', 'MX': 'Mexico', 'CU': 'Cuba',
+    'CO': 'Colombia', 'PE': 'Peru', 'CL': 'Chile', 'AR': 'Argentina', 'BR': 'Brazil',
+    'EG': 'Egypt', 'MA': 'Morocco', 'TN': 'Tunisia', 'ZA': 'South Africa', 'KE': 'Kenya',
+    'NG': 'Nigeria', 'AE': 'United Arab Emirates', 'QA': 'Qatar', 'IL': 'Israel', 'JO': 'Jordan',
+    'JP': 'Japan', 'KR': 'South Korea', 'CN': 'China', 'HK': 'China', 'TW': 'Taiwan',
+    'TH': 'Thailand', 'VN': 'Vietnam', 'SG': 'Singapore', 'MY': 'Malaysia', 'ID': 'Indonesia',
+    'PH': 'Philippines', 'IN': 'India', 'NP': 'Nepal', 'LK': 'Sri Lanka', 'MV': 'Maldives',
+    'AU': 'Australia', 'NZ': 'New Zealand',
+}
+
+
+def distance_km(lat1: float, lon1: float, lat2: float, lon2: float) -> float:
+    """Great-circle distance between two points."""
+    phi1, phi2 = math.radians(lat1), math.radians(lat2)
+    dphi, dlambda = phi2 - phi1, math.radians(lon2 - lon1)
+    a = math.sin(dphi / 2) ** 2 + math.cos(phi1) * math.cos(phi2) * math.sin(dlambda / 2) ** 2
+    return 2 * EARTH_RADIUS_KM * math.asin(min(1.0, math.sqrt(a)))
+
+
+class ReverseGeocoder:
+    """
+    Resolves coordinates to {'country', 'city'}.
+
+    Attributes:
+        online (bool): Whether the online lookup may be used (privacy toggle)
+        endpoint (str): Nominatim-compatible reverse geocoding URL
+        max_distance_km (float): How far the nearest offline place may be
+    """
+
+    def __init__(self, config, cache_path: Optional[str] = None):
+        """
+        Initialize reverse geocoder.
+
+        Args:
+            config: Configuration object (reads `geocoding_settings`)
+            cache_path (str, optional): Online answer cache (default: data/geocode_cache.json)
+        """
+        settings = getattr(config, 'geocoding_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.online = bool(settings.get('online', False))
+        self.endpoint = settings.get('endpoint') or DEFAULT_ENDPOINT
+        self.max_distance_km = float(settings.get('max_distance_km', DEFAULT_MAX_DISTANCE_KM))
+        self.dataset = settings.get('dataset') or ''
+        self.cache_path = Path(cache_path or settings.get('cache_file')
+                               or _APP_ROOT / 'data' / 'geocode_cache.json').expanduser()
+        self._places: Optional[Dict[Tuple[int, int], List[Tuple[str, str, float, float]]]] = None
+        self._cache: Optional[Dict[str, Any]] = None
+        self._lock = threading.Lock()
+
+    def place(self, latitude: float, longitude: float) -> Optional[Dict[str, str]]:
+        """
+        Coarse place name for a point.
+
+        Args:
+            latitude (float): Decimal latitude
+            longitude (float): Decimal longitude
+
+        Returns:
+            Dict or None: 'country', 'city' and 'source' ('offline' or 'online')
+        """
+        nearest = self.nearest_offline(latitude, longitude)
+        if nearest is not None:
+            return nearest
+        if self.online:
+            return self.lookup_online(latitude, longitude)
+        return None
+
+    # ==================== Offline ====================
+
+    def nearest_offline(self, latitude: float, longitude: float) -> Optional[Dict[str, str]]:
+        """Nearest known place within max_distance_km, or None."""
+        places = self._index()
+        # Grid cells are 1 degree; search as many cells as the radius spans
+        lat_cells = int(math.ceil(self.max_distance_km / 111.0))
+        lon_cells = int(math.ceil(self.max_distance_km / max(1.0, 111.0 * math.cos(math.radians(latitude)))))
+        cell_lat, cell_lon = math.floor(latitude), math.floor(longitude)
+        best, best_distance = None, self.max_distance_km
+        for dlat in range(-lat_cells, lat_cells + 1):
+            for dlon in range(-min(lon_cells, 180), min(lon_cells, 180) + 1):
+                lon_key = (cell_lon + dlon + 180) % 360 - 180
+                for place in places.get((cell_lat + dlat, lon_key), ()):
+                    d = distance_km(latitude, longitude, place[2], place[3])
+                    if d <= best_distance:
+                        best, best_distance = place, d
+        if best is None:
+            return None
+        return {'country': best[1], 'city': best[0], 'source': 'offline'}
+
+    def _index(self) -> Dict[Tuple[int, int], List[Tuple[str, str, float, float]]]:
+        with self._lock:
+            if self._places is None:
+                places = list(BUILTIN_PLACES)
+                if self.dataset:
+                    places = self._load_dataset(Path(self.dataset).expanduser()) or places
+                index: Dict[Tuple[int, int], List[Tuple[str, str, float, float]]] = {}
+                for place in places:
+                    index.setdefault((math.floor(place[2]), math.floor(place[3])), []).append(place)
+                self._places = index
+            return self._places
+
+    @staticmethod
+    def _load_dataset(path: Path) -> List[Tuple[str, str, float, float]]:
+        """Read a GeoNames cities file or a city,country,latitude,longitude CSV."""
+        places = []
+        try:
+            with open(path, 'r', encoding='utf-8', newline='') as f:
+                first = f.readline()
+                f.seek(0)
+                if first.count('\t') >= 8:
+                    # GeoNames: name is column 1, latitude 4, longitude 5, country code 8
+                    for row in csv.reader(f, delimiter='\t', quoting=csv.QUOTE_NONE):
+                        if len(row) > 8:
+                            places.append((row[1], COUNTRY_NAMES.get(row[8], row[8]),
+                                           float(row[4]), float(row[5])))
+                else:
+                    for row in csv.DictReader(f):
+                        places.append((row['city'], row['country'], float(row['latitude']), float(row['longitude'])))
+        except (OSError, KeyError, ValueError) as e:
+            logger.warning(f"Cannot read geocoding dataset {path}: {e}; using the built-in places")
+            return []
+        return places
+
+    # ==================== Online ====================
+
+    def lookup_online(self, latitude: float, longitude: float) -> Optional[Dict[str, str]]:
+        """
+        Ask the online service (only when `geocoding.online` is on).
+
+        Returns:
+            Dict or None: Place, or None if disabled, unknown or unreachable
+        """
+        if not self.online:
+            return None
+        lat, lon = round(latitude, ONLINE_PRECISION), round(longitude, ONLINE_PRECISION)
+        key = f"{lat:.{ONLINE_PRECISION}f},{lon:.{ONLINE_PRECISION}f}"
+        cache = self._load_cache()
+        if key in cache:
+            return cache[key]
+
+        query = urllib.parse.urlencode({'format': 'jsonv2', 'lat': lat, 'lon': lon,
+                                        'zoom': 10, 'accept-language': 'en'})
+        request = urllib.request.Request(f"{self.endpoint}?{query}",
+                                         headers={'User-Agent': 'AI-File-Organiser (photo folders)'})
+        global _last_request
+        try:
+            with _request_lock:
+                wait = MIN_REQUEST_INTERVAL - (time.monotonic() - _last_request)
+                if wait > 0:
+                    time.sleep(wait)
+                _last_request = time.monotonic()
+                with urllib.request.urlopen(request, timeout=10) as response:
+                    answer = json.loads(response.read().decode('utf-8'))
+        except (OSError, ValueError) as e:
+            logger.warning(f"Online reverse geocoding failed: {e}")
+            return None  # Not cached: try again next time
+
+        address = answer.get('address', {}) if isinstance(answer, dict) else {}
+        city = next((address[k] for k in ('city', 'town', 'village', 'municipality', 'county', 'state')
+                     if address.get(k)), None)
+        place = ({'country': address['country'], 'city': city, 'source': 'online'}
+                 if address.get('country') and city else None)
+        cache[key] = place
+        self._save_cache()
+        return place
+
+    def _load_cache(self) -> Dict[str, Any]:
+        if self._cache is None:
+            try:
+                self._cache = json.loads(self.cache_path.read_text(encoding='utf-8'))
+            except (OSError, ValueError):
+                self._cache = {}
+        return self._cache
+
+    def _save_cache(self) -> None:
+        try:
+            self.cache_path.parent.mkdir(parents=True, exist_ok=True)
+            self.cache_path.write_text(json.dumps(self._cache, indent=2, ensure_ascii=False), encoding='utf-8')
+        except OSError as e:
+            logger.warning(f"Cannot save geocoding cache: {e}")
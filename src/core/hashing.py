@@ -0,0 +1,92 @@
+"""
+Content Hashing Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module hashes file content for every subsystem that keys or compares
+files by what is in them: duplicates, collections, kept versions,
+snapshots, folder comparison, face scans, learned corrections, the
+classification cache and verified cross-drive moves. They all use
+hash_file() with the default algorithm, so a content hash stored by one
+can be matched against another's:
+
+    hash_file('~/Pictures/cat.jpg')
+    -> '9f2c...'  (BLAKE2b-256, hex)
+
+Other algorithms (xxhash, sha256, sha1, md5) are for callers that ask for
+one, e.g. a duplicate scan run with --algorithm, or a snapshot taken
+before BLAKE2b was the default.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import hashlib
+import logging
+from typing import Optional
+
+try:
+    import xxhash
+    XXHASH_SUPPORT = True
+except ImportError:
+    XXHASH_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+ALGORITHMS = ('blake2b', 'xxhash', 'sha256', 'sha1', 'md5')
+DEFAULT_ALGORITHM = 'blake2b'
+CHUNK_SIZE = 1024 * 1024
+
+
+def new_hasher(algorithm: str):
+    """
+    Create a hasher.
+
+    Args:
+        algorithm (str): One of ALGORITHMS ('xxhash' needs the xxhash package)
+
+    Raises:
+        ValueError: If the algorithm is unknown or not installed
+    """
+    if algorithm == 'blake2b':
+        return hashlib.blake2b(digest_size=32)
+    if algorithm == 'xxhash':
+        if not XXHASH_SUPPORT:
+            raise ValueError("xxhash is not installed (pip install xxhash)")
+        return xxhash.xxh3_128()
+    if algorithm in ('sha256', 'sha1', 'md5'):
+        return hashlib.new(algorithm)
+    raise ValueError(f"Unknown hash algorithm: {algorithm} (use {', '.join(ALGORITHMS)})")
+
+
+def hash_file(file_path, algorithm: str = DEFAULT_ALGORITHM, limit: Optional[int] = None) -> str:
+    """
+    Hash a file's content (or its first `limit` bytes).
+
+    Args:
+        file_path (str or Path): File
+        algorithm (str): One of ALGORITHMS
+        limit (int, optional): Only hash this many bytes
+
+    Returns:
+        str: Hex digest
+
+    Raises:
+        OSError: If the file cannot be read
+    """
+    hasher = new_hasher(algorithm)
+    remaining = limit
+    with open(file_path, 'rb') as f:
+        while remaining is None or remaining > 0:
+            chunk = f.read(CHUNK_SIZE if remaining is None else min(CHUNK_SIZE, remaining))
+            if not chunk:
+                break
+            hasher.update(chunk)
+            if remaining is not None:
+                remaining -= len(chunk)
+    return hasher.hexdigest()
@@ -0,0 +1,275 @@
+"""
+Folder History Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module reconstructs what a folder looked like at a past point in time
+("show me Desktop as of last Monday") and diffs it against the present.
+
+The reconstruction starts from the newest snapshot covering the folder
+taken before that time and replays the journaled and logged file changes
+up to it. Without such a snapshot it starts from the folder as it is now
+and reverses every change made since. Both sources only know about changes
+made through the app, so files created or removed by other programs since
+the last snapshot are not reflected.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+import re
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple, Set, NamedTuple
+
+logger = logging.getLogger(__name__)
+
+
+WEEKDAYS = ('monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday')
+RELATIVE_PATTERN = re.compile(r'^(\d+)\s*(minute|hour|day|week)s?\s+ago$')
+DB_TIME_FORMAT = '%Y-%m-%d %H:%M:%S'
+
+
+def parse_point_in_time(text: str, now: Optional[datetime] = None) -> datetime:
+    """
+    Parse a point in time given by the user.
+
+    Accepts ISO dates and datetimes, 'now', 'today', 'yesterday',
+    'last <weekday>' and 'N minutes/hours/days/weeks ago'. A bare day means
+    the end of that day, i.e. the folder as it was when the day was over.
+
+    Args:
+        text (str): Point in time
+        now (datetime, optional): Reference time (local), defaults to now
+
+    Returns:
+        datetime: Naive local datetime
+
+    Raises:
+        ValueError: If the text is not understood
+    """
+    now = now or datetime.now()
+    value = text.strip().lower()
+
+    def end_of(day) -> datetime:
+        return datetime.combine(day, datetime.max.time()).replace(microsecond=0)
+
+    if value in ('now', 'today'):
+        return now
+    if value == 'yesterday':
+        return end_of((now - timedelta(days=1)).date())
+
+    match = RELATIVE_PATTERN.match(value)
+    if match:
+        amount, unit = int(match.group(1)), match.group(2)
+        return now - timedelta(**{f'{unit}s': amount})
+
+    if value.startswith('last '):
+        weekday = value[5:].strip()
+        if weekday in WEEKDAYS:
+            days_back = (now.weekday() - WEEKDAYS.index(weekday)) % 7 or 7
+            return end_of((now - timedelta(days=days_back)).date())
+
+    try:
+        if re.fullmatch(r'\d{4}-\d{2}-\d{2}', value):
+            return end_of(datetime.strptime(value, '%Y-%m-%d').date())
+        return datetime.fromisoformat(text.strip()).replace(tzinfo=None)
+    except ValueError:
+        raise ValueError(f"Unrecognized point in time: {text!r}") from None
+
+
+def _to_db_time(moment: datetime) -> str:
+    """Naive local datetime -> SQLite CURRENT_TIMESTAMP format (UTC)."""
+    return moment.astimezone(timezone.utc).strftime(DB_TIME_FORMAT)
+
+
+def _normalize(path: Optional[str]) -> Optional[str]:
+    return os.path.realpath(os.path.expanduser(path)) if path else None
+
+
+class FileChange(NamedTuple):
+    """A file leaving `source` and arriving at `dest` (None = created / deleted)."""
+    time: str
+    source: Optional[str]
+    dest: Optional[str]
+
+
+class FolderHistory:
+    """
+    Reconstructs past folder states from snapshots and the journal.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager with the journal, file log and snapshots
+    """
+
+    def __init__(self, config, db_manager):
+        """
+        Initialize folder history.
+
+        Args:
+            config: Configuration object
+            db_manager: Database manager instance
+        """
+        self.config = config
+        self.db_manager = db_manager
+
+    # ==================== Change Log ====================
+
+    def _changes_since(self, since: str) -> List[FileChange]:
+        """
+        Collect file changes from the journal and the file log, oldest first.
+
+        File log entries duplicating a journal step are dropped; undone
+        journal steps contribute both the change and its reversal.
+        """
+        changes: List[Tuple[str, int, FileChange]] = []
+        journaled: Set[Tuple[Optional[str], Optional[str]]] = set()
+        trashed: Set[str] = set()
+
+        for step in self.db_manager.get_operation_steps_since(since):
+            details = json.loads(step['details']) if step.get('details') else {}
+            if step['action'] == 'mkdir' or details.get('role') == 'staging':
+                continue
+            source = _normalize(step['source_path']) if step['action'] == 'move' else None
+            dest = _normalize(step['dest_path'])
+            journaled.add((source, dest))
+            if details.get('role') == 'trash' and source:
+                trashed.add(source)
+            if step['created_at'] >= since:
+                changes.append((step['created_at'], 0, FileChange(step['created_at'], source, dest)))
+            if step['status'] == 'undone' and step.get('undone_at') and step['undone_at'] >= since:
+                changes.append((step['undone_at'], 0, FileChange(step['undone_at'], dest, source)))
+
+        for entry in self.db_manager.get_file_changes_since(since):
+            source, dest = _normalize(entry['old_path']), _normalize(entry['new_path'])
+            if (source, dest) in journaled or (dest is None and source in trashed):
+                continue
+            changes.append((entry['timestamp'], 1, FileChange(entry['timestamp'], source, dest)))
+
+        changes.sort(key=lambda item: (item[0], item[1]))
+        return [change for _, _, change in changes]
+
+    # ==================== Reconstruction ====================
+
+    @staticmethod
+    def _inside(path: Optional[str], folder: str) -> bool:
+        if not path:
+            return False
+        try:
+            return os.path.commonpath([path, folder]) == folder and path != folder
+        except ValueError:
+            return False
+
+    @staticmethod
+    def _present_files(folder: str) -> Set[str]:
+        files: Set[str] = set()
+        for dirpath, _dirnames, filenames in os.walk(folder):
+            for name in filenames:
+                path = os.path.join(dirpath, name)
+                if not os.path.islink(path):
+                    files.add(path)
+        return files
+
+    def _base_snapshot(self, folder: str, as_of: str) -> Optional[Dict[str, Any]]:
+        """Newest snapshot covering the folder taken at or before as_of."""
+        for snapshot in self.db_manager.list_snapshots(limit=500):
+            root = _normalize(snapshot['root_path'])
+            if snapshot['created_at'] <= as_of and root and (root == folder or self._inside(folder, root)):
+                return snapshot
+        return None
+
+    def folder_as_of(self, folder: str, when: datetime, include_files: bool = True) -> Dict[str, Any]:
+        """
+        Reconstruct a folder at a past point in time and diff it against now.
+
+        Args:
+            folder (str): Folder to inspect
+            when (datetime): Naive local point in time
+            include_files (bool): Include the full past file list
+
+        Returns:
+            Dict: Result with 'success', 'message', 'source', 'file_count',
+                  'files' (relative paths) and 'diff' with 'added', 'removed'
+                  and 'moved' ({'path', 'now_at'}) lists
+        """
+        folder_path = _normalize(folder)
+        if not folder_path or not os.path.isdir(folder_path):
+            return {'success': False, 'message': f'Not a directory: {folder}'}
+        if when > datetime.now():
+            return {'success': False, 'message': 'Point in time is in the future'}
+
+        as_of = _to_db_time(when)
+        present = self._present_files(folder_path)
+        snapshot = self._base_snapshot(folder_path, as_of)
+
+        since = min(as_of, snapshot['created_at']) if snapshot else as_of
+        changes = self._changes_since(since)
+        before = [c for c in changes if c.time <= as_of]
+        after = [c for c in changes if c.time > as_of]
+
+        if snapshot:
+            # Forward from the snapshot to the requested time
+            full = self.db_manager.get_snapshot(snapshot['id'])
+            root = _normalize(full['root_path'])
+            past = {p for p in (os.path.join(root, *e['rel_path'].split('/')) for e in full['entries'])
+                    if self._inside(p, folder_path)}
+            for change in before:
+                past.discard(change.source)
+                if self._inside(change.dest, folder_path):
+                    past.add(change.dest)
+            source = f"snapshot #{snapshot['id']} ({snapshot['created_at']} UTC) and journal"
+        else:
+            # Backward from the present, reversing everything done since
+            past = set(present)
+            for change in reversed(after):
+                past.discard(change.dest)
+                if self._inside(change.source, folder_path):
+                    past.add(change.source)
+            source = 'journal'
+
+        # Follow files that are gone from the folder to where they are now
+        now_at: Dict[str, Optional[str]] = {p: p for p in past - present}
+        by_location = {p: p for p in now_at}
+        for change in after:
+            original = by_location.pop(change.source, None) if change.source else None
+            if original is not None:
+                now_at[original] = change.dest
+                if change.dest:
+                    by_location[change.dest] = original
+
+        def relative(path: str) -> str:
+            return Path(path).relative_to(folder_path).as_posix()
+
+        moved = [{'path': relative(p), 'now_at': now_at[p]} for p in sorted(now_at)
+                 if now_at[p] and now_at[p] != p and os.path.exists(now_at[p])]
+        moved_paths = {m['path'] for m in moved}
+        removed = [relative(p) for p in sorted(now_at) if relative(p) not in moved_paths]
+        added = [relative(p) for p in sorted(present - past)]
+
+        result = {
+            'success': True,
+            'message': (f'{folder_path} as of {when.isoformat(sep=" ", timespec="minutes")}: '
+                        f'{len(past)} file(s); {len(added)} added, {len(removed)} removed, '
+                        f'{len(moved)} moved since'),
+            'folder': folder_path,
+            'as_of': when.isoformat(),
+            'source': source,
+            'file_count': len(past),
+            'diff': {
+                'added': added,
+                'removed': removed,
+                'moved': moved,
+                'unchanged': len(past & present)
+            }
+        }
+        if include_files:
+            result['files'] = sorted(relative(p) for p in past)
+        return result
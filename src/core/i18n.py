@@ -0,0 +1,292 @@
+"""
+Localization Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module translates built-in category names and generated destination
+folders into the user's language (e.g. "Documents/Finance/Invoices/" becomes
+"Dokumente/Finanzen/Rechnungen/" for German). Classification keeps the
+canonical English category internally so rules, statistics and caches stay
+stable when the user switches locale; only the presented label and the
+destination path are localized.
+
+The locale is selected with the `locale` key in config.json (default: "en").
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+from typing import Dict, Any, List, Optional
+
+
+DEFAULT_LOCALE = 'en'
+
+# Canonical (English) folder/category name -> localized name.
+# Product names (PDFs, Word, Excel, PowerPoint) are deliberately not translated.
+TRANSLATIONS: Dict[str, Dict[str, str]] = {
+    'de': {
+        'Documents': 'Dokumente',
+        'Pictures': 'Bilder',
+        'Videos': 'Videos',
+        'Music': 'Musik',
+        'Downloads': 'Downloads',
+        'Archives': 'Archive',
+        'Text': 'Text',
+        'Finance': 'Finanzen',
+        'Invoices': 'Rechnungen',
+        'Receipts': 'Quittungen',
+        'Taxes': 'Steuern',
+        'Personal': 'Persönlich',
+        'Resume': 'Lebenslauf',
+        'Screenshots': 'Bildschirmfotos',
+        'Projects': 'Projekte',
+        'Work': 'Arbeit',
+        'Photos': 'Fotos',
+        'Unsorted': 'Unsortiert',
+    },
+    'fr': {
+        'Documents': 'Documents',
+        'Pictures': 'Images',
+        'Videos': 'Vidéos',
+        'Music': 'Musique',
+        'Downloads': 'Téléchargements',
+        'Archives': 'Archives',
+        'Text': 'Texte',
+        'Finance': 'Finances',
+        'Invoices': 'Factures',
+        'Receipts': 'Reçus',
+        'Taxes': 'Impôts',
+        'Personal': 'Personnel',
+        'Resume': 'CV',
+        'Screenshots': "Captures d'écran",
+        'Projects': 'Projets',
+        'Work': 'Travail',
+        'Photos': 'Photos',
+        'Unsorted': 'Non triés',
+    },
+    'es': {
+        'Documents': 'Documentos',
+        'Pictures': 'Imágenes',
+        'Videos': 'Vídeos',
+        'Music': 'Música',
+        'Downloads': 'Descargas',
+        'Archives': 'Archivos comprimidos',
+        'Text': 'Texto',
+        'Finance': 'Finanzas',
+        'Invoices': 'Facturas',
+        'Receipts': 'Recibos',
+        'Taxes': 'Impuestos',
+        'Personal': 'Personal',
+        'Resume': 'Currículum',
+        'Screenshots': 'Capturas de pantalla',
+        'Projects': 'Proyectos',
+        'Work': 'Trabajo',
+        'Photos': 'Fotos',
+        'Unsorted': 'Sin clasificar',
+    },
+    'it': {
+        'Documents': 'Documenti',
+        'Pictures': 'Immagini',
+        'Videos': 'Video',
+        'Music': 'Musica',
+        'Downloads': 'Download',
+        'Archives': 'Archivi',
+        'Text': 'Testo',
+        'Finance': 'Finanze',
+        'Invoices': 'Fatture',
+        'Receipts': 'Ricevute',
+        'Taxes': 'Tasse',
+        'Personal': 'Personale',
+        'Resume': 'Curriculum',
+        'Screenshots': 'Screenshot',
+        'Projects': 'Progetti',
+        'Work': 'Lavoro',
+        'Photos': 'Foto',
+        'Unsorted': 'Non ordinati',
+    },
+    'pt': {
+        'Documents': 'Documentos',
+        'Pictures': 'Imagens',
+        'Videos': 'Vídeos',
+        'Music': 'Música',
+        'Downloads': 'Transferências',
+        'Archives': 'Arquivos',
+        'Text': 'Texto',
+        'Finance': 'Finanças',
+        'Invoices': 'Faturas',
+        'Receipts': 'Recibos',
+        'Taxes': 'Impostos',
+        'Personal': 'Pessoal',
+        'Resume': 'Currículo',
+        'Screenshots': 'Capturas de ecrã',
+        'Projects': 'Projetos',
+        'Work': 'Trabalho',
+        'Photos': 'Fotografias',
+        'Unsorted': 'Por organizar',
+    },
+    'ro': {
+        'Documents': 'Documente',
+        'Pictures': 'Imagini',
+        'Videos': 'Videoclipuri',
+        'Music': 'Muzică',
+        'Downloads': 'Descărcări',
+        'Archives': 'Arhive',
+        'Text': 'Text',
+        'Finance': 'Finanțe',
+        'Invoices': 'Facturi',
+        'Receipts': 'Chitanțe',
+        'Taxes': 'Taxe',
+        'Personal': 'Personal',
+        'Resume': 'CV',
+        'Screenshots': 'Capturi de ecran',
+        'Projects': 'Proiecte',
+        'Work': 'Muncă',
+        'Photos': 'Fotografii',
+        'Unsorted': 'Nesortate',
+    },
+    'nl': {
+        'Documents': 'Documenten',
+        'Pictures': 'Afbeeldingen',
+        'Videos': "Video's",
+        'Music': 'Muziek',
+        'Downloads': 'Downloads',
+        'Archives': 'Archieven',
+        'Text': 'Tekst',
+        'Finance': 'Financiën',
+        'Invoices': 'Facturen',
+        'Receipts': 'Bonnetjes',
+        'Taxes': 'Belastingen',
+        'Personal': 'Persoonlijk',
+        'Resume': 'CV',
+        'Screenshots': 'Schermafbeeldingen',
+        'Projects': 'Projecten',
+        'Work': 'Werk',
+        'Photos': "Foto's",
+        'Unsorted': 'Ongesorteerd',
+    },
+}
+
+
+def supported_locales() -> List[str]:
+    """Return the list of supported locale codes (English first)."""
+    return [DEFAULT_LOCALE] + sorted(TRANSLATIONS.keys())
+
+
+def normalize_locale(locale: Optional[str]) -> str:
+    """
+    Normalize a locale string to a supported two-letter code.
+
+    Accepts forms like 'de', 'de_DE', 'de-AT' or 'DE'. Unknown locales fall
+    back to English.
+
+    Args:
+        locale (str, optional): Locale string from settings
+
+    Returns:
+        str: Supported locale code
+    """
+    if not locale:
+        return DEFAULT_LOCALE
+    code = str(locale).replace('-', '_').split('_')[0].strip().lower()
+    return code if code in TRANSLATIONS or code == DEFAULT_LOCALE else DEFAULT_LOCALE
+
+
+class Translator:
+    """
+    Translates canonical category and folder names for a single locale.
+
+    Attributes:
+        locale (str): Active locale code
+    """
+
+    def __init__(self, locale: Optional[str] = None):
+        """
+        Initialize translator.
+
+        Args:
+            locale (str, optional): Locale code; defaults to English
+        """
+        self.locale = normalize_locale(locale)
+        table = TRANSLATIONS.get(self.locale, {})
+        # Case-insensitive lookup keyed by the lowercase canonical name
+        self._table = {key.lower(): value for key, value in table.items()}
+
+    def translate(self, name: str) -> str:
+        """
+        Translate a single category or folder name.
+
+        Unknown names (years, client names, AI-invented folders) are returned
+        unchanged.
+
+        Args:
+            name (str): Canonical English name
+
+        Returns:
+            str: Localized name
+        """
+        if not name or not self._table:
+            return name
+        return self._table.get(name.lower(), name)
+
+    def localize_path(self, suggested_path: Optional[str]) -> Optional[str]:
+        """
+        Translate every known segment of a relative destination path.
+
+        Args:
+            suggested_path (str, optional): Path like 'Documents/Finance/Invoices/'
+
+        Returns:
+            str or None: Localized path with the original trailing slash preserved
+        """
+        if not suggested_path or not self._table:
+            return suggested_path
+
+        trailing = suggested_path.endswith('/')
+        parts = [self.translate(part) for part in suggested_path.strip('/').split('/') if part]
+        localized = '/'.join(parts)
+        return f"{localized}/" if trailing and localized else localized
+
+    def localize_classification(self, result: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Return a localized copy of a classification result.
+
+        The canonical 'category' is kept; a 'category_label' with the
+        translated name is added and 'suggested_path' is localized.
+
+        Args:
+            result (Dict): Classification result from FileClassifier
+
+        Returns:
+            Dict: New result dictionary (the input is not modified)
+        """
+        localized = dict(result)
+        category = result.get('category')
+        if category:
+            localized['category_label'] = self.translate(category)
+        if result.get('suggested_path'):
+            localized['suggested_path'] = self.localize_path(result['suggested_path'])
+        localized['locale'] = self.locale
+        return localized
+
+
+_translators: Dict[str, Translator] = {}
+
+
+def get_translator(locale: Optional[str] = None) -> Translator:
+    """
+    Get a cached translator for a locale.
+
+    Args:
+        locale (str, optional): Locale code
+
+    Returns:
+        Translator: Translator instance
+    """
+    code = normalize_locale(locale)
+    if code not in _translators:
+        _translators[code] = Translator(code)
+    return _translators[code]
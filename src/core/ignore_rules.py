@@ -0,0 +1,320 @@
+"""
+Ignore Rules Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module decides which files and folders the organizer never touches.
+Rules are read, in this order, from:
+
+- the built-in defaults (DEFAULT_RULES: .git, node_modules, temp and
+  partial downloads, system files)
+- the "exclusions" of the user's settings (see core.settings)
+- the user's global ignore file (organizerignore in the app config dir)
+- a .organizerignore file in the folder being listed or organized
+
+One rule per line, '#' starts a comment; as in .gitignore the last rule
+that matches wins, so a later "!*.tmp" takes back an earlier "*.tmp":
+
+    node_modules/        a folder (trailing '/'), at any depth
+    *.iso                a glob on the name, at any depth
+    /Projects/*.psd      a glob on the path from the folder (has a '/')
+    !keep-this.tmp       not ignored after all
+    size > 4GB           files larger than 4 GB (also <, KB, MB, TB)
+    hidden               dot-files and files marked hidden
+    system               Thumbs.db, desktop.ini, .DS_Store and files
+                         marked system
+
+list_files() and the organize planner leave out whatever the rules ignore
+and do not look inside ignored folders.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import fnmatch
+import logging
+import os
+import re
+import stat as stat_flags
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .settings import SettingsStore, app_config_dir
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+IGNORE_FILE = '.organizerignore'
+GLOBAL_IGNORE_FILE = 'organizerignore'
+
+DEFAULT_RULES = [
+    '.git/', '.svn/', '.hg/', 'node_modules/', '__pycache__/', '.venv/',
+    '*.tmp', '*.temp', '*.part', '*.partial', '*.crdownload', '*.download', '*.swp', '~$*',
+    'system',
+]
+
+SYSTEM_NAMES = {'.ds_store', 'thumbs.db', 'desktop.ini', '.localized', '.spotlight-v100', '.trashes',
+                '.fseventsd', '$recycle.bin', 'system volume information', 'icon\r'}
+_SIZE_RULE = re.compile(r'^size\s*([<>])\s*(\d+(?:\.\d+)?)\s*([KMGT]?B?)$', re.IGNORECASE)
+_UNITS = {'': 1, 'B': 1, 'K': 1024, 'KB': 1024, 'M': 1024 ** 2, 'MB': 1024 ** 2,
+          'G': 1024 ** 3, 'GB': 1024 ** 3, 'T': 1024 ** 4, 'TB': 1024 ** 4}
+# Windows file attributes (os.stat_result.st_file_attributes)
+_HIDDEN_ATTRIBUTE = getattr(stat_flags, 'FILE_ATTRIBUTE_HIDDEN', 2)
+_SYSTEM_ATTRIBUTE = getattr(stat_flags, 'FILE_ATTRIBUTE_SYSTEM', 4)
+
+
+@dataclass
+class IgnoreRule:
+    """One parsed line of an ignore file."""
+    text: str
+    kind: str  # 'glob', 'size', 'hidden' or 'system'
+    negated: bool = False
+    pattern: str = ''
+    anchored: bool = False
+    directory_only: bool = False
+    larger: bool = False
+    size: int = 0
+    limit: str = ''  # The size as written, e.g. "4GB"
+
+    @property
+    def reason(self) -> str:
+        if self.kind == 'size':
+            return f"{'larger' if self.larger else 'smaller'} than {self.limit}"
+        if self.kind in ('hidden', 'system'):
+            return f"{self.kind} file"
+        return f"matches '{self.text}'"
+
+
+def parse_rule(line: str) -> Optional[IgnoreRule]:
+    """
+    Parse one ignore line.
+
+    Args:
+        line (str): Line of an ignore file
+
+    Returns:
+        Optional[IgnoreRule]: The rule, or None for blank lines and comments
+
+    Raises:
+        ValueError: If the line is not a valid rule
+    """
+    text = line.strip()
+    if not text or text.startswith('#'):
+        return None
+    lowered = text.lower()
+    if lowered in ('hidden', 'system'):
+        return IgnoreRule(text=text, kind=lowered)
+    size = _SIZE_RULE.match(text)
+    if size:
+        unit = size.group(3).upper()
+        return IgnoreRule(text=text, kind='size', larger=size.group(1) == '>',
+                          size=int(float(size.group(2)) * _UNITS[unit]), limit=size.group(2) + (unit or 'B'))
+    if lowered.startswith('size'):
+        raise ValueError(f"Invalid size rule {text!r} (e.g. 'size > 4GB')")
+    negated = text.startswith('!')
+    pattern = text[1:] if negated else text
+    directory_only = pattern.endswith('/')
+    pattern = pattern.rstrip('/')
+    anchored = '/' in pattern
+    pattern = pattern.lstrip('/')
+    if not pattern:
+        raise ValueError(f"Empty pattern in rule {text!r}")
+    return IgnoreRule(text=text, kind='glob', negated=negated, pattern=pattern,
+                      anchored=anchored, directory_only=directory_only)
+
+
+def parse_rules(lines: List[str], source: str = '') -> List[IgnoreRule]:
+    """Parse ignore lines, skipping (and logging) invalid ones."""
+    rules = []
+    for number, line in enumerate(lines, 1):
+        try:
+            rule = parse_rule(line)
+        except ValueError as e:
+            logger.warning(f"{source or 'ignore rules'}:{number}: {e}")
+            continue
+        if rule is not None:
+            rules.append(rule)
+    return rules
+
+
+def global_ignore_path() -> Path:
+    """The user's global ignore file."""
+    return app_config_dir() / GLOBAL_IGNORE_FILE
+
+
+def _read_lines(path: Path) -> List[str]:
+    try:
+        return path.read_text(encoding='utf-8').splitlines()
+    except FileNotFoundError:
+        return []
+    except (OSError, UnicodeDecodeError) as e:
+        logger.warning(f"Cannot read ignore file {path}: {e}")
+        return []
+
+
+class IgnoreRules:
+    """The ignore rules in force for one folder."""
+
+    def __init__(self, root: str, rules: List[IgnoreRule]):
+        """
+        Initialize with parsed rules.
+
+        Args:
+            root (str): Folder the rules apply to (anchored patterns are relative to it)
+            rules (List[IgnoreRule]): Rules in order (the last match wins)
+        """
+        self.root = Path(os.path.abspath(Path(root).expanduser()))
+        self.rules = rules
+        # Only size rules, and the hidden / system attributes of Windows, need a file's stat
+        self._needs_stat = any(rule.kind == 'size' or (os.name == 'nt' and rule.kind in ('hidden', 'system'))
+                               for rule in rules)
+
+    @classmethod
+    def for_folder(cls, folder: str, defaults: bool = True) -> 'IgnoreRules':
+        """
+        The rules for a folder: defaults, settings exclusions, the global file and its .organizerignore.
+
+        Args:
+            folder (str): Folder being listed or organized
+            defaults (bool): Start from DEFAULT_RULES
+        """
+        root = Path(os.path.abspath(Path(folder).expanduser()))
+        rules = parse_rules(DEFAULT_RULES) if defaults else []
+        try:
+            rules += parse_rules(SettingsStore().load()['exclusions'], 'settings exclusions')
+        except ConfigurationError as e:
+            logger.warning(f"Settings exclusions not applied: {e}")
+        path = global_ignore_path()
+        rules += parse_rules(_read_lines(path), str(path))
+        rules += parse_rules(_read_lines(root / IGNORE_FILE), str(root / IGNORE_FILE))
+        return cls(str(root), rules)
+
+    def match(self, path: str, is_dir: Optional[bool] = None, stat: Optional[os.stat_result] = None) -> Optional[str]:
+        """
+        Why a path is ignored, looking at the path itself only (not its folders).
+
+        Args:
+            path (str): File or folder below the root (or relative to it)
+            is_dir (bool, optional): Whether it is a folder (looked up when None)
+            stat (os.stat_result, optional): Its stat, if already known
+
+        Returns:
+            Optional[str]: The reason, or None when it is not ignored
+        """
+        full = Path(path) if os.path.isabs(path) else self.root / path
+        try:
+            relative = full.relative_to(self.root).as_posix()
+        except ValueError:
+            relative = full.name
+        if is_dir is None:
+            is_dir = full.is_dir()
+        if stat is None and self._needs_stat:
+            try:
+                stat = full.stat()
+            except OSError:
+                stat = None
+        name = full.name
+        reason = None
+        for rule in self.rules:
+            if self._matches(rule, name, relative, is_dir, stat):
+                reason = None if rule.negated else rule.reason
+        return reason
+
+    def ignores(self, path: str) -> Optional[str]:
+        """
+        Why a path is ignored, its folders below the root included.
+
+        Args:
+            path (str): File or folder
+
+        Returns:
+            Optional[str]: The reason, or None when it may be organized
+        """
+        full = Path(os.path.abspath(path))
+        try:
+            parts = full.relative_to(self.root).parts
+        except ValueError:
+            return self.match(str(full))
+        for depth in range(1, len(parts)):
+            reason = self.match(str(self.root.joinpath(*parts[:depth])), is_dir=True)
+            if reason:
+                return reason
+        return self.match(str(full))
+
+    @staticmethod
+    def _matches(rule: IgnoreRule, name: str, relative: str, is_dir: bool,
+                 stat: Optional[os.stat_result]) -> bool:
+        if rule.kind == 'glob':
+            if rule.directory_only and not is_dir:
+                return False
+            return fnmatch.fnmatch(relative if rule.anchored else name, rule.pattern)
+        attributes = getattr(stat, 'st_file_attributes', 0) if stat is not None else 0
+        if rule.kind == 'size':
+            if is_dir or stat is None:
+                return False
+            return stat.st_size > rule.size if rule.larger else stat.st_size < rule.size
+        if rule.kind == 'hidden':
+            return name.startswith('.') or bool(attributes & _HIDDEN_ATTRIBUTE)
+        return name.lower() in SYSTEM_NAMES or bool(attributes & _SYSTEM_ATTRIBUTE)
+
+
+# ==================== Managing rule files ====================
+
+def _rules_file(folder: Optional[str]) -> Path:
+    if folder is None:
+        return global_ignore_path()
+    path = Path(folder).expanduser()
+    if not path.is_dir():
+        raise NotADirectoryError(f"Not a folder: {path}")
+    return path / IGNORE_FILE
+
+
+def list_ignore_rules(folder: Optional[str] = None) -> Dict[str, Any]:
+    """
+    The rules of a folder's .organizerignore (or of the global file).
+
+    Args:
+        folder (str, optional): Folder; default: the global ignore file
+
+    Returns:
+        Dict: 'path', 'rules' (the file's rules as written) and 'defaults'
+    """
+    path = _rules_file(folder)
+    return {'path': str(path), 'rules': [rule.text for rule in parse_rules(_read_lines(path), str(path))],
+            'defaults': list(DEFAULT_RULES)}
+
+
+def add_ignore_rule(rule: str, folder: Optional[str] = None) -> Dict[str, Any]:
+    """
+    Append a rule to a folder's .organizerignore (or to the global file).
+
+    Raises:
+        ValueError: If the rule is not valid
+    """
+    parsed = parse_rule(rule)
+    if parsed is None:
+        raise ValueError("An ignore rule cannot be empty or a comment")
+    path = _rules_file(folder)
+    lines = _read_lines(path)
+    if parsed.text in (line.strip() for line in lines):
+        return {'success': True, 'message': f"Already in {path.name}: {parsed.text}", 'path': str(path)}
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text('\n'.join(lines + [parsed.text]) + '\n', encoding='utf-8')
+    return {'success': True, 'message': f"Added to {path.name}: {parsed.text}", 'path': str(path)}
+
+
+def remove_ignore_rule(rule: str, folder: Optional[str] = None) -> Dict[str, Any]:
+    """Remove a rule (and only that line; comments are kept) from an ignore file."""
+    path = _rules_file(folder)
+    lines = _read_lines(path)
+    kept = [line for line in lines if line.strip() != rule.strip()]
+    if len(kept) == len(lines):
+        return {'success': False, 'message': f"Not in {path.name}: {rule.strip()}", 'path': str(path)}
+    path.write_text('\n'.join(kept) + '\n' if kept else '', encoding='utf-8')
+    return {'success': True, 'message': f"Removed from {path.name}: {rule.strip()}", 'path': str(path)}
@@ -0,0 +1,228 @@
+"""
+Interpreter Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module decides which Python interpreter runs the engine's child
+processes (the sidecar, streaming organize runs) and where the app's
+scripts are, so packaged builds and Windows - where the interpreter is
+`python` or `py`, not `python3` - work as well as a source checkout.
+
+The interpreter is the first of:
+
+1. the AIFO_PYTHON environment variable
+2. `runtime.python` in config.json
+3. a Python bundled with the app, in its resource dir (AIFO_RESOURCE_DIR,
+   set by the packaged app, or the frozen executable's folder)
+4. a virtual environment in the app folder (.venv or venv)
+5. the interpreter running now, unless the app is a frozen executable
+6. python3 / python (py on Windows) on PATH
+
+The app folder is AIFO_APP_ROOT, `runtime.app_root`, an "app" folder in
+the resource dir, or the folder this source tree is in - whichever first
+holds src/main.py.
+
+    "runtime": {"python": "C:/Python312/python.exe", "app_root": ""}
+
+detect_python_environments() lists every interpreter it can find, with
+its version and whether it has the required packages, for the settings
+page to offer.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+import shutil
+import subprocess
+import sys
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+_SOURCE_ROOT = Path(__file__).resolve().parent.parent.parent
+MARKER = Path('src') / 'main.py'
+VENV_DIRS = ('.venv', 'venv', 'env')
+PROBE_TIMEOUT = 10.0  # Seconds for a candidate interpreter to answer
+# Printed by a candidate: its version, whether it is a venv, and whether the required packages import
+_PROBE = ("import importlib.util, json, sys; print(json.dumps({'version': '.'.join(map(str, sys.version_info[:3])), "
+          "'venv': sys.prefix != getattr(sys, 'base_prefix', sys.prefix), "
+          "'packages': all(importlib.util.find_spec(m) for m in ('requests', 'yaml', 'pydantic', 'click'))}))")
+
+
+def _settings(config) -> Dict[str, Any]:
+    if config is None:
+        try:
+            from src.config import get_config
+            config = get_config()
+        except Exception as e:
+            logger.debug(f"No configuration for the runtime settings: {e}")
+            return {}
+    settings = getattr(config, 'runtime_settings', None)
+    return settings if isinstance(settings, dict) else {}
+
+
+def _python_in(folder: Path) -> Optional[Path]:
+    """The interpreter of a Python installation or venv folder, if it has one."""
+    for relative in (('Scripts', 'python.exe'), ('python.exe',), ('bin', 'python3'), ('bin', 'python')):
+        candidate = folder.joinpath(*relative)
+        if candidate.is_file():
+            return candidate
+    return None
+
+
+def resource_dir() -> Optional[Path]:
+    """The packaged app's resource dir, or None when running from source."""
+    configured = os.environ.get('AIFO_RESOURCE_DIR')
+    if configured:
+        return Path(configured).expanduser()
+    if getattr(sys, 'frozen', False):
+        return Path(getattr(sys, '_MEIPASS', Path(sys.executable).parent))
+    return None
+
+
+def python_on_path() -> Optional[str]:
+    """The first Python interpreter on PATH, by this platform's usual names."""
+    names = ('python', 'py', 'python3') if os.name == 'nt' else ('python3', 'python')
+    return next((found for found in map(shutil.which, names) if found), None)
+
+
+def resolve_app_root(config=None) -> Path:
+    """
+    The folder holding the app's scripts (src/main.py).
+
+    Args:
+        config: Configuration object (reads `runtime_settings`; default: the active configuration)
+
+    Returns:
+        Path: The app folder (the source tree's when nothing else holds the scripts)
+    """
+    candidates = [os.environ.get('AIFO_APP_ROOT'), _settings(config).get('app_root')]
+    resources = resource_dir()
+    if resources is not None:
+        candidates += [str(resources / 'app'), str(resources)]
+    for candidate in candidates:
+        if candidate and (Path(candidate).expanduser() / MARKER).is_file():
+            return Path(candidate).expanduser().resolve()
+        if candidate:
+            logger.debug(f"No {MARKER} in {candidate}")
+    return _SOURCE_ROOT
+
+
+def _candidates(config) -> List[Tuple[str, str]]:
+    """(interpreter, source) in resolution order; may not exist."""
+    settings = _settings(config)
+    candidates = []
+    if os.environ.get('AIFO_PYTHON'):
+        candidates.append((os.environ['AIFO_PYTHON'], 'environment'))
+    if settings.get('python'):
+        candidates.append((str(settings['python']), 'settings'))
+    resources = resource_dir()
+    if resources is not None:
+        for folder in (resources / 'python', resources / 'runtime', resources):
+            bundled = _python_in(folder)
+            if bundled:
+                candidates.append((str(bundled), 'bundled'))
+    root = resolve_app_root(config)
+    for name in VENV_DIRS:
+        venv = _python_in(root / name)
+        if venv:
+            candidates.append((str(venv), 'venv'))
+    if not getattr(sys, 'frozen', False) and sys.executable:
+        candidates.append((sys.executable, 'current'))
+    on_path = python_on_path()
+    if on_path:
+        candidates.append((on_path, 'path'))
+    return candidates
+
+
+def resolve_python(config=None) -> str:
+    """
+    The interpreter to run the engine's child processes with.
+
+    Args:
+        config: Configuration object (reads `runtime_settings`; default: the active configuration)
+
+    Returns:
+        str: Path of the interpreter
+
+    Raises:
+        FileNotFoundError: If no interpreter can be found
+    """
+    for path, source in _candidates(config):
+        found = shutil.which(path) if not os.path.isabs(path) else (path if os.path.isfile(path) else None)
+        if found:
+            return found
+        if source in ('environment', 'settings'):
+            logger.warning(f"Python interpreter from {source} not found: {path}")
+    raise FileNotFoundError("No Python interpreter found; set runtime.python in config.json or AIFO_PYTHON")
+
+
+def probe_python(path: str) -> Optional[Dict[str, Any]]:
+    """Version, venv and package status of an interpreter, or None if it does not run."""
+    try:
+        completed = subprocess.run([path, '-c', _PROBE], capture_output=True, text=True, timeout=PROBE_TIMEOUT)
+        if completed.returncode != 0:
+            return None
+        return json.loads(completed.stdout.strip().splitlines()[-1])
+    except (OSError, subprocess.SubprocessError, ValueError, IndexError):
+        return None
+
+
+def _conda_environments() -> List[Path]:
+    """Interpreters of conda environments listed in ~/.conda/environments.txt."""
+    listing = Path.home() / '.conda' / 'environments.txt'
+    try:
+        folders = [Path(line.strip()) for line in listing.read_text(encoding='utf-8').splitlines() if line.strip()]
+    except OSError:
+        return []
+    return [python for python in map(_python_in, folders) if python]
+
+
+def detect_python_environments(config=None, probe: bool = True) -> Dict[str, Any]:
+    """
+    Find candidate interpreters: configured, bundled, venvs, conda environments and PATH.
+
+    Args:
+        config: Configuration object (default: the active configuration)
+        probe (bool): Run each one to read its version and packages
+
+    Returns:
+        Dict: 'selected' (what resolve_python() picks, or None), 'app_root' and
+              'environments' (one {'path', 'source', 'selected', 'exists',
+              'version', 'venv', 'packages'} per interpreter)
+    """
+    try:
+        selected = resolve_python(config)
+    except FileNotFoundError:
+        selected = None
+    candidates = _candidates(config)
+    candidates += [(str(python), 'conda') for python in _conda_environments()]
+    if os.name == 'nt':
+        candidates += [(found, 'path') for found in map(shutil.which, ('python', 'py', 'python3')) if found]
+
+    environments, seen = [], set()
+    for path, source in candidates:
+        resolved = shutil.which(path) or path
+        key = os.path.normcase(os.path.abspath(resolved))
+        if key in seen:
+            continue
+        seen.add(key)
+        exists = os.path.isfile(resolved)
+        info = probe_python(resolved) if probe and exists else None
+        environments.append({
+            'path': resolved, 'source': source, 'exists': exists,
+            'selected': selected is not None and key == os.path.normcase(os.path.abspath(selected)),
+            'version': info['version'] if info else None,
+            'venv': info['venv'] if info else None,
+            'packages': info['packages'] if info else None,
+        })
+    return {'selected': selected, 'app_root': str(resolve_app_root(config)), 'environments': environments}
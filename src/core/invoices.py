@@ -0,0 +1,508 @@
+"""
+Invoice Fields Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module reads the vendor, date and total of invoices and receipts so
+they can be filed by them, e.g.
+
+    Finance/2024/2024-03 Amazon 59.99.pdf
+
+The text comes from the document itself (PDF text, plain text, HTML or
+e-mail), or from Tesseract OCR for photographed and scanned receipts.
+Fields are found with patterns first: totals in common formats
+(1,234.56 and 1.234,56, with currency symbols or codes), dates (ISO,
+numeric and written out), and the vendor from a list of well-known
+sellers, a "Sold by"/"From" line, the sender's domain or the letterhead.
+When `invoices.llm` is on and the local AI model is available, it fills
+in whatever the patterns missed.
+
+The destination template (`invoices.template`) takes {year}, {month},
+{day}, {vendor}, {amount}, {currency} and {number}; its last part is the
+file name. scan() collects the fields of every invoice in a folder for
+a CSV export.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import csv
+import json
+import logging
+import re
+from datetime import date
+from email import policy as email_policy
+from email.parser import BytesParser
+from html.parser import HTMLParser
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .file_names import safe_name
+from .ocr import OcrEngine
+
+logger = logging.getLogger(__name__)
+
+try:
+    import PyPDF2  # type: ignore
+    HAS_PYPDF2 = True
+except ImportError:
+    PyPDF2 = None  # type: ignore
+    HAS_PYPDF2 = False
+
+
+DEFAULT_TEMPLATE = 'Finance/{year}/{year}-{month} {vendor} {amount}'
+TEXT_EXTENSIONS = {'.txt', '.md', '.csv', '.html', '.htm', '.xml'}
+IMAGE_EXTENSIONS = {'.jpg', '.jpeg', '.png', '.tif', '.tiff', '.bmp', '.webp'}
+INVOICE_EXTENSIONS = {'.pdf', '.eml'} | TEXT_EXTENSIONS | IMAGE_EXTENSIONS
+CSV_FIELDS = ('path', 'vendor', 'date', 'amount', 'currency', 'number', 'source')
+MAX_TEXT = 20000
+
+INVOICE_WORDS = re.compile(
+    r'(?i)\b(invoice|receipt|tax invoice|bill to|amount due|order total|payment received|'
+    r'rechnung|quittung|beleg|facture|re[çc]u|factura|recibo|fattura|ricevuta|factuur|bon)\b')
+INVOICE_NAME = re.compile(r'(?i)(invoice|receipt|rechnung|facture|factura|fattura|factuur|quittung|bill)')
+KNOWN_VENDORS = (
+    'Amazon', 'Apple', 'Google', 'Microsoft', 'PayPal', 'Uber', 'Lyft', 'Netflix', 'Spotify', 'Adobe',
+    'Dropbox', 'GitHub', 'DigitalOcean', 'Stripe', 'Shopify', 'eBay', 'Etsy', 'IKEA', 'Airbnb', 'Booking.com',
+    'Ryanair', 'easyJet', 'Lufthansa', 'Vodafone', 'Orange', 'Deutsche Telekom', 'Zalando', 'AliExpress',
+    'Walmart', 'Target', 'Costco', 'Best Buy', 'Tesco', 'Sainsbury', 'Lidl', 'Aldi', 'Carrefour', 'Steam',
+    'Nintendo', 'PlayStation', 'Dell', 'Lenovo', 'Hetzner', 'OVH', 'Zoom', 'Slack',
+)
+TOTAL_LINE = re.compile(
+    r'(?i)(grand total|total due|amount due|balance due|amount paid|total amount|invoice total|order total|'
+    r'total|gesamtbetrag|gesamtsumme|summe|zu zahlen|montant total|total ttc|net à payer|importe total|'
+    r'totale|te betalen|totaal)')
+SUBTOTAL_LINE = re.compile(r'(?i)(sub[- ]?total|zwischensumme|sous-total|subtotale|tax|vat|mwst|tva|iva)\b')
+AMOUNT = re.compile(
+    r'(?P<pre>[$€£¥]|USD|EUR|GBP|CHF|CAD|AUD|JPY|RON|PLN|SEK)?\s?'
+    r'(?P<num>\d{1,3}(?:[.,' "'" r' ]\d{3})*(?:[.,]\d{2})|\d+(?:[.,]\d{2}))'
+    r'\s?(?P<post>[€$£]|EUR|USD|GBP|CHF|RON|PLN|SEK|lei|zł|kr)?(?![\d])')
+SYMBOLS = {'$': 'USD', '€': 'EUR', '£': 'GBP', '¥': 'JPY', 'lei': 'RON', 'zł': 'PLN', 'kr': 'SEK'}
+MONTHS = {
+    'jan': 1, 'feb': 2, 'mar': 3, 'apr': 4, 'may': 5, 'jun': 6, 'jul': 7, 'aug': 8, 'sep': 9, 'oct': 10,
+    'nov': 11, 'dec': 12, 'januar': 1, 'februar': 2, 'märz': 3, 'mai': 5, 'juni': 6, 'juli': 7, 'okt': 10,
+    'dez': 12, 'janv': 1, 'févr': 2, 'mars': 3, 'avr': 4, 'juin': 6, 'juil': 7, 'août': 8, 'déc': 12,
+    'ene': 1, 'abr': 4, 'ago': 8, 'dic': 12,
+}
+DATE_LINE = re.compile(r'(?i)(invoice date|date of issue|issue date|order date|billing date|date|datum|fecha|data)')
+_ISO_DATE = re.compile(r'\b(20\d{2})[-/.](\d{1,2})[-/.](\d{1,2})\b')
+_NUMERIC_DATE = re.compile(r'\b(\d{1,2})[-/.](\d{1,2})[-/.](20\d{2}|\d{2})\b')
+_WORD_DATE = re.compile(r'(?i)\b(?:(\d{1,2})\.?\s+([a-zäéû]{3,9})\.?,?\s+(20\d{2})|([a-zäéû]{3,9})\.?\s+(\d{1,2}),?\s+(20\d{2}))\b')
+_NUMBER_LINE = re.compile(r'(?i)(?:invoice|receipt|order|rechnung|facture|factura)\s*(?:no\.?|number|nr\.?|#|n[°º])\s*[:#]?\s*([A-Z0-9][A-Z0-9\-/]{2,30})')
+_VENDOR_LINE = re.compile(r'(?i)^\s*(?:sold by|seller|from|vendor|merchant|issued by|verkäufer)\s*[:\-]\s*(.+)$')
+_SENDER = re.compile(r'(?i)[\w.+-]+@(?:[\w-]+\.)*?([\w-]+)\.(?:com|net|org|io|co|de|fr|es|it|nl|eu|uk|ro|co\.uk)\b')
+_MAIL_DOMAINS = {'gmail', 'googlemail', 'outlook', 'hotmail', 'yahoo', 'icloud', 'me', 'proton', 'protonmail', 'gmx', 'web'}
+
+
+class _TextOnly(HTMLParser):
+    def __init__(self):
+        super().__init__()
+        self.parts: List[str] = []
+
+    def handle_data(self, data):
+        self.parts.append(data)
+
+    def handle_starttag(self, tag, attrs):
+        if tag in ('br', 'p', 'div', 'tr', 'li', 'h1', 'h2', 'h3'):
+            self.parts.append('\n')
+
+
+def _html_text(html: str) -> str:
+    parser = _TextOnly()
+    parser.feed(html)
+    return re.sub(r'[ \t]+', ' ', ''.join(parser.parts))
+
+
+def parse_amount(number: str) -> Optional[float]:
+    """'1.234,56' or '1,234.56' or '59.99' -> float."""
+    text = number.replace(' ', '').replace("'", '')
+    if re.search(r',\d{2}$', text):
+        text = text.replace('.', '').replace(',', '.')
+    else:
+        text = text.replace(',', '')
+    try:
+        return float(text)
+    except ValueError:
+        return None
+
+
+def find_total(text: str) -> Tuple[Optional[float], Optional[str]]:
+    """
+    The total of an invoice: the last amount on the last total line.
+
+    Args:
+        text (str): Invoice text
+
+    Returns:
+        Tuple: (amount or None, currency code or None)
+    """
+    found: Tuple[Optional[float], Optional[str]] = (None, None)
+    currency = None
+    lines = text.splitlines()
+    for index, line in enumerate(lines):
+        symbol = re.search(r'[$€£¥]|\b(USD|EUR|GBP|CHF|CAD|AUD|JPY|RON|PLN|SEK)\b', line)
+        if symbol and currency is None:
+            currency = SYMBOLS.get(symbol.group(0), symbol.group(0))
+        if not TOTAL_LINE.search(line) or SUBTOTAL_LINE.search(line.split(':')[0]):
+            continue
+        # The amount may sit on the next line in column layouts
+        for candidate in (line, lines[index + 1] if index + 1 < len(lines) else ''):
+            amounts = list(AMOUNT.finditer(candidate))
+            if amounts:
+                match = amounts[-1]
+                value = parse_amount(match.group('num'))
+                code = match.group('pre') or match.group('post')
+                if value is not None:
+                    found = (value, SYMBOLS.get(code, code) if code else None)
+                break
+    if found[0] is not None and found[1] is None:
+        found = (found[0], currency)
+    return found
+
+
+def find_date(text: str, day_first: Optional[bool] = None) -> Optional[date]:
+    """
+    The invoice date: a date on a 'Date:' line, else the first date.
+
+    Args:
+        text (str): Invoice text
+        day_first (bool, optional): Read 03/04/2024 as 3 April; default: yes,
+                                    unless the text is in US dollars
+
+    Returns:
+        date or None
+    """
+    if day_first is None:
+        day_first = '$' not in text and 'USD' not in text
+    lines = text.splitlines()
+    ordered = [l for l in lines if DATE_LINE.search(l) and not re.search(r'(?i)due|fällig|échéance', l)] + lines
+    for line in ordered:
+        parsed = _parse_date(line, day_first)
+        if parsed is not None:
+            return parsed
+    return None
+
+
+def _parse_date(line: str, day_first: bool) -> Optional[date]:
+    candidates = []
+    for m in _ISO_DATE.finditer(line):
+        candidates.append((m.start(), int(m.group(1)), int(m.group(2)), int(m.group(3))))
+    for m in _NUMERIC_DATE.finditer(line):
+        a, b, year = int(m.group(1)), int(m.group(2)), int(m.group(3))
+        year += 2000 if year < 100 else 0
+        day, month = (a, b) if day_first or b > 12 else (b, a)
+        if a > 12 and not day_first:
+            day, month = a, b
+        candidates.append((m.start(), year, month, day))
+    for m in _WORD_DATE.finditer(line):
+        if m.group(1):
+            day, name, year = int(m.group(1)), m.group(2), int(m.group(3))
+        else:
+            name, day, year = m.group(4), int(m.group(5)), int(m.group(6))
+        month = MONTHS.get(name.lower()) or MONTHS.get(name.lower()[:3])
+        if month:
+            candidates.append((m.start(), year, month, day))
+    for _, year, month, day in sorted(candidates):
+        try:
+            return date(year, month, day)
+        except ValueError:
+            continue
+    return None
+
+
+def find_vendor(text: str) -> Optional[str]:
+    """The seller, from known names, a 'Sold by' line, the sender's domain or the letterhead."""
+    head = text[:3000]
+    for vendor in KNOWN_VENDORS:
+        if re.search(rf'(?i)\b{re.escape(vendor)}\b', head):
+            return vendor
+    for line in text.splitlines():
+        match = _VENDOR_LINE.match(line)
+        if match and match.group(1).strip():
+            return match.group(1).strip()[:40]
+    sender = _SENDER.search(head)
+    if sender and sender.group(1).lower() not in _MAIL_DOMAINS:
+        return sender.group(1).capitalize()
+    for line in text.splitlines()[:8]:
+        line = line.strip()
+        if (3 <= len(line) <= 40 and re.search(r'[A-Za-z]{3}', line) and not INVOICE_WORDS.search(line)
+                and not DATE_LINE.match(line) and not TOTAL_LINE.search(line) and not AMOUNT.search(line)
+                and not re.search(r'\d{3,}|@|www\.|https?:', line)):
+            return line
+    return None
+
+
+def find_fields(text: str, day_first: Optional[bool] = None) -> Dict[str, Any]:
+    """
+    Invoice fields found by patterns.
+
+    Args:
+        text (str): Invoice text
+        day_first (bool, optional): See find_date()
+
+    Returns:
+        Dict: 'vendor', 'date' (ISO), 'amount', 'currency' and 'number', None where not found
+    """
+    amount, currency = find_total(text)
+    found_date = find_date(text, day_first)
+    number = _NUMBER_LINE.search(text)
+    return {
+        'vendor': find_vendor(text),
+        'date': found_date.isoformat() if found_date else None,
+        'amount': amount,
+        'currency': currency,
+        'number': number.group(1) if number else None,
+    }
+
+
+class InvoiceExtractor:
+    """
+    Extracts invoice fields and plans destinations from them.
+
+    Attributes:
+        config: Configuration object
+        template (str): Destination template
+        use_llm (bool): Ask the local AI model for fields the patterns missed
+    """
+
+    def __init__(self, config, ollama_client=None):
+        """
+        Initialize invoice extractor.
+
+        Args:
+            config: Configuration object (reads `invoice_settings`)
+            ollama_client: Optional Ollama client for the LLM fallback
+        """
+        self.config = config
+        self.ollama_client = ollama_client
+        settings = getattr(config, 'invoice_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', True))
+        self.template = settings.get('template') or DEFAULT_TEMPLATE
+        self.use_ocr = bool(settings.get('ocr', True))
+        self.use_llm = bool(settings.get('llm', False))
+        day_first = settings.get('day_first')
+        self.day_first = None if day_first is None else bool(day_first)
+        self._ocr = OcrEngine(config)
+
+    # ==================== Text ====================
+
+    def read_text(self, path: Path) -> Tuple[Optional[str], str]:
+        """
+        A document's text.
+
+        Args:
+            path (Path): Document
+
+        Returns:
+            Tuple: (text or None, source: 'text', 'pdf', 'email' or 'ocr')
+        """
+        suffix = path.suffix.lower()
+        try:
+            if suffix == '.pdf' and HAS_PYPDF2:
+                with open(path, 'rb') as f:
+                    reader = PyPDF2.PdfReader(f)
+                    text = '\n'.join((page.extract_text() or '') for page in reader.pages[:3])
+                if not text.strip() and self.use_ocr:
+                    # A scan without a text layer
+                    return self._ocr_text(path), 'ocr'
+                return text[:MAX_TEXT] or None, 'pdf'
+            if suffix in TEXT_EXTENSIONS:
+                text = path.read_text(encoding='utf-8', errors='ignore')[:MAX_TEXT * 5]
+                if suffix in ('.html', '.htm'):
+                    text = _html_text(text)
+                return text[:MAX_TEXT], 'text'
+            if suffix == '.eml':
+                with open(path, 'rb') as f:
+                    message = BytesParser(policy=email_policy.default).parse(f)
+                body = message.get_body(preferencelist=('plain', 'html'))
+                content = body.get_content() if body is not None else ''
+                if body is not None and body.get_content_type() == 'text/html':
+                    content = _html_text(content)
+                return f"From: {message.get('from', '')}\nDate: {message.get('date', '')}\n{content}"[:MAX_TEXT], 'email'
+            if suffix in IMAGE_EXTENSIONS and self.use_ocr:
+                return self._ocr_text(path), 'ocr'
+        except Exception as e:  # PyPDF2 and the e-mail parser raise their own error types
+            logger.debug(f"Cannot read text of {path}: {e}")
+        return None, 'text'
+
+    def _ocr_text(self, path: Path) -> Optional[str]:
+        return self._ocr.ocr_file(str(path))[:MAX_TEXT] or None
+
+    # ==================== Fields ====================
+
+    def extract(self, file_path: str, text: Optional[str] = None) -> Optional[Dict[str, Any]]:
+        """
+        Fields of an invoice or receipt.
+
+        Args:
+            file_path (str): Document
+            text (str, optional): Its text, if already extracted
+
+        Returns:
+            Dict or None: Fields (see find_fields) plus 'source', or None if
+                          the document does not look like an invoice
+        """
+        path = Path(file_path)
+        if not self.enabled or path.suffix.lower() not in INVOICE_EXTENSIONS:
+            return None
+        # Images are only worth OCR when their name says receipt or invoice
+        if text is None and path.suffix.lower() in IMAGE_EXTENSIONS and not INVOICE_NAME.search(path.stem):
+            return None
+        source = 'text'
+        if text is None:
+            text, source = self.read_text(path)
+        if not text or not (INVOICE_WORDS.search(text) or INVOICE_NAME.search(path.stem)):
+            return None
+
+        fields = find_fields(text, self.day_first)
+        fields['source'] = source
+        if self.use_llm and self.ollama_client is not None and not all(
+                fields[k] for k in ('vendor', 'date', 'amount')):
+            for key, value in self._ask_llm(text).items():
+                if not fields.get(key) and value:
+                    fields[key] = value
+                    fields['source'] = f"{source}+llm"
+        if fields['amount'] is None and fields['date'] is None:
+            return None
+        return fields
+
+    def _ask_llm(self, text: str) -> Dict[str, Any]:
+        prompt = ("Extract the vendor (seller name), invoice date (YYYY-MM-DD), total amount (number) and "
+                  "currency (ISO code) from this invoice. Reply with JSON only, like "
+                  '{"vendor": "Acme", "date": "2024-03-15", "amount": 59.99, "currency": "EUR"}.\n\n'
+                  + text[:3000])
+        try:
+            reply = self.ollama_client.chat(prompt)
+            match = re.search(r'\{.*\}', reply or '', re.DOTALL)
+            answer = json.loads(match.group(0)) if match else {}
+        except (ValueError, AttributeError, TypeError) as e:
+            logger.debug(f"LLM invoice extraction failed: {e}")
+            return {}
+        result: Dict[str, Any] = {}
+        if isinstance(answer.get('vendor'), str):
+            result['vendor'] = answer['vendor'].strip()[:40]
+        if isinstance(answer.get('date'), str) and _ISO_DATE.fullmatch(answer['date'].strip()):
+            result['date'] = answer['date'].strip()
+        try:
+            result['amount'] = float(answer['amount']) if answer.get('amount') is not None else None
+        except (TypeError, ValueError):
+            pass
+        if isinstance(answer.get('currency'), str) and re.fullmatch(r'[A-Z]{3}', answer['currency']):
+            result['currency'] = answer['currency']
+        return result
+
+    # ==================== Destinations ====================
+
+    def destination(self, fields: Dict[str, Any], extension: str) -> Tuple[str, str]:
+        """
+        Render the destination template.
+
+        Args:
+            fields (Dict): Fields from extract()
+            extension (str): File extension, e.g. '.pdf'
+
+        Returns:
+            Tuple: (relative folder ending in '/', file name)
+        """
+        when = date.fromisoformat(fields['date']) if fields.get('date') else None
+        amount = fields.get('amount')
+        values = {
+            'year': f"{when:%Y}" if when else 'Undated',
+            'month': f"{when:%m}" if when else '00',
+            'day': f"{when:%d}" if when else '00',
+            'vendor': safe_name(fields.get('vendor')) or 'Unknown vendor',
+            'amount': f"{amount:.2f}" if amount is not None else '',
+            'currency': fields.get('currency') or '',
+            'number': safe_name(fields.get('number')),
+        }
+        try:
+            rendered = self.template.format(**values)
+        except (KeyError, IndexError, ValueError) as e:
+            logger.warning(f"Invalid invoice template {self.template!r}: {e}; using the default")
+            rendered = DEFAULT_TEMPLATE.format(**values)
+        parts = [re.sub(r'\s+', ' ', p).strip(' .') for p in rendered.replace('\\', '/').split('/')]
+        parts = [p for p in parts if p and p != '..']
+        name = parts.pop() if parts else 'Invoice'
+        return ''.join(f"{p}/" for p in parts), f"{name}{extension.lower()}"
+
+    def classify(self, file_path: str, text: Optional[str] = None) -> Optional[Dict[str, Any]]:
+        """
+        Classification for an invoice, filed by its fields.
+
+        Args:
+            file_path (str): Document
+            text (str, optional): Its text, if already extracted
+
+        Returns:
+            Dict or None: Classification with the fields under 'invoice',
+                          or None if the document is not an invoice
+        """
+        fields = self.extract(file_path, text)
+        if fields is None:
+            return None
+        folder, name = self.destination(fields, Path(file_path).suffix)
+        described = ', '.join(str(v) for v in (fields['vendor'], fields['date'],
+                                               fields['amount'] and f"{fields['amount']:.2f} {fields['currency'] or ''}".strip())
+                              if v)
+        return {
+            'category': 'Finance',
+            'suggested_path': folder,
+            'rename': name if name != Path(file_path).name else None,
+            'reason': f"Invoice/receipt ({described})",
+            'confidence': 'high' if all(fields[k] for k in ('vendor', 'date', 'amount')) else 'medium',
+            'method': 'rule-based',
+            'invoice': fields
+        }
+
+    # ==================== Export ====================
+
+    def scan(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Extract the fields of every invoice in a folder.
+
+        Args:
+            folder (str): Folder to scan
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: 'success', 'message', 'rows' (CSV_FIELDS each) and 'documents' checked
+        """
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {root}", 'rows': [], 'documents': 0}
+        pattern = '**/*' if recursive else '*'
+        documents = sorted(p for p in root.glob(pattern) if p.suffix.lower() in INVOICE_EXTENSIONS and p.is_file()
+                           and not any(part.startswith('.') for part in p.relative_to(root).parts))
+        rows = []
+        for document in documents:
+            fields = self.extract(str(document))
+            if fields is not None:
+                rows.append({'path': str(document), **{k: fields.get(k) for k in CSV_FIELDS if k != 'path'}})
+        return {'success': True, 'message': f"{len(rows)} invoice(s) among {len(documents)} document(s)",
+                'rows': rows, 'documents': len(documents)}
+
+    @staticmethod
+    def export_csv(rows: List[Dict[str, Any]], output: str) -> str:
+        """
+        Write extracted fields to a CSV file.
+
+        Args:
+            rows (List[Dict]): Rows from scan()
+            output (str): CSV path
+
+        Returns:
+            str: Path written
+        """
+        out = Path(output).expanduser()
+        out.parent.mkdir(parents=True, exist_ok=True)
+        with open(out, 'w', encoding='utf-8', newline='') as f:
+            writer = csv.DictWriter(f, fieldnames=CSV_FIELDS)
+            writer.writeheader()
+            for row in rows:
+                writer.writerow({k: '' if row.get(k) is None else row.get(k) for k in CSV_FIELDS})
+        return str(out)
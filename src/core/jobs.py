@@ -0,0 +1,230 @@
+"""
+Jobs Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps track of long-running organize runs so they can be
+watched and stopped: each run is a job with a UUID, run in its own child
+process (see core.progress_events), and cancel_job() stops that process.
+
+A job is a dict:
+
+    {"id", "kind", "folder", "preview", "state", "progress", "files_done",
+     "errors", "started_at", "finished_at", "message", "summary"}
+
+States: 'running', 'cancelling', then 'done', 'failed' or 'cancelled'.
+A cancelled run finishes the file it is moving (or is killed after
+CANCEL_GRACE seconds); files already moved stay moved and can be undone
+with `aifo undo`. Only the newest KEEP_FINISHED finished jobs are kept.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import copy
+import logging
+import threading
+import uuid
+from datetime import datetime
+from typing import Callable, Dict, Any, List, Optional
+
+from .progress_events import stream_organize, EVENT_PROGRESS, EVENT_FILE_DONE, EVENT_ERROR
+
+logger = logging.getLogger(__name__)
+
+
+KEEP_FINISHED = 50
+FINISHED_STATES = ('done', 'failed', 'cancelled')
+EVENT_JOB_FINISHED = 'job://finished'  # Sent to the listener with the finished job as payload
+
+
+class JobManager:
+    """
+    Registry of running and recent organize jobs.
+
+    Attributes:
+        runner (Callable): Runs one job (stream_organize, or a stand-in for tests)
+        listener (Callable, optional): Called as (job_id, event, payload) for
+                                       every progress event of every job, and
+                                       with EVENT_JOB_FINISHED when one ends
+    """
+
+    def __init__(self, runner: Callable[..., Dict[str, Any]] = stream_organize,
+                 listener: Optional[Callable[[str, str, Dict[str, Any]], None]] = None):
+        """
+        Initialize job manager.
+
+        Args:
+            runner (Callable): Called as runner(folder, on_event, preview, deep,
+                               strategy, cancel=Event) and returns its result dict
+            listener (Callable, optional): Receives every job's events
+        """
+        self.runner = runner
+        self.listener = listener
+        self._jobs: Dict[str, Dict[str, Any]] = {}
+        self._cancels: Dict[str, threading.Event] = {}
+        self._threads: Dict[str, threading.Thread] = {}
+        self._lock = threading.Lock()
+
+    def start_organize(self, folder: str, preview: bool = False, deep: bool = False,
+                       strategy: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Start organizing a folder in the background.
+
+        Args:
+            folder (str): Folder to organize
+            preview (bool): Only show what would be moved
+            deep (bool): Use deep AI analysis
+            strategy (str, optional): Planner strategy for every file
+
+        Returns:
+            Dict: The new job
+        """
+        job_id = uuid.uuid4().hex
+        job = {
+            'id': job_id,
+            'kind': 'organize',
+            'folder': str(folder),
+            'preview': bool(preview),
+            'state': 'running',
+            'progress': {'phase': 'starting', 'done': 0, 'total': 0, 'file': None},
+            'files_done': 0,
+            'errors': 0,
+            'started_at': datetime.now().isoformat(timespec='seconds'),
+            'finished_at': None,
+            'message': None,
+            'summary': None
+        }
+        cancel = threading.Event()
+        thread = threading.Thread(target=self._run, args=(job_id, folder, preview, deep, strategy, cancel),
+                                  name=f"job-{job_id[:8]}", daemon=True)
+        with self._lock:
+            self._jobs[job_id] = job
+            self._cancels[job_id] = cancel
+            self._threads[job_id] = thread
+            snapshot = copy.deepcopy(job)
+        thread.start()
+        logger.info(f"Job {job_id} started: organize {folder}")
+        return snapshot
+
+    def _run(self, job_id: str, folder: str, preview: bool, deep: bool, strategy: Optional[str],
+             cancel: threading.Event) -> None:
+        def on_event(event: str, payload: Dict[str, Any]) -> None:
+            with self._lock:
+                job = self._jobs[job_id]
+                if event == EVENT_PROGRESS:
+                    job['progress'] = {key: payload.get(key) for key in ('phase', 'done', 'total', 'file')}
+                elif event == EVENT_FILE_DONE:
+                    job['files_done'] += 1
+                elif event == EVENT_ERROR:
+                    job['errors'] += 1
+            if self.listener is not None:
+                try:
+                    self.listener(job_id, event, payload)
+                except Exception as e:
+                    logger.debug(f"Job listener failed: {e}")
+
+        try:
+            result = self.runner(folder, on_event, preview, deep, strategy, cancel=cancel)
+        except Exception as e:
+            logger.error(f"Job {job_id} crashed: {e}")
+            result = {'success': False, 'message': str(e), 'summary': None}
+
+        with self._lock:
+            job = self._jobs[job_id]
+            if cancel.is_set():
+                job['state'] = 'cancelled'
+            else:
+                job['state'] = 'done' if result.get('success') else 'failed'
+            job['message'] = result.get('message')
+            job['summary'] = result.get('summary')
+            job['finished_at'] = datetime.now().isoformat(timespec='seconds')
+            self._cancels.pop(job_id, None)
+            self._threads.pop(job_id, None)
+            self._prune()
+            finished = copy.deepcopy(job)
+        logger.info(f"Job {job_id} {job['state']}: {job['message']}")
+        if self.listener is not None:
+            try:
+                self.listener(job_id, EVENT_JOB_FINISHED, finished)
+            except Exception as e:
+                logger.debug(f"Job listener failed: {e}")
+
+    def _prune(self) -> None:
+        finished = [job for job in self._jobs.values() if job['state'] in FINISHED_STATES]
+        finished.sort(key=lambda job: job['finished_at'] or '')
+        for job in finished[:max(0, len(finished) - KEEP_FINISHED)]:
+            del self._jobs[job['id']]
+
+    def get_job(self, job_id: str) -> Optional[Dict[str, Any]]:
+        """One job, or None if it is unknown (or long finished)."""
+        with self._lock:
+            job = self._jobs.get(job_id)
+            return copy.deepcopy(job) if job else None
+
+    def list_jobs(self, include_finished: bool = True) -> List[Dict[str, Any]]:
+        """
+        Jobs, newest first.
+
+        Args:
+            include_finished (bool): Also list recently finished jobs
+
+        Returns:
+            List[Dict]: Jobs
+        """
+        with self._lock:
+            jobs = [copy.deepcopy(job) for job in self._jobs.values()
+                    if include_finished or job['state'] not in FINISHED_STATES]
+        return sorted(jobs, key=lambda job: job['started_at'], reverse=True)
+
+    def cancel_job(self, job_id: str) -> Dict[str, Any]:
+        """
+        Stop a running job.
+
+        Args:
+            job_id (str): Job to stop
+
+        Returns:
+            Dict: 'success', 'message' and the 'job'
+        """
+        with self._lock:
+            job = self._jobs.get(job_id)
+            if job is None:
+                return {'success': False, 'message': f"No job {job_id}", 'job': None}
+            if job['state'] in FINISHED_STATES:
+                return {'success': False, 'message': f"Job {job_id} already {job['state']}",
+                        'job': copy.deepcopy(job)}
+            job['state'] = 'cancelling'
+            self._cancels[job_id].set()
+            snapshot = copy.deepcopy(job)
+        logger.info(f"Job {job_id} cancelling")
+        return {'success': True, 'message': f"Stopping job {job_id} after the current file", 'job': snapshot}
+
+    def wait(self, job_id: str, timeout: Optional[float] = None) -> Optional[Dict[str, Any]]:
+        """Wait for a job to finish and return it."""
+        with self._lock:
+            thread = self._threads.get(job_id)
+        if thread is not None:
+            thread.join(timeout)
+        return self.get_job(job_id)
+
+    def shutdown(self, timeout: float = 15.0) -> int:
+        """
+        Cancel every running job and wait for them to stop.
+
+        Args:
+            timeout (float): Seconds to wait for each job
+
+        Returns:
+            int: Number of jobs cancelled
+        """
+        running = [job['id'] for job in self.list_jobs(include_finished=False)]
+        for job_id in running:
+            self.cancel_job(job_id)
+        for job_id in running:
+            self.wait(job_id, timeout)
+        return len(running)
@@ -0,0 +1,624 @@
+"""
+Operation Journal Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module records multi-step file operations (for example "extract an
+archive, organize its contents, trash the archive") as a single journal
+entry made of ordered, reversible steps. Undoing an operation replays its
+steps backwards, so the whole chain can be reverted in one go.
+
+Applied operations form an undo stack and undone ones a redo stack, so the
+user can step back and forth through everything done through the app.
+Starting a new operation clears the redo stack, as in any editor.
+Single steps can also be undone on their own (undo_item), e.g. to put back
+one file from an old run.
+
+Step actions and how they are reversed:
+    - move:   move dest_path back to source_path (and put back a replaced
+              file from its kept version)
+    - copy:   delete the copy at dest_path
+    - create: delete the file or directory created at dest_path
+    - mkdir:  remove dest_path if it is empty
+
+Redo replays the undone steps forwards; operations with 'create' steps
+(extracted or newly written files) cannot be redone.
+
+query_history(filters) sums up what was done for the history view -
+files per day, per category, per model and per operation, and what the AI
+cost - in the database, so a dashboard gets the totals rather than the log:
+
+    journal.query_history({'since': '30 days ago', 'category': 'Documents'})
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import re
+import shutil
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .history import DB_TIME_FORMAT, parse_point_in_time
+
+logger = logging.getLogger(__name__)
+
+
+PROJECT_ROOT = Path(__file__).parent.parent.parent
+REDOABLE_ACTIONS = ('move', 'copy', 'mkdir')
+HISTORY_FILTERS = ('since', 'until', 'category', 'model', 'operation', 'folder')
+NOT_DONE_OPERATIONS = ('skipped', 'agent_suggestion')  # Logged, but nothing happened to the file
+
+
+def _moment(value: Any, start_of_day: bool) -> datetime:
+    """A filter's point in time; a bare date is the start (since) or the end (until) of that day."""
+    if isinstance(value, datetime):
+        return value
+    text = str(value).strip()
+    if start_of_day and re.fullmatch(r'\d{4}-\d{2}-\d{2}', text):
+        return datetime.strptime(text, '%Y-%m-%d')
+    return parse_point_in_time(text)
+
+
+def trash_root(config) -> Path:
+    """
+    Holding directory for trashed files.
+
+    Args:
+        config: Configuration object (reads `trash_dir`)
+
+    Returns:
+        Path: Configured trash directory, or `data/trash` by default
+    """
+    configured = getattr(config, 'trash_dir', '') or ''
+    if not isinstance(configured, str):
+        configured = ''
+    return Path(configured).expanduser() if configured else PROJECT_ROOT / "data" / "trash"
+
+
+class OperationJournal:
+    """
+    Records and reverses grouped file operations.
+
+    Attributes:
+        db_manager: Database manager used for persistence
+    """
+
+    STEP_ACTIONS = ('move', 'copy', 'create', 'mkdir')
+
+    def __init__(self, db_manager):
+        """
+        Initialize journal.
+
+        Args:
+            db_manager: Database manager instance
+        """
+        self.db_manager = db_manager
+
+    def begin(self, kind: str, description: str = '') -> int:
+        """
+        Start a new operation.
+
+        A new operation invalidates the redo stack.
+
+        Args:
+            kind (str): Operation type (e.g. 'extract_organize')
+            description (str): Human-readable summary
+
+        Returns:
+            int: Operation ID
+        """
+        self.db_manager.clear_redo_stack()
+        return self.db_manager.create_operation(kind, description)
+
+    def record_step(self, operation_id: int, action: str, source_path: Optional[str] = None,
+                    dest_path: Optional[str] = None, details: Optional[Dict[str, Any]] = None) -> int:
+        """
+        Record a completed step of an operation.
+
+        Args:
+            operation_id (int): Operation ID from begin()
+            action (str): One of STEP_ACTIONS
+            source_path (str, optional): Original location
+            dest_path (str, optional): New location / created path
+            details (Dict, optional): Extra information stored as JSON
+
+        Returns:
+            int: Step ID
+
+        Raises:
+            ValueError: If the action is not reversible by the journal
+        """
+        if action not in self.STEP_ACTIONS:
+            raise ValueError(f"Unsupported journal step action: {action}")
+        return self.db_manager.add_operation_step(
+            operation_id, action,
+            str(source_path) if source_path else None,
+            str(dest_path) if dest_path else None,
+            json.dumps(details) if details else None
+        )
+
+    def complete(self, operation_id: int, success: bool = True) -> None:
+        """
+        Mark an operation as applied (or failed).
+
+        Args:
+            operation_id (int): Operation ID
+            success (bool): False marks the operation as failed
+        """
+        self.db_manager.update_operation_status(operation_id, 'applied' if success else 'failed')
+        if success:
+            self.db_manager.push_undo_stack(operation_id)
+
+    def get_operation(self, operation_id: int) -> Optional[Dict[str, Any]]:
+        """
+        Get an operation and its steps (details decoded).
+
+        Args:
+            operation_id (int): Operation ID
+
+        Returns:
+            Dict or None: Operation with 'steps'
+        """
+        operation = self.db_manager.get_operation(operation_id)
+        if operation:
+            operation['timings'] = self._decode_timings(operation.get('timings'))
+            for step in operation['steps']:
+                step['details'] = json.loads(step['details']) if step.get('details') else {}
+        return operation
+
+    def list_operations(self, limit: int = 50) -> List[Dict[str, Any]]:
+        """
+        List recent operations, newest first.
+
+        Args:
+            limit (int): Maximum number of operations
+
+        Returns:
+            List[Dict]: Operations with step counts and timings
+        """
+        operations = self.db_manager.list_operations(limit)
+        for operation in operations:
+            operation['timings'] = self._decode_timings(operation.get('timings'))
+        return operations
+
+    def record_timings(self, operation_id: int, timings: Dict[str, Any]) -> None:
+        """
+        Store how long each stage of an operation's run took.
+
+        Args:
+            operation_id (int): Operation ID
+            timings (Dict): {'elapsed': seconds, 'stages': {stage: {'seconds', 'count'}}}
+        """
+        self.db_manager.set_operation_timings(operation_id, json.dumps(timings))
+
+    def timing_report(self, limit: int = 20) -> Dict[str, Any]:
+        """
+        Where recent runs spent their time.
+
+        Args:
+            limit (int): Number of recent operations to look at
+
+        Returns:
+            Dict: 'runs' (operations that have timings, newest first) and
+                  'stages' (seconds, count and share of the total per stage,
+                  slowest first)
+        """
+        runs = [op for op in self.list_operations(limit) if op.get('timings')]
+        totals: Dict[str, Dict[str, Any]] = {}
+        for run in runs:
+            for name, entry in run['timings'].get('stages', {}).items():
+                total = totals.setdefault(name, {'seconds': 0.0, 'count': 0})
+                total['seconds'] += entry.get('seconds', 0.0)
+                total['count'] += entry.get('count', 0)
+        overall = sum(entry['seconds'] for entry in totals.values())
+        stages = {
+            name: {'seconds': round(entry['seconds'], 4), 'count': entry['count'],
+                   'share': round(entry['seconds'] / overall, 3) if overall else 0.0}
+            for name, entry in sorted(totals.items(), key=lambda item: -item[1]['seconds'])
+        }
+        return {
+            'runs': [{'id': run['id'], 'kind': run['kind'], 'description': run.get('description'),
+                      'created_at': run.get('created_at'), 'timings': run['timings']} for run in runs],
+            'stages': stages
+        }
+
+    def query_history(self, filters: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Totals of the logged file operations, for the history view.
+
+        Args:
+            filters (Dict, optional): Any of since / until (points in time
+                such as '2025-06-01', 'last monday' or '7 days ago'), category,
+                model ('rules' and 'ai' for files without a model name),
+                operation (one or a list; default: everything but skips and
+                suggestions) and folder (files that left or landed below it)
+
+        Returns:
+            Dict: 'filters', 'files', 'time_saved_minutes', 'by_day',
+                  'by_category', 'by_model' (with the AI 'calls', 'tokens' and
+                  'cost_usd' of each model), 'by_operation', 'cost_usd' (AI
+                  cost in the period, for the model filter only) and 'usage'
+
+        Raises:
+            ValueError: If a filter is unknown or a point in time is not understood
+        """
+        filters = {key: value for key, value in (filters or {}).items() if value not in (None, '', [])}
+        unknown = sorted(set(filters) - set(HISTORY_FILTERS))
+        if unknown:
+            raise ValueError(f"Unknown history filter(s): {', '.join(unknown)} (use {', '.join(HISTORY_FILTERS)})")
+        query: Dict[str, Any] = {key: filters.get(key) for key in ('category', 'model')}
+        for key in ('since', 'until'):
+            if key in filters:
+                moment = _moment(filters[key], start_of_day=key == 'since')
+                query[key] = moment.astimezone(timezone.utc).strftime(DB_TIME_FORMAT)
+                query[f"usage_{key}"] = moment.strftime(DB_TIME_FORMAT)
+        operations = filters.get('operation')
+        if operations:
+            query['operations'] = [operations] if isinstance(operations, str) else list(operations)
+        else:
+            query['exclude_operations'] = list(NOT_DONE_OPERATIONS)
+        if filters.get('folder'):
+            query['folder'] = str(Path(filters['folder']).expanduser())
+
+        stats = self.db_manager.history_stats(query)
+        usage = {row['model']: row for row in stats['usage']['by_model']}
+        for row in stats['by_model']:
+            spent = usage.get(row['model'], {})
+            row.update({key: spent.get(key, 0) for key in ('calls', 'tokens', 'cost_usd')})
+        stats['time_saved_minutes'] = round(stats['time_saved_minutes'], 2)
+        return {'filters': filters, **stats, 'cost_usd': stats['usage']['cost_usd']}
+
+    @staticmethod
+    def _decode_timings(raw: Optional[str]) -> Optional[Dict[str, Any]]:
+        if not raw:
+            return None
+        try:
+            return json.loads(raw)
+        except ValueError:
+            return None
+
+    def undo_stack(self, limit: int = 50) -> List[Dict[str, Any]]:
+        """
+        List operations that can be undone, most recent first.
+
+        Args:
+            limit (int): Maximum number of operations
+
+        Returns:
+            List[Dict]: Operations with step counts
+        """
+        return self.db_manager.list_operation_stack('undo', limit)
+
+    def redo_stack(self, limit: int = 50) -> List[Dict[str, Any]]:
+        """
+        List operations that can be redone, most recently undone first.
+
+        Args:
+            limit (int): Maximum number of operations
+
+        Returns:
+            List[Dict]: Operations with step counts
+        """
+        return self.db_manager.list_operation_stack('redo', limit)
+
+    def undo_last(self, dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Undo the operation on top of the undo stack.
+
+        Args:
+            dry_run (bool): If True, only report what would be undone
+
+        Returns:
+            Dict: Result of undo() with 'operation_id', or failure if the stack is empty
+        """
+        stack = self.undo_stack(1)
+        if not stack:
+            return {'success': False, 'message': 'Nothing to undo'}
+        result = self.undo(stack[0]['id'], dry_run=dry_run)
+        result['operation_id'] = stack[0]['id']
+        return result
+
+    def redo_last(self, dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Redo the operation on top of the redo stack.
+
+        Args:
+            dry_run (bool): If True, only report what would be redone
+
+        Returns:
+            Dict: Result of redo() with 'operation_id', or failure if the stack is empty
+        """
+        stack = self.redo_stack(1)
+        if not stack:
+            return {'success': False, 'message': 'Nothing to redo'}
+        result = self.redo(stack[0]['id'], dry_run=dry_run)
+        result['operation_id'] = stack[0]['id']
+        return result
+
+    def undo(self, operation_id: int, dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Undo every applied step of an operation, last step first.
+
+        Steps that cannot be reversed (e.g. the file was moved again by the
+        user) are reported and skipped; the rest are still undone.
+
+        Args:
+            operation_id (int): Operation ID
+            dry_run (bool): If True, only report what would be undone
+
+        Returns:
+            Dict: Result with 'success', 'message', 'undone' and 'failed' lists
+        """
+        operation = self.get_operation(operation_id)
+        if not operation:
+            return {'success': False, 'message': f'Operation {operation_id} not found'}
+
+        if operation['status'] == 'undone':
+            return {'success': False, 'message': f'Operation {operation_id} was already undone'}
+
+        undone: List[Dict[str, Any]] = []
+        failed: List[Dict[str, Any]] = []
+
+        for step in reversed(operation['steps']):
+            if step['status'] != 'applied':
+                continue
+
+            if dry_run:
+                undone.append(step)
+                continue
+
+            error = self._reverse_step(step)
+            if error:
+                logger.warning(f"Could not undo step {step['id']} of operation {operation_id}: {error}")
+                self.db_manager.update_operation_step_status(step['id'], 'failed')
+                failed.append({**step, 'error': error})
+            else:
+                self.db_manager.update_operation_step_status(step['id'], 'undone')
+                undone.append(step)
+
+        if dry_run:
+            return {
+                'success': True,
+                'message': f'[DRY RUN] Would undo {len(undone)} step(s) of operation {operation_id}',
+                'undone': undone,
+                'failed': []
+            }
+
+        status = 'undone' if not failed else 'partially_undone'
+        self.db_manager.update_operation_status(operation_id, status)
+        if not failed and all(step['action'] in REDOABLE_ACTIONS for step in operation['steps']):
+            self.db_manager.push_redo_stack(operation_id)
+
+        message = f'Undone {len(undone)} step(s) of operation {operation_id}'
+        if failed:
+            message += f'; {len(failed)} step(s) could not be undone'
+
+        return {
+            'success': not failed,
+            'message': message,
+            'undone': undone,
+            'failed': failed
+        }
+
+    def undo_item(self, operation_id: int, item_id: int, dry_run: bool = False,
+                  on_conflict: str = 'fail') -> Dict[str, Any]:
+        """
+        Undo a single step of an operation, leaving the rest of it applied.
+
+        If something now occupies the step's original path, nothing is moved
+        and the conflict is reported, unless on_conflict is 'keep_both', in
+        which case the file is restored next to the occupant with a counter
+        added to its name.
+
+        Args:
+            operation_id (int): Operation ID
+            item_id (int): Step ID within the operation
+            dry_run (bool): If True, only report what would be undone
+            on_conflict (str): 'fail' (default) or 'keep_both'
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'restored_to'; on a
+                  conflict also 'conflict': True and 'occupied_by'
+        """
+        operation = self.get_operation(operation_id)
+        if not operation:
+            return {'success': False, 'message': f'Operation {operation_id} not found'}
+
+        step = next((s for s in operation['steps'] if s['id'] == item_id), None)
+        if step is None:
+            return {'success': False, 'message': f'Item {item_id} not found in operation {operation_id}'}
+        if step['status'] != 'applied':
+            return {'success': False, 'message': f'Item {item_id} is {step["status"]}, not applied'}
+
+        restore_to = step.get('source_path')
+        if step['action'] == 'move' and restore_to and Path(restore_to).exists():
+            if on_conflict != 'keep_both':
+                return {
+                    'success': False,
+                    'conflict': True,
+                    'occupied_by': restore_to,
+                    'message': f'Cannot undo item {item_id}: {restore_to} is occupied'
+                }
+            original = Path(restore_to)
+            counter = 1
+            candidate = original
+            while candidate.exists():
+                candidate = original.with_name(f"{original.stem}_{counter}{original.suffix}")
+                counter += 1
+            restore_to = str(candidate)
+            step = {**step, 'source_path': restore_to}
+
+        if dry_run:
+            return {
+                'success': True,
+                'message': f'[DRY RUN] Would undo item {item_id} of operation {operation_id}',
+                'restored_to': restore_to
+            }
+
+        error = self._reverse_step(step)
+        if error:
+            return {'success': False, 'message': f'Could not undo item {item_id}: {error}'}
+
+        self.db_manager.update_operation_step_status(item_id, 'undone')
+        if not any(s['status'] == 'applied' for s in operation['steps'] if s['id'] != item_id):
+            self.db_manager.update_operation_status(operation_id, 'undone')
+
+        return {
+            'success': True,
+            'message': f'Undone item {item_id} of operation {operation_id}',
+            'restored_to': restore_to
+        }
+
+    def redo(self, operation_id: int, dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Replay the steps of an undone operation, first step first.
+
+        Stops at the first step that cannot be replayed and reverses the
+        steps already replayed, so an operation is never half redone.
+
+        Args:
+            operation_id (int): Operation ID
+            dry_run (bool): If True, only report what would be redone
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'redone' list (plus 'error' on failure)
+        """
+        operation = self.get_operation(operation_id)
+        if not operation:
+            return {'success': False, 'message': f'Operation {operation_id} not found'}
+
+        if operation['status'] != 'undone' or operation.get('redo_seq') is None:
+            return {'success': False, 'message': f'Operation {operation_id} cannot be redone'}
+
+        steps = [step for step in operation['steps'] if step['status'] == 'undone']
+
+        if dry_run:
+            return {
+                'success': True,
+                'message': f'[DRY RUN] Would redo {len(steps)} step(s) of operation {operation_id}',
+                'redone': steps
+            }
+
+        redone: List[Dict[str, Any]] = []
+        for step in steps:
+            error = self._replay_step(step)
+            if error:
+                logger.warning(f"Could not redo step {step['id']} of operation {operation_id}: {error}")
+                for done in reversed(redone):
+                    self._reverse_step(done)
+                return {
+                    'success': False,
+                    'message': f'Operation {operation_id} not redone: {error}',
+                    'redone': [],
+                    'error': error
+                }
+            redone.append(step)
+
+        for step in redone:
+            self.db_manager.update_operation_step_status(step['id'], 'applied')
+        self.db_manager.update_operation_status(operation_id, 'applied')
+        self.db_manager.push_undo_stack(operation_id)
+
+        return {
+            'success': True,
+            'message': f'Redone {len(redone)} step(s) of operation {operation_id}',
+            'redone': redone
+        }
+
+    def _replay_step(self, step: Dict[str, Any]) -> Optional[str]:
+        """
+        Perform a single undone step again.
+
+        Args:
+            step (Dict): Step row
+
+        Returns:
+            str or None: Error message, or None on success
+        """
+        action = step['action']
+        source = Path(step['source_path']) if step.get('source_path') else None
+        dest = Path(step['dest_path']) if step.get('dest_path') else None
+
+        if action not in REDOABLE_ACTIONS or dest is None:
+            return f'{action} steps cannot be redone'
+
+        try:
+            if action == 'mkdir':
+                dest.mkdir(parents=True, exist_ok=True)
+                return None
+
+            if source is None or not source.exists():
+                return f'file not found at {source}'
+            if dest.is_file() and (step.get('details') or {}).get('replaced_version'):
+                dest.unlink()  # The replaced file put back by undo is still kept as a version
+            if dest.exists():
+                return f'destination is occupied: {dest}'
+            dest.parent.mkdir(parents=True, exist_ok=True)
+
+            if action == 'move':
+                shutil.move(str(source), str(dest))
+            elif source.is_dir():
+                shutil.copytree(source, dest)
+            else:
+                shutil.copy2(source, dest)
+            return None
+
+        except (OSError, shutil.Error) as e:
+            return str(e)
+
+    def _reverse_step(self, step: Dict[str, Any]) -> Optional[str]:
+        """
+        Reverse a single step.
+
+        Args:
+            step (Dict): Step row
+
+        Returns:
+            str or None: Error message, or None on success
+        """
+        action = step['action']
+        source = Path(step['source_path']) if step.get('source_path') else None
+        dest = Path(step['dest_path']) if step.get('dest_path') else None
+
+        try:
+            if action == 'move':
+                if dest is None or source is None or not dest.exists():
+                    return f'moved file not found at {dest}'
+                if source.exists():
+                    return f'original location is occupied: {source}'
+                source.parent.mkdir(parents=True, exist_ok=True)
+                shutil.move(str(dest), str(source))
+                version_object = (step.get('details') or {}).get('version_object')
+                if version_object and Path(version_object).is_file():
+                    shutil.copy2(version_object, dest)
+
+            elif action in ('copy', 'create'):
+                if dest is None or not dest.exists():
+                    return None  # Already gone - nothing to do
+                if dest.is_dir():
+                    shutil.rmtree(dest)
+                else:
+                    dest.unlink()
+                version_object = (step.get('details') or {}).get('version_object')
+                if version_object and Path(version_object).is_file():
+                    shutil.copy2(version_object, dest)  # Put back the file the copy overwrote
+
+            elif action == 'mkdir':
+                if dest is not None and dest.exists():
+                    try:
+                        dest.rmdir()
+                    except OSError:
+                        pass  # Not empty - keep it
+
+            return None
+
+        except (OSError, shutil.Error) as e:
+            return str(e)
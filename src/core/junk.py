@@ -0,0 +1,290 @@
+"""
+Junk Cleaner Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module finds known clutter and turns it into a cleanup plan, which is
+reviewed and applied like an organize plan: applying moves every item to
+the trash holding directory as one journaled operation, so a single
+`aifo undo` brings it all back.
+
+What counts as junk:
+
+- installer: a .dmg/.pkg/.msi/.exe/.deb/.rpm installer for an app that is
+  already installed (or, with `cleanup.installer_max_age_days`, any
+  installer older than that)
+- partial_download: .crdownload/.part/.partial/.download fragments older
+  than `cleanup.partial_age_days` (7)
+- office_temp: "~$" lock files Office leaves behind, older than a day
+- zero_byte: empty files (except markers such as .gitkeep or __init__.py)
+- empty_folder: folders with nothing in them but OS clutter (.DS_Store,
+  Thumbs.db, desktop.ini) or other empty folders; only the outermost is
+  listed
+
+Left-alone folders (see core.leave_alone) and hidden folders are skipped.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import re
+import shutil
+import subprocess
+import sys
+import time
+from collections import Counter
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Set
+
+from .leave_alone import LeaveAloneDetector
+
+logger = logging.getLogger(__name__)
+
+
+JUNK_KINDS = ('installer', 'partial_download', 'office_temp', 'zero_byte', 'empty_folder')
+INSTALLER_EXTENSIONS = {'.dmg', '.pkg', '.msi', '.exe', '.deb', '.rpm'}
+PARTIAL_EXTENSIONS = {'.crdownload', '.part', '.partial', '.download'}
+OS_CLUTTER = {'.ds_store', 'thumbs.db', 'desktop.ini', '.localized'}
+# Empty on purpose
+KEEP_EMPTY = {'.gitkeep', '.keep', '.nomedia', '__init__.py', 'py.typed', '.placeholder'}
+OFFICE_TEMP_AGE = 24 * 3600
+# Version numbers, architectures and installer words that are not part of the app name
+_INSTALLER_NOISE = re.compile(
+    r'(?i)(?:[-_. ]v?\d+(?:\.\d+)*[a-z]?\b|[-_. ](?:x64|x86|amd64|arm64|aarch64|i386|i686|universal|win(?:32|64)?|'
+    r'mac(?:os)?|osx|linux|setup|installer|install|full|offline|web|latest|current|stable|release)\b)'
+)
+
+
+def app_name_key(name: str) -> str:
+    """
+    An installer or app name reduced for matching: "Firefox Setup 121.0.exe"
+    and "Firefox.app" both become "firefox".
+
+    Args:
+        name (str): File or app name
+
+    Returns:
+        str: Lowercase letters and digits only
+    """
+    stem = Path(name).stem if Path(name).suffix.lower() in INSTALLER_EXTENSIONS | {'.app'} else name
+    stem = _INSTALLER_NOISE.sub(' ', f" {stem} ")
+    return re.sub(r'[^a-z0-9]', '', stem.lower())
+
+
+def installed_app_names() -> Set[str]:
+    """
+    Names of the installed applications, as app_name_key() keys.
+
+    Reads /Applications on macOS, the uninstall registry on Windows, and
+    dpkg or rpm on Linux; anything unavailable is skipped.
+
+    Returns:
+        Set[str]: Keys of installed apps
+    """
+    names: Set[str] = set()
+    if sys.platform == 'darwin':
+        for folder in (Path('/Applications'), Path.home() / 'Applications'):
+            try:
+                names.update(p.name for p in folder.iterdir() if p.suffix == '.app')
+            except OSError:
+                pass
+    elif sys.platform.startswith('win'):
+        try:
+            import winreg
+        except ImportError:
+            winreg = None
+        if winreg is not None:
+            for hive, key in ((winreg.HKEY_LOCAL_MACHINE, r'SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall'),
+                              (winreg.HKEY_LOCAL_MACHINE,
+                               r'SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall'),
+                              (winreg.HKEY_CURRENT_USER, r'Software\Microsoft\Windows\CurrentVersion\Uninstall')):
+                try:
+                    with winreg.OpenKey(hive, key) as root:
+                        for i in range(winreg.QueryInfoKey(root)[0]):
+                            try:
+                                with winreg.OpenKey(root, winreg.EnumKey(root, i)) as app:
+                                    names.add(winreg.QueryValueEx(app, 'DisplayName')[0])
+                            except OSError:
+                                continue
+                except OSError:
+                    continue
+    else:
+        for command in (['dpkg-query', '-W', '-f=${Package}\n'], ['rpm', '-qa', '--qf', '%{NAME}\n']):
+            if shutil.which(command[0]) is None:
+                continue
+            try:
+                output = subprocess.run(command, capture_output=True, text=True, timeout=30).stdout
+            except (OSError, subprocess.SubprocessError):
+                continue
+            names.update(line.strip() for line in output.splitlines() if line.strip())
+    return {key for key in (app_name_key(name) for name in names) if len(key) >= 3}
+
+
+def is_empty_tree(directory: Path) -> bool:
+    """Whether a folder holds nothing but OS clutter and other such folders."""
+    try:
+        for entry in os.scandir(directory):
+            if entry.is_symlink():
+                return False
+            if entry.is_dir():
+                if not is_empty_tree(Path(entry.path)):
+                    return False
+            elif entry.name.lower() not in OS_CLUTTER:
+                return False
+    except OSError:
+        return False
+    return True
+
+
+class JunkScanner:
+    """
+    Finds junk files and folders and builds cleanup plans.
+
+    Attributes:
+        config: Configuration object
+        kinds (Set[str]): Junk kinds looked for
+        partial_age_days (float): Minimum age of partial downloads
+        installer_max_age_days (float): Age after which any installer is junk (0: off)
+    """
+
+    def __init__(self, config, leave_alone: Optional[LeaveAloneDetector] = None,
+                 installed_apps: Optional[Set[str]] = None):
+        """
+        Initialize junk scanner.
+
+        Args:
+            config: Configuration object (reads `cleanup_settings`)
+            leave_alone (LeaveAloneDetector, optional): Detector for folders to skip
+            installed_apps (Set[str], optional): app_name_key() keys of installed
+                                                 apps (default: looked up once)
+        """
+        self.config = config
+        settings = getattr(config, 'cleanup_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.kinds = set(settings.get('kinds') or JUNK_KINDS) & set(JUNK_KINDS)
+        self.partial_age_days = float(settings.get('partial_age_days', 7))
+        self.installer_max_age_days = float(settings.get('installer_max_age_days', 0))
+        self.leave_alone = leave_alone or LeaveAloneDetector(config)
+        self._installed = installed_apps
+
+    @property
+    def installed(self) -> Set[str]:
+        if self._installed is None:
+            self._installed = installed_app_names()
+        return self._installed
+
+    def classify(self, path: Path, now: Optional[float] = None) -> Optional[str]:
+        """
+        The junk kind of a file, if it is junk.
+
+        Args:
+            path (Path): File to check
+            now (float, optional): Current time (for tests)
+
+        Returns:
+            str or None: Junk kind, or None
+        """
+        now = time.time() if now is None else now
+        try:
+            stat = path.stat()
+        except OSError:
+            return None
+        age = now - stat.st_mtime
+        name, ext = path.name, path.suffix.lower()
+        if 'office_temp' in self.kinds and name.startswith('~$') and age >= OFFICE_TEMP_AGE:
+            return 'office_temp'
+        if 'partial_download' in self.kinds and ext in PARTIAL_EXTENSIONS and age >= self.partial_age_days * 86400:
+            return 'partial_download'
+        if 'installer' in self.kinds and ext in INSTALLER_EXTENSIONS and stat.st_size > 0:
+            key = app_name_key(name)
+            if key and any(key == app or (len(key) >= 4 and app.startswith(key)) or (len(key) >= 5 and key in app)
+                           for app in self.installed):
+                return 'installer'
+            if self.installer_max_age_days and age >= self.installer_max_age_days * 86400:
+                return 'installer'
+        if 'zero_byte' in self.kinds and stat.st_size == 0 and name.lower() not in KEEP_EMPTY | OS_CLUTTER:
+            return 'zero_byte'
+        return None
+
+    def build_plan(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        List the junk in a folder as a cleanup plan.
+
+        Args:
+            folder (str): Folder to clean
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: Plan with kind 'cleanup': 'items' ({file, size, modified,
+                  action 'delete', reason, ...}), 'total_size', 'reasons'
+                  counts and 'left_alone'
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+        """
+        root = Path(folder).expanduser().resolve()
+        if not root.is_dir():
+            raise NotADirectoryError(f"Not a folder: {root}")
+        now = time.time()
+        items: List[Dict[str, Any]] = []
+        left_alone: List[Dict[str, str]] = []
+        empty_parents: Set[str] = set()
+
+        for dirpath, dirnames, filenames in os.walk(root):
+            kept = []
+            for name in dirnames:
+                sub = Path(dirpath) / name
+                if name.startswith('.') or sub.is_symlink():
+                    continue
+                reason = self.leave_alone.reason(sub)
+                if reason is not None:
+                    left_alone.append({'path': str(sub), 'reason': reason})
+                elif 'empty_folder' in self.kinds and is_empty_tree(sub):
+                    items.append(self._item(sub, 'empty_folder'))
+                    empty_parents.add(str(sub))
+                elif recursive:
+                    kept.append(name)
+            dirnames[:] = kept
+            for name in filenames:
+                path = Path(dirpath) / name
+                if path.is_symlink():
+                    continue
+                kind = self.classify(path, now)
+                if kind:
+                    items.append(self._item(path, kind))
+
+        items.sort(key=lambda item: item['file'])
+        return {
+            'version': 1,
+            'kind': 'cleanup',
+            'root': str(root),
+            'created_at': datetime.now().isoformat(timespec='seconds'),
+            'items': items,
+            'unchanged': 0,
+            'conflicts': 0,
+            'total_size': sum(item['size'] for item in items),
+            'reasons': dict(Counter(item['reason'] for item in items)),
+            'left_alone': sorted(left_alone, key=lambda entry: entry['path'])
+        }
+
+    @staticmethod
+    def _item(path: Path, kind: str) -> Dict[str, Any]:
+        stat = path.stat()
+        return {
+            'file': str(path),
+            'size': 0 if kind == 'empty_folder' else stat.st_size,
+            'modified': stat.st_mtime,
+            'action': 'delete',
+            'destination': None,
+            'reason': kind,
+            'classification': {'category': 'Junk', 'reason': kind.replace('_', ' '),
+                               'method': 'rule-based', 'confidence': 'high'},
+            'conflict': None
+        }
@@ -0,0 +1,149 @@
+"""
+Keychain Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps secrets such as AI provider API keys in the operating
+system's credential store instead of a settings file: Credential Manager
+on Windows, the login Keychain on macOS and the Secret Service (GNOME
+Keyring, KWallet) on Linux, through the optional `keyring` package.
+
+Processes this application starts (the sidecar, streamed organize runs)
+are handed the secrets they need in AIFO_SECRET_<NAME> environment
+variables, never on the command line where other users can read them;
+get_secret() looks there first, so a child works without asking the
+keychain again.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import re
+from typing import Dict, Iterable, Optional
+
+try:
+    import keyring  # type: ignore
+    KEYRING_SUPPORT = True
+except ImportError:
+    keyring = None  # type: ignore
+    KEYRING_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+KEYCHAIN_SERVICE = 'ai-file-organiser'
+SECRET_ENV_PREFIX = 'AIFO_SECRET_'
+_NAME = re.compile(r'^[A-Za-z0-9][A-Za-z0-9_.\-]{0,127}$')
+
+
+class KeychainError(RuntimeError):
+    """The OS keychain is missing, locked or refused the request."""
+
+
+def keychain_available() -> bool:
+    """Whether secrets can be stored (keyring installed with a usable backend)."""
+    if not KEYRING_SUPPORT:
+        return False
+    try:
+        backend = keyring.get_keyring()
+    except Exception:
+        return False
+    # keyring falls back to a backend that refuses everything when the platform has none
+    return 'fail' not in type(backend).__module__.lower()
+
+
+def secret_env_name(name: str) -> str:
+    """Environment variable a secret is passed in (api_key.openai -> AIFO_SECRET_API_KEY_OPENAI)."""
+    return SECRET_ENV_PREFIX + re.sub(r'[^A-Z0-9]', '_', name.upper())
+
+
+def store_secret(name: str, value: str) -> None:
+    """
+    Store a secret in the OS keychain, replacing any earlier value.
+
+    Args:
+        name (str): Secret name (letters, digits, '.', '_', '-')
+        value (str): Secret
+
+    Raises:
+        ValueError: If the name or value is empty or invalid
+        KeychainError: If there is no keychain or it refused
+    """
+    _check_name(name)
+    if not isinstance(value, str) or not value:
+        raise ValueError("A secret cannot be empty")
+    if not keychain_available():
+        raise KeychainError("No OS keychain available (install the 'keyring' package)")
+    try:
+        keyring.set_password(KEYCHAIN_SERVICE, name, value)
+    except Exception as e:
+        raise KeychainError(f"The keychain did not store {name}: {e}")
+
+
+def get_secret(name: str) -> Optional[str]:
+    """
+    A secret from this process's environment, else from the OS keychain.
+
+    Args:
+        name (str): Secret name
+
+    Returns:
+        Optional[str]: The secret, or None when it is not stored anywhere
+    """
+    _check_name(name)
+    passed = os.environ.get(secret_env_name(name))
+    if passed:
+        return passed
+    if not keychain_available():
+        return None
+    try:
+        return keyring.get_password(KEYCHAIN_SERVICE, name)
+    except Exception as e:
+        logger.warning(f"Could not read {name} from the keychain: {e}")
+        return None
+
+
+def delete_secret(name: str) -> bool:
+    """
+    Remove a secret from the OS keychain.
+
+    Returns:
+        bool: True if it was there
+    """
+    _check_name(name)
+    if not keychain_available():
+        return False
+    try:
+        keyring.delete_password(KEYCHAIN_SERVICE, name)
+        return True
+    except Exception:
+        return False  # keyring raises PasswordDeleteError for names it does not have
+
+
+def secret_environment(names: Iterable[str]) -> Dict[str, str]:
+    """
+    Environment variables handing secrets to a child process.
+
+    Args:
+        names (Iterable[str]): Secrets the child needs
+
+    Returns:
+        Dict[str, str]: AIFO_SECRET_<NAME> -> secret, for the ones that are stored
+    """
+    environment = {}
+    for name in names:
+        value = get_secret(name)
+        if value:
+            environment[secret_env_name(name)] = value
+    return environment
+
+
+def _check_name(name: str) -> None:
+    if not isinstance(name, str) or not _NAME.match(name):
+        raise ValueError(f"Invalid secret name: {name!r}")
@@ -0,0 +1,285 @@
+"""
+Leave-Alone Detection Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module recognizes folders whose contents must stay exactly where
+they are, because moving a single file would break them:
+
+- Code: git/Mercurial/Subversion checkouts, project roots (package.json,
+  Cargo.toml, pyproject.toml, ...), node_modules, virtual environments
+- Applications: bundles (.app, .framework, .photoslibrary, ...) and game
+  installs (Steam libraries, Epic, GOG, Unity and Unreal builds)
+- Sync roots: Dropbox, OneDrive, iCloud Drive, Google Drive, Nextcloud,
+  Syncthing, and folders tagged as caches (CACHEDIR.TAG)
+
+The planner and the watcher treat such folders as opaque: they do not look
+inside them, and report them as left alone. Only folders below the folder
+being organized are checked; organizing a folder explicitly is the user's
+choice.
+
+Two user lists in config.json override detection:
+
+    "leave_alone": {
+        "enabled": true,
+        "always": ["~/Work/Scans"],     # never organize these
+        "allow": ["~/Dropbox/Inbox"]    # organize these despite detection
+    }
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import threading
+import time
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+CACHE_SECONDS = 300.0
+
+# Entry in a folder -> why the folder is left alone
+MARKER_DIRS = {
+    '.git': 'git repository', '.hg': 'Mercurial repository', '.svn': 'Subversion checkout',
+    '.bzr': 'Bazaar branch', '.stfolder': 'Syncthing folder', '.dropbox.cache': 'Dropbox folder',
+    '.egstore': 'Epic Games install', 'steamapps': 'Steam library',
+}
+MARKER_FILES = {
+    '.git': 'git worktree', 'pyvenv.cfg': 'Python virtual environment', 'CACHEDIR.TAG': 'cache folder',
+    '.dropbox': 'Dropbox folder', '.owncloudsync.log': 'Nextcloud/ownCloud folder',
+    'package.json': 'code project', 'Cargo.toml': 'code project', 'pyproject.toml': 'code project',
+    'go.mod': 'code project', 'pom.xml': 'code project', 'build.gradle': 'code project',
+    'steam_api.dll': 'game install', 'steam_api64.dll': 'game install', 'UnityPlayer.dll': 'game install',
+    'GameAssembly.dll': 'game install', 'steam_appid.txt': 'game install',
+}
+MARKER_PREFIXES = (('goggame-', 'GOG game install'), ('.sync_', 'Nextcloud/ownCloud folder'))
+MARKER_SUFFIXES = {'.sln': 'code project', '.uproject': 'Unreal project'}
+# Folder name -> why
+FOLDER_NAMES = {
+    'node_modules': 'Node.js dependencies', 'bower_components': 'web dependencies',
+    '__pycache__': 'Python cache', 'site-packages': 'Python packages', 'Pods': 'CocoaPods dependencies',
+}
+BUNDLE_SUFFIXES = {
+    '.app': 'application bundle', '.framework': 'framework bundle', '.bundle': 'bundle',
+    '.plugin': 'plugin bundle', '.kext': 'kernel extension', '.photoslibrary': 'Photos library',
+    '.musiclibrary': 'Music library', '.tvlibrary': 'TV library', '.aplibrary': 'Aperture library',
+    '.fcpbundle': 'Final Cut library', '.lrdata': 'Lightroom data', '.logicx': 'Logic project',
+    '.band': 'GarageBand project', '.xcodeproj': 'Xcode project', '.xcworkspace': 'Xcode workspace',
+    '.sparsebundle': 'disk image', '.vmwarevm': 'virtual machine', '.pvm': 'virtual machine',
+    '.utm': 'virtual machine', '.lrlibrary': 'Lightroom library',
+}
+# Sync roots, recognized by name directly in the home folder
+SYNC_ROOTS = {'dropbox': 'Dropbox folder', 'icloud drive': 'iCloud Drive', 'icloud': 'iCloud Drive',
+              'google drive': 'Google Drive', 'my drive': 'Google Drive', 'nextcloud': 'Nextcloud folder',
+              'owncloud': 'ownCloud folder', 'pcloud drive': 'pCloud folder', 'mega': 'MEGA folder',
+              'box': 'Box folder', 'sync': 'Sync.com folder'}
+SYNC_PREFIXES = (('onedrive', 'OneDrive folder'), ('dropbox (', 'Dropbox folder'))
+
+
+def detect(directory: Path, home: Optional[Path] = None) -> Optional[str]:
+    """
+    Why a folder must be left alone, from what it is and what it contains.
+
+    Args:
+        directory (Path): Folder to check
+        home (Path, optional): Home folder, for sync roots (default: the user's)
+
+    Returns:
+        str or None: Reason, or None if the folder can be organized
+    """
+    name = directory.name
+    reason = FOLDER_NAMES.get(name) or BUNDLE_SUFFIXES.get(directory.suffix.lower())
+    if reason:
+        return reason
+    home = home or Path.home()
+    if directory.parent == home or directory == home / 'Library' / 'Mobile Documents':
+        lowered = name.lower()
+        reason = SYNC_ROOTS.get(lowered) or next((r for p, r in SYNC_PREFIXES if lowered.startswith(p)), None)
+        if reason or name == 'Mobile Documents':
+            return reason or 'iCloud Drive'
+    try:
+        entries = list(os.scandir(directory))
+    except OSError:
+        return None
+    for entry in entries:
+        try:
+            is_dir = entry.is_dir(follow_symlinks=False)
+        except OSError:
+            continue
+        reason = (MARKER_DIRS.get(entry.name) if is_dir else
+                  MARKER_FILES.get(entry.name) or MARKER_SUFFIXES.get(os.path.splitext(entry.name)[1].lower()))
+        if reason is None and not is_dir:
+            reason = next((r for p, r in MARKER_PREFIXES if entry.name.startswith(p)), None)
+        if reason:
+            return reason
+    return None
+
+
+class LeaveAloneDetector:
+    """
+    Decides which folders the planner and watcher must not look inside.
+
+    Attributes:
+        config: Configuration object
+        enabled (bool): Whether detection is on (the 'always' list applies regardless)
+    """
+
+    def __init__(self, config):
+        """
+        Initialize detector.
+
+        Args:
+            config: Configuration object (reads `leave_alone_settings`)
+        """
+        self.config = config
+        settings = getattr(config, 'leave_alone_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', True))
+        self.always = self._paths(settings.get('always'))
+        self.allow = self._paths(settings.get('allow'))
+        self._cache: Dict[str, Tuple[Optional[str], float]] = {}
+        self._lock = threading.Lock()
+
+    @staticmethod
+    def _paths(values: Any) -> List[Path]:
+        if not isinstance(values, list):
+            return []
+        return [Path(os.path.abspath(Path(str(v)).expanduser())) for v in values if v]
+
+    @staticmethod
+    def _within(path: Path, folders: Iterable[Path]) -> Optional[Path]:
+        for folder in folders:
+            if path == folder or folder in path.parents:
+                return folder
+        return None
+
+    def reason(self, directory: Path) -> Optional[str]:
+        """
+        Why a folder is left alone, with the user's lists applied.
+
+        Args:
+            directory (Path): Folder to check
+
+        Returns:
+            str or None: Reason, or None if the folder can be organized
+        """
+        directory = Path(os.path.abspath(directory))
+        if self._within(directory, self.always) is not None:
+            return 'on your leave-alone list'
+        if not self.enabled or self._within(directory, self.allow) is not None:
+            return None
+        key = str(directory)
+        now = time.monotonic()
+        with self._lock:
+            cached = self._cache.get(key)
+            if cached is not None and now - cached[1] < CACHE_SECONDS:
+                return cached[0]
+        found = detect(directory)
+        with self._lock:
+            self._cache[key] = (found, now)
+        return found
+
+    def enclosing(self, path: Path, roots: Iterable[Path]) -> Optional[Tuple[Path, str]]:
+        """
+        The left-alone folder a file is in, looking only below its root.
+
+        Args:
+            path (Path): File
+            roots (Iterable[Path]): Folders being organized or watched
+
+        Returns:
+            Tuple or None: (folder, reason), or None if the file may be organized
+        """
+        path = Path(os.path.abspath(path))
+        listed = self._within(path.parent, self.always)
+        if listed is not None:
+            return listed, 'on your leave-alone list'
+        root = max((Path(os.path.abspath(r)) for r in roots
+                    if self._within(path.parent, [Path(os.path.abspath(r))]) is not None),
+                   key=lambda r: len(r.parts), default=None)
+        if root is None:
+            return None
+        for depth in range(len(root.parts) + 1, len(path.parent.parts) + 1):
+            folder = Path(*path.parent.parts[:depth])
+            found = self.reason(folder)
+            if found:
+                return folder, found
+        return None
+
+    def find(self, folder: str) -> Dict[str, Any]:
+        """
+        List the folders below a folder that are left alone.
+
+        Args:
+            folder (str): Folder to check
+
+        Returns:
+            Dict: 'success', 'message', 'folders' ({path, reason} each, not
+                  descending into them), and the user's 'always' and 'allow' lists
+        """
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {root}", 'folders': [],
+                    'always': [str(p) for p in self.always], 'allow': [str(p) for p in self.allow]}
+        root = Path(os.path.abspath(root))
+        found = []
+        for dirpath, dirnames, _ in os.walk(root):
+            kept = []
+            for name in sorted(dirnames):
+                reason = self.reason(Path(dirpath) / name)
+                if reason:
+                    found.append({'path': os.path.join(dirpath, name), 'reason': reason})
+                elif not name.startswith('.'):
+                    kept.append(name)
+            dirnames[:] = kept
+        return {
+            'success': True,
+            'message': f"{len(found)} folder(s) left alone in {root}",
+            'folders': found,
+            'always': [str(p) for p in self.always],
+            'allow': [str(p) for p in self.allow]
+        }
+
+    def set_override(self, path: str, mode: Optional[str]) -> Dict[str, Any]:
+        """
+        Put a folder on the 'always' or 'allow' list, or take it off both.
+
+        Args:
+            path (str): Folder
+            mode (str or None): 'always', 'allow', or None to remove it
+
+        Returns:
+            Dict: 'success', 'message', and the updated 'always' and 'allow' lists
+        """
+        if mode not in ('always', 'allow', None):
+            return {'success': False, 'message': f"Unknown list {mode!r} (use always or allow)"}
+        target = Path(os.path.abspath(Path(path).expanduser()))
+        lists = {'always': [p for p in self.always if p != target], 'allow': [p for p in self.allow if p != target]}
+        if mode:
+            lists[mode].append(target)
+        self.always, self.allow = lists['always'], lists['allow']
+        with self._lock:
+            self._cache.clear()
+        settings = getattr(self.config, 'leave_alone_settings', None)
+        settings = dict(settings) if isinstance(settings, dict) else {}
+        settings.update({name: [str(p) for p in paths] for name, paths in lists.items()})
+        try:
+            self.config.update('leave_alone', settings)
+            self.config.save()
+        except OSError as e:
+            return {'success': False, 'message': f"Cannot save config: {e}"}
+        if mode == 'always':
+            message = f"{target} will be left alone"
+        elif mode == 'allow':
+            message = f"{target} will be organized even if it looks like it should be left alone"
+        else:
+            message = f"{target} is no longer on a leave-alone list"
+        return {'success': True, 'message': message, 'always': settings['always'], 'allow': settings['allow']}
@@ -0,0 +1,129 @@
+"""
+Local Rules Classifier Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module classifies files when the engine (or its AI backend) is not
+available: only the file's extension, its MIME type and its name are
+used, so nothing is opened, no model is called and no database is needed.
+
+Results have the shape of FileClassifier.classify() results, with
+'model_used': 'local-rules', so callers can tell a fallback answer from
+the engine's. Extensions are looked up in the configured destination
+rules first, then in BUILTIN_RULES; the MIME type's family decides for
+unknown extensions.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import mimetypes
+import re
+from pathlib import Path
+from typing import Dict, Any, Optional
+
+MODEL_NAME = 'local-rules'
+
+# Used for extensions the configuration has no rule for
+BUILTIN_RULES: Dict[str, str] = {
+    **dict.fromkeys(('pdf',), 'Documents/PDFs/'),
+    **dict.fromkeys(('doc', 'docx', 'odt', 'rtf', 'pages'), 'Documents/Word/'),
+    **dict.fromkeys(('xls', 'xlsx', 'ods', 'csv', 'numbers'), 'Documents/Excel/'),
+    **dict.fromkeys(('ppt', 'pptx', 'odp', 'key'), 'Documents/PowerPoint/'),
+    **dict.fromkeys(('txt', 'md', 'rst', 'log'), 'Documents/Text/'),
+    **dict.fromkeys(('epub', 'mobi', 'azw3'), 'Documents/Books/'),
+    **dict.fromkeys(('jpg', 'jpeg', 'png', 'gif', 'bmp', 'webp', 'heic', 'tif', 'tiff', 'svg',
+                     'raw', 'cr2', 'nef', 'arw', 'dng'), 'Pictures/'),
+    **dict.fromkeys(('mp4', 'mkv', 'avi', 'mov', 'wmv', 'webm', 'm4v'), 'Videos/'),
+    **dict.fromkeys(('mp3', 'wav', 'flac', 'aac', 'ogg', 'm4a', 'opus', 'wma'), 'Music/'),
+    **dict.fromkeys(('zip', 'rar', '7z', 'tar', 'gz', 'bz2', 'xz', 'tgz'), 'Downloads/Archives/'),
+    **dict.fromkeys(('exe', 'msi', 'dmg', 'pkg', 'deb', 'rpm', 'appimage'), 'Downloads/Installers/'),
+    **dict.fromkeys(('py', 'js', 'ts', 'java', 'c', 'cpp', 'h', 'rs', 'go', 'rb', 'php',
+                     'html', 'css', 'json', 'xml', 'yaml', 'yml', 'sh'), 'Projects/Code/'),
+    **dict.fromkeys(('ttf', 'otf', 'woff', 'woff2'), 'Fonts/'),
+}
+
+# MIME family -> folder, for extensions no rule knows
+MIME_FAMILIES: Dict[str, str] = {
+    'image': 'Pictures/',
+    'video': 'Videos/',
+    'audio': 'Music/',
+    'text': 'Documents/Text/',
+    'font': 'Fonts/',
+}
+
+# (pattern on the lowercase name without extension, folder, category, reason)
+NAME_PATTERNS = (
+    (r'screen ?shot|screen ?capture|^capture', 'Pictures/Screenshots/', 'Pictures', 'screenshot'),
+    (r'invoice|receipt|bill', 'Documents/Finance/Invoices/', 'Finance', 'invoice-related keywords'),
+    (r'resume|\bcv\b|curriculum', 'Documents/Personal/Resume/', 'Documents', 'resume/CV keywords'),
+    (r'bank.?statement|statement|tax', 'Documents/Finance/', 'Finance', 'finance keywords'),
+)
+
+
+def classify_file_offline(file_path: str, config=None) -> Dict[str, Any]:
+    """
+    Classify a file from its extension, MIME type and name alone.
+
+    Args:
+        file_path (str): File to classify (it need not exist)
+        config: Configuration whose destination_rules take precedence (optional)
+
+    Returns:
+        Dict: category, suggested_path, rename (None), reason, confidence,
+              method ('rule-based') and model_used ('local-rules')
+    """
+    path = Path(file_path)
+    extension = path.suffix.lower().lstrip('.')
+    stem = path.stem.lower()
+    rules = getattr(config, 'destination_rules', None)
+    rules = rules if isinstance(rules, dict) else {}
+
+    pattern = _match_name(stem)
+    # A recognizable name says more than a generic extension, e.g. Screenshot 2025-01-01.png
+    if pattern and pattern[0] == 'Pictures/Screenshots/' and _family(path) == 'image':
+        return _result(pattern[0], f'Detected {pattern[1]} in the file name', 'high', pattern[2])
+
+    folder = rules.get(extension) or BUILTIN_RULES.get(extension)
+    if folder:
+        if pattern and pattern[2] == 'Finance' and folder.startswith('Documents/'):
+            return _result(pattern[0], f'Detected {pattern[1]} in the file name (.{extension})', 'high',
+                           pattern[2])
+        return _result(folder, f'Classified by file extension (.{extension})', 'high')
+
+    if pattern:
+        return _result(pattern[0], f'Detected {pattern[1]} in the file name', 'medium', pattern[2])
+
+    family = _family(path)
+    if family in MIME_FAMILIES:
+        return _result(MIME_FAMILIES[family], f'Classified by MIME type ({family}/*)', 'medium')
+
+    return _result('Unsorted/', 'No matching local rules', 'low', 'Unsorted')
+
+
+def _match_name(stem: str) -> Optional[tuple]:
+    for pattern, folder, category, reason in NAME_PATTERNS:
+        if re.search(pattern, stem):
+            return folder, reason, category
+    return None
+
+
+def _family(path: Path) -> Optional[str]:
+    mime_type, _ = mimetypes.guess_type(path.name)
+    return mime_type.split('/', 1)[0] if mime_type else None
+
+
+def _result(folder: str, reason: str, confidence: str, category: Optional[str] = None) -> Dict[str, Any]:
+    return {
+        'category': category or folder.strip('/').split('/', 1)[0],
+        'suggested_path': folder,
+        'rename': None,
+        'reason': reason,
+        'confidence': confidence,
+        'method': 'rule-based',
+        'model_used': MODEL_NAME
+    }
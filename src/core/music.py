@@ -0,0 +1,335 @@
+"""
+Music Library Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module is the "music" planner strategy: songs are filed by their
+embedded tags (see core.audio_tags) instead of going through the generic
+classifier, e.g.
+
+    Music/Daft Punk/Discovery/03 Digital Love.mp3
+    Music/Pink Floyd/The Wall/2-01 Hey You.flac        (multi-disc album)
+    Music/Compilations/Now 45/07 Blur - Song 2.mp3     (compilation)
+
+The folder is the album artist (else the track artist) and the album. An
+album is a compilation when it is tagged as one, its album artist is
+"Various Artists", or an untagged album in one folder has tracks by three
+or more artists; its tracks go under `music_compilations` with the artist
+in the file name.
+
+Missing tags are inferred from the file name ("03 - Digital Love.mp3",
+"03 Daft Punk - Digital Love.mp3") and the Artist/Album folders the file
+is in. `tag_fixups()` lists those inferences, and inconsistent album
+artists, as a preview; it never writes tags.
+
+Select it per folder in config.json:
+
+    "folder_policies": {
+        "~/Music/Incoming": {
+            "strategy": "music",
+            "music_destination": "Music",
+            "music_compilations": "Compilations"
+        }
+    }
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import re
+from collections import Counter, defaultdict
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .audio_tags import AUDIO_EXTENSIONS, read_tags
+from .file_names import safe_name, place_in_destination
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_DESTINATION = 'Music'
+DEFAULT_COMPILATIONS = 'Compilations'
+UNKNOWN_ARTIST = 'Unknown Artist'
+UNKNOWN_ALBUM = 'Unknown Album'
+VARIOUS_ARTISTS = 'Various Artists'
+VARIOUS_NAMES = {'various artists', 'various', 'va', 'v.a.', 'compilation', 'varios artistas',
+                 'verschiedene interpreten', 'artistes divers', 'artisti vari'}
+COMPILATION_MIN_ARTISTS = 3
+# "03 - Title", "03. Title", "1-03 Title", "03 Artist - Title", "Artist - Title"
+_NUMBERED = re.compile(r'^(?:(?P<disc>\d{1,2})[-.])?(?P<track>\d{1,3})(?:\s*[-._)]\s*|\s+)(?P<rest>.+)$')
+_ARTIST_TITLE = re.compile(r'^(?P<artist>.+?)\s+-\s+(?P<title>.+)$')
+
+
+def parse_filename(path: Path) -> Dict[str, Any]:
+    """
+    Tags that can be read from a song's file name.
+
+    Args:
+        path (Path): Song file
+
+    Returns:
+        Dict: Some of 'disc', 'track', 'artist' and 'title'
+    """
+    stem = path.stem.replace('_', ' ').strip()
+    found: Dict[str, Any] = {}
+    numbered = _NUMBERED.match(stem)
+    if numbered:
+        if numbered.group('disc'):
+            found['disc'] = int(numbered.group('disc'))
+        found['track'] = int(numbered.group('track'))
+        stem = numbered.group('rest').strip()
+    pair = _ARTIST_TITLE.match(stem)
+    if pair:
+        found['artist'] = pair.group('artist').strip()
+        stem = pair.group('title').strip()
+    if stem:
+        found['title'] = stem
+    return found
+
+
+def is_various(name: Optional[str]) -> bool:
+    """True for 'Various Artists' and its common spellings."""
+    return bool(name) and name.strip().lower() in VARIOUS_NAMES
+
+
+class MusicLibraryStrategy:
+    """
+    Plans song destinations from their tags.
+
+    Attributes:
+        config: Configuration object
+    """
+
+    name = 'music'
+
+    def __init__(self, config):
+        """
+        Initialize music strategy.
+
+        Args:
+            config: Configuration object
+        """
+        self.config = config
+        self._folder_artists: Dict[str, Dict[str, set]] = {}
+
+    # ==================== Tags ====================
+
+    @staticmethod
+    def song_tags(path: Path, root: Optional[Path] = None,
+                  tags: Optional[Dict[str, Any]] = None) -> Tuple[Dict[str, Any], Dict[str, str]]:
+        """
+        A song's tags with the missing ones inferred.
+
+        Args:
+            path (Path): Song file
+            root (Path, optional): Folder being organized; only folders below
+                                   it are read as Artist/Album names
+            tags (Dict, optional): Tags already read from the file
+
+        Returns:
+            Tuple: (tags, field -> reason for each inferred field)
+        """
+        tags = dict(read_tags(str(path)) if tags is None else tags)
+        inferred: Dict[str, str] = {}
+        from_name = parse_filename(path)
+        for field in ('title', 'track', 'disc', 'artist'):
+            if field not in tags and field in from_name:
+                tags[field] = from_name[field]
+                inferred[field] = 'from the file name'
+
+        folders: List[str] = []
+        if root is not None:
+            try:
+                folders = list(path.parent.relative_to(root).parts)
+            except ValueError:
+                folders = []
+        if 'album' not in tags and folders:
+            tags['album'] = folders[-1]
+            inferred['album'] = 'from the folder name'
+        if 'artist' not in tags and 'albumartist' not in tags and len(folders) >= 2:
+            tags['artist'] = folders[-2]
+            inferred['artist'] = 'from the folder name'
+        return tags, inferred
+
+    def is_compilation(self, path: Path, tags: Dict[str, Any]) -> bool:
+        """
+        Whether a song belongs to a compilation album.
+
+        Args:
+            path (Path): Song file
+            tags (Dict): Its tags
+
+        Returns:
+            bool: True if tagged as one, by Various Artists, or (without an
+                  album artist) its album's folder has tracks by several artists
+        """
+        if tags.get('compilation') or is_various(tags.get('albumartist')):
+            return True
+        if tags.get('albumartist') or not tags.get('album'):
+            return False
+        artists = self._album_artists(path.parent).get(tags['album'].lower(), set())
+        return len(artists) >= COMPILATION_MIN_ARTISTS
+
+    def _album_artists(self, folder: Path) -> Dict[str, set]:
+        """Album (lowercase) -> track artists, for the songs directly in a folder."""
+        key = str(folder)
+        if key not in self._folder_artists:
+            albums: Dict[str, set] = defaultdict(set)
+            try:
+                songs = [p for p in folder.iterdir() if p.suffix.lower() in AUDIO_EXTENSIONS and p.is_file()]
+            except OSError:
+                songs = []
+            for song in songs:
+                tags = read_tags(str(song))
+                if tags.get('album') and tags.get('artist'):
+                    albums[tags['album'].lower()].add(tags['artist'].lower())
+            self._folder_artists[key] = albums
+        return self._folder_artists[key]
+
+    # ==================== Placement ====================
+
+    def classify(self, file_path: str, root: Optional[Path] = None,
+                 policy: Optional[Dict[str, Any]] = None) -> Optional[Dict[str, Any]]:
+        """
+        Work out where a song belongs.
+
+        Args:
+            file_path (str): Song to place
+            root (Path, optional): Folder being organized (for folder names)
+            policy (Dict, optional): Folder policy with the music_* settings
+
+        Returns:
+            Dict or None: Classification (suggested_path is the album folder,
+                          rename the track file name), or None if the file is
+                          not audio
+        """
+        path = Path(file_path)
+        if path.suffix.lower() not in AUDIO_EXTENSIONS:
+            return None
+        policy = policy or {}
+        raw = read_tags(str(path))
+        tags, inferred = self.song_tags(path, root, raw)
+        compilation = self.is_compilation(path, tags)
+
+        album = safe_name(tags.get('album')) or UNKNOWN_ALBUM
+        artist = safe_name(tags.get('artist'))
+        if compilation:
+            folder = f"{safe_name(policy.get('music_compilations')) or DEFAULT_COMPILATIONS}/{album}"
+        else:
+            folder = f"{safe_name(tags.get('albumartist')) or artist or UNKNOWN_ARTIST}/{album}"
+
+        rename = None
+        title = safe_name(tags.get('title'))
+        if title:
+            if compilation and artist:
+                title = f"{artist} - {title}"
+            number = self.track_number(tags)
+            rename = f"{number} {title}{path.suffix}" if number else f"{title}{path.suffix}"
+            if rename == path.name:
+                rename = None
+
+        reason = 'From tags' if raw else 'Untagged'
+        if inferred:
+            reason += f" ({', '.join(sorted(inferred))} inferred from file/folder names)"
+        if compilation:
+            reason += '; compilation'
+        classification = {
+            'category': 'Music',
+            'suggested_path': f"{folder}/",
+            'rename': rename,
+            'reason': reason,
+            'confidence': 'high' if raw and not inferred else 'medium' if raw or inferred else 'low',
+            'method': 'music',
+            'compilation': compilation
+        }
+
+        destination = str(policy.get('music_destination') or DEFAULT_DESTINATION)
+        return place_in_destination(classification, destination, folder)
+
+    @staticmethod
+    def track_number(tags: Dict[str, Any]) -> str:
+        """'07', or '2-07' on multi-disc albums; '' without a track number."""
+        track = tags.get('track')
+        if not track:
+            return ''
+        disc = tags.get('disc') or 0
+        if disc > 1 or (tags.get('disctotal') or 0) > 1:
+            return f"{disc or 1}-{track:02d}"
+        return f"{track:02d}"
+
+    # ==================== Tag fixups ====================
+
+    def tag_fixups(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Preview tag corrections for the songs in a folder (nothing is written).
+
+        Suggests missing titles, track numbers, artists and albums inferred
+        from file and folder names, titles that still carry the track
+        number, a single album artist for albums tagged with several, and
+        'Various Artists' for untagged compilations.
+
+        Args:
+            folder (str): Folder to check
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: 'success', 'message', 'songs' count and 'fixups', each
+                  {path, field, current, suggested, reason}
+        """
+        root = Path(folder).expanduser()
+        if not root.is_dir():
+            return {'success': False, 'message': f"Not a folder: {root}", 'songs': 0, 'fixups': []}
+        root = root.resolve()
+        pattern = '**/*' if recursive else '*'
+        songs = sorted(p for p in root.glob(pattern)
+                       if p.suffix.lower() in AUDIO_EXTENSIONS and p.is_file()
+                       and not any(part.startswith('.') for part in p.relative_to(root).parts))
+
+        fixups: List[Dict[str, Any]] = []
+        albums: Dict[Tuple[str, str], List[Tuple[Path, Dict[str, Any]]]] = defaultdict(list)
+        for song in songs:
+            raw = read_tags(str(song))
+            tags, inferred = self.song_tags(song, root, raw)
+            for field, reason in sorted(inferred.items()):
+                fixups.append(_fixup(song, field, None, tags[field], f"Missing; {reason}"))
+            title = raw.get('title', '')
+            numbered = _NUMBERED.match(title)
+            if numbered and tags.get('track') == int(numbered.group('track')):
+                fixups.append(_fixup(song, 'title', title, numbered.group('rest').strip(),
+                                     'Title starts with the track number'))
+            if tags.get('album'):
+                albums[(str(song.parent), tags['album'].lower())].append((song, tags))
+
+        for tracks in albums.values():
+            album_artists = Counter(t['albumartist'] for _, t in tracks if t.get('albumartist'))
+            artists = {t['artist'].lower() for _, t in tracks if t.get('artist')}
+            if len(album_artists) > 1:
+                majority = album_artists.most_common(1)[0][0]
+                for song, tags in tracks:
+                    if tags.get('albumartist') != majority:
+                        fixups.append(_fixup(song, 'albumartist', tags.get('albumartist'), majority,
+                                             'Album has several album artists'))
+            elif not album_artists and len(artists) >= COMPILATION_MIN_ARTISTS:
+                for song, tags in tracks:
+                    fixups.append(_fixup(song, 'albumartist', None, VARIOUS_ARTISTS,
+                                         f"Compilation: album has {len(artists)} artists"))
+                    if not tags.get('compilation'):
+                        fixups.append(_fixup(song, 'compilation', tags.get('compilation'), True,
+                                             'Compilation not flagged'))
+
+        fixups.sort(key=lambda f: f['path'])
+        return {
+            'success': True,
+            'message': f"{len(fixups)} suggested tag fix(es) in {len(songs)} song(s)",
+            'songs': len(songs),
+            'fixups': fixups
+        }
+
+
+def _fixup(path: Path, field: str, current: Any, suggested: Any, reason: str) -> Dict[str, Any]:
+    return {'path': str(path), 'field': field, 'current': current, 'suggested': suggested, 'reason': reason}
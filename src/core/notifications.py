@@ -0,0 +1,192 @@
+"""
+Notifications Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module shows desktop notifications for what happens while nobody is
+looking at the window:
+
+- run_completed: an organize run (started by hand or on a schedule) finished
+- file_moved: a watched folder's rules moved a new file
+- conflict: files could not be moved without the user's choice (their
+  destination is taken)
+
+Each kind can be switched off in the user's settings (see core.settings),
+or all of them at once:
+
+    "notifications": {"enabled": true, "run_completed": true, "file_moved": false, "conflict": true}
+
+Notifications go through the tray icon when it is shown, else through the
+system: notify-send on Linux, osascript on macOS, a toast on Windows
+(PowerShell). They are sent in the background, so a slow notification
+service never holds up a move; without one they are only logged.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import shutil
+import subprocess
+import sys
+import threading
+from pathlib import Path
+from typing import Callable, Dict, Any, List, Optional
+from xml.sax.saxutils import escape
+
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+APP_NAME = 'AI File Organiser'
+RUN_COMPLETED = 'run_completed'
+FILE_MOVED = 'file_moved'
+CONFLICT = 'conflict'
+EVENT_TYPES = (RUN_COMPLETED, FILE_MOVED, CONFLICT)
+SEND_TIMEOUT = 10  # Seconds a notification command may take
+
+_TOAST_SCRIPT = """
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null
+[Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] | Out-Null
+$xml = New-Object Windows.Data.Xml.Dom.XmlDocument
+$xml.LoadXml('{toast}')
+$toast = [Windows.UI.Notifications.ToastNotification]::new($xml)
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{app}').Show($toast)
+"""
+_TOAST_XML = ('<toast><visual><binding template="ToastGeneric"><text>{title}</text><text>{message}</text>'
+              '</binding></visual></toast>')
+
+
+def _applescript_string(text: str) -> str:
+    return '"' + text.replace('\\', '\\\\').replace('"', '\\"') + '"'
+
+
+def _powershell_xml(text: str) -> str:
+    """Text for the toast XML inside a single-quoted PowerShell string."""
+    return escape(text, {'"': '&quot;'}).replace("'", "''")
+
+
+def system_command(title: str, message: str) -> Optional[List[str]]:
+    """
+    The command that shows a notification on this system.
+
+    Args:
+        title (str): Notification title
+        message (str): Notification text
+
+    Returns:
+        Optional[List[str]]: Arguments, or None when the system has no notification command
+    """
+    if sys.platform == 'darwin':
+        return ['osascript', '-e', f"display notification {_applescript_string(message)} "
+                                   f"with title {_applescript_string(title)}"]
+    if sys.platform.startswith('win'):
+        toast = _TOAST_XML.format(title=_powershell_xml(title), message=_powershell_xml(message))
+        script = _TOAST_SCRIPT.format(toast=toast, app=APP_NAME.replace("'", "''"))
+        return ['powershell', '-NoProfile', '-NonInteractive', '-Command', script]
+    if shutil.which('notify-send'):
+        return ['notify-send', f"--app-name={APP_NAME}", title, message]
+    return None
+
+
+def _send_with_system(title: str, message: str) -> bool:
+    command = system_command(title, message)
+    if command is None:
+        return False
+    try:
+        subprocess.run(command, capture_output=True, timeout=SEND_TIMEOUT, check=True,
+                       creationflags=getattr(subprocess, 'CREATE_NO_WINDOW', 0))
+    except (OSError, subprocess.SubprocessError) as e:
+        logger.debug(f"Notification not shown: {e}")
+        return False
+    return True
+
+
+class Notifier:
+    """
+    Sends desktop notifications, as the user's settings allow.
+
+    Attributes:
+        settings: SettingsStore holding the "notifications" switches (None: all on)
+        tray (TrayIcon, optional): Shows notifications itself when its icon is up
+        sender (Callable): Called as (title, message) to show one (default: the system's way)
+        background (bool): Send from a thread instead of the caller's
+    """
+
+    def __init__(self, settings=None, tray=None, sender: Optional[Callable[[str, str], bool]] = None,
+                 background: bool = True):
+        """
+        Initialize notifier.
+
+        Args:
+            settings: SettingsStore (None: every notification is on)
+            tray (TrayIcon, optional): Tray icon to show notifications with
+            sender (Callable, optional): Shows one notification; returns whether it did
+            background (bool): Send from a thread (False: wait for each one)
+        """
+        self.settings = settings
+        self.tray = tray
+        self.sender = sender or self._send
+        self.background = background
+
+    def wants(self, event_type: str) -> bool:
+        """Whether notifications of a kind are switched on."""
+        if event_type not in EVENT_TYPES:
+            raise ValueError(f"Unknown notification type {event_type!r} (use {', '.join(EVENT_TYPES)})")
+        if self.settings is None:
+            return True
+        try:
+            switches = self.settings.get('notifications') or {}
+        except ConfigurationError as e:
+            logger.debug(f"Notification settings unreadable, using the defaults: {e}")
+            switches = {}
+        return bool(switches.get('enabled', True)) and bool(switches.get(event_type, True))
+
+    def notify(self, event_type: str, title: str, message: str) -> bool:
+        """
+        Show a notification unless its kind is switched off.
+
+        Args:
+            event_type (str): One of EVENT_TYPES
+            title (str): Notification title
+            message (str): Notification text
+
+        Returns:
+            bool: Whether it was sent (or handed to the background thread)
+        """
+        if not self.wants(event_type):
+            return False
+        logger.info(f"Notification ({event_type}): {title} - {message}")
+        if not self.background:
+            return bool(self.sender(title, message))
+        threading.Thread(target=self.sender, args=(title, message), daemon=True, name='notify').start()
+        return True
+
+    def _send(self, title: str, message: str) -> bool:
+        if self.tray is not None and self.tray.notify(title, message):
+            return True
+        return _send_with_system(title, message)
+
+    def run_completed(self, job: Dict[str, Any]) -> bool:
+        """Notify that an organize job finished (see core.jobs)."""
+        folder = Path(job.get('folder') or '').name or job.get('folder') or 'folder'
+        titles = {'done': 'Organizing finished', 'failed': 'Organizing failed', 'cancelled': 'Organizing cancelled'}
+        return self.notify(RUN_COMPLETED, titles.get(job.get('state'), 'Organizing finished'),
+                           f"{folder}: {job.get('message') or job.get('state')}")
+
+    def file_moved(self, source: str, new_path: Optional[str]) -> bool:
+        """Notify that a watched folder's rules moved a file."""
+        destination = Path(new_path).parent if new_path else None
+        where = f" to {destination}" if destination else ''
+        return self.notify(FILE_MOVED, 'File organized', f"Moved {Path(source).name}{where}")
+
+    def conflict(self, count: int, folder: Optional[str] = None) -> bool:
+        """Notify that files wait for the user to choose what happens to them."""
+        where = f" in {Path(folder).name or folder}" if folder else ''
+        return self.notify(CONFLICT, 'Conflicts to review',
+                           f"{count} file(s){where} could not be moved: their destination is taken")
@@ -0,0 +1,200 @@
+"""
+OCR Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module reads the text of scanned documents and screenshots, so an
+image-only PDF or a photographed receipt named "IMG_2043.png" is
+classified by what it says rather than filed as just another picture.
+
+Text is read locally with the Tesseract OCR engine (nothing leaves the
+computer). PDFs are rendered page by page with Poppler's pdftoppm first;
+only the first `max_pages` pages are read. Without Tesseract nothing is
+read and classification goes on from the name, as before.
+
+Camera photos (a camera make or model in their EXIF) are not read when
+classifying: they rarely hold text and OCR is slow.
+
+    "ocr": {"enabled": true, "languages": "eng", "tesseract_path": "",
+            "max_pages": 3, "timeout": 60, "skip_camera_photos": true}
+
+The Tesseract path and languages of `screenshots` apply when these are
+not set.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import shutil
+import subprocess
+import tempfile
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .exif import read_exif
+
+logger = logging.getLogger(__name__)
+
+
+IMAGE_EXTENSIONS = {'.png', '.jpg', '.jpeg', '.tif', '.tiff', '.bmp', '.webp', '.gif'}
+DEFAULT_MAX_PAGES = 3
+DEFAULT_TIMEOUT = 60          # Seconds per page
+PDF_RESOLUTION = 200          # DPI pages are rendered at
+MAX_IMAGE_BYTES = 25 * 1024 * 1024
+WINDOWS_TESSERACT = (r'C:\Program Files\Tesseract-OCR\tesseract.exe',
+                     r'C:\Program Files (x86)\Tesseract-OCR\tesseract.exe')
+
+
+def _settings(config, name: str) -> Dict[str, Any]:
+    settings = getattr(config, name, None) if config is not None else None
+    return settings if isinstance(settings, dict) else {}
+
+
+class OcrEngine:
+    """
+    Reads text from images and image-only PDFs with Tesseract.
+
+    Attributes:
+        config: Configuration object
+        enabled (bool): Whether OCR is on (ocr.enabled)
+        languages (str): Tesseract languages, e.g. "eng+deu"
+        max_pages (int): PDF pages read
+        timeout (int): Seconds Tesseract may take per page
+        skip_camera_photos (bool): Do not read camera photos when classifying
+    """
+
+    def __init__(self, config):
+        """
+        Initialize the engine.
+
+        Args:
+            config: Configuration object (reads `ocr_settings`, then `screenshot_settings`)
+        """
+        self.config = config
+        settings = _settings(config, 'ocr_settings')
+        screenshots = _settings(config, 'screenshot_settings')
+        self.enabled = bool(settings.get('enabled', True))
+        self.languages = str(settings.get('languages') or screenshots.get('languages') or 'eng')
+        self.max_pages = max(1, int(settings.get('max_pages', DEFAULT_MAX_PAGES)))
+        self.timeout = max(1, int(settings.get('timeout', DEFAULT_TIMEOUT)))
+        self.skip_camera_photos = bool(settings.get('skip_camera_photos', True))
+        self._configured = settings.get('tesseract_path') or screenshots.get('tesseract_path') or None
+        self._tesseract: Optional[str] = None
+        self._looked_up = False
+
+    @property
+    def tesseract(self) -> Optional[str]:
+        """Path of the Tesseract executable, or None if it is not installed."""
+        if not self._looked_up:
+            candidates = [self._configured] if self._configured else []
+            candidates += [shutil.which('tesseract')]
+            if os.name == 'nt':
+                candidates += list(WINDOWS_TESSERACT)
+            self._tesseract = next((c for c in candidates if c and Path(c).exists()), None)
+            self._looked_up = True
+        return self._tesseract
+
+    @staticmethod
+    def pdftoppm() -> Optional[str]:
+        """Path of Poppler's pdftoppm, which renders PDF pages for OCR."""
+        return shutil.which('pdftoppm')
+
+    def available(self) -> bool:
+        """Whether OCR is on and Tesseract is installed."""
+        return self.enabled and self.tesseract is not None
+
+    def status(self) -> Dict[str, Any]:
+        """'enabled', 'available', 'tesseract', 'pdf' (pages can be rendered) and 'languages'."""
+        return {'enabled': self.enabled, 'available': self.available(), 'tesseract': self.tesseract,
+                'pdf': self.pdftoppm() is not None, 'languages': self.languages}
+
+    def wants(self, path: str) -> bool:
+        """Whether classifying a file (whose text could not be read otherwise) should OCR it."""
+        file = Path(path)
+        suffix = file.suffix.lower()
+        if not self.available() or (suffix != '.pdf' and suffix not in IMAGE_EXTENSIONS):
+            return False
+        if suffix == '.pdf':
+            return self.pdftoppm() is not None
+        try:
+            if file.stat().st_size > MAX_IMAGE_BYTES:
+                return False
+        except OSError:
+            return False
+        if self.skip_camera_photos:
+            tags = read_exif(str(file))
+            if tags.get('Make') or tags.get('Model'):
+                return False
+        return True
+
+    def ocr_file(self, path: str) -> str:
+        """
+        The text of an image or PDF.
+
+        Args:
+            path (str): Image or PDF
+
+        Returns:
+            str: Text read ('' without Tesseract, for other files, or when nothing is legible)
+
+        Raises:
+            FileNotFoundError: If the file does not exist
+        """
+        file = Path(path)
+        if not file.is_file():
+            raise FileNotFoundError(f"Not a file: {file}")
+        if not self.available():
+            return ''
+        suffix = file.suffix.lower()
+        if suffix == '.pdf':
+            return '\n\f'.join(text for text in self._ocr_pdf(file) if text)
+        if suffix in IMAGE_EXTENSIONS:
+            return self._run(file)
+        return ''
+
+    def _ocr_pdf(self, file: Path) -> List[str]:
+        renderer = self.pdftoppm()
+        if renderer is None:
+            logger.info(f"Install Poppler (pdftoppm) to read scanned PDFs like {file.name}")
+            return []
+        with tempfile.TemporaryDirectory(prefix='aifo-ocr-') as pages:
+            try:
+                subprocess.run([renderer, '-r', str(PDF_RESOLUTION), '-f', '1', '-l', str(self.max_pages),
+                                '-png', str(file), os.path.join(pages, 'page')],
+                               capture_output=True, timeout=self.timeout * self.max_pages, check=True)
+            except (OSError, subprocess.SubprocessError) as e:
+                logger.warning(f"Could not render {file} for OCR: {e}")
+                return []
+            return [self._run(page) for page in sorted(Path(pages).glob('page*.png'))]
+
+    def _run(self, image: Path) -> str:
+        try:
+            proc = subprocess.run([self.tesseract, str(image), 'stdout', '-l', self.languages],
+                                  capture_output=True, text=True, timeout=self.timeout)
+        except (OSError, subprocess.TimeoutExpired) as e:
+            logger.warning(f"OCR failed for {image}: {e}")
+            return ''
+        if proc.returncode != 0:
+            logger.debug(f"Tesseract could not read {image}: {proc.stderr.strip()}")
+            return ''
+        return proc.stdout.strip()
+
+
+def ocr_file(path: str, config=None) -> str:
+    """
+    The text of an image or PDF, read with Tesseract (see OcrEngine.ocr_file).
+
+    Args:
+        path (str): Image or PDF
+        config: Configuration object (default: the active configuration)
+    """
+    if config is None:
+        from src.config import get_config
+        config = get_config()
+    return OcrEngine(config).ocr_file(path)
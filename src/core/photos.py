@@ -0,0 +1,217 @@
+"""
+Photo Organizing Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module is the "photos" planner strategy: instead of sorting photos by
+type, it files them by when they were taken, e.g.
+
+    Pictures/2024/05/                      (photo_layout: month, the default)
+    Pictures/2024/2024-05-17 Lisbon trip/  (photo_layout: day)
+    Pictures/2024/Portugal/Lisbon/         (photo_layout: location)
+
+The capture time comes from EXIF (DateTimeOriginal, then DateTimeDigitized,
+then DateTime) and falls back to the file's dates. Cameras record their
+wall-clock time; that is the date used unless the folder sets a
+`photo_timezone`, in which case times with a recorded offset are converted
+to it first.
+
+Select it per folder in config.json:
+
+    "folder_policies": {
+        "~/Pictures/Camera Uploads": {
+            "strategy": "photos",
+            "photo_layout": "day",
+            "photo_destination": "Pictures",
+            "photo_timezone": "camera"
+        }
+    }
+
+For the day layout the event name is `photo_event`, or else the name of
+the subfolder (of the organized folder) the photo is in. The location
+layout reverse-geocodes the photo's GPS position (see core.geocoding);
+photos without one go to `<year>/Unknown location`.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+from datetime import datetime, timezone, tzinfo
+from pathlib import Path
+from typing import Dict, Any, Optional, Tuple, Union
+
+from .exif import PHOTO_EXTENSIONS, read_exif, capture_time, gps_coordinates, parse_offset
+from .file_names import safe_name, place_in_destination
+from .geocoding import ReverseGeocoder
+
+logger = logging.getLogger(__name__)
+
+
+LAYOUTS = ('month', 'day', 'location')
+DEFAULT_DESTINATION = 'Pictures'
+UNKNOWN_PLACE = 'Unknown location'
+_LOCAL = 'local'
+
+
+def photo_timezone(value: Optional[str]) -> Union[tzinfo, str, None]:
+    """
+    Interpret a `photo_timezone` setting.
+
+    Args:
+        value (str): 'camera' (default), 'local', 'UTC' or an offset like '+02:00'
+
+    Returns:
+        tzinfo, 'local', or None for the camera's own wall-clock time
+
+    Raises:
+        ValueError: For anything else
+    """
+    text = (value or 'camera').strip()
+    if text.lower() == 'camera':
+        return None
+    if text.lower() == _LOCAL:
+        return _LOCAL
+    if text.upper() in ('UTC', 'Z'):
+        return timezone.utc
+    zone = parse_offset(text)
+    if zone is None:
+        raise ValueError(f"Invalid photo_timezone {value!r} (use camera, local, UTC or an offset like +02:00)")
+    return zone
+
+
+class PhotoDateStrategy:
+    """
+    Plans photo destinations from capture dates.
+
+    Attributes:
+        config: Configuration object
+    """
+
+    name = 'photos'
+
+    def __init__(self, config):
+        """
+        Initialize photo strategy.
+
+        Args:
+            config: Configuration object
+        """
+        self.config = config
+        self._geocoder: Optional[ReverseGeocoder] = None
+
+    @property
+    def geocoder(self) -> ReverseGeocoder:
+        if self._geocoder is None:
+            self._geocoder = ReverseGeocoder(self.config)
+        return self._geocoder
+
+    def classify(self, file_path: str, root: Optional[Path] = None,
+                 policy: Optional[Dict[str, Any]] = None) -> Optional[Dict[str, Any]]:
+        """
+        Work out where a photo belongs.
+
+        Args:
+            file_path (str): Photo to place
+            root (Path, optional): Folder being organized (for event names)
+            policy (Dict, optional): Folder policy with the photo_* settings
+
+        Returns:
+            Dict or None: Classification (suggested_path is the date folder),
+                          or None if the file is not a photo
+        """
+        path = Path(file_path)
+        if path.suffix.lower() not in PHOTO_EXTENSIONS:
+            return None
+        policy = policy or {}
+
+        layout = policy.get('photo_layout') or 'month'
+        if layout not in LAYOUTS:
+            logger.warning(f"Unknown photo_layout {layout!r}; using 'month'")
+            layout = 'month'
+        try:
+            zone = photo_timezone(policy.get('photo_timezone'))
+        except ValueError as e:
+            logger.warning(f"{e}; using the camera's time")
+            zone = None
+
+        tags = read_exif(str(path))
+        taken, source = self.capture_date(path, zone, tags)
+        if taken is None:
+            return None
+
+        place = None
+        if layout == 'day':
+            event = self._event_name(path, root, policy)
+            folder = f"{taken:%Y}/{taken:%Y-%m-%d}" + (f" {event}" if event else '')
+        elif layout == 'location':
+            position = gps_coordinates(tags)
+            place = self.geocoder.place(*position) if position else None
+            if place:
+                folder = f"{taken:%Y}/{safe_name(place['country'])}/{safe_name(place['city'])}"
+            else:
+                folder = f"{taken:%Y}/{UNKNOWN_PLACE}"
+        else:
+            folder = f"{taken:%Y}/{taken:%m}"
+
+        classification = {
+            'category': 'Pictures',
+            'suggested_path': f"{folder}/",
+            'rename': None,
+            'reason': f"Taken {taken:%Y-%m-%d %H:%M}" + (f" (EXIF {source})" if source != 'file' else ' (file date)'),
+            'confidence': 'high' if source != 'file' else 'medium',
+            'method': 'photos',
+            'captured_at': taken.isoformat(),
+            'date_source': 'exif' if source != 'file' else 'file'
+        }
+        if place:
+            classification['place'] = place
+            classification['reason'] += f" in {place['city']}, {place['country']}"
+
+        destination = str(policy.get('photo_destination') or DEFAULT_DESTINATION)
+        return place_in_destination(classification, destination, folder)
+
+    def capture_date(self, path: Path, zone: Union[tzinfo, str, None] = None,
+                     tags: Optional[Dict[str, Any]] = None) -> Tuple[Optional[datetime], str]:
+        """
+        When a photo was taken, in the requested zone.
+
+        Args:
+            path (Path): Photo
+            zone: Result of photo_timezone() (None keeps the camera's time)
+            tags (Dict, optional): EXIF tags already read from the photo
+
+        Returns:
+            Tuple: (datetime or None, EXIF tag name or 'file')
+        """
+        taken, tag = capture_time(read_exif(str(path)) if tags is None else tags)
+        if taken is not None:
+            if taken.tzinfo is not None and zone is not None:
+                taken = taken.astimezone() if zone == _LOCAL else taken.astimezone(zone)
+            return taken, tag
+
+        try:
+            stat = path.stat()
+        except OSError:
+            return None, 'file'
+        # The earliest file date: copies get a new creation time but keep mtime
+        timestamp = min(stat.st_mtime, getattr(stat, 'st_birthtime', stat.st_mtime))
+        if zone is None or zone == _LOCAL:
+            return datetime.fromtimestamp(timestamp), 'file'
+        return datetime.fromtimestamp(timestamp, zone), 'file'
+
+    @staticmethod
+    def _event_name(path: Path, root: Optional[Path], policy: Dict[str, Any]) -> str:
+        event = policy.get('photo_event')
+        if not event and root is not None:
+            try:
+                relative = path.parent.relative_to(root)
+            except ValueError:
+                relative = None
+            if relative is not None and relative.parts:
+                event = relative.parts[0]
+        return safe_name(event)
@@ -0,0 +1,209 @@
+"""
+Pinned Folders Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps the user's pinned folders (favorites), the one-click
+targets of the sidebar, together with quick stats the backend maintains
+for each pin: how many items the folder holds, how many of them are still
+unorganized, and when the app last organized something in or out of it.
+
+Stats are cached in the database and recomputed when they are older than
+`pinned_folder_settings.stats_max_age` seconds, when the folder has been
+touched by an operation since, or on explicit refresh.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_STATS_MAX_AGE = 300  # seconds
+
+
+class PinnedFolders:
+    """
+    Manages pinned folders and their quick stats.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        classifier: Optional classifier used to count unorganized files
+        stats_max_age (int): Seconds before cached stats are recomputed
+    """
+
+    def __init__(self, config, db_manager, classifier=None):
+        """
+        Initialize pinned folders.
+
+        Args:
+            config: Configuration object (reads `pinned_folder_settings`)
+            db_manager: Database manager instance
+            classifier: Optional FileClassifier; without one every loose
+                        top-level file counts as unorganized
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.classifier = classifier
+
+        settings = getattr(config, 'pinned_folder_settings', None)
+        if not isinstance(settings, dict):
+            settings = {}
+        self.stats_max_age = int(settings.get('stats_max_age', DEFAULT_STATS_MAX_AGE))
+
+    @staticmethod
+    def _normalize(folder_path: str) -> str:
+        return os.path.realpath(os.path.expanduser(folder_path))
+
+    # ==================== Pins ====================
+
+    def pin(self, folder_path: str, label: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Pin a folder and compute its stats.
+
+        Args:
+            folder_path (str): Folder to pin
+            label (str, optional): Display label (defaults to the folder name)
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'pin'
+        """
+        path = self._normalize(folder_path)
+        if not os.path.isdir(path):
+            return {'success': False, 'message': f'Not a folder: {path}'}
+
+        self.db_manager.pin_folder(path, label)
+        self.refresh(path)
+        return {'success': True, 'message': f'Pinned {path}', 'pin': self.get(path)}
+
+    def unpin(self, folder_path: str) -> bool:
+        """
+        Remove a pin (the folder itself is not touched).
+
+        Args:
+            folder_path (str): Pinned folder
+
+        Returns:
+            bool: True if it was pinned
+        """
+        return self.db_manager.unpin_folder(self._normalize(folder_path))
+
+    def reorder(self, folder_paths: List[str]) -> List[Dict[str, Any]]:
+        """
+        Set the sidebar order of pins.
+
+        Args:
+            folder_paths (List[str]): Pinned folders in the desired order
+
+        Returns:
+            List[Dict]: Pins in their new order
+        """
+        self.db_manager.reorder_pinned_folders([self._normalize(p) for p in folder_paths])
+        return self.list(refresh=False)
+
+    def get(self, folder_path: str) -> Optional[Dict[str, Any]]:
+        """Get one pin (without refreshing its stats)."""
+        path = self._normalize(folder_path)
+        return next((p for p in self.list(refresh=False) if p['path'] == path), None)
+
+    def list(self, refresh: bool = True) -> List[Dict[str, Any]]:
+        """
+        List pins in sidebar order.
+
+        Args:
+            refresh (bool): Recompute stats that are stale
+
+        Returns:
+            List[Dict]: Pins with 'path', 'label', 'available', 'item_count',
+                        'unorganized_count', 'last_run' and 'stats_updated_at'
+        """
+        rows = self.db_manager.list_pinned_folders()
+        if refresh:
+            stale = [row['folder_path'] for row in rows if self._is_stale(row)]
+            for path in stale:
+                self.refresh(path)
+            if stale:
+                rows = self.db_manager.list_pinned_folders()
+        return [self._describe(row) for row in rows]
+
+    @staticmethod
+    def _describe(row: Dict[str, Any]) -> Dict[str, Any]:
+        path = row['folder_path']
+        return {
+            'id': row['id'],
+            'path': path,
+            'label': row['label'] or Path(path).name or path,
+            'available': os.path.isdir(path),
+            'item_count': row['item_count'],
+            'unorganized_count': row['unorganized_count'],
+            'last_run': row['last_run'],
+            'stats_updated_at': row['stats_updated_at']
+        }
+
+    # ==================== Stats ====================
+
+    def _is_stale(self, row: Dict[str, Any]) -> bool:
+        updated = row.get('stats_updated_at')
+        if not updated:
+            return True
+        updated_at = datetime.strptime(updated, '%Y-%m-%d %H:%M:%S').replace(tzinfo=timezone.utc)
+        if (datetime.now(timezone.utc) - updated_at).total_seconds() > self.stats_max_age:
+            return True
+        last_run = self.db_manager.get_last_operation_time(row['folder_path'])
+        return bool(last_run) and last_run != row.get('last_run')
+
+    def _is_unorganized(self, path: Path) -> bool:
+        if self.classifier is None:
+            return True
+        try:
+            classification = self.classifier.classify(str(path))
+        except Exception as e:
+            logger.warning(f"Could not classify {path} for pin stats: {e}")
+            return False
+        return bool(classification.get('suggested_path') or classification.get('rename'))
+
+    def refresh(self, folder_path: Optional[str] = None) -> int:
+        """
+        Recompute quick stats.
+
+        The item count covers the whole tree (hidden entries excluded);
+        unorganized files are loose files directly in the folder that the
+        classifier would move or rename.
+
+        Args:
+            folder_path (str, optional): One pinned folder; all pins if omitted
+
+        Returns:
+            int: Number of pins refreshed
+        """
+        if folder_path is None:
+            paths = [row['folder_path'] for row in self.db_manager.list_pinned_folders()]
+        else:
+            paths = [self._normalize(folder_path)]
+
+        for path in paths:
+            last_run = self.db_manager.get_last_operation_time(path)
+            if not os.path.isdir(path):
+                self.db_manager.update_pinned_folder_stats(path, None, None, last_run)
+                continue
+
+            item_count, unorganized = 0, 0
+            for dirpath, dirnames, filenames in os.walk(path):
+                dirnames[:] = [d for d in dirnames if not d.startswith('.')]
+                visible = [f for f in filenames if not f.startswith('.')]
+                item_count += len(visible) + len(dirnames)
+                if dirpath == path:
+                    unorganized = sum(1 for f in visible if self._is_unorganized(Path(dirpath) / f))
+            self.db_manager.update_pinned_folder_stats(path, item_count, unorganized, last_run)
+        return len(paths)
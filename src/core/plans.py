@@ -0,0 +1,729 @@
+"""
+Organize Plans Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module is the headless organize engine shared by the GUI, the
+dashboard and the command line: scan a folder, build an organize plan
+(what would move where), save it as a JSON plan file, and apply a plan
+later as one journaled operation that a single undo reverts. Applying a
+plan that moves enough files first snapshots their folder (see
+core.snapshots), so the run can also be restored in one step.
+
+Plan files are versioned (PLAN_VERSION) so scripts can rely on their
+shape:
+
+    {"version": 1, "kind": "organize", "root": "...", "created_at": "...", "profile": null,
+     "items": [{"file", "size", "modified", "action", "destination",
+                "classification", "conflict", "access"}], "conflicts": 0, "access_problems": 0,
+     "left_alone": [{"path", "reason"}],
+     "timings": {"elapsed": 2.4, "stages": {"scan": {...}, "classify": {...}}}}
+
+Files are classified by the classifier unless a planner strategy applies:
+a strategy (e.g. "photos", which files photos by capture date) can be
+selected per folder with the folder policy's "strategy" key, or for a
+whole run with build_plan(strategy=...). Files a strategy does not handle
+fall back to the classifier.
+
+After a file is moved, the plugin actions that match it (see core.plugins)
+run on its new location; their results are listed under the applied
+item's "plugin_actions".
+
+Folders that must not be reorganized (code repositories, app bundles,
+game installs, sync roots; see core.leave_alone) are not looked inside
+and are listed in the plan's "left_alone", as are folders the ignore rules
+exclude (see core.ignore_rules); ignored files are not planned at all.
+
+A run can use an organization profile (see core.profiles), with
+build_plan(profile=...) or the "profile" plan option: its rules, taxonomy,
+target root, AI tier, exclusions and strategy apply to that run only, and
+the plan records its name under "profile".
+
+A reviewer can send an item elsewhere by adding "override": {"destination",
+"category"} to it; applying moves it there and records the correction
+(see core.corrections), so similar files are suggested there next time.
+
+Cleanup plans (kind "cleanup", from build_cleanup_plan) list junk instead:
+their items have action "delete" and a "reason", and applying moves them
+to the trash in the same single undoable operation.
+
+plan_organization() returns the same plan as an OrganizePlan, one
+PlannedMove per file (id, source, destination, category, confidence and a
+conflict flag), for approval tables. The plan is stored under a plan_id,
+and apply_plan_items(plan_id, accepted_ids) (or apply_plan(plan,
+approved=[ids])) applies only the moves the user ticked; the rejected
+ones are logged to the history as skipped.
+
+A plan is a reviewable artifact ("plan in CI, review, apply later"): items
+whose destination is already taken, or shared with another item, are
+flagged as conflicts, and applying skips files that changed since the
+plan was made rather than acting on content nobody reviewed. Items the
+app could not move (locked or read-only files, folders that cannot be
+written to; see core.write_access) carry their problem under "access"
+({"problem", "message"}) and are counted in "access_problems".
+
+With "conflict_resolution": "ask", applying does not give a file whose
+destination is taken (by then) a new name: it is left where it is and
+returned under the result's "conflicts", for the user to resolve (see
+core.conflicts).
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+from collections import Counter
+from contextlib import nullcontext
+from dataclasses import asdict, dataclass, field, fields
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Callable
+
+from .split_archives import collapse_split_archives
+from .raw_pairs import collapse_raw_pairs
+from .photos import PhotoDateStrategy
+from .music import MusicLibraryStrategy
+from .videos import VideoLibraryStrategy
+from .ebooks import EbookLibraryStrategy
+from .fonts import FontLibraryStrategy
+from .ignore_rules import IgnoreRules
+from .leave_alone import LeaveAloneDetector
+from .plugins import PluginManager
+from .junk import JunkScanner, is_empty_tree
+from .corrections import CorrectionLearner
+from .conflicts import Conflict, ConflictManager, destination_taken
+from .profiles import ProfileManager, ProfileRun
+from .settings import SettingsStore
+from .snapshots import SnapshotManager
+from .write_access import check_write_access
+from src.utils.structured_logging import span, collect_timings, timed
+
+logger = logging.getLogger(__name__)
+
+# Planner strategies: name -> factory taking the config. Each strategy's
+# classify(file_path, root, policy) returns a classification, or None for
+# files it does not handle.
+STRATEGIES: Dict[str, Callable[[Any], Any]] = {
+    PhotoDateStrategy.name: PhotoDateStrategy,
+    MusicLibraryStrategy.name: MusicLibraryStrategy,
+    VideoLibraryStrategy.name: VideoLibraryStrategy,
+    EbookLibraryStrategy.name: EbookLibraryStrategy,
+    FontLibraryStrategy.name: FontLibraryStrategy,
+}
+DEFAULT_STRATEGY = 'classify'
+
+
+PLAN_VERSION = 1
+PLAN_KINDS = ('organize', 'cleanup')
+
+
+@dataclass
+class PlanOptions:
+    """What plan_organization() plans ("folder" is required)."""
+    folder: str
+    recursive: bool = True
+    deep: bool = False
+    strategy: Optional[str] = None
+    profile: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, options: Dict[str, Any]) -> 'PlanOptions':
+        """
+        Raises:
+            ValueError: If "folder" is missing or an option is not known
+        """
+        known = {option.name for option in fields(cls)}
+        unknown = sorted(set(options) - known)
+        if unknown:
+            raise ValueError(f"Unknown plan option(s): {', '.join(unknown)}")
+        if not options.get('folder'):
+            raise ValueError("Plan options need a folder")
+        return cls(**options)
+
+
+@dataclass
+class PlannedMove:
+    """One file of an OrganizePlan; 'id' is its index in the plan's items."""
+    id: int
+    source: str
+    destination: str
+    action: str
+    category: Optional[str]
+    confidence: Optional[str]
+    method: Optional[str]
+    size: int
+    conflict: bool
+    conflict_reason: Optional[str] = None
+    access_problem: Optional[str] = None
+
+
+@dataclass
+class OrganizePlan:
+    """A dry-run organize plan as typed rows; 'plan' is the plan file apply_plan() takes."""
+    root: str
+    created_at: str
+    moves: List[PlannedMove]
+    unchanged: int
+    left_alone: List[Dict[str, str]]
+    plan: Dict[str, Any] = field(repr=False)
+    plan_id: Optional[int] = None
+
+    @classmethod
+    def from_plan(cls, plan: Dict[str, Any], plan_id: Optional[int] = None) -> 'OrganizePlan':
+        """Rows for a plan from build_plan() or load_plan()."""
+        moves = []
+        for index, item in enumerate(plan['items']):
+            classification = item.get('classification') or {}
+            moves.append(PlannedMove(
+                id=index, source=item['file'], destination=item['destination'], action=item['action'],
+                category=classification.get('category'), confidence=classification.get('confidence'),
+                method=classification.get('method'), size=item.get('size', 0),
+                conflict=bool(item.get('conflict')), conflict_reason=item.get('conflict'),
+                access_problem=(item.get('access') or {}).get('problem')))
+        return cls(root=plan['root'], created_at=plan['created_at'], moves=moves,
+                   unchanged=plan.get('unchanged', 0), left_alone=plan.get('left_alone', []), plan=plan,
+                   plan_id=plan_id)
+
+    @property
+    def conflicts(self) -> int:
+        return sum(1 for move in self.moves if move.conflict)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """JSON-ready dictionary: the rows, their totals and the plan to send back with the approved ids."""
+        return {
+            'plan_id': self.plan_id,
+            'root': self.root,
+            'created_at': self.created_at,
+            'moves': [asdict(move) for move in self.moves],
+            'total_size': sum(move.size for move in self.moves),
+            'conflicts': self.conflicts,
+            'unchanged': self.unchanged,
+            'left_alone': self.left_alone,
+            'plan': self.plan
+        }
+
+
+class OrganizePlanner:
+    """
+    Scans folders, builds organize plans and applies them.
+
+    Attributes:
+        config: Configuration object
+        classifier: File classifier
+        action_manager: Action manager used to preview and apply actions
+    """
+
+    def __init__(self, config, classifier, action_manager):
+        """
+        Initialize planner.
+
+        Args:
+            config: Configuration object
+            classifier: FileClassifier instance
+            action_manager: ActionManager instance
+        """
+        self.config = config
+        self.classifier = classifier
+        self.action_manager = action_manager
+        self._strategies: Dict[str, Any] = {}
+        self.leave_alone = LeaveAloneDetector(config)
+        self.plugins = PluginManager(config)
+        self.junk = JunkScanner(config, self.leave_alone)
+        learner = getattr(classifier, 'corrections', None)
+        self._classifier_learns = isinstance(learner, CorrectionLearner)
+        self.corrections = learner if self._classifier_learns else CorrectionLearner(config, action_manager.db_manager)
+        self.conflicts = ConflictManager(config, action_manager.db_manager, action_manager.journal)
+        self.snapshots = SnapshotManager(config, action_manager.db_manager, action_manager.journal)
+        settings = getattr(classifier, 'settings', None)
+        self.profiles = ProfileManager(config, settings if isinstance(settings, SettingsStore) else None)
+
+    def _files(self, folder: Path, recursive: bool, left_alone: Optional[List[Dict[str, str]]] = None,
+               profile: Optional[ProfileRun] = None) -> List[str]:
+        rules = IgnoreRules.for_folder(str(folder))
+        if profile is not None and profile.profile.exclusions:
+            rules = IgnoreRules(rules.root, rules.rules + profile.profile.exclusion_rules())
+        if recursive:
+            paths = []
+            for dirpath, dirnames, filenames in os.walk(folder):
+                kept = []
+                for name in dirnames:
+                    if name.startswith('.'):
+                        continue
+                    ignored = rules.match(os.path.join(dirpath, name), is_dir=True)
+                    reason = f"Ignored ({ignored})" if ignored else self.leave_alone.reason(Path(dirpath) / name)
+                    if reason is None:
+                        kept.append(name)
+                    elif left_alone is not None:
+                        left_alone.append({'path': os.path.join(dirpath, name), 'reason': reason})
+                dirnames[:] = kept
+                paths.extend(os.path.join(dirpath, f) for f in filenames
+                             if not f.startswith('.') and not rules.match(os.path.join(dirpath, f), is_dir=False))
+        else:
+            paths = [str(p) for p in folder.iterdir() if p.is_file() and not p.name.startswith('.')
+                     and not rules.match(str(p), is_dir=False)]
+        # Split archives and RAW+JPEG pairs are one item each
+        return collapse_raw_pairs(collapse_split_archives(p for p in sorted(paths) if not os.path.islink(p)))
+
+    @staticmethod
+    def _resolve_folder(folder: str) -> Path:
+        path = Path(folder).expanduser().resolve()
+        if not path.is_dir():
+            raise NotADirectoryError(f"Not a folder: {path}")
+        return path
+
+    # ==================== Strategies ====================
+
+    def classify(self, file_path: str, root: Optional[Path] = None, deep: bool = False,
+                 strategy: Optional[str] = None, profile: Optional[ProfileRun] = None) -> Dict[str, Any]:
+        """
+        Classify a file with the strategy that applies to it.
+
+        Args:
+            file_path (str): File to classify
+            root (Path, optional): Folder being organized
+            deep (bool): Use deep AI analysis (classifier only)
+            strategy (str, optional): Strategy for this run; default: the
+                                      profile's, else the folder policy's, else the classifier
+            profile (ProfileRun, optional): Profile of this run (see profiles.run())
+
+        Returns:
+            Dict: Classification result
+        """
+        if profile is None:
+            return self._classify(file_path, root, deep, strategy)
+        ruled = profile.classify(file_path)
+        if ruled is None:
+            tier = profile.profile.tier
+            with self.classifier.using_tier(tier) if tier else nullcontext():
+                ruled = self._classify(file_path, root, deep, strategy or profile.profile.strategy)
+        return profile.place(ruled, file_path)
+
+    def _classify(self, file_path: str, root: Optional[Path], deep: bool,
+                  strategy: Optional[str]) -> Dict[str, Any]:
+        policy = self.config.get_folder_policy(file_path) or {}
+        name = strategy or policy.get('strategy') or DEFAULT_STRATEGY
+        # Learned corrections win over strategies and the AI (the classifier checks them itself)
+        if not deep and not (name == DEFAULT_STRATEGY and self._classifier_learns):
+            learned = self.corrections.suggest(file_path)
+            if learned:
+                return learned
+        if name != DEFAULT_STRATEGY:
+            handler = self._strategy(name)
+            if handler is not None:
+                with timed('classify'):
+                    result = handler.classify(file_path, root, policy)
+                if result is not None:
+                    return result
+        return self.classifier.classify(file_path, deep_analysis=deep)
+
+    def _strategy(self, name: str):
+        if name not in self._strategies:
+            factory = STRATEGIES.get(name)
+            if factory is None:
+                logger.warning(f"Unknown planner strategy {name!r}; using the classifier")
+            self._strategies[name] = factory(self.config) if factory else None
+        return self._strategies[name]
+
+    @staticmethod
+    def check_strategy(strategy: Optional[str]) -> None:
+        """
+        Raises:
+            ValueError: If the strategy name is not known
+        """
+        if strategy and strategy != DEFAULT_STRATEGY and strategy not in STRATEGIES:
+            raise ValueError(f"Unknown strategy {strategy!r} (choose from: "
+                             f"{', '.join([DEFAULT_STRATEGY, *STRATEGIES])})")
+
+    # ==================== Scan ====================
+
+    def scan(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Inventory a folder and classify its files without changing anything.
+
+        Args:
+            folder (str): Folder to scan
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: 'root', 'files', 'total_size', 'categories' and 'extensions' counts
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+        """
+        root = self._resolve_folder(folder)
+        categories, extensions = Counter(), Counter()
+        total_size, count = 0, 0
+        for file_path in self._files(root, recursive):
+            try:
+                total_size += os.path.getsize(file_path)
+            except OSError:
+                continue
+            count += 1
+            extensions[Path(file_path).suffix.lower() or '(none)'] += 1
+            categories[self.classifier.classify(file_path).get('category') or 'Unknown'] += 1
+        return {
+            'root': str(root),
+            'files': count,
+            'total_size': total_size,
+            'categories': dict(categories.most_common()),
+            'extensions': dict(extensions.most_common(20))
+        }
+
+    # ==================== Plans ====================
+
+    def build_plan(self, folder: str, recursive: bool = True, deep: bool = False,
+                   strategy: Optional[str] = None, profile: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Classify a folder into an organize plan.
+
+        Args:
+            folder (str): Folder to plan
+            recursive (bool): Include subfolders
+            deep (bool): Use deep AI analysis
+            strategy (str, optional): Planner strategy for every file (e.g.
+                                      'photos'); default: per folder policy
+            profile (str, optional): Organization profile for this run (see core.profiles)
+
+        Returns:
+            Dict: Plan (see module docstring); files with nothing to do are
+                  counted in 'unchanged', colliding destinations in 'conflicts'
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+            ValueError: If the strategy or profile is not known
+        """
+        run = self.profiles.run(profile) if profile else None
+        self.check_strategy(strategy or (run.profile.strategy if run else None))
+        root = self._resolve_folder(folder)
+        items: List[Dict[str, Any]] = []
+        left_alone: List[Dict[str, str]] = []
+        unchanged = 0
+        usage = getattr(self.classifier, 'usage', None)
+        if usage is not None:
+            usage.start_run(f"Plan for {root}")  # AI tokens this plan uses are totalled under it
+        with span('planner.build_plan', logger, logging.INFO, folder=str(root), deep=deep) as trace, \
+                collect_timings() as timings:
+            with timed('scan'):
+                files = self._files(root, recursive, left_alone, run)
+            for file_path in files:
+                classification = self.classify(file_path, root, deep, strategy, run)
+                preview = self.action_manager.preview_destination(file_path, classification)
+                if preview['action'] not in ('move', 'rename'):
+                    unchanged += 1
+                    continue
+                try:
+                    stat = os.stat(file_path)
+                except OSError as e:
+                    logger.info(f"Not planning {file_path}: it went away while planning ({e})")
+                    continue
+                items.append({
+                    'file': file_path,
+                    'size': stat.st_size,
+                    'modified': stat.st_mtime,
+                    'action': preview['action'],
+                    'destination': preview['new_path'],
+                    'classification': classification,
+                    'conflict': None,
+                    'access': None
+                })
+            trace.record(items=len(items), unchanged=unchanged)
+
+        claimed = Counter(os.path.normcase(item['destination']) for item in items)
+        for item in items:
+            if claimed[os.path.normcase(item['destination'])] > 1:
+                item['conflict'] = 'shared_destination'
+            elif os.path.exists(item['destination']) or self._renamed_around_namesake(item):
+                item['conflict'] = 'destination_exists'
+        access = check_write_access([item['file'] for item in items],
+                                    {item['file']: item['destination'] for item in items})
+        for item, report in zip(items, access['paths']):
+            if not report['ok']:
+                item['access'] = {'problem': report['problem'], 'message': report['message']}
+
+        return {
+            'version': PLAN_VERSION,
+            'kind': 'organize',
+            'root': str(root),
+            'created_at': datetime.now().isoformat(timespec='seconds'),
+            'profile': run.profile.name if run else None,
+            'items': items,
+            'unchanged': unchanged,
+            'conflicts': sum(1 for item in items if item['conflict']),
+            'access_problems': access['problems'],
+            'left_alone': sorted(left_alone, key=lambda entry: entry['path']),
+            'timings': timings.as_dict()
+        }
+
+    def plan_organization(self, options) -> OrganizePlan:
+        """
+        Dry-run an organize and return it as typed rows for review.
+
+        Args:
+            options (PlanOptions | Dict): Folder, recursive, deep, strategy and profile
+
+        Returns:
+            OrganizePlan: One PlannedMove per file that would move or be
+                          renamed, stored under its plan_id
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+            ValueError: If an option, the strategy or the profile is not known
+        """
+        if isinstance(options, dict):
+            options = PlanOptions.from_dict(options)
+        plan = self.build_plan(options.folder, options.recursive, options.deep, options.strategy, options.profile)
+        plan_id = self.action_manager.db_manager.save_plan(plan['root'], plan['kind'], json.dumps(plan))
+        return OrganizePlan.from_plan(plan, plan_id)
+
+    def apply_plan_items(self, plan_id: int, accepted_ids: List[int]) -> Dict[str, Any]:
+        """
+        Apply the accepted moves of a stored plan; the others are logged as skipped.
+
+        Args:
+            plan_id (int): ID from plan_organization()
+            accepted_ids (List[int]): PlannedMove ids to apply
+
+        Returns:
+            Dict: apply_plan() result with 'plan_id'
+
+        Raises:
+            ValueError: If the plan is unknown or already applied, or an id is not in it
+        """
+        stored = self.action_manager.db_manager.get_saved_plan(int(plan_id))
+        if stored is None:
+            raise ValueError(f"No such plan: {plan_id}")
+        if stored['applied_at']:
+            raise ValueError(f"Plan {plan_id} was already applied (operation {stored['operation_id']})")
+        result = self.apply_plan(json.loads(stored['plan']), approved=accepted_ids)
+        if not self.action_manager.dry_run:
+            self.action_manager.db_manager.mark_plan_applied(int(plan_id), result['operation_id'])
+        return {**result, 'plan_id': int(plan_id)}
+
+    def build_cleanup_plan(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        List a folder's junk (see core.junk) as a cleanup plan.
+
+        Applying it moves each item to the trash as one undoable operation.
+
+        Args:
+            folder (str): Folder to clean
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: Plan with kind 'cleanup'; items have action 'delete' and a 'reason'
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+        """
+        with span('planner.build_cleanup_plan', logger, logging.INFO, folder=folder) as trace, \
+                collect_timings() as timings:
+            with timed('scan'):
+                plan = self.junk.build_plan(folder, recursive)
+            trace.record(items=len(plan['items']), size=plan['total_size'])
+        plan['timings'] = timings.as_dict()
+        return plan
+
+    @staticmethod
+    def _renamed_around_namesake(item: Dict[str, Any]) -> bool:
+        """Whether the destination got a counter suffix because its name was taken."""
+        wanted = item['classification'].get('rename') or Path(item['file']).name
+        return Path(item['destination']).name != wanted
+
+    @staticmethod
+    def save_plan(plan: Dict[str, Any], path: str) -> str:
+        """
+        Write a plan file.
+
+        Args:
+            plan (Dict): Plan from build_plan()
+            path (str): Output path
+
+        Returns:
+            str: Path written
+        """
+        out = Path(path).expanduser()
+        out.parent.mkdir(parents=True, exist_ok=True)
+        with open(out, 'w', encoding='utf-8') as f:
+            json.dump(plan, f, indent=2)
+        return str(out)
+
+    @staticmethod
+    def load_plan(path: str) -> Dict[str, Any]:
+        """
+        Read and check a plan file.
+
+        Args:
+            path (str): Plan file
+
+        Returns:
+            Dict: Plan
+
+        Raises:
+            ValueError: If the file is not a plan this version understands
+        """
+        try:
+            with open(Path(path).expanduser(), 'r', encoding='utf-8') as f:
+                plan = json.load(f)
+        except (OSError, json.JSONDecodeError) as e:
+            raise ValueError(f"Cannot read plan file {path}: {e}")
+        if not isinstance(plan, dict) or plan.get('kind') not in PLAN_KINDS:
+            raise ValueError(f"{path} is not an organize or cleanup plan")
+        if plan.get('version') != PLAN_VERSION:
+            raise ValueError(f"Unsupported plan version {plan.get('version')} (expected {PLAN_VERSION})")
+        if not isinstance(plan.get('items'), list):
+            raise ValueError(f"{path} has no items list")
+        return plan
+
+    @staticmethod
+    def _changed_since_planning(item: Dict[str, Any]) -> bool:
+        if item.get('reason') == 'empty_folder':
+            return not is_empty_tree(Path(item['file']))
+        if 'size' not in item or 'modified' not in item:
+            return False  # Hand-written plans carry no fingerprint
+        try:
+            stat = os.stat(item['file'])
+        except OSError:
+            return True
+        return stat.st_size != item['size'] or abs(stat.st_mtime - item['modified']) > 1e-3
+
+    def _corrected(self, item: Dict[str, Any]) -> Dict[str, Any]:
+        """Apply a reviewer's override ({destination, category}) to an item, learning from it."""
+        override = item['override']
+        chosen = self.corrections.destination_for(str(override['destination']))
+        self.corrections.record(item['file'], override['destination'], override.get('category'),
+                                suggested=item['classification'])
+        classification = {**item['classification'], 'suggested_path': chosen['suggested_path'],
+                          'rename': item['classification'].get('rename'), 'method': 'user',
+                          'category': override.get('category') or item['classification'].get('category')}
+        classification.pop('destination_root', None)
+        if chosen['destination_root']:
+            classification['destination_root'] = chosen['destination_root']
+        return {**item, 'classification': classification}
+
+    def _taken_destination(self, item: Dict[str, Any]) -> Optional[str]:
+        """The destination a file asking on conflict would move to, if something else is there."""
+        classification = item['classification']
+        strategy = classification.get('on_conflict') or getattr(self.config, 'conflict_resolution', None)
+        if strategy != 'ask' or item.get('action') == 'delete':
+            return None
+        # 'replace' previews the wanted name itself rather than the next free one
+        wanted = self.action_manager.preview_destination(item['file'], {**classification, 'on_conflict': 'replace'})
+        return wanted['new_path'] if destination_taken(item['file'], wanted['new_path']) else None
+
+    def apply_plan(self, plan: Dict[str, Any], user_approved: bool = True,
+                   approved: Optional[List[int]] = None) -> Dict[str, Any]:
+        """
+        Apply a plan as one journaled operation.
+
+        Files that have disappeared or changed (size or modification time)
+        since planning are skipped.
+
+        Args:
+            plan (Dict): Plan from build_plan() or load_plan()
+            user_approved (bool): Whether the user approved the plan
+            approved (List[int], optional): Ids (item indexes) of the moves
+                to apply; the others are logged as skipped. Default: all
+
+        Returns:
+            Dict: Result with 'success', 'message', 'operation_id',
+                  'snapshot_id' (see core.snapshots; None for small runs),
+                  'applied', 'failed', 'skipped', 'rejected' and 'conflicts'
+                  (Conflict dictionaries, see core.conflicts) lists
+
+        Raises:
+            ValueError: If an approved id is not in the plan
+        """
+        rejected: List[Dict[str, Any]] = []
+        if approved is not None:
+            wanted = sorted(set(int(item_id) for item_id in approved))
+            missing = [item_id for item_id in wanted if not 0 <= item_id < len(plan['items'])]
+            if missing:
+                raise ValueError(f"No such plan item(s): {', '.join(map(str, missing))}")
+            turned_down = [item for index, item in enumerate(plan['items']) if index not in set(wanted)]
+            self._log_rejected(turned_down)
+            rejected = [{'file': item['file'], 'message': 'Not approved'} for item in turned_down]
+            plan = {**plan, 'items': [plan['items'][item_id] for item_id in wanted]}
+        applied, failed, skipped, conflicts = [], [], [], []
+        # Snapshot the folder first so the run can be restored in one step
+        snapshot_id = None
+        if not self.action_manager.dry_run:
+            snapshot_id = self.snapshots.maybe_snapshot(
+                [item['file'] for item in plan['items'] if os.path.exists(item['file'])],
+                reason=f"Before applying the plan for {plan.get('root', '')}")
+        with span('planner.apply_plan', logger, logging.INFO, root=plan.get('root'), items=len(plan['items'])) as trace, \
+                self.action_manager.journal_scope(kind='apply',
+                                                  description=f"Apply plan for {plan.get('root', '')}") as scope:
+            # The run's timings include the planning that came before
+            scope['timings'] = plan.get('timings')
+            for item in plan['items']:
+                if not os.path.exists(item['file']):
+                    skipped.append({'file': item['file'], 'message': 'File no longer exists'})
+                    continue
+                if self._changed_since_planning(item):
+                    skipped.append({'file': item['file'], 'message': 'File changed since the plan was made'})
+                    continue
+                if item.get('override'):
+                    item = self._corrected(item)
+                taken = self._taken_destination(item)
+                if taken:
+                    if self.action_manager.dry_run:
+                        conflicts.append(Conflict.from_paths(None, item['file'], taken, 'destination_exists',
+                                                             plan.get('root')))
+                    else:
+                        conflicts.append(self.conflicts.record(item['file'], taken, 'destination_exists',
+                                                               plan.get('root')))
+                    continue
+                if item.get('action') == 'delete':
+                    result = self.action_manager.delete_file(item['file'], reason=f"Cleanup: {item.get('reason')}")
+                else:
+                    result = self.action_manager.execute(item['file'], item['classification'],
+                                                         user_approved=user_approved)
+                entry = {'file': item['file'], 'new_path': result.get('new_path'), 'message': result.get('message', '')}
+                # Plugin actions run on the organized file; their failures do not fail the item
+                if result.get('success') and result.get('new_path') and not self.action_manager.dry_run:
+                    plugin_actions = self.plugins.run_matching(result['new_path'], item['classification'])
+                    if plugin_actions:
+                        entry['plugin_actions'] = plugin_actions
+                (applied if result.get('success') else failed).append(entry)
+            trace.record(applied=len(applied), failed=len(failed), skipped=len(skipped), conflicts=len(conflicts))
+            if failed:
+                trace.fail(f"{len(failed)} action(s) failed")
+
+        message = f"Applied {len(applied)} of {len(plan['items'])} planned action(s)"
+        if failed:
+            message += f", {len(failed)} failed"
+        if skipped:
+            message += f", {len(skipped)} skipped"
+        if rejected:
+            message += f", {len(rejected)} not approved"
+        if conflicts:
+            message += f", {len(conflicts)} waiting for a conflict resolution"
+            if not self.action_manager.dry_run:
+                self.conflicts.announce(conflicts, plan.get('root'))
+        return {
+            'success': not failed,
+            'message': message,
+            'operation_id': scope.get('operation_id'),
+            'snapshot_id': snapshot_id,
+            'applied': applied,
+            'failed': failed,
+            'skipped': skipped,
+            'rejected': rejected,
+            'conflicts': [conflict.to_dict() for conflict in conflicts]
+        }
+
+    def _log_rejected(self, items: List[Dict[str, Any]]) -> None:
+        """Log the moves a reviewer turned down to the history, as skipped."""
+        if self.action_manager.dry_run:
+            return
+        for item in items:
+            classification = item.get('classification') or {}
+            self.action_manager.db_manager.log_action(
+                Path(item['file']).name, item['file'], item.get('destination'), 'skipped',
+                category=classification.get('category'), user_approved=False, count_stats=False)
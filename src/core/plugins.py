@@ -0,0 +1,451 @@
+"""
+Plugin Actions Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module runs custom actions after a file has been organized, written
+as WebAssembly plugins ("upload invoices to my bookkeeping API",
+"convert HEIC to JPEG"). Each plugin is a folder in `plugins.dir`
+(default data/plugins) with a plugin.json:
+
+    {
+        "name": "bookkeeping",
+        "version": "1.0.0",
+        "module": "bookkeeping.wasm",
+        "capabilities": ["read", "net:api.example-books.com"],
+        "actions": [{"name": "upload", "description": "Send invoices to Example Books",
+                     "match": {"categories": ["Finance"], "extensions": [".pdf"]}}]
+    }
+
+Plugins run in a sandbox with no file system or network of their own.
+They talk to the organiser only through the host functions below, each
+gated by a capability the user has granted (`aifo plugins --grant`):
+
+    aifo.input(ptr, cap) -> len     JSON {action, file, name, extension, size,
+                                    category, classification}; always allowed
+    aifo.log(ptr, len)              write to the organiser's log; always allowed
+    aifo.set_result(ptr, len)       the action's message; always allowed
+    aifo.read(offset: i64, ptr, len) -> n      "read": the organized file only
+    aifo.write_output(name_ptr, name_len, data_ptr, data_len) -> 0
+                                    "write": append to a new file next to it
+    aifo.http_post(url_ptr, url_len, body_ptr, body_len) -> status
+                                    "net:<host>": HTTPS POST to that host only
+
+Denied calls return -1 and other failures -2. An action is the exported
+function of the same name, taking no arguments and returning 0 on
+success. Every run gets a fresh instance with an instruction budget
+(`plugins.fuel`) and a memory cap (`plugins.memory_mb`). Plugins need the
+optional wasmtime package.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import re
+import urllib.error
+import urllib.parse
+import urllib.request
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .file_names import safe_name
+
+logger = logging.getLogger(__name__)
+
+try:
+    import wasmtime  # type: ignore
+    HAS_WASMTIME = True
+except ImportError:
+    wasmtime = None  # type: ignore
+    HAS_WASMTIME = False
+
+
+MANIFEST = 'plugin.json'
+DEFAULT_FUEL = 2_000_000_000
+DEFAULT_MEMORY_MB = 128
+MAX_OUTPUT_BYTES = 512 * 1024 * 1024
+MAX_HTTP_BODY = 16 * 1024 * 1024
+HTTP_TIMEOUT = 30
+DENIED, FAILED = -1, -2
+_NAME = re.compile(r'^[a-z0-9][a-z0-9_-]{0,63}$')
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+
+
+class _NoRedirects(urllib.request.HTTPRedirectHandler):
+    """A redirect could lead a plugin to a host it was not granted."""
+
+    def redirect_request(self, *args, **kwargs):
+        return None
+
+
+_OPENER = urllib.request.build_opener(_NoRedirects)
+
+
+class PluginHost:
+    """
+    What one action run may do: the capability-checked side of the host functions.
+
+    Attributes:
+        plugin (str): Plugin name
+        file (Path): The organized file the action runs on
+        granted (set): Capabilities the user granted this plugin
+        outputs (List[str]): Files the action wrote
+        result (str): The action's message
+    """
+
+    def __init__(self, plugin: str, action: str, file_path: str, classification: Dict[str, Any], granted):
+        self.plugin = plugin
+        self.action = action
+        self.file = Path(file_path)
+        self.classification = classification or {}
+        self.granted = set(granted or [])
+        self.outputs: List[str] = []
+        self.result = ''
+        self._written = 0
+
+    def input(self) -> bytes:
+        """The JSON document describing the run."""
+        try:
+            size = self.file.stat().st_size
+        except OSError:
+            size = None
+        return json.dumps({
+            'action': self.action,
+            'file': str(self.file),
+            'name': self.file.name,
+            'extension': self.file.suffix.lower(),
+            'size': size,
+            'category': self.classification.get('category'),
+            'classification': {k: v for k, v in self.classification.items() if isinstance(v, (str, int, float, bool))}
+        }).encode('utf-8')
+
+    def log(self, data: bytes) -> None:
+        logger.info(f"[plugin {self.plugin}] {data.decode('utf-8', errors='replace')[:2000]}")
+
+    def set_result(self, data: bytes) -> None:
+        self.result = data.decode('utf-8', errors='replace')[:2000]
+
+    def read(self, offset: int, length: int) -> Optional[bytes]:
+        """Bytes of the organized file, or None if not allowed."""
+        if 'read' not in self.granted or offset < 0 or length < 0:
+            return None
+        with open(self.file, 'rb') as f:
+            f.seek(offset)
+            return f.read(length)
+
+    def write_output(self, name: str, data: bytes) -> int:
+        """
+        Append to a new file next to the organized one.
+
+        Returns:
+            int: 0, DENIED (no 'write' grant, or the name would replace an
+                 existing file or leave the folder) or FAILED
+        """
+        if 'write' not in self.granted:
+            return DENIED
+        safe = safe_name(name, '_')
+        target = self.file.parent / safe
+        if not safe or target.resolve().parent != self.file.parent.resolve() or target == self.file:
+            return DENIED
+        if self._written + len(data) > MAX_OUTPUT_BYTES:
+            return FAILED
+        if str(target) not in self.outputs:
+            if target.exists():
+                return DENIED
+            self.outputs.append(str(target))
+        try:
+            with open(target, 'ab') as f:
+                f.write(data)
+        except OSError as e:
+            logger.warning(f"[plugin {self.plugin}] cannot write {target}: {e}")
+            return FAILED
+        self._written += len(data)
+        return 0
+
+    def http_post(self, url: str, body: bytes) -> int:
+        """
+        POST to a host the plugin may reach.
+
+        Returns:
+            int: HTTP status, DENIED (not HTTPS or host not granted) or FAILED
+        """
+        parsed = urllib.parse.urlparse(url)
+        if parsed.scheme != 'https' or not parsed.hostname or f"net:{parsed.hostname}" not in self.granted:
+            return DENIED
+        if len(body) > MAX_HTTP_BODY:
+            return FAILED
+        request = urllib.request.Request(url, data=body, method='POST',
+                                         headers={'Content-Type': 'application/octet-stream',
+                                                  'User-Agent': f"AI-File-Organiser plugin {self.plugin}"})
+        try:
+            with _OPENER.open(request, timeout=HTTP_TIMEOUT) as response:
+                return response.status
+        except urllib.error.HTTPError as e:
+            return e.code
+        except OSError as e:
+            logger.warning(f"[plugin {self.plugin}] POST to {parsed.hostname} failed: {e}")
+            return FAILED
+
+
+class PluginManager:
+    """
+    Finds plugins, tracks what users granted them and runs their actions.
+
+    Attributes:
+        config: Configuration object
+        plugin_dir (Path): Folder holding one folder per plugin
+        enabled (bool): Whether plugin actions run when applying plans
+    """
+
+    def __init__(self, config, plugin_dir: Optional[str] = None):
+        """
+        Initialize plugin manager.
+
+        Args:
+            config: Configuration object (reads `plugin_settings`)
+            plugin_dir (str, optional): Plugin folder (default: data/plugins)
+        """
+        self.config = config
+        settings = getattr(config, 'plugin_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', True))
+        self.plugin_dir = Path(plugin_dir or settings.get('dir') or _APP_ROOT / 'data' / 'plugins').expanduser()
+        granted = settings.get('granted')
+        self.granted: Dict[str, List[str]] = granted if isinstance(granted, dict) else {}
+        self.fuel = int(settings.get('fuel') or DEFAULT_FUEL)
+        self.memory_mb = int(settings.get('memory_mb') or DEFAULT_MEMORY_MB)
+
+    # ==================== Discovery ====================
+
+    def discover(self) -> List[Dict[str, Any]]:
+        """
+        The installed plugins.
+
+        Returns:
+            List[Dict]: One per plugin: 'name', 'version', 'path', 'module',
+                        'capabilities', 'actions', 'granted' (all requested
+                        capabilities granted) and 'error' for broken manifests
+        """
+        plugins = []
+        try:
+            folders = sorted(p for p in self.plugin_dir.iterdir() if p.is_dir())
+        except OSError:
+            return []
+        for folder in folders:
+            manifest = folder / MANIFEST
+            if not manifest.is_file():
+                continue
+            plugin = self._load_manifest(manifest)
+            plugins.append(plugin)
+        return plugins
+
+    def _load_manifest(self, manifest: Path) -> Dict[str, Any]:
+        folder = manifest.parent
+        plugin: Dict[str, Any] = {'name': folder.name, 'path': str(folder), 'actions': [], 'capabilities': [],
+                                  'granted': False, 'error': None}
+        try:
+            with open(manifest, 'r', encoding='utf-8') as f:
+                data = json.load(f)
+        except (OSError, ValueError) as e:
+            plugin['error'] = f"Cannot read {MANIFEST}: {e}"
+            return plugin
+        name = str(data.get('name') or '')
+        module = folder / str(data.get('module') or '')
+        actions = [a for a in data.get('actions') or [] if isinstance(a, dict) and _NAME.match(str(a.get('name', '')))]
+        capabilities = [str(c) for c in data.get('capabilities') or []]
+        if not _NAME.match(name):
+            plugin['error'] = f"Invalid plugin name {name!r}"
+        elif not module.is_file() or module.parent != folder:
+            plugin['error'] = f"Module {data.get('module')!r} not found in the plugin folder"
+        elif not actions:
+            plugin['error'] = "The plugin declares no actions"
+        elif any(c not in ('read', 'write') and not re.match(r'^net:[A-Za-z0-9.-]+$', c) for c in capabilities):
+            plugin['error'] = f"Unknown capability in {capabilities}"
+        plugin.update(name=name or folder.name, version=str(data.get('version') or ''), module=str(module),
+                      actions=actions, capabilities=capabilities)
+        plugin['granted'] = plugin['error'] is None and set(capabilities) <= set(self.granted.get(plugin['name'], []))
+        return plugin
+
+    def get(self, name: str) -> Optional[Dict[str, Any]]:
+        return next((p for p in self.discover() if p['name'] == name), None)
+
+    @staticmethod
+    def matches(action: Dict[str, Any], file_path: str, classification: Dict[str, Any]) -> bool:
+        """Whether an action wants to run for a file (all listed conditions hold)."""
+        match = action.get('match') if isinstance(action.get('match'), dict) else {}
+        extensions = [str(e).lower() for e in match.get('extensions') or []]
+        categories = [str(c).lower() for c in match.get('categories') or []]
+        if not extensions and not categories:
+            return False  # an action that matches everything has to be run explicitly
+        if extensions and Path(file_path).suffix.lower() not in extensions:
+            return False
+        if categories and str((classification or {}).get('category') or '').lower() not in categories:
+            return False
+        return True
+
+    def matching_actions(self, file_path: str, classification: Dict[str, Any]) -> List[str]:
+        """'plugin:action' names that would run for a file after it is organized."""
+        if not self.enabled:
+            return []
+        return [f"{p['name']}:{a['name']}" for p in self.discover() if p['granted']
+                for a in p['actions'] if self.matches(a, file_path, classification)]
+
+    # ==================== Grants ====================
+
+    def set_grant(self, name: str, grant: bool) -> Dict[str, Any]:
+        """
+        Grant a plugin every capability it asks for, or revoke them.
+
+        Args:
+            name (str): Plugin name
+            grant (bool): Grant (True) or revoke (False)
+
+        Returns:
+            Dict: 'success', 'message' and the 'capabilities' now granted
+        """
+        plugin = self.get(name)
+        if plugin is None:
+            return {'success': False, 'message': f"No plugin named {name!r} in {self.plugin_dir}"}
+        if grant and plugin['error']:
+            return {'success': False, 'message': f"{name}: {plugin['error']}"}
+        granted = dict(self.granted)
+        if grant:
+            granted[name] = plugin['capabilities']
+        else:
+            granted.pop(name, None)
+        settings = getattr(self.config, 'plugin_settings', None)
+        settings = dict(settings) if isinstance(settings, dict) else {}
+        settings['granted'] = granted
+        try:
+            self.config.update('plugins', settings)
+            self.config.save()
+        except OSError as e:
+            return {'success': False, 'message': f"Cannot save config: {e}"}
+        self.granted = granted
+        if grant:
+            what = ', '.join(plugin['capabilities']) or 'no special capabilities'
+            return {'success': True, 'message': f"{name} may now run ({what})", 'capabilities': plugin['capabilities']}
+        return {'success': True, 'message': f"{name} will no longer run", 'capabilities': []}
+
+    # ==================== Running ====================
+
+    def run_action(self, name: str, action: str, file_path: str,
+                   classification: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Run one plugin action on a file.
+
+        Args:
+            name (str): Plugin name
+            action (str): Action name
+            file_path (str): File (already organized)
+            classification (Dict, optional): Its classification
+
+        Returns:
+            Dict: 'success', 'message', 'action' ('plugin:action') and 'outputs'
+                  (files the action wrote)
+        """
+        label = f"{name}:{action}"
+        plugin = self.get(name)
+        if plugin is None:
+            return {'success': False, 'message': f"No plugin named {name!r}", 'action': label, 'outputs': []}
+        if plugin['error']:
+            return {'success': False, 'message': plugin['error'], 'action': label, 'outputs': []}
+        if not plugin['granted']:
+            return {'success': False, 'message': f"{name} has not been granted {', '.join(plugin['capabilities'])}"
+                    f" (aifo plugins --grant {name})", 'action': label, 'outputs': []}
+        if action not in {a['name'] for a in plugin['actions']}:
+            return {'success': False, 'message': f"{name} has no action {action!r}", 'action': label, 'outputs': []}
+        if not HAS_WASMTIME:
+            return {'success': False, 'message': "Plugins need the wasmtime package (pip install wasmtime)",
+                    'action': label, 'outputs': []}
+
+        host = PluginHost(name, action, file_path, classification or {}, self.granted.get(name, []))
+        try:
+            code = self._execute(plugin['module'], action, host)
+        except Exception as e:  # traps, fuel exhaustion and link errors are all wasmtime errors
+            logger.warning(f"Plugin action {label} on {file_path} failed: {e}")
+            return {'success': False, 'message': f"{label} failed: {e}", 'action': label, 'outputs': host.outputs}
+        ok = code == 0
+        message = host.result or (f"{label} done" if ok else f"{label} returned {code}")
+        (logger.info if ok else logger.warning)(f"Plugin action {label} on {file_path}: {message}")
+        return {'success': ok, 'message': message, 'action': label, 'outputs': host.outputs}
+
+    def run_matching(self, file_path: str, classification: Dict[str, Any]) -> List[Dict[str, Any]]:
+        """Run every granted action that matches a freshly organized file."""
+        results = []
+        for label in self.matching_actions(file_path, classification):
+            name, action = label.split(':', 1)
+            results.append(self.run_action(name, action, file_path, classification))
+        return results
+
+    def _execute(self, module_path: str, action: str, host: PluginHost) -> int:
+        """Instantiate the module afresh in a limited store and call the action's export."""
+        engine_config = wasmtime.Config()
+        engine_config.consume_fuel = True
+        engine = wasmtime.Engine(engine_config)
+        store = wasmtime.Store(engine)
+        store.set_fuel(self.fuel)
+        store.set_limits(memory_size=self.memory_mb * 1024 * 1024)
+        module = wasmtime.Module.from_file(engine, module_path)
+
+        linker = wasmtime.Linker(engine)
+        i32, i64 = wasmtime.ValType.i32(), wasmtime.ValType.i64()
+
+        def memory(caller):
+            return caller['memory']
+
+        def read_bytes(caller, ptr, length):
+            return bytes(memory(caller).read(caller, ptr, ptr + length))
+
+        def write_bytes(caller, ptr, data):
+            memory(caller).write(caller, data, ptr)
+
+        def input_(caller, ptr, cap):
+            data = host.input()
+            write_bytes(caller, ptr, data[:cap])
+            return len(data)
+
+        def log(caller, ptr, length):
+            host.log(read_bytes(caller, ptr, length))
+
+        def set_result(caller, ptr, length):
+            host.set_result(read_bytes(caller, ptr, length))
+
+        def read(caller, offset, ptr, length):
+            try:
+                data = host.read(offset, length)
+            except OSError:
+                return FAILED
+            if data is None:
+                return DENIED
+            write_bytes(caller, ptr, data)
+            return len(data)
+
+        def write_output(caller, name_ptr, name_len, data_ptr, data_len):
+            name = read_bytes(caller, name_ptr, name_len).decode('utf-8', errors='replace')
+            return host.write_output(name, read_bytes(caller, data_ptr, data_len))
+
+        def http_post(caller, url_ptr, url_len, body_ptr, body_len):
+            url = read_bytes(caller, url_ptr, url_len).decode('utf-8', errors='replace')
+            return host.http_post(url, read_bytes(caller, body_ptr, body_len))
+
+        for name, params, results, fn in (
+                ('input', [i32, i32], [i32], input_),
+                ('log', [i32, i32], [], log),
+                ('set_result', [i32, i32], [], set_result),
+                ('read', [i64, i32, i32], [i32], read),
+                ('write_output', [i32, i32, i32, i32], [i32], write_output),
+                ('http_post', [i32, i32, i32, i32], [i32], http_post)):
+            linker.define_func('aifo', name, wasmtime.FuncType(params, results), fn, access_caller=True)
+
+        instance = linker.instantiate(store, module)
+        run = instance.exports(store).get(action)
+        if run is None:
+            raise ValueError(f"the module does not export {action!r}")
+        return int(run(store) or 0)
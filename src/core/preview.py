@@ -0,0 +1,261 @@
+"""
+File Preview Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module tells the inspector panel what a file is before a move is
+approved, reading no more than it must:
+
+- Text: the first max_bytes, decoded with the detected encoding (byte
+  order mark, then UTF-8, then charset_normalizer when installed and
+  there is enough text, else Windows-1252)
+- Images: a small base64 thumbnail (Pillow when installed, else the
+  preview the camera stored in the EXIF, else the image itself when it
+  is small)
+- PDF: version, title, author, page count and whether it is encrypted
+- Office (docx, xlsx, pptx, odt, ods, odp): title, author, pages, sheets
+  or slides and the start of the text
+- Anything else: its size and type, marked 'binary'
+
+    {"path", "name", "size", "mime_type", "kind", "text", "encoding",
+     "truncated", "thumbnail": {"mime_type", "data", "source"} | null,
+     "structure": {...} | null, "message"}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import base64
+import codecs
+import html
+import logging
+import mimetypes
+import re
+import zipfile
+from pathlib import Path
+from typing import Dict, Any, Optional, Tuple
+
+from .extract import extract_metadata
+from .thumbnails import DEFAULT_SIZE, render_thumbnail
+
+try:
+    from charset_normalizer import from_bytes
+    CHARSET_SUPPORT = True
+except ImportError:
+    CHARSET_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_MAX_BYTES = 64 * 1024
+MAX_PREVIEW_BYTES = 1024 * 1024
+MAX_XML_BYTES = 4 * 1024 * 1024  # Read of one part of an Office file (guards against zip bombs)
+EXCERPT_CHARS = 2000
+CHARSET_MIN_BYTES = 256  # Detection needs some text to go on
+
+TEXT_EXTENSIONS = {'.txt', '.md', '.rst', '.csv', '.tsv', '.log', '.json', '.xml', '.yaml', '.yml', '.ini',
+                   '.cfg', '.toml', '.html', '.htm', '.css', '.js', '.ts', '.py', '.rs', '.go', '.java',
+                   '.c', '.h', '.cpp', '.sh', '.bat', '.ps1', '.sql', '.srt', '.vtt', '.tex'}
+OFFICE_EXTENSIONS = {'.docx', '.xlsx', '.pptx', '.odt', '.ods', '.odp'}
+_BOMS = ((codecs.BOM_UTF32_LE, 'utf-32'), (codecs.BOM_UTF32_BE, 'utf-32'), (codecs.BOM_UTF8, 'utf-8-sig'),
+         (codecs.BOM_UTF16_LE, 'utf-16'), (codecs.BOM_UTF16_BE, 'utf-16'))
+
+
+def get_file_preview(path: str, max_bytes: int = DEFAULT_MAX_BYTES) -> Dict[str, Any]:
+    """
+    Preview a file for the inspector panel.
+
+    Args:
+        path (str): File to preview
+        max_bytes (int): Most bytes of text to return (up to MAX_PREVIEW_BYTES)
+
+    Returns:
+        Dict: See the module documentation
+
+    Raises:
+        FileNotFoundError: If the file does not exist
+        IsADirectoryError: If the path is a folder
+    """
+    file_path = Path(path).expanduser()
+    if file_path.is_dir():
+        raise IsADirectoryError(f"Not a file: {file_path}")
+    size = file_path.stat().st_size
+    max_bytes = max(1, min(int(max_bytes or DEFAULT_MAX_BYTES), MAX_PREVIEW_BYTES))
+    extension = file_path.suffix.lower()
+    mime_type = mimetypes.guess_type(file_path.name)[0] or 'application/octet-stream'
+    preview: Dict[str, Any] = {'path': str(file_path), 'name': file_path.name, 'size': size,
+                               'mime_type': mime_type, 'kind': 'binary', 'text': None, 'encoding': None,
+                               'truncated': False, 'thumbnail': None, 'structure': None, 'message': ''}
+    try:
+        if mime_type.startswith('image/') and extension != '.svg':
+            preview['kind'] = 'image'
+            preview['thumbnail'] = _thumbnail(file_path)
+            preview['structure'] = _photo_structure(file_path)
+            if preview['thumbnail'] is None:
+                preview['message'] = 'No thumbnail available for this image'
+        elif extension == '.pdf':
+            preview['kind'] = 'pdf'
+            preview['structure'] = _pdf_structure(file_path)
+        elif extension in OFFICE_EXTENSIONS:
+            preview['kind'] = 'office'
+            preview['structure'] = _office_structure(file_path)
+        else:
+            with open(file_path, 'rb') as f:
+                data = f.read(max_bytes)
+            text, encoding = decode_text(data, likely_text=extension in TEXT_EXTENSIONS
+                                         or mime_type.startswith('text/') or extension == '.svg',
+                                         complete=size <= len(data))
+            if text is not None:
+                preview.update(kind='text', text=text, encoding=encoding, truncated=size > len(data))
+    except (OSError, zipfile.BadZipFile, ValueError) as e:
+        logger.debug(f"Preview of {file_path} failed: {e}")
+        preview['message'] = f"Could not read the file: {getattr(e, 'strerror', None) or e}"
+    return preview
+
+
+def decode_text(data: bytes, likely_text: bool = False,
+                complete: bool = False) -> Tuple[Optional[str], Optional[str]]:
+    """
+    Decode the start of a file, or (None, None) if it is not text.
+
+    Args:
+        data (bytes): First bytes of the file
+        likely_text (bool): The file's type says text, so doubtful bytes are decoded anyway
+        complete (bool): data is the whole file (else it may end in the middle of a character)
+
+    Returns:
+        Tuple: (text, encoding name)
+    """
+    for bom, encoding in _BOMS:
+        if data.startswith(bom):
+            return _decode_prefix(data, encoding, complete), encoding
+    if b'\x00' in data[:8192] and not likely_text:
+        return None, None
+    try:
+        return _decode_prefix(data, 'utf-8', complete, strict=True), 'utf-8'
+    except UnicodeDecodeError:
+        pass
+    if CHARSET_SUPPORT and len(data) >= CHARSET_MIN_BYTES:
+        best = from_bytes(data).best()
+        # Wide encodings without a byte order mark are guesses too wild for a preview
+        if best is not None and not best.encoding.startswith(('utf_16', 'utf_32')):
+            return str(best), best.encoding
+    if not likely_text and _control_ratio(data) > 0.1:
+        return None, None
+    return data.decode('cp1252', errors='replace'), 'cp1252'
+
+
+def _decode_prefix(data: bytes, encoding: str, complete: bool, strict: bool = False) -> str:
+    """Decode, dropping a character cut off at the end of an incomplete read."""
+    decoder = codecs.getincrementaldecoder(encoding)('strict' if strict else 'replace')
+    return decoder.decode(data, final=complete)
+
+
+def _control_ratio(data: bytes) -> float:
+    if not data:
+        return 0.0
+    controls = sum(1 for byte in data if byte < 0x20 and byte not in (0x09, 0x0A, 0x0D, 0x0C))
+    return controls / len(data)
+
+
+def _thumbnail(path: Path) -> Optional[Dict[str, Any]]:
+    rendered = render_thumbnail(str(path), DEFAULT_SIZE)
+    return _encoded(*rendered) if rendered else None
+
+
+def _encoded(data: bytes, mime_type: str, source: str) -> Dict[str, Any]:
+    return {'mime_type': mime_type, 'data': base64.b64encode(data).decode('ascii'), 'source': source}
+
+
+def _photo_structure(path: Path) -> Optional[Dict[str, Any]]:
+    meta = extract_metadata(str(path))
+    structure = {name: getattr(meta, name) for name in ('date_taken', 'camera', 'latitude', 'longitude')
+                 if getattr(meta, name) is not None}
+    return structure or None
+
+
+def _pdf_structure(path: Path) -> Dict[str, Any]:
+    with open(path, 'rb') as f:
+        head = f.read(1024)
+        f.seek(0, 2)
+        f.seek(max(0, f.tell() - 64 * 1024))
+        tail = f.read()
+    version = re.match(rb'%PDF-(\d\.\d)', head)
+    meta = extract_metadata(str(path))
+    return {'format': 'pdf', 'version': version.group(1).decode() if version else None,
+            'title': meta.title, 'author': meta.author, 'pages': meta.page_count,
+            'encrypted': b'/Encrypt' in tail or b'/Encrypt' in head}
+
+
+def _office_structure(path: Path) -> Dict[str, Any]:
+    extension = path.suffix.lower()
+    structure: Dict[str, Any] = {'format': extension.lstrip('.'), 'title': None, 'author': None}
+    with zipfile.ZipFile(path) as archive:
+        names = set(archive.namelist())
+
+        def part(name: str) -> str:
+            if name not in names:
+                return ''
+            with archive.open(name) as f:
+                return f.read(MAX_XML_BYTES).decode('utf-8', errors='replace')
+
+        if extension in ('.docx', '.xlsx', '.pptx'):
+            core, app = part('docProps/core.xml'), part('docProps/app.xml')
+            structure['title'] = _xml_value(core, 'dc:title')
+            structure['author'] = _xml_value(core, 'dc:creator')
+            if extension == '.docx':
+                structure['pages'] = _int(_xml_value(app, 'Pages'))
+                text = _xml_text(part('word/document.xml'), 'w:t', 'w:p')
+            elif extension == '.xlsx':
+                structure['sheets'] = [html.unescape(name) for name in
+                                       re.findall(r'<sheet\b[^>]*\bname="([^"]*)"', part('xl/workbook.xml'))]
+                text = ' '.join(_xml_text(part('xl/sharedStrings.xml'), 't', 'si').split('\n'))
+            else:
+                slides = sorted((n for n in names if re.fullmatch(r'ppt/slides/slide\d+\.xml', n)),
+                                key=lambda n: int(re.search(r'(\d+)\.xml$', n).group(1)))
+                structure['slides'] = len(slides)
+                text = _xml_text(part(slides[0]), 'a:t', 'a:p') if slides else ''
+        else:
+            meta = part('meta.xml')
+            structure['title'] = _xml_value(meta, 'dc:title')
+            structure['author'] = _xml_value(meta, 'meta:initial-creator') or _xml_value(meta, 'dc:creator')
+            statistic = re.search(r'<meta:document-statistic\b([^>]*)>', meta)
+            if statistic:
+                for name, key in (('page-count', 'pages'), ('table-count', 'sheets')):
+                    value = re.search(rf'meta:{name}="(\d+)"', statistic.group(1))
+                    if value:
+                        structure[key] = int(value.group(1))
+            text = _xml_text(part('content.xml'), None, 'text:p')
+    structure['text'] = text[:EXCERPT_CHARS]
+    return structure
+
+
+def _xml_value(xml: str, tag: str) -> Optional[str]:
+    match = re.search(rf'<{tag}\b[^>]*>([^<]*)</{tag}>', xml)
+    if not match:
+        return None
+    return html.unescape(match.group(1)).strip() or None
+
+
+def _xml_text(xml: str, run_tag: Optional[str], paragraph_tag: str) -> str:
+    """Text of an Office XML part, one line per paragraph (regex, so a truncated part still yields text)."""
+    lines = []
+    for paragraph in re.findall(rf'<{paragraph_tag}\b[^>]*>(.*?)</{paragraph_tag}>', xml, re.S):
+        if run_tag:
+            runs = re.findall(rf'<{run_tag}\b[^>]*>([^<]*)</{run_tag}>', paragraph)
+        else:
+            runs = re.findall(r'>([^<]+)<', f'>{paragraph}<')
+        line = html.unescape(''.join(runs)).strip()
+        if line:
+            lines.append(line)
+        if sum(len(existing) for existing in lines) > EXCERPT_CHARS:
+            break
+    return '\n'.join(lines)
+
+
+def _int(value: Optional[str]) -> Optional[int]:
+    return int(value) if value and value.isdigit() else None
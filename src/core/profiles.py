@@ -0,0 +1,265 @@
+"""
+Profiles Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps organization profiles: named ways of organizing that a
+run can pick (e.g. "work", "personal", "photos"), so a photo library and a
+folder of invoices can be organized very differently. Profiles are kept in
+the "profiles" list of the user settings (see core.settings):
+
+    {"name": "photos", "description": "Camera imports",
+     "target_root": "~/Pictures/Library",
+     "taxonomy": {"Images": "{year}/{month}", "Videos": "Clips/{year}"},
+     "rules": [{"name": "Raw", "extensions": ["cr2", "nef"], "destination": "Raw/{year}"}],
+     "tier": "rules", "exclusions": ["*.xmp", "Exports/"], "strategy": "photos"}
+
+Everything but the name is optional; what a profile leaves out comes from
+the user's settings as usual. For a run with a profile:
+
+- target_root: files go under it instead of base_destination
+- taxonomy: where each category lands, as a folder template (see
+  core.rules for the placeholders), relative to the target root or absolute
+- rules: tried before the user's own rules (same format, see core.rules)
+- tier: the AI tier ("rules", "local" or "cloud") instead of default_tier
+- exclusions: ignore rules added to the usual ones (see core.ignore_rules)
+- strategy: the planner strategy, unless the run names one (see core.plans)
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .ignore_rules import IgnoreRule, parse_rule, parse_rules
+from .rules import MODEL_NAME, UserRules, render_template, unknown_placeholders, validate_rule
+from .settings import TIERS, SettingsStore
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+PROFILE_KEYS = {'name', 'description', 'target_root', 'taxonomy', 'rules', 'tier', 'exclusions', 'strategy'}
+KEPT_PLACEMENTS = (MODEL_NAME, 'learned', 'user')  # Classification methods the taxonomy does not re-place
+
+
+@dataclass
+class Profile:
+    """One organization profile (see the module docstring)."""
+    name: str
+    description: str = ''
+    target_root: Optional[str] = None
+    taxonomy: Dict[str, str] = field(default_factory=dict)
+    rules: List[Dict[str, Any]] = field(default_factory=list)
+    tier: Optional[str] = None
+    exclusions: List[str] = field(default_factory=list)
+    strategy: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, data: Any) -> 'Profile':
+        """
+        A profile from its settings entry, checked.
+
+        Raises:
+            ValueError: Naming what is wrong with the profile
+        """
+        if not isinstance(data, dict):
+            raise ValueError("A profile must be an object")
+        unknown = sorted(set(data) - PROFILE_KEYS)
+        if unknown:
+            raise ValueError(f"Unknown profile key(s): {', '.join(unknown)}")
+        name = data.get('name')
+        if not isinstance(name, str) or not name.strip() or any(c in name for c in '/\\'):
+            raise ValueError(f"Invalid profile name: {name!r}")
+        name = name.strip()
+
+        target_root = data.get('target_root') or None
+        if target_root is not None and (not isinstance(target_root, str)
+                                        or not Path(target_root).expanduser().is_absolute()):
+            raise ValueError(f"Profile '{name}': 'target_root' must be an absolute folder")
+        taxonomy = data.get('taxonomy') or {}
+        if not isinstance(taxonomy, dict) or not all(isinstance(category, str) and category and
+                                                     isinstance(template, str) and template.strip()
+                                                     for category, template in taxonomy.items()):
+            raise ValueError(f"Profile '{name}': 'taxonomy' must map categories to folder templates")
+        for category, template in taxonomy.items():
+            if unknown_placeholders(template):
+                raise ValueError(f"Profile '{name}': unknown placeholder(s) in the '{category}' folder: "
+                                 f"{', '.join('{' + u + '}' for u in unknown_placeholders(template))}")
+            if '..' in Path(template).parts:
+                raise ValueError(f"Profile '{name}': the '{category}' folder cannot contain '..'")
+        rules = data.get('rules') or []
+        if not isinstance(rules, list):
+            raise ValueError(f"Profile '{name}': 'rules' must be a list of rule objects")
+        try:
+            rules = [validate_rule(rule) for rule in rules]
+        except ValueError as e:
+            raise ValueError(f"Profile '{name}': {e}")
+        tier = data.get('tier') or None
+        if tier is not None and tier not in TIERS:
+            raise ValueError(f"Profile '{name}': 'tier' must be one of: {', '.join(TIERS)}")
+        exclusions = data.get('exclusions') or []
+        if not isinstance(exclusions, list) or not all(isinstance(pattern, str) and pattern for pattern in exclusions):
+            raise ValueError(f"Profile '{name}': 'exclusions' must be a list of patterns")
+        for pattern in exclusions:
+            try:
+                parse_rule(pattern)
+            except ValueError as e:
+                raise ValueError(f"Profile '{name}': {e}")
+        strategy = data.get('strategy') or None
+        if strategy is not None and not isinstance(strategy, str):
+            raise ValueError(f"Profile '{name}': 'strategy' must be a strategy name")
+        return cls(name=name, description=str(data.get('description') or ''), target_root=target_root,
+                   taxonomy=dict(taxonomy), rules=rules, tier=tier, exclusions=list(exclusions),
+                   strategy=strategy)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return asdict(self)
+
+    def exclusion_rules(self) -> List[IgnoreRule]:
+        """The profile's exclusions as ignore rules."""
+        return parse_rules(self.exclusions, f"profile {self.name}")
+
+
+class _ProfileRuleSource:
+    """What UserRules reads from a settings store: the profile's rules, and its taxonomy over the templates."""
+
+    def __init__(self, profile: Profile, store: SettingsStore):
+        self.profile = profile
+        self.store = store
+        self.path = store.path
+
+    def load(self) -> Dict[str, Any]:
+        templates = self.store.get('folder_templates') or {}
+        return {'rules': self.profile.rules, 'folder_templates': {**templates, **self.profile.taxonomy}}
+
+
+class ProfileRun:
+    """A profile applied to a run: its rules classify first, then files are placed by its taxonomy and root."""
+
+    def __init__(self, profile: Profile, config, store: SettingsStore):
+        self.profile = profile
+        self.rules = UserRules(config, _ProfileRuleSource(profile, store)) if profile.rules else None
+
+    def classify(self, file_path: str) -> Optional[Dict[str, Any]]:
+        """The first of the profile's rules that matches, or None."""
+        return self.rules.classify(file_path) if self.rules is not None else None
+
+    def place(self, classification: Dict[str, Any], file_path: str) -> Dict[str, Any]:
+        """
+        Set where a classified file goes under this profile.
+
+        Args:
+            classification (Dict): Classification result
+            file_path (str): File it is for
+
+        Returns:
+            Dict: The classification, with the taxonomy's folder and the target root applied
+        """
+        placed = dict(classification)
+        template = self.profile.taxonomy.get(classification.get('category') or '')
+        # Rules, learned corrections and strategies that chose a root already say exactly where files go
+        decided = classification.get('method') in KEPT_PLACEMENTS or classification.get('destination_root')
+        if template and classification.get('suggested_path') and not decided:
+            folder = render_template(template, Path(file_path))
+            target = Path(folder).expanduser()
+            if target.is_absolute():
+                placed['destination_root'], placed['suggested_path'] = str(target.parent), target.name + '/'
+            else:
+                placed['suggested_path'] = folder + '/'
+        if self.profile.target_root and placed.get('suggested_path') and not placed.get('destination_root'):
+            placed['destination_root'] = str(Path(self.profile.target_root).expanduser())
+        placed['profile'] = self.profile.name
+        return placed
+
+
+class ProfileManager:
+    """
+    Lists, saves and removes profiles in the user settings, and prepares them for runs.
+
+    Attributes:
+        config: Configuration object
+        store (SettingsStore): Settings holding the "profiles" list
+    """
+
+    def __init__(self, config, store: Optional[SettingsStore] = None):
+        """
+        Initialize profile manager.
+
+        Args:
+            config: Configuration object
+            store (SettingsStore, optional): Settings holding the profiles; default: the user's
+        """
+        self.config = config
+        self.store = store or SettingsStore()
+
+    def _profiles(self) -> List[Profile]:
+        profiles = []
+        for entry in self.store.get('profiles') or []:
+            try:
+                profiles.append(Profile.from_dict(entry))
+            except ValueError as e:
+                logger.warning(f"Skipping invalid profile: {e}")
+        return profiles
+
+    def list_profiles(self) -> List[Dict[str, Any]]:
+        """The profiles, in the order they were added."""
+        return [profile.to_dict() for profile in self._profiles()]
+
+    def get_profile(self, name: str) -> Optional[Profile]:
+        """A profile by name (case-insensitive), or None."""
+        wanted = str(name or '').strip().lower()
+        return next((profile for profile in self._profiles() if profile.name.lower() == wanted), None)
+
+    def save_profile(self, profile: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Add a profile, or replace the profile with the same name.
+
+        Args:
+            profile (Dict): Profile (see the module docstring)
+
+        Returns:
+            Dict: 'success', 'message' and the saved 'profile'
+        """
+        try:
+            saved = Profile.from_dict(profile)
+        except ValueError as e:
+            return {'success': False, 'message': str(e)}
+        existing = self._profiles()
+        profiles = [other.to_dict() for other in existing if other.name.lower() != saved.name.lower()]
+        replaced = len(profiles) < len(existing)
+        try:
+            self.store.update_settings({'profiles': profiles + [saved.to_dict()]})
+        except ConfigurationError as e:
+            return {'success': False, 'message': str(e)}
+        message = f"{'Replaced' if replaced else 'Saved'} profile '{saved.name}'"
+        return {'success': True, 'message': message, 'profile': saved.to_dict()}
+
+    def delete_profile(self, name: str) -> Dict[str, Any]:
+        """Remove the profile with this name."""
+        profiles = self._profiles()
+        kept = [profile.to_dict() for profile in profiles if profile.name.lower() != str(name).strip().lower()]
+        if len(kept) == len(profiles):
+            return {'success': False, 'message': f"No profile named '{name}'"}
+        self.store.update_settings({'profiles': kept})
+        return {'success': True, 'message': f"Removed profile '{name}'"}
+
+    def run(self, name: str) -> ProfileRun:
+        """
+        A profile ready to apply to a run.
+
+        Raises:
+            ValueError: If there is no such profile
+        """
+        profile = self.get_profile(name)
+        if profile is None:
+            known = ', '.join(p.name for p in self._profiles()) or 'none saved'
+            raise ValueError(f"No profile named {name!r} ({known})")
+        return ProfileRun(profile, self.config, self.store)
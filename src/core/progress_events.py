@@ -0,0 +1,193 @@
+"""
+Progress Events Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module streams the progress of an organize run while it happens, so a
+front end can show a live progress bar for a large folder instead of
+waiting for the process to exit and reading its output at the end.
+
+`aifo organize --events` prints one JSON object per line on stdout (human
+output goes to stderr):
+
+    {"event": "organize://progress", "payload": {"phase", "done", "total", "file"}}
+    {"event": "organize://file-done", "payload": {"file", "status", "destination", "message"}}
+    {"event": "organize://error", "payload": {"file", "message"}}
+
+Phases are 'scanning', 'classifying', 'organizing' and 'done' (whose
+payload also holds the run summary). stream_organize() spawns that command
+and hands each event to a callback as soon as its line arrives. A run sent
+SIGTERM finishes the file it is moving and ends with summary 'cancelled'.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import subprocess
+import threading
+from typing import Callable, Dict, Any, IO, List, Optional
+
+from .interpreter import resolve_app_root, resolve_python
+from .settings import child_environment
+
+logger = logging.getLogger(__name__)
+
+
+EVENT_PROGRESS = 'organize://progress'
+EVENT_FILE_DONE = 'organize://file-done'
+EVENT_ERROR = 'organize://error'
+EVENTS = (EVENT_PROGRESS, EVENT_FILE_DONE, EVENT_ERROR)
+
+# Seconds a cancelled child gets to finish its current file before it is killed
+CANCEL_GRACE = 10
+
+EventCallback = Callable[[str, Dict[str, Any]], None]
+
+
+
+def event_line(event: str, payload: Dict[str, Any]) -> str:
+    """One event as a JSON line (without the newline)."""
+    return json.dumps({'event': event, 'payload': payload}, default=str, ensure_ascii=False)
+
+
+def parse_event_line(line: str) -> Optional[Dict[str, Any]]:
+    """
+    Read one line of `--events` output.
+
+    Args:
+        line (str): A line of the child's stdout
+
+    Returns:
+        Dict or None: {'event', 'payload'}, or None for anything that is not an event
+    """
+    line = line.strip()
+    if not line.startswith('{'):
+        return None
+    try:
+        record = json.loads(line)
+    except ValueError:
+        return None
+    if not isinstance(record, dict) or record.get('event') not in EVENTS:
+        return None
+    payload = record.get('payload')
+    return {'event': record['event'], 'payload': payload if isinstance(payload, dict) else {}}
+
+
+class EventWriter:
+    """
+    Writes events as JSON lines, flushing each one so readers see it at once.
+
+    Calling the writer is an EventCallback, so it can be passed wherever a
+    run takes an `on_event` callback.
+    """
+
+    def __init__(self, stream: IO[str]):
+        """
+        Initialize writer.
+
+        Args:
+            stream: Text stream to write to (the real stdout)
+        """
+        self.stream = stream
+        self._lock = threading.Lock()
+
+    def __call__(self, event: str, payload: Dict[str, Any]) -> None:
+        with self._lock:
+            try:
+                self.stream.write(event_line(event, payload) + '\n')
+                self.stream.flush()
+            except (OSError, ValueError):  # the reader went away; the run goes on
+                pass
+
+
+def organize_command(folder: str, preview: bool = False, deep: bool = False,
+                     strategy: Optional[str] = None, python: Optional[str] = None) -> List[str]:
+    """The command line of a streaming organize run (it never waits for confirmation)."""
+    command = [python or resolve_python(), '-m', 'src.cli.commands', 'organize', str(folder), '--events', '--auto']
+    if preview:
+        command.append('--preview')
+    if deep:
+        command.append('--deep')
+    if strategy:
+        command += ['--strategy', strategy]
+    return command
+
+
+def stream_organize(folder: str, on_event: EventCallback, preview: bool = False, deep: bool = False,
+                    strategy: Optional[str] = None, command: Optional[List[str]] = None,
+                    cancel: Optional[threading.Event] = None) -> Dict[str, Any]:
+    """
+    Organize a folder in a child process, passing on its events as they arrive.
+
+    Args:
+        folder (str): Folder to organize
+        on_event (Callable): Called as (event, payload) for every event line
+        preview (bool): Only show what would be moved
+        deep (bool): Use deep AI analysis
+        strategy (str, optional): Planner strategy for every file
+        command (List[str], optional): Command to run instead (for tests)
+        cancel (threading.Event, optional): Set to stop the child (it gets
+                                            SIGTERM and CANCEL_GRACE seconds
+                                            to finish the file it is moving)
+
+    Returns:
+        Dict: 'success', 'message', 'exit_code', 'summary' (the 'done'
+              payload, None if the run ended early) and 'events' count
+    """
+    command = command or organize_command(folder, preview, deep, strategy)
+    try:
+        child = subprocess.Popen(command, cwd=str(resolve_app_root()), stdin=subprocess.DEVNULL, stdout=subprocess.PIPE,
+                                 stderr=subprocess.PIPE, text=True, encoding='utf-8', errors='replace', bufsize=1,
+                                 env=child_environment())
+    except OSError as e:
+        return {'success': False, 'message': f"Could not start the organize run: {e}",
+                'exit_code': None, 'summary': None, 'events': 0}
+
+    # Drained separately so a chatty stderr cannot fill its pipe and stall the child
+    stderr: List[str] = []
+    drain = threading.Thread(target=lambda: stderr.extend(child.stderr), daemon=True)
+    drain.start()
+    if cancel is not None:
+        def stop_on_cancel() -> None:
+            while child.poll() is None:
+                if cancel.wait(0.2):
+                    child.terminate()
+                    try:
+                        child.wait(CANCEL_GRACE)
+                    except subprocess.TimeoutExpired:
+                        logger.warning(f"Organize run {child.pid} did not stop in {CANCEL_GRACE}s; killing it")
+                        child.kill()
+                    return
+        threading.Thread(target=stop_on_cancel, daemon=True).start()
+
+    summary = None
+    count = 0
+    for line in child.stdout:
+        record = parse_event_line(line)
+        if record is None:
+            continue
+        count += 1
+        if record['event'] == EVENT_PROGRESS and record['payload'].get('phase') == 'done':
+            summary = record['payload'].get('summary')
+        try:
+            on_event(record['event'], record['payload'])
+        except Exception as e:  # a broken listener must not stop the run it listens to
+            logger.warning(f"Progress listener failed on {record['event']}: {e}")
+    code = child.wait()
+    drain.join(timeout=5)
+
+    if cancel is not None and cancel.is_set():
+        return {'success': False, 'message': 'Cancelled', 'exit_code': code, 'summary': summary, 'events': count}
+    if summary is None:
+        detail = ''.join(stderr).strip().splitlines()
+        message = f"The organize run stopped (exit code {code})" + (f": {detail[-1]}" if detail else '')
+        return {'success': False, 'message': message, 'exit_code': code, 'summary': None, 'events': count}
+    done = 'Would organize' if summary.get('preview') else 'Organized'
+    return {'success': code == 0, 'message': f"{done} {summary.get('organized', 0)} file(s)",
+            'exit_code': code, 'summary': summary, 'events': count}
@@ -0,0 +1,226 @@
+"""
+RAW+JPEG Pair Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module recognizes RAW files shot together with a JPEG (cameras set
+to RAW+JPEG write IMG_0042.CR2 and IMG_0042.JPG) so the two are treated
+as one linked pair by scanning, organizing and duplicate reports, the same
+way split archive parts are.
+
+A RAW file and a JPEG are a pair when they are in the same folder, share
+their name apart from the extension (case-insensitively), and were taken
+at most PAIR_MAX_GAP_SECONDS apart: by their EXIF capture times when both
+have one, otherwise by their modification times.
+
+The JPEG is the primary file (it is what gets classified and previewed);
+the RAW follows it. With `raw_pairs.separate_raw` the RAW moves into a
+parallel tree instead, e.g. Pictures/2024/05/IMG_0042.jpg and
+RAW/Pictures/2024/05/IMG_0042.cr2.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import os
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, Iterable, List, Optional, Tuple
+
+from .exif import JPEG_EXTENSIONS, RAW_EXTENSIONS, read_exif, capture_time
+
+
+PAIR_MAX_GAP_SECONDS = 10.0
+DEFAULT_RAW_TREE = 'RAW'
+
+
+def is_raw(path: Path) -> bool:
+    """True if the file has a camera RAW extension."""
+    return path.suffix.lower() in RAW_EXTENSIONS
+
+
+def _taken(path: Path) -> Tuple[Optional[datetime], Optional[float]]:
+    """(EXIF capture time as wall-clock time, modification time) of a file."""
+    taken, _ = capture_time(read_exif(str(path)))
+    try:
+        mtime = path.stat().st_mtime
+    except OSError:
+        mtime = None
+    return (taken.replace(tzinfo=None) if taken else None), mtime
+
+
+def shot_together(a: Path, b: Path, max_gap: float = PAIR_MAX_GAP_SECONDS) -> bool:
+    """
+    Whether two files were taken at (nearly) the same moment.
+
+    Args:
+        a (Path): First file
+        b (Path): Second file
+        max_gap (float): Largest difference in seconds
+
+    Returns:
+        bool: True if their capture (or modification) times are close enough
+    """
+    (taken_a, mtime_a), (taken_b, mtime_b) = _taken(a), _taken(b)
+    if taken_a is not None and taken_b is not None:
+        return abs((taken_a - taken_b).total_seconds()) <= max_gap
+    if mtime_a is None or mtime_b is None:
+        return False
+    return abs(mtime_a - mtime_b) <= max_gap
+
+
+def _pair_key(path: Path) -> Tuple[str, str]:
+    return (str(path.parent), path.stem.lower())
+
+
+def raw_partner(path: Path) -> Optional[Path]:
+    """
+    Find the other file of a RAW+JPEG pair.
+
+    Args:
+        path (Path): RAW or JPEG file
+
+    Returns:
+        Path or None: The JPEG for a RAW file, the RAW for a JPEG, or None
+    """
+    suffix = path.suffix.lower()
+    if suffix in RAW_EXTENSIONS:
+        wanted = JPEG_EXTENSIONS
+    elif suffix in JPEG_EXTENSIONS:
+        wanted = RAW_EXTENSIONS
+    else:
+        return None
+    try:
+        siblings = list(path.parent.iterdir())
+    except OSError:
+        return None
+    for sibling in sorted(siblings):
+        if (sibling != path and sibling.stem.lower() == path.stem.lower()
+                and sibling.suffix.lower() in wanted and sibling.is_file()
+                and shot_together(path, sibling)):
+            return sibling
+    return None
+
+
+def is_raw_sidecar(path: Path) -> bool:
+    """True if the file is the RAW half of a pair (it is organized with its JPEG)."""
+    return is_raw(path) and raw_partner(path) is not None
+
+
+def group_raw_pairs(paths: Iterable[Path]) -> List[List[Path]]:
+    """
+    Group a list of files into pairs and single files.
+
+    Only pairs whose both files are in the list are formed. Order follows
+    the first appearance of each unit in the input.
+
+    Args:
+        paths (Iterable[Path]): Files to group
+
+    Returns:
+        List[List[Path]]: Units, each [jpeg, raw] or [file]
+    """
+    paths = [Path(p) for p in paths]
+    jpegs: Dict[Tuple[str, str], Path] = {}
+    raws: Dict[Tuple[str, str], Path] = {}
+    for path in paths:
+        suffix = path.suffix.lower()
+        if suffix in JPEG_EXTENSIONS:
+            jpegs.setdefault(_pair_key(path), path)
+        elif suffix in RAW_EXTENSIONS:
+            raws.setdefault(_pair_key(path), path)
+
+    paired: Dict[Path, Path] = {}  # raw -> jpeg
+    for key, raw in raws.items():
+        jpeg = jpegs.get(key)
+        if jpeg is not None and shot_together(jpeg, raw):
+            paired[raw] = jpeg
+    partners = {jpeg: raw for raw, jpeg in paired.items()}
+
+    units = []
+    for path in paths:
+        if path in paired:
+            continue
+        units.append([path, partners[path]] if path in partners else [path])
+    return units
+
+
+def collapse_raw_pairs(paths: Iterable[str]) -> List[str]:
+    """
+    Replace each RAW+JPEG pair by its JPEG.
+
+    Args:
+        paths (Iterable[str]): File paths
+
+    Returns:
+        List[str]: One path per pair or single file
+    """
+    return [str(unit[0]) for unit in group_raw_pairs(Path(p) for p in paths)]
+
+
+def fold_raw_duplicates(groups: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """
+    Report duplicated pairs once.
+
+    When every RAW of a duplicate group belongs to a JPEG of another group
+    (the pair was copied as a whole), the RAW group is folded into the JPEG
+    group: its 'parts' maps each JPEG to [jpeg, raw] so the pair is cleaned
+    up together, and its sizes include the RAW.
+
+    Args:
+        groups (List[Dict]): Duplicate groups with 'paths', 'size' and 'total_wasted_space'
+
+    Returns:
+        List[Dict]: Groups with duplicated pairs folded
+    """
+    owner = {path: group for group in groups for path in group['paths']}
+    folded = []
+    for group in groups:
+        paths = [Path(p) for p in group['paths']]
+        if not paths or not all(is_raw(p) for p in paths):
+            continue
+        partners = [raw_partner(p) for p in paths]
+        if any(p is None for p in partners):
+            continue
+        jpeg_group = owner.get(str(partners[0]))
+        if jpeg_group is None or set(jpeg_group['paths']) != {str(p) for p in partners}:
+            continue
+        parts = jpeg_group.setdefault('parts', {})
+        for raw, jpeg in zip(paths, partners):
+            parts[str(jpeg)] = parts.get(str(jpeg), [str(jpeg)]) + [str(raw)]
+        jpeg_group['size'] += group['size']
+        jpeg_group['total_wasted_space'] += group['total_wasted_space']
+        jpeg_group['raw_pair'] = True
+        folded.append(id(group))
+    return [group for group in groups if id(group) not in folded]
+
+
+def raw_destination(jpeg_target: Path, raw: Path, root: Optional[Path], separate: bool,
+                    tree: str = DEFAULT_RAW_TREE) -> Path:
+    """
+    Where the RAW half of a pair goes when its JPEG goes to jpeg_target.
+
+    Args:
+        jpeg_target (Path): Destination of the JPEG
+        raw (Path): The RAW file
+        root (Path, optional): Destination root the JPEG is placed under
+        separate (bool): Put RAW files in their own tree
+        tree (str): Name of that tree, created under root
+
+    Returns:
+        Path: RAW destination (the JPEG's new name with the RAW's extension)
+    """
+    name = jpeg_target.stem + raw.suffix
+    folder = jpeg_target.parent
+    if separate and root is not None:
+        try:
+            relative = folder.relative_to(root)
+        except ValueError:
+            relative = None
+        if relative is not None:
+            folder = root / tree / relative
+    return Path(os.path.normpath(folder / name))
@@ -0,0 +1,264 @@
+"""
+Renamer Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module renames files by a template, so the names AI classification
+suggests (its "rename") can be applied in bulk, safely and undoably.
+
+A rename spec is {"path"} plus, optionally, "name" (the suggested name),
+"category", "date" (ISO date; default: the file's modification date) or
+a "classification" to take the name and category from. The template
+builds the new name from these fields:
+
+    {name}      the suggested name, else the original one (without extension)
+    {original}  the original name, without extension
+    {category}  the category
+    {date}      the date in `rename.date_format` ({year}, {month}, {day} also work)
+    {parent}    the folder the file is in
+    {n}         its position in the batch; {n:03} pads it to 001
+
+e.g. "{date}-{category}-{original}" gives "2024-03-01-Finance-scan001.pdf".
+The extension is always kept. Empty fields leave no doubled separators
+behind, and names are made valid on every platform (see file_names).
+
+Renames never overwrite: a name that is taken - on disk or by an earlier
+file of the same batch - gets " (2)", " (3)". Applied renames are one
+journal operation (undo restores every name) and appear in the history.
+
+    "rename": {"template": "{name}", "date_format": "%Y-%m-%d"}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import re
+import string
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Set
+
+from .file_names import suggest_file_name
+from .fsops import MAX_PLAN, move_file
+from .journal import OperationJournal
+from .safety_guardian import SafetyGuardian
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_TEMPLATE = '{name}'
+DEFAULT_DATE_FORMAT = '%Y-%m-%d'
+TEMPLATE_FIELDS = ('name', 'original', 'category', 'date', 'year', 'month', 'day', 'parent', 'n')
+
+_REPEATED_SEPARATORS = re.compile(r'([-_. ])\1+')
+
+
+def template_fields(template: str) -> List[str]:
+    """
+    The fields a template uses.
+
+    Raises:
+        ValueError: If it uses an unknown field or is malformed
+    """
+    try:
+        fields = [field for _, field, _, _ in string.Formatter().parse(template) if field is not None]
+    except ValueError as e:
+        raise ValueError(f"Malformed rename template {template!r}: {e}")
+    unknown = [field for field in fields if field not in TEMPLATE_FIELDS]
+    if unknown or '' in fields:
+        raise ValueError(f"Unknown field {{{unknown[0] if unknown else ''}}} in rename template "
+                         f"(use {', '.join('{' + field + '}' for field in TEMPLATE_FIELDS)})")
+    return fields
+
+
+def render_name(template: str, fields: Dict[str, Any], extension: str) -> str:
+    """
+    A file name from a template and field values; '' when nothing usable is left.
+
+    Args:
+        template (str): Template (see the module docstring)
+        fields (Dict): Field values (None for empty fields)
+        extension (str): Extension to keep, with its dot ('' for none)
+    """
+    values = {field: ('' if value is None else value) for field, value in fields.items()}
+    stem = template.format_map({field: values.get(field, '') for field in TEMPLATE_FIELDS})
+    stem = _REPEATED_SEPARATORS.sub(r'\1', stem).strip('-_. ')
+    return suggest_file_name(f"{stem}{extension}") if stem else ''
+
+
+def _suggested_stem(name: Optional[str], extension: str) -> Optional[str]:
+    """A suggested name without its extension (the original extension is kept)."""
+    if not name:
+        return None
+    name = Path(str(name)).name
+    if extension and name.lower().endswith(extension.lower()):
+        return name[:-len(extension)] or None
+    return name
+
+
+class FileRenamer:
+    """
+    Renames files by a template as one undoable operation.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager (journal and history)
+        journal (OperationJournal): Records renames for undo
+        template (str): Template used when the caller gives none
+        date_format (str): strftime format of {date}
+    """
+
+    def __init__(self, config, db_manager, journal: Optional[OperationJournal] = None):
+        """
+        Initialize the renamer.
+
+        Args:
+            config: Configuration object (reads `rename_settings`)
+            db_manager: Database manager instance
+            journal (OperationJournal, optional): Journal to record renames in
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.journal = journal or OperationJournal(db_manager)
+        settings = getattr(config, 'rename_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.template = settings.get('template') or DEFAULT_TEMPLATE
+        self.date_format = settings.get('date_format') or DEFAULT_DATE_FORMAT
+        self._guardian = SafetyGuardian(config)
+
+    def rename_files(self, renames: List[Dict[str, Any]], template: Optional[str] = None,
+                     dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Rename files by a template, reporting each one.
+
+        Args:
+            renames (List[Dict]): Rename specs (see the module docstring)
+            template (str, optional): Template (default: `rename.template`)
+            dry_run (bool): Only report the new names
+
+        Returns:
+            Dict: 'success', 'message', 'operation_id', 'template', 'results'
+                  (one {'source', 'destination', 'status': 'renamed' | 'skipped'
+                  | 'failed', 'message'} per spec), 'renamed', 'skipped',
+                  'failed' and 'dry_run'
+        """
+        template = template or self.template
+        try:
+            template_fields(template)
+        except ValueError as e:
+            return self._applied(False, str(e), template, dry_run)
+        if len(renames) > MAX_PLAN:
+            return self._applied(False, f"At most {MAX_PLAN} renames at once (got {len(renames)})", template, dry_run)
+
+        results: List[Dict[str, Any]] = []
+        claimed: Set[str] = set()
+        operation_id = None
+        for position, spec in enumerate(renames, start=1):
+            planned = self._plan(spec, template, position, claimed)
+            if planned['status'] != 'renamed' or dry_run:
+                results.append(planned)
+                continue
+            if operation_id is None:
+                operation_id = self.journal.begin('rename', f"Rename {len(renames)} file(s)")
+            results.append(self._apply(operation_id, planned, spec))
+
+        if operation_id is not None:
+            self.journal.complete(operation_id)
+        counts = {status: sum(1 for r in results if r['status'] == status)
+                  for status in ('renamed', 'skipped', 'failed')}
+        message = f"{'Would rename' if dry_run else 'Renamed'} {counts['renamed']} file(s)"
+        if counts['skipped']:
+            message += f", skipped {counts['skipped']}"
+        if counts['failed']:
+            message += f", {counts['failed']} failed"
+        result = self._applied(counts['failed'] == 0, message, template, dry_run)
+        result.update({'operation_id': operation_id, 'results': results, **counts})
+        return result
+
+    def _fields(self, source: Path, spec: Dict[str, Any], position: int) -> Dict[str, Any]:
+        classification = spec.get('classification') or {}
+        extension = source.suffix
+        date = spec.get('date')
+        try:
+            when = datetime.fromisoformat(str(date)) if date else datetime.fromtimestamp(source.stat().st_mtime)
+        except ValueError:
+            raise ValueError(f"Not an ISO date: {date}")
+        suggested = _suggested_stem(spec.get('name') or classification.get('rename')
+                                    or classification.get('suggested_rename'), extension)
+        return {
+            'name': suggested or source.stem,
+            'original': source.stem,
+            'category': spec.get('category') or classification.get('category'),
+            'date': when.strftime(self.date_format),
+            'year': when.strftime('%Y'), 'month': when.strftime('%m'), 'day': when.strftime('%d'),
+            'parent': source.parent.name,
+            'n': position,
+        }
+
+    def _plan(self, spec: Dict[str, Any], template: str, position: int, claimed: Set[str]) -> Dict[str, Any]:
+        """Where one file would be renamed to, claiming the name within the batch."""
+        source = Path(str(spec.get('path') or '')).expanduser()
+        result = {'source': str(source), 'destination': None, 'status': 'failed', 'message': ''}
+        if not spec.get('path') or not source.is_file():
+            result['message'] = f"Not a file: {source}"
+            return result
+        is_safe, reason = self._guardian.is_file_safe_to_modify(source)
+        if not is_safe:
+            result.update(status='skipped', message=reason)
+            return result
+        try:
+            name = render_name(template, self._fields(source, spec, position), source.suffix)
+        except (ValueError, OSError) as e:
+            result['message'] = str(e)
+            return result
+        if not name:
+            result['message'] = f"The template gives {source.name} an empty name"
+            return result
+        if name == source.name:
+            result.update(destination=str(source), status='skipped', message=f"Already named {name}")
+            return result
+
+        target = source.with_name(name)
+        counter = 2
+        while ((os.path.lexists(target) and not _same_entry(source, target))
+               or os.path.normcase(str(target)) in claimed):
+            target = source.with_name(f"{Path(name).stem} ({counter}){source.suffix}")
+            counter += 1
+        claimed.add(os.path.normcase(str(target)))
+        result.update(destination=str(target), status='renamed', message=f"Would rename to {target.name}")
+        return result
+
+    def _apply(self, operation_id: int, planned: Dict[str, Any], spec: Dict[str, Any]) -> Dict[str, Any]:
+        moved = move_file(planned['source'], planned['destination'], on_conflict='keep_both')
+        if moved['status'] != 'moved':
+            return {**planned, 'status': moved['status'], 'message': moved['message']}
+        self.journal.record_step(operation_id, 'move', planned['source'], moved['destination'],
+                                 {'method': moved['method'], 'role': 'rename'})
+        classification = spec.get('classification') or {}
+        try:
+            self.db_manager.log_action(Path(moved['destination']).name, planned['source'], moved['destination'],
+                                       'rename', category=spec.get('category') or classification.get('category'),
+                                       ai_suggested=bool(classification.get('rename')), user_approved=True)
+        except Exception as e:
+            logger.warning(f"Rename not added to the history: {e}")
+        return {**planned, 'destination': moved['destination'],
+                'message': f"Renamed to {Path(moved['destination']).name}"}
+
+    @staticmethod
+    def _applied(success: bool, message: str, template: str, dry_run: bool) -> Dict[str, Any]:
+        return {'success': success, 'message': message, 'operation_id': None, 'template': template,
+                'results': [], 'renamed': 0, 'skipped': 0, 'failed': 0, 'dry_run': dry_run}
+
+
+def _same_entry(source: Path, target: Path) -> bool:
+    """Whether target is the source itself, only spelled in another case."""
+    try:
+        return os.path.samefile(source, target)
+    except OSError:
+        return False
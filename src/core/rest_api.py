@@ -0,0 +1,497 @@
+"""
+REST API Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module lets home-automation tools, scripts and companion apps drive
+the running engine (the daemon) over HTTP, next to its control socket.
+It is off unless `rest_api.enabled` is set, listens on 127.0.0.1 only
+(unless `rest_api.allow_remote`), and every request needs the API token:
+
+    Authorization: Bearer <token>
+
+The token is `rest_api.token`, or else one generated on first start and
+kept in data/rest_api_token (readable by the current user only); see
+`aifo rest-api --token`.
+
+Endpoints (JSON in, JSON out):
+
+    GET  /api/v1/status              engine status
+    POST /api/v1/scan                {"folder", "recursive"}
+    POST /api/v1/plan                {"folder", "recursive", "deep", "strategy"}
+    POST /api/v1/apply               {"plan"} or {"plan_file"}
+    POST /api/v1/runs                {"folder", "recursive", "strategy", "apply"}: plan
+                                     (and apply) in the background -> 202 + run
+    GET  /api/v1/runs[/<id>]         runs and their progress
+    GET  /api/v1/history?limit=50    journaled operations (every organize run,
+                                     move and rename), newest first
+    GET  /api/v1/history/<id>        one operation and its steps
+    POST /api/v1/history/<id>/undo   {"dry_run"}: move its files back
+    GET  /api/v1/events              WebSocket: run updates and INFO+ log records
+
+Errors are {"error": {"code", "message"}} with the control socket's codes,
+and HTTP status 400 (bad request), 401 (no or wrong token), 404, 405 or 500.
+A failed method's error also has "data", the AppError it failed with
+(see utils.error_handler).
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import base64
+import hashlib
+import hmac
+import inspect
+import itertools
+import json
+import logging
+import os
+import secrets
+import select
+import struct
+import threading
+import time
+from datetime import datetime
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+from urllib.parse import parse_qsl
+from typing import Dict, Any, Callable, List, Optional, Tuple
+
+from .control import PARSE_ERROR, METHOD_NOT_FOUND, INVALID_PARAMS, METHOD_FAILED
+from src.utils.error_handler import AppError
+from src.utils.structured_logging import span, log_stream
+
+logger = logging.getLogger(__name__)
+
+
+API_PREFIX = '/api/v1'
+DEFAULT_PORT = 5051
+MAX_BODY = 32 * 1024 * 1024
+MAX_RUNS = 50
+UNAUTHORIZED = -32003
+WS_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11'
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+_LOOPBACK = ('127.0.0.1', '::1', 'localhost')
+
+# (HTTP method, path) -> control method
+ROUTES = {
+    ('GET', '/status'): 'status',
+    ('POST', '/scan'): 'scan',
+    ('POST', '/plan'): 'plan',
+    ('POST', '/apply'): 'apply',
+    ('GET', '/history'): 'get_history',
+}
+
+
+def api_token(settings: Dict[str, Any], token_file: Optional[Path] = None) -> str:
+    """
+    The API token: configured, or generated once and kept private to the user.
+
+    Args:
+        settings (Dict): `rest_api` settings
+        token_file (Path, optional): Where a generated token is kept
+
+    Returns:
+        str: Token
+    """
+    if settings.get('token'):
+        return str(settings['token'])
+    token_file = token_file or Path(settings.get('token_file') or _APP_ROOT / 'data' / 'rest_api_token').expanduser()
+    try:
+        token = token_file.read_text(encoding='utf-8').strip()
+        if token:
+            return token
+    except OSError:
+        pass
+    return rotate_api_token(settings, token_file)
+
+
+def rotate_api_token(settings: Dict[str, Any], token_file: Optional[Path] = None) -> str:
+    """
+    Replace the generated API token (clients using the old one stop working).
+
+    Args:
+        settings (Dict): `rest_api` settings
+        token_file (Path, optional): Where the generated token is kept
+
+    Returns:
+        str: The new token
+    """
+    token_file = token_file or Path(settings.get('token_file') or _APP_ROOT / 'data' / 'rest_api_token').expanduser()
+    token = secrets.token_urlsafe(32)
+    token_file.parent.mkdir(parents=True, exist_ok=True)
+    token_file.unlink(missing_ok=True)
+    fd = os.open(str(token_file), os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+    with os.fdopen(fd, 'w', encoding='utf-8') as f:
+        f.write(token)
+    return token
+
+
+class RunRegistry:
+    """
+    Organize runs started over the API: plan, then optionally apply, in the background.
+
+    Attributes:
+        methods (Dict): The engine's control methods ('plan' and 'apply' are used)
+    """
+
+    def __init__(self, methods: Dict[str, Callable[..., Any]]):
+        self.methods = methods
+        self._runs: Dict[int, Dict[str, Any]] = {}
+        self._ids = itertools.count(1)
+        self._version = 0
+        self._changed = threading.Condition()
+
+    def start(self, folder: str, recursive: bool = True, strategy: Optional[str] = None,
+              apply: bool = False, deep: bool = False) -> Dict[str, Any]:
+        """
+        Start a run.
+
+        Args:
+            folder (str): Folder to organize
+            recursive (bool): Include subfolders
+            strategy (str, optional): Planner strategy
+            apply (bool): Apply the plan once built (else only plan)
+            deep (bool): Deep AI analysis
+
+        Returns:
+            Dict: The run: 'id', 'folder', 'state' ('queued', 'planning',
+                  'applying', 'done' or 'failed'), 'started_at', ...
+        """
+        run = {'id': next(self._ids), 'folder': folder, 'apply': bool(apply), 'state': 'queued',
+               'started_at': datetime.now().isoformat(timespec='seconds'), 'finished_at': None,
+               'items': None, 'conflicts': None, 'applied': None, 'failed': None,
+               'operation_id': None, 'message': '', 'version': 0}
+        with self._changed:
+            self._runs[run['id']] = run
+            for old in sorted(self._runs)[:-MAX_RUNS]:
+                if self._runs[old]['state'] in ('done', 'failed'):
+                    del self._runs[old]
+            accepted = dict(run)
+        threading.Thread(target=self._run, args=(run['id'], folder, recursive, strategy, bool(apply), bool(deep)),
+                         name=f"api-run-{run['id']}", daemon=True).start()
+        return accepted
+
+    def _update(self, run_id: int, **changes) -> None:
+        with self._changed:
+            run = self._runs.get(run_id)
+            if run is None:
+                return
+            self._version += 1
+            run.update(changes, version=self._version)
+            self._changed.notify_all()
+
+    def _run(self, run_id: int, folder: str, recursive: bool, strategy: Optional[str], apply: bool,
+             deep: bool) -> None:
+        try:
+            self._update(run_id, state='planning')
+            with span('api.run', logger, logging.INFO, run_id=run_id, folder=folder):
+                plan = self.methods['plan'](folder, recursive=recursive, deep=deep, strategy=strategy)
+                self._update(run_id, items=len(plan['items']), conflicts=plan.get('conflicts'),
+                             message=f"Planned {len(plan['items'])} file(s)")
+                if apply:
+                    self._update(run_id, state='applying')
+                    result = self.methods['apply'](plan=plan)
+                    self._update(run_id, applied=len(result['applied']), failed=len(result['failed']),
+                                 operation_id=result.get('operation_id'), message=result['message'])
+            self._update(run_id, state='done', finished_at=datetime.now().isoformat(timespec='seconds'))
+        except Exception as e:  # the run reports any failure instead of dying silently
+            logger.warning(f"API run {run_id} failed: {e}")
+            self._update(run_id, state='failed', message=str(e),
+                         finished_at=datetime.now().isoformat(timespec='seconds'))
+
+    def get(self, run_id: int) -> Optional[Dict[str, Any]]:
+        with self._changed:
+            run = self._runs.get(run_id)
+            return dict(run) if run else None
+
+    def list(self) -> List[Dict[str, Any]]:
+        with self._changed:
+            return [dict(self._runs[i]) for i in sorted(self._runs, reverse=True)]
+
+    def changes(self, after: int, wait: float) -> Tuple[List[Dict[str, Any]], int]:
+        """Runs updated after a version, waiting up to `wait` seconds for one."""
+        deadline = time.monotonic() + wait
+        with self._changed:
+            while self._version <= after and time.monotonic() < deadline:
+                self._changed.wait(deadline - time.monotonic())
+            changed = [dict(r) for r in self._runs.values() if r['version'] > after]
+            return sorted(changed, key=lambda r: r['version']), self._version
+
+
+class RestApiServer:
+    """
+    HTTP (and WebSocket) front end for the engine's control methods.
+
+    Attributes:
+        methods (Dict): Control methods, as served on the control socket
+        host (str): Interface listened on
+        port (int): Port (0: any free port, see `address` once started)
+        runs (RunRegistry): Background organize runs
+    """
+
+    def __init__(self, methods: Dict[str, Callable[..., Any]], config=None, host: Optional[str] = None,
+                 port: Optional[int] = None, token: Optional[str] = None):
+        """
+        Initialize REST API server.
+
+        Args:
+            methods (Dict): Control methods ('status', 'scan', 'plan', 'apply')
+            config: Configuration object (reads `rest_api_settings`)
+            host (str, optional): Interface (default: rest_api.host, else 127.0.0.1)
+            port (int, optional): Port (default: rest_api.port, else 5051)
+            token (str, optional): API token (default: see api_token())
+
+        Raises:
+            ValueError: If asked to listen beyond this computer without allow_remote
+        """
+        settings = getattr(config, 'rest_api_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.methods = methods
+        self.host = host or settings.get('host') or '127.0.0.1'
+        self.port = int(port if port is not None else settings.get('port') or DEFAULT_PORT)
+        if self.host not in _LOOPBACK and not settings.get('allow_remote'):
+            raise ValueError(f"The REST API only listens on this computer unless rest_api.allow_remote is set "
+                             f"(asked for {self.host})")
+        self.token = token or api_token(settings)
+        self.runs = RunRegistry(methods)
+        self._httpd: Optional[ThreadingHTTPServer] = None
+        self._thread: Optional[threading.Thread] = None
+        self._stopping = threading.Event()
+
+    @property
+    def address(self) -> Tuple[str, int]:
+        return self._httpd.server_address[:2] if self._httpd else (self.host, self.port)
+
+    def start(self) -> None:
+        """Listen in a background thread."""
+        server = self
+
+        class Handler(_ApiHandler):
+            api = server
+
+        self._httpd = ThreadingHTTPServer((self.host, self.port), Handler)
+        self._httpd.daemon_threads = True
+        self._thread = threading.Thread(target=self._httpd.serve_forever, name='rest-api', daemon=True)
+        self._thread.start()
+        logger.info(f"REST API listening on http://{self.address[0]}:{self.address[1]}{API_PREFIX}")
+
+    def stop(self) -> None:
+        """Stop listening and close WebSocket streams."""
+        self._stopping.set()
+        if self._httpd is not None:
+            self._httpd.shutdown()
+            self._httpd.server_close()
+            self._httpd = None
+
+    # ==================== Requests ====================
+
+    def authorized(self, header: Optional[str]) -> bool:
+        scheme, _, supplied = (header or '').partition(' ')
+        return scheme.lower() == 'bearer' and hmac.compare_digest(supplied.strip().encode(), self.token.encode())
+
+    def handle(self, verb: str, path: str, body: Optional[bytes],
+               query: Optional[Dict[str, str]] = None) -> Tuple[int, Any]:
+        """
+        Answer one authorized API request.
+
+        Args:
+            verb (str): HTTP method
+            path (str): Path below /api/v1
+            body (bytes, optional): Request body
+            query (Dict, optional): Query string parameters (JSON values, or strings)
+
+        Returns:
+            Tuple: (HTTP status, JSON-serializable reply)
+        """
+        try:
+            params = json.loads(body.decode('utf-8')) if body else {}
+            if not isinstance(params, dict):
+                raise ValueError('the body must be a JSON object')
+        except (ValueError, UnicodeDecodeError) as e:
+            return 400, _error(PARSE_ERROR, f"Malformed request: {e}")
+        for key, value in (query or {}).items():
+            try:
+                params.setdefault(key, json.loads(value))
+            except ValueError:
+                params.setdefault(key, value)
+
+        if path == '/runs' and verb == 'GET':
+            return 200, {'runs': self.runs.list()}
+        if path == '/runs' and verb == 'POST':
+            return self._call(self.runs.start, params, 202, 'api.runs')
+        if path.startswith('/runs/') and verb == 'GET':
+            run = self.runs.get(int(path[6:])) if path[6:].isdigit() else None
+            return (200, run) if run else (404, _error(METHOD_NOT_FOUND, f"No run {path[6:]}"))
+        if path.startswith('/history/'):
+            return self._operation(verb, path[len('/history/'):], params)
+
+        method = ROUTES.get((verb, path))
+        if method is None or method not in self.methods:
+            known = {p for _, p in ROUTES} | {'/runs'}
+            if path in known:
+                return 405, _error(METHOD_NOT_FOUND, f"{verb} is not supported on {path}")
+            return 404, _error(METHOD_NOT_FOUND, f"Unknown endpoint: {path}")
+        return self._call(self.methods[method], params, 200, f"api.{method}")
+
+    def _operation(self, verb: str, rest: str, params: Dict[str, Any]) -> Tuple[int, Any]:
+        operation_id, _, action = rest.partition('/')
+        if not operation_id.isdigit() or action not in ('', 'undo'):
+            return 404, _error(METHOD_NOT_FOUND, f"Unknown endpoint: /history/{rest}")
+        if (verb, action) not in (('GET', ''), ('POST', 'undo')):
+            return 405, _error(METHOD_NOT_FOUND, f"{verb} is not supported on /history/{rest}")
+        method = 'undo_operation' if action else 'get_operation'
+        if method not in self.methods:
+            return 404, _error(METHOD_NOT_FOUND, f"Unknown endpoint: /history/{rest}")
+        status, reply = self._call(self.methods[method], dict(params, operation_id=int(operation_id)),
+                                   200, f"api.{method}")
+        if status == 200 and reply is None:
+            return 404, _error(METHOD_NOT_FOUND, f"No operation {operation_id}")
+        return status, reply
+
+    @staticmethod
+    def _call(handler: Callable[..., Any], params: Dict[str, Any], status: int, name: str) -> Tuple[int, Any]:
+        try:
+            inspect.signature(handler).bind(**params)
+        except TypeError as e:
+            return 400, _error(INVALID_PARAMS, str(e))
+        try:
+            with span(name, logger):
+                return status, handler(**params)
+        except Exception as e:  # any method failure becomes an error reply
+            error = AppError.from_exception(e)
+            return 500, _error(METHOD_FAILED, error.message, error.to_dict())
+
+
+def _error(code: int, message: str, data: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    error = {'code': code, 'message': message}
+    if data is not None:
+        error['data'] = data
+    return {'error': error}
+
+
+class _ApiHandler(BaseHTTPRequestHandler):
+    """Routes HTTP requests to the RestApiServer set as the subclass's `api`."""
+
+    api: RestApiServer
+    server_version = 'AIFileOrganiser-API/1'
+    protocol_version = 'HTTP/1.1'
+
+    def log_message(self, format, *args):  # noqa: A002 - BaseHTTPRequestHandler's signature
+        logger.debug(f"REST API {self.address_string()} {format % args}")
+
+    def _reply(self, status: int, payload: Any) -> None:
+        data = json.dumps(payload, default=str).encode('utf-8')
+        self.send_response(status)
+        self.send_header('Content-Type', 'application/json')
+        self.send_header('Content-Length', str(len(data)))
+        self.send_header('Cache-Control', 'no-store')
+        self.end_headers()
+        self.wfile.write(data)
+
+    def _dispatch(self, verb: str) -> None:
+        path, _, query = self.path.partition('?')
+        path = path.rstrip('/')
+        if not path.startswith(API_PREFIX):
+            self._reply(404, _error(METHOD_NOT_FOUND, f"Unknown endpoint: {path}"))
+            return
+        if not self.api.authorized(self.headers.get('Authorization')):
+            self._reply(401, _error(UNAUTHORIZED, 'Missing or wrong API token'))
+            return
+        path = path[len(API_PREFIX):] or '/'
+        if path == '/events' and verb == 'GET':
+            self._events()
+            return
+        try:
+            length = int(self.headers.get('Content-Length') or 0)
+        except ValueError:
+            length = -1
+        if length < 0:
+            self._reply(400, _error(PARSE_ERROR, 'Invalid Content-Length'))
+            return
+        if length > MAX_BODY:
+            self._reply(400, _error(PARSE_ERROR, 'Request body too large'))
+            return
+        body = self.rfile.read(length) if length else None
+        status, payload = self.api.handle(verb, path, body, dict(parse_qsl(query)))
+        self._reply(status, payload)
+
+    def do_GET(self):
+        self._dispatch('GET')
+
+    def do_POST(self):
+        self._dispatch('POST')
+
+    # ==================== WebSocket ====================
+
+    def _events(self) -> None:
+        key = self.headers.get('Sec-WebSocket-Key')
+        if self.headers.get('Upgrade', '').lower() != 'websocket' or not key:
+            self._reply(400, _error(PARSE_ERROR, 'Connect to /events with a WebSocket'))
+            return
+        accept = base64.b64encode(hashlib.sha1((key + WS_GUID).encode()).digest()).decode()
+        self.send_response(101)
+        self.send_header('Upgrade', 'websocket')
+        self.send_header('Connection', 'Upgrade')
+        self.send_header('Sec-WebSocket-Accept', accept)
+        self.end_headers()
+        self.close_connection = True
+
+        stream = log_stream()
+        log_cursor = stream.read(limit=0)['cursor']
+        run_version = 0
+        try:
+            while not self.api._stopping.is_set():
+                if select.select([self.connection], [], [], 0)[0] and not self._ws_receive():
+                    break
+                runs, run_version = self.api.runs.changes(run_version, wait=0.5)
+                for run in runs:
+                    self._ws_send({'type': 'run', 'run': run})
+                logs = stream.read(after=log_cursor, level='INFO', limit=200)
+                log_cursor = logs['cursor']
+                for record in logs['records']:
+                    self._ws_send({'type': 'log', 'record': {k: v for k, v in record.items() if k != 'levelno'}})
+        except OSError:
+            pass  # the client went away
+        try:
+            self._ws_frame(0x8, b'')
+        except OSError:
+            pass
+
+    def _ws_send(self, message: Dict[str, Any]) -> None:
+        self._ws_frame(0x1, json.dumps(message, default=str).encode('utf-8'))
+
+    def _ws_frame(self, opcode: int, payload: bytes) -> None:
+        header = bytes([0x80 | opcode])
+        if len(payload) < 126:
+            header += bytes([len(payload)])
+        elif len(payload) < 1 << 16:
+            header += bytes([126]) + struct.pack('>H', len(payload))
+        else:
+            header += bytes([127]) + struct.pack('>Q', len(payload))
+        self.wfile.write(header + payload)
+        self.wfile.flush()
+
+    def _ws_receive(self) -> bool:
+        """Handle one client frame; False once the client closes."""
+        head = self.rfile.read(2)
+        if len(head) < 2:
+            return False
+        opcode, length = head[0] & 0x0F, head[1] & 0x7F
+        if length == 126:
+            length = struct.unpack('>H', self.rfile.read(2))[0]
+        elif length == 127:
+            length = struct.unpack('>Q', self.rfile.read(8))[0]
+        mask = self.rfile.read(4) if head[1] & 0x80 else b'\x00' * 4
+        if length > MAX_BODY:
+            return False
+        payload = bytes(b ^ mask[i % 4] for i, b in enumerate(self.rfile.read(length)))
+        if opcode == 0x8:
+            return False
+        if opcode == 0x9:
+            self._ws_frame(0xA, payload)
+        return True
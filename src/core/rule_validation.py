@@ -0,0 +1,409 @@
+"""
+Rule Validation Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module lints a YAML rules file (the format of config.yaml, see
+config_yaml.py) without loading or running it, so shared rule packs can
+be checked in an automation pipeline before anyone uses them.
+
+It reports structured diagnostics for YAML syntax, unknown keys, invalid
+regexes and dates, destination templates with unknown placeholders,
+references to undefined filters or actions, filters that can never
+match, and rules that can never act because an earlier rule has already
+moved every file they match.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import mimetypes
+import re
+from dataclasses import fields
+from datetime import date, datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Set
+
+import yaml
+
+from src.config_yaml import FilterRule, ActionRule
+
+logger = logging.getLogger(__name__)
+
+
+ERROR = 'error'
+WARNING = 'warning'
+
+FILTER_KEYS = {f.name for f in fields(FilterRule)} - {'name'}
+ACTION_KEYS = {f.name for f in fields(ActionRule)} - {'name'}
+RULE_KEYS = {'name', 'filters', 'actions', 'tags', 'description'}
+TOP_LEVEL_KEYS = {'version', 'settings', 'filters', 'actions', 'rules'}
+
+ACTIONS = ('move', 'copy', 'delete', 'rename', 'script', 'compress')
+CONFLICT_RESOLUTIONS = ('rename', 'overwrite', 'skip')
+DATE_KEYS = ('date_modified_after', 'date_modified_before', 'date_created_after', 'date_created_before')
+REGEX_KEYS = ('regex_patterns', 'exclude_regex')
+LIST_KEYS = ('patterns', 'mime_types', 'extensions', 'exclude_patterns')
+
+# Placeholders YAMLConfig._build_context() provides to destination templates
+TEMPLATE_PLACEHOLDERS = {
+    'file_name', 'file_stem', 'file_suffix', 'file_size', 'file_mtime', 'file_ctime',
+    'base_destination', 'year', 'month', 'day'
+}
+
+_PLACEHOLDER = re.compile(r'\{([^{}]*)\}')
+
+
+class _Report:
+    """Collects diagnostics while walking the rules file."""
+
+    def __init__(self):
+        self.diagnostics: List[Dict[str, str]] = []
+
+    def add(self, severity: str, code: str, location: str, message: str) -> None:
+        self.diagnostics.append({'severity': severity, 'code': code, 'location': location, 'message': message})
+
+    def error(self, code: str, location: str, message: str) -> None:
+        self.add(ERROR, code, location, message)
+
+    def warning(self, code: str, location: str, message: str) -> None:
+        self.add(WARNING, code, location, message)
+
+
+def validate_rules(path: str) -> Dict[str, Any]:
+    """
+    Lint a YAML rules file.
+
+    Args:
+        path (str): Rules file (config.yaml format)
+
+    Returns:
+        Dict: 'file', 'valid' (no errors), 'errors', 'warnings', 'rules'
+              (number of rules) and 'diagnostics', each with 'severity',
+              'code', 'location' (e.g. "filters.documents.regex_patterns[0]")
+              and 'message'
+    """
+    report = _Report()
+    rules_file = Path(path).expanduser()
+
+    try:
+        data = yaml.safe_load(rules_file.read_text(encoding='utf-8'))
+    except OSError as e:
+        report.error('unreadable', str(rules_file), f"Cannot read rules file: {e}")
+        data = None
+    except yaml.YAMLError as e:
+        mark = getattr(e, 'problem_mark', None)
+        location = f"line {mark.line + 1}, column {mark.column + 1}" if mark else str(rules_file)
+        report.error('yaml_syntax', location, f"Invalid YAML: {getattr(e, 'problem', None) or e}")
+        data = None
+
+    rule_count = 0
+    if data is not None:
+        if not isinstance(data, dict):
+            report.error('not_a_mapping', '', "The rules file must be a mapping with filters, actions and rules")
+        else:
+            rule_count = _validate_document(data, report)
+    elif not report.diagnostics:
+        report.warning('empty', str(rules_file), "The rules file is empty")
+
+    errors = sum(1 for d in report.diagnostics if d['severity'] == ERROR)
+    return {
+        'file': str(rules_file),
+        'valid': errors == 0,
+        'errors': errors,
+        'warnings': len(report.diagnostics) - errors,
+        'rules': rule_count,
+        'diagnostics': report.diagnostics
+    }
+
+
+def _validate_document(data: Dict[str, Any], report: _Report) -> int:
+    for key in data:
+        if key not in TOP_LEVEL_KEYS:
+            report.warning('unknown_key', str(key), f"Unknown top-level key '{key}' is ignored")
+
+    filters = _section(data, 'filters', dict, report)
+    actions = _section(data, 'actions', dict, report)
+    rules = _section(data, 'rules', list, report)
+
+    never_matching = set()
+    for name, config in filters.items():
+        if _validate_filter(str(name), config, report) is False:
+            never_matching.add(name)
+    relocating = {name for name, config in actions.items()
+                  if _validate_action(str(name), config, report) and _relocates(config)}
+
+    _validate_rules_list(rules, filters, actions, never_matching, relocating, report)
+    return len(rules)
+
+
+def _section(data: Dict[str, Any], key: str, kind: type, report: _Report):
+    value = data.get(key)
+    if value is None:
+        return kind()
+    if not isinstance(value, kind):
+        report.error('wrong_type', key, f"'{key}' must be a {'mapping' if kind is dict else 'list'}")
+        return kind()
+    return value
+
+
+# ==================== Filters ====================
+
+def _validate_filter(name: str, config: Any, report: _Report) -> Optional[bool]:
+    """Check one filter; returns False if it can never match, None if unusable."""
+    location = f"filters.{name}"
+    if not isinstance(config, dict):
+        report.error('wrong_type', location, "A filter must be a mapping of criteria")
+        return None
+
+    for key in config:
+        if key not in FILTER_KEYS:
+            report.error('unknown_key', f"{location}.{key}", f"Unknown filter criterion '{key}'")
+
+    for key in ('size_min', 'size_max'):
+        if key in config and (not isinstance(config[key], int) or isinstance(config[key], bool) or config[key] < 0):
+            report.error('invalid_size', f"{location}.{key}", f"{key} must be a size in bytes")
+
+    dates = {}
+    for key in DATE_KEYS:
+        if key in config:
+            dates[key] = _parse_date(config[key])
+            if type(config[key]) is date:
+                # YAML reads an unquoted 2024-01-01 as a date, which the engine cannot compare
+                report.error('unquoted_date', f"{location}.{key}",
+                             f"Quote the date ('{config[key]}') so it is read as text")
+            elif dates[key] is None:
+                report.error('invalid_date', f"{location}.{key}",
+                             f"'{config[key]}' is not an ISO date or 'N days ago'")
+
+    for key in REGEX_KEYS:
+        if key not in config:
+            continue
+        patterns = config[key] if isinstance(config[key], list) else [config[key]]
+        for index, pattern in enumerate(patterns):
+            try:
+                re.compile(str(pattern))
+            except re.error as e:
+                report.error('invalid_regex', f"{location}.{key}[{index}]", f"Invalid regex '{pattern}': {e}")
+
+    for key in LIST_KEYS:
+        if key in config and not isinstance(config[key], list):
+            report.error('wrong_type', f"{location}.{key}", f"{key} must be a list")
+
+    for index, extension in enumerate(config.get('extensions') or []):
+        if not str(extension).startswith('.'):
+            report.warning('extension_without_dot', f"{location}.extensions[{index}]",
+                           f"'{extension}' never matches; extensions start with a dot (e.g. '.{extension}')")
+
+    return not _never_matches(location, config, dates, report)
+
+
+def _parse_date(value: Any) -> Optional[datetime]:
+    """Parse a filter date like YAMLConfig does (ISO date or "N days ago")."""
+    if isinstance(value, datetime):
+        return value
+    text = str(value)
+    try:
+        return datetime.fromisoformat(text.replace('Z', '+00:00'))
+    except ValueError:
+        pass
+    match = re.fullmatch(r'\s*(\d+)\s+days ago\s*', text)
+    if match:
+        return datetime.now()  # Only the format matters here
+    return None
+
+
+def _never_matches(location: str, config: Dict[str, Any], dates: Dict[str, Optional[datetime]],
+                   report: _Report) -> bool:
+    size_min, size_max = config.get('size_min'), config.get('size_max')
+    if isinstance(size_min, int) and isinstance(size_max, int) and size_min and size_max and size_min > size_max:
+        report.warning('never_matches', location, f"size_min ({size_min}) is larger than size_max ({size_max})")
+        return True
+
+    for kind in ('modified', 'created'):
+        after, before = dates.get(f'date_{kind}_after'), dates.get(f'date_{kind}_before')
+        if after and before and _comparable(after) >= _comparable(before):
+            report.warning('never_matches', location, f"date_{kind}_after is not before date_{kind}_before")
+            return True
+
+    extensions, mime_types = config.get('extensions'), config.get('mime_types')
+    if isinstance(extensions, list) and isinstance(mime_types, list) and extensions and mime_types:
+        if not any(mimetypes.guess_type(f"x{ext}")[0] in mime_types for ext in extensions):
+            report.warning('never_matches', location, "None of the extensions has one of the listed mime_types")
+            return True
+    return False
+
+
+def _comparable(value: datetime) -> datetime:
+    return value.replace(tzinfo=None)
+
+
+# ==================== Actions ====================
+
+def _validate_action(name: str, config: Any, report: _Report) -> bool:
+    """Check one action; returns whether it is usable."""
+    location = f"actions.{name}"
+    if not isinstance(config, dict):
+        report.error('wrong_type', location, "An action must be a mapping")
+        return False
+
+    usable = True
+    for key in config:
+        if key not in ACTION_KEYS:
+            report.error('unknown_key', f"{location}.{key}", f"Unknown action setting '{key}'")
+            usable = False
+
+    action = config.get('action')
+    if action not in ACTIONS:
+        report.error('unknown_action', f"{location}.action",
+                     f"Action must be one of {', '.join(ACTIONS)} (got {action!r})")
+        return False
+
+    if config.get('conflict_resolution', 'rename') not in CONFLICT_RESOLUTIONS:
+        report.error('invalid_conflict_resolution', f"{location}.conflict_resolution",
+                     f"conflict_resolution must be one of {', '.join(CONFLICT_RESOLUTIONS)}")
+
+    if action == 'script':
+        if not config.get('script'):
+            report.error('missing_script', location, "A script action needs 'script'")
+            return False
+        try:
+            compile(str(config['script']), f"{location}.script", 'exec')
+        except SyntaxError as e:
+            report.error('invalid_script', f"{location}.script", f"Script does not compile: {e.msg} (line {e.lineno})")
+            usable = False
+    elif action == 'rename':
+        if not config.get('template'):
+            report.error('missing_template', location, "A rename action needs 'template' (the new file name)")
+            return False
+    elif action != 'delete' and not (config.get('destination') or config.get('template')):
+        report.error('missing_destination', location, f"A {action} action needs 'destination'")
+        return False
+
+    for key in ('destination', 'template'):
+        if config.get(key):
+            usable = _validate_template(f"{location}.{key}", str(config[key]), report) and usable
+    return usable
+
+
+def _validate_template(location: str, template: str, report: _Report) -> bool:
+    stripped = _PLACEHOLDER.sub('', template)
+    if '{' in stripped or '}' in stripped:
+        report.error('unbalanced_braces', location, f"Unbalanced braces in '{template}'")
+        return False
+    valid = True
+    for placeholder in _PLACEHOLDER.findall(template):
+        if placeholder not in TEMPLATE_PLACEHOLDERS:
+            report.error('unknown_placeholder', location,
+                         f"Unknown placeholder {{{placeholder}}}; available: "
+                         f"{', '.join(sorted(TEMPLATE_PLACEHOLDERS))}")
+            valid = False
+    return valid
+
+
+def _relocates(config: Dict[str, Any]) -> bool:
+    """Whether the action leaves nothing at the original path for later rules."""
+    action = config.get('action')
+    return action in ('move', 'delete', 'rename') or (action == 'compress' and bool(config.get('remove_source')))
+
+
+# ==================== Rules ====================
+
+def _validate_rules_list(rules: List[Any], filters: Dict[str, Any], actions: Dict[str, Any],
+                         never_matching: Set[str], relocating: Set[str], report: _Report) -> None:
+    seen_names: Dict[str, int] = {}
+    # (index, name, filter set, tags) of earlier rules that move matching files away
+    movers: List[tuple] = []
+
+    for index, rule in enumerate(rules):
+        location = f"rules[{index}]"
+        if not isinstance(rule, dict):
+            report.error('wrong_type', location, "A rule must be a mapping with name, filters and actions")
+            continue
+
+        name = rule.get('name')
+        if not name:
+            report.error('missing_name', location, "Rule has no name")
+            name = location
+        elif name in seen_names:
+            report.warning('duplicate_name', location, f"Rule name '{name}' is also used by rules[{seen_names[name]}]")
+        else:
+            seen_names[name] = index
+
+        for key in rule:
+            if key not in RULE_KEYS:
+                report.warning('unknown_key', f"{location}.{key}", f"Unknown rule key '{key}' is ignored")
+
+        filter_names = _name_list(rule, 'filters', location, report)
+        action_names = _name_list(rule, 'actions', location, report)
+
+        for filter_name in filter_names:
+            if filter_name not in filters:
+                # The engine skips unknown filters, so the rule matches more than intended
+                report.error('undefined_filter', f"{location}.filters",
+                             f"Filter '{filter_name}' is not defined; the rule would ignore it")
+        for action_name in action_names:
+            if action_name not in actions:
+                report.error('undefined_action', f"{location}.actions", f"Action '{action_name}' is not defined")
+        if not action_names:
+            report.warning('no_actions', location, f"Rule '{name}' has no actions")
+
+        effective = {f for f in filter_names if f in filters}
+        dead = sorted(effective & never_matching)
+        if dead:
+            report.warning('unreachable', location,
+                           f"Rule '{name}' never matches: filter '{dead[0]}' can never match")
+            continue
+
+        # Running by tags only selects rules sharing a tag, so an earlier rule
+        # shadows this one only if every tag that selects this one selects it too
+        tags = set(_name_list(rule, 'tags', location, report))
+        shadowed_by = next((m for m in movers if m[2] <= effective and tags <= m[3]), None)
+        if shadowed_by is not None:
+            report.warning('unreachable', location,
+                           f"Rule '{name}' is unreachable: rules[{shadowed_by[0]}] ('{shadowed_by[1]}') "
+                           f"already moves every file it matches")
+        elif any(a in relocating for a in action_names):
+            for other in movers:
+                if _may_overlap(other[2], effective, filters):
+                    report.warning('conflicting', location,
+                                   f"Rule '{name}' and rules[{other[0]}] ('{other[1]}') both move some files; "
+                                   f"the first one wins and this one then fails for them")
+                    break
+
+        if any(a in relocating for a in action_names):
+            movers.append((index, name, effective, tags))
+
+
+def _name_list(rule: Dict[str, Any], key: str, location: str, report: _Report) -> List[str]:
+    value = rule.get(key) or []
+    if isinstance(value, str):
+        return [value]
+    if not isinstance(value, list):
+        report.error('wrong_type', f"{location}.{key}", f"{key} must be a list of names")
+        return []
+    return [str(v) for v in value]
+
+
+def _may_overlap(first: Set[str], second: Set[str], filters: Dict[str, Any]) -> bool:
+    """Whether two filter sets can match the same file (only extensions are compared)."""
+    first_ext, second_ext = _extensions(first, filters), _extensions(second, filters)
+    if first_ext is None or second_ext is None:
+        return True
+    return bool(first_ext & second_ext)
+
+
+def _extensions(filter_names: Set[str], filters: Dict[str, Any]) -> Optional[Set[str]]:
+    """Extensions a set of filters allows (all filters must match), or None for any."""
+    allowed = None
+    for name in filter_names:
+        extensions = filters.get(name, {}).get('extensions') if isinstance(filters.get(name), dict) else None
+        if isinstance(extensions, list):
+            lowered = {str(e).lower() for e in extensions}
+            allowed = lowered if allowed is None else allowed & lowered
+    return allowed
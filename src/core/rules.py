@@ -0,0 +1,325 @@
+"""
+Rules Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module runs the user's own organize rules, kept in the "rules" list of
+the user settings (see core.settings). A rule says which files it is for
+and where they go:
+
+    {"name": "Invoices", "folder": "~/Downloads", "extensions": ["pdf"],
+     "content_contains": "invoice", "destination": "~/Documents/Finance/{year}"}
+
+Conditions (all optional; every one given must hold):
+
+- folder: only files in this folder (and below), e.g. one watched folder
+- extensions: the file's extension is one of these
+- name_pattern: a glob on the file name ("*invoice*", case-insensitive)
+- content_contains: the file's text contains this (PDF, Word, text files)
+- min_size / max_size: bounds in bytes
+
+The destination is a template with {year}, {month}, {day} (the file's
+modification date), {name}, {stem}, {ext} and {parent}. An absolute
+destination ("~/..." included) is used as is, a relative one is placed
+under base_destination; without one the rule's category is looked up in
+the settings' folder_templates. The classifier tries the enabled rules, in
+order, before its own rules and the AI; the first rule that matches wins.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import copy
+import fnmatch
+import logging
+import re
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .settings import SettingsStore
+from .text_extractor import TextExtractor
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+MODEL_NAME = 'user-rules'
+RULE_KEYS = {'name', 'enabled', 'folder', 'extensions', 'name_pattern', 'content_contains',
+             'min_size', 'max_size', 'destination', 'category'}
+TEMPLATE_PLACEHOLDERS = {'year', 'month', 'day', 'name', 'stem', 'ext', 'parent'}
+
+_PLACEHOLDER = re.compile(r'\{([^{}]*)\}')
+
+
+def validate_rule(rule: Any) -> Dict[str, Any]:
+    """
+    Check a rule and normalize it (extensions lower-case, without dots).
+
+    Args:
+        rule (Dict): Rule as the user wrote it
+
+    Returns:
+        Dict: The normalized rule
+
+    Raises:
+        ValueError: Naming what is wrong with the rule
+    """
+    if not isinstance(rule, dict):
+        raise ValueError("A rule must be an object")
+    unknown = sorted(set(rule) - RULE_KEYS)
+    if unknown:
+        raise ValueError(f"Unknown rule key(s): {', '.join(unknown)}")
+    name = rule.get('name')
+    if not isinstance(name, str) or not name.strip():
+        raise ValueError("A rule needs a name")
+    normalized = copy.deepcopy(rule)
+    normalized['name'] = name.strip()
+    normalized['enabled'] = bool(rule.get('enabled', True))
+
+    extensions = rule.get('extensions')
+    if extensions is not None:
+        if isinstance(extensions, str):
+            extensions = [extensions]
+        if not isinstance(extensions, list) or not all(isinstance(e, str) and e.strip('.') for e in extensions):
+            raise ValueError(f"Rule '{name}': 'extensions' must be a list such as [\"pdf\"]")
+        normalized['extensions'] = [e.strip().lstrip('.').lower() for e in extensions]
+    for key in ('folder', 'name_pattern', 'content_contains', 'category'):
+        if rule.get(key) is not None and (not isinstance(rule[key], str) or not rule[key].strip()):
+            raise ValueError(f"Rule '{name}': '{key}' must be non-empty text")
+    for key in ('min_size', 'max_size'):
+        value = rule.get(key)
+        if value is not None and (not isinstance(value, int) or isinstance(value, bool) or value < 0):
+            raise ValueError(f"Rule '{name}': '{key}' must be a number of bytes")
+    if rule.get('min_size') is not None and rule.get('max_size') is not None and rule['min_size'] > rule['max_size']:
+        raise ValueError(f"Rule '{name}': 'min_size' is larger than 'max_size'")
+
+    destination = rule.get('destination')
+    if destination is not None:
+        if not isinstance(destination, str) or not destination.strip():
+            raise ValueError(f"Rule '{name}': 'destination' must be a folder template")
+        unknown = unknown_placeholders(destination)
+        if unknown:
+            raise ValueError(f"Rule '{name}': unknown placeholder(s) {', '.join('{' + u + '}' for u in unknown)} "
+                             f"(use {', '.join(sorted(TEMPLATE_PLACEHOLDERS))})")
+        if '..' in Path(destination).parts:
+            raise ValueError(f"Rule '{name}': 'destination' cannot contain '..'")
+    elif not rule.get('category'):
+        raise ValueError(f"Rule '{name}': needs a 'destination' (or a 'category' with a folder template)")
+    return normalized
+
+
+def render_template(template: str, path: Path) -> str:
+    """A destination template filled in for a file (see the module docstring)."""
+    modified = datetime.fromtimestamp(path.stat().st_mtime)
+    values = {'year': f"{modified.year:04d}", 'month': f"{modified.month:02d}", 'day': f"{modified.day:02d}",
+              'name': path.name, 'stem': path.stem, 'ext': path.suffix.lower().lstrip('.') or 'none',
+              'parent': path.parent.name}
+    return _PLACEHOLDER.sub(lambda m: values.get(m.group(1), m.group(0)), template).rstrip('/\\')
+
+
+def unknown_placeholders(template: str) -> List[str]:
+    """The placeholders of a template that render_template() does not fill in."""
+    return sorted(set(_PLACEHOLDER.findall(template)) - TEMPLATE_PLACEHOLDERS)
+
+
+class UserRules:
+    """The user's rules, read from the settings file (and re-read when it changes)."""
+
+    def __init__(self, config, store: Optional[SettingsStore] = None):
+        """
+        Initialize the rules engine.
+
+        Args:
+            config: Configuration object (base_destination, text_extract_limit)
+            store (SettingsStore, optional): Settings holding the rules; default: the user's
+        """
+        self.config = config
+        self.store = store or SettingsStore()
+        self._loaded: Optional[Tuple[Optional[int], List[Dict[str, Any]], Dict[str, str]]] = None
+        self._text = None
+
+    # ==================== Managing rules ====================
+
+    def list_rules(self) -> List[Dict[str, Any]]:
+        """The rules, in the order they are tried."""
+        return copy.deepcopy(self._current()[0])
+
+    def add_rule(self, rule: Dict[str, Any], position: Optional[int] = None) -> Dict[str, Any]:
+        """
+        Add a rule, or replace the rule with the same name.
+
+        Args:
+            rule (Dict): Rule (see the module docstring)
+            position (int, optional): Index to insert a new rule at; default: last
+
+        Returns:
+            Dict: 'success', 'message' and the saved 'rule'
+
+        Raises:
+            ValueError: If the rule is invalid
+        """
+        rule = validate_rule(rule)
+        rules = self.list_rules()
+        names = [existing['name'].lower() for existing in rules]
+        if rule['name'].lower() in names:
+            rules[names.index(rule['name'].lower())] = rule
+            message = f"Replaced rule '{rule['name']}'"
+        else:
+            rules.insert(len(rules) if position is None else position, rule)
+            message = f"Added rule '{rule['name']}'"
+        self._save(rules)
+        return {'success': True, 'message': message, 'rule': rule}
+
+    def remove_rule(self, name: str) -> Dict[str, Any]:
+        """Remove the rule with this name."""
+        rules = self.list_rules()
+        kept = [rule for rule in rules if rule['name'].lower() != name.strip().lower()]
+        if len(kept) == len(rules):
+            return {'success': False, 'message': f"No rule named '{name}'"}
+        self._save(kept)
+        return {'success': True, 'message': f"Removed rule '{name}'"}
+
+    def test_rule(self, path: str, rule: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Show what the rules would do with a file, without moving it.
+
+        Args:
+            path (str): File to try the rules on
+            rule (Dict, optional): Try only this (unsaved) rule
+
+        Returns:
+            Dict: 'path', 'matched' (the first matching rule's name or None),
+                  'destination', 'classification' and 'checked' ({"name",
+                  "matched", "reason"} for each rule tried)
+
+        Raises:
+            FileNotFoundError: If the file does not exist
+            ValueError: If the given rule is invalid
+        """
+        file_path = Path(path).expanduser()
+        if not file_path.is_file():
+            raise FileNotFoundError(f"File not found: {path}")
+        rules = [validate_rule(rule)] if rule is not None else self._current()[0]
+        checked = []
+        for candidate in rules:
+            if not candidate['enabled']:
+                checked.append({'name': candidate['name'], 'matched': False, 'reason': 'disabled'})
+                continue
+            reason = self._mismatch(candidate, file_path)
+            checked.append({'name': candidate['name'], 'matched': reason is None, 'reason': reason or 'matches'})
+            if reason is None:
+                classification = self._classification(candidate, file_path)
+                return {'path': str(file_path), 'matched': candidate['name'],
+                        'destination': self._destination(classification), 'classification': classification,
+                        'checked': checked}
+        return {'path': str(file_path), 'matched': None, 'destination': None, 'classification': None,
+                'checked': checked}
+
+    # ==================== Classifying ====================
+
+    def classify(self, file_path: str) -> Optional[Dict[str, Any]]:
+        """
+        Classify a file by the first enabled rule it matches.
+
+        Returns:
+            Optional[Dict]: A classifier result ('method': 'user-rules', 'rule'),
+                            or None when no rule matches
+        """
+        path = Path(file_path)
+        try:
+            rules = self._current()[0]
+        except ConfigurationError as e:
+            logger.warning(f"User rules not applied: {e}")
+            return None
+        for rule in rules:
+            if not rule['enabled']:
+                continue
+            try:
+                if self._mismatch(rule, path) is None:
+                    return self._classification(rule, path)
+            except OSError as e:
+                logger.debug(f"Rule '{rule['name']}' skipped {path}: {e}")
+        return None
+
+    def _mismatch(self, rule: Dict[str, Any], path: Path) -> Optional[str]:
+        """Why a rule does not match a file (None when it does)."""
+        if rule.get('folder'):
+            folder = Path(rule['folder']).expanduser().resolve()
+            if folder not in path.resolve().parents:
+                return f"not in {rule['folder']}"
+        if rule.get('extensions') and path.suffix.lower().lstrip('.') not in rule['extensions']:
+            return f"extension is not {', '.join(rule['extensions'])}"
+        if rule.get('name_pattern') and not fnmatch.fnmatch(path.name.lower(), rule['name_pattern'].lower()):
+            return f"name does not match {rule['name_pattern']}"
+        size = path.stat().st_size
+        if rule.get('min_size') is not None and size < rule['min_size']:
+            return f"smaller than {rule['min_size']} bytes"
+        if rule.get('max_size') is not None and size > rule['max_size']:
+            return f"larger than {rule['max_size']} bytes"
+        if rule.get('content_contains'):
+            text = self._extract_text(path) or ''
+            if rule['content_contains'].lower() not in text.lower():
+                return f"text does not contain '{rule['content_contains']}'"
+        return None
+
+    def _classification(self, rule: Dict[str, Any], path: Path) -> Dict[str, Any]:
+        template = rule.get('destination') or self._current()[1].get(rule.get('category') or '')
+        if not template:
+            raise ValueError(f"Rule '{rule['name']}': no folder template for category '{rule.get('category')}'")
+        folder = render_template(template, path)
+        classification = {
+            'category': rule.get('category') or rule['name'],
+            'suggested_path': folder + '/',
+            'rename': None,
+            'reason': f"Matched your rule '{rule['name']}'",
+            'confidence': 'high',
+            'method': MODEL_NAME,
+            'rule': rule['name'],
+        }
+        target = Path(folder).expanduser()
+        if target.is_absolute():
+            # Moved to exactly that folder rather than under base_destination
+            classification['destination_root'] = str(target.parent)
+            classification['suggested_path'] = target.name + '/'
+        return classification
+
+    def _destination(self, classification: Dict[str, Any]) -> str:
+        root = classification.get('destination_root') or str(Path(self.config.base_destination).expanduser())
+        return str(Path(root) / classification['suggested_path'])
+
+    def _extract_text(self, path: Path) -> Optional[str]:
+        if self._text is None:
+            self._text = TextExtractor(self.config)
+        try:
+            return self._text.extract_file_info(path).get('text_snippet')
+        except Exception as e:
+            logger.debug(f"No text from {path}: {e}")
+            return None
+
+    # ==================== Storage ====================
+
+    def _current(self) -> Tuple[List[Dict[str, Any]], Dict[str, str]]:
+        """The rules and folder templates, re-read when the settings file has changed."""
+        try:
+            stamp = self.store.path.stat().st_mtime_ns
+        except OSError:
+            stamp = None
+        if self._loaded is None or self._loaded[0] != stamp:
+            settings = self.store.load()
+            rules = []
+            for rule in settings.get('rules', []):
+                try:
+                    rules.append(validate_rule(rule))
+                except ValueError as e:
+                    logger.warning(f"Skipping invalid rule: {e}")
+            self._loaded = (stamp, rules, settings.get('folder_templates', {}))
+        return self._loaded[1], self._loaded[2]
+
+    def _save(self, rules: List[Dict[str, Any]]) -> None:
+        self.store.update_settings({'rules': rules})
+        self._loaded = None
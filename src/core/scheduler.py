@@ -0,0 +1,389 @@
+"""
+Scheduler Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module runs organize jobs on a schedule while the app (or the engine
+daemon) is running. A schedule is a folder, a cron expression and the
+job's options:
+
+    {"id", "folder", "cron": "0 18 * * 1-5", "options": {"preview", "deep",
+     "strategy"}, "enabled", "created_at", "next_run", "last_run",
+     "last_job_id", "last_state", "last_message"}
+
+Cron expressions have the usual five fields - minute, hour, day of month,
+month, day of week (0 or 7 is Sunday) - with '*', lists, ranges, '/' steps
+and month and day names, or one of @hourly, @daily, @weekly and @monthly.
+As in cron, when both the day of month and the day of week are given a
+day matching either one counts.
+
+Schedules are kept in data/schedules.json, so they survive restarts.
+Runs missed while the app was closed are not made up for: the next run is
+the next matching minute. A schedule whose previous job is still running
+skips its turn. The listener (and events()) get a "schedule.started" and
+a "schedule.finished" event, the latter with the job's state and summary.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import copy
+import json
+import logging
+import os
+import threading
+import uuid
+from collections import deque
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Callable, Dict, Any, List, Optional, Set
+
+from .jobs import JobManager, FINISHED_STATES
+from .journal import PROJECT_ROOT
+
+logger = logging.getLogger(__name__)
+
+
+EVENT_STARTED = 'schedule.started'
+EVENT_FINISHED = 'schedule.finished'
+TICK_SECONDS = 20.0       # How often due schedules are looked for
+KEEP_EVENTS = 200
+SEARCH_YEARS = 5          # An expression with no run in this time never runs (e.g. "0 0 30 2 *")
+OPTION_KEYS = {'preview', 'deep', 'strategy'}
+
+ALIASES = {'@hourly': '0 * * * *', '@daily': '0 0 * * *', '@midnight': '0 0 * * *',
+           '@weekly': '0 0 * * 0', '@monthly': '0 0 1 * *', '@yearly': '0 0 1 1 *', '@annually': '0 0 1 1 *'}
+_MONTHS = {name: number for number, name in enumerate(
+    ('jan', 'feb', 'mar', 'apr', 'may', 'jun', 'jul', 'aug', 'sep', 'oct', 'nov', 'dec'), 1)}
+_DAYS = {name: number for number, name in enumerate(('sun', 'mon', 'tue', 'wed', 'thu', 'fri', 'sat'))}
+
+
+class CronExpression:
+    """A parsed five-field cron expression."""
+
+    def __init__(self, expression: str):
+        """
+        Parse an expression.
+
+        Raises:
+            ValueError: If it is not a valid cron expression
+        """
+        if not isinstance(expression, str) or not expression.strip():
+            raise ValueError("A schedule needs a cron expression")
+        self.expression = expression.strip()
+        fields = ALIASES.get(self.expression.lower(), self.expression).split()
+        if len(fields) != 5:
+            raise ValueError(f"Cron expression {self.expression!r} needs 5 fields "
+                             f"(minute hour day-of-month month day-of-week)")
+        self.minutes = self._field(fields[0], 0, 59, 'minute')
+        self.hours = self._field(fields[1], 0, 23, 'hour')
+        self.days = self._field(fields[2], 1, 31, 'day of month')
+        self.months = self._field(fields[3], 1, 12, 'month', _MONTHS)
+        weekdays = self._field(fields[4], 0, 7, 'day of week', _DAYS)
+        self.weekdays = {0 if day == 7 else day for day in weekdays}
+        self._any_day = fields[2] == '*'
+        self._any_weekday = fields[4] == '*'
+
+    @staticmethod
+    def _field(text: str, low: int, high: int, label: str, names: Optional[Dict[str, int]] = None) -> Set[int]:
+        def value(token: str) -> int:
+            token = token.lower()
+            if names and token in names:
+                return names[token]
+            if not token.isdigit():
+                raise ValueError(f"Invalid {label} {token!r}")
+            number = int(token)
+            if not low <= number <= high:
+                raise ValueError(f"{label.capitalize()} {number} is out of range {low}-{high}")
+            return number
+
+        values: Set[int] = set()
+        for part in text.split(','):
+            span, _, step_text = part.partition('/')
+            step = 1
+            if step_text:
+                if not step_text.isdigit() or int(step_text) == 0:
+                    raise ValueError(f"Invalid {label} step {step_text!r}")
+                step = int(step_text)
+            if span == '*':
+                start, end = low, high
+            elif '-' in span:
+                first, _, last = span.partition('-')
+                start, end = value(first), value(last)
+                if start > end:
+                    raise ValueError(f"Invalid {label} range {span!r}")
+            else:
+                start = value(span)
+                end = high if step_text else start
+            values.update(range(start, end + 1, step))
+        return values
+
+    def matches_day(self, day: datetime) -> bool:
+        if day.month not in self.months:
+            return False
+        in_month = day.day in self.days
+        in_week = (day.isoweekday() % 7) in self.weekdays
+        if self._any_day or self._any_weekday:
+            return in_month and in_week
+        return in_month or in_week
+
+    def next_after(self, moment: datetime) -> datetime:
+        """
+        The first matching minute after a moment.
+
+        Raises:
+            ValueError: If the expression never matches
+        """
+        day = moment.replace(hour=0, minute=0, second=0, microsecond=0)
+        first = moment.replace(second=0, microsecond=0) + timedelta(minutes=1)
+        for _ in range(366 * SEARCH_YEARS):
+            if self.matches_day(day):
+                for hour in sorted(self.hours):
+                    for minute in sorted(self.minutes):
+                        candidate = day.replace(hour=hour, minute=minute)
+                        if candidate >= first:
+                            return candidate
+            day += timedelta(days=1)
+        raise ValueError(f"Cron expression {self.expression!r} never runs")
+
+
+class Scheduler:
+    """Organize schedules, saved to disk and run by a background thread."""
+
+    def __init__(self, jobs: JobManager, path: Optional[str] = None,
+                 listener: Optional[Callable[[str, Dict[str, Any]], None]] = None,
+                 clock: Callable[[], datetime] = datetime.now):
+        """
+        Initialize the scheduler (schedules are loaded; nothing runs until start()).
+
+        Args:
+            jobs (JobManager): Runs the organize jobs
+            path (str, optional): Schedules file; default: data/schedules.json
+            listener (Callable, optional): Called as (event, payload) when a run starts and finishes
+            clock (Callable): Current local time (for tests)
+        """
+        self.jobs = jobs
+        self.path = Path(path).expanduser() if path else PROJECT_ROOT / "data" / "schedules.json"
+        self.listener = listener
+        self.clock = clock
+        self._schedules: Dict[str, Dict[str, Any]] = {}
+        self._events: deque = deque(maxlen=KEEP_EVENTS)
+        self._event_count = 0
+        self._lock = threading.RLock()
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+        self._load()
+
+    # ==================== Schedules ====================
+
+    def schedule_organize(self, folder: str, cron_expr: str, options: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Organize a folder on a schedule.
+
+        Args:
+            folder (str): Folder to organize
+            cron_expr (str): When, e.g. "30 2 * * *" (every night at 2:30) or "@hourly"
+            options (Dict, optional): Job options: preview, deep, strategy
+
+        Returns:
+            Dict: The new schedule
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+            ValueError: If the expression or an option is invalid
+        """
+        path = Path(folder).expanduser()
+        if not path.is_dir():
+            raise NotADirectoryError(f"Not a folder: {folder}")
+        options = dict(options or {})
+        unknown = sorted(set(options) - OPTION_KEYS)
+        if unknown:
+            raise ValueError(f"Unknown schedule option(s): {', '.join(unknown)}")
+        cron = CronExpression(cron_expr)
+        schedule = {
+            'id': uuid.uuid4().hex[:12],
+            'folder': str(path.resolve()),
+            'cron': cron.expression,
+            'options': {'preview': bool(options.get('preview', False)), 'deep': bool(options.get('deep', False)),
+                        'strategy': options.get('strategy')},
+            'enabled': True,
+            'created_at': self._now().isoformat(timespec='seconds'),
+            'next_run': cron.next_after(self._now()).isoformat(timespec='minutes'),
+            'last_run': None,
+            'last_job_id': None,
+            'last_state': None,
+            'last_message': None,
+        }
+        with self._lock:
+            self._schedules[schedule['id']] = schedule
+            self._save()
+        logger.info(f"Scheduled organizing {schedule['folder']} at '{cron.expression}'")
+        return copy.deepcopy(schedule)
+
+    def list_schedules(self) -> List[Dict[str, Any]]:
+        """Schedules, soonest first."""
+        with self._lock:
+            schedules = [copy.deepcopy(schedule) for schedule in self._schedules.values()]
+        return sorted(schedules, key=lambda schedule: (not schedule['enabled'], schedule['next_run'] or ''))
+
+    def set_enabled(self, schedule_id: str, enabled: bool) -> Dict[str, Any]:
+        """Pause or resume a schedule."""
+        with self._lock:
+            schedule = self._schedules.get(schedule_id)
+            if schedule is None:
+                return {'success': False, 'message': f"No schedule {schedule_id}", 'schedule': None}
+            schedule['enabled'] = bool(enabled)
+            if enabled:
+                schedule['next_run'] = CronExpression(schedule['cron']).next_after(self._now()).isoformat(
+                    timespec='minutes')
+            self._save()
+            snapshot = copy.deepcopy(schedule)
+        return {'success': True, 'message': f"Schedule {schedule_id} {'resumed' if enabled else 'paused'}",
+                'schedule': snapshot}
+
+    def remove_schedule(self, schedule_id: str) -> Dict[str, Any]:
+        """Delete a schedule (a job it started keeps running)."""
+        with self._lock:
+            removed = self._schedules.pop(schedule_id, None)
+            if removed:
+                self._save()
+        if removed is None:
+            return {'success': False, 'message': f"No schedule {schedule_id}"}
+        return {'success': True, 'message': f"Removed the schedule for {removed['folder']}"}
+
+    def events(self, since: int = 0) -> Dict[str, Any]:
+        """
+        Recent schedule events, for front ends that poll.
+
+        Args:
+            since (int): Last event number already seen
+
+        Returns:
+            Dict: 'events' ({"seq", "event", "payload"}) after since, and 'last' (the newest seq)
+        """
+        with self._lock:
+            return {'events': [copy.deepcopy(e) for e in self._events if e['seq'] > since],
+                    'last': self._event_count}
+
+    # ==================== Running ====================
+
+    def start(self) -> None:
+        """Start checking for due schedules in the background."""
+        if self._thread and self._thread.is_alive():
+            return
+        self._stop.clear()
+        self._thread = threading.Thread(target=self._loop, name='scheduler', daemon=True)
+        self._thread.start()
+
+    def stop(self, timeout: float = 5.0) -> None:
+        """Stop the background thread (running jobs are left to the JobManager)."""
+        self._stop.set()
+        if self._thread:
+            self._thread.join(timeout)
+            self._thread = None
+
+    def run_due(self) -> List[str]:
+        """
+        Start the jobs of every schedule that is due.
+
+        Returns:
+            List[str]: IDs of the jobs started
+        """
+        now = self._now()
+        started = []
+        with self._lock:
+            due = [schedule for schedule in self._schedules.values()
+                   if schedule['enabled'] and schedule['next_run']
+                   and datetime.fromisoformat(schedule['next_run']) <= now]
+            for schedule in due:
+                schedule['next_run'] = self._next_run(schedule, now)
+                previous = schedule['last_job_id'] and self.jobs.get_job(schedule['last_job_id'])
+                if previous and previous['state'] not in FINISHED_STATES:
+                    logger.info(f"Schedule {schedule['id']} skipped: its last run is still going")
+                    continue
+                options = schedule['options']
+                job = self.jobs.start_organize(schedule['folder'], options.get('preview', False),
+                                               options.get('deep', False), options.get('strategy'))
+                schedule.update(last_run=now.isoformat(timespec='seconds'), last_job_id=job['id'],
+                                last_state=job['state'], last_message=None)
+                started.append(job['id'])
+                self._emit(EVENT_STARTED, {'schedule_id': schedule['id'], 'folder': schedule['folder'],
+                                           'job_id': job['id']})
+                threading.Thread(target=self._await, args=(schedule['id'], job['id']),
+                                 name=f"schedule-{schedule['id']}", daemon=True).start()
+            if due:
+                self._save()
+        return started
+
+    def _next_run(self, schedule: Dict[str, Any], now: datetime) -> Optional[str]:
+        try:
+            return CronExpression(schedule['cron']).next_after(now).isoformat(timespec='minutes')
+        except ValueError as e:
+            logger.warning(f"Schedule {schedule['id']} disabled: {e}")
+            schedule['enabled'] = False
+            return None
+
+    def _await(self, schedule_id: str, job_id: str) -> None:
+        job = self.jobs.wait(job_id) or {'state': 'failed', 'message': 'Job record lost', 'summary': None}
+        with self._lock:
+            schedule = self._schedules.get(schedule_id)
+            if schedule is not None and schedule['last_job_id'] == job_id:
+                schedule.update(last_state=job['state'], last_message=job.get('message'))
+                self._save()
+            self._emit(EVENT_FINISHED, {'schedule_id': schedule_id, 'job_id': job_id, 'state': job['state'],
+                                        'message': job.get('message'), 'summary': job.get('summary')})
+
+    def _loop(self) -> None:
+        while not self._stop.is_set():
+            try:
+                self.run_due()
+            except Exception as e:
+                logger.error(f"Scheduler check failed: {e}")
+            self._stop.wait(TICK_SECONDS)
+
+    def _emit(self, event: str, payload: Dict[str, Any]) -> None:
+        with self._lock:
+            self._event_count += 1
+            self._events.append({'seq': self._event_count, 'event': event, 'payload': payload})
+        if self.listener is not None:
+            try:
+                self.listener(event, payload)
+            except Exception as e:
+                logger.debug(f"Schedule listener failed: {e}")
+
+    def _now(self) -> datetime:
+        return self.clock().replace(microsecond=0)
+
+    # ==================== Storage ====================
+
+    def _load(self) -> None:
+        try:
+            with open(self.path, 'r', encoding='utf-8') as f:
+                stored = json.load(f)
+        except FileNotFoundError:
+            return
+        except (OSError, json.JSONDecodeError) as e:
+            logger.error(f"Schedules not loaded from {self.path}: {e}")
+            return
+        now = self._now()
+        for schedule in stored.get('schedules', []):
+            try:
+                CronExpression(schedule['cron'])
+            except (KeyError, TypeError, ValueError) as e:
+                logger.warning(f"Skipping invalid schedule {schedule.get('id')}: {e}")
+                continue
+            # Runs missed while the app was closed are not made up for
+            if schedule.get('enabled', True):
+                schedule['next_run'] = self._next_run(schedule, now)
+            self._schedules[schedule['id']] = schedule
+
+    def _save(self) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        partial = self.path.with_name(self.path.name + '.tmp')
+        with open(partial, 'w', encoding='utf-8') as f:
+            json.dump({'version': 1, 'schedules': list(self._schedules.values())}, f, indent=2)
+        os.replace(partial, self.path)
@@ -0,0 +1,216 @@
+"""
+Screenshot Naming Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module recognizes screenshots and suggests a descriptive name for
+them from the text they show, e.g.
+
+    Screenshot 2024-05-02 at 10.31.22.png -> 2024-05-02 Stripe invoice settings.png
+
+Screenshots are recognized by the names the common tools give them (in
+several languages) or by a PNG text chunk naming a screenshot tool. The
+text is read locally with the Tesseract OCR engine; the line that
+dominates the image (large words, near the top, where window and page
+titles are) becomes the name. Without Tesseract, or when no readable line
+is found, screenshots are still filed as screenshots, just not renamed.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import re
+import shutil
+import subprocess
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .file_names import safe_name
+
+logger = logging.getLogger(__name__)
+
+
+SCREENSHOT_EXTENSIONS = {'.png', '.jpg', '.jpeg', '.webp', '.bmp', '.gif', '.heic', '.tif', '.tiff'}
+SCREENSHOT_NAME = re.compile(
+    r'(screenshot|screen shot|screen_shot|capture d.{1,2}[ée]cran|bildschirmfoto|captura de pantalla|'
+    r'schermopname|schermafbeelding|istantanea|captura de ecr[ãa]|snip_\d|cleanshot|scr_\d)',
+    re.IGNORECASE
+)
+SCREENSHOT_TOOLS = (b'screenshot', b'screencapture', b'snipping tool', b'greenshot', b'flameshot',
+                    b'spectacle', b'sharex', b'cleanshot')
+NAME_DATE = re.compile(r'(20\d{2})[-_.]?(\d{2})[-_.]?(\d{2})')
+
+DEFAULT_MAX_WORDS = 6
+MIN_CONFIDENCE = 60           # Tesseract word confidence (0-100)
+TOP_REGION = 0.2              # Titles live in the top fifth of a window
+OCR_TIMEOUT = 30
+_WORD = re.compile(r"[^\W_][\w'&.+-]*", re.UNICODE)
+
+
+def is_screenshot(path: Path) -> bool:
+    """
+    Whether an image looks like a screenshot, by its name or its PNG metadata.
+
+    Args:
+        path (Path): Image file
+
+    Returns:
+        bool: True for screenshots
+    """
+    if path.suffix.lower() not in SCREENSHOT_EXTENSIONS:
+        return False
+    if SCREENSHOT_NAME.search(path.stem):
+        return True
+    if path.suffix.lower() != '.png':
+        return False
+    try:
+        with open(path, 'rb') as f:
+            head = f.read(64 * 1024)
+    except OSError:
+        return False
+    # tEXt / iTXt chunks (e.g. Software, XMP UserComment) come before the pixels
+    chunks = head.split(b'IDAT', 1)[0].lower()
+    return any(tool in chunks for tool in SCREENSHOT_TOOLS)
+
+
+def dominant_line(tsv: str, max_words: int = DEFAULT_MAX_WORDS) -> Optional[str]:
+    """
+    Pick the line of text that best describes a screenshot from Tesseract TSV output.
+
+    Lines are scored by their word height (big text is titles and
+    headings), boosted near the top of the image, and need at least two
+    confidently read words.
+
+    Args:
+        tsv (str): Output of `tesseract IMAGE stdout tsv`
+        max_words (int): Words kept from the chosen line
+
+    Returns:
+        str or None: The line's text, or None if nothing readable was found
+    """
+    lines: Dict[tuple, List[Dict[str, Any]]] = {}
+    image_height = 0
+    for row in tsv.splitlines()[1:]:
+        fields = row.split('\t')
+        if len(fields) < 12:
+            continue
+        try:
+            level, top, height, conf = int(fields[0]), int(fields[7]), int(fields[9]), float(fields[10])
+        except ValueError:
+            continue
+        if level == 1:
+            image_height = max(image_height, height)
+            continue
+        text = fields[11].strip()
+        if level != 5 or conf < MIN_CONFIDENCE or not _WORD.fullmatch(text):
+            continue
+        key = (fields[2], fields[3], fields[4])  # block, paragraph, line
+        lines.setdefault(key, []).append({'text': text, 'top': top, 'height': height,
+                                          'left': int(fields[6])})
+
+    best, best_score = None, 0.0
+    for words in lines.values():
+        if len(words) < 2:
+            continue
+        heights = sorted(w['height'] for w in words)
+        score = heights[len(heights) // 2] * min(len(words), max_words) ** 0.5
+        top = min(w['top'] for w in words)
+        if image_height and top <= image_height * TOP_REGION:
+            score *= 1.5
+        if score > best_score:
+            best, best_score = words, score
+    if best is None:
+        return None
+    best.sort(key=lambda w: w['left'])
+    return ' '.join(w['text'] for w in best[:max_words])
+
+
+class ScreenshotNamer:
+    """
+    Suggests names for screenshots from their text.
+
+    Attributes:
+        config: Configuration object
+        enabled (bool): Whether OCR naming is on (screenshots.ocr_naming)
+        max_words (int): Words of text used in a name
+    """
+
+    def __init__(self, config):
+        """
+        Initialize screenshot namer.
+
+        Args:
+            config: Configuration object
+        """
+        self.config = config
+        settings = getattr(config, 'screenshot_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('ocr_naming', True))
+        self.max_words = int(settings.get('max_words', DEFAULT_MAX_WORDS))
+        self.languages = str(settings.get('languages') or 'eng')
+        self._tesseract = settings.get('tesseract_path') or None
+
+    @property
+    def tesseract(self) -> Optional[str]:
+        """Path of the Tesseract executable, or None if it is not installed."""
+        return self._tesseract if self._tesseract and Path(self._tesseract).exists() else shutil.which('tesseract')
+
+    def read_title(self, path: Path) -> Optional[str]:
+        """
+        OCR a screenshot and return its dominant line of text.
+
+        Args:
+            path (Path): Screenshot
+
+        Returns:
+            str or None: Text, or None without Tesseract or readable text
+        """
+        tesseract = self.tesseract
+        if not self.enabled or tesseract is None:
+            return None
+        try:
+            proc = subprocess.run([tesseract, str(path), 'stdout', '-l', self.languages, 'tsv'],
+                                  capture_output=True, text=True, timeout=OCR_TIMEOUT)
+        except (OSError, subprocess.TimeoutExpired) as e:
+            logger.warning(f"OCR failed for {path}: {e}")
+            return None
+        if proc.returncode != 0:
+            logger.debug(f"Tesseract could not read {path}: {proc.stderr.strip()}")
+            return None
+        return dominant_line(proc.stdout, self.max_words)
+
+    @staticmethod
+    def taken(path: Path) -> datetime:
+        """When a screenshot was taken: the date in its name, else its modification time."""
+        match = NAME_DATE.search(path.stem)
+        if match:
+            try:
+                return datetime(int(match.group(1)), int(match.group(2)), int(match.group(3)))
+            except ValueError:
+                pass
+        return datetime.fromtimestamp(path.stat().st_mtime)
+
+    def suggest_name(self, path: Path) -> Optional[str]:
+        """
+        Suggest '<date> <dominant text><ext>' for a screenshot.
+
+        Args:
+            path (Path): Screenshot
+
+        Returns:
+            str or None: New file name, or None if no text could be read
+        """
+        title = self.read_title(path)
+        if not title:
+            return None
+        title = safe_name(title)
+        title = re.sub(r'\s+', ' ', title).strip(' .')[:60].rstrip()
+        if not title:
+            return None
+        return f"{self.taken(path):%Y-%m-%d} {title}{path.suffix.lower()}"
@@ -0,0 +1,356 @@
+"""
+Search Index Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps a full-text index of the user's files - names, the
+folders they are in, extracted text, and the category, tags and summary
+of their last AI classification - so "that tax PDF from 2022" is found
+without opening a single file.
+
+The index lives in the app database (SQLite FTS5; see
+db_manager.search_documents). index_folder() adds a folder, skipping
+files whose size and modification time have not changed and dropping
+files that are gone; the folder watcher keeps it current as files arrive
+and are organized. On a SQLite build without FTS5 the index is
+unavailable: indexing and searching return success False with a message
+saying so, and the watcher's updates do nothing.
+
+search() reads a plain query the way people type it:
+
+- words naming a file type ("pdf", "photos", "spreadsheet") filter by extension
+- a year ("2022") matches files modified that year or mentioning it
+- filler words ("that", "from") are dropped
+- the remaining words must all match, as word prefixes
+
+    "search": {"text_limit": 20000, "max_results": 50}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import re
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .db_manager import SEARCH_UNAVAILABLE
+from .disk_usage import categories_by_extension
+from .ignore_rules import IgnoreRules
+from .text_extractor import TextExtractor
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_TEXT_LIMIT = 20000   # Characters of text indexed per file
+DEFAULT_MAX_RESULTS = 50
+MAX_RESULTS = 500
+FOLDER_NAMES = 3             # Folders above a file whose names are searchable
+
+# Query words that name a file type
+TYPE_WORDS = {
+    'pdf': ['pdf'], 'pdfs': ['pdf'],
+    'doc': ['doc', 'docx'], 'docs': ['doc', 'docx'], 'word': ['doc', 'docx', 'odt', 'rtf'],
+    'document': ['pdf', 'doc', 'docx', 'odt', 'rtf', 'txt', 'md'],
+    'documents': ['pdf', 'doc', 'docx', 'odt', 'rtf', 'txt', 'md'],
+    'spreadsheet': ['xls', 'xlsx', 'ods', 'csv'], 'spreadsheets': ['xls', 'xlsx', 'ods', 'csv'],
+    'excel': ['xls', 'xlsx'], 'csv': ['csv'],
+    'presentation': ['ppt', 'pptx', 'odp', 'key'], 'slides': ['ppt', 'pptx', 'odp', 'key'],
+    'photo': ['jpg', 'jpeg', 'png', 'heic', 'webp', 'tiff'], 'photos': ['jpg', 'jpeg', 'png', 'heic', 'webp', 'tiff'],
+    'image': ['jpg', 'jpeg', 'png', 'gif', 'heic', 'webp', 'bmp', 'tiff', 'svg'],
+    'images': ['jpg', 'jpeg', 'png', 'gif', 'heic', 'webp', 'bmp', 'tiff', 'svg'],
+    'jpg': ['jpg', 'jpeg'], 'jpeg': ['jpg', 'jpeg'], 'png': ['png'],
+    'video': ['mp4', 'mov', 'mkv', 'avi', 'webm'], 'videos': ['mp4', 'mov', 'mkv', 'avi', 'webm'],
+    'song': ['mp3', 'flac', 'm4a', 'wav', 'ogg'], 'music': ['mp3', 'flac', 'm4a', 'wav', 'ogg'],
+    'zip': ['zip'], 'archive': ['zip', '7z', 'rar', 'tar', 'gz'], 'archives': ['zip', '7z', 'rar', 'tar', 'gz'],
+}
+
+STOPWORDS = frozenset(
+    'a an and any about all at by file files find for from in is it its me my of on or show some that '
+    'the these this those to was were where which with'.split()
+)
+
+_WORD = re.compile(r"[\w'-]+", re.UNICODE)
+_YEAR = re.compile(r"^(19|20)\d{2}$")
+
+
+def parse_query(query: str) -> Dict[str, Any]:
+    """
+    Split a plain query into search terms and the filters its words imply.
+
+    Args:
+        query (str): What the user typed, e.g. "that tax PDF from 2022"
+
+    Returns:
+        Dict: 'terms' (words to match), 'extensions' and 'year' (None when not given)
+    """
+    terms, extensions, year = [], [], None
+    for word in _WORD.findall(query.lower()):
+        word = word.strip("'-")
+        if not word or word in STOPWORDS:
+            continue
+        if word in TYPE_WORDS:
+            extensions += [extension for extension in TYPE_WORDS[word] if extension not in extensions]
+        elif _YEAR.match(word) and year is None:
+            year = int(word)
+        else:
+            terms.append(word)
+    return {'terms': terms, 'extensions': extensions, 'year': year}
+
+
+def _fts_query(terms: List[str]) -> Optional[str]:
+    """FTS5 query matching every term as a word prefix."""
+    return ' '.join('"' + term.replace('"', '""') + '"*' for term in terms) or None
+
+
+class SearchIndex:
+    """
+    Indexes files for full-text and metadata search.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        cache: Classification cache the categories, tags and summaries come from (optional)
+        tags (TagStore, optional): Adds the tags the user set to the indexed ones
+        text_limit (int): Characters of text indexed per file
+        max_results (int): Results returned when the caller gives no limit
+    """
+
+    def __init__(self, config, db_manager, cache=None):
+        """
+        Initialize the index.
+
+        Args:
+            config: Configuration object (reads `search_settings`)
+            db_manager: Database manager instance
+            cache: ClassificationCache to read classifications from (optional)
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.cache = cache
+        settings = getattr(config, 'search_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.text_limit = max(0, int(settings.get('text_limit', DEFAULT_TEXT_LIMIT)))
+        self.max_results = max(1, min(int(settings.get('max_results', DEFAULT_MAX_RESULTS)), MAX_RESULTS))
+        self.extractor = TextExtractor(config)
+        self.extractor.text_extract_limit = self.text_limit
+        self.categories = categories_by_extension(config)
+        self.tags = None
+
+    @property
+    def available(self) -> bool:
+        """Whether the database has a full-text index (SQLite with FTS5)."""
+        return getattr(self.db_manager, 'search_available', True)
+
+    def index_folder(self, folder: str, recursive: bool = True) -> Dict[str, Any]:
+        """
+        Add a folder's files to the index, or bring them up to date.
+
+        Files the ignore rules exclude are not indexed.
+
+        Args:
+            folder (str): Folder to index
+            recursive (bool): Include subfolders
+
+        Returns:
+            Dict: 'success', 'folder', 'files', 'indexed', 'unchanged',
+                  'removed', 'failed', 'seconds' and 'message'
+
+        Raises:
+            NotADirectoryError: If the folder does not exist
+        """
+        root = os.path.abspath(os.path.expanduser(folder))
+        if not os.path.isdir(root):
+            raise NotADirectoryError(f"Not a folder: {root}")
+        if not self.available:
+            return {'success': False, 'folder': root, 'message': SEARCH_UNAVAILABLE}
+        started = time.perf_counter()
+        rules = IgnoreRules.for_folder(root)
+        stamps = self.db_manager.search_document_stamps(root)
+        counts = {'files': 0, 'indexed': 0, 'unchanged': 0, 'removed': 0, 'failed': 0}
+        seen = set()
+
+        for current, dirs, names in os.walk(root):
+            if recursive:
+                dirs[:] = [name for name in dirs if not rules.match(os.path.join(current, name), is_dir=True)]
+            else:
+                dirs[:] = []
+            for name in names:
+                path = os.path.join(current, name)
+                try:
+                    stat = os.stat(path, follow_symlinks=False)
+                except OSError:
+                    continue
+                if not os.path.isfile(path) or os.path.islink(path) or rules.match(path, is_dir=False, stat=stat):
+                    continue
+                counts['files'] += 1
+                seen.add(path)
+                if stamps.get(path) == (stat.st_size, stat.st_mtime):
+                    counts['unchanged'] += 1
+                elif self._index_file(path, stat):
+                    counts['indexed'] += 1
+                else:
+                    counts['failed'] += 1
+
+        for path in stamps:
+            below = os.path.dirname(path) == root or recursive
+            if below and path not in seen:
+                counts['removed'] += self.db_manager.delete_search_documents(path)
+
+        seconds = round(time.perf_counter() - started, 3)
+        logger.info(f"Indexed {root}: {counts['indexed']} new or changed, {counts['removed']} removed ({seconds}s)")
+        return {
+            'success': True,
+            'folder': root,
+            **counts,
+            'seconds': seconds,
+            'message': f"Indexed {counts['indexed']} file{'s' if counts['indexed'] != 1 else ''}; "
+                       f"{counts['unchanged']} unchanged, {counts['removed']} removed",
+        }
+
+    def update_file(self, path: str, classification: Optional[Dict[str, Any]] = None) -> bool:
+        """
+        Index one file again (or drop it if it is gone), e.g. after the watcher saw it.
+
+        Args:
+            path (str): File
+            classification (Dict, optional): Its classification (default: the cached one)
+
+        Returns:
+            bool: Whether the file is in the index now
+        """
+        path = os.path.abspath(path)
+        if not self.available:
+            return False
+        try:
+            stat = os.stat(path)
+        except OSError:
+            self.remove_file(path)
+            return False
+        return os.path.isfile(path) and self._index_file(path, stat, classification)
+
+    def remove_file(self, path: str) -> int:
+        """Drop a file, or a folder and everything below it, from the index; returns files dropped."""
+        path = os.path.abspath(path)
+        if not self.available:
+            return 0
+        return self.db_manager.delete_search_documents(path, below=os.path.isdir(path) or not os.path.exists(path))
+
+    def move_file(self, old_path: str, new_path: str, classification: Optional[Dict[str, Any]] = None) -> bool:
+        """Follow a file that was moved or renamed."""
+        if not self.available:
+            return False
+        self.db_manager.delete_search_documents(os.path.abspath(old_path))
+        return self.update_file(new_path, classification)
+
+    def search(self, query: str = '', filters: Optional[Dict[str, Any]] = None,
+               limit: Optional[int] = None) -> Dict[str, Any]:
+        """
+        Find indexed files.
+
+        Args:
+            query (str): Plain query (see the module docstring); empty: newest files
+            filters (Dict, optional): Any of extension (str or list), category, folder,
+                                      year, modified_after / modified_before (timestamps
+                                      or ISO dates), min_size, max_size and tag; they
+                                      narrow what the query implies
+            limit (int, optional): Most results (default: max_results)
+
+        Returns:
+            Dict: 'success', 'query', 'terms', 'filters' (as applied), 'total',
+                  'results' and 'message'; each result has path, name, folder,
+                  extension, size, modified, category, tags, summary, snippet and score
+        """
+        parsed = parse_query(query or '')
+        if not self.available:
+            return {'success': False, 'query': query, 'terms': parsed['terms'], 'filters': {},
+                    'total': 0, 'results': [], 'message': SEARCH_UNAVAILABLE}
+        applied = self._filters(filters or {}, parsed)
+        limit = max(1, min(int(limit or self.max_results), MAX_RESULTS))
+        year_match = f'"{parsed["year"]}"' if parsed['year'] and 'year' not in (filters or {}) else None
+        found = self.db_manager.search_documents(_fts_query(parsed['terms']), applied, limit, year_match)
+
+        results = [{
+            'path': row['path'], 'name': row['name'], 'folder': row['folder'], 'extension': row['extension'],
+            'size': row['size'],
+            'modified': datetime.fromtimestamp(row['mtime']).isoformat(timespec='seconds') if row['mtime'] else None,
+            'category': row['category'], 'tags': row['tags'], 'summary': row['summary'],
+            'snippet': row['snippet'] or None, 'score': round(-row['score'], 3) if row['score'] else 0.0,
+        } for row in found['results']]
+        return {
+            'success': True,
+            'query': query,
+            'terms': parsed['terms'],
+            'filters': applied,
+            'total': found['total'],
+            'results': results,
+            'message': f"{found['total']} file{'s' if found['total'] != 1 else ''} found",
+        }
+
+    def stats(self) -> Dict[str, Any]:
+        """Files in the index: 'files', 'size', 'updated_at', 'categories' and 'available'."""
+        return {**self.db_manager.search_index_counts(), 'available': self.available}
+
+    def _filters(self, filters: Dict[str, Any], parsed: Dict[str, Any]) -> Dict[str, Any]:
+        applied: Dict[str, Any] = {}
+        extensions = filters.get('extension') or filters.get('extensions') or parsed['extensions']
+        if isinstance(extensions, str):
+            extensions = [extensions]
+        if extensions:
+            applied['extensions'] = [str(extension).lower().lstrip('.') for extension in extensions]
+        year = filters.get('year') or parsed['year']
+        if year:
+            applied['year'] = int(year)
+        if filters.get('folder'):
+            applied['folder'] = os.path.abspath(os.path.expanduser(filters['folder']))
+        for key in ('category', 'tag'):
+            if filters.get(key):
+                applied[key] = str(filters[key])
+        for key in ('modified_after', 'modified_before'):
+            value = filters.get(key)
+            if value:
+                applied[key] = (datetime.fromisoformat(value).timestamp() if isinstance(value, str)
+                                else float(value))
+        for key in ('min_size', 'max_size'):
+            if filters.get(key) is not None:
+                applied[key] = int(filters[key])
+        return applied
+
+    def _index_file(self, path: str, stat: os.stat_result,
+                    classification: Optional[Dict[str, Any]] = None) -> bool:
+        file = Path(path)
+        extension = file.suffix.lower().lstrip('.')
+        if classification is None and self.cache is not None:
+            classification = self.cache.get(path)
+        classification = classification or {}
+        tags = classification.get('tags') or []
+        if isinstance(tags, str):
+            tags = [tag.strip() for tag in tags.split(',')]
+        if self.tags is not None:
+            stored = self.tags.get_tags(path)['tags']
+            tags = stored + [tag for tag in tags if str(tag).casefold() not in {s.casefold() for s in stored}]
+        text = self.extractor.extract_file_info(file)['text_snippet'] if self.text_limit else None
+        if text and text.startswith('[File too large'):
+            text = None
+        folders = [part for part in file.parent.parts[-FOLDER_NAMES:] if part not in (os.sep, file.anchor)]
+        document = {
+            'path': path, 'name': file.name, 'folder': str(file.parent), 'extension': extension,
+            'size': stat.st_size, 'mtime': stat.st_mtime,
+            'year': datetime.fromtimestamp(stat.st_mtime).year,
+            'category': classification.get('category') or self.categories.get(extension),
+            'tags': [str(tag) for tag in tags if str(tag).strip()],
+            'summary': classification.get('summary') or classification.get('reason'),
+        }
+        try:
+            self.db_manager.upsert_search_document(document, text or '', ' '.join(folders))
+            return True
+        except Exception as e:
+            logger.warning(f"Could not index {path}: {e}")
+            return False
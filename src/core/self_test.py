@@ -0,0 +1,241 @@
+"""
+Self Test Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module runs an end-to-end check of the whole stack in a throwaway
+folder: it creates sample files, scans them, classifies them with the
+rule-based (fallback) classifier, builds a plan, applies it and undoes it
+again, and reports pass/fail per subsystem. Support can ask a user to run
+`aifo self-test` and see at a glance which part of their installation is
+broken.
+
+Nothing outside the temporary folder is touched: destinations, trash,
+kept versions and the database are all redirected into it, and AI
+classification is switched off so the test needs no backend.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import shutil
+import tempfile
+import time
+from pathlib import Path
+from typing import Dict, Any, List, Callable
+
+from src.utils.structured_logging import span
+
+logger = logging.getLogger(__name__)
+
+
+# Sample files the test organizes: name -> content
+SAMPLE_FILES = {
+    'quarterly report.pdf': b'%PDF-1.4\n% aifo self-test\n',
+    'holiday.jpg': b'\xff\xd8\xff\xe0 aifo self-test',
+    'song.mp3': b'ID3 aifo self-test',
+    'notes.txt': b'aifo self-test notes\n',
+    'budget.xlsx': b'PK\x03\x04 aifo self-test',
+}
+
+SUBSYSTEMS = ('filesystem', 'database', 'scan', 'classify', 'plan', 'apply', 'undo')
+
+
+class SandboxConfig:
+    """The user's configuration with every location redirected into the sandbox (and AI off unless asked)."""
+
+    def __init__(self, config, root: Path, ai: bool = False):
+        self._config = config
+        self._overrides = {
+            'base_destination': str(root / 'organized'),
+            'trash_dir': str(root / 'trash'),
+            'version_settings': {'enabled': True, 'store_dir': str(root / 'versions')},
+            'watched_folders': [str(root / 'inbox')],
+            'path_blacklist': [],
+            'dry_run': False,
+            'auto_mode': False,
+            'plugin_settings': {'enabled': False},
+            'snapshot_settings': {'enabled': False},
+        }
+        if not ai:
+            self._overrides['enable_ai'] = False
+
+    def get_folder_policy(self, path):
+        return None
+
+    def __getattr__(self, name):
+        if name in self._overrides:
+            return self._overrides[name]
+        return getattr(self._config, name)
+
+
+def run_self_test(config=None, keep: bool = False) -> Dict[str, Any]:
+    """
+    Exercise scan, classify, plan, apply and undo end-to-end.
+
+    Args:
+        config: Configuration object (default: the active configuration)
+        keep (bool): Leave the sandbox folder behind for inspection
+
+    Returns:
+        Dict: Result with 'success', 'message', 'sandbox' and 'subsystems'
+              (one {'name', 'status', 'message', 'seconds'} per subsystem;
+              status is 'passed', 'failed' or 'skipped' after a failure)
+    """
+    if config is None:
+        from src.config import get_config
+        config = get_config()
+
+    root = Path(tempfile.mkdtemp(prefix='aifo-self-test-'))
+    results: List[Dict[str, Any]] = []
+    context: Dict[str, Any] = {'root': root, 'config': SandboxConfig(config, root)}
+
+    with span('self_test', logger, logging.INFO, sandbox=str(root)) as trace:
+        for name in SUBSYSTEMS:
+            if any(r['status'] == 'failed' for r in results):
+                results.append({'name': name, 'status': 'skipped',
+                                'message': 'Skipped after an earlier failure', 'seconds': 0.0})
+                continue
+            results.append(_run_check(name, _CHECKS[name], context))
+
+        failed = [r['name'] for r in results if r['status'] == 'failed']
+        trace.record(failed=failed)
+        if failed:
+            trace.fail(f"Failed: {', '.join(failed)}")
+
+    db = context.get('db')
+    if db is not None:
+        try:
+            db.cleanup()
+        except Exception as e:
+            logger.debug(f"Self-test database cleanup failed: {e}")
+    if not keep:
+        shutil.rmtree(root, ignore_errors=True)
+
+    passed = sum(1 for r in results if r['status'] == 'passed')
+    message = (f"All {passed} subsystems passed" if not failed
+               else f"{passed} of {len(results)} subsystems passed; failed: {', '.join(failed)}")
+    return {
+        'success': not failed,
+        'message': message,
+        'sandbox': str(root) if keep else None,
+        'subsystems': results
+    }
+
+
+def _run_check(name: str, check: Callable[[Dict[str, Any]], str], context: Dict[str, Any]) -> Dict[str, Any]:
+    start = time.perf_counter()
+    try:
+        message = check(context)
+        status = 'passed'
+    except Exception as e:
+        logger.warning(f"Self-test {name} failed: {e}", exc_info=True)
+        message = f"{type(e).__name__}: {e}"
+        status = 'failed'
+    return {'name': name, 'status': status, 'message': message,
+            'seconds': round(time.perf_counter() - start, 4)}
+
+
+# ==================== Checks ====================
+# Each check raises on failure and returns a short description on success.
+
+def _check_filesystem(context: Dict[str, Any]) -> str:
+    inbox = context['root'] / 'inbox'
+    inbox.mkdir()
+    for name, content in SAMPLE_FILES.items():
+        (inbox / name).write_bytes(content)
+    for name, content in SAMPLE_FILES.items():
+        if (inbox / name).read_bytes() != content:
+            raise AssertionError(f"{name} did not read back what was written")
+    context['inbox'] = inbox
+    return f"Created {len(SAMPLE_FILES)} sample files in {inbox}"
+
+
+def _check_database(context: Dict[str, Any]) -> str:
+    from src.core.db_manager import DatabaseManager
+    from src.core.journal import OperationJournal
+
+    db = DatabaseManager(str(context['root'] / 'db' / 'self_test.db'))
+    context['db'] = db
+    db.list_operations(1)
+    context['journal'] = OperationJournal(db)
+    return "Database created and queried"
+
+
+def _check_scan(context: Dict[str, Any]) -> str:
+    from src.core.actions import ActionManager
+    from src.core.classifier import FileClassifier
+    from src.core.plans import OrganizePlanner
+
+    config = context['config']
+    context['action_manager'] = ActionManager(config, context['db'], dry_run=False, journal=context['journal'])
+    context['classifier'] = FileClassifier(config, None)
+    context['planner'] = OrganizePlanner(config, context['classifier'], context['action_manager'])
+
+    inventory = context['planner'].scan(str(context['inbox']))
+    if inventory['files'] != len(SAMPLE_FILES):
+        raise AssertionError(f"Scan found {inventory['files']} of {len(SAMPLE_FILES)} files")
+    return f"Found {inventory['files']} files"
+
+
+def _check_classify(context: Dict[str, Any]) -> str:
+    categories = {}
+    for name in SAMPLE_FILES:
+        result = context['classifier'].classify(str(context['inbox'] / name))
+        if not result.get('category'):
+            raise AssertionError(f"No category for {name}: {result.get('reason')}")
+        categories[name] = result['category']
+    unsorted = [name for name, category in categories.items() if category == 'Unsorted']
+    if len(unsorted) == len(categories):
+        raise AssertionError("The rule-based classifier recognized none of the sample files")
+    return ', '.join(f"{name} -> {category}" for name, category in categories.items())
+
+
+def _check_plan(context: Dict[str, Any]) -> str:
+    plan = context['planner'].build_plan(str(context['inbox']))
+    if not plan['items']:
+        raise AssertionError("The plan moves nothing")
+    if plan['conflicts']:
+        raise AssertionError(f"The plan has {plan['conflicts']} conflict(s) in an empty destination")
+    context['plan'] = plan
+    return f"{len(plan['items'])} planned move(s), {plan['unchanged']} unchanged"
+
+
+def _check_apply(context: Dict[str, Any]) -> str:
+    result = context['planner'].apply_plan(context['plan'])
+    if not result['success']:
+        raise AssertionError(result['message'] + ''.join(f"; {f['file']}: {f['message']}" for f in result['failed']))
+    missing = [entry['new_path'] for entry in result['applied']
+               if not entry['new_path'] or not Path(entry['new_path']).exists()]
+    if missing:
+        raise AssertionError(f"Reported as moved but not found: {', '.join(missing)}")
+    if result['operation_id'] is None:
+        raise AssertionError("The applied plan was not journaled")
+    context['operation_id'] = result['operation_id']
+    return result['message']
+
+
+def _check_undo(context: Dict[str, Any]) -> str:
+    result = context['journal'].undo(context['operation_id'])
+    if not result['success']:
+        raise AssertionError(result['message'])
+    missing = [name for name in SAMPLE_FILES if not (context['inbox'] / name).exists()]
+    if missing:
+        raise AssertionError(f"Not restored: {', '.join(missing)}")
+    return result['message']
+
+
+_CHECKS: Dict[str, Callable[[Dict[str, Any]], str]] = {
+    'filesystem': _check_filesystem,
+    'database': _check_database,
+    'scan': _check_scan,
+    'classify': _check_classify,
+    'plan': _check_plan,
+    'apply': _check_apply,
+    'undo': _check_undo,
+}
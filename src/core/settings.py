@@ -0,0 +1,347 @@
+"""
+App Settings Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps the user's own settings - provider API keys, the default
+AI tier, destination folder templates, exclusion patterns and organize
+rules (see core.rules) - in a settings.json in the per-user app config directory, apart from the
+project's config.json:
+
+- Windows: %APPDATA%/AI File Organiser
+- macOS: ~/Library/Application Support/AI File Organiser
+- Linux: $XDG_CONFIG_HOME/ai-file-organiser (~/.config/ai-file-organiser)
+
+The file carries a schema "version". Loading an older file runs the
+migrations up to SETTINGS_VERSION, keeps a copy of the original beside it
+(settings.v<N>.json) and saves the result; a file from a newer version is
+refused rather than guessed at. Saves are atomic and the file is readable
+by its owner only.
+
+API keys are kept in the OS keychain when there is one (see core.keychain);
+the file then only records which providers have a key ("<keychain>"), and
+keys an older file held in plain text are moved there on load. Without a
+keychain they stay in the owner-only file.
+
+    {"version": 1, "api_keys": {"openai": "<keychain>"}, "default_tier": "local",
+     "folder_templates": {"Documents": "Documents/{year}"},
+     "exclusions": ["*.tmp", "node_modules"],
+     "rules": [{"name": "Invoices", "extensions": ["pdf"], "destination": "Finance/{year}"}],
+     "notifications": {"enabled": true, "run_completed": true, "file_moved": false, "conflict": true},
+     "profiles": [{"name": "photos", "target_root": "~/Pictures/Library", "tier": "rules"}],
+     "taxonomy": [{"name": "Invoices", "parent": "Documents", "destination": "Finance/{year}"}]}
+
+"notifications" switches desktop notifications (see core.notifications)
+on or off, altogether and per kind of event. "profiles" are named ways of
+organizing a run can pick (see core.profiles). "taxonomy" is the user's
+own category tree, which classifications are mapped onto (see core.taxonomy).
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import copy
+import json
+import logging
+import os
+import shutil
+import sys
+import threading
+from pathlib import Path
+from typing import Dict, Any, Callable, Optional
+
+from src.utils.error_handler import ConfigurationError
+from .keychain import KeychainError, delete_secret, get_secret, keychain_available, secret_environment, store_secret
+
+logger = logging.getLogger(__name__)
+
+
+APP_NAME = 'AI File Organiser'
+SETTINGS_FILE = 'settings.json'
+SETTINGS_VERSION = 1
+TIERS = ('rules', 'local', 'cloud')
+KEYCHAIN_MARKER = '<keychain>'  # An api_keys value saying the key is in the OS keychain
+
+DEFAULT_SETTINGS: Dict[str, Any] = {
+    'version': SETTINGS_VERSION,
+    'api_keys': {},
+    'default_tier': 'local',
+    'folder_templates': {},
+    'exclusions': [],
+    'rules': [],
+    'notifications': {'enabled': True, 'run_completed': True, 'file_moved': True, 'conflict': True},
+    'profiles': [],
+    'taxonomy': [],
+}
+
+
+def app_config_dir() -> Path:
+    """The per-user directory for this application's settings (AIFO_CONFIG_DIR overrides it)."""
+    override = os.environ.get('AIFO_CONFIG_DIR')
+    if override:
+        return Path(override).expanduser()
+    if sys.platform == 'win32':
+        return Path(os.environ.get('APPDATA') or Path.home() / 'AppData' / 'Roaming') / APP_NAME
+    if sys.platform == 'darwin':
+        return Path.home() / 'Library' / 'Application Support' / APP_NAME
+    base = os.environ.get('XDG_CONFIG_HOME') or str(Path.home() / '.config')
+    return Path(base) / 'ai-file-organiser'
+
+
+def _from_unversioned(settings: Dict[str, Any]) -> Dict[str, Any]:
+    """Files written before versioning: a flat "exclude" list and "tier" key."""
+    migrated = {key: value for key, value in settings.items() if key not in ('exclude', 'tier')}
+    if 'exclude' in settings and 'exclusions' not in settings:
+        migrated['exclusions'] = settings['exclude']
+    if 'tier' in settings and 'default_tier' not in settings:
+        migrated['default_tier'] = settings['tier']
+    return migrated
+
+
+# Schema migrations: version N -> N + 1
+MIGRATIONS: Dict[int, Callable[[Dict[str, Any]], Dict[str, Any]]] = {
+    0: _from_unversioned,
+}
+
+
+def migrate(settings: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Bring a settings document up to SETTINGS_VERSION.
+
+    Args:
+        settings (Dict): Settings as read from disk
+
+    Returns:
+        Dict: Migrated settings, with defaults for anything missing
+
+    Raises:
+        ConfigurationError: If the document is from a newer version of the app
+    """
+    version = settings.get('version', 0)
+    if not isinstance(version, int) or version < 0:
+        raise ConfigurationError(f"Invalid settings version: {version!r}", 'version')
+    if version > SETTINGS_VERSION:
+        raise ConfigurationError(f"Settings were written by a newer version (schema {version}, "
+                                 f"this version understands {SETTINGS_VERSION})", 'version')
+    migrated = copy.deepcopy(settings)
+    while version < SETTINGS_VERSION:
+        migrated = MIGRATIONS[version](migrated)
+        version += 1
+        migrated['version'] = version
+    return {**copy.deepcopy(DEFAULT_SETTINGS), **migrated}
+
+
+def validate_settings(settings: Dict[str, Any]) -> None:
+    """
+    Check the types and values of a settings document.
+
+    Raises:
+        ConfigurationError: Naming the first invalid key
+    """
+    if not isinstance(settings.get('api_keys'), dict) or \
+            not all(isinstance(value, str) for value in settings['api_keys'].values()):
+        raise ConfigurationError("'api_keys' must map provider names to keys", 'api_keys')
+    if settings.get('default_tier') not in TIERS:
+        raise ConfigurationError(f"'default_tier' must be one of: {', '.join(TIERS)}", 'default_tier')
+    templates = settings.get('folder_templates')
+    if not isinstance(templates, dict) or not all(isinstance(value, str) for value in templates.values()):
+        raise ConfigurationError("'folder_templates' must map categories to folder templates", 'folder_templates')
+    exclusions = settings.get('exclusions')
+    if not isinstance(exclusions, list) or not all(isinstance(value, str) and value for value in exclusions):
+        raise ConfigurationError("'exclusions' must be a list of patterns", 'exclusions')
+    rules = settings.get('rules')
+    if not isinstance(rules, list) or not all(isinstance(rule, dict) for rule in rules):
+        raise ConfigurationError("'rules' must be a list of rule objects", 'rules')
+    profiles = settings.get('profiles')
+    if not isinstance(profiles, list) or not all(isinstance(profile, dict) for profile in profiles):
+        raise ConfigurationError("'profiles' must be a list of profile objects", 'profiles')
+    taxonomy = settings.get('taxonomy')
+    if not isinstance(taxonomy, list) or not all(isinstance(category, dict) for category in taxonomy):
+        raise ConfigurationError("'taxonomy' must be a list of category objects", 'taxonomy')
+    notifications = settings.get('notifications')
+    if not isinstance(notifications, dict) or not set(notifications) <= set(DEFAULT_SETTINGS['notifications']) \
+            or not all(isinstance(value, bool) for value in notifications.values()):
+        raise ConfigurationError(f"'notifications' must switch {', '.join(DEFAULT_SETTINGS['notifications'])} "
+                                 "on (true) or off (false)", 'notifications')
+
+
+def mask_secret(value: str) -> str:
+    """A key as the UI shows it: only its last four characters."""
+    return '•' * 8 + value[-4:] if len(value) > 8 else '•' * len(value)
+
+
+def api_key_secret(provider: str) -> str:
+    """Keychain name of a provider's API key."""
+    return f"api_key.{provider}"
+
+
+def child_environment() -> Dict[str, str]:
+    """This process's environment plus the stored API keys, for starting an engine process."""
+    environment = dict(os.environ)
+    try:
+        environment.update(SettingsStore().secret_environment())
+    except (ConfigurationError, ValueError) as e:
+        logger.warning(f"API keys not passed on: {e}")
+    return environment
+
+
+class SettingsStore:
+    """The user's settings file, loaded (and migrated) once and saved on every update."""
+
+    def __init__(self, path: Optional[str] = None, use_keychain: Optional[bool] = None):
+        """
+        Initialize the settings store.
+
+        Args:
+            path (str, optional): Settings file; default: settings.json in app_config_dir()
+            use_keychain (bool, optional): Keep API keys in the OS keychain;
+                                           default: when one is available
+        """
+        self.path = Path(path).expanduser() if path else app_config_dir() / SETTINGS_FILE
+        self.use_keychain = keychain_available() if use_keychain is None else use_keychain
+        self._settings: Optional[Dict[str, Any]] = None
+        self._lock = threading.Lock()
+
+    def load(self) -> Dict[str, Any]:
+        """
+        Read the settings file, migrating and re-saving an older one.
+
+        Returns:
+            Dict: Settings (defaults when there is no file yet)
+
+        Raises:
+            ConfigurationError: If the file is unreadable, invalid or from a newer version
+        """
+        if not self.path.exists():
+            self._settings = copy.deepcopy(DEFAULT_SETTINGS)
+            return copy.deepcopy(self._settings)
+        try:
+            with open(self.path, 'r', encoding='utf-8') as f:
+                stored = json.load(f)
+        except (OSError, json.JSONDecodeError) as e:
+            raise ConfigurationError(f"Cannot read settings file {self.path}: {e}")
+        if not isinstance(stored, dict):
+            raise ConfigurationError(f"Settings file {self.path} is not a JSON object")
+        settings = migrate(stored)
+        validate_settings(settings)
+        migrated = stored.get('version', 0) != settings['version']
+        if migrated:
+            backup = self.path.with_name(f"{self.path.stem}.v{stored.get('version', 0)}{self.path.suffix}")
+            shutil.copy2(self.path, backup)
+            logger.info(f"Migrated settings from schema {stored.get('version', 0)} to {settings['version']} "
+                        f"(original kept as {backup.name})")
+        if self._keys_to_keychain(settings['api_keys']) or migrated:
+            self._write(settings)
+        self._settings = settings
+        return copy.deepcopy(settings)
+
+    def get_settings(self, reveal_secrets: bool = False) -> Dict[str, Any]:
+        """
+        Current settings.
+
+        Args:
+            reveal_secrets (bool): Return API keys in full instead of masked
+
+        Returns:
+            Dict: Settings document
+        """
+        with self._lock:
+            if self._settings is None:
+                self.load()
+            settings = copy.deepcopy(self._settings)
+        keys = {}
+        for provider, key in settings['api_keys'].items():
+            if key == KEYCHAIN_MARKER:
+                key = get_secret(api_key_secret(provider)) or ''
+            keys[provider] = key if reveal_secrets else mask_secret(key)
+        settings['api_keys'] = keys
+        return settings
+
+    def get(self, key: str, default: Any = None) -> Any:
+        """One setting as saved (without looking API keys up in the keychain)."""
+        with self._lock:
+            if self._settings is None:
+                self.load()
+            return copy.deepcopy(self._settings.get(key, default))
+
+    def secret_environment(self) -> Dict[str, str]:
+        """Environment variables passing the keychain's API keys to a child process."""
+        with self._lock:
+            if self._settings is None:
+                self.load()
+            providers = [provider for provider, key in self._settings['api_keys'].items() if key == KEYCHAIN_MARKER]
+        return secret_environment(api_key_secret(provider) for provider in providers)
+
+    def update_settings(self, changes: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Change some settings and save the file.
+
+        Dictionaries are merged key by key (an API key set to None or "" is
+        removed); lists and other values replace what was there.
+
+        Args:
+            changes (Dict): Settings to change
+
+        Returns:
+            Dict: The saved settings, API keys masked
+
+        Raises:
+            ConfigurationError: If a key is unknown or a value is invalid (nothing is saved)
+        """
+        unknown = sorted(set(changes) - set(DEFAULT_SETTINGS) - {'version'})
+        if unknown:
+            raise ConfigurationError(f"Unknown setting(s): {', '.join(unknown)}", unknown[0])
+        with self._lock:
+            if self._settings is None:
+                self.load()
+            updated = copy.deepcopy(self._settings)
+            for key, value in changes.items():
+                if key == 'version':
+                    continue
+                if key == 'api_keys' and isinstance(value, dict):
+                    for provider, secret in value.items():
+                        if not secret and updated['api_keys'].pop(provider, None) == KEYCHAIN_MARKER:
+                            delete_secret(api_key_secret(provider))
+                        elif secret:
+                            updated['api_keys'][provider] = secret
+                elif isinstance(value, dict) and isinstance(updated.get(key), dict):
+                    updated[key].update(copy.deepcopy(value))
+                else:
+                    updated[key] = copy.deepcopy(value)
+            validate_settings(updated)
+            self._keys_to_keychain(updated['api_keys'])
+            self._write(updated)
+            self._settings = updated
+        return self.get_settings()
+
+    def _keys_to_keychain(self, keys: Dict[str, str]) -> bool:
+        """Move plain-text keys into the keychain, in place; returns whether any moved."""
+        if not self.use_keychain:
+            return False
+        moved = False
+        for provider, key in list(keys.items()):
+            if key == KEYCHAIN_MARKER:
+                continue
+            try:
+                store_secret(api_key_secret(provider), key)
+            except (KeychainError, ValueError) as e:
+                logger.warning(f"Keeping the {provider} API key in {self.path.name}: {e}")
+                continue
+            keys[provider] = KEYCHAIN_MARKER
+            moved = True
+        return moved
+
+    def _write(self, settings: Dict[str, Any]) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        partial = self.path.with_name(self.path.name + '.tmp')
+        # Created owner-only: the file holds API keys
+        descriptor = os.open(partial, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+        with os.fdopen(descriptor, 'w', encoding='utf-8') as f:
+            json.dump(settings, f, indent=2)
+            f.flush()
+            os.fsync(f.fileno())
+        os.replace(partial, self.path)
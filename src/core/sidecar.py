@@ -0,0 +1,294 @@
+"""
+Sidecar Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps one Python engine process alive for a front end, so
+classifying a file is a message to a warm process instead of a new
+interpreter (plus model client and database) for every call.
+
+`python -m src.main sidecar` serves the engine's methods (the same ones the
+daemon serves on its control socket) over its stdin and stdout, one JSON
+object per line:
+
+    -> {"id": 1, "method": "classify", "params": {"path": "..."}}
+    <- {"id": 1, "result": {...}}   or   {"id": 1, "error": {"code", "message"}}
+
+Error codes are the control socket's (see core.control). When it is
+ready, the sidecar sends {"method": "ready", "params": {"pid", "protocol"}}.
+Requests are answered as they finish, so a long plan does not hold up a
+ping. Everything else the engine prints goes to stderr.
+
+SidecarClient starts the sidecar, matches replies to calls, and starts a
+new one when it crashes (at most MAX_RESTARTS times per RESTART_WINDOW).
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import os
+import subprocess
+import sys
+import threading
+import time
+from collections import deque
+from concurrent.futures import ThreadPoolExecutor
+from typing import Dict, Any, Callable, IO, List, Optional
+
+from .control import ControlServer, ControlError, PROTOCOL_VERSION, UNAVAILABLE, METHOD_FAILED
+from .interpreter import resolve_app_root, resolve_python
+from .settings import child_environment
+
+logger = logging.getLogger(__name__)
+
+
+MAX_WORKERS = 4
+MAX_RESTARTS = 5
+RESTART_WINDOW = 60.0      # Seconds
+STARTUP_TIMEOUT = 60.0     # Seconds for the engine to load before 'ready'
+
+
+def serve_stdio(methods: Dict[str, Callable[..., Any]], stdin: Optional[IO[str]] = None,
+                stdout: Optional[IO[str]] = None, workers: int = MAX_WORKERS) -> None:
+    """
+    Serve methods over line-delimited JSON until stdin closes or 'shutdown' is called.
+
+    Args:
+        methods (Dict[str, Callable]): Method name -> handler(**params)
+        stdin: Requests (default: sys.stdin)
+        stdout: Replies (default: sys.stdout); nothing else may write to it
+        workers (int): Requests handled at the same time
+    """
+    stdin = stdin or sys.stdin
+    stdout = stdout or sys.stdout
+    stopping = threading.Event()
+    write_lock = threading.Lock()
+
+    # The control server's dispatch gives the same methods, params checks and error codes
+    server = ControlServer('', methods)
+    server.register('shutdown', lambda: stopping.set() or {'stopping': True})
+
+    def send(message: Dict[str, Any]) -> None:
+        line = json.dumps(message, default=str, ensure_ascii=False)
+        with write_lock:
+            try:
+                stdout.write(line + '\n')
+                stdout.flush()
+            except (OSError, ValueError):
+                stopping.set()  # the front end went away
+
+    def answer(line: str) -> None:
+        response = server.dispatch(line.encode('utf-8'))
+        if response.get('id') is not None or 'error' in response:
+            send(response)
+
+    send({'method': 'ready', 'params': {'pid': os.getpid(), 'protocol': PROTOCOL_VERSION}})
+    with ThreadPoolExecutor(max_workers=workers, thread_name_prefix='sidecar') as pool:
+        for line in stdin:
+            if not line.strip():
+                continue
+            future = pool.submit(answer, line)
+            if _is_shutdown(line):
+                future.result()  # reply before stopping, without waiting for more input
+            if stopping.is_set():
+                break
+    logger.info("Sidecar stopped")
+
+
+def _is_shutdown(line: str) -> bool:
+    try:
+        request = json.loads(line)
+    except ValueError:
+        return False
+    return isinstance(request, dict) and request.get('method') == 'shutdown'
+
+
+class SidecarClient:
+    """
+    Starts the sidecar and calls its methods; thread-safe.
+
+    Attributes:
+        command (List[str]): Sidecar command line
+        timeout (float): Default seconds to wait for a reply
+        restarts (int): Times the sidecar was started again after stopping
+    """
+
+    def __init__(self, command: Optional[List[str]] = None, timeout: float = 120.0,
+                 max_restarts: int = MAX_RESTARTS, restart_window: float = RESTART_WINDOW):
+        """
+        Initialize client (the sidecar starts on the first call).
+
+        Args:
+            command (List[str], optional): Command to run (default: the
+                                           resolved interpreter running `src.main sidecar`)
+            timeout (float): Default seconds to wait for each reply
+            max_restarts (int): Restarts allowed within restart_window
+            restart_window (float): Seconds over which restarts are counted
+        """
+        self.command = command or [resolve_python(), '-m', 'src.main', 'sidecar']
+        self.timeout = timeout
+        self.max_restarts = max_restarts
+        self.restart_window = restart_window
+        self.restarts = 0
+        self._process: Optional[subprocess.Popen] = None
+        self._pending: Dict[int, Dict[str, Any]] = {}
+        self._next_id = 0
+        self._lock = threading.RLock()
+        self._write_lock = threading.Lock()
+        self._ready = threading.Event()
+        self._started_at: deque = deque()
+        self._stderr: deque = deque(maxlen=20)
+        self._closed = False
+
+    @property
+    def pid(self) -> Optional[int]:
+        """The running sidecar's process ID."""
+        process = self._process
+        return process.pid if process is not None and process.poll() is None else None
+
+    def start(self) -> 'SidecarClient':
+        """
+        Start the sidecar unless it is running, and wait until it is ready.
+
+        Raises:
+            ControlError: If it cannot start, or crashed too often
+        """
+        with self._lock:
+            if self._closed:
+                raise ControlError("The sidecar client is closed", UNAVAILABLE)
+            if self._process is not None and self._process.poll() is None and self._ready.is_set():
+                return self
+            now = time.monotonic()
+            while self._started_at and now - self._started_at[0] > self.restart_window:
+                self._started_at.popleft()
+            if self._process is not None:
+                if len(self._started_at) > self.max_restarts:
+                    raise ControlError(f"The sidecar stopped {len(self._started_at)} times in "
+                                       f"{self.restart_window:g}s; not starting it again{self._last_error()}",
+                                       UNAVAILABLE)
+                self.restarts += 1
+                logger.warning(f"Sidecar stopped (exit code {self._process.poll()}); starting it again")
+            self._launch()
+            self._started_at.append(now)
+        if not self._ready.wait(STARTUP_TIMEOUT) or self.pid is None:
+            raise ControlError(f"The sidecar did not start{self._last_error()}", UNAVAILABLE)
+        return self
+
+    def _launch(self) -> None:
+        self._ready.clear()
+        try:
+            # API keys travel in the environment, never on the command line
+            process = subprocess.Popen(self.command, cwd=str(resolve_app_root()), stdin=subprocess.PIPE,
+                                       stdout=subprocess.PIPE, stderr=subprocess.PIPE, text=True,
+                                       encoding='utf-8', errors='replace', bufsize=1, env=child_environment())
+        except OSError as e:
+            raise ControlError(f"Cannot start the sidecar: {e}", UNAVAILABLE)
+        self._process = process
+        threading.Thread(target=self._read, args=(process,), name='sidecar-reader', daemon=True).start()
+        threading.Thread(target=self._drain, args=(process,), name='sidecar-stderr', daemon=True).start()
+
+    def _read(self, process: subprocess.Popen) -> None:
+        for line in process.stdout:
+            try:
+                message = json.loads(line)
+            except ValueError:
+                continue  # not protocol output
+            if not isinstance(message, dict):
+                continue
+            if message.get('method') == 'ready':
+                self._ready.set()
+                continue
+            with self._lock:
+                call = self._pending.pop(message.get('id'), None)
+            if call is not None:
+                call['response'] = message
+                call['done'].set()
+        process.wait()
+        self._ready.set()  # wake a start() waiting on a sidecar that died
+        with self._lock:
+            if self._process is not process:
+                return
+            failed, self._pending = self._pending, {}
+        for call in failed.values():
+            call['done'].set()
+
+    def _drain(self, process: subprocess.Popen) -> None:
+        for line in process.stderr:
+            if line.strip():
+                self._stderr.append(line.rstrip())
+
+    def _last_error(self) -> str:
+        return f": {self._stderr[-1]}" if self._stderr else ''
+
+    def call(self, method: str, timeout: Optional[float] = None, **params) -> Any:
+        """
+        Call a method in the sidecar, starting (or restarting) it if needed.
+
+        Args:
+            method (str): Method name (see `methods`)
+            timeout (float, optional): Seconds to wait (default: self.timeout)
+            **params: Method parameters
+
+        Returns:
+            The method's result
+
+        Raises:
+            ControlError: If the method failed, or the sidecar stopped or did not answer
+        """
+        self.start()
+        with self._lock:
+            self._next_id += 1
+            request_id = self._next_id
+            call = {'done': threading.Event(), 'response': None}
+            self._pending[request_id] = call
+            process = self._process
+        line = json.dumps({'id': request_id, 'method': method, 'params': params}, default=str)
+        try:
+            with self._write_lock:
+                process.stdin.write(line + '\n')
+                process.stdin.flush()
+        except (OSError, ValueError) as e:
+            with self._lock:
+                self._pending.pop(request_id, None)
+            raise ControlError(f"Lost the sidecar: {e}", UNAVAILABLE)
+
+        wait = self.timeout if timeout is None else timeout
+        if not call['done'].wait(wait):
+            with self._lock:
+                self._pending.pop(request_id, None)
+            raise ControlError(f"No reply to {method} within {wait:g}s")
+        response = call['response']
+        if response is None:
+            raise ControlError(f"The sidecar stopped while running {method}{self._last_error()}", UNAVAILABLE)
+        if 'error' in response:
+            raise ControlError(response['error'].get('message', 'Unknown error'),
+                               response['error'].get('code', METHOD_FAILED))
+        return response.get('result')
+
+    def close(self, timeout: float = 10.0) -> None:
+        """Ask the sidecar to stop, and make sure it does."""
+        with self._lock:
+            self._closed = True
+            process, self._process = self._process, None
+        if process is None or process.poll() is not None:
+            return
+        try:
+            with self._write_lock:
+                process.stdin.write(json.dumps({'id': 0, 'method': 'shutdown', 'params': {}}) + '\n')
+                process.stdin.close()
+            process.wait(timeout)
+        except (OSError, ValueError, subprocess.TimeoutExpired):
+            process.kill()
+            process.wait()
+
+    def __enter__(self) -> 'SidecarClient':
+        return self.start()
+
+    def __exit__(self, *exc) -> None:
+        self.close()
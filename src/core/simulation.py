@@ -0,0 +1,200 @@
+"""
+Simulation Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module lets a new user try the organizer on a copy before it touches
+real data. simulate_on_copy(folder, sample_size) copies a random sample
+of the folder's files into a temporary sandbox, runs the full pipeline
+there (classification with the user's rules, taxonomy and AI, planning,
+applying) and reports where each file went, mapped back to where it would
+go for real:
+
+    simulate_on_copy('~/Downloads', 20)
+    -> {'sampled': 20, 'moves': [{'file': '~/Downloads/report.pdf',
+                                  'destination': '~/Organized/Documents/report.pdf', ...}], ...}
+
+Nothing outside the sandbox changes: destinations, trash, kept versions and
+the database are redirected into it (see core.self_test), plugins do not
+run, and a planned destination outside the sandbox (an absolute rule or
+profile folder) is reported instead of moved. Copies keep their
+modification times, so date-based folders come out as they would for real.
+AI classification does run, so it costs what a real run of the sample would.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import random
+import shutil
+import tempfile
+from collections import Counter
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .ignore_rules import IgnoreRules
+from .self_test import SandboxConfig
+from src.utils.structured_logging import span
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_SAMPLE_SIZE = 25
+# What a sandbox classifier borrows from the user's: rules, settings and what the AI costs, not caches or tags
+BORROWED_PARTS = ('user_rules', 'settings', 'taxonomy', 'corrections', 'usage')
+
+
+def eligible_files(folder: Path) -> List[Path]:
+    """The files below a folder that the ignore rules let through, in folder order."""
+    rules = IgnoreRules.for_folder(str(folder))
+    files = []
+    for dirpath, dirnames, filenames in os.walk(folder):
+        dirnames[:] = sorted(d for d in dirnames if not rules.ignores(os.path.join(dirpath, d)))
+        files += [Path(dirpath) / name for name in sorted(filenames)
+                  if not rules.ignores(os.path.join(dirpath, name)) and os.path.isfile(os.path.join(dirpath, name))]
+    return files
+
+
+def sample_files(files: List[Path], sample_size: int, seed: Optional[int] = None) -> List[Path]:
+    """A random sample of files (the same one for the same seed), in their original order."""
+    picked = set(random.Random(seed).sample(range(len(files)), min(sample_size, len(files))))
+    return [path for index, path in enumerate(files) if index in picked]
+
+
+def simulate_on_copy(folder: str, sample_size: int = DEFAULT_SAMPLE_SIZE, config=None, classifier=None,
+                     deep: bool = False, seed: Optional[int] = None, keep: bool = False) -> Dict[str, Any]:
+    """
+    Organize a sandboxed copy of a sample of a folder.
+
+    Args:
+        folder (str): Folder to try the organizer on
+        sample_size (int): Files to copy at most
+        config: Configuration object (default: the active configuration)
+        classifier (FileClassifier, optional): Classifier whose rules, settings
+            and AI client to use; default: rules only, no AI
+        deep (bool): Use deep AI analysis
+        seed (int, optional): Seed for the sample
+        keep (bool): Leave the sandbox behind for a look at the result
+
+    Returns:
+        Dict: Result with 'success', 'message', 'folder', 'sandbox' (when
+              kept), 'total_files', 'sampled', 'moves' (one {'file',
+              'destination', 'category', 'method', 'confidence'} per file
+              that would move, with real paths), 'unchanged', 'failed',
+              'conflicts', 'outside_sandbox' and 'categories'
+
+    Raises:
+        NotADirectoryError: If the folder does not exist
+        ValueError: If the sample size is not positive
+    """
+    from src.core.actions import ActionManager
+    from src.core.classifier import FileClassifier
+    from src.core.db_manager import DatabaseManager
+    from src.core.journal import OperationJournal
+    from src.core.plans import OrganizePlanner
+
+    if config is None:
+        from src.config import get_config
+        config = get_config()
+    source = Path(folder).expanduser().resolve()
+    if not source.is_dir():
+        raise NotADirectoryError(f"Not a folder: {folder}")
+    if int(sample_size) < 1:
+        raise ValueError("The sample size must be at least 1")
+
+    root = Path(tempfile.mkdtemp(prefix='aifo-simulation-')).resolve()
+    inbox, organized = root / 'inbox', root / 'organized'
+    sandbox = SandboxConfig(config, root, ai=classifier is not None)
+    db = DatabaseManager(str(root / 'db' / 'simulation.db'))
+    try:
+        with span('simulate_on_copy', logger, logging.INFO, folder=str(source), sample_size=sample_size) as trace:
+            files = eligible_files(source)
+            picked = sample_files(files, int(sample_size), seed)
+            originals = {}
+            for path in picked:
+                copy = inbox / path.relative_to(source)
+                copy.parent.mkdir(parents=True, exist_ok=True)
+                shutil.copy2(path, copy)
+                originals[str(copy)] = str(path)
+
+            sandbox_classifier = FileClassifier(sandbox, getattr(classifier, 'ollama_client', None))
+            for part in BORROWED_PARTS:
+                setattr(sandbox_classifier, part, getattr(classifier, part, None))
+            journal = OperationJournal(db)
+            action_manager = ActionManager(sandbox, db, dry_run=False, journal=journal)
+            planner = OrganizePlanner(sandbox, sandbox_classifier, action_manager)
+
+            plan = planner.build_plan(str(inbox), deep=deep) if picked else {'items': [], 'unchanged': 0}
+            inside = [item for item in plan['items'] if _within(item.get('destination'), root)]
+            outside = [{'file': originals[item['file']], 'destination': item.get('destination')}
+                       for item in plan['items'] if not _within(item.get('destination'), root)]
+            applied = planner.apply_plan({**plan, 'items': inside}) if inside else {'applied': [], 'failed': [],
+                                                                                   'conflicts': []}
+            trace.record(sampled=len(picked), moved=len(applied['applied']), outside=len(outside))
+
+        classifications = {item['file']: item.get('classification') or {} for item in inside}
+        moves = []
+        for entry in applied['applied']:
+            classification = classifications.get(entry['file'], {})
+            moves.append({'file': originals[entry['file']],
+                          'destination': _real_path(entry['new_path'], (organized, config.base_destination),
+                                                    (inbox, str(source))),
+                          'category': classification.get('category'), 'method': classification.get('method'),
+                          'confidence': classification.get('confidence')})
+        failed = [{'file': originals.get(entry['file'], entry['file']), 'message': entry['message']}
+                  for entry in applied['failed']]
+    finally:
+        try:
+            db.cleanup()
+        except Exception as e:
+            logger.debug(f"Simulation database cleanup failed: {e}")
+        if not keep:
+            shutil.rmtree(root, ignore_errors=True)
+
+    message = f"{len(moves)} of {len(picked)} sampled file(s) would be organized"
+    if outside:
+        message += f"; {len(outside)} would go outside the sandbox and were not tried"
+    if failed:
+        message += f"; {len(failed)} failed"
+    return {
+        'success': not failed,
+        'message': message,
+        'folder': str(source),
+        'sandbox': str(root) if keep else None,
+        'total_files': len(files),
+        'sampled': len(picked),
+        'moves': moves,
+        'unchanged': plan['unchanged'],
+        'failed': failed,
+        'conflicts': len(applied['conflicts']),
+        'outside_sandbox': outside,
+        'categories': dict(Counter(move['category'] or 'Unknown' for move in moves).most_common()),
+    }
+
+
+def _within(path: Optional[str], root: Path) -> bool:
+    if not path:
+        return True
+    try:
+        Path(os.path.realpath(path)).relative_to(os.path.realpath(root))
+    except ValueError:
+        return False
+    return True
+
+
+def _real_path(path: Optional[str], *mappings) -> Optional[str]:
+    """Where a sandbox path stands for in the real folders (None if it is in neither)."""
+    if not path:
+        return None
+    for sandbox, real in mappings:
+        try:
+            return str(Path(real).expanduser() / Path(path).relative_to(sandbox))
+        except ValueError:
+            continue
+    return None
@@ -0,0 +1,381 @@
+"""
+Folder Snapshot Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module records snapshots of a folder subtree (relative paths, sizes,
+modification times and content hashes) before runs that touch more than a
+handful of files, and restores the recorded layout on request.
+
+Restoring locates each file that is no longer at its recorded path - first
+through the moves logged since the snapshot, then by content anywhere in
+the snapshot root or extra search folders - and moves it back. Restores
+are journaled, so a restore can itself be undone.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import shutil
+from collections import defaultdict
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Iterable
+
+from .hashing import DEFAULT_ALGORITHM, hash_file
+from .journal import OperationJournal
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_MIN_FILES = 5
+DEFAULT_KEEP = 20
+DEFAULT_MAX_FILES = 50000
+DEFAULT_HASH_MAX_SIZE_MB = 512
+
+
+class SnapshotManager:
+    """
+    Takes folder snapshots and restores them.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager used for persistence
+        journal (OperationJournal): Journal recording restores
+        hash_algorithm (str): Hash algorithm for new entry hashes (see core.hashing)
+    """
+
+    def __init__(self, config, db_manager, journal: Optional[OperationJournal] = None):
+        """
+        Initialize snapshot manager.
+
+        Args:
+            config: Configuration object (reads `snapshots`)
+            db_manager: Database manager instance
+            journal (OperationJournal, optional): Journal; created from db_manager if omitted
+        """
+        settings = getattr(config, 'snapshot_settings', None)
+        if not isinstance(settings, dict):
+            settings = {}
+        self.config = config
+        self.db_manager = db_manager
+        self.journal = journal or OperationJournal(db_manager)
+        self.enabled = bool(settings.get('enabled', True))
+        self.min_files = int(settings.get('min_files', DEFAULT_MIN_FILES))
+        self.keep = int(settings.get('keep', DEFAULT_KEEP))
+        self.max_files = int(settings.get('max_files', DEFAULT_MAX_FILES))
+        self.hash_max_size = int(settings.get('hash_max_size_mb', DEFAULT_HASH_MAX_SIZE_MB)) * 1024 * 1024
+        self.hash_algorithm = DEFAULT_ALGORITHM
+
+    # ==================== Taking Snapshots ====================
+
+    def _hash_file(self, path: Path, size: int, algorithm: Optional[str] = None) -> Optional[str]:
+        """Hash a file, skipping files above the configured size."""
+        if size > self.hash_max_size:
+            return None
+        try:
+            return hash_file(path, algorithm or self.hash_algorithm)
+        except (OSError, ValueError):
+            return None
+
+    def create_snapshot(self, root: str, reason: str = '') -> Dict[str, Any]:
+        """
+        Record every file under a folder.
+
+        Args:
+            root (str): Folder to snapshot
+            reason (str): Why the snapshot is taken (shown in listings)
+
+        Returns:
+            Dict: Result with 'success', 'message', 'snapshot_id' and 'file_count'
+        """
+        root_path = Path(root).expanduser()
+        if not root_path.is_dir():
+            return {'success': False, 'message': f'Not a directory: {root}', 'snapshot_id': None}
+        root_path = root_path.resolve()
+
+        entries = []
+        for dirpath, _dirnames, filenames in os.walk(root_path):
+            for name in filenames:
+                path = Path(dirpath) / name
+                if path.is_symlink():
+                    continue
+                try:
+                    stat = path.stat()
+                except OSError:
+                    continue
+                entries.append((path.relative_to(root_path).as_posix(), stat.st_size, stat.st_mtime,
+                                self._hash_file(path, stat.st_size)))
+                if len(entries) > self.max_files:
+                    return {
+                        'success': False,
+                        'message': f'{root_path} has more than {self.max_files} files; snapshot skipped',
+                        'snapshot_id': None
+                    }
+
+        snapshot_id = self.db_manager.create_snapshot(str(root_path), reason, self.hash_algorithm, entries)
+        pruned = self.db_manager.prune_snapshots(self.keep) if self.keep > 0 else 0
+        if pruned:
+            logger.info(f"Pruned {pruned} old snapshot(s)")
+
+        logger.info(f"Snapshot {snapshot_id} of {root_path}: {len(entries)} file(s)")
+        return {
+            'success': True,
+            'message': f'Snapshot {snapshot_id} recorded {len(entries)} file(s) in {root_path}',
+            'snapshot_id': snapshot_id,
+            'file_count': len(entries)
+        }
+
+    def maybe_snapshot(self, file_paths: Iterable[str], reason: str = '') -> Optional[int]:
+        """
+        Snapshot the folder containing a run's files if the run is large enough.
+
+        Args:
+            file_paths (Iterable[str]): Files the run is about to touch
+            reason (str): Why the snapshot is taken
+
+        Returns:
+            int or None: Snapshot ID, or None if no snapshot was needed or possible
+        """
+        paths = [str(Path(p).expanduser().resolve().parent) for p in file_paths]
+        if not self.enabled or len(paths) < self.min_files:
+            return None
+
+        try:
+            root = os.path.commonpath(paths)
+        except ValueError:
+            logger.warning("Run spans several drives; no snapshot taken")
+            return None
+
+        result = self.create_snapshot(root, reason or f'Before organizing {len(paths)} file(s)')
+        if not result['success']:
+            logger.warning(result['message'])
+        return result['snapshot_id']
+
+    def list_snapshots(self, limit: int = 50) -> List[Dict[str, Any]]:
+        """
+        List snapshots, newest first.
+
+        Args:
+            limit (int): Maximum number of snapshots
+
+        Returns:
+            List[Dict]: Snapshot summaries
+        """
+        return self.db_manager.list_snapshots(limit)
+
+    def get_snapshot(self, snapshot_id: int) -> Optional[Dict[str, Any]]:
+        """
+        Get a snapshot with its entries.
+
+        Args:
+            snapshot_id (int): Snapshot ID
+
+        Returns:
+            Dict or None: Snapshot with 'entries'
+        """
+        return self.db_manager.get_snapshot(snapshot_id)
+
+    # ==================== Restoring ====================
+
+    def _matches(self, path: Path, entry: Dict[str, Any], hash_cache: Dict[str, Optional[str]],
+                 algorithm: str) -> bool:
+        """Check whether a file has the content recorded for an entry (hashed with the snapshot's algorithm)."""
+        try:
+            if not path.is_file() or path.stat().st_size != entry['file_size']:
+                return False
+        except OSError:
+            return False
+        if not entry.get('file_hash'):
+            return path.name == Path(entry['rel_path']).name
+        key = str(path)
+        if key not in hash_cache:
+            hash_cache[key] = self._hash_file(path, entry['file_size'], algorithm)
+        return hash_cache[key] == entry['file_hash']
+
+    def _logged_locations(self, since: str) -> Dict[str, List[str]]:
+        """Map original paths to where logged moves took them (following chains)."""
+        forward: Dict[str, str] = {}
+        for move in self.db_manager.get_moves_since(since):
+            forward[move['old_path']] = move['new_path']
+
+        locations: Dict[str, List[str]] = {}
+        for origin in forward:
+            chain, current, seen = [], origin, set()
+            while current in forward and current not in seen:
+                seen.add(current)
+                current = forward[current]
+                chain.append(current)
+            locations[origin] = list(reversed(chain))  # Latest location first
+        return locations
+
+    def restore_snapshot(self, snapshot_id: int, dry_run: bool = False,
+                         search_paths: Optional[List[str]] = None) -> Dict[str, Any]:
+        """
+        Move files back to the layout recorded in a snapshot.
+
+        Files are never overwritten: if something else now occupies a
+        recorded path, that entry is reported as a conflict. Files added
+        after the snapshot are left where they are.
+
+        Args:
+            snapshot_id (int): Snapshot ID
+            dry_run (bool): If True, only report what would be moved
+            search_paths (List[str], optional): Extra folders to look for moved
+                files in (the configured base destination is always searched)
+
+        Returns:
+            Dict: Result with 'success', 'message', 'operation_id', 'restored'
+                  (list of moves), 'in_place', 'missing' and 'conflicts'
+        """
+        snapshot = self.db_manager.get_snapshot(snapshot_id)
+        if not snapshot:
+            return {'success': False, 'message': f'Snapshot {snapshot_id} not found'}
+
+        root = Path(snapshot['root_path'])
+        hash_cache: Dict[str, Optional[str]] = {}
+        algorithm = snapshot.get('hash_algorithm') or self.hash_algorithm  # Older ones used duplicates.hash_algorithm
+        logged = self._logged_locations(snapshot['created_at'])
+
+        in_place = 0
+        pending: List[Dict[str, Any]] = []
+        conflicts: List[str] = []
+
+        for entry in snapshot['entries']:
+            target = root / entry['rel_path']
+            if target.exists():
+                if self._matches(target, entry, hash_cache, algorithm):
+                    in_place += 1
+                else:
+                    conflicts.append(str(target))
+                continue
+            pending.append({'entry': entry, 'target': target})
+
+        # Index remaining files by size for content search
+        by_size: Optional[Dict[int, List[Path]]] = None
+        recorded = {e['rel_path'] for e in snapshot['entries']}
+        claimed: set = set()
+        restored: List[Dict[str, str]] = []
+        missing: List[str] = []
+
+        for item in pending:
+            entry, target = item['entry'], item['target']
+            candidate = None
+
+            for location in logged.get(str(target), []):
+                path = Path(location)
+                if str(path) not in claimed and self._matches(path, entry, hash_cache, algorithm):
+                    candidate = path
+                    break
+
+            if candidate is None:
+                if by_size is None:
+                    by_size = self._index_by_size([root, *self._search_roots(search_paths)])
+                for path in by_size.get(entry['file_size'], []):
+                    if str(path) in claimed or path == target:
+                        continue
+                    if self._is_recorded(path, root, recorded):
+                        continue
+                    if self._matches(path, entry, hash_cache, algorithm):
+                        candidate = path
+                        break
+
+            if candidate is None:
+                missing.append(entry['rel_path'])
+                continue
+
+            claimed.add(str(candidate))
+            restored.append({'from': str(candidate), 'to': str(target)})
+
+        if dry_run:
+            return {
+                'success': True,
+                'message': f'[DRY RUN] Would restore {len(restored)} file(s) from snapshot {snapshot_id}',
+                'snapshot_id': snapshot_id,
+                'operation_id': None,
+                'restored': restored,
+                'in_place': in_place,
+                'missing': missing,
+                'conflicts': conflicts,
+                'dry_run': True
+            }
+
+        operation_id = self.journal.begin('restore_snapshot', f'Restore snapshot {snapshot_id} of {root}')
+        failed: List[Dict[str, str]] = []
+        for move in list(restored):
+            target = Path(move['to'])
+            try:
+                target.parent.mkdir(parents=True, exist_ok=True)
+                shutil.move(move['from'], str(target))
+                self.journal.record_step(operation_id, 'move', move['from'], str(target),
+                                         {'role': 'restore', 'snapshot_id': snapshot_id})
+            except (OSError, shutil.Error) as e:
+                logger.warning(f"Could not restore {target}: {e}")
+                restored.remove(move)
+                failed.append({**move, 'error': str(e)})
+
+        self.journal.complete(operation_id, success=not failed)
+        if not failed and not missing:
+            self.db_manager.mark_snapshot_restored(snapshot_id)
+
+        message = f'Restored {len(restored)} file(s) from snapshot {snapshot_id}'
+        if missing:
+            message += f'; {len(missing)} file(s) could not be found'
+        if conflicts:
+            message += f'; {len(conflicts)} path(s) are occupied by other files'
+        if failed:
+            message += f'; {len(failed)} move(s) failed'
+
+        return {
+            'success': not failed,
+            'message': message,
+            'snapshot_id': snapshot_id,
+            'operation_id': operation_id,
+            'restored': restored,
+            'in_place': in_place,
+            'missing': missing,
+            'conflicts': conflicts,
+            'failed': failed,
+            'dry_run': False
+        }
+
+    def _search_roots(self, search_paths: Optional[List[str]]) -> List[Path]:
+        """Folders searched for moved files besides the snapshot root."""
+        roots = [Path(p).expanduser() for p in (search_paths or [])]
+        base = getattr(self.config, 'base_destination', None)
+        if isinstance(base, str) and base:
+            roots.append(Path(base).expanduser())
+        return [r for r in roots if r.is_dir()]
+
+    @staticmethod
+    def _index_by_size(roots: List[Path]) -> Dict[int, List[Path]]:
+        """Index files under several folders by size (each file once)."""
+        index: Dict[int, List[Path]] = defaultdict(list)
+        seen = set()
+        for root in roots:
+            for dirpath, _dirnames, filenames in os.walk(root):
+                for name in filenames:
+                    path = Path(dirpath) / name
+                    key = str(path.resolve())
+                    if key in seen or path.is_symlink():
+                        continue
+                    seen.add(key)
+                    try:
+                        index[path.stat().st_size].append(path)
+                    except OSError:
+                        continue
+        return index
+
+    @staticmethod
+    def _is_recorded(path: Path, root: Path, recorded: set) -> bool:
+        """True if the file sits at a path recorded in the snapshot (it belongs there)."""
+        try:
+            rel = path.resolve().relative_to(root).as_posix()
+        except ValueError:
+            return False
+        return rel in recorded
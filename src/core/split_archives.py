@@ -0,0 +1,248 @@
+"""
+Split Archive Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module recognizes split and spanned archives so their parts are
+treated as one logical unit by scanning, duplicate detection and
+organizing. Supported naming schemes:
+    - numbered volumes:  backup.zip.001, backup.zip.002 / movie.7z.001
+    - RAR 3+ volumes:    photos.part1.rar, photos.part2.rar
+    - old RAR volumes:   photos.rar, photos.r00, photos.r01
+    - spanned zip:       backup.z01, backup.z02, backup.zip
+
+Every unit has a primary part (the one an extractor is pointed at: the
+first volume, the .rar or the .zip); the other parts follow it.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import re
+from pathlib import Path
+from typing import Dict, List, NamedTuple, Optional, Tuple, Iterable
+
+
+NUMBERED_VOLUME = re.compile(r'^(?P<base>.+)\.(?P<num>\d{3})$')
+RAR_PART_VOLUME = re.compile(r'^(?P<base>.+)\.part(?P<num>\d+)\.rar$', re.IGNORECASE)
+OLD_RAR_VOLUME = re.compile(r'^(?P<base>.+)\.(?:r(?P<num>\d{2,3})|(?P<head>rar))$', re.IGNORECASE)
+SPANNED_ZIP_VOLUME = re.compile(r'^(?P<base>.+)\.(?:z(?P<num>\d{2,3})|(?P<head>zip))$', re.IGNORECASE)
+
+
+class SplitPart(NamedTuple):
+    """Position of a file within a split archive."""
+    scheme: str    # 'numbered', 'rar-part', 'rar-old', 'zip-spanned'
+    base: str      # Name shared by all parts (scheme suffixes removed)
+    index: int     # Ordering key within the unit
+    primary: bool  # True for the part extractors open
+
+
+def split_part_info(path: Path) -> Optional[SplitPart]:
+    """
+    Describe a file's place in a split archive from its name.
+
+    A plain .rar or .zip only counts as a part when a sibling .r00 / .z01
+    volume exists, otherwise it is an ordinary archive.
+
+    Args:
+        path (Path): File path
+
+    Returns:
+        SplitPart or None: Part information, or None if not a split part
+    """
+    name = path.name
+
+    match = RAR_PART_VOLUME.match(name)
+    if match:
+        index = int(match.group('num'))
+        return SplitPart('rar-part', match.group('base'), index, index == 1)
+
+    match = NUMBERED_VOLUME.match(name)
+    if match:
+        index = int(match.group('num'))
+        return SplitPart('numbered', match.group('base'), index, index <= 1)
+
+    for scheme, pattern, first_volume in (('rar-old', OLD_RAR_VOLUME, 'r00'),
+                                          ('zip-spanned', SPANNED_ZIP_VOLUME, 'z01')):
+        match = pattern.match(name)
+        if not match:
+            continue
+        base = match.group('base')
+        if match.group('head'):
+            # The .rar comes first, the .zip is the last volume of a spanned set
+            if not _sibling_exists(path, f"{base}.{first_volume}"):
+                return None
+            return SplitPart(scheme, base, -1 if scheme == 'rar-old' else 10 ** 4, True)
+        return SplitPart(scheme, base, int(match.group('num')), False)
+
+    return None
+
+
+def _sibling_exists(path: Path, name: str) -> bool:
+    """Case-insensitive check for a file next to path."""
+    if (path.parent / name).exists():
+        return True
+    try:
+        return any(p.name.lower() == name.lower() for p in path.parent.iterdir())
+    except OSError:
+        return False
+
+
+def is_split_part(path: Path) -> bool:
+    """True if the file is one part of a split archive."""
+    return split_part_info(path) is not None
+
+
+def is_secondary_part(path: Path) -> bool:
+    """True if the file is a split archive part that follows the primary part."""
+    info = split_part_info(path)
+    return info is not None and not info.primary
+
+
+def _unit_key(path: Path, info: SplitPart) -> Tuple[str, str, str]:
+    return (str(path.parent), info.scheme, info.base.lower())
+
+
+def split_archive_parts(path: Path) -> List[Path]:
+    """
+    Find every part of the split archive a file belongs to.
+
+    Args:
+        path (Path): Any part of the unit
+
+    Returns:
+        List[Path]: Parts in volume order (primary first); [path] if the
+            file is not a split archive part
+    """
+    info = split_part_info(path)
+    if info is None:
+        return [path]
+
+    key = _unit_key(path, info)
+    parts = []
+    try:
+        siblings = list(path.parent.iterdir())
+    except OSError:
+        return [path]
+
+    for sibling in siblings:
+        if not sibling.is_file():
+            continue
+        sibling_info = split_part_info(sibling)
+        if sibling_info and _unit_key(sibling, sibling_info) == key:
+            parts.append((sibling_info, sibling))
+
+    if not parts:
+        return [path]
+    return [p for _, p in sorted(parts, key=lambda item: (not item[0].primary, item[0].index))]
+
+
+def volume_order(parts: Iterable[Path]) -> List[Path]:
+    """
+    Sort parts in the order their bytes form the archive.
+
+    Args:
+        parts (Iterable[Path]): Parts of one unit
+
+    Returns:
+        List[Path]: Parts sorted by volume index
+    """
+    def key(part: Path):
+        info = split_part_info(part)
+        return info.index if info else 0
+    return sorted(parts, key=key)
+
+
+def group_split_archives(paths: Iterable[Path]) -> List[List[Path]]:
+    """
+    Group a list of files into logical units.
+
+    Split archive parts are collected under their primary part; all other
+    files form single-item units. Order follows the first appearance of
+    each unit in the input.
+
+    Args:
+        paths (Iterable[Path]): Files to group
+
+    Returns:
+        List[List[Path]]: Units, each a list of paths with the primary first
+    """
+    units: Dict[object, List[Tuple[SplitPart, Path]]] = {}
+    order: List[object] = []
+
+    for path in paths:
+        path = Path(path)
+        info = split_part_info(path)
+        key = _unit_key(path, info) if info else ('file', str(path))
+        if key not in units:
+            units[key] = []
+            order.append(key)
+        units[key].append((info, path))
+
+    grouped = []
+    for key in order:
+        members = units[key]
+        if members[0][0] is None:
+            grouped.append([members[0][1]])
+        else:
+            members.sort(key=lambda item: (not item[0].primary, item[0].index))
+            grouped.append([p for _, p in members])
+    return grouped
+
+
+def collapse_split_archives(paths: Iterable[str]) -> List[str]:
+    """
+    Replace each split archive by its primary part.
+
+    Args:
+        paths (Iterable[str]): File paths
+
+    Returns:
+        List[str]: One path per logical unit
+    """
+    return [str(unit[0]) for unit in group_split_archives(Path(p) for p in paths)]
+
+
+def unit_base_for(name: str, scheme: str) -> Optional[str]:
+    """
+    Extract the unit base name from a file name using a given scheme.
+
+    Unlike split_part_info this only looks at the name, so it also works
+    for names that do not exist on disk yet (e.g. a suggested rename).
+
+    Args:
+        name (str): File name
+        scheme (str): Naming scheme of the unit
+
+    Returns:
+        str or None: Base name, or None if the name does not fit the scheme
+    """
+    pattern = {
+        'numbered': NUMBERED_VOLUME,
+        'rar-part': RAR_PART_VOLUME,
+        'rar-old': OLD_RAR_VOLUME,
+        'zip-spanned': SPANNED_ZIP_VOLUME,
+    }.get(scheme)
+    match = pattern.match(name) if pattern else None
+    return match.group('base') if match else None
+
+
+def renamed_part(part_name: str, old_base: str, new_base: str) -> str:
+    """
+    Carry a rename of the primary part over to another part.
+
+    Args:
+        part_name (str): Name of the part to rename
+        old_base (str): Unit base name before the rename
+        new_base (str): Unit base name after the rename
+
+    Returns:
+        str: New part name (unchanged if it does not start with old_base)
+    """
+    if part_name.lower().startswith(old_base.lower()):
+        return new_base + part_name[len(old_base):]
+    return part_name
@@ -0,0 +1,255 @@
+"""
+Tags Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps tags on files: the ones the user sets, and the ones AI
+classification suggests (kept alongside, never replacing the user's).
+Tags are stored in the app database by file path and follow files the
+app moves.
+
+With `tags.write_to_file` on, tags are also written into the file itself
+so they survive outside the app - in other file managers and on other
+machines:
+
+- Linux: the `user.xdg.tags` extended attribute (comma-separated, the
+  freedesktop convention file managers and Baloo read)
+- macOS: Finder tags (`com.apple.metadata:_kMDItemUserTags`)
+- Windows: an `aifo.tags` NTFS alternate data stream
+
+A file's modification time is kept as it was. Tags found in a file the
+database knows nothing about (copied from elsewhere) are read back in.
+
+    "tags": {"write_to_file": false}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+import plistlib
+import shutil
+import subprocess
+import sys
+from typing import Dict, Any, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+MAX_TAG_LENGTH = 64
+MAX_TAGS = 50
+XDG_ATTRIBUTE = 'user.xdg.tags'
+FINDER_ATTRIBUTE = 'com.apple.metadata:_kMDItemUserTags'
+ADS_NAME = 'aifo.tags'
+
+
+def normalize_tags(tags) -> List[str]:
+    """Tags as a clean list: trimmed, without commas, no duplicates (ignoring case)."""
+    if isinstance(tags, str):
+        tags = tags.split(',')
+    cleaned, seen = [], set()
+    for tag in tags or []:
+        tag = ' '.join(str(tag).replace(',', ' ').split())[:MAX_TAG_LENGTH]
+        if tag and tag.casefold() not in seen:
+            seen.add(tag.casefold())
+            cleaned.append(tag)
+    return cleaned[:MAX_TAGS]
+
+
+def _keep_times(path: str, write) -> None:
+    """Run a write to a file's attributes without changing its access and modification times."""
+    stat = os.stat(path)
+    try:
+        write()
+    finally:
+        os.utime(path, ns=(stat.st_atime_ns, stat.st_mtime_ns))
+
+
+def write_file_tags(path: str, tags: List[str]) -> bool:
+    """
+    Write tags into a file's extended attributes (or NTFS stream); an empty list removes them.
+
+    Returns:
+        bool: Whether the platform and file system took them
+    """
+    try:
+        if os.name == 'nt':
+            stream = f"{path}:{ADS_NAME}"
+
+            def write():
+                if tags:
+                    with open(stream, 'w', encoding='utf-8') as f:
+                        f.write('\n'.join(tags))
+                elif os.path.exists(stream):
+                    os.remove(stream)
+        elif sys.platform == 'darwin':
+            if not shutil.which('xattr'):
+                return False
+            value = plistlib.dumps(list(tags), fmt=plistlib.FMT_BINARY).hex()
+            command = (['xattr', '-wx', FINDER_ATTRIBUTE, value, path] if tags
+                       else ['xattr', '-d', FINDER_ATTRIBUTE, path])
+
+            def write():
+                subprocess.run(command, capture_output=True, check=bool(tags), timeout=10)
+        elif hasattr(os, 'setxattr'):
+            def write():
+                if tags:
+                    os.setxattr(path, XDG_ATTRIBUTE, ','.join(tags).encode('utf-8'))
+                else:
+                    try:
+                        os.removexattr(path, XDG_ATTRIBUTE)
+                    except OSError:
+                        pass  # Had none
+        else:
+            return False
+        _keep_times(path, write)
+        return True
+    except (OSError, subprocess.SubprocessError) as e:
+        logger.info(f"Tags not written into {path}: {e}")
+        return False
+
+
+def read_file_tags(path: str) -> List[str]:
+    """Tags written into a file (by this app or, on macOS, Finder); empty if none or unsupported."""
+    try:
+        if os.name == 'nt':
+            with open(f"{path}:{ADS_NAME}", 'r', encoding='utf-8') as f:
+                return normalize_tags(f.read().splitlines())
+        if sys.platform == 'darwin':
+            if not shutil.which('xattr'):
+                return []
+            completed = subprocess.run(['xattr', '-px', FINDER_ATTRIBUTE, path],
+                                       capture_output=True, text=True, timeout=10)
+            if completed.returncode != 0:
+                return []
+            # Finder stores "name\ncolor"
+            tags = plistlib.loads(bytes.fromhex(''.join(completed.stdout.split())))
+            return normalize_tags(str(tag).split('\n')[0] for tag in tags)
+        if hasattr(os, 'getxattr'):
+            return normalize_tags(os.getxattr(path, XDG_ATTRIBUTE).decode('utf-8', errors='replace'))
+    except (OSError, ValueError, plistlib.InvalidFileException, subprocess.SubprocessError):
+        pass
+    return []
+
+
+class TagStore:
+    """
+    Stores file tags and, optionally, writes them into the files.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        write_to_file (bool): Also write tags into extended attributes / NTFS streams
+        search_index (SearchIndex, optional): Re-indexes files whose tags change
+    """
+
+    def __init__(self, config, db_manager):
+        """
+        Initialize the store.
+
+        Args:
+            config: Configuration object (reads `tag_settings`)
+            db_manager: Database manager instance
+        """
+        self.config = config
+        self.db_manager = db_manager
+        settings = getattr(config, 'tag_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.write_to_file = bool(settings.get('write_to_file', False))
+        self.search_index = None
+
+    @staticmethod
+    def _normalize(path: str) -> str:
+        return os.path.abspath(os.path.expanduser(path))
+
+    def set_tags(self, path: str, tags: List[str]) -> Dict[str, Any]:
+        """
+        Set a file's tags, replacing the ones it had.
+
+        Args:
+            path (str): File
+            tags (List[str]): Tags (or one comma-separated string); empty clears them
+
+        Returns:
+            Dict: 'success', 'path', 'tags', 'written_to_file' and 'message'
+        """
+        path = self._normalize(path)
+        if not os.path.isfile(path):
+            return {'success': False, 'path': path, 'message': f"Not a file: {path}"}
+        tags = normalize_tags(tags)
+        self.db_manager.set_file_tags(path, tags, 'user')
+        written = self.write_to_file and write_file_tags(path, tags)
+        self._reindex(path)
+        return {
+            'success': True,
+            'path': path,
+            'tags': tags,
+            'written_to_file': written,
+            'message': f"Tagged {os.path.basename(path)}: {', '.join(tags)}" if tags
+                       else f"Cleared the tags of {os.path.basename(path)}",
+        }
+
+    def get_tags(self, path: str) -> Dict[str, Any]:
+        """
+        A file's tags.
+
+        Args:
+            path (str): File
+
+        Returns:
+            Dict: 'path', 'tags' and 'sources' (tag -> user, ai or file)
+        """
+        path = self._normalize(path)
+        rows = self.db_manager.get_file_tags(path)
+        if not rows and os.path.isfile(path):
+            found = read_file_tags(path)
+            if found:
+                self.db_manager.set_file_tags(path, found, 'file')
+                rows = self.db_manager.get_file_tags(path)
+        return {'path': path, 'tags': [row['tag'] for row in rows],
+                'sources': {row['tag']: row['source'] for row in rows}}
+
+    def find_by_tag(self, tag: str, limit: int = 1000) -> Dict[str, Any]:
+        """
+        Files with a tag (ignoring case).
+
+        Args:
+            tag (str): Tag
+            limit (int): Most files to return
+
+        Returns:
+            Dict: 'success', 'tag', 'files' ({'path', 'tags', 'exists'}, newest first) and 'message'
+        """
+        tag = ' '.join(str(tag).split())
+        files = [{**row, 'exists': os.path.exists(row['path'])}
+                 for row in self.db_manager.find_files_by_tag(tag, int(limit))]
+        return {'success': True, 'tag': tag, 'files': files,
+                'message': f"{len(files)} file{'s' if len(files) != 1 else ''} tagged {tag!r}"}
+
+    def list_tags(self) -> List[Dict[str, Any]]:
+        """Every tag in use, with its file count, most used first."""
+        return self.db_manager.file_tag_counts()
+
+    def record_classification(self, path: str, classification: Dict[str, Any]) -> List[str]:
+        """Keep the tags an AI classification suggested, next to the user's; returns them."""
+        tags = normalize_tags(classification.get('tags'))
+        if tags:
+            path = self._normalize(path)
+            self.db_manager.set_file_tags(path, tags, 'ai', replace=False)
+        return tags
+
+    def move_tags(self, old_path: str, new_path: str) -> int:
+        """Carry a file's tags to where it was moved; returns the tags moved."""
+        return self.db_manager.move_file_tags(self._normalize(old_path), self._normalize(new_path))
+
+    def _reindex(self, path: str) -> None:
+        if self.search_index is not None:
+            try:
+                self.search_index.update_file(path)
+            except Exception as e:
+                logger.warning(f"Search index not updated for {path}: {e}")
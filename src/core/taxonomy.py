@@ -0,0 +1,346 @@
+"""
+Taxonomy Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps the user's own category tree. Without one, categories
+are whatever the classifier (or the AI behind it) returns; with one, the
+classifier maps every result onto it (see core.classifier), before any
+move is planned, so files only ever land in categories the user made.
+
+The tree is the "taxonomy" list of the user settings (see core.settings),
+parents first:
+
+    [{"name": "Documents", "destination": "Documents/{year}"},
+     {"name": "Invoices", "parent": "Documents", "destination": "Finance/Invoices/{year}",
+      "aliases": ["Finance", "Receipts"]}]
+
+A category is found by its name, its path ("Documents/Invoices") or one
+of its aliases, ignoring case. Its destination is a folder template (see
+core.rules for the placeholders), relative to the destination root or
+absolute; without one, files go to the category's path. A result the tree
+does not know goes to "Unsorted" (a category of that name sets where),
+with the classifier's own category kept as "original_category".
+
+Results of the user's rules, learned corrections and strategies that chose
+a destination root already say exactly where a file goes: they keep their
+folder and are only relabelled when the tree knows their category. Files
+the classifier left where they are stay there.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .profiles import KEPT_PLACEMENTS
+from .rules import render_template, unknown_placeholders
+from .settings import SettingsStore
+from src.utils.error_handler import ConfigurationError
+
+logger = logging.getLogger(__name__)
+
+
+CATEGORY_KEYS = {'name', 'parent', 'destination', 'aliases'}
+UNSORTED = 'Unsorted'
+
+
+@dataclass
+class Category:
+    """One category of the tree (see the module docstring)."""
+    name: str
+    parent: Optional[str] = None
+    destination: Optional[str] = None
+    aliases: List[str] = field(default_factory=list)
+
+    @classmethod
+    def from_dict(cls, data: Any) -> 'Category':
+        """
+        A category from its settings entry, checked on its own (see Taxonomy for the tree).
+
+        Raises:
+            ValueError: Naming what is wrong with the category
+        """
+        if not isinstance(data, dict):
+            raise ValueError("A category must be an object")
+        unknown = sorted(set(data) - CATEGORY_KEYS)
+        if unknown:
+            raise ValueError(f"Unknown category key(s): {', '.join(unknown)}")
+        name = data.get('name')
+        if not isinstance(name, str) or not name.strip() or any(c in name for c in '/\\'):
+            raise ValueError(f"Invalid category name: {name!r}")
+        name = name.strip()
+        parent = data.get('parent') or None
+        if parent is not None and (not isinstance(parent, str) or not parent.strip()):
+            raise ValueError(f"Category '{name}': 'parent' must be a category name")
+        destination = data.get('destination') or None
+        if destination is not None:
+            if not isinstance(destination, str) or not destination.strip():
+                raise ValueError(f"Category '{name}': 'destination' must be a folder template")
+            if unknown_placeholders(destination):
+                raise ValueError(f"Category '{name}': unknown placeholder(s) in 'destination': "
+                                 f"{', '.join('{' + u + '}' for u in unknown_placeholders(destination))}")
+            if '..' in Path(destination).parts:
+                raise ValueError(f"Category '{name}': 'destination' cannot contain '..'")
+        aliases = data.get('aliases') or []
+        if not isinstance(aliases, list) or not all(isinstance(alias, str) and alias.strip() for alias in aliases):
+            raise ValueError(f"Category '{name}': 'aliases' must be a list of names")
+        return cls(name=name, parent=parent.strip() if parent else None, destination=destination,
+                   aliases=[alias.strip() for alias in aliases])
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {'name': self.name, 'parent': self.parent, 'destination': self.destination,
+                'aliases': list(self.aliases)}
+
+
+def parents_first(categories: List[Category]) -> List[Category]:
+    """Parents before their children (a category whose parent never comes stays last and fails the check)."""
+    placed: List[Category] = []
+    names: set = set()
+    pending = list(categories)
+    while pending:
+        ready = [c for c in pending if c.parent is None or c.parent.lower() in names]
+        if not ready:
+            return placed + pending
+        for category in ready:
+            placed.append(category)
+            names.add(category.name.lower())
+            pending.remove(category)
+    return placed
+
+
+class Taxonomy:
+    """A checked category tree, and the mapping of classifications onto it."""
+
+    def __init__(self, categories: List[Category]):
+        """
+        Check a tree.
+
+        Args:
+            categories (List[Category]): Categories, parents first
+
+        Raises:
+            ValueError: If names repeat, a parent is unknown, or an alias points at two categories
+        """
+        self.categories = list(categories)
+        self._by_name: Dict[str, Category] = {}
+        for category in self.categories:
+            if category.name.lower() in self._by_name:
+                raise ValueError(f"There is already a category named '{category.name}'")
+            if category.parent is not None and category.parent.lower() not in self._by_name:
+                raise ValueError(f"Category '{category.name}': no parent category named '{category.parent}'")
+            if category.parent is not None:
+                category.parent = self._by_name[category.parent.lower()].name  # As the parent spells it
+            self._by_name[category.name.lower()] = category
+        self._lookup: Dict[str, Category] = {}
+        for category in self.categories:
+            self._lookup[category.name.lower()] = category
+            self._lookup[self.path(category).lower()] = category
+        for category in self.categories:
+            for alias in category.aliases:
+                known = self._lookup.setdefault(alias.lower(), category)
+                if known is not category:
+                    raise ValueError(f"'{alias}' cannot name both '{known.name}' and '{category.name}'")
+
+    @classmethod
+    def from_settings(cls, entries: List[Any]) -> 'Taxonomy':
+        """The tree from the settings' "taxonomy" list (ValueError if it is not valid)."""
+        return cls([Category.from_dict(entry) for entry in entries or []])
+
+    def path(self, category: Category) -> str:
+        """A category's place in the tree, e.g. "Documents/Invoices"."""
+        names = [category.name]
+        while category.parent is not None:
+            category = self._by_name[category.parent.lower()]
+            names.insert(0, category.name)
+        return '/'.join(names)
+
+    def find(self, name: Optional[str]) -> Optional[Category]:
+        """A category by name, path or alias (ignoring case), or None."""
+        return self._lookup.get(str(name or '').strip().strip('/').lower())
+
+    def tree(self) -> List[Dict[str, Any]]:
+        """The categories nested under their parents, for display."""
+        nodes = {category.name: {**category.to_dict(), 'path': self.path(category), 'children': []}
+                 for category in self.categories}
+        roots = []
+        for category in self.categories:
+            siblings = nodes[self._by_name[category.parent.lower()].name]['children'] if category.parent else roots
+            siblings.append(nodes[category.name])
+        return roots
+
+    def map_classification(self, classification: Dict[str, Any], file_path: str) -> Dict[str, Any]:
+        """
+        Put a classification into this tree.
+
+        Args:
+            classification (Dict): Classification result
+            file_path (str): File it is for
+
+        Returns:
+            Dict: The classification with the tree's category (the category's
+                  path) and its folder; unchanged when the tree is empty
+        """
+        if not self.categories or not classification.get('suggested_path'):
+            return classification
+        original = classification.get('category')
+        category = self.find(original)
+        mapped = dict(classification)
+        # Rules, learned corrections and strategies that chose a root already say exactly where files go
+        if classification.get('method') in KEPT_PLACEMENTS or classification.get('destination_root'):
+            if category is not None:
+                mapped['category'] = self.path(category)
+            return mapped
+        if category is None:
+            mapped['original_category'] = original
+            category = self.find(UNSORTED) or Category(UNSORTED)
+        path = self.path(category) if category in self.categories else category.name
+        folder = render_template(category.destination, Path(file_path)) if category.destination else path
+        target = Path(folder).expanduser()
+        if target.is_absolute():
+            mapped['destination_root'], mapped['suggested_path'] = str(target.parent), target.name + '/'
+        else:
+            mapped['suggested_path'] = folder + '/'
+        mapped['category'] = path
+        return mapped
+
+
+class TaxonomyManager:
+    """
+    Adds, changes and removes the user's categories, and maps classifications onto them.
+
+    Attributes:
+        config: Configuration object
+        store (SettingsStore): Settings holding the "taxonomy" list
+    """
+
+    def __init__(self, config, store: Optional[SettingsStore] = None):
+        """
+        Initialize taxonomy manager.
+
+        Args:
+            config: Configuration object
+            store (SettingsStore, optional): Settings holding the tree; default: the user's
+        """
+        self.config = config
+        self.store = store or SettingsStore()
+
+    def taxonomy(self) -> Taxonomy:
+        """
+        The stored tree.
+
+        Raises:
+            ConfigurationError: If the settings cannot be read
+        """
+        try:
+            return Taxonomy.from_settings(self.store.get('taxonomy') or [])
+        except ValueError as e:
+            logger.warning(f"Ignoring the invalid category tree: {e}")
+            return Taxonomy([])
+
+    def list_categories(self) -> List[Dict[str, Any]]:
+        """The categories, parents first, each with its 'path'."""
+        taxonomy = self.taxonomy()
+        return [{**category.to_dict(), 'path': taxonomy.path(category)} for category in taxonomy.categories]
+
+    def tree(self) -> List[Dict[str, Any]]:
+        """The categories nested under their parents (see Taxonomy.tree)."""
+        return self.taxonomy().tree()
+
+    def _save(self, categories: List[Category], message: str, **result) -> Dict[str, Any]:
+        try:
+            taxonomy = Taxonomy(parents_first(categories))
+        except ValueError as e:
+            return {'success': False, 'message': str(e)}
+        try:
+            self.store.update_settings({'taxonomy': [category.to_dict() for category in taxonomy.categories]})
+        except ConfigurationError as e:
+            return {'success': False, 'message': str(e)}
+        logger.info(message)
+        return {'success': True, 'message': message, **result}
+
+    def add_category(self, category: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Add a category to the tree.
+
+        Args:
+            category (Dict): Category (see the module docstring)
+
+        Returns:
+            Dict: 'success', 'message' and the added 'category'
+        """
+        try:
+            added = Category.from_dict(category)
+        except ValueError as e:
+            return {'success': False, 'message': str(e)}
+        return self._save(self.taxonomy().categories + [added], f"Added category '{added.name}'",
+                          category=added.to_dict())
+
+    def update_category(self, name: str, changes: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Change a category; renaming it moves its subcategories along.
+
+        Args:
+            name (str): Category to change
+            changes (Dict): Keys to set (see the module docstring)
+
+        Returns:
+            Dict: 'success', 'message' and the changed 'category'
+        """
+        categories = self.taxonomy().categories
+        current = next((c for c in categories if c.name.lower() == str(name).strip().lower()), None)
+        if current is None:
+            return {'success': False, 'message': f"No category named '{name}'"}
+        try:
+            changed = Category.from_dict({**current.to_dict(), **changes})
+        except ValueError as e:
+            return {'success': False, 'message': str(e)}
+        updated = []
+        for category in categories:
+            if category is current:
+                category = changed
+            elif category.parent and category.parent.lower() == current.name.lower():
+                category = Category(category.name, changed.name, category.destination, category.aliases)
+            updated.append(category)
+        if self._descends(updated, changed):
+            return {'success': False, 'message': f"Category '{changed.name}' cannot be inside itself"}
+        return self._save(updated, f"Updated category '{changed.name}'", category=changed.to_dict())
+
+    @staticmethod
+    def _descends(categories: List[Category], category: Category) -> bool:
+        """Whether a category's parents lead back to it."""
+        parents = {c.name.lower(): c.parent for c in categories}
+        parent, seen = category.parent, set()
+        while parent is not None and parent.lower() not in seen:
+            if parent.lower() == category.name.lower():
+                return True
+            seen.add(parent.lower())
+            parent = parents.get(parent.lower())
+        return False
+
+    def delete_category(self, name: str) -> Dict[str, Any]:
+        """Remove a category that has no subcategories."""
+        categories = self.taxonomy().categories
+        wanted = str(name).strip().lower()
+        if not any(category.name.lower() == wanted for category in categories):
+            return {'success': False, 'message': f"No category named '{name}'"}
+        children = [c.name for c in categories if c.parent and c.parent.lower() == wanted]
+        if children:
+            return {'success': False, 'message': f"Category '{name}' still has subcategories: {', '.join(children)}"}
+        return self._save([c for c in categories if c.name.lower() != wanted], f"Removed category '{name}'")
+
+    def map_classification(self, classification: Dict[str, Any], file_path: str) -> Dict[str, Any]:
+        """Map a classification onto the stored tree (see Taxonomy.map_classification)."""
+        try:
+            taxonomy = self.taxonomy()
+        except ConfigurationError as e:
+            logger.debug(f"Category tree unreadable, keeping the classifier's category: {e}")
+            return classification
+        return taxonomy.map_classification(classification, file_path)
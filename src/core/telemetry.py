@@ -0,0 +1,313 @@
+"""
+Usage Telemetry Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module counts which features are used and how often they fail, so
+development effort goes where it matters. It is off until the user turns
+it on, and even then works in two separate steps:
+
+1. Collect (`telemetry.enabled`): per-day counts are kept in
+   data/telemetry.json on this machine, viewable with `aifo telemetry`.
+2. Upload (`telemetry.upload`, also off by default): only the aggregate
+   shown by `aifo telemetry --preview` is sent, byte for byte.
+
+Nothing identifying is recorded: no file names, paths, folder names,
+error messages or installation ID - only feature names (CLI commands and
+dashboard endpoints), counts, error type names, the app version and the
+operating system family.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import atexit
+import json
+import logging
+import platform
+import re
+import threading
+import time
+import urllib.request
+from collections import Counter
+from datetime import date, timedelta
+from pathlib import Path
+from typing import Dict, Any, Optional
+
+from src import __version__
+
+logger = logging.getLogger(__name__)
+
+
+SCHEMA = 1
+KEEP_DAYS = 90
+FLUSH_SECONDS = 30.0
+# Feature and error names are identifiers, never free text
+_NAME = re.compile(r'^[A-Za-z0-9_.:/{}-]{1,80}$')
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+
+
+class Telemetry:
+    """
+    Local usage counters with an optional anonymized upload.
+
+    Attributes:
+        config: Configuration object
+        enabled (bool): Whether usage is counted
+        upload_enabled (bool): Whether aggregates may be sent
+        endpoint (str): URL aggregates are posted to
+    """
+
+    def __init__(self, config, store_file: Optional[str] = None):
+        """
+        Initialize telemetry.
+
+        Args:
+            config: Configuration object (reads `telemetry_settings`)
+            store_file (str, optional): Counter file (default: data/telemetry.json)
+        """
+        self.config = config
+        settings = getattr(config, 'telemetry_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', False))
+        self.upload_enabled = bool(settings.get('upload', False))
+        self.endpoint = settings.get('endpoint') or ''
+        self.store_file = Path(store_file or settings.get('store_file')
+                               or _APP_ROOT / 'data' / 'telemetry.json').expanduser()
+        self._pending: Dict[str, Dict[str, Counter]] = {}
+        self._last_flush = time.monotonic()
+        self._lock = threading.Lock()
+
+    # ==================== Recording ====================
+
+    def record(self, feature: str, error: Optional[str] = None) -> None:
+        """
+        Count one use of a feature, and its failure if it failed.
+
+        Args:
+            feature (str): Feature name, e.g. 'cli.organize' or 'api:/api/plan'
+            error (str, optional): Error type name, e.g. 'OSError' or 'exit-3'
+        """
+        if not self.enabled or not _NAME.match(feature or ''):
+            return
+        today = date.today().isoformat()
+        with self._lock:
+            day = self._pending.setdefault(today, {'features': Counter(), 'errors': Counter()})
+            day['features'][feature] += 1
+            if error:
+                name = error if _NAME.match(error) else 'other'
+                day['errors'][f"{feature}:{name}"] += 1
+            due = time.monotonic() - self._last_flush >= FLUSH_SECONDS
+        if due:
+            self.flush()
+
+    def flush(self) -> None:
+        """Add pending counts to the counter file."""
+        with self._lock:
+            pending, self._pending = self._pending, {}
+            self._last_flush = time.monotonic()
+            if not pending:
+                return
+            days = self._load()
+            for day, counts in pending.items():
+                stored = days.setdefault(day, {'features': {}, 'errors': {}})
+                for kind in ('features', 'errors'):
+                    for name, count in counts[kind].items():
+                        stored[kind][name] = stored[kind].get(name, 0) + count
+            oldest = (date.today() - timedelta(days=KEEP_DAYS)).isoformat()
+            self._save({day: counts for day, counts in days.items() if day >= oldest})
+
+    def _load(self) -> Dict[str, Any]:
+        try:
+            with open(self.store_file, 'r', encoding='utf-8') as f:
+                data = json.load(f)
+        except (OSError, ValueError):
+            return {}
+        days = data.get('days') if isinstance(data, dict) else None
+        return days if isinstance(days, dict) else {}
+
+    def _save(self, days: Dict[str, Any], uploaded: Optional[str] = None) -> None:
+        previous = self._read_meta()
+        meta = {'schema': SCHEMA, 'last_upload': uploaded or previous.get('last_upload'), 'days': days}
+        try:
+            self.store_file.parent.mkdir(parents=True, exist_ok=True)
+            temp = self.store_file.with_suffix('.tmp')
+            with open(temp, 'w', encoding='utf-8') as f:
+                json.dump(meta, f, indent=2, sort_keys=True)
+            temp.replace(self.store_file)
+        except OSError as e:
+            logger.debug(f"Cannot save telemetry counters: {e}")
+
+    def _read_meta(self) -> Dict[str, Any]:
+        try:
+            with open(self.store_file, 'r', encoding='utf-8') as f:
+                data = json.load(f)
+            return data if isinstance(data, dict) else {}
+        except (OSError, ValueError):
+            return {}
+
+    # ==================== Viewing ====================
+
+    def summary(self, days: int = 30) -> Dict[str, Any]:
+        """
+        The locally collected counts.
+
+        Args:
+            days (int): How many recent days to include
+
+        Returns:
+            Dict: 'enabled', 'upload', 'last_upload', 'days' covered, and
+                  'features' ({name, uses, errors, error_rate}, most used first)
+        """
+        self.flush()
+        since = (date.today() - timedelta(days=days - 1)).isoformat()
+        uses: Counter = Counter()
+        errors: Counter = Counter()
+        covered = 0
+        for day, counts in self._load().items():
+            if day < since:
+                continue
+            covered += 1
+            uses.update(counts.get('features') or {})
+            for key, count in (counts.get('errors') or {}).items():
+                errors[key.rsplit(':', 1)[0]] += count
+        features = [{'name': name, 'uses': count, 'errors': errors[name],
+                     'error_rate': round(errors[name] / count, 3) if count else 0.0}
+                    for name, count in uses.most_common()]
+        return {
+            'enabled': self.enabled,
+            'upload': self.upload_enabled,
+            'last_upload': self._read_meta().get('last_upload'),
+            'days': covered,
+            'features': features
+        }
+
+    def preview(self) -> Dict[str, Any]:
+        """
+        Exactly what upload() would send: all days since the last upload, summed.
+
+        Returns:
+            Dict: 'schema', 'app_version', 'os', 'period' {start, end},
+                  'features' {name: uses} and 'errors' {feature:type: count}
+        """
+        self.flush()
+        today = date.today().isoformat()
+        last = self._read_meta().get('last_upload') or ''
+        features: Counter = Counter()
+        errors: Counter = Counter()
+        period = []
+        for day, counts in sorted(self._load().items()):
+            if day <= last or day >= today:
+                continue  # today is still being counted
+            period.append(day)
+            features.update(counts.get('features') or {})
+            errors.update(counts.get('errors') or {})
+        return {
+            'schema': SCHEMA,
+            'app_version': __version__,
+            'os': platform.system() or 'unknown',
+            'period': {'start': period[0] if period else None, 'end': period[-1] if period else None},
+            'features': dict(sorted(features.items())),
+            'errors': dict(sorted(errors.items()))
+        }
+
+    # ==================== Upload ====================
+
+    def upload(self) -> Dict[str, Any]:
+        """
+        Send the preview() aggregate, if uploading is turned on.
+
+        Returns:
+            Dict: 'success', 'message' and the 'sent' payload
+        """
+        if not self.enabled or not self.upload_enabled:
+            return {'success': False, 'message': "Uploading usage statistics is turned off (telemetry.upload)"}
+        if not self.endpoint:
+            return {'success': False, 'message': "No telemetry endpoint configured (telemetry.endpoint)"}
+        payload = self.preview()
+        if not payload['features']:
+            return {'success': True, 'message': "Nothing to send yet", 'sent': None}
+        request = urllib.request.Request(
+            self.endpoint, data=json.dumps(payload, sort_keys=True).encode('utf-8'),
+            headers={'Content-Type': 'application/json'}, method='POST'
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=15) as response:
+                if response.status >= 300:
+                    return {'success': False, 'message': f"Server answered {response.status}"}
+        except OSError as e:
+            return {'success': False, 'message': f"Could not send usage statistics: {e}"}
+        with self._lock:
+            self._save(self._load(), uploaded=payload['period']['end'])
+        logger.info(f"Sent usage statistics for {payload['period']['start']} to {payload['period']['end']}")
+        return {'success': True, 'message': "Usage statistics sent", 'sent': payload}
+
+    def clear(self) -> Dict[str, Any]:
+        """Delete all locally collected counts."""
+        with self._lock:
+            self._pending = {}
+            try:
+                self.store_file.unlink(missing_ok=True)
+            except OSError as e:
+                return {'success': False, 'message': f"Cannot delete {self.store_file}: {e}"}
+        return {'success': True, 'message': "Usage statistics deleted"}
+
+    def set_options(self, enabled: Optional[bool] = None, upload: Optional[bool] = None) -> Dict[str, Any]:
+        """
+        Turn collecting or uploading on or off and save it in the configuration.
+
+        Args:
+            enabled (bool, optional): Collect counts locally
+            upload (bool, optional): Send aggregates (implies collecting)
+
+        Returns:
+            Dict: 'success', 'message', 'enabled' and 'upload'
+        """
+        settings = getattr(self.config, 'telemetry_settings', None)
+        settings = dict(settings) if isinstance(settings, dict) else {}
+        if enabled is not None:
+            settings['enabled'] = bool(enabled)
+            if not enabled:
+                settings['upload'] = False
+        if upload is not None:
+            settings['upload'] = bool(upload)
+            if upload:
+                settings['enabled'] = True
+        try:
+            self.config.update('telemetry', settings)
+            self.config.save()
+        except OSError as e:
+            return {'success': False, 'message': f"Cannot save config: {e}"}
+        self.enabled = bool(settings.get('enabled'))
+        self.upload_enabled = bool(settings.get('upload'))
+        state = ('collected locally and sent as totals' if self.upload_enabled
+                 else 'collected locally only' if self.enabled else 'not collected')
+        return {'success': True, 'message': f"Usage statistics are {state}",
+                'enabled': self.enabled, 'upload': self.upload_enabled}
+
+
+_telemetry_instance: Optional[Telemetry] = None
+
+
+def get_telemetry(config=None) -> Telemetry:
+    """
+    The process-wide telemetry recorder; pending counts are saved at exit.
+
+    Args:
+        config: Configuration object (default: the active configuration)
+
+    Returns:
+        Telemetry: Shared instance
+    """
+    global _telemetry_instance
+    if _telemetry_instance is None:
+        if config is None:
+            from src.config import get_config
+            config = get_config()
+        _telemetry_instance = Telemetry(config)
+        atexit.register(_telemetry_instance.flush)
+    return _telemetry_instance
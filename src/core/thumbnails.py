@@ -0,0 +1,258 @@
+"""
+Thumbnails Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module makes the small images the file lists show, so a folder of
+thousands of photos opens without decoding every full-size picture each
+time:
+
+- render_thumbnail() shrinks an image to fit a square of the requested
+  size (Pillow when installed, turned upright by its EXIF orientation),
+  else returns the preview the camera stored in the EXIF, else the image
+  itself when it is small enough to show as is
+- ThumbnailCache keeps what was rendered under data/thumbnails, keyed by
+  the file's path, size and modification time, so an edited photo gets a
+  new thumbnail and an unchanged one is read straight from disk; the
+  cache is trimmed, oldest first, when it grows past max_cache_mb
+
+get_thumbnail() returns the image as base64 for the UI; the dashboard
+serves the raw bytes at /api/thumbnails for <img> tags.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import base64
+import hashlib
+import io
+import logging
+import mimetypes
+import os
+import shutil
+import threading
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+
+from .exif import embedded_thumbnail
+from .journal import PROJECT_ROOT
+
+try:
+    from PIL import Image, ImageOps
+    PIL_SUPPORT = True
+except ImportError:
+    PIL_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_SIZE = 256
+MIN_SIZE = 16
+MAX_SIZE = 1024
+DEFAULT_MAX_CACHE_MB = 512
+JPEG_QUALITY = 80
+MAX_INLINE_IMAGE_BYTES = 256 * 1024  # Shown as is when there is no other thumbnail
+PRUNE_EVERY = 100  # Thumbnails written between checks of the cache size
+
+INLINE_IMAGE_TYPES = {'image/jpeg', 'image/png', 'image/gif', 'image/webp', 'image/bmp'}
+_EXTENSIONS = {'image/jpeg': '.jpg', 'image/png': '.png', 'image/gif': '.gif', 'image/webp': '.webp',
+               'image/bmp': '.bmp'}
+
+
+def render_thumbnail(path: str, size: int = DEFAULT_SIZE) -> Optional[Tuple[bytes, str, str]]:
+    """
+    Make a thumbnail of an image, without caching it.
+
+    Args:
+        path (str): Image file
+        size (int): Longest side in pixels
+
+    Returns:
+        Optional[Tuple[bytes, str, str]]: (data, mime type, source), source being
+            'pillow', 'exif' or 'original'; None when the file has no usable image
+    """
+    file_path = Path(path)
+    if PIL_SUPPORT:
+        try:
+            with Image.open(file_path) as image:
+                image = ImageOps.exif_transpose(image)
+                image.thumbnail((size, size))
+                buffer = io.BytesIO()
+                if image.mode in ('RGBA', 'LA') or 'transparency' in image.info:
+                    image.save(buffer, 'PNG', optimize=True)
+                    return buffer.getvalue(), 'image/png', 'pillow'
+                image.convert('RGB').save(buffer, 'JPEG', quality=JPEG_QUALITY)
+                return buffer.getvalue(), 'image/jpeg', 'pillow'
+        except Exception as e:
+            logger.debug(f"Pillow could not thumbnail {path}: {e}")
+    exif_preview = embedded_thumbnail(str(file_path))
+    if exif_preview:
+        return exif_preview, 'image/jpeg', 'exif'
+    mime_type = mimetypes.guess_type(file_path.name)[0]
+    if mime_type in INLINE_IMAGE_TYPES and file_path.stat().st_size <= MAX_INLINE_IMAGE_BYTES:
+        return file_path.read_bytes(), mime_type, 'original'
+    return None
+
+
+class ThumbnailCache:
+    """Thumbnails rendered once and kept on disk until their file changes."""
+
+    def __init__(self, config):
+        """
+        Initialize the thumbnail cache.
+
+        Args:
+            config: Configuration object (thumbnail_settings: cache_dir, max_cache_mb)
+        """
+        settings = getattr(config, 'thumbnail_settings', None)
+        if not isinstance(settings, dict):
+            settings = {}
+        cache_dir = settings.get('cache_dir') or ''
+        self.root = Path(cache_dir).expanduser() if cache_dir else PROJECT_ROOT / "data" / "thumbnails"
+        self.max_bytes = int(settings.get('max_cache_mb', DEFAULT_MAX_CACHE_MB)) * 1024 * 1024
+        self._written = 0
+        self._lock = threading.Lock()
+
+    def get_thumbnail(self, path: str, size: int = DEFAULT_SIZE, as_base64: bool = True) -> Optional[Dict[str, Any]]:
+        """
+        Thumbnail of an image, from the cache when the file has not changed.
+
+        Args:
+            path (str): Image file
+            size (int): Longest side in pixels (clamped to 16-1024)
+            as_base64 (bool): Return 'data' as base64 text rather than bytes
+
+        Returns:
+            Optional[Dict]: {"path", "size", "mime_type", "data", "source",
+                "cached", "etag"}, or None when the file has no usable image
+
+        Raises:
+            FileNotFoundError: If the file does not exist
+            IsADirectoryError: If the path is a folder
+        """
+        file_path = Path(path).expanduser()
+        if not file_path.exists():
+            raise FileNotFoundError(f"File not found: {path}")
+        if file_path.is_dir():
+            raise IsADirectoryError(f"Not a file: {path}")
+        size = max(MIN_SIZE, min(MAX_SIZE, int(size)))
+        key = self._key(file_path, size)
+
+        cached = self._read(key)
+        if cached:
+            data, mime_type, source = cached
+        else:
+            rendered = render_thumbnail(str(file_path), size)
+            if rendered is None:
+                return None
+            data, mime_type, source = rendered
+            self._write(key, data, mime_type, source)
+
+        return {
+            'path': str(file_path),
+            'size': size,
+            'mime_type': mime_type,
+            'data': base64.b64encode(data).decode('ascii') if as_base64 else data,
+            'source': source,
+            'cached': cached is not None,
+            'etag': key,
+        }
+
+    def get_thumbnails(self, paths: List[str], size: int = DEFAULT_SIZE) -> List[Dict[str, Any]]:
+        """get_thumbnail() for several files; files without one get 'data': None and an 'error'."""
+        results = []
+        for path in paths:
+            try:
+                thumbnail = self.get_thumbnail(path, size)
+            except OSError as e:
+                thumbnail = None
+                error = e.strerror or str(e)
+            else:
+                error = None if thumbnail else 'No thumbnail for this file'
+            results.append(thumbnail or {'path': path, 'size': size, 'mime_type': None, 'data': None,
+                                         'source': None, 'cached': False, 'etag': None, 'error': error})
+        return results
+
+    def stats(self) -> Dict[str, Any]:
+        """Number of cached thumbnails and the space they use."""
+        files = list(self._entries())
+        return {'path': str(self.root), 'thumbnails': len(files),
+                'bytes': sum(entry[1] for entry in files), 'max_bytes': self.max_bytes}
+
+    def clear(self) -> Dict[str, Any]:
+        """Remove every cached thumbnail."""
+        removed = len(list(self._entries()))
+        shutil.rmtree(self.root, ignore_errors=True)
+        return {'success': True, 'message': f"Removed {removed} cached thumbnail(s)", 'removed': removed}
+
+    def prune(self) -> int:
+        """Remove the least recently used thumbnails until the cache fits max_cache_mb; returns how many."""
+        entries = sorted(self._entries(), key=lambda entry: entry[2])
+        total = sum(entry[1] for entry in entries)
+        removed = 0
+        for path, length, _ in entries:
+            if total <= self.max_bytes:
+                break
+            try:
+                path.unlink()
+            except OSError:
+                continue
+            total -= length
+            removed += 1
+        return removed
+
+    def _key(self, path: Path, size: int) -> str:
+        stat = path.stat()
+        identity = f"{os.path.normcase(os.path.abspath(path))}\0{stat.st_mtime_ns}\0{stat.st_size}\0{size}"
+        return hashlib.blake2b(identity.encode('utf-8', 'surrogateescape'), digest_size=16).hexdigest()
+
+    def _read(self, key: str) -> Optional[Tuple[bytes, str, str]]:
+        for entry in (self.root / key[:2]).glob(f"{key}.*"):
+            parts = entry.name.split('.')
+            if len(parts) != 3:  # Half-written thumbnails end in .tmp
+                continue
+            source = parts[1]
+            try:
+                data = entry.read_bytes()
+                os.utime(entry)  # Marks it recently used for prune()
+            except OSError:
+                continue
+            mime_type = mimetypes.guess_type(entry.name)[0] or 'application/octet-stream'
+            return data, mime_type, source
+        return None
+
+    def _write(self, key: str, data: bytes, mime_type: str, source: str) -> None:
+        entry = self.root / key[:2] / f"{key}.{source}{_EXTENSIONS.get(mime_type, '.img')}"
+        partial = entry.with_name(f"{entry.name}.{os.getpid()}-{threading.get_ident()}.tmp")
+        try:
+            entry.parent.mkdir(parents=True, exist_ok=True)
+            partial.write_bytes(data)
+            os.replace(partial, entry)
+        except OSError as e:
+            logger.warning(f"Could not cache thumbnail {entry}: {e}")
+            partial.unlink(missing_ok=True)
+            return
+        with self._lock:
+            self._written += 1
+            due = self._written % PRUNE_EVERY == 0
+        if due:
+            self.prune()
+
+    def _entries(self):
+        if not self.root.is_dir():
+            return
+        for folder in self.root.iterdir():
+            if not folder.is_dir():
+                continue
+            for entry in folder.iterdir():
+                if entry.suffix == '.tmp':
+                    continue
+                try:
+                    stat = entry.stat()
+                except OSError:
+                    continue
+                yield entry, stat.st_size, stat.st_mtime
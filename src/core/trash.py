@@ -0,0 +1,334 @@
+"""
+Trash Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module puts files the user removes in the platform trash instead of
+deleting them, and can put them back.
+
+    - Linux and BSD: the freedesktop.org trash (~/.local/share/Trash, or
+      the drive's own .Trash-<uid> for other drives), so the file manager
+      shows the files and can restore them too
+    - macOS: ~/.Trash
+    - Windows: the Recycle Bin (send2trash when installed, else the shell)
+    - backend "app" in the `trash` settings: the application's trash_dir
+
+Every file trashed here is remembered in the database, which is what the
+"recently removed" view lists and what restore_from_trash() uses to put
+a file back at its old path (as "name (2).ext" if that path is taken
+again). The Recycle Bin does not say where a file went, so on Windows
+files are restored from the Recycle Bin itself.
+
+Moves made by organizing are undone with `aifo undo`; this trash is for
+files that are removed.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import errno
+import logging
+import os
+import shutil
+import stat
+import sys
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Tuple
+from urllib.parse import quote
+
+from .fsops import free_name, move_file
+from .journal import trash_root
+from .safety_guardian import SafetyGuardian
+
+try:
+    from send2trash import send2trash
+    SEND2TRASH_SUPPORT = True
+except ImportError:
+    SEND2TRASH_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+BACKENDS = ('system', 'app')
+DEFAULT_LIST_LIMIT = 100
+
+
+def platform_backend() -> str:
+    """The platform trash in use here: 'windows', 'macos' or 'freedesktop'."""
+    if sys.platform == 'win32':
+        return 'windows'
+    if sys.platform == 'darwin':
+        return 'macos'
+    return 'freedesktop'
+
+
+class SystemTrash:
+    """
+    Moves files to the trash and restores them.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager (remembers trashed files)
+        backend (str): 'freedesktop', 'macos', 'windows' or 'app'
+    """
+
+    def __init__(self, config, db_manager, guardian: Optional[SafetyGuardian] = None):
+        """
+        Initialize trash.
+
+        Args:
+            config: Configuration object (reads `trash_settings`)
+            db_manager: Database manager instance
+            guardian (SafetyGuardian, optional): Guardian to reuse for safety checks
+        """
+        self.config = config
+        self.db_manager = db_manager
+        settings = getattr(config, 'trash_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.backend = 'app' if settings.get('backend', 'system') == 'app' else platform_backend()
+        self._guardian = guardian or SafetyGuardian(config)
+
+    # ==================== Trashing ====================
+
+    def move_to_trash(self, paths: List[str]) -> Dict[str, Any]:
+        """
+        Move files or folders to the trash.
+
+        Args:
+            paths (List[str]): Files and folders to remove
+
+        Returns:
+            Dict: 'success', 'message', 'backend', 'trashed' (list of
+                  {id, path, trashed_path}) and 'failed' (list of {path, reason})
+        """
+        trashed: List[Dict[str, Any]] = []
+        failed: List[Dict[str, str]] = []
+        for raw in paths:
+            path = Path(raw).expanduser().absolute()
+            if not os.path.lexists(path):
+                failed.append({'path': str(path), 'reason': 'Not found'})
+                continue
+            is_safe, reason = self._guardian.is_file_safe_to_modify(path)
+            if not is_safe:
+                failed.append({'path': str(path), 'reason': reason})
+                continue
+            is_dir = path.is_dir() and not path.is_symlink()
+            size = _size(path)
+            try:
+                trashed_path = self._trash(path)
+            except OSError as e:
+                logger.warning(f"Could not move {path} to the trash: {e}")
+                failed.append({'path': str(path), 'reason': str(e.strerror or e)})
+                continue
+            item_id = self.db_manager.add_trash_item(str(path), trashed_path, self.backend, size, is_dir)
+            logger.info(f"Moved to the trash: {path}")
+            trashed.append({'id': item_id, 'path': str(path), 'trashed_path': trashed_path})
+
+        message = f"Moved {len(trashed)} item(s) to the trash"
+        if failed:
+            message += f", {len(failed)} could not be moved"
+        return {'success': bool(trashed) or not failed, 'message': message, 'backend': self.backend,
+                'trashed': trashed, 'failed': failed}
+
+    def _trash(self, path: Path) -> Optional[str]:
+        """Trash one path; returns where it went, or None if the platform does not say."""
+        if self.backend == 'freedesktop':
+            return self._freedesktop_trash(path)
+        if self.backend == 'macos':
+            return str(_move(path, free_name(Path.home() / '.Trash' / path.name)))
+        if self.backend == 'windows':
+            _windows_trash(path)
+            return None
+        return str(_move(path, free_name(trash_root(self.config) / 'removed' / path.name)))
+
+    def _freedesktop_trash(self, path: Path) -> str:
+        trash, info_path = _freedesktop_trash_dir(path)
+        (trash / 'files').mkdir(parents=True, exist_ok=True)
+        (trash / 'info').mkdir(parents=True, exist_ok=True)
+        info = (f"[Trash Info]\nPath={quote(info_path)}\n"
+                f"DeletionDate={datetime.now().strftime('%Y-%m-%dT%H:%M:%S')}\n")
+
+        # The .trashinfo file is created first and exclusively: it reserves the name
+        counter = 1
+        while True:
+            name = path.name if counter == 1 else f"{path.stem} ({counter}){path.suffix}"
+            counter += 1
+            if os.path.lexists(trash / 'files' / name):
+                continue
+            info_file = trash / 'info' / f"{name}.trashinfo"
+            try:
+                fd = os.open(info_file, os.O_WRONLY | os.O_CREAT | os.O_EXCL, 0o600)
+            except FileExistsError:
+                continue
+            with os.fdopen(fd, 'w', encoding='utf-8') as f:
+                f.write(info)
+            break
+        try:
+            return str(_move(path, trash / 'files' / name))
+        except OSError:
+            info_file.unlink()
+            raise
+
+    # ==================== Restoring ====================
+
+    def restore_from_trash(self, ids: List[int]) -> Dict[str, Any]:
+        """
+        Put trashed items back where they were.
+
+        Args:
+            ids (List[int]): Trash item IDs (from list_trash_items)
+
+        Returns:
+            Dict: 'success', 'message', 'restored' (list of {id, path}) and
+                  'failed' (list of {id, reason})
+        """
+        restored: List[Dict[str, Any]] = []
+        failed: List[Dict[str, Any]] = []
+        for item_id in ids:
+            item = self.db_manager.get_trash_item(int(item_id))
+            reason = self._why_not_restorable(item)
+            if reason:
+                failed.append({'id': item_id, 'reason': reason})
+                continue
+            trashed = Path(item['trashed_path'])
+            target = free_name(Path(item['original_path']))
+            try:
+                _move(trashed, target)
+            except OSError as e:
+                failed.append({'id': item_id, 'reason': str(e.strerror or e)})
+                continue
+            if item['backend'] == 'freedesktop':
+                info_file = trashed.parent.parent / 'info' / f"{trashed.name}.trashinfo"
+                try:
+                    info_file.unlink()
+                except OSError:
+                    pass
+            self.db_manager.mark_trash_item_restored(item['id'])
+            logger.info(f"Restored from the trash: {target}")
+            restored.append({'id': item['id'], 'path': str(target)})
+
+        message = f"Restored {len(restored)} item(s)"
+        if failed:
+            message += f", {len(failed)} could not be restored"
+        return {'success': bool(restored) or not failed, 'message': message,
+                'restored': restored, 'failed': failed}
+
+    @staticmethod
+    def _why_not_restorable(item: Optional[Dict[str, Any]]) -> Optional[str]:
+        if item is None:
+            return 'No such trash item'
+        if item['restored_at']:
+            return 'Already restored'
+        if not item['trashed_path']:
+            return 'The Recycle Bin does not say where it is; restore it from the Recycle Bin'
+        if not os.path.lexists(item['trashed_path']):
+            return 'No longer in the trash (it was emptied or restored elsewhere)'
+        return None
+
+    # ==================== Listing ====================
+
+    def list_trash_items(self, limit: int = DEFAULT_LIST_LIMIT) -> List[Dict[str, Any]]:
+        """
+        Items this application moved to the trash and has not restored, newest first.
+
+        Args:
+            limit (int): Most items to return
+
+        Returns:
+            List[Dict]: id, name, original_path, trashed_path, backend, size,
+                        is_dir, trashed_at, in_trash (None if unknown) and restorable
+        """
+        items = []
+        for item in self.db_manager.list_trash_items(int(limit)):
+            in_trash = os.path.lexists(item['trashed_path']) if item['trashed_path'] else None
+            items.append({**item, 'name': Path(item['original_path']).name, 'is_dir': bool(item['is_dir']),
+                          'in_trash': in_trash, 'restorable': bool(in_trash)})
+        return items
+
+
+def _size(path: Path) -> int:
+    try:
+        if not path.is_dir() or path.is_symlink():
+            return path.lstat().st_size
+        return sum(f.stat().st_size for f in path.rglob('*') if f.is_file() and not f.is_symlink())
+    except OSError:
+        return 0
+
+
+def _move(source: Path, destination: Path) -> Path:
+    """Move a file (verified across drives) or a folder to a free destination."""
+    destination.parent.mkdir(parents=True, exist_ok=True)
+    if source.is_file() and not source.is_symlink():
+        result = move_file(str(source), str(destination), on_conflict='skip')
+        if result['status'] != 'moved':
+            raise OSError(errno.EEXIST if result['status'] == 'skipped' else errno.EIO, result['message'])
+        return Path(result['destination'])
+    try:
+        os.rename(source, destination)
+    except OSError as e:
+        if e.errno != errno.EXDEV:
+            raise
+        shutil.move(str(source), str(destination))
+    return destination
+
+
+def _home_trash() -> Path:
+    data_home = os.environ.get('XDG_DATA_HOME') or os.path.join(os.path.expanduser('~'), '.local', 'share')
+    return Path(data_home) / 'Trash'
+
+
+def _mount_point(path: Path) -> Path:
+    path = path.parent.resolve()
+    device = path.stat().st_dev
+    while path.parent != path and path.parent.stat().st_dev == device:
+        path = path.parent
+    return path
+
+
+def _freedesktop_trash_dir(path: Path) -> Tuple[Path, str]:
+    """The trash for a path and the Path= value its .trashinfo gets."""
+    home = _home_trash()
+    home.mkdir(parents=True, exist_ok=True)
+    if os.stat(path.parent).st_dev == os.stat(home).st_dev:
+        return home, str(path)
+    # Another drive: its own trash, so nothing is copied across drives
+    try:
+        top = _mount_point(path)
+        uid = os.getuid()
+        shared = top / '.Trash'
+        if shared.is_dir() and not shared.is_symlink() and shared.stat().st_mode & stat.S_ISVTX:
+            trash = shared / str(uid)
+        else:
+            trash = top / f'.Trash-{uid}'
+        trash.mkdir(mode=0o700, exist_ok=True)
+        return trash, os.path.relpath(path, top)
+    except OSError as e:
+        logger.debug(f"No trash on the drive of {path} ({e}); using {home}")
+        return home, str(path)
+
+
+def _windows_trash(path: Path) -> None:
+    if SEND2TRASH_SUPPORT:
+        send2trash(str(path))
+        return
+    import ctypes
+    from ctypes import wintypes
+
+    class SHFILEOPSTRUCTW(ctypes.Structure):
+        _fields_ = [('hwnd', wintypes.HWND), ('wFunc', wintypes.UINT), ('pFrom', wintypes.LPCWSTR),
+                    ('pTo', wintypes.LPCWSTR), ('fFlags', ctypes.c_uint16),
+                    ('fAnyOperationsAborted', wintypes.BOOL), ('hNameMappings', ctypes.c_void_p),
+                    ('lpszProgressTitle', wintypes.LPCWSTR)]
+
+    fo_delete = 0x0003
+    # FOF_SILENT | FOF_NOCONFIRMATION | FOF_ALLOWUNDO | FOF_NOERRORUI
+    flags = 0x0004 | 0x0010 | 0x0040 | 0x0400
+    operation = SHFILEOPSTRUCTW(wFunc=fo_delete, pFrom=str(path) + '\0', fFlags=flags)
+    code = ctypes.windll.shell32.SHFileOperationW(ctypes.byref(operation))
+    if code or operation.fAnyOperationsAborted:
+        raise OSError(errno.EIO, f"The Recycle Bin refused {path.name} (code {code})")
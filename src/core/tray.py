@@ -0,0 +1,243 @@
+"""
+Tray Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module puts the organiser in the system tray (notification area on
+Windows, menu bar on macOS) while the dashboard or the engine runs, with
+quick actions that need no window:
+
+- Organize Downloads now: starts an organize job for the Downloads folder
+- Pause watching / Resume watching: stops or restarts the folder watcher
+- Open dashboard: opens the dashboard in the browser
+
+The tooltip shows what is going on; front ends set it with set_status()
+(the set_tray_status engine method, or POST /api/tray/status), e.g.
+"AI File Organiser - Organizing Downloads (40%)".
+
+The icon needs pystray and Pillow; without them the actions and status
+still work for callers, there is just no icon.
+
+    "tray": {"enabled": true, "downloads_folder": "", "dashboard_url": ""}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import threading
+import webbrowser
+from pathlib import Path
+from typing import Callable, Dict, Any, List, Optional, Tuple
+
+try:
+    import pystray  # type: ignore
+    from PIL import Image, ImageDraw
+    PYSTRAY_SUPPORT = True
+except ImportError:
+    PYSTRAY_SUPPORT = False
+
+logger = logging.getLogger(__name__)
+
+
+APP_NAME = 'AI File Organiser'
+DEFAULT_DASHBOARD_URL = 'http://127.0.0.1:5000'
+MAX_TOOLTIP = 127  # Windows cuts tooltips at 128 characters
+ICON_SIZE = 64
+
+
+class TrayIcon:
+    """
+    The tray icon, its menu and its tooltip.
+
+    Actions (all optional; menu items without one are left out):
+
+        organize (Callable[[str], Dict]): Start organizing a folder (returns the job)
+        pause (Callable[[], Any]): Stop watching
+        resume (Callable[[], Any]): Start watching again
+        is_watching (Callable[[], bool]): Whether the watcher runs
+
+    Attributes:
+        config: Configuration object
+        actions (Dict[str, Callable]): The actions above
+        dashboard_url (str): Opened by "Open dashboard"
+        status (Dict): The current 'text', 'progress' and 'tooltip'
+    """
+
+    def __init__(self, config, actions: Dict[str, Callable], dashboard_url: Optional[str] = None):
+        """
+        Initialize the tray icon (not shown until start()).
+
+        Args:
+            config: Configuration object (reads `tray_settings`)
+            actions (Dict[str, Callable]): See the class docstring
+            dashboard_url (str, optional): Dashboard address (default: tray.dashboard_url)
+        """
+        self.config = config
+        settings = getattr(config, 'tray_settings', None)
+        self.settings = settings if isinstance(settings, dict) else {}
+        self.actions = actions
+        self.dashboard_url = dashboard_url or self.settings.get('dashboard_url') or DEFAULT_DASHBOARD_URL
+        self.status: Dict[str, Any] = {'text': None, 'progress': None, 'tooltip': APP_NAME}
+        self._icon = None
+        self._lock = threading.Lock()
+
+    @property
+    def enabled(self) -> bool:
+        """Whether the tray icon should be shown (tray.enabled)."""
+        return bool(self.settings.get('enabled', True))
+
+    def downloads_folder(self) -> Path:
+        """The folder "Organize Downloads now" organizes (tray.downloads_folder, else ~/Downloads)."""
+        configured = self.settings.get('downloads_folder')
+        return Path(configured).expanduser() if configured else Path.home() / 'Downloads'
+
+    def menu_items(self) -> List[Tuple[str, Callable[[], Any]]]:
+        """The menu as (label, handler) pairs, in order."""
+        items = []
+        if 'organize' in self.actions:
+            items.append(('Organize Downloads now', self.organize_downloads))
+        if 'pause' in self.actions and 'resume' in self.actions:
+            items.append((self._toggle_label(), self.toggle_watching))
+        items.append(('Open dashboard', self.open_dashboard))
+        return items
+
+    def _toggle_label(self, _item=None) -> str:
+        return 'Pause watching' if self._watching() else 'Resume watching'
+
+    def _watching(self) -> bool:
+        is_watching = self.actions.get('is_watching')
+        return bool(is_watching()) if is_watching else True
+
+    def organize_downloads(self) -> Dict[str, Any]:
+        """Start organizing the Downloads folder."""
+        folder = self.downloads_folder()
+        if not folder.is_dir():
+            self.set_status(f"No Downloads folder at {folder}")
+            return {'success': False, 'message': f"Not a folder: {folder}"}
+        job = self.actions['organize'](str(folder))
+        self.set_status(f"Organizing {folder.name}", 0.0)
+        return {'success': True, 'message': f"Organizing {folder}", 'job': job}
+
+    def toggle_watching(self) -> Dict[str, Any]:
+        """Pause the watcher when it runs, else resume it."""
+        if self._watching():
+            self.actions['pause']()
+            self.set_status('Watching paused')
+            watching = False
+        else:
+            self.actions['resume']()
+            self.set_status(None)
+            watching = True
+        self._refresh_menu()
+        return {'success': True, 'watching': watching}
+
+    def open_dashboard(self) -> Dict[str, Any]:
+        """Open the dashboard in the browser."""
+        webbrowser.open(self.dashboard_url)
+        return {'success': True, 'url': self.dashboard_url}
+
+    def set_status(self, text: Optional[str] = None, progress: Optional[float] = None) -> Dict[str, Any]:
+        """
+        Show what is going on in the tooltip.
+
+        Args:
+            text (str, optional): e.g. "Organizing Downloads" (None: idle)
+            progress (float, optional): 0.0 to 1.0, shown as a percentage
+
+        Returns:
+            Dict: 'success', 'tooltip' and 'shown' (whether an icon displays it)
+        """
+        tooltip = APP_NAME
+        if text:
+            tooltip += f" - {text}"
+        if progress is not None:
+            progress = min(max(float(progress), 0.0), 1.0)
+            tooltip += f" ({round(progress * 100)}%)"
+        if len(tooltip) > MAX_TOOLTIP:
+            tooltip = tooltip[:MAX_TOOLTIP - 1] + '…'
+        with self._lock:
+            self.status = {'text': text, 'progress': progress, 'tooltip': tooltip}
+            icon = self._icon
+        if icon is not None:
+            icon.title = tooltip
+        return {'success': True, 'tooltip': tooltip, 'shown': icon is not None}
+
+    def notify(self, title: str, message: str) -> bool:
+        """Show a notification from the icon; False when no icon is up or it cannot notify."""
+        with self._lock:
+            icon = self._icon
+        if icon is None or not getattr(icon, 'HAS_NOTIFICATION', False):
+            return False
+        try:
+            icon.notify(message, title)
+        except Exception as e:
+            logger.debug(f"Tray notification failed: {e}")
+            return False
+        return True
+
+    def start(self) -> bool:
+        """
+        Show the icon (its menu runs in a background thread).
+
+        Returns:
+            bool: Whether the icon is shown (False when disabled or pystray is missing)
+        """
+        if not self.enabled:
+            return False
+        if not PYSTRAY_SUPPORT:
+            logger.info("Install pystray and Pillow for a tray icon")
+            return False
+        with self._lock:
+            if self._icon is not None:
+                return True
+            self._icon = pystray.Icon('ai-file-organiser', _icon_image(), self.status['tooltip'], self._menu())
+        try:
+            self._icon.run_detached()
+        except Exception as e:  # e.g. no tray on this desktop, or not allowed off the main thread
+            logger.warning(f"Tray icon not shown: {e}")
+            with self._lock:
+                self._icon = None
+            return False
+        return True
+
+    def stop(self) -> None:
+        """Take the icon away."""
+        with self._lock:
+            icon, self._icon = self._icon, None
+        if icon is not None:
+            icon.stop()
+
+    def _menu(self):
+        # The watching item's label is looked up each time the menu opens
+        return pystray.Menu(*(
+            pystray.MenuItem(self._toggle_label if handler == self.toggle_watching else label, _action(handler),
+                             default=handler == self.open_dashboard)
+            for label, handler in self.menu_items()))
+
+    def _refresh_menu(self) -> None:
+        if self._icon is not None:
+            self._icon.update_menu()
+
+
+def _action(handler: Callable[[], Any]) -> Callable[[], None]:
+    """A menu action whose failure is logged instead of taking the tray down."""
+    def run() -> None:
+        try:
+            handler()
+        except Exception as e:
+            logger.error(f"Tray action failed: {e}")
+    return run
+
+
+def _icon_image():
+    """A simple folder-shaped icon drawn at start, so no image file has to ship."""
+    image = Image.new('RGBA', (ICON_SIZE, ICON_SIZE), (0, 0, 0, 0))
+    draw = ImageDraw.Draw(image)
+    draw.rounded_rectangle((4, 10, 30, 22), radius=4, fill=(240, 180, 40))
+    draw.rounded_rectangle((4, 16, 60, 56), radius=6, fill=(250, 200, 60))
+    return image
@@ -0,0 +1,531 @@
+"""
+Updater Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps the installation up to date from a release channel
+("stable" or "beta"). Each channel publishes a signed JSON manifest at
+`updates.manifest_url` ({channel} is replaced by the channel name):
+
+    {
+        "version": "1.3.0",
+        "channel": "stable",
+        "notes": "...",
+        "rollout": 25,
+        "package": {"url": "...", "sha256": "...", "size": 123},
+        "deltas": [{"from": "1.2.0", "url": "...", "sha256": "...", "size": 45}],
+        "signature": "<base64 Ed25519 signature>"
+    }
+
+The signature covers the manifest without its "signature" field, as
+compact JSON with sorted keys, and is checked against
+`updates.public_key` (hex); unsigned or badly signed manifests are
+refused, and every download must match the SHA-256 its signed manifest
+lists. "rollout" is the percentage of installations offered the release;
+each installation falls into a stable bucket of its own, so a staged
+rollout widens without anyone flip-flopping between versions.
+
+Packages are ZIP files of the application tree; a delta from the running
+version holds only the changed files. Either may list files to delete in
+`aifo-removed.json`. The user's data (data/, logs/, config.json) is never
+touched. Before installing, everything the update replaces is saved; the
+next start runs the self test, and if it fails the previous version is
+put back.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import base64
+import hashlib
+import json
+import logging
+import re
+import shutil
+import threading
+import urllib.request
+import uuid
+import zipfile
+from datetime import datetime
+from pathlib import Path, PurePosixPath
+from typing import Dict, Any, List, Optional, Callable, Tuple
+
+from src import __version__
+
+logger = logging.getLogger(__name__)
+
+try:
+    from cryptography.exceptions import InvalidSignature
+    from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PublicKey
+    HAS_CRYPTOGRAPHY = True
+except ImportError:
+    HAS_CRYPTOGRAPHY = False
+
+
+CHANNELS = ('stable', 'beta')
+REMOVED_LIST = 'aifo-removed.json'
+# Never written by an update: the user's data and settings
+PRESERVED = ('data', 'logs', 'config', 'config.json', '.git')
+USER_AGENT = f"AI-File-Organiser/{__version__} updater"
+DOWNLOAD_TIMEOUT = 60
+CHUNK = 1024 * 1024
+
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+_VERSION = re.compile(r'^v?(\d+(?:\.\d+)*)(?:-([0-9A-Za-z.-]+))?$')
+
+
+def parse_version(version: str) -> Tuple[Tuple[int, ...], int, Tuple[Any, ...]]:
+    """
+    A sortable key for a version: '1.3.0-beta.2' < '1.3.0' < '1.3.1'.
+
+    Args:
+        version (str): Version string
+
+    Returns:
+        Tuple: (numbers, 1 for releases / 0 for pre-releases, pre-release parts)
+    """
+    match = _VERSION.match(str(version).strip())
+    if not match:
+        return (), 0, ()
+    numbers = tuple(int(p) for p in match.group(1).split('.'))
+    numbers = numbers + (0,) * (3 - len(numbers)) if len(numbers) < 3 else numbers
+    pre = match.group(2)
+    if not pre:
+        return numbers, 1, ()
+    return numbers, 0, tuple((0, int(p), '') if p.isdigit() else (1, 0, p) for p in pre.split('.'))
+
+
+def signed_payload(manifest: Dict[str, Any]) -> bytes:
+    """The bytes a manifest's signature covers."""
+    body = {k: v for k, v in manifest.items() if k != 'signature'}
+    return json.dumps(body, sort_keys=True, separators=(',', ':'), ensure_ascii=False).encode('utf-8')
+
+
+def _member_path(name: str) -> Optional[PurePosixPath]:
+    """A package member as a safe relative path, or None for preserved or escaping paths."""
+    path = PurePosixPath(name.replace('\\', '/'))
+    if path.is_absolute() or not path.parts or '..' in path.parts or ':' in path.parts[0]:
+        return None
+    if path.parts[0] in PRESERVED:
+        return None
+    return path
+
+
+class UpdateManager:
+    """
+    Checks for, downloads, installs and rolls back updates.
+
+    Attributes:
+        config: Configuration object
+        channel (str): Release channel
+        app_root (Path): Installation being updated
+        state_dir (Path): Staged packages, backups and update state
+    """
+
+    def __init__(self, config, app_root: Optional[Path] = None, state_dir: Optional[Path] = None,
+                 current_version: Optional[str] = None):
+        """
+        Initialize update manager.
+
+        Args:
+            config: Configuration object (reads `update_settings`)
+            app_root (Path, optional): Installation folder (default: this one)
+            state_dir (Path, optional): State folder (default: data/updates)
+            current_version (str, optional): Running version (default: src.__version__)
+        """
+        self.config = config
+        settings = getattr(config, 'update_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.enabled = bool(settings.get('enabled', True))
+        self.channel = settings.get('channel') if settings.get('channel') in CHANNELS else 'stable'
+        self.manifest_url = settings.get('manifest_url') or ''
+        self.public_key = settings.get('public_key') or ''
+        self.app_root = Path(app_root or _APP_ROOT)
+        self.state_dir = Path(state_dir or settings.get('state_dir') or self.app_root / 'data' / 'updates').expanduser()
+        self.current_version = current_version or __version__
+        self._lock = threading.Lock()
+
+    # ==================== State ====================
+
+    @property
+    def state_file(self) -> Path:
+        return self.state_dir / 'state.json'
+
+    def _load_state(self) -> Dict[str, Any]:
+        try:
+            with open(self.state_file, 'r', encoding='utf-8') as f:
+                state = json.load(f)
+            return state if isinstance(state, dict) else {}
+        except (OSError, ValueError):
+            return {}
+
+    def _save_state(self, state: Dict[str, Any]) -> None:
+        self.state_dir.mkdir(parents=True, exist_ok=True)
+        temp = self.state_file.with_suffix('.tmp')
+        with open(temp, 'w', encoding='utf-8') as f:
+            json.dump(state, f, indent=2)
+        temp.replace(self.state_file)
+
+    def install_id(self) -> str:
+        """This installation's random identifier, used for its rollout bucket."""
+        with self._lock:
+            state = self._load_state()
+            if not state.get('install_id'):
+                state['install_id'] = uuid.uuid4().hex
+                self._save_state(state)
+            return state['install_id']
+
+    def rollout_bucket(self, version: str) -> int:
+        """0-99; the release is offered when its rollout percentage is above this."""
+        digest = hashlib.sha256(f"{self.install_id()}:{version}".encode('utf-8')).hexdigest()
+        return int(digest[:8], 16) % 100
+
+    def status(self) -> Dict[str, Any]:
+        """
+        Current version, channel, and any pending or undoable update.
+
+        Returns:
+            Dict: 'current', 'channel', 'pending', 'previous', 'staged' and 'last_result'
+        """
+        state = self._load_state()
+        return {
+            'current': self.current_version,
+            'channel': self.channel,
+            'pending': state.get('pending'),
+            'previous': (state.get('previous') or {}).get('version'),
+            'staged': (state.get('staged') or {}).get('version'),
+            'last_result': state.get('last_result')
+        }
+
+    def set_channel(self, channel: str) -> Dict[str, Any]:
+        """
+        Switch release channel and save it in the configuration.
+
+        Args:
+            channel (str): 'stable' or 'beta'
+
+        Returns:
+            Dict: 'success' and 'message'
+        """
+        if channel not in CHANNELS:
+            return {'success': False, 'message': f"Unknown channel {channel!r} (use {' or '.join(CHANNELS)})"}
+        settings = getattr(self.config, 'update_settings', None)
+        settings = dict(settings) if isinstance(settings, dict) else {}
+        settings['channel'] = channel
+        try:
+            self.config.update('updates', settings)
+            self.config.save()
+        except OSError as e:
+            return {'success': False, 'message': f"Cannot save config: {e}"}
+        self.channel = channel
+        return {'success': True, 'message': f"Following the {channel} channel"}
+
+    # ==================== Check ====================
+
+    def verify_manifest(self, manifest: Dict[str, Any]) -> Optional[str]:
+        """
+        Check a manifest's signature.
+
+        Args:
+            manifest (Dict): Manifest as downloaded
+
+        Returns:
+            str or None: Why the manifest is refused, or None if it is genuine
+        """
+        if not HAS_CRYPTOGRAPHY:
+            return "The cryptography package is needed to verify updates"
+        if not self.public_key:
+            return "No update signing key configured (updates.public_key)"
+        signature = manifest.get('signature')
+        if not isinstance(signature, str):
+            return "The update manifest is not signed"
+        try:
+            key = Ed25519PublicKey.from_public_bytes(bytes.fromhex(self.public_key))
+            key.verify(base64.b64decode(signature), signed_payload(manifest))
+        except (ValueError, InvalidSignature):
+            return "The update manifest's signature is invalid"
+        return None
+
+    def fetch_manifest(self) -> Dict[str, Any]:
+        """
+        Download and verify the channel's manifest.
+
+        Returns:
+            Dict: 'success', 'message' and the verified 'manifest'
+        """
+        if not self.manifest_url:
+            return {'success': False, 'message': "No update server configured (updates.manifest_url)"}
+        url = self.manifest_url.replace('{channel}', self.channel)
+        request = urllib.request.Request(url, headers={'User-Agent': USER_AGENT})
+        try:
+            with urllib.request.urlopen(request, timeout=DOWNLOAD_TIMEOUT) as response:
+                manifest = json.loads(response.read(CHUNK).decode('utf-8'))
+        except (OSError, ValueError) as e:
+            return {'success': False, 'message': f"Cannot reach the update server: {e}"}
+        if not isinstance(manifest, dict) or not manifest.get('version') or not isinstance(manifest.get('package'), dict):
+            return {'success': False, 'message': "The update manifest is malformed"}
+        refused = self.verify_manifest(manifest)
+        if refused:
+            logger.warning(f"Refused update manifest from {url}: {refused}")
+            return {'success': False, 'message': refused}
+        if manifest.get('channel', self.channel) != self.channel:
+            return {'success': False, 'message': f"The manifest is for the {manifest['channel']} channel"}
+        return {'success': True, 'message': 'Manifest verified', 'manifest': manifest}
+
+    def check(self) -> Dict[str, Any]:
+        """
+        Whether a newer version is offered to this installation.
+
+        Returns:
+            Dict: 'success', 'message', 'available', 'current', 'version',
+                  'notes', 'delta' (a delta from this version exists),
+                  'size' of the download and 'manifest'
+        """
+        result: Dict[str, Any] = {'current': self.current_version, 'channel': self.channel, 'available': False}
+        if not self.enabled:
+            return {**result, 'success': True, 'message': 'Updates are turned off'}
+        fetched = self.fetch_manifest()
+        if not fetched['success']:
+            return {**result, 'success': False, 'message': fetched['message']}
+        manifest = fetched['manifest']
+        version = str(manifest['version'])
+        result.update(version=version, notes=manifest.get('notes') or '', manifest=manifest)
+        if parse_version(version) <= parse_version(self.current_version):
+            return {**result, 'success': True, 'message': f"{self.current_version} is up to date"}
+        rollout = manifest.get('rollout', 100)
+        rollout = rollout if isinstance(rollout, (int, float)) else 100
+        if self.rollout_bucket(version) >= rollout:
+            return {**result, 'success': True,
+                    'message': f"{version} is rolling out gradually and not yet offered here"}
+        delta = self._delta_for(manifest)
+        result.update(available=True, delta=delta is not None,
+                      size=(delta or manifest['package']).get('size'))
+        return {**result, 'success': True, 'message': f"Version {version} is available ({self.channel})"}
+
+    def _delta_for(self, manifest: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        for delta in manifest.get('deltas') or []:
+            if isinstance(delta, dict) and str(delta.get('from')) == self.current_version and delta.get('url'):
+                return delta
+        return None
+
+    # ==================== Download and install ====================
+
+    def _download(self, entry: Dict[str, Any], target: Path) -> Optional[str]:
+        """Download one package to `target`; returns why it failed, or None."""
+        url, expected = entry.get('url'), str(entry.get('sha256') or '').lower()
+        if not url or not expected:
+            return "The manifest entry has no URL or checksum"
+        limit = entry.get('size') if isinstance(entry.get('size'), int) else None
+        target.parent.mkdir(parents=True, exist_ok=True)
+        temp = target.with_suffix('.part')
+        digest = hashlib.sha256()
+        written = 0
+        try:
+            request = urllib.request.Request(url, headers={'User-Agent': USER_AGENT})
+            with urllib.request.urlopen(request, timeout=DOWNLOAD_TIMEOUT) as response, open(temp, 'wb') as f:
+                for block in iter(lambda: response.read(CHUNK), b''):
+                    written += len(block)
+                    if limit is not None and written > limit:
+                        raise ValueError("larger than the manifest says")
+                    digest.update(block)
+                    f.write(block)
+        except (OSError, ValueError) as e:
+            temp.unlink(missing_ok=True)
+            return f"Download failed: {e}"
+        if digest.hexdigest() != expected:
+            temp.unlink(missing_ok=True)
+            return "The download does not match its checksum"
+        temp.replace(target)
+        return None
+
+    def download(self, manifest: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Download an update, preferring a delta from the running version.
+
+        Args:
+            manifest (Dict, optional): Verified manifest (default: check again)
+
+        Returns:
+            Dict: 'success', 'message', 'version', 'delta' and the 'staged' package path
+        """
+        if manifest is None:
+            checked = self.check()
+            if not checked.get('available'):
+                return {'success': False, 'message': checked['message']}
+            manifest = checked['manifest']
+        version = str(manifest['version'])
+        staging = self.state_dir / 'staged'
+        shutil.rmtree(staging, ignore_errors=True)
+
+        delta = self._delta_for(manifest)
+        failure = None
+        for entry, is_delta in ((delta, True), (manifest['package'], False)):
+            if entry is None:
+                continue
+            target = staging / f"{version}{'-delta' if is_delta else ''}.zip"
+            failure = self._download(entry, target)
+            if failure is None:
+                with self._lock:
+                    state = self._load_state()
+                    state['staged'] = {'version': version, 'path': str(target), 'delta': is_delta,
+                                       'from': self.current_version}
+                    self._save_state(state)
+                kind = 'delta' if is_delta else 'full package'
+                return {'success': True, 'message': f"Downloaded {version} ({kind})", 'version': version,
+                        'delta': is_delta, 'staged': str(target)}
+            logger.warning(f"Update {'delta' if is_delta else 'package'} for {version}: {failure}")
+        return {'success': False, 'message': failure or "Nothing to download"}
+
+    def apply(self) -> Dict[str, Any]:
+        """
+        Install the staged update, saving what it replaces for rollback.
+
+        The new version takes effect on the next start, which runs the
+        self test (see verify_startup()).
+
+        Returns:
+            Dict: 'success', 'message', 'version' and the 'changed' file count
+        """
+        with self._lock:
+            state = self._load_state()
+            staged = state.get('staged') or {}
+            package = Path(staged.get('path') or '')
+            if not staged or not package.is_file():
+                return {'success': False, 'message': "No update has been downloaded"}
+            if staged.get('delta') and staged.get('from') != self.current_version:
+                return {'success': False, 'message': f"The staged delta is for {staged.get('from')}, not "
+                                                     f"{self.current_version}"}
+            backup, added, writes, removed = None, [], [], []
+            try:
+                with zipfile.ZipFile(package) as archive:
+                    members = [(m, _member_path(m.filename)) for m in archive.infolist()
+                               if not m.is_dir() and m.filename != REMOVED_LIST]
+                    if REMOVED_LIST in archive.namelist():
+                        listed = json.loads(archive.read(REMOVED_LIST).decode('utf-8'))
+                        removed = [p for p in map(_member_path, listed if isinstance(listed, list) else []) if p]
+                    writes = [(m, p) for m, p in members if p is not None]
+                    backup, added = self._backup([p for _, p in writes] + removed, staged['version'])
+                    for member, relative in writes:
+                        target = self.app_root.joinpath(*relative.parts)
+                        target.parent.mkdir(parents=True, exist_ok=True)
+                        with archive.open(member) as source, open(target, 'wb') as out:
+                            shutil.copyfileobj(source, out)
+            except (OSError, zipfile.BadZipFile, ValueError) as e:
+                logger.error(f"Installing update {staged.get('version')} failed: {e}")
+                if backup is not None:
+                    self._restore(str(backup), added)
+                return {'success': False, 'message': f"Installing the update failed: {e}"}
+            for relative in removed:
+                self.app_root.joinpath(*relative.parts).unlink(missing_ok=True)
+
+            state['previous'] = {'version': self.current_version, 'backup': str(backup), 'added': added}
+            state['pending'] = staged['version']
+            state.pop('staged', None)
+            self._save_state(state)
+        package.unlink(missing_ok=True)
+        logger.info(f"Installed update {staged['version']} over {self.current_version}; restart to finish")
+        return {'success': True, 'message': f"Installed {staged['version']}; restart to start using it",
+                'version': staged['version'], 'changed': len(writes) + len(removed)}
+
+    def _backup(self, paths: List[PurePosixPath], version: str) -> Tuple[Path, List[str]]:
+        """Zip the existing files at `paths`; return the backup and the paths that are new."""
+        backup_dir = self.state_dir / 'previous'
+        shutil.rmtree(backup_dir, ignore_errors=True)
+        backup_dir.mkdir(parents=True, exist_ok=True)
+        backup = backup_dir / f"{self.current_version}-before-{version}.zip"
+        added = []
+        with zipfile.ZipFile(backup, 'w', zipfile.ZIP_DEFLATED) as archive:
+            for relative in dict.fromkeys(paths):
+                source = self.app_root.joinpath(*relative.parts)
+                if source.is_file():
+                    archive.write(source, str(relative))
+                else:
+                    added.append(str(relative))
+        return backup, added
+
+    def _restore(self, backup: str, added: List[str]) -> None:
+        with zipfile.ZipFile(backup) as archive:
+            for member in archive.infolist():
+                relative = _member_path(member.filename)
+                if relative is None:
+                    continue
+                target = self.app_root.joinpath(*relative.parts)
+                target.parent.mkdir(parents=True, exist_ok=True)
+                with archive.open(member) as source, open(target, 'wb') as out:
+                    shutil.copyfileobj(source, out)
+        for name in added:
+            relative = _member_path(name)
+            if relative is not None:
+                self.app_root.joinpath(*relative.parts).unlink(missing_ok=True)
+
+    # ==================== Rollback ====================
+
+    def rollback(self, reason: str = 'requested') -> Dict[str, Any]:
+        """
+        Put back the version the last update replaced.
+
+        Args:
+            reason (str): Why, recorded in the update state
+
+        Returns:
+            Dict: 'success', 'message' and the restored 'version'
+        """
+        with self._lock:
+            state = self._load_state()
+            previous = state.get('previous') or {}
+            if not previous.get('backup') or not Path(previous['backup']).is_file():
+                return {'success': False, 'message': "There is no previous version to go back to"}
+            try:
+                self._restore(previous['backup'], previous.get('added') or [])
+            except (OSError, zipfile.BadZipFile) as e:
+                return {'success': False, 'message': f"Rolling back failed: {e}"}
+            undone = state.pop('pending', None) or self.current_version
+            state.pop('previous', None)
+            state['last_result'] = {'version': undone, 'result': 'rolled back', 'reason': reason,
+                                    'at': datetime.now().isoformat(timespec='seconds')}
+            self._save_state(state)
+        Path(previous['backup']).unlink(missing_ok=True)
+        logger.warning(f"Rolled back {undone} to {previous['version']}: {reason}")
+        return {'success': True, 'message': f"Restored {previous['version']}; restart to use it",
+                'version': previous['version']}
+
+    def verify_startup(self, self_test: Optional[Callable[[], Dict[str, Any]]] = None) -> Optional[Dict[str, Any]]:
+        """
+        After an update, run the self test once and roll back if it fails.
+
+        Args:
+            self_test (Callable, optional): Returns a result with 'success'
+                                            (default: src.core.self_test.run_self_test)
+
+        Returns:
+            Dict or None: None if no update is pending; else 'success' (the
+                          update was kept), 'message' and 'rolled_back'
+        """
+        state = self._load_state()
+        pending = state.get('pending')
+        if not pending:
+            return None
+        if self_test is None:
+            from .self_test import run_self_test
+            self_test = lambda: run_self_test(self.config)  # noqa: E731
+        try:
+            result = self_test()
+        except Exception as e:  # any failure of the new version counts against it
+            result = {'success': False, 'message': f"Self test crashed: {e}"}
+        if result.get('success'):
+            with self._lock:
+                state = self._load_state()
+                state.pop('pending', None)
+                state['last_result'] = {'version': pending, 'result': 'installed',
+                                        'at': datetime.now().isoformat(timespec='seconds')}
+                self._save_state(state)
+            logger.info(f"Update {pending} passed its self test")
+            return {'success': True, 'message': f"Updated to {pending}", 'rolled_back': False}
+        rolled = self.rollback(f"self test failed: {result.get('message')}")
+        return {'success': False, 'rolled_back': rolled['success'],
+                'message': f"Update {pending} failed its self test ({result.get('message')}); "
+                           + (rolled['message'] if rolled['success'] else f"rollback failed: {rolled['message']}")}
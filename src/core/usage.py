@@ -0,0 +1,183 @@
+"""
+Usage Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps a ledger of the tokens AI classification uses and what
+they cost, so the user can see where a month's spend went and cap it.
+
+Every AI call that reports its token count is recorded with the model and
+the run it belonged to (an organize plan, or the session for one-off
+classifications). The cost is the call's own cost_usd when the provider
+gives one, else tokens x the model's price per 1,000 tokens from
+`usage.prices` (local Ollama models cost nothing unless priced).
+
+`usage.monthly_budget_usd` caps a calendar month's spend (0: no budget).
+Past it, "warn" (the default) logs a warning once and carries on, while
+"block" stops AI classification until the month ends - files then get the
+rule-based result.
+
+    "usage": {"monthly_budget_usd": 5, "on_budget_exceeded": "warn",
+              "prices": {"gpt-4o-mini": 0.0006}}
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import threading
+import uuid
+from datetime import datetime, timedelta
+from typing import Dict, Any, Optional
+
+logger = logging.getLogger(__name__)
+
+
+PERIODS = ('today', 'week', 'month', 'all')
+BUDGET_ACTIONS = ('warn', 'block')
+TOP_RUNS = 20
+
+
+class UsageLedger:
+    """
+    Records AI token usage and cost, and enforces the monthly budget.
+
+    Attributes:
+        monthly_budget (float): Budget in USD per calendar month (0: none)
+        action (str): 'warn' or 'block' once the budget is spent
+        prices (Dict[str, float]): Model -> USD per 1,000 tokens
+        run_id (str): Run new usage is recorded under
+    """
+
+    def __init__(self, config, db_manager, clock=datetime.now):
+        """
+        Initialize the ledger.
+
+        Args:
+            config: Configuration object (reads `usage_settings`)
+            db_manager: Database manager instance
+            clock (Callable): Current local time (for tests)
+        """
+        self.db_manager = db_manager
+        self.clock = clock
+        settings = getattr(config, 'usage_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.monthly_budget = max(0.0, float(settings.get('monthly_budget_usd', 0) or 0))
+        self.action = settings.get('on_budget_exceeded', 'warn')
+        if self.action not in BUDGET_ACTIONS:
+            logger.warning(f"Unknown usage.on_budget_exceeded {self.action!r}; warning instead")
+            self.action = 'warn'
+        prices = settings.get('prices')
+        self.prices = {str(model): float(price) for model, price in prices.items()} if isinstance(prices, dict) else {}
+        self.run_id = f"session-{uuid.uuid4().hex[:8]}"
+        self.run_label = 'Classifications outside organize runs'
+        self._warned_month: Optional[str] = None
+        self._lock = threading.Lock()
+
+    def start_run(self, label: str) -> str:
+        """Record usage from now on under a new run (e.g. one organize plan); returns its ID."""
+        with self._lock:
+            self.run_id = uuid.uuid4().hex[:12]
+            self.run_label = label
+            return self.run_id
+
+    def price(self, model: str, tokens: int) -> float:
+        """Cost of tokens on a model, from the configured prices."""
+        per_thousand = self.prices.get(model)
+        if per_thousand is None:
+            per_thousand = self.prices.get(model.split(':')[0], 0.0)  # "llama3:8b" priced as "llama3"
+        return round(tokens * per_thousand / 1000, 6)
+
+    def record(self, model: str, tokens_used: int, cost_usd: Optional[float] = None,
+               provider: str = 'ollama') -> float:
+        """
+        Add one AI call to the ledger.
+
+        Args:
+            model (str): Model that answered
+            tokens_used (int): Prompt and completion tokens
+            cost_usd (float, optional): Cost the provider reported; default: from the prices
+            provider (str): Provider name
+
+        Returns:
+            float: The cost recorded
+        """
+        tokens = max(0, int(tokens_used or 0))
+        cost = round(float(cost_usd), 6) if cost_usd is not None else self.price(model, tokens)
+        with self._lock:
+            run_id, label = self.run_id, self.run_label
+        try:
+            self.db_manager.add_usage(run_id, label, provider, model or 'unknown', tokens, cost,
+                                      self.clock().isoformat(sep=' ', timespec='seconds'))
+        except Exception as e:
+            logger.warning(f"AI usage not recorded: {e}")
+            return cost
+        if self.monthly_budget and not self._within_budget():
+            month = self._month_start().strftime('%Y-%m')
+            if self._warned_month != month:
+                self._warned_month = month
+                logger.warning(f"Monthly AI budget of ${self.monthly_budget:.2f} reached"
+                               + ("; AI classification is paused until next month" if self.action == 'block' else ''))
+        return cost
+
+    def allows_ai(self) -> bool:
+        """False once a blocking budget is spent for this month."""
+        return self.action != 'block' or not self.monthly_budget or self._within_budget()
+
+    def budget_status(self) -> Dict[str, Any]:
+        """This month's spend against the budget."""
+        spent = self._spent_this_month()
+        return {
+            'monthly_budget_usd': self.monthly_budget or None,
+            'spent_usd': round(spent, 6),
+            'remaining_usd': round(max(0.0, self.monthly_budget - spent), 6) if self.monthly_budget else None,
+            'exceeded': bool(self.monthly_budget) and spent >= self.monthly_budget,
+            'action': self.action,
+            'ai_allowed': self.allows_ai(),
+        }
+
+    def get_usage_summary(self, period: str = 'month') -> Dict[str, Any]:
+        """
+        Token and cost totals for a period, per model and per run.
+
+        Args:
+            period (str): 'today', 'week' (last 7 days), 'month' (this calendar month) or 'all'
+
+        Returns:
+            Dict: 'period', 'since', 'calls', 'tokens', 'cost_usd', 'by_model',
+                  'by_run' (newest TOP_RUNS runs) and 'budget'
+
+        Raises:
+            ValueError: If the period is unknown
+        """
+        if period not in PERIODS:
+            raise ValueError(f"Unknown period {period!r} (use {', '.join(PERIODS)})")
+        since = self._since(period)
+        totals = self.db_manager.usage_totals(since.isoformat(sep=' ', timespec='seconds') if since else None,
+                                              TOP_RUNS)
+        return {'period': period, 'since': since.isoformat(timespec='seconds') if since else None,
+                **totals, 'budget': self.budget_status()}
+
+    def _since(self, period: str) -> Optional[datetime]:
+        today = self.clock().replace(hour=0, minute=0, second=0, microsecond=0)
+        if period == 'today':
+            return today
+        if period == 'week':
+            return today - timedelta(days=6)
+        if period == 'month':
+            return self._month_start()
+        return None
+
+    def _month_start(self) -> datetime:
+        return self.clock().replace(day=1, hour=0, minute=0, second=0, microsecond=0)
+
+    def _spent_this_month(self) -> float:
+        since = self._month_start().isoformat(sep=' ', timespec='seconds')
+        return self.db_manager.usage_totals(since, 0)['cost_usd']
+
+    def _within_budget(self) -> bool:
+        return self._spent_this_month() < self.monthly_budget
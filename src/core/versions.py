@@ -0,0 +1,184 @@
+"""
+File Versions Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module keeps replaced files as versions. When a conflict is resolved
+with "replace", the file about to be overwritten is copied into a
+content-addressed store (objects/<hash[:2]>/<hash>) and recorded against
+its path, so identical content is stored once no matter how often it is
+replaced. Versions are pruned to the newest N per path and to a maximum
+age; stored content is deleted once no version refers to it.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import shutil
+from pathlib import Path
+from typing import Dict, Any, List, Optional
+
+from .hashing import hash_file
+from .journal import PROJECT_ROOT
+
+logger = logging.getLogger(__name__)
+
+
+DEFAULT_KEEP_VERSIONS = 5
+DEFAULT_MAX_AGE_DAYS = 30
+
+
+class VersionStore:
+    """
+    Content-addressed store of replaced file versions.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager used for version records
+        root (Path): Store directory
+        keep (int): Versions kept per path
+        max_age_days (int): Versions older than this are pruned (0 = never)
+    """
+
+    def __init__(self, config, db_manager):
+        """
+        Initialize version store.
+
+        Args:
+            config: Configuration object (reads `versions`)
+            db_manager: Database manager instance
+        """
+        settings = getattr(config, 'version_settings', None)
+        if not isinstance(settings, dict):
+            settings = {}
+        self.config = config
+        self.db_manager = db_manager
+        self.enabled = bool(settings.get('enabled', True))
+        self.keep = max(1, int(settings.get('keep_versions', DEFAULT_KEEP_VERSIONS)))
+        self.max_age_days = int(settings.get('max_age_days', DEFAULT_MAX_AGE_DAYS))
+        store_dir = settings.get('store_dir') or ''
+        self.root = Path(store_dir).expanduser() if store_dir else PROJECT_ROOT / "data" / "versions"
+
+    def _object_path(self, content_hash: str) -> Path:
+        return self.root / "objects" / content_hash[:2] / content_hash
+
+    def save_version(self, file_path: str, reason: str = 'replaced') -> Optional[Dict[str, Any]]:
+        """
+        Keep the current content of a file as a version before it is replaced.
+
+        Args:
+            file_path (str): File about to be overwritten
+            reason (str): Why the version is kept
+
+        Returns:
+            Dict or None: Version record (with 'object_path'), or None if
+                          versions are disabled or the path is not a file
+        """
+        path = Path(file_path)
+        if not self.enabled or not path.is_file():
+            return None
+
+        content_hash = hash_file(path)
+        target = self._object_path(content_hash)
+        if not target.exists():
+            target.parent.mkdir(parents=True, exist_ok=True)
+            partial = target.with_name(target.name + '.partial')
+            shutil.copy2(path, partial)
+            partial.replace(target)
+
+        stat = path.stat()
+        version_id = self.db_manager.add_file_version(
+            str(path.resolve()), content_hash, stat.st_size, stat.st_mtime, reason
+        )
+        logger.info(f"Kept version {version_id} of {path} before replacing it")
+        self.prune(str(path.resolve()))
+
+        version = self.db_manager.get_file_version(version_id) or {'id': version_id}
+        version['object_path'] = str(target)
+        return version
+
+    def list_versions(self, file_path: Optional[str] = None, limit: int = 100) -> List[Dict[str, Any]]:
+        """
+        List stored versions, newest first.
+
+        Args:
+            file_path (str, optional): Only versions of this path
+            limit (int): Maximum number of versions
+
+        Returns:
+            List[Dict]: Version records
+        """
+        path = str(Path(file_path).expanduser().resolve()) if file_path else None
+        return self.db_manager.list_file_versions(path, limit)
+
+    def restore_version(self, version_id: int, target: Optional[str] = None,
+                        dry_run: bool = False) -> Dict[str, Any]:
+        """
+        Restore a version to its original path (or another target).
+
+        Whatever currently occupies the target is itself kept as a version
+        first, so restoring never loses data.
+
+        Args:
+            version_id (int): Version ID
+            target (str, optional): Where to restore; defaults to the original path
+            dry_run (bool): If True, only report what would be restored
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'path'
+        """
+        version = self.db_manager.get_file_version(version_id)
+        if not version:
+            return {'success': False, 'message': f'Version {version_id} not found'}
+
+        source = self._object_path(version['content_hash'])
+        if not source.exists():
+            return {'success': False, 'message': f'Stored content of version {version_id} is missing'}
+
+        dest = Path(target).expanduser() if target else Path(version['file_path'])
+        if dest.is_dir():
+            return {'success': False, 'message': f'Cannot restore over a directory: {dest}'}
+
+        if dry_run:
+            return {
+                'success': True,
+                'message': f'[DRY RUN] Would restore version {version_id} to {dest}',
+                'path': str(dest)
+            }
+
+        try:
+            if dest.exists():
+                self.save_version(str(dest), reason=f'replaced by restore of version {version_id}')
+            dest.parent.mkdir(parents=True, exist_ok=True)
+            shutil.copy2(source, dest)
+        except OSError as e:
+            return {'success': False, 'message': f'Error restoring version {version_id}: {e}'}
+
+        return {'success': True, 'message': f'Restored version {version_id} to {dest}', 'path': str(dest)}
+
+    def prune(self, file_path: Optional[str] = None) -> int:
+        """
+        Apply the keep-N and max-age limits and drop unreferenced content.
+
+        Args:
+            file_path (str, optional): Only prune versions of this path
+
+        Returns:
+            int: Number of stored objects deleted
+        """
+        orphaned = self.db_manager.prune_file_versions(
+            self.keep, self.max_age_days or None, file_path
+        )
+        for content_hash in orphaned:
+            try:
+                self._object_path(content_hash).unlink()
+            except FileNotFoundError:
+                pass
+            except OSError as e:
+                logger.warning(f"Could not delete stored version {content_hash}: {e}")
+        return len(orphaned)
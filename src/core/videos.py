@@ -0,0 +1,353 @@
+"""
+Video Library Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module is the "videos" planner strategy: movies and TV episodes are
+filed the way Plex and Jellyfin expect them,
+
+    Movies/Blade Runner (1982)/Blade Runner (1982).mkv
+    TV Shows/The Expanse/Season 02/The Expanse - S02E05 - Home.mkv
+
+Names are parsed offline from the file name (release tags such as 1080p,
+BluRay or x264 are dropped) and the Show/Season folders the file is in.
+Subtitles next to a video (Movie.2010.en.srt) are named alike and keep
+their language code.
+
+When `videos.online` is on and an API key is set, titles are normalized
+with a TMDB-compatible API (`videos.endpoint`): the movie's canonical
+title and year, or the show's name and the episode's title. Only the
+parsed title (and year) is sent; answers are cached in
+data/video_cache.json. Without the lookup, or when it finds nothing, the
+parsed names are used.
+
+Select it per folder in config.json:
+
+    "folder_policies": {
+        "~/Downloads/Video": {
+            "strategy": "videos",
+            "movie_destination": "Movies",
+            "tv_destination": "TV Shows"
+        }
+    }
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+import logging
+import re
+import threading
+import urllib.error
+import urllib.parse
+import urllib.request
+from pathlib import Path
+from typing import Dict, Any, Optional
+
+from .file_names import safe_name, place_in_destination
+
+logger = logging.getLogger(__name__)
+
+
+VIDEO_EXTENSIONS = {'.mkv', '.mp4', '.m4v', '.avi', '.mov', '.wmv', '.mpg', '.mpeg', '.ts', '.m2ts', '.webm', '.flv'}
+SUBTITLE_EXTENSIONS = {'.srt', '.ass', '.ssa', '.sub', '.idx', '.vtt'}
+DEFAULT_ENDPOINT = 'https://api.themoviedb.org/3'
+DEFAULT_MOVIE_DESTINATION = 'Movies'
+DEFAULT_TV_DESTINATION = 'TV Shows'
+
+_APP_ROOT = Path(__file__).resolve().parent.parent.parent
+_EPISODE = re.compile(r'(?i)\bS(\d{1,2})\s?E(\d{1,3})(?:\s?-?\s?E(\d{1,3}))?\b|\b(\d{1,2})x(\d{2,3})\b')
+_SEASON_FOLDER = re.compile(r'(?i)^(?:season|series|staffel|saison|temporada)\s*(\d{1,2})$|^S(\d{1,2})$')
+_YEAR = re.compile(r'[(\[]?\b((?:19|20)\d{2})\b[)\]]?')
+_LANGUAGE = re.compile(r'(?i)\.([a-z]{2,3}(?:-[a-z]{2})?)(?:\.(forced|sdh|cc))?$')
+# Release tags that end the title part of a scene-style name
+_RELEASE_TAG = re.compile(
+    r'(?i)\b(?:2160p|1080p|1080i|720p|576p|480p|4k|uhd|hdr10?|dv|bluray|blu-ray|brrip|bdrip|web-?dl|webrip|web|'
+    r'hdtv|dvdrip|dvd|hdrip|remux|x264|x265|h\.?264|h\.?265|hevc|avc|xvid|divx|aac|ac3|dts|ddp?5\.1|atmos|'
+    r'proper|repack|extended|unrated|remastered|imax|internal|limited|multi|subbed|dubbed)\b')
+
+
+def _clean(text: str) -> str:
+    """Turn 'The.Dark_Knight ' (or 'the.dark.knight') into 'The Dark Knight'."""
+    text = re.sub(r'[._]+', ' ', text)
+    text = re.sub(r'\s+', ' ', text).strip(' -[]()')
+    return text.title() if text.islower() else text
+
+
+def parse_video_name(path: Path) -> Optional[Dict[str, Any]]:
+    """
+    Read a movie or episode's identity from its file and folder names.
+
+    Args:
+        path (Path): Video (or subtitle) file
+
+    Returns:
+        Dict or None: {'kind': 'movie', 'title', 'year'} or
+                      {'kind': 'episode', 'show', 'season', 'episode',
+                       'last_episode', 'episode_title', 'year'};
+                      None if no title can be found
+    """
+    stem = path.stem
+    if path.suffix.lower() in SUBTITLE_EXTENSIONS:
+        stem = _LANGUAGE.sub('', stem)
+
+    episode = _EPISODE.search(stem)
+    if episode:
+        if episode.group(1):
+            season, number = int(episode.group(1)), int(episode.group(2))
+            last = int(episode.group(3)) if episode.group(3) else number
+        else:
+            season, number = int(episode.group(4)), int(episode.group(5))
+            last = number
+        show = _clean(stem[:episode.start()])
+        rest = stem[episode.end():]
+        tag = _RELEASE_TAG.search(rest)
+        episode_title = _clean(rest[:tag.start()] if tag else rest)
+        year = None
+        show_year = _YEAR.search(show)
+        if show_year and show_year.start() > 0:
+            year = int(show_year.group(1))
+            show = _clean(show[:show_year.start()])
+        if not show:
+            show = _show_from_folders(path)
+        if not show:
+            return None
+        return {'kind': 'episode', 'show': show, 'season': season, 'episode': number,
+                'last_episode': last, 'episode_title': episode_title or None, 'year': year}
+
+    # "Show/Season 2/05 - Home.mkv"
+    season_match = _SEASON_FOLDER.match(path.parent.name)
+    numbered = re.match(r'^(\d{1,3})(?:\s*[-.]\s*|\s+)(.*)$', _clean(stem))
+    if season_match and numbered and path.parent.parent.name:
+        return {'kind': 'episode', 'show': _clean(path.parent.parent.name),
+                'season': int(season_match.group(1) or season_match.group(2)),
+                'episode': int(numbered.group(1)), 'last_episode': int(numbered.group(1)),
+                'episode_title': numbered.group(2) or None, 'year': None}
+
+    tag = _RELEASE_TAG.search(stem)
+    title_part = stem[:tag.start()] if tag else stem
+    year = None
+    for match in _YEAR.finditer(title_part):
+        if match.start() > 0:  # "2001 A Space Odyssey" starts with its title
+            year = match
+    if year is not None:
+        title = _clean(title_part[:year.start()])
+        return {'kind': 'movie', 'title': title, 'year': int(year.group(1))} if title else None
+    title = _clean(title_part)
+    return {'kind': 'movie', 'title': title, 'year': None} if title else None
+
+
+def _show_from_folders(path: Path) -> str:
+    parent = path.parent
+    if _SEASON_FOLDER.match(parent.name):
+        parent = parent.parent
+    return _clean(parent.name)
+
+
+class VideoMetadataLookup:
+    """
+    Optional online title normalization through a TMDB-compatible API.
+
+    Attributes:
+        online (bool): Whether lookups may be made (privacy toggle)
+        endpoint (str): API base URL
+    """
+
+    def __init__(self, config, cache_path: Optional[str] = None):
+        """
+        Initialize lookup.
+
+        Args:
+            config: Configuration object (reads `video_settings`)
+            cache_path (str, optional): Answer cache (default: data/video_cache.json)
+        """
+        settings = getattr(config, 'video_settings', None)
+        settings = settings if isinstance(settings, dict) else {}
+        self.api_key = settings.get('api_key') or ''
+        self.online = bool(settings.get('online', False)) and bool(self.api_key)
+        self.endpoint = (settings.get('endpoint') or DEFAULT_ENDPOINT).rstrip('/')
+        self.language = settings.get('language') or 'en-US'
+        self.cache_path = Path(cache_path or settings.get('cache_file')
+                               or _APP_ROOT / 'data' / 'video_cache.json').expanduser()
+        self._cache: Optional[Dict[str, Any]] = None
+        self._lock = threading.Lock()
+
+    def movie(self, title: str, year: Optional[int] = None) -> Optional[Dict[str, Any]]:
+        """
+        Canonical title and year of a movie.
+
+        Returns:
+            Dict or None: 'title', 'year' and 'id', or None if off, unknown or unreachable
+        """
+        params = {'query': title, **({'year': year} if year else {})}
+        answer = self._get('search/movie', params)
+        results = (answer or {}).get('results') or []
+        if not results:
+            return None
+        best = results[0]
+        released = str(best.get('release_date') or '')[:4]
+        return {'title': best.get('title') or title, 'year': int(released) if released.isdigit() else year,
+                'id': best.get('id')}
+
+    def episode(self, show: str, season: int, number: int,
+                year: Optional[int] = None) -> Optional[Dict[str, Any]]:
+        """
+        Canonical show name, first-air year and episode title.
+
+        Returns:
+            Dict or None: 'show', 'year', 'episode_title' and 'id', or None
+        """
+        params = {'query': show, **({'first_air_date_year': year} if year else {})}
+        results = (self._get('search/tv', params) or {}).get('results') or []
+        if not results:
+            return None
+        best = results[0]
+        aired = str(best.get('first_air_date') or '')[:4]
+        found = {'show': best.get('name') or show, 'year': int(aired) if aired.isdigit() else year,
+                 'episode_title': None, 'id': best.get('id')}
+        if best.get('id') is not None:
+            details = self._get(f"tv/{best['id']}/season/{season}/episode/{number}", {})
+            if details and details.get('name'):
+                found['episode_title'] = details['name']
+        return found
+
+    def _get(self, route: str, params: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        if not self.online:
+            return None
+        params = {**params, 'language': self.language}
+        key = f"{route}?{urllib.parse.urlencode(sorted(params.items()))}"
+        with self._lock:
+            cache = self._load_cache()
+            if key in cache:
+                return cache[key]
+        query = urllib.parse.urlencode({**params, 'api_key': self.api_key})
+        request = urllib.request.Request(f"{self.endpoint}/{route}?{query}",
+                                         headers={'Accept': 'application/json',
+                                                  'User-Agent': 'AI-File-Organiser (video library)'})
+        try:
+            with urllib.request.urlopen(request, timeout=10) as response:
+                answer = json.loads(response.read().decode('utf-8'))
+        except urllib.error.HTTPError as e:
+            if e.code != 404:
+                logger.warning(f"Video metadata lookup failed: {e}")
+                return None  # Not cached: try again next time
+            answer = None
+        except (OSError, ValueError) as e:
+            logger.warning(f"Video metadata lookup failed: {e}")
+            return None
+        with self._lock:
+            cache[key] = answer if isinstance(answer, dict) else None
+            self._save_cache()
+        return cache[key]
+
+    def _load_cache(self) -> Dict[str, Any]:
+        if self._cache is None:
+            try:
+                self._cache = json.loads(self.cache_path.read_text(encoding='utf-8'))
+            except (OSError, ValueError):
+                self._cache = {}
+        return self._cache
+
+    def _save_cache(self) -> None:
+        try:
+            self.cache_path.parent.mkdir(parents=True, exist_ok=True)
+            self.cache_path.write_text(json.dumps(self._cache, indent=2, ensure_ascii=False), encoding='utf-8')
+        except OSError as e:
+            logger.warning(f"Cannot save video metadata cache: {e}")
+
+
+class VideoLibraryStrategy:
+    """
+    Plans movie and episode destinations in Plex/Jellyfin layout.
+
+    Attributes:
+        config: Configuration object
+    """
+
+    name = 'videos'
+
+    def __init__(self, config):
+        """
+        Initialize video strategy.
+
+        Args:
+            config: Configuration object
+        """
+        self.config = config
+        self._lookup: Optional[VideoMetadataLookup] = None
+
+    @property
+    def lookup(self) -> VideoMetadataLookup:
+        if self._lookup is None:
+            self._lookup = VideoMetadataLookup(self.config)
+        return self._lookup
+
+    def classify(self, file_path: str, root: Optional[Path] = None,
+                 policy: Optional[Dict[str, Any]] = None) -> Optional[Dict[str, Any]]:
+        """
+        Work out where a movie, episode or its subtitle belongs.
+
+        Args:
+            file_path (str): File to place
+            root (Path, optional): Folder being organized (unused)
+            policy (Dict, optional): Folder policy with the *_destination settings
+
+        Returns:
+            Dict or None: Classification, or None if the file is not a video
+                          or subtitle, is a sample, or has no recognizable name
+        """
+        path = Path(file_path)
+        suffix = path.suffix.lower()
+        if suffix not in VIDEO_EXTENSIONS and suffix not in SUBTITLE_EXTENSIONS:
+            return None
+        if re.search(r'(?i)(?:^|[ ._-])(sample|trailer)(?:$|[ ._-])', path.stem):
+            return None
+        parsed = parse_video_name(path)
+        if parsed is None:
+            return None
+        policy = policy or {}
+
+        extension = path.suffix
+        if suffix in SUBTITLE_EXTENSIONS:
+            language = _LANGUAGE.search(path.stem)
+            if language:
+                extension = f".{language.group(1)}" + (f".{language.group(2)}" if language.group(2) else '') + path.suffix
+
+        if parsed['kind'] == 'movie':
+            found = self.lookup.movie(parsed['title'], parsed['year'])
+            title = safe_name((found or parsed)['title'])
+            year = (found or parsed)['year']
+            name = f"{title} ({year})" if year else title
+            folder, rename = name, f"{name}{extension}"
+            destination_key, default = 'movie_destination', DEFAULT_MOVIE_DESTINATION
+            reason = f"Movie {name}"
+        else:
+            found = self.lookup.episode(parsed['show'], parsed['season'], parsed['episode'], parsed['year'])
+            show = safe_name((found or parsed)['show'])
+            year = (found or parsed)['year']
+            show_name = f"{show} ({year})" if year else show
+            code = f"S{parsed['season']:02d}E{parsed['episode']:02d}"
+            if parsed['last_episode'] != parsed['episode']:
+                code += f"-E{parsed['last_episode']:02d}"
+            episode_title = safe_name((found or {}).get('episode_title') or parsed['episode_title'])
+            folder = f"{show_name}/Season {parsed['season']:02d}"
+            rename = f"{show_name} - {code}" + (f" - {episode_title}" if episode_title else '') + extension
+            destination_key, default = 'tv_destination', DEFAULT_TV_DESTINATION
+            reason = f"{show_name} {code}"
+
+        classification = {
+            'category': 'Videos',
+            'suggested_path': f"{folder}/",
+            'rename': rename if rename != path.name else None,
+            'reason': reason + (' (online lookup)' if found else ' (from file name)'),
+            'confidence': 'high' if found else 'medium',
+            'method': 'videos',
+            'video': parsed['kind']
+        }
+        destination = str(policy.get(destination_key) or default)
+        return place_in_destination(classification, destination, folder)
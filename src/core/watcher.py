@@ -7,6 +7,14 @@ Proprietary Software - 200-Key Limited Release License
 This module monitors specified directories for new or modified files using
 the watchdog library (cross-platform filesystem events monitoring).
 
+FolderWatcher watches a fixed list of folders; FolderWatchManager starts and
+stops watching single folders while the app runs, each with its own rules,
+and reports what happens as events:
+
+    watch://file-detected   {"folder", "file", "classification", "auto_organize"}
+    watch://file-organized  {"folder", "file", "new_path", "message"}
+    watch://error           {"folder", "file", "message"}
+
 Reference: watchdog library for filesystem event monitoring
 Reference: inotify (Linux), FSEvents (macOS), ReadDirectoryChangesW (Windows)
 
@@ -17,12 +25,14 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
+import logging
 import time
 import threading
 import os
 from concurrent.futures import ThreadPoolExecutor
+from datetime import datetime
 from pathlib import Path
-from typing import List, Callable, Optional, TYPE_CHECKING
+from typing import Dict, Any, List, Callable, Optional, TYPE_CHECKING
 from queue import Queue
 
 # Watchdog for filesystem monitoring
@@ -49,6 +59,12 @@ try:
 except ImportError:
     WATCHFILES_SUPPORT = False
 
+from .split_archives import is_secondary_part
+from .raw_pairs import is_raw_sidecar
+from .leave_alone import LeaveAloneDetector
+
+logger = logging.getLogger(__name__)
+
 
 class FileEventHandler(FileSystemEventHandler):
     """
@@ -64,7 +80,8 @@ class FileEventHandler(FileSystemEventHandler):
         ignored_patterns (set): Filename patterns to ignore
     """
 
-    def __init__(self, callback: Optional[Callable] = None, file_queue: Optional[Queue] = None, blacklist: Optional[List[str]] = None, max_queue_size: int = 1000):
+    def __init__(self, callback: Optional[Callable] = None, file_queue: Optional[Queue] = None, blacklist: Optional[List[str]] = None, max_queue_size: int = 1000,
+                 leave_alone: Optional[LeaveAloneDetector] = None, roots: Optional[List[Path]] = None):
         """
         Initialize file event handler.
 
@@ -73,12 +90,16 @@ class FileEventHandler(FileSystemEventHandler):
             file_queue (Queue, optional): Queue to add detected files to
             blacklist (List[str], optional): List of paths to ignore
             max_queue_size (int): Maximum queue size to prevent memory leak (HIGH #4 FIX)
+            leave_alone (LeaveAloneDetector, optional): Skips files in folders that must not be reorganized
+            roots (List[Path], optional): Watched folders (left-alone folders are looked for below them)
         """
         super().__init__()
         self.callback = callback
         self.file_queue = file_queue or Queue(maxsize=max_queue_size)  # Add maxsize (HIGH #4 FIX)
         # Optional list of path prefixes to ignore
         self.blacklist = [str(Path(p).expanduser().resolve()) for p in (blacklist or [])]
+        self.leave_alone = leave_alone
+        self.roots = roots or []
 
         # Ignore temporary and system files
         self.ignored_extensions = {
@@ -119,6 +140,15 @@ class FileEventHandler(FileSystemEventHandler):
         if file_path.suffix.lower() in self.ignored_extensions:
             return False
 
+        # Later parts of a split archive are organized with their first part,
+        # and the RAW of a RAW+JPEG pair with its JPEG
+        if is_secondary_part(file_path) or is_raw_sidecar(file_path):
+            return False
+
+        # Ignore files inside repositories, app bundles, sync roots and the like
+        if self.leave_alone is not None and self.leave_alone.enclosing(file_path, self.roots):
+            return False
+
         # Ignore very small files (likely incomplete or empty)
         try:
             if file_path.stat().st_size < 100:  # Less than 100 bytes
@@ -232,17 +262,21 @@ class FolderWatcher:
         loop (asyncio.AbstractEventLoop): Event loop for async operations
     """
 
-    def __init__(self, folders: List[str], callback: Optional[Callable] = None, config: Optional[object] = None):
+    def __init__(self, folders: List[str], callback: Optional[Callable] = None, config: Optional[object] = None,
+                 recursive: bool = True):
         """
         Initialize folder watcher.
 
         Args:
             folders (List[str]): List of directory paths to watch
             callback (Callable, optional): Function to call with file path when detected
+            recursive (bool): Also watch subfolders
         """
         self.folders = [Path(f).expanduser().resolve() for f in folders]
         self.callback = callback
+        self.recursive = recursive
         self.config = config
+        self.leave_alone = LeaveAloneDetector(config) if config is not None else None
         self.observer = None  # type: ignore
         self.file_queue = Queue()
         self.processing_thread = None
@@ -270,10 +304,13 @@ class FolderWatcher:
         except Exception:
             blacklist = []
 
+        # With a processing thread, files reach the callback through the queue only
         event_handler = FileEventHandler(
-            callback=self.callback,
+            callback=None if background and self.callback else self.callback,
             file_queue=self.file_queue,
-            blacklist=blacklist
+            blacklist=blacklist,
+            leave_alone=self.leave_alone,
+            roots=self.folders
         )
 
         # Create observer
@@ -282,7 +319,7 @@ class FolderWatcher:
         # Schedule observers for all folders
         for folder in self.folders:
             if folder.exists() and folder.is_dir():
-                self.observer.schedule(event_handler, str(folder), recursive=True)
+                self.observer.schedule(event_handler, str(folder), recursive=self.recursive)
                 print(f"[Watcher] Monitoring: {folder}")
             else:
                 print(f"[Watcher] Warning: Folder not found: {folder}")
@@ -491,6 +528,14 @@ class FolderWatcher:
         if not path.exists() or path.is_dir():
             return False
 
+        # Later parts of a split archive are organized with their first part,
+        # and the RAW of a RAW+JPEG pair with its JPEG
+        if is_secondary_part(path) or is_raw_sidecar(path):
+            return False
+
+        if self.leave_alone is not None and self.leave_alone.enclosing(path, self.folders):
+            return False
+
         # Check file size (skip very large files)
         try:
             if path.stat().st_size > 100 * 1024 * 1024:  # 100MB limit
@@ -522,6 +567,241 @@ class FolderWatcher:
         return True
 
 
+EVENT_FILE_DETECTED = 'watch://file-detected'
+EVENT_FILE_ORGANIZED = 'watch://file-organized'
+EVENT_WATCH_ERROR = 'watch://error'
+# Seconds in which a second event for the same file (created, then modified) is ignored
+DUPLICATE_EVENT_WINDOW = 5.0
+
+
+class FolderWatchManager:
+    """
+    Watches folders one at a time, each with its own rules.
+
+    Rules (all optional):
+
+        auto_organize (bool): Move new files right away (default: auto_mode)
+        extensions (List[str]): Only these extensions, e.g. ["pdf", "jpg"]
+        recursive (bool): Also watch subfolders (default: True)
+        deep (bool): Use deep AI analysis when classifying
+
+    Attributes:
+        config: Configuration object
+        classifier: File classifier
+        action_manager: Action manager that moves the files
+        listener (Callable, optional): Called as (event, payload) for every event
+        search_index (SearchIndex, optional): Kept current with the files seen and moved
+        tag_store (TagStore, optional): Its tags follow the files moved
+        leave_alone (LeaveAloneDetector, optional): Shared by every watcher, so list changes apply at once
+    """
+
+    def __init__(self, config, classifier, action_manager,
+                 listener: Optional[Callable[[str, Dict[str, Any]], None]] = None,
+                 watcher_factory: Optional[Callable[..., FolderWatcher]] = None):
+        """
+        Initialize watch manager.
+
+        Args:
+            config: Configuration object
+            classifier: File classifier
+            action_manager: Action manager
+            listener (Callable, optional): Receives every event
+            watcher_factory (Callable, optional): Builds the watcher of one folder
+                                                  (default: FolderWatcher)
+        """
+        self.config = config
+        self.classifier = classifier
+        self.action_manager = action_manager
+        self.listener = listener
+        self.watcher_factory = watcher_factory or FolderWatcher
+        self.search_index = None
+        self.tag_store = None
+        self.leave_alone = None
+        self._watches: Dict[str, Dict[str, Any]] = {}
+        self._recent: Dict[str, float] = {}
+        self._lock = threading.Lock()
+
+    @staticmethod
+    def _key(folder: str) -> str:
+        return str(Path(folder).expanduser().resolve())
+
+    def _normalize_rules(self, rules: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+        rules = dict(rules or {})
+        unknown = set(rules) - {'auto_organize', 'extensions', 'recursive', 'deep'}
+        if unknown:
+            raise ValueError(f"Unknown watch rule(s): {', '.join(sorted(unknown))}")
+        extensions = rules.get('extensions') or []
+        if isinstance(extensions, str):
+            extensions = [extensions]
+        return {
+            'auto_organize': bool(rules.get('auto_organize', getattr(self.config, 'auto_mode', False))),
+            'extensions': sorted({str(ext).lower().lstrip('.') for ext in extensions if str(ext).strip('.')}),
+            'recursive': bool(rules.get('recursive', True)),
+            'deep': bool(rules.get('deep', False))
+        }
+
+    def start_watching(self, folder: str, rules: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        """
+        Start watching a folder (or change the rules of one already watched).
+
+        Args:
+            folder (str): Folder to watch
+            rules (Dict, optional): Rules for this folder (see the class docstring)
+
+        Returns:
+            Dict: 'success', 'message' and the 'watch'
+        """
+        key = self._key(folder)
+        if not Path(key).is_dir():
+            return {'success': False, 'message': f"Not a folder: {key}", 'watch': None}
+        try:
+            normalized = self._normalize_rules(rules)
+        except ValueError as e:
+            return {'success': False, 'message': str(e), 'watch': None}
+
+        with self._lock:
+            existing = self._watches.get(key)
+            if existing is not None and existing['rules']['recursive'] == normalized['recursive']:
+                existing['rules'] = normalized
+                return {'success': True, 'message': f"Updated the rules for {key}", 'watch': self._public(existing)}
+
+        if existing is not None:
+            self.stop_watching(key)
+        watcher = self.watcher_factory(folders=[key], callback=lambda path: self.handle_file(key, path),
+                                       config=self.config, recursive=normalized['recursive'])
+        if self.leave_alone is not None:
+            watcher.leave_alone = self.leave_alone
+        try:
+            watcher.start(background=True)
+        except Exception as e:  # e.g. watchdog is not installed
+            logger.error(f"Cannot watch {key}: {e}")
+            return {'success': False, 'message': f"Cannot watch {key}: {e}", 'watch': None}
+        watch = {'folder': key, 'rules': normalized, 'started_at': datetime.now().isoformat(timespec='seconds'),
+                 'detected': 0, 'organized': 0, 'errors': 0, 'watcher': watcher}
+        with self._lock:
+            self._watches[key] = watch
+        logger.info(f"Watching {key} (auto organize: {normalized['auto_organize']})")
+        return {'success': True, 'message': f"Watching {key}", 'watch': self._public(watch)}
+
+    def stop_watching(self, folder: str) -> Dict[str, Any]:
+        """
+        Stop watching a folder.
+
+        Args:
+            folder (str): Watched folder
+
+        Returns:
+            Dict: 'success' and 'message'
+        """
+        key = self._key(folder)
+        with self._lock:
+            watch = self._watches.pop(key, None)
+        if watch is None:
+            return {'success': False, 'message': f"Not watching {key}"}
+        watch['watcher'].stop()
+        logger.info(f"Stopped watching {key}")
+        return {'success': True, 'message': f"Stopped watching {key}"}
+
+    def stop_all(self) -> int:
+        """Stop watching every folder; returns how many were watched."""
+        folders = list(self._watches)
+        for folder in folders:
+            self.stop_watching(folder)
+        return len(folders)
+
+    def watching(self) -> List[Dict[str, Any]]:
+        """The watched folders, with their rules and counts."""
+        with self._lock:
+            return [self._public(watch) for watch in sorted(self._watches.values(), key=lambda w: w['folder'])]
+
+    @staticmethod
+    def _public(watch: Dict[str, Any]) -> Dict[str, Any]:
+        public = {key: value for key, value in watch.items() if key != 'watcher'}
+        public['rules'] = dict(watch['rules'])
+        return public
+
+    def _emit(self, event: str, payload: Dict[str, Any]) -> None:
+        if self.listener is not None:
+            try:
+                self.listener(event, payload)
+            except Exception as e:
+                logger.debug(f"Watch listener failed on {event}: {e}")
+
+    def handle_file(self, folder: str, file_path: str) -> Optional[Dict[str, Any]]:
+        """
+        Classify a file that landed in a watched folder and, if its rules
+        say so, organize it.
+
+        Args:
+            folder (str): The watched folder
+            file_path (str): The new file
+
+        Returns:
+            Dict or None: The classification, or None if the file was ignored
+        """
+        now = time.time()
+        with self._lock:
+            watch = self._watches.get(folder)
+            if watch is None:
+                return None
+            rules = dict(watch['rules'])
+            if now - self._recent.get(file_path, 0) < DUPLICATE_EVENT_WINDOW:
+                return None
+            self._recent = {path: seen for path, seen in self._recent.items() if now - seen < DUPLICATE_EVENT_WINDOW}
+            self._recent[file_path] = now
+        if rules['extensions'] and Path(file_path).suffix.lower().lstrip('.') not in rules['extensions']:
+            return None
+
+        try:
+            classification = self.classifier.classify(file_path, deep_analysis=rules['deep'])
+        except Exception as e:
+            self._count(folder, 'errors')
+            self._emit(EVENT_WATCH_ERROR, {'folder': folder, 'file': file_path, 'message': f"Could not classify: {e}"})
+            return None
+        self._count(folder, 'detected')
+        self._index(file_path, None, classification)
+        self._emit(EVENT_FILE_DETECTED, {'folder': folder, 'file': file_path, 'classification': classification,
+                                         'auto_organize': rules['auto_organize']})
+        if not rules['auto_organize']:
+            return classification
+
+        try:
+            result = self.action_manager.execute(file_path=file_path, classification=classification,
+                                                 user_approved=False)
+        except Exception as e:
+            result = {'success': False, 'message': str(e)}
+        if result.get('success'):
+            self._count(folder, 'organized')
+            self._index(file_path, result.get('new_path'), classification)
+            self._emit(EVENT_FILE_ORGANIZED, {'folder': folder, 'file': file_path,
+                                              'new_path': result.get('new_path'), 'message': result.get('message')})
+        else:
+            self._count(folder, 'errors')
+            self._emit(EVENT_WATCH_ERROR, {'folder': folder, 'file': file_path, 'message': result.get('message')})
+        return classification
+
+    def _index(self, file_path: str, new_path: Optional[str], classification: Dict[str, Any]) -> None:
+        if self.tag_store is not None and new_path:
+            try:
+                self.tag_store.move_tags(file_path, new_path)
+            except Exception as e:
+                logger.warning(f"Tags did not follow {file_path}: {e}")
+        if self.search_index is None:
+            return
+        try:
+            if new_path:
+                self.search_index.move_file(file_path, new_path, classification)
+            else:
+                self.search_index.update_file(file_path, classification)
+        except Exception as e:
+            logger.warning(f"Search index not updated for {file_path}: {e}")
+
+    def _count(self, folder: str, field: str) -> None:
+        with self._lock:
+            if folder in self._watches:
+                self._watches[folder][field] += 1
+
+
 def create_watcher(folders: List[str], callback: Optional[Callable] = None) -> FolderWatcher:
     """
     Create and configure a folder watcher.
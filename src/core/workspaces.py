@@ -0,0 +1,437 @@
+"""
+Workspaces Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module groups several root folders (for example Desktop, Downloads
+and two external drives) into a named workspace, and runs scans, duplicate
+detection and organize planning across all roots as one logical operation.
+Progress is reported per root, and applying a workspace plan is journaled
+as a single operation so the whole run can be undone in one step.
+
+Workspaces are stored in the `workspaces` config section as
+{"name": {"roots": [...], "description": "...", "destinations": {...}}}.
+Roots that are not available (e.g. an unplugged drive) are reported and
+skipped.
+
+The optional destination mapping says where each category lands, e.g.
+{"Documents": "D:/Docs", "Videos": "//nas/Video", "*": "@root"}. "*" is the
+fallback and "@root" means inside the root the file came from; categories
+without a mapping go to base_destination as usual. Files whose mapped
+destination is offline are held back from the plan.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import logging
+import os
+from collections import Counter
+from pathlib import Path
+from typing import Dict, Any, List, Optional, Callable, Iterator
+
+from .split_archives import collapse_split_archives
+
+logger = logging.getLogger(__name__)
+
+
+ProgressCallback = Callable[[Dict[str, Any]], None]
+
+IN_ROOT = '@root'
+
+
+class WorkspaceManager:
+    """
+    Defines workspaces and runs multi-root operations on them.
+
+    Attributes:
+        config: Configuration object
+        db_manager: Database manager instance
+        classifier: File classifier (needed for planning)
+        action_manager: Action manager (needed for applying plans)
+        duplicate_finder: Duplicate finder (needed for duplicate detection)
+    """
+
+    def __init__(self, config, db_manager, classifier=None, action_manager=None, duplicate_finder=None):
+        """
+        Initialize workspace manager.
+
+        Args:
+            config: Configuration object (reads and writes `workspaces`)
+            db_manager: Database manager instance
+            classifier: Optional FileClassifier for organize planning
+            action_manager: Optional ActionManager for applying plans
+            duplicate_finder: Optional DuplicateFinder for duplicate detection
+        """
+        self.config = config
+        self.db_manager = db_manager
+        self.classifier = classifier
+        self.action_manager = action_manager
+        self.duplicate_finder = duplicate_finder
+
+    # ==================== Definitions ====================
+
+    def _definitions(self) -> Dict[str, Dict[str, Any]]:
+        workspaces = getattr(self.config, 'workspaces', None)
+        return dict(workspaces) if isinstance(workspaces, dict) else {}
+
+    @staticmethod
+    def _describe(name: str, definition: Dict[str, Any]) -> Dict[str, Any]:
+        roots = [str(Path(r).expanduser()) for r in definition.get('roots', [])]
+        return {
+            'name': name,
+            'description': definition.get('description', ''),
+            'roots': [{'path': r, 'available': os.path.isdir(r)} for r in roots],
+            'destinations': dict(definition.get('destinations') or {})
+        }
+
+    def list_workspaces(self) -> List[Dict[str, Any]]:
+        """
+        List defined workspaces with root availability.
+
+        Returns:
+            List[Dict]: Workspaces with 'name', 'description' and 'roots'
+        """
+        return [self._describe(name, d) for name, d in sorted(self._definitions().items())]
+
+    def get_workspace(self, name: str) -> Optional[Dict[str, Any]]:
+        """
+        Get one workspace.
+
+        Args:
+            name (str): Workspace name
+
+        Returns:
+            Dict or None: Workspace, or None if undefined
+        """
+        definition = self._definitions().get(name)
+        return self._describe(name, definition) if definition is not None else None
+
+    def save_workspace(self, name: str, roots: List[str], description: str = '',
+                       destinations: Optional[Dict[str, str]] = None) -> Dict[str, Any]:
+        """
+        Create or replace a workspace and persist it to the config file.
+
+        Args:
+            name (str): Workspace name
+            roots (List[str]): Root folders; nested or repeated roots are rejected
+            description (str): Optional description
+            destinations (Dict[str, str], optional): Category -> destination folder
+                (see module docstring); keeps the existing mapping if omitted
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'workspace'
+        """
+        name = name.strip()
+        if not name or any(c in name for c in '/\\.'):
+            return {'success': False, 'message': f'Invalid workspace name: {name!r}'}
+
+        resolved = [os.path.realpath(os.path.expanduser(r)) for r in roots]
+        if not resolved:
+            return {'success': False, 'message': 'A workspace needs at least one root'}
+        for i, root in enumerate(resolved):
+            for other in resolved[i + 1:]:
+                if os.path.commonpath([root, other]) in (root, other):
+                    return {'success': False, 'message': f'Roots overlap: {root} and {other}'}
+
+        workspaces = self._definitions()
+        if destinations is None:
+            destinations = (workspaces.get(name) or {}).get('destinations') or {}
+        error = self._validate_destinations(destinations)
+        if error:
+            return {'success': False, 'message': error}
+
+        workspaces[name] = {'roots': resolved, 'description': description}
+        if destinations:
+            workspaces[name]['destinations'] = dict(destinations)
+        self._store(workspaces)
+
+        workspace = self._describe(name, workspaces[name])
+        missing = [r['path'] for r in workspace['roots'] if not r['available']]
+        message = f"Workspace '{name}' saved with {len(resolved)} root(s)"
+        if missing:
+            message += f"; not available right now: {', '.join(missing)}"
+        return {'success': True, 'message': message, 'workspace': workspace}
+
+    @staticmethod
+    def _validate_destinations(destinations: Dict[str, str]) -> Optional[str]:
+        for category, target in destinations.items():
+            if not category:
+                return 'Destination mapping has an empty category'
+            if target != IN_ROOT and not os.path.isabs(os.path.expanduser(target)):
+                return f"Destination for '{category}' must be an absolute path or {IN_ROOT}"
+        return None
+
+    def set_destinations(self, name: str, destinations: Dict[str, Optional[str]]) -> Dict[str, Any]:
+        """
+        Change a workspace's category -> destination mapping.
+
+        Args:
+            name (str): Workspace name
+            destinations (Dict): Entries to set; a None or empty value removes the entry
+
+        Returns:
+            Dict: Result with 'success', 'message' and 'workspace'
+        """
+        workspaces = self._definitions()
+        definition = workspaces.get(name)
+        if definition is None:
+            return {'success': False, 'message': f"Workspace '{name}' not found"}
+
+        mapping = dict(definition.get('destinations') or {})
+        for category, target in destinations.items():
+            if target:
+                mapping[category] = target
+            else:
+                mapping.pop(category, None)
+        error = self._validate_destinations(mapping)
+        if error:
+            return {'success': False, 'message': error}
+
+        workspaces[name] = {**definition, 'destinations': mapping}
+        self._store(workspaces)
+        return {'success': True, 'message': f"Workspace '{name}' maps {len(mapping)} categor(ies)",
+                'workspace': self._describe(name, workspaces[name])}
+
+    def resolve_destination(self, name: str, category: Optional[str], root: str) -> Optional[str]:
+        """
+        Where a file of a category from one of the roots should be organized.
+
+        Args:
+            name (str): Workspace name
+            category (str): Classified category
+            root (str): Root the file lives in
+
+        Returns:
+            str or None: Destination root, or None for base_destination
+        """
+        mapping = (self._definitions().get(name) or {}).get('destinations') or {}
+        lowered = {k.lower(): v for k, v in mapping.items()}
+        target = lowered.get((category or '').lower(), lowered.get('*'))
+        if not target:
+            return None
+        return root if target == IN_ROOT else str(Path(target).expanduser())
+
+    def delete_workspace(self, name: str) -> bool:
+        """
+        Remove a workspace definition (files are not touched).
+
+        Args:
+            name (str): Workspace name
+
+        Returns:
+            bool: True if it existed
+        """
+        workspaces = self._definitions()
+        if workspaces.pop(name, None) is None:
+            return False
+        self._store(workspaces)
+        return True
+
+    def _store(self, workspaces: Dict[str, Dict[str, Any]]) -> None:
+        self.config.update('workspaces', workspaces)
+        try:
+            self.config.save()
+        except OSError as e:
+            logger.warning(f"Could not save workspaces to config file: {e}")
+
+    # ==================== Multi-root operations ====================
+
+    def _roots(self, name: str) -> List[str]:
+        workspace = self.get_workspace(name)
+        if workspace is None:
+            raise KeyError(f"Unknown workspace: {name}")
+        return [r['path'] for r in workspace['roots']]
+
+    @staticmethod
+    def _emit(progress: Optional[ProgressCallback], **event) -> None:
+        if progress:
+            try:
+                progress({'type': 'workspace_progress', **event})
+            except Exception as e:
+                logger.warning(f"Workspace progress callback failed: {e}")
+
+    @staticmethod
+    def _walk(root: str) -> Iterator[Path]:
+        for dirpath, dirnames, filenames in os.walk(root):
+            dirnames[:] = [d for d in dirnames if not d.startswith('.')]
+            for filename in filenames:
+                path = Path(dirpath) / filename
+                if not path.is_symlink():
+                    yield path
+
+    def scan(self, name: str, progress: Optional[ProgressCallback] = None) -> Dict[str, Any]:
+        """
+        Inventory every root of a workspace in one pass.
+
+        Args:
+            name (str): Workspace name
+            progress (Callable, optional): Receives per-root progress events
+
+        Returns:
+            Dict: 'roots' with per-root file counts, sizes and top extensions,
+                  plus workspace-wide 'total_files' and 'total_size'
+        """
+        roots = self._roots(name)
+        results = []
+        for index, root in enumerate(roots):
+            if not os.path.isdir(root):
+                results.append({'root': root, 'available': False, 'files': 0, 'total_size': 0, 'extensions': {}})
+                self._emit(progress, workspace=name, phase='scan', root=root, root_index=index,
+                           root_count=len(roots), processed=0, done=True, available=False)
+                continue
+
+            count, size, extensions = 0, 0, Counter()
+            for path in self._walk(root):
+                try:
+                    size += path.stat().st_size
+                except OSError:
+                    continue
+                count += 1
+                extensions[path.suffix.lower() or '(none)'] += 1
+                if count % 500 == 0:
+                    self._emit(progress, workspace=name, phase='scan', root=root, root_index=index,
+                               root_count=len(roots), processed=count, done=False)
+
+            results.append({'root': root, 'available': True, 'files': count, 'total_size': size,
+                            'extensions': dict(extensions.most_common(10))})
+            self._emit(progress, workspace=name, phase='scan', root=root, root_index=index,
+                       root_count=len(roots), processed=count, done=True)
+
+        return {
+            'workspace': name,
+            'roots': results,
+            'total_files': sum(r['files'] for r in results),
+            'total_size': sum(r['total_size'] for r in results)
+        }
+
+    def find_duplicates(self, name: str, progress: Optional[ProgressCallback] = None) -> List[Dict[str, Any]]:
+        """
+        Find duplicates across all roots of a workspace.
+
+        Args:
+            name (str): Workspace name
+            progress (Callable, optional): Receives per-root progress events
+
+        Returns:
+            List[Dict]: Duplicate groups, each with 'roots' (roots holding a copy)
+                        and 'cross_root' (copies live in more than one root)
+        """
+        if self.duplicate_finder is None:
+            raise RuntimeError("Duplicate detection needs a DuplicateFinder")
+
+        roots = [r for r in self._roots(name) if os.path.isdir(r)]
+
+        def on_progress(root: str, index: int, hashed: int) -> None:
+            self._emit(progress, workspace=name, phase='duplicates', root=root, root_index=index,
+                       root_count=len(roots), processed=hashed)
+
+        groups = self.duplicate_finder.find_duplicates_across(roots, on_progress)
+        for group in groups:
+            holding = sorted({root for root in roots for p in group['paths'] if self._under(p, root)})
+            group['roots'] = holding
+            group['cross_root'] = len(holding) > 1
+        return groups
+
+    @staticmethod
+    def _under(path: str, root: str) -> bool:
+        try:
+            return os.path.commonpath([os.path.realpath(path), root]) == root
+        except ValueError:
+            return False
+
+    def plan_organize(self, name: str, progress: Optional[ProgressCallback] = None) -> Dict[str, Any]:
+        """
+        Classify every file of every root into one organize plan.
+
+        Args:
+            name (str): Workspace name
+            progress (Callable, optional): Receives per-root progress events
+
+        Returns:
+            Dict: Plan with 'workspace', 'items' ({'root', 'file', 'classification'}),
+                  'per_root' counts of planned and unplanned files, 'per_destination'
+                  counts and 'held' files whose mapped destination is offline
+        """
+        if self.classifier is None:
+            raise RuntimeError("Organize planning needs a classifier")
+
+        roots = self._roots(name)
+        items: List[Dict[str, Any]] = []
+        held: List[Dict[str, Any]] = []
+        per_root: Dict[str, Dict[str, int]] = {}
+        per_destination: Counter = Counter()
+
+        for index, root in enumerate(roots):
+            per_root[root] = {'planned': 0, 'unplanned': 0}
+            if not os.path.isdir(root):
+                continue
+            files = collapse_split_archives(str(p) for p in self._walk(root))
+            for position, file_path in enumerate(files, 1):
+                classification = self.classifier.classify(file_path)
+                destination = None
+                if classification.get('suggested_path'):
+                    destination = self.resolve_destination(name, classification.get('category'), root)
+                if destination and not os.path.isdir(destination):
+                    held.append({'root': root, 'file': file_path, 'destination_root': destination})
+                    per_root[root]['unplanned'] += 1
+                elif classification.get('suggested_path') or classification.get('rename'):
+                    if destination:
+                        classification = {**classification, 'destination_root': destination}
+                    items.append({'root': root, 'file': file_path, 'classification': classification})
+                    per_root[root]['planned'] += 1
+                    per_destination[destination or 'base_destination'] += 1
+                else:
+                    per_root[root]['unplanned'] += 1
+                if position % 50 == 0 or position == len(files):
+                    self._emit(progress, workspace=name, phase='plan', root=root, root_index=index,
+                               root_count=len(roots), processed=position, total=len(files))
+
+        return {'workspace': name, 'items': items, 'per_root': per_root,
+                'per_destination': dict(per_destination), 'held': held}
+
+    def apply_plan(self, plan: Dict[str, Any], user_approved: bool = True,
+                   progress: Optional[ProgressCallback] = None) -> Dict[str, Any]:
+        """
+        Execute a workspace plan as one journaled operation.
+
+        Args:
+            plan (Dict): Plan from plan_organize()
+            user_approved (bool): Whether the user approved the plan
+            progress (Callable, optional): Receives per-root progress events
+
+        Returns:
+            Dict: Result with 'success', 'message', 'operation_id' and per-root
+                  'moved' / 'failed' counts
+        """
+        if self.action_manager is None:
+            raise RuntimeError("Applying a plan needs an ActionManager")
+
+        name = plan.get('workspace', '')
+        items = plan.get('items', [])
+        roots = list(plan.get('per_root', {}).keys()) or sorted({i['root'] for i in items})
+        per_root = {root: {'moved': 0, 'failed': 0} for root in roots}
+
+        with self.action_manager.journal_scope(kind='workspace_organize',
+                                               description=f"Organize workspace {name}") as scope:
+            for position, item in enumerate(items, 1):
+                result = self.action_manager.execute(item['file'], item['classification'], user_approved=user_approved)
+                counts = per_root.setdefault(item['root'], {'moved': 0, 'failed': 0})
+                counts['moved' if result.get('success') else 'failed'] += 1
+                self._emit(progress, workspace=name, phase='apply', root=item['root'],
+                           root_index=roots.index(item['root']) if item['root'] in roots else -1,
+                           root_count=len(roots), processed=position, total=len(items))
+
+        moved = sum(c['moved'] for c in per_root.values())
+        failed = sum(c['failed'] for c in per_root.values())
+        return {
+            'success': failed == 0,
+            'message': f"Workspace '{name}': {moved} file(s) organized across {len(per_root)} root(s)"
+                       + (f", {failed} failed" if failed else ''),
+            'operation_id': scope.get('operation_id'),
+            'per_root': per_root
+        }
@@ -0,0 +1,155 @@
+"""
+Write Access Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module checks, before a run starts, that the app can actually move
+the files it plans to move, so a plan can flag the ones that would fail
+instead of the run finding out half-way:
+
+    check_write_access(['~/Downloads/report.pdf'], {'~/Downloads/report.pdf': '/mnt/nas/report.pdf'})
+    -> {'success': False, 'problems': 1, 'paths': [{'path': '~/Downloads/report.pdf',
+         'ok': False, 'problem': 'destination_read_only_mount',
+         'message': "The destination folder /mnt/nas is on a read-only drive", ...}], ...}
+
+A path can be moved when it exists and can be read, it is not locked by
+another program (Windows), it is not read-only (Windows; a read-only file
+cannot be deleted after a copy to another drive), and its folder can be
+written to, since moving a file out of a folder changes the folder. A
+destination needs its nearest existing folder to be writable; folders
+below it are created on the way.
+
+Folders are tested by creating and removing a hidden temporary file in
+them, which is true to ACLs, network shares and read-only mounts where
+permission bits are not. Each folder is tested once per check.
+
+Problems, in the order they are looked for:
+    - missing:          the path does not exist
+    - not_readable:     the file cannot be read
+    - locked:           another program has the file open (Windows)
+    - read_only_file:   the file is marked read-only (Windows)
+    - read_only_mount / permission_denied: its folder cannot be written to
+    - destination_read_only_mount / destination_permission_denied: the
+      destination folder cannot be written to
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import errno
+import logging
+import os
+import tempfile
+from pathlib import Path
+from typing import Dict, Any, Iterable, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+ERROR_SHARING_VIOLATION = 32
+ERROR_LOCK_VIOLATION = 33
+
+
+def _probe_folder(folder: str) -> Optional[Tuple[str, str]]:
+    """None if a file can be created in the folder, else (problem, reason)."""
+    try:
+        handle, probe = tempfile.mkstemp(prefix='.aifo-access-', suffix='.tmp', dir=folder)
+    except OSError as e:
+        if e.errno == errno.EROFS:
+            return 'read_only_mount', f"{folder} is on a read-only drive"
+        return 'permission_denied', f"{folder} cannot be written to ({e.strerror or e})"
+    os.close(handle)
+    try:
+        os.remove(probe)
+    except OSError as e:
+        logger.debug(f"Could not remove access probe {probe}: {e}")
+    return None
+
+
+def _locked(path: str) -> bool:
+    """Whether another program holds the file open in a way that stops it being moved (Windows only)."""
+    if os.name != 'nt' or not os.path.isfile(path):
+        return False
+    import ctypes
+    from ctypes import wintypes
+
+    kernel32 = ctypes.WinDLL('kernel32', use_last_error=True)
+    kernel32.CreateFileW.restype = wintypes.HANDLE
+    delete_access, share_all, open_existing = 0x00010000, 0x7, 3
+    handle = kernel32.CreateFileW(path, delete_access, share_all, None, open_existing, 0, None)
+    if handle == wintypes.HANDLE(-1).value:
+        return ctypes.get_last_error() in (ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION)
+    kernel32.CloseHandle(handle)
+    return False
+
+
+def _existing_folder(path: Path) -> Path:
+    """The path's nearest folder that exists (the one a new folder would be created in)."""
+    while not path.exists() and path.parent != path:
+        path = path.parent
+    return path
+
+
+def check_write_access(paths: Iterable[str], destinations: Optional[Dict[str, str]] = None) -> Dict[str, Any]:
+    """
+    Check that files (or folders) can be moved, and to where.
+
+    Args:
+        paths (Iterable[str]): Paths that would be moved
+        destinations (Dict[str, str], optional): Path -> where it would go,
+            for the paths whose destination should be checked too
+
+    Returns:
+        Dict: 'success' (nothing would fail), 'message', 'checked',
+              'problems' and 'paths': one {'path', 'destination', 'ok',
+              'problem', 'message'} per path, in order (see the module
+              docstring for the problems)
+    """
+    destinations = destinations or {}
+    folders: Dict[str, Optional[Tuple[str, str]]] = {}
+
+    def folder_problem(folder: Path) -> Optional[Tuple[str, str]]:
+        key = os.path.normcase(str(folder))
+        if key not in folders:
+            folders[key] = _probe_folder(str(folder))
+        return folders[key]
+
+    reports = []
+    for path in paths:
+        source = Path(path).expanduser()
+        destination = destinations.get(path)
+        problem = message = None
+        if not os.path.lexists(source):
+            problem, message = 'missing', f"{source} no longer exists"
+        elif not os.access(source, os.R_OK):
+            problem, message = 'not_readable', f"{source} cannot be read"
+        elif _locked(str(source)):
+            problem, message = 'locked', f"{source.name} is open in another program"
+        elif os.name == 'nt' and source.is_file() and not os.access(source, os.W_OK):
+            problem, message = 'read_only_file', f"{source.name} is marked read-only"
+        else:
+            blocked = folder_problem(source.parent)
+            if blocked is not None:
+                problem, message = blocked[0], f"Its folder {blocked[1]}"
+            elif destination:
+                blocked = folder_problem(_existing_folder(Path(destination).expanduser().parent))
+                if blocked is not None:
+                    problem, message = 'destination_' + blocked[0], f"The destination folder {blocked[1]}"
+        reports.append({'path': path, 'destination': destination, 'ok': problem is None,
+                        'problem': problem, 'message': message})
+
+    failing = sum(1 for report in reports if not report['ok'])
+    if failing:
+        logger.info(f"Write access check: {failing} of {len(reports)} path(s) cannot be moved")
+    return {
+        'success': not failing,
+        'message': (f"{failing} of {len(reports)} path(s) cannot be moved" if failing
+                    else f"All {len(reports)} path(s) can be moved"),
+        'checked': len(reports),
+        'problems': failing,
+        'paths': reports,
+    }
@@ -16,9 +16,13 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
+import os
 import sys
 import argparse
+import contextlib
+import logging
 import asyncio
+from datetime import datetime
 from pathlib import Path
 
 # Add src directory to path
@@ -28,13 +32,65 @@ from .config import get_config
 from .core.db_manager import DatabaseManager
 from .core.classifier import FileClassifier
 from .core.actions import ActionManager
-from .core.watcher import FolderWatcher
+from .core.archives import ArchiveManager
+from .core.watcher import FolderWatcher, FolderWatchManager, EVENT_FILE_ORGANIZED
 from .core.duplicates import DuplicateFinder
+from .core.dedupe import Deduplicator
+from .core.fsops import FileMover
+from .core.renamer import FileRenamer
+from .core.trash import SystemTrash
+from .core.extract import extract_many, extract_metadata
+from .core.preview import DEFAULT_MAX_BYTES, get_file_preview
+from .core.thumbnails import DEFAULT_SIZE as THUMBNAIL_SIZE, ThumbnailCache
+from .core.settings import SettingsStore
+from .core.keychain import delete_secret, get_secret, store_secret
 from .ai.ollama_client import OllamaClient
+from .ai.providers import create_provider
 from .license.validator import LicenseValidator
 from .ui.dashboard import run_dashboard
 from .core.deferred import DeferredService
+from .core.config_bundle import ConfigBundleManager
+from .core.journal import OperationJournal
+from .core.plans import OrganizePlanner
+from .core.rule_validation import validate_rules
+from .utils.structured_logging import configure_logging, span
+from .core.crash_reports import CrashReporter
+from .core.updater import UpdateManager
+from .core.telemetry import get_telemetry
+from .core.diagnostics import DiagnosticsExporter
+from .core.self_test import run_self_test
+from .core.simulation import simulate_on_copy
+from .core.write_access import check_write_access
+from .core.backend_check import check_backend
+from .core.interpreter import detect_python_environments
+from .core.disk_usage import analyze_disk_usage
+from .core.search_index import SearchIndex
+from .core.tags import TagStore
+from .core.control import ControlServer, default_address, files_to_open, forward_to_running
+from .core.rest_api import RestApiServer
+from .core.sidecar import serve_stdio
+from .core.corrections import CorrectionLearner
+from .core.scheduler import Scheduler
+from .core.usage import UsageLedger
+from .core.rules import UserRules
+from .core.taxonomy import TaxonomyManager
+from .core.classification_cache import ClassificationCache
+from .core.local_rules import classify_file_offline
+from .core.batch_classify import classify_files, DEFAULT_CONCURRENCY
+from .core.file_listing import ingest_dropped_paths, list_files, list_files_paged, list_files_recursive
+from .core.filetype import detect_file_type, detect_mismatched_extensions
+from .core.ocr import OcrEngine
+from .core.tray import TrayIcon
+from .core.autostart import autostart_status, set_autostart
+from .core.ignore_rules import add_ignore_rule, list_ignore_rules, remove_ignore_rule
+from .core.jobs import JobManager, EVENT_JOB_FINISHED
+from .core.notifications import Notifier
+from .core.split_archives import collapse_split_archives
 from .utils.error_handler import ConfigurationError
+from .utils.output_schemas import (
+    json_document, outcome_exit_code, EXIT_OK, EXIT_ERROR, EXIT_CONFLICTS,
+    EXIT_BACKEND_UNAVAILABLE, EXIT_USAGE
+)
 
 
 class ServiceContainer:
@@ -164,21 +220,17 @@ class FileOrganiser:
         """Initialize all application components with error handling."""
         failed_components = []
 
-        # Register Ollama client factory
+        # Register AI client factory (Ollama, or the provider the settings select)
         def create_ollama_client():
             try:
-                client = OllamaClient(
-                    base_url=self.config.ollama_base_url,
-                    model=self.config.ollama_model,
-                    timeout=self.config.get('ollama_timeout', 30)
-                )
+                client = create_provider(self.config)
                 if client.is_available():
                     return client
                 else:
-                    print(f"⚠️  Ollama not available - AI classification disabled")
+                    print(f"⚠️  AI provider '{client.name}' not available - AI classification disabled")
                     return None
             except Exception as e:
-                print(f"❌ Failed to create Ollama client: {e}")
+                print(f"❌ Failed to create AI client: {e}")
                 return None
 
         self.services.register('ollama_client', create_ollama_client)
@@ -186,7 +238,15 @@ class FileOrganiser:
         # Register classifier factory
         def create_classifier():
             ollama_client = self.services.get('ollama_client')
-            return FileClassifier(self.config, ollama_client)
+            classifier = FileClassifier(self.config, ollama_client)
+            classifier.user_rules = UserRules(self.config)
+            classifier.settings = SettingsStore()
+            classifier.taxonomy = TaxonomyManager(self.config, classifier.settings)
+            classifier.corrections = CorrectionLearner(self.config, self.db)
+            classifier.cache = ClassificationCache(self.config, self.db)
+            classifier.usage = UsageLedger(self.config, self.db)
+            classifier.tags = TagStore(self.config, self.db)
+            return classifier
 
         self.services.register('classifier', create_classifier)
 
@@ -225,7 +285,7 @@ class FileOrganiser:
             print("Connecting to Ollama...")
             self.ollama = self.services.get('ollama_client')
             if self.ollama:
-                print(f"✅ Ollama connected (model: {self.config.ollama_model})")
+                print(f"✅ {self.ollama.name} connected (model: {self.ollama.model})")
         except Exception as e:
             print(f"❌ Failed to initialize Ollama client: {e}")
             self.ollama = None
@@ -393,6 +453,13 @@ class FileOrganiser:
 
         print("\nPress Ctrl+C to stop\n")
 
+        # Own the control socket so later launches forward to this instance
+        control = ControlServer(default_address(self.config), {
+            'open': self._open_forwarded,
+            'status': lambda: {'mode': 'watch', 'pending': self.watcher.get_pending_count() if self.watcher else 0}
+        })
+        control.start(background=True)
+
         # Start deferred service first, then watcher
         if self.deferred:
             self.deferred.start()
@@ -408,6 +475,287 @@ class FileOrganiser:
             if self.watcher:
                 self.watcher.stop()
             print("Goodbye! 👋")
+        finally:
+            control.stop()
+
+    def engine_methods(self, mode: str = 'daemon') -> dict:
+        """
+        Methods the long-lived engine serves (control socket, REST API or sidecar).
+
+        Args:
+            mode (str): Reported by 'status' ('daemon' or 'sidecar')
+
+        Returns:
+            dict: Method name -> handler(**params); call stop_engine() when done
+        """
+        journal = OperationJournal(self.db)
+        action_manager = ActionManager(self.config, self.db, journal=journal)
+        planner = OrganizePlanner(self.config, self.classifier, action_manager)
+        dedupe = Deduplicator(self.config, self.db, journal)
+        mover = FileMover(self.config, self.db, journal)
+        renamer = FileRenamer(self.config, self.db, journal)
+        archives = ArchiveManager(self.config, self.db, self.classifier, action_manager, journal)
+        system_trash = SystemTrash(self.config, self.db)
+        ocr = getattr(self.classifier, 'ocr', None) or OcrEngine(self.config)
+        diagnostics = DiagnosticsExporter(self.config, self.db)
+        thumbnails = ThumbnailCache(self.config)
+        app_settings = SettingsStore()
+        user_rules = UserRules(self.config, app_settings)
+        taxonomy = self.classifier.taxonomy or TaxonomyManager(self.config, app_settings)
+        bundles = ConfigBundleManager(self.config, settings=planner.profiles.store)
+        local_ollama = OllamaClient(self.config.ollama_base_url, self.config.ollama_model,
+                                    self.config.get('ollama_timeout', 30))
+
+        def status():
+            return {
+                'mode': mode,
+                'pid': os.getpid(),
+                'watching': bool(self.watcher and self.watcher._running),
+                'pending': self.watcher.get_pending_count() if self.watcher else 0,
+                'watched_folders': self.config.watched_folders,
+                'ai_available': self.ollama is not None,
+                'auto_mode': self.config.auto_mode
+            }
+
+        def watch_start():
+            if not self.watcher:
+                raise RuntimeError('Folder watcher not available')
+            if self.deferred:
+                self.deferred.start()
+            self.watcher.start(background=True, async_processing=True)
+            return status()
+
+        def watch_stop():
+            if self.watcher:
+                self.watcher.stop()
+            return status()
+
+        def apply(plan: dict = None, plan_file: str = None, approved: list = None):
+            if plan_file:
+                plan = planner.load_plan(plan_file)
+            if not plan:
+                raise ValueError('apply needs a plan or a plan_file')
+            return planner.apply_plan(plan, approved=approved)
+
+        def undo(operation_id: int = None):
+            return journal.undo(int(operation_id)) if operation_id else journal.undo_last()
+
+        # Quick actions in the tray (shown by run_daemon; the front end sets the tooltip)
+        tray = self.tray = TrayIcon(self.config, {
+            'organize': lambda folder: jobs.start_organize(folder),
+            'pause': watch_stop,
+            'resume': watch_start,
+            'is_watching': lambda: bool(self.watcher and self.watcher._running)
+        })
+        notifier = self.notifier = Notifier(app_settings, tray)
+        planner.conflicts.notifier = notifier
+
+        def on_job_event(job_id, event, payload):
+            if event == EVENT_JOB_FINISHED:
+                notifier.run_completed(payload)
+
+        def on_watch_event(event, payload):
+            if event == EVENT_FILE_ORGANIZED:
+                notifier.file_moved(payload['file'], payload.get('new_path'))
+
+        # Organize runs in child processes, so they can be cancelled
+        jobs = self.jobs = JobManager(listener=on_job_event)
+        # Organize runs on a cron schedule, for as long as the engine runs
+        scheduler = self.scheduler = Scheduler(jobs)
+        scheduler.start()
+        # Folders watched on request, each with its own rules
+        folder_watches = self.folder_watches = FolderWatchManager(self.config, self.classifier, action_manager,
+                                                                  listener=on_watch_event)
+        folder_watches.leave_alone = planner.leave_alone
+        search_index = folder_watches.search_index = SearchIndex(self.config, self.db,
+                                                                 getattr(self.classifier, 'cache', None))
+        tag_store = getattr(self.classifier, 'tags', None) or TagStore(self.config, self.db)
+        folder_watches.tag_store = search_index.tags = tag_store
+        tag_store.search_index = search_index
+
+        return {
+            'open': self._open_forwarded,
+            'status': status,
+            'watcher.start': watch_start,
+            'watcher.stop': watch_stop,
+            'watch.start': lambda folder, rules=None: folder_watches.start_watching(folder, rules),
+            'watch.stop': folder_watches.stop_watching,
+            'watch.list': folder_watches.watching,
+            'classify': lambda path, deep=False: self.classifier.classify(path, deep_analysis=deep),
+            'classify_files': lambda paths, max_concurrent=DEFAULT_CONCURRENCY, deep=False: classify_files(
+                lambda path: self.classifier.classify(path, deep_analysis=deep), paths, max_concurrent),
+            'cache_stats': lambda: self.classifier.cache.stats(),
+            'get_usage_summary': lambda period='month': self.classifier.usage.get_usage_summary(period),
+            'detect_local_ai': local_ollama.detect,
+            'list_local_models': local_ollama.list_local_models,
+            'clear_cache': lambda: self.classifier.cache.clear(),
+            'find_duplicates': lambda folder, algorithm='blake2b', recursive=True, min_size=1:
+                dedupe.find_duplicates(folder, algorithm, recursive, int(min_size)),
+            'resolve_duplicates': lambda groups, mode='trash', destination=None, dry_run=False:
+                dedupe.resolve_duplicates(groups, mode, destination, bool(dry_run)),
+            'rename_files': lambda renames, template=None, dry_run=False:
+                renamer.rename_files(renames, template, bool(dry_run)),
+            'apply_moves': lambda moves, on_conflict='keep_both', dry_run=False:
+                mover.apply_plan(moves, on_conflict, bool(dry_run)),
+            'move_to_trash': lambda paths: system_trash.move_to_trash(paths),
+            'restore_from_trash': lambda ids: system_trash.restore_from_trash(ids),
+            'list_trash_items': lambda limit=100: system_trash.list_trash_items(int(limit)),
+            'extract_metadata': lambda path: extract_metadata(path).to_dict(),
+            'extract_metadata_batch': lambda paths: extract_many(paths),
+            'get_file_preview': lambda path, max_bytes=DEFAULT_MAX_BYTES: get_file_preview(path, int(max_bytes)),
+            'get_thumbnail': lambda path, size=THUMBNAIL_SIZE: thumbnails.get_thumbnail(path, int(size)),
+            'get_thumbnails': lambda paths, size=THUMBNAIL_SIZE: thumbnails.get_thumbnails(paths, int(size)),
+            'thumbnail_cache_stats': thumbnails.stats,
+            'clear_thumbnail_cache': thumbnails.clear,
+            'classify_file_offline': lambda path: classify_file_offline(path, self.config),
+            'scan': lambda folder, recursive=True: planner.scan(folder, recursive),
+            'analyze_disk_usage': lambda folder, depth=3: analyze_disk_usage(folder, depth, self.config),
+            'index_folder': lambda folder, recursive=True: search_index.index_folder(folder, bool(recursive)),
+            'search': lambda query='', filters=None, limit=None: search_index.search(query, filters, limit),
+            'search_index_stats': search_index.stats,
+            'set_tags': lambda path, tags: tag_store.set_tags(path, tags),
+            'get_tags': tag_store.get_tags,
+            'find_by_tag': lambda tag, limit=1000: tag_store.find_by_tag(tag, int(limit)),
+            'list_tags': tag_store.list_tags,
+            'list_files': list_files,
+            'detect_file_type': detect_file_type,
+            'detect_mismatched_extensions': lambda folder, recursive=True:
+                detect_mismatched_extensions(folder, bool(recursive)),
+            'ocr_file': lambda path: {'path': path, 'text': ocr.ocr_file(path)},
+            'ocr_status': ocr.status,
+            'list_archive_contents': archives.list_contents,
+            'extract_archive': lambda path, dest, selective=None, organize=False, password=None:
+                archives.extract_archive(path, dest, selective, bool(organize), password),
+            'list_files_recursive': list_files_recursive,
+            'list_files_paged': lambda directory, offset=0, limit=100, sort_by='name', order='asc', **options:
+                list_files_paged(directory, int(offset), int(limit), sort_by, order, config=self.config, **options),
+            'ingest_dropped_paths': lambda paths, **options: ingest_dropped_paths(paths, config=self.config, **options),
+            'list_ignore_rules': list_ignore_rules,
+            'add_ignore_rule': add_ignore_rule,
+            'remove_ignore_rule': remove_ignore_rule,
+            'plan': planner.build_plan,
+            'plan_organization': lambda options: planner.plan_organization(options).to_dict(),
+            'apply_plan_items': lambda plan_id, accepted_ids: planner.apply_plan_items(int(plan_id), accepted_ids),
+            'cleanup_plan': planner.build_cleanup_plan,
+            'list_conflicts': lambda limit=100: [c.to_dict() for c in planner.conflicts.list_conflicts(int(limit))],
+            'resolve_conflict': planner.conflicts.resolve_conflict,
+            'get_settings': lambda: app_settings.get_settings(),
+            'update_settings': app_settings.update_settings,
+            'list_rules': user_rules.list_rules,
+            'add_rule': lambda rule, position=None: user_rules.add_rule(rule, position),
+            'remove_rule': user_rules.remove_rule,
+            'test_rule': lambda path, rule=None: user_rules.test_rule(path, rule),
+            'list_profiles': planner.profiles.list_profiles,
+            'save_profile': planner.profiles.save_profile,
+            'delete_profile': planner.profiles.delete_profile,
+            'list_categories': taxonomy.list_categories,
+            'taxonomy_tree': taxonomy.tree,
+            'add_category': taxonomy.add_category,
+            'update_category': taxonomy.update_category,
+            'delete_category': taxonomy.delete_category,
+            'export_configuration': bundles.export_configuration,
+            'import_configuration': lambda path, sections=None: bundles.import_configuration(path, sections),
+            'store_secret': store_secret,
+            'get_secret': get_secret,
+            'delete_secret': delete_secret,
+            'correct': lambda path, destination, category=None: planner.corrections.record(path, destination, category),
+            'apply': apply,
+            'undo': undo,
+            'get_history': lambda limit=50: journal.list_operations(int(limit)),
+            'get_operation': lambda operation_id: journal.get_operation(int(operation_id)),
+            'undo_operation': lambda operation_id, dry_run=False: journal.undo(int(operation_id), bool(dry_run)),
+            'jobs.start': lambda folder, preview=False, deep=False, strategy=None:
+                jobs.start_organize(folder, preview, deep, strategy),
+            'jobs.list': lambda include_finished=True: jobs.list_jobs(include_finished),
+            'jobs.get': jobs.get_job,
+            'jobs.cancel': jobs.cancel_job,
+            'schedule_organize': lambda folder, cron_expr, options=None:
+                scheduler.schedule_organize(folder, cron_expr, options),
+            'schedules.list': scheduler.list_schedules,
+            'schedules.enable': lambda schedule_id, enabled=True: scheduler.set_enabled(schedule_id, bool(enabled)),
+            'schedules.remove': scheduler.remove_schedule,
+            'schedules.events': lambda since=0: scheduler.events(int(since)),
+            'set_tray_status': lambda text=None, progress=None: tray.set_status(text, progress),
+            'set_autostart': lambda enabled=True: set_autostart(bool(enabled), self.config),
+            'autostart_status': autostart_status,
+            'rename': lambda path, new_name, rules='portable': action_manager.rename_file(path, new_name, rules),
+            'timings': lambda limit=20: journal.timing_report(int(limit)),
+            'query_history': lambda filters=None: journal.query_history(filters),
+            'validate_rules': validate_rules,
+            'export_diagnostics': lambda output=None: diagnostics.export(output),
+            'self_test': lambda keep=False: run_self_test(self.config, keep=keep),
+            'simulate_on_copy': lambda folder, sample_size=25, deep=False, seed=None, keep=False: simulate_on_copy(
+                folder, int(sample_size), self.config, self.classifier, deep, seed, keep),
+            'check_write_access': check_write_access,
+            'check_backend': lambda expected_version=None: check_backend(self.config, expected_version),
+            'detect_python_environments': lambda: detect_python_environments(self.config),
+        }
+
+    def stop_engine(self):
+        """Stop what engine_methods() started: schedules, running jobs, folder watches and the tray icon."""
+        if getattr(self, 'scheduler', None):
+            self.scheduler.stop()
+        if getattr(self, 'jobs', None):
+            self.jobs.shutdown()
+        if getattr(self, 'folder_watches', None):
+            self.folder_watches.stop_all()
+        if getattr(self, 'tray', None):
+            self.tray.stop()
+        if self.watcher:
+            self.watcher.stop()
+
+    def run_daemon(self, address: str = None):
+        """
+        Run as the single long-lived engine, driven over the control socket.
+
+        The GUI, CLI (aifo daemon ...) and shell extensions call methods on
+        this process instead of starting engines of their own.
+
+        Args:
+            address (str, optional): Socket path or pipe name (default: per-user)
+        """
+        address = address or default_address(self.config)
+        server = ControlServer(address, self.engine_methods('daemon'))
+        server.start(background=True)
+        print(f"🛰️  Engine running; control socket: {address}")
+        self.tray.start()
+
+        rest_api = None
+        if (self.config.rest_api_settings or {}).get('enabled'):
+            try:
+                rest_api = RestApiServer(server.methods, self.config)
+                rest_api.start()
+                host, port = rest_api.address
+                print(f"🌐 REST API: http://{host}:{port}/api/v1 (token: aifo rest-api --token)")
+            except (OSError, ValueError) as e:
+                rest_api = None
+                print(f"⚠️  REST API not started: {e}")
+        print("Press Ctrl+C to stop\n")
+
+        try:
+            server.wait()
+        except KeyboardInterrupt:
+            pass
+        finally:
+            if rest_api:
+                rest_api.stop()
+            self.stop_engine()
+            server.stop()
+            print("Engine stopped. Goodbye! 👋")
+
+    def run_sidecar(self, stdout):
+        """
+        Run as a front end's engine, driven over stdin and stdout (see core.sidecar).
+
+        Args:
+            stdout: The real stdout, kept for protocol replies only
+        """
+        try:
+            serve_stdio(self.engine_methods('sidecar'), sys.stdin, stdout)
+        except KeyboardInterrupt:
+            pass
+        finally:
+            self.stop_engine()
 
     def scan_existing_files(self):
         """Scan existing files in watched folders."""
@@ -417,7 +765,7 @@ class FileOrganiser:
             print("❌ Watcher not available - cannot scan existing files")
             return
 
-        files = self.watcher.scan_existing_files()
+        files = collapse_split_archives(self.watcher.scan_existing_files())
         print(f"Found {len(files)} candidates")
         # First-run deep planning: enqueue by age
         move_older_days = int(self.config.get('deferred.first_run_move_older_days', 7))
@@ -513,15 +861,25 @@ class FileOrganiser:
                 for path in group['paths']:
                     print(f"   - {path}")
 
-    def run_dashboard(self, host: str = "127.0.0.1", port: int = 5000):
+    def run_dashboard(self, host: str = "127.0.0.1", port: int = 5000, open_paths: list = None,
+                      background: bool = False):
         """
         Run the web dashboard.
 
         Args:
             host (str): Host to bind to
             port (int): Port to listen on
+            open_paths (list, optional): Files or folders to queue for review
+            background (bool): Start watching and keep running with only the tray icon
         """
-        run_dashboard(host, port)
+        run_dashboard(host, port, open_paths, background)
+
+    def _open_forwarded(self, paths: list) -> dict:
+        """Handle files forwarded by a second launch as if they were just detected."""
+        files = files_to_open(paths)
+        for file_path in files:
+            self._on_file_detected(file_path)
+        return {'queued': files}
 
     def show_stats(self):
         """Show statistics."""
@@ -542,6 +900,252 @@ class FileOrganiser:
             print(f"Days remaining: {license_status['days_remaining']}")
 
 
+def run_validate_rules_command(args) -> int:
+    """
+    Run validate-rules <file>: lint a YAML rules file without loading it.
+
+    Args:
+        args: Parsed CLI arguments
+
+    Returns:
+        int: EXIT_OK, or EXIT_ERROR if the file has errors
+    """
+    if not args.target:
+        print("❌ validate-rules requires a rules file")
+        return EXIT_USAGE
+
+    report = validate_rules(args.target)
+    code = EXIT_OK if report['valid'] else EXIT_ERROR
+    if args.json:
+        print(json_document('validate-rules', report, ok=report['valid']))
+        return code
+
+    print(f"{report['file']}: {report['rules']} rule(s)")
+    for diagnostic in report['diagnostics']:
+        location = f"{diagnostic['location']}: " if diagnostic['location'] else ''
+        print(f"  {diagnostic['severity']}: {location}{diagnostic['message']} [{diagnostic['code']}]")
+    print(f"{report['errors']} error(s), {report['warnings']} warning(s)")
+    return code
+
+
+def run_autostart_command(args) -> int:
+    """
+    Run autostart [on|off]: start in background mode on login, or show whether it does.
+
+    Args:
+        args: Parsed CLI arguments
+
+    Returns:
+        int: EXIT_OK, EXIT_ERROR if the login item could not be changed
+    """
+    if args.target not in (None, 'on', 'off'):
+        print("❌ autostart takes on or off")
+        return EXIT_USAGE
+    if args.target is None:
+        status = autostart_status()
+        print(f"Start on login: {'on' if status['enabled'] else 'off'} ({status['location']})")
+        return EXIT_OK
+    result = set_autostart(args.target == 'on', get_config())
+    print(f"{'✅' if result['success'] else '❌'} {result['message']}")
+    return EXIT_OK if result['success'] else EXIT_ERROR
+
+
+def run_config_bundle_command(args) -> int:
+    """
+    Run export-config / import-config.
+
+    Args:
+        args: Parsed CLI arguments
+
+    Returns:
+        int: Process exit code
+    """
+    config = get_config()
+    bundles = ConfigBundleManager(config, settings=SettingsStore())
+
+    if args.command == 'export-config':
+        output = args.file or f"ai-file-organiser-config-{datetime.now():%Y%m%d}.json"
+        result = bundles.export_bundle(output)
+        print(result['message'])
+        return 0 if result['success'] else 1
+
+    if not args.file:
+        print("❌ import-config requires --file <bundle.json>")
+        return 2
+
+    try:
+        bundle = bundles.load_bundle(args.file)
+        preview = bundles.preview_import(bundle)
+    except ConfigurationError as e:
+        print(f"❌ {e}")
+        return 1
+
+    print(f"\n{preview['message']}")
+    for change in preview['changes']:
+        if change['action'] == 'added':
+            print(f"  + [{change['section']}] {change['key']} = {change['new']!r}")
+        else:
+            print(f"  ~ [{change['section']}] {change['key']}: {change['old']!r} -> {change['new']!r}")
+
+    if not preview['changes']:
+        print("Nothing to import.")
+        return 0
+
+    if not args.yes:
+        answer = input("\nApply these changes? [y/N]: ").strip().lower()
+        if answer not in ('y', 'yes'):
+            print("Import cancelled.")
+            return 0
+
+    result = bundles.apply_import(bundle)
+    print(result['message'])
+    for backup in result.get('backups', []):
+        print(f"  Backup: {backup}")
+    return 0 if result['success'] else 1
+
+
+HEADLESS_COMMANDS = ('plan', 'apply', 'undo')
+
+# Commands that run the long-lived app; a second launch forwards to the first
+SINGLE_INSTANCE_COMMANDS = ('dashboard', 'background', 'watch', 'daemon', 'open')
+
+
+def run_headless_command(args) -> int:
+    """
+    Run scan <dir>, plan <dir>, apply <plan.json> and undo [id] without a window.
+
+    These use the same engine (classifier, action manager, journal) as the
+    dashboard, so scripts get identical behavior. Nothing here prompts,
+    except apply, which asks for confirmation unless --yes is given.
+
+    With --json, human output goes to stderr and one versioned JSON document
+    (see utils/output_schemas.py) is printed on stdout; apply without --yes
+    then only reports the plan.
+
+    Args:
+        args: Parsed CLI arguments
+
+    Returns:
+        int: Process exit code (EXIT_* in utils/output_schemas.py): plan
+             returns EXIT_CONFLICTS when destinations collide, apply
+             EXIT_PARTIAL when only some items went through
+    """
+    report = {'data': None, 'errors': []}
+    with span(f"command.{args.command}", logging.getLogger(__name__), logging.INFO, target=args.target) as trace:
+        if not args.json:
+            code = _run_headless(args, report)
+        else:
+            with contextlib.redirect_stdout(sys.stderr):
+                code = _run_headless(args, report)
+        trace.record(exit_code=code)
+        if report['errors']:
+            trace.fail('; '.join(report['errors']))
+    if not args.json:
+        return code
+
+    print(json_document(args.command, report['data'], ok=code in (EXIT_OK, EXIT_CONFLICTS),
+                        errors=report['errors']))
+    return code
+
+
+def _run_headless(args, report: dict) -> int:
+    """Headless command body; fills report['data'] and report['errors'] for --json."""
+    def fail(message: str, code: int = EXIT_ERROR) -> int:
+        print(f"❌ {message}")
+        report.setdefault('errors', []).append(message)
+        return code
+
+    config = get_config()
+    try:
+        db = DatabaseManager()
+    except Exception as e:
+        return fail(f"Database unavailable: {e}", EXIT_BACKEND_UNAVAILABLE)
+
+    if not LicenseValidator(config, db).check_license_status()['is_valid']:
+        return fail("No valid license. Activate one with: --activate XXXX-XXXX-XXXX-XXXX")
+
+    journal = OperationJournal(db)
+    if args.command == 'undo':
+        if args.target and not args.target.isdigit():
+            return fail(f"Not an operation ID: {args.target}", EXIT_USAGE)
+        result = journal.undo(int(args.target)) if args.target else journal.undo_last()
+        report['data'] = result
+        print(result['message'])
+        return EXIT_OK if result['success'] else EXIT_ERROR
+
+    ollama = OllamaClient(
+        base_url=config.ollama_base_url,
+        model=config.ollama_model,
+        timeout=config.get('ollama_timeout', 30)
+    )
+    ai_available = ollama.is_available()
+    if args.deep and not ai_available:
+        return fail(f"AI backend unavailable at {config.ollama_base_url}; --deep needs it",
+                    EXIT_BACKEND_UNAVAILABLE)
+    classifier = FileClassifier(config, ollama if ai_available else None)
+    classifier.cache = ClassificationCache(config, db)
+    action_manager = ActionManager(config, db, journal=journal)
+    planner = OrganizePlanner(config, classifier, action_manager)
+
+    if args.command == 'apply':
+        try:
+            plan = planner.load_plan(args.target)
+        except ValueError as e:
+            return fail(str(e))
+        report['data'] = {'plan': plan, 'result': None}
+        print(f"Plan for {plan['root']}: {len(plan['items'])} action(s)")
+        if not args.yes:
+            if args.json:
+                print("Not applied; pass --yes to apply with --json")
+                return EXIT_OK
+            answer = input("Apply this plan? [y/N]: ").strip().lower()
+            if answer not in ('y', 'yes'):
+                print("Cancelled.")
+                return EXIT_OK
+        result = planner.apply_plan(plan)
+        report['data']['result'] = result
+        print(result['message'])
+        for failure in result['failed'] + result['skipped']:
+            print(f"  ❌ {failure['file']}: {failure['message']}")
+        for conflict in result['conflicts']:
+            print(f"  ⚠️  {conflict['source']}: {conflict['destination']} exists (conflict {conflict['id']})")
+        if result['snapshot_id']:
+            print(f"Snapshot {result['snapshot_id']} saved before applying")
+        if result['operation_id']:
+            print(f"Undo with: undo {result['operation_id']}")
+        return outcome_exit_code(len(result['applied']),
+                                 len(result['failed']) + len(result['skipped']) + len(result['conflicts']))
+
+    try:
+        if args.command == 'scan':
+            report['data'] = scan_report = planner.scan(args.target)
+            print(f"{scan_report['root']}: {scan_report['files']} files, "
+                  f"{scan_report['total_size'] / (1024 ** 2):.1f} MB")
+            for category, count in scan_report['categories'].items():
+                print(f"  {category:<20} {count}")
+            return EXIT_OK
+
+        plan = planner.build_plan(args.target, deep=args.deep)
+    except NotADirectoryError as e:
+        return fail(str(e))
+
+    output = args.file or f"organize-plan-{datetime.now():%Y%m%d-%H%M%S}.json"
+    planner.save_plan(plan, output)
+    report['data'] = {'plan_file': output, 'plan': plan}
+    for item in plan['items']:
+        flag = f"  [{item['conflict'].replace('_', ' ')}]" if item['conflict'] else ''
+        if item.get('access'):
+            flag += f"  [cannot move: {item['access']['message']}]"
+        print(f"  {item['file']} -> {item['destination']}{flag}")
+    print(f"{len(plan['items'])} action(s), {plan['unchanged']} file(s) unchanged")
+    if plan['conflicts']:
+        print(f"⚠️  {plan['conflicts']} destination conflict(s) to review before applying")
+    if plan.get('access_problems'):
+        print(f"⚠️  {plan['access_problems']} file(s) cannot be moved as things stand (see: cannot move)")
+    print(f"Plan written to {output}; apply it with: apply {output}")
+    return EXIT_CONFLICTS if plan['conflicts'] else EXIT_OK
+
+
 def main():
     """Main CLI entry point."""
     parser = argparse.ArgumentParser(
@@ -550,10 +1154,28 @@ def main():
         epilog="""
 Examples:
   %(prog)s dashboard           # Run web dashboard
+  %(prog)s background          # Keep watching and scheduled runs going with only a tray icon
+  %(prog)s autostart on        # Start in the background on login (off to stop; no argument: status)
   %(prog)s watch              # Watch folders for new files
+  %(prog)s daemon             # Run one engine for the GUI, CLI and shell extensions
+  %(prog)s sidecar            # Run one engine for a front end over stdin/stdout (JSON lines)
+  %(prog)s open FILE...       # Hand files to the running app (or start the dashboard)
   %(prog)s scan               # Scan existing files
+  %(prog)s scan ~/Downloads   # Headless: inventory and classify a folder
+  %(prog)s plan ~/Downloads --file plan.json   # Headless: write an organize plan
+  %(prog)s apply plan.json    # Headless: apply a plan (one undoable operation)
+  %(prog)s undo [ID]          # Headless: undo the last (or a given) operation
+  %(prog)s plan ~/Downloads --json   # Headless, machine-readable output
   %(prog)s duplicates         # Find duplicate files
   %(prog)s stats              # Show statistics
+  %(prog)s validate-rules rules.yaml   # Lint a rules file (exit code 1 on errors)
+  %(prog)s export-config --file backup.json   # Export settings, rules, profiles and taxonomy
+  %(prog)s export-config --file setup.zip     # The same, zipped for sharing
+  %(prog)s import-config --file backup.json   # Preview and import a bundle
+
+Exit codes (headless commands):
+  0 ok, 1 failed, 2 plan has conflicts, 3 partial failure,
+  4 AI backend or database unavailable, 64 invalid arguments
 
 For more information, visit: https://github.com/yourproject
         """
@@ -561,10 +1183,25 @@ For more information, visit: https://github.com/yourproject
 
     parser.add_argument(
         'command',
-        choices=['dashboard', 'watch', 'scan', 'duplicates', 'stats', 'license'],
+        choices=['dashboard', 'background', 'autostart', 'watch', 'daemon', 'sidecar', 'open', 'scan', 'duplicates',
+                 'stats', 'license',
+                 'export-config', 'import-config', 'validate-rules', *HEADLESS_COMMANDS],
         help='Command to execute'
     )
 
+    parser.add_argument(
+        'target',
+        nargs='?',
+        help='Folder for scan/plan, plan file for apply, operation ID for undo, file for open/validate-rules, '
+             'on/off for autostart'
+    )
+
+    parser.add_argument(
+        'paths',
+        nargs='*',
+        help='More files or folders for open'
+    )
+
     parser.add_argument(
         '--host',
         default='127.0.0.1',
@@ -590,8 +1227,91 @@ For more information, visit: https://github.com/yourproject
         help='License key to activate (format: XXXX-XXXX-XXXX-XXXX)'
     )
 
+    parser.add_argument(
+        '--file',
+        type=str,
+        help='Bundle file for export-config / import-config, output plan file for plan'
+    )
+
+    parser.add_argument(
+        '--yes',
+        action='store_true',
+        help='Apply import-config changes or a plan without asking for confirmation'
+    )
+
+    parser.add_argument(
+        '--deep',
+        action='store_true',
+        help='Headless plan: use deep AI analysis (fails with exit code 4 if the AI backend is unavailable)'
+    )
+
+    parser.add_argument(
+        '--json',
+        action='store_true',
+        help='Headless commands: print one versioned JSON document on stdout'
+    )
+
+    # Usage errors get their own exit code; 2 means "plan has conflicts"
+    parser.error = lambda message: parser.exit(EXIT_USAGE, f"{parser.prog}: error: {message}\n")
     args = parser.parse_args()
 
+    # JSON logs with span context go to a rotating file; the level can be
+    # changed later on a running instance (aifo log-level DEBUG)
+    configure_logging(get_config().logging_settings)
+    CrashReporter(get_config()).install()
+
+    # The first start after an update runs the self test, and goes back if it fails
+    update = UpdateManager(get_config()).verify_startup()
+    if update is not None and not update['success']:
+        print(f"⚠️  {update['message']}")
+        if update['rolled_back']:
+            sys.exit(EXIT_ERROR)
+
+    if args.command == 'validate-rules':
+        sys.exit(run_validate_rules_command(args))
+
+    if args.command == 'autostart':
+        sys.exit(run_autostart_command(args))
+
+    # Config bundle commands only need the configuration, not a full organiser
+    if args.command in ('export-config', 'import-config'):
+        sys.exit(run_config_bundle_command(args))
+
+    # Headless commands run the shared engine without the full organiser
+    if args.command in HEADLESS_COMMANDS or (args.command == 'scan' and args.target):
+        if args.command in ('plan', 'apply') and not args.target:
+            print(f"❌ {args.command} requires a {'folder' if args.command == 'plan' else 'plan file'}")
+            sys.exit(EXIT_USAGE)
+        code = run_headless_command(args)
+        get_telemetry().record(f"headless.{args.command}", f"exit-{code}" if code else None)
+        sys.exit(code)
+
+    # Only one app instance runs: later launches hand their paths to it and exit
+    if args.command in SINGLE_INSTANCE_COMMANDS:
+        open_paths = ([args.target] if args.target else []) + args.paths
+        try:
+            forwarded = forward_to_running(default_address(get_config()), open_paths)
+        except Exception as e:
+            print(f"❌ The running instance did not accept the request: {e}")
+            sys.exit(EXIT_ERROR)
+        if forwarded is not None:
+            print(f"➡️  Already running; handed over {len(forwarded.get('queued', []))} file(s)")
+            return
+
+    # The sidecar's stdout carries only protocol lines: everything printed goes
+    # to stderr, and a missing license fails instead of waiting for input
+    if args.command == 'sidecar':
+        protocol_out, sys.stdout = sys.stdout, sys.stderr
+        protocol_in, sys.stdin = sys.stdin, open(os.devnull)
+        try:
+            organiser = FileOrganiser()
+        except EOFError:
+            print("❌ No valid license; activate one with --activate first")
+            sys.exit(EXIT_ERROR)
+        sys.stdin = protocol_in
+        organiser.run_sidecar(protocol_out)
+        return
+
     # Handle license activation (create instance only for activation)
     if args.activate:
         organiser = FileOrganiser()
@@ -603,11 +1323,21 @@ For more information, visit: https://github.com/yourproject
     organiser = FileOrganiser()
 
     # Handle commands
-    if args.command == 'dashboard':
-        organiser.run_dashboard(args.host, args.port)
-
-    elif args.command == 'watch':
-        organiser.start_watch_mode()
+    if args.command in SINGLE_INSTANCE_COMMANDS:
+        # RuntimeError: another instance took the control socket meanwhile
+        try:
+            if args.command in ('dashboard', 'open'):
+                organiser.run_dashboard(args.host, args.port,
+                                        open_paths if args.command == 'open' else None)
+            elif args.command == 'background':
+                organiser.run_dashboard(args.host, args.port, background=True)
+            elif args.command == 'watch':
+                organiser.start_watch_mode()
+            else:
+                organiser.run_daemon()
+        except RuntimeError as e:
+            print(f"❌ {e}")
+            sys.exit(EXIT_ERROR)
 
     elif args.command == 'scan':
         organiser.scan_existing_files()
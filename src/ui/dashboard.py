@@ -18,15 +18,19 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
-from fastapi import FastAPI, HTTPException, Request
-from fastapi.responses import HTMLResponse
+from fastapi import FastAPI, HTTPException, Query, Request
+from fastapi.exceptions import RequestValidationError
+from fastapi.responses import HTMLResponse, JSONResponse, Response, StreamingResponse
+from starlette.exceptions import HTTPException as StarletteHTTPException
 from pydantic import BaseModel
 from typing import List, Dict, Any, Optional
 from pathlib import Path
 from collections import defaultdict
 from time import time
+import queue
 import sys
 import os
+import threading
 
 # Add parent directory to path for imports
 sys.path.insert(0, str(Path(__file__).parent.parent))
@@ -35,9 +39,65 @@ from ..config import get_config
 from ..core.db_manager import DatabaseManager
 from ..core.classifier import FileClassifier
 from ..core.actions import ActionManager
+from ..core.file_names import validate_file_name
+from ..core.file_listing import (ingest_dropped_paths, list_files_paged, list_files_recursive, stream_files,
+                                 EVENT_FILES_DONE, LISTING_VERSION, LISTING_VERSIONS)
+from ..core.filetype import detect_file_type, detect_mismatched_extensions
+from ..core.ignore_rules import add_ignore_rule, list_ignore_rules, remove_ignore_rule
+from ..core.corrections import CorrectionLearner
+from ..core.rules import UserRules
+from ..core.taxonomy import TaxonomyManager
+from ..core.dedupe import Deduplicator, RESOLVE_MODES
+from ..core.fsops import FileMover, CONFLICT_MODES
+from ..core.renamer import FileRenamer, template_fields
+from ..core.trash import SystemTrash
+from ..core.extract import extract_metadata
+from ..core.preview import DEFAULT_MAX_BYTES, get_file_preview
+from ..core.thumbnails import DEFAULT_SIZE as THUMBNAIL_SIZE, ThumbnailCache
+from ..core.settings import SettingsStore
+from ..core.keychain import KeychainError, delete_secret, get_secret, keychain_available, store_secret
+from ..core.classification_cache import ClassificationCache
 from ..core.duplicates import DuplicateFinder
-from ..core.watcher import FolderWatcher
+from ..core.watcher import FolderWatcher, FolderWatchManager, EVENT_FILE_DETECTED, EVENT_FILE_ORGANIZED
+from ..core.i18n import supported_locales, normalize_locale
+from ..core.config_bundle import ConfigBundleManager
+from ..core.journal import OperationJournal
+from ..core.history import FolderHistory, parse_point_in_time
+from ..core.workspaces import WorkspaceManager
+from ..core.consolidation import ConsolidationPlanner, ConsolidationRule
+from ..core.folder_compare import FolderComparer
+from ..core.pins import PinnedFolders
+from ..core.file_collections import CollectionManager
+from ..core.faces import FaceClusterer
+from ..core.bursts import BurstDetector
+from ..core.music import MusicLibraryStrategy
+from ..core.ebooks import EbookLibraryStrategy
+from ..core.fonts import FontLibraryStrategy
+from ..core.updater import UpdateManager
+from ..core.telemetry import get_telemetry
+from ..core.plans import OrganizePlanner
+from ..core.progress_events import stream_organize, event_line, EVENT_ERROR
+from ..core.batch_classify import classify_files, EVENT_CLASSIFY_DONE, MAX_BATCH
+from ..core.jobs import JobManager, EVENT_JOB_FINISHED
+from ..core.notifications import Notifier
+from ..core.scheduler import Scheduler
+from ..core.tray import TrayIcon
+from ..core.autostart import autostart_status, set_autostart
+from ..core.usage import UsageLedger
+from ..core.archives import ArchiveManager
+from ..core.crash_reports import CrashReporter
+from ..core.diagnostics import DiagnosticsExporter
+from ..core.self_test import run_self_test
+from ..core.simulation import simulate_on_copy
+from ..core.write_access import check_write_access
+from ..core.backend_check import check_backend
+from ..core.interpreter import detect_python_environments
+from ..core.disk_usage import analyze_disk_usage
+from ..core.search_index import SearchIndex
+from ..core.tags import TagStore
+from ..utils.error_handler import AppError, ConfigurationError, FileOrganizerError
 from ..ai.ollama_client import OllamaClient
+from ..ai.providers import create_provider
 from ..license.validator import LicenseValidator
 
 
@@ -86,12 +146,339 @@ class SettingsUpdateRequest(BaseModel):
     auto_mode: Optional[bool] = None
     dry_run: Optional[bool] = None
     enable_ai: Optional[bool] = None
+    locale: Optional[str] = None
 
 
 class DeepAnalyzeRequest(BaseModel):
     file_path: str
 
 
+class ExtractOrganizeRequest(BaseModel):
+    file_path: str
+    original_action: Optional[str] = None  # 'keep' or 'trash'
+    password: Optional[str] = None
+
+
+class ArchivePasswordAnswer(BaseModel):
+    password: Optional[str] = None  # None cancels the request
+    remember: bool = False
+
+
+class CreateArchiveRequest(BaseModel):
+    file_paths: List[str]
+    archive_path: str
+    remove_sources: bool = False
+
+
+class ExtractArchiveRequest(BaseModel):
+    path: str
+    dest: str
+    selective: Optional[List[str]] = None  # Entry names; None extracts everything
+    organize: bool = False                 # Sort into category folders
+    password: Optional[str] = None
+
+
+class SnapshotRequest(BaseModel):
+    path: str
+    reason: Optional[str] = None
+
+
+class VersionRestoreRequest(BaseModel):
+    target: Optional[str] = None  # Defaults to the version's original path
+    dry_run: Optional[bool] = None
+
+
+class WorkspaceRequest(BaseModel):
+    name: str
+    roots: List[str]
+    description: str = ''
+    destinations: Optional[Dict[str, str]] = None  # Category -> folder, "*" fallback, "@root" = in place
+
+
+class WorkspaceDestinationsRequest(BaseModel):
+    destinations: Dict[str, Optional[str]]  # None removes a mapping
+
+
+class ConsolidationRequest(BaseModel):
+    rule: Optional[str] = None  # Name of a saved rule; otherwise the fields below
+    sources: List[str] = []
+    workspace: Optional[str] = None
+    keywords: List[str] = []
+    extensions: List[str] = []
+    patterns: List[str] = []
+    categories: List[str] = []
+    destination: str = ''
+
+
+class FolderMergeRequest(BaseModel):
+    a: str
+    b: str
+    actions: List[str] = ['copy_missing']  # copy_missing and/or keep_newer
+    direction: str = 'a_to_b'  # a_to_b, b_to_a or both
+    dry_run: bool = False
+
+
+class PinRequest(BaseModel):
+    path: str
+    label: Optional[str] = None
+
+
+class PinOrderRequest(BaseModel):
+    paths: List[str]
+
+
+class CollectionRequest(BaseModel):
+    name: str
+    description: str = ''
+    rule: Optional[Dict[str, Any]] = None  # {'folders', 'extensions', 'keywords', 'patterns'}
+
+
+class CollectionItemsRequest(BaseModel):
+    paths: List[str]
+
+
+class CollectionExportRequest(BaseModel):
+    target: str
+    format: str = 'json'  # json, csv, m3u or folder
+
+
+class PeopleScanRequest(BaseModel):
+    folder: str
+    rescan: bool = False
+
+
+class PersonRenameRequest(BaseModel):
+    name: str
+
+
+class PeopleMergeRequest(BaseModel):
+    keep: int
+    other: int
+
+
+class BurstResolveRequest(BaseModel):
+    groups: List[Dict[str, Any]]  # From GET /api/bursts, each optionally with 'keep'
+    action: str = 'archive'       # archive or trash
+    archive_dir: Optional[str] = None
+    dry_run: bool = False
+
+
+class LeaveAloneRequest(BaseModel):
+    path: str
+    mode: Optional[str] = 'always'  # always, allow, or null to take it off both lists
+
+
+class FontInstallRequest(BaseModel):
+    path: str
+
+
+class UpdateRequest(BaseModel):
+    action: str  # install or rollback
+    channel: Optional[str] = None
+
+
+class TelemetryRequest(BaseModel):
+    enabled: Optional[bool] = None
+    upload: Optional[bool] = None
+
+
+class PluginGrantRequest(BaseModel):
+    name: str
+    grant: bool = True
+
+
+class RenameRequest(BaseModel):
+    file_path: str
+    new_name: str
+    rules: str = 'portable'  # 'portable', 'windows', 'posix' or 'macos'
+
+
+class OrganizeStreamRequest(BaseModel):
+    folder: str
+    preview: bool = False
+    deep: bool = False
+    strategy: Optional[str] = None
+
+
+class FileStreamRequest(BaseModel):
+    path: str
+    max_depth: Optional[int] = None
+    follow_symlinks: bool = False
+    include_hidden: bool = False
+    respect_ignore: bool = True
+    detect_types: bool = False
+    batch_size: int = 500
+    version: int = LISTING_VERSION  # Entry shape (see core.file_listing)
+
+
+class DroppedPathsRequest(BaseModel):
+    paths: List[str]
+    include_hidden: bool = False
+    respect_ignore: bool = True
+    detect_types: bool = True
+    queue: bool = False  # Also classify the files and queue them for review
+    version: int = LISTING_VERSION
+
+
+class TrayStatusRequest(BaseModel):
+    text: Optional[str] = None  # None: idle
+    progress: Optional[float] = None  # 0.0 to 1.0
+
+
+class AutostartRequest(BaseModel):
+    enabled: bool
+
+
+class ClassifyBatchRequest(BaseModel):
+    paths: List[str]
+    max_concurrent: int = 4
+    deep: bool = False
+
+
+class DedupeRequest(BaseModel):
+    folder: str
+    algorithm: str = 'blake2b'
+    recursive: bool = True
+    min_size: int = 1
+
+
+class DedupeResolveRequest(BaseModel):
+    groups: List[Dict[str, Any]]
+    mode: str = 'trash'
+    destination: Optional[str] = None
+    dry_run: bool = False
+
+
+class MovePlanRequest(BaseModel):
+    moves: List[Dict[str, Any]]  # source, destination
+    on_conflict: str = 'keep_both'
+    dry_run: bool = False
+
+
+class BatchRenameRequest(BaseModel):
+    renames: List[Dict[str, Any]]  # path, and optionally name, category, date or classification
+    template: Optional[str] = None  # e.g. "{date}-{category}-{original}"
+    dry_run: bool = False
+
+
+class TrashRequest(BaseModel):
+    paths: List[str]
+
+
+class TrashRestoreRequest(BaseModel):
+    ids: List[int]
+
+
+class ThumbnailsRequest(BaseModel):
+    paths: List[str]
+    size: int = THUMBNAIL_SIZE
+
+
+class WatchRequest(BaseModel):
+    folder: str
+    rules: Optional[Dict[str, Any]] = None  # auto_organize, extensions, recursive, deep
+
+
+class PlanRequest(BaseModel):
+    folder: str
+    recursive: bool = True
+    deep: bool = False
+    strategy: Optional[str] = None
+    profile: Optional[str] = None
+
+
+class ApplyPlanRequest(BaseModel):
+    plan: Dict[str, Any]
+    approved: Optional[List[int]] = None
+
+
+class ApplyPlanItemsRequest(BaseModel):
+    accepted_ids: List[int]
+
+
+class ResolveConflictRequest(BaseModel):
+    strategy: str  # keep_both, overwrite_if_newer, skip or merge_folders
+
+
+class AppSettingsRequest(BaseModel):
+    changes: Dict[str, Any]
+
+
+class SecretRequest(BaseModel):
+    name: str
+    value: str
+
+
+class IgnoreRuleRequest(BaseModel):
+    rule: str
+    folder: Optional[str] = None  # None: the global ignore file
+
+
+class UserRuleRequest(BaseModel):
+    rule: Dict[str, Any]
+    position: Optional[int] = None
+
+
+class ProfileRequest(BaseModel):
+    profile: Dict[str, Any]  # name, description, target_root, taxonomy, rules, tier, exclusions, strategy
+
+
+class CategoryRequest(BaseModel):
+    category: Dict[str, Any]  # name, parent, destination, aliases
+
+
+class RuleTestRequest(BaseModel):
+    path: str
+    rule: Optional[Dict[str, Any]] = None  # Try an unsaved rule instead of the saved ones
+
+
+class ScheduleRequest(BaseModel):
+    folder: str
+    cron: str  # e.g. "0 18 * * 1-5" or "@daily"
+    preview: bool = False
+    deep: bool = False
+    strategy: Optional[str] = None
+
+
+class TagsRequest(BaseModel):
+    path: str
+    tags: List[str] = []
+
+
+class SearchIndexRequest(BaseModel):
+    folder: str
+    recursive: bool = True
+
+
+class SearchRequest(BaseModel):
+    query: str = ''
+    filters: Dict[str, Any] = {}  # extension, category, folder, year, modified_after, min_size, tag, ...
+    limit: Optional[int] = None
+
+
+class DiagnosticsRequest(BaseModel):
+    output: Optional[str] = None
+
+
+class ConfigImportRequest(BaseModel):
+    bundle: Dict[str, Any]
+    apply: bool = False
+    sections: Optional[List[str]] = None
+
+
+class SimulationRequest(BaseModel):
+    folder: str
+    sample_size: int = 25
+    deep: bool = False
+    seed: Optional[int] = None
+    keep: bool = False
+
+
+class WriteAccessRequest(BaseModel):
+    paths: List[str]
+    destinations: Optional[Dict[str, str]] = None
+
+
 # Initialize FastAPI app
 app = FastAPI(
     title="AI File Organiser Dashboard",
@@ -99,6 +486,35 @@ app = FastAPI(
     version="1.0.0"
 )
 
+
+# Every error reply is {"detail": message, "error": {"code", "message", "details"}} (see AppError)
+def _error_response(error: AppError, status: int, headers=None) -> JSONResponse:
+    return JSONResponse(status_code=status, content={'detail': error.message, 'error': error.to_dict()},
+                        headers=headers)
+
+
+@app.exception_handler(StarletteHTTPException)
+async def http_error(request: Request, exc: StarletteHTTPException):
+    error = AppError.from_http(exc.status_code, exc.detail if isinstance(exc.detail, str) else str(exc.detail))
+    return _error_response(error, exc.status_code, getattr(exc, 'headers', None))
+
+
+@app.exception_handler(RequestValidationError)
+async def validation_error(request: Request, exc: RequestValidationError):
+    problems = '; '.join(f"{'.'.join(str(part) for part in problem['loc'])}: {problem['msg']}"
+                         for problem in exc.errors())
+    error = AppError.from_http(422, f"Invalid request: {problems}")
+    error.details['errors'] = [{'field': [str(part) for part in problem['loc']], 'message': problem['msg']}
+                               for problem in exc.errors()]
+    return _error_response(error, 422)
+
+
+@app.exception_handler(FileOrganizerError)
+@app.exception_handler(OSError)
+async def command_error(request: Request, exc: Exception):
+    error = AppError.from_exception(exc)
+    return _error_response(error, error.http_status)
+
 # Global application state
 class AppState:
     """Application state container."""
@@ -111,156 +527,1694 @@ class AppState:
         self.duplicate_finder = None
         self.watcher = None
         self.license_validator = None
+        self.journal = None
+        self.archive_manager = None
+        self.snapshots = None
+        self.workspaces = None
+        self.consolidation = None
+        self.folder_compare = None
+        self.pins = None
+        self.collections = None
+        self.faces = None
+        self.bursts = None
+        self.music = None
+        self.ebooks = None
+        self.invoices = None
+        self.fonts = None
+        self.thumbnails = None
+        self.app_settings = SettingsStore()
+        self.telemetry = None
+        self.planner = None
+        self.crash_reports = None
+        self.folder_watches = None
+        self.search_index = None
+        self.ocr = None
+        self.leave_alone = None
+        self.dedupe = None
+        self.mover = None
+        self.renamer = None
+        self.system_trash = None
+        self.folder_history = None
+        self.diagnostics = None
+        self.config_bundles = None
+        self.tray = None
+        self.notifier = None
+        self.jobs = JobManager(listener=self.on_job_event)
+        self.scheduler = Scheduler(self.jobs)
+        self.workspace_progress: Dict[str, Dict[str, Any]] = {}
         self.pending_files: List[Dict[str, Any]] = []
 
-        self._initialize()
+        self._initialize()
+        self.scheduler.start()
+
+    def _initialize(self):
+        """Initialize all components."""
+        # Initialize the AI client (Ollama, or the provider the settings select)
+        self.ollama = create_provider(self.config)
+
+        # Initialize classifier
+        ollama_client = self.ollama if self.ollama.is_available() else None
+        self.classifier = FileClassifier(self.config, ollama_client)
+        self.classifier.user_rules = UserRules(self.config)
+        self.classifier.settings = self.app_settings
+        self.classifier.taxonomy = TaxonomyManager(self.config, self.app_settings)
+        self.classifier.corrections = CorrectionLearner(self.config, self.db)
+        self.classifier.cache = ClassificationCache(self.config, self.db)
+        self.classifier.usage = UsageLedger(self.config, self.db)
+        self.classifier.tags = TagStore(self.config, self.db)
+
+        # Initialize operation journal (undo/redo stack) and action manager
+        self.journal = OperationJournal(self.db)
+        self.action_manager = ActionManager(self.config, self.db, journal=self.journal)
+
+        # Initialize duplicate finder
+        self.duplicate_finder = DuplicateFinder(self.config, self.db)
+
+        # Initialize archive workflows
+        self.archive_manager = ArchiveManager(
+            self.config, self.db, self.classifier, self.action_manager, self.journal
+        )
+        self.workspaces = WorkspaceManager(
+            self.config, self.db, self.classifier, self.action_manager, self.duplicate_finder
+        )
+        self.consolidation = ConsolidationPlanner(
+            self.config, self.db, self.classifier, self.action_manager
+        )
+        self.folder_compare = FolderComparer(self.config, self.db, self.journal, self.action_manager.versions)
+        self.pins = PinnedFolders(self.config, self.db, self.classifier)
+        self.collections = CollectionManager(self.config, self.db)
+        self.faces = FaceClusterer(self.config, self.db)
+        self.bursts = BurstDetector(self.config, self.action_manager)
+        self.music = MusicLibraryStrategy(self.config)
+        self.ebooks = EbookLibraryStrategy(self.config)
+        self.thumbnails = ThumbnailCache(self.config)
+        self.invoices = self.classifier.invoice_extractor
+        self.fonts = FontLibraryStrategy(self.config)
+        self.telemetry = get_telemetry(self.config)
+        self.planner = OrganizePlanner(self.config, self.classifier, self.action_manager)
+        self.snapshots = self.planner.snapshots  # Applying plans snapshots large runs first
+        self.crash_reports = CrashReporter(self.config)
+
+        # Built once and shared by every request, so routes can coordinate (one leave-alone list, one OCR lookup)
+        self.ocr = self.classifier.ocr
+        self.leave_alone = self.planner.leave_alone
+        self.dedupe = Deduplicator(self.config, self.db, self.journal)
+        self.mover = FileMover(self.config, self.db, self.journal)
+        self.renamer = FileRenamer(self.config, self.db, self.journal)
+        self.system_trash = SystemTrash(self.config, self.db)
+        self.folder_history = FolderHistory(self.config, self.db)
+        self.diagnostics = DiagnosticsExporter(self.config, self.db)
+        self.config_bundles = ConfigBundleManager(self.config, settings=self.app_settings)
+
+        # Initialize license validator
+        self.license_validator = LicenseValidator(self.config, self.db)
+
+        # Folders watched on request; files not moved right away wait for review
+        self.folder_watches = FolderWatchManager(self.config, self.classifier, self.action_manager,
+                                                 listener=self.on_watch_event)
+        self.search_index = self.folder_watches.search_index = SearchIndex(self.config, self.db,
+                                                                           self.classifier.cache)
+        self.folder_watches.tag_store = self.search_index.tags = self.classifier.tags
+        self.classifier.tags.search_index = self.search_index
+        self.folder_watches.leave_alone = self.leave_alone
+
+        # Initialize watcher (but don't start yet)
+        self.watcher = FolderWatcher(
+            folders=self.config.watched_folders,
+            callback=self.on_file_detected,
+            config=self.config
+        )
+        self.watcher.leave_alone = self.leave_alone
+
+        # Tray quick actions (the icon is shown by run_dashboard)
+        self.tray = TrayIcon(self.config, {
+            'organize': lambda folder: self.jobs.start_organize(folder),
+            'pause': self.stop_watcher,
+            'resume': self.start_watcher,
+            'is_watching': lambda: bool(self.watcher and self.watcher._running)
+        })
+        self.notifier = Notifier(self.app_settings, self.tray)
+        self.planner.conflicts.notifier = self.notifier
+
+    def on_file_detected(self, file_path: str):
+        """
+        Callback when watcher detects a new file.
+
+        Args:
+            file_path (str): Path to detected file
+        """
+        # Classify the file
+        if self.classifier is None:
+            print(f"Warning: Classifier not initialized, skipping file: {file_path}")
+            return
+
+        classification = self.classifier.classify(file_path)
+
+        # Add to pending files
+        self.pending_files.append({
+            'file_path': file_path,
+            'classification': classification,
+            'detected_at': Path(file_path).stat().st_mtime
+        })
+
+    def on_watch_event(self, event: str, payload: Dict[str, Any]):
+        """Queue files found in a watched folder for review, unless they were moved already."""
+        if event == EVENT_FILE_DETECTED and not payload['auto_organize']:
+            self.pending_files.append({
+                'file_path': payload['file'],
+                'classification': payload['classification'],
+                'detected_at': time()
+            })
+        elif event == EVENT_FILE_ORGANIZED and self.notifier is not None:
+            self.notifier.file_moved(payload['file'], payload.get('new_path'))
+
+    def on_job_event(self, job_id: str, event: str, payload: Dict[str, Any]):
+        """Tell the user when an organize run (by hand or scheduled) is over."""
+        if event == EVENT_JOB_FINISHED and self.notifier is not None:
+            self.notifier.run_completed(payload)
+
+    def open_paths(self, paths: List[str]) -> Dict[str, Any]:
+        """
+        Queue files handed over by another launch (e.g. the context menu) for review.
+
+        Args:
+            paths (List[str]): Files or folders
+
+        Returns:
+            Dict: 'queued' file paths
+        """
+        from src.core.control import files_to_open
+
+        known = {item['file_path'] for item in self.pending_files}
+        queued = [f for f in files_to_open(paths) if f not in known]
+        for file_path in queued:
+            self.on_file_detected(file_path)
+        return {'queued': queued}
+
+    def start_watcher(self):
+        """Start the folder watcher."""
+        if self.watcher and not self.watcher._running:
+            self.watcher.start(background=True)
+
+    def stop_watcher(self):
+        """Stop the folder watcher."""
+        if self.watcher and self.watcher._running:
+            self.watcher.stop()
+
+
+# Create global app state
+state = AppState()
+
+
+@app.middleware("http")
+async def count_feature_use(request: Request, call_next):
+    """Count API use by route (not by URL, which may hold paths) when telemetry is on."""
+    response = await call_next(request)
+    route = request.scope.get('route')
+    if state.telemetry is not None and route is not None and request.url.path.startswith('/api/'):
+        failed = f"http-{response.status_code}" if response.status_code >= 500 else None
+        state.telemetry.record(f"api:{request.method} {route.path}".replace(' ', '_'), failed)
+    return response
+
+
+# ==================== HTML Templates ====================
+
+def get_dashboard_html() -> str:
+    """Generate dashboard HTML."""
+    template_path = Path(__file__).parent / "templates" / "dashboard.html"
+    with open(template_path, 'r', encoding='utf-8') as f:
+        return f.read()
+
+
+# ==================== API Endpoints ====================
+
+@app.get("/", response_class=HTMLResponse)
+def dashboard():
+    """Serve dashboard HTML."""
+    return get_dashboard_html()
+
+
+@app.get("/api/stats")
+def get_stats():
+    """Get statistics."""
+    return state.db.get_stats('all')
+
+
+@app.get("/api/pending-files")
+def get_pending_files():
+    """Get pending files for review."""
+    return [
+        {
+            'file_path': item['file_path'],
+            'filename': Path(item['file_path']).name,
+            'classification': item['classification']
+        }
+        for item in state.pending_files
+    ]
+
+
+@app.post("/api/files/approve")
+def approve_file(request: FileActionRequest):
+    """Approve and execute file action."""
+    if state.action_manager is None:
+        raise HTTPException(status_code=500, detail="Action manager not initialized")
+
+    # Find file in pending
+    file_item = None
+    for item in state.pending_files:
+        if item['file_path'] == request.file_path:
+            file_item = item
+            break
+
+    if not file_item:
+        raise HTTPException(status_code=404, detail="File not found in pending list")
+
+    # A custom destination overrides the suggestion, and is learned for similar files
+    classification = file_item['classification']
+    if request.action == 'custom' and request.custom_path:
+        learner = state.classifier.corrections
+        chosen = learner.destination_for(request.custom_path)
+        learner.record(file_item['file_path'], request.custom_path, suggested=classification)
+        classification = {**classification, 'suggested_path': chosen['suggested_path'], 'method': 'user'}
+        classification.pop('destination_root', None)
+        if chosen['destination_root']:
+            classification['destination_root'] = chosen['destination_root']
+
+    # Execute action
+    result = state.action_manager.execute(
+        file_path=file_item['file_path'],
+        classification=classification,
+        user_approved=True
+    )
+
+    # Remove from pending
+    state.pending_files.remove(file_item)
+
+    return result
+
+
+@app.post("/api/files/reject")
+def reject_file(request: FileActionRequest):
+    """Reject file action."""
+    # Remove from pending
+    state.pending_files = [
+        item for item in state.pending_files
+        if item['file_path'] != request.file_path
+    ]
+
+    return {'success': True, 'message': 'File rejected'}
+
+
+@app.get("/api/files/list")
+def list_folder(path: str, recursive: bool = False, max_depth: Optional[int] = None,
+                follow_symlinks: bool = False, include_hidden: bool = False, respect_ignore: bool = True,
+                detect_types: bool = True, version: int = LISTING_VERSION):
+    """List a folder, or its whole tree (relative paths, folder loops and ignored entries skipped)."""
+    try:
+        return list_files_recursive(path, max_depth if recursive else 0, follow_symlinks, include_hidden,
+                                    respect_ignore=respect_ignore, detect_types=detect_types, version=version)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except PermissionError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/files/page")
+def list_folder_page(path: str, offset: int = 0, limit: int = 100, sort_by: str = 'name', order: str = 'asc',
+                     include_hidden: bool = False, respect_ignore: bool = True, detect_types: bool = True,
+                     version: int = LISTING_VERSION):
+    """One sorted page of a folder (name, size, date or category; folders first) for a virtual list."""
+    try:
+        return list_files_paged(path, offset, limit, sort_by, order, include_hidden, respect_ignore, detect_types,
+                                state.config, version)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except PermissionError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/files/dropped")
+def dropped_paths(request: DroppedPathsRequest):
+    """Take in files and folders dropped on the window: validated, folders expanded, ignored files left out."""
+    if request.version not in LISTING_VERSIONS:
+        raise HTTPException(status_code=400, detail=f"Unknown listing version {request.version}")
+    result = ingest_dropped_paths(request.paths, request.include_hidden, request.respect_ignore,
+                                  request.detect_types, config=state.config, version=request.version)
+    if request.queue:
+        known = {item['file_path'] for item in state.pending_files}
+        result['queued'] = [item['path'] for item in result['items'] if item['path'] not in known]
+        for file_path in result['queued']:
+            state.on_file_detected(file_path)
+    return result
+
+
+@app.post("/api/files/list/stream")
+def stream_folder(request: FileStreamRequest):
+    """Walk a folder tree in parallel, streaming batches of entries (and a total estimate) as JSON lines."""
+    if not Path(request.path).expanduser().is_dir():
+        raise HTTPException(status_code=404, detail=f"Not a folder: {request.path}")
+    if request.version not in LISTING_VERSIONS:
+        raise HTTPException(status_code=400, detail=f"Unknown listing version {request.version}")
+    lines: queue.Queue = queue.Queue()
+    cancel = threading.Event()
+
+    def run():
+        try:
+            stream_files(request.path, lambda event, payload: lines.put(event_line(event, payload)),
+                         request.max_depth, request.follow_symlinks, request.include_hidden, request.respect_ignore,
+                         request.detect_types, max(1, request.batch_size), cancel=cancel, version=request.version)
+        except OSError as e:
+            lines.put(event_line(EVENT_FILES_DONE, {'root': request.path, 'total': 0, 'error': str(e)}))
+        lines.put(None)
+
+    def body():
+        try:
+            for line in iter(lines.get, None):
+                yield line + '\n'
+        finally:
+            cancel.set()  # The client went away; stop walking
+
+    threading.Thread(target=run, daemon=True).start()
+    return StreamingResponse(body(), media_type='application/x-ndjson')
+
+
+@app.get("/api/files/type")
+def file_type(path: str):
+    """What a file is by its content (magic numbers), whatever its extension says."""
+    try:
+        return detect_file_type(path)
+    except (FileNotFoundError, IsADirectoryError) as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/api/files/mismatched-extensions")
+def mismatched_extensions(folder: str, recursive: bool = True):
+    """Files whose extension lies about their content, programs in disguise first."""
+    try:
+        return detect_mismatched_extensions(folder, recursive)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/api/ocr/status")
+def ocr_status():
+    """Whether Tesseract (and pdftoppm for scanned PDFs) can be used."""
+    return state.ocr.status()
+
+
+@app.get("/api/files/ocr")
+def ocr_file(path: str):
+    """The text of a screenshot, photographed receipt or scanned PDF."""
+    engine = state.ocr
+    if not engine.available():
+        raise HTTPException(status_code=503, detail="OCR needs Tesseract (see the ocr settings)")
+    try:
+        return {'path': path, 'text': engine.ocr_file(path)}
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/api/files/metadata")
+def file_metadata(path: str):
+    """EXIF date taken and GPS, audio tags, or PDF title, author and page count of a file."""
+    meta = extract_metadata(path)
+    if meta.error and not meta.size:
+        raise HTTPException(status_code=404, detail=meta.error)
+    return meta.to_dict()
+
+
+@app.get("/api/files/preview")
+def file_preview(path: str, max_bytes: int = DEFAULT_MAX_BYTES):
+    """What a file is, for the inspector panel: text, a thumbnail, or PDF / Office structure."""
+    try:
+        return get_file_preview(path, max_bytes)
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except IsADirectoryError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/thumbnails")
+def thumbnail(request: Request, path: str, size: int = THUMBNAIL_SIZE):
+    """The image itself, for <img src>; cached on disk and revalidated with its ETag."""
+    try:
+        result = state.thumbnails.get_thumbnail(path, size, as_base64=False)
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except IsADirectoryError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    if result is None:
+        raise HTTPException(status_code=404, detail='No thumbnail for this file')
+    headers = {'ETag': f'"{result["etag"]}"', 'Cache-Control': 'private, max-age=86400'}
+    if request.headers.get('if-none-match') == headers['ETag']:
+        return Response(status_code=304, headers=headers)
+    return Response(content=result['data'], media_type=result['mime_type'], headers=headers)
+
+
+@app.post("/api/thumbnails")
+def thumbnails_batch(request: ThumbnailsRequest):
+    """Base64 thumbnails of several files in one call (null data for files without one)."""
+    return state.thumbnails.get_thumbnails(request.paths, request.size)
+
+
+@app.get("/api/thumbnails/cache")
+def thumbnail_cache_stats():
+    """How many thumbnails are cached and the space they use."""
+    return state.thumbnails.stats()
+
+
+@app.delete("/api/thumbnails/cache")
+def clear_thumbnail_cache():
+    """Remove every cached thumbnail."""
+    return state.thumbnails.clear()
+
+
+@app.get("/api/ignore-rules")
+def get_ignore_rules(folder: Optional[str] = None):
+    """The rules of a folder's .organizerignore, or of the global ignore file."""
+    try:
+        return list_ignore_rules(folder)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.post("/api/ignore-rules")
+def add_ignore(request: IgnoreRuleRequest):
+    """Add a rule (glob, "size > 4GB", "hidden", "system") to an ignore file."""
+    try:
+        return add_ignore_rule(request.rule, request.folder)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.delete("/api/ignore-rules")
+def remove_ignore(request: IgnoreRuleRequest):
+    """Remove a rule from an ignore file."""
+    try:
+        return remove_ignore_rule(request.rule, request.folder)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/api/disk-usage")
+def disk_usage(folder: str, depth: int = 3):
+    """What takes up space in a folder, as a tree for the treemap, with totals per category and extension."""
+    try:
+        return analyze_disk_usage(folder, depth, state.config)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/api/tags")
+def list_tags():
+    """Every tag in use, with how many files have it."""
+    if state.classifier is None or state.classifier.tags is None:
+        raise HTTPException(status_code=503, detail="Tags not initialized")
+    return {"tags": state.classifier.tags.list_tags()}
+
+
+@app.get("/api/tags/file")
+def get_file_tags(path: str):
+    """A file's tags."""
+    if state.classifier is None or state.classifier.tags is None:
+        raise HTTPException(status_code=503, detail="Tags not initialized")
+    return state.classifier.tags.get_tags(path)
+
+
+@app.put("/api/tags/file")
+def set_file_tags(request: TagsRequest):
+    """Set a file's tags, replacing the ones it had."""
+    if state.classifier is None or state.classifier.tags is None:
+        raise HTTPException(status_code=503, detail="Tags not initialized")
+    result = state.classifier.tags.set_tags(request.path, request.tags)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.get("/api/tags/{tag}/files")
+def files_with_tag(tag: str, limit: int = 1000):
+    """Files with a tag."""
+    if state.classifier is None or state.classifier.tags is None:
+        raise HTTPException(status_code=503, detail="Tags not initialized")
+    return state.classifier.tags.find_by_tag(tag, limit)
+
+
+@app.post("/api/search/index")
+def index_for_search(request: SearchIndexRequest):
+    """Add a folder to the search index, or bring it up to date."""
+    if state.search_index is None:
+        raise HTTPException(status_code=503, detail="Search index not initialized")
+    try:
+        return state.search_index.index_folder(request.folder, request.recursive)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/api/search/query")
+def search_index_query(q: str = '', limit: Optional[int] = None):
+    """Find indexed files by a plain query, e.g. "tax pdf 2022"."""
+    if state.search_index is None:
+        raise HTTPException(status_code=503, detail="Search index not initialized")
+    return state.search_index.search(q, None, limit)
+
+
+@app.post("/api/search/query")
+def search_index_filtered(request: SearchRequest):
+    """Find indexed files by a query and filters."""
+    if state.search_index is None:
+        raise HTTPException(status_code=503, detail="Search index not initialized")
+    try:
+        return state.search_index.search(request.query, request.filters, request.limit)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/files/validate-name")
+def validate_name(name: str, rules: str = 'portable', parent: Optional[str] = None):
+    """Check a file name against platform naming rules, with a suggested fix."""
+    try:
+        return validate_file_name(name, rules, parent)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/files/rename")
+def rename_file(request: RenameRequest):
+    """Rename a file in place after validating the new name (undoable)."""
+    if state.action_manager is None:
+        raise HTTPException(status_code=500, detail="Action manager not initialized")
+    result = state.action_manager.rename_file(request.file_path, request.new_name, request.rules)
+    if not result['success'] and not result.get('problems'):
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/history")
+def get_history():
+    """Get recent file operation history."""
+    return state.db.get_recent_logs(50)
+
+
+@app.get("/api/history/folder")
+def get_folder_history(path: str, at: str, include_files: bool = True):
+    """Reconstruct a folder as of a past point in time, with a diff against now."""
+    try:
+        when = parse_point_in_time(at)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+    result = state.folder_history.folder_as_of(path, when, include_files)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/history/stats")
+def get_history_stats(since: Optional[str] = None, until: Optional[str] = None, category: Optional[str] = None,
+                      model: Optional[str] = None, operation: Optional[List[str]] = Query(None),
+                      folder: Optional[str] = None):
+    """Files per day, category, model and operation, and the AI cost, for the history view."""
+    if not state.journal:
+        raise HTTPException(status_code=500, detail="Journal not initialized")
+    try:
+        return state.journal.query_history({'since': since, 'until': until, 'category': category, 'model': model,
+                                            'operation': operation, 'folder': folder})
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/history/timings")
+def get_history_timings(limit: int = 20):
+    """Per-stage timings (scan, extract, classify, ai, hash, move) of recent runs."""
+    if not state.journal:
+        raise HTTPException(status_code=500, detail="Journal not initialized")
+    return state.journal.timing_report(limit)
+
+
+@app.get("/api/operations")
+def get_operations(limit: int = 50):
+    """Get recent grouped operations from the undo journal."""
+    return state.journal.list_operations(limit)
+
+
+@app.get("/api/operations/{operation_id}")
+def get_operation(operation_id: int):
+    """Get a grouped operation with its individual items (steps)."""
+    operation = state.journal.get_operation(operation_id)
+    if not operation:
+        raise HTTPException(status_code=404, detail=f"Operation {operation_id} not found")
+    return operation
+
+
+@app.post("/api/operations/{operation_id}/items/{item_id}/undo")
+def undo_operation_item(operation_id: int, item_id: int, on_conflict: str = 'fail'):
+    """Undo one item of an operation; on_conflict='keep_both' restores next to an occupant."""
+    if on_conflict not in ('fail', 'keep_both'):
+        raise HTTPException(status_code=400, detail="on_conflict must be 'fail' or 'keep_both'")
+    result = state.journal.undo_item(operation_id, item_id, dry_run=state.action_manager.dry_run,
+                                     on_conflict=on_conflict)
+    if not result['success'] and 'not found' in result['message']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    if result.get('conflict'):
+        raise HTTPException(status_code=409, detail=result['message'])
+    return result
+
+
+@app.post("/api/operations/{operation_id}/undo")
+def undo_operation(operation_id: int):
+    """Undo every step of a grouped operation."""
+    result = state.journal.undo(operation_id, dry_run=state.action_manager.dry_run)
+    if not result['success'] and 'not found' in result['message']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.get("/api/undo-stack")
+def get_undo_stack(limit: int = 20):
+    """Get the undo and redo stacks, top first."""
+    if state.journal is None:
+        raise HTTPException(status_code=500, detail="Journal not initialized")
+    return {'undo': state.journal.undo_stack(limit), 'redo': state.journal.redo_stack(limit)}
+
+
+@app.post("/api/undo")
+def undo_last():
+    """Undo the operation on top of the undo stack."""
+    if state.action_manager is None:
+        raise HTTPException(status_code=500, detail="Action manager not initialized")
+    return state.action_manager.undo_last_action()
+
+
+@app.post("/api/redo")
+def redo_last():
+    """Redo the most recently undone operation."""
+    if state.action_manager is None:
+        raise HTTPException(status_code=500, detail="Action manager not initialized")
+    return state.action_manager.redo_last_action()
+
+
+@app.post("/api/operations/{operation_id}/redo")
+def redo_operation(operation_id: int):
+    """Redo an undone grouped operation."""
+    result = state.journal.redo(operation_id, dry_run=state.action_manager.dry_run)
+    if not result['success'] and 'not found' in result['message']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+def _workspace_progress(event: Dict[str, Any]) -> None:
+    """Keep the latest progress event per workspace root for polling."""
+    state.workspace_progress.setdefault(event['workspace'], {})[event['root']] = event
+
+
+def _require_workspace(name: str) -> None:
+    if state.workspaces is None:
+        raise HTTPException(status_code=500, detail="Workspaces not initialized")
+    if state.workspaces.get_workspace(name) is None:
+        raise HTTPException(status_code=404, detail=f"Workspace '{name}' not found")
+
+
+@app.post("/api/plans")
+def build_plan(request: PlanRequest):
+    """Build an organize plan for a folder (same engine as the headless `plan` command)."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        return state.planner.build_plan(request.folder, request.recursive, request.deep, request.strategy,
+                                        request.profile)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/plans/organization")
+def plan_organization(request: PlanRequest):
+    """Dry-run an organize as rows (source, destination, category, confidence, conflict) for an approval table."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        return state.planner.plan_organization(request.model_dump()).to_dict()
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/corrections")
+def list_corrections(limit: int = 100):
+    """Destination corrections learned from the user, newest first."""
+    if state.classifier is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    return state.classifier.corrections.list(limit)
+
+
+@app.delete("/api/corrections")
+def forget_corrections(correction_id: Optional[int] = None):
+    """Forget one learned correction, or all of them."""
+    if state.classifier is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    result = state.classifier.corrections.forget(correction_id)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.post("/api/cleanup/plan")
+def build_cleanup_plan(request: PlanRequest):
+    """List a folder's junk as a cleanup plan; apply it with /api/plans/apply."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        return state.planner.build_cleanup_plan(request.folder, request.recursive)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.post("/api/plans/apply")
+def apply_plan(request: ApplyPlanRequest):
+    """Apply a plan as one undoable operation (only the 'approved' item ids, when given)."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    if not isinstance(request.plan.get('items'), list):
+        raise HTTPException(status_code=400, detail="Plan has no items list")
+    try:
+        return state.planner.apply_plan(request.plan, user_approved=True, approved=request.approved)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/plans/{plan_id}/apply")
+def apply_plan_items(plan_id: int, request: ApplyPlanItemsRequest):
+    """Apply the accepted moves of a plan from /api/plans/organization; the rest are logged as skipped."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        return state.planner.apply_plan_items(plan_id, request.accepted_ids)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/conflicts")
+def list_conflicts(limit: int = 100):
+    """Moves set aside because their destination is taken, waiting for a resolution."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    return {'conflicts': [conflict.to_dict() for conflict in state.planner.conflicts.list_conflicts(limit)]}
+
+
+@app.post("/api/conflicts/{conflict_id}/resolve")
+def resolve_conflict(conflict_id: int, request: ResolveConflictRequest):
+    """Keep both, overwrite if newer, skip, or merge two folders."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        return state.planner.conflicts.resolve_conflict(conflict_id, request.strategy)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/organize/stream")
+def organize_stream(request: OrganizeStreamRequest):
+    """Organize a folder in a child process, streaming its progress events as JSON lines."""
+    if not Path(request.folder).expanduser().is_dir():
+        raise HTTPException(status_code=404, detail=f"Not a folder: {request.folder}")
+    lines: queue.Queue = queue.Queue()
+
+    def run():
+        result = stream_organize(request.folder, lambda event, payload: lines.put(event_line(event, payload)),
+                                 request.preview, request.deep, request.strategy)
+        if result['summary'] is None:
+            lines.put(event_line(EVENT_ERROR, {'file': None, 'message': result['message']}))
+        lines.put(None)
+
+    threading.Thread(target=run, daemon=True).start()
+    return StreamingResponse((line + '\n' for line in iter(lines.get, None)), media_type='application/x-ndjson')
+
+
+@app.post("/api/classify/batch")
+def classify_batch(request: ClassifyBatchRequest):
+    """Classify many files a few at a time, streaming a progress event per file as JSON lines."""
+    if state.classifier is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    if len(request.paths) > MAX_BATCH:
+        raise HTTPException(status_code=400, detail=f"At most {MAX_BATCH} files per batch")
+    lines: queue.Queue = queue.Queue()
+
+    def run():
+        outcomes = classify_files(lambda path: state.classifier.classify(path, deep_analysis=request.deep),
+                                  request.paths, request.max_concurrent,
+                                  lambda event, payload: lines.put(event_line(event, payload)))
+        lines.put(event_line(EVENT_CLASSIFY_DONE, {'results': outcomes}))
+        lines.put(None)
+
+    threading.Thread(target=run, daemon=True).start()
+    return StreamingResponse((line + '\n' for line in iter(lines.get, None)), media_type='application/x-ndjson')
+
+
+@app.get("/api/cache/stats")
+def cache_stats():
+    """How many AI classifications are cached and how often they were reused."""
+    if state.classifier is None or state.classifier.cache is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    return state.classifier.cache.stats()
+
+
+@app.post("/api/cache/clear")
+def clear_cache():
+    """Forget every cached classification."""
+    if state.classifier is None or state.classifier.cache is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    return state.classifier.cache.clear()
+
+
+@app.get("/api/usage")
+def usage_summary(period: str = 'month'):
+    """AI tokens and cost for a period, per model and per run, with the monthly budget."""
+    if state.classifier is None or state.classifier.usage is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    try:
+        return state.classifier.usage.get_usage_summary(period)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/ai/local")
+def local_ai_status():
+    """Whether a local Ollama server is running, and the models installed on it."""
+    client = OllamaClient(state.config.ollama_base_url, state.config.ollama_model,
+                          state.config.get('ollama_timeout', 30))
+    status = client.detect()
+    status['local_models'] = client.list_local_models() if status['running'] else []
+    return status
+
+
+@app.post("/api/jobs")
+def start_job(request: OrganizeStreamRequest):
+    """Start organizing a folder as a background job that can be cancelled."""
+    if not Path(request.folder).expanduser().is_dir():
+        raise HTTPException(status_code=404, detail=f"Not a folder: {request.folder}")
+    return state.jobs.start_organize(request.folder, request.preview, request.deep, request.strategy)
+
+
+@app.get("/api/jobs")
+def list_jobs(include_finished: bool = True):
+    """Running (and recently finished) jobs, newest first."""
+    return state.jobs.list_jobs(include_finished)
+
+
+@app.get("/api/jobs/{job_id}")
+def get_job(job_id: str):
+    """One job's state and progress."""
+    job = state.jobs.get_job(job_id)
+    if job is None:
+        raise HTTPException(status_code=404, detail=f"No job {job_id}")
+    return job
+
+
+@app.post("/api/jobs/{job_id}/cancel")
+def cancel_job(job_id: str):
+    """Stop a running job after the file it is moving."""
+    result = state.jobs.cancel_job(job_id)
+    if result['job'] is None:
+        raise HTTPException(status_code=404, detail=result['message'])
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.post("/api/schedules")
+def create_schedule(request: ScheduleRequest):
+    """Organize a folder on a cron schedule while the app is running."""
+    try:
+        return state.scheduler.schedule_organize(request.folder, request.cron, {
+            'preview': request.preview, 'deep': request.deep, 'strategy': request.strategy})
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/schedules")
+def list_schedules():
+    """Schedules, soonest first, with their last run."""
+    return state.scheduler.list_schedules()
+
+
+@app.get("/api/schedules/events")
+def schedule_events(since: int = 0):
+    """Runs started and finished since an event number."""
+    return state.scheduler.events(since)
+
+
+@app.post("/api/schedules/{schedule_id}/enabled")
+def enable_schedule(schedule_id: str, enabled: bool = True):
+    """Pause or resume a schedule."""
+    result = state.scheduler.set_enabled(schedule_id, enabled)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.delete("/api/schedules/{schedule_id}")
+def delete_schedule(schedule_id: str):
+    """Delete a schedule."""
+    result = state.scheduler.remove_schedule(schedule_id)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.get("/api/workspaces")
+def list_workspaces():
+    """List workspaces and whether their roots are available."""
+    if state.workspaces is None:
+        raise HTTPException(status_code=500, detail="Workspaces not initialized")
+    return state.workspaces.list_workspaces()
+
+
+@app.post("/api/workspaces")
+def save_workspace(request: WorkspaceRequest):
+    """Create or replace a workspace."""
+    if state.workspaces is None:
+        raise HTTPException(status_code=500, detail="Workspaces not initialized")
+    result = state.workspaces.save_workspace(request.name, request.roots, request.description,
+                                             request.destinations)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.delete("/api/workspaces/{name}")
+def delete_workspace(name: str):
+    """Remove a workspace definition."""
+    _require_workspace(name)
+    state.workspaces.delete_workspace(name)
+    return {'success': True, 'message': f"Workspace '{name}' removed"}
+
+
+@app.post("/api/workspaces/{name}/destinations")
+def set_workspace_destinations(name: str, request: WorkspaceDestinationsRequest):
+    """Change where each category of a workspace is organized to."""
+    _require_workspace(name)
+    result = state.workspaces.set_destinations(name, request.destinations)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/workspaces/{name}/progress")
+def get_workspace_progress(name: str):
+    """Latest progress event of each root of a running workspace operation."""
+    return state.workspace_progress.get(name, {})
+
+
+@app.post("/api/workspaces/{name}/scan")
+def scan_workspace(name: str):
+    """Inventory all roots of a workspace."""
+    _require_workspace(name)
+    state.workspace_progress.pop(name, None)
+    return state.workspaces.scan(name, _workspace_progress)
+
+
+@app.post("/api/workspaces/{name}/duplicates")
+def workspace_duplicates(name: str):
+    """Find duplicates across all roots of a workspace."""
+    _require_workspace(name)
+    state.workspace_progress.pop(name, None)
+    groups = state.workspaces.find_duplicates(name, _workspace_progress)
+    return {'groups': groups, 'summary': state.duplicate_finder.get_duplicate_summary(groups)}
+
+
+@app.post("/api/workspaces/{name}/organize")
+def organize_workspace(name: str, apply: bool = False):
+    """Plan (and with apply=true, execute) organizing every root as one operation."""
+    _require_workspace(name)
+    state.workspace_progress.pop(name, None)
+    plan = state.workspaces.plan_organize(name, _workspace_progress)
+    if not apply:
+        return plan
+    return state.workspaces.apply_plan(plan, user_approved=True, progress=_workspace_progress)
+
+
+def _consolidation_rule(request: ConsolidationRequest) -> ConsolidationRule:
+    if state.consolidation is None:
+        raise HTTPException(status_code=500, detail="Consolidation not initialized")
+    if request.rule:
+        rule = state.consolidation.get_rule(request.rule)
+        if rule is None:
+            raise HTTPException(status_code=404, detail=f"Consolidation rule '{request.rule}' not found")
+        return rule
+    return ConsolidationRule.from_dict({
+        'name': 'ad hoc',
+        'sources': request.sources,
+        'workspace': request.workspace,
+        'keywords': request.keywords,
+        'extensions': request.extensions,
+        'patterns': request.patterns,
+        'categories': request.categories,
+        'destination': request.destination
+    })
+
+
+@app.get("/api/consolidate/rules")
+def list_consolidation_rules():
+    """List saved consolidation rules."""
+    if state.consolidation is None:
+        raise HTTPException(status_code=500, detail="Consolidation not initialized")
+    return [vars(rule) for rule in state.consolidation.saved_rules()]
+
+
+@app.post("/api/consolidate")
+def consolidate(request: ConsolidationRequest, apply: bool = False):
+    """Plan (and with apply=true, execute as one undoable operation) a consolidation run."""
+    rule = _consolidation_rule(request)
+    try:
+        plan = state.consolidation.plan(rule)
+    except KeyError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    if not plan['success']:
+        raise HTTPException(status_code=400, detail=plan['message'])
+    if not apply:
+        return plan
+    return state.consolidation.apply(plan, user_approved=True)
+
+
+def _require_pins() -> PinnedFolders:
+    if state.pins is None:
+        raise HTTPException(status_code=500, detail="Pinned folders not initialized")
+    return state.pins
+
+
+@app.get("/api/pins")
+def list_pins(refresh: bool = True):
+    """Pinned folders with quick stats (stale stats are recomputed)."""
+    return _require_pins().list(refresh=refresh)
+
+
+@app.post("/api/pins")
+def pin_folder(request: PinRequest):
+    """Pin a folder to the sidebar."""
+    result = _require_pins().pin(request.path, request.label)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.delete("/api/pins")
+def unpin_folder(path: str):
+    """Remove a pin."""
+    if not _require_pins().unpin(path):
+        raise HTTPException(status_code=404, detail=f"Not pinned: {path}")
+    return {'success': True, 'message': f'Unpinned {path}'}
+
+
+@app.post("/api/pins/order")
+def reorder_pins(request: PinOrderRequest):
+    """Set the sidebar order of pins."""
+    return _require_pins().reorder(request.paths)
+
+
+@app.post("/api/pins/refresh")
+def refresh_pins(path: Optional[str] = None):
+    """Recompute quick stats of one or all pins."""
+    pins = _require_pins()
+    pins.refresh(path)
+    return pins.list(refresh=False)
+
+
+def _require_collection(name: str) -> CollectionManager:
+    if state.collections is None:
+        raise HTTPException(status_code=500, detail="Collections not initialized")
+    if state.collections.get(name) is None:
+        raise HTTPException(status_code=404, detail=f"Collection '{name}' not found")
+    return state.collections
+
+
+@app.get("/api/collections")
+def list_collections():
+    """List virtual collections."""
+    if state.collections is None:
+        raise HTTPException(status_code=500, detail="Collections not initialized")
+    return state.collections.list()
+
+
+@app.post("/api/collections")
+def create_collection(request: CollectionRequest):
+    """Create a collection (filled right away if it has a rule)."""
+    if state.collections is None:
+        raise HTTPException(status_code=500, detail="Collections not initialized")
+    result = state.collections.create(request.name, request.description, request.rule)
+    if not result['success']:
+        status = 409 if 'already exists' in result['message'] else 400
+        raise HTTPException(status_code=status, detail=result['message'])
+    return result
+
+
+@app.delete("/api/collections/{name}")
+def delete_collection(name: str):
+    """Delete a collection; files stay where they are."""
+    _require_collection(name).delete(name)
+    return {'success': True, 'message': f"Collection '{name}' deleted"}
+
+
+@app.get("/api/collections/{name}/items")
+def collection_items(name: str):
+    """Browse a collection with each file's current location."""
+    return _require_collection(name).items(name)
+
+
+@app.post("/api/collections/{name}/items")
+def add_collection_items(name: str, request: CollectionItemsRequest):
+    """Add files or folders to a collection by reference."""
+    return _require_collection(name).add(name, request.paths)
+
+
+@app.delete("/api/collections/{name}/items")
+def remove_collection_item(name: str, item: str):
+    """Remove a file (by path or content hash) from a collection."""
+    if not _require_collection(name).remove(name, item):
+        raise HTTPException(status_code=404, detail=f"Not in collection: {item}")
+    return {'success': True, 'message': f"Removed from '{name}'"}
+
+
+@app.post("/api/collections/{name}/populate")
+def populate_collection(name: str):
+    """Re-run a collection's rule to pick up new matching files."""
+    added = _require_collection(name).populate(name)
+    return {'success': True, 'message': f"Added {added} file(s)", 'added': added}
+
+
+@app.post("/api/collections/{name}/export")
+def export_collection(name: str, request: CollectionExportRequest):
+    """Export a collection as a list or a folder of copies."""
+    result = _require_collection(name).export(name, request.target, request.format)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+def _require_faces() -> FaceClusterer:
+    if state.faces is None:
+        raise HTTPException(status_code=500, detail="Face clustering not initialized")
+    return state.faces
+
+
+@app.get("/api/people")
+def list_people():
+    """List people found in photos, with whether face clustering can run."""
+    faces = _require_faces()
+    return {'available': faces.unavailable_reason() is None,
+            'message': faces.unavailable_reason() or '', 'people': faces.people()}
+
+
+@app.post("/api/people/scan")
+def scan_people(request: PeopleScanRequest):
+    """Find faces in a folder's photos, cluster them and refresh the people collections."""
+    faces = _require_faces()
+    result = faces.scan(request.folder, rescan=request.rescan)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    result['collections'] = faces.sync_collections()['collections']
+    return result
+
+
+@app.get("/api/people/{person_id}/photos")
+def person_photos(person_id: int):
+    """Photos a person appears in."""
+    return {'person_id': person_id, 'photos': _require_faces().photos_of(person_id)}
+
+
+@app.post("/api/people/{person_id}/rename")
+def rename_person(person_id: int, request: PersonRenameRequest):
+    """Name a person; their "People: NAME" collection follows."""
+    faces = _require_faces()
+    result = faces.rename_person(person_id, request.name)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    faces.sync_collections()
+    return result
+
+
+@app.post("/api/people/merge")
+def merge_people(request: PeopleMergeRequest):
+    """Merge two people who are the same person."""
+    faces = _require_faces()
+    result = faces.merge_people(request.keep, request.other)
+    if not result['success']:
+        status = 404 if 'not found' in result['message'] else 400
+        raise HTTPException(status_code=status, detail=result['message'])
+    faces.sync_collections()
+    return result
+
+
+@app.get("/api/bursts")
+def find_bursts(folder: str):
+    """Group bursts and near-identical photos in a folder, with the suggested best of each."""
+    if state.bursts is None:
+        raise HTTPException(status_code=500, detail="Burst detection not initialized")
+    result = state.bursts.find_groups(folder)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.post("/api/bursts/resolve")
+def resolve_bursts(request: BurstResolveRequest):
+    """Keep one photo per group and archive or trash the rest as one undoable operation."""
+    if state.bursts is None:
+        raise HTTPException(status_code=500, detail="Burst detection not initialized")
+    result = state.bursts.resolve(request.groups, request.action, request.archive_dir, request.dry_run)
+    if not result['success'] and not result.get('failed'):
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/music/fixups")
+def music_fixups(folder: str, recursive: bool = True):
+    """Preview missing or inconsistent song tags in a folder, with suggested values."""
+    if state.music is None:
+        raise HTTPException(status_code=500, detail="Music library not initialized")
+    result = state.music.tag_fixups(folder, recursive)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/ebooks/editions")
+def ebook_editions(folder: str, recursive: bool = True):
+    """Books present more than once in a folder: copies, other formats or other editions."""
+    if state.ebooks is None:
+        raise HTTPException(status_code=500, detail="Ebook library not initialized")
+    result = state.ebooks.find_editions(folder, recursive)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/invoices")
+def invoice_fields(folder: str, recursive: bool = True):
+    """Vendor, date and total of the invoices and receipts in a folder."""
+    if state.invoices is None:
+        raise HTTPException(status_code=500, detail="Invoice extractor not initialized")
+    result = state.invoices.scan(folder, recursive)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/fonts/duplicates")
+def font_duplicates(folder: str, recursive: bool = True):
+    """Fonts present more than once in a folder, as copies or as different versions."""
+    if state.fonts is None:
+        raise HTTPException(status_code=500, detail="Font library not initialized")
+    result = state.fonts.find_duplicates(folder, recursive)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.post("/api/fonts/install")
+def install_font(request: FontInstallRequest):
+    """Install a font for the current user."""
+    if state.fonts is None:
+        raise HTTPException(status_code=500, detail="Font library not initialized")
+    result = state.fonts.install(request.path)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.get("/api/leave-alone")
+def leave_alone_folders(folder: Optional[str] = None):
+    """Folders organizing never looks inside (detected below a folder), and the user's lists."""
+    detector = state.leave_alone
+    if folder is None:
+        return {'success': True, 'folders': [], 'always': [str(p) for p in detector.always],
+                'allow': [str(p) for p in detector.allow]}
+    result = detector.find(folder)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.post("/api/leave-alone")
+def set_leave_alone(request: LeaveAloneRequest):
+    """Put a folder on the always-leave-alone or organize-anyway list, or take it off."""
+    result = state.leave_alone.set_override(request.path, request.mode)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
 
-    def _initialize(self):
-        """Initialize all components."""
-        # Initialize Ollama client
-        self.ollama = OllamaClient(
-            base_url=self.config.ollama_base_url,
-            model=self.config.ollama_model,
-            timeout=self.config.get('ollama_timeout', 30)
-        )
+@app.get("/api/update")
+def update_status(check: bool = False):
+    """The running version and update state; with check=true, whether an update is offered."""
+    manager = UpdateManager(state.config)
+    result = manager.status()
+    if check:
+        checked = manager.check()
+        checked.pop('manifest', None)
+        if not checked['success']:
+            raise HTTPException(status_code=400, detail=checked['message'])
+        result.update(checked)
+    return result
 
-        # Initialize classifier
-        ollama_client = self.ollama if self.ollama.is_available() else None
-        self.classifier = FileClassifier(self.config, ollama_client)
 
-        # Initialize action manager
-        self.action_manager = ActionManager(self.config, self.db)
+@app.post("/api/update")
+def run_update(request: UpdateRequest):
+    """Install the offered update (optionally switching channel first), or roll back the last one."""
+    manager = UpdateManager(state.config)
+    if request.channel:
+        switched = manager.set_channel(request.channel)
+        if not switched['success']:
+            raise HTTPException(status_code=400, detail=switched['message'])
+    if request.action == 'rollback':
+        result = manager.rollback()
+    elif request.action == 'install':
+        result = manager.download()
+        result = manager.apply() if result['success'] else result
+    else:
+        raise HTTPException(status_code=400, detail=f"Unknown action {request.action!r} (use install or rollback)")
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
 
-        # Initialize duplicate finder
-        self.duplicate_finder = DuplicateFinder(self.config, self.db)
 
-        # Initialize license validator
-        self.license_validator = LicenseValidator(self.config, self.db)
+@app.get("/api/telemetry")
+def telemetry_summary(days: int = 30):
+    """Locally collected usage counts, and exactly what would be uploaded."""
+    if state.telemetry is None:
+        raise HTTPException(status_code=500, detail="Telemetry not initialized")
+    return {**state.telemetry.summary(days), 'preview': state.telemetry.preview()}
 
-        # Initialize watcher (but don't start yet)
-        self.watcher = FolderWatcher(
-            folders=self.config.watched_folders,
-            callback=self.on_file_detected,
-            config=self.config
-        )
 
-    def on_file_detected(self, file_path: str):
-        """
-        Callback when watcher detects a new file.
+@app.post("/api/telemetry")
+def set_telemetry(request: TelemetryRequest):
+    """Turn collecting or uploading usage statistics on or off."""
+    if state.telemetry is None:
+        raise HTTPException(status_code=500, detail="Telemetry not initialized")
+    result = state.telemetry.set_options(request.enabled, request.upload)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
 
-        Args:
-            file_path (str): Path to detected file
-        """
-        # Classify the file
-        if self.classifier is None:
-            print(f"Warning: Classifier not initialized, skipping file: {file_path}")
-            return
 
-        classification = self.classifier.classify(file_path)
+@app.get("/api/plugins")
+def list_plugins():
+    """Installed plugins, their actions and requested capabilities, and whether they are granted."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    return {'plugin_dir': str(state.planner.plugins.plugin_dir), 'plugins': state.planner.plugins.discover()}
 
-        # Add to pending files
-        self.pending_files.append({
-            'file_path': file_path,
-            'classification': classification,
-            'detected_at': Path(file_path).stat().st_mtime
-        })
 
-    def start_watcher(self):
-        """Start the folder watcher."""
-        if self.watcher and not self.watcher._running:
-            self.watcher.start(background=True)
+@app.post("/api/plugins/grant")
+def grant_plugin(request: PluginGrantRequest):
+    """Grant a plugin the capabilities it asks for, or revoke them."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    result = state.planner.plugins.set_grant(request.name, request.grant)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
 
-    def stop_watcher(self):
-        """Stop the folder watcher."""
-        if self.watcher and self.watcher._running:
-            self.watcher.stop()
 
+@app.get("/api/compare")
+def compare_folders(a: str, b: str):
+    """Compare two folders by content: identical, only in A, only in B and conflicting files."""
+    if state.folder_compare is None:
+        raise HTTPException(status_code=500, detail="Folder compare not initialized")
+    result = state.folder_compare.compare_folders(a, b)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
 
-# Create global app state
-state = AppState()
 
+@app.post("/api/compare/merge")
+def merge_folders(request: FolderMergeRequest):
+    """Copy missing and/or newer files between two folders as one undoable operation."""
+    if state.folder_compare is None:
+        raise HTTPException(status_code=500, detail="Folder compare not initialized")
+    result = state.folder_compare.merge(request.a, request.b, request.actions,
+                                        request.direction, request.dry_run)
+    if not result['success'] and 'copies' not in result:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
 
-# ==================== HTML Templates ====================
 
-def get_dashboard_html() -> str:
-    """Generate dashboard HTML."""
-    template_path = Path(__file__).parent / "templates" / "dashboard.html"
-    with open(template_path, 'r', encoding='utf-8') as f:
-        return f.read()
+@app.get("/api/snapshots")
+def list_snapshots(limit: int = 50):
+    """List folder snapshots, newest first."""
+    if state.snapshots is None:
+        raise HTTPException(status_code=500, detail="Snapshots not initialized")
 
+    return {'snapshots': state.snapshots.list_snapshots(limit)}
 
-# ==================== API Endpoints ====================
 
-@app.get("/", response_class=HTMLResponse)
-def dashboard():
-    """Serve dashboard HTML."""
-    return get_dashboard_html()
+@app.post("/api/snapshots")
+def create_snapshot(request: SnapshotRequest):
+    """Record a snapshot of a folder."""
+    if state.snapshots is None:
+        raise HTTPException(status_code=500, detail="Snapshots not initialized")
 
+    result = state.snapshots.create_snapshot(request.path, request.reason or 'Manual snapshot')
+    if not result['success'] and result['message'].startswith('Not a directory'):
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
 
-@app.get("/api/stats")
-def get_stats():
-    """Get statistics."""
-    return state.db.get_stats('all')
 
+@app.post("/api/snapshots/{snapshot_id}/restore")
+def restore_snapshot(snapshot_id: int, dry_run: Optional[bool] = None):
+    """Move files back to the layout recorded in a snapshot."""
+    if state.snapshots is None:
+        raise HTTPException(status_code=500, detail="Snapshots not initialized")
 
-@app.get("/api/pending-files")
-def get_pending_files():
-    """Get pending files for review."""
-    return [
-        {
-            'file_path': item['file_path'],
-            'filename': Path(item['file_path']).name,
-            'classification': item['classification']
-        }
-        for item in state.pending_files
-    ]
+    if dry_run is None:
+        dry_run = state.action_manager.dry_run
+    result = state.snapshots.restore_snapshot(snapshot_id, dry_run=dry_run)
+    if not result['success'] and 'not found' in result['message']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
 
 
-@app.post("/api/files/approve")
-def approve_file(request: FileActionRequest):
-    """Approve and execute file action."""
+@app.get("/api/versions")
+def list_versions(path: Optional[str] = None, limit: int = 100):
+    """List kept versions of replaced files, optionally for one path."""
     if state.action_manager is None:
         raise HTTPException(status_code=500, detail="Action manager not initialized")
+    return state.action_manager.versions.list_versions(path, limit)
 
-    # Find file in pending
-    file_item = None
-    for item in state.pending_files:
-        if item['file_path'] == request.file_path:
-            file_item = item
-            break
 
-    if not file_item:
-        raise HTTPException(status_code=404, detail="File not found in pending list")
+@app.post("/api/versions/{version_id}/restore")
+def restore_version(version_id: int, request: VersionRestoreRequest):
+    """Restore a kept version; the file it replaces is kept as a version too."""
+    if state.action_manager is None:
+        raise HTTPException(status_code=500, detail="Action manager not initialized")
 
-    # Execute action
-    result = state.action_manager.execute(
-        file_path=file_item['file_path'],
-        classification=file_item['classification'],
-        user_approved=True
+    dry_run = state.action_manager.dry_run if request.dry_run is None else request.dry_run
+    result = state.action_manager.versions.restore_version(version_id, request.target, dry_run=dry_run)
+    if not result['success'] and 'not found' in result['message']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.get("/api/crash-reports")
+def list_crash_reports():
+    """List crash reports saved on this machine (recorded only when enabled)."""
+    if state.crash_reports is None:
+        raise HTTPException(status_code=500, detail="Crash reports not initialized")
+    return {'enabled': state.crash_reports.enabled, 'reports': state.crash_reports.list_reports()}
+
+
+@app.get("/api/crash-reports/{report_id}")
+def get_crash_report(report_id: str):
+    """A crash report exactly as it would be sent."""
+    if state.crash_reports is None:
+        raise HTTPException(status_code=500, detail="Crash reports not initialized")
+    report = state.crash_reports.get_report(report_id)
+    if report is None:
+        raise HTTPException(status_code=404, detail=f"Crash report not found: {report_id}")
+    return report
+
+
+@app.post("/api/crash-reports/{report_id}/send")
+def send_crash_report(report_id: str):
+    """Send a crash report to the developers (the one-click "send report")."""
+    if state.crash_reports is None:
+        raise HTTPException(status_code=500, detail="Crash reports not initialized")
+    result = state.crash_reports.send_report(report_id)
+    if not result['success'] and 'not found' in result['message']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.delete("/api/crash-reports/{report_id}")
+def delete_crash_report(report_id: str):
+    """Delete a saved crash report."""
+    if state.crash_reports is None:
+        raise HTTPException(status_code=500, detail="Crash reports not initialized")
+    if not state.crash_reports.delete_report(report_id):
+        raise HTTPException(status_code=404, detail=f"Crash report not found: {report_id}")
+    return {'success': True, 'message': f"Deleted crash report {report_id}"}
+
+
+@app.get("/api/logs")
+def tail_logs(after: int = 0, level: str = 'INFO', module: Optional[str] = None,
+              limit: int = 200, wait: float = 0):
+    """
+    Live log stream for the console view: records after the `after` cursor,
+    filtered by level and module. With `wait`, the request is held open
+    (up to 20 s) until a record arrives, so the console updates immediately.
+    """
+    from src.utils.structured_logging import log_stream
+    return log_stream().read(after, level, module, min(max(limit, 1), 1000), min(max(wait, 0), 20))
+
+
+@app.post("/api/diagnostics/export")
+def export_diagnostics(request: DiagnosticsRequest):
+    """Write a diagnostics zip (logs, environment, redacted settings, recent jobs) for a bug report."""
+    result = state.diagnostics.export(request.output)
+    if not result['success']:
+        raise HTTPException(status_code=500, detail=result['message'])
+    return result
+
+
+@app.get("/api/backend/status")
+def backend_status(expected_version: Optional[str] = None):
+    """Whether Python, the scripts, packages and AI backend are set up, and what to fix if not."""
+    return check_backend(state.config, expected_version)
+
+
+@app.get("/api/runtime/pythons")
+def python_environments():
+    """Interpreters the engine could run child processes with, and the one it picks."""
+    return detect_python_environments(state.config)
+
+
+@app.post("/api/self-test")
+def self_test():
+    """Run scan, classify, plan, apply and undo in a temporary folder; pass/fail per subsystem."""
+    return run_self_test(state.config)
+
+
+@app.post("/api/simulate")
+def simulate(request: SimulationRequest):
+    """Organize a sandboxed copy of a random sample of a folder and report where the files would go."""
+    try:
+        return simulate_on_copy(request.folder, request.sample_size, state.config, state.classifier,
+                                request.deep, request.seed, request.keep)
+    except NotADirectoryError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/access-check")
+def access_check(request: WriteAccessRequest):
+    """Whether each path can be moved (and to its destination), before a run finds out."""
+    return check_write_access(request.paths, request.destinations)
+
+
+@app.post("/api/archives/extract-organize")
+def extract_organize_archive(request: ExtractOrganizeRequest):
+    """Extract an archive to staging, organize its contents and keep or trash it."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    result = state.archive_manager.extract_and_organize(
+        request.file_path,
+        original_action=request.original_action,
+        password=request.password
     )
+    if not result['success'] and result['message'] == 'Archive not found':
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
 
-    # Remove from pending
-    state.pending_files.remove(file_item)
 
+@app.get("/api/archives/password-requests")
+def get_archive_password_requests():
+    """List encrypted archives waiting for a password."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    return {'requests': state.archive_manager.passwords.pending()}
+
+
+@app.post("/api/archives/password-requests/{request_id}")
+def answer_archive_password_request(request_id: str, answer: ArchivePasswordAnswer):
+    """Answer (or cancel) a pending archive password request."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    if not state.archive_manager.passwords.answer(request_id, answer.password, answer.remember):
+        raise HTTPException(status_code=404, detail="Password request not found or already answered")
+    return {'success': True}
+
+
+@app.get("/api/archives/capabilities")
+def get_archive_capabilities():
+    """Report archive tools found on this machine and supported formats."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    return state.archive_manager.capabilities()
+
+
+@app.get("/api/archives/inspect")
+def inspect_archive(path: str):
+    """List archive contents (including nested archives) within zip-bomb limits."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    result = state.archive_manager.inspect(path)
+    if not result['success'] and result['message'] == 'Archive not found':
+        raise HTTPException(status_code=404, detail=result['message'])
     return result
 
 
-@app.post("/api/files/reject")
-def reject_file(request: FileActionRequest):
-    """Reject file action."""
-    # Remove from pending
-    state.pending_files = [
-        item for item in state.pending_files
-        if item['file_path'] != request.file_path
-    ]
+@app.get("/api/archives/contents")
+def list_archive_contents(path: str):
+    """List an archive's files with the category each would be filed under, without extracting."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
 
-    return {'success': True, 'message': 'File rejected'}
+    result = state.archive_manager.list_contents(path)
+    if not result['success'] and result['message'] == 'Archive not found':
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
 
 
-@app.get("/api/history")
-def get_history():
-    """Get recent file operation history."""
-    return state.db.get_recent_logs(50)
+@app.post("/api/archives/extract")
+def extract_archive(request: ExtractArchiveRequest):
+    """Extract an archive, or chosen entries of it, as stored or into category folders (undoable)."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    result = state.archive_manager.extract_archive(request.path, request.dest, request.selective,
+                                                   request.organize, request.password)
+    if not result['success'] and result['message'] == 'Archive not found':
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.post("/api/archives/create")
+def create_archive(request: CreateArchiveRequest):
+    """Bundle files into a verified archive with a manifest."""
+    if state.archive_manager is None:
+        raise HTTPException(status_code=500, detail="Archive manager not initialized")
+
+    return state.archive_manager.create_archive(
+        request.file_paths,
+        request.archive_path,
+        remove_sources=request.remove_sources
+    )
 
 
 @app.get("/api/search")
@@ -306,6 +2260,68 @@ def scan_duplicates():
     }
 
 
+@app.post("/api/duplicates/find")
+def find_duplicates(request: DedupeRequest):
+    """Find duplicate groups in one folder (size first, then hashes), with keep/delete suggestions."""
+    try:
+        result = state.dedupe.find_duplicates(
+            request.folder, request.algorithm, request.recursive, request.min_size)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.post("/api/duplicates/resolve")
+def resolve_duplicates(request: DedupeResolveRequest):
+    """Trash, move or hard-link the copies to delete, as one undoable operation."""
+    result = state.dedupe.resolve_duplicates(
+        request.groups, request.mode, request.destination, request.dry_run)
+    if request.mode not in RESOLVE_MODES or (request.mode == 'move' and not request.destination):
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.post("/api/files/move")
+def apply_moves(request: MovePlanRequest):
+    """Move files as one undoable operation, never overwriting unless asked, with a result per file."""
+    if request.on_conflict not in CONFLICT_MODES:
+        raise HTTPException(status_code=400, detail=f"on_conflict must be one of {', '.join(CONFLICT_MODES)}")
+    return state.mover.apply_plan(
+        request.moves, request.on_conflict, request.dry_run)
+
+
+@app.post("/api/files/rename-batch")
+def rename_files(request: BatchRenameRequest):
+    """Rename files by a template as one undoable operation, never overwriting, with a result per file."""
+    if request.template:
+        try:
+            template_fields(request.template)
+        except ValueError as e:
+            raise HTTPException(status_code=400, detail=str(e))
+    return state.renamer.rename_files(
+        request.renames, request.template, request.dry_run)
+
+
+@app.get("/api/trash")
+def list_trash_items(limit: int = 100):
+    """Recently removed files, newest first, for the "recently removed" view."""
+    return {'items': state.system_trash.list_trash_items(limit)}
+
+
+@app.post("/api/trash")
+def move_to_trash(request: TrashRequest):
+    """Move files or folders to the platform trash."""
+    return state.system_trash.move_to_trash(request.paths)
+
+
+@app.post("/api/trash/restore")
+def restore_from_trash(request: TrashRestoreRequest):
+    """Put trashed items back where they were."""
+    return state.system_trash.restore_from_trash(request.ids)
+
+
 @app.get("/api/license/status")
 def get_license_status():
     """Get license status."""
@@ -425,11 +2441,212 @@ def update_settings(request: SettingsUpdateRequest):
     if request.enable_ai is not None:
         state.config.update('classification.enable_ai', request.enable_ai)
 
+    if request.locale is not None:
+        locale = request.locale.replace('-', '_').split('_')[0].strip().lower()
+        if locale not in supported_locales():
+            raise HTTPException(status_code=400, detail=f"Unsupported locale: {request.locale}")
+        state.config.update('locale', locale)
+
     state.config.save()
 
     return {'success': True, 'message': 'Settings updated'}
 
 
+@app.get("/api/app-settings")
+def get_app_settings():
+    """The user's own settings (API keys masked, default tier, folder templates, exclusions)."""
+    try:
+        return state.app_settings.get_settings()
+    except ConfigurationError as e:
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@app.put("/api/app-settings")
+def update_app_settings(request: AppSettingsRequest):
+    """Change some of the user's settings; nothing is saved if any value is invalid."""
+    try:
+        return state.app_settings.update_settings(request.changes)
+    except ConfigurationError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/user-rules")
+def list_user_rules():
+    """The user's organize rules, in the order they are tried."""
+    try:
+        return {'rules': state.classifier.user_rules.list_rules()}
+    except ConfigurationError as e:
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@app.post("/api/user-rules")
+def add_user_rule(request: UserRuleRequest):
+    """Add a rule, or replace the rule with the same name."""
+    try:
+        return state.classifier.user_rules.add_rule(request.rule, request.position)
+    except (ValueError, ConfigurationError) as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.delete("/api/user-rules/{name}")
+def remove_user_rule(name: str):
+    """Remove a rule by name."""
+    result = state.classifier.user_rules.remove_rule(name)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+@app.get("/api/profiles")
+def list_profiles():
+    """Organization profiles a run can pick (work, personal, photos...)."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        return {'profiles': state.planner.profiles.list_profiles()}
+    except ConfigurationError as e:
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@app.post("/api/profiles")
+def save_profile(request: ProfileRequest):
+    """Add a profile, or replace the profile with the same name."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    try:
+        result = state.planner.profiles.save_profile(request.profile)
+    except ConfigurationError as e:
+        raise HTTPException(status_code=500, detail=str(e))
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.delete("/api/profiles/{name}")
+def delete_profile(name: str):
+    """Remove a profile by name."""
+    if state.planner is None:
+        raise HTTPException(status_code=500, detail="Planner not initialized")
+    result = state.planner.profiles.delete_profile(name)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+def _taxonomy() -> TaxonomyManager:
+    if state.classifier is None or state.classifier.taxonomy is None:
+        raise HTTPException(status_code=500, detail="Classifier not initialized")
+    return state.classifier.taxonomy
+
+
+def _category_result(result: Dict[str, Any]) -> Dict[str, Any]:
+    if not result['success']:
+        missing = result['message'].startswith('No category named')
+        raise HTTPException(status_code=404 if missing else 400, detail=result['message'])
+    return result
+
+
+@app.get("/api/taxonomy")
+def get_taxonomy():
+    """The user's category tree, as a list (parents first) and nested."""
+    taxonomy = _taxonomy()
+    try:
+        return {'categories': taxonomy.list_categories(), 'tree': taxonomy.tree()}
+    except ConfigurationError as e:
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@app.post("/api/taxonomy")
+def add_category(request: CategoryRequest):
+    """Add a category to the tree."""
+    return _category_result(_taxonomy().add_category(request.category))
+
+
+@app.put("/api/taxonomy/{name}")
+def update_category(name: str, request: CategoryRequest):
+    """Change a category (renaming it moves its subcategories along)."""
+    return _category_result(_taxonomy().update_category(name, request.category))
+
+
+@app.delete("/api/taxonomy/{name}")
+def delete_category(name: str):
+    """Remove a category without subcategories."""
+    return _category_result(_taxonomy().delete_category(name))
+
+
+@app.post("/api/user-rules/test")
+def test_user_rule(request: RuleTestRequest):
+    """Which rule a file matches and where it would go (nothing is moved)."""
+    try:
+        return state.classifier.user_rules.test_rule(request.path, request.rule)
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.get("/api/secrets/{name}")
+def secret_status(name: str):
+    """Whether a secret is stored (its value never leaves the engine over HTTP)."""
+    try:
+        return {'name': name, 'stored': get_secret(name) is not None, 'keychain': keychain_available()}
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+
+@app.post("/api/secrets")
+def save_secret(request: SecretRequest):
+    """Store a secret such as an API key in the OS keychain."""
+    try:
+        store_secret(request.name, request.value)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except KeychainError as e:
+        raise HTTPException(status_code=503, detail=str(e))
+    return {'success': True, 'message': f"Stored {request.name} in the keychain"}
+
+
+@app.delete("/api/secrets/{name}")
+def remove_secret(name: str):
+    """Remove a secret from the OS keychain."""
+    try:
+        removed = delete_secret(name)
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    return {'success': removed, 'message': f"Removed {name}" if removed else f"{name} was not stored"}
+
+
+@app.get("/api/config/export")
+def export_config():
+    """Export settings, rules, profiles and taxonomy as a portable bundle (secrets excluded)."""
+    return state.config_bundles.build_bundle()
+
+
+@app.post("/api/config/import")
+def import_config(request: ConfigImportRequest):
+    """Preview a configuration bundle, or apply it when `apply` is set."""
+    bundles = state.config_bundles
+    try:
+        if not request.apply:
+            return bundles.preview_import(request.bundle)
+        result = bundles.apply_import(request.bundle, sections=request.sections)
+    except ConfigurationError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+
+    if result['success'] and state.action_manager is not None:
+        state.action_manager.set_dry_run(state.config.dry_run)
+    return result
+
+
+@app.get("/api/locales")
+def get_locales():
+    """Get supported locales for category and folder names."""
+    return {
+        'current': normalize_locale(state.config.locale),
+        'supported': supported_locales()
+    }
+
+
 @app.post("/api/watcher/start")
 def start_watcher():
     """Start folder watcher."""
@@ -444,18 +2661,88 @@ def stop_watcher():
     return {'success': True, 'message': 'Watcher stopped'}
 
 
-def run_dashboard(host: str = "127.0.0.1", port: int = 5000):
+@app.post("/api/tray/status")
+def set_tray_status(request: TrayStatusRequest):
+    """Show job progress (or anything else) in the tray icon's tooltip."""
+    return state.tray.set_status(request.text, request.progress)
+
+
+@app.get("/api/autostart")
+def get_autostart():
+    """Whether the organiser starts in the background on login."""
+    return autostart_status()
+
+
+@app.post("/api/autostart")
+def change_autostart(request: AutostartRequest):
+    """Start in the background on login, or stop doing so."""
+    result = set_autostart(request.enabled, state.config)
+    if not result['success']:
+        raise HTTPException(status_code=500, detail=result['message'])
+    return result
+
+
+@app.get("/api/watch")
+def list_watched_folders():
+    """Folders watched on request, with their rules."""
+    if state.folder_watches is None:
+        raise HTTPException(status_code=500, detail="Folder watching not initialized")
+    return state.folder_watches.watching()
+
+
+@app.post("/api/watch")
+def start_watching(request: WatchRequest):
+    """Watch a folder; new files are organized or queued for review per its rules."""
+    if state.folder_watches is None:
+        raise HTTPException(status_code=500, detail="Folder watching not initialized")
+    result = state.folder_watches.start_watching(request.folder, request.rules)
+    if not result['success']:
+        raise HTTPException(status_code=400, detail=result['message'])
+    return result
+
+
+@app.delete("/api/watch")
+def stop_watching(folder: str):
+    """Stop watching a folder."""
+    if state.folder_watches is None:
+        raise HTTPException(status_code=500, detail="Folder watching not initialized")
+    result = state.folder_watches.stop_watching(folder)
+    if not result['success']:
+        raise HTTPException(status_code=404, detail=result['message'])
+    return result
+
+
+def run_dashboard(host: str = "127.0.0.1", port: int = 5000, open_paths: Optional[List[str]] = None,
+                  background: bool = False):
     """
     Run the dashboard server (LOCAL ONLY - PRIVACY PROTECTED).
 
     SECURITY: This dashboard is LOCKED to localhost (127.0.0.1) only.
     It cannot be accessed from the network to protect your privacy.
 
+    The dashboard owns the control socket while it runs, so later launches
+    forward their paths here (queued for review) and bring the page up
+    instead of starting a second instance.
+
+    Closing the dashboard's window leaves the server, watcher and scheduled
+    runs going; in background mode (started on login, see core.autostart)
+    the watcher starts right away and only the tray icon shows.
+
     Args:
         host (str): Host to bind to (FORCED to 127.0.0.1 for security)
         port (int): Port to listen on
+        open_paths (List[str], optional): Files or folders to queue for review
+        background (bool): Start the folder watcher and run with only the tray icon
+
+    Raises:
+        RuntimeError: If another instance owns the control socket
     """
     import uvicorn
+    import webbrowser
+    from src.core.control import ControlServer, default_address
+    from src.utils.structured_logging import log_stream
+
+    log_stream()  # Feed the console view from the start
 
     # SECURITY: Force localhost-only access for privacy
     # This prevents network access and protects user data
@@ -480,8 +2767,32 @@ def run_dashboard(host: str = "127.0.0.1", port: int = 5000):
     ============================================
     """)
 
-    # Force localhost binding for security
-    uvicorn.run(app, host="127.0.0.1", port=port)
+    url = f"http://127.0.0.1:{port}"
+
+    def open_forwarded(paths: List[str]) -> Dict[str, Any]:
+        result = state.open_paths(paths)
+        webbrowser.open(url)  # Raise the dashboard
+        return result
+
+    control = ControlServer(default_address(state.config), {
+        'open': open_forwarded,
+        'status': lambda: {'mode': 'dashboard', 'url': url, 'pending': len(state.pending_files)}
+    })
+    control.start(background=True)
+    if open_paths:
+        state.open_paths(open_paths)
+    state.tray.dashboard_url = url
+    shown = state.tray.start()
+    if background:
+        state.start_watcher()
+        print("Running in the background" + (" - the tray icon opens the dashboard" if shown else f" at {url}"))
+
+    try:
+        # Force localhost binding for security
+        uvicorn.run(app, host="127.0.0.1", port=port)
+    finally:
+        state.tray.stop()
+        control.stop()
 
 
 if __name__ == "__main__":
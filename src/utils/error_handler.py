@@ -9,6 +9,9 @@ This module provides comprehensive error handling with:
 - Retry logic with exponential backoff
 - Error context preservation for debugging
 - Integration with structured logging
+- AppError: every failure as a stable error code and a user-facing
+  message, so the dashboard, the control socket and JSON output report
+  errors the frontend can act on without parsing their prose
 
 NOTICE: This software is proprietary and confidential.
 See LICENSE.txt for full terms and conditions.
@@ -17,8 +20,11 @@ Author: Alexandru Emanuel Vasile
 License: Proprietary (200-key limited release)
 """
 
+import json
 import logging
-from typing import Callable, Any, Optional, Type, Tuple
+from dataclasses import dataclass, field
+from enum import Enum
+from typing import Callable, Any, Dict, Optional, Type, Tuple
 from functools import wraps
 import time
 from pathlib import Path
@@ -60,6 +66,15 @@ class FileOperationError(FileOrganizerError):
         super().__init__(message, details)
 
 
+class ArchivePasswordError(FileOperationError):
+    """Archive is encrypted and no (or a wrong) password was supplied"""
+
+    def __init__(self, message: str, file_path: Optional[str] = None,
+                 wrong_password: bool = False):
+        super().__init__(message, file_path=file_path, operation='extract')
+        self.wrong_password = wrong_password
+
+
 class ClassificationError(FileOrganizerError):
     """Errors during file classification (rule-based, AI, or agent)"""
     
@@ -137,6 +152,125 @@ class LicenseError(FileOrganizerError):
     pass
 
 
+class OperationCancelledError(FileOrganizerError):
+    """Operation cancelled by the user"""
+    pass
+
+
+# ============================================================================
+# Structured Errors
+# ============================================================================
+
+class ErrorCode(str, Enum):
+    """What went wrong, as a stable code clients can branch on."""
+    NOT_FOUND = 'not_found'
+    PERMISSION_DENIED = 'permission_denied'
+    BACKEND_UNAVAILABLE = 'backend_unavailable'
+    PARSE_ERROR = 'parse_error'
+    CANCELLED = 'cancelled'
+    INVALID_INPUT = 'invalid_input'
+    CONFLICT = 'conflict'
+    INTERNAL = 'internal'
+
+
+# HTTP status the dashboard answers with for each code
+HTTP_STATUS = {
+    ErrorCode.NOT_FOUND: 404,
+    ErrorCode.PERMISSION_DENIED: 403,
+    ErrorCode.BACKEND_UNAVAILABLE: 503,
+    ErrorCode.PARSE_ERROR: 400,
+    ErrorCode.CANCELLED: 409,
+    ErrorCode.INVALID_INPUT: 400,
+    ErrorCode.CONFLICT: 409,
+    ErrorCode.INTERNAL: 500,
+}
+
+# The project's own exceptions, most specific first
+_ERROR_CODES = (
+    (OperationCancelledError, ErrorCode.CANCELLED),
+    (ArchivePasswordError, ErrorCode.PERMISSION_DENIED),
+    (SafetyViolationError, ErrorCode.PERMISSION_DENIED),
+    (LicenseError, ErrorCode.PERMISSION_DENIED),
+    (OllamaConnectionError, ErrorCode.BACKEND_UNAVAILABLE),
+    (ConfigurationError, ErrorCode.INVALID_INPUT),
+)
+
+
+@dataclass
+class AppError:
+    """
+    A failure as clients see it.
+
+    Attributes:
+        code (ErrorCode): What went wrong
+        message (str): Message to show the user
+        details (Dict): Context such as the file path (may be empty)
+    """
+    code: ErrorCode
+    message: str
+    details: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def http_status(self) -> int:
+        return HTTP_STATUS[self.code]
+
+    def to_dict(self) -> Dict[str, Any]:
+        """The error as JSON: {"code": "not_found", "message": ..., "details": {...}}."""
+        return {'code': self.code.value, 'message': self.message, 'details': dict(self.details)}
+
+    @classmethod
+    def from_exception(cls, exception: BaseException) -> 'AppError':
+        """
+        The AppError for an exception.
+
+        Args:
+            exception: Any exception a command raised
+
+        Returns:
+            AppError: Its code, a message without Python's decorations, and details
+        """
+        if isinstance(exception, FileOrganizerError):
+            code = next((code for error_type, code in _ERROR_CODES if isinstance(exception, error_type)),
+                        ErrorCode.INTERNAL)
+            return cls(code, exception.message, dict(exception.details))
+        details = {}
+        filename = getattr(exception, 'filename', None)
+        if isinstance(exception, OSError) and filename:
+            details['path'] = str(filename)
+        code, message = _code_for(exception), str(exception)
+        if isinstance(exception, OSError) and exception.strerror:
+            # "[Errno 2] No such file or directory: 'x'" -> "No such file or directory: x"
+            message = f"{exception.strerror}: {filename}" if filename else exception.strerror
+        elif isinstance(exception, KeyError) and exception.args:
+            message = str(exception.args[0])
+        return cls(code, message or type(exception).__name__, details)
+
+    @classmethod
+    def from_http(cls, status: int, message: str) -> 'AppError':
+        """The AppError for an HTTP error status and its message."""
+        codes = {404: ErrorCode.NOT_FOUND, 401: ErrorCode.PERMISSION_DENIED, 403: ErrorCode.PERMISSION_DENIED,
+                 409: ErrorCode.CONFLICT, 503: ErrorCode.BACKEND_UNAVAILABLE}
+        code = codes.get(status, ErrorCode.INVALID_INPUT if 400 <= status < 500 else ErrorCode.INTERNAL)
+        return cls(code, message)
+
+
+def _code_for(exception: BaseException) -> ErrorCode:
+    if isinstance(exception, (FileNotFoundError, NotADirectoryError, KeyError, LookupError)):
+        return ErrorCode.NOT_FOUND
+    if isinstance(exception, PermissionError):
+        return ErrorCode.PERMISSION_DENIED
+    if isinstance(exception, FileExistsError):
+        return ErrorCode.CONFLICT
+    if isinstance(exception, (ConnectionError, TimeoutError)) or type(exception).__name__ in (
+            'ConnectionError', 'Timeout', 'ConnectTimeout', 'ReadTimeout'):  # requests' own
+        return ErrorCode.BACKEND_UNAVAILABLE
+    if isinstance(exception, (json.JSONDecodeError, UnicodeDecodeError)):
+        return ErrorCode.PARSE_ERROR
+    if isinstance(exception, (ValueError, TypeError)):
+        return ErrorCode.INVALID_INPUT
+    return ErrorCode.INTERNAL
+
+
 # ============================================================================
 # Retry Decorator
 # ============================================================================
@@ -0,0 +1,90 @@
+"""
+Output Schemas Module
+
+Copyright (c) 2025 Alexandru Emanuel Vasile. All rights reserved.
+Proprietary Software - 200-Key Limited Release License
+
+This module defines the versioned JSON document printed by --json on the
+command line (both `aifo` and the headless main commands), so other tools
+can consume plans, results and reports:
+
+    {"schema": "aifo.<name>", "schema_version": 1, "ok": true,
+     "data": {...}, "messages": [...], "errors": [...]}
+
+A schema's version only changes when a field is removed or changes
+meaning; new fields may be added at any time.
+
+Both command lines also exit with the same deterministic codes (EXIT_*),
+so scripts can branch on the outcome without parsing any output.
+
+NOTICE: This software is proprietary and confidential.
+See LICENSE.txt for full terms and conditions.
+
+Author: Alexandru Emanuel Vasile
+License: Proprietary (200-key limited release)
+"""
+
+import json
+from typing import Dict, Any, List, Optional
+
+
+EXIT_OK = 0
+EXIT_ERROR = 1                 # Failed, or nothing could be done
+EXIT_CONFLICTS = 2             # Plan written, but some destinations collide
+EXIT_PARTIAL = 3               # Some items were applied, others failed or were skipped
+EXIT_BACKEND_UNAVAILABLE = 4   # The AI backend or database needed for the command is unavailable
+EXIT_USAGE = 64                # Invalid arguments
+
+
+SCHEMA_VERSIONS: Dict[str, int] = {
+    'space': 1, 'organize': 1, 'watch': 1, 'find': 1, 'dedupe': 1, 'move': 1, 'trash': 1, 'metadata': 1, 'preview': 1, 'clean': 1, 'corrections': 1, 'cache': 1, 'scan': 1, 'stats': 1, 'snapshots': 1,
+    'undo': 1, 'redo': 1, 'rename': 1, 'versions': 1, 'history': 1, 'workspace': 1,
+    'consolidate': 1, 'compare': 1, 'pin': 1, 'collection': 1, 'people': 1, 'bursts': 1,
+    'music': 1, 'ebooks': 1, 'leave-alone': 1, 'invoices': 1, 'fonts': 1, 'ask': 1,
+    'classify': 1, 'daemon': 1, 'jobs': 1, 'plan': 1, 'apply': 1, 'validate-rules': 1,
+    'log-level': 1, 'crash-reports': 1, 'diagnostics': 1, 'logs': 1,
+    'self-test': 1, 'simulate': 1, 'doctor': 1, 'update': 1, 'telemetry': 1, 'plugins': 1, 'rest-api': 1,
+}
+
+
+def json_document(schema: str, data: Any, ok: bool = True,
+                  messages: Optional[List[str]] = None, errors: Optional[List[str]] = None) -> str:
+    """
+    Render one machine-readable output document.
+
+    Args:
+        schema (str): Schema name (a key of SCHEMA_VERSIONS)
+        data: JSON-serializable payload (None when the command has no data)
+        ok (bool): Whether the command succeeded; errors always make it False
+        messages (List[str], optional): Human messages printed along the way
+        errors (List[str], optional): Error messages
+
+    Returns:
+        str: The JSON document
+    """
+    errors = list(errors or [])
+    document = {
+        'schema': f'aifo.{schema}',
+        'schema_version': SCHEMA_VERSIONS.get(schema, 1),
+        'ok': bool(ok) and not errors,
+        'data': data,
+        'messages': list(messages or []),
+        'errors': errors
+    }
+    return json.dumps(document, indent=2, default=str, ensure_ascii=False)
+
+
+def outcome_exit_code(done: int, not_done: int) -> int:
+    """
+    Exit code for a run over many items.
+
+    Args:
+        done (int): Items applied
+        not_done (int): Items that failed or were skipped
+
+    Returns:
+        int: EXIT_OK, EXIT_PARTIAL (some applied) or EXIT_ERROR (none applied)
+    """
+    if not not_done:
+        return EXIT_OK
+    return EXIT_PARTIAL if done else EXIT_ERROR
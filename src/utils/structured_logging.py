@@ -9,6 +9,13 @@ This module provides production-grade structured logging with:
 - Configurable log levels per module
 - File rotation to prevent disk space issues
 - Performance timing decorators
+- Tracing spans: nested, timed units of work (a command, a job, a backend
+  call) whose name and fields are attached to every record logged inside
+- Per-stage timings (scan, extract, classify, ai, hash, move) collected
+  for a run and stored with its journal operation
+- Log levels that can be changed while the app runs (set_log_level)
+- A live stream of recent records (LogStream) that the dashboard console
+  and `aifo logs --follow` read by cursor, filtered by level and module
 - Integration with error handling
 
 NOTICE: This software is proprietary and confidential.
@@ -24,8 +31,13 @@ import json
 import sys
 from pathlib import Path
 from datetime import datetime, timezone
-from typing import Optional, Dict, Any, Callable
+from typing import Optional, Dict, Any, Callable, List, Iterator
+import threading
 import time
+import uuid
+from collections import deque
+from contextlib import contextmanager
+from contextvars import ContextVar
 from functools import wraps
 
 
@@ -81,7 +93,8 @@ class JSONFormatter(logging.Formatter):
         custom_fields = [
             'file_path', 'operation', 'category', 'confidence', 'method',
             'risk_level', 'action', 'destination', 'duration', 'attempt',
-            'model', 'error_type', 'user', 'task_id'
+            'model', 'error_type', 'user', 'task_id', 'event_type', 'status',
+            'error', 'span', 'span_id', 'parent_span_id', 'span_fields'
         ]
         
         for field in custom_fields:
@@ -91,10 +104,396 @@ class JSONFormatter(logging.Formatter):
         return json.dumps(log_data)
 
 
+# ============================================================================
+# Tracing Spans
+# ============================================================================
+
+_current_span: ContextVar[Optional['Span']] = ContextVar('aifo_span', default=None)
+
+
+class Span:
+    """
+    A timed unit of work. Records logged while it is open carry its name,
+    id and fields; closing it logs the duration and outcome.
+
+    Spans nest per thread (and per asyncio task): a span opened inside
+    another becomes its child, and its name is the dotted path down to it.
+
+    Attributes:
+        name (str): Full span name ("command.plan/planner.build_plan")
+        span_id (str): Short random id
+        parent (Span or None): Enclosing span
+        fields (Dict): Context for the records logged inside the span
+    """
+
+    def __init__(self, name: str, logger: logging.Logger, level: int, fields: Dict[str, Any]):
+        self.parent = _current_span.get()
+        self.name = f"{self.parent.name}/{name}" if self.parent else name
+        self.span_id = uuid.uuid4().hex[:8]
+        self.fields = dict(fields)
+        self.logger = logger
+        self.level = level
+        self.error: Optional[str] = None
+        self._token = None
+        self._start = 0.0
+
+    def record(self, **fields) -> None:
+        """Add fields (e.g. counts known only at the end) to the span."""
+        self.fields.update(fields)
+
+    def fail(self, error: str) -> None:
+        """Mark the span failed without raising (for functions that return errors)."""
+        self.error = error
+
+    def __enter__(self) -> 'Span':
+        self._token = _current_span.set(self)
+        self._start = time.perf_counter()
+        self.logger.debug(f"{self.name} started", extra={'event_type': 'span_start'})
+        return self
+
+    def __exit__(self, exc_type, exc, tb) -> bool:
+        duration = round(time.perf_counter() - self._start, 6)
+        try:
+            if exc_type is not None:
+                self.logger.error(f"{self.name} failed after {duration:.3f}s: {exc}",
+                                  exc_info=(exc_type, exc, tb),
+                                  extra={'event_type': 'span_end', 'status': 'error', 'duration': duration,
+                                         'error': str(exc), 'error_type': exc_type.__name__})
+            elif self.error:
+                self.logger.warning(f"{self.name} failed after {duration:.3f}s: {self.error}",
+                                    extra={'event_type': 'span_end', 'status': 'error', 'duration': duration,
+                                           'error': self.error})
+            else:
+                self.logger.log(self.level, f"{self.name} completed in {duration:.3f}s",
+                                extra={'event_type': 'span_end', 'status': 'ok', 'duration': duration})
+        finally:
+            _current_span.reset(self._token)
+        return False
+
+
+def span(name: str, logger: Optional[logging.Logger] = None, level: int = logging.DEBUG, **fields) -> Span:
+    """
+    Open a tracing span.
+
+    Args:
+        name: Span name (e.g. "planner.apply_plan")
+        logger: Logger for the span's own records (default: "aifo.trace")
+        level: Level of the "completed" record; failures log at WARNING/ERROR
+        **fields: Context attached to every record inside the span
+
+    Returns:
+        Span: Use as a context manager
+
+    Example:
+        >>> with span("planner.apply_plan", items=12) as s:
+        ...     s.record(applied=11)
+    """
+    return Span(name, logger or logging.getLogger('aifo.trace'), level, fields)
+
+
+def current_span() -> Optional[Span]:
+    """The innermost open span, if any."""
+    return _current_span.get()
+
+
+class SpanContextFilter(logging.Filter):
+    """Attach the current span's name, ids and fields to each record."""
+
+    def filter(self, record: logging.LogRecord) -> bool:
+        current = _current_span.get()
+        if current is not None and not hasattr(record, 'span'):
+            record.span = current.name
+            record.span_id = current.span_id
+            record.parent_span_id = current.parent.span_id if current.parent else None
+            record.span_fields = current.fields
+        return True
+
+
+# ============================================================================
+# Stage Timings
+# ============================================================================
+
+_current_timings: ContextVar[Optional['StageTimings']] = ContextVar('aifo_timings', default=None)
+_current_stage: ContextVar[Optional[List[float]]] = ContextVar('aifo_stage', default=None)
+
+
+class StageTimings:
+    """
+    Time spent per stage of one run.
+
+    Stage times are exclusive: while a stage runs inside another (the AI
+    call inside classification), its time counts for the inner stage only,
+    so the stages add up to at most the elapsed time.
+    """
+
+    def __init__(self):
+        self.stages: Dict[str, Dict[str, Any]] = {}
+        self.elapsed: Optional[float] = None
+        self._start = time.perf_counter()
+        self._lock = threading.Lock()
+
+    def add(self, stage: str, seconds: float) -> None:
+        with self._lock:
+            entry = self.stages.setdefault(stage, {'seconds': 0.0, 'count': 0})
+            entry['seconds'] += seconds
+            entry['count'] += 1
+
+    def stop(self) -> None:
+        self.elapsed = time.perf_counter() - self._start
+
+    def as_dict(self) -> Dict[str, Any]:
+        """{'elapsed': seconds, 'stages': {stage: {'seconds', 'count'}}}"""
+        elapsed = self.elapsed if self.elapsed is not None else time.perf_counter() - self._start
+        with self._lock:
+            stages = {name: {'seconds': round(entry['seconds'], 4), 'count': entry['count']}
+                      for name, entry in self.stages.items()}
+        return {'elapsed': round(elapsed, 4), 'stages': stages}
+
+
+@contextmanager
+def collect_timings() -> Iterator[StageTimings]:
+    """
+    Collect the stage timings of everything run inside the block.
+
+    Nested blocks join the outer collection.
+
+    Example:
+        >>> with collect_timings() as timings:
+        ...     planner.build_plan(folder)
+        >>> timings.as_dict()['stages']['classify']
+        {'seconds': 1.82, 'count': 40}
+    """
+    current = _current_timings.get()
+    if current is not None:
+        yield current
+        return
+    timings = StageTimings()
+    token = _current_timings.set(timings)
+    try:
+        yield timings
+    finally:
+        timings.stop()
+        _current_timings.reset(token)
+
+
+@contextmanager
+def timed(stage: str) -> Iterator[None]:
+    """
+    Count the block's time towards a stage of the current run (a no-op
+    outside collect_timings).
+
+    Args:
+        stage: Stage name (scan, extract, classify, ai, hash, move)
+    """
+    timings = _current_timings.get()
+    if timings is None:
+        yield
+        return
+    parent = _current_stage.get()
+    nested = [0.0]  # Time spent in stages inside this one
+    token = _current_stage.set(nested)
+    start = time.perf_counter()
+    try:
+        yield
+    finally:
+        duration = time.perf_counter() - start
+        _current_stage.reset(token)
+        timings.add(stage, max(0.0, duration - nested[0]))
+        if parent is not None:
+            parent[0] += duration
+
+
+def merge_timings(*timings: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    """
+    Add up timings (as_dict() results) of several parts of a run, e.g. the
+    planning done before a plan was applied and the applying itself.
+    """
+    merged: Dict[str, Any] = {'elapsed': 0.0, 'stages': {}}
+    for part in timings:
+        if not part:
+            continue
+        merged['elapsed'] = round(merged['elapsed'] + part.get('elapsed', 0.0), 4)
+        for name, entry in part.get('stages', {}).items():
+            total = merged['stages'].setdefault(name, {'seconds': 0.0, 'count': 0})
+            total['seconds'] = round(total['seconds'] + entry.get('seconds', 0.0), 4)
+            total['count'] += entry.get('count', 0)
+    return merged
+
+
+# ============================================================================
+# Live Log Stream
+# ============================================================================
+
+class LogStream(logging.Handler):
+    """
+    Keeps the most recent records in memory, numbered, so viewers can read
+    what is new since their last read ("after" cursor) and wait for more.
+
+    Attributes:
+        capacity (int): Records kept; older ones are dropped
+    """
+
+    def __init__(self, capacity: int = 2000):
+        super().__init__(level=logging.NOTSET)
+        self.capacity = capacity
+        self._records: deque = deque(maxlen=capacity)
+        self._seq = 0
+        self._changed = threading.Condition()
+        self._formatter = JSONFormatter()
+
+    def emit(self, record: logging.LogRecord) -> None:
+        try:
+            entry = json.loads(self._formatter.format(record))
+        except Exception:
+            self.handleError(record)
+            return
+        entry['levelno'] = record.levelno
+        with self._changed:
+            self._seq += 1
+            entry['seq'] = self._seq
+            self._records.append(entry)
+            self._changed.notify_all()
+
+    def read(self, after: int = 0, level: str = 'DEBUG', module: Optional[str] = None,
+             limit: int = 200, wait: float = 0) -> Dict[str, Any]:
+        """
+        Records newer than a cursor.
+
+        Args:
+            after: Cursor from the previous read (0: everything still kept)
+            level: Minimum level name
+            module: Only loggers with this name or below it (e.g. "src.core")
+            limit: Maximum records returned
+            wait: Seconds to wait for a new record when there is none yet
+
+        Returns:
+            Dict: 'records' (oldest first), 'cursor' for the next read, and
+                  'dropped' (True if records after the cursor were discarded)
+        """
+        minimum = logging.getLevelName(str(level).upper())
+        minimum = minimum if isinstance(minimum, int) else logging.DEBUG
+        deadline = time.monotonic() + max(0.0, float(wait))
+        with self._changed:
+            while self._seq <= after and time.monotonic() < deadline:
+                self._changed.wait(deadline - time.monotonic())
+            kept = list(self._records)
+            cursor = self._seq
+        dropped = bool(kept) and kept[0]['seq'] > after + 1 and after > 0
+        matching = [r for r in kept if r['seq'] > after and r['levelno'] >= minimum
+                    and (not module or r['logger'] == module or r['logger'].startswith(module + '.'))]
+        if len(matching) > limit:
+            matching = matching[-limit:]
+        return {'records': matching, 'cursor': cursor, 'dropped': dropped}
+
+
+_LOG_STREAM: Optional[LogStream] = None
+
+
+def log_stream() -> LogStream:
+    """The app's live log stream, attached to the root logger on first use."""
+    global _LOG_STREAM
+    if _LOG_STREAM is None:
+        _LOG_STREAM = LogStream()
+        _LOG_STREAM.addFilter(SpanContextFilter())
+    root_logger = logging.getLogger()
+    if _LOG_STREAM not in root_logger.handlers:
+        root_logger.addHandler(_LOG_STREAM)
+    return _LOG_STREAM
+
+
+# ============================================================================
+# Runtime Log Levels
+# ============================================================================
+
+def set_log_level(level: str, logger_name: Optional[str] = None) -> Dict[str, str]:
+    """
+    Change a log level while the app runs (e.g. for debugging a daemon).
+
+    Args:
+        level: DEBUG, INFO, WARNING, ERROR or CRITICAL
+        logger_name: Logger to change (e.g. "src.core.plans"); root if omitted
+
+    Returns:
+        Dict: 'logger', 'previous' and 'level'
+
+    Raises:
+        ValueError: If the level is not a known level name
+    """
+    numeric = logging.getLevelName(str(level).upper())
+    if not isinstance(numeric, int):
+        raise ValueError(f"Unknown log level: {level}")
+    target = logging.getLogger(logger_name) if logger_name else logging.getLogger()
+    previous = logging.getLevelName(target.getEffectiveLevel())
+    target.setLevel(numeric)
+    return {'logger': logger_name or 'root', 'previous': previous, 'level': logging.getLevelName(numeric)}
+
+
+def log_levels() -> Dict[str, str]:
+    """Levels explicitly set on the root and on named loggers."""
+    levels = {'root': logging.getLevelName(logging.getLogger().level)}
+    for name, item in sorted(logging.Logger.manager.loggerDict.items()):
+        if isinstance(item, logging.Logger) and item.level != logging.NOTSET:
+            levels[name] = logging.getLevelName(item.level)
+    return levels
+
+
 # ============================================================================
 # Enhanced Logger Setup
 # ============================================================================
 
+def configure_logging(settings: Optional[Dict[str, Any]] = None, base_dir: Optional[Path] = None) -> logging.Logger:
+    """
+    Apply the "logging" settings: JSON records with span context in a
+    rotating file, warnings and errors on stderr.
+
+    Unlike setup_structured_logging() it keeps stdout free (for --json) and
+    can be called again to apply changed settings.
+
+    Args:
+        settings: {"level", "file", "json", "max_mb", "backups"}
+        base_dir: Folder a relative "file" is resolved against (default: project root)
+
+    Returns:
+        Configured root logger
+    """
+    settings = settings if isinstance(settings, dict) else {}
+    root_logger = logging.getLogger()
+    for handler in [h for h in root_logger.handlers if getattr(h, '_aifo_managed', False)]:
+        root_logger.removeHandler(handler)
+        handler.close()
+    set_log_level(settings.get('level') or 'INFO')
+
+    log_file = Path(settings.get('file') or 'logs/organiser.json.log').expanduser()
+    if not log_file.is_absolute():
+        log_file = (base_dir or Path(__file__).parent.parent.parent) / log_file
+    handlers = []
+    try:
+        log_file.parent.mkdir(parents=True, exist_ok=True)
+        file_handler = logging.handlers.RotatingFileHandler(
+            log_file,
+            maxBytes=int(settings.get('max_mb', 10)) * 1024 * 1024,
+            backupCount=int(settings.get('backups', 5)),
+            encoding='utf-8'
+        )
+        file_handler.setFormatter(JSONFormatter() if settings.get('json', True) else logging.Formatter(
+            fmt='%(asctime)s - %(name)s - %(levelname)s - %(message)s', datefmt='%Y-%m-%d %H:%M:%S'))
+        handlers.append(file_handler)
+    except OSError as e:
+        sys.stderr.write(f"Cannot write log file {log_file}: {e}\n")
+
+    console_handler = logging.StreamHandler(sys.stderr)
+    console_handler.setLevel(logging.WARNING)
+    console_handler.setFormatter(logging.Formatter('%(levelname)s: %(message)s'))
+    handlers.append(console_handler)
+
+    for handler in handlers:
+        handler._aifo_managed = True
+        handler.addFilter(SpanContextFilter())
+        root_logger.addHandler(handler)
+    log_stream()
+    return root_logger
+
+
 def setup_structured_logging(
     log_level: str = "INFO",
     log_dir: str = "logs",
@@ -170,6 +569,7 @@ def setup_structured_logging(
         )
         file_handler.setFormatter(file_formatter)
     
+    file_handler.addFilter(SpanContextFilter())
     root_logger.addHandler(file_handler)
     
     # ========================================
@@ -189,6 +589,7 @@ def setup_structured_logging(
     else:
         error_handler.setFormatter(file_formatter)
     
+    error_handler.addFilter(SpanContextFilter())
     root_logger.addHandler(error_handler)
     
     # Log startup
@@ -0,0 +1,53 @@
+"""
+Shared fixtures for the unit tests.
+
+A test module that needs more settings overrides `config` and sets them on
+the shared one before the action manager is made:
+
+    @pytest.fixture
+    def config(config):
+        config.conflict_resolution = 'replace'
+        return config
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.actions import ActionManager
+from src.core.db_manager import DatabaseManager
+from src.config import Config
+
+
+@pytest.fixture
+def config(tmp_path):
+    """Config with no folder policies or blacklist, organizing into tmp_path."""
+    config = Mock(spec=Config)
+    config.get_folder_policy.return_value = None
+    config.path_blacklist = []
+    config.base_destination = str(tmp_path / "organized")
+    config.time_estimates = {'move': 0.5}
+    config.trash_dir = str(tmp_path / "trash")
+    return config
+
+
+@pytest.fixture
+def db(tmp_path):
+    """Temporary database."""
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    yield db
+    db.cleanup()
+
+
+@pytest.fixture
+def action_manager(config, db):
+    """ActionManager that moves for real, with a guardian that approves everything."""
+    guardian = MagicMock()
+    guardian.evaluate_operation.return_value = {'approved': True, 'reasoning': 'ok', 'warnings': []}
+    guardian.is_file_safe_to_modify.return_value = (True, 'Safe to modify')
+    with patch('src.core.actions.SafetyGuardian', return_value=guardian):
+        return ActionManager(config, db, dry_run=False)
@@ -17,6 +17,7 @@ import sys
 sys.path.insert(0, str(Path(__file__).parent.parent.parent / "src"))
 
 from core.actions import ActionManager
+from core.file_names import place_in_destination, safe_name, validate_file_name
 from config import Config
 
 
@@ -201,6 +202,42 @@ class TestRenameOperation:
         assert result['action'] == 'rename'
         assert 'new_name.txt' in result.get('new_path', '')
 
+    def test_file_names_follow_platform_rules(self):
+        """Reserved, illegal or over-long names are refused with a fix that passes."""
+        assert validate_file_name('Lease 2024.pdf')['valid']
+        for bad in ('a:b.txt', 'CON', 'nul.txt', 'report.', 'notes ', 'x' * 256 + '.txt', 'tab\tname'):
+            check = validate_file_name(bad)
+            assert not check['valid'] and check['problems']
+            assert check['suggestion'] is None or validate_file_name(check['suggestion'])['valid']
+        assert validate_file_name('a:b.txt', 'posix')['valid']
+        assert not validate_file_name('a:b.txt', 'macos')['valid']
+        assert validate_file_name('CON.txt')['suggestion'] == 'CON_.txt'
+        assert not validate_file_name('a/b')['valid'] and not validate_file_name('..')['valid']
+        assert not validate_file_name('f.txt', 'windows', parent='C:\\' + 'd' * 256)['valid']
+        assert safe_name(' AC/DC Live?. ') == 'AC DC Live' and safe_name(None) == ''
+        assert safe_name('a\x00b.txt', '_') == 'a_b.txt'
+        assert place_in_destination({'suggested_path': '2024/'}, 'Pictures/', '2024') == {
+            'suggested_path': 'Pictures/2024/'}
+        assert place_in_destination({'suggested_path': '2024/'}, '/srv/photos', '2024') == {
+            'suggested_path': '2024/', 'destination_root': '/srv/photos'}
+
+    def test_rename_file_validates_and_journals(self, action_manager, temp_dir):
+        """rename_file refuses bad names and occupied targets, and journals the rename."""
+        source_file = temp_dir / "scan001.pdf"
+        source_file.write_text("lease")
+        (temp_dir / "taken.pdf").write_text("other")
+        action_manager._journal_move = Mock()
+
+        refused = action_manager.rename_file(str(source_file), 'Lease: 2024.pdf')
+        assert not refused['success'] and refused['suggestion'] == 'Lease_ 2024.pdf'
+        assert not action_manager.rename_file(str(source_file), 'taken.pdf')['success']
+        assert source_file.exists() and not action_manager._journal_move.called
+
+        result = action_manager.rename_file(str(source_file), 'Lease 2024.pdf')
+        assert result['success'] and (temp_dir / 'Lease 2024.pdf').read_text() == 'lease'
+        action_manager._journal_move.assert_called_once_with(
+            'rename', source_file, temp_dir / 'Lease 2024.pdf')
+
 
 class TestSafetyGuardianIntegration:
     """Test Safety Guardian integration."""
@@ -0,0 +1,337 @@
+"""
+Unit tests for archive workflows and the operation journal.
+"""
+
+import zipfile
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.archives import ArchiveManager
+
+
+@pytest.fixture
+def workspace(config, db, action_manager, tmp_path):
+    """Create config, database and an archive with two files."""
+    config.dry_run = False
+    config.archive_staging_dir = str(tmp_path / "staging")
+    config.archive_original_action = 'keep'
+
+    archive = tmp_path / "downloads" / "bundle.zip"
+    archive.parent.mkdir()
+    with zipfile.ZipFile(archive, 'w') as zf:
+        zf.writestr('docs/report.pdf', 'pdf content')
+        zf.writestr('photo.jpg', 'jpg content')
+        zf.writestr('../escape.txt', 'should be skipped')
+
+    classifier = Mock()
+    classifier.classify.side_effect = lambda p: {
+        'category': 'Documents' if p.endswith('.pdf') else 'Pictures',
+        'suggested_path': 'Documents/PDFs/' if p.endswith('.pdf') else 'Pictures/',
+        'rename': None,
+        'confidence': 'high',
+        'method': 'rule-based'
+    }
+
+    return ArchiveManager(config, db, classifier, action_manager), archive, tmp_path
+
+
+def test_extract_and_organize_then_undo(workspace):
+    """Contents are organized, archive trashed, and one undo reverts everything."""
+    manager, archive, tmp_path = workspace
+
+    result = manager.extract_and_organize(str(archive), original_action='trash')
+
+    assert result['success'], result['message']
+    assert result['organized'] == 2
+    assert (tmp_path / "organized" / "Documents" / "PDFs" / "report.pdf").exists()
+    assert (tmp_path / "organized" / "Pictures" / "photo.jpg").exists()
+    assert not (tmp_path / "escape.txt").exists()
+    assert not archive.exists()
+
+    operation = manager.journal.get_operation(result['operation_id'])
+    assert operation['status'] == 'applied'
+    assert [s['action'] for s in operation['steps']] == ['create', 'move', 'move', 'move']
+
+    undo = manager.journal.undo(result['operation_id'])
+
+    assert undo['success'], undo['message']
+    assert archive.exists()
+    assert not (tmp_path / "organized" / "Pictures" / "photo.jpg").exists()
+    assert not Path(result['staging_dir']).exists()
+    assert manager.journal.get_operation(result['operation_id'])['status'] == 'undone'
+
+
+def test_dry_run_changes_nothing(workspace):
+    """Dry run reports planned moves without touching the archive or creating a journal entry."""
+    manager, archive, tmp_path = workspace
+    manager.action_manager.set_dry_run(True)
+
+    result = manager.extract_and_organize(str(archive), original_action='trash')
+
+    assert result['success']
+    assert result['operation_id'] is None
+    assert archive.exists()
+    assert not (tmp_path / "organized").exists()
+    assert manager.journal.list_operations() == []
+
+
+def test_rejects_non_archives(workspace):
+    """Files that are not archives are refused."""
+    manager, _, tmp_path = workspace
+    other = tmp_path / "notes.txt"
+    other.write_text("hello")
+
+    assert manager.extract_and_organize(str(other))['success'] is False
+
+
+def test_list_contents_then_extract_chosen_entries_into_category_folders(workspace):
+    """Entries are categorized unopened; chosen ones land in category folders, never overwriting; undo clears up."""
+    manager, archive, tmp_path = workspace
+    manager.config.archive_inspection = {}
+    manager.config.destination_rules = {'pdf': 'Documents/PDFs/', 'jpg': 'Pictures/'}
+
+    listing = manager.list_contents(str(archive))
+    assert {e['name']: e['category'] for e in listing['entries']} == {
+        'docs/report.pdf': 'Documents', 'photo.jpg': 'Pictures', '../escape.txt': 'Other'}
+    assert listing['categories'] == {'Documents': 1, 'Other': 1, 'Pictures': 1}
+
+    out = tmp_path / "out"
+    (out / "Documents" / "PDFs").mkdir(parents=True)
+    (out / "Documents" / "PDFs" / "report.pdf").write_text("already here")
+    result = manager.extract_archive(str(archive), str(out), ['docs/report.pdf', 'missing.txt'], organize=True)
+    assert result['success'], result['message']
+    assert [(f['name'], Path(f['path']).relative_to(out).as_posix(), f['category']) for f in result['files']] == [
+        ('docs/report.pdf', 'Documents/PDFs/report (2).pdf', 'Documents')]
+    assert result['missing'] == ['missing.txt']
+    assert sorted(p.name for p in out.iterdir()) == ['Documents']  # No staging left behind
+
+    everything = manager.extract_archive(str(archive), str(tmp_path / "all"))
+    assert sorted(f['name'] for f in everything['files']) == ['docs/report.pdf', 'photo.jpg']
+    assert not (tmp_path / "escape.txt").exists()
+
+    assert manager.journal.undo(everything['operation_id'])['success']
+    assert list((tmp_path / "all").iterdir()) == []
+    assert manager.journal.undo(result['operation_id'])['success']
+    assert [p.name for p in (out / "Documents" / "PDFs").iterdir()] == ['report.pdf']
+
+
+def test_create_archive_verifies_and_trashes_sources(workspace):
+    """Created archive carries a manifest; sources are trashed and restored on undo."""
+    from src.core.archives import read_manifest
+
+    manager, _, tmp_path = workspace
+    receipts = tmp_path / "receipts"
+    receipts.mkdir()
+    for name in ("jan.pdf", "feb.pdf"):
+        (receipts / name).write_text(f"receipt {name}")
+
+    result = manager.create_archive(
+        [str(receipts / "jan.pdf"), str(receipts / "feb.pdf")],
+        str(tmp_path / "Receipts-2024.tar.gz"),
+        remove_sources=True
+    )
+
+    assert result['success'], result['message']
+    assert result['verification']['verified'] == 2
+    assert not (receipts / "jan.pdf").exists()
+    manifest = read_manifest(result['archive_path'])
+    assert sorted(f['name'] for f in manifest['files']) == ['feb.pdf', 'jan.pdf']
+
+    manager.journal.undo(result['operation_id'])
+
+    assert (receipts / "jan.pdf").read_text() == "receipt jan.pdf"
+    assert not Path(result['archive_path']).exists()
+
+
+def _zip_bytes(entries):
+    """Build an in-memory zip from (name, bytes) pairs."""
+    import io
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, 'w', compression=zipfile.ZIP_DEFLATED) as zf:
+        for name, data in entries:
+            zf.writestr(name, data)
+    return buffer.getvalue()
+
+
+def test_inspect_nested_archives(tmp_path):
+    """Nested archives are listed up to max_depth with '!/' paths."""
+    from src.core.archives import inspect_archive, InspectionLimits
+
+    innermost = _zip_bytes([('deep.txt', b'deep')])
+    inner = _zip_bytes([('inner.txt', b'inner'), ('level2.zip', innermost)])
+    outer = tmp_path / "outer.zip"
+    outer.write_bytes(_zip_bytes([('readme.txt', b'hello'), ('level1.zip', inner)]))
+
+    result = inspect_archive(str(outer), InspectionLimits(max_depth=1), hash_algorithm='sha1')
+
+    paths = [e['path'] for e in result['entries']]
+    assert 'outer.zip!/level1.zip!/inner.txt' in paths
+    assert 'outer.zip!/level1.zip!/level2.zip!/deep.txt' not in paths
+    assert result['limit_hit'] is None
+    assert all(e.get('hash') for e in result['entries'])
+
+
+def test_inspect_stops_on_zip_bomb(tmp_path):
+    """Highly compressible entries trip the expansion ratio limit."""
+    from src.core.archives import inspect_archive, InspectionLimits
+
+    bomb = tmp_path / "bomb.zip"
+    bomb.write_bytes(_zip_bytes([('zeros.bin', b'\0' * 5_000_000)]))
+
+    result = inspect_archive(str(bomb), InspectionLimits(max_ratio=100))
+
+    assert result['success']
+    assert 'ratio' in result['limit_hit']
+
+
+SEVEN_ZIP_LISTING = """
+7-Zip [64] 16.02 : Copyright (c) 1999-2016 Igor Pavlov : 2016-05-21
+
+Listing archive: photos.7z
+
+--
+Path = photos.7z
+Type = 7z
+Physical Size = 2048
+
+----------
+Path = trip
+Folder = +
+Size = 0
+Attributes = D
+
+Path = trip/beach.jpg
+Folder = -
+Size = 4096
+Packed Size = 2000
+Attributes = A
+Encrypted = -
+"""
+
+
+def test_parse_7z_listing():
+    """Archive header and folders are skipped; files keep sizes."""
+    from src.core.archive_tools import _parse_7z_listing
+
+    entries = _parse_7z_listing(SEVEN_ZIP_LISTING)
+
+    assert entries == [{'name': 'trip/beach.jpg', 'size': 4096, 'compressed_size': 2000, 'encrypted': False}]
+
+
+def test_missing_tools_reported(tmp_path, monkeypatch):
+    """Without 7z/unrar, capabilities say so and 7z extraction fails cleanly."""
+    import src.core.archive_tools as archive_tools
+    from src.utils.error_handler import FileOperationError
+
+    monkeypatch.setattr(archive_tools.shutil, 'which', lambda name: None)
+    monkeypatch.setattr(archive_tools, 'BUNDLED_TOOLS_DIR', tmp_path / "no-tools")
+    toolkit = archive_tools.ArchiveToolkit()
+
+    report = toolkit.capabilities()
+    assert report['formats']['zip']['extract'] is True
+    assert report['formats']['7z']['extract'] is False
+    assert report['hint']
+
+    with pytest.raises(FileOperationError):
+        toolkit.extract(str(tmp_path / "photos.7z"), tmp_path)
+
+
+def _zipcrypto_bytes(name, data, password):
+    """Build a stored zip with one ZipCrypto-encrypted entry (zipfile cannot write these)."""
+    import struct
+    import zlib
+
+    keys = [0x12345678, 0x23456789, 0x34567890]
+
+    def crc_byte(crc, b):
+        return zlib.crc32(bytes([b]), crc ^ 0xFFFFFFFF) ^ 0xFFFFFFFF
+
+    def update(c):
+        keys[0] = crc_byte(keys[0], c)
+        keys[1] = ((keys[1] + (keys[0] & 0xFF)) * 134775813 + 1) & 0xFFFFFFFF
+        keys[2] = crc_byte(keys[2], keys[1] >> 24)
+
+    def encrypt(plain):
+        out = bytearray()
+        for c in plain:
+            t = (keys[2] | 2) & 0xFFFF
+            out.append(c ^ (((t * (t ^ 1)) >> 8) & 0xFF))
+            update(c)
+        return bytes(out)
+
+    for c in password.encode():
+        update(c)
+    crc = zlib.crc32(data)
+    payload = encrypt(bytes(11) + bytes([crc >> 24])) + encrypt(data)
+
+    fname = name.encode()
+    local = struct.pack('<IHHHHHIIIHH', 0x04034b50, 20, 1, 0, 0, 0, crc, len(payload), len(data),
+                        len(fname), 0) + fname + payload
+    central = struct.pack('<IHHHHHHIIIHHHHHII', 0x02014b50, 20, 20, 1, 0, 0, 0, crc, len(payload),
+                          len(data), len(fname), 0, 0, 0, 0, 0, 0) + fname
+    end = struct.pack('<IHHHHIIH', 0x06054b50, 0, 0, 1, 1, len(central), len(local), 0)
+    return local + central + end
+
+
+@pytest.fixture
+def encrypted_archive(workspace):
+    """Workspace plus a password-protected zip."""
+    manager, _, tmp_path = workspace
+    archive = tmp_path / "downloads" / "secret.zip"
+    archive.write_bytes(_zipcrypto_bytes('contract.pdf', b'signed contract', 'hunter2'))
+    return manager, archive, tmp_path
+
+
+def test_encrypted_archive_without_password_is_left_alone(encrypted_archive):
+    """No answer to the prompt means nothing is extracted or moved."""
+    from src.core.archive_passwords import ArchivePasswordBroker
+    from src.core.archives import inspect_archive
+
+    manager, archive, tmp_path = encrypted_archive
+    manager.passwords = ArchivePasswordBroker(timeout=0)
+
+    result = manager.extract_and_organize(str(archive), original_action='trash')
+
+    assert result['success'] is False
+    assert result['needs_password'] is True
+    assert archive.exists()
+    assert not (tmp_path / "organized").exists()
+    assert inspect_archive(str(archive))['encrypted'] is True
+
+
+def test_password_round_trip_unlocks_and_remembers(encrypted_archive, monkeypatch):
+    """A wrong answer re-prompts; the right one organizes the contents and is stored."""
+    import src.core.archive_passwords as archive_passwords
+
+    manager, archive, tmp_path = encrypted_archive
+    stored = {}
+    fake_keyring = Mock()
+    fake_keyring.get_password.side_effect = lambda service, key: stored.get(key)
+    fake_keyring.set_password.side_effect = lambda service, key, value: stored.__setitem__(key, value)
+    monkeypatch.setattr(archive_passwords, 'keyring', fake_keyring)
+
+    broker = archive_passwords.ArchivePasswordBroker(timeout=5)
+    broker.use_keyring = True
+    answers = iter(['wrong', 'hunter2'])
+    events = []
+
+    def on_event(event):
+        events.append(event)
+        if event['type'] == 'archive_password_required':
+            broker.answer(event['request_id'], next(answers), remember=True)
+
+    broker.add_listener(on_event)
+    manager.passwords = broker
+
+    result = manager.extract_and_organize(str(archive))
+
+    assert result['success'], result['message']
+    assert (tmp_path / "organized" / "Documents" / "PDFs" / "contract.pdf").read_bytes() == b'signed contract'
+    prompts = [e for e in events if e['type'] == 'archive_password_required']
+    assert [e['attempt'] for e in prompts] == [1, 2]
+    assert prompts[1]['error'] == 'Wrong archive password'
+    assert list(stored.values()) == ['hunter2']
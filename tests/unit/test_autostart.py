@@ -0,0 +1,56 @@
+"""
+Unit tests for starting in background mode on login.
+"""
+
+import plistlib
+import sys
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core import autostart
+from src.core.autostart import AGENT_LABEL, autostart_status, launch_command, set_autostart
+
+PYTHON = '/opt/My Python/bin/python3'
+
+
+@pytest.fixture
+def home(tmp_path):
+    with patch('src.core.autostart.Path.home', return_value=tmp_path), \
+            patch.dict('os.environ', {'XDG_CONFIG_HOME': str(tmp_path / "config")}), \
+            patch('src.core.autostart.resolve_python', return_value=PYTHON), \
+            patch('src.core.autostart.resolve_app_root', return_value=Path('/opt/aifo')):
+        yield tmp_path
+
+
+def test_linux_desktop_entry_is_written_quoted_and_removed(home):
+    """The entry starts `-m src.main background` in the app folder; off removes it; status reads it back."""
+    with patch('src.core.autostart._system', return_value='linux'):
+        assert autostart_status()['enabled'] is False
+        result = set_autostart(True)
+        entry = home / "config" / "autostart" / "ai-file-organiser.desktop"
+        assert result['success'] and result['location'] == str(entry)
+        text = entry.read_text()
+        assert f'Exec="{PYTHON}" -m src.main background\n' in text and 'Path=/opt/aifo\n' in text
+        assert autostart_status()['command'] == f'"{PYTHON}" -m src.main background'
+
+        assert set_autostart(False)['enabled'] is False and not entry.exists()
+        assert set_autostart(False)['success']  # Already off
+
+
+def test_macos_launch_agent_and_windows_command(home):
+    """macOS gets a launch agent run at load; Windows runs the module from the app folder itself."""
+    with patch('src.core.autostart._system', return_value='macos'):
+        assert set_autostart(True)['success']
+        agent = plistlib.loads((home / "Library" / "LaunchAgents" / f"{AGENT_LABEL}.plist").read_bytes())
+    assert agent['ProgramArguments'] == [PYTHON, '-m', 'src.main', 'background']
+    assert (agent['RunAtLoad'], agent['WorkingDirectory']) == (True, '/opt/aifo')
+
+    with patch('src.core.autostart._system', return_value='windows'):
+        command, cwd = launch_command()
+    assert cwd is None and command[1] == '-c' and "os.chdir('/opt/aifo')" in command[2]
+    with patch.object(autostart.sys, 'frozen', True, create=True):
+        assert launch_command() == ([sys.executable, 'background'], None)
@@ -0,0 +1,66 @@
+"""
+Unit tests for the backend check.
+"""
+
+import json
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src import __version__
+from src.ai import providers
+from src.core import backend_check
+from src.core.backend_check import check_backend
+
+
+def _config():
+    config = Mock()
+    config.enable_ai = True
+    config.ollama_base_url, config.ollama_model = 'http://localhost:11434', 'llama3'
+    config.ai_provider_settings = {}
+    config.get.return_value = 30
+    return config
+
+
+def test_reports_missing_packages_scripts_and_python(tmp_path):
+    """Each problem comes with the command that fixes it; optional packages only warn."""
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path)}), \
+            patch('importlib.util.find_spec',
+                         side_effect=lambda name: None if name in ('fastapi', 'watchdog') else object()), \
+            patch.object(backend_check, 'python_on_path', return_value=None), \
+            patch.object(providers.OllamaProvider, 'detect',
+                         return_value={'running': True, 'version': '0.3.12', 'model_installed': True}):
+        result = check_backend(_config(), expected_version='9.9.9')
+
+    checks = {check['name']: check for check in result['checks']}
+    assert not result['success'] and result['ai_ready']
+    assert checks['packages']['status'] == 'error' and checks['packages']['fix'].endswith('-m pip install fastapi')
+    assert checks['optional_packages']['status'] == 'warning' and 'watching folders' in checks['optional_packages']['message']
+    assert checks['python_on_path']['status'] == 'warning'
+    assert checks['scripts']['message'] == f"Scripts are version {__version__}, expected 9.9.9"
+    assert checks['python']['status'] == 'ok'
+    assert checks['ai']['message'] == 'Ollama 0.3.12 at http://localhost:11434 with llama3'
+
+
+def test_ai_check_follows_the_tier(tmp_path):
+    """Local needs Ollama with its model; cloud needs an API key; rules needs nothing."""
+    settings = tmp_path / "settings.json"
+    environment = {key: value for key, value in os.environ.items() if key != 'OPENAI_API_KEY'}
+    environment['AIFO_CONFIG_DIR'] = str(tmp_path)
+    with patch.dict(os.environ, environment, clear=True), patch.object(providers, 'get_secret', return_value=None):
+        with patch.object(providers.OllamaProvider, 'detect',
+                          return_value={'running': True, 'version': None, 'model_installed': False}):
+            local = backend_check._check_ai(_config(), probe=True)
+        assert (local['status'], local['fix']) == ('warning', 'ollama pull llama3')
+        with patch.object(providers.OllamaProvider, 'detect', return_value={'running': False}):
+            assert 'ollama serve' in backend_check._check_ai(_config(), probe=True)['fix']
+
+        settings.write_text(json.dumps({'version': 1, 'default_tier': 'cloud'}))
+        cloud = backend_check._check_ai(_config(), probe=True)
+        assert cloud['status'] == 'error' and 'OPENAI_API_KEY' in cloud['fix']
+
+        settings.write_text(json.dumps({'version': 1, 'default_tier': 'rules'}))
+        assert backend_check._check_ai(_config(), probe=True)['status'] == 'ok'
@@ -0,0 +1,55 @@
+"""
+Unit tests for classifying many files with bounded concurrency.
+"""
+
+import sys
+import threading
+import time
+from pathlib import Path
+
+import pytest
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.batch_classify import classify_files, EVENT_CLASSIFY_PROGRESS, MAX_BATCH
+
+
+def test_results_keep_input_order_and_failures_do_not_stop_the_batch():
+    """Each path gets an outcome in the order given; a failing file is reported, not raised."""
+    def classify(path):
+        if path.endswith('locked.pdf'):
+            raise PermissionError('Permission denied')
+        time.sleep(0.05 if path.endswith('slow.pdf') else 0)
+        return {'category': 'Documents', 'path': path}
+
+    events = []
+    paths = ['/in/slow.pdf', '/in/locked.pdf', '/in/fast.pdf']
+    outcomes = classify_files(classify, paths, 3, lambda event, payload: events.append((event, payload)))
+
+    assert [o['path'] for o in outcomes] == paths
+    assert outcomes[0]['ok'] and outcomes[0]['result']['path'] == '/in/slow.pdf'
+    assert outcomes[1] == {'path': '/in/locked.pdf', 'ok': False, 'result': None, 'error': 'Permission denied'}
+    assert [e for e, _ in events] == [EVENT_CLASSIFY_PROGRESS] * 3
+    assert [p['done'] for _, p in events] == [1, 2, 3] and events[-1][1]['total'] == 3
+    assert events[-1][1]['file'] == '/in/slow.pdf'
+
+
+def test_no_more_than_max_concurrent_run_at_once():
+    """The concurrency limit holds, and oversized batches are refused."""
+    running, peak, lock = [0], [0], threading.Lock()
+
+    def classify(path):
+        with lock:
+            running[0] += 1
+            peak[0] = max(peak[0], running[0])
+        time.sleep(0.02)
+        with lock:
+            running[0] -= 1
+        return {}
+
+    assert len(classify_files(classify, [f'/in/{i}' for i in range(20)], max_concurrent=3)) == 20
+    assert peak[0] == 3
+    classify_files(classify, ['/in/a', '/in/b'], max_concurrent=0)
+    assert peak[0] == 3
+    with pytest.raises(ValueError):
+        classify_files(classify, ['/in/x'] * (MAX_BATCH + 1))
@@ -0,0 +1,83 @@
+"""
+Unit tests for burst and near-identical photo grouping.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.bursts import BurstDetector
+
+
+def _shot(folder, name, taken, size):
+    """A photo of a given size whose only date is its modification time."""
+    path = folder / name
+    path.write_bytes(b'\xff\xd8' + b'x' * size)
+    os.utime(path, (taken, taken))
+    return str(path)
+
+
+def _config(**settings):
+    config = Mock()
+    config.burst_settings = settings
+    return config
+
+
+def test_bursts_are_grouped_and_extras_archived_in_one_operation(tmp_path):
+    """Shots a second apart form a burst; the largest is kept and the rest archived together."""
+    start = 1_700_000_000
+    a = _shot(tmp_path, 'IMG_1.jpg', start, 100)
+    b = _shot(tmp_path, 'IMG_2.jpg', start + 1, 300)
+    c = _shot(tmp_path, 'IMG_3.jpg', start + 2.5, 200)
+    _shot(tmp_path, 'IMG_4.jpg', start + 30, 100)
+    (tmp_path / 'notes.txt').write_text('not a photo')
+
+    actions = MagicMock()
+    actions.archive_file.return_value = {'success': True}
+    detector = BurstDetector(_config(burst_seconds=2), actions)
+    with patch('src.core.bursts._pillow', return_value=None):
+        found = detector.find_groups(str(tmp_path))
+
+    assert found['success'] and found['photos'] == 4 and not found['perceptual']
+    assert len(found['groups']) == 1
+    group = found['groups'][0]
+    assert group['kind'] == 'burst' and [p['path'] for p in group['photos']] == [a, b, c]
+    assert group['best'] == b
+
+    preview = detector.resolve(found['groups'], 'archive', dry_run=True)
+    assert preview['removed'] == [a, c] and not actions.archive_file.called
+
+    group['keep'] = c
+    result = detector.resolve(found['groups'], 'archive', str(tmp_path / 'extras'))
+    assert result['success'] and result['kept'] == [c] and result['removed'] == [a, b]
+    actions.journal_scope.assert_called_once()
+    assert [call.args for call in actions.archive_file.call_args_list] == [
+        (a, str(tmp_path / 'extras')), (b, str(tmp_path / 'extras'))]
+
+    group['keep'] = str(tmp_path / 'elsewhere.jpg')
+    assert not detector.resolve(found['groups'], 'trash')['success']
+    assert not detector.resolve(found['groups'], 'shred')['success']
+
+
+def test_look_alike_shots_are_grouped_by_perceptual_hash(tmp_path):
+    """Shots further apart than a burst join when their hashes are close; the sharpest wins."""
+    start = 1_700_000_000
+    first = _shot(tmp_path, 'a.jpg', start, 100)
+    second = _shot(tmp_path, 'b.jpg', start + 20, 100)
+    other = _shot(tmp_path, 'c.jpg', start + 40, 100)
+    hashes = {first: 0b1111_0000, second: 0b1111_0001, other: 0xFFFF_FFFF_0000_0000}
+    scores = {first: 10.0, second: 50.0}
+
+    detector = BurstDetector(_config(burst_seconds=2, similar_seconds=60, max_distance=6))
+    with patch('src.core.bursts._pillow', return_value=object()), \
+         patch('src.core.bursts.perceptual_hash', side_effect=hashes.get), \
+         patch('src.core.bursts.sharpness', side_effect=scores.get):
+        found = detector.find_groups(str(tmp_path))
+
+    assert found['perceptual'] and len(found['groups']) == 1
+    group = found['groups'][0]
+    assert group['kind'] == 'similar' and group['best'] == second
+    assert {p['path'] for p in group['photos']} == {first, second}
@@ -0,0 +1,81 @@
+"""
+Unit tests for reusing AI classifications of unchanged files.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, MagicMock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.classification_cache import ClassificationCache
+from src.core.classifier import FileClassifier
+from src.core.db_manager import DatabaseManager
+
+
+@pytest.fixture
+def cache(tmp_path):
+    config = Mock()
+    config.classification_cache_settings = {'max_entries': 2}
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    yield ClassificationCache(config, db), tmp_path
+    db.cleanup()
+
+
+AI_RESULT = {'category': 'Finance', 'suggested_path': 'Finance/Acme/', 'rename': None,
+             'reason': 'Invoice from ACME', 'confidence': 'high', 'method': 'ai'}
+
+
+def test_unchanged_moved_and_changed_files(cache):
+    """Same file or same content is a hit; edited content is a miss; rule results are not stored."""
+    cache, tmp_path = cache
+    report = tmp_path / "report.pdf"
+    report.write_text("quarterly numbers")
+
+    assert cache.get(str(report)) is None
+    assert cache.put(str(report), AI_RESULT)
+    assert not cache.put(str(report), {**AI_RESULT, 'method': 'rule-based'})
+    hit = cache.get(str(report))
+    assert hit == {**AI_RESULT, 'cached': True}
+
+    moved = tmp_path / "moved.pdf"
+    os.replace(report, moved)
+    os.utime(moved, (1_000_000, 1_000_000))
+    assert cache.get(str(moved))['suggested_path'] == 'Finance/Acme/'
+
+    moved.write_text("quarterly numbers, revised")
+    assert cache.get(str(moved)) is None
+    assert cache.get(str(tmp_path / "missing.pdf")) is None
+
+    stats = cache.stats()
+    assert stats['total_hits'] == 2 and stats['session'] == {'hits': 2, 'misses': 2, 'stored': 1,
+                                                              'hit_rate': 0.5}
+    for name in ('a.pdf', 'b.pdf', 'c.pdf'):
+        (tmp_path / name).write_text(name)
+        cache.put(str(tmp_path / name), AI_RESULT)
+    assert cache.stats()['entries'] == 2
+    assert cache.clear()['removed'] == 2 and cache.stats()['entries'] == 0
+
+
+def test_classifier_asks_the_model_once_per_unchanged_file(cache):
+    """A second classification of the same file comes from the cache, not the AI backend."""
+    cache, tmp_path = cache
+    document = tmp_path / "notes.qqq"
+    document.write_text("meeting notes")
+    config = MagicMock()
+    config.destination_rules = {}
+    config.enable_ai = True
+    config.locale = 'en'
+    classifier = FileClassifier(config, MagicMock())
+    classifier.cache = cache
+    classifier._classify_by_agent = Mock(return_value=None)
+    classifier._classify_by_ai = Mock(return_value={'success': True, 'category': 'Notes',
+                                                    'suggested_path': 'Documents/Notes/'})
+
+    first = classifier.classify(str(document))
+    second = classifier.classify(str(document))
+    assert first['method'] == 'ai' and second['suggested_path'] == 'Documents/Notes/'
+    assert second['cached'] and classifier._classify_by_ai.call_count == 1
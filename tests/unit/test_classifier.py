@@ -215,6 +215,28 @@ class TestAIClassification:
             # Should only use rule-based when AI disabled
             assert result['method'] == 'rule-based'
 
+    def test_tier_routes_ai_classification(self, classifier):
+        """Test that the "rules" tier uses no AI and "local" never reaches a cloud provider."""
+        classifier.ollama_client.name = 'openai'
+        classifier.config.ollama_base_url = 'http://localhost:11434'
+        classifier.config.ollama_model = 'llama3'
+        classifier.config.get = Mock(return_value=30)
+        classifier.settings = Mock()
+        file_info = {'filename': 'notes.xyz', 'extension': '.xyz', 'size': 10}
+
+        classifier.settings.load.return_value = {'default_tier': 'cloud'}
+        assert classifier._classify_by_ai(file_info)['success'] is True
+
+        classifier.settings.load.return_value = {'default_tier': 'rules'}
+        assert classifier._classify_by_ai(file_info)['error'] == 'No AI client available'
+
+        classifier.settings.load.return_value = {'default_tier': 'local'}
+        with patch('ai.ollama_client.OllamaClient.classify_file',
+                   return_value={'success': True, 'category': 'Notes', 'provider': 'ollama'}) as local:
+            assert classifier._classify_by_ai(file_info)['provider'] == 'ollama'
+        local.assert_called_once()
+        assert classifier.ollama_client.classify_file.call_count == 1
+
 
 class TestAgentClassification:
     """Test agent-powered deep analysis."""
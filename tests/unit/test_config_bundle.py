@@ -0,0 +1,111 @@
+"""
+Unit tests for configuration bundle export/import.
+"""
+
+import json
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.config_bundle import ConfigBundleManager, redact_secrets
+from src.core.settings import SettingsStore
+from src.config import Config
+from src.utils.error_handler import ConfigurationError
+
+
+@pytest.fixture
+def config(tmp_path):
+    """Create a real Config backed by a temporary config.json."""
+    config_file = tmp_path / "config.json"
+    config_file.write_text(json.dumps({
+        'watched_folders': ['/tmp/in'],
+        'ollama_model': 'llama3',
+        'base_destination': '/tmp/out',
+        'dry_run': True,
+        'ai': {'openai_api_key': 'sk-local-secret', 'provider': 'ollama'}
+    }))
+    (tmp_path / "config.yaml").write_text("rules:\n  - name: pdfs\n    actions: [move]\n")
+    return Config(str(config_file))
+
+
+def test_export_excludes_secrets(config, tmp_path):
+    """Exported bundle contains settings and rules but no secrets."""
+    manager = ConfigBundleManager(config)
+    result = manager.export_bundle(str(tmp_path / "bundle.json"))
+
+    assert result['success']
+    bundle = json.loads((tmp_path / "bundle.json").read_text())
+    assert bundle['sections']['settings']['ai'] == {'provider': 'ollama'}
+    assert bundle['sections']['rules']['rules'][0]['name'] == 'pdfs'
+    assert 'sk-local-secret' not in (tmp_path / "bundle.json").read_text()
+
+
+def test_preview_then_apply(config):
+    """Preview lists changes without applying; apply merges and keeps local secrets."""
+    manager = ConfigBundleManager(config)
+    bundle = manager.build_bundle()
+    bundle['sections']['settings']['dry_run'] = False
+    bundle['sections']['settings']['locale'] = 'de'
+
+    preview = manager.preview_import(bundle)
+    keys = {(c['key'], c['action']) for c in preview['changes']}
+    assert keys == {('dry_run', 'changed'), ('locale', 'added')}
+    assert config.dry_run is True
+
+    result = manager.apply_import(bundle)
+    assert result['success']
+    assert result['backups']
+
+    reloaded = Config(str(config.config_path))
+    assert reloaded.dry_run is False
+    assert reloaded.locale == 'de'
+    assert reloaded.get('ai.openai_api_key') == 'sk-local-secret'
+
+
+def test_user_settings_travel_in_a_zip_and_merge_by_name(config, tmp_path):
+    """Rules, profiles and categories replace same-named local ones and are added otherwise; API keys stay."""
+    source = SettingsStore(str(tmp_path / "source" / "settings.json"), use_keychain=False)
+    source.update_settings({
+        'api_keys': {'openai': 'sk-do-not-share'}, 'exclusions': ['*.tmp'],
+        'rules': [{'name': 'Invoices', 'extensions': ['pdf'], 'destination': 'Finance'}],
+        'profiles': [{'name': 'photos', 'tier': 'rules'}],
+        'taxonomy': [{'name': 'Documents'}, {'name': 'Invoices', 'parent': 'Documents'}]})
+    result = ConfigBundleManager(config, settings=source).export_configuration(str(tmp_path / "setup.zip"))
+    assert result['success'] and {'user_settings', 'user_rules', 'profiles', 'taxonomy'} <= set(result['sections'])
+    assert b'sk-do-not-share' not in (tmp_path / "setup.zip").read_bytes()
+
+    target = SettingsStore(str(tmp_path / "target" / "settings.json"), use_keychain=False)
+    target.update_settings({'exclusions': ['*.bak'], 'rules': [
+        {'name': 'invoices', 'extensions': ['txt'], 'destination': 'Old'},
+        {'name': 'Music', 'extensions': ['mp3'], 'destination': 'Music'}]})
+    imported = ConfigBundleManager(config, settings=target).import_configuration(
+        str(tmp_path / "setup.zip"), sections=['user_settings', 'user_rules', 'profiles', 'taxonomy'])
+    assert imported['success'] and any(b.startswith(f"{target.path}.") for b in imported['backups'])
+    assert [(rule['name'], rule['destination']) for rule in target.get('rules')] == [
+        ('Invoices', 'Finance'), ('Music', 'Music')]
+    assert target.get('exclusions') == ['*.bak', '*.tmp'] and target.get('api_keys') == {}
+    assert [p['name'] for p in target.get('profiles')] == ['photos']
+    assert [(c['name'], c['parent']) for c in target.get('taxonomy')] == [('Documents', None),
+                                                                         ('Invoices', 'Documents')]
+
+    broken = ConfigBundleManager(config, settings=target).build_bundle()
+    broken['sections']['taxonomy'] = {'Taxes': {'parent': 'Finance'}}
+    failed = ConfigBundleManager(config, settings=target).apply_import(broken, sections=['taxonomy'])
+    assert not failed['success'] and "no parent category named 'Finance'" in failed['message']
+
+
+def test_rejects_foreign_files(config, tmp_path):
+    """Files that are not bundles are refused."""
+    other = tmp_path / "other.json"
+    other.write_text(json.dumps({'hello': 'world'}))
+
+    with pytest.raises(ConfigurationError):
+        ConfigBundleManager(config).load_bundle(str(other))
+
+
+def test_redact_secrets_nested():
+    """Secrets are removed at any depth, ordinary keys are kept."""
+    values = {'license': {'license_key': 'X', 'offline_mode': True}, 'token': 't', 'hash_algorithm': 'sha1'}
+    assert redact_secrets(values) == {'license': {'offline_mode': True}, 'hash_algorithm': 'sha1'}
@@ -0,0 +1,104 @@
+"""
+Unit tests for conflicts: taken destinations set aside by the plan applier and resolved by the user.
+"""
+
+import os
+import time
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock, MagicMock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.conflicts import ConflictManager
+from src.core.plans import OrganizePlanner
+
+
+@pytest.fixture
+def config(config):
+    """Asks on conflict."""
+    config.conflict_resolution = 'ask'
+    return config
+
+
+@pytest.fixture
+def planner(config, action_manager, tmp_path):
+    """A planner asking on conflict, with report.pdf already organized once."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    (downloads / "report.pdf").write_text("new report")
+    (downloads / "notes.pdf").write_text("notes")
+    organized = tmp_path / "organized" / "Documents"
+    organized.mkdir(parents=True)
+    (organized / "report.pdf").write_text("old report")
+    old = time.time() - 3600
+    os.utime(organized / "report.pdf", (old, old))
+
+    classifier = Mock()
+    classifier.classify.return_value = {'category': 'Documents', 'suggested_path': 'Documents/',
+                                        'confidence': 'high', 'method': 'rule-based'}
+    engine = OrganizePlanner(config, classifier, action_manager)
+    engine.conflicts.mover._guardian = MagicMock(is_file_safe_to_modify=Mock(return_value=(True, '')))
+    return engine, downloads, organized
+
+
+def test_apply_sets_taken_destinations_aside_and_overwrite_if_newer_replaces(planner):
+    """Nothing is renamed or overwritten on apply; the newer source replaces the older file, and undo brings it back."""
+    engine, downloads, organized = planner
+    engine.conflicts.notifier = Mock()
+
+    result = engine.apply_plan(engine.build_plan(str(downloads)))
+    assert [Path(entry['new_path']).name for entry in result['applied']] == ['notes.pdf']
+    [conflict] = result['conflicts']
+    assert (conflict['source'], conflict['destination']) == (str(downloads / "report.pdf"),
+                                                             str(organized / "report.pdf"))
+    assert conflict['source_is_newer'] and (downloads / "report.pdf").exists()
+    assert not (organized / "report_1.pdf").exists() and (organized / "report.pdf").read_text() == "old report"
+    engine.conflicts.notifier.conflict.assert_called_once_with(1, str(downloads))
+    assert [c.id for c in engine.conflicts.list_conflicts()] == [conflict['id']]
+
+    with pytest.raises(ValueError, match="Unknown conflict strategy"):
+        engine.conflicts.resolve_conflict(conflict['id'], 'rename')
+    with pytest.raises(ValueError, match="merged"):
+        engine.conflicts.resolve_conflict(conflict['id'], 'merge-folders')
+    resolved = engine.conflicts.resolve_conflict(conflict['id'], 'overwrite-if-newer')
+    assert resolved['success'] and resolved['new_path'] == str(organized / "report.pdf")
+    assert (organized / "report.pdf").read_text() == "new report" and not (downloads / "report.pdf").exists()
+    assert engine.conflicts.list_conflicts() == []
+    with pytest.raises(ValueError, match="already resolved"):
+        engine.conflicts.resolve_conflict(conflict['id'], 'skip')
+
+    assert engine.action_manager.journal.undo(resolved['operation_id'])['success']
+    assert (downloads / "report.pdf").read_text() == "new report"
+    assert (organized / "report.pdf").read_text() == "old report"
+
+
+def test_keep_both_skip_and_merge_folders(db, tmp_path):
+    """Keep both takes the next free name, skip moves nothing, and merging leaves clashing files as new conflicts."""
+    conflicts = ConflictManager(Mock(trash_dir=str(tmp_path / "trash")), db)
+    conflicts.mover._guardian = MagicMock(is_file_safe_to_modify=Mock(return_value=(True, '')))
+    source, target = tmp_path / "inbox", tmp_path / "Projects"
+    for folder in (source / "site" / "img", target / "site"):
+        folder.mkdir(parents=True)
+    (source / "plan.txt").write_text("new plan")
+    (target / "plan.txt").write_text("plan")
+    (source / "site" / "index.html").write_text("new index")
+    (source / "site" / "img" / "logo.png").write_text("logo")
+    (target / "site" / "index.html").write_text("index")
+
+    kept = conflicts.resolve_conflict(conflicts.record(str(source / "plan.txt"), str(target / "plan.txt")).id,
+                                      'keep_both')
+    assert kept['new_path'] == str(target / "plan (2).txt") and (target / "plan.txt").read_text() == "plan"
+
+    skipped_id = conflicts.record(str(source / "site" / "index.html"), str(target / "site" / "index.html")).id
+    assert conflicts.resolve_conflict(skipped_id, 'skip')['new_path'] is None
+    assert (source / "site" / "index.html").exists()
+
+    merged = conflicts.resolve_conflict(conflicts.record(str(source / "site"), str(target / "site")).id,
+                                        'merge_folders')
+    assert merged['success'] and (target / "site" / "img" / "logo.png").read_text() == "logo"
+    assert [(c['source'], c['destination']) for c in merged['conflicts']] == [
+        (str(source / "site" / "index.html"), str(target / "site" / "index.html"))]
+    assert not (source / "site" / "img").exists() and (source / "site" / "index.html").exists()
+    assert [c.id for c in conflicts.list_conflicts()] == [merged['conflicts'][0]['id']]
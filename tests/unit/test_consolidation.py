@@ -0,0 +1,68 @@
+"""
+Unit tests for consolidation runs.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.consolidation import ConsolidationPlanner, ConsolidationRule
+
+
+@pytest.fixture
+def scattered(config, db, action_manager, tmp_path):
+    """Tax documents spread over two folders, and a planner with an action manager."""
+    desktop, downloads = tmp_path / "desktop", tmp_path / "downloads"
+    (desktop / "misc").mkdir(parents=True)
+    downloads.mkdir()
+    (desktop / "tax-return-2023.pdf").write_text("2023")
+    (desktop / "misc" / "Tax_receipt_2024.pdf").write_text("2024")
+    (downloads / "tax-notes.txt").write_text("notes")
+    (downloads / "holiday.pdf").write_text("holiday")
+
+    config.workspaces = {}
+    config.consolidation_rules = [{
+        'name': 'taxes',
+        'sources': [str(desktop), str(downloads), str(tmp_path / "organized")],
+        'keywords': ['tax'],
+        'extensions': ['pdf'],
+        'destination': 'Finance/Taxes/{year}'
+    }]
+    return ConsolidationPlanner(config, db, action_manager=action_manager), desktop, tmp_path
+
+
+def test_rule_validation():
+    """Rules need a safe destination template, something to match and somewhere to look."""
+    assert ConsolidationRule.from_dict({'sources': ['x'], 'keywords': ['a'], 'destination': '../out'}).validate()
+    assert ConsolidationRule.from_dict({'sources': ['x'], 'keywords': ['a'], 'destination': 'Out/{yr}'}).validate()
+    assert ConsolidationRule.from_dict({'sources': ['x'], 'destination': 'Out'}).validate()
+    assert ConsolidationRule.from_dict({'sources': ['x'], 'extensions': ['PDF'], 'destination': 'Out/{year}'}).validate() is None
+
+
+def test_plan_and_undo(scattered):
+    """Matching files land under their year and the whole run is undone in one step."""
+    planner, desktop, tmp_path = scattered
+    already = tmp_path / "organized" / "Finance" / "Taxes" / "2022"
+    already.mkdir(parents=True)
+    (already / "tax-2022.pdf").write_text("2022")
+
+    plan = planner.plan(planner.get_rule('taxes'))
+
+    assert plan['success'], plan['message']
+    assert {Path(i['file']).name: i['suggested_path'] for i in plan['items']} == {
+        'tax-return-2023.pdf': 'Finance/Taxes/2023/',
+        'Tax_receipt_2024.pdf': 'Finance/Taxes/2024/'
+    }
+
+    result = planner.apply(plan)
+
+    assert result['success'], result['message']
+    taxes = tmp_path / "organized" / "Finance" / "Taxes"
+    assert (taxes / "2023" / "tax-return-2023.pdf").exists() and (taxes / "2024" / "Tax_receipt_2024.pdf").exists()
+
+    stack = planner.action_manager.journal.undo_stack()
+    assert stack[0]['id'] == result['operation_id'] and stack[0]['kind'] == 'consolidate'
+    assert planner.action_manager.undo_last_action()['success']
+    assert (desktop / "tax-return-2023.pdf").exists() and (desktop / "misc" / "Tax_receipt_2024.pdf").exists()
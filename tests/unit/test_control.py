@@ -0,0 +1,98 @@
+"""
+Unit tests for the daemon control socket.
+"""
+
+import os
+import sys
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.control import (
+    ControlServer, ControlClient, ControlError, is_running, files_to_open, forward_to_running,
+    METHOD_NOT_FOUND, INVALID_PARAMS, METHOD_FAILED, UNAVAILABLE
+)
+
+
+@pytest.fixture
+def server(tmp_path):
+    """An engine with a couple of methods on a socket in a temporary folder."""
+    if sys.platform == 'win32':
+        pytest.skip("Unix socket test")
+    address = str(tmp_path / "control.sock")
+
+    def fail():
+        raise RuntimeError("disk on fire")
+
+    def missing(path):
+        raise FileNotFoundError(2, 'No such file or directory', path)
+
+    control = ControlServer(address, {'add': lambda a, b=1: a + b, 'fail': fail, 'missing': missing})
+    control.start()
+    yield control
+    control.stop()
+
+
+def test_calls_and_errors(server):
+    """Results come back by method; errors carry JSON-RPC style codes."""
+    with ControlClient(server.address) as client:
+        assert client.call('ping')['pong'] is True
+        assert client.call('add', a=2) == 3 and client.call('add', a=2, b=5) == 7
+        assert 'add' in client.call('methods')
+
+        for method, params, code in (('nope', {}, METHOD_NOT_FOUND), ('add', {'c': 1}, INVALID_PARAMS),
+                                     ('fail', {}, METHOD_FAILED)):
+            with pytest.raises(ControlError) as error:
+                client.call(method, **params)
+            assert error.value.code == code
+        assert client.call('add', a=1) == 2  # The connection survives errors
+
+        with pytest.raises(ControlError) as error:
+            client.call('missing', path='/nowhere/report.pdf')
+        assert error.value.error.to_dict() == {'code': 'not_found', 'message': 'No such file or directory: /nowhere/report.pdf',
+                                               'details': {'path': '/nowhere/report.pdf'}}
+
+        changed = client.call('log_level', level='DEBUG', logger='tests.control')
+        assert changed['changed']['level'] == 'DEBUG' and changed['levels']['tests.control'] == 'DEBUG'
+        client.call('log_level', level='NOTSET', logger='tests.control')
+
+    assert oct(os.stat(server.address).st_mode & 0o777) == '0o600'
+
+
+def test_single_engine_per_address(server):
+    """A second engine cannot take over the socket; shutdown frees it."""
+    with pytest.raises(RuntimeError):
+        ControlServer(server.address).start()
+
+    with ControlClient(server.address) as client:
+        assert client.call('shutdown') == {'stopping': True}
+    server._thread.join(timeout=5)
+
+    assert not is_running(server.address)
+    with pytest.raises(ControlError) as error:
+        ControlClient(server.address).connect()
+    assert error.value.code == UNAVAILABLE
+
+
+def test_second_launch_forwards_paths(tmp_path):
+    """A second launch hands its files to the running instance, or finds none."""
+    if sys.platform == 'win32':
+        pytest.skip("Unix socket test")
+    address = str(tmp_path / "app.sock")
+    assert forward_to_running(address, ["anything"]) is None
+
+    (tmp_path / "inbox").mkdir()
+    (tmp_path / "inbox" / "a.pdf").write_text("a")
+    (tmp_path / "inbox" / ".hidden").write_text("h")
+    (tmp_path / "b.txt").write_text("b")
+    received = []
+    control = ControlServer(address, {'open': lambda paths: received.extend(paths) or {'queued': files_to_open(paths)}})
+    control.start()
+    try:
+        reply = forward_to_running(address, [str(tmp_path / "inbox"), str(tmp_path / "b.txt")])
+    finally:
+        control.stop()
+
+    assert received == [str((tmp_path / "inbox").resolve()), str((tmp_path / "b.txt").resolve())]
+    assert reply['queued'] == [str((tmp_path / "inbox" / "a.pdf").resolve()), str((tmp_path / "b.txt").resolve())]
@@ -0,0 +1,80 @@
+"""
+Unit tests for learning destinations from user corrections.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.corrections import CorrectionLearner, name_tokens
+from src.core.plans import OrganizePlanner
+
+
+@pytest.fixture
+def config(config):
+    """Learns a destination for similar files after two corrections."""
+    config.corrections_settings = {'min_repeats': 2}
+    return config
+
+
+@pytest.fixture
+def downloads(tmp_path):
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    return downloads
+
+
+def test_repeated_or_identical_corrections_stick(config, db, downloads, tmp_path):
+    """One correction is not enough for a similar file, two are; the same content sticks at once."""
+    learner = CorrectionLearner(config, db)
+    assert name_tokens('Invoice_ACME-2024-03.pdf') == ['acme', 'invoice']
+
+    for i, name in enumerate(('invoice-acme-01.pdf', 'invoice-acme-02.pdf', 'invoice-acme-03.pdf')):
+        (downloads / name).write_text(f"invoice {i}")
+    suggested = {'category': 'Documents', 'suggested_path': 'Documents/'}
+    assert learner.record(str(downloads / 'invoice-acme-01.pdf'), 'Finance/Acme', 'Finance', suggested)['success']
+    assert learner.suggest(str(downloads / 'invoice-acme-03.pdf')) is None
+
+    same = learner.suggest(str(downloads / 'invoice-acme-01.pdf'))
+    assert same['method'] == 'learned' and same['suggested_path'] == 'Finance/Acme/'
+
+    learner.record(str(downloads / 'invoice-acme-02.pdf'), str(tmp_path / 'organized' / 'Finance' / 'Acme'),
+                   suggested=suggested)
+    learned = learner.suggest(str(downloads / 'invoice-acme-03.pdf'))
+    assert learned['suggested_path'] == 'Finance/Acme/' and learned['category'] == 'Finance'
+    assert learned['corrections'] == 2 and learned['confidence'] == 'high'
+
+    (downloads / 'holiday.jpg').write_text('photo')
+    assert learner.suggest(str(downloads / 'holiday.jpg')) is None
+    outside = learner.destination_for('/srv/archive/Receipts')
+    assert outside == {'suggested_path': 'Receipts/', 'destination_root': '/srv/archive'}
+
+    assert len(learner.list()) == 2 and learner.forget(learner.list()[0]['id'])['deleted'] == 1
+    assert not learner.forget(999)['success']
+    assert learner.suggest(str(downloads / 'invoice-acme-03.pdf')) is None
+
+
+def test_plan_overrides_are_applied_and_learned_before_classifying(config, action_manager, downloads):
+    """An override in a plan moves the file there and steers the next plan without asking the classifier."""
+    classifier = Mock()
+    classifier.classify.return_value = {'category': 'Documents', 'suggested_path': 'Documents/',
+                                        'confidence': 'high', 'method': 'rule-based'}
+    planner = OrganizePlanner(config, classifier, action_manager)
+
+    for round_ in range(2):
+        (downloads / f"payslip-{round_}.pdf").write_text(f"pay {round_}")
+        plan = planner.build_plan(str(downloads))
+        plan['items'][0]['override'] = {'destination': 'Finance/Payslips', 'category': 'Finance'}
+        result = planner.apply_plan(plan)
+        assert result['success'] and result['applied'][0]['new_path'].endswith(f"Finance/Payslips/payslip-{round_}.pdf")
+
+    (downloads / 'payslip-2.pdf').write_text('pay 2')
+    classifier.classify.reset_mock()
+    plan = planner.build_plan(str(downloads))
+    assert plan['items'][0]['classification']['method'] == 'learned'
+    assert plan['items'][0]['destination'].endswith('Finance/Payslips/payslip-2.pdf')
+    assert not classifier.classify.called
@@ -0,0 +1,70 @@
+"""
+Unit tests for opt-in crash reporting.
+"""
+
+import json
+import sys
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.crash_reports import CrashReporter
+from src.config import Config
+
+
+def make_reporter(tmp_path, **settings):
+    config = Mock(spec=Config)
+    config.crash_report_settings = {'enabled': True, **settings}
+    return CrashReporter(config, store_dir=str(tmp_path / "crashes"))
+
+
+def crash(message):
+    try:
+        raise ValueError(message)
+    except ValueError:
+        return sys.exc_info()
+
+
+def test_reports_are_sanitized_unless_paths_are_allowed(tmp_path):
+    """User file paths never reach a report without consent; app frames stay readable."""
+    reporter = make_reporter(tmp_path)
+    report_id = reporter.capture(*crash("cannot move /home/alice/Taxes/2024.pdf to C:\\Users\\alice\\Docs"))
+
+    report = reporter.get_report(report_id)
+    assert report['exception']['type'] == 'builtins.ValueError'
+    assert report['exception']['message'] == 'cannot move <path> to <path>'
+    assert report['frames'][-1] == {'file': 'tests/unit/test_crash_reports.py', 'line': report['frames'][-1]['line'],
+                                    'function': 'crash'}
+    assert reporter.list_reports()[0]['id'] == report_id
+
+    consenting = make_reporter(tmp_path / "consent", include_paths=True)
+    kept = consenting.get_report(consenting.capture(*crash("bad /home/alice/file.txt")))
+    assert kept['exception']['message'] == 'bad /home/alice/file.txt' and kept['paths_included']
+
+
+def test_native_dumps_and_sending(tmp_path):
+    """Dumps left by a native crash become reports; sending needs an endpoint and marks the report."""
+    reporter = make_reporter(tmp_path, endpoint='https://reports.example/crash')
+    (tmp_path / "crashes").mkdir()
+    (tmp_path / "crashes" / "native-999999.dump").write_text(
+        'Fatal Python error: Segmentation fault\n  File "/home/alice/notes.txt", line 1\n')
+
+    [report_id] = reporter.collect_native_dumps()
+    report = reporter.get_report(report_id)
+    assert report['kind'] == 'native_crash' and 'alice' not in report['stacks']
+    assert not list((tmp_path / "crashes").glob('*.dump'))
+
+    response = MagicMock(status=200)
+    response.__enter__.return_value = response
+    with patch('src.core.crash_reports.urllib.request.urlopen', return_value=response) as urlopen:
+        result = reporter.send_report(report_id)
+    assert result['success'] and reporter.get_report(report_id)['sent_at']
+    assert json.loads(urlopen.call_args[0][0].data)['id'] == report_id
+
+    assert not make_reporter(tmp_path).send_report(report_id)['success']  # No endpoint configured
+    assert not reporter.send_report('../../etc/passwd')['success']
+
+    disabled = Mock(spec=Config)
+    disabled.crash_report_settings = {}
+    assert CrashReporter(disabled, store_dir=str(tmp_path / "off")).install() is False
@@ -0,0 +1,82 @@
+"""
+Unit tests for finding and resolving duplicate files.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.dedupe import Deduplicator, HEAD_BYTES
+from src.core.db_manager import DatabaseManager
+from src.core.journal import OperationJournal
+
+
+@pytest.fixture
+def setup(tmp_path):
+    config = Mock()
+    config.hash_algorithm = 'sha1'
+    config.trash_dir = str(tmp_path / "trash")
+    config.path_blacklist = []
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    folder = tmp_path / "photos"
+    (folder / "2024").mkdir(parents=True)
+    (folder / "Downloads").mkdir()
+    yield Deduplicator(config, db), OperationJournal(db), folder
+    db.cleanup()
+
+
+def test_only_same_size_and_head_files_are_hashed(setup):
+    """Unique sizes are never read in full; files differing after their head are not duplicates."""
+    deduplicator, _, folder = setup
+    big = os.urandom(HEAD_BYTES + 10)
+    (folder / "2024" / "trip.jpg").write_bytes(big)
+    (folder / "Downloads" / "trip (1).jpg").write_bytes(big)
+    (folder / "2024" / "other.jpg").write_bytes(big[:-1] + b'x')   # same size and head, other content
+    (folder / "2024" / "a.txt").write_text("same words")
+    (folder / "Downloads" / "a copy.txt").write_text("same words")
+    (folder / "2024" / "unique.txt").write_text("nothing else is this long")
+    (folder / ".hidden.txt").write_text("same words")
+
+    found = deduplicator.find_duplicates(str(folder), 'blake2b')
+    assert found['success'] and found['scanned'] == 6 and found['hashed'] == 5
+    assert [group['count'] for group in found['groups']] == [2, 2]
+    photos = found['groups'][0]
+    assert photos['size'] == HEAD_BYTES + 10 and photos['algorithm'] == 'blake2b'
+    assert photos['keep'] == str(folder / "2024" / "trip.jpg")
+    assert photos['delete'] == [str(folder / "Downloads" / "trip (1).jpg")]
+    assert found['summary']['total_duplicate_files'] == 2
+
+    with pytest.raises(ValueError):
+        deduplicator.find_duplicates(str(folder), 'crc7')
+    assert not deduplicator.find_duplicates(str(folder / "missing"))['success']
+
+
+def test_resolve_trashes_or_links_copies_and_undo_restores_them(setup):
+    """Copies go to the trash (or become hard links) in one operation; changed files are left alone."""
+    deduplicator, journal, folder = setup
+    keep, copy, changed = folder / "2024" / "a.txt", folder / "Downloads" / "a.txt", folder / "Downloads" / "b.txt"
+    for path in (keep, copy, changed):
+        path.write_text("same words")
+    groups = deduplicator.find_duplicates(str(folder))['groups']
+    assert groups[0]['keep'] == str(keep)
+    changed.write_text("new words!")
+
+    preview = deduplicator.resolve_duplicates(groups, 'hardlink', dry_run=True)
+    assert preview['dry_run'] and len(preview['resolved']) == 1 and copy.read_text() == 'same words'
+    assert preview['skipped'] == [{'path': str(changed), 'reason': 'changed since the scan'}]
+
+    linked = deduplicator.resolve_duplicates(groups, 'hardlink')
+    assert linked['success'] and linked['operation_id'] and linked['space_freed'] == len("same words")
+    assert os.path.samefile(keep, copy) and changed.read_text() == 'new words!'
+    assert journal.undo(linked['operation_id'])['success']
+    assert not os.path.samefile(keep, copy) and copy.read_text() == 'same words'
+
+    assert not deduplicator.resolve_duplicates(groups, 'move')['success']
+    trashed = deduplicator.resolve_duplicates(groups, 'trash')
+    assert not copy.exists() and Path(trashed['resolved'][0]['to']).read_text() == 'same words'
+    assert deduplicator.resolve_duplicates(groups, 'trash')['skipped'][0]['reason'].startswith('gone')
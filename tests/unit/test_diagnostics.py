@@ -0,0 +1,56 @@
+"""
+Unit tests for the diagnostics bundle.
+"""
+
+import json
+import sys
+import zipfile
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.diagnostics import DiagnosticsExporter
+from src.core.db_manager import DatabaseManager
+from src.core.journal import OperationJournal
+from src.config import Config
+
+
+def test_bundle_holds_logs_environment_redacted_settings_and_jobs(tmp_path):
+    """One zip with everything a bug report needs, and no secrets."""
+    config = Mock(spec=Config)
+    config.ollama_base_url = 'http://localhost:11434'
+    config.ollama_model = 'llama3'
+    config.crash_report_settings = {'store_dir': str(tmp_path / "crashes")}
+    config.as_dict.return_value = {
+        'watched_folders': ['~/Downloads'],
+        'license': {'license_key': 'ABCD-EFGH-IJKL-MNOP', 'offline_mode': True},
+        'ai': {'api_key': 'sk-secret'}
+    }
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    journal = OperationJournal(db)
+    journal.complete(journal.begin('organize', 'Organize Downloads'))
+    logs = tmp_path / "logs"
+    logs.mkdir()
+    (logs / "app.log").write_text('{"level": "ERROR", "message": "stuck job"}\n')
+    (logs / "app.log.1").write_text("older\n")
+
+    with patch('src.core.diagnostics.MAX_LOG_BYTES', 8):
+        result = DiagnosticsExporter(config, db, log_dirs=[str(logs)]).export(str(tmp_path / "diag.zip"))
+
+    assert result['success']
+    with zipfile.ZipFile(result['path']) as bundle:
+        names = set(bundle.namelist())
+        assert {'manifest.json', 'environment.json', 'settings.json', 'jobs.json',
+                'crash_reports.json', 'logs/app.log', 'logs/app.log.1'} <= names
+
+        settings = bundle.read('settings.json').decode()
+        assert 'ABCD-EFGH' not in settings and 'sk-secret' not in settings
+        assert json.loads(settings)['license'] == {'offline_mode': True}
+
+        environment = json.loads(bundle.read('environment.json'))
+        assert environment['app_version'] and environment['python']['version']
+        assert json.loads(bundle.read('jobs.json'))[0]['description'] == 'Organize Downloads'
+        trimmed = bundle.read('logs/app.log').decode()
+        assert trimmed.startswith('[trimmed') and trimmed.endswith('job"}\n')
+    db.cleanup()
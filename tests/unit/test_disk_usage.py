@@ -0,0 +1,61 @@
+"""
+Unit tests for the disk usage analyzer.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core import disk_usage
+from src.core.disk_usage import analyze_disk_usage
+
+
+def _write(path: Path, size: int) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_bytes(b'x' * size)
+
+
+def test_tree_sums_folders_and_stops_branching_at_depth(tmp_path):
+    """Sizes add up through the tree; folders below the depth are counted but not broken down."""
+    _write(tmp_path / 'movie.mkv', 5000)
+    _write(tmp_path / 'Projects' / 'site' / 'node_modules' / 'lib.js', 3000)
+    _write(tmp_path / 'Projects' / 'site' / 'index.html', 100)
+    _write(tmp_path / 'Photos' / 'a.jpg', 1200)
+    os.link(tmp_path / 'Photos' / 'a.jpg', tmp_path / 'Photos' / 'copy.jpg')   # Counted once
+
+    result = analyze_disk_usage(str(tmp_path), depth=2)
+    tree = result['tree']
+    assert (result['size'], result['files'], result['dirs']) == (9300, 4, 4)
+    assert [child['name'] for child in tree['children']] == ['movie.mkv', 'Projects', 'Photos']
+    projects = tree['children'][1]
+    site = projects['children'][0]
+    assert (projects['size'], site['size'], site['dirs']) == (3100, 3100, 1)
+    assert 'children' not in site   # Below depth 2
+    assert result['largest_files'][0] == {'path': str(tmp_path / 'movie.mkv'), 'size': 5000}
+
+    with pytest.raises(NotADirectoryError):
+        analyze_disk_usage(str(tmp_path / 'missing'))
+
+
+def test_totals_by_category_and_collapsed_small_files(tmp_path, monkeypatch):
+    """Categories come from the destination rules; a folder's small files share one entry."""
+    monkeypatch.setattr(disk_usage, 'MAX_FILES_PER_FOLDER', 2)
+    for number, size in enumerate((400, 300, 20, 10)):
+        _write(tmp_path / 'scans' / f"page{number}.pdf", size)
+    _write(tmp_path / 'song.mp3', 900)
+    _write(tmp_path / 'README', 5)
+    config = Mock(destination_rules={'pdf': 'Documents/PDFs/', 'mp3': 'Music/'})
+
+    result = analyze_disk_usage(str(tmp_path), config=config)
+    assert result['by_category'] == [{'category': 'Music', 'size': 900, 'files': 1},
+                                     {'category': 'Documents', 'size': 730, 'files': 4},
+                                     {'category': 'Other', 'size': 5, 'files': 1}]
+    assert result['by_extension'][-1] == {'extension': '(none)', 'size': 5, 'files': 1}
+    scans = next(child for child in result['tree']['children'] if child['name'] == 'scans')
+    assert [(child['name'], child['size']) for child in scans['children']] == [
+        ('page0.pdf', 400), ('page1.pdf', 300), ('(2 smaller files)', 30)]
@@ -0,0 +1,99 @@
+"""
+Unit tests for ebook metadata extraction and organizing.
+"""
+
+import struct
+import sys
+import zipfile
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.ebooks import EbookLibraryStrategy, normalize_isbn, read_ebook_metadata
+
+CONTAINER = ('<?xml version="1.0"?><container xmlns="urn:oasis:names:tc:opendocument:xmlns:container">'
+             '<rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>')
+
+
+def _epub(path, title, author, isbn):
+    opf = ('<?xml version="1.0"?><package xmlns="http://www.idpf.org/2007/opf" '
+           'xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">'
+           f'<metadata><dc:title>{title}</dc:title><dc:creator opf:role="aut">{author}</dc:creator>'
+           '<dc:creator opf:role="ill">Alan Lee</dc:creator>'
+           f'<dc:identifier opf:scheme="ISBN">{isbn}</dc:identifier><dc:date>1937-09-21</dc:date>'
+           '</metadata></package>')
+    with zipfile.ZipFile(path, 'w') as epub:
+        epub.writestr('mimetype', 'application/epub+zip')
+        epub.writestr('META-INF/container.xml', CONTAINER)
+        epub.writestr('OEBPS/content.opf', opf)
+    return path
+
+
+def _mobi(path, title, author, isbn):
+    """A PalmDB with one record holding a MOBI header and EXTH records."""
+    exth_records = b''.join(struct.pack('>LL', kind, 8 + len(value)) + value
+                            for kind, value in ((100, author.encode()), (104, isbn.encode())))
+    exth = b'EXTH' + struct.pack('>LL', 12 + len(exth_records), 2) + exth_records
+    mobi_length = 232
+    name_offset = 16 + mobi_length + len(exth)
+    mobi = bytearray(b'MOBI' + struct.pack('>LL', mobi_length, 65001) + b'\x00' * (mobi_length - 12))
+    struct.pack_into('>LL', mobi, 84 - 16, name_offset, len(title.encode()))
+    struct.pack_into('>L', mobi, 128 - 16, 0x40)
+    record0 = b'\x00' * 16 + bytes(mobi) + exth + title.encode()
+    header = bytearray(78 + 8)
+    header[:8] = b'The_Book'
+    header[60:68] = b'BOOKMOBI'
+    struct.pack_into('>L', header, 78, len(header))
+    path.write_bytes(bytes(header) + record0)
+    return path
+
+
+def test_metadata_is_read_from_epub_mobi_and_pdf(tmp_path):
+    """Title, author and ISBN come from each format; ISBN-10 is validated and widened to 13."""
+    assert normalize_isbn('0-261-10221-4') == '9780261102217'
+    assert normalize_isbn('978-0-261-10221-7') == '9780261102217'
+    assert normalize_isbn('0-261-10221-5') is None
+
+    epub = read_ebook_metadata(str(_epub(tmp_path / 'h.epub', 'The Hobbit', 'Tolkien, J. R. R.', '0261102214')))
+    assert epub == {'title': 'The Hobbit', 'authors': ['Tolkien, J. R. R.'], 'isbn': '9780261102217',
+                    'year': 1937, 'format': 'epub'}
+
+    mobi = read_ebook_metadata(str(_mobi(tmp_path / 'h.mobi', 'The Hobbit', 'J. R. R. Tolkien', '9780261102217')))
+    assert (mobi['title'], mobi['authors'], mobi['isbn']) == ('The Hobbit', ['J. R. R. Tolkien'], '9780261102217')
+
+    pdf = tmp_path / 'h.pdf'
+    pdf.write_bytes(b'%PDF-1.4\n1 0 obj << /Title (Dune) /Author (Frank Herbert) >> endobj\n'
+                    b'BT (ISBN 978-0-441-17271-9) Tj ET\n%%EOF')
+    with patch('src.core.ebooks.HAS_PYPDF2', False):
+        assert read_ebook_metadata(str(pdf)) == {'title': 'Dune', 'authors': ['Frank Herbert'],
+                                                 'isbn': '9780441172719', 'format': 'pdf'}
+
+
+def test_books_are_filed_by_author_and_title_and_editions_found(tmp_path):
+    """Formats of one book share a folder; copies and other editions are reported."""
+    shelf = tmp_path / 'shelf'
+    (shelf / 'old').mkdir(parents=True)
+    epub = _epub(shelf / 'hobbit.epub', 'The Hobbit', 'Tolkien, J. R. R.', '0261102214')
+    _epub(shelf / 'old' / 'hobbit copy.epub', 'The Hobbit', 'Tolkien, J. R. R.', '0261102214')
+    _mobi(shelf / 'hobbit.mobi', 'The Hobbit', 'J. R. R. Tolkien', '9780261102217')
+    _epub(shelf / 'anniversary.epub', 'The Hobbit: or There and Back Again', 'J.R.R. Tolkien', '9780547928227')
+    _epub(shelf / 'dune.epub', 'Dune', 'Frank Herbert', '9780441172719')
+
+    config = Mock()
+    config.ebook_settings = {}
+    strategy = EbookLibraryStrategy(config)
+    placed = strategy.classify(str(epub), shelf, {'ebook_destination': 'Library'})
+    assert (placed['suggested_path'], placed['rename']) == ('Library/J. R. R. Tolkien/The Hobbit/', 'The Hobbit.epub')
+    assert placed['isbn'] == '9780261102217'
+    assert strategy.classify(str(tmp_path / 'notes.txt')) is None
+
+    found = strategy.find_editions(str(shelf))
+    assert found['success'] and found['books'] == 5
+    assert len(found['groups']) == 1
+    group = found['groups'][0]
+    assert group['kind'] == 'copies' and len(group['files']) == 4
+
+    (shelf / 'old' / 'hobbit copy.epub').unlink()
+    kinds = [g['kind'] for g in strategy.find_editions(str(shelf))['groups']]
+    assert kinds == ['editions']
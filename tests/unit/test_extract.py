@@ -0,0 +1,79 @@
+"""
+Unit tests for metadata extraction.
+"""
+
+import struct
+import sys
+from pathlib import Path
+from unittest.mock import patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core import extract
+from src.core.extract import extract_metadata
+
+
+def _jpeg(path, taken):
+    """A JPEG whose EXIF holds Make, Model and DateTimeOriginal."""
+    # TIFF header 8 + IFD0 (2 + 3 * 12 + 4) = 50, then the EXIF IFD (2 + 12 + 4) = 68
+    values = 68
+    make, model, raw_taken = b'Canon\x00', b'Canon EOS R5\x00', taken.encode('ascii') + b'\x00'
+    ifd0 = (struct.pack('<H', 3)
+            + struct.pack('<HHLL', 0x010F, 2, len(make), values)
+            + struct.pack('<HHLL', 0x0110, 2, len(model), values + len(make))
+            + struct.pack('<HHLL', 0x8769, 4, 1, 50) + struct.pack('<L', 0))
+    exif_ifd = (struct.pack('<H', 1)
+                + struct.pack('<HHLL', 0x9003, 2, len(raw_taken), values + len(make) + len(model))
+                + struct.pack('<L', 0))
+    tiff = b'II*\x00' + struct.pack('<L', 8) + ifd0 + exif_ifd + make + model + raw_taken
+    app1 = b'Exif\x00\x00' + tiff
+    path.write_bytes(b'\xff\xd8\xff\xe1' + struct.pack('>H', len(app1) + 2) + app1 + b'\xff\xd9')
+    return str(path)
+
+
+def _pdf(path, pages, title=b'(Annual \\(draft\\) report)'):
+    objects = [b'<< /Type /Catalog /Pages 2 0 R >>',
+               b'<< /Type /Pages /Kids [' + b' '.join(b'%d 0 R' % (i + 3) for i in range(pages))
+               + b'] /Count %d >>' % pages]
+    objects += [b'<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>'] * pages
+    objects.append(b'<< /Title ' + title + b' /Author <FEFF004A006F> >>')
+    body = b''.join(b'%d 0 obj\n' % (i + 1) + obj + b'\nendobj\n' for i, obj in enumerate(objects))
+    path.write_bytes(b'%PDF-1.4\n' + body + b'trailer << /Root 1 0 R /Info %d 0 R >>\n%%%%EOF\n' % len(objects))
+    return str(path)
+
+
+def test_photos_songs_and_pdfs(tmp_path):
+    """Date taken and camera from EXIF, ID3 tags, and PDF title, author and page count."""
+    photo = extract_metadata(_jpeg(tmp_path / "IMG_0001.jpg", "2024:06:01 06:30:00"))
+    assert photo.kind == 'image' and photo.error is None
+    assert photo.date_taken == '2024-06-01T06:30:00' and photo.date_source == 'DateTimeOriginal'
+    assert photo.camera == 'Canon EOS R5' and photo.latitude is None
+
+    frames = {'TIT2': 'Song', 'TPE1': 'Artist', 'TALB': 'Album', 'TRCK': '3/12', 'TYER': '2019'}
+    body = b''.join(name.encode() + struct.pack('>L', len(text) + 1) + b'\x00\x00\x03' + text.encode()
+                    for name, text in frames.items())
+    size = len(body)
+    syncsafe = bytes([(size >> 21) & 0x7F, (size >> 14) & 0x7F, (size >> 7) & 0x7F, size & 0x7F])
+    (tmp_path / "song.mp3").write_bytes(b'ID3\x03\x00\x00' + syncsafe + body + b'\xff\xfb' + b'\x00' * 64)
+    song = extract_metadata(str(tmp_path / "song.mp3"))
+    assert (song.kind, song.artist, song.album, song.track, song.year) == ('audio', 'Artist', 'Album', 3, 2019)
+
+    pdf = extract_metadata(_pdf(tmp_path / "report.pdf", 3))
+    assert (pdf.title, pdf.author, pdf.page_count) == ('Annual (draft) report', 'Jo', 3)
+    data = pdf.to_dict()
+    assert data['page_count'] == 3 and data['kind'] == 'pdf' and data['modified']
+
+
+def test_pages_split_across_chunks_and_unreadable_files(tmp_path):
+    """Page objects are counted once across read chunks; errors are reported, not raised."""
+    path = _pdf(tmp_path / "long.pdf", 40, title=b'()')
+    # No /Count to rely on: only the page objects are counted
+    Path(path).write_bytes(Path(path).read_bytes().replace(b'/Count 40', b''))
+    with patch.object(extract, 'PDF_CHUNK_BYTES', 97), patch.object(extract, 'PDF_OVERLAP_BYTES', 30):
+        assert extract_metadata(path).page_count == 40
+
+    (tmp_path / "fake.pdf").write_text("not a pdf")
+    assert extract_metadata(str(tmp_path / "fake.pdf")).error == 'Not a PDF file'
+    missing = extract_metadata(str(tmp_path / "missing.jpg"))
+    assert missing.kind == 'image' and missing.error and missing.size == 0
+    assert extract_metadata(str(tmp_path / "fake.pdf")).to_dict()['title'] is None
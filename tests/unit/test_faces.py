@@ -0,0 +1,101 @@
+"""
+Unit tests for offline face clustering.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.faces import FaceClusterer
+from src.core.db_manager import DatabaseManager
+from src.config import Config
+
+
+ALICE, BOB = [1.0, 0.1, 0.0], [0.0, 0.2, 1.0]
+
+
+class FakeEmbedder:
+    """Stands in for the ONNX models: faces per file name, counting calls."""
+
+    def __init__(self, faces_by_name):
+        self.faces_by_name = faces_by_name
+        self.calls = []
+
+    def faces(self, path):
+        self.calls.append(Path(path).name)
+        return [{'box': [0, 0, 50, 50], 'embedding': vector}
+                for vector in self.faces_by_name.get(Path(path).name, [])]
+
+
+def _config(**faces):
+    config = Mock(spec=Config)
+    config.faces_settings = faces
+    return config
+
+
+def test_faces_are_clustered_into_people_and_collections(tmp_path):
+    """Similar faces become one person; names and merges survive rescans."""
+    photos = tmp_path / "photos"
+    photos.mkdir()
+    for i, name in enumerate(['beach.jpg', 'party.jpg', 'bob.jpg', 'empty.jpg']):
+        (photos / name).write_bytes(b'\xff\xd8' + bytes([i]) * 100)
+    (photos / 'notes.txt').write_text('not a photo')
+
+    embedder = FakeEmbedder({
+        'beach.jpg': [ALICE, BOB],
+        'party.jpg': [[0.95, 0.15, 0.05]],
+        'bob.jpg': [[0.05, 0.25, 0.9]],
+    })
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    try:
+        clusterer = FaceClusterer(_config(similarity=0.8, min_faces=2), db, embedder=embedder)
+
+        result = clusterer.scan(str(photos))
+        assert result['success'] and result['scanned'] == 4 and result['faces'] == 4
+        people = clusterer.people()
+        assert sorted(p['face_count'] for p in people) == [2, 2]
+        photos_of = {p['id']: {Path(f).name for f in clusterer.photos_of(p['id'])} for p in people}
+        alice = next(p for p in people if photos_of[p['id']] == {'beach.jpg', 'party.jpg'})
+        bob = next(p for p in people if photos_of[p['id']] == {'beach.jpg', 'bob.jpg'})
+
+        # Already scanned photos are not sent through the models again
+        embedder.calls.clear()
+        (photos / 'new.jpg').write_bytes(b'\xff\xd8new face')
+        embedder.faces_by_name['new.jpg'] = [[0.0, 0.0, 1.0]]
+        assert clusterer.scan(str(photos))['skipped'] == 4
+        assert embedder.calls == ['new.jpg']
+        assert len(clusterer.people()) == 2
+
+        assert clusterer.rename_person(alice['id'], 'Alice')['success']
+        assert clusterer.people_in(str(photos / 'party.jpg')) == ['Alice']
+        synced = clusterer.sync_collections()
+        assert sorted(synced['collections']) == ['People: Alice', f"People: Person {bob['id']}"]
+
+        # Renaming moves the collection to the new name
+        clusterer.rename_person(bob['id'], 'Bob')
+        names = sorted(clusterer.sync_collections()['collections'])
+        assert names == ['People: Alice', 'People: Bob']
+        assert f"People: Person {bob['id']}" not in [c['name'] for c in clusterer._collections().list()]
+
+        lone = db.create_person('Stranger')
+        merged = clusterer.merge_people(alice['id'], lone)
+        assert merged['success'] and merged['moved'] == 0
+        assert not clusterer.merge_people(alice['id'], alice['id'])['success']
+    finally:
+        db.cleanup()
+
+
+def test_unavailable_without_models_or_opt_in(tmp_path):
+    """Without the opt-in (or the models) the feature says why instead of failing later."""
+    db = Mock()
+    off = FaceClusterer(_config(), db)
+    assert 'faces.enabled' in off.unavailable_reason()
+    assert not off.scan(str(tmp_path))['success']
+    db.record_face_scan.assert_not_called()
+
+    missing = FaceClusterer(_config(enabled=True, detector_model=str(tmp_path / 'none.onnx'),
+                                    embedding_model=str(tmp_path / 'none.onnx')), db)
+    assert missing.unavailable_reason()
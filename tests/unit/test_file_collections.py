@@ -0,0 +1,61 @@
+"""
+Unit tests for virtual collections.
+"""
+
+import json
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.file_collections import CollectionManager
+from src.core.hashing import hash_file
+
+
+@pytest.fixture
+def collections(config, db, tmp_path):
+    """A collection manager over a temporary database, and some files."""
+    music = tmp_path / "music"
+    music.mkdir()
+    (music / "song.mp3").write_bytes(b'ID3' + b'1' * 100)
+    (music / "other.flac").write_bytes(b'fLaC' + b'2' * 100)
+    (music / "cover.jpg").write_bytes(b'\xff\xd8' + b'3' * 100)
+    return CollectionManager(config, db), music, tmp_path
+
+
+def test_rule_and_manual_items(collections):
+    """Rules fill a collection; the same content is only referenced once."""
+    manager, music, tmp_path = collections
+
+    result = manager.create('Music', rule={'folders': [str(music)], 'extensions': ['mp3', 'flac']})
+    assert result['success'] and result['collection']['item_count'] == 2
+    assert manager.create('Music')['success'] is False
+
+    (tmp_path / "copy.mp3").write_bytes((music / "song.mp3").read_bytes())
+    assert manager.add('Music', [str(tmp_path / "copy.mp3"), str(music / "cover.jpg")])['added'] == 1
+    assert manager.remove('Music', str(music / "cover.jpg"))
+    assert sorted(i['name'] for i in manager.items('Music')) == ['copy.mp3', 'other.flac']
+
+    assert manager.delete('Music') and manager.list() == []
+    assert (music / "song.mp3").exists()
+
+
+def test_items_follow_moves_and_export(collections, action_manager):
+    """Files moved by the app are found again; exports leave the originals alone."""
+    manager, music, tmp_path = collections
+    manager.create('Mix')
+    manager.add('Mix', [str(music / "song.mp3")])
+
+    action_manager.execute(str(music / "song.mp3"), {
+        'category': 'Music', 'suggested_path': 'Music/', 'confidence': 'high', 'method': 'rule-based'
+    }, user_approved=True)
+
+    item = manager.items('Mix')[0]
+    assert item['available'] and item['path'] == str(tmp_path / "organized" / "Music" / "song.mp3")
+    assert item['content_hash'] == hash_file(item['path'])  # The hash every subsystem keys content by
+
+    result = manager.export('Mix', str(tmp_path / "mix.json"))
+    assert result['success'] and json.loads((tmp_path / "mix.json").read_text())['items'][0]['name'] == 'song.mp3'
+    assert manager.export('Mix', str(tmp_path / "out"), 'folder')['exported'] == 1
+    assert (tmp_path / "out" / "song.mp3").exists() and Path(item['path']).exists()
@@ -0,0 +1,155 @@
+"""
+Unit tests for folder listings.
+"""
+
+import os
+import sys
+from datetime import datetime
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.file_listing import (ingest_dropped_paths, list_files, list_files_paged, list_files_recursive,
+                                  stream_files)
+
+
+@pytest.fixture
+def tree(tmp_path):
+    downloads = tmp_path / "Downloads"
+    (downloads / "photos" / "2024").mkdir(parents=True)
+    (downloads / "photos" / "2024" / "beach.jpg").write_text("jpg")
+    (downloads / "photos" / "cover.png").write_text("png")
+    (downloads / "report.pdf").write_text("pdf!")
+    (downloads / ".hidden").write_text("")
+    (downloads / "Archive").mkdir()
+    return downloads
+
+
+def test_recursive_listing_uses_relative_paths_and_depth(tree):
+    """Folders come first, paths are relative to the listed folder and max_depth stops the walk."""
+    flat = list_files(str(tree))
+    assert [item['path'] for item in flat['items']] == ['Archive', 'photos', 'report.pdf']
+    assert flat['items'][2]['size'] == 4 and flat['items'][0]['size'] is None
+
+    full = list_files_recursive(str(tree))
+    assert [item['path'] for item in full['items']] == [
+        'Archive', 'photos', 'photos/2024', 'photos/2024/beach.jpg', 'photos/cover.png', 'report.pdf'
+    ]
+    assert full['items'][3]['depth'] == 2 and not full['truncated'] and not full['skipped']
+
+    one_level = list_files_recursive(str(tree), max_depth=1)
+    assert 'photos/2024' in [item['path'] for item in one_level['items']]
+    assert 'photos/2024/beach.jpg' not in [item['path'] for item in one_level['items']]
+    assert '.hidden' in [item['path'] for item in list_files(str(tree), include_hidden=True)['items']]
+
+    limited = list_files_recursive(str(tree), limit=3)
+    assert len(limited['items']) == 3 and limited['truncated']
+
+    with pytest.raises(NotADirectoryError):
+        list_files_recursive(str(tree / "report.pdf"))
+
+
+def test_entries_carry_rfc3339_times_and_flags_and_version_1_keeps_the_old_shape(tree):
+    """Times parse as ISO 8601 with an offset; hidden, read-only and extension are filled in."""
+    report = tree / "report.pdf"
+    os.utime(report, (1700000000, 1709285400))
+    report.chmod(0o444)
+    items = {item['path']: item for item in list_files(str(tree), include_hidden=True)['items']}
+
+    entry = items['report.pdf']
+    modified = datetime.fromisoformat(entry['modified'])
+    assert modified.utcoffset() is not None and modified.timestamp() == 1709285400
+    assert datetime.fromisoformat(entry['accessed']).timestamp() == 1700000000
+    assert (entry['extension'], entry['is_readonly'], entry['is_hidden']) == ('pdf', True, False)
+    assert (items['.hidden']['is_hidden'], items['Archive']['extension'], items['Archive']['is_readonly']) == \
+        (True, None, False)
+    assert list_files(str(tree))['version'] == 2
+
+    legacy = list_files(str(tree), version=1)
+    assert legacy['version'] == 1 and legacy['items'][2]['modified'] == 1709285400
+    assert 'extension' not in legacy['items'][2]
+    with pytest.raises(ValueError):
+        list_files(str(tree), version=3)
+
+
+def test_pages_are_sorted_here_with_folders_first(tree):
+    """Sorting by size, date or category keeps folders ahead; pages slice the sorted folder."""
+    (tree / "notes.txt").write_text("a much longer text file")
+    (tree / "a.jpg").write_text("j")
+    for age, name in enumerate(['report.pdf', 'notes.txt', 'a.jpg']):
+        os.utime(tree / name, (1700000000 + age, 1700000000 + age))
+    config = Mock(destination_rules={'pdf': 'Documents/', 'jpg': 'Pictures/'})
+
+    def names(**options):
+        return [item['name'] for item in list_files_paged(str(tree), config=config, **options)['items']]
+
+    assert names() == ['Archive', 'photos', 'a.jpg', 'notes.txt', 'report.pdf']
+    assert names(sort_by='size', order='desc') == ['photos', 'Archive', 'notes.txt', 'report.pdf', 'a.jpg']
+    assert names(sort_by='date', order='desc', offset=2, limit=2) == ['a.jpg', 'notes.txt']
+    page = list_files_paged(str(tree), sort_by='category', offset=2, config=config)
+    assert [(item['name'], item['category']) for item in page['items']] == [
+        ('report.pdf', 'Documents'), ('notes.txt', 'Other'), ('a.jpg', 'Pictures')]
+    assert (page['total'], page['items'][0]['mime_type'] is not None) == (5, True)
+    with pytest.raises(ValueError):
+        list_files_paged(str(tree), sort_by='colour')
+
+
+@pytest.mark.skipif(sys.platform.startswith('win'), reason="symlinks need privileges on Windows")
+def test_symlink_loops_are_listed_once_and_skipped(tree):
+    """A link back to a parent folder is shown, entered only when asked, and never walked twice."""
+    os.symlink(tree, tree / "photos" / "loop")
+
+    plain = list_files_recursive(str(tree))
+    loop = next(item for item in plain['items'] if item['path'] == 'photos/loop')
+    assert loop['is_symlink'] and not plain['skipped']
+    assert not any(item['path'].startswith('photos/loop/') for item in plain['items'])
+
+    followed = list_files_recursive(str(tree), follow_symlinks=True)
+    assert followed['skipped'] == [{'path': 'photos/loop', 'reason': 'already listed (folder loop)'}]
+    assert not any(item['path'].startswith('photos/loop/') for item in followed['items'])
+
+
+def test_streamed_walk_sends_every_entry_once_in_batches_with_an_estimate(tree):
+    """Batches add up to the full listing, the last estimate is exact, and a loop is walked once."""
+    for n in range(40):
+        (tree / "Archive" / f"old{n:02}.txt").write_text("x")
+    os.symlink(tree, tree / "photos" / "loop")
+    events = []
+
+    summary = stream_files(str(tree), lambda event, payload: events.append((event, payload)),
+                           follow_symlinks=True, batch_size=10, workers=3)
+    batches = [payload for event, payload in events if event == 'files://batch']
+    streamed = sorted(item['path'] for batch in batches for item in batch['items'])
+    assert streamed == sorted(item['path'] for item in list_files_recursive(str(tree), follow_symlinks=True)['items'])
+    assert all(len(batch['items']) <= 10 for batch in batches) and len(batches) >= 5
+    assert (batches[-1]['done'], batches[-1]['estimate'], summary['total']) == (len(streamed),) * 3
+    assert events[-1] == ('files://done', summary)
+    assert summary['skipped'] == [{'path': 'photos/loop', 'reason': 'already listed (folder loop)'}]
+
+    assert stream_files(str(tree), lambda *_: None, limit=5)['truncated']
+    with pytest.raises(NotADirectoryError):
+        stream_files(str(tree / "missing"), lambda *_: None)
+
+
+def test_dropped_folders_are_expanded_and_bad_paths_turned_away(tree, tmp_path):
+    """Files once each in drop order with absolute paths; missing, relative and blacklisted paths get a reason."""
+    (tree / "photos" / "draft.tmp").write_text("tmp")
+    (tree / "part.crdownload").write_text("half")
+    secret = tmp_path / "Private"
+    secret.mkdir()
+    (secret / "keys.txt").write_text("x")
+    report = str(tree / "report.pdf")
+
+    result = ingest_dropped_paths([report, str(tree / "photos"), report, str(tmp_path / "gone.pdf"), "notes.txt",
+                                   str(secret / "keys.txt"), str(tree / "part.crdownload")],
+                                  detect_types=False, config=Mock(path_blacklist=[str(secret)]))
+    assert [(Path(item['path']).name, Path(item['source']).name) for item in result['items']] == [
+        ('report.pdf', 'report.pdf'), ('beach.jpg', 'photos'), ('cover.png', 'photos')]
+    assert all(os.path.isabs(item['path']) and not item['is_dir'] for item in result['items'])
+    assert [entry['reason'] for entry in result['rejected']] == [
+        'does not exist', 'not an absolute path', f"blacklisted ({secret})"]
+    assert result['ignored'] == 2 and not result['truncated']  # draft.tmp and part.crdownload
+    assert ingest_dropped_paths([str(tree)], limit=2)['truncated']
@@ -0,0 +1,64 @@
+"""
+Unit tests for file type detection by content.
+"""
+
+import os
+import sys
+import zipfile
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.file_listing import list_files
+from src.core.filetype import detect_file_type, detect_mismatched_extensions
+
+
+@pytest.fixture(autouse=True)
+def config_dir(tmp_path):
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path / "config")}):
+        yield
+
+
+def test_types_come_from_content_not_the_extension(tmp_path):
+    """Signatures, ZIP containers and text are told apart; unknown binaries fall back to the extension."""
+    (tmp_path / "photo.dat").write_bytes(b'\x89PNG\r\n\x1a\n' + b'\x00' * 20)
+    (tmp_path / "clip.bin").write_bytes(b'\x00\x00\x00\x18ftypheic' + b'\x00' * 20)
+    with zipfile.ZipFile(tmp_path / "report.zip", 'w') as archive:
+        archive.writestr('[Content_Types].xml', '<Types/>')
+        archive.writestr('word/document.xml', '<w:document/>')
+    (tmp_path / "notes.csv").write_text("name,amount\nwidget,3\n")
+    (tmp_path / "blob.pdf").write_bytes(bytes(range(256)))
+
+    assert detect_file_type(str(tmp_path / "photo.dat"))['mime_type'] == 'image/png'
+    assert detect_file_type(str(tmp_path / "clip.bin"))['extensions'] == ['heic', 'heif']
+    docx = detect_file_type(str(tmp_path / "report.zip"))
+    assert (docx['mime_type'].endswith('wordprocessingml.document'), docx['extensions'][0]) == (True, 'docx')
+    assert detect_file_type(str(tmp_path / "notes.csv"))['mime_type'] == 'text/csv'
+    assert detect_file_type(str(tmp_path / "blob.pdf"))['method'] == 'extension'
+
+    listing = {item['name']: item['mime_type'] for item in list_files(str(tmp_path))['items']}
+    assert (listing['photo.dat'], listing['notes.csv']) == ('image/png', 'text/csv')
+    assert list_files(str(tmp_path), detect_types=False)['items'][0]['mime_type'] is None
+
+
+def test_mismatched_extensions_put_disguised_programs_first(tmp_path):
+    """HTML saved as .pdf is a warning, a program named .jpg a danger; files that fit are not listed."""
+    (tmp_path / "invoice.pdf").write_text("<!DOCTYPE html><html><body>Session expired</body></html>")
+    (tmp_path / "Downloads").mkdir()
+    (tmp_path / "Downloads" / "cute_cat.jpg").write_bytes(b'MZ\x90\x00' + b'\x00' * 60)
+    (tmp_path / "real.pdf").write_bytes(b'%PDF-1.7\n')
+    (tmp_path / "readme.md").write_text("# Hello")
+    (tmp_path / "run").write_bytes(b'\x7fELF\x02\x01')
+
+    result = detect_mismatched_extensions(str(tmp_path))
+    assert result['checked'] == 5
+    assert [(Path(item['path']).name, item['severity'], item['suggested_extension'])
+            for item in result['mismatches']] == [('cute_cat.jpg', 'danger', 'exe'),
+                                                  ('invoice.pdf', 'warning', 'html')]
+    assert result['mismatches'][1]['detected_mime'] == 'text/html'
+    assert detect_mismatched_extensions(str(tmp_path), recursive=False)['checked'] == 4
+    with pytest.raises(NotADirectoryError):
+        detect_mismatched_extensions(str(tmp_path / "missing"))
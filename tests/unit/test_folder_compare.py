@@ -0,0 +1,68 @@
+"""
+Unit tests for folder comparison and merging.
+"""
+
+import os
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.folder_compare import FolderComparer
+from src.core.db_manager import DatabaseManager
+from src.config import Config
+
+
+@pytest.fixture
+def backups(tmp_path):
+    """Two backup folders with shared, moved, missing and conflicting files."""
+    old, new = tmp_path / "backup2019", tmp_path / "backup2021"
+    (old / "photos").mkdir(parents=True)
+    (new / "pictures").mkdir(parents=True)
+    (old / "photos" / "beach.jpg").write_bytes(b'beach' * 100)
+    (new / "pictures" / "beach.jpg").write_bytes(b'beach' * 100)  # Same content, moved
+    (old / "only-old.txt").write_text("old only")
+    (new / "only-new.txt").write_text("new only")
+    (old / "notes.txt").write_text("old notes")
+    (new / "notes.txt").write_text("newer notes")
+    os.utime(old / "notes.txt", (1_600_000_000, 1_600_000_000))
+
+    config = Mock(spec=Config)
+    config.version_settings = {'store_dir': str(tmp_path / "versions")}
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    yield FolderComparer(config, db), old, new
+    db.cleanup()
+
+
+def test_compare_groups_by_content(backups):
+    """Moved copies count as identical; same path with other content is a conflict."""
+    comparer, old, new = backups
+
+    result = comparer.compare_folders(str(old), str(new))
+
+    assert result['success']
+    assert [(g['a'], g['b']) for g in result['identical']] == [(['photos/beach.jpg'], ['pictures/beach.jpg'])]
+    assert result['only_in_a'] == ['only-old.txt'] and result['only_in_b'] == ['only-new.txt']
+    assert [(c['path'], c['newer']) for c in result['conflicting']] == [('notes.txt', 'b')]
+    assert comparer.compare_folders(str(old), str(old / "photos"))['success'] is False
+
+
+def test_merge_and_undo(backups):
+    """Merging both ways copies missing and newer files; undo restores the overwritten file."""
+    comparer, old, new = backups
+
+    preview = comparer.merge(str(old), str(new), ['copy_missing', 'keep_newer'], 'both', dry_run=True)
+    assert len(preview['copies']) == 3 and not (new / "only-old.txt").exists()
+
+    result = comparer.merge(str(old), str(new), ['copy_missing', 'keep_newer'], 'both')
+
+    assert result['success'], result['message']
+    assert (new / "only-old.txt").exists() and (old / "only-new.txt").exists()
+    assert (old / "notes.txt").read_text() == "newer notes"
+    assert comparer.compare_folders(str(old), str(new))['summary']['conflicting'] == 0
+
+    assert comparer.journal.undo(result['operation_id'])['success']
+    assert (old / "notes.txt").read_text() == "old notes"
+    assert not (new / "only-old.txt").exists() and not (old / "only-new.txt").exists()
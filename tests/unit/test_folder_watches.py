@@ -0,0 +1,108 @@
+"""
+Unit tests for watching single folders with their own rules.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.watcher import (
+    FolderWatchManager, EVENT_FILE_DETECTED, EVENT_FILE_ORGANIZED, EVENT_WATCH_ERROR
+)
+
+
+class FakeWatcher:
+    def __init__(self, folders, callback, config, recursive):
+        self.folders, self.callback, self.recursive = folders, callback, recursive
+        self.running = False
+
+    def start(self, background=True):
+        self.running = True
+
+    def stop(self):
+        self.running = False
+
+
+def _manager(events, auto_mode=False):
+    config = Mock()
+    config.auto_mode = auto_mode
+    classifier = Mock()
+    classifier.classify.return_value = {'category': 'Documents', 'suggested_path': 'Documents/'}
+    action_manager = Mock()
+    action_manager.execute.side_effect = lambda file_path, **kwargs: (
+        {'success': False, 'message': 'Blocked'} if file_path.endswith('blocked.pdf')
+        else {'success': True, 'new_path': '/out/Documents/x.pdf', 'message': 'Moved'})
+    watchers = []
+
+    def factory(**kwargs):
+        watchers.append(FakeWatcher(**kwargs))
+        return watchers[-1]
+
+    manager = FolderWatchManager(config, classifier, action_manager,
+                                 listener=lambda event, payload: events.append((event, payload)),
+                                 watcher_factory=factory)
+    return manager, watchers, action_manager
+
+
+def test_folders_are_started_updated_and_stopped(tmp_path):
+    """Each folder gets its own watcher; changing rules keeps it unless recursion changes."""
+    manager, watchers, _ = _manager([])
+    downloads, desktop = tmp_path / "Downloads", tmp_path / "Desktop"
+    downloads.mkdir()
+    desktop.mkdir()
+
+    assert manager.start_watching(str(downloads))['watch']['rules']['auto_organize'] is False
+    assert manager.start_watching(str(desktop), {'extensions': ['.PNG'], 'recursive': False})['success']
+    assert watchers[1].recursive is False and len(watchers) == 2
+
+    updated = manager.start_watching(str(downloads), {'auto_organize': True})
+    assert updated['message'].startswith('Updated') and len(watchers) == 2
+    assert manager.start_watching(str(desktop), {'extensions': ['png']})['success']
+    assert len(watchers) == 3 and not watchers[1].running and watchers[2].recursive
+
+    assert [w['folder'] for w in manager.watching()] == [str(desktop), str(downloads)]
+    assert not manager.start_watching(str(tmp_path / "missing"))['success']
+    assert manager.start_watching(str(downloads), {'when': 'later'})['message'] == "Unknown watch rule(s): when"
+
+    assert manager.stop_watching(str(downloads))['success'] and not watchers[0].running
+    assert not manager.stop_watching(str(downloads))['success']
+    assert manager.stop_all() == 1 and manager.watching() == []
+
+
+def test_new_files_are_reported_or_organized_by_the_folder_rules(tmp_path):
+    """Review folders only report files; auto folders move them; filtered and repeated events are ignored."""
+    events = []
+    manager, watchers, action_manager = _manager(events)
+    review, auto = tmp_path / "review", tmp_path / "auto"
+    review.mkdir()
+    auto.mkdir()
+    manager.start_watching(str(review), {'extensions': ['pdf']})
+    manager.start_watching(str(auto), {'auto_organize': True})
+
+    watchers[0].callback(str(review / "a.pdf"))
+    watchers[0].callback(str(review / "a.pdf"))  # modified right after created
+    watchers[0].callback(str(review / "b.jpg"))
+    assert [e for e, _ in events] == [EVENT_FILE_DETECTED] and not action_manager.execute.called
+
+    watchers[1].callback(str(auto / "x.pdf"))
+    watchers[1].callback(str(auto / "blocked.pdf"))
+    assert [e for e, _ in events[1:]] == [EVENT_FILE_DETECTED, EVENT_FILE_ORGANIZED,
+                                          EVENT_FILE_DETECTED, EVENT_WATCH_ERROR]
+    assert events[2][1]['new_path'] == '/out/Documents/x.pdf' and events[4][1]['message'] == 'Blocked'
+
+    counts = {w['folder']: (w['detected'], w['organized'], w['errors']) for w in manager.watching()}
+    assert counts == {str(review): (1, 0, 0), str(auto): (2, 1, 1)}
+    manager.stop_all()
+    assert manager.handle_file(str(auto), str(auto / "late.pdf")) is None
+
+
+def test_watchers_share_the_managers_leave_alone_detector(tmp_path):
+    """Every watcher checks the one detector, so a folder put on the leave-alone list is skipped by all."""
+    manager, watchers, _ = _manager([])
+    manager.leave_alone = detector = Mock()
+    for name in ('Downloads', 'Desktop'):
+        (tmp_path / name).mkdir()
+        assert manager.start_watching(str(tmp_path / name))['success']
+    assert [watcher.leave_alone for watcher in watchers] == [detector, detector]
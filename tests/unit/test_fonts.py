@@ -0,0 +1,93 @@
+"""
+Unit tests for font name table reading and font organizing.
+"""
+
+import struct
+import sys
+import zlib
+from pathlib import Path
+from unittest.mock import Mock
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.fonts import FontLibraryStrategy, read_font_names, version_key
+
+
+def _name_table(names):
+    """A name table with Windows (US English) records for {name_id: text}."""
+    records, strings = b'', b''
+    for name_id, text in sorted(names.items()):
+        encoded = text.encode('utf-16-be')
+        records += struct.pack('>HHHHHH', 3, 1, 0x409, name_id, len(encoded), len(strings))
+        strings += encoded
+    return struct.pack('>HHH', 0, len(names), 6 + len(records)) + records + strings
+
+
+def _ttf(path, names, magic=b'\x00\x01\x00\x00'):
+    table = _name_table(names)
+    header = magic + struct.pack('>HHHH', 1, 16, 0, 0)
+    entry = struct.pack('>4sLLL', b'name', 0, 12 + 16, len(table))
+    path.write_bytes(header + entry + table)
+    return path
+
+
+def _woff(path, names):
+    table = _name_table(names)
+    packed = zlib.compress(table)
+    packed = packed if len(packed) < len(table) else table
+    header = b'wOFF' + b'\x00\x01\x00\x00' + struct.pack('>LHH', 0, 1, 0) + b'\x00' * 28
+    entry = struct.pack('>4sLLLL', b'name', 44 + 20, len(packed), len(table), 0)
+    path.write_bytes(header + entry + packed)
+    return path
+
+
+def _strategy(tmp_path):
+    config = Mock()
+    config.font_settings = {'install_dir': str(tmp_path / 'installed')}
+    return FontLibraryStrategy(config)
+
+
+def test_names_are_read_from_truetype_opentype_and_woff(tmp_path):
+    """The typographic family wins over the legacy one; WOFF tables are inflated."""
+    ttf = _ttf(tmp_path / 'inter_sb.ttf', {1: 'Inter SemiBold', 2: 'Regular', 5: 'Version 3.019',
+                                           16: 'Inter', 17: 'SemiBold'})
+    assert read_font_names(str(ttf)) == {'format': 'truetype', 'family': 'Inter', 'style': 'SemiBold',
+                                         'version': 'Version 3.019'}
+    otf = _ttf(tmp_path / 'x.otf', {1: 'Fira Code', 2: 'Bold'}, magic=b'OTTO')
+    assert read_font_names(str(otf)) == {'format': 'opentype', 'family': 'Fira Code', 'style': 'Bold'}
+    woff = _woff(tmp_path / 'x.woff', {1: 'Lato', 2: 'Italic', 0: 'Copyright Lato authors. ' * 10})
+    assert (read_font_names(str(woff))['family'], read_font_names(str(woff))['style']) == ('Lato', 'Italic')
+    (tmp_path / 'broken.ttf').write_bytes(b'not a font')
+    assert read_font_names(str(tmp_path / 'broken.ttf')) == {}
+    assert version_key('Version 2.001;hotconv 1.0') == (2, 1) > version_key('1.9')
+
+
+def test_fonts_are_filed_by_family_duplicates_found_and_installed(tmp_path):
+    """Files go to Family folders; versions of one face are grouped; install copies once."""
+    shelf = tmp_path / 'shelf'
+    (shelf / 'old').mkdir(parents=True)
+    new = _ttf(shelf / 'Inter-Bold.ttf', {1: 'Inter', 2: 'Bold', 5: 'Version 4.0'})
+    _ttf(shelf / 'old' / 'inter_b.ttf', {1: 'Inter', 2: 'Bold', 5: 'Version 3.19'})
+    _ttf(shelf / 'Inter-Regular.ttf', {1: 'Inter', 2: 'Regular', 5: 'Version 4.0'})
+    _ttf(shelf / 'copy.ttf', {1: 'Inter', 2: 'Regular', 5: 'Version 4.0'})
+
+    strategy = _strategy(tmp_path)
+    placed = strategy.classify(str(shelf / 'old' / 'inter_b.ttf'), shelf, {})
+    assert (placed['suggested_path'], placed['rename'], placed['category']) == ('Fonts/Inter/', None, 'Fonts')
+    assert strategy.classify(str(new), shelf, {'font_destination': str(tmp_path / 'F')})['destination_root'] == \
+        str(tmp_path / 'F')
+    (shelf / 'Mystery-Light.woff2').write_bytes(b'wOF2' + b'\x00' * 40)
+    guessed = strategy.classify(str(shelf / 'Mystery-Light.woff2'))
+    assert (guessed['suggested_path'], guessed['confidence']) == ('Fonts/Mystery/', 'medium')
+    assert strategy.classify(str(tmp_path / 'notes.txt')) is None
+
+    found = strategy.find_duplicates(str(shelf))
+    assert found['success'] and found['fonts'] == 5
+    assert [(g['style'], g['kind']) for g in found['groups']] == [('Bold', 'versions'), ('Regular', 'copies')]
+    assert found['groups'][0]['newest'] == str(new)
+
+    installed = strategy.install(str(new))
+    assert installed['success'] and Path(installed['path']).read_bytes() == new.read_bytes()
+    assert strategy.install(str(shelf / 'old' / 'inter_b.ttf'))['success']
+    assert 'already installed' in strategy.install(str(new))['message']
+    assert not strategy.install(str(tmp_path / 'notes.txt'))['success']
@@ -0,0 +1,91 @@
+"""
+Unit tests for safe moves with collision handling.
+"""
+
+import errno
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.fsops import FileMover, move_file
+from src.core.db_manager import DatabaseManager
+from src.core.journal import OperationJournal
+
+
+@pytest.fixture
+def setup(tmp_path):
+    config = Mock()
+    config.trash_dir = str(tmp_path / "trash")
+    config.path_blacklist = []
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    inbox, docs = tmp_path / "inbox", tmp_path / "docs"
+    inbox.mkdir()
+    docs.mkdir()
+    yield FileMover(config, db), OperationJournal(db), inbox, docs
+    db.cleanup()
+
+
+def test_collisions_get_free_names_and_the_plan_is_undoable(setup):
+    """Nothing is overwritten: existing names get " (2)", " (3)"; skip and replace do what they say."""
+    mover, journal, inbox, docs = setup
+    (docs / "report.pdf").write_text("old")
+    (docs / "report (2).pdf").write_text("older")
+    (inbox / "report.pdf").write_text("new")
+    (inbox / "notes.txt").write_text("notes")
+    (docs / "notes.txt").write_text("kept")
+
+    preview = mover.apply_plan([{'source': str(inbox / "report.pdf"), 'destination': str(docs)}], dry_run=True)
+    assert preview['results'][0]['destination'] == str(docs / "report (3).pdf")
+    assert (inbox / "report.pdf").exists() and preview['operation_id'] is None
+
+    result = mover.apply_plan([
+        {'source': str(inbox / "report.pdf"), 'destination': str(docs)},
+        {'source': str(inbox / "missing.txt"), 'destination': str(docs)},
+    ])
+    assert [r['status'] for r in result['results']] == ['moved', 'failed']
+    assert result['results'][0]['method'] == 'rename' and not result['success']
+    assert (docs / "report (3).pdf").read_text() == "new" and (docs / "report.pdf").read_text() == "old"
+
+    skipped = mover.apply_plan([{'source': str(inbox / "notes.txt"), 'destination': str(docs)}], 'skip')
+    assert skipped['skipped'] == 1 and (docs / "notes.txt").read_text() == "kept"
+    replaced = mover.apply_plan([{'source': str(inbox / "notes.txt"), 'destination': str(docs)}], 'replace')
+    assert replaced['moved'] == 1 and (docs / "notes.txt").read_text() == "notes"
+
+    assert journal.undo(replaced['operation_id'])['success']
+    assert (docs / "notes.txt").read_text() == "kept" and (inbox / "notes.txt").read_text() == "notes"
+    assert journal.undo(result['operation_id'])['success']
+    assert (inbox / "report.pdf").read_text() == "new" and not (docs / "report (3).pdf").exists()
+
+
+def test_moves_across_drives_are_copied_verified_then_removed(setup):
+    """EXDEV falls back to copy + hash check; a bad copy leaves the source and no partial file."""
+    _, _, inbox, docs = setup
+    source = inbox / "video.mp4"
+    source.write_bytes(os.urandom(4096))
+    data = source.read_bytes()
+    cross_device = OSError(errno.EXDEV, 'Invalid cross-device link')
+    real_link = os.link
+
+    def link(src, dst):
+        if Path(src) == source:
+            raise cross_device
+        return real_link(src, dst)
+
+    with patch('src.core.fsops.os.link', side_effect=link), \
+            patch('src.core.fsops.hash_file', side_effect=['a', 'b']):
+        failed = move_file(str(source), str(docs / "video.mp4"))
+    assert failed['status'] == 'failed' and 'does not match' in failed['message']
+    assert source.read_bytes() == data and os.listdir(docs) == []
+
+    (docs / "video.mp4").write_text("taken")
+    with patch('src.core.fsops.os.link', side_effect=link):
+        moved = move_file(str(source), str(docs / "video.mp4"))
+    assert moved['status'] == 'moved' and moved['method'] == 'copy'
+    assert moved['destination'] == str(docs / "video (2).mp4")
+    assert (docs / "video (2).mp4").read_bytes() == data and not source.exists()
+    assert sorted(os.listdir(docs)) == ['video (2).mp4', 'video.mp4']
@@ -0,0 +1,117 @@
+"""
+Unit tests for reconstructing past folder states.
+"""
+
+import time
+import pytest  # type: ignore[import-untyped]
+from datetime import datetime
+from pathlib import Path
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.history import FolderHistory, parse_point_in_time
+from src.core.snapshots import SnapshotManager
+
+
+@pytest.fixture
+def config(config):
+    """Snapshots every run."""
+    config.snapshot_settings = {'min_files': 1}
+    return config
+
+
+@pytest.fixture
+def desktop(config, db, action_manager, tmp_path):
+    """A desktop folder with three files, an action manager and folder history."""
+    folder = tmp_path / "desktop"
+    folder.mkdir()
+    for name in ("a.txt", "b.txt", "c.txt"):
+        (folder / name).write_text(name)
+    return action_manager, FolderHistory(config, db), folder
+
+
+def _organize(manager, path: Path):
+    return manager.execute(str(path), {
+        'suggested_path': 'Documents/',
+        'category': 'Documents',
+        'confidence': 'high',
+        'method': 'rule-based'
+    }, user_approved=True)
+
+
+def test_parse_point_in_time():
+    """Relative phrases resolve against a reference time."""
+    now = datetime(2025, 3, 6, 15, 30)  # A Thursday
+
+    assert parse_point_in_time('last monday', now) == datetime(2025, 3, 3, 23, 59, 59)
+    assert parse_point_in_time('last thursday', now).day == 27
+    assert parse_point_in_time('2 hours ago', now) == datetime(2025, 3, 6, 13, 30)
+    assert parse_point_in_time('2025-01-31', now) == datetime(2025, 1, 31, 23, 59, 59)
+    with pytest.raises(ValueError):
+        parse_point_in_time('whenever', now)
+
+
+def test_reverses_changes_made_since(desktop):
+    """Without a snapshot the past is rebuilt by reversing journaled changes."""
+    manager, history, folder = desktop
+    before = datetime.now()
+    time.sleep(1.1)
+
+    _organize(manager, folder / "a.txt")
+    manager.delete_file(str(folder / "b.txt"))
+    (folder / "new.txt").write_text("new")
+
+    result = history.folder_as_of(str(folder), before)
+
+    assert result['success'], result['message']
+    assert result['files'] == ['a.txt', 'b.txt', 'c.txt', 'new.txt']  # new.txt was not made by the app
+    moved = {m['path']: m['now_at'] for m in result['diff']['moved']}
+    assert moved['a.txt'].endswith('Documents/a.txt')
+    assert 'trash' in moved['b.txt']
+    assert result['diff']['unchanged'] == 2
+
+
+def test_replays_from_snapshot(desktop, tmp_path):
+    """With a snapshot, files created outside the app afterwards are not in the past state."""
+    manager, history, folder = desktop
+    SnapshotManager(manager.config, manager.db_manager).create_snapshot(str(folder))
+    time.sleep(1.1)
+    (folder / "downloaded.txt").write_text("later")
+    when = datetime.now()
+    time.sleep(1.1)
+    _organize(manager, folder / "c.txt")
+
+    result = history.folder_as_of(str(folder), when)
+
+    assert result['source'].startswith('snapshot #')
+    assert result['files'] == ['a.txt', 'b.txt', 'c.txt']
+    assert result['diff']['added'] == ['downloaded.txt']
+    assert [m['path'] for m in result['diff']['moved']] == ['c.txt']
+
+
+def test_query_history_sums_up_moves_and_ai_cost(desktop):
+    """Moves are counted per day, category, model and operation; skips are left out; filters narrow it down."""
+    manager, _, folder = desktop
+    db = manager.db_manager
+    _organize(manager, folder / "a.txt")
+    manager.execute(str(folder / "b.txt"), {'suggested_path': 'Music/', 'category': 'Music', 'confidence': 'high',
+                                            'method': 'ai', 'model': 'llama3'}, user_approved=True)
+    db.log_action('c.txt', str(folder / "c.txt"), None, 'skipped', count_stats=False)
+    db.add_usage('run-1', None, 'ollama', 'llama3', 120, 0.25, datetime.now().strftime('%Y-%m-%d %H:%M:%S'))
+
+    today = datetime.now().strftime('%Y-%m-%d')
+    stats = manager.journal.query_history({'since': today})
+    assert stats['files'] == 2 and stats['cost_usd'] == 0.25
+    assert stats['by_day'] == [{'day': today, 'files': 2}]
+    assert {row['category']: row['files'] for row in stats['by_category']} == {'Documents': 1, 'Music': 1}
+    assert {row['model']: (row['files'], row['cost_usd']) for row in stats['by_model']} == {
+        'llama3': (1, 0.25), 'rules': (1, 0)}
+    assert stats['by_operation'] == [{'operation': 'move', 'files': 2}]
+
+    assert manager.journal.query_history({'model': 'rules'})['files'] == 1
+    assert manager.journal.query_history({'operation': 'skipped'})['files'] == 1
+    assert manager.journal.query_history({'category': 'music', 'folder': str(folder)})['files'] == 1
+    assert manager.journal.query_history({'until': '2000-01-01'})['files'] == 0
+    with pytest.raises(ValueError, match="Unknown history filter"):
+        manager.journal.query_history({'user': 'me'})
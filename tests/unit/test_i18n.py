@@ -0,0 +1,85 @@
+"""
+Unit tests for the localization layer.
+
+Tests translation of category names and destination folders, and that the
+classifier localizes results without polluting its cache.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent / "src"))
+
+from core.i18n import Translator, get_translator, normalize_locale, supported_locales
+from core.classifier import FileClassifier
+from config import Config
+
+
+class TestTranslator:
+    """Test Translator behaviour."""
+
+    def test_localize_path_german(self):
+        """Known segments are translated and the trailing slash is kept."""
+        translator = Translator('de')
+        assert translator.localize_path('Documents/Finance/Invoices/') == 'Dokumente/Finanzen/Rechnungen/'
+
+    def test_unknown_segments_unchanged(self):
+        """Years, client names and product names pass through."""
+        translator = Translator('de')
+        assert translator.localize_path('Projects/Acme/2024/PDFs') == 'Projekte/Acme/2024/PDFs'
+
+    def test_english_is_identity(self):
+        """English locale returns paths untouched."""
+        assert Translator('en').localize_path('Pictures/Screenshots/') == 'Pictures/Screenshots/'
+
+    @pytest.mark.parametrize("raw,expected", [
+        ('de_DE', 'de'),
+        ('fr-CA', 'fr'),
+        ('ES', 'es'),
+        ('xx', 'en'),
+        (None, 'en'),
+    ])
+    def test_normalize_locale(self, raw, expected):
+        """Regional and unknown locales are normalized."""
+        assert normalize_locale(raw) == expected
+
+    def test_supported_locales_english_first(self):
+        """English is always the first supported locale."""
+        locales = supported_locales()
+        assert locales[0] == 'en'
+        assert 'de' in locales
+
+    def test_localize_classification_keeps_category(self):
+        """Canonical category is kept; label and path are localized."""
+        result = {'category': 'Invoices', 'suggested_path': 'Documents/Finance/Invoices/'}
+        localized = get_translator('de').localize_classification(result)
+
+        assert localized['category'] == 'Invoices'
+        assert localized['category_label'] == 'Rechnungen'
+        assert localized['suggested_path'] == 'Dokumente/Finanzen/Rechnungen/'
+        assert localized['locale'] == 'de'
+        assert result['suggested_path'] == 'Documents/Finance/Invoices/'
+
+
+class TestClassifierLocalization:
+    """Test classifier integration."""
+
+    def test_classify_uses_configured_locale(self, tmp_path):
+        """Classifier output follows the locale while the cache stays canonical."""
+        config = Mock(spec=Config)
+        config.destination_rules = {'pdf': 'Documents/PDFs/'}
+        config.locale = 'de'
+        classifier = FileClassifier(config, ollama_client=None)
+
+        test_file = tmp_path / "report.pdf"
+        test_file.write_text("content")
+
+        result = classifier.classify(str(test_file))
+        assert result['suggested_path'] == 'Dokumente/PDFs/'
+        assert result['locale'] == 'de'
+
+        config.locale = 'en'
+        result = classifier.classify(str(test_file))
+        assert result['suggested_path'] == 'Documents/PDFs/'
@@ -0,0 +1,74 @@
+"""
+Unit tests for ignore rules (.organizerignore).
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.file_listing import list_files_recursive
+from src.core.ignore_rules import (IgnoreRules, add_ignore_rule, list_ignore_rules, parse_rule,
+                                   remove_ignore_rule)
+
+
+@pytest.fixture(autouse=True)
+def config_dir(tmp_path):
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path / "config")}):
+        yield tmp_path / "config"
+
+
+def test_rules_match_globs_sizes_and_negations(tmp_path):
+    """Defaults catch repositories and temp files; the last matching rule wins."""
+    project = tmp_path / "project"
+    (project / "node_modules" / "lib").mkdir(parents=True)
+    (project / "node_modules" / "lib" / "index.js").write_text("js")
+    (project / "Projects").mkdir()
+    (project / "Projects" / "cover.psd").write_text("psd")
+    (project / "big.iso").write_bytes(b'\x00' * 2048)
+    (project / ".organizerignore").write_text("# local rules\n!keep.tmp\nsize > 1KB\n/Projects/*.psd\nsize > lots\n")
+
+    rules = IgnoreRules.for_folder(str(project))
+    assert rules.match("node_modules", is_dir=True) == "matches 'node_modules/'"
+    assert rules.match(".git", is_dir=True) and not rules.match("src/node_modules", is_dir=False)
+    assert rules.match("draft.tmp", is_dir=False) == "matches '*.tmp'"
+    assert rules.match("keep.tmp", is_dir=False) is None
+    assert rules.match("big.iso") == "larger than 1KB"
+    assert rules.match("Projects/cover.psd") and not rules.match("cover.psd", is_dir=False)
+    assert rules.ignores(str(project / "node_modules" / "lib" / "index.js")) == "matches 'node_modules/'"
+    assert rules.match("Thumbs.db", is_dir=False) == "system file"
+    with pytest.raises(ValueError):
+        parse_rule("size > lots")
+
+
+def test_listing_skips_ignored_entries_and_rules_can_be_managed(tmp_path, config_dir):
+    """list_files leaves out ignored files and folders; rules are added to and removed from ignore files."""
+    folder = tmp_path / "Downloads"
+    (folder / ".git").mkdir(parents=True)
+    (folder / "node_modules").mkdir()
+    (folder / "node_modules" / "pkg.js").write_text("js")
+    (folder / "report.pdf").write_text("pdf")
+    (folder / "movie.mkv.part").write_text("partial")
+
+    listing = list_files_recursive(str(folder), include_hidden=True)
+    assert [item['path'] for item in listing['items']] == ['report.pdf'] and listing['ignored'] == 3
+    assert len(list_files_recursive(str(folder), respect_ignore=False)['items']) == 4
+
+    assert add_ignore_rule("*.pdf", str(folder))['success']
+    assert add_ignore_rule("*.pdf", str(folder))['message'].startswith("Already")
+    assert list_ignore_rules(str(folder))['rules'] == ['*.pdf']
+    assert list_files_recursive(str(folder))['items'] == []
+    assert remove_ignore_rule("*.pdf", str(folder))['success']
+    assert not remove_ignore_rule("*.pdf", str(folder))['success']
+
+    add_ignore_rule("report.*")
+    assert (config_dir / "organizerignore").read_text() == "report.*\n"
+    assert list_files_recursive(str(folder))['items'] == []
+    with pytest.raises(ValueError):
+        add_ignore_rule("# just a comment")
+    with pytest.raises(NotADirectoryError):
+        list_ignore_rules(str(tmp_path / "missing"))
@@ -0,0 +1,67 @@
+"""
+Unit tests for interpreter and app folder resolution.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.interpreter import detect_python_environments, resolve_app_root, resolve_python
+
+
+def _python(folder: Path) -> Path:
+    path = folder / 'bin' / 'python3'
+    path.parent.mkdir(parents=True)
+    path.write_text('#!/bin/sh\n')
+    return path
+
+
+def _environment(**overrides):
+    environment = {key: value for key, value in os.environ.items()
+                   if key not in ('AIFO_PYTHON', 'AIFO_APP_ROOT', 'AIFO_RESOURCE_DIR')}
+    environment.update(overrides)
+    return patch.dict(os.environ, environment, clear=True)
+
+
+def test_resolution_order(tmp_path):
+    """Environment, then settings, then the bundled runtime, then a venv beside the scripts."""
+    app = tmp_path / 'app'
+    (app / 'src').mkdir(parents=True)
+    (app / 'src' / 'main.py').write_text('')
+    venv = _python(app / '.venv')
+    bundled = _python(tmp_path / 'resources' / 'python')
+    configured = _python(tmp_path / 'configured')
+    override = _python(tmp_path / 'override')
+    config = Mock(runtime_settings={'python': str(configured), 'app_root': str(app)})
+
+    with _environment(AIFO_PYTHON=str(override), AIFO_RESOURCE_DIR=str(tmp_path / 'resources')):
+        assert resolve_app_root(config) == app.resolve()
+        assert resolve_python(config) == str(override)
+    with _environment(AIFO_PYTHON=str(tmp_path / 'missing'), AIFO_RESOURCE_DIR=str(tmp_path / 'resources')):
+        assert resolve_python(config) == str(configured)
+        config.runtime_settings['python'] = ''
+        assert resolve_python(config) == str(bundled)
+    with _environment():
+        assert resolve_python(config) == str(venv)
+        config.runtime_settings['app_root'] = str(tmp_path / 'not-an-app')
+        assert resolve_python(config) == sys.executable
+        assert (resolve_app_root(config) / 'src' / 'main.py').is_file()
+
+
+def test_detects_environments_and_marks_the_selected_one(tmp_path):
+    """Each interpreter is listed once, probed for its version and packages."""
+    config = Mock(runtime_settings={'python': sys.executable, 'app_root': ''})
+    with _environment(AIFO_PYTHON=str(tmp_path / 'missing')):
+        found = detect_python_environments(config)
+
+    assert found['selected'] == sys.executable
+    by_path = {environment['path']: environment for environment in found['environments']}
+    current = by_path[sys.executable]
+    assert current['source'] == 'settings' and current['selected']
+    assert current['version'] == '.'.join(map(str, sys.version_info[:3])) and current['packages'] is True
+    missing = by_path[str(tmp_path / 'missing')]
+    assert (missing['exists'], missing['version'], missing['selected']) == (False, None, False)
+    assert len(by_path) == len(found['environments'])
@@ -0,0 +1,82 @@
+"""
+Unit tests for invoice and receipt field extraction.
+"""
+
+import csv
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.invoices import InvoiceExtractor, find_fields
+
+AMAZON = """Amazon.com order confirmation
+Invoice
+Order number: 112-3456789-0123456
+Invoice date: March 15, 2024
+Subtotal: $54.99
+Tax: $5.00
+Grand Total: $59.99
+"""
+
+BAKERY = """Bäckerei Schmidt GmbH
+Hauptstraße 4, Berlin
+Rechnung Nr. RE-2024-118
+Datum: 02.04.2024
+Zwischensumme 1.080,00 €
+MwSt 19%   205,20 €
+Gesamtbetrag
+1.285,20 €
+"""
+
+
+def _extractor(**settings):
+    config = Mock()
+    config.invoice_settings = settings
+    config.screenshot_settings = {}
+    return InvoiceExtractor(config)
+
+
+def test_fields_are_found_in_english_and_european_invoices():
+    """Totals skip subtotals and tax; dates and amounts follow the text's conventions."""
+    assert find_fields(AMAZON) == {'vendor': 'Amazon', 'date': '2024-03-15', 'amount': 59.99,
+                                   'currency': 'USD', 'number': '112-3456789-0123456'}
+    assert find_fields(BAKERY) == {'vendor': 'Bäckerei Schmidt GmbH', 'date': '2024-04-02', 'amount': 1285.20,
+                                   'currency': 'EUR', 'number': 'RE-2024-118'}
+    assert find_fields("Receipt\nFrom: Corner Cafe\n03/04/2024\nTOTAL 7.50")['date'] == '2024-04-03'
+    assert find_fields("Receipt\nFrom: Corner Cafe\n03/04/2024\nTOTAL $7.50")['date'] == '2024-03-04'
+
+
+def test_invoices_are_filed_by_template_and_exported(tmp_path):
+    """The template names the destination; non-invoices are ignored; the LLM fills gaps."""
+    (tmp_path / 'order.txt').write_text(AMAZON)
+    (tmp_path / 'bakery.html').write_text('<p>' + BAKERY.replace('\n', '<br>') + '</p>')
+    (tmp_path / 'notes.txt').write_text('Shopping list: eggs, milk')
+
+    extractor = _extractor()
+    placed = extractor.classify(str(tmp_path / 'order.txt'))
+    assert (placed['category'], placed['suggested_path'], placed['rename']) == (
+        'Finance', 'Finance/2024/', '2024-03 Amazon 59.99.txt')
+    assert placed['confidence'] == 'high'
+    assert extractor.classify(str(tmp_path / 'notes.txt')) is None
+
+    custom = _extractor(template='Receipts/{vendor}/{year}-{month}-{day} {amount} {currency}')
+    assert custom.destination(find_fields(BAKERY), '.PDF') == (
+        'Receipts/Bäckerei Schmidt GmbH/', '2024-04-02 1285.20 EUR.pdf')
+
+    result = extractor.scan(str(tmp_path))
+    assert result['success'] and result['documents'] == 3 and len(result['rows']) == 2
+    out = extractor.export_csv(result['rows'], str(tmp_path / 'out' / 'invoices.csv'))
+    with open(out, encoding='utf-8') as f:
+        rows = list(csv.DictReader(f))
+    assert [(r['vendor'], r['amount']) for r in rows] == [('Bäckerei Schmidt GmbH', '1285.2'), ('Amazon', '59.99')]
+
+    llm = Mock()
+    llm.chat.return_value = 'Sure: {"vendor": "Corner Cafe", "date": "2024-05-01", "amount": 7.5}'
+    config = Mock()
+    config.invoice_settings = {'llm': True}
+    config.screenshot_settings = {}
+    fields = InvoiceExtractor(config, llm).extract(str(tmp_path / 'r.txt'), text='RECEIPT\nTOTAL 7.50')
+    assert (fields['vendor'], fields['date'], fields['amount']) == ('Corner Cafe', '2024-05-01', 7.5)
+    assert fields['source'] == 'text+llm'
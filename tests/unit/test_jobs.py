@@ -0,0 +1,66 @@
+"""
+Unit tests for the job registry (listing and cancelling organize runs).
+"""
+
+import sys
+import threading
+import time
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.jobs import JobManager, EVENT_JOB_FINISHED
+from src.core.progress_events import stream_organize, event_line, EVENT_PROGRESS, EVENT_FILE_DONE
+
+
+def test_jobs_are_listed_tracked_and_cancelled():
+    """A job reports progress while running, stops when cancelled and keeps its outcome."""
+    started = threading.Event()
+
+    def runner(folder, on_event, preview, deep, strategy, cancel):
+        on_event(EVENT_PROGRESS, {'phase': 'organizing', 'done': 1, 'total': 9, 'file': f"{folder}/a"})
+        on_event(EVENT_FILE_DONE, {'file': f"{folder}/a", 'status': 'organized'})
+        if folder == '/fine':
+            return {'success': True, 'message': 'Organized 1 file(s)', 'summary': {'organized': 1}}
+        started.set()
+        cancel.wait(10)
+        return {'success': False, 'message': 'Cancelled', 'summary': None}
+
+    events = []
+    jobs = JobManager(runner, listener=lambda job_id, event, payload: events.append(event))
+    fine = jobs.start_organize('/fine')
+    assert jobs.wait(fine['id'], 10)['state'] == 'done'
+
+    slow = jobs.start_organize('/slow', preview=True)
+    assert started.wait(10)
+    running = jobs.list_jobs(include_finished=False)
+    assert [job['id'] for job in running] == [slow['id']]
+    assert running[0]['progress']['done'] == 1 and running[0]['files_done'] == 1
+
+    cancelled = jobs.cancel_job(slow['id'])
+    assert cancelled['success'] and cancelled['job']['state'] == 'cancelling'
+    assert jobs.wait(slow['id'], 10)['state'] == 'cancelled'
+    assert not jobs.cancel_job(slow['id'])['success']
+    assert jobs.cancel_job('nope') == {'success': False, 'message': 'No job nope', 'job': None}
+    assert len(jobs.list_jobs()) == 2 and events.count(EVENT_FILE_DONE) == 2
+    assert events.count(EVENT_JOB_FINISHED) == 2
+
+
+def test_cancel_stops_the_child_process():
+    """Cancelling ends the spawned run promptly instead of waiting for it to finish."""
+    script = (f"import time\nprint({event_line(EVENT_PROGRESS, {'phase': 'organizing'})!r}, flush=True)\n"
+              "time.sleep(60)\n")
+
+    jobs = JobManager(lambda folder, on_event, preview, deep, strategy, cancel: stream_organize(
+        folder, on_event, command=[sys.executable, '-c', script], cancel=cancel))
+    job = jobs.start_organize('/big')
+    deadline = time.time() + 10
+    while jobs.get_job(job['id'])['progress']['phase'] != 'organizing' and time.time() < deadline:
+        time.sleep(0.05)
+
+    began = time.time()
+    jobs.cancel_job(job['id'])
+    finished = jobs.wait(job['id'], 30)
+    assert finished['state'] == 'cancelled' and finished['message'] == 'Cancelled'
+    assert time.time() - began < 10
+    assert jobs.shutdown() == 0
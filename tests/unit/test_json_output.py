@@ -0,0 +1,76 @@
+"""
+Unit tests for the CLI's machine-readable --json output.
+"""
+
+import json
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from click.testing import CliRunner
+
+from src.cli.commands import cli
+from src.utils.output_schemas import EXIT_OK, EXIT_ERROR, EXIT_USAGE
+from src.core.db_manager import DatabaseManager
+from src.config import Config
+
+
+@pytest.fixture
+def run(tmp_path):
+    """Invoke the CLI on a temporary database, keeping stdout and stderr apart."""
+    config = Mock(spec=Config)
+    config.version_settings = {'store_dir': str(tmp_path / "versions")}
+    config.pinned_folder_settings = {}
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    try:
+        runner = CliRunner(mix_stderr=False)
+    except TypeError:
+        runner = CliRunner()  # click >= 8.2 always keeps stderr apart
+
+    with patch('src.config.get_config', return_value=config), \
+            patch('src.core.db_manager.DatabaseManager', return_value=db):
+        yield lambda *args: runner.invoke(cli, list(args))
+    db.cleanup()
+
+
+def test_json_document_is_the_only_stdout(run, tmp_path):
+    """Human output moves to stderr; stdout holds one versioned document."""
+    (tmp_path / "inbox").mkdir()
+
+    result = run('--json', 'pin', str(tmp_path / "inbox"))
+
+    document = json.loads(result.stdout)
+    assert document['schema'] == 'aifo.pin' and document['schema_version'] == 1
+    assert document['ok'] and document['data']['pins'][0]['path'] == str(tmp_path / "inbox")
+    assert any('Pinned' in m for m in document['messages'])
+
+    missing = run('--json', 'collection', 'Nope', '--delete')
+    document = json.loads(missing.stdout)
+    assert document['ok'] is False and document['data'] is None and document['errors']
+    assert missing.exit_code == EXIT_ERROR
+
+
+def test_exit_codes(run, tmp_path):
+    """Scripts can tell success, failure and bad arguments apart."""
+    assert run('pin').exit_code == EXIT_OK
+    assert run('pin', str(tmp_path / "missing"), '--remove').exit_code == EXIT_ERROR
+    assert run('undo', '--steps', 'many').exit_code == EXIT_USAGE
+
+
+def test_json_declines_confirmations_without_yes(run, tmp_path):
+    """A --json run reports what it would do; --yes lets it act."""
+    a, b = tmp_path / "a", tmp_path / "b"
+    a.mkdir()
+    b.mkdir()
+    (a / "only-a.txt").write_text("a")
+
+    preview = json.loads(run('--json', 'compare', str(a), str(b), '--copy-missing').stdout)
+    assert len(preview['data']['merge_plan']['copies']) == 1 and preview['data']['merge'] is None
+    assert not (b / "only-a.txt").exists()
+
+    merged = json.loads(run('--json', '--yes', 'compare', str(a), str(b), '--copy-missing').stdout)
+    assert merged['ok'] and merged['data']['merge']['success']
+    assert (b / "only-a.txt").read_text() == "a"
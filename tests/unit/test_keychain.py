@@ -0,0 +1,82 @@
+"""
+Unit tests for keychain-backed secrets.
+"""
+
+import json
+import os
+import sys
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core import keychain
+from src.core.keychain import get_secret, secret_env_name, secret_environment, store_secret
+from src.core.settings import KEYCHAIN_MARKER, SettingsStore
+
+
+class FakeKeyring:
+    """An in-memory keyring backend."""
+
+    def __init__(self):
+        self.passwords = {}
+
+    def get_keyring(self):
+        return self
+
+    def set_password(self, service, name, value):
+        self.passwords[(service, name)] = value
+
+    def get_password(self, service, name):
+        return self.passwords.get((service, name))
+
+    def delete_password(self, service, name):
+        del self.passwords[(service, name)]
+
+
+@pytest.fixture
+def fake_keyring():
+    fake = FakeKeyring()
+    with patch.object(keychain, 'keyring', fake), patch.object(keychain, 'KEYRING_SUPPORT', True), \
+            patch.dict(os.environ, {}, clear=False):
+        yield fake
+
+
+def test_secrets_round_trip_and_reach_children_by_environment(fake_keyring):
+    """Stored secrets are read back; a child gets them in AIFO_SECRET_* variables, which win."""
+    store_secret('api_key.openai', 'sk-live-1234')
+    assert get_secret('api_key.openai') == 'sk-live-1234' and get_secret('api_key.other') is None
+    assert fake_keyring.passwords == {('ai-file-organiser', 'api_key.openai'): 'sk-live-1234'}
+
+    environment = secret_environment(['api_key.openai', 'api_key.other'])
+    assert environment == {'AIFO_SECRET_API_KEY_OPENAI': 'sk-live-1234'}
+    with patch.dict(os.environ, {secret_env_name('api_key.openai'): 'from-parent'}):
+        assert get_secret('api_key.openai') == 'from-parent'
+
+    assert keychain.delete_secret('api_key.openai') and not keychain.delete_secret('api_key.openai')
+    for bad in ('', '../x', 'a b'):
+        with pytest.raises(ValueError):
+            store_secret(bad, 'value')
+    with patch.object(keychain, 'KEYRING_SUPPORT', False), pytest.raises(keychain.KeychainError):
+        store_secret('api_key.openai', 'sk')
+
+
+def test_settings_keep_api_keys_out_of_the_file(fake_keyring, tmp_path):
+    """Keys saved through the settings go to the keychain; plain-text keys on disk are moved there."""
+    path = tmp_path / "settings.json"
+    path.write_text(json.dumps({'version': 1, 'api_keys': {'openai': 'sk-plaintext-9876'}}))
+    store = SettingsStore(str(path))
+    assert store.use_keychain
+    assert store.get_settings()['api_keys'] == {'openai': '••••••••9876'}
+    assert json.loads(path.read_text())['api_keys'] == {'openai': KEYCHAIN_MARKER}
+
+    store.update_settings({'api_keys': {'anthropic': 'sk-ant-5555'}})
+    assert 'sk-ant-5555' not in path.read_text()
+    assert store.get_settings(reveal_secrets=True)['api_keys'] == {'openai': 'sk-plaintext-9876',
+                                                                   'anthropic': 'sk-ant-5555'}
+    assert store.secret_environment() == {'AIFO_SECRET_API_KEY_OPENAI': 'sk-plaintext-9876',
+                                          'AIFO_SECRET_API_KEY_ANTHROPIC': 'sk-ant-5555'}
+    store.update_settings({'api_keys': {'openai': None}})
+    assert ('ai-file-organiser', 'api_key.openai') not in fake_keyring.passwords
@@ -0,0 +1,86 @@
+"""
+Unit tests for leave-alone folder detection.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock, MagicMock
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.leave_alone import LeaveAloneDetector, detect
+from src.core.plans import OrganizePlanner
+from src.core.watcher import FileEventHandler
+
+
+def _tree(root):
+    """Downloads with a repository, an app bundle, a game install and ordinary files."""
+    (root / 'repo' / '.git').mkdir(parents=True)
+    (root / 'repo' / 'main.py').write_text('print()' * 20)
+    (root / 'Tool.app' / 'Contents').mkdir(parents=True)
+    (root / 'Tool.app' / 'Contents' / 'Info.plist').write_text('<plist/>' * 20)
+    (root / 'Game').mkdir()
+    (root / 'Game' / 'UnityPlayer.dll').write_bytes(b'MZ' * 100)
+    (root / 'Game' / 'level1.dat').write_bytes(b'x' * 200)
+    (root / 'Trip').mkdir()
+    (root / 'Trip' / 'photo.jpg').write_bytes(b'\xff\xd8' * 100)
+    (root / 'invoice.pdf').write_bytes(b'%PDF' * 100)
+
+
+def _detector(**settings):
+    config = Mock()
+    config.leave_alone_settings = settings
+    return LeaveAloneDetector(config)
+
+
+def test_repositories_bundles_games_and_sync_roots_are_detected(tmp_path):
+    """Markers, bundle suffixes and home-level sync roots are recognized; lists override them."""
+    _tree(tmp_path)
+    assert detect(tmp_path / 'repo') == 'git repository'
+    assert detect(tmp_path / 'Tool.app') == 'application bundle'
+    assert detect(tmp_path / 'Game') == 'game install'
+    assert detect(tmp_path / 'Trip') is None
+    (tmp_path / 'Dropbox').mkdir()
+    assert detect(tmp_path / 'Dropbox', home=tmp_path) == 'Dropbox folder'
+    assert detect(tmp_path / 'Dropbox', home=tmp_path / 'elsewhere') is None
+
+    detector = _detector(always=[str(tmp_path / 'Trip')], allow=[str(tmp_path / 'Game')])
+    assert detector.reason(tmp_path / 'Trip') == 'on your leave-alone list'
+    assert detector.reason(tmp_path / 'Game') is None
+    assert detector.reason(tmp_path / 'repo') == 'git repository'
+    assert detector.enclosing(tmp_path / 'repo' / 'main.py', [tmp_path]) == (tmp_path / 'repo', 'git repository')
+    assert detector.enclosing(tmp_path / 'repo' / 'main.py', [tmp_path / 'repo']) is None
+    assert _detector(enabled=False).reason(tmp_path / 'repo') is None
+
+    config = Mock()
+    config.leave_alone_settings = {}
+    detector = LeaveAloneDetector(config)
+    result = detector.set_override(str(tmp_path / 'Trip'), 'always')
+    assert result['success'] and result['always'] == [str(tmp_path / 'Trip')]
+    config.update.assert_called_once_with('leave_alone', {'always': [str(tmp_path / 'Trip')], 'allow': []})
+    config.save.assert_called_once()
+    assert detector.set_override(str(tmp_path / 'Trip'), None)['always'] == []
+    assert not detector.set_override(str(tmp_path / 'Trip'), 'sometimes')['success']
+
+
+def test_planner_and_watcher_do_not_look_inside_left_alone_folders(tmp_path):
+    """Only ordinary files are planned; skipped folders are listed in the plan."""
+    _tree(tmp_path)
+    config = Mock()
+    config.get_folder_policy.return_value = None
+    config.leave_alone_settings = {}
+    classifier = Mock()
+    classifier.classify.return_value = {'category': 'Other', 'suggested_path': 'Other/'}
+    actions = MagicMock()
+    actions.preview_destination.side_effect = lambda path, c: {
+        'action': 'move', 'new_path': str(tmp_path / 'out' / Path(path).name)}
+
+    plan = OrganizePlanner(config, classifier, actions).build_plan(str(tmp_path))
+
+    assert sorted(Path(item['file']).name for item in plan['items']) == ['invoice.pdf', 'photo.jpg']
+    assert [(Path(e['path']).name, e['reason']) for e in plan['left_alone']] == [
+        ('Game', 'game install'), ('Tool.app', 'application bundle'), ('repo', 'git repository')]
+
+    handler = FileEventHandler(leave_alone=LeaveAloneDetector(config), roots=[tmp_path])
+    assert not handler._should_process(str(tmp_path / 'Game' / 'level1.dat'))
+    assert handler._should_process(str(tmp_path / 'Trip' / 'photo.jpg'))
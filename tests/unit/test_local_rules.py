@@ -0,0 +1,42 @@
+"""
+Unit tests for the offline local-rules classifier.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.local_rules import classify_file_offline
+
+
+def _config(rules=None):
+    config = Mock()
+    config.destination_rules = rules or {}
+    return config
+
+
+def test_extension_mime_type_and_name_decide_without_the_file():
+    """Each rule kind classifies a file that does not even exist, and says where the answer came from."""
+    pdf = classify_file_offline('/nowhere/report.PDF')
+    assert pdf['suggested_path'] == 'Documents/PDFs/' and pdf['category'] == 'Documents'
+    assert pdf['confidence'] == 'high' and pdf['model_used'] == 'local-rules'
+
+    shot = classify_file_offline('/nowhere/Screenshot 2025-03-01 at 10.00.png')
+    assert shot['suggested_path'] == 'Pictures/Screenshots/'
+    assert classify_file_offline('/nowhere/invoice-0042.pdf')['category'] == 'Finance'
+    assert classify_file_offline('/nowhere/my resume.unknownext')['confidence'] == 'medium'
+
+    by_mime = classify_file_offline('/nowhere/clip.mpg')
+    assert by_mime['suggested_path'] == 'Videos/' and 'MIME' in by_mime['reason']
+    unknown = classify_file_offline('/nowhere/blob.qqq')
+    assert unknown['suggested_path'] == 'Unsorted/' and unknown['confidence'] == 'low'
+
+
+def test_configured_destination_rules_come_first():
+    """The user's extension rules win over the built-in ones."""
+    config = _config({'pdf': 'Paperwork/Scans/'})
+    assert classify_file_offline('/x/scan.pdf', config)['suggested_path'] == 'Paperwork/Scans/'
+    assert classify_file_offline('/x/song.mp3', config)['suggested_path'] == 'Music/'
+    assert classify_file_offline('/x/song.mp3', None)['suggested_path'] == 'Music/'
@@ -0,0 +1,92 @@
+"""
+Unit tests for tag-based music library organizing.
+"""
+
+import struct
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.audio_tags import read_tags
+from src.core.music import MusicLibraryStrategy, parse_filename
+
+
+def _id3(**frames):
+    """An MP3 starting with an ID3v2.3 tag holding UTF-8 text frames."""
+    body = b''
+    for frame_id, text in frames.items():
+        data = b'\x03' + text.encode('utf-8')
+        body += frame_id.encode() + struct.pack('>L', len(data)) + b'\x00\x00' + data
+    size = len(body)
+    syncsafe = bytes([(size >> 21) & 0x7F, (size >> 14) & 0x7F, (size >> 7) & 0x7F, size & 0x7F])
+    return b'ID3\x03\x00\x00' + syncsafe + body + b'\xff\xfb' + b'\x00' * 64
+
+
+def _flac(**comments):
+    entries = [f"{k}={v}".encode() for k, v in comments.items()]
+    block = struct.pack('<L', 4) + b'test' + struct.pack('<L', len(entries))
+    block += b''.join(struct.pack('<L', len(e)) + e for e in entries)
+    return b'fLaC' + bytes([0x84]) + len(block).to_bytes(3, 'big') + block
+
+
+def _song(folder, name, content):
+    folder.mkdir(parents=True, exist_ok=True)
+    path = folder / name
+    path.write_bytes(content)
+    return path
+
+
+def test_tags_are_read_without_mutagen(tmp_path):
+    """ID3v2 and FLAC tags are normalized to the same fields."""
+    mp3 = _song(tmp_path, 'a.mp3', _id3(TIT2='Digital Love', TPE1='Daft Punk', TALB='Discovery',
+                                        TRCK='3/14', TPOS='1/1', TYER='2001', TCMP='1'))
+    flac = _song(tmp_path, 'b.flac', _flac(TITLE='Hey You', ARTIST='Pink Floyd', ALBUM='The Wall',
+                                           TRACKNUMBER='1', DISCNUMBER='2', DISCTOTAL='2', DATE='1979-11-30'))
+
+    with patch('src.core.audio_tags.HAS_MUTAGEN', False):
+        assert read_tags(str(mp3)) == {'title': 'Digital Love', 'artist': 'Daft Punk', 'album': 'Discovery',
+                                       'track': 3, 'tracktotal': 14, 'disc': 1, 'disctotal': 1,
+                                       'year': 2001, 'compilation': True}
+        assert read_tags(str(flac)) == {'title': 'Hey You', 'artist': 'Pink Floyd', 'album': 'The Wall',
+                                        'track': 1, 'disc': 2, 'disctotal': 2, 'year': 1979}
+        assert read_tags(str(_song(tmp_path, 'c.mp3', b'\x00' * 10))) == {}
+
+    assert parse_filename(Path('03 - Digital Love.mp3')) == {'track': 3, 'title': 'Digital Love'}
+    assert parse_filename(Path('2-01 Pink Floyd - Hey You.flac')) == {
+        'disc': 2, 'track': 1, 'artist': 'Pink Floyd', 'title': 'Hey You'}
+
+
+def test_songs_are_placed_by_artist_and_album_with_compilations_apart(tmp_path):
+    """Albums go under their artist, compilations under Compilations; fixups are only suggested."""
+    incoming = tmp_path / 'incoming'
+    single = _song(incoming, 'x.mp3', _id3(TIT2='Hey You', TPE1='Pink Floyd', TPE2='Pink Floyd',
+                                           TALB='The Wall', TRCK='1', TPOS='2/2'))
+    mix = incoming / 'Now 45'
+    for track, artist in enumerate(['Blur', 'Oasis', 'Pulp'], start=1):
+        _song(mix, f'{track}.mp3', _id3(TIT2=f'Song {track}', TPE1=artist, TALB='Now 45', TRCK=str(track)))
+    untagged = _song(incoming / 'Daft Punk' / 'Discovery', '03 - Digital Love.mp3', _id3())
+
+    config = Mock()
+    config.get_folder_policy.return_value = None
+    strategy = MusicLibraryStrategy(config)
+    with patch('src.core.audio_tags.HAS_MUTAGEN', False):
+        placed = strategy.classify(str(single), incoming)
+        compiled = strategy.classify(str(mix / '2.mp3'), incoming, {'music_destination': 'Audio'})
+        inferred = strategy.classify(str(untagged), incoming)
+        fixups = strategy.tag_fixups(str(incoming))
+
+    assert (placed['suggested_path'], placed['rename']) == ('Music/Pink Floyd/The Wall/', '2-01 Hey You.mp3')
+    assert placed['confidence'] == 'high' and not placed['compilation']
+    assert compiled['compilation']
+    assert (compiled['suggested_path'], compiled['rename']) == ('Audio/Compilations/Now 45/', '02 Oasis - Song 2.mp3')
+    assert inferred['suggested_path'] == 'Music/Daft Punk/Discovery/' and inferred['rename'] == '03 Digital Love.mp3'
+    assert strategy.classify(str(tmp_path / 'cover.jpg'), incoming) is None
+
+    assert fixups['success'] and fixups['songs'] == 5
+    by_field = {(Path(f['path']).name, f['field']): f['suggested'] for f in fixups['fixups']}
+    assert by_field[('03 - Digital Love.mp3', 'artist')] == 'Daft Punk'
+    assert by_field[('03 - Digital Love.mp3', 'track')] == 3
+    assert by_field[('1.mp3', 'albumartist')] == 'Various Artists' and by_field[('1.mp3', 'compilation')] is True
+    assert not any(name == 'x.mp3' for name, _ in by_field)
@@ -0,0 +1,58 @@
+"""
+Unit tests for desktop notifications.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.notifications import CONFLICT, FILE_MOVED, RUN_COMPLETED, Notifier, system_command
+from src.core.settings import SettingsStore
+from src.utils.error_handler import ConfigurationError
+
+
+def test_each_kind_follows_its_switch_in_the_settings(tmp_path):
+    """Runs, moves and conflicts get their own message; switched-off kinds are not sent."""
+    store = SettingsStore(str(tmp_path / "settings.json"), use_keychain=False)
+    sent = []
+    notifier = Notifier(store, sender=lambda title, message: sent.append((title, message)) or True,
+                        background=False)
+
+    assert notifier.run_completed({'folder': '/home/me/Downloads', 'state': 'done', 'message': 'Organized 3 file(s)'})
+    assert notifier.file_moved('/home/me/Downloads/a.pdf', '/home/me/Documents/Finance/a.pdf')
+    assert notifier.conflict(2, '/home/me/Downloads')
+    assert sent == [('Organizing finished', 'Downloads: Organized 3 file(s)'),
+                    ('File organized', 'Moved a.pdf to /home/me/Documents/Finance'),
+                    ('Conflicts to review', '2 file(s) in Downloads could not be moved: their destination is taken')]
+
+    store.update_settings({'notifications': {'file_moved': False}})
+    assert not notifier.wants(FILE_MOVED) and notifier.wants(RUN_COMPLETED)
+    store.update_settings({'notifications': {'enabled': False}})
+    assert not notifier.file_moved('/a.pdf', None) and not notifier.wants(CONFLICT) and len(sent) == 3
+    with pytest.raises(ConfigurationError):
+        store.update_settings({'notifications': {'on_error': True}})
+    with pytest.raises(ValueError):
+        notifier.notify('birthday', 'Hi', 'there')
+
+
+def test_system_commands_quote_the_text_and_the_tray_goes_first():
+    """Quotes cannot break out of the AppleScript or PowerShell strings; a shown tray icon is used instead."""
+    with patch.object(sys, 'platform', 'darwin'):
+        assert system_command('Done', 'Say "hi"') == ['osascript', '-e',
+                                                       'display notification "Say \\"hi\\"" with title "Done"']
+    with patch.object(sys, 'platform', 'win32'):
+        script = system_command("It's <done>", 'a & b')[-1]
+    assert "It''s &lt;done&gt;" in script and 'a &amp; b' in script
+    with patch.object(sys, 'platform', 'linux'), patch('src.core.notifications.shutil.which', return_value=None):
+        assert system_command('Done', 'x') is None
+
+    tray = Mock()
+    tray.notify.return_value = True
+    with patch('src.core.notifications.subprocess.run') as run:
+        assert Notifier(tray=tray, background=False).notify(RUN_COMPLETED, 'Done', 'x')
+    tray.notify.assert_called_once_with('Done', 'x')
+    run.assert_not_called()
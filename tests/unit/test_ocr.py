@@ -0,0 +1,92 @@
+"""
+Unit tests for OCR of screenshots and scanned documents.
+"""
+
+import subprocess
+import sys
+from pathlib import Path
+from unittest.mock import MagicMock, Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.classifier import FileClassifier
+from src.core.ocr import OcrEngine
+
+RECEIPT = """CORNER CAFE
+12 High Street
+Receipt
+14/03/2024
+Flat white 3.20
+TOTAL 7.50
+"""
+
+
+def _tesseract(tmp_path, text):
+    """A stand-in for Tesseract and pdftoppm: pdftoppm writes two pages, Tesseract 'reads' them."""
+    calls = []
+
+    def run(command, **kwargs):
+        calls.append(command)
+        if command[0] == 'pdftoppm':
+            Path(command[-1] + '-1.png').write_bytes(b'page')
+            Path(command[-1] + '-2.png').write_bytes(b'page')
+            return subprocess.CompletedProcess(command, 0, b'', b'')
+        return subprocess.CompletedProcess(command, 0, f"{text} ({Path(command[1]).name})\n", '')
+    return run, calls
+
+
+def _config(tmp_path, **settings):
+    binary = tmp_path / "tesseract"
+    binary.write_text("")  # Any existing file stands in for the binary
+    config = MagicMock()
+    config.ocr_settings = {'tesseract_path': str(binary), **settings}
+    config.screenshot_settings = {}
+    config.invoice_settings = {}
+    config.destination_rules = {}
+    config.enable_ai = False
+    config.text_extract_limit = 1000
+    config.locale = 'en'
+    return config
+
+
+def test_images_and_scanned_pdf_pages_are_read(tmp_path):
+    """Images go to Tesseract directly; PDFs are rendered page by page first; missing files raise."""
+    image = tmp_path / "shot.png"
+    image.write_bytes(b'\x89PNG')
+    scan = tmp_path / "scan.pdf"
+    scan.write_bytes(b'%PDF-1.4')
+    run, calls = _tesseract(tmp_path, "Hello")
+    engine = OcrEngine(_config(tmp_path, languages='eng+deu', max_pages=2))
+
+    with patch('src.core.ocr.subprocess.run', side_effect=run), \
+            patch('src.core.ocr.shutil.which', side_effect=lambda name: name if name == 'pdftoppm' else None):
+        assert engine.ocr_file(str(image)) == "Hello (shot.png)"
+        assert engine.ocr_file(str(scan)) == "Hello (page-1.png)\n\fHello (page-2.png)"
+        assert engine.status()['pdf'] is True
+    assert calls[0][-2:] == ['-l', 'eng+deu']
+    assert calls[1][:7] == ['pdftoppm', '-r', '200', '-f', '1', '-l', '2']
+
+    with pytest.raises(FileNotFoundError):
+        engine.ocr_file(str(tmp_path / "missing.png"))
+    assert OcrEngine(Mock(ocr_settings={'tesseract_path': str(tmp_path / "none")}, screenshot_settings={})
+                     ).ocr_file(str(image)) == ''
+
+
+def test_receipt_photos_are_classified_by_their_text(tmp_path):
+    """A receipt saved as IMG_2043.png is filed under Finance; camera photos are not read."""
+    receipt = tmp_path / "IMG_2043.png"
+    receipt.write_bytes(b'\x89PNG')
+    holiday = tmp_path / "IMG_2044.jpg"
+    holiday.write_bytes(b'\xff\xd8\xff')
+    run, calls = _tesseract(tmp_path, RECEIPT)
+    classifier = FileClassifier(_config(tmp_path), None)
+
+    camera = {'Make': 'Canon', 'Model': 'EOS R6'}
+    with patch('src.core.ocr.subprocess.run', side_effect=run), \
+            patch('src.core.ocr.read_exif', side_effect=lambda path: camera if path.endswith('.jpg') else {}):
+        result = classifier.classify(str(receipt))
+        assert (result['category'], result['suggested_path']) == ('Finance', 'Finance/2024/')
+        assert classifier._extract_file_info(holiday)['text_snippet'] is None
+    assert [Path(command[1]).name for command in calls] == ['IMG_2043.png']  # Read once, never the photo
@@ -133,6 +133,29 @@ class TestModelListing:
         
         assert models == []
 
+    def test_detect_and_list_local_models(self, ollama_client, mock_requests):
+        """Test detection of a running server and the details of its models."""
+        version = Mock(status_code=200)
+        version.json.return_value = {'version': '0.3.12'}
+        tags = Mock(status_code=200)
+        tags.json.return_value = {'models': [
+            {'name': 'qwen2.5:7b-instruct', 'size': 4683087332, 'modified_at': '2024-10-01T10:00:00Z',
+             'details': {'family': 'qwen2', 'parameter_size': '7.6B', 'quantization_level': 'Q4_K_M'}},
+            {'name': 'llama3:latest', 'size': 4661224676},
+        ]}
+        mock_requests.get.side_effect = lambda url, timeout: version if url.endswith('/api/version') else tags
+
+        status = ollama_client.detect()
+        assert status['running'] is True and status['version'] == '0.3.12'
+        assert status['model_installed'] is True
+        assert status['models'] == ['llama3:latest', 'qwen2.5:7b-instruct']
+        qwen = ollama_client.list_local_models()[1]
+        assert (qwen['family'], qwen['parameter_size'], qwen['quantization']) == ('qwen2', '7.6B', 'Q4_K_M')
+
+        mock_requests.get.side_effect = Exception("Connection refused")
+        assert ollama_client.detect()['running'] is False
+        assert ollama_client.list_local_models() == []
+
 
 class TestFileClassification:
     """Test file classification functionality."""
@@ -0,0 +1,121 @@
+"""
+Unit tests for EXIF reading and the photo (capture date) planner strategy.
+"""
+
+import os
+import struct
+import sys
+from datetime import datetime
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.exif import read_exif, capture_time
+from src.core.geocoding import ReverseGeocoder
+from src.core.photos import PhotoDateStrategy
+from src.core.plans import OrganizePlanner
+
+
+def exif_jpeg(path, taken=None, offset=None):
+    """Write a minimal JPEG whose EXIF holds DateTimeOriginal (and OffsetTimeOriginal)."""
+    exif_entries = []
+    data = b''
+    # Offsets below are relative to the TIFF header: header 8 + IFD0 (2 + 12 + 4) = 26
+    exif_ifd = 26
+    values_start = exif_ifd + 2 + 12 * 2 + 4
+    for tag, text in ((0x9003, taken), (0x9011, offset)):
+        if text:
+            raw = text.encode('ascii') + b'\x00'
+            exif_entries.append(struct.pack('<HHLL', tag, 2, len(raw), values_start + len(data)))
+            data += raw
+    exif_entries += [b'\x00' * 12] * (2 - len(exif_entries))  # Padding entries (tag 0 is ignored)
+    tiff = (b'II*\x00' + struct.pack('<L', 8)
+            + struct.pack('<H', 1) + struct.pack('<HHLL', 0x8769, 4, 1, exif_ifd) + struct.pack('<L', 0)
+            + struct.pack('<H', 2) + b''.join(exif_entries) + struct.pack('<L', 0) + data)
+    app1 = b'Exif\x00\x00' + tiff
+    path.write_bytes(b'\xff\xd8' + b'\xff\xe1' + struct.pack('>H', len(app1) + 2) + app1 + b'\xff\xd9')
+    return str(path)
+
+
+def test_reads_capture_time_with_offset(tmp_path):
+    photo = exif_jpeg(tmp_path / "a.jpg", "2024:05:17 23:30:00", "+09:00")
+
+    taken, source = capture_time(read_exif(photo))
+
+    assert source == 'DateTimeOriginal'
+    assert taken.isoformat() == '2024-05-17T23:30:00+09:00'
+    assert read_exif(str(tmp_path / "missing.jpg")) == {}
+
+
+def test_layouts_timezones_and_file_date_fallback(tmp_path):
+    strategy = PhotoDateStrategy(Mock())
+    trip = tmp_path / "Lisbon trip"
+    trip.mkdir()
+    photo = exif_jpeg(trip / "IMG_1.jpg", "2024:06:01 06:30:00", "+09:00")
+
+    by_month = strategy.classify(photo, tmp_path)
+    assert by_month['suggested_path'] == 'Pictures/2024/06/' and by_month['date_source'] == 'exif'
+
+    by_day = strategy.classify(photo, tmp_path, {'photo_layout': 'day', 'photo_destination': '/photos'})
+    assert by_day['suggested_path'] == '2024/2024-06-01 Lisbon trip/' and by_day['destination_root'] == '/photos'
+
+    # Early morning of June 1st at +09:00 is still May 31st in UTC
+    in_utc = strategy.classify(photo, tmp_path, {'photo_layout': 'day', 'photo_timezone': 'UTC',
+                                                 'photo_event': 'Trip'})
+    assert in_utc['suggested_path'] == 'Pictures/2024/2024-05-31 Trip/'
+    assert in_utc['captured_at'] == '2024-05-31T21:30:00+00:00'
+
+    plain = tmp_path / "scan.png"
+    plain.write_bytes(b'\x89PNG')
+    stamp = datetime(2021, 3, 4, 12, 0).timestamp()
+    os.utime(plain, (stamp, stamp))
+    fallback = strategy.classify(str(plain), tmp_path)
+    assert fallback['suggested_path'] == 'Pictures/2021/03/' and fallback['date_source'] == 'file'
+
+    assert strategy.classify(str(tmp_path / "notes.txt"), tmp_path) is None
+
+
+def test_planner_uses_the_folder_policy_strategy(tmp_path):
+    camera = tmp_path / "camera"
+    camera.mkdir()
+    photo = exif_jpeg(camera / "IMG_2.jpg", "2023:12:31 10:00:00")
+    (camera / "notes.txt").write_text("not a photo")
+
+    config = Mock()
+    config.get_folder_policy.side_effect = lambda path: {'strategy': 'photos'} if 'camera' in path else None
+    classifier = Mock()
+    classifier.classify.return_value = {'category': 'Documents', 'suggested_path': 'Documents/'}
+    planner = OrganizePlanner(config, classifier, Mock())
+
+    assert planner.classify(photo, camera)['suggested_path'] == 'Pictures/2023/12/'
+    assert planner.classify(str(camera / "notes.txt"), camera)['category'] == 'Documents'
+    classifier.classify.assert_called_once()
+
+
+def test_location_layout_resolves_places_offline_unless_online_is_allowed(tmp_path):
+    photo = exif_jpeg(tmp_path / "IMG_3.jpg", "2024:08:02 18:00:00")
+    config = Mock()
+    config.geocoding_settings = {'online': False}
+    strategy = PhotoDateStrategy(config)
+
+    with patch('src.core.photos.gps_coordinates', return_value=(38.7139, -9.1394)):
+        near_lisbon = strategy.classify(photo, tmp_path, {'photo_layout': 'location'})
+    assert near_lisbon['suggested_path'] == 'Pictures/2024/Portugal/Lisbon/'
+
+    with patch('urllib.request.urlopen') as urlopen, \
+            patch('src.core.photos.gps_coordinates', return_value=(-54.8, -68.3)):
+        remote = strategy.classify(photo, tmp_path, {'photo_layout': 'location'})
+    assert remote['suggested_path'] == 'Pictures/2024/Unknown location/'
+    urlopen.assert_not_called()  # The privacy toggle is off
+
+    config.geocoding_settings = {'online': True}
+    geocoder = ReverseGeocoder(config, cache_path=str(tmp_path / "cache.json"))
+    answer = Mock()
+    answer.read.return_value = b'{"address": {"town": "Ushuaia", "country": "Argentina"}}'
+    urlopen_cm = Mock(__enter__=Mock(return_value=answer), __exit__=Mock(return_value=False))
+    with patch('src.core.geocoding.urllib.request.urlopen', return_value=urlopen_cm) as urlopen:
+        assert geocoder.place(-54.8019, -68.3030) == {'country': 'Argentina', 'city': 'Ushuaia', 'source': 'online'}
+        geocoder.place(-54.8021, -68.3029)  # Same rounded point: answered from the cache
+    assert urlopen.call_count == 1
+    assert 'lat=-54.8&lon=-68.3' in urlopen.call_args[0][0].full_url  # Only coarse coordinates leave the machine
@@ -0,0 +1,67 @@
+"""
+Unit tests for pinned folders and their quick stats.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.pins import PinnedFolders
+
+
+@pytest.fixture
+def config(config):
+    """Keeps pinned folder stats for an hour."""
+    config.pinned_folder_settings = {'stats_max_age': 3600}
+    return config
+
+
+@pytest.fixture
+def downloads(config, db, tmp_path):
+    """A downloads folder with loose files, a subfolder and pinned folder support."""
+    folder = tmp_path / "downloads"
+    (folder / "keep").mkdir(parents=True)
+    (folder / "report.pdf").write_text("report")
+    (folder / "setup.exe").write_text("setup")
+    (folder / "keep" / "inner.txt").write_text("inner")
+    (folder / ".hidden").write_text("hidden")
+
+    classifier = Mock()
+    classifier.classify.side_effect = lambda path: {
+        'category': 'Documents', 'suggested_path': 'Documents/', 'confidence': 'high', 'method': 'rule-based'
+    } if path.endswith('.pdf') else {'category': 'Other', 'suggested_path': None, 'confidence': 'low', 'method': 'rule-based'}
+    return PinnedFolders(config, db, classifier), folder, tmp_path
+
+
+def test_pin_and_order(downloads):
+    """Pinning computes stats; pins are listed in the chosen order."""
+    pins, folder, tmp_path = downloads
+    other = tmp_path / "desktop"
+    other.mkdir()
+
+    result = pins.pin(str(folder))
+    pins.pin(str(other), label='Desk')
+
+    assert result['success']
+    assert result['pin']['item_count'] == 4 and result['pin']['unorganized_count'] == 1
+    assert result['pin']['last_run'] is None
+    assert [p['label'] for p in pins.list()] == ['downloads', 'Desk']
+    assert [p['label'] for p in pins.reorder([str(other)])] == ['Desk', 'downloads']
+    assert pins.pin(str(tmp_path / "missing"))['success'] is False
+    assert pins.unpin(str(other)) and not pins.unpin(str(other))
+
+
+def test_stats_follow_runs(downloads, action_manager):
+    """An organize run touching the folder makes its stats stale and sets last_run."""
+    pins, folder, tmp_path = downloads
+    pins.pin(str(folder))
+
+    action_manager.execute(str(folder / "report.pdf"), pins.classifier.classify('report.pdf'), user_approved=True)
+
+    pin = pins.list()[0]
+
+    assert pin['item_count'] == 3 and pin['unorganized_count'] == 0
+    assert pin['last_run'] is not None
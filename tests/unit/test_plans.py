@@ -0,0 +1,238 @@
+"""
+Unit tests for organize plans (the headless plan/apply engine).
+"""
+
+import json
+import os
+import time
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.plans import OrganizePlanner, PLAN_VERSION, PlanOptions
+from src.utils.structured_logging import timed
+
+
+@pytest.fixture
+def planner(config, action_manager, tmp_path):
+    """A downloads folder and a planner with an approving action manager."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    (downloads / "report.pdf").write_text("report")
+    (downloads / "song.mp3").write_text("song")
+    (downloads / "notes.xyz").write_text("unknown")
+
+    paths = {'.pdf': 'Documents/', '.mp3': 'Music/'}
+    classifier = Mock()
+    classifier.classify.side_effect = lambda path, deep_analysis=False: {
+        'category': 'Known' if Path(path).suffix in paths else 'Other',
+        'suggested_path': paths.get(Path(path).suffix),
+        'confidence': 'high', 'method': 'rule-based'
+    }
+    return OrganizePlanner(config, classifier, action_manager), downloads, tmp_path
+
+
+def test_plan_file_round_trip(planner):
+    """Plans list only files with something to do and survive a save/load."""
+    engine, downloads, tmp_path = planner
+
+    plan = engine.build_plan(str(downloads))
+
+    assert plan['version'] == PLAN_VERSION and plan['unchanged'] == 1
+    assert {Path(i['file']).name: i['destination'] for i in plan['items']} == {
+        'report.pdf': str(tmp_path / "organized" / "Documents" / "report.pdf"),
+        'song.mp3': str(tmp_path / "organized" / "Music" / "song.mp3")
+    }
+    assert (downloads / "report.pdf").exists()  # Planning moves nothing
+
+    path = engine.save_plan(plan, str(tmp_path / "plan.json"))
+    assert engine.load_plan(path) == plan
+
+    (tmp_path / "bad.json").write_text(json.dumps({**plan, 'version': 99}))
+    with pytest.raises(ValueError):
+        engine.load_plan(str(tmp_path / "bad.json"))
+
+
+def test_apply_is_one_undoable_operation(planner):
+    """Applying a plan skips vanished files and can be undone in one step."""
+    engine, downloads, tmp_path = planner
+    plan = engine.build_plan(str(downloads))
+    (downloads / "song.mp3").unlink()
+
+    result = engine.apply_plan(plan)
+
+    assert result['success'] and len(result['applied']) == 1 and len(result['skipped']) == 1
+    assert (tmp_path / "organized" / "Documents" / "report.pdf").exists()
+    assert engine.action_manager.journal.undo(result['operation_id'])['success']
+    assert (downloads / "report.pdf").exists()
+
+
+def test_plan_flags_conflicts_and_apply_skips_changed_files(planner):
+    """Taken destinations are flagged; files edited after planning are left alone."""
+    engine, downloads, tmp_path = planner
+    (tmp_path / "organized" / "Music").mkdir(parents=True)
+    (tmp_path / "organized" / "Music" / "song.mp3").write_text("older song")
+
+    plan = engine.build_plan(str(downloads))
+    conflicts = {Path(i['file']).name: i['conflict'] for i in plan['items']}
+    assert plan['conflicts'] == 1 and conflicts == {'report.pdf': None, 'song.mp3': 'destination_exists'}
+
+    (downloads / "report.pdf").write_text("report, edited after review")
+    result = engine.apply_plan(plan)
+
+    assert [Path(i['file']).name for i in result['skipped']] == ['report.pdf']
+    assert (downloads / "report.pdf").exists() and len(result['applied']) == 1
+
+
+def test_renamed_file_going_to_a_free_destination_is_not_a_conflict(planner):
+    """A classifier rename is the wanted name, so only a counter suffix marks a taken destination."""
+    engine, downloads, tmp_path = planner
+    engine.classifier.classify.side_effect = lambda path, deep_analysis=False: {
+        'category': 'Music', 'suggested_path': 'Music/', 'rename': '01 Title.mp3',
+        'confidence': 'high', 'method': 'rule-based'} if path.endswith('.mp3') else {
+        'category': 'Other', 'suggested_path': None, 'confidence': 'low', 'method': 'rule-based'}
+
+    plan = engine.build_plan(str(downloads))
+    assert [(Path(i['destination']).name, i['conflict']) for i in plan['items']] == [('01 Title.mp3', None)]
+    assert plan['conflicts'] == 0
+
+    (tmp_path / "organized" / "Music" / "01 Title.mp3").parent.mkdir(parents=True)
+    (tmp_path / "organized" / "Music" / "01 Title.mp3").write_text("older song")
+    plan = engine.build_plan(str(downloads))
+    assert [(Path(i['destination']).name, i['conflict']) for i in plan['items']] == [
+        ('01 Title_1.mp3', 'destination_exists')]
+
+
+def test_files_that_vanish_while_planning_are_left_out(planner):
+    """A file deleted between the scan and its stat is skipped instead of failing the plan."""
+    engine, downloads, tmp_path = planner
+    classify = engine.classifier.classify.side_effect
+
+    def classify_and_delete(path, deep_analysis=False):
+        if path.endswith('.mp3'):
+            Path(path).unlink()
+        return classify(path, deep_analysis)
+    engine.classifier.classify.side_effect = classify_and_delete
+
+    plan = engine.build_plan(str(downloads))
+    assert [Path(i['file']).name for i in plan['items']] == ['report.pdf']
+
+
+def test_organization_plan_rows_and_selective_apply(planner):
+    """plan_organization() gives one typed row per move; applying only approved ids moves only those."""
+    engine, downloads, tmp_path = planner
+    (tmp_path / "organized" / "Music").mkdir(parents=True)
+    (tmp_path / "organized" / "Music" / "song.mp3").write_text("older song")
+
+    organized = engine.plan_organization({'folder': str(downloads), 'recursive': False})
+    rows = {Path(move.source).name: move for move in organized.moves}
+    assert set(rows) == {'report.pdf', 'song.mp3'} and organized.unchanged == 1
+    assert (rows['report.pdf'].category, rows['report.pdf'].confidence, rows['report.pdf'].conflict) == \
+        ('Known', 'high', False)
+    assert rows['song.mp3'].conflict and rows['song.mp3'].conflict_reason == 'destination_exists'
+    data = organized.to_dict()
+    assert data['conflicts'] == 1 and data['total_size'] == len("report") + len("song")
+    assert json.loads(json.dumps(data))['moves'][0]['id'] == 0
+
+    with pytest.raises(ValueError):
+        PlanOptions.from_dict({'folder': str(downloads), 'preview': True})
+    with pytest.raises(ValueError):
+        engine.apply_plan(organized.plan, approved=[7])
+    result = engine.apply_plan(organized.plan, approved=[rows['report.pdf'].id])
+    assert result['success'] and [Path(i['file']).name for i in result['applied']] == ['report.pdf']
+    assert (downloads / "song.mp3").exists() and not (downloads / "report.pdf").exists()
+
+
+def test_apply_plan_items_logs_the_rejected_moves(planner):
+    """A stored plan is applied by ID once; moves left unticked show up in the history as skipped."""
+    engine, downloads, tmp_path = planner
+    organized = engine.plan_organization({'folder': str(downloads)})
+    assert organized.plan_id and organized.to_dict()['plan_id'] == organized.plan_id
+    song = next(move.id for move in organized.moves if move.source.endswith("song.mp3"))
+
+    result = engine.apply_plan_items(organized.plan_id, [song])
+    assert result['plan_id'] == organized.plan_id and len(result['applied']) == 1
+    assert [Path(i['file']).name for i in result['rejected']] == ['report.pdf']
+    assert (downloads / "report.pdf").exists() and not (downloads / "song.mp3").exists()
+    logged = {entry['filename']: entry['operation'] for entry in engine.action_manager.db_manager.get_recent_logs()}
+    assert logged['report.pdf'] == 'skipped' and logged['song.mp3'] != 'skipped'
+
+    with pytest.raises(ValueError, match="already applied"):
+        engine.apply_plan_items(organized.plan_id, [song])
+    with pytest.raises(ValueError, match="No such plan"):
+        engine.apply_plan_items(9999, [])
+
+
+def test_ignored_files_and_folders_are_not_planned(planner):
+    """node_modules, temp files and .organizerignore rules keep files out of the plan."""
+    engine, downloads, tmp_path = planner
+    (downloads / "node_modules").mkdir()
+    (downloads / "node_modules" / "readme.pdf").write_text("pkg")
+    (downloads / "draft.pdf.tmp").write_text("tmp")
+    (downloads / ".organizerignore").write_text("song.*\n")
+
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path / "config")}):
+        plan = engine.build_plan(str(downloads))
+    assert [Path(i['file']).name for i in plan['items']] == ['report.pdf']
+    assert plan['left_alone'] == [{'path': str(downloads / "node_modules"),
+                                  'reason': "Ignored (matches 'node_modules/')"}]
+
+
+def test_run_timings_are_stored_with_the_operation(planner):
+    """Planning and applying time per stage ends up in the history API."""
+    engine, downloads, tmp_path = planner
+    rules = engine.classifier.classify.side_effect
+
+    def classify(path, deep_analysis=False):
+        with timed('classify'):
+            with timed('ai'):
+                time.sleep(0.02)
+            return rules(path, deep_analysis)
+
+    engine.classifier.classify.side_effect = classify
+    plan = engine.build_plan(str(downloads))
+    result = engine.apply_plan(plan)
+
+    stages = engine.action_manager.journal.get_operation(result['operation_id'])['timings']['stages']
+    assert {'scan', 'classify', 'ai', 'move'} <= set(stages)
+    assert stages['classify']['count'] == 3 and stages['move']['count'] == 2
+    assert stages['ai']['seconds'] >= 0.06 > stages['classify']['seconds']  # Nested time counts once
+
+    report = engine.action_manager.journal.timing_report()
+    assert [run['id'] for run in report['runs']] == [result['operation_id']]
+    assert next(iter(report['stages'])) == 'ai'
+
+
+def test_cleanup_plan_trashes_junk_in_one_undoable_operation(planner):
+    """Junk is listed by reason, keepers are left, and one undo restores everything."""
+    engine, downloads, tmp_path = planner
+    engine.junk._installed = {'firefox'}
+    old = time.time() - 30 * 86400
+    junk = {
+        'Firefox Setup 121.0.exe': 'installer', 'video.mp4.crdownload': 'partial_download',
+        '~$report.docx': 'office_temp', 'empty.txt': 'zero_byte'
+    }
+    for name in junk:
+        (downloads / name).write_bytes(b'' if name == 'empty.txt' else b'data')
+        os.utime(downloads / name, (old, old))
+    (downloads / 'Gimp-2.10.exe').write_bytes(b'data')
+    (downloads / 'fresh.crdownload').write_bytes(b'data')
+    (downloads / 'sub' / '.gitkeep').parent.mkdir()
+    (downloads / 'sub' / '.gitkeep').write_bytes(b'')
+    (downloads / 'old' / 'nested').mkdir(parents=True)
+    (downloads / 'old' / '.DS_Store').write_bytes(b'x')
+
+    plan = engine.build_cleanup_plan(str(downloads))
+    found = {Path(item['file']).name: item['reason'] for item in plan['items']}
+    assert found == {**junk, 'old': 'empty_folder'} and plan['kind'] == 'cleanup'
+    saved = engine.save_plan(plan, str(tmp_path / 'cleanup.json'))
+    assert engine.load_plan(saved)['items'] == plan['items']
+
+    result = engine.apply_plan(plan)
+    assert result['success'] and len(result['applied']) == 5
+    assert not (downloads / 'old').exists() and (downloads / 'Gimp-2.10.exe').exists()
+    assert engine.action_manager.journal.undo(result['operation_id'])['success']
+    assert (downloads / 'old' / 'nested').is_dir() and (downloads / '~$report.docx').exists()
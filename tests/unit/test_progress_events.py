@@ -0,0 +1,78 @@
+"""
+Unit tests for streaming organize progress events.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import MagicMock
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.cli.organizer import Organizer
+from src.core.progress_events import (
+    stream_organize, event_line, parse_event_line, EVENT_PROGRESS, EVENT_FILE_DONE, EVENT_ERROR
+)
+
+
+def _child(*lines, stderr='', code=0):
+    script = ''.join(f"print({line!r}, flush=True)\n" for line in lines)
+    script += f"import sys\nsys.stderr.write({stderr!r})\nsys.exit({code})\n"
+    return [sys.executable, '-c', script]
+
+
+def test_stream_passes_events_on_as_they_arrive():
+    """Event lines reach the callback in order; other output and broken listeners are ignored."""
+    summary = {'folder': '/in', 'preview': False, 'organized': 1, 'errors': 1}
+    command = _child(
+        'Scanning for files...',
+        event_line(EVENT_PROGRESS, {'phase': 'organizing', 'done': 0, 'total': 2, 'file': '/in/a.pdf'}),
+        event_line(EVENT_FILE_DONE, {'file': '/in/a.pdf', 'status': 'organized', 'destination': '/out/a.pdf'}),
+        '{"event": "something-else"}',
+        event_line(EVENT_ERROR, {'file': '/in/b.pdf', 'message': 'Permission denied'}),
+        event_line(EVENT_PROGRESS, {'phase': 'done', 'done': 2, 'total': 2, 'summary': summary}),
+        stderr='progress bar\n' * 5000, code=3
+    )
+    seen = []
+
+    def listener(event, payload):
+        seen.append(event)
+        if event == EVENT_ERROR:
+            raise RuntimeError("listener bug")
+
+    result = stream_organize('/in', listener, command=command)
+    assert seen == [EVENT_PROGRESS, EVENT_FILE_DONE, EVENT_ERROR, EVENT_PROGRESS]
+    assert result['summary'] == summary and result['events'] == 4
+    assert result['exit_code'] == 3 and not result['success']
+    assert parse_event_line('not json') is None and parse_event_line('[1]') is None
+
+    crashed = stream_organize('/in', listener, command=_child(stderr='Traceback\nKeyError: boom\n', code=1))
+    assert crashed['summary'] is None and crashed['message'].endswith('KeyError: boom')
+
+
+def test_organize_folder_reports_each_phase_and_file(tmp_path):
+    """The run reports scanning, per-file classifying and organizing, a result per file and a summary."""
+    (tmp_path / "a.pdf").write_text("a")
+    (tmp_path / "b.tmp").write_text("b")
+    (tmp_path / "c.jpg").write_text("c")
+    org = Organizer.__new__(Organizer)
+    org.config, org.ollama, org.snapshots = MagicMock(), None, MagicMock()
+    org.snapshots.maybe_snapshot.return_value = None
+    org.planner = MagicMock()
+    org.planner.classify.side_effect = lambda path, *args: {
+        'category': 'Misc', 'suggested_path': None if path.endswith('.tmp') else 'Misc/'}
+    org.action_manager = MagicMock()
+    org.action_manager.get_stats.return_value = {}
+    org.action_manager.execute.side_effect = lambda file_path, **kwargs: (
+        {'success': False, 'message': 'Disk full'} if file_path.endswith('.jpg')
+        else {'success': True, 'new_path': '/out/a.pdf', 'message': 'Moved'})
+    events = []
+
+    summary = org.organize_folder(str(tmp_path), auto=True, on_event=lambda e, p: events.append((e, p)))
+
+    phases = [p['phase'] for e, p in events if e == EVENT_PROGRESS]
+    assert phases[0] == 'scanning' and phases.count('classifying') == 3 and phases.count('organizing') == 3
+    assert phases[-1] == 'done' and events[-1][1]['summary'] == summary
+    done = {Path(p['file']).name: p['status'] for e, p in events if e == EVENT_FILE_DONE}
+    assert done == {'a.pdf': 'organized', 'b.tmp': 'skipped', 'c.jpg': 'failed'}
+    assert [p['message'] for e, p in events if e == EVENT_ERROR] == ['Disk full']
+    assert summary['organized'] == 1 and summary['skipped'] == 1 and summary['errors'] == 1
@@ -0,0 +1,74 @@
+"""
+Unit tests for the AI providers.
+"""
+
+import json
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.ai import providers
+from src.ai.providers import OllamaProvider, OpenAICompatibleProvider, create_provider
+from src.utils.error_handler import ConfigurationError
+
+
+def _response(status, body):
+    response = Mock(status_code=status)
+    response.json.return_value = body
+    response.text = json.dumps(body)
+    return response
+
+
+def test_openai_compatible_provider_classifies_and_reports_tokens():
+    """The chat completions reply is parsed like Ollama's; failures come back as results, not exceptions."""
+    provider = OpenAICompatibleProvider(model='gpt-4o-mini', api_key='sk-test')
+    reply = {'model': 'gpt-4o-mini-2024-07-18', 'usage': {'total_tokens': 321},
+             'choices': [{'message': {'content': '```json\n{"category": "Finance", '
+                                                 '"suggested_path": "Finance/Invoices/", "rename": null, '
+                                                 '"reason": "An invoice"}\n```'}}]}
+    with patch.object(providers.requests, 'post', return_value=_response(200, reply)) as post:
+        result = provider.classify_file('invoice.pdf', 'pdf', 'Invoice #12', 2048)
+    assert result['success'] and result['suggested_path'] == 'Finance/Invoices/'
+    assert (result['provider'], result['model'], result['tokens_used']) == ('openai', 'gpt-4o-mini-2024-07-18', 321)
+    request = post.call_args
+    assert request.args[0] == 'https://api.openai.com/v1/chat/completions'
+    assert request.kwargs['headers']['Authorization'] == 'Bearer sk-test'
+    assert 'invoice.pdf' in request.kwargs['json']['messages'][1]['content']
+
+    with patch.object(providers.requests, 'post',
+                      return_value=_response(401, {'error': {'message': 'Incorrect API key'}})):
+        failed = provider.classify_file('invoice.pdf', 'pdf')
+    assert not failed['success'] and 'Incorrect API key' in failed['error']
+    with patch.object(providers.requests, 'post', side_effect=providers.Timeout()):
+        assert provider.classify_file('invoice.pdf', 'pdf')['error'] == 'Request timed out'
+    assert not OpenAICompatibleProvider().is_available()      # The hosted API needs a key
+
+
+def test_settings_pick_the_provider(tmp_path):
+    """config.json's ai_provider wins; otherwise the user's default tier decides."""
+    config = Mock()
+    config.ollama_base_url, config.ollama_model = 'http://nas:11434', 'llama3'
+    config.get.return_value = 30
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path), 'OPENAI_API_KEY': 'sk-env'}), \
+            patch.object(providers, 'get_secret', return_value=None):
+        config.ai_provider_settings = {}
+        local = create_provider(config)
+        assert isinstance(local, OllamaProvider) and local.base_url == 'http://nas:11434'
+
+        (tmp_path / "settings.json").write_text(json.dumps({'version': 1, 'default_tier': 'cloud'}))
+        cloud = create_provider(config)
+        assert isinstance(cloud, OpenAICompatibleProvider) and cloud.api_key == 'sk-env'
+
+        config.ai_provider_settings = {'provider': 'openai', 'base_url': 'http://localhost:1234/v1/',
+                                       'model': 'qwen2.5-7b'}
+        studio = create_provider(config)
+        assert (studio.base_url, studio.model) == ('http://localhost:1234/v1', 'qwen2.5-7b')
+
+        config.ai_provider_settings = {'provider': 'gemini'}
+        with pytest.raises(ConfigurationError):
+            create_provider(config)
@@ -0,0 +1,97 @@
+"""
+Unit tests for RAW+JPEG pair handling.
+"""
+
+import os
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.raw_pairs import group_raw_pairs, raw_partner, is_raw_sidecar
+from src.core.duplicates import DuplicateFinder
+
+
+def _shot(directory: Path, name: str, content: bytes, mtime: float = 1_700_000_000):
+    path = directory / name
+    path.write_bytes(content)
+    os.utime(path, (mtime, mtime))
+    return path
+
+
+def test_pairs_need_matching_names_and_close_times(tmp_path):
+    """Same base name and shot together pairs; a later re-shoot with the same name does not."""
+    jpeg = _shot(tmp_path, 'IMG_0042.JPG', b'\xff\xd8jpeg')
+    raw = _shot(tmp_path, 'IMG_0042.cr2', b'II*\x00raw')
+    _shot(tmp_path, 'IMG_0043.jpg', b'\xff\xd8other')
+    _shot(tmp_path, 'IMG_0043.nef', b'MM\x00*raw', mtime=1_700_003_600)
+
+    units = group_raw_pairs(sorted(tmp_path.iterdir()))
+
+    assert [[p.name for p in unit] for unit in units] == [
+        ['IMG_0042.JPG', 'IMG_0042.cr2'], ['IMG_0043.jpg'], ['IMG_0043.nef']
+    ]
+    assert raw_partner(jpeg) == raw and raw_partner(raw) == jpeg
+    assert is_raw_sidecar(raw) and not is_raw_sidecar(jpeg)
+    assert not is_raw_sidecar(tmp_path / 'IMG_0043.nef')
+
+
+@pytest.fixture
+def config(config):
+    """Keeps RAW files in their own tree."""
+    config.raw_pair_settings = {'separate_raw': True, 'raw_tree': 'RAW'}
+    return config
+
+
+def test_pair_moves_together_and_counts_once(action_manager, db, tmp_path):
+    """Organizing the JPEG takes its RAW along, renamed alike, into the RAW tree."""
+    card = tmp_path / "card"
+    card.mkdir()
+    _shot(card, 'IMG_0042.jpg', b'\xff\xd8jpeg')
+    _shot(card, 'IMG_0042.CR2', b'II*\x00raw')
+
+    result = action_manager.execute(str(card / 'IMG_0042.jpg'), {
+        'suggested_path': 'Pictures/2024/05/',
+        'rename': 'Lisbon.jpg',
+        'category': 'Pictures',
+        'confidence': 'high',
+        'method': 'rule-based'
+    }, user_approved=True)
+
+    assert result['success'], result['message']
+    assert len(result['pair']) == 2 and list(card.iterdir()) == []
+    organized = tmp_path / "organized"
+    assert (organized / "Pictures" / "2024" / "05" / "Lisbon.jpg").exists()
+    assert (organized / "RAW" / "Pictures" / "2024" / "05" / "Lisbon.CR2").exists()
+    assert db.get_stats()['files_organised'] == 1
+
+
+def test_duplicated_pair_is_reported_once(tmp_path):
+    """A pair copied to a second folder is one duplicate group whose cleanup removes both files."""
+    for folder in ('a', 'b'):
+        directory = tmp_path / folder
+        directory.mkdir()
+        _shot(directory, 'IMG_1.jpg', b'J' * 2048)
+        _shot(directory, 'IMG_1.dng', b'R' * 4096)
+
+    config = Mock()
+    config.hash_algorithm = 'sha1'
+    config.trash_settings = {'backend': 'app'}
+    config.trash_dir = str(tmp_path / 'trash')
+    with patch('src.core.duplicates.SafetyGuardian') as guardian_cls:
+        guardian_cls.return_value.is_file_safe_to_modify.return_value = (True, 'Safe to modify')
+        finder = DuplicateFinder(config, MagicMock())
+
+    groups = finder.find_duplicates_in_directory(str(tmp_path))
+
+    assert len(groups) == 1 and groups[0]['raw_pair']
+    assert all(p.endswith('IMG_1.jpg') for p in groups[0]['paths'])
+    assert groups[0]['size'] == 6144
+
+    finder.cleanup_duplicates(groups[0], dry_run=False)
+    assert sorted(p.name for p in (tmp_path / 'trash').rglob('*')) == ['IMG_1.dng', 'IMG_1.jpg', 'removed']
+    remaining = sorted(p.relative_to(tmp_path).as_posix() for p in tmp_path.rglob('*')
+                       if p.is_file() and not p.is_relative_to(tmp_path / 'trash'))
+    assert len(remaining) == 2 and remaining[0].split('/')[0] == remaining[1].split('/')[0]
@@ -0,0 +1,71 @@
+"""
+Unit tests for template renames.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.db_manager import DatabaseManager
+from src.core.journal import OperationJournal
+from src.core.renamer import FileRenamer, render_name, template_fields
+
+
+@pytest.fixture
+def setup(tmp_path):
+    config = Mock(rename_settings={}, trash_dir=str(tmp_path / "trash"), path_blacklist=[])
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    journal = OperationJournal(db)
+    inbox = tmp_path / "inbox"
+    inbox.mkdir()
+    yield FileRenamer(config, db, journal), journal, db, inbox
+    db.cleanup()
+
+
+def test_templates_fill_fields_and_never_double_separators():
+    """Empty fields leave no stray dashes; the extension is kept; unknown fields are refused."""
+    fields = {'date': '2024-03-01', 'category': 'Finance', 'original': 'scan001', 'n': 7}
+    assert render_name('{date}-{category}-{original}', fields, '.pdf') == '2024-03-01-Finance-scan001.pdf'
+    assert render_name('{date}-{category}-{original}', {**fields, 'category': None}, '.pdf') == \
+        '2024-03-01-scan001.pdf'
+    assert render_name('photo_{n:03}', fields, '.JPG') == 'photo_007.JPG'
+    assert render_name('{category}: {original}?', fields, '') == 'Finance_ scan001_'
+    assert render_name('{category}', {}, '.txt') == ''
+    assert template_fields('{year}/{month}') == ['year', 'month']
+    with pytest.raises(ValueError, match='Unknown field {size}'):
+        template_fields('{size}-{name}')
+
+
+def test_renames_number_collisions_dry_run_and_undo(setup):
+    """Names taken on disk or earlier in the batch get " (2)"; a dry run touches nothing; undo restores all."""
+    renamer, journal, db, inbox = setup
+    for name in ('a.pdf', 'b.pdf', 'c.pdf', 'taken.pdf'):
+        (inbox / name).write_text(name)
+    specs = [
+        {'path': str(inbox / 'a.pdf'), 'date': '2024-01-05', 'classification': {'rename': 'invoice.pdf',
+                                                                                 'category': 'Finance'}},
+        {'path': str(inbox / 'b.pdf'), 'date': '2024-01-05', 'name': 'invoice', 'category': 'Finance'},
+        {'path': str(inbox / 'c.pdf'), 'name': 'taken'},
+        {'path': str(inbox / 'missing.pdf')},
+    ]
+
+    preview = renamer.rename_files(specs, '{date}-{category}-{name}', dry_run=True)
+    assert [Path(r['destination']).name for r in preview['results'][:2]] == [
+        '2024-01-05-Finance-invoice.pdf', '2024-01-05-Finance-invoice (2).pdf']
+    assert (preview['renamed'], preview['failed'], preview['operation_id']) == (3, 1, None)
+    assert (inbox / 'a.pdf').exists()
+
+    result = renamer.rename_files(specs[:3], '{name}')
+    assert [Path(r['destination']).name for r in result['results']] == ['invoice.pdf', 'invoice (2).pdf',
+                                                                        'taken (2).pdf']
+    assert (inbox / 'invoice (2).pdf').read_text() == 'b.pdf'
+    assert (inbox / 'taken.pdf').read_text() == 'taken.pdf'
+    assert [log['operation'] for log in db.get_recent_logs(3)] == ['rename'] * 3
+    assert renamer.rename_files([{'path': str(inbox / 'invoice.pdf')}])['results'][0]['status'] == 'skipped'
+
+    assert journal.undo(result['operation_id'])['success']
+    assert sorted(p.name for p in inbox.iterdir()) == ['a.pdf', 'b.pdf', 'c.pdf', 'taken.pdf']
@@ -0,0 +1,164 @@
+"""
+Unit tests for the engine's REST and WebSocket API.
+"""
+
+import base64
+import json
+import os
+import socket
+import struct
+import sys
+import time
+import urllib.error
+import urllib.request
+from pathlib import Path
+
+import pytest
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.rest_api import RestApiServer, api_token, rotate_api_token
+
+
+def _methods(calls):
+    def plan(folder, recursive=True, deep=False, strategy=None):
+        calls.append(('plan', folder, strategy))
+        if folder == 'missing':
+            raise FileNotFoundError(folder)
+        return {'folder': folder, 'items': [{'source': 'a.pdf'}, {'source': 'b.jpg'}], 'conflicts': 0}
+
+    def apply(plan=None, plan_file=None):
+        calls.append(('apply', len(plan['items'])))
+        return {'success': True, 'message': 'Moved 2 file(s)', 'operation_id': 7,
+                'applied': plan['items'], 'failed': []}
+
+    return {'status': lambda: {'mode': 'daemon', 'watching': False}, 'plan': plan, 'apply': apply,
+            'scan': lambda folder, recursive=True: {'files': 2}, 'undo': lambda operation_id=None: None}
+
+
+def _request(api, method, path, body=None, token='secret'):
+    host, port = api.address
+    request = urllib.request.Request(f"http://{host}:{port}/api/v1{path}", method=method,
+                                     data=json.dumps(body).encode() if body is not None else None)
+    if token:
+        request.add_header('Authorization', f"Bearer {token}")
+    try:
+        with urllib.request.urlopen(request, timeout=10) as response:
+            return response.status, json.loads(response.read())
+    except urllib.error.HTTPError as e:
+        return e.code, json.loads(e.read())
+
+
+@pytest.fixture
+def api():
+    calls = []
+    server = RestApiServer(_methods(calls), port=0, token='secret')
+    server.calls = calls
+    server.start()
+    yield server
+    server.stop()
+
+
+def test_endpoints_need_the_token_and_validate_parameters(api, tmp_path):
+    """Requests without the right token are refused; bodies are checked against the method."""
+    assert _request(api, 'GET', '/status', token=None)[0] == 401
+    assert _request(api, 'GET', '/status', token='wrong')[0] == 401
+    assert _request(api, 'GET', '/status') == (200, {'mode': 'daemon', 'watching': False})
+
+    status, plan = _request(api, 'POST', '/plan', {'folder': '/in', 'strategy': 'photos'})
+    assert status == 200 and len(plan['items']) == 2 and api.calls == [('plan', '/in', 'photos')]
+    assert _request(api, 'POST', '/plan', {'fodler': '/in'})[1]['error']['code'] == -32602
+    status, failed = _request(api, 'POST', '/plan', {'folder': 'missing'})
+    assert status == 500 and failed['error']['data']['code'] == 'not_found'
+    assert _request(api, 'GET', '/undo')[0] == 404
+    assert _request(api, 'GET', '/plan')[0] == 405
+    raw = socket.create_connection(api.address, timeout=10)
+    raw.sendall(b"POST /api/v1/plan HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: -5\r\n\r\n")
+    assert raw.recv(64).startswith(b"HTTP/1.1 400")
+    raw.close()
+
+    with pytest.raises(ValueError):
+        RestApiServer({}, host='0.0.0.0', token='secret')
+
+    settings = {'token_file': str(tmp_path / 'token')}
+    token = api_token(settings)
+    assert api_token(settings) == token and oct(os.stat(tmp_path / 'token').st_mode & 0o777) == '0o600'
+    assert rotate_api_token(settings) != token and api_token({'token': 'fixed'}) == 'fixed'
+
+
+def test_runs_plan_and_apply_in_the_background_and_stream_progress(api):
+    """A run is accepted at once, tracked by id and its updates pushed over the WebSocket."""
+    host, port = api.address
+    ws = socket.create_connection((host, port), timeout=10)
+    ws.sendall((f"GET /api/v1/events HTTP/1.1\r\nHost: {host}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n"
+                f"Sec-WebSocket-Key: {base64.b64encode(b'0123456789abcdef').decode()}\r\n"
+                "Sec-WebSocket-Version: 13\r\nAuthorization: Bearer secret\r\n\r\n").encode())
+    reader = ws.makefile('rb')
+    assert b'101' in reader.readline()
+    while reader.readline() not in (b'\r\n', b''):
+        pass
+
+    status, run = _request(api, 'POST', '/runs', {'folder': '/in', 'apply': True})
+    assert status == 202 and run['state'] == 'queued'
+    for _ in range(100):
+        finished = _request(api, 'GET', f"/runs/{run['id']}")[1]
+        if finished['state'] in ('done', 'failed'):
+            break
+        time.sleep(0.05)
+    assert finished['state'] == 'done' and finished['items'] == 2 and finished['operation_id'] == 7
+    assert api.calls == [('plan', '/in', None), ('apply', 2)]
+    assert _request(api, 'GET', '/runs')[1]['runs'][0]['id'] == run['id']
+    assert _request(api, 'GET', '/runs/99')[0] == 404
+    assert _request(api, 'POST', '/runs', {'fodler': '/in'})[1]['error']['code'] == -32602
+
+    states = []
+    while 'done' not in states:
+        head = reader.read(2)
+        length = head[1] & 0x7F
+        if length == 126:
+            length = struct.unpack('>H', reader.read(2))[0]
+        message = json.loads(reader.read(length))
+        if message['type'] == 'run':
+            states.append(message['run']['state'])
+    assert states[-1] == 'done'
+    ws.sendall(bytes([0x88, 0x80]) + b'\x00' * 4)
+    ws.close()
+
+
+def test_history_lists_operations_and_undo_moves_files_back(tmp_path):
+    """A journaled move shows up in /history and undoing it over the API puts the file back."""
+    from src.core.db_manager import DatabaseManager
+    from src.core.journal import OperationJournal
+
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    journal = OperationJournal(db)
+    source, dest = tmp_path / "Downloads" / "report.pdf", tmp_path / "Documents" / "report.pdf"
+    source.parent.mkdir()
+    dest.parent.mkdir()
+    dest.write_text("report")
+    operation_id = journal.begin('apply', 'Organize Downloads')
+    journal.record_step(operation_id, 'move', str(source), str(dest))
+    journal.complete(operation_id)
+
+    server = RestApiServer({
+        'get_history': lambda limit=50: journal.list_operations(int(limit)),
+        'get_operation': lambda operation_id: journal.get_operation(int(operation_id)),
+        'undo_operation': lambda operation_id, dry_run=False: journal.undo(int(operation_id), bool(dry_run)),
+    }, port=0, token='secret')
+    server.start()
+    try:
+        status, history = _request(server, 'GET', '/history?limit=5')
+        assert status == 200 and [op['id'] for op in history] == [operation_id]
+        status, operation = _request(server, 'GET', f"/history/{operation_id}")
+        assert status == 200 and operation['steps'][0]['dest_path'] == str(dest)
+        assert _request(server, 'GET', '/history/999')[0] == 404
+        assert _request(server, 'GET', f"/history/{operation_id}/undo")[0] == 405
+
+        status, result = _request(server, 'POST', f"/history/{operation_id}/undo", {'dry_run': True})
+        assert status == 200 and result['success'] and dest.exists()
+        status, result = _request(server, 'POST', f"/history/{operation_id}/undo")
+        assert status == 200 and result['success']
+        assert source.read_text() == "report" and not dest.exists()
+    finally:
+        server.stop()
+        db.cleanup()
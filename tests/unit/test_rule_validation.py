@@ -0,0 +1,77 @@
+"""
+Unit tests for rules file validation.
+"""
+
+import sys
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.rule_validation import validate_rules
+
+
+def write_rules(tmp_path, text):
+    path = tmp_path / "rules.yaml"
+    path.write_text(text)
+    return str(path)
+
+
+def codes(report):
+    return {(d['code'], d['location']) for d in report['diagnostics']}
+
+
+def test_reports_syntax_regex_template_and_reference_errors(tmp_path):
+    """Each problem comes back as a diagnostic pointing at where it is."""
+    broken = validate_rules(write_rules(tmp_path, "rules: [\n  - name: x\n"))
+    assert not broken['valid'] and broken['diagnostics'][0]['code'] == 'yaml_syntax'
+
+    report = validate_rules(write_rules(tmp_path, """
+filters:
+  logs:
+    regex_patterns: ['(unclosed']
+    date_modified_after: 2024-01-01
+actions:
+  file_logs:
+    action: move
+    destination: '{base_destination}/Logs/{category}/{file_name}'
+rules:
+  - name: Logs
+    filters: [logs, missing]
+    actions: [file_logs, nope]
+"""))
+
+    assert not report['valid'] and report['rules'] == 1
+    assert {
+        ('invalid_regex', 'filters.logs.regex_patterns[0]'),
+        ('unquoted_date', 'filters.logs.date_modified_after'),
+        ('unknown_placeholder', 'actions.file_logs.destination'),
+        ('undefined_filter', 'rules[0].filters'),
+        ('undefined_action', 'rules[0].actions'),
+    } <= codes(report)
+
+
+def test_finds_unreachable_and_conflicting_rules(tmp_path):
+    """A rule whose files an earlier rule already moved away can never act."""
+    report = validate_rules(write_rules(tmp_path, """
+filters:
+  pdfs: {extensions: ['.pdf']}
+  big: {size_min: 1000}
+  impossible: {size_min: 10, size_max: 5}
+actions:
+  to_docs: {action: move, destination: '{base_destination}/Docs/{file_name}'}
+  to_archive: {action: move, destination: '{base_destination}/Archive/{file_name}'}
+  backup: {action: copy, destination: '/backup/{file_name}'}
+rules:
+  - {name: Docs, filters: [pdfs], actions: [to_docs]}
+  - {name: Big PDFs, filters: [pdfs, big], actions: [to_archive]}
+  - {name: Big files, filters: [big], actions: [to_archive]}
+  - {name: Never, filters: [impossible], actions: [backup]}
+"""))
+
+    assert report['valid'] and report['warnings'] == 4
+    assert {
+        ('never_matches', 'filters.impossible'),
+        ('unreachable', 'rules[1]'),
+        ('conflicting', 'rules[2]'),
+        ('unreachable', 'rules[3]'),
+    } <= codes(report)
@@ -0,0 +1,76 @@
+"""
+Unit tests for the user's organize rules.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.classifier import FileClassifier
+from src.core.rules import UserRules, validate_rule
+from src.core.settings import SettingsStore
+
+
+@pytest.fixture
+def rules(tmp_path):
+    config = Mock()
+    config.base_destination = str(tmp_path / "organized")
+    config.text_extract_limit = 500
+    return UserRules(config, SettingsStore(str(tmp_path / "settings.json"), use_keychain=False))
+
+
+def test_rules_match_and_render_destination_templates(rules, tmp_path):
+    """The first matching rule wins; absolute destinations are used as is, relative ones go under base_destination."""
+    downloads = tmp_path / "Downloads"
+    downloads.mkdir()
+    invoice = downloads / "march.txt"
+    invoice.write_text("INVOICE #42, total due")
+    os.utime(invoice, (1710000000, 1710000000))  # March 2024
+    (downloads / "notes.txt").write_text("shopping list")
+
+    rules.add_rule({'name': 'Invoices', 'folder': str(downloads), 'extensions': ['.TXT'],
+                    'content_contains': 'invoice', 'destination': str(tmp_path / "Finance" / "{year}")})
+    rules.add_rule({'name': 'Text', 'extensions': ['txt'], 'destination': 'Text/{parent}'})
+    assert [rule['name'] for rule in rules.list_rules()] == ['Invoices', 'Text']
+
+    tested = rules.test_rule(str(invoice))
+    assert tested['matched'] == 'Invoices' and tested['destination'] == str(tmp_path / "Finance" / "2024")
+    assert tested['classification']['destination_root'] == str(tmp_path / "Finance")
+    other = rules.test_rule(str(downloads / "notes.txt"))
+    assert other['matched'] == 'Text' and other['destination'] == str(tmp_path / "organized" / "Text" / "Downloads")
+    assert other['checked'][0] == {'name': 'Invoices', 'matched': False, 'reason': "text does not contain 'invoice'"}
+
+    assert rules.test_rule(str(invoice), {'name': 'Big', 'min_size': 10 ** 6, 'destination': 'Big'})['matched'] is None
+    assert rules.add_rule({'name': 'text', 'extensions': ['md'], 'destination': 'Notes'})['message'].startswith("Replaced")
+    assert rules.remove_rule('Invoices')['success'] and not rules.remove_rule('Invoices')['success']
+    assert rules.classify(str(invoice)) is None
+
+
+def test_invalid_rules_are_refused_and_the_classifier_tries_rules_first(rules, tmp_path):
+    """Bad rules never reach the settings; a matching rule decides before the built-in rules."""
+    for bad in ({'extensions': ['pdf'], 'destination': 'Docs'},
+                {'name': 'x', 'destination': 'Docs/{colour}'},
+                {'name': 'x', 'extensions': ['pdf']},
+                {'name': 'x', 'destination': '../outside'},
+                {'name': 'x', 'min_size': 10, 'max_size': 5, 'destination': 'Docs'},
+                {'name': 'x', 'destination': 'Docs', 'when': 'always'}):
+        with pytest.raises(ValueError):
+            validate_rule(bad)
+    assert rules.list_rules() == []
+
+    rules.store.update_settings({'folder_templates': {'Receipts': 'Finance/Receipts/{month}'}})
+    rules.add_rule({'name': 'Receipts', 'name_pattern': 'receipt*', 'category': 'Receipts'})
+    receipt = tmp_path / "Receipt-0012.pdf"
+    receipt.write_bytes(b"%PDF-1.4")
+    os.utime(receipt, (1718400000, 1718400000))  # June 2024
+
+    classifier = FileClassifier.__new__(FileClassifier)
+    classifier.user_rules, classifier.corrections, classifier.cache = rules, None, None
+    result = classifier._classify(str(receipt))
+    assert (result['method'], result['rule'], result['category']) == ('user-rules', 'Receipts', 'Receipts')
+    assert result['suggested_path'] == 'Finance/Receipts/06/'
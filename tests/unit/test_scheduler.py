@@ -0,0 +1,74 @@
+"""
+Unit tests for scheduled organize runs.
+"""
+
+import sys
+import time
+from datetime import datetime
+from pathlib import Path
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.jobs import JobManager
+from src.core.scheduler import CronExpression, Scheduler, EVENT_STARTED, EVENT_FINISHED
+
+
+def test_cron_expressions_find_the_next_run():
+    """Fields, ranges, steps, names and aliases; day of month and day of week are either/or."""
+    wednesday = datetime(2025, 1, 15, 10, 7, 30)
+    assert CronExpression("30 2 * * *").next_after(wednesday) == datetime(2025, 1, 16, 2, 30)
+    assert CronExpression("*/15 9-17 * * mon-fri").next_after(wednesday) == datetime(2025, 1, 15, 10, 15)
+    assert CronExpression("0 9 * * 6,7").next_after(wednesday) == datetime(2025, 1, 18, 9, 0)
+    assert CronExpression("0 0 1 jan-mar 5").next_after(wednesday) == datetime(2025, 1, 17, 0, 0)
+    assert CronExpression("@monthly").next_after(wednesday) == datetime(2025, 2, 1, 0, 0)
+    assert CronExpression("7 10 * * *").next_after(wednesday) == datetime(2025, 1, 16, 10, 7)
+
+    for bad in ("", "* * *", "60 * * * *", "* * * * funday", "5-1 * * * *", "*/0 * * * *"):
+        with pytest.raises(ValueError):
+            CronExpression(bad)
+    with pytest.raises(ValueError, match="never runs"):
+        CronExpression("0 0 30 2 *").next_after(wednesday)
+
+
+def test_schedules_run_when_due_report_back_and_survive_restarts(tmp_path):
+    """A due schedule starts a job and records its outcome; a reloaded scheduler keeps its schedules."""
+    folder = tmp_path / "Downloads"
+    folder.mkdir()
+    ran = []
+
+    def runner(path, on_event, preview, deep, strategy, cancel):
+        ran.append((path, preview))
+        return {'success': True, 'message': 'Organized 2 file(s)', 'summary': {'organized': 2}}
+
+    now = [datetime(2025, 1, 15, 10, 7)]
+    events = []
+    scheduler = Scheduler(JobManager(runner), str(tmp_path / "schedules.json"),
+                          listener=lambda event, payload: events.append(event), clock=lambda: now[0])
+    schedule = scheduler.schedule_organize(str(folder), "0 * * * *", {'preview': True})
+    assert schedule['next_run'] == '2025-01-15T11:00' and schedule['options']['preview']
+    with pytest.raises(ValueError):
+        scheduler.schedule_organize(str(folder), "0 * * * *", {'recursive': True})
+    with pytest.raises(NotADirectoryError):
+        scheduler.schedule_organize(str(tmp_path / "missing"), "@daily")
+
+    assert scheduler.run_due() == []
+    now[0] = datetime(2025, 1, 15, 11, 0, 5)
+    [job_id] = scheduler.run_due()
+    scheduler.jobs.wait(job_id, 10)
+    for _ in range(100):
+        if len(events) == 2:
+            break
+        time.sleep(0.05)
+    assert ran == [(str(folder.resolve()), True)] and events == [EVENT_STARTED, EVENT_FINISHED]
+    finished = scheduler.events(since=1)['events']
+    assert finished[0]['payload']['state'] == 'done' and finished[0]['payload']['summary'] == {'organized': 2}
+
+    now[0] = datetime(2025, 1, 16, 8, 30)
+    reloaded = Scheduler(JobManager(runner), str(tmp_path / "schedules.json"), clock=lambda: now[0])
+    [saved] = reloaded.list_schedules()
+    assert saved['last_state'] == 'done' and saved['last_job_id'] == job_id
+    assert saved['next_run'] == '2025-01-16T09:00'   # Missed runs are not made up for
+    assert reloaded.set_enabled(saved['id'], False)['schedule']['enabled'] is False
+    assert reloaded.remove_schedule(saved['id'])['success'] and reloaded.list_schedules() == []
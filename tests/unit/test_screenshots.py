@@ -0,0 +1,61 @@
+"""
+Unit tests for screenshot detection and OCR naming.
+"""
+
+import sys
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.screenshots import ScreenshotNamer, dominant_line, is_screenshot
+
+HEADER = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext"
+
+
+def _tsv(*words):
+    """Tesseract TSV for a 1000px tall image: (block, line, left, top, height, conf, text) per word."""
+    rows = [HEADER, "1\t1\t0\t0\t0\t0\t0\t0\t1600\t1000\t-1\t"]
+    for block, line, left, top, height, conf, text in words:
+        rows.append(f"5\t1\t{block}\t1\t{line}\t1\t{left}\t{top}\t50\t{height}\t{conf}\t{text}")
+    return '\n'.join(rows)
+
+
+def test_title_line_is_picked_from_ocr_output():
+    """Large text near the top wins over body text and low-confidence noise."""
+    tsv = _tsv(
+        (1, 1, 120, 40, 30, 95, 'settings'), (1, 1, 10, 40, 30, 96, 'Stripe'), (1, 1, 60, 40, 30, 93, 'invoice'),
+        (2, 1, 10, 500, 12, 95, 'Your'), (2, 1, 60, 500, 12, 95, 'next'), (2, 1, 110, 500, 12, 95, 'invoice'),
+        (2, 1, 160, 500, 12, 95, 'is'), (2, 1, 210, 500, 12, 95, 'due'),
+        (3, 1, 10, 900, 80, 20, 'garbled'), (3, 1, 60, 900, 80, 15, 'xx'),
+    )
+
+    assert dominant_line(tsv) == 'Stripe invoice settings'
+    assert dominant_line(_tsv((1, 1, 10, 40, 30, 95, 'Alone'))) is None
+    assert dominant_line(HEADER) is None
+
+
+def test_screenshots_are_detected_and_named_from_their_text(tmp_path):
+    """Names come from the screenshot's date and text; without OCR there is no rename."""
+    shot = tmp_path / "Screenshot 2024-05-02 at 10.31.22.png"
+    shot.write_bytes(b'\x89PNG\r\n\x1a\n')
+    tagged = tmp_path / "image1.png"
+    tagged.write_bytes(b'\x89PNG\r\n\x1a\n...tEXtSoftware\x00gnome-screenshot...IDAT')
+    photo = tmp_path / "holiday.png"
+    photo.write_bytes(b'\x89PNG\r\n\x1a\n...IDATscreenshot')
+
+    assert is_screenshot(shot) and is_screenshot(tagged)
+    assert not is_screenshot(photo) and not is_screenshot(tmp_path / "Screenshot notes.txt")
+
+    config = Mock()
+    config.screenshot_settings = {'tesseract_path': str(shot)}  # Any existing file stands in for the binary
+    namer = ScreenshotNamer(config)
+    ocr = SimpleNamespace(returncode=0, stderr='', stdout=_tsv(
+        (1, 1, 10, 40, 30, 95, 'Stripe'), (1, 1, 60, 40, 30, 93, 'invoice'), (1, 1, 120, 40, 30, 95, 'settings')))
+    with patch('src.core.screenshots.subprocess.run', return_value=ocr) as run:
+        assert namer.suggest_name(shot) == '2024-05-02 Stripe invoice settings.png'
+    assert run.call_args.args[0][:3] == [str(shot), str(shot), 'stdout']
+
+    config.screenshot_settings = {'ocr_naming': False}
+    assert ScreenshotNamer(config).suggest_name(shot) is None
@@ -0,0 +1,123 @@
+"""
+Unit tests for the full-text search index.
+"""
+
+import os
+import sqlite3
+import sys
+from datetime import datetime
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.db_manager import DatabaseManager, SEARCH_UNAVAILABLE
+from src.core.search_index import SearchIndex, parse_query
+
+
+@pytest.fixture(autouse=True)
+def config_dir(tmp_path):
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path / "config")}):
+        yield
+
+
+@pytest.fixture
+def index(tmp_path):
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    config = Mock(search_settings={}, text_extract_limit=1000, destination_rules={'pdf': 'Documents/PDFs/'})
+    yield SearchIndex(config, db)
+    db.cleanup()
+
+
+def _write(path: Path, text: str, year: int) -> Path:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(text)
+    stamp = datetime(year, 6, 1).timestamp()
+    os.utime(path, (stamp, stamp))
+    return path
+
+
+def test_plain_query_finds_the_tax_pdf_from_2022(tmp_path, index):
+    """Type words filter by extension, the year by date or mention; unchanged files are skipped next time."""
+    home = tmp_path / "home"
+    _write(home / "Finance" / "tax_return.pdf", "%PDF", 2022)
+    _write(home / "Finance" / "taxes.pdf", "%PDF", 2023)
+    notes = _write(home / "notes.txt", "Tax deadline is in April", 2022)
+    _write(home / "node_modules" / "tax.pdf", "%PDF", 2022)   # Ignored by default
+
+    assert parse_query("that tax PDF from 2022") == {'terms': ['tax'], 'extensions': ['pdf'], 'year': 2022}
+    first = index.index_folder(str(home))
+    assert (first['files'], first['indexed']) == (3, 3)
+
+    found = index.search("that tax PDF from 2022")
+    assert [result['name'] for result in found['results']] == ['tax_return.pdf']
+    assert found['results'][0]['category'] == 'Documents'
+    assert sorted(result['name'] for result in index.search("tax 2022")['results']) == ['notes.txt', 'tax_return.pdf']
+    assert index.search("deadline")['results'][0]['snippet'] == 'Tax [deadline] is in April'
+    assert index.search("finance")['total'] == 2   # Folder names are searchable
+
+    notes.unlink()
+    second = index.index_folder(str(home))
+    assert (second['indexed'], second['unchanged'], second['removed']) == (0, 2, 1)
+    assert index.search("deadline")['total'] == 0
+
+
+def test_classification_tags_filters_and_moves(tmp_path, index):
+    """Tags and summaries come from the classification; moved files follow, missing ones drop out."""
+    invoice = _write(tmp_path / "Downloads" / "scan001.pdf", "%PDF", 2024)
+    classification = {'category': 'Finance', 'tags': ['invoice', 'acme'], 'reason': 'Invoice from ACME Corp'}
+    assert index.update_file(str(invoice), classification)
+
+    assert index.search("acme")['results'][0]['tags'] == ['invoice', 'acme']
+    assert index.search("corp")['results'][0]['summary'] == 'Invoice from ACME Corp'
+    assert index.search("", {'tag': 'Invoice', 'category': 'finance'})['total'] == 1
+    assert index.search("", {'min_size': 100})['total'] == 0
+    assert index.search("", {'modified_after': '2024-01-01', 'folder': str(tmp_path / "Downloads")})['total'] == 1
+
+    moved = tmp_path / "Documents" / "acme_invoice.pdf"
+    moved.parent.mkdir()
+    invoice.rename(moved)
+    assert index.move_file(str(invoice), str(moved), classification)
+    assert [result['path'] for result in index.search("acme")['results']] == [str(moved)]
+
+    moved.unlink()
+    assert not index.update_file(str(moved))
+    assert index.stats()['files'] == 0 and index.stats()['available']
+
+
+class _NoFts5Cursor(sqlite3.Cursor):
+    def execute(self, sql, *args):
+        if 'USING fts5' in sql:
+            raise sqlite3.OperationalError("no such module: fts5")
+        return super().execute(sql, *args)
+
+
+class _NoFts5Connection(sqlite3.Connection):
+    """A connection that behaves like a SQLite build without FTS5."""
+
+    def cursor(self, factory=_NoFts5Cursor):
+        return super().cursor(factory)
+
+
+def test_without_fts5_the_database_opens_and_search_says_it_is_unavailable(tmp_path):
+    """Only search is lost: the database still works and the index reports why it cannot be used."""
+    connect = sqlite3.connect
+    with patch('src.core.db_manager.sqlite3.connect',
+               side_effect=lambda *args, **kwargs: connect(*args, factory=_NoFts5Connection, **kwargs)):
+        db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    index = SearchIndex(Mock(search_settings={}, text_extract_limit=1000, destination_rules={}), db)
+    notes = _write(tmp_path / "home" / "notes.txt", "Tax deadline is in April", 2022)
+
+    assert not db.search_available and db.get_stats()['files_organised'] == 0
+    assert index.index_folder(str(tmp_path / "home")) == {
+        'success': False, 'folder': str(tmp_path / "home"), 'message': SEARCH_UNAVAILABLE}
+    found = index.search("tax")
+    assert (found['success'], found['total'], found['results']) == (False, 0, [])
+    assert "Full-text search is unavailable" in found['message']
+    assert not index.update_file(str(notes)) and index.remove_file(str(notes)) == 0
+    assert index.stats()['available'] is False
+    with pytest.raises(RuntimeError, match="unavailable"):
+        db.search_documents(None, {})
+    db.cleanup()
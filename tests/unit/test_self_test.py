@@ -0,0 +1,35 @@
+"""
+Unit tests for the end-to-end self test.
+"""
+
+import shutil
+import sys
+from pathlib import Path
+from unittest.mock import patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.config import get_config
+from src.core.self_test import run_self_test, SUBSYSTEMS
+
+
+def test_self_test_passes_and_reports_each_subsystem():
+    """A healthy install passes every stage and leaves no sandbox behind."""
+    result = run_self_test(get_config())
+
+    assert result['success'], result['subsystems']
+    assert [r['name'] for r in result['subsystems']] == list(SUBSYSTEMS)
+    assert {r['status'] for r in result['subsystems']} == {'passed'}
+    assert result['sandbox'] is None
+
+
+def test_failed_subsystem_skips_the_ones_that_depend_on_it():
+    """A broken apply is named, and undo is skipped rather than reported broken."""
+    with patch('src.core.plans.OrganizePlanner.apply_plan', side_effect=OSError("disk full")):
+        result = run_self_test(get_config(), keep=True)
+
+    statuses = {r['name']: r['status'] for r in result['subsystems']}
+    assert not result['success'] and 'apply' in result['message']
+    assert statuses['plan'] == 'passed' and statuses['apply'] == 'failed' and statuses['undo'] == 'skipped'
+    assert Path(result['sandbox'], 'inbox', 'notes.txt').exists()
+    shutil.rmtree(result['sandbox'])
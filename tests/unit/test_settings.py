@@ -0,0 +1,56 @@
+"""
+Unit tests for the user settings store.
+"""
+
+import json
+import os
+import stat
+import sys
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.settings import SETTINGS_VERSION, SettingsStore, app_config_dir
+from src.utils.error_handler import ConfigurationError
+
+
+def test_update_saves_and_masks_keys(tmp_path):
+    """Updates merge into the file (owner-only); keys come back masked unless asked for."""
+    with patch.dict(os.environ, {'AIFO_CONFIG_DIR': str(tmp_path / "conf")}):
+        store = SettingsStore()
+        assert store.path == app_config_dir() / "settings.json" == tmp_path / "conf" / "settings.json"
+    assert store.get_settings()['default_tier'] == 'local' and not store.path.exists()
+
+    saved = store.update_settings({'api_keys': {'openai': 'sk-abcdefgh1234'}, 'exclusions': ['*.tmp'],
+                                   'folder_templates': {'Documents': 'Documents/{year}'}})
+    assert saved['api_keys'] == {'openai': '••••••••1234'} and saved['exclusions'] == ['*.tmp']
+    assert stat.S_IMODE(store.path.stat().st_mode) == 0o600
+    assert json.loads(store.path.read_text())['api_keys'] == {'openai': 'sk-abcdefgh1234'}
+
+    store.update_settings({'api_keys': {'anthropic': 'short'}})
+    store.update_settings({'api_keys': {'openai': None}})
+    assert SettingsStore(str(store.path)).get_settings(reveal_secrets=True)['api_keys'] == {'anthropic': 'short'}
+
+    for bad in ({'default_tier': 'premium'}, {'exclusions': 'node_modules'}, {'colour': 'blue'}):
+        with pytest.raises(ConfigurationError):
+            store.update_settings(bad)
+    assert store.get_settings()['default_tier'] == 'local'
+
+
+def test_older_files_are_migrated_and_newer_ones_refused(tmp_path):
+    """An unversioned file is upgraded (original kept); a file from a newer schema is not touched."""
+    path = tmp_path / "settings.json"
+    path.write_text(json.dumps({'exclude': ['.git'], 'tier': 'cloud', 'api_keys': {}}))
+    settings = SettingsStore(str(path)).get_settings()
+    assert settings['version'] == SETTINGS_VERSION
+    assert (settings['exclusions'], settings['default_tier'], settings['folder_templates']) == (['.git'], 'cloud', {})
+    assert json.loads((tmp_path / "settings.v0.json").read_text())['tier'] == 'cloud'
+    assert json.loads(path.read_text())['version'] == SETTINGS_VERSION
+
+    path.write_text(json.dumps({'version': SETTINGS_VERSION + 1}))
+    with pytest.raises(ConfigurationError, match="newer version"):
+        SettingsStore(str(path)).load()
+    assert json.loads(path.read_text()) == {'version': SETTINGS_VERSION + 1}
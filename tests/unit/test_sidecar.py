@@ -0,0 +1,82 @@
+"""
+Unit tests for the long-lived sidecar engine and its client.
+"""
+
+import io
+import json
+import sys
+import threading
+from pathlib import Path
+
+import pytest
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.control import ControlError, METHOD_NOT_FOUND, UNAVAILABLE
+from src.core.sidecar import serve_stdio, SidecarClient
+
+
+ROOT = str(Path(__file__).parent.parent.parent)
+
+
+def _sidecar(methods_source):
+    script = (f"import sys, os\nsys.path.insert(0, {ROOT!r})\n"
+              "from src.core.sidecar import serve_stdio\n"
+              "print('engine starting')\n"
+              f"serve_stdio({methods_source})\n")
+    return [sys.executable, '-c', script]
+
+
+def test_serve_stdio_answers_each_request_on_its_own_line():
+    """Replies carry the request id, slow requests don't hold up others, and shutdown stops the loop."""
+    release = threading.Event()
+    requests = "\n".join([
+        json.dumps({'id': 1, 'method': 'wait'}),
+        json.dumps({'id': 2, 'method': 'add', 'params': {'a': 2, 'b': 3}}),
+        'not json',
+        json.dumps({'id': 3, 'method': 'missing'}),
+        json.dumps({'id': 4, 'method': 'shutdown'}),
+        json.dumps({'id': 5, 'method': 'add', 'params': {'a': 1, 'b': 1}}),
+    ]) + "\n"
+
+    def add(a, b):
+        release.set()
+        return a + b
+
+    out = io.StringIO()
+    serve_stdio({'add': add, 'wait': lambda: release.wait(10) and 'waited'}, io.StringIO(requests), out)
+
+    lines = [json.loads(line) for line in out.getvalue().splitlines()]
+    assert lines[0]['method'] == 'ready' and lines[0]['params']['protocol'] >= 1
+    replies = {line.get('id'): line for line in lines[1:]}
+    assert replies[1]['result'] == 'waited' and replies[2]['result'] == 5
+    assert replies[None]['error']['code'] < 0
+    assert replies[3]['error']['code'] == METHOD_NOT_FOUND
+    assert replies[4]['result'] == {'stopping': True} and 5 not in replies
+
+
+def test_client_calls_and_restarts_a_crashed_sidecar():
+    """The client matches replies to calls, reports errors and starts the sidecar again after a crash."""
+    methods = "{'echo': lambda value: value, 'crash': lambda: os._exit(3), 'fail': lambda: 1 / 0}"
+    client = SidecarClient(_sidecar(methods), timeout=30, max_restarts=1)
+    try:
+        assert client.call('echo', value={'a': [1, 2]}) == {'a': [1, 2]}
+        first_pid = client.pid
+        with pytest.raises(ControlError, match='division by zero'):
+            client.call('fail')
+
+        with pytest.raises(ControlError) as crashed:
+            client.call('crash')
+        assert crashed.value.code == UNAVAILABLE
+
+        assert client.call('echo', value='back') == 'back'
+        assert client.restarts == 1 and client.pid != first_pid
+
+        with pytest.raises(ControlError):
+            client.call('crash')
+        with pytest.raises(ControlError) as gave_up:
+            client.call('echo', value='again')
+        assert 'not starting it again' in str(gave_up.value)
+    finally:
+        client.close()
+    assert client.pid is None
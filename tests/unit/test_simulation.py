@@ -0,0 +1,68 @@
+"""
+Unit tests for simulating a run on a sandboxed copy.
+"""
+
+import shutil
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.config import get_config
+from src.core.simulation import simulate_on_copy
+
+
+def _folder(tmp_path):
+    folder = tmp_path / "downloads"
+    (folder / "sub").mkdir(parents=True)
+    for name in ("report.pdf", "notes.txt", "photo.jpg", "song.mp3", "sub/budget.xlsx", "sub/clip.mp4"):
+        (folder / name).write_text(name)
+    return folder
+
+
+def test_a_sample_is_organized_in_the_sandbox_and_mapped_back(tmp_path):
+    """The sample moves inside the sandbox only; results name where the real files would go."""
+    folder = _folder(tmp_path)
+    before = sorted(p.relative_to(folder) for p in folder.rglob("*"))
+    config = get_config()
+
+    result = simulate_on_copy(str(folder), 3, config, seed=7)
+
+    assert result['success'], result
+    assert (result['total_files'], result['sampled'], result['sandbox']) == (6, 3, None)
+    assert sorted(p.relative_to(folder) for p in folder.rglob("*")) == before
+    assert len(result['moves']) + result['unchanged'] == 3
+    base = Path(config.base_destination).expanduser()
+    for move in result['moves']:
+        assert Path(move['file']).parent in (folder, folder / "sub")
+        assert Path(move['destination']).is_relative_to(base) and not Path(move['destination']).exists()
+    assert sum(result['categories'].values()) == len(result['moves'])
+    assert simulate_on_copy(str(folder), 3, config, seed=7)['moves'] == result['moves']
+
+
+def test_destinations_outside_the_sandbox_are_reported_not_moved(tmp_path):
+    """An absolute rule folder is listed, nothing lands there, and a kept sandbox holds the rest."""
+    folder = _folder(tmp_path)
+    outside = tmp_path / "elsewhere"
+    classifier = Mock(spec=['user_rules'])
+    classifier.user_rules.classify.side_effect = lambda path: {
+        'category': 'Music', 'suggested_path': 'Music/', 'destination_root': str(outside),
+        'confidence': 'high', 'method': 'user-rules'} if path.endswith('.mp3') else None
+
+    result = simulate_on_copy(str(folder), 10, get_config(), classifier, keep=True)
+
+    assert result['sampled'] == 6
+    assert result['outside_sandbox'] == [{'file': str(folder / "song.mp3"),
+                                          'destination': str(outside / "Music" / "song.mp3")}]
+    assert not outside.exists() and "outside the sandbox" in result['message']
+    assert all(not move['file'].endswith('.mp3') for move in result['moves'])
+    assert Path(result['sandbox'], 'inbox', 'song.mp3').exists()
+    shutil.rmtree(result['sandbox'])
+
+    with pytest.raises(NotADirectoryError):
+        simulate_on_copy(str(tmp_path / "missing"), 5, get_config())
+    with pytest.raises(ValueError, match="at least 1"):
+        simulate_on_copy(str(folder), 0, get_config())
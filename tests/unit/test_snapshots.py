@@ -0,0 +1,109 @@
+"""
+Unit tests for pre-organize folder snapshots.
+"""
+
+import shutil
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import MagicMock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.snapshots import SnapshotManager
+from src.core.plans import OrganizePlanner
+
+
+@pytest.fixture
+def config(config):
+    """Snapshots runs of five files or more, hashing with SHA-1."""
+    config.snapshot_settings = {'min_files': 5}
+    config.hash_algorithm = 'sha1'
+    return config
+
+
+@pytest.fixture
+def downloads(config, db, tmp_path):
+    """A downloads folder with six files, a snapshot manager and its database."""
+    folder = tmp_path / "downloads"
+    (folder / "old").mkdir(parents=True)
+    for i in range(5):
+        (folder / f"file{i}.txt").write_text(f"content {i}")
+    (folder / "old" / "notes.md").write_text("notes")
+    return SnapshotManager(config, db), folder, tmp_path
+
+
+def test_small_runs_are_not_snapshotted(downloads):
+    """Runs below min_files do not record a snapshot."""
+    manager, folder, _ = downloads
+
+    assert manager.maybe_snapshot([str(folder / "file0.txt")]) is None
+    assert manager.list_snapshots() == []
+
+
+def test_restore_reconstructs_layout(downloads):
+    """Moved and renamed files go back; restore is undoable; extra files stay."""
+    manager, folder, tmp_path = downloads
+    snapshot_id = manager.maybe_snapshot([str(p) for p in folder.glob('*.txt')])
+    assert manager.get_snapshot(snapshot_id)['file_count'] == 6
+
+    # Logged move out of the folder, unlogged move, rename in place, new file
+    organized = tmp_path / "organized" / "Documents"
+    organized.mkdir(parents=True)
+    shutil.move(str(folder / "file0.txt"), str(organized / "file0.txt"))
+    manager.db_manager.log_action('file0.txt', str(folder / "file0.txt"), str(organized / "file0.txt"), 'move')
+    shutil.move(str(folder / "file1.txt"), str(organized / "renamed.txt"))
+    (folder / "file2.txt").rename(folder / "old" / "file2-copy.txt")
+    (folder / "new.txt").write_text("added later")
+
+    preview = manager.restore_snapshot(snapshot_id, dry_run=True)
+    assert len(preview['restored']) == 3
+    assert not (folder / "file0.txt").exists()
+
+    result = manager.restore_snapshot(snapshot_id)
+
+    assert result['success'], result['message']
+    assert result['missing'] == [] and result['in_place'] == 3
+    for i in range(3):
+        assert (folder / f"file{i}.txt").read_text() == f"content {i}"
+    assert (folder / "new.txt").exists()
+    assert manager.get_snapshot(snapshot_id)['status'] == 'restored'
+
+    manager.journal.undo(result['operation_id'])
+
+    assert (organized / "renamed.txt").exists()
+    assert not (folder / "file1.txt").exists()
+
+
+def test_occupied_paths_are_not_overwritten(downloads):
+    """A different file at a recorded path is reported as a conflict."""
+    manager, folder, tmp_path = downloads
+    snapshot_id = manager.create_snapshot(str(folder))['snapshot_id']
+
+    (folder / "file3.txt").write_text("changed")
+
+    result = manager.restore_snapshot(snapshot_id)
+
+    assert result['conflicts'] == [str(folder.resolve() / "file3.txt")]
+    assert (folder / "file3.txt").read_text() == "changed"
+
+
+def test_applying_a_large_plan_snapshots_first(downloads, config, action_manager):
+    """Every apply path goes through apply_plan, which snapshots runs of min_files or more."""
+    _, folder, _ = downloads
+    classifier = MagicMock()
+    classifier.classify.return_value = {
+        'category': 'Documents', 'suggested_path': 'Documents/', 'confidence': 'high', 'method': 'rule-based'}
+    planner = OrganizePlanner(config, classifier, action_manager)
+
+    plan = planner.build_plan(str(folder))
+    result = planner.apply_plan(plan)
+    assert len(result['applied']) == 6 and result['snapshot_id'] is not None
+    assert not (folder / "file0.txt").exists()
+    assert planner.apply_plan({**plan, 'items': plan['items'][:1]})['snapshot_id'] is None
+
+    restored = planner.snapshots.restore_snapshot(result['snapshot_id'])
+    assert restored['success'], restored['message']
+    for i in range(5):
+        assert (folder / f"file{i}.txt").read_text() == f"content {i}"
+    assert (folder / "old" / "notes.md").read_text() == "notes"
@@ -0,0 +1,99 @@
+"""
+Unit tests for split archive detection and handling.
+"""
+
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.split_archives import split_part_info, split_archive_parts, group_split_archives
+from src.core.duplicates import DuplicateFinder
+
+
+def _touch(directory: Path, *names: str, content: bytes = b'x' * 2048):
+    for name in names:
+        (directory / name).write_bytes(content)
+
+
+def test_detects_naming_schemes(tmp_path):
+    """Numbered, partN.rar, old rar and spanned zip volumes are recognized."""
+    _touch(tmp_path, 'old.rar', 'old.r00', 'span.z01', 'span.zip', 'lonely.zip')
+
+    assert split_part_info(tmp_path / 'backup.zip.001').primary is True
+    assert split_part_info(tmp_path / 'backup.zip.002').primary is False
+    assert split_part_info(tmp_path / 'photos.part1.rar').primary is True
+    assert split_part_info(tmp_path / 'photos.part2.rar').base == 'photos'
+    assert split_part_info(tmp_path / 'old.rar').primary is True
+    assert split_part_info(tmp_path / 'span.zip').primary is True
+    assert split_part_info(tmp_path / 'span.z01').primary is False
+    assert split_part_info(tmp_path / 'lonely.zip') is None
+    assert split_part_info(tmp_path / 'report.pdf') is None
+
+
+def test_groups_parts_into_units(tmp_path):
+    """Parts are grouped under their primary part, other files stay single."""
+    _touch(tmp_path, 'movie.7z.002', 'movie.7z.001', 'movie.7z.003', 'notes.txt')
+
+    units = group_split_archives(sorted(tmp_path.iterdir()))
+
+    assert [[p.name for p in unit] for unit in units] == [
+        ['movie.7z.001', 'movie.7z.002', 'movie.7z.003'],
+        ['notes.txt']
+    ]
+    assert [p.name for p in split_archive_parts(tmp_path / 'movie.7z.003')][0] == 'movie.7z.001'
+
+
+def test_parts_move_together_with_rename(action_manager, tmp_path):
+    """Organizing any part moves every part and applies the rename to all."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    _touch(downloads, 'photos.part1.rar', 'photos.part2.rar', 'photos.part3.rar')
+
+    result = action_manager.execute(str(downloads / 'photos.part2.rar'), {
+        'suggested_path': 'Archives/',
+        'rename': 'Holiday.part2.rar',
+        'category': 'Archives',
+        'confidence': 'high',
+        'method': 'rule-based'
+    }, user_approved=True)
+
+    assert result['success'], result['message']
+    assert len(result['parts']) == 3
+    assert list(downloads.iterdir()) == []
+    moved = sorted(p.name for p in (tmp_path / "organized" / "Archives").iterdir())
+    assert moved == ['Holiday.part1.rar', 'Holiday.part2.rar', 'Holiday.part3.rar']
+
+
+def test_duplicate_split_archives_compared_as_unit(tmp_path):
+    """Identical split archives form one group; cleanup removes all their parts."""
+    first, second = tmp_path / "a", tmp_path / "b"
+    for directory in (first, second):
+        directory.mkdir()
+        (directory / 'backup.zip.001').write_bytes(b'1' * 2048)
+        (directory / 'backup.zip.002').write_bytes(b'2' * 2048)
+
+    config = Mock()
+    config.hash_algorithm = 'sha1'
+    config.trash_settings = {'backend': 'app'}
+    config.trash_dir = str(tmp_path / 'trash')
+    db = MagicMock()
+    with patch('src.core.duplicates.SafetyGuardian') as guardian_cls:
+        guardian_cls.return_value.is_file_safe_to_modify.return_value = (True, 'Safe to modify')
+        finder = DuplicateFinder(config, db)
+
+    groups = finder.find_duplicates_in_directory(str(tmp_path))
+
+    assert len(groups) == 1
+    assert all(p.endswith('backup.zip.001') for p in groups[0]['paths'])
+    assert groups[0]['size'] == 4096
+
+    result = finder.cleanup_duplicates(groups[0], dry_run=False)
+
+    assert result['deleted_count'] == 1
+    assert len(list((tmp_path / 'trash').rglob('backup.zip.00*'))) == 2
+    remaining = sorted(p.relative_to(tmp_path).as_posix() for p in tmp_path.rglob('*')
+                       if p.is_file() and not p.is_relative_to(tmp_path / 'trash'))
+    assert len(remaining) == 2
+    assert remaining[0].split('/')[0] == remaining[1].split('/')[0]
@@ -0,0 +1,96 @@
+"""
+Unit tests for tracing spans and runtime log levels.
+"""
+
+import json
+import logging
+import sys
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+import threading
+
+from src.utils.structured_logging import LogStream, configure_logging, set_log_level, span
+
+
+def read_records(path):
+    return [json.loads(line) for line in path.read_text().splitlines()]
+
+
+def test_spans_nest_and_annotate_records(tmp_path):
+    """Records inside a span carry its path, ids and fields; closing logs the outcome."""
+    log_file = tmp_path / "app.log"
+    root = logging.getLogger()
+    previous_level, previous_handlers = root.level, list(root.handlers)
+    configure_logging({'level': 'INFO', 'file': str(log_file)})
+    log = logging.getLogger('tests.spans')
+    try:
+        with span('command.plan', level=logging.INFO, target='~/Downloads') as outer:
+            with span('planner.build_plan') as inner:
+                log.info("classified")
+                inner.fail("2 files unreadable")
+            outer.record(exit_code=0)
+        with pytest.raises(ValueError):
+            with span('control.apply'):
+                raise ValueError("bad plan")
+    finally:
+        for handler in [h for h in root.handlers if h not in previous_handlers]:
+            root.removeHandler(handler)
+            handler.close()
+        root.setLevel(previous_level)
+
+    records = read_records(log_file)
+    classified = next(r for r in records if r['message'] == 'classified')
+    assert classified['span'] == 'command.plan/planner.build_plan'
+    assert classified['parent_span_id'] and classified['span_fields'] == {}
+
+    failed_inner = next(r for r in records if r.get('span') == 'command.plan/planner.build_plan'
+                        and r.get('event_type') == 'span_end')
+    assert failed_inner['status'] == 'error' and failed_inner['error'] == '2 files unreadable'
+    finished = next(r for r in records if r.get('span') == 'command.plan' and r.get('event_type') == 'span_end')
+    assert finished['status'] == 'ok' and finished['span_fields'] == {'target': '~/Downloads', 'exit_code': 0}
+    crashed = next(r for r in records if r.get('span') == 'control.apply')
+    assert crashed['error_type'] == 'ValueError' and crashed['exception']['message'] == 'bad plan'
+
+
+def test_log_level_changes_at_runtime():
+    """Levels change for the root or for one logger, reporting the previous one."""
+    noisy = logging.getLogger('tests.noisy')
+    noisy.setLevel(logging.WARNING)
+    try:
+        change = set_log_level('debug', 'tests.noisy')
+        assert change == {'logger': 'tests.noisy', 'previous': 'WARNING', 'level': 'DEBUG'}
+        assert noisy.isEnabledFor(logging.DEBUG)
+    finally:
+        noisy.setLevel(logging.NOTSET)
+
+    with pytest.raises(ValueError):
+        set_log_level('LOUD')
+
+
+def test_log_stream_reads_by_cursor_level_and_module():
+    """Viewers get what is new since their cursor, filtered, and can wait for it."""
+    stream = LogStream(capacity=3)
+    log = logging.getLogger('src.core.plans')
+    log.addHandler(stream)
+    log.setLevel(logging.DEBUG)
+    try:
+        log.debug("classifying")
+        log.warning("slow disk")
+        first = stream.read(level='INFO')
+        assert [r['message'] for r in first['records']] == ['slow disk']
+        assert stream.read(module='src.core')['records'][0]['logger'] == 'src.core.plans'
+        assert stream.read(module='src.core.pl')['records'] == []
+
+        threading.Timer(0.1, lambda: log.error("stuck job")).start()
+        waited = stream.read(after=first['cursor'], wait=5)
+        assert [r['message'] for r in waited['records']] == ['stuck job'] and not waited['dropped']
+
+        for n in range(4):
+            log.info(f"line {n}")
+        assert stream.read(after=waited['cursor'])['dropped']  # Capacity 3: some never seen
+    finally:
+        log.removeHandler(stream)
+        log.setLevel(logging.NOTSET)
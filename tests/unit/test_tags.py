@@ -0,0 +1,74 @@
+"""
+Unit tests for file tags.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core import tags as tags_module
+from src.core.db_manager import DatabaseManager
+from src.core.tags import TagStore, normalize_tags
+
+
+@pytest.fixture
+def db(tmp_path):
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    yield db
+    db.cleanup()
+
+
+def test_tags_persist_merge_with_ai_and_follow_moves(tmp_path, db):
+    """User tags replace, AI tags add; find_by_tag ignores case; tags move with the file."""
+    store = TagStore(Mock(tag_settings={}), db)
+    report = tmp_path / "report.pdf"
+    report.write_text("%PDF")
+
+    assert normalize_tags(" Tax , tax,2022 ,, work  files") == ['Tax', '2022', 'work files']
+    result = store.set_tags(str(report), ['Tax', 'tax', '2022'])
+    assert (result['success'], result['tags'], result['written_to_file']) == (True, ['Tax', '2022'], False)
+    assert store.record_classification(str(report), {'tags': ['TAX', 'invoice']}) == ['TAX', 'invoice']
+    assert store.get_tags(str(report)) == {'path': str(report), 'tags': ['Tax', '2022', 'invoice'],
+                                           'sources': {'Tax': 'user', '2022': 'user', 'invoice': 'ai'}}
+    assert [file['path'] for file in store.find_by_tag('INVOICE')['files']] == [str(report)]
+    assert store.list_tags()[0] == {'tag': '2022', 'files': 1}
+
+    moved = tmp_path / "Finance" / "report.pdf"
+    moved.parent.mkdir()
+    report.rename(moved)
+    assert store.move_tags(str(report), str(moved)) == 3
+    assert store.get_tags(str(report))['tags'] == []
+    found = store.find_by_tag('tax')['files'][0]
+    assert (found['path'], found['tags'], found['exists']) == (str(moved), ['Tax', '2022', 'invoice'], True)
+    assert not store.set_tags(str(tmp_path / "missing.pdf"), ['x'])['success']
+
+
+def test_tags_written_into_the_file_survive_the_database(tmp_path, db, monkeypatch):
+    """With write_to_file, another database reads the tags back from the file, and its mtime is kept."""
+    photo = tmp_path / "beach.jpg"
+    photo.write_bytes(b'\xff\xd8')
+    os.utime(photo, (1_600_000_000, 1_600_000_000))
+    attributes = {}
+    monkeypatch.setattr(tags_module.os, 'name', 'posix')
+    monkeypatch.setattr(tags_module.sys, 'platform', 'linux')
+    monkeypatch.setattr(tags_module.os, 'setxattr', lambda path, name, value: attributes.__setitem__((path, name), value),
+                        raising=False)
+    monkeypatch.setattr(tags_module.os, 'getxattr', lambda path, name: attributes[(path, name)], raising=False)
+
+    store = TagStore(Mock(tag_settings={'write_to_file': True}), db)
+    assert store.set_tags(str(photo), ['holiday', 'beach'])['written_to_file']
+    assert attributes == {(str(photo), 'user.xdg.tags'): b'holiday,beach'}
+    assert photo.stat().st_mtime == 1_600_000_000
+
+    other = DatabaseManager(str(tmp_path / "db" / "other.db"))
+    try:
+        fresh = TagStore(Mock(tag_settings={}), other)
+        assert fresh.get_tags(str(photo))['sources'] == {'holiday': 'file', 'beach': 'file'}
+        assert fresh.find_by_tag('beach')['files'][0]['path'] == str(photo)
+    finally:
+        other.cleanup()
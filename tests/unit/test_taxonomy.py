@@ -0,0 +1,70 @@
+"""
+Unit tests for the user's category taxonomy.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.settings import SettingsStore
+from src.core.taxonomy import Taxonomy, TaxonomyManager
+
+
+def test_categories_are_added_renamed_and_removed_as_a_tree(tmp_path):
+    """Parents must exist, renames carry subcategories along, and a category with children stays."""
+    taxonomy = TaxonomyManager(Mock(), SettingsStore(str(tmp_path / "settings.json"), use_keychain=False))
+
+    assert taxonomy.add_category({'name': 'Documents'})['success']
+    assert taxonomy.add_category({'name': 'Invoices', 'parent': 'documents', 'aliases': ['Receipts']})['success']
+    for invalid, reason in [({'name': 'Taxes', 'parent': 'Finance'}, "no parent category named 'Finance'"),
+                            ({'name': 'documents'}, "already a category named"),
+                            ({'name': 'Bills', 'aliases': ['receipts']}, "cannot name both"),
+                            ({'name': 'Scans', 'destination': '{camera}'}, "unknown placeholder"),
+                            ({'name': 'Up', 'destination': '../out'}, "cannot contain '..'")]:
+        result = taxonomy.add_category(invalid)
+        assert not result['success'] and reason in result['message']
+
+    assert taxonomy.update_category('Documents', {'name': 'Papers'})['success']
+    assert [c['path'] for c in taxonomy.list_categories()] == ['Papers', 'Papers/Invoices']
+    assert [(node['name'], [child['name'] for child in node['children']]) for node in taxonomy.tree()] == [
+        ('Papers', ['Invoices'])]
+    looped = taxonomy.update_category('Papers', {'parent': 'Invoices'})
+    assert not looped['success'] and "inside itself" in looped['message']
+
+    assert "still has subcategories" in taxonomy.delete_category('Papers')['message']
+    assert taxonomy.delete_category('Invoices')['success'] and taxonomy.delete_category('Papers')['success']
+    assert not taxonomy.delete_category('Papers')['success'] and taxonomy.list_categories() == []
+
+
+def test_results_are_mapped_onto_the_tree_with_an_unsorted_fallback(tmp_path):
+    """Known categories and aliases get the tree's folder, unknown ones go to Unsorted, rules keep theirs."""
+    invoice = tmp_path / "invoice.pdf"
+    invoice.write_text("invoice")
+    taxonomy = Taxonomy.from_settings([
+        {'name': 'Documents'},
+        {'name': 'Invoices', 'parent': 'Documents', 'destination': 'Finance/{ext}', 'aliases': ['Receipts']},
+        {'name': 'Archive', 'destination': str(tmp_path / "Archive")}])
+
+    def mapped(category, **extra):
+        result = {'category': category, 'suggested_path': 'Somewhere/', 'method': 'ai', **extra}
+        return taxonomy.map_classification(result, str(invoice))
+
+    assert mapped('receipts')['category'] == 'Documents/Invoices'
+    assert mapped('receipts')['suggested_path'] == 'Finance/pdf/' and 'original_category' not in mapped('Invoices')
+    assert mapped('Documents')['suggested_path'] == 'Documents/'
+    assert (mapped('Archive')['destination_root'], mapped('Archive')['suggested_path']) == (str(tmp_path), 'Archive/')
+    unsorted = mapped('Spreadsheets')
+    assert (unsorted['category'], unsorted['suggested_path'], unsorted['original_category']) == (
+        'Unsorted', 'Unsorted/', 'Spreadsheets')
+
+    ruled = mapped('invoices', method='user-rules')
+    assert (ruled['category'], ruled['suggested_path']) == ('Documents/Invoices', 'Somewhere/')
+    assert mapped('Holiday', method='user-rules')['category'] == 'Holiday'
+    assert mapped('Other', suggested_path=None)['category'] == 'Other'  # Left where it is
+    assert Taxonomy([]).map_classification({'category': 'Other', 'suggested_path': 'x/'}, str(invoice)) == {
+        'category': 'Other', 'suggested_path': 'x/'}
+    with pytest.raises(ValueError, match="Unknown category key"):
+        Taxonomy.from_settings([{'name': 'X', 'colour': 'red'}])
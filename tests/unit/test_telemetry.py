@@ -0,0 +1,70 @@
+"""
+Unit tests for local usage telemetry.
+"""
+
+import json
+import sys
+from datetime import date, timedelta
+from pathlib import Path
+from unittest.mock import Mock, MagicMock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.telemetry import Telemetry
+
+
+def _telemetry(tmp_path, **settings):
+    config = Mock()
+    config.telemetry_settings = settings
+    return Telemetry(config, store_file=str(tmp_path / 'telemetry.json'))
+
+
+def test_usage_is_counted_locally_only_when_enabled(tmp_path):
+    """Nothing is recorded until enabled; counts and error rates are summarized per feature."""
+    off = _telemetry(tmp_path)
+    off.record('cli.organize')
+    off.flush()
+    assert not (tmp_path / 'telemetry.json').exists()
+
+    on = _telemetry(tmp_path, enabled=True)
+    for _ in range(3):
+        on.record('cli.organize')
+    on.record('cli.organize', 'exit-3')
+    on.record('api:GET_/api/plan')
+    on.record('my report.pdf')                      # not a feature name: ignored
+    summary = on.summary()
+    assert summary['enabled'] and not summary['upload'] and summary['days'] == 1
+    assert summary['features'][0] == {'name': 'cli.organize', 'uses': 4, 'errors': 1, 'error_rate': 0.25}
+    assert [f['name'] for f in summary['features']] == ['cli.organize', 'api:GET_/api/plan']
+
+    config = Mock()
+    config.telemetry_settings = {}
+    result = Telemetry(config, store_file=str(tmp_path / 'other.json')).set_options(upload=True)
+    assert result['enabled'] and result['upload']
+    config.update.assert_called_once_with('telemetry', {'upload': True, 'enabled': True})
+    assert on.clear()['success'] and on.summary()['features'] == []
+
+
+def test_only_the_previewed_aggregate_is_uploaded(tmp_path):
+    """Finished days since the last upload are summed; what is posted equals the preview."""
+    yesterday = (date.today() - timedelta(days=1)).isoformat()
+    (tmp_path / 'telemetry.json').write_text(json.dumps({'schema': 1, 'days': {
+        yesterday: {'features': {'cli.organize': 2}, 'errors': {'cli.organize:exit-1': 1}}}}))
+    telemetry = _telemetry(tmp_path, enabled=True, upload=True, endpoint='https://example.invalid/t')
+    telemetry.record('cli.scan')                    # today: not sent yet
+
+    preview = telemetry.preview()
+    assert preview['features'] == {'cli.organize': 2} and preview['errors'] == {'cli.organize:exit-1': 1}
+    assert preview['period'] == {'start': yesterday, 'end': yesterday}
+    assert set(preview) == {'schema', 'app_version', 'os', 'period', 'features', 'errors'}
+
+    response = MagicMock(status=200)
+    response.__enter__.return_value = response
+    with patch('src.core.telemetry.urllib.request.urlopen', return_value=response) as urlopen:
+        sent = telemetry.upload()
+    assert sent['success'] and sent['sent'] == preview
+    assert json.loads(urlopen.call_args[0][0].data) == preview
+    assert telemetry.preview()['features'] == {}
+    assert telemetry.summary()['last_upload'] == yesterday
+
+    assert not _telemetry(tmp_path, enabled=True).upload()['success']
@@ -0,0 +1,73 @@
+"""
+Unit tests for the thumbnail cache.
+"""
+
+import base64
+import os
+import struct
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core import thumbnails
+from src.core.thumbnails import ThumbnailCache
+
+
+def _jpeg_with_thumbnail(path, thumbnail):
+    """A JPEG whose EXIF has an empty IFD0 and an IFD1 pointing at a JPEG thumbnail."""
+    ifd0 = struct.pack('<H', 0) + struct.pack('<L', 14)
+    ifd1 = (struct.pack('<H', 2) + struct.pack('<HHLL', 0x0201, 4, 1, 44)
+            + struct.pack('<HHLL', 0x0202, 4, 1, len(thumbnail)) + struct.pack('<L', 0))
+    app1 = b'Exif\x00\x00' + b'II*\x00' + struct.pack('<L', 8) + ifd0 + ifd1 + thumbnail
+    path.write_bytes(b'\xff\xd8\xff\xe1' + struct.pack('>H', len(app1) + 2) + app1 + b'\xff\xd9' + b'\x00' * 300000)
+    return str(path)
+
+
+@pytest.fixture
+def cache(tmp_path):
+    config = Mock()
+    config.thumbnail_settings = {'cache_dir': str(tmp_path / "thumbs"), 'max_cache_mb': 1}
+    with patch.object(thumbnails, 'PIL_SUPPORT', False):
+        yield ThumbnailCache(config)
+
+
+def test_thumbnails_are_cached_until_the_file_changes(cache, tmp_path):
+    """The second request is served from disk; editing the photo renders it again."""
+    photo = _jpeg_with_thumbnail(tmp_path / "IMG_0001.jpg", b'\xff\xd8' + b'A' * 40 + b'\xff\xd9')
+    first = cache.get_thumbnail(photo)
+    assert first['source'] == 'exif' and not first['cached'] and first['mime_type'] == 'image/jpeg'
+    assert base64.b64decode(first['data']) == b'\xff\xd8' + b'A' * 40 + b'\xff\xd9'
+    second = cache.get_thumbnail(photo, as_base64=False)
+    assert second['cached'] and second['data'] == b'\xff\xd8' + b'A' * 40 + b'\xff\xd9'
+    assert second['etag'] == first['etag'] and cache.stats()['thumbnails'] == 1
+
+    _jpeg_with_thumbnail(tmp_path / "IMG_0001.jpg", b'\xff\xd8' + b'B' * 40 + b'\xff\xd9')
+    os.utime(photo, ns=(1, 1))
+    edited = cache.get_thumbnail(photo, as_base64=False)
+    assert not edited['cached'] and edited['data'] == b'\xff\xd8' + b'B' * 40 + b'\xff\xd9'
+    assert edited['etag'] != first['etag']
+    assert cache.get_thumbnail(photo, size=64)['etag'] not in (first['etag'], edited['etag'])
+    assert cache.clear()['removed'] == 3 and cache.stats()['thumbnails'] == 0
+
+
+def test_small_images_unknown_files_and_pruning(cache, tmp_path):
+    """Small images are used as they are; other files have no thumbnail; the cache is kept under its limit."""
+    (tmp_path / "icon.png").write_bytes(b'\x89PNG\r\n\x1a\n' + b'\x00' * 20)
+    assert cache.get_thumbnail(str(tmp_path / "icon.png"))['source'] == 'original'
+    (tmp_path / "notes.txt").write_text("hello")
+    assert cache.get_thumbnail(str(tmp_path / "notes.txt")) is None
+    with pytest.raises(FileNotFoundError):
+        cache.get_thumbnail(str(tmp_path / "missing.jpg"))
+    batch = cache.get_thumbnails([str(tmp_path / "icon.png"), str(tmp_path / "missing.jpg")])
+    assert batch[0]['cached'] and batch[1]['data'] is None and batch[1]['error']
+
+    for index in range(3):
+        (tmp_path / f"big{index}.png").write_bytes(b'\x89PNG\r\n\x1a\n' + bytes([index]) * 200 * 1024)
+        cache.get_thumbnail(str(tmp_path / f"big{index}.png"))
+    cache.max_bytes = 450 * 1024
+    assert cache.prune() >= 1 and cache.stats()['bytes'] <= cache.max_bytes
+    assert cache.get_thumbnail(str(tmp_path / "big2.png"))['cached']   # The newest is kept
@@ -0,0 +1,79 @@
+"""
+Unit tests for the platform trash.
+"""
+
+import os
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.trash import SystemTrash
+from src.core.db_manager import DatabaseManager
+
+
+@pytest.fixture
+def setup(tmp_path):
+    config = Mock()
+    config.trash_dir = str(tmp_path / "app-trash")
+    config.path_blacklist = []
+    config.trash_settings = {'backend': 'system'}
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    folder = tmp_path / "files"
+    folder.mkdir()
+    with patch.dict(os.environ, {'XDG_DATA_HOME': str(tmp_path / "xdg")}), \
+            patch('src.core.trash.platform_backend', return_value='freedesktop'):
+        yield config, db, folder, tmp_path / "xdg" / "Trash"
+    db.cleanup()
+
+
+def test_freedesktop_trash_lists_and_restores(setup):
+    """Files land in Trash/files with a .trashinfo; same names get " (2)"; restore puts them back."""
+    config, db, folder, home_trash = setup
+    trash = SystemTrash(config, db)
+    (folder / "notes.txt").write_text("first")
+    sub = folder / "sub"
+    sub.mkdir()
+    (sub / "notes.txt").write_text("second")
+
+    result = trash.move_to_trash([str(folder / "notes.txt"), str(sub / "notes.txt"), str(folder / "gone")])
+    assert len(result['trashed']) == 2 and result['failed'][0]['reason'] == 'Not found'
+    assert not (folder / "notes.txt").exists() and result['backend'] == 'freedesktop'
+    assert sorted(os.listdir(home_trash / "files")) == ['notes (2).txt', 'notes.txt']
+    info = (home_trash / "info" / "notes.txt.trashinfo").read_text()
+    assert info.startswith("[Trash Info]\nPath=") and "DeletionDate=" in info
+
+    items = trash.list_trash_items()
+    assert [item['original_path'] for item in items] == [str(sub / "notes.txt"), str(folder / "notes.txt")]
+    assert all(item['restorable'] for item in items)
+
+    (folder / "notes.txt").write_text("a new file with the old name")
+    restored = trash.restore_from_trash([items[1]['id'], items[1]['id'], 999])
+    assert restored['restored'] == [{'id': items[1]['id'], 'path': str(folder / "notes (2).txt")}]
+    assert [entry['reason'] for entry in restored['failed']] == ['Already restored', 'No such trash item']
+    assert (folder / "notes (2).txt").read_text() == "first"
+    assert not (home_trash / "info" / "notes.txt.trashinfo").exists()
+    assert [item['id'] for item in trash.list_trash_items()] == [items[0]['id']]
+
+
+def test_app_backend_and_unknown_locations(setup):
+    """The app backend uses trash_dir; an item whose location the platform kept to itself is not restorable."""
+    config, db, folder, _ = setup
+    config.trash_settings = {'backend': 'app'}
+    trash = SystemTrash(config, db)
+    (folder / "photos").mkdir()
+    (folder / "photos" / "a.jpg").write_bytes(b'jpg')
+
+    result = trash.move_to_trash([str(folder / "photos")])
+    assert result['backend'] == 'app' and not (folder / "photos").exists()
+    assert Path(result['trashed'][0]['trashed_path']) == Path(config.trash_dir) / "removed" / "photos"
+    assert trash.list_trash_items()[0]['is_dir'] and trash.list_trash_items()[0]['size'] == 3
+    assert trash.restore_from_trash([result['trashed'][0]['id']])['success']
+    assert (folder / "photos" / "a.jpg").read_bytes() == b'jpg'
+
+    item_id = db.add_trash_item(str(folder / "x.txt"), None, 'windows')
+    assert 'Recycle Bin' in trash.restore_from_trash([item_id])['failed'][0]['reason']
+    assert trash.list_trash_items()[0]['in_trash'] is None
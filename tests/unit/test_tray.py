@@ -0,0 +1,60 @@
+"""
+Unit tests for the tray icon's quick actions and tooltip.
+"""
+
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.tray import MAX_TOOLTIP, TrayIcon
+
+
+def _tray(tmp_path, watching=True, **settings):
+    state = {'watching': watching}
+    actions = {
+        'organize': Mock(return_value={'id': 'job-1'}),
+        'pause': Mock(side_effect=lambda: state.update(watching=False)),
+        'resume': Mock(side_effect=lambda: state.update(watching=True)),
+        'is_watching': lambda: state['watching']
+    }
+    config = Mock(tray_settings={'downloads_folder': str(tmp_path / "Downloads"), **settings})
+    return TrayIcon(config, actions, 'http://127.0.0.1:5123'), actions
+
+
+def test_menu_actions_organize_downloads_toggle_watching_and_open_the_dashboard(tmp_path):
+    """Each menu item calls its action; the watching item names what it will do."""
+    tray, actions = _tray(tmp_path)
+    assert [label for label, _ in tray.menu_items()] == ['Organize Downloads now', 'Pause watching',
+                                                         'Open dashboard']
+
+    assert not tray.organize_downloads()['success']  # No Downloads folder yet
+    (tmp_path / "Downloads").mkdir()
+    assert tray.organize_downloads()['job'] == {'id': 'job-1'}
+    actions['organize'].assert_called_once_with(str(tmp_path / "Downloads"))
+
+    assert tray.toggle_watching() == {'success': True, 'watching': False}
+    assert tray.status['tooltip'] == 'AI File Organiser - Watching paused'
+    assert tray.menu_items()[1][0] == 'Resume watching'
+    assert tray.toggle_watching()['watching'] and actions['resume'].called
+
+    with patch('src.core.tray.webbrowser.open') as browser:
+        assert tray.open_dashboard()['url'] == 'http://127.0.0.1:5123'
+    browser.assert_called_once_with('http://127.0.0.1:5123')
+    assert [label for label, _ in TrayIcon(Mock(tray_settings={}), {}).menu_items()] == ['Open dashboard']
+
+
+def test_status_sets_the_tooltip_even_without_an_icon(tmp_path):
+    """Progress shows as a percentage, long text is cut to fit, and no icon is shown when disabled."""
+    tray, _ = _tray(tmp_path)
+    assert tray.set_status('Organizing Downloads', 0.4) == {
+        'success': True, 'tooltip': 'AI File Organiser - Organizing Downloads (40%)', 'shown': False}
+    assert tray.set_status()['tooltip'] == 'AI File Organiser'
+    assert len(tray.set_status('x' * 500)['tooltip']) == MAX_TOOLTIP
+    assert tray.set_status('Done', 7)['tooltip'].endswith('(100%)')
+
+    disabled, _ = _tray(tmp_path, enabled=False)
+    assert disabled.start() is False
+    with patch('src.core.tray.PYSTRAY_SUPPORT', False):
+        assert tray.start() is False
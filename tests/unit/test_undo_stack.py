@@ -0,0 +1,92 @@
+"""
+Unit tests for the multi-level undo/redo stack.
+"""
+
+from pathlib import Path
+
+
+def _organize(manager, path: Path):
+    return manager.execute(str(path), {
+        'suggested_path': 'Documents/',
+        'category': 'Documents',
+        'confidence': 'high',
+        'method': 'rule-based'
+    }, user_approved=True)
+
+
+def test_step_back_and_forth(action_manager, tmp_path):
+    """Moves and deletes are undone newest first and can be redone."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    (downloads / "report.pdf").write_text("report")
+    (downloads / "old.log").write_text("log")
+
+    assert _organize(action_manager, downloads / "report.pdf")['success']
+    deleted = action_manager.delete_file(str(downloads / "old.log"))
+    assert deleted['success'] and not (downloads / "old.log").exists()
+
+    stack = action_manager.journal.undo_stack()
+    assert [op['kind'] for op in stack] == ['delete', 'move']
+
+    assert action_manager.undo_last_action()['success']
+    assert (downloads / "old.log").read_text() == "log"
+    assert action_manager.undo_last_action()['success']
+    assert (downloads / "report.pdf").exists()
+    assert action_manager.undo_last_action()['message'] == 'Nothing to undo'
+
+    assert action_manager.redo_last_action()['success']
+    assert (tmp_path / "organized" / "Documents" / "report.pdf").exists()
+    assert [op['kind'] for op in action_manager.journal.redo_stack()] == ['delete']
+
+
+def test_new_action_clears_redo(action_manager, tmp_path):
+    """Anything done after an undo drops the redo stack; grouped runs are one entry."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    for name in ("a.txt", "b.txt", "c.txt"):
+        (downloads / name).write_text(name)
+
+    with action_manager.journal_scope(kind='apply', description='Organize downloads'):
+        _organize(action_manager, downloads / "a.txt")
+        _organize(action_manager, downloads / "b.txt")
+
+    stack = action_manager.journal.undo_stack()
+    assert len(stack) == 1 and stack[0]['step_count'] == 2
+
+    action_manager.undo_last_action()
+    assert action_manager.journal.redo_stack()
+
+    _organize(action_manager, downloads / "c.txt")
+
+    assert action_manager.journal.redo_stack() == []
+    assert action_manager.redo_last_action()['success'] is False
+    assert (downloads / "a.txt").exists() and (downloads / "b.txt").exists()
+
+
+def test_undo_single_item(action_manager, tmp_path):
+    """One file of a run can be put back; an occupied original path is a conflict."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    for name in ("a.txt", "b.txt"):
+        (downloads / name).write_text(name)
+
+    with action_manager.journal_scope(kind='apply', description='Organize downloads'):
+        _organize(action_manager, downloads / "a.txt")
+        _organize(action_manager, downloads / "b.txt")
+
+    journal = action_manager.journal
+    operation_id = journal.undo_stack()[0]['id']
+    first, second = journal.get_operation(operation_id)['steps']
+
+    (downloads / "a.txt").write_text("a new file")
+    conflict = journal.undo_item(operation_id, first['id'])
+    assert conflict['conflict'] and conflict['occupied_by'] == str(downloads / "a.txt")
+
+    result = journal.undo_item(operation_id, first['id'], on_conflict='keep_both')
+    assert result['success'], result['message']
+    assert (downloads / "a_1.txt").read_text() == "a.txt"
+    assert journal.get_operation(operation_id)['status'] == 'applied'
+
+    journal.undo_item(operation_id, second['id'])
+    assert (downloads / "b.txt").exists()
+    assert journal.get_operation(operation_id)['status'] == 'undone'
@@ -0,0 +1,109 @@
+"""
+Unit tests for update checks, installation and rollback.
+"""
+
+import base64
+import hashlib
+import json
+import sys
+import zipfile
+from pathlib import Path
+from unittest.mock import Mock
+
+from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.updater import UpdateManager, parse_version, signed_payload
+
+
+def _package(path, files, removed=None):
+    with zipfile.ZipFile(path, 'w') as archive:
+        for name, content in files.items():
+            archive.writestr(name, content)
+        if removed:
+            archive.writestr('aifo-removed.json', json.dumps(removed))
+    return {'url': path.as_uri(), 'sha256': hashlib.sha256(path.read_bytes()).hexdigest(),
+            'size': path.stat().st_size}
+
+
+def _server(tmp_path, key, version='1.1.0', rollout=100, deltas=None, sign=True):
+    """A signed manifest for the stable channel, served from a file: URL."""
+    full = _package(tmp_path / 'full.zip', {'src/app.py': 'v = 2\n', 'src/new.py': 'x = 1\n',
+                                            'config.json': '{"overwritten": true}'})
+    manifest = {'version': version, 'channel': 'stable', 'notes': 'Faster scans', 'rollout': rollout,
+                'package': full, 'deltas': deltas or []}
+    if sign:
+        manifest['signature'] = base64.b64encode(key.sign(signed_payload(manifest))).decode()
+    (tmp_path / 'stable.json').write_text(json.dumps(manifest))
+    return (tmp_path / '{channel}.json').as_uri().replace('%7B', '{').replace('%7D', '}')
+
+
+def _manager(tmp_path, url, key, version='1.0.0'):
+    app = tmp_path / 'app'
+    (app / 'src').mkdir(parents=True, exist_ok=True)
+    (app / 'src' / 'app.py').write_text('v = 1\n')
+    (app / 'src' / 'old.py').write_text('gone = True\n')
+    (app / 'config.json').write_text('{"mine": true}')
+    public = key.public_key().public_bytes(Encoding.Raw, PublicFormat.Raw).hex()
+    config = Mock()
+    config.update_settings = {'manifest_url': url, 'public_key': public}
+    return UpdateManager(config, app_root=app, state_dir=tmp_path / 'state', current_version=version)
+
+
+def test_manifests_must_be_signed_and_rollouts_are_staged(tmp_path):
+    """Only correctly signed manifests are used; newer versions are offered by rollout bucket."""
+    key = Ed25519PrivateKey.generate()
+    assert parse_version('1.3.0-beta.2') < parse_version('1.3.0') < parse_version('1.3.1')
+    assert parse_version('1.10') > parse_version('1.9.9')
+
+    manager = _manager(tmp_path, _server(tmp_path, key), key)
+    checked = manager.check()
+    assert checked['success'] and checked['available'] and checked['version'] == '1.1.0'
+    assert not checked['delta'] and checked['notes'] == 'Faster scans'
+    assert not _manager(tmp_path, _server(tmp_path, key), key, version='1.1.0').check()['available']
+
+    bucket = manager.rollout_bucket('1.1.0')
+    assert bucket == manager.rollout_bucket('1.1.0')
+    _server(tmp_path, key, rollout=bucket)
+    assert not manager.check()['available']
+    _server(tmp_path, key, rollout=bucket + 1)
+    assert manager.check()['available']
+
+    _server(tmp_path, key, sign=False)
+    assert 'not signed' in manager.check()['message']
+    other = Ed25519PrivateKey.generate()
+    _server(tmp_path, other)
+    assert not manager.check()['success']
+
+
+def test_updates_install_prefer_deltas_and_roll_back_on_failed_self_test(tmp_path):
+    """A delta replaces only its files and keeps config.json; a failed startup test restores them."""
+    key = Ed25519PrivateKey.generate()
+    delta = _package(tmp_path / 'delta.zip', {'src/app.py': 'v = 2\n', 'src/new.py': 'x = 1\n',
+                                              'data/database/x.db': 'no'}, removed=['src/old.py'])
+    manager = _manager(tmp_path, _server(tmp_path, key, deltas=[{'from': '1.0.0', **delta}]), key)
+    app = manager.app_root
+
+    downloaded = manager.download()
+    assert downloaded['success'] and downloaded['delta']
+    installed = manager.apply()
+    assert installed['success'] and installed['changed'] == 3
+    assert (app / 'src' / 'app.py').read_text() == 'v = 2\n' and (app / 'src' / 'new.py').exists()
+    assert not (app / 'src' / 'old.py').exists() and not (app / 'data').exists()
+    assert (app / 'config.json').read_text() == '{"mine": true}'
+    assert manager.status()['pending'] == '1.1.0'
+
+    outcome = manager.verify_startup(lambda: {'success': False, 'message': 'undo failed'})
+    assert not outcome['success'] and outcome['rolled_back']
+    assert (app / 'src' / 'app.py').read_text() == 'v = 1\n' and (app / 'src' / 'old.py').exists()
+    assert not (app / 'src' / 'new.py').exists()
+    assert manager.status()['last_result']['result'] == 'rolled back'
+    assert manager.verify_startup(lambda: {'success': True}) is None
+
+    (tmp_path / 'delta.zip').write_bytes(b'tampered')
+    assert manager.download()['delta'] is False
+    assert manager.apply()['success']
+    assert manager.verify_startup(lambda: {'success': True})['success']
+    assert manager.status()['pending'] is None and manager.status()['previous'] == '1.0.0'
@@ -0,0 +1,83 @@
+"""
+Unit tests for the AI usage ledger and monthly budget.
+"""
+
+import sys
+from datetime import datetime
+from pathlib import Path
+from unittest.mock import Mock, MagicMock
+
+import pytest  # type: ignore[import-untyped]
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.classifier import FileClassifier
+from src.core.db_manager import DatabaseManager
+from src.core.usage import UsageLedger
+
+
+@pytest.fixture
+def db(tmp_path):
+    db = DatabaseManager(str(tmp_path / "db" / "test.db"))
+    yield db
+    db.cleanup()
+
+
+def _ledger(db, now, **settings):
+    config = Mock()
+    config.usage_settings = settings
+    return UsageLedger(config, db, clock=lambda: now[0])
+
+
+def test_usage_is_totalled_per_model_run_and_period(db):
+    """Costs come from the provider or the price list; summaries cover the period asked for."""
+    now = [datetime(2025, 2, 20, 9, 0)]
+    ledger = _ledger(db, now, prices={'gpt-4o-mini': 0.5, 'llama3': 0.1})
+    assert ledger.record('llama3:8b', 1000) == 0.1          # Priced by the model family
+    now[0] = datetime(2025, 3, 3, 9, 0)
+    run = ledger.start_run("Plan for /home/me/Downloads")
+    assert ledger.record('gpt-4o-mini', 2000) == 1.0
+    assert ledger.record('qwen2.5:7b', 500) == 0.0            # Unpriced local model
+    assert ledger.record('claude', 10, cost_usd=0.25, provider='cloud') == 0.25
+
+    month = ledger.get_usage_summary()
+    assert (month['calls'], month['tokens'], month['cost_usd']) == (3, 2510, 1.25)
+    assert month['by_model'][0]['model'] == 'gpt-4o-mini' and month['by_model'][0]['cost_usd'] == 1.0
+    assert [r['run_id'] for r in month['by_run']] == [run] and month['by_run'][0]['calls'] == 3
+    everything = ledger.get_usage_summary('all')
+    assert everything['calls'] == 4 and len(everything['by_run']) == 2
+    assert ledger.get_usage_summary('today')['since'] == '2025-03-03T00:00:00'
+    with pytest.raises(ValueError):
+        ledger.get_usage_summary('decade')
+
+
+def test_a_blocking_budget_stops_ai_classification_until_next_month(db, tmp_path):
+    """Past a blocking budget the classifier keeps its rule result; a warning budget only reports."""
+    now = [datetime(2025, 3, 10, 12, 0)]
+    ledger = _ledger(db, now, monthly_budget_usd=1, on_budget_exceeded='block', prices={'llama3': 1.0})
+    ollama = MagicMock()
+    ollama.model = 'llama3'
+    ollama.classify_file.return_value = {'success': True, 'category': 'Notes', 'suggested_path': 'Notes/',
+                                         'model': 'llama3', 'tokens_used': 600}
+    config = MagicMock()
+    config.destination_rules = {}
+    config.enable_ai = True
+    config.locale = 'en'
+    classifier = FileClassifier(config, ollama)
+    classifier.usage = ledger
+    classifier._classify_by_agent = Mock(return_value=None)
+    notes = tmp_path / "notes.qqq"
+    notes.write_text("meeting notes")
+
+    first = classifier._classify_by_ai({'filename': notes.name, 'extension': 'qqq', 'size': 13})
+    assert first['cost_usd'] == 0.6 and ledger.allows_ai()
+    classifier._classify_by_ai({'filename': notes.name, 'extension': 'qqq', 'size': 13})
+    budget = ledger.budget_status()
+    assert budget['exceeded'] and not budget['ai_allowed'] and budget['remaining_usd'] == 0.0
+    assert classifier._classify_by_ai({'filename': notes.name, 'extension': 'qqq', 'size': 13}) == {
+        'success': False, 'error': 'Monthly AI budget reached'}
+    assert ollama.classify_file.call_count == 2
+
+    now[0] = datetime(2025, 4, 1, 0, 5)
+    assert ledger.allows_ai() and ledger.budget_status()['spent_usd'] == 0
+    assert _ledger(db, [datetime(2025, 3, 31)], monthly_budget_usd=1).allows_ai()   # 'warn' never blocks
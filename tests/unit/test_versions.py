@@ -0,0 +1,73 @@
+"""
+Unit tests for keeping versions of replaced files.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.versions import VersionStore
+
+
+@pytest.fixture
+def config(config, tmp_path):
+    """Replaces on conflict, keeping two versions in a temporary store."""
+    config.conflict_resolution = 'replace'
+    config.version_settings = {'keep_versions': 2, 'store_dir': str(tmp_path / "versions")}
+    return config
+
+
+def _move_in(manager, tmp_path, content: str):
+    source = tmp_path / "downloads" / "report.txt"
+    source.parent.mkdir(exist_ok=True)
+    source.write_text(content)
+    return manager.execute(str(source), {
+        'suggested_path': 'Documents/',
+        'category': 'Documents',
+        'confidence': 'high',
+        'method': 'rule-based'
+    }, user_approved=True)
+
+
+def test_replace_keeps_version_and_undo_restores_it(action_manager, tmp_path):
+    """The replaced file is kept, restorable, and put back by undo."""
+    target = tmp_path / "organized" / "Documents" / "report.txt"
+    target.parent.mkdir(parents=True)
+    target.write_text("old report")
+
+    result = _move_in(action_manager, tmp_path, "new report")
+
+    assert result['success'], result['message']
+    assert target.read_text() == "new report"
+    versions = action_manager.versions.list_versions(str(target))
+    assert [v['id'] for v in versions] == [result['replaced_version']]
+
+    action_manager.undo_last_action()
+
+    assert target.read_text() == "old report"
+    assert (tmp_path / "downloads" / "report.txt").read_text() == "new report"
+
+    action_manager.redo_last_action()
+    assert target.read_text() == "new report"
+
+    restored = action_manager.versions.restore_version(result['replaced_version'])
+    assert restored['success']
+    assert target.read_text() == "old report"
+    assert len(action_manager.versions.list_versions(str(target))) == 2
+
+
+def test_versions_are_pruned_and_deduplicated(config, db, tmp_path):
+    """Only the newest N versions stay; identical content is stored once."""
+    store = VersionStore(config, db)
+    path = tmp_path / "notes.txt"
+
+    for content in ("one", "two", "two", "three"):
+        path.write_text(content)
+        store.save_version(str(path))
+
+    kept = store.list_versions(str(path))
+    assert len(kept) == 2
+    objects = [p for p in (tmp_path / "versions" / "objects").rglob('*') if p.is_file()]
+    assert sorted(p.read_text() for p in objects) == ["three", "two"]
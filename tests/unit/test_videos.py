@@ -0,0 +1,69 @@
+"""
+Unit tests for movie and TV library structuring.
+"""
+
+import io
+import json
+import sys
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.videos import VideoLibraryStrategy, parse_video_name
+
+
+def test_names_are_parsed_offline():
+    """Release tags are dropped; years, episode codes and season folders are understood."""
+    assert parse_video_name(Path('Blade.Runner.1982.Final.Cut.1080p.BluRay.x264.mkv')) == {
+        'kind': 'movie', 'title': 'Blade Runner', 'year': 1982}
+    assert parse_video_name(Path('2001 A Space Odyssey (1968).mp4')) == {
+        'kind': 'movie', 'title': '2001 A Space Odyssey', 'year': 1968}
+    assert parse_video_name(Path('The.Expanse.S02E05.Home.720p.WEB-DL.mkv')) == {
+        'kind': 'episode', 'show': 'The Expanse', 'season': 2, 'episode': 5, 'last_episode': 5,
+        'episode_title': 'Home', 'year': None}
+    single = parse_video_name(Path('Doctor Who (2005) - 1x01.avi'))
+    assert (single['show'], single['year'], single['season'], single['episode']) == ('Doctor Who', 2005, 1, 1)
+    double = parse_video_name(Path('Doctor.Who.2005.S01E01E02.mkv'))
+    assert (double['show'], double['year'], double['last_episode']) == ('Doctor Who', 2005, 2)
+    from_folders = parse_video_name(Path('Fargo/Season 3/04 - The Narrow Escape Problem.mkv'))
+    assert (from_folders['show'], from_folders['season'], from_folders['episode']) == ('Fargo', 3, 4)
+
+
+def test_videos_are_placed_for_plex_with_optional_lookup(tmp_path):
+    """Offline names are used as parsed; with the lookup on, canonical titles replace them."""
+    config = Mock()
+    config.video_settings = {}
+    strategy = VideoLibraryStrategy(config)
+
+    movie = strategy.classify('/dl/blade.runner.1982.1080p.mkv')
+    assert (movie['suggested_path'], movie['rename']) == ('Movies/Blade Runner (1982)/', 'Blade Runner (1982).mkv')
+    subtitle = strategy.classify('/dl/blade.runner.1982.1080p.en.srt')
+    assert subtitle['rename'] == 'Blade Runner (1982).en.srt'
+    episode = strategy.classify('/dl/The.Expanse.S02E05.720p.mkv', policy={'tv_destination': 'Shows'})
+    assert (episode['suggested_path'], episode['rename']) == (
+        'Shows/The Expanse/Season 02/', 'The Expanse - S02E05.mkv')
+    assert episode['confidence'] == 'medium'
+    assert strategy.classify('/dl/blade.runner.sample.mkv') is None
+    assert strategy.classify('/dl/notes.txt') is None
+
+    answers = {
+        'search/tv': {'results': [{'id': 63639, 'name': 'The Expanse', 'first_air_date': '2015-12-14'}]},
+        'tv/63639/season/2/episode/5': {'name': 'Home'},
+    }
+
+    def fake_urlopen(request, timeout):
+        route = request.full_url.split('/3/', 1)[1].split('?')[0]
+        return io.BytesIO(json.dumps(answers[route]).encode())
+
+    config.video_settings = {'online': True, 'api_key': 'key', 'cache_file': str(tmp_path / 'cache.json')}
+    online = VideoLibraryStrategy(config)
+    with patch('src.core.videos.urllib.request.urlopen', side_effect=fake_urlopen) as urlopen:
+        episode = online.classify('/dl/The.Expanse.S02E05.720p.mkv')
+        assert urlopen.call_count == 2
+        online.classify('/dl/The.Expanse.S02E05.1080p.mkv')
+        assert urlopen.call_count == 2  # Cached
+    assert (episode['suggested_path'], episode['rename']) == (
+        'TV Shows/The Expanse (2015)/Season 02/', 'The Expanse (2015) - S02E05 - Home.mkv')
+    assert episode['confidence'] == 'high'
+    assert 'api_key=key' in urlopen.call_args_list[0].args[0].full_url
@@ -0,0 +1,113 @@
+"""
+Unit tests for multi-root workspaces.
+"""
+
+import pytest  # type: ignore[import-untyped]
+from pathlib import Path
+from unittest.mock import Mock, patch
+
+import sys
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.workspaces import WorkspaceManager
+from src.core.duplicates import DuplicateFinder
+
+
+@pytest.fixture
+def config(config):
+    """Keeps workspaces in memory and hashes with SHA-1."""
+    config.workspaces = {}
+    config.update.side_effect = lambda key, value: setattr(config, key, value)
+    config.hash_algorithm = 'sha1'
+    return config
+
+
+@pytest.fixture
+def workspace(config, db, action_manager, tmp_path):
+    """Two roots sharing one duplicate, and a workspace manager over them."""
+    desktop, drive = tmp_path / "desktop", tmp_path / "drive"
+    desktop.mkdir()
+    drive.mkdir()
+    (desktop / "notes.txt").write_text("notes " * 400)
+    (drive / "notes-backup.txt").write_text("notes " * 400)
+    (drive / "photo.jpg").write_bytes(b'\xff\xd8' + b'0' * 4096)
+    return WorkspaceManager(config, db, action_manager=action_manager), desktop, drive, tmp_path
+
+
+def test_define_workspace(workspace):
+    """Workspaces persist through the config; overlapping roots are refused."""
+    manager, desktop, drive, tmp_path = workspace
+
+    result = manager.save_workspace('home', [str(desktop), str(drive), str(tmp_path / "usb")])
+    assert result['success'] and 'not available' in result['message']
+    assert [r['available'] for r in manager.get_workspace('home')['roots']] == [True, True, False]
+    manager.config.save.assert_called()
+
+    assert manager.save_workspace('bad', [str(desktop), str(desktop / "sub")])['success'] is False
+    assert manager.delete_workspace('home') and manager.list_workspaces() == []
+
+
+def test_scan_and_duplicates_span_roots(workspace):
+    """Scans report every root; a copy on another root is one duplicate group."""
+    manager, desktop, drive, _ = workspace
+    manager.save_workspace('home', [str(desktop), str(drive)])
+    events = []
+
+    scan = manager.scan('home', events.append)
+
+    assert [r['files'] for r in scan['roots']] == [1, 2]
+    assert [e['root_index'] for e in events if e['done']] == [0, 1]
+
+    with patch('src.core.duplicates.SafetyGuardian'):
+        manager.duplicate_finder = DuplicateFinder(manager.config, manager.db_manager)
+    groups = manager.find_duplicates('home')
+
+    assert len(groups) == 1 and groups[0]['cross_root'] is True
+
+
+def test_apply_plan_is_one_operation(workspace):
+    """Organizing a workspace is a single entry on the undo stack."""
+    manager, desktop, drive, tmp_path = workspace
+    manager.save_workspace('home', [str(desktop), str(drive)])
+    manager.classifier = Mock()
+    manager.classifier.classify.side_effect = lambda path: {
+        'category': 'Documents', 'suggested_path': 'Sorted/', 'confidence': 'high', 'method': 'rule-based'
+    } if path.endswith('.txt') else {'category': 'Other', 'suggested_path': None, 'confidence': 'low', 'method': 'rule-based'}
+
+    plan = manager.plan_organize('home')
+    assert plan['per_root'][str(drive.resolve())] == {'planned': 1, 'unplanned': 1}
+
+    result = manager.apply_plan(plan)
+
+    assert result['success'], result['message']
+    stack = manager.action_manager.journal.undo_stack()
+    assert len(stack) == 1 and stack[0]['id'] == result['operation_id'] and stack[0]['step_count'] == 2
+    assert sorted(p.name for p in (tmp_path / "organized" / "Sorted").iterdir()) == ['notes-backup.txt', 'notes.txt']
+
+
+def test_destination_mapping(workspace):
+    """Mapped categories land in their own destination; offline destinations hold files back."""
+    manager, desktop, drive, tmp_path = workspace
+    nas = tmp_path / "nas"
+    nas.mkdir()
+    manager.save_workspace('home', [str(desktop), str(drive)])
+    result = manager.set_destinations('home', {'Documents': str(nas), 'Images': str(tmp_path / "offline"), '*': '@root'})
+    assert result['success'], result['message']
+    assert manager.set_destinations('home', {'Video': 'relative/dir'})['success'] is False
+    manager.classifier = Mock()
+    manager.classifier.classify.side_effect = lambda path: {
+        'category': 'Documents' if path.endswith('.txt') else 'Images',
+        'suggested_path': 'Sorted/', 'confidence': 'high', 'method': 'rule-based'
+    }
+
+    plan = manager.plan_organize('home')
+    assert plan['per_destination'] == {str(nas): 2}
+    assert [Path(h['file']).name for h in plan['held']] == ['photo.jpg']
+
+    manager.set_destinations('home', {'Images': None})
+    plan = manager.plan_organize('home')
+    assert plan['per_destination'] == {str(nas): 2, str(drive.resolve()): 1}
+
+    assert manager.apply_plan(plan)['success']
+    assert sorted(p.name for p in (nas / "Sorted").iterdir()) == ['notes-backup.txt', 'notes.txt']
+    assert (drive / "Sorted" / "photo.jpg").exists()
@@ -0,0 +1,66 @@
+"""
+Unit tests for the write access pre-flight check.
+"""
+
+import errno
+import sys
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+sys.path.insert(0, str(Path(__file__).parent.parent.parent))
+
+from src.core.plans import OrganizePlan, OrganizePlanner
+from src.core.write_access import _probe_folder, check_write_access
+
+
+def test_each_path_is_reported_with_what_would_stop_it(tmp_path):
+    """Movable files pass (also into folders still to be made); missing, locked and blocked ones say why."""
+    inbox, nas = tmp_path / "inbox", tmp_path / "nas"
+    inbox.mkdir()
+    nas.mkdir()
+    for name in ("a.txt", "b.txt", "c.txt"):
+        (inbox / name).write_text(name)
+    paths = [str(inbox / name) for name in ("a.txt", "b.txt", "c.txt", "gone.txt")]
+    destinations = {paths[0]: str(tmp_path / "new" / "deeper" / "a.txt"), paths[2]: str(nas / "c.txt")}
+
+    real_probe = _probe_folder
+    with patch('src.core.write_access._locked', side_effect=lambda path: path.endswith('b.txt')), \
+            patch('src.core.write_access._probe_folder', side_effect=lambda folder: (
+                'read_only_mount', f"{folder} is on a read-only drive") if folder == str(nas)
+                else real_probe(folder)) as probe:
+        result = check_write_access(paths, destinations)
+
+    assert (result['success'], result['checked'], result['problems']) == (False, 4, 3)
+    assert [report['problem'] for report in result['paths']] == [
+        None, 'locked', 'destination_read_only_mount', 'missing']
+    assert result['paths'][0]['destination'] == destinations[paths[0]]
+    assert "open in another program" in result['paths'][1]['message']
+    assert result['paths'][2]['message'] == f"The destination folder {nas} is on a read-only drive"
+    assert sorted(call.args[0] for call in probe.call_args_list) == sorted([str(inbox), str(nas), str(tmp_path)])
+    assert sorted(p.name for p in tmp_path.iterdir()) == ['inbox', 'nas']  # No probe files left behind
+    assert check_write_access([paths[0]])['message'] == "All 1 path(s) can be moved"
+
+    with patch('tempfile.mkstemp', side_effect=PermissionError(errno.EACCES, "Permission denied")):
+        assert _probe_folder(str(inbox))[0] == 'permission_denied'
+
+
+def test_plans_flag_files_that_cannot_be_moved(config, action_manager, tmp_path):
+    """A plan item whose folder cannot be written to carries the problem and is counted."""
+    downloads = tmp_path / "downloads"
+    downloads.mkdir()
+    (downloads / "report.pdf").write_text("report")
+
+    classifier = MagicMock()
+    classifier.classify.return_value = {
+        'category': 'Documents', 'suggested_path': 'Documents/', 'confidence': 'high', 'method': 'rule-based'}
+    planner = OrganizePlanner(config, classifier, action_manager)
+
+    assert planner.build_plan(str(downloads))['access_problems'] == 0
+    with patch('src.core.write_access._probe_folder',
+               return_value=('permission_denied', f"{downloads} cannot be written to")):
+        plan = planner.build_plan(str(downloads))
+
+    assert plan['access_problems'] == 1
+    assert plan['items'][0]['access'] == {'problem': 'permission_denied',
+                                          'message': f"Its folder {downloads} cannot be written to"}
+    assert OrganizePlan.from_plan(plan).moves[0].access_problem == 'permission_denied'